@@ -0,0 +1,132 @@
+//! Per-turn tool budget.
+//!
+//! Every tool declares a [`Latency`] and a [`Cost`]. The system prompt lists
+//! the slow and paid ones with the per-turn limits so the model reaches for
+//! fast, free tools first, and [`TurnBudget::charge`] rejects a round of
+//! tool calls that would go over before any of it runs.
+
+use crate::config::ToolBudgetConfig;
+use crate::tools::{Cost, Latency, Tool};
+use std::fmt::Write;
+
+/// What one turn has spent so far.
+#[derive(Debug)]
+pub struct TurnBudget<'a> {
+    limits: &'a ToolBudgetConfig,
+    units: u32,
+    slow_calls: u32,
+}
+
+impl<'a> TurnBudget<'a> {
+    pub fn new(limits: &'a ToolBudgetConfig) -> Self {
+        Self {
+            limits,
+            units: 0,
+            slow_calls: 0,
+        }
+    }
+
+    /// Spend the budget for a round of calls, all or nothing. The error
+    /// tells the model what went over.
+    pub fn charge(&mut self, plan: &[(Latency, Cost)]) -> Result<(), String> {
+        if !self.limits.enabled {
+            return Ok(());
+        }
+        let units = self.units + plan.iter().map(|(_, cost)| cost.units()).sum::<u32>();
+        let slow_calls = self.slow_calls
+            + u32::try_from(
+                plan.iter()
+                    .filter(|(latency, _)| *latency == Latency::Slow)
+                    .count(),
+            )
+            .unwrap_or(u32::MAX);
+        if units > self.limits.max_cost_units {
+            return Err(format!(
+                "These calls would spend {units} of {} cost units this turn",
+                self.limits.max_cost_units
+            ));
+        }
+        if slow_calls > self.limits.max_slow_calls {
+            return Err(format!(
+                "These calls would make {slow_calls} of {} slow calls this turn",
+                self.limits.max_slow_calls
+            ));
+        }
+        self.units = units;
+        self.slow_calls = slow_calls;
+        Ok(())
+    }
+}
+
+/// The system prompt note on tool costs; `None` when the budget is off or
+/// every tool is fast and free.
+pub fn prompt_section(limits: &ToolBudgetConfig, tools: &[Box<dyn Tool>]) -> Option<String> {
+    if !limits.enabled {
+        return None;
+    }
+    let listed = |keep: &dyn Fn(&dyn Tool) -> bool| -> String {
+        tools
+            .iter()
+            .filter(|t| keep(t.as_ref()))
+            .map(|t| t.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let slow = listed(&|t| t.latency() == Latency::Slow);
+    let metered = listed(&|t| t.cost() == Cost::Metered);
+    let expensive = listed(&|t| t.cost() == Cost::Expensive);
+    if slow.is_empty() && metered.is_empty() && expensive.is_empty() {
+        return None;
+    }
+
+    let mut section = format!(
+        "## Tool Budget\n\n\
+         Each turn may spend {} cost units and make {} slow calls. Try fast, free tools \
+         (local files, memory) first; a round of tool calls over budget is rejected without \
+         running.\n",
+        limits.max_cost_units, limits.max_slow_calls
+    );
+    for (label, names) in [
+        ("Slow", slow),
+        ("Metered, 1 unit per call", metered),
+        ("Expensive, 5 units per call", expensive),
+    ] {
+        if !names.is_empty() {
+            let _ = write!(section, "\n- {label}: {names}");
+        }
+    }
+    Some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn over_budget_rounds_are_rejected_whole() {
+        let limits = ToolBudgetConfig {
+            max_cost_units: 6,
+            max_slow_calls: 1,
+            ..ToolBudgetConfig::default()
+        };
+        let mut budget = TurnBudget::new(&limits);
+        assert!(budget
+            .charge(&[
+                (Latency::Medium, Cost::Metered),
+                (Latency::Fast, Cost::Free)
+            ])
+            .is_ok());
+
+        let err = budget
+            .charge(&[
+                (Latency::Medium, Cost::Metered),
+                (Latency::Slow, Cost::Expensive),
+            ])
+            .unwrap_err();
+        assert!(err.contains("7 of 6 cost units"));
+        // Nothing of the rejected round was spent.
+        assert!(budget.charge(&[(Latency::Slow, Cost::Expensive)]).is_ok());
+        let err = budget.charge(&[(Latency::Slow, Cost::Free)]).unwrap_err();
+        assert!(err.contains("2 of 1 slow calls"));
+    }
+}
@@ -0,0 +1,1001 @@
+use super::budget::{self, TurnBudget};
+use super::citations::{self, Citations};
+use super::context::{self, ContextBudget};
+use super::failures::ToolFailureLog;
+use super::tool_calls::{self, RepairCounts, RepairStats};
+use super::trace::PromptTrace;
+use crate::artifacts::ArtifactStore;
+use crate::config::{ToolBudgetConfig, ToolOutputConfig};
+use crate::conversations::ConversationStore;
+use crate::experiments::{Arm, Experiment, Route, TurnUsage};
+use crate::memory::{Memory, MemoryCategory};
+use crate::observability::{Observer, ObserverEvent, ObserverMetric};
+use crate::providers::Provider;
+use crate::security::Guardrails;
+use crate::tools::{Tool, ToolResult};
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Instant;
+
+const DEFAULT_TEMPERATURE: f64 = 0.7;
+/// Session used for the failure log when no conversation store is attached.
+const DEFAULT_SESSION: &str = "default";
+const DEFAULT_TOOL_REPAIRS: usize = 2;
+/// Provider calls one turn may spend on tool calls before giving up.
+const DEFAULT_TOOL_ROUNDS: usize = 8;
+
+/// An embeddable agent: provider + model + system prompt, with optional
+/// memory (recall context, auto-save turns) and a tool registry.
+///
+/// ```no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use zeroclaw::agent::Agent;
+///
+/// let agent = Agent::builder()
+///     .provider_boxed(zeroclaw::providers::create_provider("openrouter", Some("sk-or-..."))?)
+///     .model("anthropic/claude-sonnet-4-20250514")
+///     .system_prompt("You are a terse build assistant.")
+///     .build()?;
+/// println!("{}", agent.chat("Summarize today's CI failures").await?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Agent {
+    provider: Box<dyn Provider>,
+    model: String,
+    temperature: f64,
+    system_prompt: String,
+    memory: Option<Arc<dyn Memory>>,
+    auto_save: bool,
+    tools: Vec<Box<dyn Tool>>,
+    conversations: Option<(Arc<ConversationStore>, String)>,
+    memory_session: Option<String>,
+    context_budget: ContextBudget,
+    pinned: Vec<String>,
+    failures: Arc<ToolFailureLog>,
+    guardrails: Option<(Arc<Guardrails>, String)>,
+    max_tool_repairs: usize,
+    max_tool_rounds: usize,
+    tool_budget: ToolBudgetConfig,
+    repairs: RepairStats,
+    tool_output: ToolOutputConfig,
+    artifacts: Option<Arc<ArtifactStore>>,
+    experiment: Option<Experiment>,
+    observer: Option<Arc<dyn Observer>>,
+    trace_prompts: bool,
+    citations: bool,
+}
+
+impl Agent {
+    pub fn builder() -> AgentBuilder {
+        AgentBuilder::default()
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub fn system_prompt(&self) -> &str {
+        &self.system_prompt
+    }
+
+    pub fn tools(&self) -> &[Box<dyn Tool>] {
+        &self.tools
+    }
+
+    /// Open the provider connection ahead of the first turn. Failures are
+    /// logged and otherwise ignored; the turn will simply connect itself.
+    pub async fn warmup(&self) {
+        if let Err(e) = self.provider.warmup().await {
+            tracing::debug!("Provider warmup failed (non-fatal): {e}");
+        }
+    }
+
+    /// How often malformed tool calls were repaired so far.
+    pub fn repair_stats(&self) -> RepairCounts {
+        self.repairs.snapshot()
+    }
+
+    /// Which providers served the replies so far, when a fallback chain is in use.
+    pub fn provider_usage(&self) -> Vec<crate::providers::ProviderUsage> {
+        self.provider.usage()
+    }
+
+    pub fn tool(&self, name: &str) -> Option<&dyn Tool> {
+        self.tools
+            .iter()
+            .find(|t| t.name() == name)
+            .map(AsRef::as_ref)
+    }
+
+    fn session(&self) -> &str {
+        self.conversations
+            .as_ref()
+            .map_or(DEFAULT_SESSION, |(_, session)| session.as_str())
+    }
+
+    /// Run a registered tool directly. Failures are remembered and listed in
+    /// the context of the following turns; every call is kept in the
+    /// conversation log for `sessions to-script`.
+    pub async fn call_tool(&self, name: &str, args: serde_json::Value) -> Result<ToolResult> {
+        let tool = self
+            .tool(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {name}"))?;
+        let started = Instant::now();
+        let result = tool.execute(args.clone()).await;
+        if let Some(observer) = &self.observer {
+            observer.record_event(&ObserverEvent::ToolCall {
+                tool: name.to_string(),
+                duration: started.elapsed(),
+                success: matches!(&result, Ok(done) if done.success),
+            });
+        }
+        let session = self.session();
+        match &result {
+            Ok(done) if done.success => self.failures.record_success(session, name, &args),
+            Ok(failed) => {
+                let error = failed.error.as_deref().unwrap_or(&failed.output);
+                self.failures.record_failure(session, name, &args, error);
+            }
+            Err(e) => self
+                .failures
+                .record_failure(session, name, &args, &format!("{e:#}")),
+        }
+        if let Some((store, session)) = &self.conversations {
+            let success = matches!(&result, Ok(done) if done.success);
+            if let Err(e) = store.record_action(session, name, &args, success) {
+                tracing::warn!("Failed to record tool action: {e}");
+            }
+        }
+        result
+    }
+
+    /// One turn: recall memory context, ask the provider, auto-save both sides.
+    pub async fn chat(&self, message: &str) -> Result<String> {
+        let turn = self.conversations.as_ref().and_then(|(store, session)| {
+            store
+                .begin_turn(session, message)
+                .map_err(|e| tracing::warn!("Failed to persist conversation turn: {e}"))
+                .ok()
+                .map(|id| (store, id))
+        });
+        let mut trace = (self.trace_prompts && turn.is_some()).then(PromptTrace::default);
+        let arm = self
+            .experiment
+            .as_ref()
+            .map_or(Arm::Control, Experiment::pick);
+        let route = self.route(arm);
+        let started = Instant::now();
+        let mut usage = TurnUsage::default();
+        let result = self
+            .respond(message, &route, trace.as_mut(), &mut usage)
+            .await;
+        if let Some(experiment) = &self.experiment {
+            experiment.record(
+                arm,
+                route.model,
+                turn.map(|(_, id)| id),
+                result.is_ok(),
+                started.elapsed(),
+                usage,
+            );
+        }
+        if let (Some((store, id)), Some(trace)) = (turn, &trace) {
+            super::trace::save(store, id, trace);
+        }
+        if let (Ok(response), Some(session), Some(mem)) =
+            (&result, &self.memory_session, &self.memory)
+        {
+            if let Err(e) =
+                super::sessions::save_turn(mem.as_ref(), session, message, response).await
+            {
+                tracing::warn!("Failed to save session turn to memory: {e}");
+            }
+        }
+        if let Some((store, id)) = turn {
+            let saved = match result {
+                Ok(ref response) => store.complete_turn(id, response),
+                Err(ref e) => store.fail_turn(id, &format!("{e:#}")),
+            };
+            if let Err(e) = saved {
+                tracing::warn!("Failed to persist conversation turn: {e}");
+            }
+        }
+        result
+    }
+
+    /// Provider, model and system prompt for a turn on `arm`.
+    fn route(&self, arm: Arm) -> Route<'_> {
+        let control = Route {
+            provider: self.provider.as_ref(),
+            model: &self.model,
+            system_prompt: Cow::Borrowed(&self.system_prompt),
+        };
+        match &self.experiment {
+            Some(experiment) => experiment.route(arm, control),
+            None => control,
+        }
+    }
+
+    async fn respond(
+        &self,
+        message: &str,
+        route: &Route<'_>,
+        mut trace: Option<&mut PromptTrace>,
+        usage: &mut TurnUsage,
+    ) -> Result<String> {
+        let mem = self.memory.as_deref();
+        if let (Some(mem), true) = (mem, self.auto_save) {
+            let _ = mem
+                .store("user_msg", message, MemoryCategory::Conversation)
+                .await;
+        }
+
+        let history = match &self.memory_session {
+            Some(session) => Some(context::History::Memory(session)),
+            None => self
+                .conversations
+                .as_ref()
+                .map(|(store, session)| context::History::Log(store, session)),
+        };
+        let failures = self.failures.notes(self.session());
+        let packed = context::gather(
+            &self.context_budget,
+            &self.pinned,
+            &failures,
+            history,
+            mem,
+            message,
+        )
+        .await;
+        if let Some(trace) = trace.as_deref_mut() {
+            *trace = PromptTrace::new(
+                route.model,
+                &route.system_prompt,
+                &self.context_budget,
+                &packed,
+            )
+            .with_tools(&self.tools);
+        }
+        let context = packed.text;
+        let enriched = if context.is_empty() {
+            message.to_string()
+        } else {
+            format!("{context}{message}")
+        };
+
+        let mut sources = self.citations.then(Citations::default);
+        let response = self
+            .complete(message, enriched, route, trace, sources.as_mut(), usage)
+            .await?;
+        let response = match &sources {
+            Some(sources) => sources.append(&response),
+            None => response,
+        };
+        let response = match &self.guardrails {
+            Some((guardrails, channel)) => guardrails.check(channel, &response).await.text,
+            None => response,
+        };
+
+        if let (Some(mem), true) = (mem, self.auto_save) {
+            let summary = truncate_with_ellipsis(&response, 100);
+            let _ = mem
+                .store("assistant_resp", &summary, MemoryCategory::Daily)
+                .await;
+        }
+        Ok(response)
+    }
+
+    /// Run one tool call and return its result as the model will see it:
+    /// tagged for citations and shortened, with the length it had before.
+    async fn tool_result(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+        message: &str,
+        sources: Option<&mut Citations>,
+    ) -> (usize, String) {
+        let output = match self.call_tool(name, arguments.clone()).await {
+            Ok(result) if result.success => result.output,
+            Ok(result) => format!("Error: {}", result.error.unwrap_or(result.output)),
+            Err(e) => format!("Error: {e:#}"),
+        };
+        let output = match sources {
+            Some(sources) => sources.tag(name, arguments, output),
+            None => output,
+        };
+        let original_chars = output.chars().count();
+        let output = super::tool_output::shorten(
+            &self.tool_output,
+            self.provider.as_ref(),
+            self.artifacts.as_deref(),
+            name,
+            message,
+            output,
+        )
+        .await;
+        (original_chars, output)
+    }
+
+    /// Ask the provider, running the tool calls in its replies until it
+    /// answers in plain text. A malformed call is sent back with the error
+    /// up to `max_tool_repairs` times per turn.
+    async fn complete(
+        &self,
+        message: &str,
+        mut prompt: String,
+        route: &Route<'_>,
+        mut trace: Option<&mut PromptTrace>,
+        mut sources: Option<&mut Citations>,
+        usage: &mut TurnUsage,
+    ) -> Result<String> {
+        let names: Vec<&str> = self.tools.iter().map(|t| t.name()).collect();
+        let mut repairs = 0;
+        let mut budget = TurnBudget::new(&self.tool_budget);
+        for _ in 0..self.max_tool_rounds {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.record_round(&prompt);
+            }
+            let started = Instant::now();
+            let reply = route
+                .provider
+                .chat_with_system(
+                    Some(&route.system_prompt),
+                    &prompt,
+                    route.model,
+                    self.temperature,
+                )
+                .await?;
+            usage.add(&route.system_prompt, &prompt, &reply);
+            if let Some(observer) = &self.observer {
+                observer.record_metric(&ObserverMetric::RequestLatency(started.elapsed()));
+            }
+            if names.is_empty() {
+                return Ok(reply);
+            }
+            match tool_calls::parse(&reply, &names) {
+                Ok((calls, _)) if calls.is_empty() => {
+                    if repairs > 0 {
+                        self.repairs.record_outcome(true);
+                    }
+                    return Ok(reply);
+                }
+                Ok((calls, renamed)) => {
+                    self.repairs.record_renamed(renamed);
+                    let _ = write!(prompt, "\n\n[Assistant]\n{}", reply.trim());
+                    let plan: Vec<_> = calls
+                        .iter()
+                        .filter_map(|call| self.tools.iter().find(|t| t.name() == call.name))
+                        .map(|t| (t.latency(), t.cost()))
+                        .collect();
+                    if let Err(over) = budget.charge(&plan) {
+                        tracing::info!("Tool calls rejected: {over}");
+                        let _ = write!(
+                            prompt,
+                            "\n\n[Tool budget]\n{over}, so none of them ran. Use fewer or \
+                             cheaper tools, or answer with what you have."
+                        );
+                        continue;
+                    }
+                    for call in calls {
+                        let (original_chars, output) = self
+                            .tool_result(
+                                &call.name,
+                                &call.arguments,
+                                message,
+                                sources.as_deref_mut(),
+                            )
+                            .await;
+                        if let Some(trace) = trace.as_deref_mut() {
+                            trace.record_output(&call.name, original_chars, &output);
+                        }
+                        let _ = write!(prompt, "\n\n[Tool result: {}]\n{output}", call.name);
+                    }
+                }
+                Err(error) => {
+                    self.repairs.record(&error);
+                    if repairs == self.max_tool_repairs {
+                        self.repairs.record_outcome(false);
+                        anyhow::bail!(
+                            "Model sent a malformed tool call {} time(s): {error}",
+                            repairs + 1
+                        );
+                    }
+                    repairs += 1;
+                    let _ = write!(
+                        prompt,
+                        "\n\n[Assistant]\n{}\n\n{}",
+                        reply.trim(),
+                        tool_calls::repair_prompt(&error)
+                    );
+                }
+            }
+        }
+        anyhow::bail!(
+            "No answer after {} rounds of tool calls",
+            self.max_tool_rounds
+        )
+    }
+}
+
+/// Builder for [`Agent`]. `provider` and `model` are required.
+pub struct AgentBuilder {
+    provider: Option<Box<dyn Provider>>,
+    model: Option<String>,
+    temperature: f64,
+    system_prompt: Option<String>,
+    memory: Option<Arc<dyn Memory>>,
+    auto_save: bool,
+    tools: Vec<Box<dyn Tool>>,
+    conversations: Option<(Arc<ConversationStore>, String)>,
+    memory_session: Option<String>,
+    context_budget: ContextBudget,
+    pinned: Vec<String>,
+    failures: Option<Arc<ToolFailureLog>>,
+    guardrails: Option<(Arc<Guardrails>, String)>,
+    max_tool_repairs: usize,
+    max_tool_rounds: usize,
+    tool_budget: ToolBudgetConfig,
+    tool_output: ToolOutputConfig,
+    artifacts: Option<Arc<ArtifactStore>>,
+    experiment: Option<Experiment>,
+    observer: Option<Arc<dyn Observer>>,
+    trace_prompts: bool,
+    citations: bool,
+}
+
+impl Default for AgentBuilder {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            model: None,
+            temperature: DEFAULT_TEMPERATURE,
+            system_prompt: None,
+            memory: None,
+            auto_save: true,
+            tools: Vec::new(),
+            conversations: None,
+            memory_session: None,
+            context_budget: ContextBudget::default(),
+            pinned: Vec::new(),
+            failures: None,
+            guardrails: None,
+            max_tool_repairs: DEFAULT_TOOL_REPAIRS,
+            max_tool_rounds: DEFAULT_TOOL_ROUNDS,
+            tool_budget: ToolBudgetConfig::default(),
+            tool_output: ToolOutputConfig::default(),
+            artifacts: None,
+            experiment: None,
+            observer: None,
+            trace_prompts: false,
+            citations: false,
+        }
+    }
+}
+
+impl AgentBuilder {
+    pub fn provider(self, provider: impl Provider + 'static) -> Self {
+        self.provider_boxed(Box::new(provider))
+    }
+
+    /// Use an already boxed provider, e.g. from [`crate::providers::create_provider`].
+    pub fn provider_boxed(mut self, provider: Box<dyn Provider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Defaults to a short prompt listing the registered tools.
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn memory(mut self, memory: Arc<dyn Memory>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Store each turn in memory (default: on; needs [`Self::memory`]).
+    pub fn auto_save(mut self, enabled: bool) -> Self {
+        self.auto_save = enabled;
+        self
+    }
+
+    /// Log every turn of `session` to `store` before and after the provider call.
+    pub fn conversations(
+        mut self,
+        store: Arc<ConversationStore>,
+        session: impl Into<String>,
+    ) -> Self {
+        self.conversations = Some((store, session.into()));
+        self
+    }
+
+    /// Save each answered turn of `session` to the memory backend and take
+    /// its recent turns from there instead of the conversation log (needs
+    /// [`Self::memory`]).
+    pub fn memory_session(mut self, session: impl Into<String>) -> Self {
+        self.memory_session = Some(session.into());
+        self
+    }
+
+    /// Token budget for the context packed in front of each message.
+    pub fn context_budget(mut self, budget: ContextBudget) -> Self {
+        self.context_budget = budget;
+        self
+    }
+
+    /// A note included with every message, ahead of history and memories.
+    pub fn pin(mut self, note: impl Into<String>) -> Self {
+        self.pinned.push(note.into());
+        self
+    }
+
+    /// Share a tool failure log (e.g. across agents serving one session).
+    pub fn failure_log(mut self, log: Arc<ToolFailureLog>) -> Self {
+        self.failures = Some(log);
+        self
+    }
+
+    /// Check every reply against the output policies of `channel`.
+    pub fn guardrails(mut self, guardrails: Arc<Guardrails>, channel: impl Into<String>) -> Self {
+        self.guardrails = Some((guardrails, channel.into()));
+        self
+    }
+
+    /// Re-prompts allowed per turn when the model sends a broken tool call
+    /// (cut-off JSON, unknown tool). Default: 2.
+    pub fn max_tool_repairs(mut self, repairs: usize) -> Self {
+        self.max_tool_repairs = repairs;
+        self
+    }
+
+    /// Provider calls a turn may spend on tool calls before it fails.
+    /// Default: 8.
+    pub fn max_tool_rounds(mut self, rounds: usize) -> Self {
+        self.max_tool_rounds = rounds.max(1);
+        self
+    }
+
+    /// Cost units and slow calls a turn may spend on tools. Default: 10
+    /// units, 3 slow calls.
+    pub fn tool_budget(mut self, config: ToolBudgetConfig) -> Self {
+        self.tool_budget = config;
+        self
+    }
+
+    /// How long tool results may get before they are windowed or summarized.
+    pub fn tool_output(mut self, config: ToolOutputConfig) -> Self {
+        self.tool_output = config;
+        self
+    }
+
+    /// Upload shortened tool results whole to `store` and link them.
+    pub fn artifacts(mut self, store: Option<Arc<ArtifactStore>>) -> Self {
+        self.artifacts = store;
+        self
+    }
+
+    /// Answer a share of turns with the experiment's candidate and log
+    /// every turn for `zeroclaw experiments report`.
+    pub fn experiment(mut self, experiment: Option<Experiment>) -> Self {
+        self.experiment = experiment;
+        self
+    }
+
+    /// Report tool calls and provider latency to `observer`.
+    pub fn observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Keep a [`PromptTrace`] of every logged turn (needs [`Self::conversations`]).
+    pub fn trace_prompts(mut self, enabled: bool) -> Self {
+        self.trace_prompts = enabled;
+        self
+    }
+
+    /// Number the files and pages tools return and list the ones a reply
+    /// cites under it.
+    pub fn citations(mut self, enabled: bool) -> Self {
+        self.citations = enabled;
+        self
+    }
+
+    pub fn tool(mut self, tool: impl Tool + 'static) -> Self {
+        self.tools.push(Box::new(tool));
+        self
+    }
+
+    pub fn tools(mut self, tools: impl IntoIterator<Item = Box<dyn Tool>>) -> Self {
+        self.tools.extend(tools);
+        self
+    }
+
+    pub fn build(self) -> Result<Agent> {
+        let provider = self
+            .provider
+            .ok_or_else(|| anyhow::anyhow!("Agent needs a provider"))?;
+        let model = self
+            .model
+            .filter(|m| !m.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Agent needs a model"))?;
+
+        let mut seen = std::collections::HashSet::new();
+        if let Some(dup) = self
+            .tools
+            .iter()
+            .find(|t| !seen.insert(t.name().to_string()))
+        {
+            anyhow::bail!("Duplicate tool name: {}", dup.name());
+        }
+
+        let mut system_prompt = self
+            .system_prompt
+            .unwrap_or_else(|| default_system_prompt(&self.tools));
+        if !self.tools.is_empty() && !system_prompt.contains(tool_calls::OPEN) {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(tool_calls::PROTOCOL);
+        }
+        if let Some(section) = budget::prompt_section(&self.tool_budget, &self.tools) {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(&section);
+        }
+        if self.citations {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(citations::PROMPT_SECTION);
+        }
+        Ok(Agent {
+            provider,
+            model,
+            temperature: self.temperature,
+            system_prompt,
+            memory: self.memory,
+            auto_save: self.auto_save,
+            tools: self.tools,
+            conversations: self.conversations,
+            memory_session: self.memory_session,
+            context_budget: self.context_budget,
+            pinned: self.pinned,
+            failures: self.failures.unwrap_or_default(),
+            guardrails: self.guardrails,
+            max_tool_repairs: self.max_tool_repairs,
+            max_tool_rounds: self.max_tool_rounds,
+            tool_budget: self.tool_budget,
+            repairs: RepairStats::default(),
+            tool_output: self.tool_output,
+            artifacts: self.artifacts,
+            experiment: self.experiment,
+            observer: self.observer,
+            trace_prompts: self.trace_prompts,
+            citations: self.citations,
+        })
+    }
+}
+
+fn default_system_prompt(tools: &[Box<dyn Tool>]) -> String {
+    let mut prompt = String::from("You are ZeroClaw, a helpful AI assistant.\n");
+    if !tools.is_empty() {
+        prompt.push_str("\n## Tools\n\n");
+        for tool in tools {
+            let _ = writeln!(prompt, "- **{}**: {}", tool.name(), tool.description());
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MarkdownMemory;
+    use crate::testing::MockProvider;
+    use crate::tools::MemoryStoreTool;
+    use serde_json::json;
+
+    #[test]
+    fn provider_and_model_are_required() {
+        assert!(Agent::builder().model("m").build().is_err());
+        assert!(Agent::builder()
+            .provider(MockProvider::new())
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn chat_uses_memory_context_and_auto_saves() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(MarkdownMemory::new(tmp.path()));
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let agent = Agent::builder()
+            .provider(MockProvider::with_replies(["Rust it is"]))
+            .model("mock")
+            .memory(mem.clone())
+            .tool(MemoryStoreTool::new(mem.clone()))
+            .build()
+            .unwrap();
+
+        assert!(agent.system_prompt().contains("memory_store"));
+        assert_eq!(agent.chat("Rust").await.unwrap(), "Rust it is");
+        assert!(!mem.recall("Rust it is", 5).await.unwrap().is_empty());
+
+        let result = agent
+            .call_tool("memory_store", json!({"key": "k", "content": "v"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(agent.call_tool("nope", json!({})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn chat_persists_turns() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(ConversationStore::open(tmp.path()).unwrap());
+        let provider = MockProvider::with_replies(["hi there"]);
+        provider.push_error("rate limited");
+        let agent = Agent::builder()
+            .provider(provider)
+            .model("mock")
+            .conversations(store.clone(), "cli:local")
+            .build()
+            .unwrap();
+
+        agent.chat("hello").await.unwrap();
+        assert!(agent.chat("again").await.is_err());
+
+        let turns = store.history("cli:local", 10).unwrap();
+        assert_eq!(turns[0].response.as_deref(), Some("hi there"));
+        assert_eq!(turns[1].status, crate::conversations::TurnStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn memory_sessions_save_answered_turns_to_memory() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(crate::memory::SqliteMemory::new(tmp.path()).unwrap());
+        let provider = MockProvider::with_replies(["Noted, Ada"]);
+        provider.push_error("rate limited");
+        let agent = Agent::builder()
+            .provider(provider)
+            .model("mock")
+            .auto_save(false)
+            .memory(mem.clone())
+            .memory_session("cli:work")
+            .build()
+            .unwrap();
+
+        agent.chat("My name is Ada").await.unwrap();
+        assert!(agent.chat("again").await.is_err());
+
+        let turns = super::super::sessions::load(mem.as_ref(), "cli:work", 10)
+            .await
+            .unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].user, "My name is Ada");
+        assert_eq!(turns[0].assistant, "Noted, Ada");
+    }
+
+    #[tokio::test]
+    async fn prompt_traces_are_stored_per_turn() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(ConversationStore::open(tmp.path()).unwrap());
+        let mem: Arc<dyn Memory> = Arc::new(MarkdownMemory::new(tmp.path()));
+        let agent = Agent::builder()
+            .provider(MockProvider::with_replies([
+                r#"<tool_call>{"name": "memory_store", "arguments": {"key": "lang", "content": "Rust"}}</tool_call>"#,
+                "Saved.",
+            ]))
+            .model("mock")
+            .auto_save(false)
+            .tool(MemoryStoreTool::new(mem))
+            .conversations(store.clone(), "cli:local")
+            .trace_prompts(true)
+            .build()
+            .unwrap();
+
+        agent.chat("remember I like Rust").await.unwrap();
+        let (turn, raw) = store.trace(None).unwrap().unwrap();
+        assert_eq!(turn.user_message, "remember I like Rust");
+        let trace: PromptTrace = serde_json::from_str(&raw).unwrap();
+        assert_eq!(trace.model, "mock");
+        assert_eq!(trace.tools[0].name, "memory_store");
+        assert_eq!(trace.rounds.len(), 2);
+        assert!(trace.rounds[1].contains("memory_store"));
+    }
+
+    #[tokio::test]
+    async fn cited_files_are_listed_under_the_reply() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("deploy.md"), "No deploys on Fridays.").unwrap();
+        let security = Arc::new(crate::security::SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..crate::security::SecurityPolicy::default()
+        });
+        let agent = Agent::builder()
+            .provider(MockProvider::with_replies([
+                r#"<tool_call>{"name": "fs_read", "arguments": {"path": "deploy.md"}}</tool_call>"#,
+                "Not on a Friday [1].",
+            ]))
+            .model("mock")
+            .auto_save(false)
+            .tool(crate::tools::FsReadTool::new(security))
+            .citations(true)
+            .build()
+            .unwrap();
+
+        assert!(agent.system_prompt().contains("## Citations"));
+        assert_eq!(
+            agent.chat("can I deploy today?").await.unwrap(),
+            "Not on a Friday [1].\n\nSources:\n[1] deploy.md"
+        );
+    }
+
+    /// A slow tool that counts its calls.
+    struct Crawl(Arc<std::sync::atomic::AtomicUsize>);
+
+    #[async_trait::async_trait]
+    impl Tool for Crawl {
+        fn name(&self) -> &str {
+            "crawl"
+        }
+
+        fn description(&self) -> &str {
+            "Crawl a site"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object"})
+        }
+
+        fn latency(&self) -> crate::tools::Latency {
+            crate::tools::Latency::Slow
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> Result<ToolResult> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(ToolResult {
+                success: true,
+                output: "crawled".into(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn tool_rounds_over_budget_do_not_run() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let call = r#"<tool_call>{"name": "crawl", "arguments": {}}</tool_call>"#;
+        let agent = Agent::builder()
+            .provider(MockProvider::with_replies([
+                format!("{call}\n{call}"),
+                call.to_string(),
+                "Done.".to_string(),
+            ]))
+            .model("mock")
+            .auto_save(false)
+            .tool(Crawl(calls.clone()))
+            .tool_budget(ToolBudgetConfig {
+                max_slow_calls: 1,
+                ..ToolBudgetConfig::default()
+            })
+            .build()
+            .unwrap();
+
+        assert!(agent.system_prompt().contains("- Slow: crawl"));
+        assert_eq!(agent.chat("crawl twice").await.unwrap(), "Done.");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    /// Replies with the prompt it was given.
+    struct EchoProvider;
+
+    #[async_trait::async_trait]
+    impl Provider for EchoProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            Ok(message.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_tool_calls_are_noted_in_the_next_prompt() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let security = Arc::new(crate::security::SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..crate::security::SecurityPolicy::default()
+        });
+        let agent = Agent::builder()
+            .provider(EchoProvider)
+            .model("mock")
+            .tool(crate::tools::FileReadTool::new(security))
+            .build()
+            .unwrap();
+
+        let args = json!({"path": "missing.txt"});
+        assert!(
+            !agent
+                .call_tool("file_read", args.clone())
+                .await
+                .unwrap()
+                .success
+        );
+        let prompt = agent.chat("read it again").await.unwrap();
+        assert!(prompt.contains("[Recent tool failures"));
+        assert!(prompt.contains(r#"file_read({"path":"missing.txt"}) failed"#));
+
+        std::fs::write(tmp.path().join("missing.txt"), "here now").unwrap();
+        assert!(agent.call_tool("file_read", args).await.unwrap().success);
+        assert!(!agent
+            .chat("thanks")
+            .await
+            .unwrap()
+            .contains("tool failures"));
+    }
+
+    #[tokio::test]
+    async fn malformed_tool_calls_are_sent_back_for_repair() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(MarkdownMemory::new(tmp.path()));
+        let agent = Agent::builder()
+            .provider(MockProvider::with_replies([
+                r#"<tool_call>{"name": "memory_store", "arguments": {"key": "lang", "cont"#,
+                r#"<tool_call>{"name": "Memory-Store", "arguments": {"key": "lang", "content": "Rust"}}</tool_call>"#,
+                "Saved.",
+            ]))
+            .model("mock")
+            .auto_save(false)
+            .tool(MemoryStoreTool::new(mem.clone()))
+            .build()
+            .unwrap();
+
+        assert!(agent.system_prompt().contains(tool_calls::OPEN));
+        assert_eq!(agent.chat("remember I like Rust").await.unwrap(), "Saved.");
+        assert!(mem.get("lang").await.unwrap().is_some());
+        let stats = agent.repair_stats();
+        assert_eq!((stats.truncated, stats.renamed, stats.repaired), (1, 1, 1));
+
+        let stubborn = Agent::builder()
+            .provider(MockProvider::with_replies([
+                r#"<tool_call>{"name": "launch_rockets"}</tool_call>"#,
+            ]))
+            .model("mock")
+            .max_tool_repairs(0)
+            .tool(MemoryStoreTool::new(mem))
+            .build()
+            .unwrap();
+        assert!(stubborn.chat("go").await.is_err());
+        assert_eq!(stubborn.repair_stats().gave_up, 1);
+    }
+
+    #[test]
+    fn duplicate_tool_names_are_rejected() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(MarkdownMemory::new(tmp.path()));
+        let result = Agent::builder()
+            .provider(MockProvider::new())
+            .model("mock")
+            .tool(MemoryStoreTool::new(mem.clone()))
+            .tool(MemoryStoreTool::new(mem))
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Source citations for answers built on workspace files and the web.
+//!
+//! While a turn runs, every source a tool hands the model (an `fs_search`
+//! passage, a file read, a search result, an opened page) gets a number for
+//! the whole turn and is tagged `[n]` in the tool output. The model cites
+//! the ones it uses; [`Citations::append`] then lists exactly those under the
+//! reply, with the file's line range or the URL.
+
+use regex::Regex;
+use serde_json::Value;
+use std::fmt::{self, Write};
+use std::sync::LazyLock;
+
+/// Added to the system prompt when citations are on.
+pub const PROMPT_SECTION: &str = "## Citations\n\n\
+    Tool results tag their sources as [n]. When your answer uses one, cite it \
+    inline with the same marker, e.g. \"Deploys freeze on Fridays [2].\" \
+    The list of cited sources is added to your reply for you; don't write one.";
+
+static PASSAGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^## (?P<path>[^\s:]+)(?::(?P<start>\d+)-(?P<end>\d+))?").unwrap()
+});
+static RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\[(?P<n>\d+)\] (?P<rest>.*\n\s+(?P<url>https?://\S+))").unwrap()
+});
+static MARKER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[(\d+)\]").unwrap());
+
+/// Where part of an answer came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File {
+        path: String,
+        lines: Option<(usize, usize)>,
+    },
+    Url(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File {
+                path,
+                lines: Some((start, end)),
+            } if start == end => write!(f, "{path}, line {start}"),
+            Self::File {
+                path,
+                lines: Some((start, end)),
+            } => write!(f, "{path}, lines {start}–{end}"),
+            Self::File { path, lines: None } => f.write_str(path),
+            Self::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+/// The sources seen during one turn, numbered from 1.
+#[derive(Debug, Default)]
+pub struct Citations {
+    sources: Vec<Source>,
+}
+
+impl Citations {
+    pub fn sources(&self) -> &[Source] {
+        &self.sources
+    }
+
+    /// The number of `source`, giving it the next one if it is new.
+    fn number(&mut self, source: Source) -> usize {
+        if let Some(i) = self.sources.iter().position(|s| *s == source) {
+            return i + 1;
+        }
+        self.sources.push(source);
+        self.sources.len()
+    }
+
+    /// Number the sources in a `tool` result and tag them in `output`.
+    /// Output of tools that don't read documents comes back unchanged.
+    pub fn tag(&mut self, tool: &str, args: &Value, output: String) -> String {
+        let arg = |name: &str| args.get(name).and_then(Value::as_str).map(str::to_string);
+        let line = |name: &str| {
+            args.get(name)
+                .and_then(Value::as_u64)
+                .and_then(|n| usize::try_from(n).ok())
+        };
+        match tool {
+            "fs_search" => PASSAGE
+                .replace_all(&output, |caps: &regex::Captures| {
+                    let lines = caps.name("start").zip(caps.name("end")).and_then(|(s, e)| {
+                        Some((s.as_str().parse().ok()?, e.as_str().parse().ok()?))
+                    });
+                    let n = self.number(Source::File {
+                        path: caps["path"].to_string(),
+                        lines,
+                    });
+                    format!("## [{n}] {}", &caps[0][3..])
+                })
+                .into_owned(),
+            "web_search" => RESULT
+                .replace_all(&output, |caps: &regex::Captures| {
+                    let n = self.number(Source::Url(caps["url"].to_string()));
+                    format!("[{n}] {}", &caps["rest"])
+                })
+                .into_owned(),
+            "fs_read" | "file_read" => match arg("path") {
+                Some(path) => {
+                    let lines = match (line("start_line"), line("end_line")) {
+                        (None, None) => None,
+                        (start, end) => Some((
+                            start.unwrap_or(1),
+                            end.unwrap_or_else(|| {
+                                (start.unwrap_or(1) + output.lines().count()).saturating_sub(1)
+                            }),
+                        )),
+                    };
+                    let n = self.number(Source::File { path, lines });
+                    format!("[{n}]\n{output}")
+                }
+                None => output,
+            },
+            "browser_open" | "browser" => match arg("url") {
+                Some(url) => {
+                    let n = self.number(Source::Url(url));
+                    format!("[{n}]\n{output}")
+                }
+                None => output,
+            },
+            _ => output,
+        }
+    }
+
+    /// `reply` with a "Sources:" list of the sources it cites. A reply that
+    /// cites nothing, or only numbers no tool handed out, is left as is.
+    pub fn append(&self, reply: &str) -> String {
+        let mut cited: Vec<usize> = MARKER
+            .captures_iter(reply)
+            .filter_map(|caps| caps[1].parse().ok())
+            .filter(|n| (1..=self.sources.len()).contains(n))
+            .collect();
+        cited.sort_unstable();
+        cited.dedup();
+        if cited.is_empty() {
+            return reply.to_string();
+        }
+        let mut out = format!("{}\n\nSources:", reply.trim_end());
+        for n in cited {
+            let _ = write!(out, "\n[{n}] {}", self.sources[n - 1]);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sources_are_numbered_across_tools_and_only_cited_ones_listed() {
+        let mut citations = Citations::default();
+        let passages = citations.tag(
+            "fs_search",
+            &json!({"query": "deploy"}),
+            "Found 2 passages:\n\n## docs/deploy.md:12-30 — Freezes\nNo deploys on Fridays.\n\n## notes.txt\nAsk Sam first."
+                .into(),
+        );
+        assert!(passages.contains("## [1] docs/deploy.md:12-30 — Freezes"));
+        assert!(passages.contains("## [2] notes.txt"));
+
+        let results = citations.tag(
+            "web_search",
+            &json!({"query": "friday deploys"}),
+            "Results for \"friday deploys\" (brave):\n\n[1] Why not Friday\n    https://blog.test/friday\n".into(),
+        );
+        assert!(results.contains("[3] Why not Friday\n    https://blog.test/friday"));
+
+        let read = citations.tag(
+            "fs_read",
+            &json!({"path": "docs/deploy.md", "start_line": 12, "end_line": 14}),
+            "a\nb\nc".into(),
+        );
+        assert!(read.starts_with("[4]\n"));
+        assert_eq!(citations.tag("shell", &json!({}), "ok".into()), "ok");
+
+        let reply = citations.append("No deploys on Fridays [1][3]. See [9].");
+        assert_eq!(
+            reply,
+            "No deploys on Fridays [1][3]. See [9].\n\nSources:\n\
+             [1] docs/deploy.md, lines 12–30\n[3] https://blog.test/friday"
+        );
+        assert_eq!(citations.append("Nothing cited."), "Nothing cited.");
+        assert_eq!(
+            citations.sources()[3].to_string(),
+            "docs/deploy.md, lines 12–14"
+        );
+    }
+}
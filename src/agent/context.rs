@@ -0,0 +1,455 @@
+use crate::conversations::{ConversationStore, TurnStatus};
+use crate::memory::Memory;
+use std::fmt::Write;
+
+const RECENT_TURN_LIMIT: usize = 20;
+const MEMORY_RECALL_LIMIT: usize = 5;
+/// Ingested document chunks recalled per message.
+const DOCUMENT_RECALL_LIMIT: usize = 4;
+
+/// Rough token estimate used for budgeting (~4 chars per token, same ratio
+/// as the memory chunker).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Prompt sections, highest priority first. When the budget runs short,
+/// later sections give way to earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Pinned,
+    Failures,
+    Recent,
+    Memories,
+    Documents,
+    Tools,
+}
+
+impl Section {
+    const ALL: [Self; 6] = [
+        Self::Pinned,
+        Self::Failures,
+        Self::Recent,
+        Self::Memories,
+        Self::Documents,
+        Self::Tools,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Pinned => "[Pinned]",
+            Self::Failures => "[Recent tool failures — fix the cause before retrying]",
+            Self::Recent => "[Recent conversation]",
+            Self::Memories => "[Memory context]",
+            Self::Documents => "[Documents — name the source when you use one]",
+            Self::Tools => "[Tools]",
+        }
+    }
+}
+
+/// Total token budget plus optional per-section caps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextBudget {
+    pub total: usize,
+    pub pinned: Option<usize>,
+    pub failures: Option<usize>,
+    pub recent: Option<usize>,
+    pub memories: Option<usize>,
+    pub documents: Option<usize>,
+    pub tools: Option<usize>,
+}
+
+impl ContextBudget {
+    /// Caps default to a share of `total`: pinned 20%, failures 10%, recent 40%,
+    /// memories 30%, documents 30%, tools uncapped.
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            pinned: Some(total / 5),
+            failures: Some(total / 10),
+            recent: Some(total * 2 / 5),
+            memories: Some(total * 3 / 10),
+            documents: Some(total * 3 / 10),
+            tools: None,
+        }
+    }
+
+    fn cap(&self, section: Section) -> usize {
+        match section {
+            Section::Pinned => self.pinned,
+            Section::Failures => self.failures,
+            Section::Recent => self.recent,
+            Section::Memories => self.memories,
+            Section::Documents => self.documents,
+            Section::Tools => self.tools,
+        }
+        .unwrap_or(self.total)
+    }
+}
+
+impl Default for ContextBudget {
+    fn default() -> Self {
+        Self::new(2000)
+    }
+}
+
+/// What made it into the prompt.
+#[derive(Debug, Clone, Default)]
+pub struct PackedContext {
+    pub text: String,
+    pub used_tokens: usize,
+    /// Items left out because their section or the total budget was full.
+    pub dropped: usize,
+    /// Every candidate, kept or not, in section order (for prompt traces).
+    pub items: Vec<PackedItem>,
+}
+
+/// One candidate and whether it fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedItem {
+    pub section: Section,
+    pub text: String,
+    pub tokens: usize,
+    /// Recall score, for memories
+    pub score: Option<f64>,
+    pub kept: bool,
+}
+
+struct Item {
+    text: String,
+    tokens: usize,
+    rank: f64,
+    score: Option<f64>,
+}
+
+/// Collects candidate prompt items and packs the most important ones into
+/// a [`ContextBudget`].
+///
+/// Within a section: pinned items keep insertion order, recent turns keep
+/// the newest, memories and documents keep the highest score, tools keep
+/// insertion order.
+#[derive(Default)]
+pub struct ContextPacker {
+    budget: ContextBudget,
+    items: Vec<(Section, Item)>,
+    next_seq: usize,
+}
+
+impl ContextPacker {
+    pub fn new(budget: ContextBudget) -> Self {
+        Self {
+            budget,
+            items: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, section: Section, text: String, score: Option<f64>) {
+        // Default rank favours earlier items (pinned, tools) or later ones (recent).
+        let seq = self.next_seq as f64;
+        self.next_seq += 1;
+        let rank = score.unwrap_or(match section {
+            Section::Recent | Section::Failures => seq,
+            _ => -seq,
+        });
+        let tokens = estimate_tokens(&text);
+        self.items.push((
+            section,
+            Item {
+                text,
+                tokens,
+                rank,
+                score,
+            },
+        ));
+    }
+
+    pub fn pin(&mut self, text: impl Into<String>) -> &mut Self {
+        self.push(Section::Pinned, text.into(), None);
+        self
+    }
+
+    /// Add failures oldest first; the newest win when space is short.
+    pub fn failure(&mut self, note: impl Into<String>) -> &mut Self {
+        self.push(Section::Failures, note.into(), None);
+        self
+    }
+
+    /// Add turns oldest first; the newest win when space is short.
+    pub fn recent_turn(&mut self, user: &str, assistant: Option<&str>) -> &mut Self {
+        let mut text = format!("User: {user}");
+        if let Some(reply) = assistant {
+            let _ = write!(text, "\nAssistant: {reply}");
+        }
+        self.push(Section::Recent, text, None);
+        self
+    }
+
+    pub fn memory(&mut self, key: &str, content: &str, score: Option<f64>) -> &mut Self {
+        self.push(
+            Section::Memories,
+            format!("{key}: {content}"),
+            Some(score.unwrap_or(0.0)),
+        );
+        self
+    }
+
+    /// An ingested document chunk; its text starts with its source.
+    pub fn document(&mut self, chunk: &str, score: Option<f64>) -> &mut Self {
+        self.push(
+            Section::Documents,
+            chunk.to_string(),
+            Some(score.unwrap_or(0.0)),
+        );
+        self
+    }
+
+    pub fn tool(&mut self, name: &str, schema: &serde_json::Value) -> &mut Self {
+        self.push(Section::Tools, format!("{name}: {schema}"), None);
+        self
+    }
+
+    pub fn pack(&self) -> PackedContext {
+        let mut remaining = self.budget.total;
+        let mut packed = PackedContext::default();
+
+        for section in Section::ALL {
+            let mut candidates: Vec<&Item> = self
+                .items
+                .iter()
+                .filter(|(s, _)| *s == section)
+                .map(|(_, item)| item)
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            candidates.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+
+            let header_tokens = estimate_tokens(section.header()) + 1;
+            let mut room = self.budget.cap(section).min(remaining);
+            let mut kept: Vec<&Item> = Vec::new();
+            let fits_header = room > header_tokens;
+            room = room.saturating_sub(header_tokens);
+            for item in &candidates {
+                let fits = fits_header && item.tokens <= room;
+                if fits {
+                    room -= item.tokens;
+                    kept.push(item);
+                }
+                packed.items.push(PackedItem {
+                    section,
+                    text: item.text.clone(),
+                    tokens: item.tokens,
+                    score: item.score,
+                    kept: fits,
+                });
+            }
+            packed.dropped += candidates.len() - kept.len();
+            if kept.is_empty() {
+                continue;
+            }
+
+            // Turns and failures read oldest → newest regardless of selection order.
+            if matches!(section, Section::Recent | Section::Failures) {
+                kept.reverse();
+            }
+            let _ = writeln!(packed.text, "{}", section.header());
+            let mut used = header_tokens;
+            for item in kept {
+                let _ = writeln!(packed.text, "- {}", item.text);
+                used += item.tokens;
+            }
+            packed.text.push('\n');
+            remaining -= used;
+            packed.used_tokens += used;
+        }
+        packed
+    }
+}
+
+/// Where a conversation's recent turns come from.
+#[derive(Clone, Copy)]
+pub enum History<'a> {
+    /// The conversation log, for channel conversations.
+    Log(&'a ConversationStore, &'a str),
+    /// A CLI session saved in the memory backend.
+    Memory(&'a str),
+}
+
+/// Pack the usual per-message context: pinned notes, recent tool failures,
+/// the session's recent completed turns, and the memories and ingested
+/// document chunks recalled for `message`.
+pub async fn gather(
+    budget: &ContextBudget,
+    pinned: &[String],
+    failures: &[String],
+    history: Option<History<'_>>,
+    mem: Option<&dyn Memory>,
+    message: &str,
+) -> PackedContext {
+    let mut packer = ContextPacker::new(budget.clone());
+    for note in pinned {
+        packer.pin(note.clone());
+    }
+    for note in failures {
+        packer.failure(note.clone());
+    }
+    match history {
+        Some(History::Log(store, session)) => match store.history(session, RECENT_TURN_LIMIT) {
+            Ok(turns) => {
+                for turn in turns.iter().filter(|t| t.status == TurnStatus::Complete) {
+                    packer.recent_turn(&turn.user_message, turn.response.as_deref());
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load recent turns: {e}"),
+        },
+        Some(History::Memory(session)) => {
+            let loaded = match mem {
+                Some(mem) => super::sessions::load(mem, session, RECENT_TURN_LIMIT).await,
+                None => Ok(Vec::new()),
+            };
+            match loaded {
+                Ok(turns) => {
+                    for turn in &turns {
+                        packer.recent_turn(&turn.user, Some(&turn.assistant));
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load session turns: {e}"),
+            }
+        }
+        None => {}
+    }
+    if let Some(mem) = mem {
+        let limit = MEMORY_RECALL_LIMIT + DOCUMENT_RECALL_LIMIT;
+        if let Ok(entries) = mem.recall(message, limit).await {
+            // Saved session turns come in through `history`, not recall.
+            let (documents, memories): (Vec<_>, Vec<_>) = entries
+                .iter()
+                .filter(|e| super::sessions::saved_turn(e).is_none())
+                .partition(|e| e.category == crate::memory::ingest::category());
+            for entry in documents.into_iter().take(DOCUMENT_RECALL_LIMIT) {
+                packer.document(&entry.content, entry.score);
+            }
+            for entry in memories.into_iter().take(MEMORY_RECALL_LIMIT) {
+                packer.memory(&entry.key, &entry.content, entry.score);
+            }
+        }
+    }
+    packer.pack()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_packer_produces_nothing() {
+        let result = ContextPacker::new(ContextBudget::default()).pack();
+        assert!(result.text.is_empty());
+        assert_eq!(result.used_tokens, 0);
+    }
+
+    #[test]
+    fn sections_render_in_priority_order() {
+        let mut packer = ContextPacker::new(ContextBudget::new(500));
+        packer
+            .memory("lang", "User prefers Rust", Some(0.9))
+            .recent_turn("hi", Some("hello"))
+            .pin("Timezone: CET")
+            .document("[Source: runbook.md]\nDeploys stop at 4pm", Some(0.8));
+        packer.failure("shell(make deploy) failed: no rule");
+        let text = packer.pack().text;
+        let pinned = text.find("[Pinned]").unwrap();
+        let failures = text.find("[Recent tool failures").unwrap();
+        let recent = text.find("[Recent conversation]").unwrap();
+        let memories = text
+            .find("[Memory context]\n- lang: User prefers Rust")
+            .unwrap();
+        let documents = text.find("- [Source: runbook.md]").unwrap();
+        assert!(pinned < failures && failures < recent && recent < memories);
+        assert!(memories < documents);
+    }
+
+    #[test]
+    fn section_caps_keep_newest_turns_and_best_memories() {
+        let budget = ContextBudget {
+            total: 1000,
+            pinned: None,
+            failures: None,
+            recent: Some(25),
+            memories: Some(16),
+            documents: None,
+            tools: Some(0),
+        };
+        let mut packer = ContextPacker::new(budget);
+        for i in 0..10 {
+            packer.recent_turn(&format!("question {i}"), Some("ok"));
+        }
+        packer
+            .memory("low", "barely relevant note", Some(0.1))
+            .memory("high", "deploy freeze on Fridays", Some(0.95))
+            .tool("shell", &serde_json::json!({"type": "object"}));
+
+        let result = packer.pack();
+        assert!(result.text.contains("question 9"));
+        assert!(!result.text.contains("question 0"));
+        assert!(result.text.find("question 8").unwrap() < result.text.find("question 9").unwrap());
+        assert!(result.text.contains("deploy freeze"));
+        assert!(!result.text.contains("barely relevant"));
+        assert!(!result.text.contains("[Tools]"));
+        assert!(result.dropped > 0);
+        let low = result
+            .items
+            .iter()
+            .find(|item| item.text.starts_with("low:"))
+            .unwrap();
+        assert_eq!(
+            (low.section, low.score, low.kept),
+            (Section::Memories, Some(0.1), false)
+        );
+    }
+
+    #[test]
+    fn total_budget_is_never_exceeded() {
+        let mut packer = ContextPacker::new(ContextBudget {
+            total: 50,
+            pinned: None,
+            failures: None,
+            recent: None,
+            memories: None,
+            documents: None,
+            tools: None,
+        });
+        packer.pin("a".repeat(120));
+        for i in 0..20 {
+            packer.memory(&format!("k{i}"), &"x".repeat(40), Some(f64::from(i)));
+        }
+        let result = packer.pack();
+        assert!(result.used_tokens <= 50);
+        assert!(result.text.contains(&"a".repeat(120)));
+        assert!(result.text.contains("k19"));
+    }
+
+    #[tokio::test]
+    async fn memory_sessions_come_back_as_recent_turns_not_memories() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        crate::agent::sessions::save_turn(&mem, "cli:work", "deploy plan?", "Friday")
+            .await
+            .unwrap();
+        let packed = gather(
+            &ContextBudget::default(),
+            &[],
+            &[],
+            Some(History::Memory("cli:work")),
+            Some(&mem),
+            "deploy plan",
+        )
+        .await;
+        assert!(packed
+            .text
+            .contains("- User: deploy plan?\nAssistant: Friday"));
+        assert!(!packed.text.contains("[Memory context]"));
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::Semaphore;
+
+type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// One conversation: a sender on a channel.
+pub fn session_key(channel: &str, sender: &str) -> String {
+    format!("{channel}:{sender}")
+}
+
+/// Runs jobs for many sessions at once, bounded by a shared worker limit,
+/// while keeping each session's jobs strictly in submission order.
+///
+/// Each active session gets a short-lived worker task that drains its queue
+/// and retires once the queue is empty, so idle sessions cost nothing.
+pub struct SessionExecutor {
+    permits: Arc<Semaphore>,
+    sessions: Arc<Mutex<HashMap<String, mpsc::Sender<Job>>>>,
+    queue_limit: usize,
+    // Every worker holds a clone; `shutdown` waits for all of them to drop.
+    done_tx: mpsc::Sender<()>,
+    done_rx: mpsc::Receiver<()>,
+}
+
+impl SessionExecutor {
+    pub fn new(max_concurrent: usize, queue_limit: usize) -> Self {
+        let (done_tx, done_rx) = mpsc::channel(1);
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            queue_limit: queue_limit.max(1),
+            done_tx,
+            done_rx,
+        }
+    }
+
+    pub fn from_config(config: &crate::config::AgentConfig) -> Self {
+        Self::new(config.max_concurrent_sessions, config.session_queue_limit)
+    }
+
+    /// Queue `job` behind earlier jobs of the same session. Returns `false`
+    /// (and drops the job) when that session's queue is full.
+    pub fn submit(&self, session: &str, job: impl Future<Output = ()> + Send + 'static) -> bool {
+        let mut job: Job = Box::pin(job);
+        let mut sessions = self
+            .sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if let Some(queue) = sessions.get(session) {
+            match queue.try_send(job) {
+                Ok(()) => return true,
+                Err(TrySendError::Full(_)) => {
+                    tracing::warn!(session, "Session queue full; dropping message");
+                    return false;
+                }
+                // The worker died (a job panicked); start a fresh one below.
+                Err(TrySendError::Closed(returned)) => job = returned,
+            }
+        }
+
+        let (tx, rx) = mpsc::channel(self.queue_limit);
+        sessions.insert(session.to_string(), tx);
+        tokio::spawn(run_session(
+            session.to_string(),
+            job,
+            rx,
+            self.permits.clone(),
+            self.sessions.clone(),
+            self.done_tx.clone(),
+        ));
+        true
+    }
+
+    /// Sessions that currently have a worker (running or queued jobs).
+    pub fn active_sessions(&self) -> usize {
+        self.sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .len()
+    }
+
+    /// Stop accepting work and wait for every queued job to finish.
+    pub async fn shutdown(self) {
+        let Self {
+            sessions,
+            done_tx,
+            mut done_rx,
+            ..
+        } = self;
+        drop(done_tx);
+        sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+        let _ = done_rx.recv().await;
+    }
+}
+
+async fn run_session(
+    key: String,
+    first: Job,
+    mut rx: mpsc::Receiver<Job>,
+    permits: Arc<Semaphore>,
+    sessions: Arc<Mutex<HashMap<String, mpsc::Sender<Job>>>>,
+    _done: mpsc::Sender<()>,
+) {
+    let mut next = Some(first);
+    while let Some(job) = next.take() {
+        let permit = permits.clone().acquire_owned().await;
+        job.await;
+        drop(permit);
+
+        // Check for more work under the map lock so a concurrent `submit`
+        // either lands in this queue or starts a new worker, never neither.
+        let mut map = sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match rx.try_recv() {
+            Ok(job) => next = Some(job),
+            // The entry can only belong to another worker after this
+            // receiver is gone, so it is still ours to retire.
+            Err(_) => {
+                map.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn jobs_in_one_session_run_in_order() {
+        let executor = SessionExecutor::new(4, 16);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        for i in 0..5_u64 {
+            let seen = seen.clone();
+            assert!(executor.submit("cli:me", async move {
+                // Later jobs sleep less; order must still hold.
+                tokio::time::sleep(Duration::from_millis(20 - i * 4)).await;
+                seen.lock().unwrap().push(i);
+            }));
+        }
+        executor.shutdown().await;
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn sessions_do_not_queue_behind_each_other() {
+        let executor = SessionExecutor::new(2, 16);
+        let (slow_tx, slow_rx) = tokio::sync::oneshot::channel::<()>();
+        let (fast_tx, fast_rx) = tokio::sync::oneshot::channel();
+
+        executor.submit("cli:me", async move {
+            let _ = slow_rx.await;
+        });
+        executor.submit("discord:friend", async move {
+            let _ = fast_tx.send(());
+        });
+
+        // The Discord reply finishes while the CLI job is still blocked.
+        tokio::time::timeout(Duration::from_secs(2), fast_rx)
+            .await
+            .expect("second session was blocked by the first")
+            .unwrap();
+        let _ = slow_tx.send(());
+        executor.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_bounded() {
+        let executor = SessionExecutor::new(2, 16);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        for i in 0..6 {
+            let (running, peak) = (running.clone(), peak.clone());
+            executor.submit(&session_key("slack", &format!("u{i}")), async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+        executor.shutdown().await;
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn full_session_queue_drops_new_jobs() {
+        let executor = SessionExecutor::new(1, 1);
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        assert!(executor.submit("s", async move {
+            let _ = release_rx.await;
+        }));
+        assert!(executor.submit("s", async {}));
+        assert!(!executor.submit("s", async {}));
+        let _ = release_tx.send(());
+        executor.shutdown().await;
+    }
+}
@@ -0,0 +1,162 @@
+use crate::util::truncate_with_ellipsis;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const MAX_PER_SESSION: usize = 8;
+const FAILURE_TTL: Duration = Duration::from_mins(30);
+const ARGS_CHARS: usize = 120;
+const ERROR_CHARS: usize = 160;
+
+/// One tool call that keeps failing the same way.
+#[derive(Debug, Clone)]
+pub struct ToolFailure {
+    pub tool: String,
+    /// Compact JSON of the arguments, truncated.
+    pub args: String,
+    pub error: String,
+    pub count: u32,
+    pub last_at: Instant,
+}
+
+impl ToolFailure {
+    fn note(&self) -> String {
+        let times = if self.count > 1 {
+            format!(" ({}×)", self.count)
+        } else {
+            String::new()
+        };
+        format!("{}({}) failed{times}: {}", self.tool, self.args, self.error)
+    }
+}
+
+/// Recent tool errors per session, so the next prompt can say what already
+/// failed and why instead of letting the model retry it blindly.
+///
+/// Entries expire after 30 minutes, and a later successful call with the
+/// same arguments clears them.
+#[derive(Default)]
+pub struct ToolFailureLog {
+    sessions: Mutex<HashMap<String, VecDeque<ToolFailure>>>,
+}
+
+impl ToolFailureLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_failure(&self, session: &str, tool: &str, args: &serde_json::Value, error: &str) {
+        let args = truncate_with_ellipsis(&args.to_string(), ARGS_CHARS);
+        let error = truncate_with_ellipsis(error.trim(), ERROR_CHARS);
+        let mut sessions = self.lock();
+        let failures = sessions.entry(session.to_string()).or_default();
+
+        if let Some(pos) = failures
+            .iter()
+            .position(|f| f.tool == tool && f.args == args)
+        {
+            if let Some(mut existing) = failures.remove(pos) {
+                existing.count += 1;
+                existing.error = error;
+                existing.last_at = Instant::now();
+                failures.push_back(existing);
+            }
+            return;
+        }
+
+        if failures.len() == MAX_PER_SESSION {
+            failures.pop_front();
+        }
+        failures.push_back(ToolFailure {
+            tool: tool.to_string(),
+            args,
+            error,
+            count: 1,
+            last_at: Instant::now(),
+        });
+    }
+
+    /// A call that works again is no longer worth warning about.
+    pub fn record_success(&self, session: &str, tool: &str, args: &serde_json::Value) {
+        let args = truncate_with_ellipsis(&args.to_string(), ARGS_CHARS);
+        if let Some(failures) = self.lock().get_mut(session) {
+            failures.retain(|f| !(f.tool == tool && f.args == args));
+        }
+    }
+
+    /// Unexpired failures for `session`, oldest first.
+    pub fn recent(&self, session: &str) -> Vec<ToolFailure> {
+        let mut sessions = self.lock();
+        let Some(failures) = sessions.get_mut(session) else {
+            return Vec::new();
+        };
+        failures.retain(|f| f.last_at.elapsed() < FAILURE_TTL);
+        failures.iter().cloned().collect()
+    }
+
+    /// One line per recent failure, ready for the prompt context.
+    pub fn notes(&self, session: &str) -> Vec<String> {
+        self.recent(session).iter().map(ToolFailure::note).collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, VecDeque<ToolFailure>>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn repeated_failures_collapse_into_one_note() {
+        let log = ToolFailureLog::new();
+        let args = json!({"command": "make deploy"});
+        log.record_failure(
+            "cli:local",
+            "shell",
+            &args,
+            "make: *** No rule to make target 'deploy'",
+        );
+        log.record_failure(
+            "cli:local",
+            "shell",
+            &args,
+            "make: *** No rule to make target 'deploy'",
+        );
+        log.record_failure(
+            "slack:bob",
+            "browser_open",
+            &json!({"url": "https://x.test/a"}),
+            "404",
+        );
+
+        let notes = log.notes("cli:local");
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].starts_with(r#"shell({"command":"make deploy"}) failed (2×): make:"#));
+        assert_eq!(log.notes("slack:bob").len(), 1);
+        assert!(log.notes("discord:eve").is_empty());
+    }
+
+    #[test]
+    fn success_clears_and_log_is_bounded() {
+        let log = ToolFailureLog::new();
+        for i in 0..12 {
+            log.record_failure(
+                "s",
+                "file_read",
+                &json!({"path": format!("f{i}")}),
+                "not found",
+            );
+        }
+        let recent = log.recent("s");
+        assert_eq!(recent.len(), MAX_PER_SESSION);
+        assert!(recent[0].args.contains("f4"));
+
+        log.record_success("s", "file_read", &json!({"path": "f11"}));
+        assert!(log.notes("s").iter().all(|n| !n.contains("f11")));
+    }
+}
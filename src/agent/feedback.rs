@@ -0,0 +1,150 @@
+//! Ratings and notes on replies.
+//!
+//! `/rate up|down [note]`, `/note <text>` or a bare 👍/👎 (also as a Discord
+//! reaction) mark the session's latest answered turn. The marks live next to
+//! the turns in the conversation log, and `zeroclaw agent sessions export`
+//! writes them out as JSONL for fine-tuning sets or prompt review.
+
+use crate::conversations::{AnnotatedTurn, ConversationStore, Rating};
+use anyhow::Result;
+use serde_json::json;
+
+/// What the user said about the last reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feedback {
+    Rate {
+        rating: Rating,
+        note: Option<String>,
+    },
+    Note(String),
+}
+
+fn rating_word(word: &str) -> Option<Rating> {
+    match strip_skin_tone(word) {
+        "up" | "good" | "+" | "+1" | "👍" => Some(Rating::Up),
+        "down" | "bad" | "-" | "-1" | "👎" => Some(Rating::Down),
+        _ => None,
+    }
+}
+
+/// `👍🏽` is still a thumbs up.
+fn strip_skin_tone(word: &str) -> &str {
+    word.trim_end_matches(|c: char| ('\u{1F3FB}'..='\u{1F3FF}').contains(&c))
+}
+
+/// Parse a message as feedback, or `None` if it is a normal message.
+pub fn parse(text: &str) -> Option<Feedback> {
+    let text = text.trim();
+    let note = |rest: &str| Some(rest.trim().to_string()).filter(|n| !n.is_empty());
+    if let Some(rest) = text.strip_prefix("/rate") {
+        let rest = rest.trim_start();
+        let (word, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        return rating_word(word).map(|rating| Feedback::Rate {
+            rating,
+            note: note(rest),
+        });
+    }
+    if let Some(rest) = text.strip_prefix("/note") {
+        return note(rest).map(Feedback::Note);
+    }
+    // Bare words like "up" are ordinary messages; only the emoji stands alone.
+    if !text.starts_with(['👍', '👎']) {
+        return None;
+    }
+    rating_word(text).map(|rating| Feedback::Rate { rating, note: None })
+}
+
+/// Record `feedback` on the latest answered turn of `session`; returns the
+/// acknowledgement to show the user.
+pub fn apply(store: &ConversationStore, session: &str, feedback: &Feedback) -> Result<String> {
+    let Some(turn) = store.last_complete_turn(session)? else {
+        return Ok("Nothing to rate yet — there is no answered message in this session.".into());
+    };
+    let quoted = crate::util::truncate_with_ellipsis(&turn.user_message, 40);
+    match feedback {
+        Feedback::Rate { rating, note } => {
+            store.annotate(turn.id, Some(*rating), note.as_deref())?;
+            Ok(format!(
+                "{} Rated the reply to \"{quoted}\".",
+                rating.emoji()
+            ))
+        }
+        Feedback::Note(note) => {
+            store.annotate(turn.id, None, Some(note))?;
+            Ok(format!("📝 Noted on the reply to \"{quoted}\"."))
+        }
+    }
+}
+
+/// One JSONL line per turn: the exchange in chat-message form plus its marks.
+pub fn export_jsonl(turns: &[AnnotatedTurn]) -> String {
+    let mut out = String::new();
+    for annotated in turns {
+        let turn = &annotated.turn;
+        let line = json!({
+            "session": turn.session,
+            "turn_id": turn.id,
+            "at": turn.started_at.to_rfc3339(),
+            "messages": [
+                {"role": "user", "content": turn.user_message},
+                {"role": "assistant", "content": turn.response.as_deref().unwrap_or_default()},
+            ],
+            "rating": annotated.rating.map(Rating::as_str),
+            "note": annotated.note,
+        });
+        out.push_str(&line.to_string());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_commands_and_bare_thumbs() {
+        assert_eq!(
+            parse("/rate down wrong timezone"),
+            Some(Feedback::Rate {
+                rating: Rating::Down,
+                note: Some("wrong timezone".into())
+            })
+        );
+        assert_eq!(
+            parse(" 👍🏽 "),
+            Some(Feedback::Rate {
+                rating: Rating::Up,
+                note: None
+            })
+        );
+        assert_eq!(
+            parse("/note cite the source next time"),
+            Some(Feedback::Note("cite the source next time".into()))
+        );
+        assert_eq!(parse("up"), None);
+        assert_eq!(parse("/rate maybe"), None);
+        assert_eq!(parse("👍 thanks, now book it"), None);
+    }
+
+    #[test]
+    fn rating_lands_on_the_last_reply_and_exports() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        let session = "telegram:42";
+        let none = apply(&store, session, &parse("👍").unwrap()).unwrap();
+        assert!(none.starts_with("Nothing to rate"));
+
+        let id = store.begin_turn(session, "weather tomorrow?").unwrap();
+        store.complete_turn(id, "Sunny, 21°C.").unwrap();
+        let ack = apply(&store, session, &parse("/rate up spot on").unwrap()).unwrap();
+        assert!(ack.contains("weather tomorrow?"));
+
+        let jsonl = export_jsonl(&store.annotated_turns(None, true).unwrap());
+        let line: serde_json::Value = serde_json::from_str(jsonl.trim()).unwrap();
+        assert_eq!(line["messages"][1]["content"], "Sunny, 21°C.");
+        assert_eq!(line["rating"], "up");
+        assert_eq!(line["note"], "spot on");
+    }
+}
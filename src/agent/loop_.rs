@@ -1,46 +1,143 @@
+use super::context::ContextBudget;
+use super::Agent;
+use crate::artifacts::ArtifactStore;
 use crate::config::Config;
-use crate::memory::{self, Memory, MemoryCategory};
+use crate::conversations::ConversationStore;
+use crate::memory::{self, Memory};
 use crate::observability::{self, Observer, ObserverEvent};
+use crate::providers::recording::CassetteMode;
 use crate::providers::{self, Provider};
 use crate::runtime;
-use crate::security::SecurityPolicy;
+use crate::security::{AutonomyLevel, Guardrails, SecurityPolicy};
 use crate::tools;
-use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
-use std::fmt::Write;
 use std::sync::Arc;
 use std::time::Instant;
 
-/// Build context preamble by searching memory for relevant entries
-async fn build_context(mem: &dyn Memory, user_msg: &str) -> String {
-    let mut context = String::new();
+/// Conversation-store session for the interactive/one-shot CLI agent.
+pub const CLI_SESSION: &str = "cli:local";
+/// Interactive command that puts the last reply on the clipboard.
+const COPY_COMMAND: &str = "/copy";
 
-    // Pull relevant memories for this message
-    if let Ok(entries) = mem.recall(user_msg, 5).await {
-        if !entries.is_empty() {
-            context.push_str("[Memory context]\n");
-            for entry in &entries {
-                let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
-            }
-            context.push('\n');
-        }
+/// Who approves risky calls: one-shot runs ask on the terminal; the
+/// interactive loop owns stdin, so it asks in the conversation and the CLI
+/// channel hands the next line over as the answer.
+fn cli_approver(config: &Config, interactive: bool) -> Arc<dyn tools::approval::Approver> {
+    use tools::approval::{ChannelApprover, ConsoleApprover};
+    let timeout = std::time::Duration::from_secs(config.autonomy.approvals.timeout_secs);
+    if interactive {
+        Arc::new(ChannelApprover::new(
+            Arc::new(crate::channels::CliChannel::new()),
+            crate::channels::cli::CLI_SENDER,
+            timeout,
+        ))
+    } else {
+        Arc::new(ConsoleApprover::new(timeout))
     }
+}
 
-    context
+/// What a sub-agent shares with the agent that spawns it.
+struct SubAgentParts {
+    security: Arc<SecurityPolicy>,
+    memory: Arc<dyn Memory>,
+    runtime: Arc<dyn runtime::RuntimeAdapter>,
+    middleware: Vec<Arc<dyn tools::ToolMiddleware>>,
+    dry_run: bool,
+    offline: bool,
 }
 
-#[allow(clippy::too_many_lines)]
+/// Sub-agents for the `delegate` tool: same provider and model, no memory
+/// context or history, only the tools `[agent.delegate].tools` names and the
+/// same middleware (approvals included) around them.
+fn subagent_factory(
+    config: &Config,
+    provider_name: &str,
+    model_name: &str,
+    parts: SubAgentParts,
+) -> tools::delegate::SubAgentFactory {
+    let config = config.clone();
+    let provider_name = provider_name.to_string();
+    let model_name = model_name.to_string();
+    Arc::new(move || {
+        let composio_key = if config.composio.enabled {
+            config.composio.api_key.as_deref()
+        } else {
+            None
+        };
+        let allowed = &config.agent.delegate.tools;
+        let artifacts = ArtifactStore::for_config(&config.artifacts);
+        let mut sub_tools: Vec<Box<dyn tools::Tool>> = tools::all_tools(
+            &parts.security,
+            parts.memory.clone(),
+            composio_key,
+            &config.browser,
+            &config.integrations,
+            artifacts.as_ref(),
+            &parts.runtime,
+        )
+        .into_iter()
+        .filter(|t| allowed.iter().any(|name| name == t.name()))
+        .collect();
+        if parts.dry_run {
+            sub_tools = tools::dry_run::simulate_all(sub_tools);
+        }
+        if parts.offline {
+            sub_tools = tools::offline::disable_network(sub_tools);
+        }
+        let provider = providers::create_resilient_provider(
+            &provider_name,
+            config.api_key.as_deref(),
+            &config.reliability,
+            &config.providers.fallback,
+        )?;
+        Agent::builder()
+            .provider_boxed(provider)
+            .model(&model_name)
+            .system_prompt(tools::delegate::SUBAGENT_PROMPT)
+            .auto_save(false)
+            .max_tool_rounds(config.agent.delegate.max_tool_rounds)
+            .max_tool_repairs(config.agent.tool_call_repairs)
+            .tool_budget(config.agent.tool_budget.clone())
+            .tool_output(config.tool_output.clone())
+            .artifacts(artifacts)
+            .tools(tools::middleware::apply(
+                sub_tools,
+                parts.middleware.clone(),
+            ))
+            .build()
+    })
+}
+
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 pub async fn run(
-    config: Config,
+    mut config: Config,
     message: Option<String>,
     provider_override: Option<String>,
     model_override: Option<String>,
     temperature: f64,
+    cassette: Option<CassetteMode>,
+    dry_run: bool,
+    session: Option<String>,
 ) -> Result<()> {
+    let session = super::sessions::session_for(session.as_deref())?;
+
+    // Dry run: the provider is real, but nothing the agent does sticks.
+    if dry_run {
+        config.memory.auto_save = false;
+        println!("🧪 Dry run: tool calls are simulated and memory is not written");
+    }
+
+    // Skills are a directory scan; run it while the rest is wired up.
+    let skills_scan = {
+        let workspace_dir = config.workspace_dir.clone();
+        tokio::task::spawn_blocking(move || crate::skills::load_skills_cached(&workspace_dir))
+    };
+
     // ── Wire up agnostic subsystems ──────────────────────────────
     let observer: Arc<dyn Observer> =
         Arc::from(observability::create_observer(&config.observability));
-    let _runtime = runtime::create_runtime(&config.runtime)?;
+    let runtime: Arc<dyn runtime::RuntimeAdapter> =
+        Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
         &config.autonomy,
         &config.workspace_dir,
@@ -60,24 +157,167 @@ pub async fn run(
     } else {
         None
     };
-    let _tools = tools::all_tools(&security, mem.clone(), composio_key, &config.browser);
+    let mcp_tools = crate::mcp::register_servers(&config.mcp).await;
+    if mcp_tools > 0 {
+        println!("🔌 MCP: {mcp_tools} tool(s) connected");
+    }
+    let artifacts = ArtifactStore::for_config(&config.artifacts);
+    let mut all_tools = tools::all_tools(
+        &security,
+        mem.clone(),
+        composio_key,
+        &config.browser,
+        &config.integrations,
+        artifacts.as_ref(),
+        &runtime,
+    );
+    if config.workspace_index.enabled {
+        match crate::workspace_index::WorkspaceIndex::open(&config) {
+            Ok(index) => all_tools.push(Box::new(tools::FsSearchTool::new(Arc::new(index)))),
+            Err(e) => tracing::warn!("Workspace index unavailable, fs_search is off: {e}"),
+        }
+    }
+    if !config.federation.peers.is_empty() {
+        all_tools.push(Box::new(tools::DelegatePeerTool::new(
+            config.federation.clone(),
+        )));
+    }
+    all_tools.push(Box::new(tools::ConfigUpdateTool::new(
+        config.config_path.clone(),
+        security.clone(),
+    )));
+    if config.voice.speak_tool {
+        all_tools.push(Box::new(tools::SpeakTool::new()));
+    }
+    let channel_names: Vec<String> = crate::channels::configured_channels(&config)
+        .iter()
+        .map(|(_, channel)| channel.name().to_string())
+        .collect();
+    let can_send = !channel_names.is_empty();
+    if can_send {
+        all_tools.push(Box::new(tools::SendMessageTool::new(
+            config.clone(),
+            security.clone(),
+            channel_names,
+        )));
+        all_tools.push(Box::new(tools::NotifyTool::new(
+            config.clone(),
+            security.clone(),
+        )));
+    }
+    let skills = skills_scan.await.unwrap_or_default();
+    let trust = Arc::new(crate::skills::trust::TrustStore::open(
+        &config.workspace_dir,
+        &config.skills,
+    ));
+    let wasm = Arc::new(crate::skills::wasm::WasmHost::new(&config.skills));
+    for tool in tools::skill_tools(&skills, &security, &runtime, &trust, &wasm) {
+        if all_tools.iter().any(|t| t.name() == tool.name()) {
+            tracing::warn!(
+                tool = tool.name(),
+                "Skill tool shadows an existing tool; skipped"
+            );
+        } else {
+            all_tools.push(tool);
+        }
+    }
+    let tools = if dry_run {
+        tools::dry_run::simulate_all(all_tools)
+    } else {
+        all_tools
+    };
 
     // ── Resolve provider ─────────────────────────────────────────
-    let provider_name = provider_override
+    let mut provider_name = provider_override
         .as_deref()
         .or(config.default_provider.as_deref())
         .unwrap_or("openrouter");
 
-    let model_name = model_override
+    let mut model_name = model_override
         .as_deref()
         .or(config.default_model.as_deref())
         .unwrap_or("anthropic/claude-sonnet-4-20250514");
 
-    let provider: Box<dyn Provider> = providers::create_resilient_provider(
+    // Offline: a replayed cassette needs no network, so only probe live runs.
+    let offline = if cassette.is_none() {
+        crate::offline::check(&config, provider_name).await
+    } else {
+        None
+    };
+    let tools = if let Some(plan) = &offline {
+        println!(
+            "📴 No network: using {} ({}); network tools are off",
+            plan.provider, plan.model
+        );
+        provider_name = &plan.provider;
+        model_name = &plan.model;
+        tools::offline::disable_network(tools)
+    } else {
+        tools
+    };
+    // Local models: make sure the chosen one fits in memory before loading it.
+    let fit = if cassette.is_none() {
+        crate::model_fit::check(&config, provider_name, model_name).await
+    } else {
+        None
+    };
+    if let Some(advice) = &fit {
+        println!("🧮 {}", advice.message);
+        if let Some(model) = &advice.model {
+            model_name = model;
+        }
+    }
+
+    // Supervised tools confirm through the same approver as interactive autonomy.
+    let approver = cli_approver(&config, message.is_none());
+    crate::security::approval::install_approver(approver.clone());
+    let mut middleware = tools::middleware::from_config(&config, &session);
+    if config.autonomy.level == AutonomyLevel::Interactive && !dry_run {
+        middleware.push(Arc::new(tools::approval::AskApproval::new(
+            &config.autonomy.approvals,
+            approver,
+        )));
+    }
+    let mut tools = tools;
+    if config.agent.delegate.enabled {
+        tools.push(Box::new(tools::DelegateTool::new(
+            config.agent.delegate.clone(),
+            subagent_factory(
+                &config,
+                provider_name,
+                model_name,
+                SubAgentParts {
+                    security: security.clone(),
+                    memory: mem.clone(),
+                    runtime: runtime.clone(),
+                    middleware: middleware.clone(),
+                    dry_run,
+                    offline: offline.is_some(),
+                },
+            ),
+        )));
+    }
+    let tools = tools::middleware::apply(tools, middleware);
+
+    let provider: Box<dyn Provider> = providers::create_provider_with_cassette(
         provider_name,
         config.api_key.as_deref(),
         &config.reliability,
+        &config.providers.fallback,
+        cassette.as_ref(),
     )?;
+    match cassette {
+        Some(CassetteMode::Record(ref path)) => {
+            println!("⏺️  Recording provider traffic to {}", path.display());
+        }
+        Some(CassetteMode::Replay(ref path)) => {
+            println!(
+                "📼 Offline: replaying provider responses from {}",
+                path.display()
+            );
+        }
+        None => {}
+    }
 
     observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.to_string(),
@@ -85,7 +325,6 @@ pub async fn run(
     });
 
     // ── Build system prompt from workspace MD files (OpenClaw framework) ──
-    let skills = crate::skills::load_skills(&config.workspace_dir);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         (
             "shell",
@@ -99,6 +338,22 @@ pub async fn run(
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "fs_read",
+            "Read a file or a line range of it. Use when: you need exact lines before patching. Don't use when: fs_search can find the passage.",
+        ),
+        (
+            "fs_write",
+            "Create, replace or append to a file. Use when: writing a new file or rewriting most of one. Don't use when: a few lines change (use fs_patch).",
+        ),
+        (
+            "fs_list",
+            "List a directory with sizes. Use when: finding files or checking layout. Don't use when: you already know the path.",
+        ),
+        (
+            "fs_patch",
+            "Apply a unified diff to workspace files. Use when: making focused edits to existing files. Don't use when: the file does not exist yet and has no /dev/null header.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -111,13 +366,69 @@ pub async fn run(
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "list_tasks",
+            "List tracked tasks and their progress notes. Use when: asked what is in flight, or before picking up earlier multi-step work. Don't use when: the work fits in this one reply.",
+        ),
+        (
+            "update_task",
+            "Open a task or record progress on one. Use when: starting work that spans several steps or may outlive this session; checkpoint after each step and close it when done. Don't use when: the request is a one-off answer.",
+        ),
+        (
+            "goals",
+            "Manage standing goals the heartbeat checks and acts on. Use when: the user wants something kept true over time (\"keep my inbox under 20\", \"remind me when the plants are dry\"). Don't use when: it is a one-off task or a fixed-time reminder (use cron).",
+        ),
+        (
+            "config_update",
+            "Change a ZeroClaw setting in config.toml after the user confirms the diff. Use when: the user asks to change how ZeroClaw itself behaves (\"set heartbeat to 30 minutes\", \"add +4915... to the iMessage allowlist\"). Don't use when: the setting is a provider key, autonomy or gateway; tell them to edit config.toml.",
+        ),
     ];
+    if config.agent.delegate.enabled {
+        tool_descs.push((
+            "delegate",
+            "Run independent subtasks in parallel on sub-agents and get their answers back. Use when: the request splits into several self-contained parts (\"research these 5 topics\", \"check each of these repos\"). Don't use when: the steps depend on each other or one quick lookup answers it.",
+        ));
+    }
+    if can_send {
+        tool_descs.push((
+            "send_message",
+            "Send a message to someone on a configured channel. Use when: asked to notify, remind or tell someone outside this conversation. Don't use when: replying to the current conversation (just answer). A queued message is retried automatically; never send it twice.",
+        ));
+        tool_descs.push((
+            "notify",
+            "Alert the user, or a household member by @name, through their configured notification route. Use when: a background check or long task found something they should know (\"the backup failed\", \"the price dropped\"). Don't use when: they are reading this conversation (just answer), or the message is for someone else (use send_message). Use severity critical only for what can't wait until morning.",
+        ));
+    }
+    if config.voice.speak_tool {
+        tool_descs.push((
+            "speak",
+            "Answer with a voice message. Use when: the user asks to hear the answer, or it is meant to be listened to (a read-aloud brief, a pronunciation). Don't use when: the answer has tables, links or code.",
+        ));
+    }
+    if artifacts.is_some() {
+        tool_descs.push((
+            "share_file",
+            "Upload a workspace file and get a link to it. Use when: the answer is a file (a chart, a recording, a report, a long log) the user should open. Don't use when: the content is short enough to answer with directly.",
+        ));
+    }
     if config.browser.enabled {
         tool_descs.push((
             "browser_open",
             "Open approved HTTPS URLs in Brave Browser (allowlist-only, no scraping)",
         ));
     }
+    let mcp_descs: Vec<(String, String)> = tools::registered_tools()
+        .into_iter()
+        .filter(|info| matches!(info.source, crate::registry::PluginSource::Mcp(_)))
+        .map(|info| match info.capabilities.join(", ") {
+            args if args.is_empty() => (info.name, info.description),
+            args => (
+                info.name,
+                format!("{} Arguments: {args}.", info.description),
+            ),
+        })
+        .collect();
+    tool_descs.extend(mcp_descs.iter().map(|(n, d)| (n.as_str(), d.as_str())));
     let system_prompt = crate::channels::build_system_prompt(
         &config.workspace_dir,
         model_name,
@@ -125,40 +436,83 @@ pub async fn run(
         &skills,
     );
 
+    let mut builder = Agent::builder()
+        .provider_boxed(provider)
+        .model(model_name)
+        .temperature(temperature)
+        .system_prompt(system_prompt)
+        .memory(mem.clone())
+        .auto_save(config.memory.auto_save)
+        .context_budget(ContextBudget::new(config.agent.context_tokens))
+        .max_tool_repairs(config.agent.tool_call_repairs)
+        .tool_budget(config.agent.tool_budget.clone())
+        .trace_prompts(config.agent.trace_prompts)
+        .citations(
+            config
+                .styles
+                .get("cli")
+                .is_some_and(|style| style.citations),
+        )
+        .tool_output(config.tool_output.clone())
+        .artifacts(artifacts.clone())
+        .observer(observer.clone())
+        .guardrails(Arc::new(Guardrails::for_config(&config)?), "cli")
+        .tools(tools);
+    let mut feedback_store = None;
+    if !dry_run {
+        let store = Arc::new(ConversationStore::open(&config.workspace_dir)?);
+        feedback_store = Some((store.clone(), session.clone()));
+        report_interrupted(&store, |s| s == session);
+        if message.is_none() && session != CLI_SESSION {
+            let turns = super::sessions::load(mem.as_ref(), &session, usize::MAX)
+                .await?
+                .len();
+            if turns > 0 {
+                println!(
+                    "↩️  Resuming session '{}' ({turns} turn(s))",
+                    session.trim_start_matches(super::sessions::PREFIX)
+                );
+            }
+        }
+        builder = builder
+            .memory_session(session.clone())
+            .conversations(store, session);
+    }
+    if let Some(note) = crate::presence::context_note(&config) {
+        builder = builder.pin(note);
+    }
+    // Experiments compare interactive turns only: not one-shot runs, which
+    // are mostly cron jobs, goal checks and heartbeat tasks.
+    if message.is_none() && !dry_run && offline.is_none() {
+        match crate::experiments::Experiment::for_config(&config) {
+            Ok(experiment) => builder = builder.experiment(experiment),
+            Err(e) => tracing::warn!("Experiment disabled: {e:#}"),
+        }
+    }
+    if offline.is_some() {
+        builder = builder.pin(crate::offline::CONTEXT_NOTE);
+    }
+    let agent = builder.build()?;
+
     // ── Execute ──────────────────────────────────────────────────
     let start = Instant::now();
 
     if let Some(msg) = message {
-        // Auto-save user message to memory
-        if config.memory.auto_save {
-            let _ = mem
-                .store("user_msg", &msg, MemoryCategory::Conversation)
-                .await;
-        }
-
-        // Inject memory context into user message
-        let context = build_context(mem.as_ref(), &msg).await;
-        let enriched = if context.is_empty() {
-            msg.clone()
+        if let Some(result) = crate::workflows::run_command(&config, &msg).await {
+            println!("{}", result?);
         } else {
-            format!("{context}{msg}")
-        };
-
-        let response = provider
-            .chat_with_system(Some(&system_prompt), &enriched, model_name, temperature)
-            .await?;
-        println!("{response}");
-
-        // Auto-save assistant response to daily log
-        if config.memory.auto_save {
-            let summary = truncate_with_ellipsis(&response, 100);
-            let _ = mem
-                .store("assistant_resp", &summary, MemoryCategory::Daily)
-                .await;
+            let msg = match crate::prompts::expand_command(&config, &msg) {
+                Some(rendered) => rendered?,
+                None => msg,
+            };
+            let response = agent.chat(&msg).await?;
+            println!("{}", crate::tools::reply::flatten(&response));
         }
     } else {
         println!("🦀 ZeroClaw Interactive Mode");
-        println!("Type /quit to exit.\n");
+        println!(
+            "Type /quit to exit, /rate up|down [note] to rate the last reply, /copy to copy it.\n"
+        );
 
         let (tx, mut rx) = tokio::sync::mpsc::channel(32);
         let cli = crate::channels::CliChannel::new();
@@ -168,39 +522,74 @@ pub async fn run(
             let _ = crate::channels::Channel::listen(&cli, tx).await;
         });
 
+        // Connect while the user types the first message.
+        agent.warmup().await;
+
+        let mut last_reply: Option<String> = None;
         while let Some(msg) = rx.recv().await {
-            // Auto-save conversation turns
-            if config.memory.auto_save {
-                let _ = mem
-                    .store("user_msg", &msg.content, MemoryCategory::Conversation)
-                    .await;
+            if msg.content == COPY_COMMAND {
+                let copied = match &last_reply {
+                    Some(reply) => crate::platform::copy_to_clipboard(reply).await,
+                    None => Err(anyhow::anyhow!("No reply to copy yet")),
+                };
+                match copied {
+                    Ok(()) => println!("\n📋 Copied the last reply\n"),
+                    Err(e) => println!("\n⚠️  {e}\n"),
+                }
+                continue;
             }
-
-            // Inject memory context into user message
-            let context = build_context(mem.as_ref(), &msg.content).await;
-            let enriched = if context.is_empty() {
-                msg.content.clone()
-            } else {
-                format!("{context}{}", msg.content)
+            if let Some(feedback) = super::feedback::parse(&msg.content) {
+                match &feedback_store {
+                    Some((store, session)) => {
+                        match super::feedback::apply(store, session, &feedback) {
+                            Ok(ack) => println!("\n{ack}\n"),
+                            Err(e) => println!("\n⚠️  {e}\n"),
+                        }
+                    }
+                    None => println!("\n🧪 Dry run: ratings are not recorded\n"),
+                }
+                continue;
+            }
+            if let Some(arg) = crate::focus::parse_command(&msg.content) {
+                match crate::focus::apply(&config, arg).await {
+                    Ok(reply) => println!("\n{reply}\n"),
+                    Err(e) => println!("\n⚠️  {e}\n"),
+                }
+                continue;
+            }
+            if let Some(result) = crate::workflows::run_command(&config, &msg.content).await {
+                match result {
+                    Ok(summary) => println!("\n{summary}\n"),
+                    Err(e) => println!("\n⚠️  {e:#}\n"),
+                }
+                continue;
+            }
+            let content = match crate::prompts::expand_command(&config, &msg.content) {
+                Some(Ok(rendered)) => rendered,
+                Some(Err(e)) => {
+                    println!("\n⚠️  {e}\n");
+                    continue;
+                }
+                None => msg.content,
             };
-
-            let response = provider
-                .chat_with_system(Some(&system_prompt), &enriched, model_name, temperature)
-                .await?;
+            let response = crate::tools::reply::flatten(&agent.chat(&content).await?);
             println!("\n{response}\n");
-
-            if config.memory.auto_save {
-                let summary = truncate_with_ellipsis(&response, 100);
-                let _ = mem
-                    .store("assistant_resp", &summary, MemoryCategory::Daily)
-                    .await;
-            }
+            last_reply = Some(response);
         }
 
         listen_handle.abort();
     }
 
     let duration = start.elapsed();
+    for usage in agent.provider_usage() {
+        observer.record_event(&ObserverEvent::ProviderUsage {
+            provider: usage.provider,
+            model: usage.model,
+            responses: usage.responses,
+            prompt_chars: usage.prompt_chars,
+            response_chars: usage.response_chars,
+        });
+    }
     observer.record_event(&ObserverEvent::AgentEnd {
         duration,
         tokens_used: None,
@@ -208,3 +597,25 @@ pub async fn run(
 
     Ok(())
 }
+
+/// Mark turns a crashed previous run left pending, and say so.
+pub(crate) fn report_interrupted(store: &ConversationStore, owns: impl Fn(&str) -> bool) {
+    match store.recover_interrupted(owns) {
+        Ok(turns) if !turns.is_empty() => {
+            println!(
+                "♻️  {} turn(s) were interrupted last run (marked in {}):",
+                turns.len(),
+                store.db_path().display()
+            );
+            for turn in turns {
+                println!(
+                    "   - [{}] {}",
+                    turn.session,
+                    crate::util::truncate_with_ellipsis(&turn.user_message, 80)
+                );
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Conversation recovery failed: {e}"),
+    }
+}
@@ -1,3 +1,20 @@
+pub mod budget;
+pub mod builder;
+pub mod citations;
+pub mod context;
+pub mod executor;
+pub mod failures;
+pub mod feedback;
 pub mod loop_;
+pub mod script;
+pub mod sessions;
+pub mod tool_calls;
+pub mod tool_output;
+pub mod trace;
 
+pub use builder::{Agent, AgentBuilder};
+pub use context::{ContextBudget, ContextPacker};
+pub use executor::{session_key, SessionExecutor};
+pub use failures::ToolFailureLog;
 pub use loop_::run;
+pub use tool_calls::RepairCounts;
@@ -0,0 +1,176 @@
+//! Turn a session's tool calls into a shell script.
+//!
+//! After the agent has run commands and written files, `zeroclaw agent
+//! sessions to-script <name>` writes out what actually ran, turn by turn, so
+//! it can be reviewed and replayed elsewhere. Shell commands and file writes
+//! become script lines; failed calls are kept but commented out, and tools
+//! with no shell equivalent (memory, web, ...) are listed as comments.
+
+use crate::conversations::{Action, Turn};
+use serde_json::Value;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Quote `s` as one POSIX shell word.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// A heredoc delimiter that does not occur as a line of `content`.
+fn delimiter(content: &str) -> String {
+    let mut tag = "ZEROCLAW_EOF".to_string();
+    while content.lines().any(|line| line == tag) {
+        tag.push('_');
+    }
+    tag
+}
+
+fn write_file(path: &str, content: &str) -> String {
+    let mut step = String::new();
+    if let Some(dir) = Path::new(path).parent().and_then(Path::to_str) {
+        if !dir.is_empty() {
+            let _ = writeln!(step, "mkdir -p {}", quote(dir));
+        }
+    }
+    if content.ends_with('\n') {
+        let tag = delimiter(content);
+        let _ = write!(step, "cat > {} <<'{tag}'\n{content}{tag}\n", quote(path));
+    } else {
+        // No trailing newline: a heredoc would add one.
+        let _ = writeln!(step, "printf '%s' {} > {}", quote(content), quote(path));
+    }
+    step
+}
+
+/// The script lines for one call, or `None` when it has no shell equivalent.
+fn replay(action: &Action) -> Option<String> {
+    let arg = |name: &str| action.arguments.get(name).and_then(Value::as_str);
+    match action.tool.as_str() {
+        "shell" => arg("command").map(|command| format!("{}\n", command.trim_end())),
+        "file_write" => Some(write_file(arg("path")?, arg("content")?)),
+        _ => None,
+    }
+}
+
+/// The replay script for `session`. `workspace` is where the commands ran;
+/// the script changes into it unless given another directory as `$1`.
+pub fn render(session: &str, workspace: &Path, turns: &[Turn], actions: &[Action]) -> String {
+    let mut script = String::new();
+    let _ = writeln!(script, "#!/bin/sh");
+    let _ = writeln!(script, "# Replay of ZeroClaw session '{session}'.");
+    let _ = writeln!(
+        script,
+        "# Review before running: commands run exactly as the agent ran them."
+    );
+    let _ = writeln!(script, "# Usage: sh script.sh [workspace-dir]");
+    let _ = writeln!(script, "set -eu");
+    let workspace: String = workspace
+        .display()
+        .to_string()
+        .chars()
+        .flat_map(|c| {
+            let escape = matches!(c, '"' | '$' | '`' | '\\').then_some('\\');
+            escape.into_iter().chain([c])
+        })
+        .collect();
+    let _ = writeln!(script, "cd \"${{1:-{workspace}}}\"");
+
+    let mut current_turn = None;
+    for action in actions {
+        if action.turn_id != current_turn {
+            current_turn = action.turn_id;
+            let request = turns
+                .iter()
+                .find(|t| Some(t.id) == action.turn_id)
+                .map(|t| crate::util::truncate_with_ellipsis(&t.user_message, 70));
+            let _ = writeln!(
+                script,
+                "\n# ── {} ── {}",
+                action.at.format("%Y-%m-%d %H:%M"),
+                request.unwrap_or_default().replace('\n', " ")
+            );
+        }
+        match replay(action) {
+            Some(step) if action.success => {
+                let _ = write!(script, "# {}\n{step}", action.tool);
+            }
+            Some(step) => {
+                let _ = writeln!(
+                    script,
+                    "# {} (failed when recorded, not replayed)",
+                    action.tool
+                );
+                for line in step.lines() {
+                    let _ = writeln!(script, "# {line}");
+                }
+            }
+            None => {
+                let args = crate::util::truncate_with_ellipsis(&action.arguments.to_string(), 120);
+                let _ = writeln!(script, "# not replayable: {} {args}", action.tool);
+            }
+        }
+    }
+    if actions.is_empty() {
+        let _ = writeln!(script, "\n# The session made no tool calls.");
+    }
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversations::ConversationStore;
+    use serde_json::json;
+
+    #[test]
+    fn sessions_render_as_reviewable_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConversationStore::open(dir.path()).unwrap();
+        let turn = store.begin_turn("cli:fix", "fix the build").unwrap();
+        store
+            .record_action("cli:fix", "shell", &json!({"command": "cargo build"}), true)
+            .unwrap();
+        store
+            .record_action(
+                "cli:fix",
+                "file_write",
+                &json!({"path": "src/it's.rs", "content": "fn main() {}\n"}),
+                true,
+            )
+            .unwrap();
+        store
+            .record_action("cli:fix", "shell", &json!({"command": "rm -rf out"}), false)
+            .unwrap();
+        store
+            .record_action("cli:fix", "memory_store", &json!({"key": "k"}), true)
+            .unwrap();
+        store.complete_turn(turn, "done").unwrap();
+
+        let actions = store.actions("cli:fix").unwrap();
+        assert_eq!(actions[0].turn_id, Some(turn));
+        let turns = store.history("cli:fix", 10).unwrap();
+        let script = render("fix", Path::new("/work"), &turns, &actions);
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("cd \"${1:-/work}\"\n"));
+        assert!(script.contains("fix the build"));
+        assert!(script.contains("# shell\ncargo build\n"));
+        assert!(script.contains(
+            "mkdir -p 'src'\ncat > 'src/it'\\''s.rs' <<'ZEROCLAW_EOF'\nfn main() {}\nZEROCLAW_EOF\n"
+        ));
+        assert!(script.contains("# shell (failed when recorded, not replayed)\n# rm -rf out\n"));
+        assert!(script.contains("# not replayable: memory_store {\"key\":\"k\"}"));
+
+        assert_eq!(store.delete_session("cli:fix").unwrap(), 1);
+        assert!(store.actions("cli:fix").unwrap().is_empty());
+    }
+
+    #[test]
+    fn contents_without_a_final_newline_are_written_exactly() {
+        assert_eq!(
+            write_file("a.txt", "no newline"),
+            "printf '%s' 'no newline' > 'a.txt'\n"
+        );
+        assert_eq!(delimiter("x\nZEROCLAW_EOF\n"), "ZEROCLAW_EOF_");
+    }
+}
@@ -0,0 +1,290 @@
+//! Named CLI conversations.
+//!
+//! `zeroclaw agent --session <name>` saves each answered turn to the
+//! configured `[memory]` backend under `cli:<name>` and loads the recent ones
+//! back as context, so a later run with the same name picks up where it left
+//! off. Without `--session` the CLI uses `cli:local`.
+//!
+//! The conversation log still records every turn and tool call, for
+//! `sessions export` and `sessions to-script`.
+
+use crate::config::Config;
+use crate::conversations::ConversationStore;
+use crate::memory::{Memory, MemoryCategory, MemoryEntry};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Prefix of every session the CLI agent owns.
+pub const PREFIX: &str = "cli:";
+const DEFAULT_NAME: &str = "local";
+
+/// The conversation-log session for `--session <name>`.
+pub fn session_for(name: Option<&str>) -> Result<String> {
+    let name = name.map_or(DEFAULT_NAME, str::trim);
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        anyhow::bail!("Session names must be non-empty and contain no spaces: {name:?}");
+    }
+    Ok(format!("{PREFIX}{name}"))
+}
+
+pub fn is_cli_session(session: &str) -> bool {
+    session.starts_with(PREFIX)
+}
+
+/// Memory category session turns are stored under.
+pub fn category() -> MemoryCategory {
+    MemoryCategory::Custom("session".into())
+}
+
+/// One answered turn as kept in the memory backend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedTurn {
+    pub session: String,
+    pub user: String,
+    pub assistant: String,
+    pub at: DateTime<Utc>,
+}
+
+/// The turn stored in `entry`, if it holds one. Backends that keep only
+/// text (markdown) prefix the content with its key, so parsing starts at
+/// the first brace.
+pub fn saved_turn(entry: &MemoryEntry) -> Option<SavedTurn> {
+    let json = &entry.content[entry.content.find('{')?..];
+    serde_json::from_str(json).ok()
+}
+
+/// Save one answered turn of `session`.
+pub async fn save_turn(
+    memory: &dyn Memory,
+    session: &str,
+    user: &str,
+    assistant: &str,
+) -> Result<()> {
+    let turn = SavedTurn {
+        session: session.to_string(),
+        user: user.to_string(),
+        assistant: assistant.to_string(),
+        at: Utc::now(),
+    };
+    let key = format!("session:{session}:{}", turn.at.timestamp_micros());
+    memory
+        .store(&key, &serde_json::to_string(&turn)?, category())
+        .await
+}
+
+/// Every saved turn, grouped by session, oldest first within each.
+async fn saved_turns(memory: &dyn Memory) -> Result<BTreeMap<String, Vec<(String, SavedTurn)>>> {
+    let mut sessions: BTreeMap<String, Vec<(String, SavedTurn)>> = BTreeMap::new();
+    for entry in memory.list(None).await? {
+        if let Some(turn) = saved_turn(&entry) {
+            sessions
+                .entry(turn.session.clone())
+                .or_default()
+                .push((entry.key, turn));
+        }
+    }
+    for turns in sessions.values_mut() {
+        turns.sort_by_key(|(_, turn)| turn.at);
+    }
+    Ok(sessions)
+}
+
+/// The last `limit` saved turns of `session`, oldest first.
+pub async fn load(memory: &dyn Memory, session: &str, limit: usize) -> Result<Vec<SavedTurn>> {
+    let mut turns: Vec<SavedTurn> = saved_turns(memory)
+        .await?
+        .remove(session)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(_, turn)| turn)
+        .collect();
+    turns.drain(..turns.len().saturating_sub(limit));
+    Ok(turns)
+}
+
+/// Forget every saved turn of `session`; returns how many were removed.
+async fn forget(memory: &dyn Memory, session: &str) -> Result<usize> {
+    let mut removed = 0;
+    for (key, _) in saved_turns(memory)
+        .await?
+        .remove(session)
+        .unwrap_or_default()
+    {
+        if memory.forget(&key).await? {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// `sessions list`: CLI sessions in the memory backend, most recent first.
+async fn print_list(memory: &dyn Memory) -> Result<()> {
+    let mut sessions: Vec<_> = saved_turns(memory)
+        .await?
+        .into_iter()
+        .filter(|(session, _)| is_cli_session(session))
+        .filter_map(|(session, turns)| {
+            let last = turns.last()?.1.clone();
+            Some((session, turns.len(), last))
+        })
+        .collect();
+    if sessions.is_empty() {
+        println!("No saved sessions yet.");
+        println!("\nUsage:");
+        println!("  zeroclaw agent --session work");
+        return Ok(());
+    }
+    sessions.sort_by_key(|(_, _, last)| std::cmp::Reverse(last.at));
+    println!("💬 Sessions ({}):", sessions.len());
+    for (session, turns, last) in sessions {
+        println!(
+            "- {} | {turns} turn(s) | last {}\n    {}",
+            session.trim_start_matches(PREFIX),
+            last.at.format("%Y-%m-%d %H:%M"),
+            crate::util::truncate_with_ellipsis(&last.user, 80)
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_command(command: crate::SessionCommands, config: &Config) -> Result<()> {
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    let memory = || {
+        crate::memory::create_memory(
+            &config.memory,
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )
+    };
+    match command {
+        crate::SessionCommands::List => print_list(memory()?.as_ref()).await,
+        crate::SessionCommands::Delete { name } => {
+            let session = session_for(Some(&name))?;
+            let memory = memory()?;
+            let saved = load(memory.as_ref(), &session, usize::MAX).await?.len();
+            let removed = forget(memory.as_ref(), &session).await?;
+            let logged = store.delete_session(&session)?;
+            if saved == 0 && logged == 0 {
+                anyhow::bail!("No session named '{name}'");
+            }
+            println!("🗑️  Deleted session '{name}' ({removed} turn(s))");
+            if removed < saved {
+                println!(
+                    "   The {} memory backend cannot forget entries; {} turn(s) stay in it.",
+                    memory.name(),
+                    saved - removed
+                );
+            }
+            Ok(())
+        }
+        crate::SessionCommands::ToScript { name, output } => {
+            let session = session_for(Some(&name))?;
+            let turns = store.history(&session, usize::MAX)?;
+            if turns.is_empty() {
+                anyhow::bail!("No session named '{name}'");
+            }
+            let actions = store.actions(&session)?;
+            let script = super::script::render(&name, &config.workspace_dir, &turns, &actions);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, script)?;
+                    #[cfg(unix)]
+                    {
+                        use std::os::unix::fs::PermissionsExt;
+                        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+                    }
+                    println!(
+                        "📜 Wrote {} tool call(s) from '{name}' to {}",
+                        actions.len(),
+                        path.display()
+                    );
+                }
+                None => print!("{script}"),
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Export {
+            name,
+            rated,
+            output,
+        } => {
+            let session = match name.as_deref() {
+                Some(name) if name.contains(':') => Some(name.to_string()),
+                Some(name) => Some(session_for(Some(name))?),
+                None => None,
+            };
+            let turns = store.annotated_turns(session.as_deref(), rated)?;
+            let jsonl = super::feedback::export_jsonl(&turns);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, jsonl)?;
+                    let rated = turns.iter().filter(|t| t.rating.is_some()).count();
+                    println!(
+                        "📤 Exported {} turn(s), {rated} rated, to {}",
+                        turns.len(),
+                        path.display()
+                    );
+                }
+                None => print!("{jsonl}"),
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_names_map_into_the_cli_namespace() {
+        assert_eq!(session_for(None).unwrap(), crate::agent::loop_::CLI_SESSION);
+        assert_eq!(session_for(Some(" work ")).unwrap(), "cli:work");
+        assert!(session_for(Some("")).is_err());
+        assert!(session_for(Some("two words")).is_err());
+        assert!(is_cli_session("cli:work"));
+        assert!(!is_cli_session("telegram:42"));
+    }
+
+    #[tokio::test]
+    async fn turns_round_trip_through_the_memory_backend() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let memory = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        save_turn(&memory, "cli:work", "first", "one")
+            .await
+            .unwrap();
+        save_turn(&memory, "cli:work", "second", "two")
+            .await
+            .unwrap();
+        save_turn(&memory, "cli:home", "elsewhere", "three")
+            .await
+            .unwrap();
+
+        let turns = load(&memory, "cli:work", 20).await.unwrap();
+        let users: Vec<&str> = turns.iter().map(|t| t.user.as_str()).collect();
+        assert_eq!(users, ["first", "second"]);
+        assert_eq!(turns[1].assistant, "two");
+        assert_eq!(
+            load(&memory, "cli:work", 1).await.unwrap()[0].user,
+            "second"
+        );
+
+        assert_eq!(forget(&memory, "cli:work").await.unwrap(), 2);
+        assert!(load(&memory, "cli:work", 20).await.unwrap().is_empty());
+        assert_eq!(load(&memory, "cli:home", 20).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn markdown_backend_keeps_sessions_too() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let memory = crate::memory::MarkdownMemory::new(tmp.path());
+        save_turn(&memory, "cli:work", "hi {there}", "hello")
+            .await
+            .unwrap();
+        let turns = load(&memory, "cli:work", 20).await.unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].user, "hi {there}");
+    }
+}
@@ -0,0 +1,297 @@
+//! Tool calls written into the model's reply, and repairing broken ones.
+//!
+//! Providers return plain text, so the model asks for a tool by replying
+//! with `<tool_call>{"name": "...", "arguments": {...}}</tool_call>`. Flaky
+//! models cut the JSON short or invent tool names. Instead of failing the
+//! turn, the agent sends the error back and lets the model try again a
+//! bounded number of times; near-miss names (`Memory-Store`) are fixed
+//! without a retry.
+
+use serde_json::Value;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub const OPEN: &str = "<tool_call>";
+pub const CLOSE: &str = "</tool_call>";
+
+/// Appended to the system prompt of agents with tools.
+pub const PROTOCOL: &str = "## Tool Calls\n\n\
+To use a tool, reply with only one or more blocks of the form\n\
+<tool_call>{\"name\": \"tool_name\", \"arguments\": {...}}</tool_call>\n\
+The results are sent back to you. Answer in plain text once you are done.";
+
+/// A tool call parsed from a reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub name: String,
+    pub arguments: Value,
+}
+
+/// Why a reply's tool call could not be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Malformed {
+    /// The JSON stops before it is complete, usually a length cut-off.
+    Truncated,
+    InvalidJson(String),
+    MissingName,
+    UnknownTool {
+        name: String,
+        suggestion: Option<String>,
+    },
+}
+
+impl fmt::Display for Malformed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(
+                f,
+                "the tool call JSON was cut off before it ended; send it again, complete and shorter"
+            ),
+            Self::InvalidJson(e) => write!(f, "the tool call is not valid JSON ({e})"),
+            Self::MissingName => write!(f, "the tool call has no \"name\""),
+            Self::UnknownTool {
+                name,
+                suggestion: Some(s),
+            } => write!(f, "there is no tool named \"{name}\" (did you mean \"{s}\"?)"),
+            Self::UnknownTool {
+                name,
+                suggestion: None,
+            } => write!(f, "there is no tool named \"{name}\""),
+        }
+    }
+}
+
+/// `Memory-Store` → `memory_store`.
+fn normalize(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '-' | ' ' | '.' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(ca != cb));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The registered name `name` was meant to be, and whether it was a near miss.
+fn resolve_name(name: &str, known: &[&str]) -> Result<(String, bool), Malformed> {
+    if known.contains(&name) {
+        return Ok((name.to_string(), false));
+    }
+    let wanted = normalize(name);
+    if let Some(exact) = known.iter().find(|k| normalize(k) == wanted) {
+        return Ok(((*exact).to_string(), true));
+    }
+    let suggestion = known
+        .iter()
+        .map(|k| (edit_distance(&wanted, k), k))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| (*k).to_string());
+    Err(Malformed::UnknownTool {
+        name: name.to_string(),
+        suggestion,
+    })
+}
+
+fn parse_one(body: &str, known: &[&str]) -> Result<(ToolCall, bool), Malformed> {
+    let body = body.trim();
+    let body = body
+        .strip_prefix("```json")
+        .or_else(|| body.strip_prefix("```"))
+        .map_or(body, |b| b.trim_end().trim_end_matches("```"))
+        .trim();
+    let value: Value = serde_json::from_str(body).map_err(|e| {
+        if e.is_eof() {
+            Malformed::Truncated
+        } else {
+            Malformed::InvalidJson(e.to_string())
+        }
+    })?;
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or(Malformed::MissingName)?;
+    let (name, renamed) = resolve_name(name, known)?;
+    let arguments = match value.get("arguments") {
+        // Some models send the arguments as a JSON string.
+        Some(Value::String(s)) => serde_json::from_str(s).map_err(|e| {
+            if e.is_eof() {
+                Malformed::Truncated
+            } else {
+                Malformed::InvalidJson(e.to_string())
+            }
+        })?,
+        Some(args) => args.clone(),
+        None => Value::Object(serde_json::Map::new()),
+    };
+    Ok((ToolCall { name, arguments }, renamed))
+}
+
+/// Tool calls in `reply`, plus how many names were corrected on the way.
+/// An empty list means the reply is a plain answer.
+pub fn parse(reply: &str, known: &[&str]) -> Result<(Vec<ToolCall>, usize), Malformed> {
+    let mut calls = Vec::new();
+    let mut renamed = 0;
+    let mut rest = reply;
+    while let Some(start) = rest.find(OPEN) {
+        let after = &rest[start + OPEN.len()..];
+        let (body, next, closed) = match after.find(CLOSE) {
+            Some(end) => (&after[..end], &after[end + CLOSE.len()..], true),
+            None => (after, "", false),
+        };
+        let (call, fixed) = match parse_one(body, known) {
+            // No closing tag: the reply was cut off mid-call.
+            Err(Malformed::InvalidJson(_)) if !closed => return Err(Malformed::Truncated),
+            other => other?,
+        };
+        renamed += usize::from(fixed);
+        calls.push(call);
+        rest = next;
+    }
+    Ok((calls, renamed))
+}
+
+/// The note sent back to the model after a malformed call.
+pub fn repair_prompt(error: &Malformed) -> String {
+    format!(
+        "[Tool call error] Your last tool call could not be used: {error}. \
+         Reply again with a corrected {OPEN}…{CLOSE} block, or answer in plain text."
+    )
+}
+
+/// Counts of tool-call repairs, shared by every turn of an agent.
+#[derive(Debug, Default)]
+pub struct RepairStats {
+    truncated: AtomicU64,
+    invalid_json: AtomicU64,
+    unknown_tool: AtomicU64,
+    renamed: AtomicU64,
+    repaired: AtomicU64,
+    gave_up: AtomicU64,
+}
+
+/// A point-in-time copy of [`RepairStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepairCounts {
+    pub truncated: u64,
+    pub invalid_json: u64,
+    pub unknown_tool: u64,
+    /// Near-miss tool names corrected without asking the model again
+    pub renamed: u64,
+    /// Turns that recovered after at least one re-prompt
+    pub repaired: u64,
+    /// Turns that ran out of retries
+    pub gave_up: u64,
+}
+
+impl RepairStats {
+    pub fn record(&self, error: &Malformed) {
+        let counter = match error {
+            Malformed::Truncated => &self.truncated,
+            Malformed::InvalidJson(_) | Malformed::MissingName => &self.invalid_json,
+            Malformed::UnknownTool { .. } => &self.unknown_tool,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        tracing::info!("Repairing malformed tool call: {error}");
+    }
+
+    pub fn record_renamed(&self, count: usize) {
+        self.renamed.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_outcome(&self, repaired: bool) {
+        let counter = if repaired {
+            &self.repaired
+        } else {
+            &self.gave_up
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        let counts = self.snapshot();
+        tracing::info!(
+            "Tool call repairs: {} repaired, {} gave up ({} truncated, {} invalid JSON, {} unknown tool, {} renamed)",
+            counts.repaired,
+            counts.gave_up,
+            counts.truncated,
+            counts.invalid_json,
+            counts.unknown_tool,
+            counts.renamed
+        );
+    }
+
+    pub fn snapshot(&self) -> RepairCounts {
+        RepairCounts {
+            truncated: self.truncated.load(Ordering::Relaxed),
+            invalid_json: self.invalid_json.load(Ordering::Relaxed),
+            unknown_tool: self.unknown_tool.load(Ordering::Relaxed),
+            renamed: self.renamed.load(Ordering::Relaxed),
+            repaired: self.repaired.load(Ordering::Relaxed),
+            gave_up: self.gave_up.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KNOWN: &[&str] = &["memory_store", "file_read"];
+
+    #[test]
+    fn calls_are_parsed_and_near_miss_names_fixed() {
+        let reply = r#"Saving that.
+<tool_call>{"name": "memory_store", "arguments": {"key": "k", "content": "v"}}</tool_call>
+<tool_call>```json
+{"name": "File-Read", "arguments": "{\"path\": \"a.txt\"}"}
+```</tool_call>"#;
+        let (calls, renamed) = parse(reply, KNOWN).unwrap();
+        assert_eq!(renamed, 1);
+        assert_eq!(calls[0].arguments, json!({"key": "k", "content": "v"}));
+        assert_eq!(calls[1].name, "file_read");
+        assert_eq!(calls[1].arguments, json!({"path": "a.txt"}));
+        assert_eq!(parse("Just an answer.", KNOWN).unwrap().0, []);
+    }
+
+    #[test]
+    fn broken_calls_are_classified() {
+        assert_eq!(
+            parse(r#"<tool_call>{"name": "file_read", "argum"#, KNOWN),
+            Err(Malformed::Truncated)
+        );
+        assert!(matches!(
+            parse(
+                r#"<tool_call>{"name": "file_read", "arguments": {"path": }</tool_call>"#,
+                KNOWN
+            ),
+            Err(Malformed::InvalidJson(_))
+        ));
+        assert_eq!(
+            parse(r#"<tool_call>{"name": "file_reed"}</tool_call>"#, KNOWN),
+            Err(Malformed::UnknownTool {
+                name: "file_reed".into(),
+                suggestion: Some("file_read".into())
+            })
+        );
+        assert_eq!(
+            parse(r#"<tool_call>{"arguments": {}}</tool_call>"#, KNOWN),
+            Err(Malformed::MissingName)
+        );
+    }
+}
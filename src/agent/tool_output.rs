@@ -0,0 +1,175 @@
+//! Keep oversized tool results from flooding the agent's context.
+//!
+//! A fetched web page or a wide SQL result can be far larger than the model
+//! needs. Results over the tool's `max_chars` are either windowed (start and
+//! end kept, middle dropped) or condensed by a cheap `summary_model` with the
+//! user's request as the focus. Results barely over the limit are always
+//! windowed, since a summary would cost a call to save very little. With
+//! `[artifacts]` on, the whole result is uploaded first and linked below
+//! the shortened one.
+
+use crate::artifacts::ArtifactStore;
+use crate::config::{ToolOutputConfig, ToolOutputMode};
+use crate::providers::Provider;
+
+/// Summaries are only worth a provider call above this multiple of the limit.
+const SUMMARIZE_ABOVE: usize = 2;
+/// The summary model is shown at most this multiple of the limit.
+const SUMMARY_INPUT_FACTOR: usize = 8;
+
+const SUMMARY_PROMPT: &str = "You condense tool output for another assistant. Keep every fact, \
+number, name, path and error message relevant to the request; drop boilerplate, navigation \
+and repetition. Reply with the condensed output only.";
+
+/// `output` cut to about `max_chars`: the first two thirds of the budget
+/// from the start, the rest from the end.
+pub fn window(output: &str, max_chars: usize) -> String {
+    let total = output.chars().count();
+    if total <= max_chars {
+        return output.to_string();
+    }
+    let head = max_chars * 2 / 3;
+    let tail = max_chars - head;
+    let start: String = output.chars().take(head).collect();
+    let end: String = output.chars().skip(total - tail).collect();
+    format!(
+        "{start}\n[… {} characters omitted …]\n{end}",
+        total - head - tail
+    )
+}
+
+/// Fit a result from `tool` into its budget, summarizing with
+/// `provider`/`summary_model` when configured and worth it, and linking the
+/// full result when `artifacts` is set.
+pub async fn shorten(
+    config: &ToolOutputConfig,
+    provider: &dyn Provider,
+    artifacts: Option<&ArtifactStore>,
+    tool: &str,
+    request: &str,
+    output: String,
+) -> String {
+    let (max_chars, mode) = config.rule_for(tool);
+    let total = output.chars().count();
+    if total <= max_chars || mode == ToolOutputMode::Full {
+        return output;
+    }
+    let link = match artifacts {
+        Some(store) => match store
+            .upload(&format!("{tool}-output.txt"), output.clone().into_bytes())
+            .await
+        {
+            Ok(url) => Some(url),
+            Err(e) => {
+                tracing::warn!("Uploading {tool} output failed: {e:#}");
+                None
+            }
+        },
+        None => None,
+    };
+    let shortened = condense(config, provider, tool, request, &output, max_chars, mode).await;
+    match link {
+        Some(url) => format!("{shortened}\n[Full output ({total} characters): {url}]"),
+        None => shortened,
+    }
+}
+
+/// `output` summarized or windowed to `max_chars`.
+async fn condense(
+    config: &ToolOutputConfig,
+    provider: &dyn Provider,
+    tool: &str,
+    request: &str,
+    output: &str,
+    max_chars: usize,
+    mode: ToolOutputMode,
+) -> String {
+    let total = output.chars().count();
+    let summary_model = config
+        .summary_model
+        .as_deref()
+        .filter(|_| mode == ToolOutputMode::Summarize && total > max_chars * SUMMARIZE_ABOVE);
+    if let Some(model) = summary_model {
+        let input = window(output, max_chars * SUMMARY_INPUT_FACTOR);
+        let message = format!(
+            "Request: {request}\n\nOutput of the `{tool}` tool:\n{input}\n\n\
+             Condense this to at most {max_chars} characters."
+        );
+        match provider
+            .chat_with_system(Some(SUMMARY_PROMPT), &message, model, 0.0)
+            .await
+        {
+            Ok(summary) => {
+                return format!(
+                    "[Summary of {total} characters of output]\n{}",
+                    window(summary.trim(), max_chars)
+                )
+            }
+            Err(e) => tracing::warn!("Summarizing {tool} output failed, windowing instead: {e}"),
+        }
+    }
+    window(output, max_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ToolOutputRule;
+    use crate::testing::MockProvider;
+
+    #[test]
+    fn windows_keep_the_start_and_end() {
+        assert_eq!(window("short", 10), "short");
+        let long = format!("{}{}{}", "a".repeat(60), "b".repeat(100), "c".repeat(30));
+        let windowed = window(&long, 90);
+        assert!(windowed.starts_with(&"a".repeat(60)));
+        assert!(windowed.ends_with(&"c".repeat(30)));
+        assert!(windowed.contains("[… 100 characters omitted …]"));
+    }
+
+    #[tokio::test]
+    async fn large_results_are_summarized_and_small_overruns_windowed() {
+        let mut config = ToolOutputConfig {
+            max_chars: 100,
+            summary_model: Some("cheap".into()),
+            ..ToolOutputConfig::default()
+        };
+        config.tools.insert(
+            "sql".into(),
+            ToolOutputRule {
+                mode: Some(ToolOutputMode::Full),
+                ..ToolOutputRule::default()
+            },
+        );
+        let provider = MockProvider::with_replies(["3 rows failed: ids 4, 9, 12"]);
+        let huge = "row ".repeat(500);
+
+        let summary = shorten(
+            &config,
+            &provider,
+            None,
+            "web_fetch",
+            "which rows failed?",
+            huge.clone(),
+        )
+        .await;
+        assert_eq!(
+            summary,
+            "[Summary of 2000 characters of output]\n3 rows failed: ids 4, 9, 12"
+        );
+        assert_eq!(provider.calls()[0].model, "cheap");
+        assert!(provider.calls()[0].message.contains("which rows failed?"));
+
+        let slightly_over = "x".repeat(150);
+        assert!(
+            shorten(&config, &provider, None, "web_fetch", "", slightly_over)
+                .await
+                .contains("characters omitted")
+        );
+        assert_eq!(
+            shorten(&config, &provider, None, "sql", "", huge.clone()).await,
+            huge
+        );
+        assert_eq!(provider.calls().len(), 1);
+    }
+}
@@ -0,0 +1,317 @@
+//! Prompt traces — exactly what went into each provider call of a turn.
+//!
+//! With `[agent] trace_prompts = true` every logged turn also records its
+//! system prompt, each context candidate (recalled memories with their
+//! scores) and whether it fit the budget, the tool schemas offered, the tool
+//! results that were shortened and the full prompt of every provider round.
+//! Traces are kept with the turn in the conversation log and printed by
+//! `zeroclaw debug prompt <turn-id>`.
+
+use super::context::{ContextBudget, PackedContext};
+use crate::config::Config;
+use crate::conversations::ConversationStore;
+use crate::tools::Tool;
+use crate::DebugCommands;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A context candidate and whether the budget let it in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextEntry {
+    pub section: String,
+    pub text: String,
+    pub tokens: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    pub kept: bool,
+}
+
+/// A tool schema as the model saw it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolSchema {
+    pub name: String,
+    pub schema: serde_json::Value,
+}
+
+/// A tool result shortened before it went back to the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Truncation {
+    pub tool: String,
+    pub original_chars: usize,
+    pub final_chars: usize,
+    pub summarized: bool,
+}
+
+/// Everything that went into one turn's prompts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PromptTrace {
+    pub model: String,
+    pub system_prompt: String,
+    pub context_budget: usize,
+    pub context_tokens: usize,
+    pub context: Vec<ContextEntry>,
+    pub tools: Vec<ToolSchema>,
+    pub truncations: Vec<Truncation>,
+    /// The user-side prompt of each provider call, tool results included
+    pub rounds: Vec<String>,
+}
+
+impl PromptTrace {
+    pub fn new(
+        model: &str,
+        system_prompt: &str,
+        budget: &ContextBudget,
+        packed: &PackedContext,
+    ) -> Self {
+        Self {
+            model: model.to_string(),
+            system_prompt: system_prompt.to_string(),
+            context_budget: budget.total,
+            context_tokens: packed.used_tokens,
+            context: packed
+                .items
+                .iter()
+                .map(|item| ContextEntry {
+                    section: item.section.header().to_string(),
+                    text: item.text.clone(),
+                    tokens: item.tokens,
+                    score: item.score,
+                    kept: item.kept,
+                })
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_tools(mut self, tools: &[Box<dyn Tool>]) -> Self {
+        self.tools = tools
+            .iter()
+            .map(|tool| ToolSchema {
+                name: tool.name().to_string(),
+                schema: tool.parameters_schema(),
+            })
+            .collect();
+        self
+    }
+
+    pub fn record_round(&mut self, prompt: &str) {
+        self.rounds.push(prompt.to_string());
+    }
+
+    /// Note a shortened result; results that went through whole are skipped.
+    pub fn record_output(&mut self, tool: &str, original_chars: usize, output: &str) {
+        let final_chars = output.chars().count();
+        if final_chars < original_chars {
+            self.truncations.push(Truncation {
+                tool: tool.to_string(),
+                original_chars,
+                final_chars,
+                summarized: output.starts_with("[Summary of "),
+            });
+        }
+    }
+
+    /// `## ` headings of the system prompt with the size of each section.
+    pub fn system_sections(&self) -> Vec<(String, usize)> {
+        let mut sections = vec![("(preamble)".to_string(), 0)];
+        for line in self.system_prompt.lines() {
+            if let Some(heading) = line.strip_prefix("## ") {
+                sections.push((heading.trim().to_string(), 0));
+            }
+            if let Some(last) = sections.last_mut() {
+                last.1 += line.chars().count() + 1;
+            }
+        }
+        sections.retain(|(_, chars)| *chars > 0);
+        sections
+    }
+
+    /// Human-readable report, everything but the full round prompts unless `full`.
+    pub fn render(&self, full: bool) -> String {
+        let mut out = String::new();
+        let estimate = super::context::estimate_tokens;
+        let _ = writeln!(out, "Model: {}", self.model);
+
+        let _ = writeln!(
+            out,
+            "\n## System prompt ({} chars, ~{} tokens)",
+            self.system_prompt.chars().count(),
+            estimate(&self.system_prompt)
+        );
+        for (heading, chars) in self.system_sections() {
+            let _ = writeln!(out, "  {chars:>7} chars  {heading}");
+        }
+
+        let dropped = self.context.iter().filter(|c| !c.kept).count();
+        let _ = writeln!(
+            out,
+            "\n## Context ({} of {} tokens, {dropped} dropped)",
+            self.context_tokens, self.context_budget
+        );
+        let mut section = "";
+        for entry in &self.context {
+            if entry.section != section {
+                section = &entry.section;
+                let _ = writeln!(out, "  {section}");
+            }
+            let mark = if entry.kept { "✓" } else { "✗" };
+            let score = entry
+                .score
+                .map(|s| format!(" score {s:.2}"))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "    {mark} ~{} tokens{score}  {}",
+                entry.tokens,
+                crate::util::truncate_with_ellipsis(&entry.text.replace('\n', " ⏎ "), 100)
+            );
+        }
+
+        let _ = writeln!(out, "\n## Tools ({})", self.tools.len());
+        for tool in &self.tools {
+            let schema = tool.schema.to_string();
+            let _ = writeln!(out, "  {} (~{} tokens)", tool.name, estimate(&schema));
+        }
+
+        if !self.truncations.is_empty() {
+            let _ = writeln!(out, "\n## Truncated tool output");
+            for t in &self.truncations {
+                let how = if t.summarized {
+                    "summarized"
+                } else {
+                    "windowed"
+                };
+                let _ = writeln!(
+                    out,
+                    "  {}: {} → {} chars ({how})",
+                    t.tool, t.original_chars, t.final_chars
+                );
+            }
+        }
+
+        let _ = writeln!(out, "\n## Provider rounds ({})", self.rounds.len());
+        for (i, round) in self.rounds.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  round {}: {} chars, ~{} tokens",
+                i + 1,
+                round.chars().count(),
+                estimate(round)
+            );
+            if full {
+                let _ = writeln!(out, "  ──────\n{round}\n  ──────");
+            }
+        }
+        if !full && !self.rounds.is_empty() {
+            let _ = writeln!(out, "  (--full prints each prompt)");
+        }
+        out
+    }
+}
+
+/// Store `trace` for `turn_id`; failures are logged, never fatal.
+pub fn save(store: &ConversationStore, turn_id: i64, trace: &PromptTrace) {
+    let saved = serde_json::to_string(trace)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| store.save_trace(turn_id, &json));
+    if let Err(e) = saved {
+        tracing::warn!("Failed to save prompt trace for turn {turn_id}: {e}");
+    }
+}
+
+pub fn handle_command(command: &DebugCommands, config: &Config) -> Result<()> {
+    let store = ConversationStore::open(&config.workspace_dir)?;
+    match command {
+        DebugCommands::Prompt {
+            turn_id,
+            full,
+            json,
+        } => {
+            let Some((turn, raw)) = store.trace(*turn_id)? else {
+                if let Some(id) = turn_id {
+                    anyhow::bail!("No prompt trace for turn {id}");
+                }
+                println!("No prompt traces yet.");
+                println!("\nTurn them on in ~/.zeroclaw/config.toml:");
+                println!("  [agent]");
+                println!("  trace_prompts = true");
+                return Ok(());
+            };
+            if *json {
+                println!("{raw}");
+                return Ok(());
+            }
+            let trace: PromptTrace = serde_json::from_str(&raw)?;
+            println!(
+                "🔎 Turn {} ({}, {})",
+                turn.id,
+                turn.session,
+                turn.started_at.format("%Y-%m-%d %H:%M:%S")
+            );
+            println!(
+                "   {}",
+                crate::util::truncate_with_ellipsis(&turn.user_message, 100)
+            );
+            if let Some(error) = &turn.error {
+                println!("   ❌ {error}");
+            }
+            print!("{}", trace.render(*full));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::context::ContextPacker;
+
+    #[test]
+    fn trace_keeps_scores_drops_and_truncations() {
+        let budget = ContextBudget {
+            total: 1000,
+            pinned: None,
+            failures: None,
+            recent: None,
+            memories: Some(12),
+            documents: None,
+            tools: None,
+        };
+        let mut packer = ContextPacker::new(budget.clone());
+        packer
+            .memory("deploys", "freeze on Fridays", Some(0.91))
+            .memory("lunch", "likes ramen, hates cilantro", Some(0.12));
+        let system = "You are ZeroClaw.\n\n## Tools\n\n- shell\n\n## Safety\n\nAsk first.";
+        let mut trace = PromptTrace::new("mock", system, &budget, &packer.pack());
+        trace.record_round("[Memory context]\n- deploys: freeze on Fridays\n\ndeploy?");
+        trace.record_output("web_fetch", 20_000, "[Summary of 20000 characters]\nshort");
+        trace.record_output("shell", 10, "untouched!");
+
+        let sections: Vec<String> = trace.system_sections().into_iter().map(|s| s.0).collect();
+        assert_eq!(sections, ["(preamble)", "Tools", "Safety"]);
+        let lunch = trace
+            .context
+            .iter()
+            .find(|c| c.text.starts_with("lunch"))
+            .unwrap();
+        assert!(!lunch.kept);
+        assert_eq!(lunch.score, Some(0.12));
+        assert_eq!(trace.truncations.len(), 1);
+        assert!(trace.truncations[0].summarized);
+
+        let report = trace.render(false);
+        assert!(report.contains("✓ ~"));
+        assert!(report.contains("score 0.91"));
+        assert!(report.contains("✗ ~"));
+        assert!(report.contains("web_fetch: 20000 →"));
+        assert!(report.contains("round 1:"));
+        assert!(!report.contains("deploy?"));
+        assert!(trace.render(true).contains("deploy?"));
+
+        let json = serde_json::to_string(&trace).unwrap();
+        let back: PromptTrace = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, trace);
+    }
+}
@@ -0,0 +1,191 @@
+//! Large artifacts in an S3-compatible bucket (`[artifacts]` in the config).
+//!
+//! Screenshots, files the agent shares and tool results too long for the
+//! context are uploaded and referenced by URL, so conversations and channel
+//! replies carry a link instead of megabytes. Links point under `public_url`
+//! when it is set, and are presigned for `url_expiry_hours` otherwise.
+
+use crate::config::ArtifactsConfig;
+use crate::sync::backend::{uri_encode, S3Backend};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Where artifacts go, and how links to them are made.
+pub struct ArtifactStore {
+    s3: S3Backend,
+    public_url: Option<String>,
+    expiry: Duration,
+}
+
+impl ArtifactStore {
+    pub fn new(config: &ArtifactsConfig) -> Result<Self> {
+        let s3 = S3Backend::new(
+            "artifacts",
+            config.bucket.as_deref(),
+            &config.region,
+            config.endpoint.as_deref(),
+            &config.prefix,
+            (
+                config.access_key_id.as_deref(),
+                config.secret_access_key.as_deref(),
+            ),
+        )?;
+        Ok(Self {
+            s3,
+            public_url: config
+                .public_url
+                .as_deref()
+                .map(str::trim)
+                .filter(|u| !u.is_empty())
+                .map(|u| u.trim_end_matches('/').to_string()),
+            expiry: Duration::from_hours(config.url_expiry_hours),
+        })
+    }
+
+    /// The store `[artifacts]` describes, or `None` when it is off. A
+    /// broken configuration is logged and treated as off.
+    pub fn for_config(config: &ArtifactsConfig) -> Option<Arc<Self>> {
+        if !config.enabled {
+            return None;
+        }
+        match Self::new(config) {
+            Ok(store) => Some(Arc::new(store)),
+            Err(e) => {
+                tracing::warn!("Artifact storage disabled: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Upload `data` under a fresh key ending in `file_name` and return a
+    /// link to it.
+    pub async fn upload(&self, file_name: &str, data: Vec<u8>) -> Result<String> {
+        let key = object_key(file_name, Utc::now());
+        let size = data.len();
+        self.s3
+            .put_object(&key, data, content_type(file_name))
+            .await?;
+        tracing::info!("Uploaded artifact {key} ({size} bytes)");
+        self.url(&key)
+    }
+
+    fn url(&self, key: &str) -> Result<String> {
+        match &self.public_url {
+            Some(base) => Ok(format!("{base}/{}", uri_encode(key, false))),
+            None => self.s3.presigned_url(key, self.expiry),
+        }
+    }
+}
+
+/// `YYYY/MM/DD/<random>-<name>`: unguessable, and easy to expire by date
+/// with a bucket lifecycle rule.
+fn object_key(file_name: &str, now: DateTime<Utc>) -> String {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    format!(
+        "{}/{}-{}",
+        now.format("%Y/%m/%d"),
+        &id[..12],
+        sanitize_file_name(file_name)
+    )
+}
+
+/// The last path component of `file_name`, with anything unusual in a URL
+/// replaced by `-`.
+fn sanitize_file_name(file_name: &str) -> String {
+    let name: String = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let name = name.trim_matches(['.', '-']);
+    if name.is_empty() {
+        "artifact".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `Content-Type` for `file_name` by its extension, so links open in the
+/// browser rather than download.
+pub fn content_type(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "txt" | "log" | "md" => "text/plain; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "json" => "application/json",
+        "html" | "htm" => "text/html; charset=utf-8",
+        "mp3" => "audio/mpeg",
+        "ogg" | "opus" | "oga" => "audio/ogg",
+        "wav" => "audio/wav",
+        "m4a" => "audio/mp4",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_dated_and_links_public_or_presigned() {
+        let now = DateTime::parse_from_rfc3339("2026-03-05T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let key = object_key("../screens/Home page (1).PNG", now);
+        assert!(key.starts_with("2026/03/05/"));
+        assert!(key.ends_with("-Home-page--1-.PNG"));
+        assert_eq!(sanitize_file_name("..."), "artifact");
+        assert_eq!(content_type("Home.PNG"), "image/png");
+        assert_eq!(content_type("build"), "application/octet-stream");
+
+        let mut config = ArtifactsConfig {
+            enabled: true,
+            bucket: Some("agent".into()),
+            endpoint: Some("https://minio.example:9000".into()),
+            access_key_id: Some("AKID".into()),
+            secret_access_key: Some("secret".into()),
+            public_url: Some("https://cdn.example/artifacts/".into()),
+            ..ArtifactsConfig::default()
+        };
+        let store = ArtifactStore::new(&config).unwrap();
+        assert_eq!(
+            store.url("2026/03/05/ab-a b.png").unwrap(),
+            "https://cdn.example/artifacts/2026/03/05/ab-a%20b.png"
+        );
+
+        config.public_url = None;
+        let store = ArtifactStore::for_config(&config).unwrap();
+        let url = store.url("2026/03/05/ab-shot.png").unwrap();
+        assert!(
+            url.starts_with("https://minio.example:9000/agent/artifacts/2026/03/05/ab-shot.png?")
+        );
+        assert!(url.contains("X-Amz-Expires=604800"));
+
+        config.bucket = None;
+        assert!(ArtifactStore::for_config(&config).is_none());
+    }
+}
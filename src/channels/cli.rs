@@ -1,8 +1,11 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelMessage, SenderProfile};
 use async_trait::async_trait;
 use tokio::io::{self, AsyncBufReadExt, BufReader};
 use uuid::Uuid;
 
+/// Who `CliChannel` messages are from.
+pub const CLI_SENDER: &str = "user";
+
 /// CLI channel — stdin/stdout, always available, zero deps
 pub struct CliChannel;
 
@@ -36,16 +39,22 @@ impl Channel for CliChannel {
             if line == "/quit" || line == "/exit" {
                 break;
             }
+            if crate::tools::approval::answer(&crate::agent::session_key("cli", CLI_SENDER), &line)
+            {
+                continue;
+            }
 
             let msg = ChannelMessage {
                 id: Uuid::new_v4().to_string(),
-                sender: "user".to_string(),
+                sender: CLI_SENDER.to_string(),
                 content: line,
                 channel: "cli".to_string(),
                 timestamp: std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
                     .as_secs(),
+                profile: SenderProfile::default(),
+                images: Vec::new(),
             };
 
             if tx.send(msg).await.is_err() {
@@ -93,6 +102,8 @@ mod tests {
             content: "hello".into(),
             channel: "cli".into(),
             timestamp: 1_234_567_890,
+            profile: SenderProfile::default(),
+            images: Vec::new(),
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -109,6 +120,8 @@ mod tests {
             content: "c".into(),
             channel: "ch".into(),
             timestamp: 0,
+            profile: SenderProfile::default(),
+            images: Vec::new(),
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -0,0 +1,32 @@
+use super::traits::{Channel, ChannelMessage};
+use async_trait::async_trait;
+
+/// Desktop notification channel — shows proactive messages (briefs, alerts,
+/// workflow results) as a notification on the machine the daemon runs on,
+/// through [`crate::platform::notify`]. Always available, nothing to set up.
+///
+/// Send-only: nothing is read back, so `listen` idles until shutdown.
+pub struct DesktopChannel;
+
+impl DesktopChannel {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Channel for DesktopChannel {
+    fn name(&self) -> &str {
+        "desktop"
+    }
+
+    async fn send(&self, message: &str, _recipient: &str) -> anyhow::Result<()> {
+        let body = crate::util::truncate_with_ellipsis(message.trim(), 500);
+        crate::platform::notify("ZeroClaw", &body).await
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tx.closed().await;
+        Ok(())
+    }
+}
@@ -1,28 +1,182 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelMessage, Reaction, SenderProfile};
+use crate::providers::ImageAttachment;
+use crate::tools::reply::{self, StructuredReply};
+use crate::util::truncate_with_ellipsis;
+use crate::vision;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
+const API: &str = "https://discord.com/api/v10";
+/// Message IDs of slash commands start with this; there is nothing to react to.
+const INTERACTION_PREFIX: &str = "interaction:";
+/// How long Discord accepts answers to a slash command.
+const INTERACTION_TTL_SECS: u64 = 15 * 60;
+
 /// Discord channel — connects via Gateway WebSocket for real-time messages
+#[allow(clippy::struct_excessive_bools)]
 pub struct DiscordChannel {
     bot_token: String,
     guild_id: Option<String>,
     allowed_users: Vec<String>,
+    /// Servers and channels answered in; empty = any
+    allowed_guilds: Vec<String>,
+    allowed_channels: Vec<String>,
+    /// Acknowledge inbound messages with reactions
+    reactions: bool,
+    slash_commands: bool,
+    /// Answer server messages in a thread started from them
+    thread_replies: bool,
+    /// Download image attachments for the model
+    images: bool,
+    state: Mutex<GatewayState>,
     client: reqwest::Client,
 }
 
+/// What the gateway connection has learned that sending needs.
+#[derive(Default)]
+struct GatewayState {
+    application_id: Option<String>,
+    /// Thread ID → parent channel ID
+    threads: HashMap<String, String>,
+    /// Channel ID → token of the slash command waiting for its answer there
+    pending: HashMap<String, (String, Instant)>,
+    /// Our slash command answers: message ID → interaction token, for edits
+    answers: HashMap<String, (String, Instant)>,
+}
+
+/// One use of `/ask`, `/reset` or `/status`.
+#[derive(Debug, Clone, PartialEq)]
+struct SlashCommand {
+    id: String,
+    token: String,
+    guild_id: Option<String>,
+    channel_id: String,
+    user_id: String,
+    user_name: Option<String>,
+    name: String,
+    prompt: String,
+}
+
 impl DiscordChannel {
     pub fn new(bot_token: String, guild_id: Option<String>, allowed_users: Vec<String>) -> Self {
         Self {
             bot_token,
             guild_id,
             allowed_users,
-            client: reqwest::Client::new(),
+            allowed_guilds: Vec::new(),
+            allowed_channels: Vec::new(),
+            reactions: true,
+            slash_commands: true,
+            thread_replies: false,
+            images: false,
+            state: Mutex::new(GatewayState::default()),
+            client: crate::util::shared_client().clone(),
         }
     }
 
+    /// Turn acknowledgement reactions on or off.
+    #[must_use]
+    pub fn with_reactions(mut self, reactions: bool) -> Self {
+        self.reactions = reactions;
+        self
+    }
+
+    /// Only answer in these servers and channels (threads count as their
+    /// parent channel); an empty list allows any.
+    #[must_use]
+    pub fn with_allowlists(mut self, guilds: Vec<String>, channels: Vec<String>) -> Self {
+        self.allowed_guilds = guilds;
+        self.allowed_channels = channels;
+        self
+    }
+
+    /// Register `/ask`, `/reset` and `/status` on connect, or not.
+    #[must_use]
+    pub fn with_slash_commands(mut self, slash_commands: bool) -> Self {
+        self.slash_commands = slash_commands;
+        self
+    }
+
+    /// Answer server messages in a thread started from each one.
+    #[must_use]
+    pub fn with_thread_replies(mut self, thread_replies: bool) -> Self {
+        self.thread_replies = thread_replies;
+        self
+    }
+
+    /// Download image attachments for the model.
+    #[must_use]
+    pub fn with_images(mut self, enabled: bool) -> Self {
+        self.images = enabled;
+        self
+    }
+
+    /// The image attachments of a message, downloaded, if images are on.
+    async fn message_images(&self, d: &serde_json::Value) -> Vec<ImageAttachment> {
+        if !self.images {
+            return Vec::new();
+        }
+        let mut images = Vec::new();
+        for (url, content_type) in image_attachments(d) {
+            let download = async {
+                self.client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await
+            };
+            match download.await {
+                Ok(data) => images.push(ImageAttachment::new(content_type, data.to_vec())),
+                Err(e) => tracing::warn!("Discord: attachment download failed: {e}"),
+            }
+        }
+        images
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, GatewayState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// PUT adds our reaction, DELETE takes it away again.
+    async fn reaction_request(
+        &self,
+        method: reqwest::Method,
+        message: &ChannelMessage,
+        reaction: Reaction,
+    ) -> anyhow::Result<()> {
+        // A thread started from a message shares its ID; the message itself
+        // stays in the parent channel.
+        let parent = self.state().threads.get(&message.sender).cloned();
+        let channel_id = match parent {
+            Some(parent) if message.id == message.sender => parent,
+            _ => message.sender.clone(),
+        };
+        let url = reaction_url(&channel_id, &message.id, reaction.emoji())?;
+        let resp = self
+            .client
+            .request(method, url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .header(reqwest::header::CONTENT_LENGTH, 0)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Discord reaction failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     /// Check if a Discord user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -30,68 +184,235 @@ impl DiscordChannel {
         self.allowed_users.iter().any(|u| u == "*" || u == user_id)
     }
 
-    fn bot_user_id_from_token(token: &str) -> Option<String> {
-        // Discord bot tokens are base64(bot_user_id).timestamp.hmac
-        let part = token.split('.').next()?;
-        base64_decode(part)
+    /// Whether the server and channel filters let a message through;
+    /// `guild_id` is `None` in DMs.
+    fn is_place_allowed(&self, guild_id: Option<&str>, channel_id: &str) -> bool {
+        let guild = guild_id.unwrap_or_default();
+        if self.guild_id.as_deref().is_some_and(|gid| gid != guild)
+            || (!self.allowed_guilds.is_empty() && !self.allowed_guilds.iter().any(|g| g == guild))
+        {
+            return false;
+        }
+        if self.allowed_channels.is_empty() {
+            return true;
+        }
+        let parent = self.state().threads.get(channel_id).cloned();
+        self.allowed_channels
+            .iter()
+            .any(|c| c == channel_id || Some(c) == parent.as_ref())
     }
-}
 
-const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    /// Remember threads (from `THREAD_CREATE` or a guild's active threads)
+    /// so they are filtered and answered like their parent channel.
+    fn remember_thread(&self, thread: &serde_json::Value) {
+        let field = |key: &str| thread.get(key).and_then(serde_json::Value::as_str);
+        if let (Some(id), Some(parent)) = (field("id"), field("parent_id")) {
+            self.state()
+                .threads
+                .insert(id.to_string(), parent.to_string());
+        }
+    }
 
-/// Minimal base64 decode (no extra dep) — only needs to decode the user ID portion
-#[allow(clippy::cast_possible_truncation)]
-fn base64_decode(input: &str) -> Option<String> {
-    let padded = match input.len() % 4 {
-        2 => format!("{input}=="),
-        3 => format!("{input}="),
-        _ => input.to_string(),
-    };
-
-    let mut bytes = Vec::new();
-    let chars: Vec<u8> = padded.bytes().collect();
-
-    for chunk in chars.chunks(4) {
-        if chunk.len() < 4 {
-            break;
+    /// Start a thread from a server message; its ID, or `None` if that failed
+    /// and the reply should go to the channel.
+    async fn start_thread(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> Option<String> {
+        let first_line = content.lines().next().unwrap_or_default().trim();
+        let name = if first_line.is_empty() {
+            "Conversation".to_string()
+        } else {
+            truncate_with_ellipsis(first_line, 90)
+        };
+        let resp = self
+            .client
+            .post(format!(
+                "{API}/channels/{channel_id}/messages/{message_id}/threads"
+            ))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "name": name, "auto_archive_duration": 1440 }))
+            .send()
+            .await
+            .ok()?;
+        if !resp.status().is_success() {
+            tracing::warn!("Discord: couldn't start a thread ({})", resp.status());
+            return None;
         }
+        let thread: serde_json::Value = resp.json().await.ok()?;
+        let id = thread.get("id")?.as_str()?.to_string();
+        self.state()
+            .threads
+            .insert(id.clone(), channel_id.to_string());
+        Some(id)
+    }
 
-        let mut v = [0usize; 4];
-        for (i, &b) in chunk.iter().enumerate() {
-            if b == b'=' {
-                v[i] = 0;
-            } else {
-                v[i] = BASE64_ALPHABET.iter().position(|&a| a == b)?;
+    /// Bulk-overwrite our slash commands in each allowed server, or globally
+    /// when the bot isn't limited to any (those can take an hour to appear).
+    async fn register_commands(&self, application_id: &str) {
+        let mut guilds: Vec<&String> = self.guild_id.iter().chain(&self.allowed_guilds).collect();
+        guilds.dedup();
+        let urls: Vec<String> = if guilds.is_empty() {
+            vec![format!("{API}/applications/{application_id}/commands")]
+        } else {
+            guilds
+                .iter()
+                .map(|g| format!("{API}/applications/{application_id}/guilds/{g}/commands"))
+                .collect()
+        };
+        for url in urls {
+            let resp = self
+                .client
+                .put(&url)
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .json(&slash_command_definitions())
+                .send()
+                .await;
+            match resp {
+                Ok(resp) if resp.status().is_success() => {}
+                Ok(resp) => tracing::warn!(
+                    "Discord: registering slash commands failed ({})",
+                    resp.status()
+                ),
+                Err(e) => tracing::warn!("Discord: registering slash commands failed: {e}"),
             }
         }
+    }
 
-        bytes.push(((v[0] << 2) | (v[1] >> 4)) as u8);
-        if chunk[2] != b'=' {
-            bytes.push((((v[1] & 0xF) << 4) | (v[2] >> 2)) as u8);
+    /// The interaction response for `command`, and the message to hand to
+    /// the agent, if any. `/ask` and `/reset` are deferred ("thinking…")
+    /// and answered once the reply is ready.
+    fn slash_response(&self, command: &SlashCommand) -> (serde_json::Value, Option<String>) {
+        if !self.is_user_allowed(&command.user_id)
+            || !self.is_place_allowed(command.guild_id.as_deref(), &command.channel_id)
+        {
+            return (ephemeral("You can't use this bot here."), None);
         }
-        if chunk[3] != b'=' {
-            bytes.push((((v[2] & 0x3) << 6) | v[3]) as u8);
+        let deferred = json!({ "type": 5 });
+        match command.name.as_str() {
+            "ask" if !command.prompt.trim().is_empty() => (deferred, Some(command.prompt.clone())),
+            "ask" => (ephemeral("Tell me what to ask."), None),
+            "reset" => (deferred, Some(super::RESET_COMMAND.to_string())),
+            "status" => (ephemeral(&status_text()), None),
+            other => (ephemeral(&format!("Unknown command /{other}")), None),
         }
     }
 
-    String::from_utf8(bytes).ok()
-}
+    /// Answer a slash command; `/ask` and `/reset` become an inbound message
+    /// whose reply completes the deferred response.
+    async fn handle_slash_command(&self, d: &serde_json::Value) -> Option<ChannelMessage> {
+        let command = parse_slash_command(d)?;
+        let (response, content) = self.slash_response(&command);
+        let sent = self
+            .client
+            .post(format!(
+                "{API}/interactions/{}/{}/callback",
+                command.id, command.token
+            ))
+            .json(&response)
+            .send()
+            .await;
+        match sent {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => {
+                tracing::warn!(
+                    "Discord: answering /{} failed ({})",
+                    command.name,
+                    resp.status()
+                );
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Discord: answering /{} failed: {e}", command.name);
+                return None;
+            }
+        }
+        let content = content?;
+        self.state().pending.insert(
+            command.channel_id.clone(),
+            (command.token.clone(), Instant::now()),
+        );
+        Some(ChannelMessage {
+            id: format!("{INTERACTION_PREFIX}{}", command.id),
+            sender: command.channel_id,
+            content,
+            channel: "discord".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            profile: SenderProfile::new(command.user_name.as_deref(), None, None),
+            images: Vec::new(),
+        })
+    }
 
-#[async_trait]
-impl Channel for DiscordChannel {
-    fn name(&self) -> &str {
-        "discord"
+    /// Application ID and token of the slash command awaiting an answer in
+    /// `channel_id`, taken so only the first reply answers it.
+    fn take_interaction(&self, channel_id: &str) -> Option<(String, String)> {
+        let mut state = self.state();
+        let (token, at) = state.pending.remove(channel_id)?;
+        let application_id = state.application_id.clone()?;
+        (at.elapsed().as_secs() < INTERACTION_TTL_SECS).then_some((application_id, token))
     }
 
-    async fn send(&self, message: &str, channel_id: &str) -> anyhow::Result<()> {
-        let url = format!("https://discord.com/api/v10/channels/{channel_id}/messages");
-        let body = json!({ "content": message });
+    /// Webhook URL for editing one of our slash command answers.
+    fn answer_url(&self, message_id: &str) -> Option<String> {
+        let state = self.state();
+        let (token, at) = state.answers.get(message_id)?;
+        let application_id = state.application_id.as_ref()?;
+        (at.elapsed().as_secs() < INTERACTION_TTL_SECS)
+            .then(|| format!("{API}/webhooks/{application_id}/{token}/messages/{message_id}"))
+    }
 
-        let resp = self
-            .client
-            .post(&url)
+    /// A 👍/👎 reaction on one of our replies as a feedback message for the
+    /// channel's session; other reactions, and ones from users outside the
+    /// allowlist or guild filter, yield `None`.
+    fn rating_reaction(&self, d: &serde_json::Value, bot_user_id: &str) -> Option<ChannelMessage> {
+        let field = |key: &str| d.get(key).and_then(serde_json::Value::as_str);
+        let user_id = field("user_id")?;
+        let emoji = d.get("emoji")?.get("name")?.as_str()?;
+        let on_our_reply = field("message_author_id").is_none_or(|author| author == bot_user_id);
+        if user_id == bot_user_id
+            || !on_our_reply
+            || !self.is_user_allowed(user_id)
+            || !emoji.starts_with(['👍', '👎'])
+        {
+            return None;
+        }
+        if !self.is_place_allowed(field("guild_id"), field("channel_id")?) {
+            return None;
+        }
+        Some(ChannelMessage {
+            id: format!("reaction:{}:{user_id}:{emoji}", field("message_id")?),
+            sender: field("channel_id")?.to_string(),
+            content: emoji.to_string(),
+            channel: "discord".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            profile: SenderProfile::default(),
+            images: Vec::new(),
+        })
+    }
+
+    fn bot_user_id_from_token(token: &str) -> Option<String> {
+        // Discord bot tokens are base64(bot_user_id).timestamp.hmac
+        let part = token.split('.').next()?;
+        base64_decode(part)
+    }
+
+    async fn message_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        content: &str,
+        action: &str,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = request
             .header("Authorization", format!("Bot {}", self.bot_token))
-            .json(&body)
+            .json(&message_body(content))
             .send()
             .await?;
 
@@ -101,10 +422,274 @@ impl Channel for DiscordChannel {
                 .text()
                 .await
                 .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-            anyhow::bail!("Discord send message failed ({status}): {err}");
+            anyhow::bail!("Discord {action} failed ({status}): {err}");
         }
 
-        Ok(())
+        Ok(resp.json().await.unwrap_or_default())
+    }
+}
+
+/// Discord caps a message at 10 embeds with 25 fields each.
+const MAX_EMBEDS: usize = 10;
+const MAX_EMBED_FIELDS: usize = 25;
+
+/// Endpoint for our own `emoji` reaction on a message; the emoji is
+/// percent-encoded as a path segment.
+fn reaction_url(channel_id: &str, message_id: &str, emoji: &str) -> anyhow::Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(&format!("{API}/channels"))?;
+    url.path_segments_mut()
+        .map_err(|()| anyhow::anyhow!("Discord API URL cannot take a path"))?
+        .extend([
+            channel_id,
+            "messages",
+            message_id,
+            "reactions",
+            emoji,
+            "@me",
+        ]);
+    Ok(url)
+}
+
+/// Message payload for `content`, with its tool cards as embeds.
+/// URL and type of each image attachment on a message, skipping files too
+/// large to download.
+fn image_attachments(d: &serde_json::Value) -> Vec<(&str, &str)> {
+    d.get("attachments")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|a| {
+            a.get("size")
+                .and_then(serde_json::Value::as_u64)
+                .is_none_or(|size| size <= vision::MAX_DOWNLOAD_BYTES as u64)
+        })
+        .filter_map(|a| {
+            let content_type = a.get("content_type")?.as_str()?;
+            let url = a.get("url")?.as_str()?;
+            vision::is_image(content_type).then_some((url, content_type))
+        })
+        .collect()
+}
+
+fn message_body(content: &str) -> serde_json::Value {
+    let (text, cards) = reply::extract(content);
+    // Cards past the limit still reach the user, as text.
+    let mut text = text;
+    for card in cards.iter().skip(MAX_EMBEDS) {
+        text.push_str("\n\n");
+        text.push_str(&card.to_text());
+    }
+    let embeds: Vec<serde_json::Value> = cards.iter().take(MAX_EMBEDS).map(embed).collect();
+    json!({ "content": text, "embeds": embeds })
+}
+
+fn embed(card: &StructuredReply) -> serde_json::Value {
+    let mut embed = json!({ "title": truncate_with_ellipsis(&card.title, 253) });
+    if let Some(description) = &card.description {
+        embed["description"] = json!(truncate_with_ellipsis(description, 4093));
+    }
+    if let Some(url) = &card.url {
+        embed["url"] = json!(url);
+    }
+    if let Some(color) = card.color {
+        embed["color"] = json!(color);
+    }
+    if !card.fields.is_empty() {
+        let fields: Vec<serde_json::Value> = card
+            .fields
+            .iter()
+            .take(MAX_EMBED_FIELDS)
+            .map(|field| {
+                json!({
+                    "name": truncate_with_ellipsis(&field.name, 253),
+                    "value": truncate_with_ellipsis(&field.value, 1021),
+                    "inline": field.inline
+                })
+            })
+            .collect();
+        embed["fields"] = json!(fields);
+    }
+    if let Some(footer) = &card.footer {
+        embed["footer"] = json!({ "text": truncate_with_ellipsis(footer, 2045) });
+    }
+    embed
+}
+
+/// The commands registered with Discord.
+fn slash_command_definitions() -> serde_json::Value {
+    json!([
+        {
+            "name": "ask",
+            "description": "Ask the assistant",
+            "type": 1,
+            "options": [{
+                "type": 3,
+                "name": "prompt",
+                "description": "What to ask",
+                "required": true
+            }]
+        },
+        {
+            "name": "reset",
+            "description": "Start a fresh conversation in this channel",
+            "type": 1
+        },
+        {
+            "name": "status",
+            "description": "Check that the assistant is up",
+            "type": 1
+        }
+    ])
+}
+
+/// An application command from an `INTERACTION_CREATE` payload.
+fn parse_slash_command(d: &serde_json::Value) -> Option<SlashCommand> {
+    // 2 = application command; pings, buttons and modals aren't ours
+    if d.get("type").and_then(serde_json::Value::as_u64) != Some(2) {
+        return None;
+    }
+    let field = |key: &str| d.get(key).and_then(serde_json::Value::as_str);
+    // In servers the user comes inside `member`, in DMs on its own
+    let user = d
+        .get("member")
+        .and_then(|m| m.get("user"))
+        .or_else(|| d.get("user"))?;
+    let data = d.get("data")?;
+    let prompt = data
+        .get("options")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|o| o.get("name").and_then(serde_json::Value::as_str) == Some("prompt"))
+        .and_then(|o| o.get("value")?.as_str())
+        .unwrap_or_default();
+    Some(SlashCommand {
+        id: field("id")?.to_string(),
+        token: field("token")?.to_string(),
+        guild_id: field("guild_id").map(str::to_string),
+        channel_id: field("channel_id")?.to_string(),
+        user_id: user.get("id")?.as_str()?.to_string(),
+        user_name: user
+            .get("global_name")
+            .filter(|n| n.is_string())
+            .or_else(|| user.get("username"))
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string),
+        name: data.get("name")?.as_str()?.to_string(),
+        prompt: prompt.to_string(),
+    })
+}
+
+/// An immediate answer only the user who ran the command sees.
+fn ephemeral(content: &str) -> serde_json::Value {
+    json!({ "type": 4, "data": { "content": content, "flags": 64 } })
+}
+
+/// `/status`: uptime, message counts and any failing component.
+fn status_text() -> String {
+    let health = crate::health::snapshot();
+    let (inbound, outbound) = crate::health::message_counts();
+    let failing: Vec<&str> = health
+        .components
+        .iter()
+        .filter(|(_, c)| c.status != "ok")
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let minutes = health.uptime_seconds / 60;
+    let summary = format!(
+        "up {}h {}m, {inbound} messages in, {outbound} out",
+        minutes / 60,
+        minutes % 60
+    );
+    if failing.is_empty() {
+        format!("🟢 All good: {summary}.")
+    } else {
+        format!("🟠 Trouble with {}: {summary}.", failing.join(", "))
+    }
+}
+
+/// Minimal base64 decode (no extra dep) — only needs to decode the user ID portion
+fn base64_decode(input: &str) -> Option<String> {
+    String::from_utf8(crate::util::base64_decode(input)?).ok()
+}
+
+#[async_trait]
+impl Channel for DiscordChannel {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    async fn send(&self, message: &str, channel_id: &str) -> anyhow::Result<()> {
+        self.send_editable(message, channel_id).await.map(|_| ())
+    }
+
+    fn supports_edits(&self) -> bool {
+        true
+    }
+
+    fn supports_embeds(&self) -> bool {
+        true
+    }
+
+    async fn send_editable(&self, message: &str, channel_id: &str) -> anyhow::Result<String> {
+        // The first reply after a slash command is its answer.
+        if let Some((application_id, token)) = self.take_interaction(channel_id) {
+            let url = format!("{API}/webhooks/{application_id}/{token}/messages/@original");
+            let sent = self
+                .message_request(self.client.patch(&url), message, "answer command")
+                .await?;
+            let id = sent
+                .get("id")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let mut state = self.state();
+            state
+                .answers
+                .retain(|_, (_, at)| at.elapsed().as_secs() < INTERACTION_TTL_SECS);
+            state.answers.insert(id.clone(), (token, Instant::now()));
+            return Ok(id);
+        }
+        let url = format!("{API}/channels/{channel_id}/messages");
+        let sent = self
+            .message_request(self.client.post(&url), message, "send message")
+            .await?;
+        Ok(sent
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn edit(&self, message_id: &str, message: &str, channel_id: &str) -> anyhow::Result<()> {
+        let url = self
+            .answer_url(message_id)
+            .unwrap_or_else(|| format!("{API}/channels/{channel_id}/messages/{message_id}"));
+        self.message_request(self.client.patch(&url), message, "edit message")
+            .await
+            .map(|_| ())
+    }
+
+    fn supports_reactions(&self) -> bool {
+        self.reactions
+    }
+
+    async fn react(&self, message: &ChannelMessage, reaction: Reaction) -> anyhow::Result<()> {
+        if message.id.starts_with(INTERACTION_PREFIX) {
+            return Ok(());
+        }
+        if reaction != Reaction::Processing {
+            let _ = self
+                .reaction_request(reqwest::Method::DELETE, message, Reaction::Processing)
+                .await;
+        }
+        self.reaction_request(reqwest::Method::PUT, message, reaction)
+            .await
+    }
+
+    fn edit_interval(&self) -> std::time::Duration {
+        // Discord allows roughly five message edits per channel every five seconds.
+        std::time::Duration::from_millis(1200)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -114,7 +699,7 @@ impl Channel for DiscordChannel {
         // Get Gateway URL
         let gw_resp: serde_json::Value = self
             .client
-            .get("https://discord.com/api/v10/gateway/bot")
+            .get(format!("{API}/gateway/bot"))
             .header("Authorization", format!("Bot {}", self.bot_token))
             .send()
             .await?
@@ -146,7 +731,9 @@ impl Channel for DiscordChannel {
             "op": 2,
             "d": {
                 "token": self.bot_token,
-                "intents": 33281, // GUILDS | GUILD_MESSAGES | MESSAGE_CONTENT | DIRECT_MESSAGES
+                // GUILDS | GUILD_MESSAGES | GUILD_MESSAGE_REACTIONS | DIRECT_MESSAGES
+                // | DIRECT_MESSAGE_REACTIONS | MESSAGE_CONTENT
+                "intents": 42497,
                 "properties": {
                     "os": "linux",
                     "browser": "zeroclaw",
@@ -176,8 +763,6 @@ impl Channel for DiscordChannel {
             }
         });
 
-        let guild_filter = self.guild_id.clone();
-
         loop {
             tokio::select! {
                 _ = hb_rx.recv() => {
@@ -229,15 +814,46 @@ impl Channel for DiscordChannel {
                         _ => {}
                     }
 
-                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
+                    // Opcode 0 dispatches: messages, rating reactions, slash
+                    // commands, and what they need to know about threads
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
-                    if event_type != "MESSAGE_CREATE" {
-                        continue;
-                    }
-
                     let Some(d) = event.get("d") else {
                         continue;
                     };
+                    let inbound = match event_type {
+                        "READY" => {
+                            let app_id = d.get("application").and_then(|a| a.get("id")).and_then(serde_json::Value::as_str);
+                            if let Some(app_id) = app_id {
+                                self.state().application_id = Some(app_id.to_string());
+                                if self.slash_commands {
+                                    self.register_commands(app_id).await;
+                                }
+                            }
+                            None
+                        }
+                        "GUILD_CREATE" => {
+                            for thread in d.get("threads").and_then(serde_json::Value::as_array).into_iter().flatten() {
+                                self.remember_thread(thread);
+                            }
+                            None
+                        }
+                        "THREAD_CREATE" => {
+                            self.remember_thread(d);
+                            None
+                        }
+                        "MESSAGE_REACTION_ADD" => self.rating_reaction(d, &bot_user_id),
+                        "INTERACTION_CREATE" => self.handle_slash_command(d).await,
+                        _ => None,
+                    };
+                    if let Some(inbound) = inbound {
+                        if tx.send(inbound).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    if event_type != "MESSAGE_CREATE" {
+                        continue;
+                    }
 
                     // Skip messages from the bot itself
                     let author_id = d.get("author").and_then(|a| a.get("id")).and_then(|i| i.as_str()).unwrap_or("");
@@ -256,30 +872,50 @@ impl Channel for DiscordChannel {
                         continue;
                     }
 
-                    // Guild filter
-                    if let Some(ref gid) = guild_filter {
-                        let msg_guild = d.get("guild_id").and_then(serde_json::Value::as_str).unwrap_or("");
-                        if msg_guild != gid {
-                            continue;
-                        }
+                    // Guild and channel filters
+                    let guild_id = d.get("guild_id").and_then(serde_json::Value::as_str);
+                    let mut channel_id = d.get("channel_id").and_then(|c| c.as_str()).unwrap_or("").to_string();
+                    if !self.is_place_allowed(guild_id, &channel_id) {
+                        continue;
                     }
 
+                    let images = self.message_images(d).await;
                     let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
+                    let content = if images.is_empty() {
+                        content.to_string()
+                    } else {
+                        vision::caption_or_placeholder(Some(content))
+                    };
                     if content.is_empty() {
                         continue;
                     }
 
-                    let channel_id = d.get("channel_id").and_then(|c| c.as_str()).unwrap_or("").to_string();
+                    let id = d.get("id").and_then(|i| i.as_str()).map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+
+                    // Top-level server messages get their own thread
+                    let in_thread = self.state().threads.contains_key(&channel_id);
+                    if self.thread_replies && guild_id.is_some() && !in_thread {
+                        if let Some(thread_id) = self.start_thread(&channel_id, &id, &content).await {
+                            channel_id = thread_id;
+                        }
+                    }
+
+                    let author = d.get("author");
+                    let name = author
+                        .and_then(|a| a.get("global_name").filter(|n| n.is_string()).or_else(|| a.get("username")))
+                        .and_then(serde_json::Value::as_str);
 
                     let channel_msg = ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
+                        id,
                         sender: channel_id,
-                        content: content.to_string(),
+                        content,
                         channel: "discord".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        profile: SenderProfile::new(name, None, None),
+                        images,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -294,7 +930,7 @@ impl Channel for DiscordChannel {
 
     async fn health_check(&self) -> bool {
         self.client
-            .get("https://discord.com/api/v10/users/@me")
+            .get(format!("{API}/users/@me"))
             .header("Authorization", format!("Bot {}", self.bot_token))
             .send()
             .await
@@ -307,12 +943,130 @@ impl Channel for DiscordChannel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn thumbs_on_our_replies_become_ratings() {
+        let ch = DiscordChannel::new("fake".into(), None, vec!["*".into()]);
+        let reaction = |user: &str, author: &str, emoji: &str| {
+            json!({
+                "user_id": user,
+                "message_author_id": author,
+                "channel_id": "111",
+                "message_id": "222",
+                "emoji": {"id": null, "name": emoji}
+            })
+        };
+
+        let msg = ch
+            .rating_reaction(&reaction("42", "bot", "👎"), "bot")
+            .unwrap();
+        assert_eq!(msg.sender, "111");
+        assert_eq!(msg.content, "👎");
+        assert!(ch
+            .rating_reaction(&reaction("42", "someone", "👍"), "bot")
+            .is_none());
+        assert!(ch
+            .rating_reaction(&reaction("42", "bot", "🎉"), "bot")
+            .is_none());
+        assert!(ch
+            .rating_reaction(&reaction("bot", "bot", "👍"), "bot")
+            .is_none());
+    }
+
+    #[test]
+    fn guild_and_channel_allowlists_cover_threads() {
+        let ch = DiscordChannel::new("fake".into(), None, vec!["*".into()])
+            .with_allowlists(vec!["g1".into()], vec!["c1".into()]);
+        ch.remember_thread(&json!({"id": "t1", "parent_id": "c1"}));
+
+        assert!(ch.is_place_allowed(Some("g1"), "c1"));
+        assert!(ch.is_place_allowed(Some("g1"), "t1"));
+        assert!(!ch.is_place_allowed(Some("g1"), "c2"));
+        assert!(!ch.is_place_allowed(Some("g2"), "c1"));
+        assert!(!ch.is_place_allowed(None, "dm"));
+
+        let open = DiscordChannel::new("fake".into(), Some("g1".into()), vec!["*".into()]);
+        assert!(open.is_place_allowed(Some("g1"), "anything"));
+        assert!(!open.is_place_allowed(Some("g2"), "anything"));
+    }
+
+    #[test]
+    fn slash_commands_are_parsed_and_answered() {
+        let ch = DiscordChannel::new("fake".into(), None, vec!["42".into()])
+            .with_allowlists(vec![], vec!["c1".into()]);
+        let interaction = |user: &str, channel: &str, name: &str| {
+            json!({
+                "id": "900",
+                "type": 2,
+                "token": "tok",
+                "guild_id": "g1",
+                "channel_id": channel,
+                "member": {"user": {"id": user, "username": "kim", "global_name": null}},
+                "data": {"name": name, "options": [{"name": "prompt", "type": 3, "value": "hi there"}]}
+            })
+        };
+
+        let ask = parse_slash_command(&interaction("42", "c1", "ask")).unwrap();
+        assert_eq!(ask.prompt, "hi there");
+        assert_eq!(ask.user_name.as_deref(), Some("kim"));
+        let (response, content) = ch.slash_response(&ask);
+        assert_eq!(response["type"], 5);
+        assert_eq!(content.as_deref(), Some("hi there"));
+
+        let reset = parse_slash_command(&interaction("42", "c1", "reset")).unwrap();
+        assert_eq!(ch.slash_response(&reset).1.as_deref(), Some("/reset"));
+
+        let status = parse_slash_command(&interaction("42", "c1", "status")).unwrap();
+        let (response, content) = ch.slash_response(&status);
+        assert_eq!(response["data"]["flags"], 64);
+        assert!(content.is_none());
+
+        for (user, channel) in [("7", "c1"), ("42", "c2")] {
+            let denied = parse_slash_command(&interaction(user, channel, "ask")).unwrap();
+            let (response, content) = ch.slash_response(&denied);
+            assert_eq!(response["type"], 4);
+            assert!(content.is_none());
+        }
+
+        let mut ping = interaction("42", "c1", "ask");
+        ping["type"] = json!(1);
+        assert!(parse_slash_command(&ping).is_none());
+    }
+
+    #[test]
+    fn image_attachments_are_found() {
+        let d = json!({
+            "content": "",
+            "attachments": [
+                { "url": "https://cdn.example/a.png", "content_type": "image/png", "size": 2048 },
+                { "url": "https://cdn.example/b.pdf", "content_type": "application/pdf", "size": 2048 },
+                { "url": "https://cdn.example/c.jpg", "content_type": "image/jpeg", "size": 90_000_000 }
+            ]
+        });
+        assert_eq!(
+            image_attachments(&d),
+            vec![("https://cdn.example/a.png", "image/png")]
+        );
+        assert!(image_attachments(&json!({ "content": "hi" })).is_empty());
+    }
+
     #[test]
     fn discord_channel_name() {
         let ch = DiscordChannel::new("fake".into(), None, vec![]);
         assert_eq!(ch.name(), "discord");
     }
 
+    #[test]
+    fn reaction_url_encodes_the_emoji() {
+        let url = reaction_url("111", "222", Reaction::Done.emoji()).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://discord.com/api/v10/channels/111/messages/222/reactions/%E2%9C%85/@me"
+        );
+        let ch = DiscordChannel::new("fake".into(), None, vec![]);
+        assert!(ch.supports_reactions());
+        assert!(!ch.with_reactions(false).supports_reactions());
+    }
+
     #[test]
     fn base64_decode_bot_id() {
         // "MTIzNDU2" decodes to "123456"
@@ -380,6 +1134,26 @@ mod tests {
         assert!(!ch.is_user_allowed("Abc"));
     }
 
+    #[test]
+    fn tool_cards_become_embeds() {
+        let card = StructuredReply::new("CI on main")
+            .color(reply::COLOR_FAILURE)
+            .field("github:a/one", "❌ failed", false)
+            .footer("3 repos");
+        let body = message_body(&format!("Main is red.\n{}", card.to_block()));
+        assert_eq!(body["content"], "Main is red.");
+        let embed = &body["embeds"][0];
+        assert_eq!(embed["title"], "CI on main");
+        assert_eq!(embed["color"], reply::COLOR_FAILURE);
+        assert_eq!(embed["fields"][0]["name"], "github:a/one");
+        assert_eq!(embed["fields"][0]["inline"], false);
+        assert_eq!(embed["footer"]["text"], "3 repos");
+
+        let body = message_body("just text");
+        assert_eq!(body["content"], "just text");
+        assert_eq!(body["embeds"], json!([]));
+    }
+
     #[test]
     fn base64_decode_empty_string() {
         let decoded = base64_decode("");
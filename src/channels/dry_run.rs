@@ -0,0 +1,74 @@
+use super::traits::{Channel, ChannelMessage};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Dry-run wrapper — listens on the real channel but prints replies instead of sending them.
+pub struct DryRunChannel {
+    inner: Arc<dyn Channel>,
+}
+
+impl DryRunChannel {
+    pub fn new(inner: Arc<dyn Channel>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl Channel for DryRunChannel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
+        println!(
+            "  📤 [dry-run] {} → {}:\n{message}",
+            self.name(),
+            if recipient.is_empty() {
+                "(default)"
+            } else {
+                recipient
+            }
+        );
+        Ok(())
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        self.inner.listen(tx).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Unsendable;
+
+    #[async_trait]
+    impl Channel for Unsendable {
+        fn name(&self) -> &str {
+            "slack"
+        }
+
+        async fn send(&self, _message: &str, _recipient: &str) -> anyhow::Result<()> {
+            panic!("dry run must not reach the real channel");
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn send_is_printed_not_delivered() {
+        let ch = DryRunChannel::new(Arc::new(Unsendable));
+        assert_eq!(ch.name(), "slack");
+        ch.send("hello", "C123").await.unwrap();
+    }
+}
@@ -14,9 +14,10 @@ use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::Write as IoWrite;
 use std::net::TcpStream;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
@@ -24,7 +25,7 @@ use tokio::time::{interval, sleep};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelMessage, SenderProfile};
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +58,9 @@ pub struct EmailConfig {
     /// Allowed sender addresses/domains (empty = deny all, ["*"] = allow all)
     #[serde(default)]
     pub allowed_senders: Vec<String>,
+    /// Save attachments from allowed senders under `email/attachments/` in the workspace
+    #[serde(default = "default_true")]
+    pub save_attachments: bool,
 }
 
 fn default_imap_port() -> u16 {
@@ -89,14 +93,70 @@ impl Default for EmailConfig {
             from_address: String::new(),
             poll_interval_secs: default_poll_interval(),
             allowed_senders: Vec::new(),
+            save_attachments: true,
         }
     }
 }
 
+/// A fetched email, before the allowlist check.
+#[derive(Debug, Clone)]
+pub(crate) struct InboundEmail {
+    pub id: String,
+    pub sender: String,
+    pub subject: String,
+    pub content: String,
+    pub timestamp: u64,
+    /// `References` of the incoming mail, for chaining the reply onto it
+    pub references: Vec<String>,
+    /// `(file name, bytes)` of each attachment
+    pub attachments: Vec<(String, Vec<u8>)>,
+}
+
+/// The mail a reply to a sender should thread under.
+#[derive(Debug, Clone)]
+struct Thread {
+    subject: String,
+    message_id: String,
+    references: Vec<String>,
+}
+
+/// `<id>`, whether or not `id` already has angle brackets.
+fn angle(id: &str) -> String {
+    if id.starts_with('<') {
+        id.to_string()
+    } else {
+        format!("<{id}>")
+    }
+}
+
+/// A file or directory name safe to create inside the workspace.
+fn safe_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(100)
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "attachment".into()
+    } else {
+        cleaned.to_string()
+    }
+}
+
 /// Email channel — IMAP polling for inbound, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Mutex<HashSet<String>>,
+    workspace_dir: Option<PathBuf>,
+    /// Latest mail from each sender (lowercased), so replies land in its thread
+    threads: Mutex<HashMap<String, Thread>>,
 }
 
 impl EmailChannel {
@@ -104,7 +164,31 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Mutex::new(HashSet::new()),
+            workspace_dir: None,
+            threads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Save attachments of incoming mail under this workspace.
+    pub fn with_workspace(mut self, workspace_dir: PathBuf) -> Self {
+        self.workspace_dir = Some(workspace_dir);
+        self
+    }
+
+    /// Write `email`'s attachments to `email/attachments/<message id>/` and
+    /// return their paths relative to the workspace.
+    fn save_attachments(workspace_dir: &Path, email: &InboundEmail) -> Result<Vec<String>> {
+        let relative = Path::new("email")
+            .join("attachments")
+            .join(safe_name(email.id.trim_matches(['<', '>'])));
+        std::fs::create_dir_all(workspace_dir.join(&relative))?;
+        let mut saved = Vec::new();
+        for (name, bytes) in &email.attachments {
+            let path = relative.join(safe_name(name));
+            std::fs::write(workspace_dir.join(&path), bytes)?;
+            saved.push(path.display().to_string());
         }
+        Ok(saved)
     }
 
     /// Check if a sender email is in the allowlist
@@ -146,7 +230,7 @@ impl EmailChannel {
     }
 
     /// Extract the sender address from a parsed email
-    fn extract_sender(parsed: &mail_parser::Message) -> String {
+    pub(crate) fn extract_sender(parsed: &mail_parser::Message) -> String {
         parsed
             .from()
             .and_then(|addr| addr.first())
@@ -156,7 +240,7 @@ impl EmailChannel {
     }
 
     /// Extract readable text from a parsed email
-    fn extract_text(parsed: &mail_parser::Message) -> String {
+    pub(crate) fn extract_text(parsed: &mail_parser::Message) -> String {
         if let Some(text) = parsed.body_text(0) {
             return text.to_string();
         }
@@ -177,8 +261,160 @@ impl EmailChannel {
         "(no readable content)".to_string()
     }
 
+    /// Parse a raw RFC 822 message.
+    pub(crate) fn parse_raw(raw: &[u8]) -> Option<InboundEmail> {
+        let parsed = MessageParser::default().parse(raw)?;
+        let sender = Self::extract_sender(&parsed);
+        let subject = parsed.subject().unwrap_or("(no subject)").to_string();
+        let body = Self::extract_text(&parsed);
+        let content = format!("Subject: {}\n\n{}", subject, body);
+        let msg_id = parsed
+            .message_id()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
+        #[allow(clippy::cast_sign_loss)]
+        let ts = parsed
+            .date()
+            .map(|d| {
+                let naive = chrono::NaiveDate::from_ymd_opt(
+                    d.year as i32,
+                    u32::from(d.month),
+                    u32::from(d.day),
+                )
+                .and_then(|date| {
+                    date.and_hms_opt(u32::from(d.hour), u32::from(d.minute), u32::from(d.second))
+                });
+                naive.map_or(0, |n| n.and_utc().timestamp() as u64)
+            })
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0)
+            });
+        let references = parsed
+            .references()
+            .as_text_list()
+            .map(|ids| ids.iter().map(|id| id.to_string()).collect())
+            .unwrap_or_default();
+        let attachments = parsed
+            .attachments()
+            .map(|part| {
+                let name = MimeHeaders::attachment_name(part).unwrap_or("attachment");
+                (name.to_string(), part.contents().to_vec())
+            })
+            .collect();
+        Some(InboundEmail {
+            id: msg_id,
+            sender,
+            subject,
+            content,
+            timestamp: ts,
+            references,
+            attachments,
+        })
+    }
+
     /// Fetch unseen emails via IMAP (blocking, run in spawn_blocking)
-    fn fetch_unseen_imap(config: &EmailConfig) -> Result<Vec<(String, String, String, u64)>> {
+    fn fetch_unseen_imap(config: &EmailConfig) -> Result<Vec<InboundEmail>> {
+        let mut imap = ImapSession::connect(config)?;
+
+        // Select folder
+        let _select = imap.command(&format!("SELECT \"{}\"", config.imap_folder))?;
+
+        // Search unseen
+        let search_resp = imap.command("SEARCH UNSEEN")?;
+        let uids = ImapSession::search_results(&search_resp);
+
+        let mut results = Vec::new();
+        for uid in &uids {
+            let fetch_resp = imap.command(&format!("FETCH {} RFC822", uid))?;
+            if let Some(message) = Self::parse_raw(ImapSession::literal(&fetch_resp).as_bytes()) {
+                results.push(message);
+            }
+
+            // Mark as seen
+            let _ = imap.command(&format!("STORE {uid} +FLAGS (\\Seen)"));
+        }
+
+        imap.logout();
+        Ok(results)
+    }
+
+    /// Send `body` as a reply in the thread of the message `in_reply_to`.
+    pub(crate) fn send_reply(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+    ) -> Result<()> {
+        self.send_threaded(to, subject, body, in_reply_to, &[])
+    }
+
+    /// [`Self::send_reply`], chaining `References` after the thread's earlier ids.
+    fn send_threaded(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+        in_reply_to: Option<&str>,
+        references: &[String],
+    ) -> Result<()> {
+        let subject = if subject.to_ascii_lowercase().starts_with("re:") {
+            subject.to_string()
+        } else {
+            format!("Re: {subject}")
+        };
+        let mut builder = Message::builder()
+            .from(self.config.from_address.parse()?)
+            .to(to.parse()?)
+            .subject(subject);
+        if let Some(id) = in_reply_to {
+            let id = angle(id);
+            let chain: Vec<String> = references
+                .iter()
+                .map(|r| angle(r))
+                .chain(std::iter::once(id.clone()))
+                .collect();
+            builder = builder.in_reply_to(id).references(chain.join(" "));
+        }
+        let email = builder.body(body.to_string())?;
+        self.create_smtp_transport()?.send(&email)?;
+        info!("Email reply sent to {}", to);
+        Ok(())
+    }
+
+    fn create_smtp_transport(&self) -> Result<SmtpTransport> {
+        let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
+        let transport = if self.config.smtp_tls {
+            SmtpTransport::relay(&self.config.smtp_host)?
+                .port(self.config.smtp_port)
+                .credentials(creds)
+                .build()
+        } else {
+            SmtpTransport::builder_dangerous(&self.config.smtp_host)
+                .port(self.config.smtp_port)
+                .credentials(creds)
+                .build()
+        };
+        Ok(transport)
+    }
+}
+
+type TlsStream =
+    tokio_rustls::rustls::StreamOwned<tokio_rustls::rustls::ClientConnection, TcpStream>;
+
+/// A minimal blocking IMAP client: TLS, LOGIN, and tagged commands whose
+/// response lines are returned as-is. Run it inside `spawn_blocking`.
+pub(crate) struct ImapSession {
+    tls: TlsStream,
+    tag: u32,
+}
+
+impl ImapSession {
+    /// Connect, read the greeting and log in.
+    pub(crate) fn connect(config: &EmailConfig) -> Result<Self> {
         use rustls::ClientConfig as TlsConfig;
         use rustls_pki_types::ServerName;
         use std::sync::Arc;
@@ -198,161 +434,105 @@ impl EmailChannel {
         );
         let server_name: ServerName<'_> = ServerName::try_from(config.imap_host.clone())?;
         let conn = rustls::ClientConnection::new(tls_config, server_name)?;
-        let mut tls = rustls::StreamOwned::new(conn, tcp);
-
-        let read_line =
-            |tls: &mut rustls::StreamOwned<rustls::ClientConnection, TcpStream>| -> Result<String> {
-                let mut buf = Vec::new();
-                loop {
-                    let mut byte = [0u8; 1];
-                    match std::io::Read::read(tls, &mut byte) {
-                        Ok(0) => return Err(anyhow!("IMAP connection closed")),
-                        Ok(_) => {
-                            buf.push(byte[0]);
-                            if buf.ends_with(b"\r\n") {
-                                return Ok(String::from_utf8_lossy(&buf).to_string());
-                            }
-                        }
-                        Err(e) => return Err(e.into()),
-                    }
-                }
-            };
-
-        let send_cmd = |tls: &mut rustls::StreamOwned<rustls::ClientConnection, TcpStream>,
-                        tag: &str,
-                        cmd: &str|
-         -> Result<Vec<String>> {
-            let full = format!("{} {}\r\n", tag, cmd);
-            IoWrite::write_all(tls, full.as_bytes())?;
-            IoWrite::flush(tls)?;
-            let mut lines = Vec::new();
-            loop {
-                let line = read_line(tls)?;
-                let done = line.starts_with(tag);
-                lines.push(line);
-                if done {
-                    break;
-                }
-            }
-            Ok(lines)
+        let mut session = Self {
+            tls: rustls::StreamOwned::new(conn, tcp),
+            tag: 0,
         };
 
         // Read greeting
-        let _greeting = read_line(&mut tls)?;
+        let _greeting = session.read_line()?;
 
         // Login
-        let login_resp = send_cmd(
-            &mut tls,
-            "A1",
-            &format!("LOGIN \"{}\" \"{}\"", config.username, config.password),
-        )?;
-        if !login_resp.last().map_or(false, |l| l.contains("OK")) {
+        let login_resp = session.command(&format!(
+            "LOGIN \"{}\" \"{}\"",
+            config.username, config.password
+        ))?;
+        if !Self::is_ok(&login_resp) {
             return Err(anyhow!("IMAP login failed"));
         }
+        Ok(session)
+    }
 
-        // Select folder
-        let _select = send_cmd(
-            &mut tls,
-            "A2",
-            &format!("SELECT \"{}\"", config.imap_folder),
-        )?;
-
-        // Search unseen
-        let search_resp = send_cmd(&mut tls, "A3", "SEARCH UNSEEN")?;
-        let mut uids: Vec<&str> = Vec::new();
-        for line in &search_resp {
-            if line.starts_with("* SEARCH") {
-                let parts: Vec<&str> = line.trim().split_whitespace().collect();
-                if parts.len() > 2 {
-                    uids.extend_from_slice(&parts[2..]);
+    fn read_line(&mut self) -> Result<String> {
+        let mut buf = Vec::new();
+        loop {
+            let mut byte = [0u8; 1];
+            match std::io::Read::read(&mut self.tls, &mut byte) {
+                Ok(0) => return Err(anyhow!("IMAP connection closed")),
+                Ok(_) => {
+                    buf.push(byte[0]);
+                    if buf.ends_with(b"\r\n") {
+                        return Ok(String::from_utf8_lossy(&buf).to_string());
+                    }
                 }
+                Err(e) => return Err(e.into()),
             }
         }
+    }
 
-        let mut results = Vec::new();
-        let mut tag_counter = 4_u32; // Start after A1, A2, A3
-
-        for uid in &uids {
-            // Fetch RFC822 with unique tag
-            let fetch_tag = format!("A{}", tag_counter);
-            tag_counter += 1;
-            let fetch_resp = send_cmd(&mut tls, &fetch_tag, &format!("FETCH {} RFC822", uid))?;
-            // Reconstruct the raw email from the response (skip first and last lines)
-            let raw: String = fetch_resp
-                .iter()
-                .skip(1)
-                .take(fetch_resp.len().saturating_sub(2))
-                .cloned()
-                .collect();
-
-            if let Some(parsed) = MessageParser::default().parse(raw.as_bytes()) {
-                let sender = Self::extract_sender(&parsed);
-                let subject = parsed.subject().unwrap_or("(no subject)").to_string();
-                let body = Self::extract_text(&parsed);
-                let content = format!("Subject: {}\n\n{}", subject, body);
-                let msg_id = parsed
-                    .message_id()
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
-                #[allow(clippy::cast_sign_loss)]
-                let ts = parsed
-                    .date()
-                    .map(|d| {
-                        let naive = chrono::NaiveDate::from_ymd_opt(
-                            d.year as i32,
-                            u32::from(d.month),
-                            u32::from(d.day),
-                        )
-                        .and_then(|date| {
-                            date.and_hms_opt(
-                                u32::from(d.hour),
-                                u32::from(d.minute),
-                                u32::from(d.second),
-                            )
-                        });
-                        naive.map_or(0, |n| n.and_utc().timestamp() as u64)
-                    })
-                    .unwrap_or_else(|| {
-                        SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .map(|d| d.as_secs())
-                            .unwrap_or(0)
-                    });
-
-                results.push((msg_id, sender, content, ts));
+    /// Send one command with a fresh tag; returns every line up to and
+    /// including the tagged completion.
+    pub(crate) fn command(&mut self, cmd: &str) -> Result<Vec<String>> {
+        self.tag += 1;
+        let tag = format!("A{}", self.tag);
+        let full = format!("{} {}\r\n", tag, cmd);
+        IoWrite::write_all(&mut self.tls, full.as_bytes())?;
+        IoWrite::flush(&mut self.tls)?;
+        let mut lines = Vec::new();
+        loop {
+            let line = self.read_line()?;
+            let done = line.starts_with(&format!("{tag} "));
+            lines.push(line);
+            if done {
+                break;
             }
+        }
+        Ok(lines)
+    }
 
-            // Mark as seen with unique tag
-            let store_tag = format!("A{tag_counter}");
-            tag_counter += 1;
-            let _ = send_cmd(
-                &mut tls,
-                &store_tag,
-                &format!("STORE {uid} +FLAGS (\\Seen)"),
-            );
+    /// Like [`ImapSession::command`], failing unless the server answers OK.
+    pub(crate) fn command_ok(&mut self, cmd: &str) -> Result<Vec<String>> {
+        let lines = self.command(cmd)?;
+        if Self::is_ok(&lines) {
+            Ok(lines)
+        } else {
+            let verb = cmd.split_whitespace().take(2).collect::<Vec<_>>().join(" ");
+            Err(anyhow!(
+                "IMAP {verb} failed: {}",
+                lines.last().map_or("", |l| l.trim())
+            ))
         }
+    }
 
-        // Logout with unique tag
-        let logout_tag = format!("A{tag_counter}");
-        let _ = send_cmd(&mut tls, &logout_tag, "LOGOUT");
+    pub(crate) fn is_ok(lines: &[String]) -> bool {
+        lines
+            .last()
+            .and_then(|l| l.split_whitespace().nth(1))
+            .is_some_and(|status| status.eq_ignore_ascii_case("OK"))
+    }
 
-        Ok(results)
+    /// IDs from a `* SEARCH` response.
+    pub(crate) fn search_results(lines: &[String]) -> Vec<String> {
+        lines
+            .iter()
+            .filter(|line| line.starts_with("* SEARCH"))
+            .flat_map(|line| line.split_whitespace().skip(2).map(String::from))
+            .collect()
     }
 
-    fn create_smtp_transport(&self) -> Result<SmtpTransport> {
-        let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
-        let transport = if self.config.smtp_tls {
-            SmtpTransport::relay(&self.config.smtp_host)?
-                .port(self.config.smtp_port)
-                .credentials(creds)
-                .build()
-        } else {
-            SmtpTransport::builder_dangerous(&self.config.smtp_host)
-                .port(self.config.smtp_port)
-                .credentials(creds)
-                .build()
-        };
-        Ok(transport)
+    /// The message body of a FETCH response (everything between the first
+    /// line and the closing paren + tagged line).
+    pub(crate) fn literal(lines: &[String]) -> String {
+        lines
+            .iter()
+            .skip(1)
+            .take(lines.len().saturating_sub(2))
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn logout(mut self) {
+        let _ = self.command("LOGOUT");
     }
 }
 
@@ -363,6 +543,26 @@ impl Channel for EmailChannel {
     }
 
     async fn send(&self, message: &str, recipient: &str) -> Result<()> {
+        // Answers to incoming mail go back in that mail's thread.
+        let thread = if message.starts_with("Subject: ") {
+            None
+        } else {
+            self.threads
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .get(&recipient.to_lowercase())
+                .cloned()
+        };
+        if let Some(thread) = thread {
+            return self.send_threaded(
+                recipient,
+                &thread.subject,
+                message,
+                Some(&thread.message_id),
+                &thread.references,
+            );
+        }
+
         let (subject, body) = if message.starts_with("Subject: ") {
             if let Some(pos) = message.find('\n') {
                 (&message[9..pos], message[pos + 1..].trim())
@@ -398,24 +598,50 @@ impl Channel for EmailChannel {
             let cfg = config.clone();
             match tokio::task::spawn_blocking(move || Self::fetch_unseen_imap(&cfg)).await {
                 Ok(Ok(messages)) => {
-                    for (id, sender, content, ts) in messages {
+                    for email in messages {
                         {
                             let mut seen = self.seen_messages.lock().unwrap();
-                            if seen.contains(&id) {
+                            if seen.contains(&email.id) {
                                 continue;
                             }
-                            if !self.is_sender_allowed(&sender) {
-                                warn!("Blocked email from {}", sender);
+                            if !self.is_sender_allowed(&email.sender) {
+                                warn!("Blocked email from {}", email.sender);
                                 continue;
                             }
-                            seen.insert(id.clone());
+                            seen.insert(email.id.clone());
                         } // MutexGuard dropped before await
+                        let mut content = email.content.clone();
+                        if let Some(dir) = self.workspace_dir.as_ref().filter(|_| {
+                            self.config.save_attachments && !email.attachments.is_empty()
+                        }) {
+                            match Self::save_attachments(dir, &email) {
+                                Ok(saved) => {
+                                    content.push_str("\n\n[Attachments saved in the workspace: ");
+                                    content.push_str(&saved.join(", "));
+                                    content.push(']');
+                                }
+                                Err(e) => warn!("Could not save email attachments: {}", e),
+                            }
+                        }
+                        self.threads
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .insert(
+                                email.sender.to_lowercase(),
+                                Thread {
+                                    subject: email.subject,
+                                    message_id: email.id.clone(),
+                                    references: email.references,
+                                },
+                            );
                         let msg = ChannelMessage {
-                            id,
-                            sender,
+                            id: email.id,
+                            sender: email.sender,
                             content,
                             channel: "email".to_string(),
-                            timestamp: ts,
+                            timestamp: email.timestamp,
+                            profile: SenderProfile::default(),
+                            images: Vec::new(),
                         };
                         if tx.send(msg).await.is_err() {
                             return Ok(());
@@ -444,3 +670,44 @@ impl Channel for EmailChannel {
         .unwrap_or_default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const RAW: &str = "From: Sam <sam@example.com>\r\n\
+To: bot@example.com\r\n\
+Subject: Quarterly numbers\r\n\
+Message-ID: <m2@example.com>\r\n\
+References: <m1@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"b\"\r\n\
+\r\n\
+--b\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Figures attached.\r\n\
+--b\r\n\
+Content-Type: text/csv\r\n\
+Content-Disposition: attachment; filename=\"../q3 report.csv\"\r\n\
+\r\n\
+a,b\r\n\
+--b--\r\n";
+
+    #[test]
+    fn incoming_mail_keeps_thread_ids_and_saves_attachments() {
+        let email = EmailChannel::parse_raw(RAW.as_bytes()).unwrap();
+        assert_eq!(email.id, "m2@example.com");
+        assert_eq!(email.sender, "sam@example.com");
+        assert_eq!(email.subject, "Quarterly numbers");
+        assert_eq!(email.references, ["m1@example.com"]);
+        assert!(email.content.contains("Figures attached."));
+        assert_eq!(email.attachments.len(), 1);
+
+        let tmp = TempDir::new().unwrap();
+        let saved = EmailChannel::save_attachments(tmp.path(), &email).unwrap();
+        assert_eq!(saved, ["email/attachments/m2_example.com/_q3_report.csv"]);
+        assert!(tmp.path().join(&saved[0]).is_file());
+    }
+}
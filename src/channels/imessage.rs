@@ -1,16 +1,31 @@
-use crate::channels::traits::{Channel, ChannelMessage};
+use crate::channels::traits::{Channel, ChannelMessage, SenderProfile};
+use crate::providers::ImageAttachment;
+use crate::tools::reply;
+use crate::vision;
+use crate::voice::VoiceClient;
 use async_trait::async_trait;
 use directories::UserDirs;
 use rusqlite::{Connection, OpenFlags};
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
+/// Rows read from the Messages database per poll (the queries' `LIMIT`).
+const PAGE: usize = 20;
+
 /// iMessage channel using macOS `AppleScript` bridge.
 /// Polls the Messages database for new messages and sends replies via `osascript`.
 #[derive(Clone)]
 pub struct IMessageChannel {
     allowed_contacts: Vec<String>,
     poll_interval_secs: u64,
+    voice: Option<Arc<VoiceClient>>,
+    voice_replies: bool,
+    /// Contacts whose latest message was a voice note still waiting for a reply
+    voice_chats: Arc<Mutex<HashSet<String>>>,
+    /// Read photo attachments for the model
+    images: bool,
 }
 
 impl IMessageChannel {
@@ -18,7 +33,129 @@ impl IMessageChannel {
         Self {
             allowed_contacts,
             poll_interval_secs: 3,
+            voice: None,
+            voice_replies: false,
+            voice_chats: Arc::default(),
+            images: false,
+        }
+    }
+
+    /// Transcribe voice notes with `voice`, and with `replies` answer them
+    /// with an audio file after the reply text.
+    #[must_use]
+    pub fn with_voice(mut self, voice: Option<Arc<VoiceClient>>, replies: bool) -> Self {
+        self.voice = voice;
+        self.voice_replies = replies;
+        self
+    }
+
+    /// Read photo attachments for the model.
+    #[must_use]
+    pub fn with_images(mut self, enabled: bool) -> Self {
+        self.images = enabled;
+        self
+    }
+
+    /// The photos attached to message `rowid`, read from Messages'
+    /// attachment folder.
+    async fn message_images(&self, db_path: &Path, rowid: i64) -> Vec<ImageAttachment> {
+        let attachments = match fetch_image_attachments(db_path, rowid).await {
+            Ok(attachments) => attachments,
+            Err(e) => {
+                tracing::debug!("iMessage: attachment query failed: {e}");
+                return Vec::new();
+            }
+        };
+        let mut images = Vec::new();
+        for (filename, mime_type) in attachments {
+            let path = PathBuf::from(shellexpand::tilde(&filename).as_ref());
+            let too_large = tokio::fs::metadata(&path)
+                .await
+                .is_ok_and(|meta| meta.len() > vision::MAX_DOWNLOAD_BYTES as u64);
+            if too_large {
+                continue;
+            }
+            match tokio::fs::read(&path).await {
+                Ok(data) => images.push(ImageAttachment::new(mime_type, data)),
+                Err(e) => tracing::warn!("iMessage: could not read {}: {e}", path.display()),
+            }
+        }
+        images
+    }
+
+    fn voice_chats(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.voice_chats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Text of the voice note at `attachment` (a `.caf` in Messages'
+    /// attachment folder), converted to WAV with `afconvert` first.
+    async fn transcribe(&self, voice: &VoiceClient, attachment: &str) -> anyhow::Result<String> {
+        let source = PathBuf::from(shellexpand::tilde(attachment).as_ref());
+        let wav =
+            std::env::temp_dir().join(format!("zeroclaw-imessage-{}.wav", uuid::Uuid::new_v4()));
+        let converted = tokio::process::Command::new("afconvert")
+            .args(["-f", "WAVE", "-d", "LEI16@16000", "-c", "1"])
+            .arg(&source)
+            .arg(&wav)
+            .output()
+            .await?;
+        if !converted.status.success() {
+            anyhow::bail!(
+                "afconvert failed: {}",
+                String::from_utf8_lossy(&converted.stderr).trim()
+            );
         }
+        let audio = tokio::fs::read(&wav).await;
+        let _ = tokio::fs::remove_file(&wav).await;
+        voice.transcribe(audio?, "voice.wav").await
+    }
+
+    /// Speak `message` and send it to `target` as an audio file.
+    async fn send_voice_reply(
+        &self,
+        voice: &VoiceClient,
+        message: &str,
+        target: &str,
+    ) -> anyhow::Result<()> {
+        let audio = voice.speak_as(message, "mp3").await?;
+        // Messages uploads the file after `send` returns, so it is left for
+        // the system to clean out of the temp directory.
+        let path =
+            std::env::temp_dir().join(format!("zeroclaw-reply-{}.mp3", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, audio).await?;
+        let file = escape_applescript(&path.display().to_string());
+        let target = escape_applescript(target);
+        run_script(&format!(
+            r#"tell application "Messages"
+    set targetService to 1st account whose service type = iMessage
+    set targetBuddy to participant "{target}" of targetService
+    send POSIX file "{file}" to targetBuddy
+end tell"#
+        ))
+        .await
+    }
+
+    async fn send_text(&self, message: &str, target: &str) -> anyhow::Result<()> {
+        // SECURITY: Escape both message AND target to prevent AppleScript injection
+        // See: CWE-78 (OS Command Injection)
+        let escaped_msg = escape_applescript(message);
+        let escaped_target = escape_applescript(target);
+
+        let script = format!(
+            r#"tell application "Messages"
+    set targetService to 1st account whose service type = iMessage
+    set targetBuddy to participant "{escaped_target}" of targetService
+    send "{escaped_msg}" to targetBuddy
+end tell"#
+        );
+
+        run_script(&script).await
+    }
+
+    fn transcriber(&self) -> Option<&VoiceClient> {
+        self.voice.as_deref().filter(|voice| voice.transcribes())
     }
 
     fn is_contact_allowed(&self, sender: &str) -> bool {
@@ -103,33 +240,36 @@ impl Channel for IMessageChannel {
             );
         }
 
-        // SECURITY: Escape both message AND target to prevent AppleScript injection
-        // See: CWE-78 (OS Command Injection)
-        let escaped_msg = escape_applescript(message);
-        let escaped_target = escape_applescript(target);
-
-        let script = format!(
-            r#"tell application "Messages"
-    set targetService to 1st account whose service type = iMessage
-    set targetBuddy to participant "{escaped_target}" of targetService
-    send "{escaped_msg}" to targetBuddy
-end tell"#
-        );
+        // A `speech` block is said instead of shown.
+        if let (Some(voice), (text, Some(speech))) = (&self.voice, reply::extract_speech(message)) {
+            if !text.is_empty() {
+                self.send_text(&text, target).await?;
+            }
+            if let Err(e) = self.send_voice_reply(voice, &speech, target).await {
+                tracing::warn!("iMessage: speech failed, sending text: {e}");
+                self.send_text(&speech, target).await?;
+            }
+            return Ok(());
+        }
 
-        let output = tokio::process::Command::new("osascript")
-            .arg("-e")
-            .arg(&script)
-            .output()
-            .await?;
+        self.send_text(message, target).await?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("iMessage send failed: {stderr}");
+        let voice = self
+            .voice
+            .as_ref()
+            .filter(|_| self.voice_chats().remove(target));
+        if let Some(voice) = voice {
+            if let Err(e) = self.send_voice_reply(voice, message, target).await {
+                tracing::warn!("iMessage: voice reply failed: {e}");
+            }
         }
-
         Ok(())
     }
 
+    fn supports_speech(&self) -> bool {
+        self.voice.is_some()
+    }
+
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         tracing::info!("iMessage channel listening (AppleScript bridge)...");
 
@@ -152,11 +292,25 @@ end tell"#
         loop {
             tokio::time::sleep(tokio::time::Duration::from_secs(self.poll_interval_secs)).await;
 
-            let new_messages = fetch_new_messages(&db_path, last_rowid).await;
+            let new_messages = match fetch_new_messages(&db_path, last_rowid).await {
+                Ok(messages) => {
+                    let notes = match self.transcriber() {
+                        Some(_) => fetch_voice_notes(&db_path, last_rowid)
+                            .await
+                            .unwrap_or_else(|e| {
+                                tracing::debug!("iMessage: voice note query failed: {e}");
+                                Vec::new()
+                            }),
+                        None => Vec::new(),
+                    };
+                    Ok(merge_voice_notes(messages, notes))
+                }
+                Err(e) => Err(e),
+            };
 
             match new_messages {
                 Ok(messages) => {
-                    for (rowid, sender, text) in messages {
+                    for (rowid, (sender, incoming)) in messages {
                         if rowid > last_rowid {
                             last_rowid = rowid;
                         }
@@ -165,19 +319,54 @@ end tell"#
                             continue;
                         }
 
-                        if text.trim().is_empty() {
+                        let text = match (incoming, self.transcriber()) {
+                            (Incoming::Text(text), _) => text,
+                            (Incoming::Voice(attachment), Some(voice)) => {
+                                match self.transcribe(voice, &attachment).await {
+                                    Ok(text) => {
+                                        if self.voice_replies && !text.is_empty() {
+                                            self.voice_chats().insert(sender.clone());
+                                        }
+                                        text
+                                    }
+                                    Err(e) => {
+                                        tracing::warn!(
+                                            "iMessage: could not transcribe voice note: {e}"
+                                        );
+                                        continue;
+                                    }
+                                }
+                            }
+                            (Incoming::Voice(_), None) => continue,
+                        };
+
+                        // U+FFFC stands in for an attachment in the text column.
+                        let images = if self.images && text.contains('\u{fffc}') {
+                            self.message_images(&db_path, rowid).await
+                        } else {
+                            Vec::new()
+                        };
+                        let caption = text.replace('\u{fffc}', "");
+                        if caption.trim().is_empty() && images.is_empty() {
                             continue;
                         }
+                        let content = if images.is_empty() {
+                            text
+                        } else {
+                            vision::caption_or_placeholder(Some(&caption))
+                        };
 
                         let msg = ChannelMessage {
                             id: rowid.to_string(),
                             sender: sender.clone(),
-                            content: text,
+                            content,
                             channel: "imessage".to_string(),
                             timestamp: std::time::SystemTime::now()
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap_or_default()
                                 .as_secs(),
+                            profile: SenderProfile::default(),
+                            images,
                         };
 
                         if tx.send(msg).await.is_err() {
@@ -205,6 +394,117 @@ end tell"#
     }
 }
 
+async fn run_script(script: &str) -> anyhow::Result<()> {
+    let output = tokio::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("iMessage send failed: {stderr}");
+    }
+
+    Ok(())
+}
+
+/// What a new row in the Messages database carries.
+#[derive(Debug, PartialEq, Eq)]
+enum Incoming {
+    Text(String),
+    /// Path of the voice note's audio file
+    Voice(String),
+}
+
+/// New text messages and voice notes by ROWID, sender first. A voice note
+/// replaces its row's placeholder text. When either page came back full,
+/// rows past its end wait for the next poll so none is skipped.
+fn merge_voice_notes(
+    messages: Vec<(i64, String, String)>,
+    notes: Vec<(i64, String, String)>,
+) -> BTreeMap<i64, (String, Incoming)> {
+    let horizon = [&messages, &notes]
+        .into_iter()
+        .filter(|page| page.len() >= PAGE)
+        .filter_map(|page| page.last().map(|row| row.0))
+        .min()
+        .unwrap_or(i64::MAX);
+    let mut merged = BTreeMap::new();
+    for (rowid, sender, text) in messages {
+        merged.insert(rowid, (sender, Incoming::Text(text)));
+    }
+    for (rowid, sender, attachment) in notes {
+        merged.insert(rowid, (sender, Incoming::Voice(attachment)));
+    }
+    merged.retain(|rowid, _| *rowid <= horizon);
+    merged
+}
+
+/// Voice notes newer than `since_rowid`, with their audio file path.
+async fn fetch_voice_notes(
+    db_path: &Path,
+    since_rowid: i64,
+) -> anyhow::Result<Vec<(i64, String, String)>> {
+    let path = db_path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<(i64, String, String)>> {
+        let conn = Connection::open_with_flags(
+            &path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        let mut stmt = conn.prepare(
+            "SELECT m.ROWID, h.id, a.filename \
+             FROM message m \
+             JOIN handle h ON m.handle_id = h.ROWID \
+             JOIN message_attachment_join j ON j.message_id = m.ROWID \
+             JOIN attachment a ON a.ROWID = j.attachment_id \
+             WHERE m.ROWID > ?1 \
+             AND m.is_from_me = 0 \
+             AND m.is_audio_message = 1 \
+             AND a.filename IS NOT NULL \
+             ORDER BY m.ROWID ASC \
+             LIMIT 20",
+        )?;
+        let rows = stmt.query_map([since_rowid], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })
+    .await?
+}
+
+/// Image attachments of message `rowid`: file path and type.
+async fn fetch_image_attachments(
+    db_path: &Path,
+    rowid: i64,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let path = db_path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<(String, String)>> {
+        let conn = Connection::open_with_flags(
+            &path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        let mut stmt = conn.prepare(
+            "SELECT a.filename, a.mime_type \
+             FROM message_attachment_join j \
+             JOIN attachment a ON a.ROWID = j.attachment_id \
+             WHERE j.message_id = ?1 \
+             AND a.filename IS NOT NULL \
+             AND a.mime_type LIKE 'image/%' \
+             ORDER BY a.ROWID ASC",
+        )?;
+        let rows = stmt.query_map([rowid], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })
+    .await?
+}
+
 /// Get the current max ROWID from the messages table.
 /// Uses rusqlite with parameterized queries for security (CWE-89 prevention).
 async fn get_max_rowid(db_path: &Path) -> anyhow::Result<i64> {
@@ -563,6 +863,67 @@ mod tests {
         (dir, db_path)
     }
 
+    #[tokio::test]
+    async fn voice_notes_replace_their_placeholder_text() {
+        let (_dir, db_path) = create_test_db();
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "ALTER TABLE message ADD COLUMN is_audio_message INTEGER DEFAULT 0;
+            CREATE TABLE attachment (ROWID INTEGER PRIMARY KEY, filename TEXT);
+            CREATE TABLE message_attachment_join (message_id INTEGER, attachment_id INTEGER);
+            INSERT INTO handle (ROWID, id) VALUES (1, '+1234567890');
+            INSERT INTO message (ROWID, handle_id, text) VALUES (1, 1, 'Hi');
+            INSERT INTO message (ROWID, handle_id, text, is_audio_message) VALUES (2, 1, '\u{fffc}', 1);
+            INSERT INTO attachment (ROWID, filename) VALUES (7, '~/Library/Messages/Attachments/a/Audio Message.caf');
+            INSERT INTO message_attachment_join VALUES (2, 7);",
+        )
+        .unwrap();
+
+        let messages = fetch_new_messages(&db_path, 0).await.unwrap();
+        let notes = fetch_voice_notes(&db_path, 0).await.unwrap();
+        let merged = merge_voice_notes(messages, notes);
+        assert_eq!(merged[&1].1, Incoming::Text("Hi".into()));
+        assert_eq!(
+            merged[&2].1,
+            Incoming::Voice("~/Library/Messages/Attachments/a/Audio Message.caf".into())
+        );
+
+        // A full page of text holds back later voice notes until next poll.
+        let page: Vec<_> = (1..=20)
+            .map(|i| (i, "+1".to_string(), "t".to_string()))
+            .collect();
+        let late = vec![(25, "+1".to_string(), "x.caf".to_string())];
+        assert_eq!(merge_voice_notes(page, late).len(), 20);
+    }
+
+    #[tokio::test]
+    async fn image_attachments_are_found_by_message() {
+        let (_dir, db_path) = create_test_db();
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE attachment (ROWID INTEGER PRIMARY KEY, filename TEXT, mime_type TEXT);
+            CREATE TABLE message_attachment_join (message_id INTEGER, attachment_id INTEGER);
+            INSERT INTO attachment VALUES (1, '~/Library/Messages/Attachments/a/IMG_1.HEIC', 'image/heic');
+            INSERT INTO attachment VALUES (2, '~/Library/Messages/Attachments/b/Audio Message.caf', 'audio/x-caf');
+            INSERT INTO attachment VALUES (3, '~/Library/Messages/Attachments/c/IMG_2.jpeg', 'image/jpeg');
+            INSERT INTO message_attachment_join VALUES (5, 1), (5, 2), (6, 3);",
+        )
+        .unwrap();
+
+        let images = fetch_image_attachments(&db_path, 5).await.unwrap();
+        assert_eq!(
+            images,
+            vec![(
+                "~/Library/Messages/Attachments/a/IMG_1.HEIC".to_string(),
+                "image/heic".to_string()
+            )]
+        );
+        assert!(fetch_image_attachments(&db_path, 9)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn get_max_rowid_empty_database() {
         let (_dir, db_path) = create_test_db();
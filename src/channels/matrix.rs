@@ -1,20 +1,37 @@
-use crate::channels::traits::{Channel, ChannelMessage};
+use crate::channels::traits::{Channel, ChannelMessage, SenderProfile};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
 /// Matrix channel using the Client-Server API (no SDK needed).
 /// Connects to any Matrix homeserver (Element, Synapse, etc.).
+///
+/// Messages are answered in the room they came from, as a reply to the
+/// message (inside its thread when it was sent in one).
 #[derive(Clone)]
 pub struct MatrixChannel {
     homeserver: String,
     access_token: String,
     room_id: String,
     allowed_users: Vec<String>,
+    /// Rooms answered in besides `room_id`
+    rooms: Vec<String>,
+    /// Last inbound message per room, which the next send replies to
+    reply_to: Arc<Mutex<HashMap<String, ReplyTarget>>>,
     client: Client,
 }
 
+/// The message a reply relates to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReplyTarget {
+    event_id: String,
+    /// Root of the thread the message was posted in, if any
+    thread_root: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct SyncResponse {
     next_batch: String,
@@ -46,6 +63,8 @@ struct TimelineEvent {
     event_type: String,
     sender: String,
     #[serde(default)]
+    event_id: String,
+    #[serde(default)]
     content: EventContent,
 }
 
@@ -55,6 +74,16 @@ struct EventContent {
     body: Option<String>,
     #[serde(default)]
     msgtype: Option<String>,
+    #[serde(default, rename = "m.relates_to")]
+    relates_to: Option<RelatesTo>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RelatesTo {
+    #[serde(default)]
+    rel_type: Option<String>,
+    #[serde(default)]
+    event_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -79,7 +108,28 @@ impl MatrixChannel {
             access_token,
             room_id,
             allowed_users,
-            client: Client::new(),
+            rooms: Vec::new(),
+            reply_to: Arc::new(Mutex::new(HashMap::new())),
+            client: crate::util::shared_client().clone(),
+        }
+    }
+
+    /// Also answer in `rooms` (the bot must already be joined to them).
+    pub fn with_rooms(mut self, rooms: Vec<String>) -> Self {
+        self.rooms = rooms;
+        self
+    }
+
+    fn is_room_allowed(&self, room: &str) -> bool {
+        room == self.room_id || self.rooms.iter().any(|r| r == room)
+    }
+
+    /// The room a send goes to: the recipient when it is one of ours.
+    fn target_room<'a>(&'a self, recipient: &'a str) -> &'a str {
+        if self.is_room_allowed(recipient) {
+            recipient
+        } else {
+            &self.room_id
         }
     }
 
@@ -117,17 +167,26 @@ impl Channel for MatrixChannel {
         "matrix"
     }
 
-    async fn send(&self, message: &str, _target: &str) -> anyhow::Result<()> {
-        let txn_id = format!("zc_{}", chrono::Utc::now().timestamp_millis());
+    async fn send(&self, message: &str, target: &str) -> anyhow::Result<()> {
+        let room = self.target_room(target);
+        let txn_id = format!("zc_{}", uuid::Uuid::new_v4().simple());
         let url = format!(
             "{}/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
-            self.homeserver, self.room_id, txn_id
+            self.homeserver, room, txn_id
         );
 
-        let body = serde_json::json!({
+        let reply_to = self
+            .reply_to
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(room);
+        let mut body = serde_json::json!({
             "msgtype": "m.text",
             "body": message
         });
+        if let Some(target) = reply_to {
+            body["m.relates_to"] = reply_relation(&target);
+        }
 
         let resp = self
             .client
@@ -146,7 +205,10 @@ impl Channel for MatrixChannel {
     }
 
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
-        tracing::info!("Matrix channel listening on room {}...", self.room_id);
+        tracing::info!(
+            "Matrix channel listening on {} room(s)...",
+            1 + self.rooms.len()
+        );
 
         let my_user_id = self.get_my_user_id().await?;
 
@@ -202,8 +264,11 @@ impl Channel for MatrixChannel {
             let sync: SyncResponse = resp.json().await?;
             since = sync.next_batch;
 
-            // Process events from our room
-            if let Some(room) = sync.rooms.join.get(&self.room_id) {
+            // Process events from our rooms
+            for (room_id, room) in &sync.rooms.join {
+                if !self.is_room_allowed(room_id) {
+                    continue;
+                }
                 for event in &room.timeline.events {
                     // Skip our own messages
                     if event.sender == my_user_id {
@@ -227,15 +292,27 @@ impl Channel for MatrixChannel {
                         continue;
                     }
 
+                    let id = if event.event_id.is_empty() {
+                        format!("mx_{}", chrono::Utc::now().timestamp_millis())
+                    } else {
+                        self.reply_to
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner)
+                            .insert(room_id.clone(), reply_target(event));
+                        event.event_id.clone()
+                    };
+
                     let msg = ChannelMessage {
-                        id: format!("mx_{}", chrono::Utc::now().timestamp_millis()),
-                        sender: event.sender.clone(),
+                        id,
+                        sender: room_id.clone(),
                         content: body.clone(),
                         channel: "matrix".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        profile: SenderProfile::default(),
+                        images: Vec::new(),
                     };
 
                     if tx.send(msg).await.is_err() {
@@ -262,6 +339,34 @@ impl Channel for MatrixChannel {
     }
 }
 
+fn reply_target(event: &TimelineEvent) -> ReplyTarget {
+    let thread_root = event
+        .content
+        .relates_to
+        .as_ref()
+        .filter(|r| r.rel_type.as_deref() == Some("m.thread"))
+        .and_then(|r| r.event_id.clone());
+    ReplyTarget {
+        event_id: event.event_id.clone(),
+        thread_root,
+    }
+}
+
+/// `m.relates_to` for a reply: a threaded reply inside a thread, otherwise
+/// a plain rich reply.
+fn reply_relation(target: &ReplyTarget) -> serde_json::Value {
+    let in_reply_to = serde_json::json!({ "event_id": target.event_id });
+    match &target.thread_root {
+        Some(root) => serde_json::json!({
+            "rel_type": "m.thread",
+            "event_id": root,
+            "is_falling_back": false,
+            "m.in_reply_to": in_reply_to,
+        }),
+        None => serde_json::json!({ "m.in_reply_to": in_reply_to }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -464,4 +569,35 @@ mod tests {
         let resp: SyncResponse = serde_json::from_str(json).unwrap();
         assert!(resp.rooms.join.is_empty());
     }
+
+    #[test]
+    fn replies_go_to_allowed_rooms_only() {
+        let ch = make_channel().with_rooms(vec!["!ops:matrix.org".into()]);
+        assert!(ch.is_room_allowed("!room:matrix.org"));
+        assert!(ch.is_room_allowed("!ops:matrix.org"));
+        assert!(!ch.is_room_allowed("!other:matrix.org"));
+        assert_eq!(ch.target_room("!ops:matrix.org"), "!ops:matrix.org");
+        assert_eq!(ch.target_room("@user:matrix.org"), "!room:matrix.org");
+    }
+
+    #[test]
+    fn replies_thread_under_the_message_they_answer() {
+        let json = r#"{"type":"m.room.message","sender":"@u:m","event_id":"$reply","content":{
+            "msgtype":"m.text","body":"and tomorrow?",
+            "m.relates_to":{"rel_type":"m.thread","event_id":"$root"}}}"#;
+        let event: TimelineEvent = serde_json::from_str(json).unwrap();
+        let threaded = reply_relation(&reply_target(&event));
+        assert_eq!(threaded["rel_type"], "m.thread");
+        assert_eq!(threaded["event_id"], "$root");
+        assert_eq!(threaded["m.in_reply_to"]["event_id"], "$reply");
+
+        let plain = reply_relation(&ReplyTarget {
+            event_id: "$msg".into(),
+            thread_root: None,
+        });
+        assert_eq!(
+            plain,
+            serde_json::json!({"m.in_reply_to": {"event_id": "$msg"}})
+        );
+    }
 }
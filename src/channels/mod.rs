@@ -1,36 +1,59 @@
 pub mod cli;
+pub mod desktop;
 pub mod discord;
+pub mod dry_run;
 pub mod email_channel;
 pub mod imessage;
 pub mod irc;
 pub mod matrix;
+pub mod plugin;
+pub mod push;
+pub mod signal;
 pub mod slack;
+mod streaming;
+mod style;
 pub mod telegram;
 pub mod traits;
+pub mod webhook;
 pub mod whatsapp;
 
 pub use cli::CliChannel;
+pub use desktop::DesktopChannel;
 pub use discord::DiscordChannel;
+pub use dry_run::DryRunChannel;
 pub use email_channel::EmailChannel;
 pub use imessage::IMessageChannel;
 pub use irc::IrcChannel;
 pub use matrix::MatrixChannel;
+pub use plugin::{register_channel, registered_channels};
+pub use push::PushChannel;
+pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
 pub use traits::Channel;
+pub use webhook::WebhookChannel;
 pub use whatsapp::WhatsAppChannel;
 
+use crate::agent::context::ContextBudget;
+use crate::agent::{session_key, SessionExecutor};
 use crate::config::Config;
+use crate::conversations::ConversationStore;
+use crate::experiments::{Arm, Experiment, Route, TurnUsage};
 use crate::memory::{self, Memory};
+use crate::observability::{ObserverEvent, ObserverMetric};
 use crate::providers::{self, Provider};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use std::sync::Arc;
 use std::time::Duration;
+use traits::Reaction;
 
 /// Maximum characters per injected workspace file (matches `OpenClaw` default).
 const BOOTSTRAP_MAX_CHARS: usize = 20_000;
 
+/// Archives the chat's conversation so the next message starts fresh.
+pub(crate) const RESET_COMMAND: &str = "/reset";
+
 const DEFAULT_CHANNEL_INITIAL_BACKOFF_SECS: u64 = 2;
 const DEFAULT_CHANNEL_MAX_BACKOFF_SECS: u64 = 60;
 
@@ -103,6 +126,13 @@ pub fn build_system_prompt(
         for (name, desc) in tools {
             let _ = writeln!(prompt, "- **{name}**: {desc}");
         }
+        prompt.push_str(
+            "\nWhen a tool result ends in an ```embed or ```speech block, copy the block \
+             unchanged into your reply; an embed is shown to the user as a card and speech is \
+             sent as a voice message. When the user should pick one of a \
+             few answers, end your reply with a ```choices block listing one option per line; \
+             they are shown as buttons and the picked option comes back as their message.\n",
+        );
         prompt.push('\n');
     }
 
@@ -240,12 +270,15 @@ fn inject_workspace_file(prompt: &mut String, workspace_dir: &std::path::Path, f
 
 pub fn handle_command(command: crate::ChannelCommands, config: &Config) -> Result<()> {
     match command {
-        crate::ChannelCommands::Start => {
+        crate::ChannelCommands::Start { .. } => {
             anyhow::bail!("Start must be handled in main.rs (requires async runtime)")
         }
         crate::ChannelCommands::Doctor => {
             anyhow::bail!("Doctor must be handled in main.rs (requires async runtime)")
         }
+        crate::ChannelCommands::Pair { .. } => {
+            anyhow::bail!("Pair must be handled in main.rs (blocks while waiting)")
+        }
         crate::ChannelCommands::List => {
             println!("Channels:");
             println!("  ✅ CLI (always available)");
@@ -259,6 +292,8 @@ pub fn handle_command(command: crate::ChannelCommands, config: &Config) -> Resul
                 ("WhatsApp", config.channels_config.whatsapp.is_some()),
                 ("Email", config.channels_config.email.is_some()),
                 ("IRC", config.channels_config.irc.is_some()),
+                ("Signal", config.channels_config.signal.is_some()),
+                ("Push", config.channels_config.push.is_some()),
             ] {
                 println!("  {} {name}", if configured { "✅" } else { "❌" });
             }
@@ -298,80 +333,126 @@ fn classify_health_result(
     }
 }
 
-/// Run health checks for configured channels.
-pub async fn doctor_channels(config: Config) -> Result<()> {
-    let mut channels: Vec<(&'static str, Arc<dyn Channel>)> = Vec::new();
+/// Instantiate every channel with a config section, paired with a display label.
+#[allow(clippy::too_many_lines)]
+pub fn configured_channels(config: &Config) -> Vec<(String, Arc<dyn Channel>)> {
+    let mut channels: Vec<(String, Arc<dyn Channel>)> = Vec::new();
 
     if let Some(ref tg) = config.channels_config.telegram {
         channels.push((
-            "Telegram",
-            Arc::new(TelegramChannel::new(
-                tg.bot_token.clone(),
-                tg.allowed_users.clone(),
-            )),
+            "Telegram".into(),
+            Arc::new(
+                TelegramChannel::new(tg.bot_token.clone(), tg.allowed_users.clone())
+                    .with_voice(
+                        crate::voice::VoiceClient::for_config(&config.voice).map(Arc::new),
+                        tg.voice_replies,
+                    )
+                    .with_images(config.vision.enabled),
+            ),
         ));
     }
 
     if let Some(ref dc) = config.channels_config.discord {
         channels.push((
-            "Discord",
-            Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-            )),
+            "Discord".into(),
+            Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                )
+                .with_reactions(dc.reactions)
+                .with_allowlists(dc.allowed_guilds.clone(), dc.allowed_channels.clone())
+                .with_slash_commands(dc.slash_commands)
+                .with_thread_replies(dc.thread_replies)
+                .with_images(config.vision.enabled),
+            ),
         ));
     }
 
     if let Some(ref sl) = config.channels_config.slack {
         channels.push((
-            "Slack",
-            Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            "Slack".into(),
+            Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_reactions(sl.reactions),
+            ),
         ));
     }
 
+    if let Some(ref wh) = config.channels_config.webhook {
+        // Inbound webhooks live in the gateway; only outbound targets make a channel.
+        if !wh.targets.is_empty() {
+            channels.push((
+                "Webhook".into(),
+                Arc::new(WebhookChannel::new(wh.targets.clone())),
+            ));
+        }
+    }
+
     if let Some(ref im) = config.channels_config.imessage {
         channels.push((
-            "iMessage",
-            Arc::new(IMessageChannel::new(im.allowed_contacts.clone())),
+            "iMessage".into(),
+            Arc::new(
+                IMessageChannel::new(im.allowed_contacts.clone())
+                    .with_voice(
+                        crate::voice::VoiceClient::for_config(&config.voice).map(Arc::new),
+                        im.voice_replies,
+                    )
+                    .with_images(config.vision.enabled),
+            ),
         ));
     }
 
     if let Some(ref mx) = config.channels_config.matrix {
         channels.push((
-            "Matrix",
-            Arc::new(MatrixChannel::new(
-                mx.homeserver.clone(),
-                mx.access_token.clone(),
-                mx.room_id.clone(),
-                mx.allowed_users.clone(),
-            )),
+            "Matrix".into(),
+            Arc::new(
+                MatrixChannel::new(
+                    mx.homeserver.clone(),
+                    mx.access_token.clone(),
+                    mx.room_id.clone(),
+                    mx.allowed_users.clone(),
+                )
+                .with_rooms(mx.rooms.clone()),
+            ),
         ));
     }
 
     if let Some(ref wa) = config.channels_config.whatsapp {
         channels.push((
-            "WhatsApp",
-            Arc::new(WhatsAppChannel::new(
-                wa.access_token.clone(),
-                wa.phone_number_id.clone(),
-                wa.verify_token.clone(),
-                wa.allowed_numbers.clone(),
-            )),
+            "WhatsApp".into(),
+            Arc::new(
+                WhatsAppChannel::new(
+                    wa.access_token.clone(),
+                    wa.phone_number_id.clone(),
+                    wa.verify_token.clone(),
+                    wa.allowed_numbers.clone(),
+                )
+                .with_voice(
+                    crate::voice::VoiceClient::for_config(&config.voice).map(Arc::new),
+                    wa.voice_replies,
+                ),
+            ),
         ));
     }
 
     if let Some(ref email_cfg) = config.channels_config.email {
-        channels.push(("Email", Arc::new(EmailChannel::new(email_cfg.clone()))));
+        channels.push((
+            "Email".into(),
+            Arc::new(
+                EmailChannel::new(email_cfg.clone()).with_workspace(config.workspace_dir.clone()),
+            ),
+        ));
     }
 
     if let Some(ref irc) = config.channels_config.irc {
         channels.push((
-            "IRC",
+            "IRC".into(),
             Arc::new(IrcChannel::new(
                 irc.server.clone(),
                 irc.port,
@@ -387,6 +468,72 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
         ));
     }
 
+    if let Some(ref sg) = config.channels_config.signal {
+        channels.push((
+            "Signal".into(),
+            Arc::new(SignalChannel::new(
+                sg.http_url.clone(),
+                sg.account.clone(),
+                sg.allowed_numbers.clone(),
+                sg.groups.clone(),
+            )),
+        ));
+    }
+
+    if let Some(ref push) = config.channels_config.push {
+        channels.push(("Push".into(), Arc::new(PushChannel::new(push.clone()))));
+    }
+
+    plugin::merge_registered(&mut channels);
+    channels
+}
+
+/// Look up a configured channel by its `Channel::name()` (e.g. "slack", "telegram").
+/// `desktop` (a notification on this machine) is always there.
+pub fn find_channel(config: &Config, name: &str) -> Option<Arc<dyn Channel>> {
+    configured_channels(config)
+        .into_iter()
+        .map(|(_, channel)| channel)
+        .find(|channel| channel.name().eq_ignore_ascii_case(name.trim()))
+        .or_else(|| {
+            // Needs no config, so it is only looked up, never listened on.
+            name.trim()
+                .eq_ignore_ascii_case("desktop")
+                .then(|| Arc::new(DesktopChannel::new()) as Arc<dyn Channel>)
+        })
+}
+
+/// Send now, parking the message in the DLQ when the channel is missing or
+/// delivery fails.
+pub(crate) async fn send_notification(
+    config: &Config,
+    source: &str,
+    channel_name: &str,
+    recipient: &str,
+    message: &str,
+) {
+    let Some(channel) = find_channel(config, channel_name) else {
+        tracing::warn!("{source} message not sent: channel '{channel_name}' is not configured");
+        let _ = crate::dlq::push(
+            config,
+            channel_name,
+            recipient,
+            message,
+            "channel not configured",
+        );
+        return;
+    };
+    if let Err(e) =
+        crate::dlq::send_or_dead_letter(config, channel.as_ref(), message, recipient).await
+    {
+        tracing::warn!("Failed to send {source} message: {e}");
+    }
+}
+
+/// Run health checks for configured channels.
+pub async fn doctor_channels(config: Config) -> Result<()> {
+    let channels = configured_channels(&config);
+
     if channels.is_empty() {
         println!("No real-time channels configured. Run `zeroclaw onboard` first.");
         return Ok(());
@@ -420,7 +567,7 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     }
 
     if config.channels_config.webhook.is_some() {
-        println!("  ℹ️  Webhook   check via `zeroclaw gateway` then GET /health");
+        println!("  ℹ️  Webhook   inbound: check via `zeroclaw gateway` then GET /health");
     }
 
     println!();
@@ -430,11 +577,15 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 
 /// Start all configured channels and route messages to the agent
 #[allow(clippy::too_many_lines)]
-pub async fn start_channels(config: Config) -> Result<()> {
+pub async fn start_channels(mut config: Config, dry_run: bool) -> Result<()> {
+    if dry_run {
+        config.memory.auto_save = false;
+    }
     let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider(
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
         &config.reliability,
+        &config.providers.fallback,
     )?);
 
     // Warm up the provider connection pool (TLS handshake, DNS, HTTP/2 setup)
@@ -443,11 +594,15 @@ pub async fn start_channels(config: Config) -> Result<()> {
         tracing::warn!("Provider warmup failed (non-fatal): {e}");
     }
 
-    let model = config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
-    let temperature = config.default_temperature;
+    let model = crate::model_fit::resolve(
+        &config,
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config
+            .default_model
+            .clone()
+            .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into()),
+    )
+    .await;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory(
         &config.memory,
         &config.workspace_dir,
@@ -472,6 +627,22 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "fs_read",
+            "Read a file or a line range of it. Use when: you need exact lines before patching. Don't use when: fs_search can find the passage.",
+        ),
+        (
+            "fs_write",
+            "Create, replace or append to a file. Use when: writing a new file or rewriting most of one. Don't use when: a few lines change (use fs_patch).",
+        ),
+        (
+            "fs_list",
+            "List a directory with sizes. Use when: finding files or checking layout. Don't use when: you already know the path.",
+        ),
+        (
+            "fs_patch",
+            "Apply a unified diff to workspace files. Use when: making focused edits to existing files. Don't use when: the file does not exist yet and has no /dev/null header.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -484,6 +655,18 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "list_tasks",
+            "List tracked tasks and their progress notes. Use when: asked what is in flight, or before picking up earlier multi-step work. Don't use when: the work fits in this one reply.",
+        ),
+        (
+            "update_task",
+            "Open a task or record progress on one. Use when: starting work that spans several steps or may outlive this session; checkpoint after each step and close it when done. Don't use when: the request is a one-off answer.",
+        ),
+        (
+            "goals",
+            "Manage standing goals the heartbeat checks and acts on. Use when: the user wants something kept true over time (\"keep my inbox under 20\", \"remind me when the plants are dry\"). Don't use when: it is a one-off task or a fixed-time reminder (use cron).",
+        ),
     ];
 
     if config.browser.enabled {
@@ -507,71 +690,16 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     // Collect active channels
-    let mut channels: Vec<Arc<dyn Channel>> = Vec::new();
-
-    if let Some(ref tg) = config.channels_config.telegram {
-        channels.push(Arc::new(TelegramChannel::new(
-            tg.bot_token.clone(),
-            tg.allowed_users.clone(),
-        )));
-    }
-
-    if let Some(ref dc) = config.channels_config.discord {
-        channels.push(Arc::new(DiscordChannel::new(
-            dc.bot_token.clone(),
-            dc.guild_id.clone(),
-            dc.allowed_users.clone(),
-        )));
-    }
-
-    if let Some(ref sl) = config.channels_config.slack {
-        channels.push(Arc::new(SlackChannel::new(
-            sl.bot_token.clone(),
-            sl.channel_id.clone(),
-            sl.allowed_users.clone(),
-        )));
-    }
-
-    if let Some(ref im) = config.channels_config.imessage {
-        channels.push(Arc::new(IMessageChannel::new(im.allowed_contacts.clone())));
-    }
-
-    if let Some(ref mx) = config.channels_config.matrix {
-        channels.push(Arc::new(MatrixChannel::new(
-            mx.homeserver.clone(),
-            mx.access_token.clone(),
-            mx.room_id.clone(),
-            mx.allowed_users.clone(),
-        )));
-    }
-
-    if let Some(ref wa) = config.channels_config.whatsapp {
-        channels.push(Arc::new(WhatsAppChannel::new(
-            wa.access_token.clone(),
-            wa.phone_number_id.clone(),
-            wa.verify_token.clone(),
-            wa.allowed_numbers.clone(),
-        )));
-    }
-
-    if let Some(ref email_cfg) = config.channels_config.email {
-        channels.push(Arc::new(EmailChannel::new(email_cfg.clone())));
-    }
-
-    if let Some(ref irc) = config.channels_config.irc {
-        channels.push(Arc::new(IrcChannel::new(
-            irc.server.clone(),
-            irc.port,
-            irc.nickname.clone(),
-            irc.username.clone(),
-            irc.channels.clone(),
-            irc.allowed_users.clone(),
-            irc.server_password.clone(),
-            irc.nickserv_password.clone(),
-            irc.sasl_password.clone(),
-            irc.verify_tls.unwrap_or(true),
-        )));
-    }
+    let channels: Vec<Arc<dyn Channel>> = configured_channels(&config)
+        .into_iter()
+        .map(|(_, channel)| {
+            if dry_run {
+                Arc::new(DryRunChannel::new(channel)) as Arc<dyn Channel>
+            } else {
+                channel
+            }
+        })
+        .collect();
 
     if channels.is_empty() {
         println!("No channels configured. Run `zeroclaw onboard` to set up channels.");
@@ -579,6 +707,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     println!("🦀 ZeroClaw Channel Server");
+    if dry_run {
+        println!("  🧪 Dry run: replies are printed, not sent; memory auto-save is off");
+    }
     println!("  🤖 Model:    {model}");
     println!(
         "  🧠 Memory:   {} (auto-save: {})",
@@ -623,61 +754,460 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
     drop(tx); // Drop our copy so rx closes when all channels stop
 
-    // Process incoming messages — call the LLM and reply
+    // Process incoming messages — sessions (channel + sender) run concurrently,
+    // each one strictly in order.
+    let executor = SessionExecutor::from_config(&config.agent);
+    let cache_capacity = crate::config::profile::cache_capacity(&config.runtime.profile);
+    let conversations = if dry_run {
+        None
+    } else {
+        let store = ConversationStore::open(&config.workspace_dir)?;
+        crate::agent::loop_::report_interrupted(&store, |session| {
+            !crate::agent::sessions::is_cli_session(session)
+        });
+        Some(store)
+    };
+    let guardrails = crate::security::Guardrails::for_config(&config)?;
+    let observer = Arc::from(crate::observability::create_observer(&config.observability));
+    let experiment = if dry_run {
+        None
+    } else {
+        Experiment::for_config(&config).unwrap_or_else(|e| {
+            tracing::warn!("Experiment disabled: {e:#}");
+            None
+        })
+    };
+    let runtime = Arc::new(ChannelRuntime {
+        config,
+        channels,
+        provider,
+        mem,
+        system_prompt,
+        model,
+        conversations,
+        guardrails,
+        observer,
+        experiment,
+    });
+    // Platforms redeliver on slow or lost acks; answer each message once.
+    let seen =
+        crate::idempotency::IdempotencyCache::new(crate::idempotency::DEFAULT_TTL, cache_capacity);
     while let Some(msg) = rx.recv().await {
-        println!(
-            "  💬 [{}] from {}: {}",
-            msg.channel,
-            msg.sender,
-            truncate_with_ellipsis(&msg.content, 80)
-        );
+        if !seen.first_seen(&format!("{}:{}", msg.channel, msg.id)) {
+            tracing::debug!("Skipping redelivered {} message {}", msg.channel, msg.id);
+            continue;
+        }
+        let session = session_key(&msg.channel, &msg.sender);
+        let runtime = runtime.clone();
+        let lock_name = session.clone();
+        let accepted = executor.submit(&session, async move {
+            // Another worker process may be answering this sender already.
+            let _lock = crate::redis::lock_session(&lock_name).await;
+            process_message(&runtime, msg).await;
+        });
+        if !accepted {
+            eprintln!("  ⚠️  {session} has too many pending messages; dropped one");
+        }
+    }
+    executor.shutdown().await;
 
-        // Auto-save to memory
-        if config.memory.auto_save {
-            let _ = mem
-                .store(
-                    &format!("{}_{}", msg.channel, msg.sender),
-                    &msg.content,
-                    crate::memory::MemoryCategory::Conversation,
-                )
-                .await;
+    // Wait for all channel tasks
+    for h in handles {
+        let _ = h.await;
+    }
+
+    Ok(())
+}
+
+/// Everything a channel message needs to be answered.
+pub(crate) struct ChannelRuntime {
+    pub config: Config,
+    pub channels: Vec<Arc<dyn Channel>>,
+    pub provider: Arc<dyn Provider>,
+    pub mem: Arc<dyn Memory>,
+    pub system_prompt: String,
+    pub model: String,
+    /// Durable turn log; `None` in dry runs.
+    pub conversations: Option<ConversationStore>,
+    /// Output policies checked before each reply goes out.
+    pub guardrails: crate::security::Guardrails,
+    /// Message throughput and provider latency go here.
+    pub observer: Arc<dyn crate::observability::Observer>,
+    /// A/B experiment answering a share of messages; `None` in dry runs.
+    pub experiment: Option<Experiment>,
+}
+
+/// Handle one inbound message end to end: auto-save, ask the provider, reply on
+/// the originating channel (dead-lettering failed replies). The turn is logged
+/// as pending before the provider call so a crash leaves a trace.
+pub(crate) async fn process_message(rt: &ChannelRuntime, mut msg: traits::ChannelMessage) {
+    let config = &rt.config;
+    rt.observer.record_event(&ObserverEvent::ChannelMessage {
+        channel: msg.channel.clone(),
+        direction: "inbound".into(),
+    });
+    crate::health::count_message(true);
+    println!(
+        "  💬 [{}] from {}: {}",
+        msg.channel,
+        msg.sender,
+        truncate_with_ellipsis(&msg.content, 80)
+    );
+
+    // 👍/👎 (typed or as a reaction), `/rate` and `/note` mark the last reply.
+    if let Some(feedback) = crate::agent::feedback::parse(&msg.content) {
+        record_feedback(rt, &msg, &feedback).await;
+        return;
+    }
+
+    // `/reset` starts a fresh conversation; earlier turns are archived, not deleted.
+    if msg.content.trim() == RESET_COMMAND {
+        let session = session_key(&msg.channel, &msg.sender);
+        let reply = match rt
+            .conversations
+            .as_ref()
+            .map(|s| s.archive_session(&session))
+        {
+            Some(Err(e)) => format!("⚠️ Couldn't reset the conversation: {e}"),
+            _ => "🧹 Started a fresh conversation.".to_string(),
+        };
+        if let Some(ch) = rt.channels.iter().find(|ch| ch.name() == msg.channel) {
+            let _ = ch.send(&reply, &msg.sender).await;
         }
+        return;
+    }
 
-        // Call the LLM with system prompt (identity + soul + tools)
-        match provider
-            .chat_with_system(Some(&system_prompt), &msg.content, &model, temperature)
+    // `/focus 2h`, `/focus off` and `/focus` start, end and show a focus session.
+    if let Some(arg) = crate::focus::parse_command(&msg.content) {
+        let reply = crate::focus::apply(config, arg)
             .await
-        {
-            Ok(response) => {
-                println!("  🤖 Reply: {}", truncate_with_ellipsis(&response, 80));
-                // Find the channel that sent this message and reply
-                for ch in &channels {
-                    if ch.name() == msg.channel {
-                        if let Err(e) = ch.send(&response, &msg.sender).await {
-                            eprintln!("  ❌ Failed to reply on {}: {e}", ch.name());
+            .unwrap_or_else(|e| format!("⚠️ {e}"));
+        if let Some(ch) = rt.channels.iter().find(|ch| ch.name() == msg.channel) {
+            let _ = ch.send(&reply, &msg.sender).await;
+        }
+        return;
+    }
+
+    // `/run <workflow> key=value` runs a workspace workflow and reports back.
+    if let Some(result) = crate::workflows::run_command(config, &msg.content).await {
+        let reply = result.unwrap_or_else(|e| format!("⚠️ {e:#}"));
+        if let Some(ch) = rt.channels.iter().find(|ch| ch.name() == msg.channel) {
+            let _ = ch.send(&reply, &msg.sender).await;
+        }
+        return;
+    }
+
+    // `/run <template> key=value` expands a workspace prompt template.
+    match crate::prompts::expand_command(config, &msg.content) {
+        Some(Ok(rendered)) => msg.content = rendered,
+        Some(Err(e)) => {
+            if let Some(ch) = rt.channels.iter().find(|ch| ch.name() == msg.channel) {
+                let _ = ch.send(&format!("⚠️ {e}"), &msg.sender).await;
+            }
+            return;
+        }
+        None => {}
+    }
+
+    let session = session_key(&msg.channel, &msg.sender);
+    let turn = rt.conversations.as_ref().and_then(|store| {
+        store
+            .begin_turn(&session, &msg.content)
+            .map_err(|e| tracing::warn!("Failed to persist conversation turn: {e}"))
+            .ok()
+    });
+
+    // Known household members get their own slice of memory.
+    let person = crate::household::identify(&config.household, &msg.channel, &msg.sender);
+    if let Some(person) = person {
+        msg.profile = person.profile().or(std::mem::take(&mut msg.profile));
+    }
+    let mem: Arc<dyn Memory> = match person {
+        Some(person) => Arc::new(crate::household::ScopedMemory::new(rt.mem.clone(), person)),
+        None => rt.mem.clone(),
+    };
+
+    // Auto-save to memory
+    if config.memory.auto_save {
+        let _ = mem
+            .store(
+                &format!("{}_{}", msg.channel, msg.sender),
+                &msg.content,
+                crate::memory::MemoryCategory::Conversation,
+            )
+            .await;
+    }
+
+    let pinned: Vec<String> = person
+        .map(|p| p.context_note())
+        .into_iter()
+        .chain(crate::presence::context_note(config))
+        .collect();
+    let budget = ContextBudget::new(config.agent.context_tokens);
+    let context = crate::agent::context::gather(
+        &budget,
+        &pinned,
+        &[],
+        rt.conversations
+            .as_ref()
+            .map(|store| crate::agent::context::History::Log(store, session.as_str())),
+        Some(mem.as_ref()),
+        &msg.content,
+    )
+    .await;
+    let enriched = format!("{}{}", context.text, msg.content);
+    let arm = rt
+        .experiment
+        .as_ref()
+        .map_or(Arm::Control, Experiment::pick);
+    let route = reply_route(rt, &msg, arm);
+    let trace = (config.agent.trace_prompts && turn.is_some()).then(|| {
+        let mut trace = crate::agent::trace::PromptTrace::new(
+            route.model,
+            &route.system_prompt,
+            &budget,
+            &context,
+        );
+        trace.record_round(&enriched);
+        trace
+    });
+
+    let channel = rt.channels.iter().find(|ch| ch.name() == msg.channel);
+    acknowledge(channel, &msg, Reaction::Processing).await;
+    let started = std::time::Instant::now();
+    let (result, delivered) = generate_reply(rt, channel, &msg, &enriched, &route).await;
+    rt.observer
+        .record_metric(&ObserverMetric::RequestLatency(started.elapsed()));
+    if let Some(experiment) = &rt.experiment {
+        let mut usage = TurnUsage::default();
+        usage.add(
+            &route.system_prompt,
+            &enriched,
+            result.as_deref().unwrap_or_default(),
+        );
+        experiment.record(
+            arm,
+            route.model,
+            turn,
+            result.is_ok(),
+            started.elapsed(),
+            usage,
+        );
+    }
+    rt.observer.record_event(&ObserverEvent::AgentEnd {
+        duration: started.elapsed(),
+        tokens_used: None,
+    });
+
+    if let (Some(store), Some(id)) = (rt.conversations.as_ref(), turn) {
+        let saved = match result {
+            Ok(ref response) => store.complete_turn(id, response),
+            Err(ref e) => store.fail_turn(id, &format!("{e:#}")),
+        };
+        if let Err(e) = saved {
+            tracing::warn!("Failed to persist conversation turn: {e}");
+        }
+        if let Some(trace) = &trace {
+            crate::agent::trace::save(store, id, trace);
+        }
+    }
+
+    deliver_reply(rt, channel, &msg, result, delivered).await;
+}
+
+/// Store a rating or note from `msg` on its session's last answered turn.
+/// Typed commands get an acknowledgement; bare emoji and reactions do not.
+async fn record_feedback(
+    rt: &ChannelRuntime,
+    msg: &traits::ChannelMessage,
+    feedback: &crate::agent::feedback::Feedback,
+) {
+    let Some(store) = rt.conversations.as_ref() else {
+        return;
+    };
+    let session = session_key(&msg.channel, &msg.sender);
+    let ack = match crate::agent::feedback::apply(store, &session, feedback) {
+        Ok(ack) => ack,
+        Err(e) => {
+            tracing::warn!("Failed to record feedback for {session}: {e}");
+            return;
+        }
+    };
+    if msg.content.trim_start().starts_with('/') {
+        if let Some(ch) = rt.channels.iter().find(|ch| ch.name() == msg.channel) {
+            let _ = ch.send(&ack, &msg.sender).await;
+        }
+    }
+}
+
+/// `reply` with the blocks `channel` cannot render natively flattened to text.
+pub(crate) fn shape_reply(channel: &dyn Channel, reply: &str) -> String {
+    use crate::tools::reply;
+    let reply = if channel.supports_embeds() {
+        reply.to_string()
+    } else {
+        reply::flatten_embeds(reply)
+    };
+    let reply = if channel.supports_choices() {
+        reply
+    } else {
+        reply::flatten_choices(&reply)
+    };
+    if channel.supports_speech() {
+        reply
+    } else {
+        reply::flatten_speech(&reply)
+    }
+}
+
+/// Reply on the channel that sent `msg`, unless streaming already did, and
+/// mark `msg` with how that went.
+async fn deliver_reply(
+    rt: &ChannelRuntime,
+    channel: Option<&Arc<dyn Channel>>,
+    msg: &traits::ChannelMessage,
+    result: anyhow::Result<String>,
+    delivered: bool,
+) {
+    let reaction = match result {
+        Ok(response) => {
+            println!("  🤖 Reply: {}", truncate_with_ellipsis(&response, 80));
+            rt.observer.record_event(&ObserverEvent::ChannelMessage {
+                channel: msg.channel.clone(),
+                direction: "outbound".into(),
+            });
+            crate::health::count_message(false);
+            match channel.filter(|_| !delivered) {
+                Some(ch) => {
+                    let response = shape_reply(ch.as_ref(), &response);
+                    match crate::dlq::send_or_dead_letter(
+                        &rt.config,
+                        ch.as_ref(),
+                        &response,
+                        &msg.sender,
+                    )
+                    .await
+                    {
+                        Ok(()) => Reaction::Done,
+                        Err(e) => {
+                            eprintln!("  ❌ Failed to reply on {} (queued in DLQ): {e}", ch.name());
+                            Reaction::Failed
                         }
-                        break;
                     }
                 }
+                None => Reaction::Done,
             }
-            Err(e) => {
-                eprintln!("  ❌ LLM error: {e}");
-                for ch in &channels {
-                    if ch.name() == msg.channel {
-                        let _ = ch.send(&format!("⚠️ Error: {e}"), &msg.sender).await;
-                        break;
-                    }
-                }
+        }
+        Err(e) => {
+            eprintln!("  ❌ LLM error: {e}");
+            if let Some(ch) = channel.filter(|_| !delivered) {
+                let _ = ch.send(&format!("⚠️ Error: {e}"), &msg.sender).await;
             }
+            Reaction::Failed
+        }
+    };
+    acknowledge(channel, msg, reaction).await;
+}
+
+/// Mark `msg` with `reaction` where the channel supports it. Failures are
+/// only logged: the reply matters, the mark is a courtesy.
+async fn acknowledge(
+    channel: Option<&Arc<dyn Channel>>,
+    msg: &traits::ChannelMessage,
+    reaction: Reaction,
+) {
+    if let Some(ch) = channel.filter(|ch| ch.supports_reactions()) {
+        if let Err(e) = ch.react(msg, reaction).await {
+            tracing::debug!(channel = ch.name(), "Failed to react to message: {e}");
         }
     }
+}
 
-    // Wait for all channel tasks
-    for h in handles {
-        let _ = h.await;
+/// The system prompt for replying to `msg`: the channel's style and the
+/// sender's profile on top of the shared prompt.
+fn reply_system_prompt<'a>(
+    rt: &'a ChannelRuntime,
+    msg: &traits::ChannelMessage,
+) -> std::borrow::Cow<'a, str> {
+    let mut system_prompt =
+        style::system_prompt(&rt.system_prompt, rt.config.styles.get(&msg.channel));
+    if let Some(section) = msg.profile.prompt_section() {
+        let prompt = system_prompt.to_mut();
+        prompt.push_str("\n\n");
+        prompt.push_str(&section);
     }
+    system_prompt
+}
 
-    Ok(())
+/// Provider, model and system prompt for replying to `msg` on `arm`.
+fn reply_route<'a>(rt: &'a ChannelRuntime, msg: &traits::ChannelMessage, arm: Arm) -> Route<'a> {
+    let control = Route {
+        provider: rt.provider.as_ref(),
+        model: &rt.model,
+        system_prompt: reply_system_prompt(rt, msg),
+    };
+    match &rt.experiment {
+        Some(experiment) => experiment.route(arm, control),
+        None => control,
+    }
+}
+
+/// Call the LLM with system prompt (identity + soul + tools + sender),
+/// streaming into an edited placeholder where the channel supports it and no guardrail
+/// needs to see the full reply first. The channel's reply style is stated in
+/// the prompt and applied to the final text. The flag is `true` when the
+/// reply is already visible on the channel. Photos on the message go to
+/// the model too, without streaming.
+async fn generate_reply(
+    rt: &ChannelRuntime,
+    channel: Option<&Arc<dyn Channel>>,
+    msg: &traits::ChannelMessage,
+    enriched: &str,
+    route: &Route<'_>,
+) -> (anyhow::Result<String>, bool) {
+    let temperature = rt.config.default_temperature;
+    let reply_style = rt.config.styles.get(&msg.channel);
+    let images = crate::vision::prepare(&msg.images, &rt.config.vision).await;
+    if let Some(ch) = channel.filter(|ch| {
+        images.is_empty() && ch.streams_to(&msg.sender) && !rt.guardrails.is_active(&msg.channel)
+    }) {
+        let streamed = streaming::stream_reply(ch.as_ref(), &msg.sender, |deltas| async move {
+            route
+                .provider
+                .chat_with_system_streaming(
+                    Some(&route.system_prompt),
+                    enriched,
+                    route.model,
+                    temperature,
+                    &deltas,
+                )
+                .await
+                .map(|reply| style::apply(reply_style, &reply))
+        })
+        .await;
+        return (streamed.result, streamed.delivered);
+    }
+
+    let result = route
+        .provider
+        .chat_with_images(
+            Some(&route.system_prompt),
+            enriched,
+            &images,
+            route.model,
+            temperature,
+        )
+        .await;
+    match result {
+        Ok(response) => {
+            let response = style::apply(reply_style, &response);
+            (
+                Ok(rt.guardrails.check(&msg.channel, &response).await.text),
+                false,
+            )
+        }
+        Err(e) => (Err(e), false),
+    }
 }
 
 #[cfg(test)]
@@ -850,6 +1380,7 @@ mod tests {
             tags: vec![],
             tools: vec![],
             prompts: vec!["Long prompt content that should NOT appear in system prompt".into()],
+            permissions: vec![],
             location: None,
         }];
 
@@ -908,6 +1439,134 @@ mod tests {
         assert!(prompt.contains(&format!("Working directory: `{}`", ws.path().display())));
     }
 
+    #[test]
+    fn sender_profile_prompt_section() {
+        assert_eq!(traits::SenderProfile::default().prompt_section(), None);
+        let profile = traits::SenderProfile::new(Some("  "), None, Some("Asia/Tokyo"))
+            .or(traits::SenderProfile::new(Some("Kenji"), Some("ja"), None));
+        let section = profile.prompt_section().unwrap();
+        assert!(section.starts_with("## Sender\n\n- Name: Kenji\n"));
+        assert!(section.contains("- Language: ja. Reply in this language"));
+        assert!(section.contains("- Timezone: Asia/Tokyo."));
+    }
+
+    #[tokio::test]
+    async fn messages_are_acknowledged_with_reactions() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let channel = Arc::new(crate::testing::MemoryChannel::new("test"));
+        let provider = Arc::new(crate::testing::MockProvider::new());
+        provider.push_reply("Hello");
+        provider.push_error("upstream 503");
+        let rt = ChannelRuntime {
+            channels: vec![channel.clone() as Arc<dyn Channel>],
+            provider: provider.clone(),
+            mem: Arc::from(memory::create_memory(&config.memory, tmp.path(), None).unwrap()),
+            system_prompt: String::new(),
+            model: "mock".into(),
+            conversations: None,
+            guardrails: crate::security::Guardrails::for_config(&config).unwrap(),
+            observer: Arc::new(crate::observability::NoopObserver),
+            experiment: None,
+            config,
+        };
+
+        let ok = channel.inbound("alice", "hi");
+        let failed = channel.inbound("alice", "again");
+        process_message(&rt, ok.clone()).await;
+        process_message(&rt, failed.clone()).await;
+        assert_eq!(
+            channel.reactions(),
+            vec![(ok.id, Reaction::Done), (failed.id, Reaction::Failed)]
+        );
+    }
+
+    #[tokio::test]
+    async fn thumbs_rate_the_last_reply_without_calling_the_model() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let channel = Arc::new(crate::testing::MemoryChannel::new("test"));
+        let provider = Arc::new(crate::testing::MockProvider::new());
+        provider.push_reply("Hello");
+        let rt = ChannelRuntime {
+            channels: vec![channel.clone() as Arc<dyn Channel>],
+            provider: provider.clone(),
+            mem: Arc::from(memory::create_memory(&config.memory, tmp.path(), None).unwrap()),
+            system_prompt: String::new(),
+            model: "mock".into(),
+            conversations: Some(ConversationStore::open(tmp.path()).unwrap()),
+            guardrails: crate::security::Guardrails::for_config(&config).unwrap(),
+            observer: Arc::new(crate::observability::NoopObserver),
+            experiment: None,
+            config,
+        };
+
+        process_message(&rt, channel.inbound("alice", "hi")).await;
+        process_message(&rt, channel.inbound("alice", "👎")).await;
+        process_message(&rt, channel.inbound("alice", "/note too terse")).await;
+
+        assert_eq!(provider.calls().len(), 1);
+        let sent = channel.sent();
+        assert_eq!(sent.len(), 2);
+        assert!(sent[1].content.starts_with("📝"));
+        let store = rt.conversations.as_ref().unwrap();
+        let rated = store.annotated_turns(Some("test:alice"), true).unwrap();
+        assert_eq!(rated[0].rating, Some(crate::conversations::Rating::Down));
+        assert_eq!(rated[0].note.as_deref(), Some("too terse"));
+    }
+
+    #[tokio::test]
+    async fn experiment_candidate_answers_and_is_logged_with_its_rating() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.experiment = crate::config::ExperimentConfig {
+            enabled: true,
+            name: "terse".into(),
+            share_percent: 100,
+            model: Some("small".into()),
+            persona: Some("Answer in one sentence.".into()),
+            ..crate::config::ExperimentConfig::default()
+        };
+        let channel = Arc::new(crate::testing::MemoryChannel::new("test"));
+        let provider = Arc::new(crate::testing::MockProvider::with_replies(["Hello"]));
+        let rt = ChannelRuntime {
+            channels: vec![channel.clone() as Arc<dyn Channel>],
+            provider: provider.clone(),
+            mem: Arc::from(memory::create_memory(&config.memory, tmp.path(), None).unwrap()),
+            system_prompt: "You are ZeroClaw.".into(),
+            model: "mock".into(),
+            conversations: Some(ConversationStore::open(tmp.path()).unwrap()),
+            guardrails: crate::security::Guardrails::for_config(&config).unwrap(),
+            observer: Arc::new(crate::observability::NoopObserver),
+            experiment: Experiment::for_config(&config).unwrap(),
+            config,
+        };
+
+        process_message(&rt, channel.inbound("alice", "hi")).await;
+        process_message(&rt, channel.inbound("alice", "👍")).await;
+
+        let calls = provider.calls();
+        assert_eq!(calls[0].model, "small");
+        assert!(calls[0]
+            .system_prompt
+            .as_deref()
+            .unwrap()
+            .ends_with("Answer in one sentence."));
+        let report = crate::experiments::report(&rt.config, None).unwrap();
+        assert!(report.contains("'terse'"));
+        let rate = report.lines().find(|l| l.contains("👍 rate")).unwrap();
+        assert!(rate.ends_with("100%"));
+    }
+
     #[test]
     fn classify_health_ok_true() {
         let state = classify_health_result(&Ok(true));
@@ -0,0 +1,106 @@
+use super::traits::Channel;
+use crate::registry::{OnCollision, PluginInfo, PluginKind, PluginSource, Registered, Registry};
+use std::sync::{Arc, Mutex, OnceLock};
+
+static CHANNELS: OnceLock<Mutex<Registry<Arc<dyn Channel>>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry<Arc<dyn Channel>>> {
+    CHANNELS.get_or_init(|| Mutex::new(Registry::new()))
+}
+
+/// Register a channel alongside the configured ones. A name that matches a
+/// configured channel (e.g. "slack") only takes over with `Replace`.
+pub fn register_channel(
+    channel: Arc<dyn Channel>,
+    source: PluginSource,
+    on_collision: OnCollision,
+) -> anyhow::Result<Registered> {
+    let info = PluginInfo::new(PluginKind::Channel, channel.name(), source).with_capabilities([
+        "send",
+        "listen",
+        "health_check",
+    ]);
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .register(info, channel, on_collision)
+}
+
+pub fn unregister_channel(name: &str) -> bool {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(name)
+        .is_some()
+}
+
+/// Metadata for every runtime-registered channel.
+pub fn registered_channels() -> Vec<PluginInfo> {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .infos()
+        .cloned()
+        .collect()
+}
+
+pub(crate) fn merge_registered(channels: &mut Vec<(String, Arc<dyn Channel>)>) {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .merge_into(
+            channels,
+            |(_, channel)| channel.name(),
+            |channel| (channel.name().to_string(), channel.clone()),
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use async_trait::async_trait;
+
+    struct Named(&'static str);
+
+    #[async_trait]
+    impl Channel for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn send(&self, _message: &str, _recipient: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn registered_channel_is_found_by_name() {
+        let name = "plugin_test_pager";
+        register_channel(
+            Arc::new(Named(name)),
+            PluginSource::Wasm("pager.wasm".into()),
+            OnCollision::Reject,
+        )
+        .unwrap();
+
+        let config = crate::config::Config::default();
+        let found = crate::channels::find_channel(&config, name).unwrap();
+        assert_eq!(found.name(), name);
+        let info = registered_channels()
+            .into_iter()
+            .find(|i| i.name == name)
+            .unwrap();
+        assert_eq!(info.kind, PluginKind::Channel);
+
+        assert!(unregister_channel(name));
+        assert!(crate::channels::find_channel(&config, name).is_none());
+    }
+}
@@ -0,0 +1,193 @@
+use super::traits::{Channel, ChannelMessage};
+use crate::config::PushConfig;
+use async_trait::async_trait;
+use serde_json::json;
+use std::time::Duration;
+
+const NTFY_SERVER: &str = "https://ntfy.sh";
+/// ntfy turns longer messages into attachments; Gotify has no hard limit
+/// but a notification is the wrong place for more.
+const MAX_MESSAGE_CHARS: usize = 4_000;
+const TITLE: &str = "ZeroClaw";
+
+/// Push notification channel — delivers proactive messages (briefs, alerts,
+/// workflow results) to a phone through ntfy or Gotify, no chat app needed.
+///
+/// Send-only: nothing is read back, so `listen` idles until shutdown.
+pub struct PushChannel {
+    config: PushConfig,
+    client: crate::util::LazyClient,
+}
+
+/// Where a notification goes and what it carries.
+#[derive(Debug)]
+struct Publish {
+    url: String,
+    body: serde_json::Value,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl PushChannel {
+    pub fn new(config: PushConfig) -> Self {
+        Self {
+            config,
+            client: crate::util::LazyClient::new(Duration::from_secs(15)),
+        }
+    }
+
+    fn is_gotify(&self) -> bool {
+        self.config.service.trim().eq_ignore_ascii_case("gotify")
+    }
+
+    fn server(&self) -> String {
+        let server = self
+            .config
+            .server_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(NTFY_SERVER);
+        server.trim_end_matches('/').to_string()
+    }
+
+    /// The request for `message`; `recipient` overrides the ntfy topic.
+    fn publish(&self, message: &str, recipient: &str) -> anyhow::Result<Publish> {
+        let message = crate::util::truncate_with_ellipsis(message.trim(), MAX_MESSAGE_CHARS);
+        let token = self
+            .config
+            .token
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty());
+        if self.is_gotify() {
+            if self.config.server_url.is_none() {
+                anyhow::bail!("Gotify needs [channels_config.push].server_url");
+            }
+            let token = token.ok_or_else(|| {
+                anyhow::anyhow!("Gotify needs an application token in [channels_config.push].token")
+            })?;
+            return Ok(Publish {
+                url: format!("{}/message", self.server()),
+                body: json!({
+                    "title": TITLE,
+                    "message": message,
+                    "priority": self.config.priority.unwrap_or(5),
+                    "extras": {"client::display": {"contentType": "text/markdown"}}
+                }),
+                headers: vec![("X-Gotify-Key", token.to_string())],
+            });
+        }
+        let topic = match recipient.trim() {
+            "" | "*" => self.config.topic.trim(),
+            other => other,
+        };
+        if topic.is_empty() {
+            anyhow::bail!("ntfy needs a topic in [channels_config.push].topic");
+        }
+        let mut body = json!({
+            "topic": topic,
+            "title": TITLE,
+            "message": message,
+            "markdown": true
+        });
+        if let Some(priority) = self.config.priority {
+            body["priority"] = json!(priority.clamp(1, 5));
+        }
+        Ok(Publish {
+            url: self.server(),
+            body,
+            headers: token
+                .map(|t| ("Authorization", format!("Bearer {t}")))
+                .into_iter()
+                .collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl Channel for PushChannel {
+    fn name(&self) -> &str {
+        "push"
+    }
+
+    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
+        let publish = self.publish(message, recipient)?;
+        let mut req = self.client.post(&publish.url).json(&publish.body);
+        for (key, value) in &publish.headers {
+            req = req.header(*key, value);
+        }
+        let resp = req.send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Push via {} failed ({status}): {}",
+                self.config.service,
+                crate::util::truncate_with_ellipsis(&text, 200)
+            );
+        }
+        Ok(())
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tx.closed().await;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        let path = if self.is_gotify() {
+            "/health"
+        } else {
+            "/v1/health"
+        };
+        self.publish("", "").is_ok()
+            && self
+                .client
+                .get(format!("{}{path}", self.server()))
+                .send()
+                .await
+                .is_ok_and(|r| r.status().is_success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(service: &str) -> PushConfig {
+        PushConfig {
+            service: service.into(),
+            server_url: None,
+            topic: "zc-alerts-7f3k".into(),
+            token: None,
+            priority: None,
+        }
+    }
+
+    #[test]
+    fn ntfy_publishes_json_to_the_topic() {
+        let ch = PushChannel::new(config("ntfy"));
+        let publish = ch.publish("Build **failed**", "").unwrap();
+        assert_eq!(publish.url, "https://ntfy.sh");
+        assert_eq!(publish.body["topic"], "zc-alerts-7f3k");
+        assert_eq!(publish.body["markdown"], true);
+        assert!(publish.headers.is_empty());
+        assert_eq!(
+            ch.publish("hi", "other-topic").unwrap().body["topic"],
+            "other-topic"
+        );
+    }
+
+    #[test]
+    fn gotify_needs_a_server_and_app_token() {
+        let mut cfg = config("gotify");
+        assert!(PushChannel::new(cfg.clone()).publish("hi", "").is_err());
+        cfg.server_url = Some("https://push.example.com/".into());
+        assert!(PushChannel::new(cfg.clone()).publish("hi", "").is_err());
+        cfg.token = Some("A1b2".into());
+        let publish = PushChannel::new(cfg).publish("hi", "").unwrap();
+        assert_eq!(publish.url, "https://push.example.com/message");
+        assert_eq!(publish.headers, vec![("X-Gotify-Key", "A1b2".to_string())]);
+        assert_eq!(publish.body["priority"], 5);
+    }
+}
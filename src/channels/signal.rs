@@ -0,0 +1,363 @@
+use crate::channels::traits::{Channel, ChannelMessage, SenderProfile};
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Recipient prefix for group chats; anything else is a phone number.
+const GROUP_PREFIX: &str = "group:";
+
+/// Signal channel through signal-cli's JSON-RPC daemon
+/// (`signal-cli -a <account> daemon --http 127.0.0.1:8686`).
+///
+/// Messages arrive on the daemon's event stream and are sent with its
+/// `send` method. Direct messages are answered to the sender's number,
+/// group messages in the group (`group:<id>` as the recipient).
+pub struct SignalChannel {
+    http_url: String,
+    account: String,
+    allowed_numbers: Vec<String>,
+    /// Group IDs answered in; `*` for all, empty for direct messages only
+    groups: Vec<String>,
+    client: LazyClient,
+}
+
+#[derive(Debug, Deserialize)]
+struct Event {
+    envelope: Envelope,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    #[serde(default)]
+    source_number: Option<String>,
+    #[serde(default)]
+    source_uuid: Option<String>,
+    #[serde(default)]
+    source_name: Option<String>,
+    #[serde(default)]
+    timestamp: u64,
+    #[serde(default)]
+    data_message: Option<DataMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataMessage {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    group_info: Option<GroupInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupInfo {
+    group_id: String,
+}
+
+impl SignalChannel {
+    pub fn new(
+        mut http_url: String,
+        account: String,
+        allowed_numbers: Vec<String>,
+        groups: Vec<String>,
+    ) -> Self {
+        http_url.truncate(http_url.trim_end_matches('/').len());
+        Self {
+            http_url,
+            account,
+            allowed_numbers,
+            groups,
+            client: LazyClient::new(Duration::from_secs(30)),
+        }
+    }
+
+    /// Check if a number or account UUID is allowed (`*` allows everyone)
+    fn is_sender_allowed(&self, sender: &str) -> bool {
+        self.allowed_numbers
+            .iter()
+            .any(|n| n == "*" || n.eq_ignore_ascii_case(sender))
+    }
+
+    fn is_group_allowed(&self, group_id: &str) -> bool {
+        self.groups.iter().any(|g| g == "*" || g == group_id)
+    }
+
+    /// The message in `event`, if it is one to answer.
+    fn parse_event(&self, event: &Event) -> Option<ChannelMessage> {
+        let envelope = &event.envelope;
+        let data = envelope.data_message.as_ref()?;
+        let content = data.message.as_deref().map(str::trim)?;
+        if content.is_empty() {
+            return None;
+        }
+        let from = envelope
+            .source_number
+            .as_deref()
+            .or(envelope.source_uuid.as_deref())?;
+        if from == self.account {
+            return None;
+        }
+        let number_allowed = envelope
+            .source_number
+            .as_deref()
+            .is_some_and(|n| self.is_sender_allowed(n));
+        let uuid_allowed = envelope
+            .source_uuid
+            .as_deref()
+            .is_some_and(|u| self.is_sender_allowed(u));
+        if !number_allowed && !uuid_allowed {
+            tracing::warn!("Signal: ignoring message from unauthorized sender {from}");
+            return None;
+        }
+        let sender = match &data.group_info {
+            Some(group) if self.is_group_allowed(&group.group_id) => {
+                format!("{GROUP_PREFIX}{}", group.group_id)
+            }
+            Some(_) => return None,
+            None => from.to_string(),
+        };
+        Some(ChannelMessage {
+            id: format!("{from}_{}", envelope.timestamp),
+            sender,
+            content: content.to_string(),
+            channel: "signal".to_string(),
+            timestamp: envelope.timestamp / 1000,
+            profile: SenderProfile::new(envelope.source_name.as_deref(), None, None),
+            images: Vec::new(),
+        })
+    }
+
+    /// JSON-RPC params for sending `message` to `recipient`.
+    fn send_params(&self, message: &str, recipient: &str) -> Value {
+        let mut params = json!({ "account": self.account, "message": message });
+        match recipient.strip_prefix(GROUP_PREFIX) {
+            Some(group_id) => params["groupId"] = json!(group_id),
+            None => params["recipient"] = json!([recipient]),
+        }
+        params
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> anyhow::Result<Value> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": uuid::Uuid::new_v4().to_string(),
+        });
+        let resp = self
+            .client
+            .post(format!("{}/api/v1/rpc", self.http_url))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Signal {method} failed ({status}): {err}");
+        }
+        let reply: Value = resp.json().await?;
+        if let Some(error) = reply.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("Signal {method} failed: {message}");
+        }
+        Ok(reply.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Read the event stream until it ends; every complete event goes to `tx`.
+    async fn stream_events(&self, tx: &mpsc::Sender<ChannelMessage>) -> anyhow::Result<bool> {
+        // No request timeout: the stream stays open for as long as the daemon runs.
+        let mut resp = crate::util::shared_client()
+            .get(format!("{}/api/v1/events", self.http_url))
+            .query(&[("account", &self.account)])
+            .header("Accept", "text/event-stream")
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Signal event stream failed ({})", resp.status());
+        }
+        let mut buffer = Vec::new();
+        while let Some(chunk) = resp.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            for data in drain_events(&mut buffer) {
+                let Ok(event) = serde_json::from_str::<Event>(&data) else {
+                    continue;
+                };
+                if let Some(msg) = self.parse_event(&event) {
+                    if tx.send(msg).await.is_err() {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Take the complete server-sent events off the front of `buffer` and
+/// return their `data`; a partial event stays for the next chunk.
+fn drain_events(buffer: &mut Vec<u8>) -> Vec<String> {
+    let mut events = Vec::new();
+    while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+        let raw: Vec<u8> = buffer.drain(..end + 2).collect();
+        let raw = String::from_utf8_lossy(&raw);
+        let data: Vec<&str> = raw
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(str::trim_start)
+            .collect();
+        if !data.is_empty() {
+            events.push(data.join("\n"));
+        }
+    }
+    events
+}
+
+#[async_trait]
+impl Channel for SignalChannel {
+    fn name(&self) -> &str {
+        "signal"
+    }
+
+    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
+        self.rpc("send", self.send_params(message, recipient))
+            .await
+            .map(|_| ())
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tracing::info!("Signal channel listening as {}...", self.account);
+        loop {
+            match self.stream_events(&tx).await {
+                Ok(false) => return Ok(()),
+                Ok(true) => tracing::warn!("Signal event stream ended, reconnecting..."),
+                Err(e) => tracing::warn!("Signal event stream error: {e}, retrying..."),
+            }
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        self.client
+            .get(format!("{}/api/v1/check", self.http_url))
+            .send()
+            .await
+            .is_ok_and(|r| r.status().is_success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_channel() -> SignalChannel {
+        SignalChannel::new(
+            "http://127.0.0.1:8686/".into(),
+            "+15550000000".into(),
+            vec!["+15551234567".into()],
+            vec!["Zm9vYmFy".into()],
+        )
+    }
+
+    fn event(json: &str) -> Event {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn direct_messages_from_allowed_numbers_are_received() {
+        let ch = make_channel();
+        assert_eq!(ch.http_url, "http://127.0.0.1:8686");
+        let msg = ch
+            .parse_event(&event(
+                r#"{"envelope":{"sourceNumber":"+15551234567","sourceUuid":"abc",
+                "sourceName":"Alice","timestamp":1700000000123,
+                "dataMessage":{"timestamp":1700000000123,"message":" hi "}},"account":"+15550000000"}"#,
+            ))
+            .unwrap();
+        assert_eq!(msg.sender, "+15551234567");
+        assert_eq!(msg.content, "hi");
+        assert_eq!(msg.id, "+15551234567_1700000000123");
+        assert_eq!(msg.timestamp, 1_700_000_000);
+        assert_eq!(msg.profile.display_name.as_deref(), Some("Alice"));
+
+        let stranger = r#"{"envelope":{"sourceNumber":"+15559999999","timestamp":1,
+            "dataMessage":{"message":"hi"}}}"#;
+        assert!(ch.parse_event(&event(stranger)).is_none());
+        let receipt = r#"{"envelope":{"sourceNumber":"+15551234567","timestamp":1,
+            "receiptMessage":{"isRead":true}}}"#;
+        assert!(ch.parse_event(&event(receipt)).is_none());
+    }
+
+    #[test]
+    fn group_messages_are_answered_in_allowed_groups() {
+        let ch = make_channel();
+        let in_group = |id: &str| {
+            event(&format!(
+                r#"{{"envelope":{{"sourceNumber":"+15551234567","timestamp":1,
+                "dataMessage":{{"message":"hi","groupInfo":{{"groupId":"{id}","type":"DELIVER"}}}}}}}}"#
+            ))
+        };
+        let msg = ch.parse_event(&in_group("Zm9vYmFy")).unwrap();
+        assert_eq!(msg.sender, "group:Zm9vYmFy");
+        assert!(ch.parse_event(&in_group("b3RoZXI=")).is_none());
+
+        assert_eq!(
+            ch.send_params("yo", "group:Zm9vYmFy"),
+            json!({"account": "+15550000000", "message": "yo", "groupId": "Zm9vYmFy"})
+        );
+        assert_eq!(
+            ch.send_params("yo", "+15551234567"),
+            json!({"account": "+15550000000", "message": "yo", "recipient": ["+15551234567"]})
+        );
+    }
+
+    #[test]
+    fn wildcard_and_uuid_senders_are_allowed() {
+        let ch = SignalChannel::new(
+            "http://localhost:8686".into(),
+            "+15550000000".into(),
+            vec!["*".into()],
+            vec![],
+        );
+        assert!(ch.is_sender_allowed("+49123"));
+        assert!(!ch.is_group_allowed("Zm9vYmFy"));
+
+        let ch = SignalChannel::new(
+            "http://localhost:8686".into(),
+            "+15550000000".into(),
+            vec!["0C4E7F3A-0000-4000-8000-000000000000".into()],
+            vec!["*".into()],
+        );
+        let msg = ch
+            .parse_event(&event(
+                r#"{"envelope":{"sourceUuid":"0c4e7f3a-0000-4000-8000-000000000000","timestamp":5,
+                "dataMessage":{"message":"hidden number"}}}"#,
+            ))
+            .unwrap();
+        assert_eq!(msg.sender, "0c4e7f3a-0000-4000-8000-000000000000");
+        assert!(ch.is_group_allowed("anything"));
+    }
+
+    #[test]
+    fn server_sent_events_are_split_across_chunks() {
+        let mut buffer = b"event:receive\ndata:{\"a\":1}\n\nid:2\ndata:\"\xC3".to_vec();
+        assert_eq!(drain_events(&mut buffer), vec![r#"{"a":1}"#]);
+        assert_eq!(buffer, b"id:2\ndata:\"\xC3");
+        // The rest of a multi-byte character arrives in the next chunk.
+        buffer.extend_from_slice(b"\xA9\"\n\n:keepalive\n\n");
+        assert_eq!(drain_events(&mut buffer), vec!["\"é\""]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn name_returns_signal() {
+        assert_eq!(make_channel().name(), "signal");
+    }
+}
@@ -1,13 +1,18 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelMessage, Reaction, SenderProfile};
 use async_trait::async_trait;
-use uuid::Uuid;
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 /// Slack channel — polls conversations.history via Web API
 pub struct SlackChannel {
     bot_token: String,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    /// Acknowledge inbound messages with reactions
+    reactions: bool,
     client: reqwest::Client,
+    /// `users.info` answers, by user ID
+    profiles: Mutex<HashMap<String, SenderProfile>>,
 }
 
 impl SlackChannel {
@@ -16,10 +21,19 @@ impl SlackChannel {
             bot_token,
             channel_id,
             allowed_users,
-            client: reqwest::Client::new(),
+            reactions: true,
+            client: crate::util::shared_client().clone(),
+            profiles: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Turn acknowledgement reactions on or off.
+    #[must_use]
+    pub fn with_reactions(mut self, reactions: bool) -> Self {
+        self.reactions = reactions;
+        self
+    }
+
     /// Check if a Slack user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -44,25 +58,53 @@ impl SlackChannel {
             .and_then(|u| u.as_str())
             .map(String::from)
     }
-}
 
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
+    /// Name, locale and timezone of `user_id`, looked up once per user.
+    async fn sender_profile(&self, user_id: &str) -> SenderProfile {
+        if let Some(profile) = self
+            .profiles
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(user_id)
+        {
+            return profile.clone();
+        }
+        let resp: Option<serde_json::Value> = async {
+            self.client
+                .get("https://slack.com/api/users.info")
+                .bearer_auth(&self.bot_token)
+                .query(&[("user", user_id), ("include_locale", "true")])
+                .send()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()
+        }
+        .await;
+        let Some(user) = resp.as_ref().and_then(|r| r.get("user")) else {
+            // Not cached, so a transient failure is retried next message.
+            return SenderProfile::default();
+        };
+        let profile = profile_from_user(user);
+        self.profiles
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(user_id.to_string(), profile.clone());
+        profile
     }
 
-    async fn send(&self, message: &str, channel: &str) -> anyhow::Result<()> {
-        let body = serde_json::json!({
-            "channel": channel,
-            "text": message
-        });
-
+    /// POST to a Web API method and return the parsed body
+    async fn web_api(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
         let resp = self
             .client
-            .post("https://slack.com/api/chat.postMessage")
+            .post(format!("https://slack.com/api/{method}"))
             .bearer_auth(&self.bot_token)
-            .json(&body)
+            .json(body)
             .send()
             .await?;
 
@@ -73,7 +115,7 @@ impl Channel for SlackChannel {
             .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
 
         if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
         }
 
         // Slack returns 200 for most app-level errors; check JSON "ok" field
@@ -83,10 +125,78 @@ impl Channel for SlackChannel {
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, message: &str, channel: &str) -> anyhow::Result<()> {
+        self.send_editable(message, channel).await.map(|_| ())
+    }
+
+    fn supports_edits(&self) -> bool {
+        true
+    }
+
+    async fn send_editable(&self, message: &str, channel: &str) -> anyhow::Result<String> {
+        let body = serde_json::json!({
+            "channel": channel,
+            "text": message
+        });
+        let sent = self.web_api("chat.postMessage", &body).await?;
+        Ok(sent
+            .get("ts")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn edit(&self, message_id: &str, message: &str, channel: &str) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "channel": channel,
+            "ts": message_id,
+            "text": message
+        });
+        self.web_api("chat.update", &body).await.map(|_| ())
+    }
+
+    fn supports_reactions(&self) -> bool {
+        self.reactions
+    }
+
+    async fn react(&self, message: &ChannelMessage, reaction: Reaction) -> anyhow::Result<()> {
+        let ts = message
+            .id
+            .strip_prefix(&format!("{}_", message.sender))
+            .ok_or_else(|| anyhow::anyhow!("Not a Slack message ID: {}", message.id))?;
+        let body = |reaction: Reaction| {
+            serde_json::json!({
+                "channel": message.sender,
+                "timestamp": ts,
+                "name": reaction_name(reaction)
+            })
+        };
+        if reaction != Reaction::Processing {
+            let _ = self
+                .web_api("reactions.remove", &body(Reaction::Processing))
+                .await;
         }
+        self.web_api("reactions.add", &body(reaction))
+            .await
+            .map(|_| ())
+    }
 
-        Ok(())
+    fn edit_interval(&self) -> std::time::Duration {
+        // chat.update is a Tier 3 method (~50 calls per minute).
+        std::time::Duration::from_millis(1500)
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -159,8 +269,9 @@ impl Channel for SlackChannel {
 
                     last_ts = ts.to_string();
 
+                    let profile = self.sender_profile(user).await;
                     let channel_msg = ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
+                        id: format!("{channel_id}_{ts}"),
                         sender: channel_id.clone(),
                         content: text.to_string(),
                         channel: "slack".to_string(),
@@ -168,6 +279,8 @@ impl Channel for SlackChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        profile,
+                        images: Vec::new(),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -189,6 +302,30 @@ impl Channel for SlackChannel {
     }
 }
 
+/// Slack names reactions by emoji shortcode.
+fn reaction_name(reaction: Reaction) -> &'static str {
+    match reaction {
+        Reaction::Processing => "eyes",
+        Reaction::Done => "white_check_mark",
+        Reaction::Failed => "warning",
+    }
+}
+
+/// Sender profile from a `users.info` user object.
+fn profile_from_user(user: &serde_json::Value) -> SenderProfile {
+    let profile = user.get("profile");
+    let name = profile
+        .and_then(|p| p.get("display_name"))
+        .and_then(|n| n.as_str())
+        .filter(|n| !n.trim().is_empty())
+        .or_else(|| user.get("real_name").and_then(|n| n.as_str()));
+    SenderProfile::new(
+        name,
+        user.get("locale").and_then(|l| l.as_str()),
+        user.get("tz").and_then(|t| t.as_str()),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,4 +389,24 @@ mod tests {
         assert!(ch.is_user_allowed("U111"));
         assert!(ch.is_user_allowed("anyone"));
     }
+
+    #[test]
+    fn profile_from_user_prefers_display_name() {
+        let user = serde_json::json!({
+            "real_name": "Anna Schmidt",
+            "locale": "de-DE",
+            "tz": "Europe/Berlin",
+            "profile": {"display_name": "anna"}
+        });
+        let profile = profile_from_user(&user);
+        assert_eq!(profile.display_name.as_deref(), Some("anna"));
+        assert_eq!(profile.language.as_deref(), Some("de-DE"));
+        assert_eq!(profile.timezone.as_deref(), Some("Europe/Berlin"));
+
+        let user = serde_json::json!({"real_name": "Anna", "profile": {"display_name": ""}});
+        assert_eq!(
+            profile_from_user(&user).display_name.as_deref(),
+            Some("Anna")
+        );
+    }
 }
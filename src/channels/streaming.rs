@@ -0,0 +1,211 @@
+use super::traits::Channel;
+use crate::providers::DeltaSender;
+use crate::tools::reply;
+use std::future::Future;
+use tokio::time::Instant;
+
+const PLACEHOLDER: &str = "…";
+const CURSOR: &str = " ▌";
+
+/// Outcome of a streamed reply.
+pub(crate) struct Streamed {
+    pub result: anyhow::Result<String>,
+    /// The final text (or error) is already visible in the edited message.
+    /// When `false` the caller should deliver the outcome the normal way.
+    pub delivered: bool,
+}
+
+/// Post a placeholder on `channel`, then keep editing it with the text
+/// `generate` streams into its sender, at most once per
+/// [`Channel::edit_interval`]. The last edit carries the complete reply.
+pub(crate) async fn stream_reply<F, Fut>(
+    channel: &dyn Channel,
+    recipient: &str,
+    generate: F,
+) -> Streamed
+where
+    F: FnOnce(DeltaSender) -> Fut,
+    Fut: Future<Output = anyhow::Result<String>>,
+{
+    let placeholder = match channel.send_editable(PLACEHOLDER, recipient).await {
+        Ok(id) => Some(id),
+        Err(e) => {
+            tracing::warn!(
+                "{}: placeholder failed, replying without streaming: {e}",
+                channel.name()
+            );
+            None
+        }
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let interval = channel.edit_interval();
+    let progress = async {
+        let Some(id) = placeholder.as_deref() else {
+            // Still drain so the provider never blocks on a dead receiver.
+            while rx.recv().await.is_some() {}
+            return;
+        };
+        let mut text = String::new();
+        let mut shown = 0;
+        let mut last_edit = Instant::now();
+        while let Some(delta) = rx.recv().await {
+            text.push_str(&delta);
+            if text.len() == shown || last_edit.elapsed() < interval {
+                continue;
+            }
+            if let Err(e) = channel
+                .edit(id, &format!("{text}{CURSOR}"), recipient)
+                .await
+            {
+                tracing::debug!("{}: streaming edit failed: {e}", channel.name());
+            }
+            shown = text.len();
+            last_edit = Instant::now();
+        }
+    };
+    let (result, ()) = tokio::join!(generate(tx), progress);
+
+    let Some(id) = placeholder else {
+        return Streamed {
+            result,
+            delivered: false,
+        };
+    };
+    // An edited message can't turn into audio: the edit shows the words and
+    // the voice message follows it.
+    let (last, speech) = match &result {
+        Ok(reply) => (
+            super::shape_reply(channel, &reply::flatten_speech(reply)),
+            reply::extract_speech(reply)
+                .1
+                .filter(|_| channel.supports_speech()),
+        ),
+        Err(e) => (format!("⚠️ Error: {e}"), None),
+    };
+    let delivered = match channel.edit(&id, &last, recipient).await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("{}: final streaming edit failed: {e}", channel.name());
+            false
+        }
+    };
+    if let Some(speech) = speech.filter(|_| delivered) {
+        if let Err(e) = channel.send(&reply::speech_block(&speech), recipient).await {
+            tracing::warn!("{}: voice message failed: {e}", channel.name());
+        }
+    }
+    Streamed { result, delivered }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct EditableChannel {
+        fail_placeholder: bool,
+        log: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for EditableChannel {
+        fn name(&self) -> &str {
+            "editable"
+        }
+
+        async fn send(&self, message: &str, _recipient: &str) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push(format!("send:{message}"));
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn supports_edits(&self) -> bool {
+            true
+        }
+
+        async fn send_editable(&self, message: &str, _recipient: &str) -> anyhow::Result<String> {
+            if self.fail_placeholder {
+                anyhow::bail!("rate limited");
+            }
+            self.log.lock().unwrap().push(format!("send:{message}"));
+            Ok("m1".into())
+        }
+
+        async fn edit(&self, id: &str, message: &str, _recipient: &str) -> anyhow::Result<()> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("edit:{id}:{message}"));
+            Ok(())
+        }
+
+        fn edit_interval(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    #[tokio::test]
+    async fn placeholder_is_edited_as_text_streams_in() {
+        let ch = EditableChannel::default();
+        let streamed = stream_reply(&ch, "u", |tx| async move {
+            for part in ["Hel", "lo", ""] {
+                let _ = tx.send(part.to_string());
+                tokio::task::yield_now().await;
+            }
+            Ok("Hello".to_string())
+        })
+        .await;
+
+        assert!(streamed.delivered);
+        assert_eq!(streamed.result.unwrap(), "Hello");
+        let log = ch.log.lock().unwrap();
+        assert_eq!(log[0], "send:…");
+        assert!(log.contains(&"edit:m1:Hel ▌".to_string()));
+        assert_eq!(log.last().unwrap(), "edit:m1:Hello");
+        // The empty delta did not trigger a redundant edit.
+        assert_eq!(
+            log.iter()
+                .filter(|l| l.starts_with("edit:m1:Hello ▌"))
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_replace_the_placeholder() {
+        let ch = EditableChannel::default();
+        let streamed = stream_reply(&ch, "u", |_tx| async { anyhow::bail!("timeout") }).await;
+        assert!(streamed.delivered && streamed.result.is_err());
+        assert_eq!(
+            ch.log.lock().unwrap().last().unwrap(),
+            "edit:m1:⚠️ Error: timeout"
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_placeholder_leaves_delivery_to_the_caller() {
+        let ch = EditableChannel {
+            fail_placeholder: true,
+            ..EditableChannel::default()
+        };
+        let streamed = stream_reply(&ch, "u", |tx| async move {
+            let _ = tx.send("partial".into());
+            Ok("done".to_string())
+        })
+        .await;
+        assert!(!streamed.delivered);
+        assert_eq!(streamed.result.unwrap(), "done");
+        assert!(ch.log.lock().unwrap().is_empty());
+    }
+}
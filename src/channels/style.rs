@@ -0,0 +1,261 @@
+use crate::config::ChannelStyle;
+use regex::Regex;
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\((https?://[^)\s]+)\)").unwrap());
+static URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"https?://[^\s)>\]]+").unwrap());
+static EMPHASIS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\*\*|__|\*|`)([^*_`\n]+)(\*\*|__|\*|`)").unwrap());
+static SOURCES_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?im)^\W*sources?\W*:").unwrap());
+
+/// The channel system prompt with `style` stated at the end, or the prompt
+/// unchanged when the channel has no constraints.
+pub(crate) fn system_prompt<'a>(base: &'a str, style: Option<&ChannelStyle>) -> Cow<'a, str> {
+    let Some(style) = style else {
+        return Cow::Borrowed(base);
+    };
+    let mut rules = String::new();
+    if let Some(max) = style.max_words {
+        let _ = writeln!(rules, "- Keep every reply under {max} words.");
+    }
+    if style.plain_text {
+        rules.push_str(
+            "- Reply in plain text: no Markdown, headings, bold, code blocks or tables.\n",
+        );
+    } else if style.no_tables {
+        rules.push_str("- Never use Markdown tables; use short bullet lists instead.\n");
+    }
+    if style.require_sources {
+        rules.push_str("- End with a \"Sources:\" list of the links you relied on.\n");
+    }
+    if let Some(extra) = style.instructions.as_deref().map(str::trim) {
+        if !extra.is_empty() {
+            let _ = writeln!(rules, "- {extra}");
+        }
+    }
+    if rules.is_empty() {
+        return Cow::Borrowed(base);
+    }
+    Cow::Owned(format!("{base}\n## Reply Style\n\n{rules}"))
+}
+
+/// Enforce `style` on a generated reply, in case the model ignored the prompt.
+pub(crate) fn apply(style: Option<&ChannelStyle>, reply: &str) -> String {
+    let Some(style) = style else {
+        return reply.to_string();
+    };
+    let mut text = reply.to_string();
+    if style.no_tables || style.plain_text {
+        text = tables_to_lists(&text);
+    }
+    if style.plain_text {
+        text = strip_markdown(&text);
+    }
+    if style.require_sources {
+        text = collect_sources(&text);
+    }
+    if let Some(max) = style.max_words {
+        text = limit_words(&text, max);
+    }
+    text
+}
+
+/// Rewrite each Markdown table as one bullet per row ("- Col: value, …").
+fn tables_to_lists(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let is_table = i + 1 < lines.len()
+            && is_row(lines[i])
+            && cells(lines[i + 1])
+                .iter()
+                .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':')));
+        if !is_table {
+            out.push(lines[i].to_string());
+            i += 1;
+            continue;
+        }
+        let header = cells(lines[i]);
+        i += 2;
+        while i < lines.len() && is_row(lines[i]) {
+            let row = cells(lines[i])
+                .iter()
+                .zip(&header)
+                .filter(|(value, _)| !value.is_empty())
+                .map(|(value, name)| {
+                    if name.is_empty() {
+                        (*value).to_string()
+                    } else {
+                        format!("{name}: {value}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push(format!("- {row}"));
+            i += 1;
+        }
+    }
+    let mut joined = out.join("\n");
+    if text.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+fn is_row(line: &str) -> bool {
+    let line = line.trim();
+    line.len() > 1 && line.starts_with('|') && line.ends_with('|')
+}
+
+fn cells(line: &str) -> Vec<&str> {
+    line.trim()
+        .trim_matches('|')
+        .split('|')
+        .map(str::trim)
+        .collect()
+}
+
+fn strip_markdown(text: &str) -> String {
+    let lines: Vec<String> = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .map(|line| {
+            let line = line.trim_start_matches('#').trim_start_matches(' ');
+            let line = LINK.replace_all(line, "$1 ($2)");
+            EMPHASIS.replace_all(&line, "$2").into_owned()
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Gather the links a reply cites into a trailing "Sources:" list when it
+/// does not already have one. Replies without links are left alone.
+fn collect_sources(text: &str) -> String {
+    if SOURCES_HEADING.is_match(text) {
+        return text.to_string();
+    }
+    let mut urls: Vec<&str> = Vec::new();
+    for m in URL.find_iter(text) {
+        let url = m.as_str().trim_end_matches(['.', ',', ';', ':']);
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    if urls.is_empty() {
+        return text.to_string();
+    }
+    let mut out = format!("{}\n\nSources:", text.trim_end());
+    for url in urls {
+        let _ = write!(out, "\n- {url}");
+    }
+    out
+}
+
+/// Keep the first `max` words, preferring to end on a sentence boundary.
+fn limit_words(text: &str, max: usize) -> String {
+    let mut words = 0;
+    let mut in_word = false;
+    let mut cut = None;
+    for (idx, ch) in text.char_indices() {
+        if ch.is_whitespace() {
+            in_word = false;
+        } else if !in_word {
+            in_word = true;
+            words += 1;
+            if words > max {
+                cut = Some(idx);
+                break;
+            }
+        }
+    }
+    let Some(cut) = cut else {
+        return text.to_string();
+    };
+    let kept = text[..cut].trim_end();
+    // Back up to the last full sentence if that keeps at least half.
+    let sentence_end = kept
+        .rfind(['.', '!', '?'])
+        .filter(|&end| kept[..end].split_whitespace().count() * 2 >= max);
+    match sentence_end {
+        Some(end) => kept[..=end].to_string(),
+        None => format!("{kept}…"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_lists_only_configured_rules() {
+        assert!(matches!(system_prompt("base", None), Cow::Borrowed("base")));
+        assert!(matches!(
+            system_prompt("base", Some(&ChannelStyle::default())),
+            Cow::Borrowed("base")
+        ));
+
+        let style = ChannelStyle {
+            max_words: Some(40),
+            no_tables: true,
+            require_sources: true,
+            ..ChannelStyle::default()
+        };
+        let prompt = system_prompt("base", Some(&style));
+        assert!(prompt.starts_with("base\n## Reply Style"));
+        assert!(prompt.contains("under 40 words"));
+        assert!(prompt.contains("Never use Markdown tables"));
+        assert!(prompt.contains("\"Sources:\""));
+    }
+
+    #[test]
+    fn tables_become_bullets() {
+        let style = ChannelStyle {
+            no_tables: true,
+            ..ChannelStyle::default()
+        };
+        let reply =
+            "Plans:\n\n| Plan | Price |\n|---|:---:|\n| Basic | $5 |\n| Pro | $12 |\n\nPick one.";
+        assert_eq!(
+            apply(Some(&style), reply),
+            "Plans:\n\n- Plan: Basic, Price: $5\n- Plan: Pro, Price: $12\n\nPick one."
+        );
+        // A lone pipe line is not a table.
+        assert_eq!(apply(Some(&style), "| not a table |"), "| not a table |");
+    }
+
+    #[test]
+    fn plain_text_and_word_limit() {
+        let style = ChannelStyle {
+            max_words: Some(12),
+            plain_text: true,
+            ..ChannelStyle::default()
+        };
+        let reply = "## Status\n\nThe **deploy** finished in `4m`. See [the log](https://ci.test/42) for details. Everything else is green and nothing needs attention today.";
+        assert_eq!(
+            apply(Some(&style), reply),
+            "Status\n\nThe deploy finished in 4m. See the log (https://ci.test/42) for details."
+        );
+        assert_eq!(limit_words("one two three four", 2), "one two…");
+    }
+
+    #[test]
+    fn cited_links_are_collected_once() {
+        let style = ChannelStyle {
+            require_sources: true,
+            ..ChannelStyle::default()
+        };
+        let reply = "Rust 1.80 added LazyLock (https://blog.rust-lang.org/1.80). Details: https://doc.rust-lang.org/std/sync/struct.LazyLock.html, and again https://blog.rust-lang.org/1.80.";
+        let styled = apply(Some(&style), reply);
+        assert!(styled.ends_with(
+            "Sources:\n- https://blog.rust-lang.org/1.80\n- https://doc.rust-lang.org/std/sync/struct.LazyLock.html"
+        ));
+        let cited = "See the docs.\n\nSources: https://a.test";
+        assert_eq!(apply(Some(&style), cited), cited);
+        assert_eq!(apply(Some(&style), "No links here."), "No links here.");
+    }
+}
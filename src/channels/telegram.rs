@@ -1,14 +1,33 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelMessage, SenderProfile};
+use crate::providers::ImageAttachment;
+use crate::tools::reply::{self, Choice};
+use crate::vision;
+use crate::voice::VoiceClient;
 use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// Longest caption Telegram accepts on a media message.
+const MAX_CAPTION_CHARS: usize = 1024;
+/// Longest `callback_data` Telegram accepts on an inline button, in bytes.
+const MAX_CALLBACK_DATA: usize = 64;
+/// Marks button data that is the choice's index rather than its reply.
+const CALLBACK_INDEX: char = '#';
+
 /// Telegram channel — long-polls the Bot API for updates
 pub struct TelegramChannel {
     bot_token: String,
     allowed_users: Vec<String>,
     client: reqwest::Client,
+    voice: Option<Arc<VoiceClient>>,
+    voice_replies: bool,
+    /// Chats whose latest message was a voice note still waiting for a reply
+    voice_chats: Mutex<HashSet<String>>,
+    /// Download photos for the model
+    images: bool,
 }
 
 impl TelegramChannel {
@@ -16,14 +35,92 @@ impl TelegramChannel {
         Self {
             bot_token,
             allowed_users,
-            client: reqwest::Client::new(),
+            client: crate::util::shared_client().clone(),
+            voice: None,
+            voice_replies: false,
+            voice_chats: Mutex::new(HashSet::new()),
+            images: false,
         }
     }
 
+    /// Transcribe voice notes with `voice`, and with `replies` answer them
+    /// with a voice note captioned with the reply text.
+    #[must_use]
+    pub fn with_voice(mut self, voice: Option<Arc<VoiceClient>>, replies: bool) -> Self {
+        self.voice = voice;
+        self.voice_replies = replies;
+        self
+    }
+
+    /// Download photos (and images sent as files) for the model.
+    #[must_use]
+    pub fn with_images(mut self, enabled: bool) -> Self {
+        self.images = enabled;
+        self
+    }
+
     fn api_url(&self, method: &str) -> String {
         format!("https://api.telegram.org/bot{}/{method}", self.bot_token)
     }
 
+    fn file_url(&self, file_path: &str) -> String {
+        format!(
+            "https://api.telegram.org/file/bot{}/{file_path}",
+            self.bot_token
+        )
+    }
+
+    fn transcriber(&self) -> Option<&VoiceClient> {
+        self.voice.as_deref().filter(|voice| voice.transcribes())
+    }
+
+    fn voice_chats(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.voice_chats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Download a file the bot received, with its path on Telegram's side.
+    async fn download_file(&self, file_id: &str) -> anyhow::Result<(Vec<u8>, String)> {
+        let file = self
+            .call("getFile", &serde_json::json!({ "file_id": file_id }))
+            .await?;
+        let file_path = file
+            .pointer("/result/file_path")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Telegram getFile returned no file_path"))?;
+        let data = self
+            .client
+            .get(self.file_url(file_path))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok((data.to_vec(), file_path.to_string()))
+    }
+
+    /// Download a voice note and transcribe it.
+    async fn transcribe_voice(&self, voice: &VoiceClient, file_id: &str) -> anyhow::Result<String> {
+        let (audio, file_path) = self.download_file(file_id).await?;
+        let file_name = file_path.rsplit('/').next().unwrap_or("voice.ogg");
+        voice.transcribe(audio, file_name).await
+    }
+
+    /// The photo in `message`, downloaded, if images are on.
+    async fn message_images(&self, message: &serde_json::Value) -> Vec<ImageAttachment> {
+        let Some((file_id, mime_type)) = self.images.then(|| image_file(message)).flatten() else {
+            return Vec::new();
+        };
+        match self.download_file(file_id).await {
+            Ok((data, _)) => vec![ImageAttachment::new(mime_type, data)],
+            Err(e) => {
+                tracing::warn!("Telegram: photo download failed: {e}");
+                Vec::new()
+            }
+        }
+    }
+
     fn is_user_allowed(&self, username: &str) -> bool {
         self.allowed_users.iter().any(|u| u == "*" || u == username)
     }
@@ -35,6 +132,31 @@ impl TelegramChannel {
         identities.into_iter().any(|id| self.is_user_allowed(id))
     }
 
+    /// Call a Bot API method with a JSON body and return the parsed response
+    async fn call(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = self
+            .client
+            .post(self.api_url(method))
+            .json(body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Telegram {method} failed ({status}): {err}");
+        }
+
+        Ok(resp.json().await.unwrap_or_default())
+    }
+
     /// Send a document/file to a Telegram chat
     pub async fn send_document(
         &self,
@@ -298,6 +420,170 @@ impl TelegramChannel {
         Ok(())
     }
 
+    /// Whether `message` has anything to answer: text, a voice note to
+    /// transcribe or a photo to look at.
+    fn is_answerable(&self, message: &serde_json::Value) -> bool {
+        message.get("text").is_some()
+            || (self.transcriber().is_some() && message.get("voice").is_some())
+            || (self.images && image_file(message).is_some())
+    }
+
+    /// The text of `message`, with voice notes transcribed, or a photo with
+    /// its caption. Remembers whether the reply to `chat_id` should be a
+    /// voice note.
+    async fn message_content(
+        &self,
+        message: &serde_json::Value,
+        chat_id: &str,
+    ) -> Option<(String, Vec<ImageAttachment>)> {
+        let images = self.message_images(message).await;
+        if !images.is_empty() {
+            self.voice_chats().remove(chat_id);
+            let caption = message.get("caption").and_then(serde_json::Value::as_str);
+            return Some((vision::caption_or_placeholder(caption), images));
+        }
+        let text = message.get("text").and_then(serde_json::Value::as_str);
+        let content = match (text, self.transcriber()) {
+            (Some(text), _) => text.to_string(),
+            (None, Some(voice)) => {
+                let file_id = message
+                    .pointer("/voice/file_id")
+                    .and_then(serde_json::Value::as_str)?;
+                match self.transcribe_voice(voice, file_id).await {
+                    Ok(transcript) if !transcript.is_empty() => transcript,
+                    Ok(_) => return None,
+                    Err(e) => {
+                        tracing::warn!("Telegram: voice note transcription failed: {e}");
+                        return None;
+                    }
+                }
+            }
+            (None, None) => return None,
+        };
+        if self.voice_replies && text.is_none() {
+            self.voice_chats().insert(chat_id.to_string());
+        } else {
+            self.voice_chats().remove(chat_id);
+        }
+        Some((content, Vec::new()))
+    }
+
+    /// Send a voice message from bytes (Ogg/Opus) to a Telegram chat
+    pub async fn send_voice_bytes(
+        &self,
+        chat_id: &str,
+        file_bytes: Vec<u8>,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let part = Part::bytes(file_bytes).file_name("voice.ogg");
+
+        let mut form = Form::new()
+            .text("chat_id", chat_id.to_string())
+            .part("voice", part);
+
+        if let Some(cap) = caption {
+            form = form.text("caption", cap.to_string());
+        }
+
+        let resp = self
+            .client
+            .post(self.api_url("sendVoice"))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let err = resp.text().await?;
+            anyhow::bail!("Telegram sendVoice failed: {err}");
+        }
+
+        tracing::info!("Telegram voice reply sent to {chat_id}");
+        Ok(())
+    }
+
+    async fn send_text(&self, message: &str, chat_id: &str) -> anyhow::Result<()> {
+        let (text, choices) = reply::extract_choices(message);
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": text,
+            "parse_mode": "Markdown"
+        });
+        if !choices.is_empty() {
+            body["reply_markup"] = inline_keyboard(&choices);
+        }
+        self.call("sendMessage", &body).await.map(|_| ())
+    }
+
+    /// Acknowledge a button press, then swap the keyboard under its message
+    /// for a note of what was picked so it cannot be pressed twice.
+    async fn settle_callback(&self, query: &serde_json::Value, picked: &str) {
+        let id = query.get("id").cloned().unwrap_or_default();
+        if let Err(e) = self
+            .call(
+                "answerCallbackQuery",
+                &serde_json::json!({ "callback_query_id": id }),
+            )
+            .await
+        {
+            tracing::debug!("Telegram: answerCallbackQuery failed: {e}");
+        }
+        let Some(message) = query.get("message") else {
+            return;
+        };
+        let text = message
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let body = serde_json::json!({
+            "chat_id": message.pointer("/chat/id"),
+            "message_id": message.get("message_id"),
+            "text": format!("{text}\n\n☑️ {picked}"),
+        });
+        if let Err(e) = self.call("editMessageText", &body).await {
+            tracing::debug!("Telegram: could not close the keyboard: {e}");
+        }
+    }
+
+    /// A button press from an allowed user, as a message carrying the
+    /// picked choice's reply.
+    async fn callback_message(&self, query: &serde_json::Value) -> Option<ChannelMessage> {
+        let username = query
+            .pointer("/from/username")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown");
+        let user_id = query
+            .pointer("/from/id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string());
+        let identities = [Some(username), user_id.as_deref()];
+        if !self.is_any_user_allowed(identities.into_iter().flatten()) {
+            tracing::warn!("Telegram: ignoring button press from unauthorized user {username}");
+            return None;
+        }
+        let chat_id = query
+            .pointer("/message/chat/id")
+            .and_then(serde_json::Value::as_i64)?
+            .to_string();
+        let choice = picked_choice(query)?;
+        self.settle_callback(query, &choice.label).await;
+        let id = query
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .map_or_else(|| Uuid::new_v4().to_string(), ToString::to_string);
+        Some(ChannelMessage {
+            id: format!("callback:{id}"),
+            sender: chat_id,
+            content: choice.reply,
+            profile: sender_profile(query),
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            images: Vec::new(),
+        })
+    }
+
     /// Send a file by URL (Telegram will download it)
     pub async fn send_document_by_url(
         &self,
@@ -370,29 +656,89 @@ impl Channel for TelegramChannel {
     }
 
     async fn send(&self, message: &str, chat_id: &str) -> anyhow::Result<()> {
-        let body = serde_json::json!({
+        if let (Some(voice), (text, Some(speech))) =
+            (self.voice.as_ref(), reply::extract_speech(message))
+        {
+            if !text.is_empty() {
+                self.send_text(&text, chat_id).await?;
+            }
+            return match voice.speak(&speech).await {
+                Ok(audio) => self.send_voice_bytes(chat_id, audio, None).await,
+                Err(e) => {
+                    tracing::warn!("Telegram: speech failed, sending text: {e}");
+                    self.send_text(&speech, chat_id).await
+                }
+            };
+        }
+        // Buttons need a text message to hang from.
+        let voice = self
+            .voice
+            .as_ref()
+            .filter(|_| self.voice_chats().remove(chat_id))
+            .filter(|_| reply::extract_choices(message).1.is_empty());
+        if let Some(voice) = voice {
+            match voice.speak(message).await {
+                Ok(audio) => {
+                    // Short replies ride along as the caption; longer ones
+                    // go out as text first.
+                    let caption = (message.chars().count() <= MAX_CAPTION_CHARS).then_some(message);
+                    if caption.is_none() {
+                        self.send_text(message, chat_id).await?;
+                    }
+                    return self.send_voice_bytes(chat_id, audio, caption).await;
+                }
+                Err(e) => tracing::warn!("Telegram: voice reply failed, sending text: {e}"),
+            }
+        }
+        self.send_text(message, chat_id).await
+    }
+
+    fn supports_edits(&self) -> bool {
+        true
+    }
+
+    fn streams_to(&self, chat_id: &str) -> bool {
+        !self.voice_chats().contains(chat_id)
+    }
+
+    fn supports_choices(&self) -> bool {
+        true
+    }
+
+    fn supports_speech(&self) -> bool {
+        self.voice.is_some()
+    }
+
+    async fn send_editable(&self, message: &str, chat_id: &str) -> anyhow::Result<String> {
+        // Plain text: a half-streamed reply is rarely valid Markdown.
+        let body = serde_json::json!({ "chat_id": chat_id, "text": message });
+        let sent = self.call("sendMessage", &body).await?;
+        Ok(sent
+            .pointer("/result/message_id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())
+            .unwrap_or_default())
+    }
+
+    async fn edit(&self, message_id: &str, message: &str, chat_id: &str) -> anyhow::Result<()> {
+        let (text, choices) = reply::extract_choices(message);
+        let mut body = serde_json::json!({
             "chat_id": chat_id,
-            "text": message,
+            "message_id": message_id.parse::<i64>().unwrap_or_default(),
+            "text": text,
             "parse_mode": "Markdown"
         });
-
-        let resp = self
-            .client
-            .post(self.api_url("sendMessage"))
-            .json(&body)
-            .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let err = resp
-                .text()
-                .await
-                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-            anyhow::bail!("Telegram sendMessage failed ({status}): {err}");
+        if !choices.is_empty() {
+            body["reply_markup"] = inline_keyboard(&choices);
         }
-
-        Ok(())
+        if self.call("editMessageText", &body).await.is_ok() {
+            return Ok(());
+        }
+        // Retry without Markdown in case the (partial) text does not parse.
+        if let Some(fields) = body.as_object_mut() {
+            fields.remove("parse_mode");
+        }
+        self.call("editMessageText", &body).await.map(|_| ())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -405,7 +751,7 @@ impl Channel for TelegramChannel {
             let body = serde_json::json!({
                 "offset": offset,
                 "timeout": 30,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
 
             let resp = match self.client.post(&url).json(&body).send().await {
@@ -433,13 +779,23 @@ impl Channel for TelegramChannel {
                         offset = uid + 1;
                     }
 
+                    if let Some(query) = update.get("callback_query") {
+                        let Some(msg) = self.callback_message(query).await else {
+                            continue;
+                        };
+                        if tx.send(msg).await.is_err() {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+
                     let Some(message) = update.get("message") else {
                         continue;
                     };
 
-                    let Some(text) = message.get("text").and_then(serde_json::Value::as_str) else {
+                    if !self.is_answerable(message) {
                         continue;
-                    };
+                    }
 
                     let username_opt = message
                         .get("from")
@@ -474,15 +830,27 @@ Allowlist Telegram @username or numeric user ID, then run `zeroclaw onboard --ch
                         .map(|id| id.to_string())
                         .unwrap_or_default();
 
+                    let id = message
+                        .get("message_id")
+                        .and_then(serde_json::Value::as_i64)
+                        .map_or_else(|| Uuid::new_v4().to_string(), |m| format!("{chat_id}_{m}"));
+
+                    let Some((content, images)) = self.message_content(message, &chat_id).await
+                    else {
+                        continue;
+                    };
+
                     let msg = ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
+                        id,
                         sender: chat_id,
-                        content: text.to_string(),
+                        content,
+                        profile: sender_profile(message),
                         channel: "telegram".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        images,
                     };
 
                     if tx.send(msg).await.is_err() {
@@ -503,6 +871,90 @@ Allowlist Telegram @username or numeric user ID, then run `zeroclaw onboard --ch
     }
 }
 
+/// `reply_markup` with one button per choice, one per row. A reply too long
+/// for `callback_data` is sent as its index and read back from the button.
+fn inline_keyboard(choices: &[Choice]) -> serde_json::Value {
+    let rows: Vec<_> = choices
+        .iter()
+        .enumerate()
+        .map(|(i, choice)| {
+            let data = if choice.reply.len() <= MAX_CALLBACK_DATA
+                && !choice.reply.starts_with(CALLBACK_INDEX)
+            {
+                choice.reply.clone()
+            } else {
+                format!("{CALLBACK_INDEX}{i}")
+            };
+            serde_json::json!([{ "text": choice.label, "callback_data": data }])
+        })
+        .collect();
+    serde_json::json!({ "inline_keyboard": rows })
+}
+
+/// The choice a `callback_query` picked: its data, or for an index the label
+/// of the pressed button (the full reply did not fit on it).
+fn picked_choice(query: &serde_json::Value) -> Option<Choice> {
+    let data = query.get("data").and_then(serde_json::Value::as_str)?;
+    let label = query
+        .pointer("/message/reply_markup/inline_keyboard")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_array)
+        .flatten()
+        .find(|b| b.get("callback_data").and_then(serde_json::Value::as_str) == Some(data))
+        .and_then(|b| b.get("text"))
+        .and_then(serde_json::Value::as_str);
+    if data.starts_with(CALLBACK_INDEX) {
+        let label = label?;
+        return Some(Choice::new(label, label));
+    }
+    Some(Choice::new(label.unwrap_or(data), data))
+}
+
+/// Name and language of the sender of `message` (Telegram has no timezone).
+/// File ID and type of the image in `message`: the largest size of a
+/// photo, or an image sent as a file. Too-large files are skipped.
+fn image_file(message: &serde_json::Value) -> Option<(&str, &str)> {
+    fn file_id(file: &serde_json::Value) -> Option<&str> {
+        file.get("file_id")?.as_str()
+    }
+    let small_enough = |file: &serde_json::Value| {
+        file.get("file_size")
+            .and_then(serde_json::Value::as_u64)
+            .is_none_or(|size| size <= vision::MAX_DOWNLOAD_BYTES as u64)
+    };
+    if let Some(largest) = message
+        .get("photo")
+        .and_then(serde_json::Value::as_array)
+        .and_then(|sizes| sizes.last())
+    {
+        return small_enough(largest)
+            .then(|| file_id(largest))
+            .flatten()
+            .map(|id| (id, "image/jpeg"));
+    }
+    let document = message.get("document")?;
+    let mime_type = document.get("mime_type")?.as_str()?;
+    (vision::is_image(mime_type) && small_enough(document))
+        .then(|| file_id(document))
+        .flatten()
+        .map(|id| (id, mime_type))
+}
+
+fn sender_profile(message: &serde_json::Value) -> SenderProfile {
+    let from = message.get("from");
+    let field = |name: &str| {
+        from.and_then(|f| f.get(name))
+            .and_then(serde_json::Value::as_str)
+    };
+    let name = match (field("first_name"), field("last_name")) {
+        (Some(first), Some(last)) => Some(format!("{first} {last}")),
+        (first, _) => first.or(field("username")).map(ToString::to_string),
+    };
+    SenderProfile::new(name.as_deref(), field("language_code"), None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -630,6 +1082,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn telegram_file_url() {
+        let ch = TelegramChannel::new("123:ABC".into(), vec![]);
+        assert_eq!(
+            ch.file_url("voice/file_7.oga"),
+            "https://api.telegram.org/file/bot123:ABC/voice/file_7.oga"
+        );
+    }
+
+    #[test]
+    fn telegram_sender_profile_from_message() {
+        let message = serde_json::json!({
+            "from": {"id": 7, "first_name": "Ingrid", "last_name": "Berg", "language_code": "de"}
+        });
+        assert_eq!(
+            sender_profile(&message),
+            SenderProfile::new(Some("Ingrid Berg"), Some("de"), None)
+        );
+        assert_eq!(
+            sender_profile(&serde_json::json!({})),
+            SenderProfile::default()
+        );
+    }
+
+    #[test]
+    fn telegram_choices_become_buttons_and_come_back_as_replies() {
+        let long = "x".repeat(80);
+        let keyboard = inline_keyboard(&[
+            Choice::new("✅ Approve", "yes"),
+            Choice::new("Long one", long),
+        ]);
+        assert_eq!(
+            keyboard["inline_keyboard"][0][0],
+            serde_json::json!({ "text": "✅ Approve", "callback_data": "yes" })
+        );
+        assert_eq!(keyboard["inline_keyboard"][1][0]["callback_data"], "#1");
+
+        let press = |data: &str| {
+            serde_json::json!({
+                "id": "42",
+                "data": data,
+                "from": { "id": 7, "username": "alice" },
+                "message": { "message_id": 3, "chat": { "id": 99 }, "reply_markup": keyboard }
+            })
+        };
+        assert_eq!(
+            picked_choice(&press("yes")),
+            Some(Choice::new("✅ Approve", "yes"))
+        );
+        assert_eq!(
+            picked_choice(&press("#1")),
+            Some(Choice::new("Long one", "Long one"))
+        );
+        assert_eq!(picked_choice(&press("#5")), None);
+    }
+
+    #[test]
+    fn telegram_picks_the_largest_photo_or_an_image_document() {
+        let photo = serde_json::json!({
+            "photo": [
+                { "file_id": "small", "width": 90, "height": 67 },
+                { "file_id": "large", "width": 1280, "height": 960, "file_size": 120_000 }
+            ],
+            "caption": "What is this?"
+        });
+        assert_eq!(image_file(&photo), Some(("large", "image/jpeg")));
+        let document = serde_json::json!({
+            "document": { "file_id": "doc", "mime_type": "image/png" }
+        });
+        assert_eq!(image_file(&document), Some(("doc", "image/png")));
+        let pdf = serde_json::json!({
+            "document": { "file_id": "doc", "mime_type": "application/pdf" }
+        });
+        assert_eq!(image_file(&pdf), None);
+        let huge = serde_json::json!({
+            "photo": [{ "file_id": "huge", "file_size": 50_000_000 }]
+        });
+        assert_eq!(image_file(&huge), None);
+    }
+
+    #[test]
+    fn telegram_voice_chats_are_not_streamed_to() {
+        let ch = TelegramChannel::new("123:ABC".into(), vec![]).with_voice(None, true);
+        assert!(ch.streams_to("42"));
+        ch.voice_chats().insert("42".into());
+        assert!(!ch.streams_to("42"));
+        assert!(ch.streams_to("7"));
+    }
+
     // ── File sending integration tests (with mock server) ──────────
 
     #[tokio::test]
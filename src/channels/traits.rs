@@ -8,6 +8,87 @@ pub struct ChannelMessage {
     pub content: String,
     pub channel: String,
     pub timestamp: u64,
+    /// What the platform says about the sender
+    pub profile: SenderProfile,
+    /// Photos sent with the message, for vision-capable models
+    pub images: Vec<crate::providers::ImageAttachment>,
+}
+
+/// Sender details a platform exposes; every field is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderProfile {
+    pub display_name: Option<String>,
+    /// Language hint as a BCP 47 tag, e.g. `de` or `pt-BR`
+    pub language: Option<String>,
+    /// IANA zone (`Europe/Berlin`) or UTC offset (`+02:00`)
+    pub timezone: Option<String>,
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string)
+}
+
+impl SenderProfile {
+    pub fn new(display_name: Option<&str>, language: Option<&str>, timezone: Option<&str>) -> Self {
+        Self {
+            display_name: non_empty(display_name),
+            language: non_empty(language),
+            timezone: non_empty(timezone),
+        }
+    }
+
+    /// Fields of `self`, with the gaps filled from `fallback`.
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            display_name: self.display_name.or(fallback.display_name),
+            language: self.language.or(fallback.language),
+            timezone: self.timezone.or(fallback.timezone),
+        }
+    }
+
+    /// The system prompt section for this sender, if anything is known.
+    pub fn prompt_section(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(name) = &self.display_name {
+            lines.push(format!("- Name: {name}"));
+        }
+        if let Some(language) = &self.language {
+            lines.push(format!(
+                "- Language: {language}. Reply in this language unless they write in another."
+            ));
+        }
+        if let Some(timezone) = &self.timezone {
+            lines.push(format!(
+                "- Timezone: {timezone}. State and schedule times in this timezone."
+            ));
+        }
+        (!lines.is_empty()).then(|| format!("## Sender\n\n{}\n", lines.join("\n")))
+    }
+}
+
+/// Acknowledgement left on an inbound message while and after it is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reaction {
+    /// The agent is working on it
+    Processing,
+    /// The reply went out
+    Done,
+    /// The reply failed
+    Failed,
+}
+
+impl Reaction {
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::Processing => "👀",
+            Self::Done => "✅",
+            Self::Failed => "⚠️",
+        }
+    }
 }
 
 /// Core channel trait — implement for any messaging platform
@@ -26,4 +107,67 @@ pub trait Channel: Send + Sync {
     async fn health_check(&self) -> bool {
         true
     }
+
+    /// Whether sent messages can be edited in place (enables streamed replies)
+    fn supports_edits(&self) -> bool {
+        false
+    }
+
+    /// Whether the reply to `recipient` may be streamed into an edited
+    /// message. Channels that sometimes answer in another form (e.g. a voice
+    /// note) return `false` so [`Channel::send`] gets the whole reply.
+    fn streams_to(&self, _recipient: &str) -> bool {
+        self.supports_edits()
+    }
+
+    /// Whether tool cards (`embed` blocks, see [`crate::tools::reply`]) in
+    /// replies are rendered natively. Otherwise they are flattened to text
+    /// before [`Channel::send`] sees them.
+    fn supports_embeds(&self) -> bool {
+        false
+    }
+
+    /// Whether `choices` blocks (see [`crate::tools::reply::Choice`]) in
+    /// messages are shown as buttons whose presses come back as the picked
+    /// reply. Otherwise they are flattened to a numbered list first.
+    fn supports_choices(&self) -> bool {
+        false
+    }
+
+    /// Whether `speech` blocks (see [`crate::tools::reply::speech_block`])
+    /// in messages go out as voice messages. Otherwise their text is shown.
+    fn supports_speech(&self) -> bool {
+        false
+    }
+
+    /// Whether [`Channel::react`] can mark inbound messages
+    fn supports_reactions(&self) -> bool {
+        false
+    }
+
+    /// React to `message`, one this channel received. A final reaction
+    /// replaces the [`Reaction::Processing`] one.
+    async fn react(&self, _message: &ChannelMessage, _reaction: Reaction) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support reactions", self.name())
+    }
+
+    /// Send a message and return its platform ID for later [`Channel::edit`] calls
+    async fn send_editable(&self, _message: &str, _recipient: &str) -> anyhow::Result<String> {
+        anyhow::bail!("{} does not support editing messages", self.name())
+    }
+
+    /// Replace the text of a message previously sent with [`Channel::send_editable`]
+    async fn edit(
+        &self,
+        _message_id: &str,
+        _message: &str,
+        _recipient: &str,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support editing messages", self.name())
+    }
+
+    /// Minimum time between edits of one message, to stay under rate limits
+    fn edit_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(1)
+    }
 }
@@ -0,0 +1,271 @@
+use super::traits::{Channel, ChannelMessage};
+use crate::config::WebhookTarget;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Signature header: `sha256=<hex HMAC of the raw body>`.
+const SIGNATURE_HEADER: &str = "X-ZeroClaw-Signature-256";
+/// Stable across retries so receivers can de-duplicate.
+const DELIVERY_HEADER: &str = "X-ZeroClaw-Delivery";
+/// Upper bound for a single retry delay.
+const MAX_BACKOFF_MS: u64 = 30_000;
+
+/// Webhook channel — outbound delivery to configured HTTP targets.
+///
+/// Inbound webhooks are served by the gateway (`POST /webhook`); this side
+/// only sends, so `listen` just idles until the channel server shuts down.
+pub struct WebhookChannel {
+    targets: Vec<WebhookTarget>,
+    client: crate::util::LazyClient,
+}
+
+impl WebhookChannel {
+    pub fn new(targets: Vec<WebhookTarget>) -> Self {
+        Self {
+            targets,
+            client: crate::util::LazyClient::new(Duration::from_secs(15)),
+        }
+    }
+
+    /// Targets addressed by `recipient`: a target name, or every target for "" / "*".
+    fn resolve(&self, recipient: &str) -> anyhow::Result<Vec<&WebhookTarget>> {
+        let recipient = recipient.trim();
+        if recipient.is_empty() || recipient == "*" {
+            return Ok(self.targets.iter().collect());
+        }
+        let target = self
+            .targets
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(recipient))
+            .ok_or_else(|| anyhow::anyhow!("No webhook target named '{recipient}'"))?;
+        Ok(vec![target])
+    }
+
+    async fn deliver(
+        &self,
+        target: &WebhookTarget,
+        message: &str,
+        recipient: &str,
+    ) -> anyhow::Result<()> {
+        let delivery_id = Uuid::new_v4().to_string();
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let body = render_payload(
+            target.payload_template.as_deref(),
+            message,
+            recipient,
+            &delivery_id,
+            timestamp,
+        );
+        let signature = target.secret.as_deref().map(|s| sign(s, &body));
+
+        let mut backoff_ms = target.initial_backoff_ms.max(1);
+        let mut attempt = 0;
+        loop {
+            let mut req = self
+                .client
+                .post(&target.url)
+                .header("Content-Type", "application/json")
+                .header(DELIVERY_HEADER, &delivery_id)
+                .header("X-ZeroClaw-Timestamp", timestamp.to_string());
+            for (key, value) in &target.headers {
+                req = req.header(key, value);
+            }
+            if let Some(ref signature) = signature {
+                req = req.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match req.body(body.clone()).send().await {
+                Ok(resp) if resp.status().is_success() => return Ok(()),
+                Ok(resp) => {
+                    let status = resp.status();
+                    let text = resp.text().await.unwrap_or_default();
+                    let error = anyhow::anyhow!(
+                        "Webhook '{}' returned {status}: {}",
+                        target.name,
+                        crate::util::truncate_with_ellipsis(&text, 200)
+                    );
+                    if !is_retryable(status) {
+                        return Err(error);
+                    }
+                    error
+                }
+                Err(e) => anyhow::anyhow!("Webhook '{}' request failed: {e}", target.name),
+            };
+
+            if attempt >= target.max_retries {
+                return Err(error.context(format!("gave up after {} attempt(s)", attempt + 1)));
+            }
+            attempt += 1;
+            tracing::warn!(
+                "{error}; retrying in {backoff_ms}ms ({attempt}/{})",
+                target.max_retries
+            );
+            tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = backoff_ms.saturating_mul(2).min(MAX_BACKOFF_MS);
+        }
+    }
+}
+
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// JSON string literal (with quotes) for substitution into a template.
+fn json_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+fn render_payload(
+    template: Option<&str>,
+    message: &str,
+    recipient: &str,
+    id: &str,
+    timestamp: u64,
+) -> String {
+    match template {
+        Some(template) => template
+            .replace("{{message}}", &json_string(message))
+            .replace("{{recipient}}", &json_string(recipient))
+            .replace("{{id}}", &json_string(id))
+            .replace("{{timestamp}}", &timestamp.to_string()),
+        None => serde_json::json!({
+            "id": id,
+            "source": "zeroclaw",
+            "recipient": recipient,
+            "message": message,
+            "timestamp": timestamp,
+        })
+        .to_string(),
+    }
+}
+
+#[async_trait]
+impl Channel for WebhookChannel {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
+        let targets = self.resolve(recipient)?;
+        if targets.is_empty() {
+            anyhow::bail!("No outbound webhook targets configured");
+        }
+        let mut failures = Vec::new();
+        for target in targets {
+            if let Err(e) = self.deliver(target, message, recipient).await {
+                failures.push(format!("{e:#}"));
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("{}", failures.join("; "))
+        }
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tx.closed().await;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        !self.targets.is_empty()
+            && self
+                .targets
+                .iter()
+                .all(|t| t.url.starts_with("https://") || t.url.starts_with("http://"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(name: &str) -> WebhookTarget {
+        WebhookTarget {
+            name: name.into(),
+            url: "https://example.com/hook".into(),
+            headers: std::collections::BTreeMap::new(),
+            payload_template: None,
+            secret: None,
+            max_retries: 0,
+            initial_backoff_ms: 1,
+        }
+    }
+
+    #[test]
+    fn webhook_channel_name() {
+        assert_eq!(WebhookChannel::new(vec![]).name(), "webhook");
+    }
+
+    #[test]
+    fn recipient_selects_target_or_broadcasts() {
+        let ch = WebhookChannel::new(vec![target("ops"), target("crm")]);
+        assert_eq!(ch.resolve("CRM").unwrap()[0].name, "crm");
+        assert_eq!(ch.resolve("").unwrap().len(), 2);
+        assert_eq!(ch.resolve("*").unwrap().len(), 2);
+        assert!(ch.resolve("billing").is_err());
+    }
+
+    #[test]
+    fn default_payload_is_json_envelope() {
+        let body = render_payload(None, "Deploy done", "ops", "abc", 1_700_000_000);
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["message"], "Deploy done");
+        assert_eq!(parsed["recipient"], "ops");
+        assert_eq!(parsed["timestamp"], 1_700_000_000);
+    }
+
+    #[test]
+    fn template_values_are_json_escaped() {
+        let body = render_payload(
+            Some(r#"{"text": {{message}}, "ts": {{timestamp}}}"#),
+            "line one\n\"quoted\"",
+            "ops",
+            "abc",
+            42,
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["text"], "line one\n\"quoted\"");
+        assert_eq!(parsed["ts"], 42);
+    }
+
+    #[test]
+    fn signature_is_hex_hmac_of_body() {
+        let sig = sign("secret", r#"{"message":"hi"}"#);
+        assert!(sig.starts_with("sha256="));
+        assert_eq!(sig.len(), "sha256=".len() + 64);
+        assert_eq!(sig, sign("secret", r#"{"message":"hi"}"#));
+        assert_ne!(sig, sign("other", r#"{"message":"hi"}"#));
+    }
+
+    #[test]
+    fn only_server_errors_and_rate_limits_retry() {
+        assert!(is_retryable(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn health_requires_targets() {
+        assert!(!WebhookChannel::new(vec![]).health_check().await);
+        assert!(
+            WebhookChannel::new(vec![target("ops")])
+                .health_check()
+                .await
+        );
+    }
+}
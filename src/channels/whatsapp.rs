@@ -1,7 +1,38 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelMessage, SenderProfile};
+use crate::tools::reply;
+use crate::voice::VoiceClient;
 use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// Longest text body the Cloud API accepts, in characters.
+const MAX_BODY_CHARS: usize = 4096;
+const GRAPH_API: &str = "https://graph.facebook.com/v18.0";
+
+/// Split a reply into bodies the Cloud API accepts, preferring line breaks.
+fn split_body(message: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = message;
+    while rest.chars().count() > MAX_BODY_CHARS {
+        let limit = rest
+            .char_indices()
+            .nth(MAX_BODY_CHARS)
+            .map_or(rest.len(), |(i, _)| i);
+        let cut = rest[..limit]
+            .rfind('\n')
+            .filter(|&i| i > 0)
+            .unwrap_or(limit);
+        parts.push(&rest[..cut]);
+        rest = rest[cut..].trim_start_matches('\n');
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(rest);
+    }
+    parts
+}
+
 /// `WhatsApp` channel — uses `WhatsApp` Business Cloud API
 ///
 /// This channel operates in webhook mode (push-based) rather than polling.
@@ -14,6 +45,10 @@ pub struct WhatsAppChannel {
     verify_token: String,
     allowed_numbers: Vec<String>,
     client: reqwest::Client,
+    voice: Option<Arc<VoiceClient>>,
+    voice_replies: bool,
+    /// Numbers whose latest message was a voice note still waiting for a reply
+    voice_chats: Mutex<HashSet<String>>,
 }
 
 impl WhatsAppChannel {
@@ -28,22 +63,187 @@ impl WhatsAppChannel {
             phone_number_id,
             verify_token,
             allowed_numbers,
-            client: reqwest::Client::new(),
+            client: crate::util::shared_client().clone(),
+            voice: None,
+            voice_replies: false,
+            voice_chats: Mutex::new(HashSet::new()),
         }
     }
 
+    /// Transcribe voice notes with `voice`, and with `replies` answer them
+    /// with a voice note after the reply text.
+    #[must_use]
+    pub fn with_voice(mut self, voice: Option<Arc<VoiceClient>>, replies: bool) -> Self {
+        self.voice = voice;
+        self.voice_replies = replies;
+        self
+    }
+
+    fn voice_chats(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.voice_chats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Download the media `media_id` points at, with its MIME type.
+    async fn download_media(&self, media_id: &str) -> anyhow::Result<(Vec<u8>, String)> {
+        let meta: serde_json::Value = self
+            .client
+            .get(format!("{GRAPH_API}/{media_id}"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let url = meta
+            .get("url")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media {media_id} has no download URL"))?;
+        let mime = meta
+            .get("mime_type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("audio/ogg")
+            .to_string();
+        let bytes = self
+            .client
+            .get(url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok((bytes.to_vec(), mime))
+    }
+
+    /// Upload Ogg/Opus `audio` and send it to `to` as a voice note.
+    async fn send_voice_note(&self, to: &str, audio: Vec<u8>) -> anyhow::Result<()> {
+        let form = Form::new()
+            .text("messaging_product", "whatsapp")
+            .text("type", "audio/ogg")
+            .part(
+                "file",
+                Part::bytes(audio)
+                    .file_name("reply.ogg")
+                    .mime_str("audio/ogg")?,
+            );
+        let uploaded: serde_json::Value = self
+            .client
+            .post(format!("{GRAPH_API}/{}/media", self.phone_number_id))
+            .bearer_auth(&self.access_token)
+            .multipart(form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let id = uploaded
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media upload returned no id"))?;
+        let body = serde_json::json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": to,
+            "type": "audio",
+            "audio": { "id": id }
+        });
+        self.client
+            .post(format!("{GRAPH_API}/{}/messages", self.phone_number_id))
+            .bearer_auth(&self.access_token)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
     /// Check if a phone number is allowed (E.164 format: +1234567890)
     fn is_number_allowed(&self, phone: &str) -> bool {
         self.allowed_numbers.iter().any(|n| n == "*" || n == phone)
     }
 
+    async fn send_text(&self, url: &str, to: &str, text: &str) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": to,
+            "type": "text",
+            "text": {
+                "preview_url": false,
+                "body": text
+            }
+        });
+
+        let resp = self
+            .client
+            .post(url)
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp send failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp API error: {status}");
+        }
+
+        Ok(())
+    }
+
     /// Get the verify token for webhook verification
     pub fn verify_token(&self) -> &str {
         &self.verify_token
     }
 
-    /// Parse an incoming webhook payload from Meta and extract messages
+    /// Parse an incoming webhook payload from Meta and extract its text
+    /// messages (see [`Self::receive_webhook`] for voice notes)
     pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
+        self.parse_messages(payload)
+            .into_iter()
+            .filter_map(|(msg, audio)| audio.is_none().then_some(msg))
+            .collect()
+    }
+
+    /// The messages of a webhook payload, with voice notes transcribed when
+    /// `[voice].transcribe` is on.
+    pub async fn receive_webhook(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
+        let mut messages = Vec::new();
+        for (mut msg, audio) in self.parse_messages(payload) {
+            if let Some(media_id) = audio {
+                let Some(voice) = self.voice.as_ref().filter(|v| v.transcribes()) else {
+                    tracing::debug!("WhatsApp: skipping voice note from {}", msg.sender);
+                    continue;
+                };
+                let transcript = match self.download_media(&media_id).await {
+                    Ok((audio, mime)) => voice.transcribe(audio, audio_file_name(&mime)).await,
+                    Err(e) => Err(e),
+                };
+                match transcript {
+                    Ok(text) if !text.is_empty() => {
+                        msg.content = text;
+                        if self.voice_replies {
+                            self.voice_chats().insert(msg.sender.clone());
+                        }
+                    }
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!("WhatsApp: could not transcribe voice note: {e}");
+                        continue;
+                    }
+                }
+            }
+            messages.push(msg);
+        }
+        messages
+    }
+
+    /// Text messages and voice notes (empty content, with their media ID).
+    fn parse_messages(&self, payload: &serde_json::Value) -> Vec<(ChannelMessage, Option<String>)> {
         let mut messages = Vec::new();
 
         // WhatsApp Cloud API webhook structure:
@@ -67,6 +267,16 @@ impl WhatsAppChannel {
                     continue;
                 };
 
+                // Profile names of the senders, by WhatsApp ID
+                let contacts = value.get("contacts").and_then(|c| c.as_array());
+                let contact_name = |wa_id: &str| {
+                    contacts?
+                        .iter()
+                        .find(|c| c.get("wa_id").and_then(|w| w.as_str()) == Some(wa_id))?
+                        .pointer("/profile/name")?
+                        .as_str()
+                };
+
                 for msg in msgs {
                     // Get sender phone number
                     let Some(from) = msg.get("from").and_then(|f| f.as_str()) else {
@@ -88,20 +298,26 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
+                    // Text, or a voice note to transcribe later
+                    let audio = msg
+                        .pointer("/audio/id")
+                        .and_then(|i| i.as_str())
+                        .map(str::to_string);
                     let content = if let Some(text_obj) = msg.get("text") {
                         text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
                             .to_string()
-                    } else {
-                        // Could be image, audio, etc. — skip for now
+                    } else if audio.is_none() {
+                        // Could be image, video, etc. — skip for now
                         tracing::debug!("WhatsApp: skipping non-text message from {from}");
                         continue;
+                    } else {
+                        String::new()
                     };
 
-                    if content.is_empty() {
+                    if content.is_empty() && audio.is_none() {
                         continue;
                     }
 
@@ -117,13 +333,25 @@ impl WhatsAppChannel {
                                 .as_secs()
                         });
 
-                    messages.push(ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
+                    let id = msg
+                        .get("id")
+                        .and_then(|i| i.as_str())
+                        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+
+                    let message = ChannelMessage {
+                        id,
                         sender: normalized_from,
                         content,
                         channel: "whatsapp".to_string(),
                         timestamp,
-                    });
+                        profile: SenderProfile::new(
+                            contact_name(from.trim_start_matches('+')),
+                            None,
+                            None,
+                        ),
+                        images: Vec::new(),
+                    };
+                    messages.push((message, audio));
                 }
             }
         }
@@ -132,12 +360,26 @@ impl WhatsAppChannel {
     }
 }
 
+/// A file name whose extension tells the transcription API the format.
+fn audio_file_name(mime: &str) -> &'static str {
+    match mime.split(';').next().unwrap_or_default().trim() {
+        "audio/mpeg" => "voice.mp3",
+        "audio/mp4" | "audio/aac" => "voice.m4a",
+        "audio/amr" => "voice.amr",
+        _ => "voice.ogg",
+    }
+}
+
 #[async_trait]
 impl Channel for WhatsAppChannel {
     fn name(&self) -> &str {
         "whatsapp"
     }
 
+    fn supports_speech(&self) -> bool {
+        self.voice.is_some()
+    }
+
     async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
         // WhatsApp Cloud API: POST to /v18.0/{phone_number_id}/messages
         let url = format!(
@@ -148,33 +390,38 @@ impl Channel for WhatsAppChannel {
         // Normalize recipient (remove leading + if present for API)
         let to = recipient.strip_prefix('+').unwrap_or(recipient);
 
-        let body = serde_json::json!({
-            "messaging_product": "whatsapp",
-            "recipient_type": "individual",
-            "to": to,
-            "type": "text",
-            "text": {
-                "preview_url": false,
-                "body": message
-            }
-        });
-
-        let resp = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.access_token))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+        // A `speech` block is said instead of shown.
+        let (text, speech) = match (&self.voice, reply::extract_speech(message)) {
+            (Some(_), (text, Some(speech))) => (text, Some(speech)),
+            _ => (message.to_string(), None),
+        };
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let error_body = resp.text().await.unwrap_or_default();
-            tracing::error!("WhatsApp send failed: {status} — {error_body}");
-            anyhow::bail!("WhatsApp API error: {status}");
+        // Long replies go out as consecutive messages.
+        for part in split_body(&text).into_iter().filter(|p| !p.is_empty()) {
+            self.send_text(&url, to, part).await?;
         }
 
+        // Voice notes can't carry a caption, so the spoken reply follows.
+        let spoken = speech.clone().or_else(|| {
+            self.voice_chats()
+                .remove(recipient)
+                .then(|| message.to_string())
+        });
+        if let (Some(voice), Some(spoken)) = (&self.voice, spoken) {
+            let sent = match voice.speak(&spoken).await {
+                Ok(audio) => self.send_voice_note(to, audio).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = sent {
+                tracing::warn!("WhatsApp: voice reply failed: {e}");
+                // Nothing of a `speech` block was shown yet.
+                if speech.is_some() {
+                    for part in split_body(&spoken) {
+                        self.send_text(&url, to, part).await?;
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -295,6 +542,31 @@ mod tests {
         assert_eq!(msgs[0].content, "Hello ZeroClaw!");
         assert_eq!(msgs[0].channel, "whatsapp");
         assert_eq!(msgs[0].timestamp, 1_699_999_999);
+        assert_eq!(msgs[0].id, "wamid.xxx");
+        assert_eq!(msgs[0].profile.display_name, None);
+    }
+
+    #[test]
+    fn whatsapp_parse_takes_sender_name_from_contacts() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "entry": [{
+                "changes": [{
+                    "value": {
+                        "contacts": [{ "wa_id": "1234567890", "profile": { "name": "Mama" } }],
+                        "messages": [{
+                            "from": "1234567890",
+                            "id": "wamid.yyy",
+                            "type": "text",
+                            "text": { "body": "Hallo" }
+                        }]
+                    }
+                }]
+            }]
+        });
+
+        let msgs = ch.parse_webhook_payload(&payload);
+        assert_eq!(msgs[0].profile.display_name.as_deref(), Some("Mama"));
     }
 
     #[test]
@@ -728,6 +1000,32 @@ mod tests {
         assert!(msgs.is_empty());
     }
 
+    #[tokio::test]
+    async fn whatsapp_voice_notes_wait_for_transcription() {
+        let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
+        let payload = serde_json::json!({
+            "entry": [{
+                "changes": [{
+                    "value": {
+                        "messages": [
+                            { "from": "111", "id": "a", "type": "audio", "audio": { "id": "media9", "mime_type": "audio/ogg; codecs=opus" } },
+                            { "from": "111", "id": "b", "type": "text", "text": { "body": "Hi" } }
+                        ]
+                    }
+                }]
+            }]
+        });
+        let parsed = ch.parse_messages(&payload);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].1.as_deref(), Some("media9"));
+        // Without [voice] the voice note is dropped, the text kept.
+        let received = ch.receive_webhook(&payload).await;
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].content, "Hi");
+        assert_eq!(audio_file_name("audio/ogg; codecs=opus"), "voice.ogg");
+        assert_eq!(audio_file_name("audio/mpeg"), "voice.mp3");
+    }
+
     #[test]
     fn whatsapp_parse_video_message_skipped() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
@@ -1107,4 +1405,20 @@ mod tests {
             "<script>alert('xss')</script> & \"quotes\" 'apostrophe'"
         );
     }
+
+    #[test]
+    fn whatsapp_long_replies_split_at_line_breaks() {
+        assert_eq!(split_body("hi"), ["hi"]);
+        assert_eq!(split_body(""), [""]);
+
+        let first = "é".repeat(4000);
+        let second = "b".repeat(500);
+        let message = format!("{first}\n{second}");
+        assert_eq!(split_body(&message), [first.as_str(), second.as_str()]);
+
+        let unbroken = "x".repeat(MAX_BODY_CHARS * 2 + 1);
+        let parts = split_body(&unbroken);
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|p| p.chars().count() <= MAX_BODY_CHARS));
+    }
 }
@@ -0,0 +1,251 @@
+//! Single-key edits to `config.toml` from chat.
+//!
+//! Only keys matching [`SAFE_KEYS`] can be changed: no provider keys, no
+//! autonomy, no gateway. An edit is applied to the file as written, so
+//! `secret:` references and keys left at their defaults stay that way. The
+//! result has to load as a [`Config`] and keep the new value, which catches
+//! wrong types and misspelled keys before anything is written.
+
+use super::Config;
+use anyhow::{Context, Result};
+use serde_json::Value as Json;
+use std::path::Path;
+use toml::Value;
+
+/// Dotted keys that may be changed from chat; `*` matches one segment.
+pub const SAFE_KEYS: &[&str] = &[
+    "default_model",
+    "default_temperature",
+    "heartbeat.enabled",
+    "heartbeat.interval_minutes",
+    "memory.auto_save",
+    "agent.context_tokens",
+    "agent.trace_prompts",
+    "goals.max_actions_per_day",
+    "tool_output.max_chars",
+    "channels_config.*.allowed_users",
+    "channels_config.*.allowed_contacts",
+    "channels_config.*.allowed_numbers",
+    "channels_config.*.allowed_senders",
+    "styles.*.max_words",
+    "styles.*.no_tables",
+    "styles.*.plain_text",
+    "styles.*.require_sources",
+    "styles.*.citations",
+    "styles.*.instructions",
+];
+
+pub fn is_safe(key: &str) -> bool {
+    SAFE_KEYS.iter().any(|pattern| {
+        let mut pattern = pattern.split('.');
+        let mut key = key.split('.');
+        loop {
+            match (pattern.next(), key.next()) {
+                (None, None) => return true,
+                (Some(p), Some(k)) if p == "*" || p == k => {}
+                _ => return false,
+            }
+        }
+    })
+}
+
+/// What to do with the value at the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    Set,
+    /// Append to a list, unless it is already there
+    Add,
+    /// Remove from a list
+    Remove,
+}
+
+impl EditOp {
+    pub fn parse(op: &str) -> Result<Self> {
+        match op {
+            "set" => Ok(Self::Set),
+            "add" => Ok(Self::Add),
+            "remove" => Ok(Self::Remove),
+            other => anyhow::bail!("Unknown op '{other}' (expected set, add or remove)"),
+        }
+    }
+}
+
+/// A change to one key and the file contents that result from it.
+#[derive(Debug, Clone)]
+pub struct Preview {
+    /// `- old` / `+ new` lines under the key
+    pub diff: String,
+    pub changed: bool,
+    contents: String,
+}
+
+fn get<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(value, |v, segment| v.get(segment))
+}
+
+/// The value at `key`, creating missing tables along the way.
+fn get_mut<'a>(value: &'a mut Value, key: &str) -> Result<&'a mut Value> {
+    let mut current = value;
+    let segments: Vec<&str> = key.split('.').collect();
+    let (last, parents) = segments.split_last().context("Empty key")?;
+    for segment in parents {
+        let table = current
+            .as_table_mut()
+            .with_context(|| format!("'{segment}' in {key} is not a table"))?;
+        current = table
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Table(toml::Table::new()));
+    }
+    let table = current
+        .as_table_mut()
+        .with_context(|| format!("{key} is not inside a table"))?;
+    Ok(table
+        .entry(last.to_string())
+        .or_insert_with(|| Value::Array(Vec::new())))
+}
+
+fn render(value: Option<&Value>) -> String {
+    value.map_or_else(|| "(unset)".into(), ToString::to_string)
+}
+
+/// Work out what `op` with `value` on `key` does to the config file at
+/// `path`, without writing anything.
+pub fn preview(path: &Path, key: &str, op: EditOp, value: &Json) -> Result<Preview> {
+    if !is_safe(key) {
+        anyhow::bail!("{key} can't be changed from chat; edit config.toml directly");
+    }
+    let contents = std::fs::read_to_string(path).context("Failed to read config file")?;
+    let mut raw: Value = toml::from_str(&contents).context("Failed to parse config file")?;
+    let effective = |raw: Value| -> Result<Value> {
+        let config: Config = raw.try_into()?;
+        Ok(Value::try_from(&config)?)
+    };
+    let before = effective(raw.clone()).context("The current config does not load")?;
+    let old = get(&before, key).cloned();
+
+    let value = Value::try_from(value).context("Value can't be written to TOML")?;
+    match op {
+        EditOp::Set => *get_mut(&mut raw, key)? = value.clone(),
+        EditOp::Add | EditOp::Remove => {
+            let slot = get_mut(&mut raw, key)?;
+            if let (Value::Array(list), Some(Value::Array(current))) = (&*slot, &old) {
+                if list.is_empty() {
+                    // Start from the effective list so defaults aren't lost.
+                    *slot = Value::Array(current.clone());
+                }
+            }
+            let list = slot
+                .as_array_mut()
+                .with_context(|| format!("{key} is not a list; use op=set"))?;
+            match op {
+                EditOp::Add if !list.contains(&value) => list.push(value.clone()),
+                EditOp::Remove => list.retain(|v| *v != value),
+                _ => {}
+            }
+        }
+    }
+
+    let after =
+        effective(raw.clone()).map_err(|e| anyhow::anyhow!("Invalid value for {key}: {e}"))?;
+    let new = get(&after, key);
+    let kept = match (op, new) {
+        (EditOp::Set, Some(new)) => *new == value,
+        (EditOp::Add, Some(Value::Array(list))) => list.contains(&value),
+        (EditOp::Remove, Some(Value::Array(list))) => !list.contains(&value),
+        _ => false,
+    };
+    if !kept {
+        anyhow::bail!("{key} is not a setting ZeroClaw knows; check the spelling");
+    }
+    Ok(Preview {
+        diff: format!("  {key}\n- {}\n+ {}", render(old.as_ref()), render(new)),
+        changed: old.as_ref() != new,
+        contents: toml::to_string_pretty(&raw).context("Failed to serialize config")?,
+    })
+}
+
+/// Write a previewed edit to `path`.
+pub fn apply(path: &Path, preview: &Preview) -> Result<()> {
+    std::fs::write(path, &preview.contents).context("Failed to write config file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config_file(contents: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    const BASE: &str = r#"
+api_key = "secret:openrouter"
+default_temperature = 0.7
+
+[heartbeat]
+enabled = true
+interval_minutes = 60
+
+[channels_config]
+cli = true
+
+[channels_config.imessage]
+allowed_contacts = ["+15551234567"]
+"#;
+
+    #[test]
+    fn only_listed_keys_are_safe() {
+        assert!(is_safe("heartbeat.interval_minutes"));
+        assert!(is_safe("channels_config.imessage.allowed_contacts"));
+        assert!(!is_safe("autonomy.level"));
+        assert!(!is_safe("api_key"));
+        assert!(!is_safe("channels_config.imessage"));
+    }
+
+    #[test]
+    fn set_and_add_keep_the_rest_of_the_file() {
+        let (_dir, path) = config_file(BASE);
+        let heartbeat =
+            preview(&path, "heartbeat.interval_minutes", EditOp::Set, &json!(30)).unwrap();
+        assert_eq!(heartbeat.diff, "  heartbeat.interval_minutes\n- 60\n+ 30");
+        apply(&path, &heartbeat).unwrap();
+
+        let key = "channels_config.imessage.allowed_contacts";
+        let contact = preview(&path, key, EditOp::Add, &json!("+4915112345678")).unwrap();
+        assert!(contact
+            .diff
+            .ends_with(r#"+ ["+15551234567", "+4915112345678"]"#));
+        apply(&path, &contact).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("interval_minutes = 30"));
+        assert!(written.contains("\"secret:openrouter\""));
+        assert!(written.contains("+4915112345678"));
+    }
+
+    #[test]
+    fn bad_types_and_unknown_keys_are_refused() {
+        let (_dir, path) = config_file(BASE);
+        let err = preview(
+            &path,
+            "heartbeat.interval_minutes",
+            EditOp::Set,
+            &json!("soon"),
+        );
+        assert!(err.unwrap_err().to_string().starts_with("Invalid value"));
+        // iMessage allowlists are `allowed_contacts`.
+        let err = preview(
+            &path,
+            "channels_config.imessage.allowed_users",
+            EditOp::Add,
+            &json!("x"),
+        );
+        assert!(err.unwrap_err().to_string().contains("not a setting"));
+        let err = preview(&path, "autonomy.level", EditOp::Set, &json!("full"));
+        assert!(err.unwrap_err().to_string().contains("can't be changed"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), BASE);
+    }
+}
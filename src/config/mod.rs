@@ -1,8 +1,24 @@
+pub mod edit;
+pub mod profile;
 pub mod schema;
 
 pub use schema::{
-    AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DiscordConfig,
-    GatewayConfig, HeartbeatConfig, IMessageConfig, IdentityConfig, MatrixConfig, MemoryConfig,
-    ObservabilityConfig, ReliabilityConfig, RuntimeConfig, SecretsConfig, SlackConfig,
-    TelegramConfig, TunnelConfig, WeatherConfig, WebhookConfig,
+    AgentConfig, AliasesConfig, ApprovalsConfig, ArtifactsConfig, AutonomyConfig, AwsCostConfig,
+    BitbucketConfig, BrowserBackend, BrowserConfig, CalendarConfig, CalendarFeed, ChannelStyle,
+    ChannelsConfig, CiStatusConfig, CommandAlias, ComposioConfig, Config, DelegateConfig,
+    DiscordConfig, DockerRuntimeConfig, EvalCanary, EvalConfig, ExperimentConfig, FallbackConfig,
+    FallbackProvider, FederationConfig, FederationPeerConfig, FocusConfig, GatewayConfig,
+    GcpBillingConfig, GitHubConfig, GitLabConfig, GoalsConfig, GuardrailAction, GuardrailKind,
+    GuardrailRule, GuardrailsConfig, HeartbeatConfig, HouseholdConfig, HouseholdMember,
+    IMessageConfig, IdentityConfig, InboxConfig, InboxLabel, IntegrationsConfig,
+    LaunchDarklyConfig, MatrixConfig, McpConfig, McpServerConfig, MemberRole, MemoryConfig,
+    NotificationRule, NotificationsConfig, OAuthClientConfig, OAuthConfig, ObservabilityConfig,
+    OfflineConfig, OllamaConfig, OnCallConfig, OpsgenieConfig, OutboxConfig, PagerDutyConfig,
+    PasswordManagerBackend, PasswordManagerConfig, PresenceConfig, PresenceMethod, ProvidersConfig,
+    PushConfig, QuietHours, RedditConfig, RedisConfig, ReliabilityConfig, RuntimeConfig,
+    SecretsConfig, SentryConfig, Severity, ShortcutsConfig, SignalConfig, SkillsConfig,
+    SlackConfig, SyncConfig, TelegramConfig, TerraformConfig, TokenPrices, ToolBudgetConfig,
+    ToolMiddlewareConfig, ToolOutputConfig, ToolOutputMode, ToolOutputRule, TriggerEvent,
+    TriggerRule, TriggersConfig, TunnelConfig, UnleashConfig, VisionConfig, VoiceConfig,
+    WeatherConfig, WebSearchConfig, WebhookConfig, WebhookTarget, WorkspaceIndexConfig,
 };
@@ -0,0 +1,238 @@
+//! Config presets picked with `[runtime] profile`.
+//!
+//! A profile fills in keys the file leaves out before it is parsed, so
+//! anything written in `config.toml` still wins. The filled keys are
+//! remembered and left out again when the config is saved, so switching
+//! profiles keeps working after the dashboard or onboarding writes the file. `low_memory` (alias `pi`)
+//! sizes `ZeroClaw` for a Raspberry Pi 4 or a small VPS: no browser, one
+//! session answered at a time and smaller caches.
+
+use toml::Value;
+
+pub const STANDARD: &str = "standard";
+pub const LOW_MEMORY: &str = "low_memory";
+
+/// Keys `idempotency` caches hold under `low_memory`.
+const LOW_MEMORY_CACHE_CAPACITY: usize = 1_000;
+
+/// Dotted keys the `low_memory` profile sets when the file doesn't.
+fn low_memory_preset() -> Vec<(&'static str, Value)> {
+    vec![
+        ("browser.enabled", Value::Boolean(false)),
+        ("agent.max_concurrent_sessions", Value::Integer(1)),
+        ("agent.session_queue_limit", Value::Integer(4)),
+        ("agent.delegate.max_subtasks", Value::Integer(2)),
+        ("memory.embedding_cache_size", Value::Integer(1_000)),
+        ("workspace_index.max_file_kb", Value::Integer(128)),
+        ("workspace_index.watch_interval_secs", Value::Integer(120)),
+    ]
+}
+
+/// The canonical name of `profile`, or `None` if it isn't one.
+pub fn normalize(profile: &str) -> Option<&'static str> {
+    match profile
+        .trim()
+        .to_ascii_lowercase()
+        .replace('-', "_")
+        .as_str()
+    {
+        "" | "standard" | "default" => Some(STANDARD),
+        "low_memory" | "pi" | "raspberry_pi" => Some(LOW_MEMORY),
+        _ => None,
+    }
+}
+
+/// Fill in the preset named by `runtime.profile` in a raw config file.
+/// Returns the dotted keys it filled and the values it used.
+pub fn apply(raw: &mut Value) -> anyhow::Result<Vec<(String, Value)>> {
+    let name = raw
+        .get("runtime")
+        .and_then(|r| r.get("profile"))
+        .and_then(Value::as_str)
+        .unwrap_or(STANDARD);
+    let profile = normalize(name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown [runtime].profile '{name}' (expected \"standard\" or \"low_memory\")"
+        )
+    })?;
+    let mut filled = Vec::new();
+    if profile == LOW_MEMORY {
+        // The preset sits on top of the defaults of the sections it touches,
+        // so it never leaves a required field of a section missing.
+        let defaults = Value::try_from(super::Config::default())?;
+        let mut preset = Value::Table(toml::map::Map::new());
+        for (key, value) in low_memory_preset() {
+            if get(raw, key).is_none() {
+                filled.push((key.to_string(), value.clone()));
+            }
+            set(&mut preset, &defaults, key, value);
+        }
+        fill_missing(raw, preset);
+    }
+    Ok(filled)
+}
+
+/// Remove the keys `apply` filled from a config about to be saved, unless
+/// they were changed since.
+pub fn strip(tree: &mut Value, filled: &[(String, Value)]) {
+    for (key, value) in filled {
+        let (parent, leaf) = key.rsplit_once('.').unwrap_or(("", key));
+        let mut table = Some(&mut *tree);
+        for part in parent.split('.').filter(|p| !p.is_empty()) {
+            table = table.and_then(|t| t.get_mut(part));
+        }
+        if let Some(Value::Table(map)) = table {
+            if map.get(leaf) == Some(value) {
+                map.remove(leaf);
+            }
+        }
+    }
+}
+
+/// Entries kept by the in-process dedup caches.
+pub fn cache_capacity(profile: &str) -> usize {
+    if normalize(profile) == Some(LOW_MEMORY) {
+        LOW_MEMORY_CACHE_CAPACITY
+    } else {
+        crate::idempotency::DEFAULT_CAPACITY
+    }
+}
+
+/// The value at dotted `key` in `tree`.
+fn get<'a>(tree: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(tree, |table, part| table.get(part))
+}
+
+/// Set dotted `key` in `tree`, taking a missing top-level section from `defaults`.
+fn set(tree: &mut Value, defaults: &Value, key: &str, value: Value) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(leaf) = parts.pop() else {
+        return;
+    };
+    let mut table = tree;
+    for (depth, part) in parts.into_iter().enumerate() {
+        let Some(map) = table.as_table_mut() else {
+            return;
+        };
+        table = map.entry(part).or_insert_with(|| {
+            defaults
+                .get(part)
+                .filter(|d| depth == 0 && d.is_table())
+                .cloned()
+                .unwrap_or_else(|| Value::Table(toml::map::Map::new()))
+        });
+    }
+    if let Some(map) = table.as_table_mut() {
+        map.insert(leaf.to_string(), value);
+    }
+}
+
+/// Copy into `raw` every key of `preset` that `raw` doesn't have.
+fn fill_missing(raw: &mut Value, preset: Value) {
+    let (Some(raw), Value::Table(preset)) = (raw.as_table_mut(), preset) else {
+        return;
+    };
+    for (key, value) in preset {
+        match raw.get_mut(&key) {
+            Some(existing) if existing.is_table() && value.is_table() => {
+                fill_missing(existing, value);
+            }
+            Some(_) => {}
+            None => {
+                raw.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn low_memory_fills_gaps_but_keeps_explicit_keys() {
+        let mut raw: Value = toml::from_str(
+            r#"
+            default_temperature = 0.7
+
+            [runtime]
+            kind = "native"
+            profile = "pi"
+
+            [agent]
+            max_concurrent_sessions = 2
+            "#,
+        )
+        .unwrap();
+        apply(&mut raw).unwrap();
+        let config: Config = raw.try_into().unwrap();
+        assert!(!config.browser.enabled);
+        assert_eq!(config.agent.max_concurrent_sessions, 2);
+        assert_eq!(config.agent.session_queue_limit, 4);
+        assert_eq!(config.agent.delegate.max_subtasks, 2);
+        assert_eq!(config.memory.backend, "sqlite");
+        assert_eq!(config.memory.embedding_cache_size, 1_000);
+        assert_eq!(config.workspace_index.max_file_kb, 128);
+        assert_eq!(cache_capacity(&config.runtime.profile), 1_000);
+
+        let mut standard: Value = toml::from_str("[runtime]\nkind = \"native\"").unwrap();
+        apply(&mut standard).unwrap();
+        assert!(standard.get("agent").is_none());
+        assert_eq!(
+            cache_capacity(STANDARD),
+            crate::idempotency::DEFAULT_CAPACITY
+        );
+
+        let mut unknown: Value = toml::from_str("[runtime]\nprofile = \"tiny\"").unwrap();
+        assert!(apply(&mut unknown).is_err());
+    }
+
+    #[test]
+    fn saving_leaves_the_preset_to_the_profile() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "default_temperature = 0.7\n\n[runtime]\nkind = \"native\"\nprofile = \"low_memory\"\n\n[agent]\nmax_concurrent_sessions = 2\n",
+        )
+        .unwrap();
+        let load = || {
+            let raw = std::fs::read_to_string(&path).unwrap();
+            let mut raw: Value = toml::from_str(&raw).unwrap();
+            let filled = apply(&mut raw).unwrap();
+            let mut config: Config = raw.try_into().unwrap();
+            config.config_path.clone_from(&path);
+            config.profile_defaults = filled;
+            config
+        };
+
+        let mut config = load();
+        assert_eq!(config.agent.session_queue_limit, 4);
+        config.agent.delegate.max_subtasks = 3;
+        config.save().unwrap();
+
+        let saved: Value = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        for (key, _) in low_memory_preset() {
+            let kept = [
+                "agent.max_concurrent_sessions",
+                "agent.delegate.max_subtasks",
+            ];
+            assert_eq!(get(&saved, key).is_some(), kept.contains(&key), "{key}");
+        }
+        assert_eq!(load().agent.delegate.max_subtasks, 3);
+
+        // Switching back to the standard profile gets the standard values.
+        let standard = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("profile = \"low_memory\"", "profile = \"standard\"");
+        std::fs::write(&path, standard).unwrap();
+        let config = load();
+        assert!(config.profile_defaults.is_empty());
+        assert_eq!(
+            config.agent.session_queue_limit,
+            Config::default().agent.session_queue_limit
+        );
+        assert_eq!(config.browser.enabled, Config::default().browser.enabled);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::security::AutonomyLevel;
+use crate::security::{AutonomyLevel, ToolClass};
 use anyhow::{Context, Result};
 use directories::UserDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -15,6 +16,12 @@ pub struct Config {
     /// Path to config.toml - computed from home, not serialized
     #[serde(skip)]
     pub config_path: PathBuf,
+    /// `secret:` references resolved at load; [`Config::save`] writes them back
+    #[serde(skip)]
+    pub secret_refs: Vec<crate::security::vault::SecretRef>,
+    /// Keys the `[runtime] profile` filled in at load; [`Config::save`] leaves them out
+    #[serde(skip)]
+    pub profile_defaults: Vec<(String, toml::Value)>,
     pub api_key: Option<String>,
     pub default_provider: Option<String>,
     pub default_model: Option<String>,
@@ -61,6 +68,97 @@ pub struct Config {
 
     #[serde(default)]
     pub identity: IdentityConfig,
+
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+
+    #[serde(default)]
+    pub agent: AgentConfig,
+
+    #[serde(default)]
+    pub guardrails: GuardrailsConfig,
+
+    /// Channel name → reply length and formatting constraints
+    #[serde(default)]
+    pub styles: BTreeMap<String, ChannelStyle>,
+
+    #[serde(default)]
+    pub triggers: TriggersConfig,
+
+    #[serde(default)]
+    pub presence: PresenceConfig,
+
+    #[serde(default)]
+    pub inbox: InboxConfig,
+
+    #[serde(default)]
+    pub household: HouseholdConfig,
+
+    #[serde(default)]
+    pub offline: OfflineConfig,
+
+    #[serde(default)]
+    pub eval: EvalConfig,
+
+    #[serde(default)]
+    pub aliases: AliasesConfig,
+
+    #[serde(default)]
+    pub ollama: OllamaConfig,
+
+    #[serde(default)]
+    pub tool_output: ToolOutputConfig,
+
+    #[serde(default)]
+    pub tool_middleware: ToolMiddlewareConfig,
+
+    #[serde(default)]
+    pub workspace_index: WorkspaceIndexConfig,
+
+    #[serde(default)]
+    pub skills: SkillsConfig,
+
+    #[serde(default)]
+    pub providers: ProvidersConfig,
+
+    #[serde(default)]
+    pub voice: VoiceConfig,
+
+    #[serde(default)]
+    pub oauth: OAuthConfig,
+
+    #[serde(default)]
+    pub federation: FederationConfig,
+
+    #[serde(default)]
+    pub goals: GoalsConfig,
+
+    #[serde(default)]
+    pub focus: FocusConfig,
+
+    #[serde(default)]
+    pub mcp: McpConfig,
+
+    #[serde(default)]
+    pub outbox: OutboxConfig,
+
+    #[serde(default)]
+    pub sync: SyncConfig,
+
+    #[serde(default)]
+    pub vision: VisionConfig,
+
+    #[serde(default)]
+    pub artifacts: ArtifactsConfig,
+
+    #[serde(default)]
+    pub experiment: ExperimentConfig,
+
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    #[serde(default)]
+    pub redis: RedisConfig,
 }
 
 // ── Identity (AIEOS / OpenClaw format) ──────────────────────────
@@ -95,6 +193,7 @@ impl Default for IdentityConfig {
 // ── Gateway security ─────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct GatewayConfig {
     /// Gateway port (default: 8080)
     #[serde(default = "default_gateway_port")]
@@ -111,193 +210,2251 @@ pub struct GatewayConfig {
     /// Paired bearer tokens (managed automatically, not user-edited)
     #[serde(default)]
     pub paired_tokens: Vec<String>,
+    /// Serve the admin dashboard at `/admin` (needs pairing; default: true)
+    #[serde(default = "default_true")]
+    pub dashboard: bool,
+    /// Serve a redacted, unauthenticated status page at `/status` (default: false)
+    #[serde(default)]
+    pub status_page: bool,
 }
 
 fn default_gateway_port() -> u16 {
     3000
 }
 
-fn default_gateway_host() -> String {
-    "127.0.0.1".into()
+fn default_gateway_host() -> String {
+    "127.0.0.1".into()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            port: default_gateway_port(),
+            host: default_gateway_host(),
+            require_pairing: true,
+            allow_public_bind: false,
+            paired_tokens: Vec::new(),
+            dashboard: true,
+            status_page: false,
+        }
+    }
+}
+
+// ── Composio (managed tool surface) ─────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposioConfig {
+    /// Enable Composio integration for 1000+ OAuth tools
+    #[serde(default)]
+    pub enabled: bool,
+    /// Composio API key (stored encrypted when secrets.encrypt = true)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Default entity ID for multi-user setups
+    #[serde(default = "default_entity_id")]
+    pub entity_id: String,
+}
+
+fn default_entity_id() -> String {
+    "default".into()
+}
+
+impl Default for ComposioConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_key: None,
+            entity_id: default_entity_id(),
+        }
+    }
+}
+
+// ── Secrets (encrypted credential store) ────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    /// Enable encryption for API keys and tokens in config.toml
+    #[serde(default = "default_true")]
+    pub encrypt: bool,
+    /// Where `zeroclaw secrets set` stores values that config.toml refers to
+    /// as `secret:<name>`: "auto", "keychain", "secret-service" or "file"
+    #[serde(default = "default_secrets_backend")]
+    pub backend: String,
+}
+
+fn default_secrets_backend() -> String {
+    "auto".into()
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            encrypt: true,
+            backend: default_secrets_backend(),
+        }
+    }
+}
+
+// ── Browser (friendly-service browsing only) ───────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BrowserConfig {
+    /// Enable `browser_open` tool (opens URLs in Brave without scraping)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Allowed domains for `browser_open` (exact or subdomain match)
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Browser session name (for agent-browser automation)
+    #[serde(default)]
+    pub session_name: Option<String>,
+    /// What drives the `browser` tool: the agent-browser CLI or headless
+    /// Chromium over `DevTools` (`cdp`)
+    #[serde(default)]
+    pub backend: BrowserBackend,
+    /// Chromium/Chrome binary for the `cdp` backend (default: first found on PATH)
+    #[serde(default)]
+    pub chromium_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserBackend {
+    #[default]
+    AgentBrowser,
+    Cdp,
+}
+
+// ── Weather API ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WeatherConfig {
+    /// WeatherAPI.com key used by the `weather_api` tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+// ── Integrations (third-party tool credentials) ─────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IntegrationsConfig {
+    /// Reddit search + keyword monitoring (`reddit` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reddit: Option<RedditConfig>,
+    /// GitLab issues, merge requests and pipelines (`gitlab` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitlab: Option<GitLabConfig>,
+    /// Bitbucket Cloud issues, pull requests and pipelines (`bitbucket` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitbucket: Option<BitbucketConfig>,
+    /// GitHub issues, pull requests and Actions runs (`github` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<GitHubConfig>,
+    /// Latest CI runs across configured repos (`ci_status` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<CiStatusConfig>,
+    /// `PagerDuty` incidents (`pagerduty` tool + `/incidents/pagerduty` webhook)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagerduty: Option<PagerDutyConfig>,
+    /// Opsgenie alerts (`opsgenie` tool + `/incidents/opsgenie` webhook)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opsgenie: Option<OpsgenieConfig>,
+    /// Where enriched page summaries from incident webhooks are posted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oncall: Option<OnCallConfig>,
+    /// Sentry issue triage (`sentry` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentry: Option<SentryConfig>,
+    /// `LaunchDarkly` flags (`launchdarkly` tool; toggles need approval unless autonomy is full)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launchdarkly: Option<LaunchDarklyConfig>,
+    /// Unleash flags (`unleash` tool; toggles need approval unless autonomy is full)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unleash: Option<UnleashConfig>,
+    /// AWS Cost Explorer daily spend (`cloud_cost` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aws_cost: Option<AwsCostConfig>,
+    /// GCP billing export in `BigQuery` (`cloud_cost` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gcp_billing: Option<GcpBillingConfig>,
+    /// Allowlisted secret lookups via the 1Password / Bitwarden CLI (`password_manager` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_manager: Option<PasswordManagerConfig>,
+    /// Terraform / `OpenTofu` plan summaries for workspace repos (`terraform_plan` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terraform: Option<TerraformConfig>,
+    /// iCalendar feeds (`calendar` tool + meeting prep briefs from the heartbeat)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<CalendarConfig>,
+    /// Brave Search, `SearXNG` or `DuckDuckGo` (`web_search` tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_search: Option<WebSearchConfig>,
+    /// Apple Shortcuts (`shortcuts` tool on macOS + `/shortcuts` gateway endpoint)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortcuts: Option<ShortcutsConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutsConfig {
+    /// Shortcuts the agent may run, by name
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// Kill `shortcuts run` after this many seconds
+    #[serde(default = "default_shortcuts_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Serve `POST /shortcuts` so shortcuts can send prompts to the agent
+    /// (only when the gateway requires pairing)
+    #[serde(default = "default_true")]
+    pub callback: bool,
+}
+
+fn default_shortcuts_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for ShortcutsConfig {
+    fn default() -> Self {
+        Self {
+            allowed: Vec::new(),
+            timeout_secs: default_shortcuts_timeout_secs(),
+            callback: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebSearchConfig {
+    /// "duckduckgo" (no key), "brave" or "searxng"
+    #[serde(default = "default_web_search_engine")]
+    pub engine: String,
+    /// Brave Search API key (falls back to `BRAVE_API_KEY`)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// `SearXNG` instance, e.g. `https://search.example.com`
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Results returned when the agent does not ask for a number
+    #[serde(default = "default_web_search_max_results")]
+    pub max_results: u64,
+    #[serde(default = "default_web_search_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_web_search_engine() -> String {
+    "duckduckgo".into()
+}
+
+fn default_web_search_max_results() -> u64 {
+    5
+}
+
+fn default_web_search_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for WebSearchConfig {
+    fn default() -> Self {
+        Self {
+            engine: default_web_search_engine(),
+            api_key: None,
+            url: None,
+            max_results: default_web_search_max_results(),
+            timeout_secs: default_web_search_timeout_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedditConfig {
+    /// User-Agent sent to Reddit (their API rules require a descriptive one)
+    #[serde(default = "default_reddit_user_agent")]
+    pub user_agent: String,
+    /// Subreddits watched by `action = "monitor"` when none are passed
+    #[serde(default)]
+    pub subreddits: Vec<String>,
+    /// Keywords watched by `action = "monitor"` when none are passed
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Minimum upvotes for a thread to count as notable
+    #[serde(default = "default_reddit_min_score")]
+    pub min_score: i64,
+}
+
+fn default_reddit_user_agent() -> String {
+    "zeroclaw:monitor:v0.1.0".into()
+}
+
+fn default_reddit_min_score() -> i64 {
+    2
+}
+
+impl Default for RedditConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: default_reddit_user_agent(),
+            subreddits: Vec::new(),
+            keywords: Vec::new(),
+            min_score: default_reddit_min_score(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabConfig {
+    /// Instance URL (default: <https://gitlab.com>; set for self-managed)
+    #[serde(default = "default_gitlab_base_url")]
+    pub base_url: String,
+    /// Personal or project access token (`read_api` scope is enough)
+    pub token: String,
+    /// Project path used when none is given, e.g. "group/project"
+    #[serde(default)]
+    pub default_project: Option<String>,
+}
+
+fn default_gitlab_base_url() -> String {
+    "https://gitlab.com".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitbucketConfig {
+    /// Bitbucket username (not email)
+    pub username: String,
+    /// App password with repository, issue, pull request and pipeline read scopes
+    pub app_password: String,
+    /// Repository used when none is given, e.g. "workspace/repo"
+    #[serde(default)]
+    pub default_repo: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubConfig {
+    /// API root (default: <https://api.github.com>; set for GitHub Enterprise)
+    #[serde(default = "default_github_api_url")]
+    pub api_url: String,
+    /// Personal access token (fine-grained tokens need issues, PRs and actions read access)
+    pub token: String,
+    /// Repository used when none is given, e.g. "owner/repo"
+    #[serde(default)]
+    pub default_repo: Option<String>,
+}
+
+fn default_github_api_url() -> String {
+    "https://api.github.com".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiStatusConfig {
+    /// Repos to check, prefixed with their forge: "github:owner/repo", "gitlab:group/project"
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Branch watched for red/green transitions
+    #[serde(default = "default_ci_branch")]
+    pub branch: String,
+    /// Slack incoming-webhook URL that receives "main is red" alerts from `action = "watch"`
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+}
+
+fn default_ci_branch() -> String {
+    "main".into()
+}
+
+impl Default for CiStatusConfig {
+    fn default() -> Self {
+        Self {
+            repos: Vec::new(),
+            branch: default_ci_branch(),
+            slack_webhook_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagerDutyConfig {
+    /// REST API token (Integrations → API Access Keys)
+    pub api_token: String,
+    /// Email of a `PagerDuty` user; required as the `From` header when acknowledging
+    pub from_email: String,
+    /// Events API v2 integration key used by `action = "trigger"`
+    #[serde(default)]
+    pub routing_key: Option<String>,
+    /// Webhook v3 signing secret for `X-PagerDuty-Signature` verification
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpsgenieConfig {
+    /// API integration key
+    pub api_key: String,
+    /// API root (default: <https://api.opsgenie.com>; EU accounts use api.eu.opsgenie.com)
+    #[serde(default = "default_opsgenie_api_url")]
+    pub api_url: String,
+    /// Shared secret expected in the `X-Webhook-Secret` header of outgoing webhooks
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+fn default_opsgenie_api_url() -> String {
+    "https://api.opsgenie.com".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnCallConfig {
+    /// Channel that receives page summaries: "slack", "telegram", "discord", …
+    pub channel: String,
+    /// Recipient on that channel (Slack channel ID, Telegram chat ID, …)
+    pub recipient: String,
+    /// Workspace-relative directory of runbooks matched against the paged service
+    #[serde(default = "default_runbook_dir")]
+    pub runbook_dir: String,
+}
+
+fn default_runbook_dir() -> String {
+    "runbooks".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryConfig {
+    /// Instance URL (default: <https://sentry.io>; set for self-hosted)
+    #[serde(default = "default_sentry_base_url")]
+    pub base_url: String,
+    /// Auth token with `event:read` and `project:read` scopes
+    pub auth_token: String,
+    /// Organization slug
+    pub organization: String,
+    /// Project slug; when unset, issues from every project in the organization are listed
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+fn default_sentry_base_url() -> String {
+    "https://sentry.io".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchDarklyConfig {
+    /// API root (default: <https://app.launchdarkly.com>)
+    #[serde(default = "default_launchdarkly_base_url")]
+    pub base_url: String,
+    /// API access token with writer role (reader is enough for list/get)
+    pub api_token: String,
+    /// Project key, e.g. "default"
+    pub project_key: String,
+    /// Environment whose targeting is read and toggled, e.g. "production"
+    pub environment_key: String,
+}
+
+fn default_launchdarkly_base_url() -> String {
+    "https://app.launchdarkly.com".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnleashConfig {
+    /// API root including `/api`, e.g. "<https://unleash.example.com/api>"
+    pub api_url: String,
+    /// Admin API token
+    pub api_token: String,
+    /// Project ID (default: "default")
+    #[serde(default = "default_unleash_project")]
+    pub project: String,
+    /// Environment whose flag state is read and toggled (default: "production")
+    #[serde(default = "default_unleash_environment")]
+    pub environment: String,
+}
+
+fn default_unleash_project() -> String {
+    "default".into()
+}
+
+fn default_unleash_environment() -> String {
+    "production".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AwsCostConfig {
+    /// IAM access key with `ce:GetCostAndUsage` (read-only)
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Session token when using temporary credentials
+    #[serde(default)]
+    pub session_token: Option<String>,
+    /// Cost Explorer region (default: "us-east-1", where the API lives)
+    #[serde(default = "default_aws_cost_region")]
+    pub region: String,
+}
+
+fn default_aws_cost_region() -> String {
+    "us-east-1".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcpBillingConfig {
+    /// Project that runs (and pays for) the `BigQuery` query
+    pub project_id: String,
+    /// Billing export table, e.g. "my-project.billing.gcp_billing_export_v1_XXXXXX"
+    pub billing_table: String,
+    /// OAuth access token; when unset, `gcloud auth print-access-token` is used
+    #[serde(default)]
+    pub access_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PasswordManagerBackend {
+    /// 1Password CLI (`op`)
+    #[serde(rename = "1password")]
+    OnePassword,
+    /// Bitwarden CLI (`bw`)
+    #[serde(rename = "bitwarden")]
+    Bitwarden,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordManagerConfig {
+    /// "1password" | "bitwarden"
+    pub backend: PasswordManagerBackend,
+    /// Items the agent may read, by exact name/ID (or `op://` reference).
+    /// Nothing outside this list is ever fetched, and the vault is never listed.
+    #[serde(default)]
+    pub allowed_items: Vec<String>,
+    /// 1Password vault to look items up in (optional)
+    #[serde(default)]
+    pub vault: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformConfig {
+    /// CLI to run: "tofu" (default) or "terraform"
+    #[serde(default = "default_terraform_binary")]
+    pub binary: String,
+    /// Kill `plan` after this many seconds
+    #[serde(default = "default_terraform_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Environment variable prefixes passed through for provider credentials
+    #[serde(default = "default_terraform_env_prefixes")]
+    pub env_prefixes: Vec<String>,
+}
+
+fn default_terraform_binary() -> String {
+    "tofu".into()
+}
+
+fn default_terraform_timeout_secs() -> u64 {
+    300
+}
+
+fn default_terraform_env_prefixes() -> Vec<String> {
+    ["TF_", "AWS_", "GOOGLE_", "CLOUDSDK_", "ARM_"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+impl Default for TerraformConfig {
+    fn default() -> Self {
+        Self {
+            binary: default_terraform_binary(),
+            timeout_secs: default_terraform_timeout_secs(),
+            env_prefixes: default_terraform_env_prefixes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    #[serde(default)]
+    pub calendars: Vec<CalendarFeed>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarFeed {
+    pub name: String,
+    /// iCalendar (.ics) URL, e.g. Google's "secret address in iCal format"
+    pub url: String,
+    /// Send a prep brief before each meeting (daemon with heartbeat enabled)
+    #[serde(default)]
+    pub prep_briefs: bool,
+    /// Minutes before the start that the brief is sent
+    #[serde(default = "default_brief_lead_minutes")]
+    pub lead_minutes: u32,
+    /// Channel that receives briefs: "telegram", "slack", …
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on that channel (chat ID, Slack channel ID, …)
+    #[serde(default)]
+    pub recipient: Option<String>,
+}
+
+fn default_brief_lead_minutes() -> u32 {
+    15
+}
+
+// ── Memory ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryConfig {
+    /// "sqlite" | "markdown" | "none". CLI sessions are saved here too.
+    pub backend: String,
+    /// Auto-save conversation context to memory
+    pub auto_save: bool,
+    /// Run memory/session hygiene (archiving + retention cleanup)
+    #[serde(default = "default_hygiene_enabled")]
+    pub hygiene_enabled: bool,
+    /// Archive daily/session files older than this many days
+    #[serde(default = "default_archive_after_days")]
+    pub archive_after_days: u32,
+    /// Purge archived files older than this many days
+    #[serde(default = "default_purge_after_days")]
+    pub purge_after_days: u32,
+    /// For sqlite backend: prune conversation rows older than this many days
+    #[serde(default = "default_conversation_retention_days")]
+    pub conversation_retention_days: u32,
+    /// Embedding provider: "none" | "openai" | "custom:URL"
+    #[serde(default = "default_embedding_provider")]
+    pub embedding_provider: String,
+    /// Embedding model name (e.g. "text-embedding-3-small")
+    #[serde(default = "default_embedding_model")]
+    pub embedding_model: String,
+    /// Embedding vector dimensions
+    #[serde(default = "default_embedding_dims")]
+    pub embedding_dimensions: usize,
+    /// Weight for vector similarity in hybrid search (0.0–1.0)
+    #[serde(default = "default_vector_weight")]
+    pub vector_weight: f64,
+    /// Weight for keyword BM25 in hybrid search (0.0–1.0)
+    #[serde(default = "default_keyword_weight")]
+    pub keyword_weight: f64,
+    /// Max embedding cache entries before LRU eviction
+    #[serde(default = "default_cache_size")]
+    pub embedding_cache_size: usize,
+    /// Max tokens per chunk for document splitting
+    #[serde(default = "default_chunk_size")]
+    pub chunk_max_tokens: usize,
+}
+
+fn default_embedding_provider() -> String {
+    "none".into()
+}
+fn default_hygiene_enabled() -> bool {
+    true
+}
+fn default_archive_after_days() -> u32 {
+    7
+}
+fn default_purge_after_days() -> u32 {
+    30
+}
+fn default_conversation_retention_days() -> u32 {
+    30
+}
+fn default_embedding_model() -> String {
+    "text-embedding-3-small".into()
+}
+fn default_embedding_dims() -> usize {
+    1536
+}
+fn default_vector_weight() -> f64 {
+    0.7
+}
+fn default_keyword_weight() -> f64 {
+    0.3
+}
+fn default_cache_size() -> usize {
+    10_000
+}
+fn default_chunk_size() -> usize {
+    512
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            backend: "sqlite".into(),
+            auto_save: true,
+            hygiene_enabled: default_hygiene_enabled(),
+            archive_after_days: default_archive_after_days(),
+            purge_after_days: default_purge_after_days(),
+            conversation_retention_days: default_conversation_retention_days(),
+            embedding_provider: default_embedding_provider(),
+            embedding_model: default_embedding_model(),
+            embedding_dimensions: default_embedding_dims(),
+            vector_weight: default_vector_weight(),
+            keyword_weight: default_keyword_weight(),
+            embedding_cache_size: default_cache_size(),
+            chunk_max_tokens: default_chunk_size(),
+        }
+    }
+}
+
+// ── Agent sessions ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentConfig {
+    /// Sessions (one per channel + sender) that may talk to the provider at
+    /// once. Messages within one session are always handled in order.
+    #[serde(default = "default_max_concurrent_sessions")]
+    pub max_concurrent_sessions: usize,
+    /// Messages a single session may have waiting before new ones are dropped.
+    #[serde(default = "default_session_queue_limit")]
+    pub session_queue_limit: usize,
+    /// Token budget for pinned notes, recent turns and recalled memories
+    /// packed in front of each message.
+    #[serde(default = "default_context_tokens")]
+    pub context_tokens: usize,
+    /// Times a turn may send a malformed tool call back for the model to fix
+    #[serde(default = "default_tool_call_repairs")]
+    pub tool_call_repairs: usize,
+    /// Record exactly what went into each prompt, for `zeroclaw debug prompt`
+    #[serde(default)]
+    pub trace_prompts: bool,
+    /// Sub-agents the `delegate` tool may spawn
+    #[serde(default)]
+    pub delegate: DelegateConfig,
+    /// Per-turn limits on slow and paid tool calls
+    #[serde(default)]
+    pub tool_budget: ToolBudgetConfig,
+}
+
+fn default_max_concurrent_sessions() -> usize {
+    4
+}
+
+fn default_session_queue_limit() -> usize {
+    16
+}
+
+fn default_context_tokens() -> usize {
+    2000
+}
+
+fn default_tool_call_repairs() -> usize {
+    2
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_sessions: default_max_concurrent_sessions(),
+            session_queue_limit: default_session_queue_limit(),
+            context_tokens: default_context_tokens(),
+            tool_call_repairs: default_tool_call_repairs(),
+            trace_prompts: false,
+            delegate: DelegateConfig::default(),
+            tool_budget: ToolBudgetConfig::default(),
+        }
+    }
+}
+
+/// Bounded sub-agents for parallel subtasks ("research these 5 topics").
+/// Each starts with an empty context and answers one subtask.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegateConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Subtasks one `delegate` call may run; they run in parallel
+    #[serde(default = "default_delegate_max_subtasks")]
+    pub max_subtasks: usize,
+    /// Provider calls each sub-agent may spend on tool calls
+    #[serde(default = "default_delegate_max_tool_rounds")]
+    pub max_tool_rounds: usize,
+    /// Seconds before an unfinished sub-agent is abandoned
+    #[serde(default = "default_delegate_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Tools sub-agents get; they never get `delegate` itself
+    #[serde(default = "default_delegate_tools")]
+    pub tools: Vec<String>,
+}
+
+fn default_delegate_max_subtasks() -> usize {
+    5
+}
+
+fn default_delegate_max_tool_rounds() -> usize {
+    4
+}
+
+fn default_delegate_timeout_secs() -> u64 {
+    180
+}
+
+fn default_delegate_tools() -> Vec<String> {
+    [
+        "web_search",
+        "file_read",
+        "fs_read",
+        "fs_list",
+        "memory_recall",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for DelegateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_subtasks: default_delegate_max_subtasks(),
+            max_tool_rounds: default_delegate_max_tool_rounds(),
+            timeout_secs: default_delegate_timeout_secs(),
+            tools: default_delegate_tools(),
+        }
+    }
+}
+
+/// What one turn may spend on tool calls. Metered calls (a billed search)
+/// cost 1 unit and expensive ones (sub-agents, peers) 5; a round of calls
+/// that would go over is rejected before any of it runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolBudgetConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Cost units per turn
+    #[serde(default = "default_tool_budget_cost_units")]
+    pub max_cost_units: u32,
+    /// Calls to slow tools (browser, terraform, sub-agents) per turn
+    #[serde(default = "default_tool_budget_slow_calls")]
+    pub max_slow_calls: u32,
+}
+
+fn default_tool_budget_cost_units() -> u32 {
+    10
+}
+
+fn default_tool_budget_slow_calls() -> u32 {
+    3
+}
+
+impl Default for ToolBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_cost_units: default_tool_budget_cost_units(),
+            max_slow_calls: default_tool_budget_slow_calls(),
+        }
+    }
+}
+
+// ── Guardrails ────────────────────────────────────────────────────
+
+/// Output policies checked before a reply is sent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuardrailsConfig {
+    /// Policies applied on channels not listed in `channels`
+    #[serde(default)]
+    pub default: Vec<String>,
+    /// Channel name → policies for its replies (replaces `default`)
+    #[serde(default)]
+    pub channels: BTreeMap<String, Vec<String>>,
+    /// Named rule sets; `secrets` is built in
+    #[serde(default)]
+    pub policies: BTreeMap<String, Vec<GuardrailRule>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GuardrailRule {
+    pub name: String,
+    pub kind: GuardrailKind,
+    /// Regexes, keywords/phrases, or example sentences for `semantic`
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub action: GuardrailAction,
+    /// `rewrite`: text substituted for each match (default "[redacted]")
+    #[serde(default)]
+    pub replacement: Option<String>,
+    /// `block`: reply sent instead of the response
+    #[serde(default)]
+    pub message: Option<String>,
+    /// `semantic`: minimum similarity to any example (default 0.82)
+    #[serde(default)]
+    pub threshold: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GuardrailKind {
+    /// Regular expressions
+    Regex,
+    /// Case-insensitive whole words or phrases
+    Keyword,
+    /// Embedding similarity to example sentences (needs `memory.embedding_provider`)
+    Semantic,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GuardrailAction {
+    /// Replace the whole response with the rule's message
+    #[default]
+    Block,
+    /// Replace only the matched text
+    Rewrite,
+}
+
+// ── Reply styles ──────────────────────────────────────────────────
+
+/// How replies on one channel should look. Each constraint is stated in the
+/// system prompt and enforced again on the generated text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ChannelStyle {
+    /// Cut replies after this many words
+    #[serde(default)]
+    pub max_words: Option<usize>,
+    /// Rewrite Markdown tables as bullet lists
+    #[serde(default)]
+    pub no_tables: bool,
+    /// Strip Markdown formatting (SMS, iMessage)
+    #[serde(default)]
+    pub plain_text: bool,
+    /// Ask for a "Sources:" list and collect cited links into one
+    #[serde(default)]
+    pub require_sources: bool,
+    /// Number the files and pages tools return and list the ones the reply
+    /// cites (file + line range or URL) under it
+    #[serde(default)]
+    pub citations: bool,
+    /// Free-form guidance appended to the prompt
+    #[serde(default)]
+    pub instructions: Option<String>,
+}
+
+// ── Event triggers ────────────────────────────────────────────────
+
+/// Local system events that wake the agent (run by the daemon).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggersConfig {
+    /// How often event sources are checked
+    #[serde(default = "default_trigger_poll_secs")]
+    pub poll_secs: u64,
+    #[serde(default)]
+    pub rules: Vec<TriggerRule>,
+}
+
+fn default_trigger_poll_secs() -> u64 {
+    10
+}
+
+impl Default for TriggersConfig {
+    fn default() -> Self {
+        Self {
+            poll_secs: default_trigger_poll_secs(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TriggerRule {
+    pub name: String,
+    pub event: TriggerEvent,
+    /// Prompt sent to the agent; `{placeholders}` are filled from the event
+    pub prompt: String,
+    /// `file_created`: folder to watch (`~` is expanded)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// `battery_low`: percentage at or below which the rule fires (default 20)
+    #[serde(default)]
+    pub threshold: Option<u8>,
+    /// Minimum time between two runs of this rule
+    #[serde(default)]
+    pub cooldown_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerEvent {
+    /// A file appeared in `path` — `{path}`, `{name}`
+    FileCreated,
+    /// A USB device was plugged in — `{device}`
+    UsbConnected,
+    /// A USB device was removed — `{device}`
+    UsbDisconnected,
+    /// Battery discharging at or below `threshold` — `{level}`
+    BatteryLow,
+    /// A network interface went up or down — `{interface}`, `{state}`
+    NetworkChange,
+    /// Presence detection saw the owner come home — `{since}`
+    OwnerArrived,
+    /// Presence detection decided the owner left — `{since}`
+    OwnerLeft,
+}
+
+// ── Presence ──────────────────────────────────────────────────────
+
+/// Whether the owner is home, detected from their devices (run by the daemon).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub method: PresenceMethod,
+    /// IP addresses (`ping`), IP or MAC addresses (`arp`), Bluetooth MACs (`bluetooth`)
+    #[serde(default)]
+    pub devices: Vec<String>,
+    /// `home_assistant`: instance URL, e.g. <http://homeassistant.local:8123>
+    #[serde(default)]
+    pub home_assistant_url: Option<String>,
+    /// `home_assistant`: long-lived access token
+    #[serde(default)]
+    pub home_assistant_token: Option<String>,
+    /// `home_assistant`: entity whose state is "home" when present, e.g. `person.alex`
+    #[serde(default)]
+    pub entity: Option<String>,
+    #[serde(default = "default_presence_poll_secs")]
+    pub poll_secs: u64,
+    /// Devices must be unseen this long before the owner counts as away
+    #[serde(default = "default_presence_away_after_mins")]
+    pub away_after_mins: u64,
+}
+
+fn default_presence_poll_secs() -> u64 {
+    60
+}
+
+fn default_presence_away_after_mins() -> u64 {
+    10
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            method: PresenceMethod::default(),
+            devices: Vec::new(),
+            home_assistant_url: None,
+            home_assistant_token: None,
+            entity: None,
+            poll_secs: default_presence_poll_secs(),
+            away_after_mins: default_presence_away_after_mins(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceMethod {
+    /// ICMP ping to each device
+    #[default]
+    Ping,
+    /// Look devices up in the ARP table
+    Arp,
+    /// `hcitool name` against each Bluetooth address
+    Bluetooth,
+    /// A Home Assistant `person` or `device_tracker` entity
+    HomeAssistant,
+}
+
+// ── Inbox triage ──────────────────────────────────────────────────
+
+/// Inbox-zero workflow over the `[channels_config.email]` mailbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxConfig {
+    /// Triage new mail from the daemon
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_inbox_poll_secs")]
+    pub poll_secs: u64,
+    /// System prompt for the triage assistant (a built-in one is used when unset)
+    #[serde(default)]
+    pub persona: Option<String>,
+    /// Where a summary of each pass with new drafts or urgent mail is sent
+    #[serde(default)]
+    pub notify_channel: Option<String>,
+    #[serde(default)]
+    pub notify_recipient: Option<String>,
+    /// Label name → what belongs there and what to do with it
+    #[serde(default = "default_inbox_labels")]
+    pub labels: BTreeMap<String, InboxLabel>,
+}
+
+fn default_inbox_poll_secs() -> u64 {
+    300
+}
+
+fn default_inbox_labels() -> BTreeMap<String, InboxLabel> {
+    let label = |rules: &str| InboxLabel {
+        rules: rules.into(),
+        ..InboxLabel::default()
+    };
+    BTreeMap::from([
+        (
+            "urgent".into(),
+            InboxLabel {
+                flag: true,
+                draft_reply: true,
+                notify: true,
+                ..label("Needs the owner today: time-sensitive requests, outages, deadlines, close contacts")
+            },
+        ),
+        (
+            "action".into(),
+            InboxLabel {
+                draft_reply: true,
+                ..label("Asks the owner to reply, decide or do something, but can wait")
+            },
+        ),
+        (
+            "newsletter".into(),
+            InboxLabel {
+                folder: Some("Newsletters".into()),
+                mark_read: true,
+                ..label("Newsletters, marketing, receipts, automated notifications and digests")
+            },
+        ),
+        (
+            "spam".into(),
+            InboxLabel {
+                folder: Some("Junk".into()),
+                mark_read: true,
+                ..label("Unsolicited offers, phishing and scams")
+            },
+        ),
+    ])
+}
+
+impl Default for InboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_secs: default_inbox_poll_secs(),
+            persona: None,
+            notify_channel: None,
+            notify_recipient: None,
+            labels: default_inbox_labels(),
+        }
+    }
+}
+
+/// Each flag is an independent action taken on matching mail.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InboxLabel {
+    /// What belongs under this label, in plain words for the classifier
+    pub rules: String,
+    /// Move matching mail to this IMAP folder; it stays in the inbox when unset
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub flag: bool,
+    #[serde(default)]
+    pub mark_read: bool,
+    /// Draft a reply and queue it for approval
+    #[serde(default)]
+    pub draft_reply: bool,
+    /// Mention matching mail in the pass summary
+    #[serde(default)]
+    pub notify: bool,
+}
+
+// ── Household ─────────────────────────────────────────────────────
+
+/// Known people who message the agent, keyed by a short name (`sam`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HouseholdConfig {
+    #[serde(default)]
+    pub members: BTreeMap<String, HouseholdMember>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HouseholdMember {
+    /// How the agent addresses them (defaults to the key)
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Sender handles as `channel:sender`, e.g. `telegram:123456789`
+    #[serde(default)]
+    pub handles: Vec<String>,
+    /// Profile notes shown to the agent when this person writes
+    #[serde(default)]
+    pub about: Option<String>,
+    #[serde(default)]
+    pub role: MemberRole,
+    /// Channels to reach them on, most preferred first
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// Language to reply in, e.g. `de`; overrides what the platform reports
+    #[serde(default)]
+    pub language: Option<String>,
+    /// IANA timezone, e.g. `Europe/Berlin`; overrides what the platform reports
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Which memories a member's conversations can draw on.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberRole {
+    /// Everything, including other members' memories
+    Owner,
+    /// Shared memories and their own
+    #[default]
+    Member,
+    /// Only their own
+    Guest,
+}
+
+// ── Offline mode ──────────────────────────────────────────────────
+
+/// What `zeroclaw agent` does when there is no network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineConfig {
+    /// Check connectivity at startup and fall back when offline
+    #[serde(default = "default_true")]
+    pub auto: bool,
+    /// Local provider to use offline, e.g. `ollama` or `custom:http://localhost:8080` (llama.cpp)
+    #[serde(default = "default_offline_provider")]
+    pub provider: String,
+    #[serde(default = "default_offline_model")]
+    pub model: String,
+    /// `host:port` addresses tried over TCP; any answer means online
+    #[serde(default = "default_offline_probes")]
+    pub probes: Vec<String>,
+    #[serde(default = "default_offline_probe_timeout_ms")]
+    pub probe_timeout_ms: u64,
+}
+
+fn default_offline_provider() -> String {
+    "ollama".into()
+}
+
+fn default_offline_model() -> String {
+    "llama3.2".into()
+}
+
+fn default_offline_probes() -> Vec<String> {
+    vec!["1.1.1.1:443".into(), "8.8.8.8:53".into()]
+}
+
+fn default_offline_probe_timeout_ms() -> u64 {
+    1500
+}
+
+impl Default for OfflineConfig {
+    fn default() -> Self {
+        Self {
+            auto: true,
+            provider: default_offline_provider(),
+            model: default_offline_model(),
+            probes: default_offline_probes(),
+            probe_timeout_ms: default_offline_probe_timeout_ms(),
+        }
+    }
+}
+
+// ── Model-quality canaries ────────────────────────────────────────
+
+/// Canary prompts run on a schedule to catch silent model regressions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalConfig {
+    /// Run the canaries from the daemon
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cron expression for the run (default: nightly at 03:00 UTC)
+    #[serde(default = "default_eval_schedule")]
+    pub schedule: String,
+    /// Sampling temperature; keep it low so results are comparable run to run
+    #[serde(default)]
+    pub temperature: f64,
+    /// Where regression alerts go (`@name` reaches a household member)
+    #[serde(default)]
+    pub notify_channel: Option<String>,
+    #[serde(default)]
+    pub notify_recipient: Option<String>,
+    /// Checks to run; a built-in set is used when empty
+    #[serde(default)]
+    pub canaries: Vec<EvalCanary>,
+}
+
+fn default_eval_schedule() -> String {
+    "0 3 * * *".into()
+}
+
+impl Default for EvalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: default_eval_schedule(),
+            temperature: 0.0,
+            notify_channel: None,
+            notify_recipient: None,
+            canaries: Vec::new(),
+        }
+    }
+}
+
+/// One prompt and the properties its reply must have.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvalCanary {
+    pub name: String,
+    pub prompt: String,
+    pub system: Option<String>,
+    /// Case-insensitive substrings the reply must contain
+    pub expect_contains: Vec<String>,
+    pub expect_not_contains: Vec<String>,
+    /// Regex the whole reply must match somewhere
+    pub expect_regex: Option<String>,
+    /// Reply must parse as JSON (code fences are ignored)
+    pub expect_json: bool,
+    pub max_chars: Option<usize>,
+    pub max_latency_ms: Option<u64>,
+}
+
+// ── Slash-command aliases ─────────────────────────────────────────
+
+/// Short chat commands, keyed by name: `[aliases.standup]` answers `/standup`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AliasesConfig {
+    #[serde(flatten)]
+    pub commands: BTreeMap<String, CommandAlias>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CommandAlias {
+    /// Shown by `zeroclaw prompts list`
+    pub description: Option<String>,
+    /// Prompt template under `prompts/` to run
+    pub template: Option<String>,
+    /// Inline template text, instead of `template`
+    pub prompt: Option<String>,
+    /// Fixed variables; `key=value` arguments override them
+    pub vars: BTreeMap<String, String>,
+    /// Tools the agent should stick to for this command
+    pub tools: Vec<String>,
+    /// How the agent should act for this command
+    pub persona: Option<String>,
+}
+
+// ── Local model sizing ────────────────────────────────────────────
+
+/// Fitting Ollama models to the machine's memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    /// Swap in a smaller installed variant when the chosen model will not fit
+    /// (`false` only warns)
+    #[serde(default = "default_true")]
+    pub auto_select: bool,
+    /// Memory budget in GiB for models; detected from VRAM/RAM when unset
+    #[serde(default)]
+    pub memory_gb: Option<f64>,
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        Self {
+            auto_select: true,
+            memory_gb: None,
+        }
+    }
+}
+
+// ── Tool output ───────────────────────────────────────────────────
+
+/// What happens to a tool result longer than its limit.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolOutputMode {
+    /// Condense it with `summary_model`; windowed when no model is set or
+    /// the result is barely over the limit
+    #[default]
+    Summarize,
+    /// Keep the start and the end, drop the middle
+    Window,
+    /// Pass it through untouched
+    Full,
+}
+
+/// Limits on tool results fed back into the agent's context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolOutputConfig {
+    /// Characters a tool result may take before it is shortened
+    #[serde(default = "default_tool_output_max_chars")]
+    pub max_chars: usize,
+    #[serde(default)]
+    pub mode: ToolOutputMode,
+    /// Cheap model (same provider) for summaries, e.g. `anthropic/claude-3-haiku`
+    #[serde(default)]
+    pub summary_model: Option<String>,
+    /// Per-tool overrides, keyed by tool name
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolOutputRule>,
+}
+
+/// Override of [`ToolOutputConfig`] for one tool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolOutputRule {
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+    #[serde(default)]
+    pub mode: Option<ToolOutputMode>,
+}
+
+fn default_tool_output_max_chars() -> usize {
+    8000
+}
+
+impl Default for ToolOutputConfig {
+    fn default() -> Self {
+        Self {
+            max_chars: default_tool_output_max_chars(),
+            mode: ToolOutputMode::default(),
+            summary_model: None,
+            tools: BTreeMap::new(),
+        }
+    }
+}
+
+impl ToolOutputConfig {
+    /// Limit and mode for `tool`, with its override applied.
+    pub fn rule_for(&self, tool: &str) -> (usize, ToolOutputMode) {
+        let rule = self.tools.get(tool);
+        (
+            rule.and_then(|r| r.max_chars).unwrap_or(self.max_chars),
+            rule.and_then(|r| r.mode).unwrap_or(self.mode),
+        )
+    }
+}
+
+// ── Tool middleware ───────────────────────────────────────────────
+
+/// Checks run around every tool call (see `tools::middleware`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolMiddlewareConfig {
+    /// Reject calls whose arguments do not match the tool's schema
+    #[serde(default = "default_true")]
+    pub validate_args: bool,
+    /// Append every call to `audit/tools.jsonl` in the workspace
+    #[serde(default)]
+    pub audit_log: bool,
+    /// Calls per minute allowed for a tool, keyed by tool name
+    #[serde(default)]
+    pub rate_limits: BTreeMap<String, u32>,
+    /// Commit workspace changes from each tool call to `~/.zeroclaw/history/`
+    /// (`zeroclaw undo` reverts them)
+    #[serde(default = "default_true")]
+    pub workspace_history: bool,
+}
+
+impl Default for ToolMiddlewareConfig {
+    fn default() -> Self {
+        Self {
+            validate_args: true,
+            audit_log: false,
+            rate_limits: BTreeMap::new(),
+            workspace_history: true,
+        }
+    }
+}
+
+// ── Workspace index ───────────────────────────────────────────────
+
+/// Search index of workspace files (see `workspace_index`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceIndexConfig {
+    /// Offer `fs_search` and keep the index fresh from the daemon
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How often the daemon checks the workspace for changed files
+    #[serde(default = "default_index_watch_secs")]
+    pub watch_interval_secs: u64,
+    /// Files larger than this are not indexed
+    #[serde(default = "default_index_max_file_kb")]
+    pub max_file_kb: u64,
+}
+
+fn default_index_watch_secs() -> u64 {
+    30
+}
+
+fn default_index_max_file_kb() -> u64 {
+    512
+}
+
+impl Default for WorkspaceIndexConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            watch_interval_secs: default_index_watch_secs(),
+            max_file_kb: default_index_max_file_kb(),
+        }
+    }
+}
+
+// ── Skills ────────────────────────────────────────────────────────
+
+/// Quarantine for newly installed skills (see `skills::trust`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillsConfig {
+    /// Simulate and log the tool calls of untrusted skills instead of running them
+    #[serde(default = "default_true")]
+    pub quarantine: bool,
+    /// Days after install before a skill is trusted automatically (0 = never)
+    #[serde(default = "default_quarantine_days")]
+    pub quarantine_days: u32,
+    /// Simulated calls before a skill is trusted automatically (0 = never)
+    #[serde(default = "default_quarantine_invocations")]
+    pub quarantine_invocations: u32,
+    /// `wasmtime` binary that runs wasm skills
+    #[serde(default = "default_wasmtime")]
+    pub wasmtime: String,
+    /// Wasm skill calls are killed after this long
+    #[serde(default = "default_wasm_timeout_secs")]
+    pub wasm_timeout_secs: u64,
+    /// Linear memory limit per wasm skill call (0 = wasmtime's default)
+    #[serde(default = "default_wasm_memory_mb")]
+    pub wasm_memory_mb: u64,
+}
+
+fn default_quarantine_days() -> u32 {
+    7
+}
+
+fn default_quarantine_invocations() -> u32 {
+    20
+}
+
+fn default_wasmtime() -> String {
+    "wasmtime".into()
+}
+
+fn default_wasm_timeout_secs() -> u64 {
+    30
+}
+
+fn default_wasm_memory_mb() -> u64 {
+    64
+}
+
+impl Default for SkillsConfig {
+    fn default() -> Self {
+        Self {
+            quarantine: true,
+            quarantine_days: default_quarantine_days(),
+            quarantine_invocations: default_quarantine_invocations(),
+            wasmtime: default_wasmtime(),
+            wasm_timeout_secs: default_wasm_timeout_secs(),
+            wasm_memory_mb: default_wasm_memory_mb(),
+        }
+    }
+}
+
+// ── Providers ─────────────────────────────────────────────────────
+
+/// Settings shared by all providers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProvidersConfig {
+    #[serde(default)]
+    pub fallback: FallbackConfig,
+}
+
+/// Providers to fall back on when the default one is rate limited, failing
+/// or too slow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackConfig {
+    /// Tried in order after `default_provider`
+    #[serde(default)]
+    pub chain: Vec<FallbackProvider>,
+    /// An attempt that takes longer counts as failed (0 = no limit)
+    #[serde(default = "default_fallback_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_fallback_timeout_secs() -> u64 {
+    180
+}
+
+impl Default for FallbackConfig {
+    fn default() -> Self {
+        Self {
+            chain: Vec::new(),
+            timeout_secs: default_fallback_timeout_secs(),
+        }
+    }
+}
+
+/// One provider in the fallback chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackProvider {
+    pub provider: String,
+    /// Model to ask this provider for; default: the requested model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Default: the provider's own env var, or `api_key` for the default provider
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+// ── Voice ─────────────────────────────────────────────────────────
+
+/// Speech-to-text and text-to-speech for voice notes (see `voice`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceConfig {
+    /// Transcribe incoming voice notes before the agent sees them
+    #[serde(default)]
+    pub transcribe: bool,
+    /// Who transcribes: `"openai"` (`api_url`), `"groq"` (Groq's Whisper,
+    /// `GROQ_API_KEY`) or `"whisper_cpp"` (local binary, no network)
+    #[serde(default = "default_stt_provider")]
+    pub stt_provider: String,
+    /// whisper.cpp CLI for `stt_provider = "whisper_cpp"`
+    #[serde(default = "default_whisper_cpp_bin")]
+    pub whisper_cpp_bin: String,
+    /// ggml model file for whisper.cpp, e.g. `~/models/ggml-base.bin`
+    #[serde(default)]
+    pub whisper_cpp_model: Option<String>,
+    /// OpenAI-compatible base URL serving `/audio/transcriptions` and `/audio/speech`
+    #[serde(default = "default_voice_api_url")]
+    pub api_url: String,
+    /// Default: `OPENAI_API_KEY`
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default = "default_stt_model")]
+    pub stt_model: String,
+    #[serde(default = "default_tts_model")]
+    pub tts_model: String,
+    /// Voice name, or the voice ID for `ElevenLabs`
+    #[serde(default = "default_tts_voice")]
+    pub tts_voice: String,
+    /// Who speaks replies: `"openai"` (`api_url`), `"elevenlabs"`
+    /// (`ELEVENLABS_API_KEY`) or `"piper"` (local binary, no network)
+    #[serde(default = "default_tts_provider")]
+    pub tts_provider: String,
+    /// piper CLI for `tts_provider = "piper"`
+    #[serde(default = "default_piper_bin")]
+    pub piper_bin: String,
+    /// piper voice model, e.g. `~/voices/en_US-lessac-medium.onnx`
+    #[serde(default)]
+    pub piper_model: Option<String>,
+    /// Offer the `speak` tool, so the agent can answer with a voice message
+    /// on channels that send them
+    #[serde(default)]
+    pub speak_tool: bool,
+}
+
+fn default_voice_api_url() -> String {
+    "https://api.openai.com/v1".into()
+}
+
+fn default_stt_provider() -> String {
+    "openai".into()
+}
+
+fn default_whisper_cpp_bin() -> String {
+    "whisper-cli".into()
+}
+
+fn default_stt_model() -> String {
+    "whisper-1".into()
+}
+
+fn default_tts_model() -> String {
+    "tts-1".into()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".into()
+}
+
+fn default_tts_provider() -> String {
+    "openai".into()
+}
+
+fn default_piper_bin() -> String {
+    "piper".into()
+}
+
+impl Default for VoiceConfig {
+    fn default() -> Self {
+        Self {
+            transcribe: false,
+            stt_provider: default_stt_provider(),
+            whisper_cpp_bin: default_whisper_cpp_bin(),
+            whisper_cpp_model: None,
+            api_url: default_voice_api_url(),
+            api_key: None,
+            stt_model: default_stt_model(),
+            tts_model: default_tts_model(),
+            tts_voice: default_tts_voice(),
+            tts_provider: default_tts_provider(),
+            piper_bin: default_piper_bin(),
+            piper_model: None,
+            speak_tool: false,
+        }
+    }
+}
+
+// ── Vision ────────────────────────────────────────────────────────
+
+/// Photos received over chat, passed to vision-capable models (see `vision`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisionConfig {
+    /// Download photos from Telegram, Discord and iMessage for the model
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Images per message; the rest are dropped
+    #[serde(default = "default_vision_max_images")]
+    pub max_images: usize,
+    /// Longest side in pixels; larger images are downscaled with ffmpeg
+    #[serde(default = "default_vision_max_dimension")]
+    pub max_dimension: u32,
+    /// Size cap per image after downscaling, in KiB
+    #[serde(default = "default_vision_max_image_kb")]
+    pub max_image_kb: usize,
+}
+
+fn default_vision_max_images() -> usize {
+    4
+}
+
+fn default_vision_max_dimension() -> u32 {
+    1568
+}
+
+fn default_vision_max_image_kb() -> usize {
+    1024
+}
+
+impl Default for VisionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_images: default_vision_max_images(),
+            max_dimension: default_vision_max_dimension(),
+            max_image_kb: default_vision_max_image_kb(),
+        }
+    }
+}
+
+// ── OAuth ─────────────────────────────────────────────────────────
+
+/// OAuth clients that integrations sign in with (see `oauth`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthConfig {
+    /// Where providers send the browser back to: the gateway's
+    /// `/oauth/callback`. Register it with each provider.
+    #[serde(default = "default_oauth_redirect_url")]
+    pub redirect_url: String,
+    /// `[oauth.google]`, `[oauth.spotify]`, … by provider name
+    #[serde(flatten)]
+    pub clients: BTreeMap<String, OAuthClientConfig>,
+}
+
+fn default_oauth_redirect_url() -> String {
+    "http://127.0.0.1:8080/oauth/callback".into()
+}
+
+impl Default for OAuthConfig {
+    fn default() -> Self {
+        Self {
+            redirect_url: default_oauth_redirect_url(),
+            clients: BTreeMap::new(),
+        }
+    }
+}
+
+/// One OAuth client registration. Endpoints default to the provider's own
+/// for `google`, `spotify`, `notion` and `strava`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OAuthClientConfig {
+    pub client_id: String,
+    /// Not needed for public clients using PKCE
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub auth_url: Option<String>,
+    #[serde(default)]
+    pub token_url: Option<String>,
+    /// Device authorization endpoint, for signing in without a browser here
+    #[serde(default)]
+    pub device_url: Option<String>,
+}
+
+// ── Federation (ZeroClaw-to-ZeroClaw) ─────────────────────────────
+
+/// Other instances this one exchanges messages with (see `federation`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationConfig {
+    /// This instance's name, as its peers list it (default: "zeroclaw")
+    #[serde(default = "default_federation_name")]
+    pub name: String,
+    /// `[federation.peers.<name>]` — gateway URL and shared token per peer
+    #[serde(default)]
+    pub peers: BTreeMap<String, FederationPeerConfig>,
+    /// How long a task delegated by a peer may run here (default: 300)
+    #[serde(default = "default_federation_task_timeout_secs")]
+    pub task_timeout_secs: u64,
+}
+
+fn default_federation_name() -> String {
+    "zeroclaw".into()
+}
+
+fn default_federation_task_timeout_secs() -> u64 {
+    300
+}
+
+impl Default for FederationConfig {
+    fn default() -> Self {
+        Self {
+            name: default_federation_name(),
+            peers: BTreeMap::new(),
+            task_timeout_secs: default_federation_task_timeout_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationPeerConfig {
+    /// The peer's gateway, e.g. `http://homeserver.lan:3000`
+    pub url: String,
+    /// Shared secret; both sides configure the same value for each other
+    pub token: String,
+    /// Let this peer run full agent turns (tools included) here
+    #[serde(default)]
+    pub allow_tasks: bool,
+}
+
+// ── Goals ─────────────────────────────────────────────────────────
+
+/// Limits on what heartbeat goal checks may do (see `goals`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalsConfig {
+    /// Times one goal may act in 24 hours before checks only report (default: 3)
+    #[serde(default = "default_goal_max_actions_per_day")]
+    pub max_actions_per_day: u32,
+}
+
+fn default_goal_max_actions_per_day() -> u32 {
+    3
+}
+
+impl Default for GoalsConfig {
+    fn default() -> Self {
+        Self {
+            max_actions_per_day: default_goal_max_actions_per_day(),
+        }
+    }
+}
+
+// ── Focus ─────────────────────────────────────────────────────────
+
+/// Do-not-disturb sessions (see `focus`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FocusConfig {
+    /// Sources that still get through while focusing: `briefs`, `inbox`,
+    /// `eval` or `heartbeat`
+    #[serde(default)]
+    pub urgent: Vec<String>,
+}
+
+// ── MCP ───────────────────────────────────────────────────────────
+
+/// Model Context Protocol servers whose tools the agent gets (see `mcp`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpConfig {
+    /// Servers by name; their tools appear as `<name>__<tool>`
+    #[serde(default)]
+    pub servers: BTreeMap<String, McpServerConfig>,
+}
+
+/// One MCP server: a `command` to run over stdio, or an SSE `url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Program to start, e.g. `npx`
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment for the process
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// SSE endpoint of a remote server
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra HTTP headers for `url`, e.g. `Authorization`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Seconds to wait for the server to start or answer a call
+    #[serde(default = "default_mcp_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_mcp_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for McpServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            command: None,
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            url: None,
+            headers: BTreeMap::new(),
+            timeout_secs: default_mcp_timeout_secs(),
+        }
+    }
+}
+
+// ── Outbox ────────────────────────────────────────────────────────
+
+/// Durable delivery of messages the agent decides to send (see `outbox`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxConfig {
+    /// Delivery attempts before a message moves to the dead-letter queue
+    #[serde(default = "default_outbox_max_attempts")]
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles with every attempt
+    #[serde(default = "default_outbox_retry_base_secs")]
+    pub retry_base_secs: u64,
+    /// The same message to the same recipient within this window is sent once
+    #[serde(default = "default_outbox_dedupe_window_secs")]
+    pub dedupe_window_secs: u64,
+    /// How often the daemon's dispatcher looks for due messages
+    #[serde(default = "default_outbox_poll_secs")]
+    pub poll_secs: u64,
+}
+
+fn default_outbox_max_attempts() -> u32 {
+    5
+}
+
+fn default_outbox_retry_base_secs() -> u64 {
+    30
+}
+
+fn default_outbox_dedupe_window_secs() -> u64 {
+    3600
+}
+
+fn default_outbox_poll_secs() -> u64 {
+    15
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_outbox_max_attempts(),
+            retry_base_secs: default_outbox_retry_base_secs(),
+            dedupe_window_secs: default_outbox_dedupe_window_secs(),
+            poll_secs: default_outbox_poll_secs(),
+        }
+    }
+}
+
+// ── Sync ──────────────────────────────────────────────────────────
+
+/// End-to-end-encrypted sync of memories, sessions and config between
+/// devices (see `sync`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// "folder" (Syncthing, Dropbox, a network share), "webdav" or "s3"
+    #[serde(default = "default_sync_backend")]
+    pub backend: String,
+    /// Shared folder for the `folder` backend
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Collection URL for `webdav`, e.g. `https://cloud.example.com/remote.php/dav/files/me/zeroclaw/`
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Bucket for `s3`
+    #[serde(default)]
+    pub bucket: Option<String>,
+    #[serde(default = "default_sync_region")]
+    pub region: String,
+    /// S3-compatible endpoint (`MinIO`, R2, B2); default is AWS for `region`
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Key prefix inside the bucket
+    #[serde(default = "default_sync_prefix")]
+    pub prefix: String,
+    /// Falls back to `AWS_ACCESS_KEY_ID`
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    /// Falls back to `AWS_SECRET_ACCESS_KEY`
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// What to share: any of "memory", "sessions", "config"
+    #[serde(default = "default_sync_include")]
+    pub include: Vec<String>,
+    /// How often the daemon syncs
+    #[serde(default = "default_sync_interval_minutes")]
+    pub interval_minutes: u64,
+}
+
+fn default_sync_backend() -> String {
+    "folder".into()
+}
+
+fn default_sync_region() -> String {
+    "us-east-1".into()
+}
+
+fn default_sync_prefix() -> String {
+    "zeroclaw/".into()
+}
+
+fn default_sync_include() -> Vec<String> {
+    vec!["memory".into(), "sessions".into(), "config".into()]
+}
+
+fn default_sync_interval_minutes() -> u64 {
+    15
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_sync_backend(),
+            folder: None,
+            url: None,
+            username: None,
+            password: None,
+            bucket: None,
+            region: default_sync_region(),
+            endpoint: None,
+            prefix: default_sync_prefix(),
+            access_key_id: None,
+            secret_access_key: None,
+            include: default_sync_include(),
+            interval_minutes: default_sync_interval_minutes(),
+        }
+    }
+}
+
+// ── Artifacts ─────────────────────────────────────────────────────
+
+/// An S3-compatible bucket for large tool outputs, screenshots and files
+/// the agent shares by link (see `artifacts`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub bucket: Option<String>,
+    #[serde(default = "default_sync_region")]
+    pub region: String,
+    /// S3-compatible endpoint (`MinIO`, R2, B2); default is AWS for `region`
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Key prefix inside the bucket
+    #[serde(default = "default_artifacts_prefix")]
+    pub prefix: String,
+    /// Falls back to `AWS_ACCESS_KEY_ID`
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    /// Falls back to `AWS_SECRET_ACCESS_KEY`
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// Public base URL of the prefix (a public bucket or CDN); without it,
+    /// links are presigned and expire
+    #[serde(default)]
+    pub public_url: Option<String>,
+    /// How long presigned links work; at most 168 (7 days)
+    #[serde(default = "default_artifacts_url_expiry_hours")]
+    pub url_expiry_hours: u64,
+}
+
+fn default_artifacts_prefix() -> String {
+    "artifacts/".into()
 }
 
-fn default_true() -> bool {
-    true
+fn default_artifacts_url_expiry_hours() -> u64 {
+    168
 }
 
-impl Default for GatewayConfig {
+impl Default for ArtifactsConfig {
     fn default() -> Self {
         Self {
-            port: default_gateway_port(),
-            host: default_gateway_host(),
-            require_pairing: true,
-            allow_public_bind: false,
-            paired_tokens: Vec::new(),
+            enabled: false,
+            bucket: None,
+            region: default_sync_region(),
+            endpoint: None,
+            prefix: default_artifacts_prefix(),
+            access_key_id: None,
+            secret_access_key: None,
+            public_url: None,
+            url_expiry_hours: default_artifacts_url_expiry_hours(),
         }
     }
 }
 
-// ── Composio (managed tool surface) ─────────────────────────────
+// ── Experiments ───────────────────────────────────────────────────
 
+/// A/B test of a candidate model, provider or persona on a share of
+/// interactive turns (see `experiments`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ComposioConfig {
-    /// Enable Composio integration for 1000+ OAuth tools
+pub struct ExperimentConfig {
     #[serde(default)]
     pub enabled: bool,
-    /// Composio API key (stored encrypted when secrets.encrypt = true)
+    /// Names the turn log; start a new name for a new comparison
+    #[serde(default = "default_experiment_name")]
+    pub name: String,
+    /// Percent of turns that go to the candidate
+    #[serde(default = "default_experiment_share_percent")]
+    pub share_percent: u8,
+    /// Candidate provider; default is the configured one
     #[serde(default)]
-    pub api_key: Option<String>,
-    /// Default entity ID for multi-user setups
-    #[serde(default = "default_entity_id")]
-    pub entity_id: String,
+    pub provider: Option<String>,
+    /// Candidate model; default is the configured one
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Added to the candidate's system prompt
+    #[serde(default)]
+    pub persona: Option<String>,
+    /// USD per million tokens, for the report's cost line
+    #[serde(default)]
+    pub control_prices: Option<TokenPrices>,
+    #[serde(default)]
+    pub candidate_prices: Option<TokenPrices>,
 }
 
-fn default_entity_id() -> String {
+/// USD per million input and output tokens.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct TokenPrices {
+    pub input: f64,
+    pub output: f64,
+}
+
+fn default_experiment_name() -> String {
     "default".into()
 }
 
-impl Default for ComposioConfig {
+fn default_experiment_share_percent() -> u8 {
+    10
+}
+
+impl Default for ExperimentConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            api_key: None,
-            entity_id: default_entity_id(),
+            name: default_experiment_name(),
+            share_percent: default_experiment_share_percent(),
+            provider: None,
+            model: None,
+            persona: None,
+            control_prices: None,
+            candidate_prices: None,
         }
     }
 }
 
-// ── Secrets (encrypted credential store) ────────────────────────
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SecretsConfig {
-    /// Enable encryption for API keys and tokens in config.toml
-    #[serde(default = "default_true")]
-    pub encrypt: bool,
-}
-
-impl Default for SecretsConfig {
-    fn default() -> Self {
-        Self { encrypt: true }
-    }
+// ── Notifications ─────────────────────────────────────────────────
+
+/// How important an alert is. Ordered, so rules can match "this or worse".
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    /// Goes out during quiet hours and focus sessions
+    Critical,
 }
 
-// ── Browser (friendly-service browsing only) ───────────────────
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct BrowserConfig {
-    /// Enable `browser_open` tool (opens URLs in Brave without scraping)
+/// Where proactive alerts go and when they may interrupt (see
+/// `notifications`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Channel for alerts that don't name one, e.g. `telegram`
     #[serde(default)]
-    pub enabled: bool,
-    /// Allowed domains for `browser_open` (exact or subdomain match)
+    pub channel: Option<String>,
+    /// Recipient for alerts that don't name one, or `@name` for a household
+    /// member (who is reached on their preferred channel)
     #[serde(default)]
-    pub allowed_domains: Vec<String>,
-    /// Browser session name (for agent-browser automation)
+    pub to: Option<String>,
+    /// Local-time window in which alerts below `critical` wait
     #[serde(default)]
-    pub session_name: Option<String>,
+    pub quiet_hours: Option<QuietHours>,
+    /// Checked in order; the first that matches an alert applies
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
 }
 
-// ── Weather API ─────────────────────────────────────────────────
+/// `start` and `end` as `HH:MM`; a window past midnight (22:00–07:00) is fine.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct WeatherConfig {
-    /// WeatherAPI.com key used by the `weather_api` tool
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>,
+/// Reroute or mute alerts by source and severity.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationRule {
+    /// What raised the alert: `agent`, `heartbeat`, `eval`, `inbox`,
+    /// `briefs`, or whatever `zeroclaw notify --source` names; any when unset
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Match alerts of this severity or worse
+    #[serde(default)]
+    pub min_severity: Severity,
+    /// Send matching alerts on this channel instead
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// ...and/or to this recipient (`@name` for a household member)
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Drop matching alerts
+    #[serde(default)]
+    pub mute: bool,
 }
 
-// ── Memory ───────────────────────────────────────────────────
+// ── Redis ─────────────────────────────────────────────────────────
 
+/// Session locks, rate-limit counters and the outbox in Redis, shared by
+/// separate gateway, channel and daemon processes (see `redis`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MemoryConfig {
-    /// "sqlite" | "markdown" | "none"
-    pub backend: String,
-    /// Auto-save conversation context to memory
-    pub auto_save: bool,
-    /// Run memory/session hygiene (archiving + retention cleanup)
-    #[serde(default = "default_hygiene_enabled")]
-    pub hygiene_enabled: bool,
-    /// Archive daily/session files older than this many days
-    #[serde(default = "default_archive_after_days")]
-    pub archive_after_days: u32,
-    /// Purge archived files older than this many days
-    #[serde(default = "default_purge_after_days")]
-    pub purge_after_days: u32,
-    /// For sqlite backend: prune conversation rows older than this many days
-    #[serde(default = "default_conversation_retention_days")]
-    pub conversation_retention_days: u32,
-    /// Embedding provider: "none" | "openai" | "custom:URL"
-    #[serde(default = "default_embedding_provider")]
-    pub embedding_provider: String,
-    /// Embedding model name (e.g. "text-embedding-3-small")
-    #[serde(default = "default_embedding_model")]
-    pub embedding_model: String,
-    /// Embedding vector dimensions
-    #[serde(default = "default_embedding_dims")]
-    pub embedding_dimensions: usize,
-    /// Weight for vector similarity in hybrid search (0.0–1.0)
-    #[serde(default = "default_vector_weight")]
-    pub vector_weight: f64,
-    /// Weight for keyword BM25 in hybrid search (0.0–1.0)
-    #[serde(default = "default_keyword_weight")]
-    pub keyword_weight: f64,
-    /// Max embedding cache entries before LRU eviction
-    #[serde(default = "default_cache_size")]
-    pub embedding_cache_size: usize,
-    /// Max tokens per chunk for document splitting
-    #[serde(default = "default_chunk_size")]
-    pub chunk_max_tokens: usize,
+pub struct RedisConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// `redis://[[user]:password@]host[:port][/db]`
+    #[serde(default = "default_redis_url")]
+    pub url: String,
+    /// Put before every key, so several deployments can share a server
+    #[serde(default = "default_redis_key_prefix")]
+    pub key_prefix: String,
+    /// Longest a turn holds its session; the lock of a crashed worker
+    /// frees itself after this
+    #[serde(default = "default_redis_session_lock_secs")]
+    pub session_lock_secs: u64,
 }
 
-fn default_embedding_provider() -> String {
-    "none".into()
-}
-fn default_hygiene_enabled() -> bool {
-    true
-}
-fn default_archive_after_days() -> u32 {
-    7
-}
-fn default_purge_after_days() -> u32 {
-    30
-}
-fn default_conversation_retention_days() -> u32 {
-    30
-}
-fn default_embedding_model() -> String {
-    "text-embedding-3-small".into()
-}
-fn default_embedding_dims() -> usize {
-    1536
+fn default_redis_url() -> String {
+    "redis://127.0.0.1:6379".into()
 }
-fn default_vector_weight() -> f64 {
-    0.7
-}
-fn default_keyword_weight() -> f64 {
-    0.3
-}
-fn default_cache_size() -> usize {
-    10_000
+
+fn default_redis_key_prefix() -> String {
+    "zeroclaw:".into()
 }
-fn default_chunk_size() -> usize {
-    512
+
+fn default_redis_session_lock_secs() -> u64 {
+    300
 }
 
-impl Default for MemoryConfig {
+impl Default for RedisConfig {
     fn default() -> Self {
         Self {
-            backend: "sqlite".into(),
-            auto_save: true,
-            hygiene_enabled: default_hygiene_enabled(),
-            archive_after_days: default_archive_after_days(),
-            purge_after_days: default_purge_after_days(),
-            conversation_retention_days: default_conversation_retention_days(),
-            embedding_provider: default_embedding_provider(),
-            embedding_model: default_embedding_model(),
-            embedding_dimensions: default_embedding_dims(),
-            vector_weight: default_vector_weight(),
-            keyword_weight: default_keyword_weight(),
-            embedding_cache_size: default_cache_size(),
-            chunk_max_tokens: default_chunk_size(),
+            enabled: false,
+            url: default_redis_url(),
+            key_prefix: default_redis_key_prefix(),
+            session_lock_secs: default_redis_session_lock_secs(),
         }
     }
 }
@@ -308,12 +2465,17 @@ impl Default for MemoryConfig {
 pub struct ObservabilityConfig {
     /// "none" | "log" | "prometheus" | "otel"
     pub backend: String,
+    /// With the prometheus backend, also serve `/metrics` on
+    /// `127.0.0.1:<port>` (the gateway serves it regardless)
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
 }
 
 impl Default for ObservabilityConfig {
     fn default() -> Self {
         Self {
             backend: "none".into(),
+            metrics_port: None,
         }
     }
 }
@@ -328,6 +2490,34 @@ pub struct AutonomyConfig {
     pub forbidden_paths: Vec<String>,
     pub max_actions_per_hour: u32,
     pub max_cost_per_day_cents: u32,
+    /// What `level = "interactive"` asks about
+    #[serde(default)]
+    pub approvals: ApprovalsConfig,
+}
+
+/// Interactive approvals: which tools wait for a yes before they run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalsConfig {
+    /// Seconds to wait for an answer; no answer means no
+    #[serde(default = "default_approval_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Per-tool classification on top of the built-in one, e.g.
+    /// `tools = { http_request = "write", memory_forget = "read" }`
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolClass>,
+}
+
+fn default_approval_timeout_secs() -> u64 {
+    300
+}
+
+impl Default for ApprovalsConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_approval_timeout_secs(),
+            tools: BTreeMap::new(),
+        }
+    }
 }
 
 impl Default for AutonomyConfig {
@@ -349,28 +2539,12 @@ impl Default for AutonomyConfig {
                 "head".into(),
                 "tail".into(),
             ],
-            forbidden_paths: vec![
-                "/etc".into(),
-                "/root".into(),
-                "/home".into(),
-                "/usr".into(),
-                "/bin".into(),
-                "/sbin".into(),
-                "/lib".into(),
-                "/opt".into(),
-                "/boot".into(),
-                "/dev".into(),
-                "/proc".into(),
-                "/sys".into(),
-                "/var".into(),
-                "/tmp".into(),
-                "~/.ssh".into(),
-                "~/.gnupg".into(),
-                "~/.aws".into(),
-                "~/.config".into(),
-            ],
+            forbidden_paths: crate::platform::default_forbidden_paths(
+                crate::platform::Os::current(),
+            ),
             max_actions_per_hour: 20,
             max_cost_per_day_cents: 500,
+            approvals: ApprovalsConfig::default(),
         }
     }
 }
@@ -379,16 +2553,86 @@ impl Default for AutonomyConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuntimeConfig {
-    /// Runtime kind (currently supported: "native").
+    /// Runtime kind (currently supported: "native", "docker").
     ///
-    /// Reserved values (not implemented yet): "docker", "cloudflare".
+    /// Reserved values (not implemented yet): "cloudflare".
     pub kind: String,
+    /// Container settings for `kind = "docker"`
+    #[serde(default)]
+    pub docker: DockerRuntimeConfig,
+    /// Config preset: "standard" or `low_memory` (alias "pi") for a
+    /// Raspberry Pi or small VPS; keys set in the file override it
+    #[serde(default = "default_runtime_profile")]
+    pub profile: String,
+}
+
+fn default_runtime_profile() -> String {
+    crate::config::profile::STANDARD.into()
 }
 
 impl Default for RuntimeConfig {
     fn default() -> Self {
         Self {
             kind: "native".into(),
+            docker: DockerRuntimeConfig::default(),
+            profile: default_runtime_profile(),
+        }
+    }
+}
+
+/// Sandbox for agent shell commands: each runs in a throwaway container
+/// with the workspace mounted at `/workspace`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerRuntimeConfig {
+    #[serde(default = "default_docker_image")]
+    pub image: String,
+    /// Docker network mode; "none" cuts the container off entirely
+    #[serde(default = "default_docker_network")]
+    pub network: String,
+    /// CPU cores the container may use (0 = no limit)
+    #[serde(default = "default_docker_cpus")]
+    pub cpus: f64,
+    /// Memory limit in MiB (0 = no limit)
+    #[serde(default = "default_docker_memory_mb")]
+    pub memory_mb: u64,
+    /// Seconds before a command's container is killed
+    #[serde(default = "default_docker_timeout_secs")]
+    pub timeout_secs: u64,
+    /// `--user` for the container, e.g. "1000:1000" so files in the
+    /// workspace stay owned by you
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+fn default_docker_image() -> String {
+    "alpine:3.20".into()
+}
+
+fn default_docker_network() -> String {
+    "none".into()
+}
+
+fn default_docker_cpus() -> f64 {
+    1.0
+}
+
+fn default_docker_memory_mb() -> u64 {
+    512
+}
+
+fn default_docker_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for DockerRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            image: default_docker_image(),
+            network: default_docker_network(),
+            cpus: default_docker_cpus(),
+            memory_mb: default_docker_memory_mb(),
+            timeout_secs: default_docker_timeout_secs(),
+            user: None,
         }
     }
 }
@@ -555,6 +2799,9 @@ pub struct ChannelsConfig {
     pub whatsapp: Option<WhatsAppConfig>,
     pub email: Option<crate::channels::email_channel::EmailConfig>,
     pub irc: Option<IrcConfig>,
+    pub signal: Option<SignalConfig>,
+    /// Push notifications to a phone via ntfy or Gotify (send-only)
+    pub push: Option<PushConfig>,
 }
 
 impl Default for ChannelsConfig {
@@ -570,6 +2817,8 @@ impl Default for ChannelsConfig {
             whatsapp: None,
             email: None,
             irc: None,
+            signal: None,
+            push: None,
         }
     }
 }
@@ -578,6 +2827,9 @@ impl Default for ChannelsConfig {
 pub struct TelegramConfig {
     pub bot_token: String,
     pub allowed_users: Vec<String>,
+    /// Answer voice notes with a voice note too (needs `[voice].transcribe`)
+    #[serde(default)]
+    pub voice_replies: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -586,6 +2838,21 @@ pub struct DiscordConfig {
     pub guild_id: Option<String>,
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// React 👀/✅/⚠️ to messages while and after handling them
+    #[serde(default = "default_true")]
+    pub reactions: bool,
+    /// Servers the bot answers in; empty = any (`guild_id` still applies)
+    #[serde(default)]
+    pub allowed_guilds: Vec<String>,
+    /// Channels the bot answers in, threads included; empty = any
+    #[serde(default)]
+    pub allowed_channels: Vec<String>,
+    /// Register `/ask`, `/reset` and `/status`
+    #[serde(default = "default_true")]
+    pub slash_commands: bool,
+    /// Answer server messages in a thread started from them
+    #[serde(default)]
+    pub thread_replies: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -595,25 +2862,69 @@ pub struct SlackConfig {
     pub channel_id: Option<String>,
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// React 👀/✅/⚠️ to messages while and after handling them
+    #[serde(default = "default_true")]
+    pub reactions: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookConfig {
     pub port: u16,
     pub secret: Option<String>,
+    /// Outbound delivery targets for agent-initiated messages (`[[channels_config.webhook.targets]]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<WebhookTarget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookTarget {
+    /// Name used as the recipient when sending, e.g. "ops-bus"
+    pub name: String,
+    pub url: String,
+    /// Extra request headers (e.g. Authorization)
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+    /// JSON body template; `{{message}}`, `{{recipient}}`, `{{id}}` and `{{timestamp}}`
+    /// are substituted (as JSON-escaped strings). Default: a small JSON envelope.
+    #[serde(default)]
+    pub payload_template: Option<String>,
+    /// HMAC-SHA256 key; when set, the body is signed in `X-ZeroClaw-Signature-256`
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Retries after the first attempt on network errors, 429 and 5xx
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: u32,
+    /// First retry delay; doubles on each retry
+    #[serde(default = "default_webhook_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+}
+
+fn default_webhook_max_retries() -> u32 {
+    3
+}
+
+fn default_webhook_initial_backoff_ms() -> u64 {
+    500
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IMessageConfig {
     pub allowed_contacts: Vec<String>,
+    /// Answer voice notes with a voice note too (needs `[voice].transcribe`)
+    #[serde(default)]
+    pub voice_replies: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatrixConfig {
     pub homeserver: String,
     pub access_token: String,
+    /// Main room; proactive messages without a room go here
     pub room_id: String,
     pub allowed_users: Vec<String>,
+    /// Further rooms to answer in (the bot must already be joined)
+    #[serde(default)]
+    pub rooms: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -630,6 +2941,9 @@ pub struct WhatsAppConfig {
     /// Allowed phone numbers (E.164 format: +1234567890) or "*" for all
     #[serde(default)]
     pub allowed_numbers: Vec<String>,
+    /// Answer voice notes with a voice note too (needs `[voice].transcribe`)
+    #[serde(default)]
+    pub voice_replies: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -663,6 +2977,48 @@ fn default_irc_port() -> u16 {
     6697
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalConfig {
+    /// signal-cli HTTP daemon (`signal-cli -a <account> daemon --http 127.0.0.1:8686`)
+    #[serde(default = "default_signal_http_url")]
+    pub http_url: String,
+    /// The bot's registered number (E.164 format: +1234567890)
+    pub account: String,
+    /// Allowed phone numbers (E.164) or account UUIDs, or "*" for all
+    #[serde(default)]
+    pub allowed_numbers: Vec<String>,
+    /// Group IDs to answer in, or "*" for every group; empty answers direct messages only
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+fn default_signal_http_url() -> String {
+    "http://127.0.0.1:8686".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushConfig {
+    /// "ntfy" or "gotify"
+    #[serde(default = "default_push_service")]
+    pub service: String,
+    /// Server base URL; ntfy defaults to <https://ntfy.sh>, Gotify needs one
+    #[serde(default)]
+    pub server_url: Option<String>,
+    /// ntfy topic to publish to; anyone who knows it can subscribe, so make it hard to guess
+    #[serde(default)]
+    pub topic: String,
+    /// ntfy access token, or the Gotify application token
+    #[serde(default)]
+    pub token: Option<String>,
+    /// 1 (min) to 5 (max); unset = the server's default (Gotify: 5)
+    #[serde(default)]
+    pub priority: Option<u8>,
+}
+
+fn default_push_service() -> String {
+    "ntfy".into()
+}
+
 // ── Config impl ──────────────────────────────────────────────────
 
 impl Default for Config {
@@ -674,6 +3030,8 @@ impl Default for Config {
         Self {
             workspace_dir: zeroclaw_dir.join("workspace"),
             config_path: zeroclaw_dir.join("config.toml"),
+            secret_refs: Vec::new(),
+            profile_defaults: Vec::new(),
             api_key: None,
             default_provider: Some("openrouter".to_string()),
             default_model: Some("anthropic/claude-sonnet-4-20250514".to_string()),
@@ -692,6 +3050,36 @@ impl Default for Config {
             browser: BrowserConfig::default(),
             weather: WeatherConfig::default(),
             identity: IdentityConfig::default(),
+            integrations: IntegrationsConfig::default(),
+            agent: AgentConfig::default(),
+            guardrails: GuardrailsConfig::default(),
+            styles: BTreeMap::new(),
+            triggers: TriggersConfig::default(),
+            presence: PresenceConfig::default(),
+            inbox: InboxConfig::default(),
+            household: HouseholdConfig::default(),
+            offline: OfflineConfig::default(),
+            eval: EvalConfig::default(),
+            aliases: AliasesConfig::default(),
+            ollama: OllamaConfig::default(),
+            tool_output: ToolOutputConfig::default(),
+            tool_middleware: ToolMiddlewareConfig::default(),
+            workspace_index: WorkspaceIndexConfig::default(),
+            skills: SkillsConfig::default(),
+            providers: ProvidersConfig::default(),
+            voice: VoiceConfig::default(),
+            oauth: OAuthConfig::default(),
+            federation: FederationConfig::default(),
+            goals: GoalsConfig::default(),
+            focus: FocusConfig::default(),
+            mcp: McpConfig::default(),
+            outbox: OutboxConfig::default(),
+            sync: SyncConfig::default(),
+            vision: VisionConfig::default(),
+            artifacts: ArtifactsConfig::default(),
+            experiment: ExperimentConfig::default(),
+            notifications: NotificationsConfig::default(),
+            redis: RedisConfig::default(),
         }
     }
 }
@@ -713,10 +3101,28 @@ impl Config {
         if config_path.exists() {
             let contents =
                 fs::read_to_string(&config_path).context("Failed to read config file")?;
-            let mut config: Config =
+            let mut raw: toml::Value =
                 toml::from_str(&contents).context("Failed to parse config file")?;
+            let secret_refs = if contents.contains(crate::security::vault::REFERENCE_PREFIX) {
+                let backend = raw
+                    .get("secrets")
+                    .and_then(|s| s.get("backend"))
+                    .and_then(toml::Value::as_str)
+                    .unwrap_or("auto");
+                let vault = crate::security::Vault::new(
+                    &zeroclaw_dir,
+                    crate::security::VaultBackend::from_config(backend)?,
+                );
+                crate::security::vault::resolve_references(&mut raw, &vault)
+            } else {
+                Vec::new()
+            };
+            let profile_defaults = super::profile::apply(&mut raw)?;
+            let mut config: Config = raw.try_into().context("Failed to parse config file")?;
             // Set computed paths that are skipped during serialization
             config.config_path = config_path.clone();
+            config.secret_refs = secret_refs;
+            config.profile_defaults = profile_defaults;
             config.workspace_dir = zeroclaw_dir.join("workspace");
             config.sync_weather_env();
             Ok(config)
@@ -815,7 +3221,17 @@ impl Config {
     }
 
     pub fn save(&self) -> Result<()> {
-        let toml_str = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        let toml_str = if self.secret_refs.is_empty() && self.profile_defaults.is_empty() {
+            toml::to_string_pretty(self)
+        } else {
+            // Write `secret:` references, not the values they resolved to,
+            // and leave the profile's presets to the profile
+            let mut value = toml::Value::try_from(self).context("Failed to serialize config")?;
+            crate::security::vault::restore_references(&mut value, &self.secret_refs);
+            super::profile::strip(&mut value, &self.profile_defaults);
+            toml::to_string_pretty(&value)
+        }
+        .context("Failed to serialize config")?;
         fs::write(&self.config_path, toml_str).context("Failed to write config file")?;
         Ok(())
     }
@@ -896,12 +3312,15 @@ mod tests {
         let config = Config {
             workspace_dir: PathBuf::from("/tmp/test/workspace"),
             config_path: PathBuf::from("/tmp/test/config.toml"),
+            secret_refs: Vec::new(),
+            profile_defaults: Vec::new(),
             api_key: Some("sk-test-key".into()),
             default_provider: Some("openrouter".into()),
             default_model: Some("gpt-4o".into()),
             default_temperature: 0.5,
             observability: ObservabilityConfig {
                 backend: "log".into(),
+                metrics_port: None,
             },
             autonomy: AutonomyConfig {
                 level: AutonomyLevel::Full,
@@ -910,9 +3329,11 @@ mod tests {
                 forbidden_paths: vec!["/secret".into()],
                 max_actions_per_hour: 50,
                 max_cost_per_day_cents: 1000,
+                ..AutonomyConfig::default()
             },
             runtime: RuntimeConfig {
                 kind: "docker".into(),
+                ..RuntimeConfig::default()
             },
             reliability: ReliabilityConfig::default(),
             heartbeat: HeartbeatConfig {
@@ -924,6 +3345,7 @@ mod tests {
                 telegram: Some(TelegramConfig {
                     bot_token: "123:ABC".into(),
                     allowed_users: vec!["user1".into()],
+                    voice_replies: false,
                 }),
                 discord: None,
                 slack: None,
@@ -933,6 +3355,8 @@ mod tests {
                 whatsapp: None,
                 email: None,
                 irc: None,
+                signal: None,
+                push: None,
             },
             memory: MemoryConfig::default(),
             tunnel: TunnelConfig::default(),
@@ -942,6 +3366,7 @@ mod tests {
             browser: BrowserConfig::default(),
             weather: WeatherConfig::default(),
             identity: IdentityConfig::default(),
+            ..Config::default()
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -964,6 +3389,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn experiment_config_toml_roundtrip() {
+        let config = Config {
+            experiment: ExperimentConfig {
+                enabled: true,
+                name: "haiku-vs-sonnet".into(),
+                share_percent: 25,
+                model: Some("claude-haiku".into()),
+                candidate_prices: Some(TokenPrices {
+                    input: 0.8,
+                    output: 4.0,
+                }),
+                ..ExperimentConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: Config = toml::from_str(&toml_str).unwrap();
+
+        assert!(parsed.experiment.enabled);
+        assert_eq!(parsed.experiment.name, "haiku-vs-sonnet");
+        assert_eq!(parsed.experiment.share_percent, 25);
+        assert_eq!(parsed.experiment.model.as_deref(), Some("claude-haiku"));
+        assert!(parsed.experiment.provider.is_none());
+        assert!(parsed.experiment.control_prices.is_none());
+        let prices = parsed.experiment.candidate_prices.unwrap();
+        assert!((prices.output - 4.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn config_minimal_toml_uses_defaults() {
         let minimal = r#"
@@ -995,6 +3450,8 @@ default_temperature = 0.7
         let config = Config {
             workspace_dir: dir.join("workspace"),
             config_path: config_path.clone(),
+            secret_refs: Vec::new(),
+            profile_defaults: Vec::new(),
             api_key: Some("sk-roundtrip".into()),
             default_provider: Some("openrouter".into()),
             default_model: Some("test-model".into()),
@@ -1013,6 +3470,36 @@ default_temperature = 0.7
             browser: BrowserConfig::default(),
             weather: WeatherConfig::default(),
             identity: IdentityConfig::default(),
+            integrations: IntegrationsConfig::default(),
+            agent: AgentConfig::default(),
+            guardrails: GuardrailsConfig::default(),
+            styles: BTreeMap::new(),
+            triggers: TriggersConfig::default(),
+            presence: PresenceConfig::default(),
+            inbox: InboxConfig::default(),
+            household: HouseholdConfig::default(),
+            offline: OfflineConfig::default(),
+            eval: EvalConfig::default(),
+            aliases: AliasesConfig::default(),
+            ollama: OllamaConfig::default(),
+            tool_output: ToolOutputConfig::default(),
+            tool_middleware: ToolMiddlewareConfig::default(),
+            workspace_index: WorkspaceIndexConfig::default(),
+            skills: SkillsConfig::default(),
+            providers: ProvidersConfig::default(),
+            voice: VoiceConfig::default(),
+            oauth: OAuthConfig::default(),
+            federation: FederationConfig::default(),
+            goals: GoalsConfig::default(),
+            focus: FocusConfig::default(),
+            mcp: McpConfig::default(),
+            outbox: OutboxConfig::default(),
+            sync: SyncConfig::default(),
+            vision: VisionConfig::default(),
+            artifacts: ArtifactsConfig::default(),
+            experiment: ExperimentConfig::default(),
+            notifications: NotificationsConfig::default(),
+            redis: RedisConfig::default(),
         };
 
         config.save().unwrap();
@@ -1034,6 +3521,7 @@ default_temperature = 0.7
         let tc = TelegramConfig {
             bot_token: "123:XYZ".into(),
             allowed_users: vec!["alice".into(), "bob".into()],
+            voice_replies: false,
         };
         let json = serde_json::to_string(&tc).unwrap();
         let parsed: TelegramConfig = serde_json::from_str(&json).unwrap();
@@ -1047,6 +3535,11 @@ default_temperature = 0.7
             bot_token: "discord-token".into(),
             guild_id: Some("12345".into()),
             allowed_users: vec![],
+            reactions: true,
+            allowed_guilds: vec![],
+            allowed_channels: vec![],
+            slash_commands: true,
+            thread_replies: false,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -1060,10 +3553,17 @@ default_temperature = 0.7
             bot_token: "tok".into(),
             guild_id: None,
             allowed_users: vec![],
+            reactions: true,
+            allowed_guilds: vec![],
+            allowed_channels: vec![],
+            slash_commands: true,
+            thread_replies: false,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
         assert!(parsed.guild_id.is_none());
+        let parsed: DiscordConfig = serde_json::from_str(r#"{"bot_token": "tok"}"#).unwrap();
+        assert!(parsed.reactions);
     }
 
     // ── iMessage / Matrix config ────────────────────────────
@@ -1072,6 +3572,7 @@ default_temperature = 0.7
     fn imessage_config_serde() {
         let ic = IMessageConfig {
             allowed_contacts: vec!["+1234567890".into(), "user@icloud.com".into()],
+            voice_replies: false,
         };
         let json = serde_json::to_string(&ic).unwrap();
         let parsed: IMessageConfig = serde_json::from_str(&json).unwrap();
@@ -1083,6 +3584,7 @@ default_temperature = 0.7
     fn imessage_config_empty_contacts() {
         let ic = IMessageConfig {
             allowed_contacts: vec![],
+            voice_replies: false,
         };
         let json = serde_json::to_string(&ic).unwrap();
         let parsed: IMessageConfig = serde_json::from_str(&json).unwrap();
@@ -1093,6 +3595,7 @@ default_temperature = 0.7
     fn imessage_config_wildcard() {
         let ic = IMessageConfig {
             allowed_contacts: vec!["*".into()],
+            voice_replies: false,
         };
         let toml_str = toml::to_string(&ic).unwrap();
         let parsed: IMessageConfig = toml::from_str(&toml_str).unwrap();
@@ -1106,6 +3609,7 @@ default_temperature = 0.7
             access_token: "syt_token_abc".into(),
             room_id: "!room123:matrix.org".into(),
             allowed_users: vec!["@user:matrix.org".into()],
+            rooms: Vec::new(),
         };
         let json = serde_json::to_string(&mc).unwrap();
         let parsed: MatrixConfig = serde_json::from_str(&json).unwrap();
@@ -1122,6 +3626,7 @@ default_temperature = 0.7
             access_token: "tok".into(),
             room_id: "!abc:synapse.local".into(),
             allowed_users: vec!["@admin:synapse.local".into(), "*".into()],
+            rooms: Vec::new(),
         };
         let toml_str = toml::to_string(&mc).unwrap();
         let parsed: MatrixConfig = toml::from_str(&toml_str).unwrap();
@@ -1139,16 +3644,20 @@ default_temperature = 0.7
             webhook: None,
             imessage: Some(IMessageConfig {
                 allowed_contacts: vec!["+1".into()],
+                voice_replies: false,
             }),
             matrix: Some(MatrixConfig {
                 homeserver: "https://m.org".into(),
                 access_token: "tok".into(),
                 room_id: "!r:m".into(),
                 allowed_users: vec!["@u:m".into()],
+                rooms: Vec::new(),
             }),
             whatsapp: None,
             email: None,
             irc: None,
+            signal: None,
+            push: None,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -1231,6 +3740,28 @@ channel_id = "C123"
         let parsed: WebhookConfig = serde_json::from_str(json).unwrap();
         assert!(parsed.secret.is_none());
         assert_eq!(parsed.port, 8080);
+        assert!(parsed.targets.is_empty());
+    }
+
+    #[test]
+    fn webhook_config_with_outbound_targets() {
+        let toml_str = r#"
+port = 8080
+
+[[targets]]
+name = "ops-bus"
+url = "https://ops.example.com/hooks/zeroclaw"
+secret = "s3cr3t"
+headers = { Authorization = "Bearer abc" }
+"#;
+        let parsed: WebhookConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(parsed.targets.len(), 1);
+        let target = &parsed.targets[0];
+        assert_eq!(target.name, "ops-bus");
+        assert_eq!(target.headers["Authorization"], "Bearer abc");
+        assert_eq!(target.max_retries, 3);
+        assert_eq!(target.initial_backoff_ms, 500);
+        assert!(target.payload_template.is_none());
     }
 
     // ── WhatsApp config ──────────────────────────────────────
@@ -1243,6 +3774,7 @@ channel_id = "C123"
             verify_token: "my-verify-token".into(),
             app_secret: None,
             allowed_numbers: vec!["+1234567890".into(), "+9876543210".into()],
+            voice_replies: false,
         };
         let json = serde_json::to_string(&wc).unwrap();
         let parsed: WhatsAppConfig = serde_json::from_str(&json).unwrap();
@@ -1260,6 +3792,7 @@ channel_id = "C123"
             verify_token: "verify".into(),
             app_secret: None,
             allowed_numbers: vec!["+1".into()],
+            voice_replies: false,
         };
         let toml_str = toml::to_string(&wc).unwrap();
         let parsed: WhatsAppConfig = toml::from_str(&toml_str).unwrap();
@@ -1282,6 +3815,7 @@ channel_id = "C123"
             verify_token: "ver".into(),
             app_secret: None,
             allowed_numbers: vec!["*".into()],
+            voice_replies: false,
         };
         let toml_str = toml::to_string(&wc).unwrap();
         let parsed: WhatsAppConfig = toml::from_str(&toml_str).unwrap();
@@ -1304,9 +3838,12 @@ channel_id = "C123"
                 verify_token: "ver".into(),
                 app_secret: None,
                 allowed_numbers: vec!["+1".into()],
+                voice_replies: false,
             }),
             email: None,
             irc: None,
+            signal: None,
+            push: None,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -1373,6 +3910,8 @@ channel_id = "C123"
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: vec!["zc_test_token".into()],
+            dashboard: true,
+            status_page: false,
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -1482,7 +4021,10 @@ enabled = true
 
     #[test]
     fn secrets_config_serde_roundtrip() {
-        let s = SecretsConfig { encrypt: false };
+        let s = SecretsConfig {
+            encrypt: false,
+            ..SecretsConfig::default()
+        };
         let toml_str = toml::to_string(&s).unwrap();
         let parsed: SecretsConfig = toml::from_str(&toml_str).unwrap();
         assert!(!parsed.encrypt);
@@ -1525,12 +4067,15 @@ default_temperature = 0.7
             enabled: true,
             allowed_domains: vec!["example.com".into(), "docs.example.com".into()],
             session_name: None,
+            backend: BrowserBackend::Cdp,
+            chromium_path: None,
         };
         let toml_str = toml::to_string(&b).unwrap();
         let parsed: BrowserConfig = toml::from_str(&toml_str).unwrap();
         assert!(parsed.enabled);
         assert_eq!(parsed.allowed_domains.len(), 2);
         assert_eq!(parsed.allowed_domains[0], "example.com");
+        assert_eq!(parsed.backend, BrowserBackend::Cdp);
     }
 
     #[test]
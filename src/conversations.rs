@@ -0,0 +1,748 @@
+//! Durable conversation log.
+//!
+//! Every turn is written *before* the provider is called (status `pending`)
+//! and updated once it finishes, so a crash or `kill -9` in the middle of a
+//! long turn leaves a record behind. On the next start
+//! [`ConversationStore::recover_interrupted`] marks those turns `interrupted`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnStatus {
+    Pending,
+    Complete,
+    Failed,
+    Interrupted,
+}
+
+impl TurnStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Complete => "complete",
+            Self::Failed => "failed",
+            Self::Interrupted => "interrupted",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "pending" => Ok(Self::Pending),
+            "complete" => Ok(Self::Complete),
+            "failed" => Ok(Self::Failed),
+            "interrupted" => Ok(Self::Interrupted),
+            other => anyhow::bail!("Unknown turn status in conversation DB: {other}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Turn {
+    pub id: i64,
+    pub session: String,
+    pub user_message: String,
+    pub response: Option<String>,
+    pub error: Option<String>,
+    pub status: TurnStatus,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// The user's verdict on a reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Up,
+    Down,
+}
+
+impl Rating {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::Up => "👍",
+            Self::Down => "👎",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "up" => Ok(Self::Up),
+            "down" => Ok(Self::Down),
+            other => anyhow::bail!("Unknown rating in conversation DB: {other}"),
+        }
+    }
+}
+
+/// A turn together with the rating and note the user left on it.
+#[derive(Debug, Clone)]
+pub struct AnnotatedTurn {
+    pub turn: Turn,
+    pub rating: Option<Rating>,
+    pub note: Option<String>,
+}
+
+/// One session's place in the log, as listed by `zeroclaw agent sessions list`.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub session: String,
+    pub turns: usize,
+    pub last_message: String,
+    pub last_at: DateTime<Utc>,
+}
+
+/// A tool call made during a session, kept so it can be replayed.
+#[derive(Debug, Clone)]
+pub struct Action {
+    pub id: i64,
+    /// The session's latest turn when the tool ran
+    pub turn_id: Option<i64>,
+    pub tool: String,
+    pub arguments: serde_json::Value,
+    pub success: bool,
+    pub at: DateTime<Utc>,
+}
+
+/// SQLite-backed turn log (WAL, fully synchronous commits).
+pub struct ConversationStore {
+    conn: Mutex<Connection>,
+    db_path: PathBuf,
+}
+
+impl ConversationStore {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("conversations").join("conversations.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!(
+                    "Failed to create conversations directory: {}",
+                    parent.display()
+                )
+            })?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open conversation DB: {}", db_path.display()))?;
+        // WAL + FULL: each committed turn survives a crash of this process or the host.
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = FULL;
+             CREATE TABLE IF NOT EXISTS turns (
+                id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                session      TEXT NOT NULL,
+                user_message TEXT NOT NULL,
+                response     TEXT,
+                error        TEXT,
+                status       TEXT NOT NULL,
+                started_at   TEXT NOT NULL,
+                finished_at  TEXT
+             );
+             CREATE INDEX IF NOT EXISTS idx_turns_session ON turns(session, id);
+             CREATE INDEX IF NOT EXISTS idx_turns_status ON turns(status);
+             CREATE TABLE IF NOT EXISTS actions (
+                id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                session      TEXT NOT NULL,
+                turn_id      INTEGER,
+                tool         TEXT NOT NULL,
+                arguments    TEXT NOT NULL,
+                success      INTEGER NOT NULL,
+                at           TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_actions_session ON actions(session, id);
+             CREATE TABLE IF NOT EXISTS prompt_traces (
+                turn_id      INTEGER PRIMARY KEY,
+                session      TEXT NOT NULL,
+                trace        TEXT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS annotations (
+                turn_id      INTEGER PRIMARY KEY,
+                session      TEXT NOT NULL,
+                rating       TEXT,
+                note         TEXT,
+                at           TEXT NOT NULL
+             );",
+        )
+        .context("Failed to initialize conversation schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            db_path,
+        })
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Record the user's message as a pending turn; returns the turn ID.
+    pub fn begin_turn(&self, session: &str, user_message: &str) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO turns (session, user_message, status, started_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                session,
+                user_message,
+                TurnStatus::Pending.as_str(),
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .context("Failed to record conversation turn")?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn complete_turn(&self, id: i64, response: &str) -> Result<()> {
+        self.finish(id, TurnStatus::Complete, Some(response), None)
+    }
+
+    pub fn fail_turn(&self, id: i64, error: &str) -> Result<()> {
+        self.finish(id, TurnStatus::Failed, None, Some(error))
+    }
+
+    fn finish(
+        &self,
+        id: i64,
+        status: TurnStatus,
+        response: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.conn()
+            .execute(
+                "UPDATE turns SET status = ?1, response = ?2, error = ?3, finished_at = ?4
+                 WHERE id = ?5",
+                params![
+                    status.as_str(),
+                    response,
+                    error,
+                    Utc::now().to_rfc3339(),
+                    id
+                ],
+            )
+            .context("Failed to update conversation turn")?;
+        Ok(())
+    }
+
+    /// Mark turns left `pending` by a previous process as `interrupted`.
+    ///
+    /// `owns` limits recovery to the sessions this process serves (the CLI
+    /// and the channel server share the DB). Call once at startup, before
+    /// any of those sessions begin new turns.
+    pub fn recover_interrupted(&self, owns: impl Fn(&str) -> bool) -> Result<Vec<Turn>> {
+        let pending: Vec<Turn> = self
+            .query(
+                "WHERE status = ?1 ORDER BY id ASC",
+                params![TurnStatus::Pending.as_str()],
+            )?
+            .into_iter()
+            .filter(|turn| owns(&turn.session))
+            .collect();
+
+        let now = Utc::now();
+        let now_raw = now.to_rfc3339();
+        let conn = self.conn();
+        for turn in &pending {
+            conn.execute(
+                "UPDATE turns SET status = ?1, finished_at = ?2 WHERE id = ?3",
+                params![TurnStatus::Interrupted.as_str(), now_raw, turn.id],
+            )
+            .context("Failed to mark interrupted turn")?;
+        }
+        Ok(pending
+            .into_iter()
+            .map(|turn| Turn {
+                status: TurnStatus::Interrupted,
+                finished_at: Some(now),
+                ..turn
+            })
+            .collect())
+    }
+
+    /// The last `limit` turns of a session, oldest first.
+    pub fn history(&self, session: &str, limit: usize) -> Result<Vec<Turn>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let mut turns = self.query(
+            "WHERE session = ?1 ORDER BY id DESC LIMIT ?2",
+            params![session, limit],
+        )?;
+        turns.reverse();
+        Ok(turns)
+    }
+
+    /// Sessions whose name starts with `prefix`, most recently used first.
+    pub fn sessions(&self, prefix: &str) -> Result<Vec<SessionSummary>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT t.session, c.turns, t.user_message, t.started_at
+             FROM turns t
+             JOIN (SELECT session, COUNT(*) AS turns, MAX(id) AS last_id
+                   FROM turns WHERE substr(session, 1, length(?1)) = ?1
+                   GROUP BY session) c ON t.id = c.last_id
+             ORDER BY t.id DESC",
+        )?;
+        let rows = stmt.query_map(params![prefix], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        let mut sessions = Vec::new();
+        for row in rows {
+            let (session, turns, last_message, last_at) = row?;
+            sessions.push(SessionSummary {
+                session,
+                turns: usize::try_from(turns).unwrap_or_default(),
+                last_message,
+                last_at: parse_rfc3339(&last_at)?,
+            });
+        }
+        Ok(sessions)
+    }
+
+    /// Drop every turn of `session`; returns how many were removed.
+    pub fn delete_session(&self, session: &str) -> Result<usize> {
+        let conn = self.conn();
+        conn.execute("DELETE FROM actions WHERE session = ?1", params![session])
+            .context("Failed to delete session actions")?;
+        conn.execute(
+            "DELETE FROM prompt_traces WHERE session = ?1",
+            params![session],
+        )
+        .context("Failed to delete session prompt traces")?;
+        conn.execute(
+            "DELETE FROM annotations WHERE session = ?1",
+            params![session],
+        )
+        .context("Failed to delete session annotations")?;
+        conn.execute("DELETE FROM turns WHERE session = ?1", params![session])
+            .context("Failed to delete conversation session")
+    }
+
+    /// Move a session's turns aside under `{session}@{timestamp}` so the next
+    /// message starts fresh; ratings and traces move with them. Returns the
+    /// number of turns archived.
+    pub fn archive_session(&self, session: &str) -> Result<usize> {
+        let archived = format!("{session}@{}", Utc::now().to_rfc3339());
+        let conn = self.conn();
+        for table in ["actions", "prompt_traces", "annotations"] {
+            conn.execute(
+                &format!("UPDATE {table} SET session = ?2 WHERE session = ?1"),
+                params![session, archived],
+            )
+            .with_context(|| format!("Failed to archive session {table}"))?;
+        }
+        conn.execute(
+            "UPDATE turns SET session = ?2 WHERE session = ?1",
+            params![session, archived],
+        )
+        .context("Failed to archive conversation session")
+    }
+
+    /// Record a tool call against the session's current turn.
+    pub fn record_action(
+        &self,
+        session: &str,
+        tool: &str,
+        arguments: &serde_json::Value,
+        success: bool,
+    ) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO actions (session, turn_id, tool, arguments, success, at)
+             VALUES (?1, (SELECT MAX(id) FROM turns WHERE session = ?1), ?2, ?3, ?4, ?5)",
+            params![
+                session,
+                tool,
+                arguments.to_string(),
+                success,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .context("Failed to record tool action")?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Every tool call of `session`, oldest first.
+    pub fn actions(&self, session: &str) -> Result<Vec<Action>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, turn_id, tool, arguments, success, at
+             FROM actions WHERE session = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![session], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, bool>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?;
+        let mut actions = Vec::new();
+        for row in rows {
+            let (id, turn_id, tool, arguments, success, at) = row?;
+            actions.push(Action {
+                id,
+                turn_id,
+                tool,
+                arguments: serde_json::from_str(&arguments).unwrap_or(serde_json::Value::Null),
+                success,
+                at: parse_rfc3339(&at)?,
+            });
+        }
+        Ok(actions)
+    }
+
+    /// Rate and/or attach a note to a turn. `None` keeps what was there.
+    pub fn annotate(&self, turn_id: i64, rating: Option<Rating>, note: Option<&str>) -> Result<()> {
+        self.conn()
+            .execute(
+                "INSERT INTO annotations (turn_id, session, rating, note, at)
+                 SELECT id, session, ?2, ?3, ?4 FROM turns WHERE id = ?1
+                 ON CONFLICT(turn_id) DO UPDATE SET
+                    rating = COALESCE(excluded.rating, rating),
+                    note = COALESCE(excluded.note, note),
+                    at = excluded.at",
+                params![
+                    turn_id,
+                    rating.map(Rating::as_str),
+                    note,
+                    Utc::now().to_rfc3339()
+                ],
+            )
+            .context("Failed to annotate conversation turn")?;
+        Ok(())
+    }
+
+    /// The session's latest answered turn, the one a rating refers to.
+    pub fn last_complete_turn(&self, session: &str) -> Result<Option<Turn>> {
+        Ok(self
+            .query(
+                "WHERE session = ?1 AND status = ?2 ORDER BY id DESC LIMIT 1",
+                params![session, TurnStatus::Complete.as_str()],
+            )?
+            .pop())
+    }
+
+    /// Keep the prompt trace (JSON) of a turn.
+    pub fn save_trace(&self, turn_id: i64, trace: &str) -> Result<()> {
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO prompt_traces (turn_id, session, trace)
+                 SELECT id, session, ?2 FROM turns WHERE id = ?1",
+                params![turn_id, trace],
+            )
+            .context("Failed to save prompt trace")?;
+        Ok(())
+    }
+
+    /// The turn and prompt trace for `turn_id`, or the latest traced turn.
+    pub fn trace(&self, turn_id: Option<i64>) -> Result<Option<(Turn, String)>> {
+        let found = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare(
+                "SELECT turn_id, trace FROM prompt_traces
+                 WHERE ?1 IS NULL OR turn_id = ?1 ORDER BY turn_id DESC LIMIT 1",
+            )?;
+            stmt.query_row(params![turn_id], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })
+            .optional()?
+        };
+        let Some((id, trace)) = found else {
+            return Ok(None);
+        };
+        Ok(self
+            .query("WHERE id = ?1", params![id])?
+            .pop()
+            .map(|turn| (turn, trace)))
+    }
+
+    /// Answered turns with their annotations, oldest first. `session`
+    /// narrows to one session; `rated_only` drops turns without a rating.
+    pub fn annotated_turns(
+        &self,
+        session: Option<&str>,
+        rated_only: bool,
+    ) -> Result<Vec<AnnotatedTurn>> {
+        let turns = match session {
+            Some(session) => self.query(
+                "WHERE session = ?1 AND status = ?2 ORDER BY id",
+                params![session, TurnStatus::Complete.as_str()],
+            )?,
+            None => self.query(
+                "WHERE status = ?1 ORDER BY id",
+                params![TurnStatus::Complete.as_str()],
+            )?,
+        };
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT rating, note FROM annotations WHERE turn_id = ?1")?;
+        let mut annotated = Vec::new();
+        for turn in turns {
+            let found = stmt
+                .query_row(params![turn.id], |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                    ))
+                })
+                .optional()?;
+            let (rating, note) = found.unwrap_or_default();
+            let rating = rating.as_deref().map(Rating::parse).transpose()?;
+            if rated_only && rating.is_none() {
+                continue;
+            }
+            annotated.push(AnnotatedTurn { turn, rating, note });
+        }
+        Ok(annotated)
+    }
+
+    /// Every answered turn, oldest first.
+    pub fn complete_turns(&self) -> Result<Vec<Turn>> {
+        self.query(
+            "WHERE status = ?1 ORDER BY id",
+            params![TurnStatus::Complete.as_str()],
+        )
+    }
+
+    /// Add an answered turn recorded elsewhere (by `sync`), unless this log
+    /// already has it. Returns whether it was added.
+    pub fn import_turn(
+        &self,
+        session: &str,
+        user_message: &str,
+        response: &str,
+        started_at: DateTime<Utc>,
+        finished_at: Option<DateTime<Utc>>,
+    ) -> Result<bool> {
+        let inserted = self
+            .conn()
+            .execute(
+                "INSERT INTO turns
+                    (session, user_message, response, status, started_at, finished_at)
+                 SELECT ?1, ?2, ?3, ?4, ?5, ?6
+                 WHERE NOT EXISTS (SELECT 1 FROM turns
+                    WHERE session = ?1 AND started_at = ?5 AND user_message = ?2)",
+                params![
+                    session,
+                    user_message,
+                    response,
+                    TurnStatus::Complete.as_str(),
+                    started_at.to_rfc3339(),
+                    finished_at.map(|at| at.to_rfc3339())
+                ],
+            )
+            .context("Failed to import conversation turn")?;
+        Ok(inserted > 0)
+    }
+
+    /// Drop one turn with its rating and trace.
+    pub fn delete_turn(&self, id: i64) -> Result<()> {
+        let conn = self.conn();
+        for table in ["prompt_traces", "annotations"] {
+            conn.execute(
+                &format!("DELETE FROM {table} WHERE turn_id = ?1"),
+                params![id],
+            )
+            .with_context(|| format!("Failed to delete turn {table}"))?;
+        }
+        conn.execute("DELETE FROM turns WHERE id = ?1", params![id])
+            .context("Failed to delete conversation turn")?;
+        Ok(())
+    }
+
+    fn query(&self, clause: &str, args: &[&dyn rusqlite::ToSql]) -> Result<Vec<Turn>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, session, user_message, response, error, status, started_at, finished_at
+             FROM turns {clause}"
+        ))?;
+        let rows = stmt.query_map(args, |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+                row.get::<_, Option<String>>(7)?,
+            ))
+        })?;
+
+        let mut turns = Vec::new();
+        for row in rows {
+            let (id, session, user_message, response, error, status, started, finished) = row?;
+            turns.push(Turn {
+                id,
+                session,
+                user_message,
+                response,
+                error,
+                status: TurnStatus::parse(&status)?,
+                started_at: parse_rfc3339(&started)?,
+                finished_at: finished.as_deref().map(parse_rfc3339).transpose()?,
+            });
+        }
+        Ok(turns)
+    }
+}
+
+fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Invalid RFC3339 timestamp in conversation DB: {raw}"))?;
+    Ok(parsed.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn turns_are_recorded_before_and_after_the_reply() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+
+        let id = store.begin_turn("discord:alice", "deploy status?").unwrap();
+        let turns = store.history("discord:alice", 10).unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].status, TurnStatus::Pending);
+
+        store.complete_turn(id, "All green").unwrap();
+        let failed = store.begin_turn("discord:alice", "and prod?").unwrap();
+        store.fail_turn(failed, "timeout").unwrap();
+
+        let turns = store.history("discord:alice", 10).unwrap();
+        assert_eq!(turns[0].response.as_deref(), Some("All green"));
+        assert_eq!(turns[0].status, TurnStatus::Complete);
+        assert_eq!(turns[1].status, TurnStatus::Failed);
+        assert_eq!(turns[1].error.as_deref(), Some("timeout"));
+        assert!(turns[1].finished_at.is_some());
+        assert!(store.history("cli:local", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn pending_turns_are_marked_interrupted_after_a_crash() {
+        let tmp = TempDir::new().unwrap();
+        {
+            // Simulates a process that died mid-turn: the store is dropped
+            // without the turn ever being completed.
+            let store = ConversationStore::open(tmp.path()).unwrap();
+            let done = store.begin_turn("cli:local", "first").unwrap();
+            store.complete_turn(done, "ok").unwrap();
+            store
+                .begin_turn("cli:local", "run the long migration")
+                .unwrap();
+            store
+                .begin_turn("slack:bob", "still running elsewhere")
+                .unwrap();
+        }
+
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        let recovered = store
+            .recover_interrupted(|s| s.starts_with("cli:"))
+            .unwrap();
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].user_message, "run the long migration");
+        assert_eq!(recovered[0].status, TurnStatus::Interrupted);
+
+        let history = store.history("cli:local", 1).unwrap();
+        assert_eq!(history[0].status, TurnStatus::Interrupted);
+        assert!(store
+            .recover_interrupted(|s| s.starts_with("cli:"))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            store.history("slack:bob", 1).unwrap()[0].status,
+            TurnStatus::Pending
+        );
+    }
+
+    #[test]
+    fn sessions_are_listed_by_prefix_and_deleted_whole() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        for (session, message) in [
+            ("cli:work", "plan the sprint"),
+            ("cli:local", "hello"),
+            ("cli:work", "draft the retro notes"),
+            ("telegram:42", "hi"),
+        ] {
+            let id = store.begin_turn(session, message).unwrap();
+            store.complete_turn(id, "ok").unwrap();
+        }
+
+        let sessions = store.sessions("cli:").unwrap();
+        let names: Vec<&str> = sessions.iter().map(|s| s.session.as_str()).collect();
+        assert_eq!(names, ["cli:work", "cli:local"]);
+        assert_eq!(sessions[0].turns, 2);
+        assert_eq!(sessions[0].last_message, "draft the retro notes");
+
+        assert_eq!(store.delete_session("cli:work").unwrap(), 2);
+        assert!(store.history("cli:work", 10).unwrap().is_empty());
+        assert_eq!(store.sessions("").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn archived_sessions_start_fresh_but_keep_ratings() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        let id = store.begin_turn("discord:123", "hi").unwrap();
+        store.complete_turn(id, "hello").unwrap();
+        store.annotate(id, Some(Rating::Up), None).unwrap();
+
+        assert_eq!(store.archive_session("discord:123").unwrap(), 1);
+        assert!(store.history("discord:123", 10).unwrap().is_empty());
+        let sessions = store.sessions("discord:123@").unwrap();
+        assert_eq!(sessions.len(), 1);
+        let rated = store.annotated_turns(None, true).unwrap();
+        assert_eq!(rated[0].turn.session, sessions[0].session);
+    }
+
+    #[test]
+    fn ratings_and_notes_attach_to_the_last_answered_turn() {
+        let tmp = TempDir::new().unwrap();
+        let store = ConversationStore::open(tmp.path()).unwrap();
+        let first = store.begin_turn("cli:work", "summarize the PR").unwrap();
+        store.complete_turn(first, "It adds caching.").unwrap();
+        let second = store.begin_turn("cli:work", "and the risks?").unwrap();
+        store.fail_turn(second, "timeout").unwrap();
+
+        let last = store.last_complete_turn("cli:work").unwrap().unwrap();
+        assert_eq!(last.id, first);
+        store.annotate(first, Some(Rating::Down), None).unwrap();
+        store
+            .annotate(first, None, Some("missed the migration"))
+            .unwrap();
+
+        let rated = store.annotated_turns(Some("cli:work"), true).unwrap();
+        assert_eq!(rated.len(), 1);
+        assert_eq!(rated[0].rating, Some(Rating::Down));
+        assert_eq!(rated[0].note.as_deref(), Some("missed the migration"));
+        assert!(store
+            .annotated_turns(Some("cli:other"), false)
+            .unwrap()
+            .is_empty());
+
+        store.delete_session("cli:work").unwrap();
+        assert!(store.annotated_turns(None, false).unwrap().is_empty());
+    }
+}
@@ -8,14 +8,110 @@ use uuid::Uuid;
 
 pub mod scheduler;
 
+/// What a job's `command` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// A shell command, checked against the autonomy policy
+    Shell,
+    /// A prompt for a one-shot agent turn
+    Agent,
+}
+
+impl JobKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shell => "shell",
+            Self::Agent => "agent",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "shell" => Ok(Self::Shell),
+            "agent" => Ok(Self::Agent),
+            other => anyhow::bail!("Unknown job kind in cron DB: {other}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CronJob {
     pub id: String,
     pub expression: String,
     pub command: String,
+    pub kind: JobKind,
+    /// Agent jobs: provider instead of `default_provider`
+    pub provider: Option<String>,
+    /// Agent jobs: model instead of `default_model`
+    pub model: Option<String>,
     pub next_run: DateTime<Utc>,
     pub last_run: Option<DateTime<Utc>>,
     pub last_status: Option<String>,
+    pub last_output: Option<String>,
+}
+
+impl CronJob {
+    /// One-line description of what the job runs.
+    pub fn describe(&self) -> String {
+        match self.kind {
+            JobKind::Shell => format!("cmd: {}", self.command),
+            JobKind::Agent => {
+                let mut target = String::new();
+                if let Some(provider) = &self.provider {
+                    target.push_str(provider);
+                }
+                if let Some(model) = &self.model {
+                    if !target.is_empty() {
+                        target.push('/');
+                    }
+                    target.push_str(model);
+                }
+                if target.is_empty() {
+                    format!("agent: {}", self.command)
+                } else {
+                    format!("agent ({target}): {}", self.command)
+                }
+            }
+        }
+    }
+}
+
+/// Columns `job_from_row` reads, in order.
+const JOB_COLUMNS: &str =
+    "id, expression, command, kind, provider, model, next_run, last_run, last_status, last_output";
+
+fn job_from_row(row: &rusqlite::Row<'_>) -> Result<CronJob> {
+    let kind: String = row.get(3)?;
+    let next_run: String = row.get(6)?;
+    let last_run: Option<String> = row.get(7)?;
+    Ok(CronJob {
+        id: row.get(0)?,
+        expression: row.get(1)?,
+        command: row.get(2)?,
+        kind: JobKind::parse(&kind)?,
+        provider: row.get(4)?,
+        model: row.get(5)?,
+        next_run: parse_rfc3339(&next_run)?,
+        last_run: last_run.as_deref().map(parse_rfc3339).transpose()?,
+        last_status: row.get(8)?,
+        last_output: row.get(9)?,
+    })
+}
+
+fn query_jobs(
+    conn: &Connection,
+    filter: &str,
+    args: impl rusqlite::Params,
+) -> Result<Vec<CronJob>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {JOB_COLUMNS} FROM cron_jobs {filter} ORDER BY next_run ASC"
+    ))?;
+    let mut rows = stmt.query(args)?;
+    let mut jobs = Vec::new();
+    while let Some(row) = rows.next()? {
+        jobs.push(job_from_row(row)?);
+    }
+    Ok(jobs)
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -26,7 +122,8 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             if jobs.is_empty() {
                 println!("No scheduled tasks yet.");
                 println!("\nUsage:");
-                println!("  zeroclaw cron add '0 9 * * *' 'agent -m \"Good morning!\"'");
+                println!("  zeroclaw cron add '*/30 * * * *' 'df -h /'");
+                println!("  zeroclaw cron add '0 9 * * *' 'Good morning briefing' --agent");
                 return Ok(());
             }
 
@@ -35,28 +132,46 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 let last_run = job
                     .last_run
                     .map_or_else(|| "never".into(), |d| d.to_rfc3339());
-                let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
+                let last_status = job.last_status.as_deref().unwrap_or("n/a");
                 println!(
-                    "- {} | {} | next={} | last={} ({})\n    cmd: {}",
+                    "- {} | {} | next={} | last={} ({})\n    {}",
                     job.id,
                     job.expression,
                     job.next_run.to_rfc3339(),
                     last_run,
                     last_status,
-                    job.command
+                    job.describe()
                 );
+                if last_status == "error" {
+                    if let Some(output) = &job.last_output {
+                        println!("    last output: {}", last_line(output));
+                    }
+                }
             }
             Ok(())
         }
         crate::CronCommands::Add {
             expression,
             command,
+            agent,
+            provider,
+            model,
         } => {
-            let job = add_job(config, &expression, &command)?;
+            let job = if agent {
+                add_agent_job(
+                    config,
+                    &expression,
+                    &command,
+                    provider.as_deref(),
+                    model.as_deref(),
+                )?
+            } else {
+                add_job(config, &expression, &command)?
+            };
             println!("✅ Added cron job {}", job.id);
             println!("  Expr: {}", job.expression);
             println!("  Next: {}", job.next_run.to_rfc3339());
-            println!("  Cmd : {}", job.command);
+            println!("  Run : {}", job.describe());
             Ok(())
         }
         crate::CronCommands::Remove { id } => remove_job(config, &id),
@@ -64,18 +179,44 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
 }
 
 pub fn add_job(config: &Config, expression: &str, command: &str) -> Result<CronJob> {
+    insert_job(config, expression, command, JobKind::Shell, None, None)
+}
+
+/// Schedule `prompt` as a one-shot agent turn, optionally on another
+/// provider or model than the configured defaults.
+pub fn add_agent_job(
+    config: &Config,
+    expression: &str,
+    prompt: &str,
+    provider: Option<&str>,
+    model: Option<&str>,
+) -> Result<CronJob> {
+    insert_job(config, expression, prompt, JobKind::Agent, provider, model)
+}
+
+fn insert_job(
+    config: &Config,
+    expression: &str,
+    command: &str,
+    kind: JobKind,
+    provider: Option<&str>,
+    model: Option<&str>,
+) -> Result<CronJob> {
     let now = Utc::now();
     let next_run = next_run_for(expression, now)?;
     let id = Uuid::new_v4().to_string();
 
     with_connection(config, |conn| {
         conn.execute(
-            "INSERT INTO cron_jobs (id, expression, command, created_at, next_run)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO cron_jobs (id, expression, command, kind, provider, model, created_at, next_run)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 id,
                 expression,
                 command,
+                kind.as_str(),
+                provider,
+                model,
                 now.to_rfc3339(),
                 next_run.to_rfc3339()
             ],
@@ -88,49 +229,18 @@ pub fn add_job(config: &Config, expression: &str, command: &str) -> Result<CronJ
         id,
         expression: expression.to_string(),
         command: command.to_string(),
+        kind,
+        provider: provider.map(String::from),
+        model: model.map(String::from),
         next_run,
         last_run: None,
         last_status: None,
+        last_output: None,
     })
 }
 
 pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
-    with_connection(config, |conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, expression, command, next_run, last_run, last_status
-             FROM cron_jobs ORDER BY next_run ASC",
-        )?;
-
-        let rows = stmt.query_map([], |row| {
-            let next_run_raw: String = row.get(3)?;
-            let last_run_raw: Option<String> = row.get(4)?;
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                next_run_raw,
-                last_run_raw,
-                row.get::<_, Option<String>>(5)?,
-            ))
-        })?;
-
-        let mut jobs = Vec::new();
-        for row in rows {
-            let (id, expression, command, next_run_raw, last_run_raw, last_status) = row?;
-            jobs.push(CronJob {
-                id,
-                expression,
-                command,
-                next_run: parse_rfc3339(&next_run_raw)?,
-                last_run: match last_run_raw {
-                    Some(raw) => Some(parse_rfc3339(&raw)?),
-                    None => None,
-                },
-                last_status,
-            });
-        }
-        Ok(jobs)
-    })
+    with_connection(config, |conn| query_jobs(conn, "", []))
 }
 
 pub fn remove_job(config: &Config, id: &str) -> Result<()> {
@@ -149,40 +259,7 @@ pub fn remove_job(config: &Config, id: &str) -> Result<()> {
 
 pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, expression, command, next_run, last_run, last_status
-             FROM cron_jobs WHERE next_run <= ?1 ORDER BY next_run ASC",
-        )?;
-
-        let rows = stmt.query_map(params![now.to_rfc3339()], |row| {
-            let next_run_raw: String = row.get(3)?;
-            let last_run_raw: Option<String> = row.get(4)?;
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                next_run_raw,
-                last_run_raw,
-                row.get::<_, Option<String>>(5)?,
-            ))
-        })?;
-
-        let mut jobs = Vec::new();
-        for row in rows {
-            let (id, expression, command, next_run_raw, last_run_raw, last_status) = row?;
-            jobs.push(CronJob {
-                id,
-                expression,
-                command,
-                next_run: parse_rfc3339(&next_run_raw)?,
-                last_run: match last_run_raw {
-                    Some(raw) => Some(parse_rfc3339(&raw)?),
-                    None => None,
-                },
-                last_status,
-            });
-        }
-        Ok(jobs)
+        query_jobs(conn, "WHERE next_run <= ?1", params![now.to_rfc3339()])
     })
 }
 
@@ -214,7 +291,7 @@ pub fn reschedule_after_run(
     })
 }
 
-fn next_run_for(expression: &str, from: DateTime<Utc>) -> Result<DateTime<Utc>> {
+pub(crate) fn next_run_for(expression: &str, from: DateTime<Utc>) -> Result<DateTime<Utc>> {
     let normalized = normalize_expression(expression)?;
     let schedule = Schedule::from_str(&normalized)
         .with_context(|| format!("Invalid cron expression: {expression}"))?;
@@ -239,6 +316,17 @@ fn normalize_expression(expression: &str) -> Result<String> {
     }
 }
 
+/// Last non-empty line of a run's output, shortened for `cron list`.
+fn last_line(output: &str) -> String {
+    let line = output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.ends_with(':'))
+        .unwrap_or("");
+    crate::util::truncate_with_ellipsis(line, 120)
+}
+
 fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
     let parsed = DateTime::parse_from_rfc3339(raw)
         .with_context(|| format!("Invalid RFC3339 timestamp in cron DB: {raw}"))?;
@@ -264,15 +352,40 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             next_run    TEXT NOT NULL,
             last_run    TEXT,
             last_status TEXT,
-            last_output TEXT
+            last_output TEXT,
+            kind        TEXT NOT NULL DEFAULT 'shell',
+            provider    TEXT,
+            model       TEXT
         );
         CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);",
     )
     .context("Failed to initialize cron schema")?;
+    migrate(&conn)?;
 
     f(&conn)
 }
 
+/// Add the agent-job columns to databases created before they existed.
+fn migrate(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (column, definition) in [
+        ("kind", "TEXT NOT NULL DEFAULT 'shell'"),
+        ("provider", "TEXT"),
+        ("model", "TEXT"),
+    ] {
+        if !columns.iter().any(|c| c == column) {
+            conn.execute_batch(&format!(
+                "ALTER TABLE cron_jobs ADD COLUMN {column} {definition};"
+            ))
+            .context("Failed to migrate cron schema")?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,4 +465,45 @@ mod tests {
         assert_eq!(stored.last_status.as_deref(), Some("error"));
         assert!(stored.last_run.is_some());
     }
+
+    #[test]
+    fn agent_jobs_keep_their_overrides_and_old_dbs_migrate() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let db = config.workspace_dir.join("cron").join("jobs.db");
+        std::fs::create_dir_all(db.parent().unwrap()).unwrap();
+        Connection::open(&db)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE cron_jobs (id TEXT PRIMARY KEY, expression TEXT NOT NULL,
+                 command TEXT NOT NULL, created_at TEXT NOT NULL, next_run TEXT NOT NULL,
+                 last_run TEXT, last_status TEXT, last_output TEXT);
+                 INSERT INTO cron_jobs VALUES ('old', '* * * * *', 'echo old',
+                 '2025-01-01T00:00:00Z', '2025-01-01T00:01:00Z', NULL, NULL, NULL);",
+            )
+            .unwrap();
+
+        let job = add_agent_job(
+            &config,
+            "0 9 * * *",
+            "Morning briefing",
+            Some("ollama"),
+            Some("llama3.2"),
+        )
+        .unwrap();
+        reschedule_after_run(&config, &job, false, "status=1\nstderr:\nprovider down\n").unwrap();
+
+        let listed = list_jobs(&config).unwrap();
+        assert_eq!(listed[0].kind, JobKind::Shell);
+        let stored = listed.iter().find(|j| j.id == job.id).unwrap();
+        assert_eq!(stored.kind, JobKind::Agent);
+        assert_eq!(
+            stored.describe(),
+            "agent (ollama/llama3.2): Morning briefing"
+        );
+        assert_eq!(
+            last_line(stored.last_output.as_deref().unwrap()),
+            "provider down"
+        );
+    }
 }
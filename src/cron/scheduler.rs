@@ -1,6 +1,8 @@
 use crate::config::Config;
-use crate::cron::{due_jobs, reschedule_after_run, CronJob};
+use crate::cron::{due_jobs, reschedule_after_run, CronJob, JobKind};
+use crate::observability::ObserverEvent;
 use crate::security::SecurityPolicy;
+use crate::tasks::{TaskOrigin, TaskStatus, TaskStore};
 use anyhow::Result;
 use chrono::Utc;
 use tokio::process::Command;
@@ -12,6 +14,7 @@ pub async fn run(config: Config) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
     let mut interval = time::interval(Duration::from_secs(poll_secs));
     let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let observer = crate::observability::create_observer(&config.observability);
 
     crate::health::mark_component_ok("scheduler");
 
@@ -29,7 +32,17 @@ pub async fn run(config: Config) -> Result<()> {
 
         for job in jobs {
             crate::health::mark_component_ok("scheduler");
+            let task = open_task(&config, &job);
+            let started = std::time::Instant::now();
             let (success, output) = execute_job_with_retry(&config, &security, &job).await;
+            observer.record_event(&ObserverEvent::CronRun {
+                job: job.id.clone(),
+                duration: started.elapsed(),
+                success,
+            });
+            if let Some((store, id)) = task {
+                close_task(&store, &id, success, &output);
+            }
 
             if !success {
                 crate::health::mark_component_error("scheduler", format!("job {} failed", job.id));
@@ -43,6 +56,38 @@ pub async fn run(config: Config) -> Result<()> {
     }
 }
 
+/// Track each run as a task so cron work shows up in `zeroclaw tasks`.
+fn open_task(config: &Config, job: &CronJob) -> Option<(TaskStore, String)> {
+    let store = TaskStore::open(&config.workspace_dir)
+        .map_err(|e| tracing::warn!("Failed to open tasks DB: {e}"))
+        .ok()?;
+    let title = format!("cron {}: {}", job.expression, job.command);
+    match store.create(&title, TaskOrigin::Cron, TaskStatus::Running) {
+        Ok(task) => Some((store, task.id)),
+        Err(e) => {
+            tracing::warn!("Failed to record cron task: {e}");
+            None
+        }
+    }
+}
+
+fn close_task(store: &TaskStore, id: &str, success: bool, output: &str) {
+    let status = if success {
+        TaskStatus::Done
+    } else {
+        TaskStatus::Failed
+    };
+    let summary = crate::util::truncate_with_ellipsis(output.trim(), 500);
+    let note = if summary.is_empty() {
+        "(no output)"
+    } else {
+        &summary
+    };
+    if let Err(e) = store.set_status(id, status, Some(note)) {
+        tracing::warn!("Failed to update cron task: {e}");
+    }
+}
+
 async fn execute_job_with_retry(
     config: &Config,
     security: &SecurityPolicy,
@@ -87,7 +132,7 @@ fn strip_wrapping_quotes(token: &str) -> &str {
     token.trim_matches(|c| c == '"' || c == '\'')
 }
 
-fn forbidden_path_argument(security: &SecurityPolicy, command: &str) -> Option<String> {
+pub(crate) fn forbidden_path_argument(security: &SecurityPolicy, command: &str) -> Option<String> {
     let mut normalized = command.to_string();
     for sep in ["&&", "||"] {
         normalized = normalized.replace(sep, "\x00");
@@ -138,6 +183,9 @@ async fn run_job_command(
     security: &SecurityPolicy,
     job: &CronJob,
 ) -> (bool, String) {
+    if job.kind == JobKind::Agent {
+        return run_agent_job(config, job).await;
+    }
     if !security.is_command_allowed(&job.command) {
         return (
             false,
@@ -155,13 +203,53 @@ async fn run_job_command(
         );
     }
 
-    let output = Command::new("sh")
-        .arg("-lc")
-        .arg(&job.command)
+    let output = crate::platform::shell_command(&job.command, true)
         .current_dir(&config.workspace_dir)
         .output()
         .await;
+    combined_output(output)
+}
 
+/// `zeroclaw agent` arguments for one run of an agent job. Each job keeps its
+/// own session so its runs stay out of the interactive CLI history.
+fn agent_args(config: &Config, job: &CronJob) -> Vec<String> {
+    let short_id = &job.id[..job.id.len().min(8)];
+    let mut args = vec![
+        "agent".to_string(),
+        "--message".into(),
+        job.command.clone(),
+        "--temperature".into(),
+        config.default_temperature.to_string(),
+        "--session".into(),
+        format!("cron-{short_id}"),
+    ];
+    if let Some(provider) = &job.provider {
+        args.extend(["--provider".into(), provider.clone()]);
+    }
+    if let Some(model) = &job.model {
+        args.extend(["--model".into(), model.clone()]);
+    }
+    args
+}
+
+/// Agent jobs run as a child `zeroclaw agent` process, so a failing turn
+/// cannot take the scheduler down and the output is captured like a shell
+/// job's.
+async fn run_agent_job(config: &Config, job: &CronJob) -> (bool, String) {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return (false, format!("spawn error: {e}")),
+    };
+    let output = Command::new(exe)
+        .args(agent_args(config, job))
+        .current_dir(&config.workspace_dir)
+        .kill_on_drop(true)
+        .output()
+        .await;
+    combined_output(output)
+}
+
+fn combined_output(output: std::io::Result<std::process::Output>) -> (bool, String) {
     match output {
         Ok(output) => {
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -200,12 +288,32 @@ mod tests {
             id: "test-job".into(),
             expression: "* * * * *".into(),
             command: command.into(),
+            kind: JobKind::Shell,
+            provider: None,
+            model: None,
             next_run: Utc::now(),
             last_run: None,
             last_status: None,
+            last_output: None,
         }
     }
 
+    #[test]
+    fn agent_jobs_run_the_agent_with_their_overrides() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let mut job = test_job("Summarise my inbox");
+        job.id = "0123456789abcdef".into();
+        job.kind = JobKind::Agent;
+        job.model = Some("gpt-4o-mini".into());
+
+        let args = agent_args(&config, &job);
+        assert_eq!(args[..3], ["agent", "--message", "Summarise my inbox"]);
+        assert!(args.join(" ").contains("--session cron-01234567"));
+        assert!(args.ends_with(&["--model".into(), "gpt-4o-mini".into()]));
+        assert!(!args.contains(&"--provider".to_string()));
+    }
+
     #[tokio::test]
     async fn run_job_command_success() {
         let tmp = TempDir::new().unwrap();
@@ -49,7 +49,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 max_backoff,
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    async move { crate::channels::start_channels(cfg, false).await }
                 },
             ));
         } else {
@@ -84,6 +84,22 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         ));
     }
 
+    {
+        let focus_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "focus",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = focus_cfg.clone();
+                async move { crate::focus::run(cfg).await }
+            },
+        ));
+    }
+
+    spawn_delivery_components(&config, initial_backoff, max_backoff, &mut handles);
+    spawn_optional_components(&config, initial_backoff, max_backoff, &mut handles);
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
@@ -102,6 +118,152 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// The outbox sender and scheduled workflows.
+fn spawn_delivery_components(
+    config: &Config,
+    initial_backoff: u64,
+    max_backoff: u64,
+    handles: &mut Vec<JoinHandle<()>>,
+) {
+    {
+        let outbox_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "outbox",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = outbox_cfg.clone();
+                async move { crate::outbox::run(cfg).await }
+            },
+        ));
+    }
+
+    {
+        let workflows_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "workflows",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = workflows_cfg.clone();
+                async move { crate::workflows::run(cfg).await }
+            },
+        ));
+    }
+}
+
+/// Components that only run when configured: meeting briefs, the metrics
+/// port, presence detection, event triggers and the workspace index.
+fn spawn_optional_components(
+    config: &Config,
+    initial_backoff: u64,
+    max_backoff: u64,
+    handles: &mut Vec<JoinHandle<()>>,
+) {
+    if config.heartbeat.enabled && has_prep_brief_calendars(config) {
+        let briefs_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "meeting_briefs",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = briefs_cfg.clone();
+                async move { crate::heartbeat::briefs::run(cfg).await }
+            },
+        ));
+    }
+
+    if let (Some(port), "prometheus") = (
+        config.observability.metrics_port,
+        config.observability.backend.as_str(),
+    ) {
+        handles.push(spawn_component_supervisor(
+            "metrics",
+            initial_backoff,
+            max_backoff,
+            move || crate::gateway::run_metrics_server(port),
+        ));
+    }
+
+    if config.presence.enabled {
+        let presence_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "presence",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = presence_cfg.clone();
+                async move { crate::presence::run(cfg).await }
+            },
+        ));
+    }
+
+    if config.sync.enabled {
+        let sync_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "sync",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = sync_cfg.clone();
+                async move { crate::sync::run(cfg).await }
+            },
+        ));
+    }
+
+    if config.inbox.enabled {
+        let inbox_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "inbox",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = inbox_cfg.clone();
+                async move { crate::inbox::run(cfg).await }
+            },
+        ));
+    }
+
+    if config.eval.enabled {
+        let eval_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "eval",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = eval_cfg.clone();
+                async move { crate::eval::run(cfg).await }
+            },
+        ));
+    }
+
+    if config.workspace_index.enabled {
+        let index_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "workspace_index",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = index_cfg.clone();
+                async move { crate::workspace_index::run(cfg).await }
+            },
+        ));
+    }
+
+    if !config.triggers.rules.is_empty() {
+        let triggers_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "triggers",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = triggers_cfg.clone();
+                async move { crate::triggers::run(cfg).await }
+            },
+        ));
+    }
+}
+
 pub fn state_file_path(config: &Config) -> PathBuf {
     config
         .config_path
@@ -185,15 +347,43 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     loop {
         interval.tick().await;
 
+        match crate::goals::check_due(&config).await {
+            Ok(0) => {}
+            Ok(checked) => tracing::info!("Heartbeat: checked {checked} goal(s)"),
+            Err(e) => tracing::warn!("Heartbeat goal checks failed: {e}"),
+        }
+
         let tasks = engine.collect_tasks().await?;
-        if tasks.is_empty() {
+        let checklist = crate::heartbeat::tasks::load(&config.workspace_dir).unwrap_or_else(|e| {
+            crate::health::mark_component_error("heartbeat", format!("{e:#}"));
+            tracing::warn!("Heartbeat tasks not loaded: {e:#}");
+            Vec::new()
+        });
+        if tasks.is_empty() && checklist.is_empty() {
+            continue;
+        }
+        if crate::focus::holds(&config, "heartbeat") {
+            tracing::info!(
+                "Focus on: {} heartbeat task(s) wait",
+                tasks.len() + checklist.len()
+            );
             continue;
         }
 
         for task in tasks {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
-            if let Err(e) = crate::agent::run(config.clone(), Some(prompt), None, None, temp).await
+            if let Err(e) = Box::pin(crate::agent::run(
+                config.clone(),
+                Some(prompt),
+                None,
+                None,
+                temp,
+                None,
+                false,
+                None,
+            ))
+            .await
             {
                 crate::health::mark_component_error("heartbeat", e.to_string());
                 tracing::warn!("Heartbeat task failed: {e}");
@@ -201,9 +391,26 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 crate::health::mark_component_ok("heartbeat");
             }
         }
+
+        match crate::heartbeat::tasks::run(&config, &checklist).await {
+            Ok(0) => {}
+            Ok(sent) => tracing::info!("Heartbeat: {sent} task(s) had something new"),
+            Err(e) => {
+                crate::health::mark_component_error("heartbeat", format!("{e:#}"));
+                tracing::warn!("{e:#}");
+            }
+        }
     }
 }
 
+fn has_prep_brief_calendars(config: &Config) -> bool {
+    config
+        .integrations
+        .calendar
+        .as_ref()
+        .is_some_and(|c| c.calendars.iter().any(|feed| feed.prep_briefs))
+}
+
 fn has_supervised_channels(config: &Config) -> bool {
     config.channels_config.telegram.is_some()
         || config.channels_config.discord.is_some()
@@ -212,6 +419,7 @@ fn has_supervised_channels(config: &Config) -> bool {
         || config.channels_config.matrix.is_some()
         || config.channels_config.whatsapp.is_some()
         || config.channels_config.email.is_some()
+        || config.channels_config.signal.is_some()
 }
 
 #[cfg(test)]
@@ -288,6 +496,7 @@ mod tests {
         config.channels_config.telegram = Some(crate::config::TelegramConfig {
             bot_token: "token".into(),
             allowed_users: vec![],
+            voice_replies: false,
         });
         assert!(has_supervised_channels(&config));
     }
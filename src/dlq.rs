@@ -0,0 +1,404 @@
+//! Dead-letter queue for channel deliveries that failed after retries.
+//!
+//! Anything the agent tried to send (a reply, an on-call briefing, a webhook
+//! delivery) and couldn't is parked here instead of being dropped, and can be
+//! replayed with `zeroclaw dlq retry` once the outage is over.
+
+use crate::channels::Channel;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub id: String,
+    pub channel: String,
+    pub recipient: String,
+    pub message: String,
+    pub error: String,
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: DateTime<Utc>,
+}
+
+pub async fn handle_command(command: crate::DlqCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::DlqCommands::List => {
+            let letters = list(config)?;
+            if letters.is_empty() {
+                println!("📭 Dead-letter queue is empty.");
+                return Ok(());
+            }
+            println!("📬 Dead letters ({}):", letters.len());
+            for letter in letters {
+                println!(
+                    "- {} | {} → {} | attempts={} | last={}\n    error: {}\n    msg:   {}",
+                    short_id(&letter.id),
+                    letter.channel,
+                    if letter.recipient.is_empty() {
+                        "(default)"
+                    } else {
+                        &letter.recipient
+                    },
+                    letter.attempts,
+                    letter.last_attempt_at.to_rfc3339(),
+                    crate::util::truncate_with_ellipsis(&letter.error, 120),
+                    crate::util::truncate_with_ellipsis(&letter.message.replace('\n', " "), 120)
+                );
+            }
+            Ok(())
+        }
+        crate::DlqCommands::Retry { id } => {
+            let (delivered, failed) = retry(config, id.as_deref(), |name| {
+                crate::channels::find_channel(config, name)
+            })
+            .await?;
+            println!("✅ Delivered {delivered}, still failing {failed}");
+            Ok(())
+        }
+        crate::DlqCommands::Purge { id, all } => {
+            let removed = match (id, all) {
+                (Some(id), _) => {
+                    let full = resolve_id(config, &id)?;
+                    usize::from(remove(config, &full)?)
+                }
+                (None, true) => purge_all(config)?,
+                (None, false) => {
+                    anyhow::bail!("Pass a dead-letter ID, or --all to purge everything")
+                }
+            };
+            println!("🗑️  Purged {removed} dead letter(s)");
+            Ok(())
+        }
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(8)]
+}
+
+/// Send through `channel`; on failure park the message in the queue and return the error.
+pub async fn send_or_dead_letter(
+    config: &Config,
+    channel: &dyn Channel,
+    message: &str,
+    recipient: &str,
+) -> Result<()> {
+    let Err(error) = channel.send(message, recipient).await else {
+        return Ok(());
+    };
+    match push(
+        config,
+        channel.name(),
+        recipient,
+        message,
+        &format!("{error:#}"),
+    ) {
+        Ok(letter) => tracing::warn!(
+            "Delivery to {} failed; saved as dead letter {}",
+            channel.name(),
+            short_id(&letter.id)
+        ),
+        Err(e) => tracing::error!(
+            "Delivery to {} failed and could not be queued: {e}",
+            channel.name()
+        ),
+    }
+    Err(error)
+}
+
+pub fn push(
+    config: &Config,
+    channel: &str,
+    recipient: &str,
+    message: &str,
+    error: &str,
+) -> Result<DeadLetter> {
+    let now = Utc::now();
+    let letter = DeadLetter {
+        id: Uuid::new_v4().to_string(),
+        channel: channel.to_string(),
+        recipient: recipient.to_string(),
+        message: message.to_string(),
+        error: error.to_string(),
+        attempts: 1,
+        created_at: now,
+        last_attempt_at: now,
+    };
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO dead_letters
+             (id, channel, recipient, message, error, attempts, created_at, last_attempt_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                letter.id,
+                letter.channel,
+                letter.recipient,
+                letter.message,
+                letter.error,
+                letter.attempts,
+                now.to_rfc3339(),
+                now.to_rfc3339()
+            ],
+        )
+        .context("Failed to insert dead letter")?;
+        Ok(())
+    })?;
+    Ok(letter)
+}
+
+pub fn list(config: &Config) -> Result<Vec<DeadLetter>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, channel, recipient, message, error, attempts, created_at, last_attempt_at
+             FROM dead_letters ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, u32>(5)?,
+                row.get::<_, String>(6)?,
+                row.get::<_, String>(7)?,
+            ))
+        })?;
+
+        let mut letters = Vec::new();
+        for row in rows {
+            let (id, channel, recipient, message, error, attempts, created, last) = row?;
+            letters.push(DeadLetter {
+                id,
+                channel,
+                recipient,
+                message,
+                error,
+                attempts,
+                created_at: parse_rfc3339(&created)?,
+                last_attempt_at: parse_rfc3339(&last)?,
+            });
+        }
+        Ok(letters)
+    })
+}
+
+/// Expand a unique ID prefix (as shown by `dlq list`) to the full ID.
+pub fn resolve_id(config: &Config, prefix: &str) -> Result<String> {
+    let matches: Vec<String> = list(config)?
+        .into_iter()
+        .map(|l| l.id)
+        .filter(|id| id.starts_with(prefix.trim()))
+        .collect();
+    match matches.as_slice() {
+        [one] => Ok(one.clone()),
+        [] => anyhow::bail!("Dead letter '{prefix}' not found"),
+        _ => anyhow::bail!("Dead letter ID '{prefix}' is ambiguous; use more characters"),
+    }
+}
+
+pub fn remove(config: &Config, id: &str) -> Result<bool> {
+    let changed = with_connection(config, |conn| {
+        conn.execute("DELETE FROM dead_letters WHERE id = ?1", params![id])
+            .context("Failed to delete dead letter")
+    })?;
+    Ok(changed > 0)
+}
+
+pub fn purge_all(config: &Config) -> Result<usize> {
+    with_connection(config, |conn| {
+        conn.execute("DELETE FROM dead_letters", [])
+            .context("Failed to purge dead letters")
+    })
+}
+
+fn record_failure(config: &Config, id: &str, error: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE dead_letters
+             SET attempts = attempts + 1, error = ?1, last_attempt_at = ?2
+             WHERE id = ?3",
+            params![error, Utc::now().to_rfc3339(), id],
+        )
+        .context("Failed to update dead letter")?;
+        Ok(())
+    })
+}
+
+/// Re-send one dead letter (by ID prefix) or all of them. Returns (delivered, still failing).
+pub async fn retry(
+    config: &Config,
+    id: Option<&str>,
+    lookup: impl Fn(&str) -> Option<Arc<dyn Channel>>,
+) -> Result<(usize, usize)> {
+    let letters = match id {
+        Some(prefix) => {
+            let full = resolve_id(config, prefix)?;
+            list(config)?.into_iter().filter(|l| l.id == full).collect()
+        }
+        None => list(config)?,
+    };
+
+    let (mut delivered, mut failed) = (0, 0);
+    for letter in letters {
+        let result = match lookup(&letter.channel) {
+            Some(channel) => channel
+                .send(&letter.message, &letter.recipient)
+                .await
+                .map_err(|e| format!("{e:#}")),
+            None => Err(format!("Channel '{}' is not configured", letter.channel)),
+        };
+        match result {
+            Ok(()) => {
+                remove(config, &letter.id)?;
+                delivered += 1;
+            }
+            Err(error) => {
+                record_failure(config, &letter.id, &error)?;
+                failed += 1;
+            }
+        }
+    }
+    Ok((delivered, failed))
+}
+
+fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Invalid RFC3339 timestamp in DLQ DB: {raw}"))?;
+    Ok(parsed.with_timezone(&Utc))
+}
+
+fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = config.workspace_dir.join("dlq").join("dead_letters.db");
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create DLQ directory: {}", parent.display()))?;
+    }
+
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open DLQ DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS dead_letters (
+            id              TEXT PRIMARY KEY,
+            channel         TEXT NOT NULL,
+            recipient       TEXT NOT NULL,
+            message         TEXT NOT NULL,
+            error           TEXT NOT NULL,
+            attempts        INTEGER NOT NULL,
+            created_at      TEXT NOT NULL,
+            last_attempt_at TEXT NOT NULL
+        );",
+    )
+    .context("Failed to initialize DLQ schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    /// Fails until `up` is flipped on.
+    struct FlakyChannel {
+        up: AtomicBool,
+    }
+
+    #[async_trait]
+    impl Channel for FlakyChannel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, _message: &str, _recipient: &str) -> anyhow::Result<()> {
+            if self.up.load(Ordering::SeqCst) {
+                Ok(())
+            } else {
+                anyhow::bail!("503 Service Unavailable")
+            }
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn push_list_purge_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let letter = push(&config, "slack", "C123", "Deploy finished", "timeout").unwrap();
+        push(&config, "webhook", "ops", "Backup done", "502").unwrap();
+
+        let letters = list(&config).unwrap();
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters[0].message, "Deploy finished");
+        assert_eq!(letters[0].attempts, 1);
+
+        assert_eq!(
+            resolve_id(&config, short_id(&letter.id)).unwrap(),
+            letter.id
+        );
+        assert!(remove(&config, &letter.id).unwrap());
+        assert_eq!(purge_all(&config).unwrap(), 1);
+        assert!(list(&config).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_send_is_queued_and_retried() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let channel = Arc::new(FlakyChannel {
+            up: AtomicBool::new(false),
+        });
+
+        assert!(
+            send_or_dead_letter(&config, channel.as_ref(), "hello", "ops")
+                .await
+                .is_err()
+        );
+        assert_eq!(list(&config).unwrap().len(), 1);
+
+        let lookup = |_: &str| Some(channel.clone() as Arc<dyn Channel>);
+        assert_eq!(retry(&config, None, lookup).await.unwrap(), (0, 1));
+        let letters = list(&config).unwrap();
+        assert_eq!(letters[0].attempts, 2);
+        assert!(letters[0].error.contains("503"));
+
+        channel.up.store(true, Ordering::SeqCst);
+        assert_eq!(retry(&config, None, lookup).await.unwrap(), (1, 0));
+        assert!(list(&config).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_keeps_letters_for_unconfigured_channels() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        push(&config, "matrix", "!room", "hi", "down").unwrap();
+
+        assert_eq!(retry(&config, None, |_| None).await.unwrap(), (0, 1));
+        assert!(list(&config).unwrap()[0].error.contains("not configured"));
+    }
+}
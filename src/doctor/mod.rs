@@ -106,6 +106,13 @@ pub fn run(config: &Config) -> Result<()> {
         println!("  Channel summary: {channel_count} total, {stale_channels} stale");
     }
 
+    if let Some(shared) = crate::redis::shared() {
+        match shared.ping() {
+            Ok(()) => println!("  ✅ redis reachable"),
+            Err(e) => println!("  ❌ redis unreachable: {e:#}"),
+        }
+    }
+
     Ok(())
 }
 
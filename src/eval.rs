@@ -0,0 +1,418 @@
+//! Model-quality canaries.
+//!
+//! A provider can swap or quantize the model behind a name without notice.
+//! `[eval]` runs a small set of prompts with checkable replies (exact
+//! answers, JSON output, length and latency limits) on a cron schedule, keeps
+//! every run in `eval/history.jsonl`, and alerts the owner when a canary that
+//! passed last time starts failing.
+
+use crate::config::{Config, EvalCanary, Severity};
+use crate::notifications::Alert;
+use crate::providers::{self, Provider};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Runs shown by `zeroclaw eval history`.
+const HISTORY_SHOWN: usize = 10;
+
+/// The checks used when `[[eval.canaries]]` is empty.
+pub fn builtin_canaries() -> Vec<EvalCanary> {
+    vec![
+        EvalCanary {
+            name: "arithmetic".into(),
+            prompt: "What is 17 * 23? Reply with the number only.".into(),
+            expect_regex: Some(r"^\s*391\.?\s*$".into()),
+            ..EvalCanary::default()
+        },
+        EvalCanary {
+            name: "exact_instruction".into(),
+            prompt: "Reply with exactly the word OK in capitals and nothing else.".into(),
+            expect_regex: Some(r"^\s*OK\.?\s*$".into()),
+            ..EvalCanary::default()
+        },
+        EvalCanary {
+            name: "json_output".into(),
+            prompt: "Give the capital city and country of France as a JSON object with keys \
+                     \"city\" and \"country\". Reply with the JSON only."
+                .into(),
+            expect_json: true,
+            expect_contains: vec!["paris".into()],
+            ..EvalCanary::default()
+        },
+        EvalCanary {
+            name: "summary".into(),
+            prompt: "Summarize in one sentence: The quarterly invoice from Acme is overdue by \
+                     two weeks. Finance asked us to chase it before Friday, otherwise the \
+                     account will be put on hold and the March delivery will slip."
+                .into(),
+            expect_contains: vec!["invoice".into()],
+            max_chars: Some(300),
+            ..EvalCanary::default()
+        },
+    ]
+}
+
+fn canaries(config: &Config) -> Vec<EvalCanary> {
+    if config.eval.canaries.is_empty() {
+        builtin_canaries()
+    } else {
+        config.eval.canaries.clone()
+    }
+}
+
+/// What went wrong with `reply`; empty when the canary passed.
+pub fn check(canary: &EvalCanary, reply: &str, latency_ms: u64) -> Vec<String> {
+    let mut failures = Vec::new();
+    let lower = reply.to_lowercase();
+    for needle in &canary.expect_contains {
+        if !lower.contains(&needle.to_lowercase()) {
+            failures.push(format!("reply does not contain {needle:?}"));
+        }
+    }
+    for needle in &canary.expect_not_contains {
+        if lower.contains(&needle.to_lowercase()) {
+            failures.push(format!("reply contains {needle:?}"));
+        }
+    }
+    if let Some(pattern) = &canary.expect_regex {
+        match regex::Regex::new(pattern) {
+            Ok(re) if re.is_match(reply) => {}
+            Ok(_) => failures.push(format!("reply does not match /{pattern}/")),
+            Err(e) => failures.push(format!("invalid expect_regex: {e}")),
+        }
+    }
+    if canary.expect_json {
+        let body = strip_code_fence(reply);
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(body) {
+            failures.push(format!("reply is not valid JSON: {e}"));
+        }
+    }
+    if let Some(max) = canary.max_chars {
+        let len = reply.chars().count();
+        if len > max {
+            failures.push(format!("reply is {len} chars (max {max})"));
+        }
+    }
+    if let Some(max) = canary.max_latency_ms {
+        if latency_ms > max {
+            failures.push(format!("took {latency_ms} ms (max {max})"));
+        }
+    }
+    failures
+}
+
+fn strip_code_fence(reply: &str) -> &str {
+    let trimmed = reply.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let rest = rest.split_once('\n').map_or("", |(_, body)| body);
+    rest.strip_suffix("```").unwrap_or(rest).trim()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryResult {
+    pub name: String,
+    pub passed: bool,
+    pub failures: Vec<String>,
+    pub latency_ms: u64,
+}
+
+/// One pass over all canaries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalRun {
+    pub at: DateTime<Utc>,
+    pub provider: String,
+    pub model: String,
+    pub results: Vec<CanaryResult>,
+}
+
+impl EvalRun {
+    pub fn passed_count(&self) -> usize {
+        self.results.iter().filter(|r| r.passed).count()
+    }
+
+    /// Canaries that passed in `previous` (same model) and fail now.
+    pub fn regressions(&self, previous: Option<&EvalRun>) -> Vec<&CanaryResult> {
+        let Some(previous) = previous.filter(|p| p.model == self.model) else {
+            return Vec::new();
+        };
+        self.results
+            .iter()
+            .filter(|r| {
+                !r.passed
+                    && previous
+                        .results
+                        .iter()
+                        .any(|p| p.name == r.name && p.passed)
+            })
+            .collect()
+    }
+}
+
+/// Run every canary against `provider`.
+pub async fn run_once(
+    config: &Config,
+    provider: &dyn Provider,
+    provider_name: &str,
+    model: &str,
+) -> EvalRun {
+    let mut results = Vec::new();
+    for canary in canaries(config) {
+        let started = Instant::now();
+        let reply = provider
+            .chat_with_system(
+                canary.system.as_deref(),
+                &canary.prompt,
+                model,
+                config.eval.temperature,
+            )
+            .await;
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let failures = match reply {
+            Ok(reply) => check(&canary, &reply, latency_ms),
+            Err(e) => vec![format!(
+                "provider error: {}",
+                providers::sanitize_api_error(&e.to_string())
+            )],
+        };
+        results.push(CanaryResult {
+            name: canary.name,
+            passed: failures.is_empty(),
+            failures,
+            latency_ms,
+        });
+    }
+    EvalRun {
+        at: Utc::now(),
+        provider: provider_name.to_string(),
+        model: model.to_string(),
+        results,
+    }
+}
+
+fn history_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("eval").join("history.jsonl")
+}
+
+/// Every recorded run, oldest first; unreadable lines are skipped.
+pub fn load_history(workspace_dir: &Path) -> Vec<EvalRun> {
+    std::fs::read_to_string(history_path(workspace_dir))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+pub fn append_history(workspace_dir: &Path, run: &EvalRun) -> Result<()> {
+    let path = history_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)?;
+    Ok(())
+}
+
+fn alert_message(run: &EvalRun, regressions: &[&CanaryResult]) -> String {
+    let mut message = format!(
+        "🐤 Model canary regression on {} ({}): {} of {} checks started failing.",
+        run.model,
+        run.provider,
+        regressions.len(),
+        run.results.len()
+    );
+    for result in regressions {
+        let _ = write!(
+            message,
+            "\n- {}: {}",
+            result.name,
+            result.failures.join("; ")
+        );
+    }
+    message
+}
+
+async fn notify(config: &Config, message: &str) {
+    let alert = Alert::new("eval", message).severity(Severity::Warning).via(
+        config.eval.notify_channel.as_deref(),
+        config.eval.notify_recipient.as_deref(),
+    );
+    crate::notifications::notify(config, alert).await;
+}
+
+/// Run, record and compare with the previous run; returns the new run.
+async fn evaluate(config: &Config) -> Result<(EvalRun, Option<String>)> {
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let provider = providers::create_resilient_provider(
+        provider_name,
+        config.api_key.as_deref(),
+        &config.reliability,
+        &config.providers.fallback,
+    )?;
+    let model = config
+        .default_model
+        .as_deref()
+        .unwrap_or("anthropic/claude-sonnet-4-20250514");
+    let previous = load_history(&config.workspace_dir).pop();
+    let run = run_once(config, provider.as_ref(), provider_name, model).await;
+    append_history(&config.workspace_dir, &run)?;
+    let regressions = run.regressions(previous.as_ref());
+    let alert = (!regressions.is_empty()).then(|| alert_message(&run, &regressions));
+    Ok((run, alert))
+}
+
+/// Daemon component: run the canaries on `[eval].schedule`.
+pub async fn run(config: Config) -> Result<()> {
+    loop {
+        let now = Utc::now();
+        let next = crate::cron::next_run_for(&config.eval.schedule, now)?;
+        crate::health::mark_component_ok("eval");
+        tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+        let (run, alert) = evaluate(&config).await?;
+        tracing::info!(
+            "Canaries: {}/{} passed on {}",
+            run.passed_count(),
+            run.results.len(),
+            run.model
+        );
+        if let Some(alert) = alert {
+            notify(&config, &alert).await;
+        }
+    }
+}
+
+pub async fn handle_command(command: Option<crate::EvalCommands>, config: &Config) -> Result<()> {
+    match command.unwrap_or(crate::EvalCommands::Run) {
+        crate::EvalCommands::Run => {
+            let (run, alert) = evaluate(config).await?;
+            println!(
+                "🐤 Canaries on {} ({}): {}/{} passed",
+                run.model,
+                run.provider,
+                run.passed_count(),
+                run.results.len()
+            );
+            for result in &run.results {
+                let mark = if result.passed { "✓" } else { "✗" };
+                println!("   {mark} {} ({} ms)", result.name, result.latency_ms);
+                for failure in &result.failures {
+                    println!("      - {failure}");
+                }
+            }
+            if let Some(alert) = alert {
+                println!("\n{alert}");
+            }
+            Ok(())
+        }
+        crate::EvalCommands::History => {
+            let history = load_history(&config.workspace_dir);
+            if history.is_empty() {
+                println!("No canary runs yet. Run `zeroclaw eval run`.");
+                return Ok(());
+            }
+            for run in history.iter().rev().take(HISTORY_SHOWN) {
+                let failing: Vec<&str> = run
+                    .results
+                    .iter()
+                    .filter(|r| !r.passed)
+                    .map(|r| r.name.as_str())
+                    .collect();
+                println!(
+                    "- {} | {} | {}/{} passed{}",
+                    run.at.format("%Y-%m-%d %H:%M"),
+                    run.model,
+                    run.passed_count(),
+                    run.results.len(),
+                    if failing.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" | failing: {}", failing.join(", "))
+                    }
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn builtin(name: &str) -> EvalCanary {
+        builtin_canaries()
+            .into_iter()
+            .find(|c| c.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn canary_checks_report_each_failed_property() {
+        assert!(check(&builtin("arithmetic"), " 391\n", 10).is_empty());
+        assert_eq!(check(&builtin("arithmetic"), "It is 391.", 10).len(), 1);
+
+        let json = builtin("json_output");
+        assert!(check(
+            &json,
+            "```json\n{\"city\": \"Paris\", \"country\": \"France\"}\n```",
+            10
+        )
+        .is_empty());
+        let failures = check(&json, "The capital is Lyon.", 10);
+        assert_eq!(failures.len(), 2, "{failures:?}");
+
+        let slow = EvalCanary {
+            max_latency_ms: Some(100),
+            max_chars: Some(5),
+            ..builtin("exact_instruction")
+        };
+        let failures = check(&slow, "Sure! OK", 250);
+        assert_eq!(failures.len(), 3, "{failures:?}");
+    }
+
+    #[test]
+    fn regressions_compare_with_the_last_run_of_the_same_model() {
+        let tmp = TempDir::new().unwrap();
+        let result = |name: &str, passed: bool| CanaryResult {
+            name: name.into(),
+            passed,
+            failures: if passed {
+                Vec::new()
+            } else {
+                vec!["reply does not match".into()]
+            },
+            latency_ms: 5,
+        };
+        let run = |model: &str, results| EvalRun {
+            at: Utc::now(),
+            provider: "openrouter".into(),
+            model: model.into(),
+            results,
+        };
+        let first = run("m1", vec![result("a", true), result("b", false)]);
+        append_history(tmp.path(), &first).unwrap();
+        let previous = load_history(tmp.path()).pop();
+
+        let second = run("m1", vec![result("a", false), result("b", false)]);
+        let regressions = second.regressions(previous.as_ref());
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].name, "a");
+        assert!(alert_message(&second, &regressions).contains("1 of 2 checks"));
+
+        let other_model = run("m2", vec![result("a", false)]);
+        assert!(other_model.regressions(previous.as_ref()).is_empty());
+        assert!(second.regressions(None).is_empty());
+    }
+}
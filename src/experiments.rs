@@ -0,0 +1,466 @@
+//! Provider A/B experiments (`[experiment]` in the config).
+//!
+//! A share of interactive turns, channel messages and the interactive CLI,
+//! goes to a candidate arm: another model or provider, a persona added to
+//! the system prompt, or both. Every turn of either arm is logged to
+//! `experiments/<name>.jsonl` with its latency and estimated token use.
+//! `zeroclaw experiments report` joins that log with the ratings left on the
+//! replies (`/rate`, 👍/👎) and compares the arms side by side.
+
+use crate::agent::context::estimate_tokens;
+use crate::config::{Config, ExperimentConfig, TokenPrices};
+use crate::conversations::{ConversationStore, Rating};
+use crate::providers::{self, Provider};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Below this many ratings per arm, the report warns that the difference
+/// may be noise.
+const FEW_RATINGS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Arm {
+    Control,
+    Candidate,
+}
+
+/// Provider, model and system prompt one turn is answered with.
+pub struct Route<'a> {
+    pub provider: &'a dyn Provider,
+    pub model: &'a str,
+    pub system_prompt: Cow<'a, str>,
+}
+
+/// Estimated tokens a turn sent and received, over all its provider calls.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TurnUsage {
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+}
+
+impl TurnUsage {
+    /// Count one provider call.
+    pub fn add(&mut self, system_prompt: &str, prompt: &str, reply: &str) {
+        self.input_tokens += estimate_tokens(system_prompt) + estimate_tokens(prompt);
+        self.output_tokens += estimate_tokens(reply);
+    }
+}
+
+/// One logged turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnRecord {
+    pub at: DateTime<Utc>,
+    pub arm: Arm,
+    pub model: String,
+    /// Turn in the conversation log, where its rating is kept
+    pub turn_id: Option<i64>,
+    pub ok: bool,
+    pub latency_ms: u64,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+}
+
+/// The running experiment: how turns are split and where they are logged.
+pub struct Experiment {
+    config: ExperimentConfig,
+    provider: Option<Arc<dyn Provider>>,
+    log_path: PathBuf,
+}
+
+impl Experiment {
+    pub fn new(
+        config: &ExperimentConfig,
+        workspace_dir: &Path,
+        provider: Option<Arc<dyn Provider>>,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            provider,
+            log_path: log_path(workspace_dir, &config.name),
+        }
+    }
+
+    /// The experiment `[experiment]` describes, or `None` when it is off or
+    /// has no candidate to compare.
+    pub fn for_config(config: &Config) -> Result<Option<Self>> {
+        let experiment = &config.experiment;
+        if !experiment.enabled {
+            return Ok(None);
+        }
+        if experiment.provider.is_none()
+            && experiment.model.is_none()
+            && experiment.persona.is_none()
+        {
+            tracing::warn!("[experiment] has no provider, model or persona to try; ignored");
+            return Ok(None);
+        }
+        let default_provider = config.default_provider.as_deref().unwrap_or("openrouter");
+        let provider = match experiment.provider.as_deref() {
+            Some(name) => {
+                // The configured key belongs to the default provider
+                let api_key = (name == default_provider)
+                    .then_some(config.api_key.as_deref())
+                    .flatten();
+                let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider(
+                    name,
+                    api_key,
+                    &config.reliability,
+                    &config.providers.fallback,
+                )?);
+                Some(provider)
+            }
+            None => None,
+        };
+        Ok(Some(Self::new(experiment, &config.workspace_dir, provider)))
+    }
+
+    /// The arm for the next turn.
+    pub fn pick(&self) -> Arm {
+        let roll = uuid::Uuid::new_v4().as_u128() % 100;
+        self.assign(u8::try_from(roll).unwrap_or_default())
+    }
+
+    /// The arm for a roll in `0..100`.
+    fn assign(&self, roll: u8) -> Arm {
+        if roll < self.config.share_percent {
+            Arm::Candidate
+        } else {
+            Arm::Control
+        }
+    }
+
+    /// `control` as `arm` answers it.
+    pub fn route<'a>(&'a self, arm: Arm, control: Route<'a>) -> Route<'a> {
+        if arm == Arm::Control {
+            return control;
+        }
+        let system_prompt = match self.config.persona.as_deref().map(str::trim) {
+            Some(persona) if !persona.is_empty() => Cow::Owned(format!(
+                "{}\n\n## Persona\n\n{persona}",
+                control.system_prompt
+            )),
+            _ => control.system_prompt,
+        };
+        Route {
+            provider: self.provider.as_deref().unwrap_or(control.provider),
+            model: self.config.model.as_deref().unwrap_or(control.model),
+            system_prompt,
+        }
+    }
+
+    /// Log a finished turn. Failures only warn: the reply matters more.
+    pub fn record(
+        &self,
+        arm: Arm,
+        model: &str,
+        turn_id: Option<i64>,
+        ok: bool,
+        latency: Duration,
+        usage: TurnUsage,
+    ) {
+        let record = TurnRecord {
+            at: Utc::now(),
+            arm,
+            model: model.to_string(),
+            turn_id,
+            ok,
+            latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+        };
+        if let Err(e) = append(&self.log_path, &record) {
+            tracing::warn!("Failed to log experiment turn: {e:#}");
+        }
+    }
+}
+
+fn log_path(workspace_dir: &Path, name: &str) -> PathBuf {
+    let file: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    workspace_dir
+        .join("experiments")
+        .join(format!("{file}.jsonl"))
+}
+
+fn append(path: &Path, record: &TurnRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+fn load(path: &Path) -> Result<Vec<TurnRecord>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("No turns logged in {}", path.display()))?;
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+// ── Report ────────────────────────────────────────────────────────
+
+/// One arm's numbers.
+#[derive(Debug, Default)]
+struct ArmStats {
+    models: Vec<String>,
+    turns: usize,
+    errors: usize,
+    up: usize,
+    down: usize,
+    latencies_ms: Vec<u64>,
+    input_tokens: usize,
+    output_tokens: usize,
+}
+
+impl ArmStats {
+    fn rated(&self) -> usize {
+        self.up + self.down
+    }
+
+    /// Latency below which `percent` of the turns finished.
+    fn latency(&self, percent: usize) -> Option<u64> {
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * percent).div_ceil(100).max(1);
+        sorted.get(rank - 1).copied()
+    }
+
+    fn per_turn(&self, total: usize) -> usize {
+        total.checked_div(self.turns).unwrap_or_default()
+    }
+
+    /// Average cost of a turn in USD.
+    fn cost_per_turn(&self, prices: TokenPrices) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let (input, output, turns) = (
+            self.input_tokens as f64,
+            self.output_tokens as f64,
+            self.turns.max(1) as f64,
+        );
+        (input * prices.input + output * prices.output) / 1_000_000.0 / turns
+    }
+}
+
+fn stats(records: &[TurnRecord], ratings: &BTreeMap<i64, Rating>) -> BTreeMap<Arm, ArmStats> {
+    let mut arms: BTreeMap<Arm, ArmStats> = [Arm::Control, Arm::Candidate]
+        .into_iter()
+        .map(|arm| (arm, ArmStats::default()))
+        .collect();
+    for record in records {
+        let arm = arms.entry(record.arm).or_default();
+        arm.turns += 1;
+        if !arm.models.contains(&record.model) {
+            arm.models.push(record.model.clone());
+        }
+        if !record.ok {
+            arm.errors += 1;
+            continue;
+        }
+        arm.latencies_ms.push(record.latency_ms);
+        arm.input_tokens += record.input_tokens;
+        arm.output_tokens += record.output_tokens;
+        match record.turn_id.and_then(|id| ratings.get(&id)) {
+            Some(Rating::Up) => arm.up += 1,
+            Some(Rating::Down) => arm.down += 1,
+            None => {}
+        }
+    }
+    arms
+}
+
+fn seconds(ms: Option<u64>) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    ms.map_or_else(|| "—".into(), |ms| format!("{:.1}s", ms as f64 / 1000.0))
+}
+
+fn format_report(
+    name: &str,
+    records: &[TurnRecord],
+    arms: &BTreeMap<Arm, ArmStats>,
+    config: &ExperimentConfig,
+) -> String {
+    let since = records
+        .first()
+        .map_or_else(String::new, |r| r.at.format(" since %Y-%m-%d").to_string());
+    let mut out = format!("🧪 Experiment '{name}'{since}, {} turns\n\n", records.len());
+    let _ = writeln!(out, "  {:<14}{:<24}candidate", "", "control");
+    let (control, candidate) = (&arms[&Arm::Control], &arms[&Arm::Candidate]);
+    let mut row = |label: &str, value: &dyn Fn(&ArmStats) -> String| {
+        let _ = writeln!(
+            out,
+            "  {label:<14}{:<24}{}",
+            value(control),
+            value(candidate)
+        );
+    };
+    row("model", &|arm| arm.models.join(", "));
+    row("turns", &|arm| arm.turns.to_string());
+    row("errors", &|arm| arm.errors.to_string());
+    row("rated", &|arm| arm.rated().to_string());
+    row("👍 rate", &|arm| {
+        (arm.up * 100)
+            .checked_div(arm.rated())
+            .map_or_else(|| "—".into(), |rate| format!("{rate}%"))
+    });
+    row("latency p50", &|arm| seconds(arm.latency(50)));
+    row("latency p90", &|arm| seconds(arm.latency(90)));
+    row("tokens/turn", &|arm| {
+        format!(
+            "{} in, {} out",
+            arm.per_turn(arm.input_tokens),
+            arm.per_turn(arm.output_tokens)
+        )
+    });
+    if let (Some(control_prices), Some(candidate_prices)) =
+        (config.control_prices, config.candidate_prices)
+    {
+        let control_cost = format!("${:.4}", control.cost_per_turn(control_prices));
+        let _ = writeln!(
+            out,
+            "  {:<14}{control_cost:<24}${:.4}",
+            "cost/turn",
+            candidate.cost_per_turn(candidate_prices)
+        );
+    }
+    if control.rated().min(candidate.rated()) < FEW_RATINGS {
+        let _ = write!(
+            out,
+            "\nFewer than {FEW_RATINGS} ratings on an arm: the 👍 rates may be noise."
+        );
+    }
+    out
+}
+
+/// The comparison for experiment `name` (default: the configured one).
+pub fn report(config: &Config, name: Option<&str>) -> Result<String> {
+    let name = name.unwrap_or(&config.experiment.name);
+    let records = load(&log_path(&config.workspace_dir, name))?;
+    let ratings: BTreeMap<i64, Rating> = ConversationStore::open(&config.workspace_dir)?
+        .annotated_turns(None, true)?
+        .into_iter()
+        .filter_map(|annotated| Some((annotated.turn.id, annotated.rating?)))
+        .collect();
+    let arms = stats(&records, &ratings);
+    Ok(format_report(name, &records, &arms, &config.experiment))
+}
+
+pub fn handle_command(command: Option<crate::ExperimentCommands>, config: &Config) -> Result<()> {
+    match command.unwrap_or(crate::ExperimentCommands::Report { name: None }) {
+        crate::ExperimentCommands::Report { name } => {
+            println!("{}", report(config, name.as_deref())?);
+            if !config.experiment.enabled {
+                println!("\n⚠️  [experiment] is disabled, so no new turns are being logged.");
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockProvider;
+
+    fn record(arm: Arm, turn_id: i64, ok: bool, latency_ms: u64) -> TurnRecord {
+        TurnRecord {
+            at: Utc::now(),
+            arm,
+            model: if arm == Arm::Control { "big" } else { "small" }.into(),
+            turn_id: Some(turn_id),
+            ok,
+            latency_ms,
+            input_tokens: 1000,
+            output_tokens: 200,
+        }
+    }
+
+    #[test]
+    fn candidate_gets_its_share_model_and_persona() {
+        let config = ExperimentConfig {
+            enabled: true,
+            share_percent: 20,
+            model: Some("small".into()),
+            persona: Some("Answer in one sentence.".into()),
+            ..ExperimentConfig::default()
+        };
+        let experiment = Experiment::new(&config, Path::new("/tmp"), None);
+        assert_eq!(experiment.assign(19), Arm::Candidate);
+        assert_eq!(experiment.assign(20), Arm::Control);
+
+        let provider = MockProvider::new();
+        let control = || Route {
+            provider: &provider,
+            model: "big",
+            system_prompt: Cow::Borrowed("You are ZeroClaw."),
+        };
+        let route = experiment.route(Arm::Candidate, control());
+        assert_eq!(route.model, "small");
+        assert!(route
+            .system_prompt
+            .ends_with("## Persona\n\nAnswer in one sentence."));
+        assert_eq!(
+            experiment.route(Arm::Control, control()).system_prompt,
+            "You are ZeroClaw."
+        );
+    }
+
+    #[test]
+    fn report_compares_ratings_latency_and_cost() {
+        let records = vec![
+            record(Arm::Control, 1, true, 1000),
+            record(Arm::Control, 2, true, 3000),
+            record(Arm::Control, 3, false, 9000),
+            record(Arm::Candidate, 4, true, 500),
+        ];
+        let ratings = BTreeMap::from([(1, Rating::Up), (2, Rating::Down), (4, Rating::Up)]);
+        let arms = stats(&records, &ratings);
+        let control = &arms[&Arm::Control];
+        assert_eq!((control.turns, control.errors, control.rated()), (3, 1, 2));
+        assert_eq!(control.latency(50), Some(1000));
+        assert_eq!(control.latency(90), Some(3000));
+        assert_eq!(arms[&Arm::Candidate].up, 1);
+
+        let config = ExperimentConfig {
+            control_prices: Some(TokenPrices {
+                input: 3.0,
+                output: 15.0,
+            }),
+            candidate_prices: Some(TokenPrices {
+                input: 1.0,
+                output: 5.0,
+            }),
+            ..ExperimentConfig::default()
+        };
+        let report = format_report("terse", &records, &arms, &config);
+        let rate = report.lines().find(|l| l.contains("👍 rate")).unwrap();
+        assert_eq!(
+            rate.split_whitespace().collect::<Vec<_>>(),
+            ["👍", "rate", "50%", "100%"]
+        );
+        assert!(report.contains("$0.0040"));
+        assert!(report.contains("may be noise"));
+    }
+}
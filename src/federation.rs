@@ -0,0 +1,393 @@
+//! Federation — `ZeroClaw` instances talking to each other over the gateway.
+//!
+//! Each side lists the other under `[federation.peers.<name>]` with its
+//! gateway URL and a token both sides share. The token never goes over the
+//! wire. A request to `POST /federation` carries the sender's own
+//! `[federation].name`, a fresh nonce, a timestamp and an HMAC over all of
+//! them and the body, keyed by the token; the receiver rejects stale or
+//! replayed nonces. The answer carries an HMAC of the nonce and the answer
+//! body, so the caller knows it came from the peer it meant to, not whatever
+//! answers at that address. Messages are answered by the peer's model; tasks
+//! run a full agent turn with tools on the peer, and only for peers with
+//! `allow_tasks`.
+
+use crate::config::{Config, FederationConfig, FederationPeerConfig};
+use crate::idempotency::IdempotencyCache;
+use crate::security::pairing::constant_time_eq;
+use crate::util::LazyClient;
+use crate::FederationCommands;
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Name the sender goes by in the receiver's `[federation.peers]`
+pub const PEER_HEADER: &str = "X-ZeroClaw-Peer";
+/// Random per-request value; each is accepted once
+pub const NONCE_HEADER: &str = "X-ZeroClaw-Nonce";
+/// Unix seconds when the request was signed
+pub const TIMESTAMP_HEADER: &str = "X-ZeroClaw-Timestamp";
+/// The sender's [`sign_request`] over the request
+pub const SIGNATURE_HEADER: &str = "X-ZeroClaw-Signature";
+/// The receiver's [`proof`] over its answer
+pub const PROOF_HEADER: &str = "X-ZeroClaw-Proof";
+/// Longest nonce the gateway accepts
+pub const MAX_NONCE_LEN: usize = 128;
+/// How far a request's timestamp may be from the receiver's clock.
+pub const MAX_CLOCK_SKEW_SECS: i64 = 300;
+
+/// Slack on top of the peer's task timeout before the caller gives up.
+const RESPONSE_GRACE: Duration = Duration::from_secs(15);
+
+static CLIENT: LazyClient = LazyClient::new(Duration::from_mins(1));
+
+/// What the peer should do with a message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestKind {
+    /// Answer with the model alone
+    #[default]
+    Message,
+    /// Run a full agent turn, tools included
+    Task,
+}
+
+/// Body of `POST /federation`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FederationRequest {
+    #[serde(default)]
+    pub kind: RequestKind,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FederationReply {
+    response: Option<String>,
+    error: Option<String>,
+}
+
+/// The signed parts of one request, as they arrived.
+pub struct SignedRequest<'a> {
+    pub peer: &'a str,
+    pub nonce: &'a str,
+    pub timestamp: &'a str,
+    pub body: &'a [u8],
+    pub signature: &'a str,
+}
+
+/// `hex(HMAC-SHA256(token, part₁ \n part₂ …))`; the leading part says what is
+/// signed, so a request signature can't pass for an answer's proof.
+fn mac(token: &str, parts: &[&[u8]]) -> String {
+    // HMAC takes keys of any length; this cannot fail
+    let Ok(mut mac) = <Hmac<Sha256> as Mac>::new_from_slice(token.as_bytes()) else {
+        return String::new();
+    };
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            mac.update(b"\n");
+        }
+        mac.update(part);
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// The sender's signature over a request.
+pub fn sign_request(token: &str, peer: &str, nonce: &str, timestamp: &str, body: &[u8]) -> String {
+    mac(
+        token,
+        &[
+            b"request",
+            peer.as_bytes(),
+            nonce.as_bytes(),
+            timestamp.as_bytes(),
+            body,
+        ],
+    )
+}
+
+/// The receiver's proof that it holds the token and wrote `body`.
+pub fn proof(token: &str, nonce: &str, body: &[u8]) -> String {
+    mac(token, &[b"response", nonce.as_bytes(), body])
+}
+
+fn matches(expected: &str, header: &str) -> bool {
+    !expected.is_empty() && constant_time_eq(expected, header.trim())
+}
+
+/// Nonces accepted in the last two skew windows, so none is used twice.
+fn seen_nonces() -> &'static IdempotencyCache {
+    static SEEN: OnceLock<IdempotencyCache> = OnceLock::new();
+    SEEN.get_or_init(|| {
+        IdempotencyCache::new(
+            Duration::from_secs(MAX_CLOCK_SKEW_SECS.unsigned_abs() * 2),
+            10_000,
+        )
+    })
+}
+
+/// The peer that signed `request`, if it is known, the signature matches its
+/// token, the timestamp is fresh and the nonce hasn't been used before.
+pub fn authenticate<'a>(
+    config: &'a FederationConfig,
+    request: &SignedRequest<'_>,
+    now: i64,
+) -> Option<&'a FederationPeerConfig> {
+    let peer = config
+        .peers
+        .get(request.peer)
+        .filter(|peer| !peer.token.is_empty())?;
+    let fresh = request
+        .timestamp
+        .parse::<i64>()
+        .is_ok_and(|t| (now - t).abs() <= MAX_CLOCK_SKEW_SECS);
+    let expected = sign_request(
+        &peer.token,
+        request.peer,
+        request.nonce,
+        request.timestamp,
+        request.body,
+    );
+    (fresh
+        && matches(&expected, request.signature)
+        && seen_nonces().first_seen(&format!("{}:{}", request.peer, request.nonce)))
+    .then_some(peer)
+}
+
+/// Send `message` to the peer `name` and return its answer.
+pub async fn send(
+    config: &FederationConfig,
+    name: &str,
+    kind: RequestKind,
+    message: &str,
+) -> Result<String> {
+    let peer = config.peers.get(name).with_context(|| {
+        format!("Unknown peer '{name}'. Add it under [federation.peers.{name}] in config.toml")
+    })?;
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let url = format!("{}/federation", peer.url.trim_end_matches('/'));
+    let body = serde_json::to_vec(&FederationRequest {
+        kind,
+        message: message.to_string(),
+    })?;
+    let signature = sign_request(&peer.token, &config.name, &nonce, &timestamp, &body);
+
+    let resp = CLIENT
+        .post(&url)
+        .header(PEER_HEADER, &config.name)
+        .header(NONCE_HEADER, &nonce)
+        .header(TIMESTAMP_HEADER, &timestamp)
+        .header(SIGNATURE_HEADER, signature)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .timeout(Duration::from_secs(config.task_timeout_secs) + RESPONSE_GRACE)
+        .send()
+        .await
+        .with_context(|| format!("Could not reach peer '{name}' at {url}"))?;
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        anyhow::bail!(
+            "Peer '{name}' rejected our signature — it must list us as '{}' with the same \
+             token, and both clocks must be within {MAX_CLOCK_SKEW_SECS}s",
+            config.name
+        );
+    }
+    let proof_header = resp
+        .headers()
+        .get(PROOF_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let answer = resp
+        .bytes()
+        .await
+        .with_context(|| format!("Peer '{name}' sent an unreadable answer"))?;
+    if !matches(&proof(&peer.token, &nonce, &answer), &proof_header) {
+        anyhow::bail!("Peer '{name}' did not prove it holds the shared token; ignoring its answer");
+    }
+
+    let reply: FederationReply = serde_json::from_slice(&answer)
+        .with_context(|| format!("Peer '{name}' sent an unreadable answer"))?;
+    match (status.is_success(), reply.response, reply.error) {
+        (true, Some(response), _) => Ok(response),
+        (_, _, Some(error)) => anyhow::bail!("Peer '{name}': {error}"),
+        _ => anyhow::bail!("Peer '{name}' answered {status} with no response"),
+    }
+}
+
+/// Run a task delegated by `peer` as a child `zeroclaw agent` process, the
+/// way cron runs agent jobs, and return what it printed.
+pub async fn run_task(config: &Config, peer: &str, message: &str) -> Result<String> {
+    let exe = std::env::current_exe().context("Cannot locate the zeroclaw binary")?;
+    let session: String = peer
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let mut command = Command::new(exe);
+    command
+        // `=` keeps a message that starts with `-` from reading as a flag
+        .args(["agent", &format!("--message={message}"), "--session"])
+        .arg(format!("federation-{session}"))
+        .current_dir(&config.workspace_dir)
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(config.federation.task_timeout_secs),
+        command.output(),
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Task timed out after {}s",
+            config.federation.task_timeout_secs
+        )
+    })??;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Task failed ({}): {}", output.status, stderr.trim())
+    }
+}
+
+pub async fn handle_command(command: FederationCommands, config: &Config) -> Result<()> {
+    match command {
+        FederationCommands::Peers => {
+            let federation = &config.federation;
+            if federation.peers.is_empty() {
+                println!("No federation peers configured.");
+                println!("\nAdd one to ~/.zeroclaw/config.toml:");
+                println!("  [federation.peers.home]");
+                println!("  url = \"http://homeserver.lan:3000\"");
+                println!("  token = \"<shared secret>\"");
+                return Ok(());
+            }
+            println!("This instance: {}", federation.name);
+            for (name, peer) in &federation.peers {
+                let tasks = if peer.allow_tasks {
+                    "may delegate tasks"
+                } else {
+                    "messages only"
+                };
+                println!("  {name:<12} {}  ({tasks})", peer.url);
+            }
+            Ok(())
+        }
+        FederationCommands::Send {
+            peer,
+            message,
+            task,
+        } => {
+            let kind = if task {
+                RequestKind::Task
+            } else {
+                RequestKind::Message
+            };
+            let reply = send(&config.federation, &peer, kind, &message).await?;
+            println!("{reply}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn federation() -> FederationConfig {
+        let mut config = FederationConfig::default();
+        config.peers.insert(
+            "laptop".into(),
+            FederationPeerConfig {
+                url: "http://laptop.lan:3000".into(),
+                token: "s3cret".into(),
+                allow_tasks: false,
+            },
+        );
+        config
+    }
+
+    /// A request from `peer`, signed with `token` at `timestamp`.
+    fn check(
+        config: &FederationConfig,
+        peer: &str,
+        token: &str,
+        nonce: &str,
+        timestamp: i64,
+    ) -> bool {
+        let timestamp = timestamp.to_string();
+        let body = br#"{"message":"hi"}"#;
+        let signature = sign_request(token, peer, nonce, &timestamp, body);
+        let request = SignedRequest {
+            peer,
+            nonce,
+            timestamp: &timestamp,
+            body,
+            signature: &signature,
+        };
+        authenticate(config, &request, 1_700_000_000).is_some()
+    }
+
+    #[test]
+    fn authenticate_needs_known_peer_its_token_and_a_fresh_nonce() {
+        let config = federation();
+        let now = 1_700_000_000;
+        assert!(check(&config, "laptop", "s3cret", "n-1", now));
+        assert!(!check(&config, "laptop", "s3cret", "n-1", now), "replayed");
+        assert!(!check(&config, "laptop", "wrong", "n-2", now));
+        assert!(!check(&config, "phone", "s3cret", "n-3", now));
+        assert!(
+            !check(&config, "laptop", "s3cret", "n-4", now - 301),
+            "stale"
+        );
+        assert!(check(&config, "laptop", "s3cret", "n-5", now + 60));
+    }
+
+    #[test]
+    fn signatures_cover_the_body() {
+        let config = federation();
+        let signature = sign_request("s3cret", "laptop", "n-6", "1700000000", b"{}");
+        let tampered = SignedRequest {
+            peer: "laptop",
+            nonce: "n-6",
+            timestamp: "1700000000",
+            body: br#"{"kind":"task"}"#,
+            signature: &signature,
+        };
+        assert!(authenticate(&config, &tampered, 1_700_000_000).is_none());
+    }
+
+    #[test]
+    fn empty_token_never_authenticates() {
+        let mut config = federation();
+        config.peers.get_mut("laptop").unwrap().token = String::new();
+        assert!(!check(&config, "laptop", "", "n-7", 1_700_000_000));
+    }
+
+    #[test]
+    fn proof_is_bound_to_token_nonce_and_answer() {
+        let p = proof("s3cret", "nonce-1", b"ok");
+        assert_eq!(p.len(), 64);
+        assert!(matches(&proof("s3cret", "nonce-1", b"ok"), &p));
+        assert!(!matches(&proof("other", "nonce-1", b"ok"), &p));
+        assert!(!matches(&proof("s3cret", "nonce-2", b"ok"), &p));
+        assert!(!matches(&proof("s3cret", "nonce-1", b"forged"), &p));
+        // A request signature is never a valid answer proof.
+        assert_ne!(sign_request("s3cret", "", "nonce-1", "", b"ok"), p);
+    }
+
+    #[test]
+    fn request_kind_defaults_to_message() {
+        let req: FederationRequest = serde_json::from_str(r#"{"message": "hi"}"#).unwrap();
+        assert_eq!(req.kind, RequestKind::Message);
+        let req: FederationRequest =
+            serde_json::from_str(r#"{"kind": "task", "message": "run backup"}"#).unwrap();
+        assert_eq!(req.kind, RequestKind::Task);
+    }
+}
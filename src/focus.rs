@@ -0,0 +1,347 @@
+//! Focus sessions: a do-not-disturb window (`zeroclaw focus 2h`, or `/focus
+//! 2h` in chat) during which proactive messages — meeting briefs, inbox
+//! summaries, canary alerts — are held instead of sent, and heartbeat tasks
+//! wait. When the window ends the held messages go out as one summary per
+//! recipient.
+//!
+//! The session lives in `workspace/focus/state.json` so the CLI, channels and
+//! the daemon all see the same one; the daemon ends it on time.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// How often the daemon checks whether the window is over.
+const POLL_SECS: u64 = 30;
+/// Longest a held message gets in the summary.
+const SUMMARY_CHARS: usize = 500;
+
+/// A proactive message that arrived during focus.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HeldMessage {
+    /// What sent it: `briefs`, `inbox` or `eval`
+    pub source: String,
+    pub channel: String,
+    pub recipient: String,
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FocusSession {
+    pub started: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    #[serde(default)]
+    pub held: Vec<HeldMessage>,
+}
+
+impl FocusSession {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        now < self.until
+    }
+
+    /// "1h 20m left", rounded up to the minute.
+    pub fn remaining(&self, now: DateTime<Utc>) -> String {
+        let seconds = (self.until - now).num_seconds().max(0);
+        format_minutes((seconds + 59) / 60)
+    }
+}
+
+fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+/// Parse `2h`, `45m`, `1h30m` or a bare number of minutes.
+pub fn parse_duration(raw: &str) -> Result<Duration> {
+    let raw = raw.trim().to_lowercase();
+    if let Ok(minutes) = raw.parse::<i64>() {
+        return positive(Duration::minutes(minutes), &raw);
+    }
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in raw.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let n: i64 = number
+            .parse()
+            .with_context(|| format!("Can't read '{raw}' as a duration (try 2h or 45m)"))?;
+        number.clear();
+        total += match c {
+            'h' => Duration::hours(n),
+            'm' => Duration::minutes(n),
+            _ => anyhow::bail!("Can't read '{raw}' as a duration (try 2h or 45m)"),
+        };
+    }
+    if !number.is_empty() {
+        anyhow::bail!("Can't read '{raw}' as a duration (try 2h or 45m)");
+    }
+    positive(total, &raw)
+}
+
+fn positive(duration: Duration, raw: &str) -> Result<Duration> {
+    if duration <= Duration::zero() || duration > Duration::days(1) {
+        anyhow::bail!("Focus must last between 1 minute and 24 hours, not '{raw}'");
+    }
+    Ok(duration)
+}
+
+pub fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("focus").join("state.json")
+}
+
+/// Serializes read-modify-write of the state file within this process.
+fn lock() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// The current session, including one whose window is over but has not
+/// been ended yet.
+pub fn load(workspace_dir: &Path) -> Option<FocusSession> {
+    let raw = std::fs::read_to_string(state_path(workspace_dir)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn save(workspace_dir: &Path, session: &FocusSession) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(session)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Start focusing for `duration`. A running session is extended and keeps
+/// what it already holds.
+pub fn start(workspace_dir: &Path, duration: Duration) -> Result<FocusSession> {
+    let _guard = lock();
+    let now = Utc::now();
+    let session = match load(workspace_dir) {
+        Some(mut session) if session.is_active(now) => {
+            session.until = now + duration;
+            session
+        }
+        Some(expired) => FocusSession {
+            started: now,
+            until: now + duration,
+            held: expired.held,
+        },
+        None => FocusSession {
+            started: now,
+            until: now + duration,
+            held: Vec::new(),
+        },
+    };
+    save(workspace_dir, &session)?;
+    Ok(session)
+}
+
+/// End the session now and hand back what it held.
+pub fn end(workspace_dir: &Path) -> Result<Option<FocusSession>> {
+    let _guard = lock();
+    let session = load(workspace_dir);
+    if session.is_some() {
+        std::fs::remove_file(state_path(workspace_dir)).context("Failed to end focus session")?;
+    }
+    Ok(session)
+}
+
+/// Whether `source`'s messages should wait: a session is on and the source
+/// is not listed in `[focus].urgent`.
+pub fn holds(config: &Config, source: &str) -> bool {
+    !config.focus.urgent.iter().any(|s| s == source)
+        && load(&config.workspace_dir).is_some_and(|s| s.is_active(Utc::now()))
+}
+
+/// Keep a proactive message for the end-of-focus summary. Returns `false`
+/// when it should be sent now.
+pub fn hold(config: &Config, source: &str, channel: &str, recipient: &str, message: &str) -> bool {
+    if !holds(config, source) {
+        return false;
+    }
+    let _guard = lock();
+    let Some(mut session) = load(&config.workspace_dir) else {
+        return false;
+    };
+    session.held.push(HeldMessage {
+        source: source.to_string(),
+        channel: channel.to_string(),
+        recipient: recipient.to_string(),
+        message: message.to_string(),
+        at: Utc::now(),
+    });
+    match save(&config.workspace_dir, &session) {
+        Ok(()) => {
+            tracing::info!("Focus on: held a {source} message for {channel}:{recipient}");
+            true
+        }
+        Err(e) => {
+            tracing::warn!("Could not hold {source} message, sending it: {e}");
+            false
+        }
+    }
+}
+
+/// One summary per channel and recipient, in the order messages arrived.
+pub fn summaries(session: &FocusSession) -> Vec<(String, String, String)> {
+    let mut out: Vec<(String, String, String)> = Vec::new();
+    for held in &session.held {
+        let existing = out
+            .iter()
+            .position(|(ch, to, _)| *ch == held.channel && *to == held.recipient);
+        let index = existing.unwrap_or_else(|| {
+            out.push((
+                held.channel.clone(),
+                held.recipient.clone(),
+                "🔕 Focus ended. While you were focused:".to_string(),
+            ));
+            out.len() - 1
+        });
+        let _ = write!(
+            out[index].2,
+            "\n\n[{} · {}] {}",
+            held.at.format("%H:%M UTC"),
+            held.source,
+            crate::util::truncate_with_ellipsis(held.message.trim(), SUMMARY_CHARS)
+        );
+    }
+    out
+}
+
+/// Send the summaries of an ended session.
+pub async fn deliver_summary(config: &Config, session: &FocusSession) {
+    for (channel, recipient, summary) in summaries(session) {
+        crate::channels::send_notification(config, "focus", &channel, &recipient, &summary).await;
+    }
+}
+
+/// End the session and send its summary; the message for whoever ended it.
+pub async fn finish(config: &Config) -> Result<String> {
+    let Some(session) = end(&config.workspace_dir)? else {
+        return Ok("Not focusing.".into());
+    };
+    let held = session.held.len();
+    deliver_summary(config, &session).await;
+    Ok(if held == 0 {
+        "🔔 Focus ended; nothing was held.".into()
+    } else {
+        format!("🔔 Focus ended; sent a summary of {held} held message(s).")
+    })
+}
+
+/// Start, show or end a session from a `/focus` or `zeroclaw focus`
+/// argument: a duration, `off`, or nothing for the status.
+pub async fn apply(config: &Config, arg: &str) -> Result<String> {
+    let arg = arg.trim();
+    let now = Utc::now();
+    match arg {
+        "" | "status" => Ok(match load(&config.workspace_dir) {
+            Some(session) if session.is_active(now) => format!(
+                "🔕 Focusing until {} ({} left); {} message(s) held.",
+                session.until.format("%H:%M UTC"),
+                session.remaining(now),
+                session.held.len()
+            ),
+            _ => "Not focusing.".into(),
+        }),
+        "off" | "end" | "stop" => finish(config).await,
+        duration => {
+            let session = start(&config.workspace_dir, parse_duration(duration)?)?;
+            Ok(format!(
+                "🔕 Focusing until {}. Briefs, summaries and heartbeat tasks wait until then; \
+                 `/focus off` ends it early.",
+                session.until.format("%H:%M UTC")
+            ))
+        }
+    }
+}
+
+/// The argument of a `/focus` chat command, if `text` is one.
+pub fn parse_command(text: &str) -> Option<&str> {
+    let rest = text.trim().strip_prefix("/focus")?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace)).then_some(rest)
+}
+
+/// Daemon component: end sessions whose window is over and send their summary.
+pub async fn run(config: Config) -> Result<()> {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(POLL_SECS));
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok("focus");
+        let expired = load(&config.workspace_dir).is_some_and(|s| !s.is_active(Utc::now()));
+        if expired {
+            if let Some(session) = end(&config.workspace_dir)? {
+                tracing::info!(
+                    "Focus window over; summarizing {} held message(s)",
+                    session.held.len()
+                );
+                deliver_summary(&config, &session).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn durations_parse() {
+        assert_eq!(parse_duration("2h").unwrap(), Duration::hours(2));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("45").unwrap(), Duration::minutes(45));
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("2d").is_err());
+    }
+
+    #[test]
+    fn focus_holds_non_urgent_messages_until_it_ends() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = config(&tmp);
+        config.focus.urgent = vec!["eval".into()];
+        assert!(!hold(&config, "inbox", "slack", "U1", "3 new emails"));
+
+        start(&config.workspace_dir, Duration::hours(2)).unwrap();
+        assert!(hold(&config, "inbox", "slack", "U1", "3 new emails"));
+        assert!(hold(&config, "briefs", "slack", "U1", "Standup in 10m"));
+        assert!(hold(&config, "briefs", "telegram", "42", "1:1 in 10m"));
+        assert!(!hold(&config, "eval", "slack", "U1", "Canary failed"));
+
+        let session = end(&config.workspace_dir).unwrap().unwrap();
+        let summaries = summaries(&session);
+        assert_eq!(summaries.len(), 2);
+        let (channel, recipient, slack) = &summaries[0];
+        assert_eq!((channel.as_str(), recipient.as_str()), ("slack", "U1"));
+        assert!(slack.contains("inbox] 3 new emails"));
+        assert!(slack.contains("briefs] Standup in 10m"));
+        assert!(!holds(&config, "inbox"));
+    }
+
+    #[test]
+    fn chat_command_needs_the_exact_word() {
+        assert_eq!(parse_command("/focus 2h"), Some(" 2h"));
+        assert_eq!(parse_command("/focus"), Some(""));
+        assert_eq!(parse_command("/focused"), None);
+        assert_eq!(parse_command("focus 2h"), None);
+    }
+}
@@ -0,0 +1,516 @@
+//! Admin dashboard served by the gateway at `/admin`.
+//!
+//! The page is one embedded HTML file with nothing secret in it: it asks for
+//! a paired bearer token and calls the JSON API under `/admin/api/` with it.
+//! From there a home-server deployment can be looked after without SSH —
+//! channel health, saved sessions, the tool and guardrail audit logs, channel
+//! allowlists, autonomy budgets, and a test message to any channel. Edits are
+//! written to `config.toml` and take effect when the daemon restarts.
+
+use super::AppState;
+use crate::config::{ChannelsConfig, Config};
+use crate::conversations::ConversationStore;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{rejection::JsonRejection, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Json, Response},
+    routing::{get, post, put},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const PAGE: &str = include_str!("dashboard.html");
+/// Audit logs the dashboard can show, by name, relative to the workspace.
+const AUDIT_LOGS: [(&str, &str); 2] = [
+    ("tools", "audit/tools.jsonl"),
+    ("guardrails", "guardrails/audit.jsonl"),
+];
+const DEFAULT_AUDIT_ENTRIES: usize = 100;
+const MAX_AUDIT_ENTRIES: usize = 1_000;
+const DEFAULT_SESSION_TURNS: usize = 50;
+/// Each channel's health check gets this long before it counts as unknown.
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The running config, kept in step with every edit saved from the dashboard.
+pub struct Dashboard {
+    config: Mutex<Config>,
+}
+
+impl Dashboard {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            config: Mutex::new(config.clone()),
+        }
+    }
+}
+
+/// `/admin` plus its API; merged into the gateway router when enabled.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin", get(handle_page))
+        .route("/admin/api/channels", get(handle_channels))
+        .route("/admin/api/sessions", get(handle_sessions))
+        .route("/admin/api/session", get(handle_session))
+        .route("/admin/api/audit", get(handle_audit))
+        .route("/admin/api/allowlist", put(handle_allowlist))
+        .route(
+            "/admin/api/budgets",
+            get(handle_budgets).put(handle_update_budgets),
+        )
+        .route("/admin/api/test-message", post(handle_test_message))
+}
+
+/// Autonomy limits editable from the dashboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Budgets {
+    pub max_actions_per_hour: u32,
+    pub max_cost_per_day_cents: u32,
+    /// Calls per minute allowed for a tool, keyed by tool name
+    pub tool_rate_limits: BTreeMap<String, u32>,
+}
+
+impl Budgets {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_actions_per_hour: config.autonomy.max_actions_per_hour,
+            max_cost_per_day_cents: config.autonomy.max_cost_per_day_cents,
+            tool_rate_limits: config.tool_middleware.rate_limits.clone(),
+        }
+    }
+
+    fn apply(&self, config: &mut Config) {
+        config.autonomy.max_actions_per_hour = self.max_actions_per_hour;
+        config.autonomy.max_cost_per_day_cents = self.max_cost_per_day_cents;
+        config
+            .tool_middleware
+            .rate_limits
+            .clone_from(&self.tool_rate_limits);
+    }
+}
+
+/// Sender allowlists of the configured channels, by channel name.
+pub fn allowlists(channels: &ChannelsConfig) -> BTreeMap<&'static str, &[String]> {
+    let mut lists: BTreeMap<&'static str, &[String]> = BTreeMap::new();
+    if let Some(ref c) = channels.telegram {
+        lists.insert("telegram", &c.allowed_users);
+    }
+    if let Some(ref c) = channels.discord {
+        lists.insert("discord", &c.allowed_users);
+    }
+    if let Some(ref c) = channels.slack {
+        lists.insert("slack", &c.allowed_users);
+    }
+    if let Some(ref c) = channels.imessage {
+        lists.insert("imessage", &c.allowed_contacts);
+    }
+    if let Some(ref c) = channels.matrix {
+        lists.insert("matrix", &c.allowed_users);
+    }
+    if let Some(ref c) = channels.whatsapp {
+        lists.insert("whatsapp", &c.allowed_numbers);
+    }
+    if let Some(ref c) = channels.email {
+        lists.insert("email", &c.allowed_senders);
+    }
+    if let Some(ref c) = channels.irc {
+        lists.insert("irc", &c.allowed_users);
+    }
+    if let Some(ref c) = channels.signal {
+        lists.insert("signal", &c.allowed_numbers);
+    }
+    lists
+}
+
+fn allowlist_mut<'a>(channels: &'a mut ChannelsConfig, name: &str) -> Option<&'a mut Vec<String>> {
+    match name {
+        "telegram" => channels.telegram.as_mut().map(|c| &mut c.allowed_users),
+        "discord" => channels.discord.as_mut().map(|c| &mut c.allowed_users),
+        "slack" => channels.slack.as_mut().map(|c| &mut c.allowed_users),
+        "imessage" => channels.imessage.as_mut().map(|c| &mut c.allowed_contacts),
+        "matrix" => channels.matrix.as_mut().map(|c| &mut c.allowed_users),
+        "whatsapp" => channels.whatsapp.as_mut().map(|c| &mut c.allowed_numbers),
+        "email" => channels.email.as_mut().map(|c| &mut c.allowed_senders),
+        "irc" => channels.irc.as_mut().map(|c| &mut c.allowed_users),
+        "signal" => channels.signal.as_mut().map(|c| &mut c.allowed_numbers),
+        _ => None,
+    }
+}
+
+/// Trim entries and drop blanks and repeats, keeping the given order.
+fn clean_entries(entries: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let entry = entry.trim();
+        if !entry.is_empty() && !cleaned.iter().any(|e| e == entry) {
+            cleaned.push(entry.to_string());
+        }
+    }
+    cleaned
+}
+
+/// The last `limit` entries of a JSONL log, oldest first; unparsable lines
+/// are returned as strings.
+fn tail_jsonl(path: &Path, limit: usize) -> Vec<Value> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(limit)..]
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|_| json!(line)))
+        .collect()
+}
+
+/// Apply `edit` to the config file as it is on disk, save it, then to the
+/// in-memory copy. Saving `current` itself would write its environment
+/// overrides (API keys, ports) into the file.
+fn edit_config_file(current: &mut Config, edit: impl Fn(&mut Config) -> Result<()>) -> Result<()> {
+    let contents =
+        std::fs::read_to_string(&current.config_path).context("Failed to read config file")?;
+    let mut on_disk: Config = toml::from_str(&contents).context("Failed to parse config file")?;
+    on_disk.config_path.clone_from(&current.config_path);
+    edit(&mut on_disk)?;
+    on_disk.save()?;
+    edit(current)
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() }))).into_response()
+}
+
+/// The dashboard, if enabled and the request carries a paired bearer token.
+#[allow(clippy::result_large_err)]
+fn authorize<'a>(state: &'a AppState, headers: &HeaderMap) -> Result<&'a Dashboard, Response> {
+    let Some(ref dashboard) = state.dashboard else {
+        return Err(error(StatusCode::NOT_FOUND, "Dashboard not enabled"));
+    };
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if !state.pairing.require_pairing() || !state.pairing.is_authenticated(token) {
+        tracing::warn!("Dashboard: rejected — invalid bearer token");
+        return Err(error(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized — send Authorization: Bearer <token> from POST /pair",
+        ));
+    }
+    Ok(dashboard)
+}
+
+/// GET /admin — the dashboard page (public; all data needs the token)
+async fn handle_page() -> impl IntoResponse {
+    Html(PAGE)
+}
+
+/// GET /admin/api/channels — configured channels, live health and allowlists
+async fn handle_channels(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let dashboard = match authorize(&state, &headers) {
+        Ok(d) => d,
+        Err(resp) => return resp,
+    };
+    let config = dashboard.config.lock().await.clone();
+    let lists = allowlists(&config.channels_config);
+    let mut channels = Vec::new();
+    for (label, channel) in crate::channels::configured_channels(&config) {
+        let healthy = tokio::time::timeout(HEALTH_TIMEOUT, channel.health_check())
+            .await
+            .ok();
+        channels.push(json!({
+            "name": channel.name(),
+            "label": label,
+            "healthy": healthy,
+            "allowlist": lists.get(channel.name()),
+        }));
+    }
+    Json(json!({
+        "channels": channels,
+        "runtime": crate::health::snapshot_json(),
+    }))
+    .into_response()
+}
+
+/// GET /admin/api/sessions — saved sessions, most recent first
+async fn handle_sessions(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Err(resp) = authorize(&state, &headers) {
+        return resp;
+    }
+    let sessions = ConversationStore::open(&state.workspace_dir).and_then(|s| s.sessions(""));
+    match sessions {
+        Ok(sessions) => {
+            let sessions: Vec<Value> = sessions
+                .into_iter()
+                .map(|s| {
+                    json!({
+                        "session": s.session,
+                        "turns": s.turns,
+                        "last_message": crate::util::truncate_with_ellipsis(&s.last_message, 120),
+                        "last_at": s.last_at.to_rfc3339(),
+                    })
+                })
+                .collect();
+            Json(json!({ "sessions": sessions })).into_response()
+        }
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionQuery {
+    pub name: String,
+    pub limit: Option<usize>,
+}
+
+/// GET /admin/api/session?name= — the latest turns of one session
+async fn handle_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<SessionQuery>,
+) -> Response {
+    if let Err(resp) = authorize(&state, &headers) {
+        return resp;
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_SESSION_TURNS);
+    let history =
+        ConversationStore::open(&state.workspace_dir).and_then(|s| s.history(&query.name, limit));
+    match history {
+        Ok(turns) => {
+            let turns: Vec<Value> = turns
+                .into_iter()
+                .map(|t| {
+                    json!({
+                        "id": t.id,
+                        "status": t.status.as_str(),
+                        "user_message": t.user_message,
+                        "response": t.response,
+                        "error": t.error,
+                        "started_at": t.started_at.to_rfc3339(),
+                    })
+                })
+                .collect();
+            Json(json!({ "session": query.name, "turns": turns })).into_response()
+        }
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub log: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// GET /admin/api/audit?log=tools|guardrails — the latest audit entries
+async fn handle_audit(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AuditQuery>,
+) -> Response {
+    if let Err(resp) = authorize(&state, &headers) {
+        return resp;
+    }
+    let name = query.log.as_deref().unwrap_or("tools");
+    let Some((_, relative)) = AUDIT_LOGS.iter().find(|(log, _)| *log == name) else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            format!("Unknown audit log: {name}"),
+        );
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUDIT_ENTRIES)
+        .min(MAX_AUDIT_ENTRIES);
+    let entries = tail_jsonl(&state.workspace_dir.join(relative), limit);
+    Json(json!({ "log": name, "entries": entries })).into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AllowlistUpdate {
+    pub channel: String,
+    pub entries: Vec<String>,
+}
+
+/// PUT /admin/api/allowlist — replace one channel's sender allowlist
+async fn handle_allowlist(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<AllowlistUpdate>, JsonRejection>,
+) -> Response {
+    let dashboard = match authorize(&state, &headers) {
+        Ok(d) => d,
+        Err(resp) => return resp,
+    };
+    let Json(update) = match body {
+        Ok(b) => b,
+        Err(e) => return error(StatusCode::BAD_REQUEST, format!("Invalid JSON: {e}")),
+    };
+    let entries = clean_entries(update.entries);
+    let mut config = dashboard.config.lock().await;
+    let saved = edit_config_file(&mut config, |c| {
+        let list = allowlist_mut(&mut c.channels_config, &update.channel)
+            .with_context(|| format!("Channel '{}' has no allowlist configured", update.channel))?;
+        list.clone_from(&entries);
+        Ok(())
+    });
+    match saved {
+        Ok(()) => {
+            tracing::info!("Dashboard: updated {} allowlist", update.channel);
+            Json(json!({ "channel": update.channel, "entries": entries, "restart_required": true }))
+                .into_response()
+        }
+        Err(e) => error(StatusCode::BAD_REQUEST, format!("{e:#}")),
+    }
+}
+
+/// GET /admin/api/budgets — autonomy and tool rate limits
+async fn handle_budgets(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let dashboard = match authorize(&state, &headers) {
+        Ok(d) => d,
+        Err(resp) => return resp,
+    };
+    let config = dashboard.config.lock().await;
+    Json(Budgets::from_config(&config)).into_response()
+}
+
+/// PUT /admin/api/budgets — replace the autonomy and tool rate limits
+async fn handle_update_budgets(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<Budgets>, JsonRejection>,
+) -> Response {
+    let dashboard = match authorize(&state, &headers) {
+        Ok(d) => d,
+        Err(resp) => return resp,
+    };
+    let Json(budgets) = match body {
+        Ok(b) => b,
+        Err(e) => return error(StatusCode::BAD_REQUEST, format!("Invalid JSON: {e}")),
+    };
+    let mut config = dashboard.config.lock().await;
+    match edit_config_file(&mut config, |c| {
+        budgets.apply(c);
+        Ok(())
+    }) {
+        Ok(()) => {
+            tracing::info!("Dashboard: updated budgets");
+            Json(Budgets::from_config(&config)).into_response()
+        }
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TestMessage {
+    pub channel: String,
+    pub recipient: String,
+    #[serde(default = "default_test_message")]
+    pub message: String,
+}
+
+fn default_test_message() -> String {
+    "ZeroClaw test message from the admin dashboard ✅".into()
+}
+
+/// POST /admin/api/test-message — send a message through a channel
+async fn handle_test_message(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<TestMessage>, JsonRejection>,
+) -> Response {
+    let dashboard = match authorize(&state, &headers) {
+        Ok(d) => d,
+        Err(resp) => return resp,
+    };
+    let Json(test) = match body {
+        Ok(b) => b,
+        Err(e) => return error(StatusCode::BAD_REQUEST, format!("Invalid JSON: {e}")),
+    };
+    let config = dashboard.config.lock().await.clone();
+    let Some(channel) = crate::channels::find_channel(&config, &test.channel) else {
+        return error(
+            StatusCode::NOT_FOUND,
+            format!("Channel '{}' is not configured", test.channel),
+        );
+    };
+    match channel.send(&test.message, test.recipient.trim()).await {
+        Ok(()) => Json(json!({ "sent": true, "channel": channel.name() })).into_response(),
+        Err(e) => error(StatusCode::BAD_GATEWAY, format!("Send failed: {e:#}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramConfig;
+
+    #[test]
+    fn allowlists_cover_configured_channels_only() {
+        let mut channels = ChannelsConfig::default();
+        assert!(allowlists(&channels).is_empty());
+        channels.telegram = Some(TelegramConfig {
+            bot_token: "t".into(),
+            allowed_users: vec!["alice".into()],
+            voice_replies: false,
+        });
+        assert_eq!(allowlists(&channels)["telegram"], ["alice".to_string()]);
+        assert!(allowlist_mut(&mut channels, "telegram").is_some());
+        assert!(allowlist_mut(&mut channels, "slack").is_none());
+    }
+
+    #[test]
+    fn clean_entries_trims_and_dedupes() {
+        let cleaned = clean_entries(vec![
+            " alice ".into(),
+            String::new(),
+            "alice".into(),
+            "bob".into(),
+        ]);
+        assert_eq!(cleaned, vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn tail_jsonl_keeps_last_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        std::fs::write(&path, "{\"n\":1}\n{\"n\":2}\nnot json\n").unwrap();
+        let entries = tail_jsonl(&path, 2);
+        assert_eq!(entries, vec![json!({"n": 2}), json!("not json")]);
+        assert!(tail_jsonl(&dir.path().join("missing.jsonl"), 5).is_empty());
+    }
+
+    #[test]
+    fn budget_edits_are_saved_to_the_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            config_path: dir.path().join("config.toml"),
+            workspace_dir: dir.path().join("workspace"),
+            ..Config::default()
+        };
+        config.save().unwrap();
+        // An env override in memory must not leak into the file
+        config.default_temperature = 1.5;
+
+        let mut budgets = Budgets::from_config(&config);
+        budgets.max_actions_per_hour = 42;
+        budgets.tool_rate_limits.insert("shell".into(), 5);
+        edit_config_file(&mut config, |c| {
+            budgets.apply(c);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(config.autonomy.max_actions_per_hour, 42);
+
+        let on_disk: Config =
+            toml::from_str(&std::fs::read_to_string(&config.config_path).unwrap()).unwrap();
+        assert_eq!(on_disk.autonomy.max_actions_per_hour, 42);
+        assert_eq!(on_disk.tool_middleware.rate_limits["shell"], 5);
+        assert!((on_disk.default_temperature - 1.5).abs() > f64::EPSILON);
+    }
+}
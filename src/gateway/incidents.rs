@@ -0,0 +1,414 @@
+//! Incident webhooks — turn PagerDuty/Opsgenie pages into on-call briefings.
+//!
+//! A page arrives at `/incidents/pagerduty` or `/incidents/opsgenie`, gets
+//! acknowledged to the sender right away, and is enriched in the background:
+//! the matching runbook from the workspace and the latest CI runs are handed
+//! to the LLM, and its summary is posted to the configured on-call channel.
+
+use crate::config::{Config, OnCallConfig};
+use crate::providers::Provider;
+use crate::util::truncate_with_ellipsis;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Runbook text handed to the LLM is capped so one long doc can't crowd out the page.
+const MAX_RUNBOOK_CHARS: usize = 4_000;
+/// Latest runs per watched repo listed as "recent deploys".
+const RECENT_RUNS_PER_REPO: usize = 3;
+
+const BRIEFING_SYSTEM_PROMPT: &str = "You are the on-call assistant. A page just fired. \
+Write a short briefing for the engineer on call: what is broken, the first steps from the \
+runbook (if one is provided), and whether a recent deploy or CI failure looks related. \
+Be concise and concrete; do not invent runbook steps.";
+
+/// A page reduced to what the briefing needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub source: &'static str,
+    pub id: String,
+    pub title: String,
+    pub service: String,
+    pub urgency: String,
+    pub url: String,
+    pub description: String,
+}
+
+/// Everything the background briefing task needs, shared via `AppState`.
+pub struct IncidentContext {
+    pub config: Config,
+    pub oncall: OnCallConfig,
+    pub pagerduty_secret: Option<Arc<str>>,
+    pub opsgenie_secret: Option<Arc<str>>,
+}
+
+impl IncidentContext {
+    /// `None` unless an on-call channel and at least one paging service are configured.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let integrations = &config.integrations;
+        let oncall = integrations.oncall.clone()?;
+        if integrations.pagerduty.is_none() && integrations.opsgenie.is_none() {
+            return None;
+        }
+        Some(Self {
+            pagerduty_secret: integrations
+                .pagerduty
+                .as_ref()
+                .and_then(|p| p.webhook_secret.as_deref())
+                .map(Arc::from),
+            opsgenie_secret: integrations
+                .opsgenie
+                .as_ref()
+                .and_then(|o| o.webhook_secret.as_deref())
+                .map(Arc::from),
+            config: config.clone(),
+            oncall,
+        })
+    }
+}
+
+/// Extract a newly triggered incident from a `PagerDuty` v3 webhook; other events are ignored.
+pub fn parse_pagerduty_webhook(payload: &serde_json::Value) -> Option<Page> {
+    let event = payload.get("event")?;
+    if event.get("event_type")?.as_str()? != "incident.triggered" {
+        return None;
+    }
+    let data = event.get("data")?;
+    let text = |v: Option<&serde_json::Value>| v.and_then(|v| v.as_str()).unwrap_or("").to_string();
+    Some(Page {
+        source: "PagerDuty",
+        id: text(data.get("id")),
+        title: text(data.get("title")),
+        service: text(data.get("service").and_then(|s| s.get("summary"))),
+        urgency: text(data.get("urgency")),
+        url: text(data.get("html_url")),
+        description: String::new(),
+    })
+}
+
+/// Extract a newly created alert from an Opsgenie outgoing webhook; other actions are ignored.
+pub fn parse_opsgenie_webhook(payload: &serde_json::Value) -> Option<Page> {
+    if payload.get("action")?.as_str()? != "Create" {
+        return None;
+    }
+    let alert = payload.get("alert")?;
+    let text = |key: &str| {
+        alert
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let tags: Vec<&str> = alert
+        .get("tags")
+        .and_then(|t| t.as_array())
+        .map(|tags| tags.iter().filter_map(|t| t.as_str()).collect())
+        .unwrap_or_default();
+    let id = text("alertId");
+    Some(Page {
+        source: "Opsgenie",
+        url: format!("https://app.opsgenie.com/alert/detail/{id}/details"),
+        id,
+        title: text("message"),
+        service: tags.join(", "),
+        urgency: text("priority"),
+        description: text("description"),
+    })
+}
+
+/// Verify `X-PagerDuty-Signature` (comma-separated `v1=<hex hmac-sha256>` entries;
+/// several are sent while a secret is being rotated).
+pub fn verify_pagerduty_signature(secret: &str, body: &[u8], signature_header: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    signature_header
+        .split(',')
+        .filter_map(|sig| sig.trim().strip_prefix("v1="))
+        .filter_map(|hex_sig| hex::decode(hex_sig).ok())
+        .any(|expected| {
+            let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+                return false;
+            };
+            mac.update(body);
+            mac.verify_slice(&expected).is_ok()
+        })
+}
+
+/// Pick the runbook whose file name best matches the paged service or title.
+///
+/// `checkout-api.md` matches a page from service "checkout-api"; the longest
+/// matching name wins so `db-replica.md` beats `db.md` for "db-replica lag".
+pub fn find_runbook(dir: &Path, page: &Page) -> Option<(PathBuf, String)> {
+    let haystack = format!("{} {}", page.service, page.title).to_lowercase();
+    let mut best: Option<(usize, PathBuf)> = None;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let is_doc = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("md") || e.eq_ignore_ascii_case("txt"));
+        if !is_doc {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let stem = stem.to_lowercase();
+        if stem.is_empty() || !haystack.contains(&stem) {
+            continue;
+        }
+        if best.as_ref().is_none_or(|(len, _)| stem.len() > *len) {
+            best = Some((stem.len(), path));
+        }
+    }
+    let (_, path) = best?;
+    let content = std::fs::read_to_string(&path).ok()?;
+    Some((path, truncate_with_ellipsis(&content, MAX_RUNBOOK_CHARS)))
+}
+
+/// Latest CI runs on the watched branch of every `[integrations.ci]` repo.
+async fn recent_runs(config: &Config) -> Option<String> {
+    let ci = config.integrations.ci.as_ref()?;
+    if ci.repos.is_empty() {
+        return None;
+    }
+    let forges = crate::tools::code_forges(&config.integrations);
+    let mut out = String::new();
+    for spec in &ci.repos {
+        let (prefix, repo) = spec.split_once(':').unwrap_or(("", spec.as_str()));
+        let forge = forges
+            .iter()
+            .find(|f| prefix.is_empty() || f.name().eq_ignore_ascii_case(prefix));
+        let Some(forge) = forge else {
+            continue;
+        };
+        match forge
+            .list_pipelines(repo, Some(&ci.branch), RECENT_RUNS_PER_REPO)
+            .await
+        {
+            Ok(runs) => {
+                for run in runs {
+                    let short_sha: String = run.commit.chars().take(8).collect();
+                    let _ = writeln!(out, "- {spec} {short_sha}: {} ({})", run.status, run.url);
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(out, "- {spec}: unavailable ({e})");
+            }
+        }
+    }
+    (!out.is_empty()).then_some(out)
+}
+
+/// User message for the briefing: the page, then whatever context was found.
+pub fn build_briefing_prompt(
+    page: &Page,
+    runbook: Option<&(PathBuf, String)>,
+    recent_runs: Option<&str>,
+) -> String {
+    let mut prompt = format!(
+        "{} page: {}\nService: {}\nUrgency: {}\nLink: {}\n",
+        page.source, page.title, page.service, page.urgency, page.url
+    );
+    if !page.description.trim().is_empty() {
+        let _ = writeln!(prompt, "Details: {}", page.description.trim());
+    }
+    match runbook {
+        Some((path, content)) => {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("runbook");
+            let _ = write!(prompt, "\nRunbook ({name}):\n{content}\n");
+        }
+        None => prompt.push_str("\nNo matching runbook found.\n"),
+    }
+    if let Some(runs) = recent_runs {
+        let _ = write!(prompt, "\nRecent deploys / CI runs:\n{runs}");
+    }
+    prompt
+}
+
+/// Enrich a page and post the briefing to the on-call channel.
+pub async fn brief_on_call(
+    ctx: Arc<IncidentContext>,
+    provider: Arc<dyn Provider>,
+    model: String,
+    temperature: f64,
+    page: Page,
+) {
+    let runbook_dir = ctx.config.workspace_dir.join(&ctx.oncall.runbook_dir);
+    let runbook = find_runbook(&runbook_dir, &page);
+    let runs = recent_runs(&ctx.config).await;
+    let prompt = build_briefing_prompt(&page, runbook.as_ref(), runs.as_deref());
+
+    let summary = match provider
+        .chat_with_system(Some(BRIEFING_SYSTEM_PROMPT), &prompt, &model, temperature)
+        .await
+    {
+        Ok(summary) => summary,
+        Err(e) => {
+            tracing::warn!(
+                "Incident briefing LLM call failed: {}",
+                crate::providers::sanitize_api_error(&e.to_string())
+            );
+            // Still page the channel with the raw details rather than dropping it.
+            format!("(briefing unavailable)\n\n{prompt}")
+        }
+    };
+    let message = format!(
+        "🚨 {} — {}\n{}\n\n{summary}",
+        page.source, page.title, page.url
+    );
+
+    let Some(channel) = crate::channels::find_channel(&ctx.config, &ctx.oncall.channel) else {
+        tracing::warn!(
+            "Incident briefing not sent: on-call channel '{}' is not configured",
+            ctx.oncall.channel
+        );
+        // Park it so `zeroclaw dlq retry` can deliver once the channel is set up.
+        let _ = crate::dlq::push(
+            &ctx.config,
+            &ctx.oncall.channel,
+            &ctx.oncall.recipient,
+            &message,
+            "channel not configured",
+        );
+        return;
+    };
+    if let Err(e) = crate::dlq::send_or_dead_letter(
+        &ctx.config,
+        channel.as_ref(),
+        &message,
+        &ctx.oncall.recipient,
+    )
+    .await
+    {
+        tracing::warn!("Failed to post incident briefing: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn page(service: &str, title: &str) -> Page {
+        Page {
+            source: "PagerDuty",
+            id: "P1".into(),
+            title: title.into(),
+            service: service.into(),
+            urgency: "high".into(),
+            url: "https://pd/P1".into(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn pagerduty_triggered_event_is_parsed() {
+        let payload = json!({"event": {
+            "event_type": "incident.triggered",
+            "data": {
+                "id": "Q1", "title": "High error rate", "urgency": "high",
+                "html_url": "https://acme.pagerduty.com/incidents/Q1",
+                "service": {"summary": "checkout-api"}
+            }
+        }});
+        let page = parse_pagerduty_webhook(&payload).unwrap();
+        assert_eq!(page.service, "checkout-api");
+        assert_eq!(page.title, "High error rate");
+    }
+
+    #[test]
+    fn pagerduty_other_events_are_ignored() {
+        let payload = json!({"event": {"event_type": "incident.resolved", "data": {}}});
+        assert!(parse_pagerduty_webhook(&payload).is_none());
+    }
+
+    #[test]
+    fn opsgenie_create_action_is_parsed() {
+        let payload = json!({
+            "action": "Create",
+            "alert": {
+                "alertId": "a-1", "message": "Queue backlog", "priority": "P2",
+                "tags": ["payments", "queue"], "description": "depth > 10k"
+            }
+        });
+        let page = parse_opsgenie_webhook(&payload).unwrap();
+        assert_eq!(page.service, "payments, queue");
+        assert_eq!(page.description, "depth > 10k");
+        assert!(parse_opsgenie_webhook(&json!({"action": "Close", "alert": {}})).is_none());
+    }
+
+    #[test]
+    fn pagerduty_signature_accepts_any_valid_entry() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+        let body = br#"{"event":{}}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(body);
+        let good = hex::encode(mac.finalize().into_bytes());
+
+        let header = format!("v1=deadbeef, v1={good}");
+        assert!(verify_pagerduty_signature("secret", body, &header));
+        assert!(!verify_pagerduty_signature("other", body, &header));
+        assert!(!verify_pagerduty_signature("secret", body, ""));
+    }
+
+    #[test]
+    fn runbook_prefers_longest_matching_name() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("db.md"), "generic db").unwrap();
+        std::fs::write(tmp.path().join("db-replica.md"), "replica steps").unwrap();
+        std::fs::write(tmp.path().join("notes.json"), "{}").unwrap();
+
+        let (path, content) = find_runbook(tmp.path(), &page("db-replica", "lag")).unwrap();
+        assert!(path.ends_with("db-replica.md"));
+        assert_eq!(content, "replica steps");
+
+        assert!(find_runbook(tmp.path(), &page("checkout", "5xx")).is_none());
+    }
+
+    #[test]
+    fn briefing_prompt_includes_runbook_and_runs() {
+        let runbook = (
+            PathBuf::from("runbooks/api.md"),
+            "1. Restart pods".to_string(),
+        );
+        let prompt = build_briefing_prompt(
+            &page("api", "5xx"),
+            Some(&runbook),
+            Some("- github:acme/api abc12345: failed (u)\n"),
+        );
+        assert!(prompt.contains("Runbook (api.md):\n1. Restart pods"));
+        assert!(prompt.contains("Recent deploys / CI runs:"));
+
+        let bare = build_briefing_prompt(&page("api", "5xx"), None, None);
+        assert!(bare.contains("No matching runbook found."));
+    }
+
+    #[test]
+    fn context_requires_oncall_and_a_paging_service() {
+        let mut config = Config::default();
+        assert!(IncidentContext::from_config(&config).is_none());
+
+        config.integrations.oncall = Some(OnCallConfig {
+            channel: "slack".into(),
+            recipient: "C123".into(),
+            runbook_dir: "runbooks".into(),
+        });
+        assert!(IncidentContext::from_config(&config).is_none());
+
+        config.integrations.opsgenie = Some(crate::config::OpsgenieConfig {
+            api_key: "k".into(),
+            api_url: "https://api.opsgenie.com".into(),
+            webhook_secret: Some("s".into()),
+        });
+        let ctx = IncidentContext::from_config(&config).unwrap();
+        assert_eq!(ctx.opsgenie_secret.as_deref(), Some("s"));
+        assert!(ctx.pagerduty_secret.is_none());
+    }
+}
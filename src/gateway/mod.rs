@@ -7,9 +7,17 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
+pub mod dashboard;
+pub mod incidents;
+pub mod shortcuts;
+
+use crate::agent::session_key;
 use crate::channels::{Channel, WhatsAppChannel};
 use crate::config::Config;
+use crate::federation;
+use crate::idempotency::{Claim, Held, IdempotencyCache, DEFAULT_TTL};
 use crate::memory::{self, Memory, MemoryCategory};
+use crate::oauth::OAuthManager;
 use crate::providers::{self, Provider};
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
 use crate::util::truncate_with_ellipsis;
@@ -32,6 +40,8 @@ use tower_http::timeout::TimeoutLayer;
 pub const MAX_BODY_SIZE: usize = 65_536;
 /// Request timeout (30s) — prevents slow-loris attacks
 pub const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Longest accepted `Idempotency-Key` header value
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
 
 /// Shared state for all axum handlers
 #[derive(Clone)]
@@ -46,6 +56,22 @@ pub struct AppState {
     pub whatsapp: Option<Arc<WhatsAppChannel>>,
     /// `WhatsApp` app secret for webhook signature verification (`X-Hub-Signature-256`)
     pub whatsapp_app_secret: Option<Arc<str>>,
+    /// On-call briefing context for `/incidents/*` webhooks (None = routes disabled)
+    pub incidents: Option<Arc<incidents::IncidentContext>>,
+    /// `/webhook` responses by `Idempotency-Key`, so retries are not re-run
+    pub idempotency: Arc<IdempotencyCache<serde_json::Value>>,
+    /// `WhatsApp` message ids already answered (Meta redelivers on slow acks)
+    pub seen_messages: Arc<IdempotencyCache>,
+    /// Workspace for `/run` prompt templates
+    pub workspace_dir: Arc<std::path::Path>,
+    /// Finishes browser sign-ins started by `zeroclaw oauth login`
+    pub oauth: Arc<OAuthManager>,
+    /// Admin UI at `/admin` (None = disabled, or pairing is off)
+    pub dashboard: Option<Arc<dashboard::Dashboard>>,
+    /// Config for `/federation` peers and delegated tasks (None = no peers)
+    pub federation: Option<Arc<Config>>,
+    /// Config for `/run` workflows
+    pub config: Arc<Config>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
@@ -70,11 +96,17 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
         &config.reliability,
+        &config.providers.fallback,
     )?);
-    let model = config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let model = crate::model_fit::resolve(
+        &config,
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config
+            .default_model
+            .clone()
+            .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into()),
+    )
+    .await;
     let temperature = config.default_temperature;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory(
         &config.memory,
@@ -93,12 +125,18 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     // WhatsApp channel (if configured)
     let whatsapp_channel: Option<Arc<WhatsAppChannel>> =
         config.channels_config.whatsapp.as_ref().map(|wa| {
-            Arc::new(WhatsAppChannel::new(
-                wa.access_token.clone(),
-                wa.phone_number_id.clone(),
-                wa.verify_token.clone(),
-                wa.allowed_numbers.clone(),
-            ))
+            Arc::new(
+                WhatsAppChannel::new(
+                    wa.access_token.clone(),
+                    wa.phone_number_id.clone(),
+                    wa.verify_token.clone(),
+                    wa.allowed_numbers.clone(),
+                )
+                .with_voice(
+                    crate::voice::VoiceClient::for_config(&config.voice).map(Arc::new),
+                    wa.voice_replies,
+                ),
+            )
         });
 
     // WhatsApp app secret for webhook signature verification
@@ -120,6 +158,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         })
         .map(Arc::from);
 
+    // Incident webhooks (PagerDuty / Opsgenie → on-call channel)
+    let incident_ctx = incidents::IncidentContext::from_config(&config).map(Arc::new);
+
     // ── Pairing guard ──────────────────────────────────────
     let pairing = Arc::new(PairingGuard::new(
         config.gateway.require_pairing,
@@ -154,7 +195,58 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         println!("  GET  /whatsapp  — Meta webhook verification");
         println!("  POST /whatsapp  — WhatsApp message webhook");
     }
+    if let Some(ref ctx) = incident_ctx {
+        if config.integrations.pagerduty.is_some() {
+            println!("  POST /incidents/pagerduty — PagerDuty webhook → on-call briefing");
+            if ctx.pagerduty_secret.is_none() {
+                println!("     ⚠️  No PagerDuty webhook_secret set — signatures are not verified");
+            }
+        }
+        if config.integrations.opsgenie.is_some() {
+            println!("  POST /incidents/opsgenie  — Opsgenie webhook → on-call briefing");
+            if ctx.opsgenie_secret.is_none() {
+                println!(
+                    "     ⚠️  No Opsgenie webhook_secret set — requests are not authenticated"
+                );
+            }
+        }
+    }
+    if !config.oauth.clients.is_empty() {
+        println!("  GET  /oauth/callback — OAuth sign-in redirect");
+    }
+    // Runs the model for whoever holds a token, so it is only served behind pairing
+    let shortcuts_wanted = config
+        .integrations
+        .shortcuts
+        .as_ref()
+        .is_some_and(|s| s.callback);
+    let shortcuts_callback = shortcuts_wanted && pairing.require_pairing();
+    if shortcuts_callback {
+        println!("  POST /shortcuts — prompts from Apple Shortcuts (bearer token)");
+    } else if shortcuts_wanted {
+        println!("  ⚠️  /shortcuts disabled — it requires pairing");
+    }
+    // The dashboard edits config, so it is only served behind pairing tokens
+    let dashboard = (config.gateway.dashboard && pairing.require_pairing())
+        .then(|| Arc::new(dashboard::Dashboard::new(&config)));
+    if dashboard.is_some() {
+        println!("  GET  /admin     — admin dashboard (bearer token)");
+    } else if config.gateway.dashboard {
+        println!("  ⚠️  Admin dashboard disabled — it requires pairing");
+    }
+    let federation = (!config.federation.peers.is_empty()).then(|| Arc::new(config.clone()));
+    if federation.is_some() {
+        println!(
+            "  POST /federation — messages and tasks from {} peer(s), as '{}'",
+            config.federation.peers.len(),
+            config.federation.name
+        );
+    }
     println!("  GET  /health    — health check");
+    let metrics = config.observability.backend == "prometheus";
+    if metrics {
+        println!("  GET  /metrics   — Prometheus metrics");
+    }
     if let Some(code) = pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
@@ -175,6 +267,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     crate::health::mark_component_ok("gateway");
 
     // Build shared state
+    let cache_capacity = crate::config::profile::cache_capacity(&config.runtime.profile);
     let state = AppState {
         provider,
         model,
@@ -185,21 +278,56 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         pairing,
         whatsapp: whatsapp_channel,
         whatsapp_app_secret,
+        incidents: incident_ctx,
+        idempotency: Arc::new(IdempotencyCache::new(DEFAULT_TTL, cache_capacity)),
+        seen_messages: Arc::new(IdempotencyCache::new(DEFAULT_TTL, cache_capacity)),
+        workspace_dir: Arc::from(config.workspace_dir.as_path()),
+        oauth: Arc::new(OAuthManager::new(&config)),
+        dashboard,
+        federation,
+        config: Arc::new(config.clone()),
     };
 
     // Build router with middleware
-    let app = Router::new()
-        .route("/health", get(handle_health))
+    let mut app = Router::new().route("/health", get(handle_health));
+    if metrics {
+        app = app.route("/metrics", get(handle_metrics));
+    }
+    if config.gateway.status_page {
+        app = app.route("/status", get(handle_status));
+    }
+    if state.dashboard.is_some() {
+        app = app.merge(dashboard::routes());
+    }
+    if shortcuts_callback {
+        app = app.merge(shortcuts::routes());
+    }
+    // Delegated tasks run a whole agent turn, so `/federation` is mounted
+    // after the 30s timeout and bounded by `task_timeout_secs` instead
+    let federation_routes = state.federation.is_some().then(|| {
+        Router::new()
+            .route("/federation", post(handle_federation))
+            .with_state(state.clone())
+            .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
+    });
+    let app = app
         .route("/pair", post(handle_pair))
         .route("/webhook", post(handle_webhook))
         .route("/whatsapp", get(handle_whatsapp_verify))
         .route("/whatsapp", post(handle_whatsapp_message))
+        .route("/incidents/pagerduty", post(handle_pagerduty_webhook))
+        .route("/incidents/opsgenie", post(handle_opsgenie_webhook))
+        .route("/oauth/callback", get(handle_oauth_callback))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(REQUEST_TIMEOUT_SECS),
         ));
+    let app = match federation_routes {
+        Some(routes) => app.merge(routes),
+        None => app,
+    };
 
     // Run the server
     axum::serve(listener, app).await?;
@@ -221,6 +349,79 @@ async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
     Json(body)
 }
 
+/// GET /status — public status page (opt-in): overall state, uptime,
+/// component states and message counts, with no errors, PIDs or content.
+/// Answers 503 while a component is failing so uptime monitors alert.
+async fn handle_status() -> impl IntoResponse {
+    let (status, body) = status_body(&crate::health::snapshot(), crate::health::message_counts());
+    (status, Json(body))
+}
+
+fn status_body(
+    snapshot: &crate::health::HealthSnapshot,
+    (received, sent): (u64, u64),
+) -> (StatusCode, serde_json::Value) {
+    let components: serde_json::Map<String, serde_json::Value> = snapshot
+        .components
+        .iter()
+        .map(|(name, health)| {
+            let state = match health.status.as_str() {
+                "ok" => "ok",
+                "error" => "down",
+                _ => "starting",
+            };
+            (name.clone(), state.into())
+        })
+        .collect();
+    let degraded = components.values().any(|state| state == "down");
+    let body = serde_json::json!({
+        "status": if degraded { "degraded" } else { "ok" },
+        "uptime_seconds": snapshot.uptime_seconds,
+        "components": components,
+        "messages": {"received": received, "sent": sent},
+    });
+    let code = if degraded {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (code, body)
+}
+
+/// GET /metrics — Prometheus scrape endpoint; needs the bearer token when
+/// pairing is on
+async fn handle_metrics(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if state.pairing.require_pairing() {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "))
+            .unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            return (StatusCode::UNAUTHORIZED, "Unauthorized\n".to_string()).into_response();
+        }
+    }
+    metrics_response().into_response()
+}
+
+fn metrics_response() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        crate::observability::prometheus::render(),
+    )
+}
+
+/// Serve only `/metrics`, unauthenticated, on `127.0.0.1:port` — for
+/// scrapers that should not reach the gateway.
+pub async fn run_metrics_server(port: u16) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    let app = Router::new().route("/metrics", get(|| async { metrics_response() }));
+    tracing::info!("Prometheus metrics on http://127.0.0.1:{port}/metrics");
+    crate::health::mark_component_ok("metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
 /// POST /pair — exchange one-time code for bearer token
 async fn handle_pair(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
     let code = headers
@@ -310,7 +511,46 @@ async fn handle_webhook(
         }
     };
 
-    let message = &webhook_body.message;
+    // ── `/run <workflow> key=value` runs a workspace workflow ──
+    if let Some(result) = crate::workflows::run_command(&state.config, &webhook_body.message).await
+    {
+        return match result {
+            Ok(summary) => (
+                StatusCode::OK,
+                Json(serde_json::json!({"response": summary})),
+            ),
+            Err(e) => {
+                let err = serde_json::json!({"error": format!("{e:#}")});
+                (StatusCode::BAD_REQUEST, Json(err))
+            }
+        };
+    }
+
+    // ── `/run <template> key=value` expands a workspace prompt template ──
+    let message = match crate::prompts::expand(&state.workspace_dir, &webhook_body.message) {
+        Some(Ok(rendered)) => rendered,
+        Some(Err(e)) => {
+            let err = serde_json::json!({"error": e.to_string()});
+            return (StatusCode::BAD_REQUEST, Json(err));
+        }
+        None => webhook_body.message,
+    };
+    let message = &message;
+
+    // ── Idempotency-Key: a retried request gets the first response back ──
+    let idempotency_key = headers
+        .get("Idempotency-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|k| !k.is_empty());
+    // Released if this request fails or is dropped before it answers.
+    let held = match idempotency_key
+        .map(|key| claim_idempotency_key(&state, key))
+        .transpose()
+    {
+        Ok(held) => held,
+        Err(early) => return early,
+    };
 
     if state.auto_save {
         let _ = state
@@ -326,9 +566,13 @@ async fn handle_webhook(
     {
         Ok(response) => {
             let body = serde_json::json!({"response": response, "model": state.model});
+            if let Some(held) = held {
+                held.complete(body.clone());
+            }
             (StatusCode::OK, Json(body))
         }
         Err(e) => {
+            drop(held);
             tracing::error!(
                 "Webhook provider error: {}",
                 providers::sanitize_api_error(&e.to_string())
@@ -339,6 +583,181 @@ async fn handle_webhook(
     }
 }
 
+/// POST /federation — a message or task from another instance.
+/// The sender names itself in `X-ZeroClaw-Peer` and signs the request with
+/// that peer's shared token; the answer carries an HMAC of its nonce and body
+/// so the sender can tell it came from us.
+async fn handle_federation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> axum::response::Response {
+    let Some(config) = state.federation.as_ref() else {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({}))).into_response();
+    };
+    let header_str = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    let name = header_str(federation::PEER_HEADER);
+    let nonce = header_str(federation::NONCE_HEADER);
+    if nonce.is_empty() || nonce.len() > federation::MAX_NONCE_LEN {
+        let err = serde_json::json!({
+            "error": format!("{} must be 1-{} characters", federation::NONCE_HEADER, federation::MAX_NONCE_LEN)
+        });
+        return (StatusCode::BAD_REQUEST, Json(err)).into_response();
+    }
+    let timestamp = header_str(federation::TIMESTAMP_HEADER);
+    let signature = header_str(federation::SIGNATURE_HEADER);
+    let signed = federation::SignedRequest {
+        peer: &name,
+        nonce: &nonce,
+        timestamp: &timestamp,
+        body: &body,
+        signature: &signature,
+    };
+    let now = chrono::Utc::now().timestamp();
+    let Some(peer) = federation::authenticate(&config.federation, &signed, now) else {
+        tracing::warn!("Federation: rejected request claiming to be peer '{name}'");
+        let err = serde_json::json!({"error": "Unknown peer, bad signature, stale timestamp or reused nonce"});
+        return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
+    };
+    // Authenticated from here on, so every answer carries our proof
+    let reply = |status: StatusCode, body: serde_json::Value| {
+        let body = body.to_string().into_bytes();
+        let proof = federation::proof(&peer.token, &nonce, &body);
+        (
+            status,
+            [
+                (
+                    header::CONTENT_TYPE.as_str(),
+                    "application/json".to_string(),
+                ),
+                (federation::PROOF_HEADER, proof),
+            ],
+            body,
+        )
+            .into_response()
+    };
+
+    let request: federation::FederationRequest = match serde_json::from_slice(&body) {
+        Ok(r) => r,
+        Err(e) => {
+            let err = serde_json::json!({
+                "error": format!("Invalid JSON: {e}. Expected: {{\"kind\": \"message\", \"message\": \"...\"}}")
+            });
+            return reply(StatusCode::BAD_REQUEST, err);
+        }
+    };
+
+    let result = match request.kind {
+        federation::RequestKind::Message => state
+            .provider
+            .chat(&request.message, &state.model, state.temperature)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "Federation provider error: {}",
+                    providers::sanitize_api_error(&e.to_string())
+                );
+                "LLM request failed".to_string()
+            }),
+        federation::RequestKind::Task if !peer.allow_tasks => {
+            let err = serde_json::json!({
+                "error": format!("Peer '{name}' may not delegate tasks here (allow_tasks = false)")
+            });
+            return reply(StatusCode::FORBIDDEN, err);
+        }
+        federation::RequestKind::Task => {
+            tracing::info!("Federation: running task delegated by '{name}'");
+            federation::run_task(config, &name, &request.message)
+                .await
+                .map_err(|e| e.to_string())
+        }
+    };
+
+    match result {
+        Ok(response) => {
+            let body = serde_json::json!({"response": response, "from": config.federation.name});
+            reply(StatusCode::OK, body)
+        }
+        Err(error) => reply(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            serde_json::json!({"error": error}),
+        ),
+    }
+}
+
+/// Claim `key` for a new `/webhook` request; `Err` is the response to send
+/// instead (bad key, still in flight, or the stored first response).
+fn claim_idempotency_key<'a>(
+    state: &'a AppState,
+    key: &str,
+) -> Result<Held<'a, serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        let err = serde_json::json!({
+            "error": format!("Idempotency-Key must be at most {MAX_IDEMPOTENCY_KEY_LEN} characters")
+        });
+        return Err((StatusCode::BAD_REQUEST, Json(err)));
+    }
+    match state.idempotency.claim(key) {
+        Claim::New => Ok(state.idempotency.hold(key)),
+        Claim::InFlight => {
+            let err = serde_json::json!({
+                "error": "A request with this Idempotency-Key is still being processed"
+            });
+            Err((StatusCode::CONFLICT, Json(err)))
+        }
+        Claim::Done(body) => {
+            tracing::info!("Webhook: replaying response for a repeated Idempotency-Key");
+            Err((StatusCode::OK, Json(body)))
+        }
+    }
+}
+
+/// Query params of the provider's redirect back to `/oauth/callback`
+#[derive(serde::Deserialize)]
+pub struct OAuthCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+/// GET /oauth/callback — finishes a `zeroclaw oauth login`. Public, since the
+/// browser cannot carry the bearer token; only states the CLI started are
+/// accepted. Answers in plain text so nothing from the query is rendered.
+async fn handle_oauth_callback(
+    State(state): State<AppState>,
+    Query(params): Query<OAuthCallbackQuery>,
+) -> impl IntoResponse {
+    let Some(login) = params.state.filter(|s| state.oauth.login_pending(s)) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Unknown or expired sign-in. Run `zeroclaw oauth login` again.".to_string(),
+        );
+    };
+    let Some(code) = params.code else {
+        let error = params.error.unwrap_or_else(|| "no code returned".into());
+        // Drop the pending login so the waiting CLI gives up.
+        state.oauth.cancel_login(&login);
+        return (StatusCode::BAD_REQUEST, format!("Sign-in failed: {error}"));
+    };
+    match state.oauth.complete_login(&login, &code).await {
+        Ok(provider) => (
+            StatusCode::OK,
+            format!("Signed in to {provider}. You can close this tab."),
+        ),
+        Err(e) => {
+            tracing::warn!("OAuth sign-in failed: {e:#}");
+            (StatusCode::BAD_GATEWAY, format!("Sign-in failed: {e}"))
+        }
+    }
+}
+
 /// `WhatsApp` verification query params
 #[derive(serde::Deserialize)]
 pub struct WhatsAppVerifyQuery {
@@ -448,7 +867,7 @@ async fn handle_whatsapp_message(
     };
 
     // Parse messages from the webhook payload
-    let messages = wa.parse_webhook_payload(&payload);
+    let messages = wa.receive_webhook(&payload).await;
 
     if messages.is_empty() {
         // Acknowledge the webhook even if no messages (could be status updates)
@@ -457,11 +876,19 @@ async fn handle_whatsapp_message(
 
     // Process each message
     for msg in &messages {
+        if !state
+            .seen_messages
+            .first_seen(&format!("whatsapp:{}", msg.id))
+        {
+            tracing::debug!("WhatsApp: skipping redelivered message {}", msg.id);
+            continue;
+        }
         tracing::info!(
             "WhatsApp message from {}: {}",
             msg.sender,
             truncate_with_ellipsis(&msg.content, 50)
         );
+        let _lock = crate::redis::lock_session(&session_key("whatsapp", &msg.sender)).await;
 
         // Auto-save to memory
         if state.auto_save {
@@ -503,10 +930,147 @@ async fn handle_whatsapp_message(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// POST /incidents/pagerduty — `PagerDuty` v3 webhook
+async fn handle_pagerduty_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ctx) = state.incidents.clone() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Incident webhooks not configured"})),
+        );
+    };
+    if let Some(ref secret) = ctx.pagerduty_secret {
+        let signature = headers
+            .get("X-PagerDuty-Signature")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        if !incidents::verify_pagerduty_signature(secret, &body, signature) {
+            tracing::warn!("PagerDuty webhook signature verification failed");
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid signature"})),
+            );
+        }
+    }
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+    dispatch_page(&state, ctx, incidents::parse_pagerduty_webhook(&payload))
+}
+
+/// POST /incidents/opsgenie — Opsgenie outgoing webhook
+async fn handle_opsgenie_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ctx) = state.incidents.clone() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Incident webhooks not configured"})),
+        );
+    };
+    if let Some(ref secret) = ctx.opsgenie_secret {
+        let header_val = headers
+            .get("X-Webhook-Secret")
+            .and_then(|v| v.to_str().ok());
+        if !header_val.is_some_and(|val| constant_time_eq(val, secret.as_ref())) {
+            tracing::warn!("Opsgenie webhook: rejected — invalid or missing X-Webhook-Secret");
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid or missing X-Webhook-Secret"})),
+            );
+        }
+    }
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+    dispatch_page(&state, ctx, incidents::parse_opsgenie_webhook(&payload))
+}
+
+/// Acknowledge the webhook immediately and brief on-call in the background,
+/// since the LLM call can outlast the sender's delivery timeout.
+fn dispatch_page(
+    state: &AppState,
+    ctx: Arc<incidents::IncidentContext>,
+    page: Option<incidents::Page>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Some(page) = page else {
+        // Acks, resolves, notes … — nothing to brief on.
+        return (
+            StatusCode::OK,
+            Json(serde_json::json!({"status": "ignored"})),
+        );
+    };
+    tracing::info!(
+        "{} page {}: {}",
+        page.source,
+        page.id,
+        truncate_with_ellipsis(&page.title, 80)
+    );
+    tokio::spawn(incidents::brief_on_call(
+        ctx,
+        state.provider.clone(),
+        state.model.clone(),
+        state.temperature,
+        page,
+    ));
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({"status": "accepted"})),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn status_page_hides_errors_and_reports_degraded() {
+        let component = |status: &str, error: Option<&str>| crate::health::ComponentHealth {
+            status: status.into(),
+            updated_at: String::new(),
+            last_ok: None,
+            last_error: error.map(String::from),
+            restart_count: 3,
+        };
+        let mut snapshot = crate::health::HealthSnapshot {
+            pid: 4242,
+            updated_at: String::new(),
+            uptime_seconds: 90,
+            rss_kb: None,
+            components: [
+                ("gateway".to_string(), component("ok", None)),
+                ("channels".to_string(), component("starting", None)),
+            ]
+            .into(),
+        };
+        let (code, body) = status_body(&snapshot, (5, 4));
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["messages"]["received"], 5);
+
+        snapshot.components.insert(
+            "channels".into(),
+            component("error", Some("token sk-secret rejected")),
+        );
+        let (code, body) = status_body(&snapshot, (5, 4));
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["components"]["channels"], "down");
+        let text = body.to_string();
+        assert!(!text.contains("sk-secret") && !text.contains("4242"));
+    }
+
     #[test]
     fn security_body_limit_is_64kb() {
         assert_eq!(MAX_BODY_SIZE, 65_536);
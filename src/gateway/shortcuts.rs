@@ -0,0 +1,153 @@
+//! `POST /shortcuts` — Apple Shortcuts sends a prompt and gets the reply back.
+//!
+//! "Get Contents of URL" posts `{"prompt": "..."}` with the pairing token in
+//! `Authorization` and reads the reply as the plain-text body. For
+//! x-callback-url style round trips, add `x-success` and `x-error`: the body
+//! is then that app URL with the reply as `result` (or the error as
+//! `errorMessage`), for an "Open URLs" action to hand back to the calling
+//! app. Only `shortcuts://` URLs are accepted, so a reply can't be sent off
+//! to a website. The route is only mounted when pairing is required.
+
+use super::AppState;
+use crate::memory::MemoryCategory;
+use crate::providers;
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde::Deserialize;
+
+/// Schemes a callback may use: apps that hand the reply back on the device.
+const CALLBACK_SCHEMES: [&str; 1] = ["shortcuts"];
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/shortcuts", post(handle_shortcut))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShortcutRequest {
+    #[serde(default)]
+    prompt: String,
+    #[serde(rename = "x-success")]
+    x_success: Option<String>,
+    #[serde(rename = "x-error")]
+    x_error: Option<String>,
+}
+
+/// POST /shortcuts {"prompt": "...", "x-success": "...", "x-error": "..."}
+async fn handle_shortcut(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<ShortcutRequest>, JsonRejection>,
+) -> Response {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if !state.pairing.is_authenticated(token) {
+        tracing::warn!("Shortcuts: rejected — not paired / invalid token");
+        return (
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>",
+        )
+            .into_response();
+    }
+    let request = match body {
+        Ok(Json(request)) => request,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.body_text()).into_response(),
+    };
+    let prompt = request.prompt.trim();
+    if prompt.is_empty() {
+        return reply(
+            &request,
+            Err((StatusCode::BAD_REQUEST, "Missing `prompt`".into())),
+        );
+    }
+    let answer = answer(&state, prompt).await;
+    reply(&request, answer)
+}
+
+/// Run a prompt the way `/webhook` does: workflows, templates, then the model.
+async fn answer(state: &AppState, prompt: &str) -> Result<String, (StatusCode, String)> {
+    if let Some(result) = crate::workflows::run_command(&state.config, prompt).await {
+        return result.map_err(|e| (StatusCode::BAD_REQUEST, format!("{e:#}")));
+    }
+    let message = match crate::prompts::expand(&state.workspace_dir, prompt) {
+        Some(Ok(rendered)) => rendered,
+        Some(Err(e)) => return Err((StatusCode::BAD_REQUEST, e.to_string())),
+        None => prompt.to_string(),
+    };
+    if state.auto_save {
+        let _ = state
+            .mem
+            .store("shortcuts_msg", &message, MemoryCategory::Conversation)
+            .await;
+    }
+    state
+        .provider
+        .chat(&message, &state.model, state.temperature)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Shortcuts provider error: {}",
+                providers::sanitize_api_error(&e.to_string())
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "LLM request failed".to_string(),
+            )
+        })
+}
+
+fn reply(request: &ShortcutRequest, result: Result<String, (StatusCode, String)>) -> Response {
+    let (callback, key, status, text) = match result {
+        Ok(text) => (&request.x_success, "result", StatusCode::OK, text),
+        Err((status, text)) => (&request.x_error, "errorMessage", status, text),
+    };
+    match callback.as_deref().map(|url| callback_url(url, key, &text)) {
+        Some(Some(url)) => (status, url).into_response(),
+        Some(None) => (
+            StatusCode::BAD_REQUEST,
+            "x-success and x-error must be shortcuts:// URLs, e.g. shortcuts://x-callback-url/...",
+        )
+            .into_response(),
+        None => (status, text).into_response(),
+    }
+}
+
+/// `base` with `key=value` added; `None` unless it is a `shortcuts://` URL.
+fn callback_url(base: &str, key: &str, value: &str) -> Option<String> {
+    let mut url = reqwest::Url::parse(base).ok()?;
+    if !CALLBACK_SCHEMES.contains(&url.scheme()) {
+        return None;
+    }
+    url.query_pairs_mut().append_pair(key, value);
+    Some(url.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callbacks_only_go_to_apps() {
+        assert_eq!(
+            callback_url(
+                "shortcuts://x-callback-url/run-shortcut?name=Speak",
+                "result",
+                "Rain & wind"
+            )
+            .as_deref(),
+            Some("shortcuts://x-callback-url/run-shortcut?name=Speak&result=Rain+%26+wind")
+        );
+        assert!(callback_url("https://evil.example/collect", "result", "x").is_none());
+        assert!(callback_url("javascript:alert(1)", "result", "x").is_none());
+        assert!(callback_url("intent://evil#Intent;end", "result", "x").is_none());
+        assert!(callback_url("vbscript:msgbox", "result", "x").is_none());
+        assert!(callback_url("not a url", "result", "x").is_none());
+    }
+}
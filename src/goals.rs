@@ -0,0 +1,683 @@
+//! Standing goals the heartbeat keeps an eye on.
+//!
+//! A goal is a condition in plain words ("keep my inbox under 20 unread",
+//! "remind me to water the plants when the soil sensor reads dry"). Each
+//! heartbeat tick, every due goal is handed to a child `zeroclaw agent` run
+//! that checks the condition with its tools and either leaves it alone, takes
+//! a step to restore it, or reports what should be done. Whether it may act
+//! is decided here, before the run: never under read-only autonomy, and at
+//! most `[goals].max_actions_per_day` times per goal. Every check is logged in
+//! `workspace/goals/goals.db`.
+
+use crate::config::Config;
+use crate::security::AutonomyLevel;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rusqlite::{params, Connection};
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::process::Command;
+use uuid::Uuid;
+
+/// Longest a single goal check may run before it is killed
+const CHECK_TIMEOUT: Duration = Duration::from_mins(10);
+/// Heartbeat ticks drift a little; a goal this close to due counts as due
+const DUE_SLACK_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalStatus {
+    Active,
+    Paused,
+}
+
+impl GoalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Paused => "paused",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "active" => Ok(Self::Active),
+            "paused" => Ok(Self::Paused),
+            other => anyhow::bail!("Unknown goal status in goals DB: {other}"),
+        }
+    }
+}
+
+/// What a check concluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The goal holds; nothing was done
+    Ok,
+    /// The goal did not hold and the agent acted on it
+    Acted,
+    /// Action is needed but was not allowed or not possible
+    Blocked,
+    /// The run finished without a verdict line
+    Unclear,
+    /// The run itself failed
+    Failed,
+}
+
+impl Verdict {
+    const ALL: [Self; 5] = [
+        Self::Ok,
+        Self::Acted,
+        Self::Blocked,
+        Self::Unclear,
+        Self::Failed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Acted => "acted",
+            Self::Blocked => "blocked",
+            Self::Unclear => "unclear",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == raw)
+            .ok_or_else(|| anyhow::anyhow!("Unknown goal verdict in goals DB: {raw}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Goal {
+    pub id: String,
+    pub description: String,
+    pub status: GoalStatus,
+    /// Minutes between checks; None = every heartbeat tick
+    pub interval_minutes: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+}
+
+impl Goal {
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(8)]
+    }
+
+    /// Whether the goal should be checked on a tick at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        if self.status != GoalStatus::Active {
+            return false;
+        }
+        match (self.last_checked_at, self.interval_minutes) {
+            (Some(last), Some(minutes)) => {
+                (now - last).num_seconds() + DUE_SLACK_SECS >= i64::from(minutes) * 60
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GoalCheck {
+    pub at: DateTime<Utc>,
+    pub verdict: Verdict,
+    pub detail: String,
+}
+
+pub struct GoalStore {
+    conn: Mutex<Connection>,
+}
+
+impl GoalStore {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("goals").join("goals.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create goals directory: {}", parent.display())
+            })?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open goals DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS goals (
+                id               TEXT PRIMARY KEY,
+                description      TEXT NOT NULL,
+                status           TEXT NOT NULL,
+                interval_minutes INTEGER,
+                created_at       TEXT NOT NULL,
+                last_checked_at  TEXT
+             );
+             CREATE TABLE IF NOT EXISTS goal_checks (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                goal_id    TEXT NOT NULL,
+                checked_at TEXT NOT NULL,
+                verdict    TEXT NOT NULL,
+                detail     TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_goal_checks_goal ON goal_checks(goal_id, id);",
+        )
+        .context("Failed to initialize goals schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub fn create(&self, description: &str, interval_minutes: Option<u32>) -> Result<Goal> {
+        let description = description.trim();
+        if description.is_empty() {
+            anyhow::bail!("A goal needs a description");
+        }
+        let goal = Goal {
+            id: Uuid::new_v4().to_string(),
+            description: description.to_string(),
+            status: GoalStatus::Active,
+            interval_minutes: interval_minutes.filter(|m| *m > 0),
+            created_at: Utc::now(),
+            last_checked_at: None,
+        };
+        self.conn()
+            .execute(
+                "INSERT INTO goals (id, description, status, interval_minutes, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    goal.id,
+                    goal.description,
+                    goal.status.as_str(),
+                    goal.interval_minutes,
+                    goal.created_at.to_rfc3339()
+                ],
+            )
+            .context("Failed to insert goal")?;
+        Ok(goal)
+    }
+
+    pub fn set_status(&self, id: &str, status: GoalStatus) -> Result<()> {
+        let changed = self
+            .conn()
+            .execute(
+                "UPDATE goals SET status = ?1 WHERE id = ?2",
+                params![status.as_str(), id],
+            )
+            .context("Failed to update goal")?;
+        if changed == 0 {
+            anyhow::bail!("Goal '{id}' not found");
+        }
+        Ok(())
+    }
+
+    /// Delete a goal and its check history.
+    pub fn remove(&self, id: &str) -> Result<()> {
+        let conn = self.conn();
+        let changed = conn
+            .execute("DELETE FROM goals WHERE id = ?1", params![id])
+            .context("Failed to delete goal")?;
+        if changed == 0 {
+            anyhow::bail!("Goal '{id}' not found");
+        }
+        conn.execute("DELETE FROM goal_checks WHERE goal_id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Log a check; `at` is when it started, so the next one is due an
+    /// interval after this one began rather than after it finished.
+    pub fn record_check(
+        &self,
+        id: &str,
+        at: DateTime<Utc>,
+        verdict: Verdict,
+        detail: &str,
+    ) -> Result<()> {
+        let at = at.to_rfc3339();
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO goal_checks (goal_id, checked_at, verdict, detail) VALUES (?1, ?2, ?3, ?4)",
+            params![id, at, verdict.as_str(), detail],
+        )
+        .context("Failed to record goal check")?;
+        conn.execute(
+            "UPDATE goals SET last_checked_at = ?1 WHERE id = ?2",
+            params![at, id],
+        )?;
+        Ok(())
+    }
+
+    /// The most recent `limit` checks of a goal, newest first.
+    pub fn checks(&self, id: &str, limit: usize) -> Result<Vec<GoalCheck>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT checked_at, verdict, detail FROM goal_checks
+             WHERE goal_id = ?1 ORDER BY id DESC LIMIT ?2",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = stmt.query_map(params![id, limit], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut checks = Vec::new();
+        for row in rows {
+            let (at, verdict, detail) = row?;
+            checks.push(GoalCheck {
+                at: parse_rfc3339(&at)?,
+                verdict: Verdict::parse(&verdict)?,
+                detail,
+            });
+        }
+        Ok(checks)
+    }
+
+    /// How many checks of a goal acted since `since`.
+    pub fn actions_since(&self, id: &str, since: DateTime<Utc>) -> Result<usize> {
+        let count: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM goal_checks
+             WHERE goal_id = ?1 AND verdict = 'acted' AND checked_at >= ?2",
+            params![id, since.to_rfc3339()],
+            |row| row.get(0),
+        )?;
+        Ok(usize::try_from(count).unwrap_or(0))
+    }
+
+    /// All goals, oldest first.
+    pub fn list(&self) -> Result<Vec<Goal>> {
+        self.query("ORDER BY created_at ASC", [])
+    }
+
+    pub fn get(&self, id: &str) -> Result<Goal> {
+        self.query("WHERE id = ?1", [id])?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Goal '{id}' not found"))
+    }
+
+    /// Expand a unique ID prefix (as shown by `goals list`) to the full ID.
+    pub fn resolve_id(&self, prefix: &str) -> Result<String> {
+        let pattern = format!("{}%", prefix.trim());
+        let matches = self.query("WHERE id LIKE ?1", [pattern])?;
+        match matches.as_slice() {
+            [one] => Ok(one.id.clone()),
+            [] => anyhow::bail!("Goal '{prefix}' not found"),
+            _ => anyhow::bail!("Goal ID '{prefix}' is ambiguous; use more characters"),
+        }
+    }
+
+    fn query(&self, clause: &str, args: impl rusqlite::Params) -> Result<Vec<Goal>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, description, status, interval_minutes, created_at, last_checked_at
+             FROM goals {clause}"
+        ))?;
+        let rows = stmt.query_map(args, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<u32>>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?;
+
+        let mut goals = Vec::new();
+        for row in rows {
+            let (id, description, status, interval_minutes, created, checked) = row?;
+            goals.push(Goal {
+                id,
+                description,
+                status: GoalStatus::parse(&status)?,
+                interval_minutes,
+                created_at: parse_rfc3339(&created)?,
+                last_checked_at: checked.as_deref().map(parse_rfc3339).transpose()?,
+            });
+        }
+        Ok(goals)
+    }
+}
+
+/// Why a check may not act, if it may not.
+pub fn action_block(config: &Config, acted_today: usize) -> Option<String> {
+    if config.autonomy.level == AutonomyLevel::ReadOnly {
+        return Some("autonomy is read-only".into());
+    }
+    let limit = config.goals.max_actions_per_day as usize;
+    (acted_today >= limit).then(|| {
+        format!(
+            "this goal already acted {acted_today} time(s) in the last 24 hours (limit {limit})"
+        )
+    })
+}
+
+/// The message that asks the agent to check one goal.
+pub fn check_prompt(goal: &Goal, block: Option<&str>) -> String {
+    let mut prompt = format!(
+        "[Goal check] Standing goal {}: \"{}\"\n\
+         Use your tools to find out whether this goal currently holds.\n\
+         - If it holds, change nothing.\n",
+        goal.short_id(),
+        goal.description
+    );
+    match block {
+        None => prompt.push_str(
+            "- If it does not, take the smallest step that restores it (a reminder or \
+             message counts), within your normal permissions. Do not do anything \
+             destructive or irreversible for a goal; report it instead.\n",
+        ),
+        Some(reason) => {
+            let _ = writeln!(
+                prompt,
+                "- If it does not, do NOT act ({reason}); only say what should be done."
+            );
+        }
+    }
+    prompt.push_str(
+        "End your reply with exactly one of these lines:\n\
+         GOAL_OK: <current state>\n\
+         GOAL_ACTED: <what you did>\n\
+         GOAL_BLOCKED: <what needs doing and why you did not do it>",
+    );
+    prompt
+}
+
+/// The verdict line from the end of a check's output.
+pub fn parse_verdict(output: &str) -> (Verdict, String) {
+    for line in output.lines().rev() {
+        let line = line.trim().trim_matches('*').trim();
+        for (marker, verdict) in [
+            ("GOAL_OK:", Verdict::Ok),
+            ("GOAL_ACTED:", Verdict::Acted),
+            ("GOAL_BLOCKED:", Verdict::Blocked),
+        ] {
+            if let Some(detail) = line.strip_prefix(marker) {
+                return (verdict, detail.trim().to_string());
+            }
+        }
+    }
+    let last = output.lines().rev().find(|l| !l.trim().is_empty());
+    (
+        Verdict::Unclear,
+        crate::util::truncate_with_ellipsis(last.unwrap_or("(no output)").trim(), 200),
+    )
+}
+
+/// Check one goal now and log the result.
+pub async fn check(config: &Config, store: &GoalStore, goal: &Goal) -> Result<GoalCheck> {
+    let started = Utc::now();
+    let acted_today = store.actions_since(&goal.id, started - ChronoDuration::hours(24))?;
+    let block = action_block(config, acted_today);
+    let prompt = check_prompt(goal, block.as_deref());
+
+    let (mut verdict, detail) = match run_agent(config, goal, &prompt).await {
+        Ok(output) => parse_verdict(&output),
+        Err(e) => (Verdict::Failed, format!("{e:#}")),
+    };
+    // A run told not to act that claims it did is logged as blocked, so it
+    // does not eat into the action budget
+    if verdict == Verdict::Acted && block.is_some() {
+        tracing::warn!(goal = goal.short_id(), "Goal check acted while blocked");
+        verdict = Verdict::Blocked;
+    }
+    store.record_check(&goal.id, started, verdict, &detail)?;
+    Ok(GoalCheck {
+        at: started,
+        verdict,
+        detail,
+    })
+}
+
+/// Check every due goal; called from the heartbeat. Returns how many ran.
+pub async fn check_due(config: &Config) -> Result<usize> {
+    let store = GoalStore::open(&config.workspace_dir)?;
+    let now = Utc::now();
+    let due: Vec<Goal> = store
+        .list()?
+        .into_iter()
+        .filter(|g| g.is_due(now))
+        .collect();
+    for goal in &due {
+        let result = check(config, &store, goal).await?;
+        tracing::info!(
+            goal = goal.short_id(),
+            verdict = result.verdict.as_str(),
+            "Goal checked: {}",
+            result.detail
+        );
+    }
+    Ok(due.len())
+}
+
+/// Goal checks run as a child `zeroclaw agent` process in their own session,
+/// the way cron runs agent jobs.
+async fn run_agent(config: &Config, goal: &Goal, prompt: &str) -> Result<String> {
+    let exe = std::env::current_exe().context("Cannot locate the zeroclaw binary")?;
+    let mut command = Command::new(exe);
+    command
+        .args(["agent", "--message", prompt, "--session"])
+        .arg(format!("goal-{}", goal.short_id()))
+        .current_dir(&config.workspace_dir)
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(CHECK_TIMEOUT, command.output())
+        .await
+        .context("Goal check timed out")??;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Agent exited with {}: {}", output.status, stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn describe_interval(goal: &Goal) -> String {
+    goal.interval_minutes
+        .map_or_else(|| "every heartbeat".into(), |m| format!("every {m} min"))
+}
+
+pub async fn handle_command(command: Option<crate::GoalCommands>, config: &Config) -> Result<()> {
+    let store = GoalStore::open(&config.workspace_dir)?;
+    match command.unwrap_or(crate::GoalCommands::List) {
+        crate::GoalCommands::List => {
+            let goals = store.list()?;
+            if goals.is_empty() {
+                println!("No goals yet.");
+                println!("\nUsage:");
+                println!("  zeroclaw goals add \"Keep my inbox under 20 unread\" --every 60");
+                return Ok(());
+            }
+            println!("🎯 Goals ({}):", goals.len());
+            for goal in &goals {
+                let last = store.checks(&goal.id, 1)?.pop().map_or_else(
+                    || "never checked".to_string(),
+                    |c| format!("{} at {}", c.verdict.as_str(), c.at.to_rfc3339()),
+                );
+                println!(
+                    "- {} | {:<6} | {} | {last}\n    {}",
+                    goal.short_id(),
+                    goal.status.as_str(),
+                    describe_interval(goal),
+                    goal.description
+                );
+            }
+            if !config.heartbeat.enabled {
+                println!("\n⚠️  [heartbeat] is disabled, so goals are only checked with `zeroclaw goals check`.");
+            }
+            Ok(())
+        }
+        crate::GoalCommands::Show { id } => {
+            let goal = store.get(&store.resolve_id(&id)?)?;
+            println!("🎯 {} — {}", goal.short_id(), goal.description);
+            println!("  Status : {}", goal.status.as_str());
+            println!("  Checked: {}", describe_interval(&goal));
+            println!("  Created: {}", goal.created_at.to_rfc3339());
+            for check in store.checks(&goal.id, 20)? {
+                println!(
+                    "  [{}] {:<7} {}",
+                    check.at.to_rfc3339(),
+                    check.verdict.as_str(),
+                    check.detail
+                );
+            }
+            Ok(())
+        }
+        crate::GoalCommands::Add { description, every } => {
+            let goal = store.create(&description, every)?;
+            println!(
+                "✅ Added goal {} ({})",
+                goal.short_id(),
+                describe_interval(&goal)
+            );
+            Ok(())
+        }
+        crate::GoalCommands::Pause { id } => {
+            let id = store.resolve_id(&id)?;
+            store.set_status(&id, GoalStatus::Paused)?;
+            println!("⏸️  Paused goal {}", &id[..8]);
+            Ok(())
+        }
+        crate::GoalCommands::Resume { id } => {
+            let id = store.resolve_id(&id)?;
+            store.set_status(&id, GoalStatus::Active)?;
+            println!("▶️  Resumed goal {}", &id[..8]);
+            Ok(())
+        }
+        crate::GoalCommands::Remove { id } => {
+            let id = store.resolve_id(&id)?;
+            store.remove(&id)?;
+            println!("🗑️  Removed goal {}", &id[..8]);
+            Ok(())
+        }
+        crate::GoalCommands::Check { id } => {
+            let goals = match id {
+                Some(id) => vec![store.get(&store.resolve_id(&id)?)?],
+                None => store
+                    .list()?
+                    .into_iter()
+                    .filter(|g| g.status == GoalStatus::Active)
+                    .collect(),
+            };
+            for goal in &goals {
+                println!("🔎 Checking {} — {}", goal.short_id(), goal.description);
+                let result = check(config, &store, goal).await?;
+                println!("   {}: {}", result.verdict.as_str(), result.detail);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Invalid RFC3339 timestamp in goals DB: {raw}"))?;
+    Ok(parsed.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn goal_lifecycle_is_persisted() {
+        let tmp = TempDir::new().unwrap();
+        let store = GoalStore::open(tmp.path()).unwrap();
+        let goal = store.create("Inbox under 20 unread", Some(60)).unwrap();
+        assert!(store.create("   ", None).is_err());
+
+        let now = Utc::now();
+        store
+            .record_check(&goal.id, now, Verdict::Acted, "Archived 12 newsletters")
+            .unwrap();
+        store
+            .record_check(&goal.id, now, Verdict::Ok, "14 unread")
+            .unwrap();
+        assert_eq!(
+            store
+                .actions_since(&goal.id, now - ChronoDuration::hours(1))
+                .unwrap(),
+            1
+        );
+
+        let reopened = GoalStore::open(tmp.path()).unwrap();
+        let id = reopened.resolve_id(&goal.id[..6]).unwrap();
+        let stored = reopened.get(&id).unwrap();
+        assert_eq!(stored.interval_minutes, Some(60));
+        assert!(stored.last_checked_at.is_some());
+        let checks = reopened.checks(&id, 10).unwrap();
+        assert_eq!(checks[0].verdict, Verdict::Ok);
+
+        reopened.set_status(&id, GoalStatus::Paused).unwrap();
+        assert!(!reopened
+            .get(&id)
+            .unwrap()
+            .is_due(now + ChronoDuration::days(1)));
+        reopened.remove(&id).unwrap();
+        assert!(reopened.list().unwrap().is_empty());
+        assert!(reopened.checks(&id, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn due_respects_interval_with_slack() {
+        let now = Utc::now();
+        let mut goal = Goal {
+            id: "g".into(),
+            description: "d".into(),
+            status: GoalStatus::Active,
+            interval_minutes: Some(30),
+            created_at: now,
+            last_checked_at: None,
+        };
+        assert!(goal.is_due(now));
+        goal.last_checked_at = Some(now - ChronoDuration::minutes(10));
+        assert!(!goal.is_due(now));
+        goal.last_checked_at = Some(now - ChronoDuration::seconds(29 * 60 + 30));
+        assert!(goal.is_due(now));
+        goal.interval_minutes = None;
+        goal.last_checked_at = Some(now);
+        assert!(goal.is_due(now));
+    }
+
+    #[test]
+    fn action_budget_and_read_only_block_acting() {
+        let mut config = Config::default();
+        config.goals.max_actions_per_day = 2;
+        assert!(action_block(&config, 1).is_none());
+        assert!(action_block(&config, 2).unwrap().contains("limit 2"));
+        config.autonomy.level = AutonomyLevel::ReadOnly;
+        assert!(action_block(&config, 0).unwrap().contains("read-only"));
+
+        let goal = GoalStore::open(TempDir::new().unwrap().path())
+            .unwrap()
+            .create("Water the plants when dry", None)
+            .unwrap();
+        let prompt = check_prompt(&goal, Some("autonomy is read-only"));
+        assert!(prompt.contains("do NOT act (autonomy is read-only)"));
+        assert!(prompt.contains(goal.short_id()));
+        assert!(check_prompt(&goal, None).contains("smallest step"));
+    }
+
+    #[test]
+    fn verdict_is_read_from_the_last_marker_line() {
+        let (verdict, detail) =
+            parse_verdict("Checked the sensor.\n**GOAL_ACTED: Sent a reminder**\n");
+        assert_eq!(verdict, Verdict::Acted);
+        assert_eq!(detail, "Sent a reminder");
+        assert_eq!(parse_verdict("GOAL_OK: 3 unread").0, Verdict::Ok);
+        assert_eq!(
+            parse_verdict("GOAL_OK: fine\nGOAL_BLOCKED: needs a login").0,
+            Verdict::Blocked
+        );
+        let (verdict, detail) = parse_verdict("I looked around.\n\n");
+        assert_eq!(verdict, Verdict::Unclear);
+        assert_eq!(detail, "I looked around.");
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::Utc;
 use serde::Serialize;
 use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::Instant;
 
@@ -18,12 +19,16 @@ pub struct HealthSnapshot {
     pub pid: u32,
     pub updated_at: String,
     pub uptime_seconds: u64,
+    /// Resident memory of this process (Linux only)
+    pub rss_kb: Option<u64>,
     pub components: BTreeMap<String, ComponentHealth>,
 }
 
 struct HealthRegistry {
     started_at: Instant,
     components: Mutex<BTreeMap<String, ComponentHealth>>,
+    received: AtomicU64,
+    sent: AtomicU64,
 }
 
 static REGISTRY: OnceLock<HealthRegistry> = OnceLock::new();
@@ -32,6 +37,8 @@ fn registry() -> &'static HealthRegistry {
     REGISTRY.get_or_init(|| HealthRegistry {
         started_at: Instant::now(),
         components: Mutex::new(BTreeMap::new()),
+        received: AtomicU64::new(0),
+        sent: AtomicU64::new(0),
     })
 }
 
@@ -82,6 +89,38 @@ pub fn bump_component_restart(component: &str) {
     });
 }
 
+/// Count a channel message handled by this process.
+pub fn count_message(inbound: bool) {
+    let counter = if inbound {
+        &registry().received
+    } else {
+        &registry().sent
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Channel messages (received, sent) since this process started.
+pub fn message_counts() -> (u64, u64) {
+    (
+        registry().received.load(Ordering::Relaxed),
+        registry().sent.load(Ordering::Relaxed),
+    )
+}
+
+/// Resident set size of this process in KiB, from `/proc/self/status`.
+pub fn resident_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
+
 pub fn snapshot() -> HealthSnapshot {
     let components = registry()
         .components
@@ -92,6 +131,7 @@ pub fn snapshot() -> HealthSnapshot {
         pid: std::process::id(),
         updated_at: now_rfc3339(),
         uptime_seconds: registry().started_at.elapsed().as_secs(),
+        rss_kb: resident_kb(),
         components,
     }
 }
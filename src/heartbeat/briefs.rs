@@ -0,0 +1,293 @@
+use crate::config::{CalendarFeed, Config};
+use crate::memory::{self, Memory, MemoryEntry};
+use crate::notifications::Alert;
+use crate::providers::{self, Provider};
+use crate::tools::calendar::{self, CalendarEvent};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const TICK_SECS: u64 = 60;
+/// Feeds are re-downloaded at most this often.
+const REFRESH_MINS: i64 = 10;
+const NOTES_PER_QUERY: usize = 3;
+const MAX_NOTES: usize = 8;
+const MAX_ATTENDEE_QUERIES: usize = 5;
+
+const BRIEF_SYSTEM_PROMPT: &str = "You prepare the user for a meeting that starts soon. \
+Write a short brief: purpose of the meeting, who is attending and what the user knows about them, \
+open threads from the related notes, and two or three suggested talking points. \
+Use only the information given; say so when the notes are thin.";
+
+/// Meetings already briefed, keyed by UID and start time.
+struct SentLog {
+    path: PathBuf,
+    sent: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl SentLog {
+    fn load(workspace_dir: &Path) -> Self {
+        let path = workspace_dir.join("heartbeat").join("briefs_sent.json");
+        let sent = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self { path, sent }
+    }
+
+    fn key(event: &CalendarEvent) -> String {
+        format!("{}@{}", event.uid, event.start.to_rfc3339())
+    }
+
+    fn contains(&self, event: &CalendarEvent) -> bool {
+        self.sent.contains_key(&Self::key(event))
+    }
+
+    fn record(&mut self, event: &CalendarEvent) -> Result<()> {
+        self.sent.insert(Self::key(event), event.start);
+        let cutoff = Utc::now() - Duration::days(2);
+        self.sent.retain(|_, start| *start > cutoff);
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.sent)?)?;
+        Ok(())
+    }
+}
+
+/// Timed events on `feed` whose brief is due at `now` and not sent yet.
+fn due<'a>(
+    events: &'a [CalendarEvent],
+    feed: &CalendarFeed,
+    now: DateTime<Utc>,
+    log: &SentLog,
+) -> Vec<&'a CalendarEvent> {
+    let lead = Duration::minutes(i64::from(feed.lead_minutes));
+    events
+        .iter()
+        .filter(|e| !e.all_day && e.start > now && e.start - lead <= now && !log.contains(e))
+        .collect()
+}
+
+/// Memories mentioning the meeting or its attendees, most relevant first.
+async fn related_notes(mem: &dyn Memory, event: &CalendarEvent) -> Vec<MemoryEntry> {
+    let mut queries = vec![event.summary.clone()];
+    queries.extend(
+        event
+            .attendees
+            .iter()
+            .take(MAX_ATTENDEE_QUERIES)
+            .map(|a| a.split(" <").next().unwrap_or(a).to_string()),
+    );
+    let mut notes: Vec<MemoryEntry> = Vec::new();
+    for query in queries {
+        for entry in mem
+            .recall(&query, NOTES_PER_QUERY)
+            .await
+            .unwrap_or_default()
+        {
+            if notes.len() < MAX_NOTES && !notes.iter().any(|n| n.id == entry.id) {
+                notes.push(entry);
+            }
+        }
+    }
+    notes
+}
+
+fn build_brief_prompt(event: &CalendarEvent, notes: &[MemoryEntry]) -> String {
+    let mut prompt = format!("Meeting: {}\n", calendar::format_event(event));
+    if let Some(description) = &event.description {
+        let _ = writeln!(
+            prompt,
+            "Agenda:\n{}",
+            crate::util::truncate_with_ellipsis(description, 1500)
+        );
+    }
+    if notes.is_empty() {
+        prompt.push_str("\nNo related notes or emails in memory.\n");
+    } else {
+        prompt.push_str("\nRelated notes and emails from memory:\n");
+        for note in notes {
+            let _ = writeln!(
+                prompt,
+                "- [{}] {}",
+                note.key,
+                crate::util::truncate_with_ellipsis(&note.content, 400)
+            );
+        }
+    }
+    prompt
+}
+
+async fn send_brief(config: &Config, feed: &CalendarFeed, message: &str) {
+    let alert =
+        Alert::new("briefs", message).via(feed.channel.as_deref(), feed.recipient.as_deref());
+    crate::notifications::notify(config, alert).await;
+}
+
+/// Daemon component: watch the calendars that have `prep_briefs` on and send
+/// a brief `lead_minutes` before each meeting.
+pub async fn run(config: Config) -> Result<()> {
+    let feeds: Vec<CalendarFeed> = config
+        .integrations
+        .calendar
+        .iter()
+        .flat_map(|c| c.calendars.iter())
+        .filter(|feed| feed.prep_briefs)
+        .filter(|feed| {
+            let routed = feed.channel.is_some() && feed.recipient.is_some();
+            if !routed {
+                tracing::warn!(
+                    "Calendar '{}' has prep_briefs on but no channel/recipient; skipping",
+                    feed.name
+                );
+            }
+            routed
+        })
+        .cloned()
+        .collect();
+    if feeds.is_empty() {
+        anyhow::bail!("No calendar with prep_briefs has a channel and recipient");
+    }
+
+    let provider: Box<dyn Provider> = providers::create_resilient_provider(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        &config.reliability,
+        &config.providers.fallback,
+    )?;
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let mem = memory::create_memory(
+        &config.memory,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let client = calendar::http_client();
+    let mut log = SentLog::load(&config.workspace_dir);
+    let mut cache: Vec<(Vec<CalendarEvent>, Option<DateTime<Utc>>)> =
+        vec![(Vec::new(), None); feeds.len()];
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(TICK_SECS));
+
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        for (feed, (events, fetched_at)) in feeds.iter().zip(cache.iter_mut()) {
+            if fetched_at.is_none_or(|at| now - at >= Duration::minutes(REFRESH_MINS)) {
+                let horizon =
+                    now + Duration::minutes(i64::from(feed.lead_minutes) + REFRESH_MINS * 2);
+                match calendar::fetch(&client, feed).await {
+                    Ok(parsed) => {
+                        *events = calendar::occurrences(&parsed, now, horizon);
+                        *fetched_at = Some(now);
+                    }
+                    Err(e) => tracing::warn!("Calendar '{}' fetch failed: {e}", feed.name),
+                }
+            }
+
+            for event in due(events, feed, now, &log) {
+                let notes = related_notes(mem.as_ref(), event).await;
+                let prompt = build_brief_prompt(event, &notes);
+                let brief = match provider
+                    .chat_with_system(
+                        Some(BRIEF_SYSTEM_PROMPT),
+                        &prompt,
+                        &model,
+                        config.default_temperature,
+                    )
+                    .await
+                {
+                    Ok(brief) => brief,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Meeting brief LLM call failed: {}",
+                            providers::sanitize_api_error(&e.to_string())
+                        );
+                        // Still send what we gathered rather than nothing.
+                        prompt.clone()
+                    }
+                };
+                let minutes = (event.start - now).num_minutes().max(1);
+                let message = format!("📅 {} in {minutes} min\n\n{brief}", event.summary);
+                send_brief(&config, feed, &message).await;
+                log.record(event)?;
+            }
+        }
+        crate::health::mark_component_ok("meeting_briefs");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn feed(lead_minutes: u32) -> CalendarFeed {
+        CalendarFeed {
+            name: "work".into(),
+            url: "https://cal.test/work.ics".into(),
+            prep_briefs: true,
+            lead_minutes,
+            channel: Some("telegram".into()),
+            recipient: Some("42".into()),
+        }
+    }
+
+    fn events(now: DateTime<Utc>) -> Vec<CalendarEvent> {
+        let stamp = |mins: i64| (now + Duration::minutes(mins)).format("%Y%m%dT%H%M%SZ");
+        let ics = format!(
+            "BEGIN:VEVENT\nUID:soon\nDTSTART:{}\nSUMMARY:1:1 with Jane\nATTENDEE;CN=Jane Doe:mailto:jane@example.com\nEND:VEVENT\n\
+             BEGIN:VEVENT\nUID:later\nDTSTART:{}\nSUMMARY:Planning\nEND:VEVENT\n\
+             BEGIN:VEVENT\nUID:past\nDTSTART:{}\nSUMMARY:Earlier\nEND:VEVENT\n",
+            stamp(10),
+            stamp(90),
+            stamp(-5)
+        );
+        calendar::occurrences(
+            &calendar::parse_ics(&ics, "work"),
+            now - Duration::hours(1),
+            now + Duration::hours(3),
+        )
+    }
+
+    #[test]
+    fn briefs_are_due_inside_the_lead_window_once() {
+        let tmp = TempDir::new().unwrap();
+        let now = Utc::now();
+        let events = events(now);
+        let mut log = SentLog::load(tmp.path());
+
+        let due_now: Vec<&str> = due(&events, &feed(15), now, &log)
+            .iter()
+            .map(|e| e.uid.as_str())
+            .collect();
+        assert_eq!(due_now, ["soon"]);
+        assert!(due(&events, &feed(5), now, &log).is_empty());
+
+        log.record(&events[1]).unwrap();
+        assert!(due(&events, &feed(15), now, &SentLog::load(tmp.path())).is_empty());
+    }
+
+    #[test]
+    fn prompt_lists_attendees_and_notes() {
+        let now = Utc::now();
+        let event = events(now).remove(1);
+        let notes = vec![MemoryEntry {
+            id: "1".into(),
+            key: "email_jane".into(),
+            content: "Jane asked for the Q4 hiring plan before our next 1:1".into(),
+            category: crate::memory::MemoryCategory::Conversation,
+            timestamp: now.to_rfc3339(),
+            session_id: None,
+            score: None,
+        }];
+        let prompt = build_brief_prompt(&event, &notes);
+        assert!(prompt.contains("1:1 with Jane — with Jane Doe <jane@example.com>"));
+        assert!(prompt.contains("- [email_jane] Jane asked for the Q4 hiring plan"));
+        assert!(build_brief_prompt(&event, &[]).contains("No related notes"));
+    }
+}
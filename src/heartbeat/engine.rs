@@ -96,7 +96,10 @@ impl HeartbeatEngine {
                            # Examples:\n\
                            # - Check my email for important messages\n\
                            # - Review my calendar for upcoming events\n\
-                           # - Check the weather forecast\n";
+                           # - Check the weather forecast\n\
+                           #\n\
+                           # Tasks that report to a channel, and only when something\n\
+                           # changed, go in heartbeat.toml instead.\n";
             tokio::fs::write(&path, default).await?;
         }
         Ok(())
@@ -1 +1,3 @@
+pub mod briefs;
 pub mod engine;
+pub mod tasks;
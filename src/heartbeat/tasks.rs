@@ -0,0 +1,390 @@
+//! Heartbeat tasks from `<workspace>/heartbeat.toml`.
+//!
+//! ```toml
+//! [[task]]
+//! name = "calendar"
+//! prompt = "Check my calendar for the next four hours and list anything I need to prepare."
+//! channel = "telegram"
+//! to = "123456789"
+//!
+//! [[task]]
+//! name = "gateway"
+//! command = "curl -fsS http://127.0.0.1:3000/health"
+//! channel = "telegram"
+//! to = "@alice"
+//! ```
+//!
+//! Every heartbeat tick runs each task: a `prompt` as a one-shot agent turn
+//! in the task's own session, a `command` through the shell under the
+//! autonomy policy. The result goes out as a notification to the task's
+//! `channel` and `to`, or where `[notifications]` sends alerts by default.
+//! With `skip_unchanged` (the default) a result is
+//! only sent when it differs from the last one sent, and agent tasks see
+//! their last report and may answer [`NOTHING_NEW`] instead.
+
+use crate::config::Config;
+use crate::notifications::Alert;
+use crate::security::SecurityPolicy;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+/// What an agent task answers when there is nothing worth reporting.
+pub const NOTHING_NEW: &str = "NOTHING_NEW";
+
+const TASK_TIMEOUT: Duration = Duration::from_mins(10);
+/// The last report is kept, and shown to the agent, up to this length.
+const MAX_REPORT_CHARS: usize = 2000;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaskFile {
+    #[serde(default)]
+    task: Vec<HeartbeatTask>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HeartbeatTask {
+    pub name: String,
+    /// Instruction for a one-shot agent turn
+    pub prompt: Option<String>,
+    /// Shell command, checked against the autonomy policy
+    pub command: Option<String>,
+    /// Channel the result is sent on
+    pub channel: Option<String>,
+    /// Recipient on the channel, or `@name` for a household member
+    pub to: Option<String>,
+    /// Only send a result that differs from the last one sent
+    #[serde(default = "default_skip_unchanged")]
+    pub skip_unchanged: bool,
+}
+
+fn default_skip_unchanged() -> bool {
+    true
+}
+
+enum Action<'a> {
+    Prompt(&'a str),
+    Command(&'a str),
+}
+
+impl HeartbeatTask {
+    fn action(&self) -> Result<Action<'_>> {
+        match (&self.prompt, &self.command) {
+            (Some(prompt), None) => Ok(Action::Prompt(prompt)),
+            (None, Some(command)) => Ok(Action::Command(command)),
+            _ => anyhow::bail!(
+                "Heartbeat task '{}' needs exactly one of `prompt` or `command`",
+                self.name
+            ),
+        }
+    }
+
+    /// Session of the task's agent turns, kept apart from the CLI history.
+    fn session(&self) -> String {
+        let slug: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        format!("heartbeat-{slug}")
+    }
+}
+
+/// Tasks in `<workspace>/heartbeat.toml`; none when the file is missing.
+pub fn load(workspace_dir: &Path) -> Result<Vec<HeartbeatTask>> {
+    let path = workspace_dir.join("heartbeat.toml");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&raw).with_context(|| format!("Invalid {}", path.display()))
+}
+
+fn parse(raw: &str) -> Result<Vec<HeartbeatTask>> {
+    let file: TaskFile = toml::from_str(raw)?;
+    let mut names = BTreeSet::new();
+    for task in &file.task {
+        task.action()?;
+        if task.name.trim().is_empty() {
+            anyhow::bail!("Every heartbeat task needs a name");
+        }
+        if !names.insert(task.name.as_str()) {
+            anyhow::bail!("Two heartbeat tasks are named '{}'", task.name);
+        }
+        if task.channel.is_some() != task.to.is_some() {
+            anyhow::bail!(
+                "Heartbeat task '{}' needs both `channel` and `to`, or neither",
+                task.name
+            );
+        }
+    }
+    Ok(file.task)
+}
+
+/// The last report sent for a task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LastReport {
+    digest: String,
+    at: DateTime<Utc>,
+    report: String,
+}
+
+/// Last reports by task name, in `<workspace>/heartbeat/tasks.json`.
+struct ReportLog {
+    path: PathBuf,
+    reports: BTreeMap<String, LastReport>,
+}
+
+impl ReportLog {
+    fn load(workspace_dir: &Path) -> Self {
+        let path = workspace_dir.join("heartbeat").join("tasks.json");
+        let reports = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self { path, reports }
+    }
+
+    fn record(&mut self, task: &str, report: &str, now: DateTime<Utc>) -> Result<()> {
+        self.reports.insert(
+            task.to_string(),
+            LastReport {
+                digest: digest(report),
+                at: now,
+                report: crate::util::truncate_with_ellipsis(report, MAX_REPORT_CHARS),
+            },
+        );
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.reports)?)?;
+        Ok(())
+    }
+}
+
+fn digest(report: &str) -> String {
+    hex::encode(Sha256::digest(report.trim().as_bytes()))
+}
+
+/// The agent turn for a prompt task, with its last report when only news
+/// should be sent.
+fn agent_prompt(task: &HeartbeatTask, prompt: &str, last: Option<&LastReport>) -> String {
+    let mut message = format!("[Heartbeat Task] {prompt}");
+    if task.skip_unchanged {
+        if let Some(last) = last {
+            let _ = write!(
+                message,
+                "\n\nYour last report on this, at {}:\n{}",
+                last.at.to_rfc3339(),
+                last.report
+            );
+        }
+        let _ = write!(
+            message,
+            "\n\nIf nothing has changed that the user should hear about, reply with exactly \
+             {NOTHING_NEW}."
+        );
+    }
+    message
+}
+
+/// The report to send for `output`, or `None` when there is nothing new.
+fn report(task: &HeartbeatTask, output: &str, last: Option<&LastReport>) -> Option<String> {
+    let output = output.trim();
+    if output.is_empty() {
+        return None;
+    }
+    // The agent's own status lines may come first
+    let nothing_new = output
+        .lines()
+        .last()
+        .is_some_and(|line| line.trim().trim_end_matches('.') == NOTHING_NEW);
+    if task.skip_unchanged
+        && (nothing_new || last.is_some_and(|last| last.digest == digest(output)))
+    {
+        return None;
+    }
+    Some(output.to_string())
+}
+
+/// One-shot `zeroclaw agent` run in the task's session, the way goal checks
+/// and cron agent jobs run.
+async fn run_agent(config: &Config, task: &HeartbeatTask, message: &str) -> Result<String> {
+    let exe = std::env::current_exe().context("Cannot locate the zeroclaw binary")?;
+    let mut command = Command::new(exe);
+    command
+        .args(["agent", "--message", message, "--session"])
+        .arg(task.session())
+        .current_dir(&config.workspace_dir)
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(TASK_TIMEOUT, command.output())
+        .await
+        .context("Heartbeat task timed out")??;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Agent exited with {}: {}", output.status, stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Output of a shell task. A failing command is a result worth reporting
+/// (the gateway is down), not an error.
+async fn run_command(config: &Config, command: &str) -> Result<String> {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    if !security.is_command_allowed(command) {
+        anyhow::bail!("Command not allowed by security policy: {command}");
+    }
+    if let Some(path) = crate::cron::scheduler::forbidden_path_argument(&security, command) {
+        anyhow::bail!("Forbidden path argument: {path}");
+    }
+    let output = tokio::time::timeout(
+        TASK_TIMEOUT,
+        crate::platform::shell_command(command, true)
+            .current_dir(&config.workspace_dir)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .context("Heartbeat task timed out")??;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        return Ok(stdout.into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(format!(
+        "Failed ({}): {}",
+        output.status,
+        [stdout.trim(), stderr.trim()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))
+}
+
+async fn deliver(config: &Config, task: &HeartbeatTask, report: &str) {
+    let message = format!("💓 {}\n\n{report}", task.name);
+    let alert = Alert::new("heartbeat", &message).via(task.channel.as_deref(), task.to.as_deref());
+    crate::notifications::notify(config, alert).await;
+}
+
+/// Run every task once and send what is new. Returns how many sent a report.
+pub async fn run(config: &Config, tasks: &[HeartbeatTask]) -> Result<usize> {
+    let mut log = ReportLog::load(&config.workspace_dir);
+    let mut sent = 0;
+    let mut failed = Vec::new();
+    for task in tasks {
+        let last = log.reports.get(&task.name);
+        let output = match task.action()? {
+            Action::Prompt(prompt) => {
+                run_agent(config, task, &agent_prompt(task, prompt, last)).await
+            }
+            Action::Command(command) => run_command(config, command).await,
+        };
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                tracing::warn!("Heartbeat task '{}' failed: {e:#}", task.name);
+                failed.push(task.name.as_str());
+                continue;
+            }
+        };
+        let Some(report) = report(task, &output, last) else {
+            tracing::debug!("Heartbeat task '{}': nothing new", task.name);
+            continue;
+        };
+        deliver(config, task, &report).await;
+        log.record(&task.name, &report, Utc::now())?;
+        sent += 1;
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("Heartbeat task(s) failed: {}", failed.join(", "));
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        [[task]]
+        name = "Calendar check"
+        prompt = "Check my calendar"
+        channel = "telegram"
+        to = "42"
+
+        [[task]]
+        name = "gateway"
+        command = "curl -fsS http://127.0.0.1:3000/health"
+        skip_unchanged = false
+    "#;
+
+    #[test]
+    fn tasks_parse_and_are_checked() {
+        let tasks = parse(EXAMPLE).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks[0].skip_unchanged);
+        assert_eq!(tasks[0].session(), "heartbeat-calendar-check");
+        assert!(matches!(
+            tasks[1].action().unwrap(),
+            Action::Command(c) if c.starts_with("curl")
+        ));
+
+        let both = "[[task]]\nname = \"x\"\nprompt = \"a\"\ncommand = \"b\"";
+        assert!(parse(both).unwrap_err().to_string().contains("exactly one"));
+        let twice =
+            "[[task]]\nname = \"x\"\nprompt = \"a\"\n[[task]]\nname = \"x\"\nprompt = \"b\"";
+        assert!(parse(twice).unwrap_err().to_string().contains("Two"));
+        let half = "[[task]]\nname = \"x\"\nprompt = \"a\"\nchannel = \"telegram\"";
+        assert!(parse(half).is_err());
+        assert!(load(Path::new("/nonexistent")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn only_new_results_are_reported() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = parse(EXAMPLE).unwrap();
+        let (calendar, gateway) = (&tasks[0], &tasks[1]);
+        let mut log = ReportLog::load(tmp.path());
+
+        let first = agent_prompt(calendar, "Check my calendar", None);
+        assert!(first.ends_with(&format!("reply with exactly {NOTHING_NEW}.")));
+        assert_eq!(
+            report(calendar, "Dentist at 3pm\n", None).as_deref(),
+            Some("Dentist at 3pm")
+        );
+        log.record(&calendar.name, "Dentist at 3pm", Utc::now())
+            .unwrap();
+
+        let log = ReportLog::load(tmp.path());
+        let last = log.reports.get(&calendar.name);
+        assert!(agent_prompt(calendar, "Check my calendar", last).contains("Dentist at 3pm"));
+        assert_eq!(report(calendar, " Dentist at 3pm ", last), None);
+        assert_eq!(
+            report(calendar, "🔌 MCP: 2 tool(s) connected\nNOTHING_NEW.", last),
+            None
+        );
+        assert!(report(calendar, "Dentist moved to 4pm", last).is_some());
+
+        assert_eq!(agent_prompt(gateway, "ping", None), "[Heartbeat Task] ping");
+        assert!(report(gateway, "ok", Some(&log.reports[&calendar.name])).is_some());
+        assert_eq!(report(gateway, "  ", None), None);
+    }
+}
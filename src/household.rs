@@ -0,0 +1,326 @@
+//! Several known people sharing one agent.
+//!
+//! `[household.members]` maps each person to the sender handles they write
+//! from. A message from a known handle gets that person's profile pinned to
+//! the context, and its memories are tagged `person:<name>:…` so one
+//! member's reminders and notes are not recalled for another (see
+//! [`MemberRole`] for who sees what).
+
+use crate::channels::traits::SenderProfile;
+use crate::config::{Config, HouseholdConfig, HouseholdMember, MemberRole};
+use crate::memory::{Memory, MemoryCategory, MemoryEntry};
+use async_trait::async_trait;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const KEY_PREFIX: &str = "person:";
+
+/// A household member identified from a message.
+#[derive(Debug, Clone, Copy)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub member: &'a HouseholdMember,
+}
+
+impl Person<'_> {
+    pub fn display_name(&self) -> &str {
+        self.member.display_name.as_deref().unwrap_or(self.name)
+    }
+
+    /// The profile line pinned to the agent context.
+    pub fn context_note(&self) -> String {
+        let role = match self.member.role {
+            MemberRole::Owner => "owner",
+            MemberRole::Member => "household member",
+            MemberRole::Guest => "guest",
+        };
+        let mut note = format!(
+            "Speaking with: {} ({role}). Address them by name and keep their reminders to themselves.",
+            self.display_name()
+        );
+        if let Some(about) = self.member.about.as_deref().map(str::trim) {
+            if !about.is_empty() {
+                let _ = write!(note, "\nAbout {}: {about}", self.display_name());
+            }
+        }
+        note
+    }
+
+    /// What their config says about name, language and timezone; wins over
+    /// what the platform reports (see [`SenderProfile::or`]).
+    pub fn profile(&self) -> SenderProfile {
+        SenderProfile::new(
+            self.member.display_name.as_deref(),
+            self.member.language.as_deref(),
+            self.member.timezone.as_deref(),
+        )
+    }
+
+    /// Their handle on `channel`, if they have one.
+    fn handle_on(&self, channel: &str) -> Option<&str> {
+        self.member.handles.iter().find_map(|handle| {
+            let (ch, sender) = handle.split_once(':')?;
+            ch.eq_ignore_ascii_case(channel).then_some(sender)
+        })
+    }
+}
+
+/// The member writing from `sender` on `channel`.
+pub fn identify<'a>(
+    household: &'a HouseholdConfig,
+    channel: &str,
+    sender: &str,
+) -> Option<Person<'a>> {
+    household
+        .members
+        .iter()
+        .map(|(name, member)| Person { name, member })
+        .find(|person| {
+            person
+                .handle_on(channel)
+                .is_some_and(|h| h.eq_ignore_ascii_case(sender))
+        })
+}
+
+/// Resolve a `@name` recipient to the member's preferred channel and handle.
+/// `channel` is kept when they have a handle there and list no preference;
+/// any other recipient passes through unchanged.
+pub fn route(config: &Config, channel: &str, recipient: &str) -> (String, String) {
+    let unchanged = (channel.to_string(), recipient.to_string());
+    let Some(name) = recipient.strip_prefix('@') else {
+        return unchanged;
+    };
+    let Some(member) = config.household.members.get(name) else {
+        return unchanged;
+    };
+    let person = Person { name, member };
+    member
+        .channels
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(channel))
+        .find_map(|ch| {
+            person
+                .handle_on(ch)
+                .map(|h| (ch.to_string(), h.to_string()))
+        })
+        .unwrap_or(unchanged)
+}
+
+/// The member a memory key belongs to; `None` for shared memories.
+fn owner_of(key: &str) -> Option<&str> {
+    key.strip_prefix(KEY_PREFIX)?
+        .split_once(':')
+        .map(|(name, _)| name)
+}
+
+/// A member's view of the shared memory: what they store is tagged with
+/// their name, and recall only returns what their role lets them see.
+pub struct ScopedMemory {
+    inner: Arc<dyn Memory>,
+    name: String,
+    role: MemberRole,
+}
+
+impl ScopedMemory {
+    pub fn new(inner: Arc<dyn Memory>, person: Person<'_>) -> Self {
+        Self {
+            inner,
+            name: person.name.to_string(),
+            role: person.member.role,
+        }
+    }
+
+    fn visible(&self, key: &str) -> bool {
+        let owner = owner_of(key);
+        match self.role {
+            MemberRole::Owner => true,
+            MemberRole::Member => owner.is_none_or(|o| o == self.name),
+            MemberRole::Guest => owner == Some(self.name.as_str()),
+        }
+    }
+
+    fn scoped_key(&self, key: &str) -> String {
+        if owner_of(key) == Some(self.name.as_str()) {
+            key.to_string()
+        } else {
+            format!("{KEY_PREFIX}{}:{key}", self.name)
+        }
+    }
+}
+
+#[async_trait]
+impl Memory for ScopedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .store(&self.scoped_key(key), content, category)
+            .await
+    }
+
+    async fn recall(&self, query: &str, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        // Over-fetch so hidden entries do not starve the result.
+        let mut entries = self.inner.recall(query, limit.saturating_mul(3)).await?;
+        entries.retain(|e| self.visible(&e.key));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        if let Some(entry) = self.inner.get(&self.scoped_key(key)).await? {
+            return Ok(Some(entry));
+        }
+        if !self.visible(key) {
+            return Ok(None);
+        }
+        self.inner.get(key).await
+    }
+
+    async fn list(&self, category: Option<&MemoryCategory>) -> anyhow::Result<Vec<MemoryEntry>> {
+        let mut entries = self.inner.list(category).await?;
+        entries.retain(|e| self.visible(&e.key));
+        Ok(entries)
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        if self.inner.forget(&self.scoped_key(key)).await? {
+            return Ok(true);
+        }
+        if self.visible(key) {
+            self.inner.forget(key).await
+        } else {
+            Ok(false)
+        }
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        Ok(self.list(None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn household() -> Config {
+        let mut config = Config::default();
+        config.household.members.insert(
+            "sam".into(),
+            HouseholdMember {
+                display_name: Some("Sam".into()),
+                handles: vec!["telegram:111".into(), "email:sam@example.com".into()],
+                about: Some("Prefers short answers.".into()),
+                role: MemberRole::Owner,
+                channels: vec!["telegram".into()],
+                language: Some("de".into()),
+                timezone: None,
+            },
+        );
+        config.household.members.insert(
+            "alex".into(),
+            HouseholdMember {
+                handles: vec!["telegram:222".into(), "imessage:+15550100".into()],
+                channels: vec!["imessage".into(), "telegram".into()],
+                ..HouseholdMember::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn senders_map_to_members_and_names_route_to_preferred_channels() {
+        let config = household();
+        let sam = identify(&config.household, "email", "Sam@Example.com").unwrap();
+        assert_eq!(sam.name, "sam");
+        assert!(sam
+            .context_note()
+            .starts_with("Speaking with: Sam (owner)."));
+        assert!(sam
+            .context_note()
+            .ends_with("About Sam: Prefers short answers."));
+        assert!(identify(&config.household, "discord", "111").is_none());
+        let profile = sam.profile().or(SenderProfile::new(
+            Some("sammy"),
+            Some("en"),
+            Some("Europe/Berlin"),
+        ));
+        assert_eq!(profile.display_name.as_deref(), Some("Sam"));
+        assert_eq!(profile.language.as_deref(), Some("de"));
+        assert_eq!(profile.timezone.as_deref(), Some("Europe/Berlin"));
+
+        assert_eq!(
+            route(&config, "telegram", "@alex"),
+            ("imessage".into(), "+15550100".into())
+        );
+        assert_eq!(
+            route(&config, "email", "@sam"),
+            ("telegram".into(), "111".into())
+        );
+        assert_eq!(
+            route(&config, "telegram", "@nobody"),
+            ("telegram".into(), "@nobody".into())
+        );
+        assert_eq!(
+            route(&config, "telegram", "333"),
+            ("telegram".into(), "333".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn members_only_recall_their_own_and_shared_memories() {
+        let tmp = TempDir::new().unwrap();
+        let inner: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let config = household();
+        let members = &config.household;
+        let sam = ScopedMemory::new(inner.clone(), identify(members, "telegram", "111").unwrap());
+        let alex = ScopedMemory::new(inner.clone(), identify(members, "telegram", "222").unwrap());
+
+        inner
+            .store(
+                "wifi",
+                "reminder: wifi password is on the fridge",
+                MemoryCategory::Core,
+            )
+            .await
+            .unwrap();
+        sam.store(
+            "dentist",
+            "reminder: dentist on Friday",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        alex.store("gym", "reminder: gym at 7", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let keys = |entries: Vec<MemoryEntry>| {
+            let mut keys: Vec<String> = entries.into_iter().map(|e| e.key).collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(
+            keys(alex.recall("reminder", 10).await.unwrap()),
+            ["person:alex:gym", "wifi"]
+        );
+        assert_eq!(keys(sam.recall("reminder", 10).await.unwrap()).len(), 3);
+        assert!(alex.get("person:sam:dentist").await.unwrap().is_none());
+        assert!(alex.get("gym").await.unwrap().is_some());
+        assert!(!alex.forget("person:sam:dentist").await.unwrap());
+        assert_eq!(alex.count().await.unwrap(), 2);
+    }
+}
@@ -0,0 +1,219 @@
+//! Deduplication for redelivered events.
+//!
+//! Chat platforms and webhook senders retry when an acknowledgement is slow
+//! or lost, so the same message can arrive twice. Channels key messages by
+//! the platform message id and the gateway by the `Idempotency-Key` header;
+//! a key seen within [`DEFAULT_TTL`] is not processed again. The cache lives
+//! in the process, so it covers retries, not replays after a restart.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a key is remembered.
+pub const DEFAULT_TTL: Duration = Duration::from_hours(24);
+/// Keys kept before the oldest are evicted.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// What to do with an event whose key was just claimed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim<V> {
+    /// First delivery: process it, then call `complete` or `release` (or
+    /// [`IdempotencyCache::hold`] the key so an abandoned request releases it).
+    New,
+    /// An earlier delivery is still being processed.
+    InFlight,
+    /// Already processed; this is the stored result.
+    Done(V),
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    at: Instant,
+    /// Insertion order, for evicting the oldest key.
+    seq: u64,
+    result: Option<V>,
+}
+
+#[derive(Debug)]
+struct Entries<V> {
+    map: HashMap<String, Entry<V>>,
+    next_seq: u64,
+}
+
+#[derive(Debug)]
+pub struct IdempotencyCache<V = ()> {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<Entries<V>>,
+}
+
+impl<V: Clone> Default for IdempotencyCache<V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL, DEFAULT_CAPACITY)
+    }
+}
+
+impl<V: Clone> IdempotencyCache<V> {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            entries: Mutex::new(Entries {
+                map: HashMap::new(),
+                next_seq: 0,
+            }),
+        }
+    }
+
+    /// Claim `key`, or report how an earlier delivery of it went.
+    pub fn claim(&self, key: &str) -> Claim<V> {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let now = Instant::now();
+        if let Some(entry) = entries.map.get(key) {
+            if now.duration_since(entry.at) < self.ttl {
+                return entry.result.clone().map_or(Claim::InFlight, Claim::Done);
+            }
+        }
+        if entries.map.len() >= self.capacity {
+            entries
+                .map
+                .retain(|_, e| now.duration_since(e.at) < self.ttl);
+        }
+        if entries.map.len() >= self.capacity {
+            if let Some(oldest) = entries
+                .map
+                .iter()
+                .min_by_key(|(_, e)| e.seq)
+                .map(|(k, _)| k.clone())
+            {
+                entries.map.remove(&oldest);
+            }
+        }
+        let seq = entries.next_seq;
+        entries.next_seq += 1;
+        entries.map.insert(
+            key.to_string(),
+            Entry {
+                at: now,
+                seq,
+                result: None,
+            },
+        );
+        Claim::New
+    }
+
+    /// Store the result for a claimed key; later deliveries get it back.
+    pub fn complete(&self, key: &str, result: V) {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(entry) = entries.map.get_mut(key) {
+            entry.result = Some(result);
+        }
+    }
+
+    /// Forget a claimed key after a failure so a retry is processed.
+    pub fn release(&self, key: &str) {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .map
+            .remove(key);
+    }
+
+    /// Hold a key just claimed as [`Claim::New`] until the work is done. A
+    /// request that is dropped midway (a timeout, a client that hung up)
+    /// releases it, instead of leaving it in flight for the whole TTL.
+    pub fn hold(&self, key: &str) -> Held<'_, V> {
+        Held {
+            cache: self,
+            key: Some(key.to_string()),
+        }
+    }
+}
+
+/// A claimed key; released on drop unless completed.
+#[derive(Debug)]
+pub struct Held<'a, V: Clone> {
+    cache: &'a IdempotencyCache<V>,
+    key: Option<String>,
+}
+
+impl<V: Clone> Held<'_, V> {
+    /// Store the result; later deliveries get it back.
+    pub fn complete(mut self, result: V) {
+        if let Some(key) = self.key.take() {
+            self.cache.complete(&key, result);
+        }
+    }
+}
+
+impl<V: Clone> Drop for Held<'_, V> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.cache.release(&key);
+        }
+    }
+}
+
+impl IdempotencyCache<()> {
+    /// True the first time `key` is seen; the key is marked done at once.
+    pub fn first_seen(&self, key: &str) -> bool {
+        if self.claim(key) == Claim::New {
+            self.complete(key, ());
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redelivered_keys_replay_the_stored_result() {
+        let cache: IdempotencyCache<String> = IdempotencyCache::default();
+        assert_eq!(cache.claim("k1"), Claim::New);
+        assert_eq!(cache.claim("k1"), Claim::InFlight);
+        cache.complete("k1", "reply".into());
+        assert_eq!(cache.claim("k1"), Claim::Done("reply".into()));
+
+        assert_eq!(cache.claim("k2"), Claim::New);
+        cache.release("k2");
+        assert_eq!(cache.claim("k2"), Claim::New);
+    }
+
+    #[test]
+    fn abandoned_claims_are_released() {
+        let cache: IdempotencyCache<String> = IdempotencyCache::default();
+        assert_eq!(cache.claim("k1"), Claim::New);
+        let held = cache.hold("k1");
+        assert_eq!(cache.claim("k1"), Claim::InFlight);
+        drop(held);
+        assert_eq!(cache.claim("k1"), Claim::New);
+
+        cache.hold("k1").complete("reply".into());
+        assert_eq!(cache.claim("k1"), Claim::Done("reply".into()));
+    }
+
+    #[test]
+    fn keys_expire_and_capacity_is_bounded() {
+        let seen = IdempotencyCache::new(Duration::from_hours(1), 2);
+        assert!(seen.first_seen("telegram:1"));
+        assert!(!seen.first_seen("telegram:1"));
+        assert!(seen.first_seen("telegram:2"));
+        assert!(seen.first_seen("telegram:3"));
+        assert!(seen.first_seen("telegram:1"));
+
+        let short = IdempotencyCache::new(Duration::ZERO, 10);
+        assert!(short.first_seen("a"));
+        assert!(short.first_seen("a"));
+    }
+}
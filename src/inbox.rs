@@ -0,0 +1,640 @@
+//! Inbox-zero triage over the email channel's mailbox.
+//!
+//! Each pass fetches mail that arrived since the last one, has the model sort
+//! every message into one of the configured labels (urgent, action,
+//! newsletter, spam by default) and applies that label's rules: flag it, mark
+//! it read, move it to a folder, and for labels with `draft_reply` queue a
+//! reply in `workspace/inbox/inbox.db`. Drafts are only sent once approved
+//! with `zeroclaw inbox approve <id>`.
+
+use crate::channels::email_channel::{EmailChannel, EmailConfig, ImapSession};
+use crate::config::{Config, InboxLabel};
+use crate::notifications::Alert;
+use crate::providers::{self, Provider};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use mail_parser::MessageParser;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Messages classified per pass; the rest wait for the next one.
+const MAX_PER_PASS: usize = 25;
+const MAX_BODY_CHARS: usize = 3000;
+
+const DEFAULT_PERSONA: &str = "You are the owner's email assistant. You triage their inbox: \
+decide which label each new email belongs to and, when asked, draft a short, polite reply in the \
+owner's voice. Never invent facts, commitments or dates; leave a [placeholder] where the owner \
+must fill something in.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    pub id: i64,
+    pub message_id: Option<String>,
+    pub sender: String,
+    pub subject: String,
+    pub label: String,
+    pub reply: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct InboxStore {
+    conn: Mutex<Connection>,
+    db_path: PathBuf,
+}
+
+impl InboxStore {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("inbox").join("inbox.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create inbox directory: {}", parent.display())
+            })?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open inbox DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS drafts (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id TEXT,
+                sender     TEXT NOT NULL,
+                subject    TEXT NOT NULL,
+                label      TEXT NOT NULL,
+                reply      TEXT NOT NULL,
+                created_at TEXT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS cursor (
+                id           INTEGER PRIMARY KEY CHECK (id = 1),
+                uid_validity INTEGER NOT NULL,
+                last_uid     INTEGER NOT NULL
+             );",
+        )
+        .context("Failed to initialize inbox schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            db_path,
+        })
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// `(UIDVALIDITY, last triaged UID)` from the previous pass.
+    fn cursor(&self) -> Result<Option<(u32, u32)>> {
+        Ok(self
+            .conn()
+            .query_row(
+                "SELECT uid_validity, last_uid FROM cursor WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?)
+    }
+
+    fn set_cursor(&self, uid_validity: u32, last_uid: u32) -> Result<()> {
+        self.conn()
+            .execute(
+                "INSERT INTO cursor (id, uid_validity, last_uid) VALUES (1, ?1, ?2)
+                 ON CONFLICT(id) DO UPDATE SET uid_validity = ?1, last_uid = ?2",
+                params![uid_validity, last_uid],
+            )
+            .context("Failed to save inbox cursor")?;
+        Ok(())
+    }
+
+    fn add_draft(&self, email: &Incoming, label: &str, reply: &str) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO drafts (message_id, sender, subject, label, reply, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                email.message_id,
+                email.sender,
+                email.subject,
+                label,
+                reply.trim(),
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .context("Failed to save reply draft")?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Pending drafts, oldest first.
+    pub fn drafts(&self) -> Result<Vec<Draft>> {
+        self.query("ORDER BY id ASC", [])
+    }
+
+    pub fn get(&self, id: i64) -> Result<Draft> {
+        self.query("WHERE id = ?1", [id])?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Draft {id} not found"))
+    }
+
+    pub fn remove(&self, id: i64) -> Result<()> {
+        let removed = self
+            .conn()
+            .execute("DELETE FROM drafts WHERE id = ?1", [id])
+            .context("Failed to delete draft")?;
+        if removed == 0 {
+            anyhow::bail!("Draft {id} not found");
+        }
+        Ok(())
+    }
+
+    fn query(&self, clause: &str, args: impl rusqlite::Params) -> Result<Vec<Draft>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, message_id, sender, subject, label, reply, created_at FROM drafts {clause}"
+        ))?;
+        let rows = stmt.query_map(args, |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?;
+
+        let mut drafts = Vec::new();
+        for row in rows {
+            let (id, message_id, sender, subject, label, reply, created) = row?;
+            let created_at = DateTime::parse_from_rfc3339(&created)
+                .with_context(|| format!("Invalid RFC3339 timestamp in inbox DB: {created}"))?
+                .with_timezone(&Utc);
+            drafts.push(Draft {
+                id,
+                message_id,
+                sender,
+                subject,
+                label,
+                reply,
+                created_at,
+            });
+        }
+        Ok(drafts)
+    }
+}
+
+/// A message fetched for triage.
+#[derive(Debug, Clone)]
+struct Incoming {
+    uid: u32,
+    message_id: Option<String>,
+    sender: String,
+    subject: String,
+    body: String,
+}
+
+impl Incoming {
+    fn parse(uid: u32, raw: &[u8]) -> Option<Self> {
+        let parsed = MessageParser::default().parse(raw)?;
+        Some(Self {
+            uid,
+            message_id: parsed.message_id().map(str::to_string),
+            sender: EmailChannel::extract_sender(&parsed),
+            subject: parsed.subject().unwrap_or("(no subject)").to_string(),
+            body: EmailChannel::extract_text(&parsed),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Classification {
+    label: String,
+    #[serde(default)]
+    reason: String,
+    #[serde(default)]
+    reply: Option<String>,
+}
+
+fn classify_prompt(labels: &BTreeMap<String, InboxLabel>, email: &Incoming) -> String {
+    let mut prompt = String::from("Sort this email into exactly one label.\n\nLabels:\n");
+    for (name, label) in labels {
+        let drafts = if label.draft_reply {
+            " (draft a reply)"
+        } else {
+            ""
+        };
+        let _ = writeln!(prompt, "- {name}{drafts}: {}", label.rules.trim());
+    }
+    let _ = write!(
+        prompt,
+        "\nAnswer with JSON only: {{\"label\": \"<label or none>\", \"reason\": \"<a few words>\", \
+         \"reply\": \"<reply text, only for labels marked (draft a reply), otherwise null>\"}}\n\n\
+         From: {}\nSubject: {}\n\n{}",
+        email.sender,
+        email.subject,
+        crate::util::truncate_with_ellipsis(&email.body, MAX_BODY_CHARS)
+    );
+    prompt
+}
+
+/// The model's answer, if it names a configured label. Surrounding prose and
+/// code fences are ignored; anything else ("none", made-up labels) is `None`
+/// and the message is left in the inbox untouched.
+fn parse_classification(
+    raw: &str,
+    labels: &BTreeMap<String, InboxLabel>,
+) -> Option<Classification> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    let mut parsed: Classification = serde_json::from_str(raw.get(start..=end)?).ok()?;
+    parsed.label = parsed.label.trim().to_ascii_lowercase();
+    parsed.reply = parsed.reply.filter(|r| !r.trim().is_empty());
+    labels.contains_key(&parsed.label).then_some(parsed)
+}
+
+/// The number in a `* OK [UIDVALIDITY n]` line of a SELECT response.
+fn uid_validity(lines: &[String]) -> Option<u32> {
+    lines.iter().find_map(|line| {
+        let rest = &line[line.find("[UIDVALIDITY ")? + 13..];
+        rest[..rest.find(']')?].trim().parse().ok()
+    })
+}
+
+/// Connect and fetch up to [`MAX_PER_PASS`] unread messages newer than the
+/// cursor (all unread mail when there is no cursor or the mailbox was
+/// recreated). Nothing is marked read here.
+fn fetch_new(email: &EmailConfig, cursor: Option<(u32, u32)>) -> Result<(u32, Vec<Incoming>)> {
+    let mut imap = ImapSession::connect(email)?;
+    let select = imap.command_ok(&format!("SELECT \"{}\"", email.imap_folder))?;
+    let validity = uid_validity(&select).unwrap_or(0);
+    let last = cursor
+        .filter(|(v, _)| *v == validity)
+        .map_or(0, |(_, last)| last);
+
+    let search = if last == 0 {
+        imap.command_ok("UID SEARCH UNSEEN")?
+    } else {
+        imap.command_ok(&format!("UID SEARCH UNSEEN UID {}:*", last + 1))?
+    };
+    // `n:*` always matches the newest message, even when it is below n.
+    let mut uids: Vec<u32> = ImapSession::search_results(&search)
+        .iter()
+        .filter_map(|uid| uid.parse().ok())
+        .filter(|uid| *uid > last)
+        .collect();
+    uids.sort_unstable();
+    uids.truncate(MAX_PER_PASS);
+
+    let mut messages = Vec::new();
+    for uid in uids {
+        let fetched = imap.command_ok(&format!("UID FETCH {uid} BODY.PEEK[]"))?;
+        if let Some(message) = Incoming::parse(uid, ImapSession::literal(&fetched).as_bytes()) {
+            messages.push(message);
+        } else {
+            tracing::warn!("Inbox: could not parse message UID {uid}; skipping");
+        }
+    }
+    imap.logout();
+    Ok((validity, messages))
+}
+
+/// Apply each label's mailbox rules: flags first, then moves.
+fn apply_labels(email: &EmailConfig, actions: &[(u32, InboxLabel)]) -> Result<()> {
+    if actions.is_empty() {
+        return Ok(());
+    }
+    let mut imap = ImapSession::connect(email)?;
+    imap.command_ok(&format!("SELECT \"{}\"", email.imap_folder))?;
+    let mut moved = false;
+    for (uid, label) in actions {
+        if label.flag {
+            imap.command_ok(&format!("UID STORE {uid} +FLAGS (\\Flagged)"))?;
+        }
+        if label.mark_read {
+            imap.command_ok(&format!("UID STORE {uid} +FLAGS (\\Seen)"))?;
+        }
+        let Some(folder) = label.folder.as_deref() else {
+            continue;
+        };
+        let copy = format!("UID COPY {uid} \"{folder}\"");
+        let copied = imap.command(&copy)?;
+        if !ImapSession::is_ok(&copied) {
+            if !copied.iter().any(|line| line.contains("[TRYCREATE]")) {
+                anyhow::bail!("IMAP COPY to '{folder}' failed");
+            }
+            imap.command_ok(&format!("CREATE \"{folder}\""))?;
+            imap.command_ok(&copy)?;
+        }
+        imap.command_ok(&format!("UID STORE {uid} +FLAGS (\\Deleted)"))?;
+        moved = true;
+    }
+    if moved {
+        imap.command_ok("EXPUNGE")?;
+    }
+    imap.logout();
+    Ok(())
+}
+
+/// What one pass did.
+#[derive(Debug, Default)]
+pub struct TriageReport {
+    /// Messages per label, plus "unsorted" for those left alone
+    pub counts: BTreeMap<String, usize>,
+    pub drafted: usize,
+    /// Messages with a `notify` label: "label: sender — subject (reason)"
+    pub notable: Vec<String>,
+}
+
+impl TriageReport {
+    /// The message for `notify_channel`, or `None` when nothing needs a look.
+    pub fn summary(&self) -> Option<String> {
+        if self.drafted == 0 && self.notable.is_empty() {
+            return None;
+        }
+        let counts = self
+            .counts
+            .iter()
+            .map(|(label, n)| format!("{n} {label}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut out = format!("📥 Inbox triage: {counts}");
+        for line in &self.notable {
+            let _ = write!(out, "\n- {line}");
+        }
+        if self.drafted > 0 {
+            let _ = write!(
+                out,
+                "\n\n{} reply draft(s) waiting: zeroclaw inbox drafts",
+                self.drafted
+            );
+        }
+        Some(out)
+    }
+}
+
+fn email_config(config: &Config) -> Result<EmailConfig> {
+    config
+        .channels_config
+        .email
+        .clone()
+        .context("Inbox triage needs an email account in [channels_config.email]")
+}
+
+/// Fetch, classify and file new mail once.
+pub async fn triage_once(config: &Config, provider: &dyn Provider) -> Result<TriageReport> {
+    let email = email_config(config)?;
+    let store = InboxStore::open(&config.workspace_dir)?;
+    let cursor = store.cursor()?;
+    let fetch_cfg = email.clone();
+    let (validity, messages) =
+        tokio::task::spawn_blocking(move || fetch_new(&fetch_cfg, cursor)).await??;
+
+    let labels = &config.inbox.labels;
+    let persona = config.inbox.persona.as_deref().unwrap_or(DEFAULT_PERSONA);
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let mut report = TriageReport::default();
+    let mut actions = Vec::new();
+    let mut last_uid = cursor
+        .filter(|(v, _)| *v == validity)
+        .map_or(0, |(_, last)| last);
+
+    for message in &messages {
+        let answer = match provider
+            .chat_with_system(
+                Some(persona),
+                &classify_prompt(labels, message),
+                &model,
+                config.default_temperature,
+            )
+            .await
+        {
+            Ok(answer) => answer,
+            Err(e) => {
+                // Stop here so the rest is retried next pass.
+                tracing::warn!(
+                    "Inbox classification failed: {}",
+                    providers::sanitize_api_error(&e.to_string())
+                );
+                break;
+            }
+        };
+        last_uid = last_uid.max(message.uid);
+        let Some(class) = parse_classification(&answer, labels) else {
+            *report.counts.entry("unsorted".into()).or_default() += 1;
+            continue;
+        };
+        let label = &labels[&class.label];
+        *report.counts.entry(class.label.clone()).or_default() += 1;
+        if label.draft_reply {
+            if let Some(reply) = &class.reply {
+                store.add_draft(message, &class.label, reply)?;
+                report.drafted += 1;
+            }
+        }
+        if label.notify {
+            let reason = if class.reason.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", class.reason)
+            };
+            report.notable.push(format!(
+                "{}: {} — {}{reason}",
+                class.label, message.sender, message.subject
+            ));
+        }
+        actions.push((message.uid, label.clone()));
+    }
+
+    tokio::task::spawn_blocking(move || apply_labels(&email, &actions)).await??;
+    store.set_cursor(validity, last_uid)?;
+    Ok(report)
+}
+
+async fn notify(config: &Config, message: &str) {
+    let alert = Alert::new("inbox", message).via(
+        config.inbox.notify_channel.as_deref(),
+        config.inbox.notify_recipient.as_deref(),
+    );
+    crate::notifications::notify(config, alert).await;
+}
+
+fn create_provider(config: &Config) -> Result<Box<dyn Provider>> {
+    providers::create_resilient_provider(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        &config.reliability,
+        &config.providers.fallback,
+    )
+}
+
+/// Daemon component: triage every `poll_secs`.
+pub async fn run(config: Config) -> Result<()> {
+    email_config(&config)?;
+    let provider = create_provider(&config)?;
+    let mut interval = tokio::time::interval(Duration::from_secs(config.inbox.poll_secs.max(30)));
+
+    loop {
+        interval.tick().await;
+        match triage_once(&config, provider.as_ref()).await {
+            Ok(report) => {
+                if let Some(summary) = report.summary() {
+                    notify(&config, &summary).await;
+                }
+                crate::health::mark_component_ok("inbox");
+            }
+            Err(e) => {
+                crate::health::mark_component_error("inbox", e.to_string());
+                tracing::warn!("Inbox triage failed: {e:#}");
+            }
+        }
+    }
+}
+
+pub async fn handle_command(command: Option<crate::InboxCommands>, config: &Config) -> Result<()> {
+    let store = InboxStore::open(&config.workspace_dir)?;
+    match command.unwrap_or(crate::InboxCommands::Drafts) {
+        crate::InboxCommands::Run => {
+            let provider = create_provider(config)?;
+            let report = triage_once(config, provider.as_ref()).await?;
+            if report.counts.is_empty() {
+                println!("📥 No new mail.");
+            } else {
+                println!(
+                    "{}",
+                    report
+                        .summary()
+                        .unwrap_or_else(|| format!("📥 Inbox triage: {:?}", report.counts))
+                );
+            }
+            Ok(())
+        }
+        crate::InboxCommands::Drafts => {
+            let drafts = store.drafts()?;
+            if drafts.is_empty() {
+                println!("No reply drafts waiting.");
+                return Ok(());
+            }
+            println!("✉️  Reply drafts ({}):", drafts.len());
+            for draft in &drafts {
+                println!(
+                    "\n#{} | {} | {} — {}\n{}",
+                    draft.id, draft.label, draft.sender, draft.subject, draft.reply
+                );
+            }
+            println!("\nSend with: zeroclaw inbox approve <id>");
+            Ok(())
+        }
+        crate::InboxCommands::Approve { id } => {
+            let draft = store.get(id)?;
+            let channel = EmailChannel::new(email_config(config)?);
+            let sent = draft.clone();
+            tokio::task::spawn_blocking(move || {
+                channel.send_reply(
+                    &sent.sender,
+                    &sent.subject,
+                    &sent.reply,
+                    sent.message_id.as_deref(),
+                )
+            })
+            .await??;
+            store.remove(id)?;
+            println!("✅ Sent reply to {}", draft.sender);
+            Ok(())
+        }
+        crate::InboxCommands::Discard { id } => {
+            store.remove(id)?;
+            println!("🗑️  Discarded draft #{id}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn incoming() -> Incoming {
+        Incoming::parse(
+            7,
+            b"From: Jane <jane@example.com>\r\nSubject: Contract today?\r\nMessage-ID: <abc@example.com>\r\n\r\nCan you sign before 5pm?\r\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn prompt_lists_labels_and_the_email() {
+        let labels = crate::config::InboxConfig::default().labels;
+        let prompt = classify_prompt(&labels, &incoming());
+        assert!(prompt.contains("- urgent (draft a reply): Needs the owner today"));
+        assert!(prompt.contains("- spam: Unsolicited"));
+        assert!(prompt.contains("From: jane@example.com\nSubject: Contract today?"));
+        assert!(prompt.contains("Can you sign before 5pm?"));
+    }
+
+    #[test]
+    fn classification_must_name_a_configured_label() {
+        let labels = crate::config::InboxConfig::default().labels;
+        let answer = "Sure:\n```json\n{\"label\": \"Urgent\", \"reason\": \"deadline\", \"reply\": \"Signing now.\"}\n```";
+        assert_eq!(
+            parse_classification(answer, &labels),
+            Some(Classification {
+                label: "urgent".into(),
+                reason: "deadline".into(),
+                reply: Some("Signing now.".into()),
+            })
+        );
+        let blank = "{\"label\": \"newsletter\", \"reply\": \"  \"}";
+        assert_eq!(parse_classification(blank, &labels).unwrap().reply, None);
+        assert_eq!(parse_classification("{\"label\": \"none\"}", &labels), None);
+        assert_eq!(parse_classification("urgent", &labels), None);
+    }
+
+    #[test]
+    fn select_response_yields_uid_validity() {
+        let lines = vec![
+            "* 12 EXISTS\r\n".to_string(),
+            "* OK [UIDVALIDITY 1700000001] UIDs valid\r\n".to_string(),
+            "A2 OK [READ-WRITE] SELECT completed\r\n".to_string(),
+        ];
+        assert_eq!(uid_validity(&lines), Some(1_700_000_001));
+        assert!(ImapSession::is_ok(&lines));
+        assert_eq!(uid_validity(&lines[..1]), None);
+    }
+
+    #[test]
+    fn drafts_and_cursor_persist() {
+        let tmp = TempDir::new().unwrap();
+        let store = InboxStore::open(tmp.path()).unwrap();
+        assert_eq!(store.cursor().unwrap(), None);
+        store.set_cursor(42, 7).unwrap();
+        store.set_cursor(42, 9).unwrap();
+
+        let id = store
+            .add_draft(&incoming(), "urgent", " Signing now. ")
+            .unwrap();
+        let store = InboxStore::open(tmp.path()).unwrap();
+        assert_eq!(store.cursor().unwrap(), Some((42, 9)));
+        let draft = store.get(id).unwrap();
+        assert_eq!(draft.sender, "jane@example.com");
+        assert_eq!(draft.message_id.as_deref(), Some("abc@example.com"));
+        assert_eq!(draft.reply, "Signing now.");
+
+        store.remove(id).unwrap();
+        assert!(store.drafts().unwrap().is_empty());
+        assert!(store.remove(id).is_err());
+    }
+}
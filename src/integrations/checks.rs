@@ -0,0 +1,420 @@
+//! Live checks behind `zeroclaw integrations check`: for each active
+//! integration, one authenticated request that proves the endpoint is
+//! reachable and the token is accepted (and, where the API reports them,
+//! that its scopes are enough). Results are kept in
+//! `~/.zeroclaw/integration_checks.json` so `integrations list` can show when
+//! each one last passed or failed.
+
+use super::{registry, IntegrationStatus};
+use crate::config::Config;
+use crate::util::LazyClient;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+static HTTP: LazyClient = LazyClient::new(CHECK_TIMEOUT);
+
+/// Outcome of the last check of one integration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub ok: bool,
+    pub detail: String,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl CheckResult {
+    /// Short annotation for `integrations list`, e.g. "ok 5m ago".
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        let age = now - self.checked_at;
+        let ago = if age.num_days() > 0 {
+            format!("{}d", age.num_days())
+        } else if age.num_hours() > 0 {
+            format!("{}h", age.num_hours())
+        } else {
+            format!("{}m", age.num_minutes().max(0))
+        };
+        if self.ok {
+            format!("ok {ago} ago")
+        } else {
+            format!("failed {ago} ago: {}", self.detail)
+        }
+    }
+}
+
+/// Integration name → its last check.
+pub type CheckResults = BTreeMap<String, CheckResult>;
+
+pub fn results_path(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), PathBuf::from)
+        .join("integration_checks.json")
+}
+
+/// The stored results; empty when nothing was checked yet.
+pub fn load(config: &Config) -> CheckResults {
+    std::fs::read_to_string(results_path(config))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save(config: &Config, results: &CheckResults) -> Result<()> {
+    let path = results_path(config);
+    std::fs::write(&path, serde_json::to_string_pretty(results)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Check every active integration, or only `name`, and store the results.
+/// Returns the results of this run.
+pub async fn run(config: &Config, name: Option<&str>) -> Result<CheckResults> {
+    let entries = registry::all_integrations();
+    let selected: Vec<_> = match name {
+        Some(name) => {
+            let entry = entries
+                .iter()
+                .find(|e| e.name.eq_ignore_ascii_case(name))
+                .with_context(|| format!("Unknown integration: {name}"))?;
+            if (entry.status_fn)(config) != IntegrationStatus::Active {
+                anyhow::bail!("{} is not configured", entry.name);
+            }
+            vec![entry]
+        }
+        None => entries
+            .iter()
+            .filter(|e| (e.status_fn)(config) == IntegrationStatus::Active)
+            .collect(),
+    };
+
+    let mut ran = CheckResults::new();
+    for entry in selected {
+        let outcome = tokio::time::timeout(CHECK_TIMEOUT, check(config, entry.name))
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after 10s")));
+        let (ok, detail) = match outcome {
+            Ok(detail) => (true, detail),
+            Err(e) => (false, format!("{e:#}")),
+        };
+        ran.insert(
+            entry.name.to_string(),
+            CheckResult {
+                ok,
+                detail,
+                checked_at: Utc::now(),
+            },
+        );
+    }
+
+    let mut stored = load(config);
+    stored.extend(ran.clone());
+    save(config, &stored)?;
+    Ok(ran)
+}
+
+/// One live check; `Ok` carries what was confirmed.
+#[allow(clippy::too_many_lines)]
+async fn check(config: &Config, name: &str) -> Result<String> {
+    let integrations = &config.integrations;
+    match name {
+        "Slack" => {
+            let slack = config
+                .channels_config
+                .slack
+                .as_ref()
+                .context("not configured")?;
+            check_slack(&slack.bot_token, slack.reactions).await
+        }
+        "GitHub" => {
+            let github = integrations.github.as_ref().context("not configured")?;
+            let resp = send(
+                HTTP.get(format!("{}/user", github.api_url.trim_end_matches('/')))
+                    .bearer_auth(&github.token)
+                    .header("User-Agent", "zeroclaw"),
+            )
+            .await?;
+            // Classic tokens list their scopes; fine-grained ones do not.
+            let scopes = header(&resp, "x-oauth-scopes");
+            let login = json_field(resp, "login").await?;
+            Ok(match scopes {
+                Some(scopes) if !scope_list(&scopes).contains(&"repo") => {
+                    format!("signed in as {login}; no `repo` scope, private repos are hidden")
+                }
+                _ => format!("signed in as {login}"),
+            })
+        }
+        "GitLab" => {
+            let gitlab = integrations.gitlab.as_ref().context("not configured")?;
+            let url = format!("{}/api/v4/user", gitlab.base_url.trim_end_matches('/'));
+            let resp = send(HTTP.get(url).header("PRIVATE-TOKEN", &gitlab.token)).await?;
+            Ok(format!(
+                "signed in as {}",
+                json_field(resp, "username").await?
+            ))
+        }
+        "Bitbucket" => {
+            let bb = integrations.bitbucket.as_ref().context("not configured")?;
+            let resp = send(
+                HTTP.get("https://api.bitbucket.org/2.0/user")
+                    .basic_auth(&bb.username, Some(&bb.app_password)),
+            )
+            .await?;
+            Ok(format!(
+                "signed in as {}",
+                json_field(resp, "username").await?
+            ))
+        }
+        "PagerDuty" => {
+            let pd = integrations.pagerduty.as_ref().context("not configured")?;
+            send(
+                HTTP.get("https://api.pagerduty.com/abilities")
+                    .header("Authorization", format!("Token token={}", pd.api_token)),
+            )
+            .await?;
+            Ok("API token accepted".into())
+        }
+        "Opsgenie" => {
+            let og = integrations.opsgenie.as_ref().context("not configured")?;
+            let url = format!("{}/v2/account", og.api_url.trim_end_matches('/'));
+            send(
+                HTTP.get(url)
+                    .header("Authorization", format!("GenieKey {}", og.api_key)),
+            )
+            .await?;
+            Ok("API key accepted".into())
+        }
+        "Sentry" => {
+            let sentry = integrations.sentry.as_ref().context("not configured")?;
+            let url = format!(
+                "{}/api/0/organizations/{}/",
+                sentry.base_url.trim_end_matches('/'),
+                sentry.organization
+            );
+            send(HTTP.get(url).bearer_auth(&sentry.auth_token)).await?;
+            Ok(format!("organization {} readable", sentry.organization))
+        }
+        "LaunchDarkly" => {
+            let ld = integrations
+                .launchdarkly
+                .as_ref()
+                .context("not configured")?;
+            let url = format!(
+                "{}/api/v2/projects/{}",
+                ld.base_url.trim_end_matches('/'),
+                ld.project_key
+            );
+            send(HTTP.get(url).header("Authorization", &ld.api_token)).await?;
+            Ok(format!("project {} readable", ld.project_key))
+        }
+        "Unleash" => {
+            let unleash = integrations.unleash.as_ref().context("not configured")?;
+            let url = format!("{}/admin/projects", unleash.api_url.trim_end_matches('/'));
+            send(HTTP.get(url).header("Authorization", &unleash.api_token)).await?;
+            Ok("admin API token accepted".into())
+        }
+        "Calendar" => {
+            let calendar = integrations.calendar.as_ref().context("not configured")?;
+            for feed in &calendar.calendars {
+                send(HTTP.get(&feed.url))
+                    .await
+                    .with_context(|| format!("feed {}", feed.name))?;
+            }
+            Ok(format!("{} feed(s) reachable", calendar.calendars.len()))
+        }
+        "Shortcuts" => {
+            let shortcuts = integrations.shortcuts.as_ref().context("not configured")?;
+            let security = std::sync::Arc::new(crate::security::SecurityPolicy::default());
+            let installed = crate::tools::ShortcutsTool::new(shortcuts.clone(), security)
+                .installed()
+                .await?;
+            let missing: Vec<&str> = shortcuts
+                .allowed
+                .iter()
+                .filter(|name| !installed.contains(name))
+                .map(String::as_str)
+                .collect();
+            if !missing.is_empty() {
+                anyhow::bail!("not installed: {}", missing.join(", "));
+            }
+            Ok(format!(
+                "{} allowed shortcut(s) installed",
+                shortcuts.allowed.len()
+            ))
+        }
+        "Web Search" => {
+            let web_search = integrations.web_search.as_ref().context("not configured")?;
+            let engine = crate::tools::web_search::engine(web_search)?;
+            let hits = engine.search("zeroclaw", 1).await?;
+            Ok(format!(
+                "{} returned {} result(s)",
+                engine.name(),
+                hits.len()
+            ))
+        }
+        _ => check_channel_or_provider(config, name).await,
+    }
+}
+
+/// Channels answer through their own health check, AI models through the
+/// default provider's warmup request.
+async fn check_channel_or_provider(config: &Config, name: &str) -> Result<String> {
+    if let Some((_, channel)) = crate::channels::configured_channels(config)
+        .into_iter()
+        .find(|(channel, _)| channel == name)
+    {
+        return if channel.health_check().await {
+            Ok("healthy".into())
+        } else {
+            anyhow::bail!("unhealthy (auth/config/network)")
+        };
+    }
+    let is_model = registry::all_integrations()
+        .iter()
+        .any(|e| e.name == name && e.category == super::IntegrationCategory::AiModel);
+    if is_model {
+        let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+        let provider = crate::providers::create_provider(provider_name, config.api_key.as_deref())?;
+        provider.warmup().await?;
+        return Ok(format!("{provider_name} reachable"));
+    }
+    Ok("configured (no live check available)".into())
+}
+
+/// Slack: `auth.test` proves the token; its `x-oauth-scopes` header shows
+/// whether the bot can read and answer messages.
+async fn check_slack(bot_token: &str, reactions: bool) -> Result<String> {
+    let resp = send(
+        HTTP.post("https://slack.com/api/auth.test")
+            .bearer_auth(bot_token),
+    )
+    .await?;
+    let scopes = header(&resp, "x-oauth-scopes").unwrap_or_default();
+    let body: serde_json::Value = resp.json().await?;
+    if body.get("ok").and_then(serde_json::Value::as_bool) != Some(true) {
+        let error = body
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown error");
+        anyhow::bail!("auth.test: {error}");
+    }
+    let missing = missing_slack_scopes(&scopes, reactions);
+    if !missing.is_empty() {
+        anyhow::bail!("missing scopes: {}", missing.join(", "));
+    }
+    let user = body
+        .get("user")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("bot");
+    Ok(format!("signed in as {user}"))
+}
+
+fn missing_slack_scopes(granted: &str, reactions: bool) -> Vec<&'static str> {
+    let granted = scope_list(granted);
+    let mut required = vec!["chat:write", "channels:history"];
+    if reactions {
+        required.push("reactions:write");
+    }
+    required
+        .into_iter()
+        .filter(|scope| !granted.contains(scope))
+        .collect()
+}
+
+fn scope_list(scopes: &str) -> Vec<&str> {
+    scopes
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn header(resp: &reqwest::Response, name: &str) -> Option<String> {
+    resp.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
+/// Send `request`; non-2xx answers are errors that name the status.
+async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    let resp = request.send().await?;
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        anyhow::bail!("credentials rejected ({status})");
+    }
+    if !status.is_success() {
+        anyhow::bail!("request failed ({status})");
+    }
+    Ok(resp)
+}
+
+async fn json_field(resp: reqwest::Response, field: &str) -> Result<String> {
+    let body: serde_json::Value = resp.json().await?;
+    Ok(body
+        .get(field)
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("unknown")
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn slack_scopes_are_checked_against_what_the_bot_needs() {
+        assert!(missing_slack_scopes("chat:write, channels:history", false).is_empty());
+        assert_eq!(
+            missing_slack_scopes("chat:write,channels:history", true),
+            vec!["reactions:write"]
+        );
+        assert_eq!(
+            missing_slack_scopes("", false),
+            vec!["chat:write", "channels:history"]
+        );
+    }
+
+    #[tokio::test]
+    async fn results_are_stored_for_the_list() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        assert!(load(&config).is_empty());
+        let err = run(&config, Some("GitHub")).await.unwrap_err();
+        assert!(err.to_string().contains("not configured"));
+        assert!(run(&config, Some("Nope")).await.is_err());
+
+        let now = Utc::now();
+        let mut results = CheckResults::new();
+        results.insert(
+            "GitHub".into(),
+            CheckResult {
+                ok: false,
+                detail: "credentials rejected (401 Unauthorized)".into(),
+                checked_at: now - chrono::Duration::hours(3),
+            },
+        );
+        save(&config, &results).unwrap();
+        let stored = load(&config);
+        assert_eq!(stored, results);
+        assert_eq!(
+            stored["GitHub"].summary(now),
+            "failed 3h ago: credentials rejected (401 Unauthorized)"
+        );
+        let fresh = CheckResult {
+            ok: true,
+            detail: "signed in as octocat".into(),
+            checked_at: now,
+        };
+        assert_eq!(fresh.summary(now), "ok 0m ago");
+    }
+}
@@ -1,3 +1,4 @@
+pub mod checks;
 pub mod registry;
 
 use crate::config::Config;
@@ -67,12 +68,73 @@ pub struct IntegrationEntry {
 }
 
 /// Handle the `integrations` CLI command
-pub fn handle_command(command: crate::IntegrationCommands, config: &Config) -> Result<()> {
+pub async fn handle_command(command: crate::IntegrationCommands, config: &Config) -> Result<()> {
     match command {
+        crate::IntegrationCommands::List => {
+            list_integrations(config);
+            Ok(())
+        }
         crate::IntegrationCommands::Info { name } => show_integration_info(config, &name),
+        crate::IntegrationCommands::Check { name } => run_checks(config, name.as_deref()).await,
+    }
+}
+
+fn status_icon(status: IntegrationStatus) -> &'static str {
+    match status {
+        IntegrationStatus::Active => "✅",
+        IntegrationStatus::Available => "⚪",
+        IntegrationStatus::ComingSoon => "🔜",
+    }
+}
+
+fn list_integrations(config: &Config) {
+    let entries = registry::all_integrations();
+    let checks = checks::load(config);
+    let now = chrono::Utc::now();
+    for category in IntegrationCategory::all() {
+        println!("\n{}", console::style(category.label()).white().bold());
+        for entry in entries.iter().filter(|e| e.category == *category) {
+            let status = (entry.status_fn)(config);
+            let last_check = match checks.get(entry.name) {
+                Some(check) if status == IntegrationStatus::Active => {
+                    format!("  [{}]", check.summary(now))
+                }
+                _ => String::new(),
+            };
+            println!(
+                "  {} {:<16} {}{last_check}",
+                status_icon(status),
+                entry.name,
+                entry.description
+            );
+        }
     }
+    println!("\nCheck active integrations: zeroclaw integrations check [name]");
 }
 
+async fn run_checks(config: &Config, name: Option<&str>) -> Result<()> {
+    println!("🩺 Checking integrations...");
+    let results = checks::run(config, name).await?;
+    if results.is_empty() {
+        println!(
+            "  No active integrations. Run `zeroclaw integrations list` to see what is available."
+        );
+        return Ok(());
+    }
+    let failed = results.values().filter(|r| !r.ok).count();
+    for (name, result) in &results {
+        let icon = if result.ok { "✅" } else { "❌" };
+        println!("  {icon} {name:<16} {}", result.detail);
+    }
+    println!();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} integration check(s) failed", results.len());
+    }
+    println!("All {} integration check(s) passed.", results.len());
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
 fn show_integration_info(config: &Config, name: &str) -> Result<()> {
     let entries = registry::all_integrations();
     let name_lower = name.to_lowercase();
@@ -84,10 +146,11 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
     };
 
     let status = (entry.status_fn)(config);
-    let (icon, label) = match status {
-        IntegrationStatus::Active => ("✅", "Active"),
-        IntegrationStatus::Available => ("⚪", "Available"),
-        IntegrationStatus::ComingSoon => ("🔜", "Coming Soon"),
+    let icon = status_icon(status);
+    let label = match status {
+        IntegrationStatus::Active => "Active",
+        IntegrationStatus::Available => "Available",
+        IntegrationStatus::ComingSoon => "Coming Soon",
     };
 
     println!();
@@ -99,6 +162,9 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
     );
     println!("  Category: {}", entry.category.label());
     println!("  Status:   {label}");
+    if let Some(check) = checks::load(config).get(entry.name) {
+        println!("  Checked:  {}", check.summary(chrono::Utc::now()));
+    }
     println!();
 
     // Show setup hints based on integration
@@ -143,12 +209,122 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
         "GitHub" => {
             println!("  Setup:");
             println!("    1. Create a personal access token at https://github.com/settings/tokens");
+            println!("       (Issues and Pull requests read/write, Actions and Contents read)");
             println!("    2. Add to config: [integrations.github] token = \"ghp_...\"");
+            println!("       Optional: default_repo = \"owner/repo\", api_url (GitHub Enterprise)");
+            println!("    3. For CI alerts: [integrations.ci] repos = [\"github:owner/repo\"]");
+            println!("       slack_webhook_url = \"https://hooks.slack.com/...\", then schedule:");
+            println!("       zeroclaw cron add '*/10 * * * *' 'zeroclaw agent -m \"Run ci_status watch\"'");
+        }
+        "GitLab" => {
+            println!("  Setup:");
+            println!("    1. Create an access token with read_api scope");
+            println!("    2. Add to config: [integrations.gitlab] token = \"glpat-...\"");
+            println!(
+                "       Optional: base_url (self-managed), default_project = \"group/project\""
+            );
+        }
+        "Bitbucket" => {
+            println!("  Setup:");
+            println!("    1. Create an app password at https://bitbucket.org/account/settings/app-passwords/");
+            println!("    2. Add to config: [integrations.bitbucket] username = \"...\" app_password = \"...\"");
+            println!("       Optional: default_repo = \"workspace/repo\"");
+        }
+        "PagerDuty" | "Opsgenie" => {
+            let section = name.to_ascii_lowercase();
+            println!("  Setup:");
+            if name == "PagerDuty" {
+                println!("    1. Add to config: [integrations.pagerduty]");
+                println!("       api_token = \"...\", from_email = \"you@example.com\"");
+                println!("       routing_key = \"...\" (Events v2, needed to trigger pages)");
+            } else {
+                println!("    1. Add to config: [integrations.opsgenie] api_key = \"...\"");
+            }
+            println!("    2. On-call briefings: [integrations.oncall]");
+            println!("       channel = \"slack\", recipient = \"C0123456\" (runbooks in workspace/runbooks/)");
+            println!("    3. Point the {name} webhook at POST /incidents/{section} on the gateway");
+            println!("       and set webhook_secret in [integrations.{section}]");
+        }
+        "Sentry" => {
+            println!("  Setup:");
+            println!("    1. Create an auth token with event:read and project:read scopes");
+            println!("    2. Add to config: [integrations.sentry]");
+            println!("       auth_token = \"...\", organization = \"acme\", project = \"web\" (optional)");
+            println!("    3. Overnight triage: zeroclaw cron add '0 7 * * *' \\");
+            println!("       'zeroclaw agent -m \"Triage Sentry issues from the last 12h\"'");
+        }
+        "LaunchDarkly" => {
+            println!("  Setup:");
+            println!("    1. Create an API access token (Writer role to allow toggles)");
+            println!("    2. Add to config: [integrations.launchdarkly]");
+            println!("       api_token = \"api-...\", project_key = \"default\", environment_key = \"production\"");
+            println!("  Toggles need user approval unless [autonomy] level = \"full\".");
+        }
+        "Unleash" => {
+            println!("  Setup:");
+            println!("    1. Create an admin API token");
+            println!("    2. Add to config: [integrations.unleash]");
+            println!("       api_url = \"https://unleash.example.com/api\", api_token = \"...\"");
+            println!("       Optional: project = \"default\", environment = \"production\"");
+            println!("  Toggles need user approval unless [autonomy] level = \"full\".");
+        }
+        "AWS Costs" | "GCP Billing" => {
+            println!("  Setup (either or both, read-only):");
+            println!("    AWS: IAM key with ce:GetCostAndUsage → [integrations.aws_cost]");
+            println!("       access_key_id = \"AKIA...\", secret_access_key = \"...\"");
+            println!("    GCP: enable the billing export to BigQuery → [integrations.gcp_billing]");
+            println!("       project_id = \"acme\", billing_table = \"acme.billing.gcp_billing_export_v1_...\"");
+            println!("       (uses `gcloud auth print-access-token` unless access_token is set)");
+            println!("  Morning digest: zeroclaw cron add '0 8 * * 1-5' \\");
+            println!("       'zeroclaw agent -m \"Run cloud_cost and summarize spend changes\"'");
+        }
+        "1Password" | "Bitwarden" => {
+            println!("  Setup:");
+            println!("    1. Install and sign in to the CLI (`op` with a service account, or `bw` + BW_SESSION)");
+            println!("    2. Add to config: [integrations.password_manager]");
+            println!("       backend = \"1password\" (or \"bitwarden\")");
+            println!("       allowed_items = [\"Staging DB\", \"op://Ops/Deploy/token\"]");
+            println!("  Only allowlisted items are readable; the vault is never listed.");
+            println!("  Each read needs user approval unless [autonomy] level = \"full\".");
+        }
+        "Terraform" => {
+            println!("  Setup:");
+            println!("    1. Install OpenTofu (`tofu`) or Terraform and run `init` in the repo");
+            println!("    2. Add to config: [integrations.terraform]");
+            println!("       binary = \"tofu\" (or \"terraform\")");
+            println!("  Plans run with -lock=false and no plan file; the agent never applies.");
+        }
+        "Calendar" => {
+            println!("  Setup:");
+            println!("    1. Copy the calendar's private iCal (.ics) address");
+            println!("    2. Add to config: [[integrations.calendar.calendars]]");
+            println!("       name = \"work\", url = \"https://…/basic.ics\"");
+            println!("    3. For prep briefs: prep_briefs = true, channel = \"telegram\", recipient = \"<chat id>\"");
+            println!("  Briefs are sent by `zeroclaw daemon` with [heartbeat] enabled = true.");
+        }
+        "Reddit" => {
+            println!("  Setup:");
+            println!("    1. Add to config: [integrations.reddit]");
+            println!("       subreddits = [\"rust\"], keywords = [\"zeroclaw\"]");
+            println!("    2. Schedule a check: zeroclaw cron add '0 * * * *' \\");
+            println!("       'zeroclaw agent -m \"Run reddit monitor and summarize new threads\"'");
+        }
+        "Web Search" => {
+            println!("  Setup:");
+            println!("    Add to config: [integrations.web_search]");
+            println!(
+                "       engine = \"duckduckgo\" (no key), \"brave\" (api_key) or \"searxng\" (url)"
+            );
+            println!("       Optional: max_results = 5");
+            println!("  SearXNG instances need `json` in search.formats.");
         }
         "Browser" => {
-            println!("  Built-in:");
-            println!("    ZeroClaw can control Chrome/Chromium for web tasks.");
-            println!("    Uses headless browser automation.");
+            println!("  Setup:");
+            println!("    Add to config: [browser]");
+            println!("       enabled = true");
+            println!("       allowed_domains = [\"example.com\"]");
+            println!("       backend = \"cdp\" (headless Chromium) or \"agent_browser\" (npm CLI)");
+            println!("  Navigation outside allowed_domains is blocked.");
         }
         "Cron" => {
             println!("  Built-in:");
@@ -159,6 +335,21 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
             println!("  Built-in:");
             println!("    HTTP endpoint for external triggers.");
             println!("    Run: zeroclaw gateway");
+            println!("  Outbound delivery: [[channels_config.webhook.targets]]");
+            println!(
+                "       name = \"ops-bus\", url = \"https://...\", secret = \"...\" (HMAC signing)"
+            );
+            println!(
+                "       Optional: headers, payload_template (uses {{{{message}}}}), max_retries"
+            );
+        }
+        "ntfy / Gotify" => {
+            println!("  Setup:");
+            println!("    Add to config: [channels_config.push]");
+            println!("       service = \"ntfy\", topic = \"<hard-to-guess topic>\"");
+            println!("       or: service = \"gotify\", server_url = \"https://...\", token = \"<app token>\"");
+            println!("  Subscribe to the topic in the ntfy app (or add the Gotify app),");
+            println!("  then deliver briefs and alerts to channel \"push\".");
         }
         _ => {
             if status == IntegrationStatus::ComingSoon {
@@ -43,7 +43,7 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
         },
         IntegrationEntry {
             name: "Webhooks",
-            description: "HTTP endpoint for triggers",
+            description: "HTTP triggers & outbound delivery",
             category: IntegrationCategory::Chat,
             status_fn: |c| {
                 if c.channels_config.webhook.is_some() {
@@ -63,7 +63,25 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Signal",
             description: "Privacy-focused via signal-cli",
             category: IntegrationCategory::Chat,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.channels_config.signal.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "ntfy / Gotify",
+            description: "Push notifications to your phone",
+            category: IntegrationCategory::Chat,
+            status_fn: |c| {
+                if c.channels_config.push.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "iMessage",
@@ -435,9 +453,39 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
         // ── Productivity ────────────────────────────────────────
         IntegrationEntry {
             name: "GitHub",
-            description: "Code, issues, PRs",
+            description: "Code, issues, PRs, Actions",
             category: IntegrationCategory::Productivity,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.integrations.github.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "GitLab",
+            description: "Issues, MRs, pipelines",
+            category: IntegrationCategory::Productivity,
+            status_fn: |c| {
+                if c.integrations.gitlab.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Bitbucket",
+            description: "Issues, PRs, pipelines",
+            category: IntegrationCategory::Productivity,
+            status_fn: |c| {
+                if c.integrations.bitbucket.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Notion",
@@ -530,7 +578,25 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Browser",
             description: "Chrome/Chromium control",
             category: IntegrationCategory::ToolsAutomation,
-            status_fn: |_| IntegrationStatus::Available,
+            status_fn: |c| {
+                if c.browser.enabled {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Web Search",
+            description: "Brave, SearXNG or DuckDuckGo results",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.web_search.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Shell",
@@ -550,6 +616,130 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             category: IntegrationCategory::ToolsAutomation,
             status_fn: |_| IntegrationStatus::Available,
         },
+        IntegrationEntry {
+            name: "PagerDuty",
+            description: "Incidents, acks, on-call briefings",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.pagerduty.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Opsgenie",
+            description: "Alerts, acks, on-call briefings",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.opsgenie.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Sentry",
+            description: "Error triage & stack traces",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.sentry.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "LaunchDarkly",
+            description: "Feature flags & kill switches",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.launchdarkly.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Unleash",
+            description: "Feature flags & kill switches",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.unleash.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "AWS Costs",
+            description: "Cost Explorer daily spend",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.aws_cost.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "GCP Billing",
+            description: "Billing export daily spend",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.gcp_billing.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Terraform",
+            description: "Plan summaries & risk flags",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.terraform.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Shortcuts",
+            description: "Run Apple Shortcuts; shortcuts send prompts back",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations.shortcuts.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Calendar",
+            description: "Upcoming events & meeting prep briefs",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations
+                    .calendar
+                    .as_ref()
+                    .is_some_and(|cal| !cal.calendars.is_empty())
+                {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
         IntegrationEntry {
             name: "Voice",
             description: "Voice wake + talk mode",
@@ -566,7 +756,31 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "1Password",
             description: "Secure credentials",
             category: IntegrationCategory::ToolsAutomation,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.integrations.password_manager.as_ref().is_some_and(|p| {
+                    p.backend == crate::config::PasswordManagerBackend::OnePassword
+                }) {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
+        IntegrationEntry {
+            name: "Bitwarden",
+            description: "Secure credentials",
+            category: IntegrationCategory::ToolsAutomation,
+            status_fn: |c| {
+                if c.integrations
+                    .password_manager
+                    .as_ref()
+                    .is_some_and(|p| p.backend == crate::config::PasswordManagerBackend::Bitwarden)
+                {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Weather",
@@ -618,6 +832,18 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             category: IntegrationCategory::Social,
             status_fn: |_| IntegrationStatus::ComingSoon,
         },
+        IntegrationEntry {
+            name: "Reddit",
+            description: "Subreddit search & keyword monitoring",
+            category: IntegrationCategory::Social,
+            status_fn: |c| {
+                if c.integrations.reddit.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
         // ── Platforms ───────────────────────────────────────────
         IntegrationEntry {
             name: "macOS",
@@ -667,7 +893,7 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::schema::{IMessageConfig, MatrixConfig, TelegramConfig};
+    use crate::config::schema::{IMessageConfig, MatrixConfig, SignalConfig, TelegramConfig};
     use crate::config::Config;
 
     #[test]
@@ -730,6 +956,7 @@ mod tests {
         config.channels_config.telegram = Some(TelegramConfig {
             bot_token: "123:ABC".into(),
             allowed_users: vec!["user".into()],
+            voice_replies: false,
         });
         let entries = all_integrations();
         let tg = entries.iter().find(|e| e.name == "Telegram").unwrap();
@@ -752,6 +979,7 @@ mod tests {
         let mut config = Config::default();
         config.channels_config.imessage = Some(IMessageConfig {
             allowed_contacts: vec!["*".into()],
+            voice_replies: false,
         });
         let entries = all_integrations();
         let im = entries.iter().find(|e| e.name == "iMessage").unwrap();
@@ -777,12 +1005,34 @@ mod tests {
             access_token: "tok".into(),
             room_id: "!r:m".into(),
             allowed_users: vec![],
+            rooms: Vec::new(),
         });
         let entries = all_integrations();
         let mx = entries.iter().find(|e| e.name == "Matrix").unwrap();
         assert!(matches!((mx.status_fn)(&config), IntegrationStatus::Active));
     }
 
+    #[test]
+    fn signal_active_when_configured() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let signal = entries.iter().find(|e| e.name == "Signal").unwrap();
+        assert!(matches!(
+            (signal.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.channels_config.signal = Some(SignalConfig {
+            http_url: "http://127.0.0.1:8686".into(),
+            account: "+15550000000".into(),
+            allowed_numbers: vec!["*".into()],
+            groups: Vec::new(),
+        });
+        assert!(matches!(
+            (signal.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
     #[test]
     fn matrix_available_when_not_configured() {
         let config = Config::default();
@@ -798,7 +1048,7 @@ mod tests {
     fn coming_soon_integrations_stay_coming_soon() {
         let config = Config::default();
         let entries = all_integrations();
-        for name in ["WhatsApp", "Signal", "Nostr", "Spotify", "Home Assistant"] {
+        for name in ["WhatsApp", "Nostr", "Spotify", "Home Assistant"] {
             let entry = entries.iter().find(|e| e.name == name).unwrap();
             assert!(
                 matches!((entry.status_fn)(&config), IntegrationStatus::ComingSoon),
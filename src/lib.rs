@@ -1,3 +1,10 @@
+//! `ZeroClaw` as a library: the agent loop, providers, tools, memory and
+//! channels behind the `zeroclaw` CLI, usable from other Rust programs.
+//!
+//! Start with [`agent::Agent::builder`]; [`providers::create_provider`],
+//! [`memory::create_memory`] and [`tools::all_tools`] build the same pieces
+//! the CLI wires up from [`Config`].
+
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(
     clippy::missing_errors_doc,
@@ -15,27 +22,59 @@ use clap::Subcommand;
 use serde::{Deserialize, Serialize};
 
 pub mod agent;
+pub mod artifacts;
 pub mod channels;
 pub mod config;
+pub mod conversations;
 pub mod cron;
 pub mod daemon;
+pub mod dlq;
 pub mod doctor;
+pub mod eval;
+pub mod experiments;
+pub mod federation;
+pub mod focus;
 pub mod gateway;
+pub mod goals;
 pub mod health;
 pub mod heartbeat;
+pub mod household;
+pub mod idempotency;
+pub mod inbox;
 pub mod integrations;
+pub mod mcp;
 pub mod memory;
 pub mod migration;
+pub mod model_fit;
+pub mod notifications;
+pub mod oauth;
 pub mod observability;
+pub mod offline;
 pub mod onboard;
+pub mod outbox;
+pub mod platform;
+pub mod presence;
+pub mod prompts;
 pub mod providers;
+pub mod redis;
+pub mod registry;
 pub mod runtime;
 pub mod security;
 pub mod service;
+pub mod skillforge;
 pub mod skills;
+pub mod sync;
+pub mod tasks;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod tools;
+pub mod triggers;
 pub mod tunnel;
 pub mod util;
+pub mod vision;
+pub mod voice;
+pub mod workflows;
+pub mod workspace_index;
 
 pub use config::Config;
 
@@ -59,9 +98,13 @@ pub enum ServiceCommands {
 pub enum ChannelCommands {
     /// List all configured channels
     List,
-    /// Start all configured channels (handled in main.rs for async)
-    Start,
-    /// Run health checks for configured channels (handled in main.rs for async)
+    /// Start all configured channels (Telegram, Discord, Slack)
+    Start {
+        /// Print replies instead of sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Run health checks for configured channels
     Doctor,
     /// Add a new channel configuration
     Add {
@@ -75,6 +118,25 @@ pub enum ChannelCommands {
         /// Channel name to remove
         name: String,
     },
+    /// Allowlist yourself by sending the bot a one-time code
+    Pair {
+        /// Channel type (telegram, discord)
+        channel_type: String,
+    },
+}
+
+/// Prompt template subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PromptCommands {
+    /// List templates in the workspace `prompts/` directory
+    List,
+    /// Render a template without running it
+    Show {
+        /// Template name (path under `prompts/` without the extension)
+        name: String,
+        /// Variables as `key=value`
+        vars: Vec<String>,
+    },
 }
 
 /// Skills management subcommands
@@ -92,6 +154,11 @@ pub enum SkillCommands {
         /// Skill name to remove
         name: String,
     },
+    /// End a skill's quarantine and grant its manifest permissions
+    Trust {
+        /// Skill name to trust
+        name: String,
+    },
 }
 
 /// Migration subcommands
@@ -118,8 +185,17 @@ pub enum CronCommands {
     Add {
         /// Cron expression
         expression: String,
-        /// Command to run
+        /// Command to run (the prompt, with --agent)
         command: String,
+        /// Run the command as a prompt through the agent instead of the shell
+        #[arg(long)]
+        agent: bool,
+        /// Provider for this job's agent runs
+        #[arg(long, requires = "agent")]
+        provider: Option<String>,
+        /// Model for this job's agent runs
+        #[arg(long, requires = "agent")]
+        model: Option<String>,
     },
     /// Remove a scheduled task
     Remove {
@@ -128,12 +204,362 @@ pub enum CronCommands {
     },
 }
 
+/// OAuth subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OAuthCommands {
+    /// Show configured clients and whether each is signed in
+    Status,
+    /// Sign in to a provider
+    Login {
+        /// Provider name, as in `[oauth.<provider>]`
+        provider: String,
+        /// Use the device flow (no browser on this machine)
+        #[arg(long)]
+        device: bool,
+    },
+    /// Forget the stored token for a provider
+    Logout {
+        /// Provider name
+        provider: String,
+    },
+}
+
+/// Federation subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FederationCommands {
+    /// List configured peer instances
+    Peers,
+    /// Send a message to a peer and print its answer
+    Send {
+        /// Peer name, as in `[federation.peers.<name>]`
+        peer: String,
+        /// The message (or task) for the peer
+        message: String,
+        /// Run it as a task with the peer's tools (the peer must allow it)
+        #[arg(long)]
+        task: bool,
+    },
+}
+
+/// Sync subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SyncCommands {
+    /// Sync now: pull the other devices' changes and publish this one's
+    Now,
+    /// Show the backend, this device's ID and what was synced last
+    Status,
+    /// Print the sync key (created on first use) to copy to other devices
+    Key,
+    /// Use the sync key printed by `zeroclaw sync key` on another device
+    Join {
+        /// The key, 64 hex digits
+        key: String,
+        /// Replace a different key this device already has
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Goal subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GoalCommands {
+    /// List goals with their last check
+    List,
+    /// Show a goal with its recent checks
+    Show {
+        /// Goal ID (prefix is enough)
+        id: String,
+    },
+    /// Add a goal for the heartbeat to keep an eye on
+    Add {
+        /// The condition to keep, in plain words
+        description: String,
+        /// Minutes between checks (default: every heartbeat tick)
+        #[arg(long)]
+        every: Option<u32>,
+    },
+    /// Stop checking a goal
+    Pause {
+        /// Goal ID (prefix is enough)
+        id: String,
+    },
+    /// Start checking a paused goal again
+    Resume {
+        /// Goal ID (prefix is enough)
+        id: String,
+    },
+    /// Delete a goal and its history
+    Remove {
+        /// Goal ID (prefix is enough)
+        id: String,
+    },
+    /// Check one goal, or all active ones, right now
+    Check {
+        /// Goal ID (prefix is enough)
+        id: Option<String>,
+    },
+}
+
+/// Secrets vault subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SecretCommands {
+    /// Store a secret (prompts for the value unless --value is given)
+    Set {
+        /// Name to refer to it by, as `secret:<name>` in config.toml
+        name: String,
+        /// The value; omit to type it hidden or pipe it on stdin
+        #[arg(long)]
+        value: Option<String>,
+    },
+    /// Print a stored secret
+    Get {
+        /// Secret name
+        name: String,
+    },
+    /// List the names of stored secrets
+    List,
+    /// Delete a stored secret
+    Delete {
+        /// Secret name
+        name: String,
+    },
+}
+
+/// Debugging subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DebugCommands {
+    /// Show what went into a turn's prompts (the latest traced turn if omitted)
+    Prompt {
+        /// Turn ID (`turn_id` in `zeroclaw agent sessions export`)
+        turn_id: Option<i64>,
+        /// Print the full prompt of every provider round
+        #[arg(long)]
+        full: bool,
+        /// Print the raw trace as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Task subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TaskCommands {
+    /// List open tasks
+    List {
+        /// Include done and failed tasks
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show a task with its progress notes
+    Show {
+        /// Task ID (prefix is enough)
+        id: String,
+    },
+    /// Open a new task for the agent to work on
+    Add {
+        /// What needs doing
+        title: String,
+    },
+    /// Hand an open task back to the agent, with its progress so far
+    Resume {
+        /// Task ID (prefix is enough)
+        id: String,
+    },
+    /// Close a task as failed without running it
+    Cancel {
+        /// Task ID (prefix is enough)
+        id: String,
+    },
+}
+
+/// Dead-letter queue subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DlqCommands {
+    /// List messages that failed delivery
+    List,
+    /// Re-send one dead letter, or all of them
+    Retry {
+        /// Dead-letter ID (prefix is enough); omit to retry everything
+        id: Option<String>,
+    },
+    /// Delete one dead letter, or all with --all
+    Purge {
+        /// Dead-letter ID (prefix is enough)
+        id: Option<String>,
+        /// Delete every dead letter
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+/// MCP subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum McpCommands {
+    /// Serve our tools to MCP clients (e.g. Claude Desktop) over stdio
+    Serve {
+        /// Only offer this tool (repeatable); all enabled tools by default
+        #[arg(long = "tool")]
+        tools: Vec<String>,
+    },
+}
+
+/// Outbox subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutboxCommands {
+    /// List messages waiting to be sent
+    List {
+        /// Include messages already sent
+        #[arg(long)]
+        all: bool,
+    },
+    /// Send everything that is due now
+    Flush,
+}
+
+/// Workflow subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkflowCommands {
+    /// List workflows and check that they load
+    List,
+    /// Run a workflow now
+    Run {
+        /// Workflow name (file stem in workspace/workflows/)
+        name: String,
+        /// Inputs as key=value
+        vars: Vec<String>,
+    },
+}
+
+/// Agent subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AgentCommands {
+    /// Manage saved conversation sessions
+    Sessions {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+}
+
+/// Saved conversation session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List saved sessions, most recent first
+    List,
+    /// Delete a session and its history
+    Delete {
+        /// Session name (as passed to `--session`)
+        name: String,
+    },
+    /// Write the shell commands and file writes of a session as a script
+    ToScript {
+        /// Session name (as passed to `--session`)
+        name: String,
+        /// Write the script here instead of printing it
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Export answered turns with their ratings and notes as JSONL
+    Export {
+        /// Session name, or a channel session like `telegram:42`; omit for all
+        name: Option<String>,
+        /// Only turns that were rated 👍 or 👎
+        #[arg(long)]
+        rated: bool,
+        /// Write the JSONL here instead of printing it
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Model-quality canary subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EvalCommands {
+    /// Run the canaries now and record the result
+    Run,
+    /// Show recent canary runs
+    History,
+}
+
+/// Provider A/B experiment subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExperimentCommands {
+    /// Compare the arms: ratings, errors, latency, tokens and cost
+    Report {
+        /// Experiment name (default: `[experiment] name`)
+        name: Option<String>,
+    },
+}
+
+/// Workspace index subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IndexCommands {
+    /// Show how many files and chunks are indexed
+    Status,
+    /// Re-index every workspace file from scratch
+    Rebuild,
+}
+
+/// Memory subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MemoryCommands {
+    /// Chunk and embed documents (PDF, Markdown, HTML, code) for retrieval
+    Ingest {
+        /// File, directory or http(s) URL
+        source: String,
+    },
+    /// List ingested documents
+    Documents,
+    /// Forget an ingested document
+    Remove {
+        /// Source as shown by `memory documents`
+        source: String,
+    },
+}
+
+/// Inbox triage subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum InboxCommands {
+    /// Triage new mail once, now
+    Run,
+    /// List reply drafts waiting for approval
+    Drafts,
+    /// Send a drafted reply
+    Approve {
+        /// Draft ID (as shown by `inbox drafts`)
+        id: i64,
+    },
+    /// Delete a drafted reply without sending it
+    Discard {
+        /// Draft ID (as shown by `inbox drafts`)
+        id: i64,
+    },
+}
+
+/// Scenario test subcommands (`testing` feature)
+#[cfg(any(test, feature = "testing"))]
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TestCommands {
+    /// Run scenario files against the mock provider and an in-memory channel
+    Scenario {
+        /// Scenario TOML file(s)
+        #[arg(required = true)]
+        files: Vec<std::path::PathBuf>,
+    },
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum IntegrationCommands {
+    /// List integrations by category, with the last check of active ones
+    List,
     /// Show details about a specific integration
     Info {
         /// Integration name
         name: String,
     },
+    /// Test connectivity and credentials of active integrations
+    Check {
+        /// Only check this integration
+        name: Option<String>,
+    },
 }
@@ -12,32 +12,18 @@ use anyhow::{bail, Result};
 use clap::{Parser, Subcommand};
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
-
-mod agent;
-mod channels;
-mod config;
-mod cron;
-mod daemon;
-mod doctor;
-mod gateway;
-mod health;
-mod heartbeat;
-mod integrations;
-mod memory;
-mod migration;
-mod observability;
-mod onboard;
-mod providers;
-mod runtime;
-mod security;
-mod service;
-mod skillforge;
-mod skills;
-mod tools;
-mod tunnel;
-mod util;
-
-use config::Config;
+use zeroclaw::{
+    agent, channels, cron, daemon, dlq, doctor, eval, experiments, federation, focus, gateway,
+    goals, inbox, integrations, mcp, memory, migration, notifications, oauth, onboard, outbox,
+    prompts, providers, security, service, skills, sync, tasks, tools, workflows, workspace_index,
+    AgentCommands, ChannelCommands, Config, CronCommands, DebugCommands, DlqCommands, EvalCommands,
+    ExperimentCommands, FederationCommands, GoalCommands, InboxCommands, IndexCommands,
+    IntegrationCommands, McpCommands, MemoryCommands, MigrateCommands, OAuthCommands,
+    OutboxCommands, PromptCommands, SecretCommands, ServiceCommands, SkillCommands, SyncCommands,
+    TaskCommands, WorkflowCommands,
+};
+#[cfg(feature = "testing")]
+use zeroclaw::{testing, TestCommands};
 
 /// `ZeroClaw` - Zero overhead. Zero compromise. 100% Rust.
 #[derive(Parser, Debug)]
@@ -50,20 +36,6 @@ struct Cli {
     command: Commands,
 }
 
-#[derive(Subcommand, Debug)]
-enum ServiceCommands {
-    /// Install daemon service unit for auto-start and restart
-    Install,
-    /// Start daemon service
-    Start,
-    /// Stop daemon service
-    Stop,
-    /// Check daemon service status
-    Status,
-    /// Uninstall daemon service unit
-    Uninstall,
-}
-
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Initialize your workspace and configuration
@@ -95,7 +67,7 @@ enum Commands {
         #[arg(short, long)]
         message: Option<String>,
 
-        /// Provider to use (openrouter, anthropic, openai)
+        /// Provider to use (openrouter, anthropic, openai, groq, ...)
         #[arg(short, long)]
         provider: Option<String>,
 
@@ -106,6 +78,30 @@ enum Commands {
         /// Temperature (0.0 - 2.0)
         #[arg(short, long, default_value = "0.7")]
         temperature: f64,
+
+        /// Record provider requests/responses to the cassette (secrets stripped)
+        #[arg(long, conflicts_with = "offline")]
+        record: bool,
+
+        /// Replay provider responses from the cassette instead of calling the provider
+        #[arg(long)]
+        offline: bool,
+
+        /// Cassette file for --record/--offline (default: workspace/cassettes/provider.json)
+        #[arg(long)]
+        cassette: Option<std::path::PathBuf>,
+
+        /// Call the provider normally but simulate every tool call and skip memory writes
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Resume (or start) a named conversation; its history is kept between runs
+        /// in the memory backend
+        #[arg(short, long)]
+        session: Option<String>,
+
+        #[command(subcommand)]
+        agent_command: Option<AgentCommands>,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -148,107 +144,171 @@ enum Commands {
         cron_command: CronCommands,
     },
 
-    /// Manage channels (telegram, discord, slack)
-    Channel {
+    /// Sign in to integrations with OAuth
+    Oauth {
         #[command(subcommand)]
-        channel_command: ChannelCommands,
+        oauth_command: OAuthCommands,
     },
 
-    /// Browse 50+ integrations
-    Integrations {
+    /// Talk to other `ZeroClaw` instances (peers)
+    Federation {
         #[command(subcommand)]
-        integration_command: IntegrationCommands,
+        federation_command: FederationCommands,
     },
 
-    /// Manage skills (user-defined capabilities)
-    Skills {
+    /// Share memories, sessions and config with your other devices (encrypted)
+    Sync {
         #[command(subcommand)]
-        skill_command: SkillCommands,
+        sync_command: SyncCommands,
     },
 
-    /// Migrate data from other agent runtimes
-    Migrate {
+    /// Standing goals the heartbeat checks (lists goals without a subcommand)
+    Goals {
         #[command(subcommand)]
-        migrate_command: MigrateCommands,
+        goal_command: Option<GoalCommands>,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum MigrateCommands {
-    /// Import memory from an `OpenClaw` workspace into this `ZeroClaw` workspace
-    Openclaw {
-        /// Optional path to `OpenClaw` workspace (defaults to ~/.openclaw/workspace)
+    /// Hold briefs, summaries and heartbeat tasks for a while (`focus 2h`,
+    /// `focus off`; shows the session without an argument)
+    Focus {
+        /// How long, e.g. 2h, 45m or 1h30m; or `off` to end it now
+        duration: Option<String>,
+    },
+
+    /// Send an alert through `[notifications]` (for scripts and cron jobs)
+    Notify {
+        message: String,
+        /// info, warning or critical (critical ignores quiet hours and focus)
+        #[arg(long, default_value = "info")]
+        severity: String,
+        /// What raised it, for `[[notifications.rules]]` to match
+        #[arg(long, default_value = "cli")]
+        source: String,
+        /// Channel to send on instead of the configured one
+        #[arg(long)]
+        channel: Option<String>,
+        /// Recipient instead of the configured one, or @name
         #[arg(long)]
-        source: Option<std::path::PathBuf>,
+        to: Option<String>,
+    },
 
-        /// Validate and preview migration without writing any data
+    /// Inspect what went into the agent's prompts
+    Debug {
+        #[command(subcommand)]
+        debug_command: DebugCommands,
+    },
+
+    /// Store API keys and tokens in the OS keychain instead of config.toml
+    Secrets {
+        #[command(subcommand)]
+        secret_command: SecretCommands,
+    },
+
+    /// Track multi-step tasks (lists open tasks without a subcommand)
+    Tasks {
+        #[command(subcommand)]
+        task_command: Option<TaskCommands>,
+    },
+
+    /// Inspect and replay messages that failed delivery
+    Dlq {
+        #[command(subcommand)]
+        dlq_command: DlqCommands,
+    },
+
+    /// Inspect and flush messages waiting to be sent
+    Outbox {
+        #[command(subcommand)]
+        outbox_command: OutboxCommands,
+    },
+
+    /// Revert the agent's last workspace changes
+    Undo {
+        /// How many changes to revert
+        #[arg(default_value_t = 1)]
+        n: usize,
+        /// Show recent changes instead
         #[arg(long)]
-        dry_run: bool,
+        list: bool,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum CronCommands {
-    /// List all scheduled tasks
-    List,
-    /// Add a new scheduled task
-    Add {
-        /// Cron expression
-        expression: String,
-        /// Command to run
-        command: String,
+    /// List and run workflows from workspace/workflows/
+    Workflows {
+        #[command(subcommand)]
+        workflow_command: WorkflowCommands,
     },
-    /// Remove a scheduled task
-    Remove {
-        /// Task ID
-        id: String,
+
+    /// Run model-quality canaries (runs them without a subcommand)
+    Eval {
+        #[command(subcommand)]
+        eval_command: Option<EvalCommands>,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum ChannelCommands {
-    /// List configured channels
-    List,
-    /// Start all configured channels (Telegram, Discord, Slack)
-    Start,
-    /// Run health checks for configured channels
-    Doctor,
-    /// Add a new channel
-    Add {
-        /// Channel type
-        channel_type: String,
-        /// Configuration JSON
-        config: String,
+    /// Provider A/B experiments (reports without a subcommand)
+    Experiments {
+        #[command(subcommand)]
+        experiment_command: Option<ExperimentCommands>,
     },
-    /// Remove a channel
-    Remove {
-        /// Channel name
-        name: String,
+
+    /// Triage the email inbox and review reply drafts (lists drafts without a subcommand)
+    Inbox {
+        #[command(subcommand)]
+        inbox_command: Option<InboxCommands>,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum SkillCommands {
-    /// List installed skills
-    List,
-    /// Install a skill from a GitHub URL or local path
-    Install {
-        /// GitHub URL or local path
-        source: String,
+    /// Inspect or rebuild the workspace search index
+    Index {
+        #[command(subcommand)]
+        index_command: IndexCommands,
     },
-    /// Remove an installed skill
-    Remove {
-        /// Skill name
-        name: String,
+
+    /// Ingest documents into memory for retrieval
+    Memory {
+        #[command(subcommand)]
+        memory_command: MemoryCommands,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum IntegrationCommands {
-    /// Show details about a specific integration
-    Info {
-        /// Integration name
-        name: String,
+    /// Offer `ZeroClaw`'s tools to other MCP clients
+    Mcp {
+        #[command(subcommand)]
+        mcp_command: McpCommands,
+    },
+
+    /// Manage channels (telegram, discord, slack)
+    Channel {
+        #[command(subcommand)]
+        channel_command: ChannelCommands,
+    },
+
+    /// Browse 50+ integrations
+    Integrations {
+        #[command(subcommand)]
+        integration_command: IntegrationCommands,
+    },
+
+    /// Manage prompt templates (run them with `/run <name> key=value`)
+    Prompts {
+        #[command(subcommand)]
+        prompt_command: PromptCommands,
+    },
+
+    /// Manage skills (user-defined capabilities)
+    Skills {
+        #[command(subcommand)]
+        skill_command: SkillCommands,
+    },
+
+    /// Migrate data from other agent runtimes
+    Migrate {
+        #[command(subcommand)]
+        migrate_command: MigrateCommands,
+    },
+
+    /// Run deterministic end-to-end scenarios (built with `--features testing`)
+    #[cfg(feature = "testing")]
+    Test {
+        #[command(subcommand)]
+        test_command: TestCommands,
     },
 }
 
@@ -257,12 +317,14 @@ enum IntegrationCommands {
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    // Initialize logging
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .finish();
-
-    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    // Initialize logging; an MCP server's stdout carries the protocol
+    let subscriber = FmtSubscriber::builder().with_max_level(Level::INFO);
+    if matches!(cli.command, Commands::Mcp { .. }) {
+        tracing::subscriber::set_global_default(subscriber.with_writer(std::io::stderr).finish())
+    } else {
+        tracing::subscriber::set_global_default(subscriber.finish())
+    }
+    .expect("setting default subscriber failed");
 
     // Onboard runs quick setup by default, or the interactive wizard with --interactive
     if let Commands::Onboard {
@@ -289,13 +351,14 @@ async fn main() -> Result<()> {
         };
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
-            channels::start_channels(config).await?;
+            channels::start_channels(config, false).await?;
         }
         return Ok(());
     }
 
     // All other commands need config loaded first
     let config = Config::load_or_init()?;
+    zeroclaw::redis::init(&config.redis);
 
     match cli.command {
         Commands::Onboard { .. } => unreachable!(),
@@ -305,7 +368,38 @@ async fn main() -> Result<()> {
             provider,
             model,
             temperature,
-        } => agent::run(config, message, provider, model, temperature).await,
+            record,
+            offline,
+            cassette,
+            dry_run,
+            session,
+            agent_command,
+        } => {
+            if let Some(AgentCommands::Sessions { session_command }) = agent_command {
+                return agent::sessions::handle_command(session_command, &config).await;
+            }
+            let cassette_path = cassette.unwrap_or_else(|| {
+                providers::recording::default_cassette_path(&config.workspace_dir)
+            });
+            let cassette = if offline {
+                Some(providers::recording::CassetteMode::Replay(cassette_path))
+            } else if record {
+                Some(providers::recording::CassetteMode::Record(cassette_path))
+            } else {
+                None
+            };
+            Box::pin(agent::run(
+                config,
+                message,
+                provider,
+                model,
+                temperature,
+                cassette,
+                dry_run,
+                session,
+            ))
+            .await
+        }
 
         Commands::Gateway { port, host } => {
             if port == 0 {
@@ -342,7 +436,10 @@ async fn main() -> Result<()> {
             );
             println!("📊 Observability:  {}", config.observability.backend);
             println!("🛡️  Autonomy:      {:?}", config.autonomy.level);
-            println!("⚙️  Runtime:       {}", config.runtime.kind);
+            println!(
+                "⚙️  Runtime:       {} (profile: {})",
+                config.runtime.kind, config.runtime.profile
+            );
             println!(
                 "💓 Heartbeat:      {}",
                 if config.heartbeat.enabled {
@@ -395,28 +492,102 @@ async fn main() -> Result<()> {
         }
 
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
+        Commands::Oauth { oauth_command } => oauth::handle_command(oauth_command, &config).await,
+        Commands::Sync { sync_command } => sync::handle_command(sync_command, &config).await,
+
+        Commands::Federation { federation_command } => {
+            federation::handle_command(federation_command, &config).await
+        }
+
+        Commands::Goals { goal_command } => goals::handle_command(goal_command, &config).await,
+        Commands::Focus { duration } => {
+            println!(
+                "{}",
+                focus::apply(&config, duration.as_deref().unwrap_or_default()).await?
+            );
+            Ok(())
+        }
+        Commands::Notify {
+            message,
+            severity,
+            source,
+            channel,
+            to,
+        } => {
+            notifications::handle_command(
+                &config,
+                &message,
+                &severity,
+                &source,
+                (channel.as_deref(), to.as_deref()),
+            )
+            .await
+        }
+        Commands::Debug { debug_command } => agent::trace::handle_command(&debug_command, &config),
+        Commands::Secrets { secret_command } => {
+            security::vault::handle_command(secret_command, &config)
+        }
+        Commands::Tasks { task_command } => tasks::handle_command(task_command, &config).await,
+
+        Commands::Dlq { dlq_command } => dlq::handle_command(dlq_command, &config).await,
+
+        Commands::Outbox { outbox_command } => {
+            outbox::handle_command(outbox_command, &config).await
+        }
+
+        Commands::Undo { n, list } => tools::history::handle_undo(&config, n, list).await,
+
+        Commands::Workflows { workflow_command } => {
+            workflows::handle_command(workflow_command, &config).await
+        }
+
+        Commands::Eval { eval_command } => eval::handle_command(eval_command, &config).await,
+        Commands::Experiments { experiment_command } => {
+            experiments::handle_command(experiment_command, &config)
+        }
+
+        Commands::Inbox { inbox_command } => inbox::handle_command(inbox_command, &config).await,
+
+        Commands::Index { index_command } => {
+            workspace_index::handle_command(index_command, &config).await
+        }
+
+        Commands::Memory { memory_command } => {
+            memory::ingest::handle_command(memory_command, &config).await
+        }
+
+        Commands::Mcp { mcp_command } => mcp::handle_command(mcp_command, &config).await,
 
         Commands::Service { service_command } => service::handle_command(&service_command, &config),
 
         Commands::Doctor => doctor::run(&config),
 
         Commands::Channel { channel_command } => match channel_command {
-            ChannelCommands::Start => channels::start_channels(config).await,
+            ChannelCommands::Start { dry_run } => channels::start_channels(config, dry_run).await,
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
+            ChannelCommands::Pair { channel_type } => {
+                tokio::task::spawn_blocking(move || {
+                    onboard::pairing::pair_channel(config, &channel_type)
+                })
+                .await?
+            }
             other => channels::handle_command(other, &config),
         },
 
         Commands::Integrations {
             integration_command,
-        } => integrations::handle_command(integration_command, &config),
+        } => integrations::handle_command(integration_command, &config).await,
 
-        Commands::Skills { skill_command } => {
-            skills::handle_command(skill_command, &config.workspace_dir)
-        }
+        Commands::Prompts { prompt_command } => prompts::handle_command(prompt_command, &config),
+
+        Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
+
+        #[cfg(feature = "testing")]
+        Commands::Test { test_command } => testing::handle_command(test_command).await,
     }
 }
 
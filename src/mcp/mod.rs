@@ -0,0 +1,341 @@
+//! Model Context Protocol client.
+//!
+//! Every server in `[mcp.servers]` is started (`command`, over stdio) or
+//! connected to (`url`, over SSE) when the agent starts. After the
+//! `initialize` handshake its tools are listed and registered as plugin
+//! tools named `<server>__<tool>`, so they reach the agent through the
+//! tool registry like any other tool. A server that fails to start is
+//! logged and skipped. [`server`] goes the other way and offers our own
+//! tools to other MCP clients.
+
+pub mod server;
+pub mod transport;
+
+use crate::config::{Config, McpConfig, McpServerConfig};
+use crate::registry::{OnCollision, PluginSource};
+use crate::tools::{plugin, McpTool, ToolResult};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use transport::Transport;
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+/// JSON-RPC "method not found", for server requests we don't handle.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// A tool as the server describes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpToolInfo {
+    pub name: String,
+    pub description: String,
+    pub input_schema: Value,
+}
+
+/// A connection to one MCP server. Requests are sent one at a time.
+pub struct McpClient {
+    server: String,
+    transport: Mutex<Transport>,
+    next_id: AtomicU64,
+    timeout: Duration,
+}
+
+impl McpClient {
+    /// Open the transport and complete the `initialize` handshake.
+    pub async fn connect(server: &str, config: &McpServerConfig) -> Result<Self> {
+        let timeout = Duration::from_secs(config.timeout_secs.max(1));
+        let transport = tokio::time::timeout(timeout, Transport::open(config))
+            .await
+            .context("Timed out connecting")??;
+        let client = Self {
+            server: server.to_string(),
+            transport: Mutex::new(transport),
+            next_id: AtomicU64::new(1),
+            timeout,
+        };
+        client
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {"name": "zeroclaw", "version": env!("CARGO_PKG_VERSION")}
+                }),
+            )
+            .await?;
+        client
+            .transport
+            .lock()
+            .await
+            .send(&json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+            .await?;
+        Ok(client)
+    }
+
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    /// Send a request and wait for its response, answering pings and
+    /// skipping notifications that arrive in between.
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut transport = self.transport.lock().await;
+        let exchange = async {
+            transport
+                .send(&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+                .await?;
+            loop {
+                let message = transport.receive().await?;
+                if let Some(request) = message.get("method").and_then(Value::as_str) {
+                    if let Some(request_id) = message.get("id") {
+                        let reply = if request == "ping" {
+                            json!({"jsonrpc": "2.0", "id": request_id, "result": {}})
+                        } else {
+                            json!({"jsonrpc": "2.0", "id": request_id, "error": {
+                                "code": METHOD_NOT_FOUND,
+                                "message": format!("{request} is not supported")
+                            }})
+                        };
+                        transport.send(&reply).await?;
+                    }
+                    continue;
+                }
+                if message.get("id").and_then(Value::as_u64) != Some(id) {
+                    continue;
+                }
+                if let Some(error) = message.get("error") {
+                    anyhow::bail!(
+                        "{}",
+                        error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown error")
+                    );
+                }
+                return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+            }
+        };
+        tokio::time::timeout(self.timeout, exchange)
+            .await
+            .with_context(|| format!("{method} timed out after {}s", self.timeout.as_secs()))?
+            .with_context(|| format!("MCP server {}: {method} failed", self.server))
+    }
+
+    /// Every tool the server offers, following pagination.
+    pub async fn list_tools(&self) -> Result<Vec<McpToolInfo>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = cursor
+                .as_ref()
+                .map_or_else(|| json!({}), |c| json!({"cursor": c}));
+            let page = self.request("tools/list", params).await?;
+            for tool in page
+                .get("tools")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let Some(name) = tool.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                tools.push(McpToolInfo {
+                    name: name.to_string(),
+                    description: tool
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    input_schema: tool
+                        .get("inputSchema")
+                        .cloned()
+                        .unwrap_or_else(|| json!({"type": "object"})),
+                });
+            }
+            cursor = page
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .map(String::from);
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    /// Call a tool; text content is joined, other content is named.
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<ToolResult> {
+        let result = self
+            .request("tools/call", json!({"name": name, "arguments": arguments}))
+            .await?;
+        let output = result
+            .get("content")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|item| match item.get("type").and_then(Value::as_str) {
+                Some("text") => item
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                Some("resource") => item
+                    .pointer("/resource/text")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| "[resource]".to_string(), String::from),
+                Some(other) => format!("[{other} content]"),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let is_error = result
+            .get("isError")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        Ok(ToolResult {
+            success: !is_error,
+            error: is_error.then(|| output.clone()),
+            output,
+        })
+    }
+}
+
+/// Connect to one server and register its tools; returns how many.
+async fn register_server(server: &str, config: &McpServerConfig) -> Result<usize> {
+    let client = Arc::new(McpClient::connect(server, config).await?);
+    let tools = client.list_tools().await?;
+    let remote = config.url.is_some();
+    for info in &tools {
+        let tool = McpTool::new(client.clone(), info.clone(), remote);
+        plugin::register_tool(
+            Arc::new(tool),
+            PluginSource::Mcp(server.to_string()),
+            OnCollision::Replace,
+        )?;
+    }
+    Ok(tools.len())
+}
+
+/// Connect to every enabled server in parallel and register their tools.
+/// Returns the number of tools registered.
+pub async fn register_servers(config: &McpConfig) -> usize {
+    let mut connecting = tokio::task::JoinSet::new();
+    for (server, server_config) in config.servers.iter().filter(|(_, c)| c.enabled) {
+        let server = server.clone();
+        let server_config = server_config.clone();
+        connecting.spawn(async move {
+            let registered = register_server(&server, &server_config).await;
+            (server, registered)
+        });
+    }
+    let mut total = 0;
+    while let Some(joined) = connecting.join_next().await {
+        match joined {
+            Ok((server, Ok(count))) => {
+                tracing::info!("MCP server {server}: {count} tool(s)");
+                total += count;
+            }
+            Ok((server, Err(e))) => tracing::warn!("MCP server {server} skipped: {e:#}"),
+            Err(e) => tracing::warn!("MCP connect task failed: {e}"),
+        }
+    }
+    total
+}
+
+pub async fn handle_command(command: crate::McpCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::McpCommands::Serve { tools } => {
+            let server = server::McpServer::for_config(config, &tools)?;
+            // stdout carries the protocol, so progress goes to stderr.
+            eprintln!(
+                "🔌 Serving {} tool(s) over MCP on stdio: {}",
+                server.tool_names().len(),
+                server.tool_names().join(", ")
+            );
+            server
+                .serve(
+                    tokio::io::BufReader::new(tokio::io::stdin()),
+                    tokio::io::stdout(),
+                )
+                .await
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A stdio server in shell: answers initialize, tools/list and
+    /// tools/call by matching on the method.
+    const SERVER: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"method":"initialize"'*)
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"protocolVersion\":\"2024-11-05\",\"capabilities\":{\"tools\":{}}}}" ;;
+    *'"method":"tools/list"'*)
+      echo 'starting up...'
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"tools\":[{\"name\":\"echo\",\"description\":\"Echo text\",\"inputSchema\":{\"type\":\"object\",\"properties\":{\"text\":{\"type\":\"string\"}}}}]}}" ;;
+    *'"method":"tools/call"'*)
+      echo "{\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\",\"params\":{}}"
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"content\":[{\"type\":\"text\",\"text\":\"pong\"}]}}" ;;
+  esac
+done
+"#;
+
+    fn server_config() -> McpServerConfig {
+        McpServerConfig {
+            command: Some("sh".into()),
+            args: vec!["-c".into(), SERVER.into()],
+            timeout_secs: 10,
+            ..McpServerConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn stdio_server_tools_can_be_listed_and_called() {
+        let client = McpClient::connect("local", &server_config()).await.unwrap();
+        let tools = client.list_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "echo");
+        assert_eq!(
+            tools[0].input_schema["properties"]["text"]["type"],
+            "string"
+        );
+
+        let result = client
+            .call_tool("echo", json!({"text": "ping"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "pong");
+    }
+
+    #[tokio::test]
+    async fn servers_register_prefixed_tools() {
+        let config = McpConfig {
+            servers: [
+                ("echoes".to_string(), server_config()),
+                (
+                    "broken".to_string(),
+                    McpServerConfig {
+                        command: Some("/nonexistent/mcp-server".into()),
+                        ..McpServerConfig::default()
+                    },
+                ),
+            ]
+            .into(),
+        };
+        assert_eq!(register_servers(&config).await, 1);
+        let info = plugin::registered_tools()
+            .into_iter()
+            .find(|t| t.name == "echoes__echo")
+            .unwrap();
+        assert_eq!(info.source, PluginSource::Mcp("echoes".into()));
+        assert_eq!(info.capabilities, ["text"]);
+        assert!(plugin::unregister_tool("echoes__echo"));
+    }
+}
@@ -0,0 +1,286 @@
+//! `zeroclaw mcp serve`: our own tools offered to MCP clients such as
+//! Claude Desktop, over newline-delimited JSON-RPC on stdin/stdout. Calls
+//! run under the same autonomy policy and tool middleware as the agent's.
+//! Interactive approvals can't be asked for here, since stdin carries the
+//! protocol, so under `autonomy.level = "interactive"` write-class tools are
+//! withheld instead.
+
+use super::{METHOD_NOT_FOUND, PROTOCOL_VERSION};
+use crate::config::Config;
+use crate::security::{AutonomyLevel, ToolClass};
+use crate::tools::{self, Tool};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_PARAMS: i64 = -32602;
+
+pub struct McpServer {
+    tools: Vec<Box<dyn Tool>>,
+    /// Write-class tools that need an approval nobody can give over MCP.
+    withheld: Vec<String>,
+}
+
+impl McpServer {
+    pub fn new(tools: Vec<Box<dyn Tool>>) -> Self {
+        Self {
+            tools,
+            withheld: Vec::new(),
+        }
+    }
+
+    /// The tools a config enables; with `only`, just the ones named there.
+    pub fn for_config(config: &Config, only: &[String]) -> Result<Self> {
+        let mut all = tools::for_config(config, "mcp")?;
+        if let Some(unknown) = only.iter().find(|n| !all.iter().any(|t| t.name() == *n)) {
+            anyhow::bail!("No tool named '{unknown}' is enabled");
+        }
+        if !only.is_empty() {
+            all.retain(|t| only.iter().any(|n| n == t.name()));
+        }
+        let server = Self::new(all);
+        if config.autonomy.level != AutonomyLevel::Interactive {
+            return Ok(server);
+        }
+        let server = server.withhold_writes(&config.autonomy.approvals.tools);
+        if !server.withheld.is_empty() {
+            eprintln!(
+                "Interactive autonomy: not serving {} (they need approval)",
+                server.withheld.join(", ")
+            );
+        }
+        Ok(server)
+    }
+
+    /// Stop serving write-class tools; calls to them are refused.
+    #[must_use]
+    pub fn withhold_writes(mut self, overrides: &BTreeMap<String, ToolClass>) -> Self {
+        let (write, read): (Vec<Box<dyn Tool>>, _) = std::mem::take(&mut self.tools)
+            .into_iter()
+            .partition(|t| ToolClass::of(t.name(), t.class(), overrides) == ToolClass::Write);
+        self.tools = read;
+        self.withheld
+            .extend(write.iter().map(|t| t.name().to_string()));
+        self
+    }
+
+    pub fn tool_names(&self) -> Vec<&str> {
+        self.tools.iter().map(|t| t.name()).collect()
+    }
+
+    /// Answer one message; `None` for notifications, which get no reply.
+    pub async fn handle(&self, message: &Value) -> Option<Value> {
+        let id = message.get("id")?.clone();
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": params
+                    .get("protocolVersion")
+                    .and_then(Value::as_str)
+                    .unwrap_or(PROTOCOL_VERSION),
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "zeroclaw", "version": env!("CARGO_PKG_VERSION")}
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({"tools": self.list()})),
+            "tools/call" => self.call(&params).await,
+            other => Err((METHOD_NOT_FOUND, format!("{other} is not supported"))),
+        };
+        Some(match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => json!({"jsonrpc": "2.0", "id": id, "error": {
+                "code": code,
+                "message": message
+            }}),
+        })
+    }
+
+    fn list(&self) -> Vec<Value> {
+        self.tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.name(),
+                    "description": tool.description(),
+                    "inputSchema": tool.parameters_schema(),
+                    "annotations": {"openWorldHint": tool.needs_network()}
+                })
+            })
+            .collect()
+    }
+
+    async fn call(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if self.withheld.iter().any(|n| n == name) {
+            return Ok(json!({
+                "content": [{"type": "text", "text": format!(
+                    "{name} needs the user's approval under interactive autonomy, which \
+                     can't be asked for over MCP, so it was not run."
+                )}],
+                "isError": true
+            }));
+        }
+        let tool = self
+            .tools
+            .iter()
+            .find(|t| t.name() == name)
+            .ok_or_else(|| (INVALID_PARAMS, format!("Unknown tool: {name}")))?;
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        // Failures are tool results, so the client's model can read them.
+        let (text, is_error) = match tool.execute(arguments).await {
+            Ok(result) if result.success => (result.output, false),
+            Ok(result) => (result.error.unwrap_or(result.output), true),
+            Err(e) => (format!("{e:#}"), true),
+        };
+        Ok(json!({
+            "content": [{"type": "text", "text": text}],
+            "isError": is_error
+        }))
+    }
+
+    /// Answer messages from `input` on `output` until `input` ends.
+    pub async fn serve<R, W>(&self, input: R, mut output: W) -> Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = input.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let reply = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(&message).await,
+                Err(e) => Some(json!({"jsonrpc": "2.0", "id": null, "error": {
+                    "code": PARSE_ERROR,
+                    "message": e.to_string()
+                }})),
+            };
+            if let Some(reply) = reply {
+                let mut bytes = serde_json::to_vec(&reply)?;
+                bytes.push(b'\n');
+                output.write_all(&bytes).await?;
+                output.flush().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+
+    struct Upper;
+
+    #[async_trait]
+    impl Tool for Upper {
+        fn name(&self) -> &str {
+            "upper"
+        }
+        fn description(&self) -> &str {
+            "Uppercase text"
+        }
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object", "properties": {"text": {"type": "string"}}})
+        }
+        async fn execute(&self, args: Value) -> Result<ToolResult> {
+            let text = args["text"].as_str().unwrap_or_default();
+            Ok(ToolResult {
+                success: !text.is_empty(),
+                output: text.to_uppercase(),
+                error: text.is_empty().then(|| "Missing 'text'".into()),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn session_lists_and_calls_tools() {
+        let server = McpServer::new(vec![Box::new(Upper)]);
+        let input = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}"#,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"upper","arguments":{"text":"hi"}}}"#,
+            r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"upper","arguments":{}}}"#,
+            r#"{"jsonrpc":"2.0","id":5,"method":"tools/call","params":{"name":"shell"}}"#,
+            r#"{"jsonrpc":"2.0","id":6,"method":"resources/list"}"#,
+            "not json",
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+        server.serve(input.as_bytes(), &mut output).await.unwrap();
+
+        let replies: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(replies.len(), 7);
+        assert_eq!(replies[0]["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(replies[1]["result"]["tools"][0]["name"], "upper");
+        assert_eq!(replies[2]["result"]["content"][0]["text"], "HI");
+        assert_eq!(replies[2]["result"]["isError"], false);
+        assert_eq!(replies[3]["result"]["content"][0]["text"], "Missing 'text'");
+        assert_eq!(replies[3]["result"]["isError"], true);
+        assert_eq!(replies[4]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(replies[5]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(replies[6]["error"]["code"], PARSE_ERROR);
+    }
+
+    struct Write;
+
+    #[async_trait]
+    impl Tool for Write {
+        fn name(&self) -> &str {
+            "fs_write"
+        }
+        fn description(&self) -> &str {
+            "Write a file"
+        }
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+        fn class(&self) -> ToolClass {
+            ToolClass::Write
+        }
+        async fn execute(&self, _args: Value) -> Result<ToolResult> {
+            panic!("a withheld tool must not run");
+        }
+    }
+
+    #[tokio::test]
+    async fn interactive_autonomy_withholds_write_tools() {
+        let server = McpServer::new(vec![Box::new(Upper), Box::new(Write)])
+            .withhold_writes(&BTreeMap::new());
+        assert_eq!(server.tool_names(), vec!["upper"]);
+
+        let call = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": {"name": "fs_write", "arguments": {"path": "x"}}});
+        let reply = server.handle(&call).await.unwrap();
+        assert_eq!(reply["result"]["isError"], true);
+        assert!(reply["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("approval"));
+
+        // A tool configured as read-only is still served.
+        let overrides = BTreeMap::from([("fs_write".to_string(), ToolClass::Read)]);
+        let server = McpServer::new(vec![Box::new(Write)]).withhold_writes(&overrides);
+        assert_eq!(server.tool_names(), vec!["fs_write"]);
+    }
+}
@@ -0,0 +1,224 @@
+//! The two MCP transports: newline-delimited JSON-RPC over a child
+//! process's stdin/stdout, and the HTTP+SSE transport, where replies arrive
+//! as `message` events on a long-lived event stream and requests are posted
+//! to the endpoint the stream announces first.
+
+use crate::config::McpServerConfig;
+use anyhow::{Context, Result};
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+use std::pin::Pin;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+type ByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<Vec<u8>>> + Send>>;
+
+pub enum Transport {
+    Stdio {
+        // Held so the server is killed when the transport is dropped.
+        _child: Child,
+        stdin: ChildStdin,
+        stdout: Lines<BufReader<ChildStdout>>,
+    },
+    Sse {
+        client: reqwest::Client,
+        headers: reqwest::header::HeaderMap,
+        /// Where requests are posted
+        endpoint: reqwest::Url,
+        body: ByteStream,
+        events: SseEvents,
+    },
+}
+
+impl Transport {
+    /// Start the server process or open its event stream.
+    pub async fn open(config: &McpServerConfig) -> Result<Self> {
+        match (&config.command, &config.url) {
+            (Some(command), _) => Self::spawn(command, config),
+            (None, Some(url)) => Self::connect(url, config).await,
+            (None, None) => anyhow::bail!("needs a `command` or a `url`"),
+        }
+    }
+
+    fn spawn(command: &str, config: &McpServerConfig) -> Result<Self> {
+        let mut child = Command::new(command)
+            .args(&config.args)
+            .envs(&config.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start `{command}`"))?;
+        let stdin = child.stdin.take().context("No stdin")?;
+        let stdout = child.stdout.take().context("No stdout")?;
+        Ok(Self::Stdio {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+        })
+    }
+
+    async fn connect(url: &str, config: &McpServerConfig) -> Result<Self> {
+        let url = reqwest::Url::parse(url).with_context(|| format!("Invalid URL: {url}"))?;
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &config.headers {
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(name.as_bytes())?,
+                reqwest::header::HeaderValue::from_str(value)?,
+            );
+        }
+        let client = reqwest::Client::new();
+        let response = client
+            .get(url.clone())
+            .headers(headers.clone())
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {url}"))?
+            .error_for_status()?;
+        let mut body: ByteStream = Box::pin(
+            response
+                .bytes_stream()
+                .map(|chunk| chunk.map(|bytes| bytes.to_vec())),
+        );
+        let mut events = SseEvents::default();
+        // The first event names the endpoint for requests.
+        let endpoint = loop {
+            match next_event(&mut body, &mut events).await? {
+                (kind, data) if kind == "endpoint" => break url.join(data.trim())?,
+                _ => {}
+            }
+        };
+        Ok(Self::Sse {
+            client,
+            headers,
+            endpoint,
+            body,
+            events,
+        })
+    }
+
+    pub async fn send(&mut self, message: &Value) -> Result<()> {
+        match self {
+            Self::Stdio { stdin, .. } => {
+                let mut line = serde_json::to_vec(message)?;
+                line.push(b'\n');
+                stdin.write_all(&line).await?;
+                stdin.flush().await?;
+            }
+            Self::Sse {
+                client,
+                headers,
+                endpoint,
+                ..
+            } => {
+                client
+                    .post(endpoint.clone())
+                    .headers(headers.clone())
+                    .json(message)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The next message from the server.
+    pub async fn receive(&mut self) -> Result<Value> {
+        match self {
+            Self::Stdio { stdout, .. } => loop {
+                let line = stdout
+                    .next_line()
+                    .await?
+                    .context("The server closed its output")?;
+                // Servers may log to stdout; only JSON lines are messages.
+                if let Ok(message) = serde_json::from_str(&line) {
+                    return Ok(message);
+                }
+            },
+            Self::Sse { body, events, .. } => loop {
+                let (kind, data) = next_event(body, events).await?;
+                if kind == "message" {
+                    if let Ok(message) = serde_json::from_str(&data) {
+                        return Ok(message);
+                    }
+                }
+            },
+        }
+    }
+}
+
+async fn next_event(body: &mut ByteStream, events: &mut SseEvents) -> Result<(String, String)> {
+    loop {
+        if let Some(event) = events.next_event() {
+            return Ok(event);
+        }
+        let chunk = body.next().await.context("The event stream ended")??;
+        events.feed(&chunk);
+    }
+}
+
+/// Incremental parser for `text/event-stream` bodies.
+#[derive(Debug, Default)]
+pub struct SseEvents {
+    pending: Vec<u8>,
+    kind: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseEvents {
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.pending.extend_from_slice(chunk);
+    }
+
+    /// The next complete event as `(event type, data)`; the type defaults
+    /// to `message`.
+    pub fn next_event(&mut self) -> Option<(String, String)> {
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line[..end]);
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                if self.data.is_empty() {
+                    self.kind = None;
+                    continue;
+                }
+                let kind = self.kind.take().unwrap_or_else(|| "message".into());
+                return Some((kind, std::mem::take(&mut self.data).join("\n")));
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.kind = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_split_across_chunks() {
+        let mut events = SseEvents::default();
+        events.feed(b": keep-alive\n\nevent: endpoint\ndata: /messages?session=1\r\n");
+        assert_eq!(events.next_event(), None);
+        events.feed(b"\r\ndata: {\"id\":1,\ndata: \"result\":{}}\n\n");
+        assert_eq!(
+            events.next_event(),
+            Some(("endpoint".into(), "/messages?session=1".into()))
+        );
+        assert_eq!(
+            events.next_event(),
+            Some(("message".into(), "{\"id\":1,\n\"result\":{}}".into()))
+        );
+        assert_eq!(events.next_event(), None);
+    }
+}
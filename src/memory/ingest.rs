@@ -0,0 +1,515 @@
+//! Document ingestion for retrieval (`zeroclaw memory ingest <path|url>`).
+//!
+//! PDFs, Markdown, HTML, code and plain text are split into chunks and
+//! stored in the sqlite memory under the `document` category, where each
+//! chunk gets an embedding like any other memory. Every chunk starts with a
+//! `[Source: …]` line naming the file or URL and the page or lines it came
+//! from, so the chunks recalled into a prompt carry their own attribution.
+//! Ingesting a source again replaces its earlier chunks.
+
+use super::chunker::chunk_markdown;
+use super::{Memory, MemoryCategory};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Memory category ingested chunks are stored under.
+pub const CATEGORY: &str = "document";
+/// Key prefix of ingested chunks: `doc:<source>#<n>`.
+const KEY_PREFIX: &str = "doc:";
+/// Larger files and downloads are skipped.
+const MAX_DOCUMENT_BYTES: u64 = 20 * 1024 * 1024;
+const FETCH_TIMEOUT_SECS: u64 = 60;
+/// Build output and dependency trees that only add noise.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+const CODE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "ts", "tsx", "jsx", "go", "java", "kt", "c", "h", "cc", "cpp", "hpp", "cs",
+    "rb", "php", "swift", "sh", "sql", "toml", "yaml", "yml", "json",
+];
+
+static SCRIPT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<(script|style|noscript|svg)\b.*?</\s*\w+\s*>").unwrap());
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<h([1-6])\b[^>]*>(.*?)</h[1-6]\s*>").unwrap());
+static LIST_ITEM: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<li\b[^>]*>").unwrap());
+static BLOCK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)</?(p|div|br|tr|ul|ol|li|table|section|article|blockquote|pre|header|footer|main)\b[^>]*>")
+        .unwrap()
+});
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+static BLANK_LINES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
+pub fn category() -> MemoryCategory {
+    MemoryCategory::Custom(CATEGORY.into())
+}
+
+/// How a document is read and split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+    Pdf,
+    Code,
+    Text,
+}
+
+impl Format {
+    /// From a file extension; `None` for files that are not ingested.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        let ext = ext.to_ascii_lowercase();
+        match ext.as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            "pdf" => Some(Self::Pdf),
+            "txt" | "text" | "rst" => Some(Self::Text),
+            _ if CODE_EXTENSIONS.contains(&ext.as_str()) => Some(Self::Code),
+            _ => None,
+        }
+    }
+
+    fn from_path(path: &str) -> Option<Self> {
+        Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// From a download: the content type, then the URL's extension.
+    fn from_response(content_type: &str, url: &str) -> Self {
+        let content_type = content_type.to_ascii_lowercase();
+        if content_type.contains("pdf") {
+            Self::Pdf
+        } else if content_type.contains("html") {
+            Self::Html
+        } else if content_type.contains("markdown") {
+            Self::Markdown
+        } else {
+            let path = url.split(['?', '#']).next().unwrap_or(url);
+            Self::from_path(path).unwrap_or(Self::Text)
+        }
+    }
+}
+
+/// One piece of a document, ready to store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentChunk {
+    /// Page or line range within the source, when known
+    pub location: Option<String>,
+    pub text: String,
+}
+
+impl DocumentChunk {
+    /// The stored form: attribution line, then the text.
+    pub fn render(&self, source: &str) -> String {
+        match &self.location {
+            Some(location) => format!("[Source: {source}, {location}]\n{}", self.text),
+            None => format!("[Source: {source}]\n{}", self.text),
+        }
+    }
+}
+
+/// Markdown-ish text of an HTML page: scripts and styles dropped, headings
+/// kept as `#` lines so the chunker can split on them.
+pub fn html_to_text(html: &str) -> String {
+    let text = SCRIPT.replace_all(html, "");
+    let text = HEADING.replace_all(&text, |caps: &regex::Captures| {
+        let level = caps[1].parse::<usize>().unwrap_or(1);
+        format!(
+            "\n\n{} {}\n\n",
+            "#".repeat(level),
+            TAG.replace_all(&caps[2], "")
+        )
+    });
+    let text = LIST_ITEM.replace_all(&text, "\n- ");
+    let text = BLOCK.replace_all(&text, "\n\n");
+    let text = TAG.replace_all(&text, "");
+    let text = decode_entities(&text);
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    BLANK_LINES
+        .replace_all(lines.join("\n").trim(), "\n\n")
+        .into_owned()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Split extracted text into chunks of at most `max_tokens` (roughly).
+/// PDF text is split per page first (pages end in form feeds); code is cut
+/// on line boundaries and keeps its line numbers.
+pub fn chunk(format: Format, text: &str, max_tokens: usize) -> Vec<DocumentChunk> {
+    match format {
+        Format::Pdf => text
+            .split('\x0c')
+            .enumerate()
+            .flat_map(|(page, body)| {
+                chunk_markdown(body, max_tokens)
+                    .into_iter()
+                    .map(move |c| DocumentChunk {
+                        location: Some(format!("page {}", page + 1)),
+                        text: c.content,
+                    })
+            })
+            .filter(|c| !c.text.is_empty())
+            .collect(),
+        Format::Code => chunk_lines(text, max_tokens * 4),
+        Format::Markdown | Format::Html | Format::Text => chunk_markdown(text, max_tokens)
+            .into_iter()
+            .filter(|c| !c.content.is_empty())
+            .map(|c| DocumentChunk {
+                location: None,
+                text: c.content,
+            })
+            .collect(),
+    }
+}
+
+fn chunk_lines(text: &str, max_chars: usize) -> Vec<DocumentChunk> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut first = 1;
+    let lines: Vec<&str> = text.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        if !current.is_empty() && current.len() + line.len() > max_chars {
+            chunks.push(DocumentChunk {
+                location: Some(format!("lines {first}-{i}")),
+                text: std::mem::take(&mut current).trim_end().to_string(),
+            });
+            first = i + 1;
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        chunks.push(DocumentChunk {
+            location: Some(format!("lines {first}-{}", lines.len())),
+            text: current.trim_end().to_string(),
+        });
+    }
+    chunks
+}
+
+/// Text of a PDF, pages separated by form feeds, via poppler's `pdftotext`.
+async fn pdf_text(path: &Path) -> Result<String> {
+    let output = tokio::process::Command::new("pdftotext")
+        .arg("-layout")
+        .arg(path)
+        .arg("-")
+        .output()
+        .await
+        .context("Reading PDFs needs `pdftotext` (install poppler-utils)")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "pdftotext failed on {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn read_file(path: &Path, format: Format) -> Result<String> {
+    if format == Format::Pdf {
+        return pdf_text(path).await;
+    }
+    let raw = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(if format == Format::Html {
+        html_to_text(&raw)
+    } else {
+        raw
+    })
+}
+
+async fn fetch(url: &str) -> Result<(Format, String)> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+        .build()?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {url}"))?
+        .error_for_status()?;
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_DOCUMENT_BYTES)
+    {
+        anyhow::bail!("{url} is larger than {MAX_DOCUMENT_BYTES} bytes");
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let format = Format::from_response(&content_type, url);
+    let bytes = response.bytes().await?;
+    let text = match format {
+        Format::Pdf => {
+            let tmp = std::env::temp_dir().join(format!("zeroclaw-{}.pdf", uuid::Uuid::new_v4()));
+            tokio::fs::write(&tmp, &bytes).await?;
+            let text = pdf_text(&tmp).await;
+            let _ = tokio::fs::remove_file(&tmp).await;
+            text?
+        }
+        Format::Html => html_to_text(&String::from_utf8_lossy(&bytes)),
+        _ => String::from_utf8_lossy(&bytes).into_owned(),
+    };
+    Ok((format, text))
+}
+
+/// Files under `dir` that can be ingested, sorted.
+fn collect_files(dir: &Path) -> Vec<(PathBuf, Format)> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            if name.starts_with('.') || kind.is_symlink() {
+                continue;
+            }
+            let path = entry.path();
+            if kind.is_dir() {
+                if !SKIPPED_DIRS.contains(&name.as_ref()) {
+                    pending.push(path);
+                }
+                continue;
+            }
+            let small = entry
+                .metadata()
+                .is_ok_and(|m| m.len() <= MAX_DOCUMENT_BYTES);
+            if let Some(format) = Format::from_path(&name).filter(|_| small) {
+                files.push((path, format));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
+/// What an ingest stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    pub documents: usize,
+    pub chunks: usize,
+    /// Documents skipped, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Replace the chunks stored for `source` with `chunks`.
+pub async fn store_document(
+    mem: &dyn Memory,
+    source: &str,
+    chunks: &[DocumentChunk],
+) -> Result<()> {
+    remove_document(mem, source).await?;
+    for (i, chunk) in chunks.iter().enumerate() {
+        mem.store(
+            &format!("{KEY_PREFIX}{source}#{i}"),
+            &chunk.render(source),
+            category(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Forget every chunk of `source`; returns how many there were.
+pub async fn remove_document(mem: &dyn Memory, source: &str) -> Result<usize> {
+    let prefix = format!("{KEY_PREFIX}{source}#");
+    let mut removed = 0;
+    for entry in mem.list(Some(&category())).await? {
+        if entry.key.starts_with(&prefix) && mem.forget(&entry.key).await? {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Ingested sources and their chunk counts, sorted by source.
+pub async fn documents(mem: &dyn Memory) -> Result<Vec<(String, usize)>> {
+    let mut counts = std::collections::BTreeMap::new();
+    for entry in mem.list(Some(&category())).await? {
+        if let Some((source, _)) = entry
+            .key
+            .strip_prefix(KEY_PREFIX)
+            .and_then(|k| k.rsplit_once('#'))
+        {
+            *counts.entry(source.to_string()).or_insert(0) += 1;
+        }
+    }
+    Ok(counts.into_iter().collect())
+}
+
+/// Ingest a file, a directory (every supported file under it) or a URL.
+pub async fn ingest(mem: &dyn Memory, target: &str, max_tokens: usize) -> Result<IngestReport> {
+    let mut report = IngestReport::default();
+    let mut documents = Vec::new();
+    if target.starts_with("http://") || target.starts_with("https://") {
+        let (format, text) = fetch(target).await?;
+        documents.push((target.to_string(), chunk(format, &text, max_tokens)));
+    } else {
+        let path = PathBuf::from(shellexpand::tilde(target).as_ref());
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("No such file or directory: {target}"))?;
+        let files = if path.is_dir() {
+            collect_files(&path)
+        } else {
+            let format = Format::from_path(&path.to_string_lossy()).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Don't know how to read {target} (PDF, Markdown, HTML, code or text)"
+                )
+            })?;
+            vec![(path, format)]
+        };
+        for (file, format) in files {
+            let source = file.display().to_string();
+            match read_file(&file, format).await {
+                Ok(text) => documents.push((source, chunk(format, &text, max_tokens))),
+                Err(e) => report.skipped.push((source, format!("{e:#}"))),
+            }
+        }
+    }
+
+    for (source, chunks) in documents {
+        if chunks.is_empty() {
+            report.skipped.push((source, "no text".into()));
+            continue;
+        }
+        store_document(mem, &source, &chunks).await?;
+        report.documents += 1;
+        report.chunks += chunks.len();
+    }
+    Ok(report)
+}
+
+pub async fn handle_command(command: crate::MemoryCommands, config: &Config) -> Result<()> {
+    if config.memory.backend != "sqlite" {
+        anyhow::bail!(
+            "Document ingestion needs the sqlite memory backend (set [memory].backend = \"sqlite\")"
+        );
+    }
+    let mem = super::create_memory(
+        &config.memory,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    match command {
+        crate::MemoryCommands::Ingest { source } => {
+            let report = ingest(mem.as_ref(), &source, config.memory.chunk_max_tokens).await?;
+            println!(
+                "✅ Ingested {} document(s) as {} chunk(s)",
+                report.documents, report.chunks
+            );
+            for (source, reason) in &report.skipped {
+                println!("   ⚠️  Skipped {source}: {reason}");
+            }
+            if config.memory.embedding_provider == "none" {
+                println!(
+                    "   Set [memory].embedding_provider for semantic retrieval; until then \
+                     chunks are found by keyword."
+                );
+            }
+        }
+        crate::MemoryCommands::Documents => {
+            let documents = documents(mem.as_ref()).await?;
+            if documents.is_empty() {
+                println!("No documents ingested yet. Try `zeroclaw memory ingest <path|url>`.");
+            }
+            for (source, chunks) in documents {
+                println!("  {source} ({chunks} chunks)");
+            }
+        }
+        crate::MemoryCommands::Remove { source } => {
+            let removed = remove_document(mem.as_ref(), &source).await?;
+            println!("🗑  Removed {removed} chunk(s) of {source}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[test]
+    fn html_keeps_text_and_headings() {
+        let html = "<html><head><style>p{color:red}</style><script>alert(1)</script></head>\
+            <body><h2>Deploys</h2><p>No deploys on <b>Fridays</b> &amp; holidays.</p>\
+            <ul><li>Ask #ops</li></ul></body></html>";
+        let text = html_to_text(html);
+        assert_eq!(
+            text,
+            "## Deploys\n\nNo deploys on Fridays & holidays.\n\n- Ask #ops"
+        );
+    }
+
+    #[test]
+    fn pdf_pages_and_code_lines_are_located() {
+        let pdf = chunk(Format::Pdf, "Intro text\x0cSecond page", 200);
+        assert_eq!(pdf[1].location.as_deref(), Some("page 2"));
+        assert_eq!(
+            pdf[1].render("guide.pdf"),
+            "[Source: guide.pdf, page 2]\nSecond page"
+        );
+
+        let code = "fn a() {}\n".repeat(30);
+        let chunks = chunk(Format::Code, &code, 20);
+        assert_eq!(chunks[0].location.as_deref(), Some("lines 1-8"));
+        assert_eq!(chunks[1].location.as_deref(), Some("lines 9-16"));
+    }
+
+    #[tokio::test]
+    async fn ingesting_again_replaces_a_document() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let docs = tmp.path().join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        let runbook = docs.join("runbook.md");
+        std::fs::write(&runbook, "# Deploys\n\nNo deploys on Fridays.").unwrap();
+        std::fs::write(docs.join("logo.png"), [0u8; 4]).unwrap();
+
+        let report = ingest(&mem, &docs.to_string_lossy(), 200).await.unwrap();
+        assert_eq!((report.documents, report.chunks), (1, 1));
+
+        std::fs::write(
+            &runbook,
+            "# Deploys\n\nDeploys any day.\n\n# Rollbacks\n\nUse the button.",
+        )
+        .unwrap();
+        ingest(&mem, &runbook.to_string_lossy(), 200).await.unwrap();
+        let listed = documents(&mem).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].1, 2);
+
+        let hits = mem.recall("rollbacks button", 3).await.unwrap();
+        assert!(hits[0].content.starts_with("[Source: "));
+        assert!(hits[0].content.contains("Use the button."));
+        assert!(mem.recall("Fridays", 3).await.unwrap().is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 pub mod chunker;
 pub mod embeddings;
 pub mod hygiene;
+pub mod ingest;
 pub mod markdown;
 pub mod sqlite;
 pub mod traits;
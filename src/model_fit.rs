@@ -0,0 +1,257 @@
+//! Keep local Ollama models inside the machine's memory.
+//!
+//! A model that is too big for the machine still loads, then swaps or gets
+//! OOM-killed halfway through an answer. Before a run the model's size (from
+//! Ollama's `/api/tags`) is compared with GPU memory, or free RAM without a
+//! GPU. If it will not fit we warn, or with `[ollama].auto_select` switch to
+//! the largest installed variant of the same model that does.
+
+use crate::config::{Config, OllamaConfig};
+use serde::Deserialize;
+use std::process::Command;
+use std::time::Duration;
+
+const OLLAMA_URL: &str = "http://localhost:11434";
+const GIB: u64 = 1024 * 1024 * 1024;
+
+/// A model pulled into the local Ollama store.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct InstalledModel {
+    pub name: String,
+    /// Size of the weights on disk, in bytes
+    pub size: u64,
+}
+
+#[derive(Deserialize)]
+struct Tags {
+    #[serde(default)]
+    models: Vec<InstalledModel>,
+}
+
+/// What to tell the user before the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advice {
+    /// The model to run instead, when auto-selection found one
+    pub model: Option<String>,
+    pub message: String,
+}
+
+/// Memory a model needs once loaded: the weights plus about a fifth again
+/// for the KV cache and runtime buffers.
+fn needed(size: u64) -> u64 {
+    size + size / 5
+}
+
+/// `llama3.1:70b-instruct-q4_K_M` → `llama3.1`.
+fn family(name: &str) -> &str {
+    name.split(':').next().unwrap_or(name)
+}
+
+fn gib(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let gib = bytes as f64 / GIB as f64;
+    format!("{gib:.1} GiB")
+}
+
+/// Memory available for model weights, in bytes.
+fn memory_budget(config: &OllamaConfig) -> Option<u64> {
+    if let Some(gb) = config.memory_gb {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        return Some((gb.max(0.0) * GIB as f64) as u64);
+    }
+    gpu_memory().or_else(system_memory)
+}
+
+/// Total VRAM across NVIDIA GPUs; Ollama splits layers between them.
+fn gpu_memory() -> Option<u64> {
+    let output = Command::new("nvidia-smi")
+        .args(["--query-gpu=memory.total", "--format=csv,noheader,nounits"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mib: u64 = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse::<u64>().ok())
+        .sum();
+    (mib > 0).then_some(mib * 1024 * 1024)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn system_memory() -> Option<u64> {
+    parse_meminfo(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+#[cfg(target_os = "macos")]
+fn system_memory() -> Option<u64> {
+    // Apple silicon shares RAM with the GPU, which may use about three quarters of it.
+    let output = Command::new("sysctl")
+        .args(["-n", "hw.memsize"])
+        .output()
+        .ok()?;
+    let total: u64 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()?;
+    Some(total / 4 * 3)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn system_memory() -> Option<u64> {
+    None
+}
+
+/// `MemAvailable` (or `MemTotal` on old kernels) from `/proc/meminfo`, in bytes.
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn parse_meminfo(meminfo: &str) -> Option<u64> {
+    let field = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let kib = line.strip_prefix(name)?.trim().strip_suffix("kB")?;
+            kib.trim().parse::<u64>().ok()
+        })
+    };
+    field("MemAvailable:")
+        .or_else(|| field("MemTotal:"))
+        .map(|kib| kib * 1024)
+}
+
+/// Compare `wanted` against the budget. `None` when it fits or is not installed.
+fn advise(
+    installed: &[InstalledModel],
+    wanted: &str,
+    budget: u64,
+    auto_select: bool,
+) -> Option<Advice> {
+    let current = installed
+        .iter()
+        .find(|m| m.name == wanted || m.name == format!("{wanted}:latest"))?;
+    let need = needed(current.size);
+    if need <= budget {
+        return None;
+    }
+    let problem = format!(
+        "{wanted} needs about {} but only {} is available",
+        gib(need),
+        gib(budget)
+    );
+    let smaller = installed
+        .iter()
+        .filter(|m| family(&m.name) == family(wanted) && needed(m.size) <= budget)
+        .max_by_key(|m| m.size);
+    Some(match smaller {
+        Some(alt) if auto_select => Advice {
+            model: Some(alt.name.clone()),
+            message: format!(
+                "{problem}; using {} instead (set [ollama].auto_select = false to keep it)",
+                alt.name
+            ),
+        },
+        Some(alt) => Advice {
+            model: None,
+            message: format!(
+                "{problem} — expect swapping or an out-of-memory crash; {} would fit",
+                alt.name
+            ),
+        },
+        None => Advice {
+            model: None,
+            message: format!(
+                "{problem} — expect swapping or an out-of-memory crash; pull a smaller or \
+                 more quantized tag (e.g. `ollama pull {}:3b` or a q4_K_M variant)",
+                family(wanted)
+            ),
+        },
+    })
+}
+
+async fn installed_models() -> Option<Vec<InstalledModel>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .ok()?;
+    let tags: Tags = client
+        .get(format!("{OLLAMA_URL}/api/tags"))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    Some(tags.models)
+}
+
+/// Check an Ollama model against this machine before a run. Other providers,
+/// an unreachable Ollama and unknown memory are all left alone.
+pub async fn check(config: &Config, provider: &str, model: &str) -> Option<Advice> {
+    if provider != "ollama" {
+        return None;
+    }
+    let installed = installed_models().await?;
+    let ollama = config.ollama.clone();
+    let budget = tokio::task::spawn_blocking(move || memory_budget(&ollama))
+        .await
+        .ok()??;
+    advise(&installed, model, budget, config.ollama.auto_select)
+}
+
+/// [`check`] for long-running services: logs the advice and returns the model to use.
+pub async fn resolve(config: &Config, provider: &str, model: String) -> String {
+    match check(config, provider, &model).await {
+        Some(advice) => {
+            tracing::warn!("{}", advice.message);
+            advice.model.unwrap_or(model)
+        }
+        None => model,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(name: &str, gib: u64) -> InstalledModel {
+        InstalledModel {
+            name: name.into(),
+            size: gib * GIB,
+        }
+    }
+
+    #[test]
+    fn oversized_models_fall_back_to_the_largest_variant_that_fits() {
+        let installed = [
+            model("llama3.1:70b", 40),
+            model("llama3.1:8b", 5),
+            model("llama3.1:8b-instruct-q2_K", 3),
+            model("qwen2.5:7b", 4),
+        ];
+        assert_eq!(advise(&installed, "llama3.1:8b", 16 * GIB, true), None);
+        assert_eq!(advise(&installed, "mistral", 1, true), None);
+
+        let switched = advise(&installed, "llama3.1:70b", 16 * GIB, true).unwrap();
+        assert_eq!(switched.model.as_deref(), Some("llama3.1:8b"));
+
+        let warned = advise(&installed, "llama3.1:70b", 16 * GIB, false).unwrap();
+        assert_eq!(warned.model, None);
+        assert!(warned.message.contains("llama3.1:8b would fit"));
+
+        let stuck = advise(&installed, "qwen2.5:7b", 2 * GIB, true).unwrap();
+        assert_eq!(stuck.model, None);
+        assert!(stuck.message.contains("ollama pull qwen2.5:3b"));
+    }
+
+    #[test]
+    fn meminfo_prefers_available_memory() {
+        let meminfo = "MemTotal:       16314020 kB\nMemFree:         1218040 kB\nMemAvailable:    8157010 kB\n";
+        assert_eq!(parse_meminfo(meminfo), Some(8_157_010 * 1024));
+        assert_eq!(
+            parse_meminfo("MemTotal:       1024 kB\n"),
+            Some(1024 * 1024)
+        );
+        assert_eq!(parse_meminfo(""), None);
+    }
+}
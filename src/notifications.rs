@@ -0,0 +1,398 @@
+//! Proactive alerts: one place that decides how to reach people.
+//!
+//! The agent (`notify` tool), heartbeat tasks, evals, inbox triage, meeting
+//! briefs and scripts (`zeroclaw notify`) raise an [`Alert`] with a source
+//! and a [`Severity`], and optionally a channel and recipient. The
+//! `[notifications]` config fills in what is missing, its rules reroute or
+//! mute by source and severity, and `@name` recipients go to the household
+//! member's preferred channel. Alerts below `critical` wait out quiet hours
+//! (queued in the outbox until they end) and focus sessions; everything
+//! goes through the outbox, so a failed send is retried.
+
+use crate::config::{Config, NotificationRule, QuietHours, Severity};
+use crate::outbox::{self, Enqueued, Status};
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "info" => Ok(Self::Info),
+            "warning" | "warn" => Ok(Self::Warning),
+            "critical" => Ok(Self::Critical),
+            other => anyhow::bail!("Unknown severity '{other}' (use info, warning or critical)"),
+        }
+    }
+}
+
+/// Something worth telling someone who didn't ask.
+#[derive(Debug, Clone, Copy)]
+pub struct Alert<'a> {
+    /// What raised it: `agent`, `heartbeat`, `eval`, ...
+    pub source: &'a str,
+    pub severity: Severity,
+    /// Where the source would send it; `[notifications]` fills in the rest
+    pub channel: Option<&'a str>,
+    pub to: Option<&'a str>,
+    pub message: &'a str,
+}
+
+impl<'a> Alert<'a> {
+    pub fn new(source: &'a str, message: &'a str) -> Self {
+        Self {
+            source,
+            severity: Severity::Info,
+            channel: None,
+            to: None,
+            message,
+        }
+    }
+
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    #[must_use]
+    pub fn via(mut self, channel: Option<&'a str>, to: Option<&'a str>) -> Self {
+        self.channel = channel;
+        self.to = to;
+        self
+    }
+}
+
+/// What happened to an alert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Sent {
+        channel: String,
+        to: String,
+    },
+    /// Delivery failed; the outbox retries it
+    Retrying {
+        channel: String,
+        to: String,
+    },
+    /// Queued until quiet hours end
+    Deferred {
+        channel: String,
+        to: String,
+        until: DateTime<Utc>,
+    },
+    /// Kept for the end-of-focus summary
+    Held,
+    /// The same alert went to the same recipient recently
+    Duplicate,
+    Muted,
+    /// No channel or recipient configured for it
+    Unrouted,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sent { channel, to } => write!(f, "Sent to {to} on {channel}."),
+            Self::Retrying { channel, to } => write!(
+                f,
+                "Queued: sending to {to} on {channel} failed and will be retried automatically."
+            ),
+            Self::Deferred { channel, to, until } => write!(
+                f,
+                "Quiet hours: queued for {to} on {channel} until {}.",
+                until.with_timezone(&Local).format("%H:%M")
+            ),
+            Self::Held => f.write_str("Focus session on: held for the end-of-focus summary."),
+            Self::Duplicate => f.write_str("Not sent again: the same alert went out recently."),
+            Self::Muted => f.write_str("Muted by a [notifications] rule."),
+            Self::Unrouted => f.write_str(
+                "Not sent: no channel and recipient for it. Set [notifications] channel and to.",
+            ),
+        }
+    }
+}
+
+fn matches(rule: &NotificationRule, alert: &Alert<'_>) -> bool {
+    rule.source
+        .as_deref()
+        .is_none_or(|s| s == "*" || s.eq_ignore_ascii_case(alert.source))
+        && alert.severity >= rule.min_severity
+}
+
+/// The channel and recipient an alert goes to after the rules, the
+/// defaults and household routing; the outcome instead when it goes nowhere.
+fn route(config: &Config, alert: &Alert<'_>) -> std::result::Result<(String, String), Outcome> {
+    let settings = &config.notifications;
+    let rule = settings.rules.iter().find(|rule| matches(rule, alert));
+    if rule.is_some_and(|rule| rule.mute) {
+        return Err(Outcome::Muted);
+    }
+    let pick = |of_rule: Option<&'_ str>, of_alert, default: &Option<String>| {
+        of_rule
+            .or(of_alert)
+            .or(default.as_deref())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+    };
+    let channel = pick(
+        rule.and_then(|r| r.channel.as_deref()),
+        alert.channel,
+        &settings.channel,
+    );
+    let Some(to) = pick(rule.and_then(|r| r.to.as_deref()), alert.to, &settings.to) else {
+        return Err(Outcome::Unrouted);
+    };
+    let (channel, to) =
+        crate::household::route(config, channel.as_deref().unwrap_or_default(), &to);
+    if channel.is_empty() || to.starts_with('@') {
+        return Err(Outcome::Unrouted);
+    }
+    Ok((channel, to))
+}
+
+fn parse_window(quiet: &QuietHours) -> Result<(NaiveTime, NaiveTime)> {
+    let parse = |raw: &str| {
+        NaiveTime::parse_from_str(raw.trim(), "%H:%M").map_err(|_| {
+            anyhow::anyhow!("[notifications.quiet_hours] times must be HH:MM, not '{raw}'")
+        })
+    };
+    Ok((parse(&quiet.start)?, parse(&quiet.end)?))
+}
+
+/// When the quiet window containing `now` ends; `None` outside it.
+fn quiet_until((start, end): (NaiveTime, NaiveTime), now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let time = now.time();
+    let quiet = if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    };
+    if !quiet {
+        return None;
+    }
+    let day = if time < end {
+        now.date()
+    } else {
+        now.date().succ_opt()?
+    };
+    Some(day.and_time(end))
+}
+
+/// End of the current quiet hours, if it is quiet now.
+fn quiet_now(config: &Config) -> Option<DateTime<Utc>> {
+    let window = match parse_window(config.notifications.quiet_hours.as_ref()?) {
+        Ok(window) => window,
+        Err(e) => {
+            tracing::warn!("Ignoring quiet hours: {e}");
+            return None;
+        }
+    };
+    quiet_until(window, Local::now().naive_local())?
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|until| until.with_timezone(&Utc))
+}
+
+/// Route an alert and queue it: sent now, or held while focusing, or
+/// delivered when quiet hours end. Critical alerts skip both waits.
+pub async fn send(config: &Config, alert: &Alert<'_>) -> Result<Outcome> {
+    let (channel, to) = match route(config, alert) {
+        Ok(target) => target,
+        Err(outcome) => return Ok(outcome),
+    };
+    let urgent = alert.severity == Severity::Critical;
+    if !urgent && crate::focus::hold(config, alert.source, &channel, &to, alert.message) {
+        return Ok(Outcome::Held);
+    }
+    if let Some(until) = quiet_now(config).filter(|_| !urgent) {
+        let queued =
+            outbox::enqueue_after(config, alert.source, &channel, &to, alert.message, until)?;
+        return Ok(match queued {
+            Enqueued::New(_) => Outcome::Deferred { channel, to, until },
+            Enqueued::Duplicate(_) => Outcome::Duplicate,
+        });
+    }
+    let intent = match outbox::enqueue(config, alert.source, &channel, &to, alert.message, None)? {
+        Enqueued::New(intent) => intent,
+        Enqueued::Duplicate(_) => return Ok(Outcome::Duplicate),
+    };
+    let delivered = outbox::deliver(config, &intent.id, |name| {
+        crate::channels::find_channel(config, name)
+    })
+    .await?;
+    Ok(match delivered {
+        Some((Status::Sent, _)) => Outcome::Sent { channel, to },
+        _ => Outcome::Retrying { channel, to },
+    })
+}
+
+/// [`send`] for background jobs: the outcome is logged, not returned.
+pub async fn notify(config: &Config, alert: Alert<'_>) {
+    match send(config, &alert).await {
+        Ok(Outcome::Sent { .. }) => {}
+        Ok(Outcome::Unrouted) => tracing::info!(
+            "{} {} alert not sent (no [notifications] route): {}",
+            alert.source,
+            alert.severity.as_str(),
+            crate::util::truncate_with_ellipsis(alert.message, 200)
+        ),
+        Ok(outcome) => tracing::info!("{} alert: {outcome}", alert.source),
+        Err(e) => tracing::warn!("Failed to queue {} alert: {e:#}", alert.source),
+    }
+}
+
+pub async fn handle_command(
+    config: &Config,
+    message: &str,
+    severity: &str,
+    source: &str,
+    (channel, to): (Option<&str>, Option<&str>),
+) -> Result<()> {
+    let alert = Alert::new(source, message)
+        .severity(severity.parse()?)
+        .via(channel, to);
+    let outcome = send(config, &alert).await?;
+    println!("{outcome}");
+    if outcome == Outcome::Unrouted {
+        anyhow::bail!("Alert was not sent");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{HouseholdMember, NotificationsConfig};
+    use tempfile::TempDir;
+
+    fn at(raw: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight() {
+        let night = parse_window(&QuietHours {
+            start: "22:00".into(),
+            end: "07:00".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            quiet_until(night, at("2026-03-05 23:30")),
+            Some(at("2026-03-06 07:00"))
+        );
+        assert_eq!(
+            quiet_until(night, at("2026-03-06 06:59")),
+            Some(at("2026-03-06 07:00"))
+        );
+        assert_eq!(quiet_until(night, at("2026-03-06 07:00")), None);
+        assert_eq!(quiet_until(night, at("2026-03-06 12:00")), None);
+
+        let lunch = (
+            NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(13, 0, 0).unwrap(),
+        );
+        assert_eq!(
+            quiet_until(lunch, at("2026-03-06 12:15")),
+            Some(at("2026-03-06 13:00"))
+        );
+        assert_eq!(quiet_until(lunch, at("2026-03-06 21:00")), None);
+        assert!(parse_window(&QuietHours {
+            start: "10pm".into(),
+            end: "07:00".into(),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn rules_defaults_and_household_pick_the_route() {
+        let mut config = Config::default();
+        config.household.members.insert(
+            "alice".into(),
+            HouseholdMember {
+                handles: vec!["telegram:111".into(), "slack:U1".into()],
+                channels: vec!["slack".into()],
+                ..HouseholdMember::default()
+            },
+        );
+        let alert = Alert::new("heartbeat", "disk almost full");
+        assert_eq!(route(&config, &alert), Err(Outcome::Unrouted));
+
+        config.notifications = NotificationsConfig {
+            channel: Some("telegram".into()),
+            to: Some("999".into()),
+            rules: vec![
+                NotificationRule {
+                    source: Some("inbox".into()),
+                    mute: true,
+                    ..NotificationRule::default()
+                },
+                NotificationRule {
+                    min_severity: Severity::Critical,
+                    to: Some("@alice".into()),
+                    ..NotificationRule::default()
+                },
+            ],
+            ..NotificationsConfig::default()
+        };
+        assert_eq!(
+            route(&config, &alert),
+            Ok(("telegram".into(), "999".into()))
+        );
+        assert_eq!(
+            route(&config, &alert.via(Some("discord"), Some("42"))),
+            Ok(("discord".into(), "42".into()))
+        );
+        assert_eq!(
+            route(&config, &alert.severity(Severity::Critical)),
+            Ok(("slack".into(), "U1".into()))
+        );
+        assert_eq!(
+            route(&config, &Alert::new("inbox", "3 new")),
+            Err(Outcome::Muted)
+        );
+        assert_eq!(
+            route(&config, &alert.via(None, Some("@bob"))),
+            Err(Outcome::Unrouted)
+        );
+    }
+
+    #[tokio::test]
+    async fn alerts_are_queued_in_the_outbox_once() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.notifications.channel = Some("matrix".into());
+        config.notifications.to = Some("!room".into());
+        let alert = Alert::new("agent", "Backup failed").severity(Severity::Critical);
+
+        let first = send(&config, &alert).await.unwrap();
+        assert_eq!(
+            first,
+            Outcome::Retrying {
+                channel: "matrix".into(),
+                to: "!room".into()
+            }
+        );
+        assert_eq!(send(&config, &alert).await.unwrap(), Outcome::Duplicate);
+        let queued = outbox::list(&config, false).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].source, "agent");
+    }
+}
@@ -0,0 +1,686 @@
+//! OAuth 2.0 sign-in for integrations, in one place.
+//!
+//! `[oauth.<provider>]` registers a client; `google`, `spotify`, `notion`
+//! and `strava` come with their endpoints, anything else names its own.
+//! There are two ways to sign in:
+//!
+//! - `zeroclaw oauth login <provider>`: authorization code with PKCE. The
+//!   browser comes back to the gateway's `/oauth/callback`, which finishes
+//!   the exchange. Only logins the CLI started are accepted there.
+//! - `zeroclaw oauth login <provider> --device`: the device flow, for hosts
+//!   without a browser.
+//!
+//! Tokens are encrypted with the [`SecretStore`] under `~/.zeroclaw/oauth/`.
+//! Integrations call [`OAuthManager::access_token`], which refreshes a token
+//! shortly before it expires.
+
+use crate::config::{Config, OAuthClientConfig, OAuthConfig};
+use crate::security::SecretStore;
+use crate::util::{base64_url_encode, LazyClient};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Tokens this close to expiry are refreshed before use.
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// How long a browser login may take to come back.
+const LOGIN_TTL_SECS: i64 = 600;
+
+const DEVICE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Endpoints and quirks of a provider we know.
+struct Preset {
+    auth_url: &'static str,
+    token_url: &'static str,
+    device_url: Option<&'static str>,
+    scope_separator: &'static str,
+    /// Client credentials as Basic auth and a JSON body (Notion)
+    basic_auth: bool,
+}
+
+fn preset(provider: &str) -> Option<Preset> {
+    let preset = match provider {
+        "google" => Preset {
+            auth_url: "https://accounts.google.com/o/oauth2/v2/auth",
+            token_url: "https://oauth2.googleapis.com/token",
+            device_url: Some("https://oauth2.googleapis.com/device/code"),
+            scope_separator: " ",
+            basic_auth: false,
+        },
+        "spotify" => Preset {
+            auth_url: "https://accounts.spotify.com/authorize",
+            token_url: "https://accounts.spotify.com/api/token",
+            device_url: None,
+            scope_separator: " ",
+            basic_auth: false,
+        },
+        "notion" => Preset {
+            auth_url: "https://api.notion.com/v1/oauth/authorize",
+            token_url: "https://api.notion.com/v1/oauth/token",
+            device_url: None,
+            scope_separator: " ",
+            basic_auth: true,
+        },
+        "strava" => Preset {
+            auth_url: "https://www.strava.com/oauth/authorize",
+            token_url: "https://www.strava.com/oauth/token",
+            device_url: None,
+            scope_separator: ",",
+            basic_auth: false,
+        },
+        _ => return None,
+    };
+    Some(preset)
+}
+
+/// A configured client with its endpoints resolved.
+#[derive(Debug, Clone)]
+pub struct OAuthClient {
+    pub provider: String,
+    client_id: String,
+    client_secret: Option<String>,
+    scopes: Vec<String>,
+    auth_url: String,
+    token_url: String,
+    device_url: Option<String>,
+    scope_separator: &'static str,
+    basic_auth: bool,
+}
+
+impl OAuthClient {
+    pub fn new(provider: &str, config: &OAuthClientConfig) -> Result<Self> {
+        let preset = preset(provider);
+        let pick = |own: &Option<String>, known: Option<&'static str>, what: &str| {
+            own.clone()
+                .or_else(|| known.map(String::from))
+                .with_context(|| format!("[oauth.{provider}] needs {what}"))
+        };
+        Ok(Self {
+            provider: provider.to_string(),
+            client_id: config.client_id.clone(),
+            client_secret: config.client_secret.clone(),
+            scopes: config.scopes.clone(),
+            auth_url: pick(
+                &config.auth_url,
+                preset.as_ref().map(|p| p.auth_url),
+                "auth_url",
+            )?,
+            token_url: pick(
+                &config.token_url,
+                preset.as_ref().map(|p| p.token_url),
+                "token_url",
+            )?,
+            device_url: config
+                .device_url
+                .clone()
+                .or_else(|| preset.as_ref()?.device_url.map(String::from)),
+            scope_separator: preset.as_ref().map_or(" ", |p| p.scope_separator),
+            basic_auth: preset.as_ref().is_some_and(|p| p.basic_auth),
+        })
+    }
+
+    fn scope(&self) -> String {
+        self.scopes.join(self.scope_separator)
+    }
+
+    /// Where to send the browser to sign in.
+    pub fn authorize_url(
+        &self,
+        redirect_url: &str,
+        state: &str,
+        challenge: &str,
+    ) -> Result<String> {
+        let mut url = reqwest::Url::parse(&self.auth_url)
+            .with_context(|| format!("Invalid auth_url: {}", self.auth_url))?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &self.client_id)
+                .append_pair("redirect_uri", redirect_url)
+                .append_pair("state", state)
+                .append_pair("code_challenge", challenge)
+                .append_pair("code_challenge_method", "S256");
+            if !self.scopes.is_empty() {
+                query.append_pair("scope", &self.scope());
+            }
+            if self.provider == "google" {
+                // Google only hands out a refresh token when asked for offline access.
+                query
+                    .append_pair("access_type", "offline")
+                    .append_pair("prompt", "consent");
+            }
+        }
+        Ok(url.into())
+    }
+
+    fn token_request(
+        &self,
+        http: &LazyClient,
+        mut params: Vec<(&'static str, String)>,
+    ) -> reqwest::RequestBuilder {
+        let request = http.post(&self.token_url);
+        if self.basic_auth {
+            let body: serde_json::Map<String, Value> = params
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), Value::String(v)))
+                .collect();
+            return request
+                .basic_auth(&self.client_id, self.client_secret.as_deref())
+                .json(&body);
+        }
+        params.push(("client_id", self.client_id.clone()));
+        if let Some(secret) = &self.client_secret {
+            params.push(("client_secret", secret.clone()));
+        }
+        request.form(&params)
+    }
+}
+
+/// Stored credentials for one provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// `None` for tokens that do not expire (Notion)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl Token {
+    /// Parse a token endpoint response. Refreshes may leave out the refresh
+    /// token, in which case `previous` is kept.
+    fn from_response(body: &Value, previous: Option<String>, now: DateTime<Utc>) -> Result<Self> {
+        let access_token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .context("Token response has no access_token")?
+            .to_string();
+        // Most providers say how long the token lasts; Strava says when it ends.
+        let expires_at = body
+            .get("expires_in")
+            .and_then(Value::as_i64)
+            .map(|secs| now + chrono::Duration::seconds(secs))
+            .or_else(|| {
+                body.get("expires_at")
+                    .and_then(Value::as_i64)
+                    .and_then(|at| DateTime::from_timestamp(at, 0))
+            });
+        Ok(Self {
+            access_token,
+            refresh_token: body
+                .get("refresh_token")
+                .and_then(Value::as_str)
+                .map(String::from)
+                .or(previous),
+            expires_at,
+            scope: body.get("scope").and_then(Value::as_str).map(String::from),
+        })
+    }
+
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|at| at - chrono::Duration::seconds(REFRESH_MARGIN_SECS) <= now)
+    }
+}
+
+/// A browser login the CLI started and the gateway callback finishes.
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingLogin {
+    provider: String,
+    verifier: String,
+    created_at: DateTime<Utc>,
+}
+
+/// A device-flow login waiting for the user to enter the code.
+#[derive(Debug, Clone)]
+pub struct DeviceLogin {
+    pub user_code: String,
+    pub verification_uri: String,
+    device_code: String,
+    interval: Duration,
+    expires_at: DateTime<Utc>,
+}
+
+/// Signs in, stores and refreshes tokens for every `[oauth.*]` client.
+pub struct OAuthManager {
+    config: OAuthConfig,
+    dir: PathBuf,
+    secrets: SecretStore,
+    http: LazyClient,
+    /// One refresh at a time, so a rotated refresh token is not used twice
+    refresh: tokio::sync::Mutex<()>,
+}
+
+impl OAuthManager {
+    pub fn new(config: &Config) -> Self {
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        Self {
+            config: config.oauth.clone(),
+            dir: zeroclaw_dir.join("oauth"),
+            secrets: SecretStore::new(&zeroclaw_dir, config.secrets.encrypt),
+            http: LazyClient::new(Duration::from_secs(30)),
+            refresh: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn client(&self, provider: &str) -> Result<OAuthClient> {
+        let config = self
+            .config
+            .clients
+            .get(provider)
+            .with_context(|| format!("No [oauth.{provider}] client configured"))?;
+        OAuthClient::new(provider, config)
+    }
+
+    /// Providers with a configured client.
+    pub fn providers(&self) -> impl Iterator<Item = &str> {
+        self.config.clients.keys().map(String::as_str)
+    }
+
+    fn path(&self, name: &str, extension: &str) -> Result<PathBuf> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            anyhow::bail!("Invalid OAuth name: {name:?}");
+        }
+        Ok(self.dir.join(format!("{name}.{extension}")))
+    }
+
+    fn write_private(&self, path: &Path, contents: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        std::fs::write(path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        if let Err(e) = crate::platform::restrict_to_owner(path) {
+            tracing::warn!("{e}");
+        }
+        Ok(())
+    }
+
+    /// The stored token for `provider`, if signed in.
+    pub fn token(&self, provider: &str) -> Result<Option<Token>> {
+        let path = self.path(provider, "token")?;
+        let Ok(stored) = std::fs::read_to_string(&path) else {
+            return Ok(None);
+        };
+        let json = self
+            .secrets
+            .decrypt(stored.trim())
+            .with_context(|| format!("Failed to decrypt {}", path.display()))?;
+        Ok(Some(serde_json::from_str(&json)?))
+    }
+
+    fn save(&self, provider: &str, token: &Token) -> Result<()> {
+        let sealed = self.secrets.encrypt(&serde_json::to_string(token)?)?;
+        self.write_private(&self.path(provider, "token")?, &sealed)
+    }
+
+    /// Forget the token for `provider`. `false` when there was none.
+    pub fn logout(&self, provider: &str) -> Result<bool> {
+        match std::fs::remove_file(self.path(provider, "token")?) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Start a browser login: returns the URL to open and the login's state.
+    pub fn begin_login(&self, provider: &str) -> Result<(String, String)> {
+        let client = self.client(provider)?;
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let verifier = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        let url = client.authorize_url(
+            &self.config.redirect_url,
+            &state,
+            &pkce_challenge(&verifier),
+        )?;
+        let pending = PendingLogin {
+            provider: provider.to_string(),
+            verifier,
+            created_at: Utc::now(),
+        };
+        self.write_private(
+            &self.path(&state, "pending")?,
+            &serde_json::to_string(&pending)?,
+        )?;
+        Ok((url, state))
+    }
+
+    /// Whether the browser login `state` is still waiting for its callback.
+    pub fn login_pending(&self, state: &str) -> bool {
+        self.path(state, "pending").is_ok_and(|path| path.exists())
+    }
+
+    /// Drop the browser login `state`, e.g. when the user denied access.
+    pub fn cancel_login(&self, state: &str) {
+        if let Ok(path) = self.path(state, "pending") {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Finish the browser login `state` with the provider's `code`. Returns
+    /// the provider signed in to.
+    pub async fn complete_login(&self, state: &str, code: &str) -> Result<String> {
+        let path = self.path(state, "pending")?;
+        let raw = std::fs::read_to_string(&path).map_err(|_| {
+            anyhow::anyhow!("Unknown or expired login; run `zeroclaw oauth login` again")
+        })?;
+        // A state is good for one callback, whatever its outcome.
+        let _ = std::fs::remove_file(&path);
+        let pending: PendingLogin = serde_json::from_str(&raw)?;
+        if Utc::now() - pending.created_at > chrono::Duration::seconds(LOGIN_TTL_SECS) {
+            anyhow::bail!("Login expired; run `zeroclaw oauth login` again");
+        }
+        let client = self.client(&pending.provider)?;
+        let params = vec![
+            ("grant_type", "authorization_code".to_string()),
+            ("code", code.to_string()),
+            ("redirect_uri", self.config.redirect_url.clone()),
+            ("code_verifier", pending.verifier),
+        ];
+        let token = self.exchange(&client, params, None).await?;
+        self.save(&pending.provider, &token)?;
+        Ok(pending.provider)
+    }
+
+    /// Start a device-flow login; show the user its code and URI.
+    pub async fn begin_device_login(&self, provider: &str) -> Result<DeviceLogin> {
+        let client = self.client(provider)?;
+        let device_url = client.device_url.as_deref().with_context(|| {
+            format!("{provider} has no device flow; set device_url or sign in with the browser")
+        })?;
+        let mut params = vec![("client_id", client.client_id.clone())];
+        if !client.scopes.is_empty() {
+            params.push(("scope", client.scope()));
+        }
+        let body = send(self.http.post(device_url).form(&params)).await?;
+        let field = |name: &str| body.get(name).and_then(Value::as_str).map(String::from);
+        Ok(DeviceLogin {
+            user_code: field("user_code").context("Device response has no user_code")?,
+            // Google calls it verification_url.
+            verification_uri: field("verification_uri")
+                .or_else(|| field("verification_url"))
+                .context("Device response has no verification_uri")?,
+            device_code: field("device_code").context("Device response has no device_code")?,
+            interval: Duration::from_secs(
+                body.get("interval").and_then(Value::as_u64).unwrap_or(5),
+            ),
+            expires_at: Utc::now()
+                + chrono::Duration::seconds(
+                    body.get("expires_in")
+                        .and_then(Value::as_i64)
+                        .unwrap_or(LOGIN_TTL_SECS),
+                ),
+        })
+    }
+
+    /// Poll until the user has entered the code of `login`, then store the token.
+    pub async fn finish_device_login(&self, provider: &str, login: &DeviceLogin) -> Result<()> {
+        let client = self.client(provider)?;
+        let mut interval = login.interval;
+        while Utc::now() < login.expires_at {
+            tokio::time::sleep(interval).await;
+            let params = vec![
+                ("grant_type", DEVICE_GRANT.to_string()),
+                ("device_code", login.device_code.clone()),
+            ];
+            let response = client.token_request(&self.http, params).send().await?;
+            let body: Value = response.json().await.unwrap_or_default();
+            match body.get("error").and_then(Value::as_str) {
+                None => {
+                    let token = Token::from_response(&body, None, Utc::now())?;
+                    return self.save(provider, &token);
+                }
+                Some("authorization_pending") => {}
+                Some("slow_down") => interval += Duration::from_secs(5),
+                Some(error) => {
+                    anyhow::bail!("Device login failed: {}", describe_error(&body, error))
+                }
+            }
+        }
+        anyhow::bail!("Device login expired before the code was entered")
+    }
+
+    /// A valid access token for `provider`, refreshed first if it is about
+    /// to expire. This is what integrations call.
+    pub async fn access_token(&self, provider: &str) -> Result<String> {
+        let token = self.token(provider)?.with_context(|| {
+            format!("Not signed in to {provider}; run `zeroclaw oauth login {provider}`")
+        })?;
+        if !token.needs_refresh(Utc::now()) {
+            return Ok(token.access_token);
+        }
+        let _guard = self.refresh.lock().await;
+        // Another caller may have refreshed while we waited.
+        let token = self.token(provider)?.unwrap_or(token);
+        if !token.needs_refresh(Utc::now()) {
+            return Ok(token.access_token);
+        }
+        let refresh_token = token.refresh_token.clone().with_context(|| {
+            format!("{provider} token expired and cannot be refreshed; run `zeroclaw oauth login {provider}`")
+        })?;
+        let client = self.client(provider)?;
+        let params = vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token.clone()),
+        ];
+        let refreshed = self.exchange(&client, params, Some(refresh_token)).await?;
+        self.save(provider, &refreshed)?;
+        tracing::debug!(provider, "OAuth token refreshed");
+        Ok(refreshed.access_token)
+    }
+
+    async fn exchange(
+        &self,
+        client: &OAuthClient,
+        params: Vec<(&'static str, String)>,
+        previous_refresh: Option<String>,
+    ) -> Result<Token> {
+        let body = send(client.token_request(&self.http, params)).await?;
+        Token::from_response(&body, previous_refresh, Utc::now())
+    }
+}
+
+/// Send `request`, failing with the provider's error description.
+async fn send(request: reqwest::RequestBuilder) -> Result<Value> {
+    let response = request.send().await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let error = body
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        anyhow::bail!(
+            "OAuth request failed ({status}): {}",
+            describe_error(&body, error)
+        );
+    }
+    Ok(body)
+}
+
+fn describe_error(body: &Value, error: &str) -> String {
+    match body.get("error_description").and_then(Value::as_str) {
+        Some(description) => format!("{error}: {description}"),
+        None => error.to_string(),
+    }
+}
+
+/// The S256 PKCE challenge for `verifier`.
+fn pkce_challenge(verifier: &str) -> String {
+    base64_url_encode(&Sha256::digest(verifier.as_bytes()))
+}
+
+pub async fn handle_command(command: crate::OAuthCommands, config: &Config) -> Result<()> {
+    let manager = OAuthManager::new(config);
+    match command {
+        crate::OAuthCommands::Status => {
+            if config.oauth.clients.is_empty() {
+                println!("No OAuth clients configured.");
+                println!("\nAdd one to config.toml:");
+                println!("  [oauth.google]");
+                println!("  client_id = \"...\"");
+                println!("  scopes = [\"https://www.googleapis.com/auth/calendar.readonly\"]");
+                return Ok(());
+            }
+            println!("🔑 OAuth clients:");
+            let now = Utc::now();
+            for provider in manager.providers() {
+                let state = match manager.token(provider) {
+                    Ok(Some(token)) => match token.expires_at {
+                        None => "signed in".to_string(),
+                        Some(_) if !token.needs_refresh(now) => "signed in".to_string(),
+                        Some(_) if token.refresh_token.is_some() => {
+                            "signed in (refreshes on next use)".to_string()
+                        }
+                        Some(at) => format!("expired {}", at.to_rfc3339()),
+                    },
+                    Ok(None) => "not signed in".to_string(),
+                    Err(e) => format!("unreadable: {e}"),
+                };
+                println!("- {provider}: {state}");
+            }
+            Ok(())
+        }
+        crate::OAuthCommands::Login { provider, device } => {
+            if device {
+                let login = manager.begin_device_login(&provider).await?;
+                println!(
+                    "Open {} and enter the code: {}",
+                    login.verification_uri, login.user_code
+                );
+                manager.finish_device_login(&provider, &login).await?;
+            } else {
+                let (url, state) = manager.begin_login(&provider)?;
+                println!("Open this URL to sign in to {provider}:\n\n  {url}\n");
+                println!(
+                    "Waiting for the gateway at {} (start it with `zeroclaw gateway` or `zeroclaw daemon`)...",
+                    config.oauth.redirect_url
+                );
+                let deadline = Utc::now() + chrono::Duration::seconds(LOGIN_TTL_SECS);
+                while manager.login_pending(&state) {
+                    if Utc::now() > deadline {
+                        manager.cancel_login(&state);
+                        anyhow::bail!("Timed out waiting for the sign-in to finish");
+                    }
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                if manager.token(&provider)?.is_none() {
+                    anyhow::bail!("Sign-in failed; the browser shows why");
+                }
+            }
+            println!("✅ Signed in to {provider}");
+            Ok(())
+        }
+        crate::OAuthCommands::Logout { provider } => {
+            if manager.logout(&provider)? {
+                println!("✅ Signed out of {provider}");
+            } else {
+                println!("Not signed in to {provider}");
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manager(tmp: &TempDir) -> OAuthManager {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.oauth.clients.insert(
+            "strava".into(),
+            OAuthClientConfig {
+                client_id: "42".into(),
+                client_secret: Some("shh".into()),
+                scopes: vec!["read".into(), "activity:read".into()],
+                ..OAuthClientConfig::default()
+            },
+        );
+        OAuthManager::new(&config)
+    }
+
+    #[test]
+    fn pkce_challenge_matches_rfc_7636() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn presets_fill_in_endpoints_and_logins_are_single_use() {
+        let tmp = TempDir::new().unwrap();
+        let manager = manager(&tmp);
+        let (url, state) = manager.begin_login("strava").unwrap();
+        assert!(url.starts_with("https://www.strava.com/oauth/authorize?response_type=code"));
+        assert!(url.contains("client_id=42"));
+        assert!(url.contains("scope=read%2Cactivity%3Aread"));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains(&format!("state={state}")));
+        assert!(manager.login_pending(&state));
+        assert!(!url.contains("shh"));
+
+        assert!(manager.client("spotify").is_err());
+        assert!(manager.path("../evil", "token").is_err());
+        let err = OAuthClient::new("acme", &OAuthClientConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("auth_url"));
+    }
+
+    #[test]
+    fn tokens_are_stored_encrypted_and_refresh_before_expiry() {
+        let tmp = TempDir::new().unwrap();
+        let manager = manager(&tmp);
+        let now = Utc::now();
+        let token = Token::from_response(
+            &serde_json::json!({"access_token": "at-1", "refresh_token": "rt-1", "expires_in": 3600}),
+            None,
+            now,
+        )
+        .unwrap();
+        assert!(!token.needs_refresh(now));
+        assert!(token.needs_refresh(now + chrono::Duration::minutes(59)));
+
+        manager.save("strava", &token).unwrap();
+        let raw = std::fs::read_to_string(tmp.path().join("oauth/strava.token")).unwrap();
+        assert!(!raw.contains("at-1"));
+        assert_eq!(manager.token("strava").unwrap(), Some(token));
+
+        // Strava-style absolute expiry; the refresh token is kept when not returned.
+        let refreshed = Token::from_response(
+            &serde_json::json!({"access_token": "at-2", "expires_at": 1_900_000_000}),
+            Some("rt-1".into()),
+            now,
+        )
+        .unwrap();
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("rt-1"));
+        assert_eq!(refreshed.expires_at.unwrap().timestamp(), 1_900_000_000);
+
+        assert!(manager.logout("strava").unwrap());
+        assert!(!manager.logout("strava").unwrap());
+    }
+}
@@ -34,6 +34,30 @@ impl Observer for LogObserver {
             ObserverEvent::ChannelMessage { channel, direction } => {
                 info!(channel = %channel, direction = %direction, "channel.message");
             }
+            ObserverEvent::ProviderUsage {
+                provider,
+                model,
+                responses,
+                prompt_chars,
+                response_chars,
+            } => {
+                info!(
+                    provider = %provider,
+                    model = %model,
+                    responses,
+                    prompt_chars,
+                    response_chars,
+                    "provider.usage"
+                );
+            }
+            ObserverEvent::CronRun {
+                job,
+                duration,
+                success,
+            } => {
+                let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+                info!(job = %job, duration_ms = ms, success = success, "cron.run");
+            }
             ObserverEvent::HeartbeatTick => {
                 info!("heartbeat.tick");
             }
@@ -1,11 +1,13 @@
 pub mod log;
 pub mod multi;
 pub mod noop;
+pub mod prometheus;
 pub mod traits;
 
 pub use self::log::LogObserver;
 pub use noop::NoopObserver;
-pub use traits::{Observer, ObserverEvent};
+pub use prometheus::PrometheusObserver;
+pub use traits::{Observer, ObserverEvent, ObserverMetric};
 
 use crate::config::ObservabilityConfig;
 
@@ -14,6 +16,7 @@ pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
         "none" | "noop" => Box::new(NoopObserver),
+        "prometheus" => Box::new(PrometheusObserver),
         _ => {
             tracing::warn!(
                 "Unknown observability backend '{}', falling back to noop",
@@ -32,6 +35,7 @@ mod tests {
     fn factory_none_returns_noop() {
         let cfg = ObservabilityConfig {
             backend: "none".into(),
+            ..ObservabilityConfig::default()
         };
         assert_eq!(create_observer(&cfg).name(), "noop");
     }
@@ -40,6 +44,7 @@ mod tests {
     fn factory_noop_returns_noop() {
         let cfg = ObservabilityConfig {
             backend: "noop".into(),
+            ..ObservabilityConfig::default()
         };
         assert_eq!(create_observer(&cfg).name(), "noop");
     }
@@ -48,14 +53,25 @@ mod tests {
     fn factory_log_returns_log() {
         let cfg = ObservabilityConfig {
             backend: "log".into(),
+            ..ObservabilityConfig::default()
         };
         assert_eq!(create_observer(&cfg).name(), "log");
     }
 
     #[test]
-    fn factory_unknown_falls_back_to_noop() {
+    fn factory_prometheus_returns_prometheus() {
         let cfg = ObservabilityConfig {
             backend: "prometheus".into(),
+            ..ObservabilityConfig::default()
+        };
+        assert_eq!(create_observer(&cfg).name(), "prometheus");
+    }
+
+    #[test]
+    fn factory_unknown_falls_back_to_noop() {
+        let cfg = ObservabilityConfig {
+            backend: "otel".into(),
+            ..ObservabilityConfig::default()
         };
         assert_eq!(create_observer(&cfg).name(), "noop");
     }
@@ -64,6 +80,7 @@ mod tests {
     fn factory_empty_string_falls_back_to_noop() {
         let cfg = ObservabilityConfig {
             backend: String::new(),
+            ..ObservabilityConfig::default()
         };
         assert_eq!(create_observer(&cfg).name(), "noop");
     }
@@ -72,6 +89,7 @@ mod tests {
     fn factory_garbage_falls_back_to_noop() {
         let cfg = ObservabilityConfig {
             backend: "xyzzy_garbage_123".into(),
+            ..ObservabilityConfig::default()
         };
         assert_eq!(create_observer(&cfg).name(), "noop");
     }
@@ -0,0 +1,345 @@
+use super::traits::{Observer, ObserverEvent, ObserverMetric};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the latency histogram buckets
+const BUCKETS: [f64; 11] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+/// Name and help text of every metric, in exposition order
+const METRICS: [(&str, &str, &str); 16] = [
+    (
+        "zeroclaw_agent_turns_total",
+        "counter",
+        "Agent turns completed",
+    ),
+    (
+        "zeroclaw_agent_turn_duration_seconds",
+        "histogram",
+        "Wall time of an agent turn",
+    ),
+    (
+        "zeroclaw_tokens_used_total",
+        "counter",
+        "Tokens reported by providers",
+    ),
+    (
+        "zeroclaw_tool_calls_total",
+        "counter",
+        "Tool invocations by outcome",
+    ),
+    (
+        "zeroclaw_tool_duration_seconds",
+        "histogram",
+        "Wall time of a tool invocation",
+    ),
+    (
+        "zeroclaw_provider_request_duration_seconds",
+        "histogram",
+        "Latency of provider requests",
+    ),
+    (
+        "zeroclaw_provider_responses_total",
+        "counter",
+        "Responses served per provider and model",
+    ),
+    (
+        "zeroclaw_provider_prompt_chars_total",
+        "counter",
+        "Prompt characters sent per provider and model",
+    ),
+    (
+        "zeroclaw_provider_response_chars_total",
+        "counter",
+        "Response characters received per provider and model",
+    ),
+    (
+        "zeroclaw_channel_messages_total",
+        "counter",
+        "Channel messages by direction",
+    ),
+    (
+        "zeroclaw_cron_runs_total",
+        "counter",
+        "Cron job runs by outcome",
+    ),
+    (
+        "zeroclaw_cron_run_duration_seconds",
+        "histogram",
+        "Wall time of a cron job run",
+    ),
+    (
+        "zeroclaw_heartbeat_ticks_total",
+        "counter",
+        "Heartbeat ticks",
+    ),
+    ("zeroclaw_errors_total", "counter", "Errors by component"),
+    (
+        "zeroclaw_active_sessions",
+        "gauge",
+        "Sessions currently active",
+    ),
+    (
+        "zeroclaw_queue_depth",
+        "gauge",
+        "Messages waiting to be processed",
+    ),
+];
+
+type Labels = Vec<(&'static str, String)>;
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    /// Counters and gauges
+    values: BTreeMap<(&'static str, Labels), f64>,
+    histograms: BTreeMap<(&'static str, Labels), Histogram>,
+}
+
+impl Registry {
+    fn add(&mut self, name: &'static str, labels: Labels, by: f64) {
+        *self.values.entry((name, labels)).or_default() += by;
+    }
+
+    fn set(&mut self, name: &'static str, value: f64) {
+        self.values.insert((name, Vec::new()), value);
+    }
+
+    fn observe(&mut self, name: &'static str, labels: Labels, duration: Duration) {
+        self.histograms
+            .entry((name, labels))
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+}
+
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+fn with_registry(update: impl FnOnce(&mut Registry)) {
+    let registry = REGISTRY.get_or_init(|| Mutex::new(Registry::default()));
+    update(
+        &mut registry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+    );
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+fn label_set(labels: &[(&str, String)], extra: Option<(&str, &str)>) -> String {
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| (*k, v.as_str()))
+        .chain(extra)
+        .map(|(k, v)| format!("{k}=\"{}\"", escape(v)))
+        .collect();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// Everything recorded in this process, in the Prometheus text format.
+pub fn render() -> String {
+    let mut out = String::new();
+    with_registry(|registry| {
+        for (name, kind, help) in METRICS {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for ((_, labels), value) in registry.values.iter().filter(|((n, _), _)| *n == name) {
+                let _ = writeln!(out, "{name}{} {value}", label_set(labels, None));
+            }
+            for ((_, labels), histogram) in
+                registry.histograms.iter().filter(|((n, _), _)| *n == name)
+            {
+                for (bound, count) in BUCKETS.iter().zip(histogram.buckets) {
+                    let le = bound.to_string();
+                    let _ = writeln!(
+                        out,
+                        "{name}_bucket{} {count}",
+                        label_set(labels, Some(("le", &le)))
+                    );
+                }
+                let _ = writeln!(
+                    out,
+                    "{name}_bucket{} {}",
+                    label_set(labels, Some(("le", "+Inf"))),
+                    histogram.count
+                );
+                let labels = label_set(labels, None);
+                let _ = writeln!(out, "{name}_sum{labels} {}", histogram.sum);
+                let _ = writeln!(out, "{name}_count{labels} {}", histogram.count);
+            }
+        }
+    });
+    out
+}
+
+/// Prometheus observer — records into a process-wide registry that the
+/// gateway (or the dedicated metrics port) serves at `/metrics`.
+pub struct PrometheusObserver;
+
+impl Observer for PrometheusObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        with_registry(|r| match event {
+            ObserverEvent::AgentStart { .. } => {}
+            ObserverEvent::AgentEnd {
+                duration,
+                tokens_used,
+            } => {
+                r.add("zeroclaw_agent_turns_total", Vec::new(), 1.0);
+                r.observe(
+                    "zeroclaw_agent_turn_duration_seconds",
+                    Vec::new(),
+                    *duration,
+                );
+                if let Some(tokens) = tokens_used {
+                    #[allow(clippy::cast_precision_loss)]
+                    r.add("zeroclaw_tokens_used_total", Vec::new(), *tokens as f64);
+                }
+            }
+            ObserverEvent::ToolCall {
+                tool,
+                duration,
+                success,
+            } => {
+                let outcome = if *success { "success" } else { "failure" };
+                r.add(
+                    "zeroclaw_tool_calls_total",
+                    vec![("tool", tool.clone()), ("outcome", outcome.into())],
+                    1.0,
+                );
+                r.observe(
+                    "zeroclaw_tool_duration_seconds",
+                    vec![("tool", tool.clone())],
+                    *duration,
+                );
+            }
+            ObserverEvent::ChannelMessage { channel, direction } => r.add(
+                "zeroclaw_channel_messages_total",
+                vec![
+                    ("channel", channel.clone()),
+                    ("direction", direction.clone()),
+                ],
+                1.0,
+            ),
+            ObserverEvent::ProviderUsage {
+                provider,
+                model,
+                responses,
+                prompt_chars,
+                response_chars,
+            } => {
+                let labels: Labels = vec![("provider", provider.clone()), ("model", model.clone())];
+                #[allow(clippy::cast_precision_loss)]
+                for (name, value) in [
+                    ("zeroclaw_provider_responses_total", *responses),
+                    ("zeroclaw_provider_prompt_chars_total", *prompt_chars),
+                    ("zeroclaw_provider_response_chars_total", *response_chars),
+                ] {
+                    r.add(name, labels.clone(), value as f64);
+                }
+            }
+            ObserverEvent::CronRun {
+                job,
+                duration,
+                success,
+            } => {
+                let outcome = if *success { "success" } else { "failure" };
+                r.add(
+                    "zeroclaw_cron_runs_total",
+                    vec![("job", job.clone()), ("outcome", outcome.into())],
+                    1.0,
+                );
+                r.observe("zeroclaw_cron_run_duration_seconds", Vec::new(), *duration);
+            }
+            ObserverEvent::HeartbeatTick => {
+                r.add("zeroclaw_heartbeat_ticks_total", Vec::new(), 1.0);
+            }
+            ObserverEvent::Error { component, .. } => r.add(
+                "zeroclaw_errors_total",
+                vec![("component", component.clone())],
+                1.0,
+            ),
+        });
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        with_registry(|r| match metric {
+            ObserverMetric::RequestLatency(d) => {
+                r.observe("zeroclaw_provider_request_duration_seconds", Vec::new(), *d);
+            }
+            #[allow(clippy::cast_precision_loss)]
+            ObserverMetric::TokensUsed(t) => {
+                r.add("zeroclaw_tokens_used_total", Vec::new(), *t as f64);
+            }
+            #[allow(clippy::cast_precision_loss)]
+            ObserverMetric::ActiveSessions(s) => r.set("zeroclaw_active_sessions", *s as f64),
+            #[allow(clippy::cast_precision_loss)]
+            ObserverMetric::QueueDepth(d) => r.set("zeroclaw_queue_depth", *d as f64),
+        });
+    }
+
+    fn name(&self) -> &str {
+        "prometheus"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_render_as_prometheus_text() {
+        let obs = PrometheusObserver;
+        obs.record_event(&ObserverEvent::ToolCall {
+            tool: "prom_test_tool".into(),
+            duration: Duration::from_millis(300),
+            success: false,
+        });
+        obs.record_event(&ObserverEvent::ChannelMessage {
+            channel: "say \"hi\"".into(),
+            direction: "inbound".into(),
+        });
+        obs.record_metric(&ObserverMetric::QueueDepth(3));
+
+        let text = render();
+        assert!(text.contains("# TYPE zeroclaw_tool_calls_total counter\n"));
+        assert!(text.contains(
+            "zeroclaw_tool_calls_total{tool=\"prom_test_tool\",outcome=\"failure\"} 1\n"
+        ));
+        assert!(text.contains(
+            "zeroclaw_tool_duration_seconds_bucket{tool=\"prom_test_tool\",le=\"0.25\"} 0\n"
+        ));
+        assert!(text.contains(
+            "zeroclaw_tool_duration_seconds_bucket{tool=\"prom_test_tool\",le=\"0.5\"} 1\n"
+        ));
+        assert!(text.contains("zeroclaw_tool_duration_seconds_count{tool=\"prom_test_tool\"} 1\n"));
+        assert!(text.contains(r#"channel="say \"hi\"""#));
+        assert!(text.contains("zeroclaw_queue_depth 3\n"));
+    }
+}
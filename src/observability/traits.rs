@@ -20,6 +20,20 @@ pub enum ObserverEvent {
         channel: String,
         direction: String,
     },
+    /// Responses one provider and model served this run, for cost tracking.
+    ProviderUsage {
+        provider: String,
+        model: String,
+        responses: u64,
+        prompt_chars: u64,
+        response_chars: u64,
+    },
+    /// One run of a scheduled cron job.
+    CronRun {
+        job: String,
+        duration: Duration,
+        success: bool,
+    },
     HeartbeatTick,
     Error {
         component: String,
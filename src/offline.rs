@@ -0,0 +1,136 @@
+//! Graceful degradation when there is no network.
+//!
+//! At startup the agent probes a few well-known addresses. If none answers and
+//! the configured provider is remote, it switches to `[offline].provider`
+//! (Ollama or a llama.cpp server) and wraps network tools so calls fail fast
+//! with a clear message instead of hanging on timeouts.
+
+use crate::config::{Config, OfflineConfig};
+use std::time::Duration;
+
+/// Pinned to the context so the model knows why tools are unavailable.
+pub const CONTEXT_NOTE: &str = "The device is offline. You are running on a local model and \
+network tools (web, calendars, cloud and forge APIs) are disabled. Work from local files and \
+memory, and say plainly when something has to wait until the connection is back.";
+
+/// The local provider and model to use instead of the configured ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflinePlan {
+    pub provider: String,
+    pub model: String,
+}
+
+/// True if any probe accepts a TCP connection within the timeout.
+pub async fn network_available(config: &OfflineConfig) -> bool {
+    if config.probes.is_empty() {
+        return true;
+    }
+    let timeout = Duration::from_millis(config.probe_timeout_ms);
+    let mut probes = tokio::task::JoinSet::new();
+    for addr in config.probes.clone() {
+        probes.spawn(async move {
+            matches!(
+                tokio::time::timeout(timeout, tokio::net::TcpStream::connect(addr.as_str())).await,
+                Ok(Ok(_))
+            )
+        });
+    }
+    while let Some(result) = probes.join_next().await {
+        if result.unwrap_or(false) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Providers that keep working without internet access.
+pub fn is_local_provider(name: &str) -> bool {
+    if name == "ollama" {
+        return true;
+    }
+    let Some(url) = name
+        .strip_prefix("custom:")
+        .or_else(|| name.strip_prefix("anthropic-custom:"))
+    else {
+        return false;
+    };
+    let host = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split(['/', '?'])
+        .next()
+        .unwrap_or_default();
+    let authority = host.to_ascii_lowercase();
+    let host = if let Some(bracketed) = authority.strip_prefix('[') {
+        bracketed.split(']').next().unwrap_or_default()
+    } else {
+        authority
+            .rsplit_once(':')
+            .map_or(authority.as_str(), |(h, _)| h)
+    };
+    host == "localhost"
+        || host == "::1"
+        || host.starts_with("127.")
+        || host.rsplit('.').next() == Some("local")
+}
+
+/// Decide whether to fall back for a session that would use `provider`.
+pub async fn check(config: &Config, provider: &str) -> Option<OfflinePlan> {
+    if !config.offline.auto || is_local_provider(provider) {
+        return None;
+    }
+    if network_available(&config.offline).await {
+        return None;
+    }
+    tracing::warn!(
+        "No network: falling back from '{provider}' to '{}'",
+        config.offline.provider
+    );
+    Some(OfflinePlan {
+        provider: config.offline.provider.clone(),
+        model: config.offline.model.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_providers_are_recognised() {
+        assert!(is_local_provider("ollama"));
+        assert!(is_local_provider("custom:http://localhost:8080/v1"));
+        assert!(is_local_provider("custom:http://127.0.0.1:1234"));
+        assert!(is_local_provider("anthropic-custom:http://[::1]:9000"));
+        assert!(is_local_provider("custom:http://llama.local:8080"));
+        assert!(!is_local_provider("openrouter"));
+        assert!(!is_local_provider("custom:https://api.example.com"));
+        assert!(!is_local_provider("custom:https://localhost.example.com"));
+    }
+
+    #[tokio::test]
+    async fn unreachable_probes_mean_offline() {
+        // Bind then drop a listener so the port is known to be closed.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut config = Config::default();
+        config.offline.probes = vec![format!("127.0.0.1:{port}")];
+        config.offline.probe_timeout_ms = 200;
+        assert!(!network_available(&config.offline).await);
+        assert_eq!(
+            check(&config, "openrouter").await,
+            Some(OfflinePlan {
+                provider: "ollama".into(),
+                model: "llama3.2".into()
+            })
+        );
+        assert_eq!(check(&config, "ollama").await, None);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        config.offline.probes = vec![listener.local_addr().unwrap().to_string()];
+        assert!(network_available(&config.offline).await);
+    }
+}
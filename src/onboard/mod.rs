@@ -1,3 +1,4 @@
+pub mod pairing;
 pub mod wizard;
 
 pub use wizard::{run_channels_repair_wizard, run_quick_setup, run_wizard};
@@ -0,0 +1,306 @@
+//! Allowlist the owner by having them message the bot once.
+//!
+//! Finding a Telegram or Discord user id by hand is the fiddliest part of
+//! setup. Instead we print a one-time code (and, for Telegram, a deep link
+//! that sends it for you), wait for the first message carrying it, and take
+//! the sender's id from that message.
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use console::style;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// How long to wait for the owner to send the code.
+const PAIRING_TIMEOUT: Duration = Duration::from_mins(5);
+
+const PAIRED_REPLY: &str = "✅ Paired — ZeroClaw will answer you here.";
+
+/// A short one-time code, safe to use as a Telegram `/start` payload.
+fn new_code() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..8].to_ascii_uppercase()
+}
+
+/// Whether `text` carries `code`: `/start CODE` from a deep link, the bare
+/// code, or the code after a mention.
+fn carries_code(text: &str, code: &str) -> bool {
+    text.split_whitespace()
+        .any(|word| word.eq_ignore_ascii_case(code))
+}
+
+/// The largest Discord snowflake among `messages`, if newer than `after`.
+fn newest_id(messages: &[Value], after: u64) -> u64 {
+    messages
+        .iter()
+        .filter_map(|m| m.get("id")?.as_str()?.parse::<u64>().ok())
+        .fold(after, u64::max)
+}
+
+/// Pair a Telegram account: prints the link, then returns the numeric user
+/// id of whoever sends the code, or `None` if nobody did in time.
+pub fn telegram(token: &str, bot_name: &str) -> Option<String> {
+    let code = new_code();
+    println!();
+    println!(
+        "  {} Open {} and press Start,",
+        style("🔗").cyan(),
+        style(format!("https://t.me/{bot_name}?start={code}"))
+            .cyan()
+            .bold()
+    );
+    println!("     or send the bot this code: {}", style(&code).bold());
+    println!(
+        "  {} Waiting up to {} minutes...",
+        style("⏳").dim(),
+        PAIRING_TIMEOUT.as_secs() / 60
+    );
+    report(wait_telegram(token, &code))
+}
+
+fn wait_telegram(token: &str, code: &str) -> Result<Option<String>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(40))
+        .build()?;
+    let api = |method: &str| format!("https://api.telegram.org/bot{token}/{method}");
+    let poll = |offset: i64, timeout: u64| -> Result<Value> {
+        Ok(client
+            .post(api("getUpdates"))
+            .json(&json!({ "offset": offset, "timeout": timeout, "allowed_updates": ["message"] }))
+            .send()?
+            .error_for_status()?
+            .json()?)
+    };
+
+    // Skip anything sent before the code was shown.
+    let mut offset = poll(-1, 0)?
+        .get("result")
+        .and_then(Value::as_array)
+        .and_then(|r| r.last())
+        .and_then(|u| u.get("update_id")?.as_i64())
+        .map_or(0, |id| id + 1);
+
+    let deadline = Instant::now() + PAIRING_TIMEOUT;
+    while Instant::now() < deadline {
+        let data = poll(offset, 20)?;
+        for update in data
+            .get("result")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(id) = update.get("update_id").and_then(Value::as_i64) {
+                offset = id + 1;
+            }
+            let Some(message) = update.get("message") else {
+                continue;
+            };
+            let text = message.get("text").and_then(Value::as_str).unwrap_or("");
+            let Some(user_id) = message.pointer("/from/id").and_then(Value::as_i64) else {
+                continue;
+            };
+            if !carries_code(text, code) {
+                continue;
+            }
+            let _ = client
+                .post(api("sendMessage"))
+                .json(&json!({ "chat_id": message.pointer("/chat/id"), "text": PAIRED_REPLY }))
+                .send();
+            // Confirm the update so the channel listener does not answer the code.
+            let _ = poll(offset, 0);
+            return Ok(Some(user_id.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Pair a Discord account: returns the user id of whoever posts the code in
+/// a text channel the bot can read. Bots cannot list their DMs over REST, so
+/// the code has to go in a server the bot has joined.
+pub fn discord(token: &str, guild_id: Option<&str>) -> Option<String> {
+    let code = new_code();
+    println!();
+    println!(
+        "  {} Post this code in any channel the bot can read: {}",
+        style("🔗").cyan(),
+        style(&code).bold()
+    );
+    println!(
+        "  {} Waiting up to {} minutes...",
+        style("⏳").dim(),
+        PAIRING_TIMEOUT.as_secs() / 60
+    );
+    report(wait_discord(token, guild_id, &code))
+}
+
+fn wait_discord(token: &str, guild_id: Option<&str>, code: &str) -> Result<Option<String>> {
+    let client = reqwest::blocking::Client::new();
+    let auth = format!("Bot {token}");
+    let get = |path: &str| -> Result<Value> {
+        Ok(client
+            .get(format!("https://discord.com/api/v10{path}"))
+            .header("Authorization", &auth)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    };
+
+    let guilds: Vec<String> = match guild_id {
+        Some(id) => vec![id.to_string()],
+        None => get("/users/@me/guilds")?
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|g| Some(g.get("id")?.as_str()?.to_string()))
+            .collect(),
+    };
+    // (channel id, newest message id seen) for every text channel.
+    let mut watched: Vec<(String, u64)> = Vec::new();
+    for guild in &guilds {
+        let channels = get(&format!("/guilds/{guild}/channels"))?;
+        for channel in channels.as_array().into_iter().flatten() {
+            if channel.get("type").and_then(Value::as_u64) != Some(0) {
+                continue;
+            }
+            let Some(id) = channel.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            let last = channel
+                .get("last_message_id")
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            watched.push((id.to_string(), last));
+        }
+    }
+    if watched.is_empty() {
+        bail!("the bot cannot see any text channels — invite it to a server first");
+    }
+
+    let deadline = Instant::now() + PAIRING_TIMEOUT;
+    while Instant::now() < deadline {
+        for (channel, after) in &mut watched {
+            // Channels the bot lacks access to just stay quiet.
+            let Ok(Value::Array(messages)) = get(&format!(
+                "/channels/{channel}/messages?after={after}&limit=50"
+            )) else {
+                continue;
+            };
+            *after = newest_id(&messages, *after);
+            let Some(message) = messages.iter().find(|m| {
+                m.pointer("/author/bot").and_then(Value::as_bool) != Some(true)
+                    && carries_code(m.get("content").and_then(Value::as_str).unwrap_or(""), code)
+            }) else {
+                continue;
+            };
+            let Some(user_id) = message.pointer("/author/id").and_then(Value::as_str) else {
+                continue;
+            };
+            let _ = client
+                .post(format!(
+                    "https://discord.com/api/v10/channels/{channel}/messages"
+                ))
+                .header("Authorization", &auth)
+                .json(&json!({
+                    "content": PAIRED_REPLY,
+                    "message_reference": { "message_id": message.get("id") },
+                }))
+                .send();
+            return Ok(Some(user_id.to_string()));
+        }
+        std::thread::sleep(Duration::from_secs(3));
+    }
+    Ok(None)
+}
+
+fn report(result: Result<Option<String>>) -> Option<String> {
+    match result {
+        Ok(Some(id)) => {
+            println!(
+                "  {} Paired with user ID {}",
+                style("✅").green().bold(),
+                style(&id).green()
+            );
+            Some(id)
+        }
+        Ok(None) => {
+            println!("  {} No code received in time", style("⚠").yellow().bold());
+            None
+        }
+        Err(e) => {
+            println!("  {} Pairing failed: {e}", style("❌").red().bold());
+            None
+        }
+    }
+}
+
+/// `zeroclaw channel pair <telegram|discord>`: pair an already configured
+/// channel and add the owner to its allowlist.
+pub fn pair_channel(mut config: Config, channel_type: &str) -> Result<()> {
+    let (allowed_users, id) =
+        match channel_type.to_ascii_lowercase().as_str() {
+            "telegram" => {
+                let tg = config.channels_config.telegram.as_mut().context(
+                    "Telegram is not configured — run `zeroclaw onboard --channels-only`",
+                )?;
+                let me: Value = reqwest::blocking::Client::new()
+                    .get(format!(
+                        "https://api.telegram.org/bot{}/getMe",
+                        tg.bot_token
+                    ))
+                    .send()?
+                    .error_for_status()
+                    .context("Telegram rejected the bot token")?
+                    .json()?;
+                let bot_name = me
+                    .pointer("/result/username")
+                    .and_then(Value::as_str)
+                    .context("Telegram did not return the bot username")?
+                    .to_string();
+                let id = telegram(&tg.bot_token, &bot_name);
+                (&mut tg.allowed_users, id)
+            }
+            "discord" => {
+                let dc = config.channels_config.discord.as_mut().context(
+                    "Discord is not configured — run `zeroclaw onboard --channels-only`",
+                )?;
+                let id = discord(&dc.bot_token, dc.guild_id.as_deref());
+                (&mut dc.allowed_users, id)
+            }
+            other => bail!("Pairing is supported for telegram and discord, not '{other}'"),
+        };
+    let Some(id) = id else {
+        bail!("Pairing did not complete — nothing was changed");
+    };
+    if !allowed_users.contains(&id) {
+        allowed_users.push(id);
+    }
+    config.save()?;
+    println!(
+        "  {} Saved to {}",
+        style("✓").green().bold(),
+        config.config_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_found_in_deep_links_and_plain_messages() {
+        let code = new_code();
+        assert_eq!(code.len(), 8);
+        assert!(carries_code(&format!("/start {code}"), &code));
+        assert!(carries_code(
+            &format!("<@123> {}", code.to_lowercase()),
+            &code
+        ));
+        assert!(!carries_code("/start", &code));
+        assert!(!carries_code(&format!("{code}X"), &code));
+
+        let messages = [json!({ "id": "1200" }), json!({ "id": "1500" })];
+        assert_eq!(newest_id(&messages, 1000), 1500);
+        assert_eq!(newest_id(&[], 1000), 1000);
+    }
+}
@@ -90,6 +90,8 @@ pub fn run_wizard() -> Result<Config> {
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: Vec::new(),
+        profile_defaults: Vec::new(),
         api_key: if api_key.is_empty() {
             None
         } else {
@@ -114,6 +116,36 @@ pub fn run_wizard() -> Result<Config> {
             api_key: weather_api_key.clone(),
         },
         identity: crate::config::IdentityConfig::default(),
+        integrations: crate::config::IntegrationsConfig::default(),
+        agent: crate::config::AgentConfig::default(),
+        guardrails: crate::config::GuardrailsConfig::default(),
+        styles: std::collections::BTreeMap::new(),
+        triggers: crate::config::TriggersConfig::default(),
+        presence: crate::config::PresenceConfig::default(),
+        inbox: crate::config::InboxConfig::default(),
+        household: crate::config::HouseholdConfig::default(),
+        offline: crate::config::OfflineConfig::default(),
+        eval: crate::config::EvalConfig::default(),
+        aliases: crate::config::AliasesConfig::default(),
+        ollama: crate::config::OllamaConfig::default(),
+        tool_output: crate::config::ToolOutputConfig::default(),
+        tool_middleware: crate::config::ToolMiddlewareConfig::default(),
+        workspace_index: crate::config::WorkspaceIndexConfig::default(),
+        skills: crate::config::SkillsConfig::default(),
+        providers: crate::config::ProvidersConfig::default(),
+        voice: crate::config::VoiceConfig::default(),
+        oauth: crate::config::OAuthConfig::default(),
+        federation: crate::config::FederationConfig::default(),
+        goals: crate::config::GoalsConfig::default(),
+        focus: crate::config::FocusConfig::default(),
+        mcp: crate::config::McpConfig::default(),
+        outbox: crate::config::OutboxConfig::default(),
+        sync: crate::config::SyncConfig::default(),
+        vision: crate::config::VisionConfig::default(),
+        artifacts: crate::config::ArtifactsConfig::default(),
+        experiment: crate::config::ExperimentConfig::default(),
+        notifications: crate::config::NotificationsConfig::default(),
+        redis: crate::config::RedisConfig::default(),
     };
 
     println!(
@@ -152,7 +184,8 @@ pub fn run_wizard() -> Result<Config> {
         || config.channels_config.slack.is_some()
         || config.channels_config.imessage.is_some()
         || config.channels_config.matrix.is_some()
-        || config.channels_config.email.is_some();
+        || config.channels_config.email.is_some()
+        || config.channels_config.signal.is_some();
 
     if has_channels && config.api_key.is_some() {
         let launch: bool = Confirm::new()
@@ -208,7 +241,8 @@ pub fn run_channels_repair_wizard() -> Result<Config> {
         || config.channels_config.slack.is_some()
         || config.channels_config.imessage.is_some()
         || config.channels_config.matrix.is_some()
-        || config.channels_config.email.is_some();
+        || config.channels_config.email.is_some()
+        || config.channels_config.signal.is_some();
 
     if has_channels && config.api_key.is_some() {
         let launch: bool = Confirm::new()
@@ -302,6 +336,8 @@ pub fn run_quick_setup(
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: Vec::new(),
+        profile_defaults: Vec::new(),
         api_key: api_key.map(String::from),
         default_provider: Some(provider_name.clone()),
         default_model: Some(model.clone()),
@@ -322,6 +358,36 @@ pub fn run_quick_setup(
             api_key: weather_api_key.clone(),
         },
         identity: crate::config::IdentityConfig::default(),
+        integrations: crate::config::IntegrationsConfig::default(),
+        agent: crate::config::AgentConfig::default(),
+        guardrails: crate::config::GuardrailsConfig::default(),
+        styles: std::collections::BTreeMap::new(),
+        triggers: crate::config::TriggersConfig::default(),
+        presence: crate::config::PresenceConfig::default(),
+        inbox: crate::config::InboxConfig::default(),
+        household: crate::config::HouseholdConfig::default(),
+        offline: crate::config::OfflineConfig::default(),
+        eval: crate::config::EvalConfig::default(),
+        aliases: crate::config::AliasesConfig::default(),
+        ollama: crate::config::OllamaConfig::default(),
+        tool_output: crate::config::ToolOutputConfig::default(),
+        tool_middleware: crate::config::ToolMiddlewareConfig::default(),
+        workspace_index: crate::config::WorkspaceIndexConfig::default(),
+        skills: crate::config::SkillsConfig::default(),
+        providers: crate::config::ProvidersConfig::default(),
+        voice: crate::config::VoiceConfig::default(),
+        oauth: crate::config::OAuthConfig::default(),
+        federation: crate::config::FederationConfig::default(),
+        goals: crate::config::GoalsConfig::default(),
+        focus: crate::config::FocusConfig::default(),
+        mcp: crate::config::McpConfig::default(),
+        outbox: crate::config::OutboxConfig::default(),
+        sync: crate::config::SyncConfig::default(),
+        vision: crate::config::VisionConfig::default(),
+        artifacts: crate::config::ArtifactsConfig::default(),
+        experiment: crate::config::ExperimentConfig::default(),
+        notifications: crate::config::NotificationsConfig::default(),
+        redis: crate::config::RedisConfig::default(),
     };
 
     config.save()?;
@@ -850,6 +916,25 @@ fn setup_provider() -> Result<(String, String, String)> {
         _ => vec![("default", "Default model")],
     };
 
+    // Groq retires models often; offer what the key can use right now.
+    let mut live_models = if provider_name == "groq" && !api_key.trim().is_empty() {
+        crate::providers::groq::list_models_blocking(api_key.trim()).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    // Keep the recommended model as the default choice.
+    if let Some(pos) = live_models.iter().position(|id| *id == models[0].0) {
+        live_models[..=pos].rotate_right(1);
+    }
+    let models: Vec<(&str, &str)> = if live_models.is_empty() {
+        models
+    } else {
+        live_models
+            .iter()
+            .map(|id| (id.as_str(), id.as_str()))
+            .collect()
+    };
+
     let model_labels: Vec<&str> = models.iter().map(|(_, label)| *label).collect();
 
     let model_idx = Select::new()
@@ -971,7 +1056,10 @@ fn setup_tool_mode() -> Result<(ComposioConfig, SecretsConfig)> {
         .default(true)
         .interact()?;
 
-    let secrets_config = SecretsConfig { encrypt };
+    let secrets_config = SecretsConfig {
+        encrypt,
+        ..SecretsConfig::default()
+    };
 
     if encrypt {
         println!(
@@ -1206,6 +1294,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
         whatsapp: None,
         email: None,
         irc: None,
+        signal: None,
+        push: None,
     };
 
     loop {
@@ -1310,18 +1400,20 @@ fn setup_channels() -> Result<ChannelsConfig> {
                 print!("  {} Testing connection... ", style("⏳").dim());
                 let client = reqwest::blocking::Client::new();
                 let url = format!("https://api.telegram.org/bot{token}/getMe");
-                match client.get(&url).send() {
+                let bot_name = match client.get(&url).send() {
                     Ok(resp) if resp.status().is_success() => {
                         let data: serde_json::Value = resp.json().unwrap_or_default();
                         let bot_name = data
                             .get("result")
                             .and_then(|r| r.get("username"))
                             .and_then(serde_json::Value::as_str)
-                            .unwrap_or("unknown");
+                            .unwrap_or("unknown")
+                            .to_string();
                         println!(
                             "\r  {} Connected as @{bot_name}        ",
                             style("✅").green().bold()
                         );
+                        bot_name
                     }
                     _ => {
                         println!(
@@ -1330,6 +1422,22 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         );
                         continue;
                     }
+                };
+
+                let pair = Confirm::new()
+                    .with_prompt("  Pair your account by messaging the bot a one-time code?")
+                    .default(true)
+                    .interact()?;
+                if let Some(id) = pair
+                    .then(|| super::pairing::telegram(&token, &bot_name))
+                    .flatten()
+                {
+                    config.telegram = Some(TelegramConfig {
+                        bot_token: token,
+                        allowed_users: vec![id],
+                        voice_replies: false,
+                    });
+                    continue;
                 }
 
                 print_bullet(
@@ -1367,6 +1475,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                 config.telegram = Some(TelegramConfig {
                     bot_token: token,
                     allowed_users,
+                    voice_replies: false,
                 });
             }
             1 => {
@@ -1423,6 +1532,28 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     .allow_empty(true)
                     .interact_text()?;
 
+                let pair = Confirm::new()
+                    .with_prompt("  Pair your account by posting a one-time code in your server?")
+                    .default(true)
+                    .interact()?;
+                let guild_id = if guild.is_empty() { None } else { Some(guild) };
+                if let Some(id) = pair
+                    .then(|| super::pairing::discord(&token, guild_id.as_deref()))
+                    .flatten()
+                {
+                    config.discord = Some(DiscordConfig {
+                        bot_token: token,
+                        guild_id,
+                        allowed_users: vec![id],
+                        reactions: true,
+                        allowed_guilds: vec![],
+                        allowed_channels: vec![],
+                        slash_commands: true,
+                        thread_replies: false,
+                    });
+                    continue;
+                }
+
                 print_bullet("Allowlist your own Discord user ID first (recommended).");
                 print_bullet(
                     "Get it in Discord: Settings -> Advanced -> Developer Mode (ON), then right-click your profile -> Copy User ID.",
@@ -1455,8 +1586,13 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 config.discord = Some(DiscordConfig {
                     bot_token: token,
-                    guild_id: if guild.is_empty() { None } else { Some(guild) },
+                    guild_id,
                     allowed_users,
+                    reactions: true,
+                    allowed_guilds: vec![],
+                    allowed_channels: vec![],
+                    slash_commands: true,
+                    thread_replies: false,
                 });
             }
             2 => {
@@ -1575,6 +1711,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    reactions: true,
                 });
             }
             3 => {
@@ -1614,7 +1751,10 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         .collect()
                 };
 
-                config.imessage = Some(IMessageConfig { allowed_contacts });
+                config.imessage = Some(IMessageConfig {
+                    allowed_contacts,
+                    voice_replies: false,
+                });
                 println!(
                     "  {} iMessage configured (contacts: {})",
                     style("✅").green().bold(),
@@ -1694,11 +1834,23 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     users_str.split(',').map(|s| s.trim().to_string()).collect()
                 };
 
+                let rooms_str: String = Input::new()
+                    .with_prompt("  Other rooms to answer in (comma-separated room IDs, optional)")
+                    .allow_empty(true)
+                    .interact_text()?;
+                let rooms = rooms_str
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty())
+                    .map(String::from)
+                    .collect();
+
                 config.matrix = Some(MatrixConfig {
                     homeserver: homeserver.trim_end_matches('/').to_string(),
                     access_token,
                     room_id,
                     allowed_users,
+                    rooms,
                 });
             }
             5 => {
@@ -1784,6 +1936,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     verify_token: verify_token.trim().to_string(),
                     allowed_numbers,
                     app_secret: None, // Can be set via ZEROCLAW_WHATSAPP_APP_SECRET env var
+                    voice_replies: false,
                 });
             }
             6 => {
@@ -1951,6 +2104,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     } else {
                         Some(secret)
                     },
+                    targets: Vec::new(),
                 });
                 println!(
                     "  {} Webhook on port {}",
@@ -2330,7 +2484,13 @@ fn scaffold_workspace(workspace_dir: &Path, ctx: &ProjectContext) -> Result<()>
            - Don't use when: the answer is already in current files/conversation.\n\
          - **memory_forget** — Delete a memory entry\n\
            - Use when: memory is incorrect, stale, or explicitly requested to be removed.\n\
-           - Don't use when: uncertain about impact; verify before deleting.\n\n\
+           - Don't use when: uncertain about impact; verify before deleting.\n\
+         - **list_tasks** — List tracked tasks and their progress\n\
+           - Use when: asked what is in flight, or before resuming earlier work.\n\
+           - Don't use when: the work fits in a single reply.\n\
+         - **update_task** — Open a task or checkpoint progress on one\n\
+           - Use when: work spans several steps or may outlive this session.\n\
+           - Don't use when: the request is a one-off answer.\n\n\
          ---\n\
          *Add whatever helps you do your job. This is your cheat sheet.*\n";
 
@@ -2442,7 +2602,8 @@ fn print_summary(config: &Config) {
         || config.channels_config.slack.is_some()
         || config.channels_config.imessage.is_some()
         || config.channels_config.matrix.is_some()
-        || config.channels_config.email.is_some();
+        || config.channels_config.email.is_some()
+        || config.channels_config.signal.is_some();
 
     println!();
     println!(
@@ -2516,9 +2677,15 @@ fn print_summary(config: &Config) {
     if config.channels_config.email.is_some() {
         channels.push("Email");
     }
+    if config.channels_config.signal.is_some() {
+        channels.push("Signal");
+    }
     if config.channels_config.webhook.is_some() {
         channels.push("Webhook");
     }
+    if config.channels_config.push.is_some() {
+        channels.push("Push");
+    }
     println!(
         "    {} Channels:      {}",
         style("📡").cyan(),
@@ -2973,6 +3140,8 @@ mod tests {
             "memory_store",
             "memory_recall",
             "memory_forget",
+            "list_tasks",
+            "update_task",
         ] {
             assert!(
                 tools.contains(tool),
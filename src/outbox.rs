@@ -0,0 +1,623 @@
+//! Outbox for messages the agent decides to send.
+//!
+//! A send is first written to `outbox/outbox.db` as an intent and only then
+//! delivered, so a crash between deciding and sending can't drop it: the
+//! daemon's dispatcher picks up every intent still pending (or claimed by a
+//! process that died mid-send) and retries with backoff, moving it to the
+//! dead-letter queue after `[outbox].max_attempts`. Each intent carries a
+//! dedupe key (by default a hash of channel, recipient and text); a second
+//! intent with the same key inside `dedupe_window_secs` is not sent again,
+//! so a replayed turn or a repeated tool call can't double-send.
+//!
+//! With `[redis]` on, intents are kept in Redis instead (see
+//! [`crate::redis::queue`]), so every process of a deployment shares one
+//! queue.
+
+use crate::channels::Channel;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A claim older than this belongs to a process that died mid-send.
+const STALE_CLAIM_SECS: i64 = 300;
+/// Longest wait between retries.
+const MAX_BACKOFF_SECS: u64 = 3600;
+/// Delivered intents are kept this long, then deleted.
+const KEEP_SENT_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Waiting for its first or next attempt
+    Pending,
+    /// Claimed by a dispatcher that is sending it now
+    Sending,
+    Sent,
+    /// Out of attempts; a copy is in the dead-letter queue
+    Dead,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sending => "sending",
+            Self::Sent => "sent",
+            Self::Dead => "dead",
+        }
+    }
+
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "sending" => Self::Sending,
+            "sent" => Self::Sent,
+            "dead" => Self::Dead,
+            _ => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Intent {
+    pub id: String,
+    pub dedupe_key: String,
+    /// What decided to send it, e.g. the `send_message` tool
+    pub source: String,
+    pub channel: String,
+    pub recipient: String,
+    pub message: String,
+    pub status: Status,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+/// Result of [`enqueue`].
+#[derive(Debug, Clone)]
+pub enum Enqueued {
+    New(Intent),
+    /// The same send was already recorded within the dedupe window.
+    Duplicate(Intent),
+}
+
+/// Default dedupe key: the same text to the same recipient.
+pub fn dedupe_key(channel: &str, recipient: &str, message: &str) -> String {
+    let digest = Sha256::digest(format!(
+        "{}\n{recipient}\n{message}",
+        channel.to_lowercase()
+    ));
+    hex::encode(&digest[..16])
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    // Fixed width, so timestamps compare correctly as text.
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_timestamp(raw: &str) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+const COLUMNS: &str = "id, dedupe_key, source, channel, recipient, message, status, attempts, \
+                       last_error, created_at, next_attempt_at";
+
+fn read_intent(row: &Row) -> rusqlite::Result<Intent> {
+    Ok(Intent {
+        id: row.get(0)?,
+        dedupe_key: row.get(1)?,
+        source: row.get(2)?,
+        channel: row.get(3)?,
+        recipient: row.get(4)?,
+        message: row.get(5)?,
+        status: Status::parse(&row.get::<_, String>(6)?),
+        attempts: row.get(7)?,
+        last_error: row.get(8)?,
+        created_at: parse_timestamp(&row.get::<_, String>(9)?)?,
+        next_attempt_at: parse_timestamp(&row.get::<_, String>(10)?)?,
+    })
+}
+
+/// Record an intent to send. `key` overrides the default dedupe key.
+pub fn enqueue(
+    config: &Config,
+    source: &str,
+    channel: &str,
+    recipient: &str,
+    message: &str,
+    key: Option<&str>,
+) -> Result<Enqueued> {
+    let key = key.map_or_else(|| dedupe_key(channel, recipient, message), String::from);
+    insert(
+        config,
+        source,
+        (channel, recipient),
+        message,
+        &key,
+        Utc::now(),
+    )
+}
+
+/// Record an intent that the dispatcher first attempts at `not_before`.
+pub fn enqueue_after(
+    config: &Config,
+    source: &str,
+    channel: &str,
+    recipient: &str,
+    message: &str,
+    not_before: DateTime<Utc>,
+) -> Result<Enqueued> {
+    let key = dedupe_key(channel, recipient, message);
+    insert(
+        config,
+        source,
+        (channel, recipient),
+        message,
+        &key,
+        not_before,
+    )
+}
+
+fn insert(
+    config: &Config,
+    source: &str,
+    (channel, recipient): (&str, &str),
+    message: &str,
+    key: &str,
+    not_before: DateTime<Utc>,
+) -> Result<Enqueued> {
+    let now = Utc::now();
+    let window = Duration::seconds(
+        i64::try_from(config.outbox.dedupe_window_secs).unwrap_or(i64::MAX / 1000),
+    );
+    let new_intent = || Intent {
+        id: Uuid::new_v4().to_string(),
+        dedupe_key: key.to_string(),
+        source: source.to_string(),
+        channel: channel.to_string(),
+        recipient: recipient.to_string(),
+        message: message.to_string(),
+        status: Status::Pending,
+        attempts: 0,
+        last_error: None,
+        created_at: now,
+        next_attempt_at: not_before,
+    };
+    if let Some(shared) = crate::redis::shared() {
+        return crate::redis::queue::insert(&shared, new_intent(), window);
+    }
+    with_connection(config, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let existing = tx
+            .query_row(
+                &format!(
+                    "SELECT {COLUMNS} FROM outbox
+                     WHERE dedupe_key = ?1 AND status != 'dead' AND created_at >= ?2
+                     ORDER BY created_at DESC LIMIT 1"
+                ),
+                params![key, timestamp(now - window)],
+                read_intent,
+            )
+            .optional()?;
+        if let Some(intent) = existing {
+            return Ok(Enqueued::Duplicate(intent));
+        }
+        let intent = new_intent();
+        tx.execute(
+            &format!(
+                "INSERT INTO outbox ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, NULL, ?8, ?9)"
+            ),
+            params![
+                intent.id,
+                intent.dedupe_key,
+                intent.source,
+                intent.channel,
+                intent.recipient,
+                intent.message,
+                Status::Pending.as_str(),
+                timestamp(now),
+                timestamp(not_before)
+            ],
+        )
+        .context("Failed to record outbox intent")?;
+        tx.commit()?;
+        Ok(Enqueued::New(intent))
+    })
+}
+
+/// Take a pending intent for sending; `None` if another dispatcher has it
+/// or it is no longer pending.
+fn claim(config: &Config, id: &str) -> Result<Option<Intent>> {
+    if let Some(shared) = crate::redis::shared() {
+        return crate::redis::queue::claim(&shared, id);
+    }
+    with_connection(config, |conn| {
+        let claimed = conn.execute(
+            "UPDATE outbox SET status = 'sending', attempts = attempts + 1, claimed_at = ?1
+             WHERE id = ?2 AND status = 'pending'",
+            params![timestamp(Utc::now()), id],
+        )?;
+        if claimed == 0 {
+            return Ok(None);
+        }
+        Ok(Some(conn.query_row(
+            &format!("SELECT {COLUMNS} FROM outbox WHERE id = ?1"),
+            params![id],
+            read_intent,
+        )?))
+    })
+}
+
+/// Record the outcome of an attempt; `error` is `None` when it was sent.
+fn finish(config: &Config, intent: &Intent, error: Option<&str>) -> Result<Status> {
+    let now = Utc::now();
+    let status = match error {
+        None => Status::Sent,
+        Some(_) if intent.attempts >= config.outbox.max_attempts => Status::Dead,
+        Some(_) => Status::Pending,
+    };
+    let shift = intent.attempts.saturating_sub(1).min(16);
+    let backoff = config
+        .outbox
+        .retry_base_secs
+        .saturating_mul(1 << shift)
+        .min(MAX_BACKOFF_SECS);
+    let next = now + Duration::seconds(i64::try_from(backoff).unwrap_or(0));
+    if let Some(shared) = crate::redis::shared() {
+        crate::redis::queue::finish(
+            &shared,
+            intent,
+            (status, error),
+            next,
+            Duration::days(KEEP_SENT_DAYS),
+        )?;
+    } else {
+        record_outcome(config, intent, (status, error), next, now)?;
+    }
+    if let (Status::Dead, Some(error)) = (status, error) {
+        tracing::warn!(
+            "Outbox gave up on {} after {} attempts: {error}",
+            intent.channel,
+            intent.attempts
+        );
+        crate::dlq::push(
+            config,
+            &intent.channel,
+            &intent.recipient,
+            &intent.message,
+            error,
+        )?;
+    }
+    Ok(status)
+}
+
+fn record_outcome(
+    config: &Config,
+    intent: &Intent,
+    (status, error): (Status, Option<&str>),
+    next: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE outbox SET status = ?1, last_error = ?2, next_attempt_at = ?3,
+                 sent_at = CASE WHEN ?1 = 'sent' THEN ?4 ELSE sent_at END
+             WHERE id = ?5",
+            params![
+                status.as_str(),
+                error,
+                timestamp(next),
+                timestamp(now),
+                intent.id
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Make one delivery attempt for a pending intent. Returns its status
+/// afterwards, or `None` when it was not pending (sent, dead or claimed).
+pub async fn deliver(
+    config: &Config,
+    id: &str,
+    lookup: impl Fn(&str) -> Option<Arc<dyn Channel>>,
+) -> Result<Option<(Status, Option<String>)>> {
+    let Some(intent) = claim(config, id)? else {
+        return Ok(None);
+    };
+    let result = match lookup(&intent.channel) {
+        Some(channel) => channel
+            .send(
+                &crate::channels::shape_reply(channel.as_ref(), &intent.message),
+                &intent.recipient,
+            )
+            .await
+            .map_err(|e| format!("{e:#}")),
+        None => Err(format!("Channel '{}' is not configured", intent.channel)),
+    };
+    let error = result.err();
+    Ok(Some((finish(config, &intent, error.as_deref())?, error)))
+}
+
+/// Release claims left by a process that died mid-send and drop old sent
+/// intents. A released intent is sent again: a crash inside the channel's
+/// own send call is the one case that can repeat a message.
+fn recover(config: &Config) -> Result<usize> {
+    let now = Utc::now();
+    if let Some(shared) = crate::redis::shared() {
+        return crate::redis::queue::recover(&shared, now - Duration::seconds(STALE_CLAIM_SECS));
+    }
+    with_connection(config, |conn| {
+        conn.execute(
+            "DELETE FROM outbox WHERE status = 'sent' AND sent_at < ?1",
+            params![timestamp(now - Duration::days(KEEP_SENT_DAYS))],
+        )?;
+        Ok(conn.execute(
+            "UPDATE outbox SET status = 'pending'
+             WHERE status = 'sending' AND claimed_at < ?1",
+            params![timestamp(now - Duration::seconds(STALE_CLAIM_SECS))],
+        )?)
+    })
+}
+
+/// Attempt every intent that is due. Returns (sent, failed).
+pub async fn dispatch_due(
+    config: &Config,
+    lookup: impl Fn(&str) -> Option<Arc<dyn Channel>>,
+) -> Result<(usize, usize)> {
+    let released = recover(config)?;
+    if released > 0 {
+        tracing::warn!("Outbox: retrying {released} send(s) interrupted by a crash");
+    }
+    let due: Vec<String> = if let Some(shared) = crate::redis::shared() {
+        crate::redis::queue::due(&shared)?
+    } else {
+        due_ids(config)?
+    };
+    let (mut sent, mut failed) = (0, 0);
+    for id in due {
+        match deliver(config, &id, &lookup).await? {
+            Some((Status::Sent, _)) => sent += 1,
+            Some(_) => failed += 1,
+            None => {}
+        }
+    }
+    Ok((sent, failed))
+}
+
+fn due_ids(config: &Config) -> Result<Vec<String>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id FROM outbox WHERE status = 'pending' AND next_attempt_at <= ?1
+             ORDER BY created_at ASC",
+        )?;
+        let ids = stmt
+            .query_map(params![timestamp(Utc::now())], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(ids)
+    })
+}
+
+/// Intents not yet delivered, oldest first; with `all`, delivered ones too.
+pub fn list(config: &Config, all: bool) -> Result<Vec<Intent>> {
+    if let Some(shared) = crate::redis::shared() {
+        let mut intents = crate::redis::queue::list(&shared)?;
+        intents.retain(|intent| all || intent.status != Status::Sent);
+        return Ok(intents);
+    }
+    with_connection(config, |conn| {
+        let filter = if all { "" } else { "WHERE status != 'sent'" };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM outbox {filter} ORDER BY created_at ASC"
+        ))?;
+        let intents = stmt
+            .query_map([], read_intent)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(intents)
+    })
+}
+
+/// Daemon component: deliver due intents every `[outbox].poll_secs`.
+pub async fn run(config: Config) -> Result<()> {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.outbox.poll_secs.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok("outbox");
+        match dispatch_due(&config, |name| crate::channels::find_channel(&config, name)).await {
+            Ok((0, 0)) => {}
+            Ok((sent, failed)) => tracing::info!("Outbox: {sent} sent, {failed} to retry"),
+            Err(e) => tracing::warn!("Outbox dispatch failed: {e}"),
+        }
+    }
+}
+
+pub async fn handle_command(command: crate::OutboxCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::OutboxCommands::List { all } => {
+            let intents = list(config, all)?;
+            if intents.is_empty() {
+                println!("📭 Outbox is empty.");
+                return Ok(());
+            }
+            for intent in intents {
+                println!(
+                    "- {} | {} | {} → {} | attempts={} | {}\n    msg:   {}",
+                    &intent.id[..8],
+                    intent.status.as_str(),
+                    intent.channel,
+                    intent.recipient,
+                    intent.attempts,
+                    intent.source,
+                    crate::util::truncate_with_ellipsis(&intent.message.replace('\n', " "), 120)
+                );
+                if let Some(error) = &intent.last_error {
+                    println!(
+                        "    error: {}",
+                        crate::util::truncate_with_ellipsis(error, 120)
+                    );
+                }
+            }
+            Ok(())
+        }
+        crate::OutboxCommands::Flush => {
+            let (sent, failed) =
+                dispatch_due(config, |name| crate::channels::find_channel(config, name)).await?;
+            println!("✅ Sent {sent}, {failed} left to retry");
+            Ok(())
+        }
+    }
+}
+
+fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = config.workspace_dir.join("outbox").join("outbox.db");
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create outbox directory: {}", parent.display()))?;
+    }
+
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open outbox DB: {}", db_path.display()))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS outbox (
+            id              TEXT PRIMARY KEY,
+            dedupe_key      TEXT NOT NULL,
+            source          TEXT NOT NULL,
+            channel         TEXT NOT NULL,
+            recipient       TEXT NOT NULL,
+            message         TEXT NOT NULL,
+            status          TEXT NOT NULL,
+            attempts        INTEGER NOT NULL,
+            last_error      TEXT,
+            created_at      TEXT NOT NULL,
+            next_attempt_at TEXT NOT NULL,
+            claimed_at      TEXT,
+            sent_at         TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_outbox_due ON outbox(status, next_attempt_at);
+        CREATE INDEX IF NOT EXISTS idx_outbox_key ON outbox(dedupe_key, created_at);",
+    )
+    .context("Failed to initialize outbox schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MemoryChannel;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    fn lookup(channel: &Arc<MemoryChannel>) -> impl Fn(&str) -> Option<Arc<dyn Channel>> + '_ {
+        move |_| Some(channel.clone() as Arc<dyn Channel>)
+    }
+
+    #[tokio::test]
+    async fn repeated_sends_are_delivered_once() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let channel = Arc::new(MemoryChannel::new("slack"));
+
+        let Enqueued::New(intent) =
+            enqueue(&config, "test", "slack", "U1", "Deploy done", None).unwrap()
+        else {
+            panic!("first send should be new");
+        };
+        let delivered = deliver(&config, &intent.id, lookup(&channel))
+            .await
+            .unwrap();
+        assert_eq!(delivered, Some((Status::Sent, None)));
+
+        // A replayed turn records the same send again.
+        let again = enqueue(&config, "test", "slack", "U1", "Deploy done", None).unwrap();
+        assert!(matches!(again, Enqueued::Duplicate(ref i) if i.id == intent.id));
+        assert_eq!(
+            deliver(&config, &intent.id, lookup(&channel))
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            dispatch_due(&config, lookup(&channel)).await.unwrap(),
+            (0, 0)
+        );
+        assert_eq!(channel.sent().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_sends_retry_then_dead_letter() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.outbox.max_attempts = 2;
+        config.outbox.retry_base_secs = 0;
+        let channel = Arc::new(MemoryChannel::new("slack"));
+        channel.set_failing(true);
+
+        enqueue(&config, "test", "slack", "U1", "Alert", None).unwrap();
+        assert_eq!(
+            dispatch_due(&config, lookup(&channel)).await.unwrap(),
+            (0, 1)
+        );
+        assert_eq!(list(&config, false).unwrap()[0].status, Status::Pending);
+        assert_eq!(
+            dispatch_due(&config, lookup(&channel)).await.unwrap(),
+            (0, 1)
+        );
+        assert_eq!(list(&config, false).unwrap()[0].status, Status::Dead);
+        assert_eq!(crate::dlq::list(&config).unwrap().len(), 1);
+
+        // Dead intents don't block a fresh attempt at the same message.
+        channel.set_failing(false);
+        assert!(matches!(
+            enqueue(&config, "test", "slack", "U1", "Alert", None).unwrap(),
+            Enqueued::New(_)
+        ));
+        assert_eq!(
+            dispatch_due(&config, lookup(&channel)).await.unwrap(),
+            (1, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn claims_left_by_a_crash_are_retried() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let channel = Arc::new(MemoryChannel::new("slack"));
+        let Enqueued::New(intent) = enqueue(&config, "test", "slack", "U1", "Hi", None).unwrap()
+        else {
+            panic!("first send should be new");
+        };
+        claim(&config, &intent.id).unwrap().unwrap();
+        with_connection(&config, |conn| {
+            conn.execute(
+                "UPDATE outbox SET claimed_at = ?1",
+                params![timestamp(Utc::now() - Duration::hours(1))],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            dispatch_due(&config, lookup(&channel)).await.unwrap(),
+            (1, 0)
+        );
+        assert_eq!(channel.sent()[0].content, "Hi");
+    }
+}
@@ -0,0 +1,429 @@
+//! What differs between the systems `ZeroClaw` runs on — the shell, the
+//! environment a command needs, system directories, file permissions,
+//! desktop notifications and the clipboard — behind one set of functions so
+//! callers don't grow their own `cfg` blocks.
+//!
+//! The pure helpers take an [`Os`], so the Windows behaviour is tested on
+//! every CI runner; the functions that act pass [`Os::current`].
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Linux,
+    MacOs,
+    Windows,
+    Other,
+}
+
+impl Os {
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else if cfg!(any(target_os = "linux", target_os = "android")) {
+            Self::Linux
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Variables a Windows command can't do without: `SystemRoot` for DLL
+/// lookup, `PATHEXT` to find `.exe`/`.cmd`, and the profile folders.
+const WINDOWS_ENV_VARS: &[&str] = &[
+    "PATHEXT",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "USERPROFILE",
+    "USERNAME",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "TEMP",
+    "TMP",
+];
+
+/// Environment variables to keep on `os` on top of the common safe set.
+pub fn extra_env_vars(os: Os) -> &'static [&'static str] {
+    match os {
+        Os::Windows => WINDOWS_ENV_VARS,
+        _ => &[],
+    }
+}
+
+/// Program and arguments that run `command` through the system shell:
+/// `sh -c` (`-lc` with `login`, to read the profile) or `cmd /C`.
+pub fn shell_args(os: Os, command: &str, login: bool) -> (&'static str, Vec<String>) {
+    match os {
+        Os::Windows => (
+            "cmd",
+            vec!["/D".into(), "/S".into(), "/C".into(), command.into()],
+        ),
+        _ => (
+            "sh",
+            vec![if login { "-lc" } else { "-c" }.into(), command.into()],
+        ),
+    }
+}
+
+/// A command that runs `command` through this system's shell.
+pub fn shell_command(command: &str, login: bool) -> tokio::process::Command {
+    let (program, args) = shell_args(Os::current(), command, login);
+    let mut cmd = tokio::process::Command::new(program);
+    #[cfg(windows)]
+    {
+        // cmd parses its own command line; Rust's argument quoting would
+        // reach it as literal quotes.
+        cmd.args(&args[..3]).raw_arg(format!("\"{}\"", args[3]));
+    }
+    #[cfg(not(windows))]
+    cmd.args(args);
+    cmd
+}
+
+/// File names `name` can have on `os`: on Windows `name.exe`, `name.cmd`
+/// and the rest of `pathext` unless it already has an extension.
+pub fn executable_names(os: Os, name: &str, pathext: &str) -> Vec<String> {
+    if os != Os::Windows || Path::new(name).extension().is_some() {
+        return vec![name.to_string()];
+    }
+    pathext
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| format!("{name}{}", ext.to_ascii_lowercase()))
+        .collect()
+}
+
+/// Where `name` is found on `PATH`, if anywhere.
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+    let names = executable_names(Os::current(), name, &pathext);
+    let search_path = std::env::var_os("PATH").unwrap_or_default();
+    std::env::split_paths(&search_path)
+        .flat_map(|dir| names.iter().map(move |n| dir.join(n)))
+        .find(|p| p.is_file())
+}
+
+/// Directories the agent may never touch, whatever the workspace settings.
+pub fn default_forbidden_paths(os: Os) -> Vec<String> {
+    let system: &[&str] = match os {
+        Os::Windows => &[
+            "C:\\Windows",
+            "C:\\Program Files",
+            "C:\\Program Files (x86)",
+            "C:\\ProgramData",
+            "C:\\Users\\Default",
+        ],
+        _ => &[
+            "/etc", "/root", "/home", "/usr", "/bin", "/sbin", "/lib", "/opt", "/boot", "/dev",
+            "/proc", "/sys", "/var", "/tmp",
+        ],
+    };
+    let dotfiles: &[&str] = match os {
+        Os::Windows => &["~/.ssh", "~/.gnupg", "~/.aws", "~/AppData"],
+        _ => &["~/.ssh", "~/.gnupg", "~/.aws", "~/.config"],
+    };
+    system
+        .iter()
+        .chain(dotfiles)
+        .map(|p| (*p).to_string())
+        .collect()
+}
+
+/// The user's home directory (`USERPROFILE` on Windows).
+pub fn home_dir() -> Option<PathBuf> {
+    directories::UserDirs::new().map(|u| u.home_dir().to_path_buf())
+}
+
+/// `path` with a leading `~/` (or `~\`) replaced by `home`.
+pub fn expand_home(path: &str, home: Option<&Path>) -> String {
+    let rest = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\"));
+    match (rest, home) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+/// Whether `path` is `prefix` or inside it, component by component. On
+/// Windows either slash separates and case does not matter.
+pub fn path_starts_with(os: Os, path: &str, prefix: &str) -> bool {
+    if os != Os::Windows {
+        return Path::new(path).starts_with(prefix);
+    }
+    let normalize = |p: &str| {
+        p.replace('\\', "/")
+            .trim_end_matches('/')
+            .to_ascii_lowercase()
+    };
+    let (path, prefix) = (normalize(path), normalize(prefix));
+    !prefix.is_empty()
+        && (path == prefix
+            || path
+                .strip_prefix(&prefix)
+                .is_some_and(|rest| rest.starts_with('/')))
+}
+
+/// Make `path` readable and writable by its owner only: mode 0600 on Unix,
+/// an ACL granting just the current user on Windows.
+pub fn restrict_to_owner(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to restrict {}", path.display()))?;
+    }
+    #[cfg(windows)]
+    {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        if user.trim().is_empty() {
+            anyhow::bail!("USERNAME is not set; cannot restrict {}", path.display());
+        }
+        let output = std::process::Command::new("icacls")
+            .arg(path)
+            .args(["/inheritance:r", "/grant:r"])
+            .arg(format!("{}:F", user.trim()))
+            .output()
+            .context("Could not run icacls")?;
+        if !output.status.success() {
+            anyhow::bail!("icacls could not restrict {}", path.display());
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    let _ = path;
+    Ok(())
+}
+
+/// `ping` arguments for one probe of `host`, waiting about two seconds.
+pub fn ping_args(os: Os, host: &str) -> Vec<String> {
+    let (count, wait, millis) = match os {
+        Os::Windows => ("-n", "-w", "2000"),
+        Os::MacOs => ("-c", "-W", "2000"),
+        _ => ("-c", "-W", "2"),
+    };
+    vec![
+        count.into(),
+        "1".into(),
+        wait.into(),
+        millis.into(),
+        host.into(),
+    ]
+}
+
+/// Text for a single-quoted `PowerShell` string.
+fn powershell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Text for a double-quoted `AppleScript` string.
+fn applescript_quote(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', " ")
+    )
+}
+
+/// The command that shows a desktop notification on `os`, if it has one.
+pub fn notify_args(os: Os, title: &str, body: &str) -> Option<(&'static str, Vec<String>)> {
+    match os {
+        Os::Linux if crate::util::is_termux() => Some((
+            "termux-notification",
+            vec![
+                "--title".into(),
+                title.into(),
+                "--content".into(),
+                body.into(),
+            ],
+        )),
+        Os::Linux => Some(("notify-send", vec![title.into(), body.into()])),
+        Os::MacOs => Some((
+            "osascript",
+            vec![
+                "-e".into(),
+                format!(
+                    "display notification {} with title {}",
+                    applescript_quote(body),
+                    applescript_quote(title)
+                ),
+            ],
+        )),
+        Os::Windows => Some((
+            "powershell",
+            vec![
+                "-NoProfile".into(),
+                "-NonInteractive".into(),
+                "-Command".into(),
+                format!(
+                    "Add-Type -AssemblyName System.Windows.Forms; \
+                     $n = New-Object System.Windows.Forms.NotifyIcon; \
+                     $n.Icon = [System.Drawing.SystemIcons]::Information; \
+                     $n.Visible = $true; \
+                     $n.ShowBalloonTip(8000, {}, {}, 'Info'); \
+                     Start-Sleep -Seconds 8; $n.Dispose()",
+                    powershell_quote(title),
+                    powershell_quote(body)
+                ),
+            ],
+        )),
+        Os::Other => None,
+    }
+}
+
+/// The command that puts its stdin on the clipboard on `os`, if any.
+pub fn clipboard_args(os: Os, wayland: bool) -> Option<(&'static str, Vec<String>)> {
+    match os {
+        Os::Linux if crate::util::is_termux() => Some(("termux-clipboard-set", vec![])),
+        Os::Linux if wayland => Some(("wl-copy", vec![])),
+        Os::Linux => Some(("xclip", vec!["-selection".into(), "clipboard".into()])),
+        Os::MacOs => Some(("pbcopy", vec![])),
+        // `clip.exe` mangles anything outside the console code page.
+        Os::Windows => Some((
+            "powershell",
+            vec![
+                "-NoProfile".into(),
+                "-NonInteractive".into(),
+                "-Command".into(),
+                "[Console]::InputEncoding = [Text.Encoding]::UTF8; \
+                 Set-Clipboard -Value ([Console]::In.ReadToEnd())"
+                    .into(),
+            ],
+        )),
+        Os::Other => None,
+    }
+}
+
+/// Show `body` as a desktop notification.
+pub async fn notify(title: &str, body: &str) -> Result<()> {
+    let (program, args) = notify_args(Os::current(), title, body)
+        .context("Desktop notifications are not supported on this system")?;
+    let output = tokio::process::Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .with_context(|| format!("Could not run {program}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Put `text` on the clipboard.
+pub async fn copy_to_clipboard(text: &str) -> Result<()> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let (program, args) = clipboard_args(Os::current(), wayland)
+        .context("Clipboard access is not supported on this system")?;
+    let mut child = tokio::process::Command::new(program)
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Could not run {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_run_through_each_systems_shell() {
+        assert_eq!(
+            shell_args(Os::Linux, "ls -la", false),
+            ("sh", vec!["-c".to_string(), "ls -la".into()])
+        );
+        assert_eq!(shell_args(Os::MacOs, "ls", true).1[0], "-lc");
+        let (program, args) = shell_args(Os::Windows, "dir /b", true);
+        assert_eq!(program, "cmd");
+        assert_eq!(args, ["/D", "/S", "/C", "dir /b"]);
+        assert!(extra_env_vars(Os::Windows).contains(&"SYSTEMROOT"));
+        assert!(extra_env_vars(Os::Linux).is_empty());
+        assert_eq!(ping_args(Os::Windows, "10.0.0.2")[..2], ["-n", "1"]);
+        assert_eq!(ping_args(Os::Linux, "10.0.0.2")[3], "2");
+        assert_eq!(
+            executable_names(Os::Windows, "git", ".EXE;.CMD"),
+            ["git.exe", "git.cmd"]
+        );
+        assert_eq!(
+            executable_names(Os::Windows, "run.bat", ".EXE"),
+            ["run.bat"]
+        );
+        assert_eq!(executable_names(Os::Linux, "git", ".EXE"), ["git"]);
+    }
+
+    #[test]
+    fn windows_paths_match_without_case_or_slash_style() {
+        let forbidden = default_forbidden_paths(Os::Windows);
+        assert!(forbidden.contains(&"C:\\Windows".to_string()));
+        assert!(default_forbidden_paths(Os::Linux).contains(&"/etc".to_string()));
+
+        assert!(path_starts_with(
+            Os::Windows,
+            "c:/windows/System32/drivers",
+            "C:\\Windows"
+        ));
+        assert!(path_starts_with(
+            Os::Windows,
+            "C:\\Windows",
+            "C:\\Windows\\"
+        ));
+        assert!(!path_starts_with(
+            Os::Windows,
+            "C:\\WindowsApps\\x",
+            "C:\\Windows"
+        ));
+        assert!(path_starts_with(Os::Linux, "/etc/passwd", "/etc"));
+        assert!(!path_starts_with(Os::Linux, "/etcetera", "/etc"));
+
+        let home = Path::new("/home/zc");
+        assert_eq!(
+            expand_home("~/.ssh/id_rsa", Some(home)),
+            "/home/zc/.ssh/id_rsa"
+        );
+        assert_eq!(expand_home("~\\.aws", Some(home)), "/home/zc/.aws");
+        assert_eq!(expand_home("~/.ssh", None), "~/.ssh");
+    }
+
+    #[test]
+    fn notifications_and_clipboard_use_native_tools() {
+        let (program, args) = notify_args(Os::Windows, "ZeroClaw", "It's done").unwrap();
+        assert_eq!(program, "powershell");
+        assert!(args[3].contains("'It''s done'"));
+        let (program, args) = notify_args(Os::MacOs, "ZeroClaw", "Say \"hi\"").unwrap();
+        assert_eq!(program, "osascript");
+        assert_eq!(
+            args[1],
+            "display notification \"Say \\\"hi\\\"\" with title \"ZeroClaw\""
+        );
+        assert!(notify_args(Os::Other, "a", "b").is_none());
+
+        assert_eq!(clipboard_args(Os::MacOs, false).unwrap().0, "pbcopy");
+        assert_eq!(clipboard_args(Os::Windows, false).unwrap().0, "powershell");
+        if !crate::util::is_termux() {
+            assert_eq!(clipboard_args(Os::Linux, true).unwrap().0, "wl-copy");
+            assert_eq!(clipboard_args(Os::Linux, false).unwrap().0, "xclip");
+        }
+    }
+}
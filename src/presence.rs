@@ -0,0 +1,309 @@
+//! Owner presence ("home" / "away") detected from devices on the local
+//! network, over Bluetooth, or read from Home Assistant.
+//!
+//! The daemon probes on an interval and writes the current state to
+//! `workspace/presence/state.json`; the agent context and trigger rules read
+//! it from there.
+
+use crate::config::{Config, PresenceConfig, PresenceMethod};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Presence {
+    Home,
+    Away,
+}
+
+impl Presence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Home => "home",
+            Self::Away => "away",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresenceStatus {
+    pub state: Presence,
+    /// When the current state began
+    pub since: DateTime<Utc>,
+    /// Last probe, successful or not
+    pub checked_at: DateTime<Utc>,
+    /// Last time any device was seen
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+pub fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("presence").join("state.json")
+}
+
+/// The last recorded state, if the daemon has written one.
+pub fn load(workspace_dir: &Path) -> Option<PresenceStatus> {
+    let raw = std::fs::read_to_string(state_path(workspace_dir)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn save(workspace_dir: &Path, status: &PresenceStatus) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(status)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// A line for the agent context ("Owner is away since …"), or `None` when
+/// presence is disabled or the daemon has not probed recently.
+pub fn context_note(config: &Config) -> Option<String> {
+    if !config.presence.enabled {
+        return None;
+    }
+    let status = load(&config.workspace_dir)?;
+    let fresh_for = chrono::Duration::seconds(
+        i64::try_from(config.presence.poll_secs.saturating_mul(3).max(300)).unwrap_or(i64::MAX),
+    );
+    if Utc::now() - status.checked_at > fresh_for {
+        return None;
+    }
+    Some(format!(
+        "Owner presence: {} since {}",
+        status.state.as_str(),
+        status.since.format("%Y-%m-%d %H:%M UTC")
+    ))
+}
+
+/// Turns raw "seen / not seen" probes into a debounced home/away state.
+struct Tracker {
+    status: Option<PresenceStatus>,
+    away_after: chrono::Duration,
+}
+
+impl Tracker {
+    /// Record one probe; returns the new state when it changed.
+    fn observe(&mut self, seen: bool, now: DateTime<Utc>) -> Option<Presence> {
+        let last_seen = if seen {
+            Some(now)
+        } else {
+            self.status.as_ref().and_then(|s| s.last_seen)
+        };
+        let next = match (&self.status, seen) {
+            (_, true) => Presence::Home,
+            (None, false) => Presence::Away,
+            (Some(status), false) => {
+                let gone_long_enough = last_seen.is_none_or(|at| now - at >= self.away_after);
+                if status.state == Presence::Home && gone_long_enough {
+                    Presence::Away
+                } else {
+                    status.state
+                }
+            }
+        };
+        let changed = self.status.as_ref().map(|s| s.state) != Some(next);
+        let since = match &self.status {
+            Some(status) if !changed => status.since,
+            // The owner left when their devices were last seen.
+            Some(_) if next == Presence::Away => last_seen.unwrap_or(now),
+            _ => now,
+        };
+        let first = self.status.is_none();
+        self.status = Some(PresenceStatus {
+            state: next,
+            since,
+            checked_at: now,
+            last_seen,
+        });
+        (changed && !first).then_some(next)
+    }
+}
+
+/// Whether `table` (`/proc/net/arp` or `arp -an` output) has a resolved
+/// entry for `device` (an IP or MAC address).
+fn arp_has(table: &str, device: &str) -> bool {
+    let device = device.to_ascii_lowercase();
+    table.lines().any(|line| {
+        let line = line.to_ascii_lowercase();
+        let mut tokens = line.split(|c: char| c.is_whitespace() || c == '(' || c == ')');
+        let matches = tokens.any(|t| t == device);
+        matches
+            && !line.contains("incomplete")
+            && !line.contains("00:00:00:00:00:00")
+            && !line.split_whitespace().any(|t| t == "0x0")
+    })
+}
+
+async fn command_succeeds(program: &str, args: &[&str]) -> Result<(bool, String)> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run `{program}` for presence detection"))?;
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    ))
+}
+
+/// One probe: is any of the owner's devices around right now?
+async fn probe(config: &PresenceConfig, client: &crate::util::LazyClient) -> Result<bool> {
+    match config.method {
+        PresenceMethod::Ping => {
+            let os = crate::platform::Os::current();
+            for device in &config.devices {
+                let args = crate::platform::ping_args(os, device);
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                if command_succeeds("ping", &args).await?.0 {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        PresenceMethod::Arp => {
+            let table = match std::fs::read_to_string("/proc/net/arp") {
+                Ok(table) => table,
+                Err(_) => command_succeeds("arp", &["-an"]).await?.1,
+            };
+            Ok(config.devices.iter().any(|d| arp_has(&table, d)))
+        }
+        PresenceMethod::Bluetooth => {
+            for device in &config.devices {
+                let (ok, name) = command_succeeds("hcitool", &["name", device]).await?;
+                if ok && !name.trim().is_empty() {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        PresenceMethod::HomeAssistant => {
+            let (Some(url), Some(token), Some(entity)) = (
+                config.home_assistant_url.as_deref(),
+                config.home_assistant_token.as_deref(),
+                config.entity.as_deref(),
+            ) else {
+                anyhow::bail!(
+                    "presence.method = \"home_assistant\" needs home_assistant_url, home_assistant_token and entity"
+                );
+            };
+            let state: serde_json::Value = client
+                .get(format!("{}/api/states/{entity}", url.trim_end_matches('/')))
+                .bearer_auth(token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            Ok(state.get("state").and_then(|s| s.as_str()) == Some("home"))
+        }
+    }
+}
+
+/// Daemon component: probe on an interval and keep the state file current.
+pub async fn run(config: Config) -> Result<()> {
+    let presence = &config.presence;
+    if presence.devices.is_empty() && presence.method != PresenceMethod::HomeAssistant {
+        anyhow::bail!("presence is enabled but presence.devices is empty");
+    }
+    let client = crate::util::LazyClient::new(Duration::from_secs(10));
+    let mut tracker = Tracker {
+        status: load(&config.workspace_dir),
+        away_after: chrono::Duration::minutes(
+            i64::try_from(presence.away_after_mins).unwrap_or(i64::MAX),
+        ),
+    };
+    let mut interval = tokio::time::interval(Duration::from_secs(presence.poll_secs.max(5)));
+
+    loop {
+        interval.tick().await;
+        let seen = match probe(presence, &client).await {
+            Ok(seen) => seen,
+            Err(e) => {
+                crate::health::mark_component_error("presence", e.to_string());
+                tracing::warn!("Presence probe failed: {e}");
+                continue;
+            }
+        };
+        if let Some(state) = tracker.observe(seen, Utc::now()) {
+            tracing::info!("Owner is now {}", state.as_str());
+        }
+        if let Some(status) = &tracker.status {
+            save(&config.workspace_dir, status)?;
+        }
+        crate::health::mark_component_ok("presence");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn away_needs_the_grace_period_and_dates_from_last_sighting() {
+        let mut tracker = Tracker {
+            status: None,
+            away_after: chrono::Duration::minutes(10),
+        };
+        let t0 = Utc::now();
+        let at = |mins| t0 + chrono::Duration::minutes(mins);
+
+        // The first probe sets the state without reporting a change.
+        assert_eq!(tracker.observe(true, t0), None);
+        assert_eq!(tracker.observe(false, at(5)), None);
+        assert_eq!(tracker.status.as_ref().unwrap().state, Presence::Home);
+
+        assert_eq!(tracker.observe(false, at(11)), Some(Presence::Away));
+        assert_eq!(tracker.status.as_ref().unwrap().since, t0);
+        assert_eq!(tracker.observe(false, at(20)), None);
+
+        assert_eq!(tracker.observe(true, at(30)), Some(Presence::Home));
+        assert_eq!(tracker.status.as_ref().unwrap().since, at(30));
+    }
+
+    #[test]
+    fn arp_tables_from_linux_and_bsd() {
+        let proc = "IP address       HW type     Flags       HW address            Mask     Device\n\
+                    192.168.1.23     0x1         0x2         a4:83:e7:12:34:56     *        wlan0\n\
+                    192.168.1.40     0x1         0x0         00:00:00:00:00:00     *        wlan0\n";
+        assert!(arp_has(proc, "192.168.1.23"));
+        assert!(arp_has(proc, "A4:83:E7:12:34:56"));
+        assert!(!arp_has(proc, "192.168.1.40"));
+        assert!(!arp_has(proc, "192.168.1.2"));
+
+        let bsd = "? (192.168.1.23) at a4:83:e7:12:34:56 on en0 ifscope [ethernet]\n\
+                   ? (192.168.1.40) at (incomplete) on en0 ifscope [ethernet]\n";
+        assert!(arp_has(bsd, "192.168.1.23"));
+        assert!(!arp_has(bsd, "192.168.1.40"));
+    }
+
+    #[test]
+    fn context_note_requires_a_fresh_state_file() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.presence.enabled = true;
+        assert_eq!(context_note(&config), None);
+
+        let since = Utc::now() - chrono::Duration::hours(2);
+        let mut status = PresenceStatus {
+            state: Presence::Away,
+            since,
+            checked_at: Utc::now(),
+            last_seen: Some(since),
+        };
+        save(tmp.path(), &status).unwrap();
+        assert!(context_note(&config)
+            .unwrap()
+            .starts_with("Owner presence: away since "));
+
+        status.checked_at = Utc::now() - chrono::Duration::hours(1);
+        save(tmp.path(), &status).unwrap();
+        assert_eq!(context_note(&config), None);
+    }
+}
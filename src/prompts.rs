@@ -0,0 +1,518 @@
+//! Reusable prompt templates.
+//!
+//! Templates live in `<workspace>/prompts/<name>.md` (or `.txt`) and are
+//! invoked as `/run <name> key=value …` from chat, `zeroclaw agent -m`
+//! (and so from cron jobs) and the `/webhook` endpoint. The syntax is a small
+//! Jinja subset:
+//!
+//! ```text
+//! {% include "house-style" %}
+//! Summarize the week for {{ team }} as of {{ date }}.
+//! Tone: {{ tone | default("friendly") }}.
+//! {{ input }}
+//! ```
+//!
+//! Words after the name that are not `key=value` become `{{ input }}`.
+//! `date`, `time` and `weekday` are always defined.
+
+use crate::config::{CommandAlias, Config};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Chat prefix that runs a template.
+pub const RUN_COMMAND: &str = "/run";
+const EXTENSIONS: [&str; 2] = ["md", "txt"];
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// A parsed `/run <name> key=value …` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub name: String,
+    pub vars: BTreeMap<String, String>,
+}
+
+pub fn prompts_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("prompts")
+}
+
+/// Split `args` on whitespace, keeping quoted runs (`"a b"`, `'a b'`) together.
+fn split_args(args: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    for c in args.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        anyhow::bail!("Unclosed quote in: {args}");
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Parse what follows `/run`: a template name, then `key=value` pairs and
+/// free text (collected into `input`).
+pub fn parse_invocation(args: &str) -> Result<Invocation> {
+    let mut words = split_args(args)?.into_iter();
+    let name = words
+        .next()
+        .context("Usage: /run <template> [key=value ...]")?;
+    let mut vars = BTreeMap::new();
+    let mut input = Vec::new();
+    for word in words {
+        match word.split_once('=') {
+            Some((key, value)) if is_identifier(key) => {
+                vars.insert(key.to_string(), value.to_string());
+            }
+            _ => input.push(word),
+        }
+    }
+    if !input.is_empty() {
+        vars.entry("input".into())
+            .or_insert_with(|| input.join(" "));
+    }
+    Ok(Invocation { name, vars })
+}
+
+fn is_identifier(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn template_path(workspace_dir: &Path, name: &str) -> Result<PathBuf> {
+    let valid = !name.is_empty()
+        && name.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+    if !valid {
+        anyhow::bail!("Invalid template name: {name}");
+    }
+    let dir = prompts_dir(workspace_dir);
+    EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{name}.{ext}")))
+        .find(|path| path.is_file())
+        .with_context(|| {
+            format!(
+                "No template '{name}' in {} (expected {name}.md)",
+                dir.display()
+            )
+        })
+}
+
+/// Template names under `prompts/`, sorted.
+pub fn list(workspace_dir: &Path) -> Vec<String> {
+    fn walk(dir: &Path, prefix: &str, out: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if path.is_dir() {
+                walk(&path, &format!("{prefix}{file_name}/"), out);
+            } else if let Some((stem, ext)) = file_name.rsplit_once('.') {
+                if EXTENSIONS.contains(&ext) {
+                    out.push(format!("{prefix}{stem}"));
+                }
+            }
+        }
+    }
+    let mut names = Vec::new();
+    walk(&prompts_dir(workspace_dir), "", &mut names);
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn builtin_vars() -> BTreeMap<String, String> {
+    let now = chrono::Local::now();
+    BTreeMap::from([
+        ("date".into(), now.format("%Y-%m-%d").to_string()),
+        ("time".into(), now.format("%H:%M").to_string()),
+        ("weekday".into(), now.format("%A").to_string()),
+    ])
+}
+
+/// Render template `name` with `vars` (plus the built-ins).
+pub fn render(workspace_dir: &Path, name: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    render_with(workspace_dir, name, vars, |r| r.render_named(name))
+}
+
+/// Render template text that is not in a file (an alias `prompt`).
+pub fn render_inline(
+    workspace_dir: &Path,
+    source: &str,
+    vars: &BTreeMap<String, String>,
+) -> Result<String> {
+    render_with(workspace_dir, "inline prompt", vars, |r| {
+        r.render_source(source)
+    })
+}
+
+fn render_with(
+    workspace_dir: &Path,
+    label: &str,
+    vars: &BTreeMap<String, String>,
+    run: impl FnOnce(&mut Renderer<'_>) -> Result<String>,
+) -> Result<String> {
+    let mut all = builtin_vars();
+    all.extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let mut renderer = Renderer {
+        workspace_dir,
+        vars: &all,
+        stack: Vec::new(),
+        missing: Vec::new(),
+    };
+    let prompt = run(&mut renderer)?;
+    let mut missing = renderer.missing;
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        let hint: Vec<String> = missing.iter().map(|m| format!("{m}=…")).collect();
+        anyhow::bail!(
+            "Template '{label}' needs {} (pass {})",
+            missing.join(", "),
+            hint.join(" ")
+        );
+    }
+    Ok(prompt.trim().to_string())
+}
+
+struct Renderer<'a> {
+    workspace_dir: &'a Path,
+    vars: &'a BTreeMap<String, String>,
+    /// Templates being rendered, outermost first, to catch include cycles.
+    stack: Vec<String>,
+    missing: Vec<String>,
+}
+
+impl Renderer<'_> {
+    fn render_named(&mut self, name: &str) -> Result<String> {
+        if self.stack.iter().any(|n| n == name) {
+            anyhow::bail!(
+                "Template include cycle: {} -> {name}",
+                self.stack.join(" -> ")
+            );
+        }
+        if self.stack.len() >= MAX_INCLUDE_DEPTH {
+            anyhow::bail!("Templates nested more than {MAX_INCLUDE_DEPTH} deep at '{name}'");
+        }
+        let path = template_path(self.workspace_dir, name)?;
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        self.stack.push(name.to_string());
+        let rendered = self.render_source(&source)?;
+        self.stack.pop();
+        Ok(rendered)
+    }
+
+    /// Expand `{{ … }}` and `{% include … %}` in `source`.
+    fn render_source(&mut self, source: &str) -> Result<String> {
+        let mut out = String::with_capacity(source.len());
+        let mut rest = source;
+        loop {
+            let next_var = rest.find("{{");
+            let next_tag = rest.find("{%");
+            let (start, close) = match (next_var, next_tag) {
+                (Some(v), Some(t)) if t < v => (t, "%}"),
+                (Some(v), _) => (v, "}}"),
+                (None, Some(t)) => (t, "%}"),
+                (None, None) => break,
+            };
+            out.push_str(&rest[..start]);
+            let body_start = start + 2;
+            let end = rest[body_start..]
+                .find(close)
+                .with_context(|| format!("Unclosed '{}' in template", &rest[start..body_start]))?;
+            let body = rest[body_start..body_start + end].trim();
+            if close == "}}" {
+                let (var, default) = parse_expression(body)?;
+                match self.vars.get(var).map(String::as_str).or(default) {
+                    Some(value) => out.push_str(value),
+                    None => self.missing.push(var.to_string()),
+                }
+            } else {
+                let target = body
+                    .strip_prefix("include")
+                    .map(str::trim)
+                    .and_then(unquote)
+                    .with_context(|| format!("Unsupported template tag: {{% {body} %}}"))?;
+                out.push_str(self.render_named(target)?.trim_end());
+            }
+            rest = &rest[body_start + end + close.len()..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// `name` or `name | default("value")`.
+fn parse_expression(body: &str) -> Result<(&str, Option<&str>)> {
+    let (var, filter) = match body.split_once('|') {
+        Some((var, filter)) => (var.trim(), Some(filter.trim())),
+        None => (body, None),
+    };
+    if !is_identifier(var) {
+        anyhow::bail!("Invalid template variable: {{{{ {body} }}}}");
+    }
+    let default = match filter {
+        None => None,
+        Some(filter) => Some(
+            filter
+                .strip_prefix("default(")
+                .and_then(|f| f.strip_suffix(')'))
+                .map(str::trim)
+                .and_then(unquote)
+                .with_context(|| format!("Unsupported template filter: {filter}"))?,
+        ),
+    };
+    Ok((var, default))
+}
+
+fn unquote(raw: &str) -> Option<&str> {
+    raw.strip_prefix('"')
+        .and_then(|r| r.strip_suffix('"'))
+        .or_else(|| raw.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')))
+}
+
+/// If `message` is a `/run` command, the rendered prompt (or why it failed).
+pub fn expand(workspace_dir: &Path, message: &str) -> Option<Result<String>> {
+    let args = command_args(message, RUN_COMMAND)?;
+    Some(parse_invocation(args).and_then(|inv| render(workspace_dir, &inv.name, &inv.vars)))
+}
+
+/// What follows `/<command>` in `message`, if it starts with that command.
+pub(crate) fn command_args<'a>(message: &'a str, command: &str) -> Option<&'a str> {
+    let args = message.trim().strip_prefix(command)?;
+    (args.is_empty() || args.starts_with(char::is_whitespace)).then_some(args)
+}
+
+/// Expand a configured alias (`/standup …`) or a `/run` command; `None` for
+/// ordinary messages. Aliases work in the CLI and chat channels.
+pub fn expand_command(config: &Config, message: &str) -> Option<Result<String>> {
+    config
+        .aliases
+        .commands
+        .iter()
+        .find_map(|(name, alias)| {
+            let args = command_args(message, &format!("/{name}"))?;
+            Some(expand_alias(&config.workspace_dir, name, alias, args))
+        })
+        .or_else(|| expand(&config.workspace_dir, message))
+}
+
+fn expand_alias(
+    workspace_dir: &Path,
+    name: &str,
+    alias: &CommandAlias,
+    args: &str,
+) -> Result<String> {
+    let mut vars = alias.vars.clone();
+    let invocation = parse_invocation(&format!("{name} {args}"))?;
+    vars.extend(invocation.vars);
+    vars.insert("args".into(), args.trim().to_string());
+    let body = match (&alias.template, &alias.prompt) {
+        (Some(template), None) => render(workspace_dir, template, &vars)?,
+        (None, Some(prompt)) => render_inline(workspace_dir, prompt, &vars)?,
+        _ => anyhow::bail!("Alias /{name} needs exactly one of `template` or `prompt`"),
+    };
+    let mut preamble = Vec::new();
+    if let Some(persona) = alias.persona.as_deref().map(str::trim) {
+        if !persona.is_empty() {
+            preamble.push(format!("For this request, act as: {persona}"));
+        }
+    }
+    if !alias.tools.is_empty() {
+        preamble.push(format!(
+            "Only use these tools for this request: {}.",
+            alias.tools.join(", ")
+        ));
+    }
+    if preamble.is_empty() {
+        Ok(body)
+    } else {
+        Ok(format!("[/{name}] {}\n\n{body}", preamble.join(" ")))
+    }
+}
+
+pub fn handle_command(command: crate::PromptCommands, config: &Config) -> Result<()> {
+    let workspace_dir = &config.workspace_dir;
+    match command {
+        crate::PromptCommands::List => {
+            let names = list(workspace_dir);
+            let aliases = &config.aliases.commands;
+            if names.is_empty() && aliases.is_empty() {
+                println!(
+                    "No prompt templates yet. Add one to {}",
+                    prompts_dir(workspace_dir).join("standup.md").display()
+                );
+                return Ok(());
+            }
+            if !names.is_empty() {
+                println!("📝 Prompt templates ({}):", names.len());
+                for name in names {
+                    println!("- {name}");
+                }
+            }
+            if !aliases.is_empty() {
+                println!("⚡ Aliases ({}):", aliases.len());
+                for (name, alias) in aliases {
+                    let target = alias
+                        .template
+                        .as_deref()
+                        .map_or_else(|| "inline prompt".to_string(), |t| format!("template {t}"));
+                    println!(
+                        "- /{name} → {target}{}",
+                        alias
+                            .description
+                            .as_deref()
+                            .map(|d| format!(" — {d}"))
+                            .unwrap_or_default()
+                    );
+                }
+            }
+            Ok(())
+        }
+        crate::PromptCommands::Show { name, vars } => {
+            let invocation = parse_invocation(&format!("{name} {}", vars.join(" ")))?;
+            println!(
+                "{}",
+                render(workspace_dir, &invocation.name, &invocation.vars)?
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn workspace(files: &[(&str, &str)]) -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        for (name, body) in files {
+            let path = prompts_dir(tmp.path()).join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, body).unwrap();
+        }
+        tmp
+    }
+
+    #[test]
+    fn run_commands_parse_pairs_quotes_and_free_text() {
+        let inv = parse_invocation(r#" weekly team=core tone="very formal" the rest"#).unwrap();
+        assert_eq!(inv.name, "weekly");
+        assert_eq!(inv.vars["team"], "core");
+        assert_eq!(inv.vars["tone"], "very formal");
+        assert_eq!(inv.vars["input"], "the rest");
+        assert!(parse_invocation("").is_err());
+        assert!(parse_invocation("x a=\"open").is_err());
+    }
+
+    #[test]
+    fn templates_render_variables_defaults_and_includes() {
+        let tmp = workspace(&[
+            ("style.md", "Be concise.\n"),
+            (
+                "ops/weekly.md",
+                "{% include \"style\" %}\nReport for {{ team }}, tone {{ tone | default('friendly') }}.",
+            ),
+            ("loop.md", "{% include 'loop' %}"),
+        ]);
+        let vars = BTreeMap::from([("team".to_string(), "core".to_string())]);
+        assert_eq!(
+            render(tmp.path(), "ops/weekly", &vars).unwrap(),
+            "Be concise.\nReport for core, tone friendly."
+        );
+        assert_eq!(list(tmp.path()), ["loop", "ops/weekly", "style"]);
+
+        let err = render(tmp.path(), "ops/weekly", &BTreeMap::new()).unwrap_err();
+        assert!(err.to_string().contains("needs team"), "{err}");
+        assert!(render(tmp.path(), "loop", &vars)
+            .unwrap_err()
+            .to_string()
+            .contains("cycle"));
+        assert!(render(tmp.path(), "../secrets", &vars).is_err());
+
+        let expanded = expand(tmp.path(), "/run ops/weekly team=infra").unwrap();
+        assert!(expanded
+            .unwrap()
+            .ends_with("Report for infra, tone friendly."));
+        assert!(expand(tmp.path(), "/running late").is_none());
+        assert!(expand(tmp.path(), "hello").is_none());
+    }
+
+    #[test]
+    fn aliases_fill_templates_and_state_persona_and_tools() {
+        let tmp = workspace(&[(
+            "forecast.md",
+            "Forecast for {{ place }}, {{ when | default(\"today\") }}.",
+        )]);
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.aliases.commands.insert(
+            "weather-home".into(),
+            CommandAlias {
+                template: Some("forecast".into()),
+                vars: BTreeMap::from([("place".into(), "Oslo".into())]),
+                tools: vec!["weather_api".into()],
+                ..CommandAlias::default()
+            },
+        );
+        config.aliases.commands.insert(
+            "standup".into(),
+            CommandAlias {
+                prompt: Some("Standup for {{ team }}: {{ args }}".into()),
+                persona: Some("a terse scrum master".into()),
+                ..CommandAlias::default()
+            },
+        );
+
+        let weather = expand_command(&config, "/weather-home when=tomorrow")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            weather,
+            "[/weather-home] Only use these tools for this request: weather_api.\n\nForecast for Oslo, tomorrow."
+        );
+        let standup = expand_command(&config, "/standup team=core")
+            .unwrap()
+            .unwrap();
+        assert!(standup.starts_with("[/standup] For this request, act as: a terse scrum master"));
+        assert!(standup.ends_with("Standup for core: team=core"));
+        assert!(expand_command(&config, "/standup").unwrap().is_err());
+        assert!(expand_command(&config, "/run forecast place=Rome")
+            .unwrap()
+            .is_ok());
+        assert!(expand_command(&config, "/weather-homes").is_none());
+        assert!(expand_command(&config, "what's the weather?").is_none());
+    }
+}
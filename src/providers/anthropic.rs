@@ -1,12 +1,12 @@
-use crate::providers::traits::Provider;
+use crate::providers::traits::{ImageAttachment, Provider};
+use crate::util::LazyClient;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
-    client: Client,
+    client: LazyClient,
 }
 
 #[derive(Debug, Serialize)]
@@ -22,7 +22,7 @@ struct ChatRequest {
 #[derive(Debug, Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,6 +35,29 @@ struct ContentBlock {
     text: String,
 }
 
+/// Text alone, or image blocks followed by the text (images first is what
+/// Anthropic recommends).
+fn user_content(message: &str, images: &[ImageAttachment]) -> serde_json::Value {
+    if images.is_empty() {
+        return message.into();
+    }
+    let mut blocks: Vec<serde_json::Value> = images
+        .iter()
+        .map(|image| {
+            serde_json::json!({
+                "type": "image",
+                "source": {
+                    "type": "base64",
+                    "media_type": image.mime_type,
+                    "data": image.base64(),
+                }
+            })
+        })
+        .collect();
+    blocks.push(serde_json::json!({ "type": "text", "text": message }));
+    serde_json::Value::Array(blocks)
+}
+
 impl AnthropicProvider {
     pub fn new(api_key: Option<&str>) -> Self {
         Self::with_base_url(api_key, None)
@@ -51,11 +74,7 @@ impl AnthropicProvider {
                 .filter(|k| !k.is_empty())
                 .map(ToString::to_string),
             base_url,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(120)),
         }
     }
 
@@ -66,12 +85,30 @@ impl AnthropicProvider {
 
 #[async_trait]
 impl Provider for AnthropicProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        // Any response leaves a kept-alive connection in the shared pool.
+        self.client.head(&self.base_url).send().await?;
+        Ok(())
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
         message: &str,
         model: &str,
         temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
     ) -> anyhow::Result<String> {
         let credential = self.credential.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -85,7 +122,7 @@ impl Provider for AnthropicProvider {
             system: system_prompt.map(ToString::to_string),
             messages: vec![Message {
                 role: "user".to_string(),
-                content: message.to_string(),
+                content: user_content(message, images),
             }],
             temperature,
         };
@@ -124,6 +161,16 @@ impl Provider for AnthropicProvider {
 mod tests {
     use super::*;
 
+    #[test]
+    fn images_come_before_the_text() {
+        assert_eq!(user_content("hi", &[]), serde_json::json!("hi"));
+        let image = ImageAttachment::new("image/jpeg", b"jpg".to_vec());
+        let content = user_content("What is this?", &[image]);
+        assert_eq!(content[0]["source"]["media_type"], "image/jpeg");
+        assert_eq!(content[0]["source"]["data"], "anBn");
+        assert_eq!(content[1]["text"], "What is this?");
+    }
+
     #[test]
     fn creates_with_key() {
         let p = AnthropicProvider::new(Some("sk-ant-test123"));
@@ -209,7 +256,7 @@ mod tests {
             system: None,
             messages: vec![Message {
                 role: "user".to_string(),
-                content: "hello".to_string(),
+                content: "hello".into(),
             }],
             temperature: 0.7,
         };
@@ -230,7 +277,7 @@ mod tests {
             system: Some("You are ZeroClaw".to_string()),
             messages: vec![Message {
                 role: "user".to_string(),
-                content: "hello".to_string(),
+                content: "hello".into(),
             }],
             temperature: 0.7,
         };
@@ -2,9 +2,9 @@
 //! Most LLM APIs follow the same `/v1/chat/completions` format.
 //! This module provides a single implementation that works for all of them.
 
-use crate::providers::traits::Provider;
+use crate::providers::traits::{DeltaSender, ImageAttachment, Provider};
+use crate::util::LazyClient;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 /// A provider that speaks the OpenAI-compatible chat completions API.
@@ -15,7 +15,7 @@ pub struct OpenAiCompatibleProvider {
     pub(crate) base_url: String,
     pub(crate) api_key: Option<String>,
     pub(crate) auth_header: AuthStyle,
-    client: Client,
+    client: LazyClient,
 }
 
 /// How the provider expects the API key to be sent.
@@ -36,11 +36,7 @@ impl OpenAiCompatibleProvider {
             base_url: base_url.trim_end_matches('/').to_string(),
             api_key: api_key.map(ToString::to_string),
             auth_header: auth_style,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(120)),
         }
     }
 
@@ -72,12 +68,14 @@ struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     temperature: f64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -218,47 +216,94 @@ impl OpenAiCompatibleProvider {
     }
 }
 
-#[async_trait]
-impl Provider for OpenAiCompatibleProvider {
-    async fn chat_with_system(
-        &self,
-        system_prompt: Option<&str>,
-        message: &str,
-        model: &str,
-        temperature: f64,
-    ) -> anyhow::Result<String> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| {
+impl OpenAiCompatibleProvider {
+    fn require_key(&self) -> anyhow::Result<&str> {
+        self.api_key.as_deref().ok_or_else(|| {
             anyhow::anyhow!(
                 "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
                 self.name
             )
-        })?;
+        })
+    }
 
+    /// POST to chat completions; non-success responses are returned as-is.
+    /// `content` is the user's turn, from [`super::openai_user_content`].
+    async fn post_chat(
+        &self,
+        api_key: &str,
+        system_prompt: Option<&str>,
+        content: serde_json::Value,
+        model: &str,
+        temperature: f64,
+        stream: bool,
+    ) -> anyhow::Result<reqwest::Response> {
         let mut messages = Vec::new();
 
         if let Some(sys) = system_prompt {
             messages.push(Message {
                 role: "system".to_string(),
-                content: sys.to_string(),
+                content: sys.into(),
             });
         }
 
         messages.push(Message {
             role: "user".to_string(),
-            content: message.to_string(),
+            content,
         });
 
         let request = ChatRequest {
             model: model.to_string(),
             messages,
             temperature,
+            stream,
         };
 
         let url = self.chat_completions_url();
 
-        let response = self
+        Ok(self
             .apply_auth_header(self.client.post(&url).json(&request), api_key)
             .send()
+            .await?)
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAiCompatibleProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        // Any response leaves a kept-alive connection in the shared pool.
+        self.client.head(&self.base_url).send().await?;
+        Ok(())
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let api_key = self.require_key()?;
+        let response = self
+            .post_chat(
+                api_key,
+                system_prompt,
+                super::openai_user_content(message, images),
+                model,
+                temperature,
+                false,
+            )
             .await?;
 
         if !response.status().is_success() {
@@ -289,6 +334,42 @@ impl Provider for OpenAiCompatibleProvider {
             .map(|c| c.message.content)
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))
     }
+
+    async fn chat_with_system_streaming(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        deltas: &DeltaSender,
+    ) -> anyhow::Result<String> {
+        let api_key = self.require_key()?;
+        let response = self
+            .post_chat(
+                api_key,
+                system_prompt,
+                message.into(),
+                model,
+                temperature,
+                true,
+            )
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            // No chat completions endpoint: answer via the Responses API in one piece.
+            let reply = self
+                .chat_via_responses(api_key, system_prompt, message, model)
+                .await?;
+            let _ = deltas.send(reply.clone());
+            return Ok(reply);
+        }
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("{} API error: {error}", self.name);
+        }
+
+        super::sse::read_chat_stream(response, deltas).await
+    }
 }
 
 #[cfg(test)]
@@ -339,19 +420,21 @@ mod tests {
             messages: vec![
                 Message {
                     role: "system".to_string(),
-                    content: "You are ZeroClaw".to_string(),
+                    content: "You are ZeroClaw".into(),
                 },
                 Message {
                     role: "user".to_string(),
-                    content: "hello".to_string(),
+                    content: "hello".into(),
                 },
             ],
             temperature: 0.7,
+            stream: false,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("llama-3.3-70b"));
         assert!(json.contains("system"));
         assert!(json.contains("user"));
+        assert!(!json.contains("stream"));
     }
 
     #[test]
@@ -3,17 +3,17 @@
 //! - Gemini CLI OAuth tokens (reuse existing ~/.gemini/ authentication)
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
-use crate::providers::traits::Provider;
+use crate::providers::traits::{ImageAttachment, Provider};
+use crate::util::LazyClient;
 use async_trait::async_trait;
 use directories::UserDirs;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// Gemini provider supporting multiple authentication methods.
 pub struct GeminiProvider {
     api_key: Option<String>,
-    client: Client,
+    client: LazyClient,
 }
 
 // ══════════════════════════════════════════════════════════════════════════════
@@ -38,7 +38,35 @@ struct Content {
 
 #[derive(Debug, Serialize)]
 struct Part {
-    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inline_data: Option<InlineData>,
+}
+
+impl Part {
+    fn text(text: &str) -> Self {
+        Self {
+            text: Some(text.to_string()),
+            inline_data: None,
+        }
+    }
+
+    fn image(image: &ImageAttachment) -> Self {
+        Self {
+            text: None,
+            inline_data: Some(InlineData {
+                mime_type: image.mime_type.clone(),
+                data: image.base64(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct InlineData {
+    mime_type: String,
+    data: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -110,11 +138,7 @@ impl GeminiProvider {
 
         Self {
             api_key: resolved_key,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(120)),
         }
     }
 
@@ -181,12 +205,33 @@ impl GeminiProvider {
 
 #[async_trait]
 impl Provider for GeminiProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        // Any response leaves a kept-alive connection in the shared pool.
+        self.client
+            .head("https://generativelanguage.googleapis.com/")
+            .send()
+            .await?;
+        Ok(())
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
         message: &str,
         model: &str,
         temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
     ) -> anyhow::Result<String> {
         let api_key = self.api_key.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -201,17 +246,17 @@ impl Provider for GeminiProvider {
         // Build request
         let system_instruction = system_prompt.map(|sys| Content {
             role: None,
-            parts: vec![Part {
-                text: sys.to_string(),
-            }],
+            parts: vec![Part::text(sys)],
         });
 
         let request = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: message.to_string(),
-                }],
+                parts: images
+                    .iter()
+                    .map(Part::image)
+                    .chain(std::iter::once(Part::text(message)))
+                    .collect(),
             }],
             system_instruction,
             generation_config: GenerationConfig {
@@ -320,15 +365,14 @@ mod tests {
         let request = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: "Hello".to_string(),
-                }],
+                parts: vec![
+                    Part::image(&ImageAttachment::new("image/png", b"png".to_vec())),
+                    Part::text("Hello"),
+                ],
             }],
             system_instruction: Some(Content {
                 role: None,
-                parts: vec![Part {
-                    text: "You are helpful".to_string(),
-                }],
+                parts: vec![Part::text("You are helpful")],
             }),
             generation_config: GenerationConfig {
                 temperature: 0.7,
@@ -339,6 +383,7 @@ mod tests {
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("\"role\":\"user\""));
         assert!(json.contains("\"text\":\"Hello\""));
+        assert!(json.contains(r#"{"inline_data":{"mime_type":"image/png","data":"cG5n"}}"#));
         assert!(json.contains("\"temperature\":0.7"));
         assert!(json.contains("\"maxOutputTokens\":8192"));
     }
@@ -0,0 +1,366 @@
+use crate::agent::tool_calls::{CLOSE, OPEN};
+use crate::providers::traits::{DeltaSender, ImageAttachment, Provider};
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write;
+
+const BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+/// Groq's OpenAI-compatible API. Tool calls use the agent's text protocol;
+/// the provider also accepts native `tool_calls` in a reply and recovers the
+/// model's text when Groq rejects a call it could not parse
+/// (`tool_use_failed`), so the agent can repair it instead of failing.
+pub struct GroqProvider {
+    api_key: Option<String>,
+    client: LazyClient,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: String,
+    content: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<NativeToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeToolCall {
+    function: NativeFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeFunction {
+    name: String,
+    /// JSON-encoded arguments
+    #[serde(default)]
+    arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    data: Vec<ModelInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelInfo {
+    id: String,
+    #[serde(default = "default_active")]
+    active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl ResponseMessage {
+    /// The reply text, with native tool calls rewritten as protocol blocks.
+    fn into_text(self) -> String {
+        let mut text = self.content.unwrap_or_default();
+        for call in self.tool_calls {
+            let arguments = serde_json::from_str::<Value>(&call.function.arguments)
+                .unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
+            let block = serde_json::json!({"name": call.function.name, "arguments": arguments});
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            let _ = write!(text, "{OPEN}{block}{CLOSE}");
+        }
+        text
+    }
+}
+
+/// The model's raw output from a `tool_use_failed` error body, if any.
+fn failed_generation(body: &str) -> Option<String> {
+    let error: Value = serde_json::from_str(body).ok()?;
+    let error = error.get("error")?;
+    if error.get("code").and_then(Value::as_str) != Some("tool_use_failed") {
+        return None;
+    }
+    error
+        .get("failed_generation")
+        .and_then(Value::as_str)
+        .filter(|text| !text.trim().is_empty())
+        .map(String::from)
+}
+
+/// Chat models from a model list, sorted; speech and guard models are left out.
+fn chat_models(list: ModelList) -> Vec<String> {
+    let mut models: Vec<String> = list
+        .data
+        .into_iter()
+        .filter(|m| m.active)
+        .map(|m| m.id)
+        .filter(|id| {
+            !["whisper", "tts", "guard"]
+                .iter()
+                .any(|skip| id.contains(skip))
+        })
+        .collect();
+    models.sort();
+    models
+}
+
+/// Models the key can use, for the onboarding wizard.
+pub fn list_models_blocking(api_key: &str) -> anyhow::Result<Vec<String>> {
+    let list: ModelList = reqwest::blocking::Client::new()
+        .get(format!("{BASE_URL}/models"))
+        .bearer_auth(api_key)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()?
+        .error_for_status()?
+        .json()?;
+    Ok(chat_models(list))
+}
+
+enum Sent {
+    Ok(reqwest::Response),
+    /// Groq could not parse a tool call; this is what the model wrote.
+    FailedGeneration(String),
+}
+
+impl GroqProvider {
+    pub fn new(api_key: Option<&str>) -> Self {
+        Self {
+            api_key: api_key.map(ToString::to_string),
+            client: LazyClient::new(std::time::Duration::from_mins(2)),
+        }
+    }
+
+    fn require_key(&self) -> anyhow::Result<&str> {
+        self.api_key.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Groq API key not set. Run `zeroclaw onboard` or set GROQ_API_KEY env var."
+            )
+        })
+    }
+
+    /// Models the key can use.
+    pub async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let response = self
+            .client
+            .get(format!("{BASE_URL}/models"))
+            .bearer_auth(self.require_key()?)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Groq", response).await);
+        }
+        Ok(chat_models(response.json().await?))
+    }
+
+    async fn send_chat(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+        stream: bool,
+    ) -> anyhow::Result<Sent> {
+        let api_key = self.require_key()?;
+
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(Message {
+                role: "system".to_string(),
+                content: sys.into(),
+            });
+        }
+        messages.push(Message {
+            role: "user".to_string(),
+            content: super::openai_user_content(message, images),
+        });
+
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages,
+            temperature,
+            stream,
+        };
+
+        let response = self
+            .client
+            .post(format!("{BASE_URL}/chat/completions"))
+            .bearer_auth(api_key)
+            .json(&request)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            let body = response.text().await.unwrap_or_default();
+            return match failed_generation(&body) {
+                Some(text) => Ok(Sent::FailedGeneration(text)),
+                None => anyhow::bail!(
+                    "Groq API error (400 Bad Request): {}",
+                    super::sanitize_api_error(&body)
+                ),
+            };
+        }
+        if !response.status().is_success() {
+            return Err(super::api_error("Groq", response).await);
+        }
+        Ok(Sent::Ok(response))
+    }
+}
+
+#[async_trait]
+impl Provider for GroqProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        if self.api_key.is_some() {
+            self.list_models().await?;
+        }
+        Ok(())
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let response = match self
+            .send_chat(system_prompt, message, images, model, temperature, false)
+            .await?
+        {
+            Sent::Ok(response) => response,
+            Sent::FailedGeneration(text) => return Ok(text),
+        };
+
+        let chat_response: ChatResponse = response.json().await?;
+
+        chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.into_text())
+            .ok_or_else(|| anyhow::anyhow!("No response from Groq"))
+    }
+
+    async fn chat_with_system_streaming(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        deltas: &DeltaSender,
+    ) -> anyhow::Result<String> {
+        match self
+            .send_chat(system_prompt, message, &[], model, temperature, true)
+            .await?
+        {
+            Sent::Ok(response) => super::sse::read_chat_stream(response, deltas).await,
+            Sent::FailedGeneration(text) => {
+                let _ = deltas.send(text.clone());
+                Ok(text)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::tool_calls;
+
+    #[tokio::test]
+    async fn chat_fails_without_key() {
+        let provider = GroqProvider::new(None);
+        let err = provider
+            .chat("hi", "llama-3.3-70b-versatile", 0.7)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("GROQ_API_KEY"));
+    }
+
+    #[test]
+    fn native_tool_calls_become_protocol_blocks() {
+        let response: ChatResponse = serde_json::from_str(
+            r#"{"choices":[{"message":{"role":"assistant","content":null,"tool_calls":[
+                {"id":"call_1","type":"function","function":{"name":"weather_api","arguments":"{\"city\":\"Oslo\"}"}}
+            ]}}]}"#,
+        )
+        .unwrap();
+        let text = response
+            .choices
+            .into_iter()
+            .next()
+            .unwrap()
+            .message
+            .into_text();
+        let (calls, _) = tool_calls::parse(&text, &["weather_api"]).unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "weather_api");
+        assert_eq!(calls[0].arguments["city"], "Oslo");
+    }
+
+    #[test]
+    fn tool_use_failed_returns_the_generation() {
+        let body = r#"{"error":{"message":"Failed to call a function.","type":"invalid_request_error",
+            "code":"tool_use_failed","failed_generation":"<tool_call>{\"name\": \"shell\"</tool_call>"}}"#;
+        assert_eq!(
+            failed_generation(body).as_deref(),
+            Some("<tool_call>{\"name\": \"shell\"</tool_call>")
+        );
+        assert_eq!(
+            failed_generation(r#"{"error":{"code":"model_not_found"}}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn model_list_keeps_active_chat_models() {
+        let list: ModelList = serde_json::from_str(
+            r#"{"object":"list","data":[
+                {"id":"llama-3.3-70b-versatile","active":true},
+                {"id":"whisper-large-v3","active":true},
+                {"id":"gemma-7b-it","active":false},
+                {"id":"llama-3.1-8b-instant"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            chat_models(list),
+            ["llama-3.1-8b-instant", "llama-3.3-70b-versatile"]
+        );
+    }
+}
@@ -1,15 +1,21 @@
 pub mod anthropic;
 pub mod compatible;
 pub mod gemini;
+pub mod groq;
 pub mod ollama;
 pub mod openai;
 pub mod openrouter;
+pub mod plugin;
+pub mod recording;
 pub mod reliable;
+pub(crate) mod sse;
 pub mod traits;
 
-pub use traits::Provider;
+pub use plugin::{register_provider, registered_providers, ProviderFactory};
+pub use traits::{DeltaSender, ImageAttachment, Provider, ProviderUsage};
 
 use compatible::{AuthStyle, OpenAiCompatibleProvider};
+use recording::{CassetteMode, RecordingProvider, ReplayProvider};
 use reliable::ReliableProvider;
 
 const MAX_API_ERROR_CHARS: usize = 200;
@@ -79,6 +85,19 @@ pub fn sanitize_api_error(input: &str) -> String {
     format!("{}...", &scrubbed[..end])
 }
 
+/// The user's turn in the `OpenAI` chat format: the text alone, or the text
+/// and the images as content parts.
+pub(crate) fn openai_user_content(message: &str, images: &[ImageAttachment]) -> serde_json::Value {
+    if images.is_empty() {
+        return message.into();
+    }
+    let mut parts = vec![serde_json::json!({ "type": "text", "text": message })];
+    parts.extend(images.iter().map(|image| {
+        serde_json::json!({ "type": "image_url", "image_url": { "url": image.data_url() } })
+    }));
+    serde_json::Value::Array(parts)
+}
+
 /// Build a sanitized provider error from a failed HTTP response.
 pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::Error {
     let status = response.status();
@@ -151,8 +170,20 @@ fn resolve_api_key(name: &str, api_key: Option<&str>) -> Option<String> {
 }
 
 /// Factory: create the right provider from config
-#[allow(clippy::too_many_lines)]
+///
+/// Runtime-registered providers (see [`register_provider`]) are checked first.
 pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<dyn Provider>> {
+    if let Some(provider) = plugin::create(name, api_key) {
+        return provider;
+    }
+    create_builtin_provider(name, api_key)
+}
+
+#[allow(clippy::too_many_lines)]
+pub(crate) fn create_builtin_provider(
+    name: &str,
+    api_key: Option<&str>,
+) -> anyhow::Result<Box<dyn Provider>> {
     let resolved_key = resolve_api_key(name, api_key);
     match name {
         // ── Primary providers (custom implementations) ───────
@@ -165,6 +196,7 @@ pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<
         "gemini" | "google" | "google-gemini" => {
             Ok(Box::new(gemini::GeminiProvider::new(api_key)))
         }
+        "groq" => Ok(Box::new(groq::GroqProvider::new(api_key))),
 
         // ── OpenAI-compatible providers ──────────────────────
         "venice" => Ok(Box::new(OpenAiCompatibleProvider::new(
@@ -208,9 +240,6 @@ pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<
         ))),
 
         // ── Extended ecosystem (community favorites) ─────────
-        "groq" => Ok(Box::new(OpenAiCompatibleProvider::new(
-            "Groq", "https://api.groq.com/openai", api_key, AuthStyle::Bearer,
-        ))),
         "mistral" => Ok(Box::new(OpenAiCompatibleProvider::new(
             "Mistral", "https://api.mistral.ai", api_key, AuthStyle::Bearer,
         ))),
@@ -271,21 +300,50 @@ pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<
     }
 }
 
-/// Create provider chain with retry and fallback behavior.
+/// Create provider chain with retry and fallback behavior: the primary
+/// provider, then `[providers.fallback]`, then `reliability.fallback_providers`.
 pub fn create_resilient_provider(
     primary_name: &str,
     api_key: Option<&str>,
     reliability: &crate::config::ReliabilityConfig,
+    fallback: &crate::config::FallbackConfig,
 ) -> anyhow::Result<Box<dyn Provider>> {
-    let mut providers: Vec<(String, Box<dyn Provider>)> = Vec::new();
-
-    providers.push((
-        primary_name.to_string(),
-        create_provider(primary_name, api_key)?,
-    ));
+    let mut links = vec![reliable::FallbackLink {
+        name: primary_name.to_string(),
+        provider: create_provider(primary_name, api_key)?,
+        model: None,
+    }];
+
+    for entry in &fallback.chain {
+        if links
+            .iter()
+            .any(|link| link.name == entry.provider && link.model == entry.model)
+        {
+            continue;
+        }
+        // Each provider reads its own env var unless a key is configured;
+        // only the primary shares the top-level key.
+        let key = entry
+            .api_key
+            .as_deref()
+            .or(api_key.filter(|_| entry.provider == primary_name));
+        match create_provider(&entry.provider, key) {
+            Ok(provider) => links.push(reliable::FallbackLink {
+                name: entry.provider.clone(),
+                provider,
+                model: entry.model.clone(),
+            }),
+            Err(e) => {
+                tracing::warn!(
+                    fallback_provider = entry.provider,
+                    "Ignoring invalid fallback provider: {e}"
+                );
+            }
+        }
+    }
 
     for fallback in &reliability.fallback_providers {
-        if fallback == primary_name || providers.iter().any(|(name, _)| name == fallback) {
+        if links.iter().any(|link| &link.name == fallback) {
             continue;
         }
 
@@ -299,7 +357,11 @@ pub fn create_resilient_provider(
         }
 
         match create_provider(fallback, api_key) {
-            Ok(provider) => providers.push((fallback.clone(), provider)),
+            Ok(provider) => links.push(reliable::FallbackLink {
+                name: fallback.clone(),
+                provider,
+                model: None,
+            }),
             Err(e) => {
                 tracing::warn!(
                     fallback_provider = fallback,
@@ -309,17 +371,56 @@ pub fn create_resilient_provider(
         }
     }
 
-    Ok(Box::new(ReliableProvider::new(
-        providers,
+    let mut chain = ReliableProvider::chain(
+        links,
         reliability.provider_retries,
         reliability.provider_backoff_ms,
-    )))
+    );
+    if fallback.timeout_secs > 0 {
+        chain = chain.with_attempt_timeout(std::time::Duration::from_secs(fallback.timeout_secs));
+    }
+    Ok(Box::new(chain))
+}
+
+/// Resilient provider, optionally recording to or replaying from a cassette.
+///
+/// Replay never constructs the real provider, so it works without keys or network.
+pub fn create_provider_with_cassette(
+    primary_name: &str,
+    api_key: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    fallback: &crate::config::FallbackConfig,
+    cassette: Option<&CassetteMode>,
+) -> anyhow::Result<Box<dyn Provider>> {
+    let secrets: Vec<String> = resolve_api_key(primary_name, api_key).into_iter().collect();
+    match cassette {
+        None => create_resilient_provider(primary_name, api_key, reliability, fallback),
+        Some(CassetteMode::Replay(path)) => Ok(Box::new(ReplayProvider::load(path, secrets)?)),
+        Some(CassetteMode::Record(path)) => Ok(Box::new(RecordingProvider::new(
+            create_resilient_provider(primary_name, api_key, reliability, fallback)?,
+            path.clone(),
+            secrets,
+        )?)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn images_become_openai_content_parts() {
+        assert_eq!(openai_user_content("hi", &[]), serde_json::json!("hi"));
+        let image = ImageAttachment::new("image/png", b"png".to_vec());
+        assert_eq!(
+            openai_user_content("What is this?", &[image]),
+            serde_json::json!([
+                { "type": "text", "text": "What is this?" },
+                { "type": "image_url", "image_url": { "url": "data:image/png;base64,cG5n" } }
+            ])
+        );
+    }
+
     // ── Primary providers ────────────────────────────────────
 
     #[test]
@@ -566,14 +667,24 @@ mod tests {
             scheduler_retries: 2,
         };
 
-        let provider = create_resilient_provider("openrouter", Some("sk-test"), &reliability);
+        let provider = create_resilient_provider(
+            "openrouter",
+            Some("sk-test"),
+            &reliability,
+            &crate::config::FallbackConfig::default(),
+        );
         assert!(provider.is_ok());
     }
 
     #[test]
     fn resilient_provider_errors_for_invalid_primary() {
         let reliability = crate::config::ReliabilityConfig::default();
-        let provider = create_resilient_provider("totally-invalid", Some("sk-test"), &reliability);
+        let provider = create_resilient_provider(
+            "totally-invalid",
+            Some("sk-test"),
+            &reliability,
+            &crate::config::FallbackConfig::default(),
+        );
         assert!(provider.is_err());
     }
 
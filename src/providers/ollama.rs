@@ -1,11 +1,11 @@
-use crate::providers::traits::Provider;
+use crate::providers::traits::{ImageAttachment, Provider};
+use crate::util::LazyClient;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct OllamaProvider {
     base_url: String,
-    client: Client,
+    client: LazyClient,
 }
 
 #[derive(Debug, Serialize)]
@@ -20,6 +20,9 @@ struct ChatRequest {
 struct Message {
     role: String,
     content: String,
+    /// Base64 images for multimodal models (llava, llama3.2-vision, ...)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -44,11 +47,7 @@ impl OllamaProvider {
                 .unwrap_or("http://localhost:11434")
                 .trim_end_matches('/')
                 .to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(300)) // Ollama runs locally, may be slow
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(300)), // Ollama runs locally, may be slow
         }
     }
 }
@@ -61,6 +60,18 @@ impl Provider for OllamaProvider {
         message: &str,
         model: &str,
         temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
     ) -> anyhow::Result<String> {
         let mut messages = Vec::new();
 
@@ -68,12 +79,14 @@ impl Provider for OllamaProvider {
             messages.push(Message {
                 role: "system".to_string(),
                 content: sys.to_string(),
+                images: Vec::new(),
             });
         }
 
         messages.push(Message {
             role: "user".to_string(),
             content: message.to_string(),
+            images: images.iter().map(ImageAttachment::base64).collect(),
         });
 
         let request = ChatRequest {
@@ -133,10 +146,12 @@ mod tests {
                 Message {
                     role: "system".to_string(),
                     content: "You are ZeroClaw".to_string(),
+                    images: Vec::new(),
                 },
                 Message {
                     role: "user".to_string(),
                     content: "hello".to_string(),
+                    images: Vec::new(),
                 },
             ],
             stream: false,
@@ -147,6 +162,7 @@ mod tests {
         assert!(json.contains("llama3"));
         assert!(json.contains("system"));
         assert!(json.contains("\"temperature\":0.7"));
+        assert!(!json.contains("images"));
     }
 
     #[test]
@@ -156,6 +172,7 @@ mod tests {
             messages: vec![Message {
                 role: "user".to_string(),
                 content: "test".to_string(),
+                images: vec!["cG5n".to_string()],
             }],
             stream: false,
             options: Options { temperature: 0.0 },
@@ -163,6 +180,7 @@ mod tests {
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("\"role\":\"system\""));
         assert!(json.contains("mistral"));
+        assert!(json.contains("\"images\":[\"cG5n\"]"));
     }
 
     #[test]
@@ -1,11 +1,11 @@
-use crate::providers::traits::Provider;
+use crate::providers::traits::{ImageAttachment, Provider};
+use crate::util::LazyClient;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct OpenAiProvider {
     api_key: Option<String>,
-    client: Client,
+    client: LazyClient,
 }
 
 #[derive(Debug, Serialize)]
@@ -18,7 +18,7 @@ struct ChatRequest {
 #[derive(Debug, Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,23 +40,40 @@ impl OpenAiProvider {
     pub fn new(api_key: Option<&str>) -> Self {
         Self {
             api_key: api_key.map(ToString::to_string),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(120)),
         }
     }
 }
 
 #[async_trait]
 impl Provider for OpenAiProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        // Any response leaves a kept-alive connection in the shared pool.
+        self.client
+            .head("https://api.openai.com/v1/models")
+            .send()
+            .await?;
+        Ok(())
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
         message: &str,
         model: &str,
         temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
     ) -> anyhow::Result<String> {
         let api_key = self.api_key.as_ref().ok_or_else(|| {
             anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
@@ -67,13 +84,13 @@ impl Provider for OpenAiProvider {
         if let Some(sys) = system_prompt {
             messages.push(Message {
                 role: "system".to_string(),
-                content: sys.to_string(),
+                content: sys.into(),
             });
         }
 
         messages.push(Message {
             role: "user".to_string(),
-            content: message.to_string(),
+            content: super::openai_user_content(message, images),
         });
 
         let request = ChatRequest {
@@ -151,11 +168,11 @@ mod tests {
             messages: vec![
                 Message {
                     role: "system".to_string(),
-                    content: "You are ZeroClaw".to_string(),
+                    content: "You are ZeroClaw".into(),
                 },
                 Message {
                     role: "user".to_string(),
-                    content: "hello".to_string(),
+                    content: "hello".into(),
                 },
             ],
             temperature: 0.7,
@@ -172,7 +189,7 @@ mod tests {
             model: "gpt-4o".to_string(),
             messages: vec![Message {
                 role: "user".to_string(),
-                content: "hello".to_string(),
+                content: "hello".into(),
             }],
             temperature: 0.0,
         };
@@ -1,11 +1,11 @@
-use crate::providers::traits::Provider;
+use crate::providers::traits::{DeltaSender, ImageAttachment, Provider};
+use crate::util::LazyClient;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct OpenRouterProvider {
     api_key: Option<String>,
-    client: Client,
+    client: LazyClient,
 }
 
 #[derive(Debug, Serialize)]
@@ -13,12 +13,14 @@ struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     temperature: f64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,38 +42,19 @@ impl OpenRouterProvider {
     pub fn new(api_key: Option<&str>) -> Self {
         Self {
             api_key: api_key.map(ToString::to_string),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(120)),
         }
     }
-}
 
-#[async_trait]
-impl Provider for OpenRouterProvider {
-    async fn warmup(&self) -> anyhow::Result<()> {
-        // Hit a lightweight endpoint to establish TLS + HTTP/2 connection pool.
-        // This prevents the first real chat request from timing out on cold start.
-        if let Some(api_key) = self.api_key.as_ref() {
-            self.client
-                .get("https://openrouter.ai/api/v1/auth/key")
-                .header("Authorization", format!("Bearer {api_key}"))
-                .send()
-                .await?
-                .error_for_status()?;
-        }
-        Ok(())
-    }
-
-    async fn chat_with_system(
+    async fn send_chat(
         &self,
         system_prompt: Option<&str>,
         message: &str,
+        images: &[ImageAttachment],
         model: &str,
         temperature: f64,
-    ) -> anyhow::Result<String> {
+        stream: bool,
+    ) -> anyhow::Result<reqwest::Response> {
         let api_key = self.api_key.as_ref()
             .ok_or_else(|| anyhow::anyhow!("OpenRouter API key not set. Run `zeroclaw onboard` or set OPENROUTER_API_KEY env var."))?;
 
@@ -80,19 +63,20 @@ impl Provider for OpenRouterProvider {
         if let Some(sys) = system_prompt {
             messages.push(Message {
                 role: "system".to_string(),
-                content: sys.to_string(),
+                content: sys.into(),
             });
         }
 
         messages.push(Message {
             role: "user".to_string(),
-            content: message.to_string(),
+            content: super::openai_user_content(message, images),
         });
 
         let request = ChatRequest {
             model: model.to_string(),
             messages,
             temperature,
+            stream,
         };
 
         let response = self
@@ -111,6 +95,48 @@ impl Provider for OpenRouterProvider {
         if !response.status().is_success() {
             return Err(super::api_error("OpenRouter", response).await);
         }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Provider for OpenRouterProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        // Hit a lightweight endpoint to establish TLS + HTTP/2 connection pool.
+        // This prevents the first real chat request from timing out on cold start.
+        if let Some(api_key) = self.api_key.as_ref() {
+            self.client
+                .get("https://openrouter.ai/api/v1/auth/key")
+                .header("Authorization", format!("Bearer {api_key}"))
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        Ok(())
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .send_chat(system_prompt, message, images, model, temperature, false)
+            .await?;
 
         let chat_response: ChatResponse = response.json().await?;
 
@@ -121,4 +147,18 @@ impl Provider for OpenRouterProvider {
             .map(|c| c.message.content)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))
     }
+
+    async fn chat_with_system_streaming(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        deltas: &DeltaSender,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .send_chat(system_prompt, message, &[], model, temperature, true)
+            .await?;
+        super::sse::read_chat_stream(response, deltas).await
+    }
 }
@@ -0,0 +1,123 @@
+use super::traits::Provider;
+use crate::registry::{OnCollision, PluginInfo, PluginKind, Registered, Registry};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Builds a provider from an optional API key, like the built-in factory arms.
+pub type ProviderFactory =
+    Arc<dyn Fn(Option<&str>) -> anyhow::Result<Box<dyn Provider>> + Send + Sync>;
+
+static PROVIDERS: OnceLock<Mutex<Registry<ProviderFactory>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry<ProviderFactory>> {
+    PROVIDERS.get_or_init(|| Mutex::new(Registry::new()))
+}
+
+/// Make `name` usable anywhere a provider name is accepted (config,
+/// `--provider`, fallback chains).
+///
+/// Built-in names are part of the collision check: taking over "openai"
+/// needs `OnCollision::Replace`.
+pub fn register_provider(
+    info: PluginInfo,
+    factory: ProviderFactory,
+    on_collision: OnCollision,
+) -> anyhow::Result<Registered> {
+    anyhow::ensure!(
+        info.kind == PluginKind::Provider,
+        "'{}' is registered as a {:?}, not a provider",
+        info.name,
+        info.kind
+    );
+    if on_collision != OnCollision::Replace
+        && super::create_builtin_provider(&info.name, None).is_ok()
+    {
+        return match on_collision {
+            OnCollision::Skip => Ok(Registered::Skipped),
+            _ => anyhow::bail!(
+                "Provider '{}' is built in; register with OnCollision::Replace to override it",
+                info.name
+            ),
+        };
+    }
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .register(info, factory, on_collision)
+}
+
+pub fn unregister_provider(name: &str) -> bool {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(name)
+        .is_some()
+}
+
+/// Metadata for every runtime-registered provider.
+pub fn registered_providers() -> Vec<PluginInfo> {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .infos()
+        .cloned()
+        .collect()
+}
+
+/// `None` when no plugin claims `name`. The lock is released before the
+/// factory runs so factories may create other providers.
+pub(crate) fn create(
+    name: &str,
+    api_key: Option<&str>,
+) -> Option<anyhow::Result<Box<dyn Provider>>> {
+    let factory = registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(name)
+        .cloned()?;
+    Some(factory(api_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::PluginSource;
+    use crate::testing::MockProvider;
+
+    fn mock_factory() -> ProviderFactory {
+        Arc::new(|_key| {
+            Ok(Box::new(MockProvider::with_replies(["from plugin"])) as Box<dyn Provider>)
+        })
+    }
+
+    #[tokio::test]
+    async fn registered_provider_is_created_by_name() {
+        let name = "plugin-test-local";
+        let info = PluginInfo::new(PluginKind::Provider, name, PluginSource::Embedded)
+            .with_capabilities(["chat"]);
+        register_provider(info, mock_factory(), OnCollision::Reject).unwrap();
+
+        let provider = crate::providers::create_provider(name, None).unwrap();
+        let reply = provider
+            .chat_with_system(None, "hi", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(reply, "from plugin");
+        assert!(registered_providers().iter().any(|i| i.name == name));
+
+        assert!(unregister_provider(name));
+        assert!(crate::providers::create_provider(name, None).is_err());
+    }
+
+    #[test]
+    fn builtin_names_need_replace() {
+        let info = PluginInfo::new(PluginKind::Provider, "anthropic", PluginSource::Embedded);
+        assert!(register_provider(info.clone(), mock_factory(), OnCollision::Reject).is_err());
+        assert_eq!(
+            register_provider(info, mock_factory(), OnCollision::Skip).unwrap(),
+            Registered::Skipped
+        );
+
+        let tool_info = PluginInfo::new(PluginKind::Tool, "not-a-provider", PluginSource::Embedded);
+        assert!(register_provider(tool_info, mock_factory(), OnCollision::Reject).is_err());
+    }
+}
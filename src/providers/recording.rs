@@ -0,0 +1,366 @@
+use super::traits::{ImageAttachment, Provider, ProviderUsage};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const CASSETTE_VERSION: u32 = 1;
+
+/// Where provider traffic goes for `--record` / `--offline`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Call the real provider and append every exchange to the cassette.
+    Record(PathBuf),
+    /// Answer from the cassette only; never touch the network.
+    Replay(PathBuf),
+}
+
+/// Default cassette location inside the workspace.
+pub fn default_cassette_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("cassettes").join("provider.json")
+}
+
+/// One recorded provider call.
+///
+/// The system prompt is stored as a hash only: it is large, mostly workspace
+/// files, and may contain personal notes that don't belong in fixtures.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Interaction {
+    pub model: String,
+    pub temperature: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_sha256: Option<String>,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Cassette {
+    pub version: u32,
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let cassette: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid cassette {}", path.display()))?;
+        if cassette.version != CASSETTE_VERSION {
+            anyhow::bail!(
+                "Unsupported cassette version {} in {} (expected {CASSETTE_VERSION})",
+                cassette.version,
+                path.display()
+            );
+        }
+        Ok(cassette)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write cassette {}", path.display()))
+    }
+}
+
+fn prompt_hash(system_prompt: Option<&str>) -> Option<String> {
+    system_prompt.map(|p| hex::encode(Sha256::digest(p.as_bytes())))
+}
+
+/// Strip the configured key and anything that looks like a token.
+fn scrub(text: &str, secrets: &[String]) -> String {
+    let mut out = text.to_string();
+    for secret in secrets.iter().filter(|s| s.len() >= 8) {
+        out = out.replace(secret.as_str(), "[REDACTED]");
+    }
+    super::scrub_secret_patterns(&out)
+}
+
+/// Wraps a real provider and writes each exchange to a cassette file.
+pub struct RecordingProvider {
+    inner: Box<dyn Provider>,
+    path: PathBuf,
+    secrets: Vec<String>,
+    cassette: Mutex<Cassette>,
+}
+
+impl RecordingProvider {
+    /// Appends to an existing cassette at `path`, or starts a new one.
+    pub fn new(inner: Box<dyn Provider>, path: PathBuf, secrets: Vec<String>) -> Result<Self> {
+        let cassette = if path.exists() {
+            Cassette::load(&path)?
+        } else {
+            Cassette {
+                version: CASSETTE_VERSION,
+                interactions: Vec::new(),
+            }
+        };
+        Ok(Self {
+            inner,
+            path,
+            secrets,
+            cassette: Mutex::new(cassette),
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for RecordingProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    /// Images are passed on but not recorded; replay matches on the text.
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        let result = self
+            .inner
+            .chat_with_images(system_prompt, message, images, model, temperature)
+            .await;
+
+        let interaction = Interaction {
+            model: model.to_string(),
+            temperature,
+            system_prompt_sha256: prompt_hash(system_prompt),
+            message: scrub(message, &self.secrets),
+            response: result.as_ref().ok().map(|r| scrub(r, &self.secrets)),
+            error: result
+                .as_ref()
+                .err()
+                .map(|e| scrub(&format!("{e:#}"), &self.secrets)),
+        };
+        // Save after every call so an interrupted session still leaves a usable cassette.
+        let snapshot = {
+            let mut cassette = self
+                .cassette
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            cassette.interactions.push(interaction);
+            cassette.clone()
+        };
+        if let Err(e) = snapshot.save(&self.path) {
+            tracing::warn!("Failed to update cassette: {e}");
+        }
+
+        result
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn usage(&self) -> Vec<ProviderUsage> {
+        self.inner.usage()
+    }
+}
+
+/// Serves responses from a cassette.
+///
+/// Calls match on model + message + system prompt hash first, then on message
+/// alone (so prompt edits don't invalidate a cassette). Each recording is used
+/// once in order; when a match is exhausted its last recording is repeated.
+pub struct ReplayProvider {
+    interactions: Vec<Interaction>,
+    used: Mutex<Vec<bool>>,
+    secrets: Vec<String>,
+}
+
+impl ReplayProvider {
+    pub fn new(cassette: Cassette, secrets: Vec<String>) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        Self {
+            interactions: cassette.interactions,
+            used: Mutex::new(used),
+            secrets,
+        }
+    }
+
+    pub fn load(path: &Path, secrets: Vec<String>) -> Result<Self> {
+        Ok(Self::new(Cassette::load(path)?, secrets))
+    }
+
+    fn find(&self, system_prompt: Option<&str>, message: &str, model: &str) -> Option<usize> {
+        let message = scrub(message, &self.secrets);
+        let hash = prompt_hash(system_prompt);
+        let exact = |i: &Interaction| {
+            i.message == message && i.model == model && i.system_prompt_sha256 == hash
+        };
+        let loose = |i: &Interaction| i.message == message;
+        // Packed context (recent turns, recalled memories) is prepended to the
+        // user's message and drifts with memory state; the message itself doesn't.
+        let trailing = |i: &Interaction| !i.message.is_empty() && message.ends_with(&i.message);
+
+        let mut used = self
+            .used
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let pick = [&exact as &dyn Fn(&Interaction) -> bool, &loose, &trailing]
+            .into_iter()
+            .find_map(|matches| {
+                let unused = self
+                    .interactions
+                    .iter()
+                    .enumerate()
+                    .position(|(idx, i)| !used[idx] && matches(i));
+                unused.or_else(|| self.interactions.iter().rposition(matches))
+            })?;
+        used[pick] = true;
+        Some(pick)
+    }
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        _temperature: f64,
+    ) -> Result<String> {
+        let Some(idx) = self.find(system_prompt, message, model) else {
+            anyhow::bail!(
+                "No recorded response for this message (offline replay). \
+                 Re-run with --record to capture it: {}",
+                crate::util::truncate_with_ellipsis(message, 80)
+            );
+        };
+        let interaction = &self.interactions[idx];
+        match (&interaction.response, &interaction.error) {
+            (Some(response), _) => Ok(response.clone()),
+            (None, Some(error)) => anyhow::bail!("{error}"),
+            (None, None) => anyhow::bail!("Recorded interaction has neither response nor error"),
+        }
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        _images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        self.chat_with_system(system_prompt, message, model, temperature)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl Provider for EchoProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            if message == "fail" {
+                anyhow::bail!("upstream 503");
+            }
+            Ok(format!("echo: {message}"))
+        }
+    }
+
+    const KEY: &str = "or-live-0123456789abcdef";
+
+    #[tokio::test]
+    async fn record_then_replay_roundtrip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("cassettes/provider.json");
+
+        let recorder =
+            RecordingProvider::new(Box::new(EchoProvider), path.clone(), vec![KEY.into()]).unwrap();
+        recorder
+            .chat_with_system(Some("sys"), "hello", "m", 0.7)
+            .await
+            .unwrap();
+        assert!(recorder.chat("fail", "m", 0.7).await.is_err());
+
+        let replay = ReplayProvider::load(&path, vec![]).unwrap();
+        assert_eq!(
+            replay
+                .chat_with_system(Some("sys"), "hello", "m", 0.0)
+                .await
+                .unwrap(),
+            "echo: hello"
+        );
+        // Prompt edits fall back to matching on the message alone.
+        assert_eq!(
+            replay
+                .chat_with_system(Some("edited"), "hello", "m", 0.0)
+                .await
+                .unwrap(),
+            "echo: hello"
+        );
+        let err = replay.chat("fail", "m", 0.0).await.unwrap_err();
+        assert!(err.to_string().contains("upstream 503"));
+        assert!(replay.chat("never recorded", "m", 0.0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn secrets_are_stripped_from_fixtures() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("c.json");
+        let recorder =
+            RecordingProvider::new(Box::new(EchoProvider), path.clone(), vec![KEY.into()]).unwrap();
+        recorder
+            .chat(&format!("my key is {KEY} and sk-abcdef123456"), "m", 0.7)
+            .await
+            .unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains(KEY));
+        assert!(!raw.contains("sk-abcdef123456"));
+        assert!(raw.contains("[REDACTED]"));
+        assert!(!raw.contains("\"sys\""));
+    }
+
+    #[tokio::test]
+    async fn repeated_messages_replay_in_order() {
+        let interaction = |response: &str| Interaction {
+            model: "m".into(),
+            temperature: 0.7,
+            system_prompt_sha256: None,
+            message: "status?".into(),
+            response: Some(response.into()),
+            error: None,
+        };
+        let replay = ReplayProvider::new(
+            Cassette {
+                version: CASSETTE_VERSION,
+                interactions: vec![interaction("first"), interaction("second")],
+            },
+            vec![],
+        );
+        assert_eq!(replay.chat("status?", "m", 0.0).await.unwrap(), "first");
+        assert_eq!(replay.chat("status?", "m", 0.0).await.unwrap(), "second");
+        assert_eq!(replay.chat("status?", "m", 0.0).await.unwrap(), "second");
+    }
+}
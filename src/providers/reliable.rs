@@ -1,5 +1,9 @@
-use super::Provider;
+use super::traits::ProviderUsage;
+use super::{DeltaSender, ImageAttachment, Provider};
 use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Mutex;
 use std::time::Duration;
 
 /// Check if an error is non-retryable (client errors that won't resolve with retries).
@@ -26,11 +30,48 @@ fn is_non_retryable(err: &anyhow::Error) -> bool {
     false
 }
 
+/// Check if a provider is rate limiting us: a quick retry would hit the same
+/// limit, so the next provider in the chain is tried instead.
+fn is_rate_limited(err: &anyhow::Error) -> bool {
+    if let Some(status) = err
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+    {
+        return status.as_u16() == 429;
+    }
+    let msg = err.to_string();
+    msg.split(|c: char| !c.is_ascii_digit())
+        .any(|word| word == "429")
+}
+
+/// Run `call`, failing it after `limit`.
+async fn within<T>(
+    limit: Option<Duration>,
+    call: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    let Some(limit) = limit else {
+        return call.await;
+    };
+    tokio::time::timeout(limit, call)
+        .await
+        .unwrap_or_else(|_| anyhow::bail!("timed out after {}s", limit.as_secs()))
+}
+
+/// One provider in a fallback chain. `model` replaces the caller's model
+/// for this provider, since model names rarely carry over between them.
+pub struct FallbackLink {
+    pub name: String,
+    pub provider: Box<dyn Provider>,
+    pub model: Option<String>,
+}
+
 /// Provider wrapper with retry + fallback behavior.
 pub struct ReliableProvider {
-    providers: Vec<(String, Box<dyn Provider>)>,
+    links: Vec<FallbackLink>,
     max_retries: u32,
     base_backoff_ms: u64,
+    attempt_timeout: Option<Duration>,
+    usage: Mutex<BTreeMap<(String, String), ProviderUsage>>,
 }
 
 impl ReliableProvider {
@@ -39,42 +80,119 @@ impl ReliableProvider {
         max_retries: u32,
         base_backoff_ms: u64,
     ) -> Self {
+        let links = providers
+            .into_iter()
+            .map(|(name, provider)| FallbackLink {
+                name,
+                provider,
+                model: None,
+            })
+            .collect();
+        Self::chain(links, max_retries, base_backoff_ms)
+    }
+
+    /// Try `links` in order, each with its own model.
+    pub fn chain(links: Vec<FallbackLink>, max_retries: u32, base_backoff_ms: u64) -> Self {
         Self {
-            providers,
+            links,
             max_retries,
             base_backoff_ms: base_backoff_ms.max(50),
+            attempt_timeout: None,
+            usage: Mutex::new(BTreeMap::new()),
         }
     }
+
+    /// Count an attempt that takes longer than `timeout` as failed.
+    #[must_use]
+    pub fn with_attempt_timeout(mut self, timeout: Duration) -> Self {
+        self.attempt_timeout = Some(timeout);
+        self
+    }
+
+    /// Note which link served a response.
+    fn served(&self, index: usize, model: &str, prompt_chars: usize, response: &str) {
+        let link = &self.links[index];
+        if index > 0 {
+            tracing::info!(provider = %link.name, model, "Response served by fallback provider");
+        }
+        let mut usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = usage
+            .entry((link.name.clone(), model.to_string()))
+            .or_insert_with(|| ProviderUsage {
+                provider: link.name.clone(),
+                model: model.to_string(),
+                ..ProviderUsage::default()
+            });
+        entry.responses += 1;
+        entry.prompt_chars += prompt_chars as u64;
+        entry.response_chars += response.chars().count() as u64;
+    }
 }
 
 #[async_trait]
 impl Provider for ReliableProvider {
     async fn warmup(&self) -> anyhow::Result<()> {
-        for (name, provider) in &self.providers {
-            tracing::info!(provider = name, "Warming up provider connection pool");
-            if let Err(e) = provider.warmup().await {
-                tracing::warn!(provider = name, "Warmup failed (non-fatal): {e}");
+        for link in &self.links {
+            tracing::info!(provider = link.name, "Warming up provider connection pool");
+            if let Err(e) = link.provider.warmup().await {
+                tracing::warn!(provider = link.name, "Warmup failed (non-fatal): {e}");
             }
         }
         Ok(())
     }
 
+    fn usage(&self) -> Vec<ProviderUsage> {
+        self.usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect()
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
         message: &str,
         model: &str,
         temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_images(system_prompt, message, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
     ) -> anyhow::Result<String> {
         let mut failures = Vec::new();
+        let prompt_chars = system_prompt.map_or(0, |p| p.chars().count()) + message.chars().count();
 
-        for (provider_name, provider) in &self.providers {
+        for (index, link) in self.links.iter().enumerate() {
+            let provider_name = &link.name;
+            let model = link.model.as_deref().unwrap_or(model);
+            let has_fallback = index + 1 < self.links.len();
             let mut backoff_ms = self.base_backoff_ms;
 
             for attempt in 0..=self.max_retries {
-                match provider
-                    .chat_with_system(system_prompt, message, model, temperature)
-                    .await
+                match within(
+                    self.attempt_timeout,
+                    link.provider.chat_with_images(
+                        system_prompt,
+                        message,
+                        images,
+                        model,
+                        temperature,
+                    ),
+                )
+                .await
                 {
                     Ok(resp) => {
                         if attempt > 0 {
@@ -84,19 +202,22 @@ impl Provider for ReliableProvider {
                                 "Provider recovered after retries"
                             );
                         }
+                        self.served(index, model, prompt_chars, &resp);
                         return Ok(resp);
                     }
                     Err(e) => {
                         let non_retryable = is_non_retryable(&e);
+                        let rate_limited = has_fallback && is_rate_limited(&e);
                         failures.push(format!(
                             "{provider_name} attempt {}/{}: {e}",
                             attempt + 1,
                             self.max_retries + 1
                         ));
 
-                        if non_retryable {
+                        if non_retryable || rate_limited {
                             tracing::warn!(
                                 provider = provider_name,
+                                rate_limited,
                                 "Non-retryable error, switching provider"
                             );
                             break;
@@ -121,6 +242,76 @@ impl Provider for ReliableProvider {
 
         anyhow::bail!("All providers failed. Attempts:\n{}", failures.join("\n"))
     }
+
+    /// Same retry/fallback policy, but once any text has been streamed the
+    /// attempt is final: retrying would repeat output the user already saw.
+    async fn chat_with_system_streaming(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        deltas: &DeltaSender,
+    ) -> anyhow::Result<String> {
+        let mut failures = Vec::new();
+        let prompt_chars = system_prompt.map_or(0, |p| p.chars().count()) + message.chars().count();
+
+        for (index, link) in self.links.iter().enumerate() {
+            let provider_name = &link.name;
+            let model = link.model.as_deref().unwrap_or(model);
+            let has_fallback = index + 1 < self.links.len();
+            let mut backoff_ms = self.base_backoff_ms;
+
+            for attempt in 0..=self.max_retries {
+                let (attempt_tx, mut attempt_rx) = tokio::sync::mpsc::unbounded_channel();
+                let call = within(self.attempt_timeout, async move {
+                    link.provider
+                        .chat_with_system_streaming(
+                            system_prompt,
+                            message,
+                            model,
+                            temperature,
+                            &attempt_tx,
+                        )
+                        .await
+                });
+                let forward = async {
+                    let mut forwarded = false;
+                    while let Some(delta) = attempt_rx.recv().await {
+                        forwarded = true;
+                        let _ = deltas.send(delta);
+                    }
+                    forwarded
+                };
+                let (result, forwarded) = tokio::join!(call, forward);
+
+                let e = match result {
+                    Ok(resp) => {
+                        self.served(index, model, prompt_chars, &resp);
+                        return Ok(resp);
+                    }
+                    Err(e) if forwarded => return Err(e),
+                    Err(e) => e,
+                };
+                failures.push(format!(
+                    "{provider_name} attempt {}/{}: {e}",
+                    attempt + 1,
+                    self.max_retries + 1
+                ));
+                if is_non_retryable(&e) || (has_fallback && is_rate_limited(&e)) {
+                    break;
+                }
+                if attempt < self.max_retries {
+                    tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                }
+            }
+
+            tracing::warn!(provider = provider_name, "Switching to fallback provider");
+        }
+
+        anyhow::bail!("All providers failed. Attempts:\n{}", failures.join("\n"))
+    }
 }
 
 #[cfg(test)]
@@ -330,4 +521,78 @@ mod tests {
         assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
         assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
     }
+
+    struct ModelEcho {
+        calls: Arc<AtomicUsize>,
+        delay: Duration,
+        error: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Provider for ModelEcho {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            match self.error {
+                Some(error) => anyhow::bail!(error),
+                None => Ok(format!("answered by {model}")),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limits_and_timeouts_fall_through_to_the_next_provider_and_model() {
+        let link = |name: &str, model: Option<&str>, delay_ms, error, calls: &Arc<AtomicUsize>| {
+            FallbackLink {
+                name: name.into(),
+                provider: Box::new(ModelEcho {
+                    calls: Arc::clone(calls),
+                    delay: Duration::from_millis(delay_ms),
+                    error,
+                }),
+                model: model.map(Into::into),
+            }
+        };
+        let limited = Arc::new(AtomicUsize::new(0));
+        let slow = Arc::new(AtomicUsize::new(0));
+        let local = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::chain(
+            vec![
+                link(
+                    "openrouter",
+                    None,
+                    0,
+                    Some("OpenRouter API error (429): rate limited"),
+                    &limited,
+                ),
+                link("anthropic", Some("claude-sonnet"), 5_000, None, &slow),
+                link("ollama", Some("llama3.1:8b"), 0, None, &local),
+            ],
+            2,
+            1,
+        )
+        .with_attempt_timeout(Duration::from_millis(20));
+
+        let result = provider
+            .chat("hello", "anthropic/claude-sonnet", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(result, "answered by llama3.1:8b");
+        // A 429 moves on at once; a timeout is retried like any transient error.
+        assert_eq!(limited.load(Ordering::SeqCst), 1);
+        assert_eq!(slow.load(Ordering::SeqCst), 3);
+
+        let usage = provider.usage();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].provider, "ollama");
+        assert_eq!(usage[0].model, "llama3.1:8b");
+        assert_eq!(usage[0].responses, 1);
+        assert_eq!(usage[0].prompt_chars, 5);
+    }
 }
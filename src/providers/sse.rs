@@ -0,0 +1,78 @@
+use super::traits::DeltaSender;
+use futures_util::StreamExt;
+
+/// One line of an OpenAI-style `chat/completions` event stream.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum SseLine {
+    Delta(String),
+    Done,
+    Ignore,
+}
+
+pub(crate) fn parse_line(line: &str) -> SseLine {
+    let Some(data) = line.trim_end_matches('\r').strip_prefix("data:") else {
+        return SseLine::Ignore;
+    };
+    let data = data.trim_start();
+    if data == "[DONE]" {
+        return SseLine::Done;
+    }
+    serde_json::from_str::<serde_json::Value>(data)
+        .ok()
+        .and_then(|event| {
+            event
+                .pointer("/choices/0/delta/content")
+                .and_then(serde_json::Value::as_str)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        })
+        .map_or(SseLine::Ignore, SseLine::Delta)
+}
+
+/// Read a streaming `chat/completions` response, forwarding each content
+/// delta and returning the concatenated reply.
+pub(crate) async fn read_chat_stream(
+    response: reqwest::Response,
+    deltas: &DeltaSender,
+) -> anyhow::Result<String> {
+    let mut body = response.bytes_stream();
+    let mut pending = Vec::new();
+    let mut reply = String::new();
+
+    while let Some(chunk) = body.next().await {
+        pending.extend_from_slice(&chunk?);
+        // Events are newline-delimited; keep any partial line for the next chunk.
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            match parse_line(&String::from_utf8_lossy(&line[..end])) {
+                SseLine::Delta(text) => {
+                    reply.push_str(&text);
+                    let _ = deltas.send(text);
+                }
+                SseLine::Done => return Ok(reply),
+                SseLine::Ignore => {}
+            }
+        }
+    }
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_content_deltas_and_done() {
+        assert_eq!(
+            parse_line(r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#),
+            SseLine::Delta("Hel".into())
+        );
+        assert_eq!(parse_line("data: [DONE]\r"), SseLine::Done);
+        assert_eq!(
+            parse_line(r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#),
+            SseLine::Ignore
+        );
+        assert_eq!(parse_line(": OPENROUTER PROCESSING"), SseLine::Ignore);
+        assert_eq!(parse_line(""), SseLine::Ignore);
+    }
+}
@@ -1,5 +1,44 @@
 use async_trait::async_trait;
 
+/// Receives text deltas as a streaming reply is generated.
+pub type DeltaSender = tokio::sync::mpsc::UnboundedSender<String>;
+
+/// Responses one provider and model served, for cost tracking.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderUsage {
+    pub provider: String,
+    pub model: String,
+    pub responses: u64,
+    pub prompt_chars: u64,
+    pub response_chars: u64,
+}
+
+/// A picture sent with the user's message, for vision-capable models.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageAttachment {
+    /// e.g. `image/jpeg`
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl ImageAttachment {
+    pub fn new(mime_type: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            mime_type: mime_type.into(),
+            data,
+        }
+    }
+
+    pub fn base64(&self) -> String {
+        crate::util::base64_encode(&self.data)
+    }
+
+    /// `data:` URL, as OpenAI-style APIs take images.
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, self.base64())
+    }
+}
+
 #[async_trait]
 pub trait Provider: Send + Sync {
     async fn chat(&self, message: &str, model: &str, temperature: f64) -> anyhow::Result<String> {
@@ -15,9 +54,61 @@ pub trait Provider: Send + Sync {
         temperature: f64,
     ) -> anyhow::Result<String>;
 
+    /// Like [`Provider::chat_with_system`], but pushes text deltas to `deltas`
+    /// as they arrive and returns the full reply at the end.
+    ///
+    /// Default implementation sends the whole reply as a single delta;
+    /// providers with a streaming API should override.
+    async fn chat_with_system_streaming(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        deltas: &DeltaSender,
+    ) -> anyhow::Result<String> {
+        let reply = self
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await?;
+        let _ = deltas.send(reply.clone());
+        Ok(reply)
+    }
+
+    /// Like [`Provider::chat_with_system`], with `images` attached to the
+    /// user's message.
+    ///
+    /// Default implementation is for providers without an image API: the
+    /// model answers from the text and is told the images were left out.
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageAttachment],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if images.is_empty() {
+            return self
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+        let message = format!(
+            "{message}\n\n[{} image(s) attached, but this provider can't show images to the model]",
+            images.len()
+        );
+        self.chat_with_system(system_prompt, &message, model, temperature)
+            .await
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Which providers served the responses so far. Only provider chains
+    /// track this; single providers report nothing.
+    fn usage(&self) -> Vec<ProviderUsage> {
+        Vec::new()
+    }
 }
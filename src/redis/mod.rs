@@ -0,0 +1,330 @@
+//! Optional Redis layer for running `ZeroClaw` as several processes.
+//!
+//! By default all runtime state lives in the process and the workspace:
+//! the per-session queues, the action and tool rate limits, and the outbox
+//! in `outbox/outbox.db`. With `[redis] enabled = true` those three move to
+//! a Redis server, so the gateway, channel workers and the daemon can run
+//! in separate containers and scale independently:
+//!
+//! - **Session locks.** A turn holds its session (`channel:sender`) for up
+//!   to `session_lock_secs`, so two workers never answer one conversation
+//!   at the same time.
+//! - **Rate-limit counters.** `max_actions_per_hour` and the per-tool
+//!   limits in `[tool_middleware]` count across every process, in fixed
+//!   windows.
+//! - **Outbound queue.** Outbox intents, their retry schedule and dedupe
+//!   keys are kept in Redis, and any daemon may dispatch them.
+//!
+//! When Redis can't be reached, locks and counters fall back to the
+//! process (with a warning); the outbox reports the error, since a message
+//! queued locally would be invisible to the other processes.
+
+pub mod queue;
+pub mod resp;
+
+use crate::config::RedisConfig;
+use anyhow::Result;
+use resp::{Address, Client, Value};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How often a worker checks whether a busy session has come free.
+const LOCK_POLL: Duration = Duration::from_millis(250);
+
+/// Releases the lock only if it still holds our token, so a turn that
+/// outlived its lock can't free another worker's.
+const UNLOCK_SCRIPT: &str =
+    "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) end return 0";
+
+/// Increments a window counter and sets its expiry on first use.
+const COUNT_SCRIPT: &str = "local n = redis.call('INCR', KEYS[1]) \
+     if n == 1 then redis.call('EXPIRE', KEYS[1], ARGV[1]) end return n";
+
+static SHARED: OnceLock<Option<Arc<SharedState>>> = OnceLock::new();
+
+/// State shared with the other processes of a deployment.
+pub struct SharedState {
+    client: Client,
+    prefix: String,
+    lock_ttl: Duration,
+}
+
+impl SharedState {
+    pub fn new(config: &RedisConfig) -> Result<Self> {
+        Ok(Self {
+            client: Client::new(Address::parse(&config.url)?),
+            prefix: config.key_prefix.clone(),
+            lock_ttl: Duration::from_secs(config.session_lock_secs.max(1)),
+        })
+    }
+
+    pub(crate) fn key(&self, name: &str) -> String {
+        format!("{}{name}", self.prefix)
+    }
+
+    pub(crate) fn command(&self, args: &[&str]) -> Result<Value> {
+        self.client.command(args)
+    }
+
+    pub fn ping(&self) -> Result<()> {
+        match self.command(&["PING"])? {
+            Value::Status(pong) if pong == "PONG" => Ok(()),
+            other => anyhow::bail!("Unexpected reply to PING: {other:?}"),
+        }
+    }
+
+    /// Take `session` if no one holds it.
+    fn try_lock(&self, session: &str) -> Result<Option<SessionLock>> {
+        let key = self.key(&format!("lock:session:{session}"));
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let ttl = self.lock_ttl.as_millis().to_string();
+        match self.command(&["SET", &key, &token, "NX", "PX", &ttl])? {
+            Value::Nil => Ok(None),
+            _ => Ok(Some(SessionLock { key, token })),
+        }
+    }
+
+    fn unlock(&self, lock: &SessionLock) -> Result<()> {
+        self.command(&["EVAL", UNLOCK_SCRIPT, "1", &lock.key, &lock.token])?;
+        Ok(())
+    }
+
+    /// Count one more `name` event in the current `window` across all
+    /// processes; returns the count including this one.
+    pub fn count(&self, name: &str, window: Duration) -> Result<u64> {
+        let key = self.window_key(name, window);
+        let expiry = window.as_secs().max(1).to_string();
+        let n = self
+            .command(&["EVAL", COUNT_SCRIPT, "1", &key, &expiry])?
+            .as_int()
+            .unwrap_or_default();
+        Ok(u64::try_from(n).unwrap_or_default())
+    }
+
+    /// Events counted so far in the current `window`.
+    pub fn counted(&self, name: &str, window: Duration) -> Result<u64> {
+        let key = self.window_key(name, window);
+        let n = self.command(&["GET", &key])?.as_int().unwrap_or_default();
+        Ok(u64::try_from(n).unwrap_or_default())
+    }
+
+    fn window_key(&self, name: &str, window: Duration) -> String {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let window = window.as_secs().max(1);
+        self.key(&format!("rate:{name}:{}", now / window))
+    }
+}
+
+struct SessionLock {
+    key: String,
+    token: String,
+}
+
+/// Held for the length of a turn; dropping it frees the session.
+pub struct SessionGuard {
+    shared: Arc<SharedState>,
+    lock: SessionLock,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.shared.unlock(&self.lock) {
+            tracing::warn!("Failed to release {}: {e:#}", self.lock.key);
+        }
+    }
+}
+
+/// Use `[redis]` for shared state in this process. Call once at startup;
+/// nothing connects until the state is first used.
+pub fn init(config: &RedisConfig) {
+    let shared = if config.enabled {
+        match SharedState::new(config) {
+            Ok(shared) => Some(Arc::new(shared)),
+            Err(e) => {
+                tracing::warn!("Redis disabled: {e:#}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let _ = SHARED.set(shared);
+}
+
+/// The shared state, when `[redis]` is on.
+pub fn shared() -> Option<Arc<SharedState>> {
+    SHARED.get().cloned().flatten()
+}
+
+/// Wait until `session` is free in every process, then hold it until the
+/// guard is dropped. `None` without Redis, or when it can't be reached.
+pub async fn lock_session(session: &str) -> Option<SessionGuard> {
+    let shared = shared()?;
+    // A holder that crashed frees the lock when it expires.
+    let deadline = Instant::now() + shared.lock_ttl + LOCK_POLL;
+    loop {
+        match shared.try_lock(session) {
+            Ok(Some(lock)) => return Some(SessionGuard { shared, lock }),
+            Ok(None) if Instant::now() < deadline => tokio::time::sleep(LOCK_POLL).await,
+            Ok(None) => {
+                tracing::warn!("Session {session} is still locked; answering anyway");
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Session lock unavailable, answering unlocked: {e:#}");
+                return None;
+            }
+        }
+    }
+}
+
+/// Count a rate-limited event in Redis; `None` to count in the process.
+pub fn count(name: &str, window: Duration) -> Option<u64> {
+    let shared = shared()?;
+    shared
+        .count(name, window)
+        .inspect_err(|e| tracing::warn!("Redis counter unavailable, counting locally: {e:#}"))
+        .ok()
+}
+
+/// Peek at a rate-limited count in Redis; `None` to use the process's.
+pub fn counted(name: &str, window: Duration) -> Option<u64> {
+    let shared = shared()?;
+    shared
+        .counted(name, window)
+        .inspect_err(|e| tracing::warn!("Redis counter unavailable, counting locally: {e:#}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Just enough of a Redis server for these tests: strings, sorted sets
+    /// and the two scripts; no expiry.
+    pub(super) fn fake() -> SharedState {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let mut strings: HashMap<String, String> = HashMap::new();
+            let mut sets: HashMap<String, Vec<(i64, String)>> = HashMap::new();
+            for stream in listener.incoming() {
+                let mut reader = BufReader::new(stream.unwrap());
+                while let Ok(Ok(resp::Value::Array(args))) = resp::read_value(&mut reader) {
+                    let args: Vec<String> = args
+                        .into_iter()
+                        .filter_map(resp::Value::into_string)
+                        .collect();
+                    let reply = answer(&args, &mut strings, &mut sets);
+                    reader.get_mut().write_all(reply.as_bytes()).unwrap();
+                }
+            }
+        });
+        SharedState::new(&RedisConfig {
+            enabled: true,
+            url: format!("redis://127.0.0.1:{port}"),
+            ..RedisConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn bulk(value: Option<&String>) -> String {
+        value.map_or("$-1\r\n".into(), |v| format!("${}\r\n{v}\r\n", v.len()))
+    }
+
+    fn answer(
+        args: &[String],
+        strings: &mut HashMap<String, String>,
+        sets: &mut HashMap<String, Vec<(i64, String)>>,
+    ) -> String {
+        let arg = |i: usize| args[i].as_str();
+        match arg(0) {
+            "PING" => "+PONG\r\n".into(),
+            "GET" => bulk(strings.get(arg(1))),
+            "SET" if args.iter().any(|a| a == "NX") && strings.contains_key(arg(1)) => {
+                "$-1\r\n".into()
+            }
+            "SET" => {
+                strings.insert(arg(1).into(), arg(2).into());
+                "+OK\r\n".into()
+            }
+            "EVAL" if arg(1) == UNLOCK_SCRIPT => {
+                let owned = strings.get(arg(3)).is_some_and(|t| t == arg(4));
+                if owned {
+                    strings.remove(arg(3));
+                }
+                format!(":{}\r\n", i32::from(owned))
+            }
+            "EVAL" if arg(1) == COUNT_SCRIPT => {
+                let n = strings.get(arg(3)).map_or(0, |v| v.parse::<i64>().unwrap()) + 1;
+                strings.insert(arg(3).into(), n.to_string());
+                format!(":{n}\r\n")
+            }
+            "ZADD" => {
+                let set = sets.entry(arg(1).into()).or_default();
+                set.retain(|(_, m)| m != arg(3));
+                set.push((arg(2).parse().unwrap(), arg(3).into()));
+                set.sort();
+                ":1\r\n".into()
+            }
+            "ZREM" => {
+                let set = sets.entry(arg(1).into()).or_default();
+                let before = set.len();
+                set.retain(|(_, m)| m != arg(2));
+                format!(":{}\r\n", before - set.len())
+            }
+            "ZRANGE" | "ZRANGEBYSCORE" => {
+                let max = if arg(0) == "ZRANGE" {
+                    i64::MAX
+                } else {
+                    arg(3).parse().unwrap()
+                };
+                let members: Vec<&String> = sets
+                    .get(arg(1))
+                    .into_iter()
+                    .flatten()
+                    .filter(|(score, _)| *score <= max)
+                    .map(|(_, m)| m)
+                    .collect();
+                let mut out = format!("*{}\r\n", members.len());
+                for m in members {
+                    out.push_str(&bulk(Some(m)));
+                }
+                out
+            }
+            other => format!("-ERR unknown command '{other}'\r\n"),
+        }
+    }
+
+    #[test]
+    fn session_locks_exclude_and_counters_add_up() {
+        let shared = fake();
+        shared.ping().unwrap();
+
+        let lock = shared.try_lock("telegram:42").unwrap().unwrap();
+        assert!(lock.key.ends_with("zeroclaw:lock:session:telegram:42"));
+        assert!(shared.try_lock("telegram:42").unwrap().is_none());
+        assert!(shared.try_lock("slack:U1").unwrap().is_some());
+        // Only the holder's token frees it.
+        let stolen = SessionLock {
+            key: lock.key.clone(),
+            token: "someone-else".into(),
+        };
+        shared.unlock(&stolen).unwrap();
+        assert!(shared.try_lock("telegram:42").unwrap().is_none());
+        shared.unlock(&lock).unwrap();
+        assert!(shared.try_lock("telegram:42").unwrap().is_some());
+
+        let minute = Duration::from_mins(1);
+        assert_eq!(shared.counted("tool:shell", minute).unwrap(), 0);
+        assert_eq!(shared.count("tool:shell", minute).unwrap(), 1);
+        assert_eq!(shared.count("tool:shell", minute).unwrap(), 2);
+        assert_eq!(shared.counted("tool:shell", minute).unwrap(), 2);
+    }
+}
@@ -0,0 +1,223 @@
+//! The outbox in Redis (see [`crate::outbox`] for the semantics).
+//!
+//! Each intent is a JSON string under `outbox:intent:<id>`. Sorted sets
+//! order them: `outbox:due` by next attempt, `outbox:sending` by claim time
+//! and `outbox:all` by creation. Removing an id from `outbox:due` is the
+//! claim, so only one process sends each attempt. Dedupe keys are plain
+//! keys that expire with the dedupe window, and sent intents expire after
+//! the outbox's retention.
+
+use super::resp::Value;
+use super::SharedState;
+use crate::outbox::{Enqueued, Intent, Status};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+
+fn intent_key(shared: &SharedState, id: &str) -> String {
+    shared.key(&format!("outbox:intent:{id}"))
+}
+
+fn load(shared: &SharedState, id: &str) -> Result<Option<Intent>> {
+    match shared.command(&["GET", &intent_key(shared, id)])? {
+        Value::Data(raw) => Ok(Some(
+            serde_json::from_slice(&raw).context("Malformed outbox intent in Redis")?,
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Write `intent`; `expire` sets how long it is kept.
+fn save(shared: &SharedState, intent: &Intent, expire: Option<Duration>) -> Result<()> {
+    let key = intent_key(shared, &intent.id);
+    let json = serde_json::to_string(intent)?;
+    match expire {
+        Some(expire) => {
+            let secs = expire.num_seconds().max(1).to_string();
+            shared.command(&["SET", &key, &json, "EX", &secs])?
+        }
+        None => shared.command(&["SET", &key, &json])?,
+    };
+    Ok(())
+}
+
+fn schedule(shared: &SharedState, set: &str, at: DateTime<Utc>, id: &str) -> Result<()> {
+    let score = at.timestamp_millis().to_string();
+    shared.command(&["ZADD", &shared.key(set), &score, id])?;
+    Ok(())
+}
+
+fn unschedule(shared: &SharedState, set: &str, id: &str) -> Result<bool> {
+    Ok(shared.command(&["ZREM", &shared.key(set), id])?.as_int() == Some(1))
+}
+
+fn ids_until(shared: &SharedState, set: &str, until: DateTime<Utc>) -> Result<Vec<String>> {
+    let max = until.timestamp_millis().to_string();
+    Ok(shared
+        .command(&["ZRANGEBYSCORE", &shared.key(set), "-inf", &max])?
+        .into_strings())
+}
+
+/// Record a new intent unless a live one with its dedupe key exists.
+pub fn insert(shared: &SharedState, intent: Intent, window: Duration) -> Result<Enqueued> {
+    let dedupe = shared.key(&format!("outbox:dedupe:{}", intent.dedupe_key));
+    let window = window.num_milliseconds().max(1).to_string();
+    let claimed = shared.command(&["SET", &dedupe, &intent.id, "NX", "PX", &window])?;
+    if claimed == Value::Nil {
+        let earlier = shared.command(&["GET", &dedupe])?.into_string();
+        if let Some(earlier) = earlier.map(|id| load(shared, &id)).transpose()?.flatten() {
+            if earlier.status != Status::Dead {
+                return Ok(Enqueued::Duplicate(earlier));
+            }
+        }
+        shared.command(&["SET", &dedupe, &intent.id, "PX", &window])?;
+    }
+    save(shared, &intent, None)?;
+    schedule(shared, "outbox:all", intent.created_at, &intent.id)?;
+    schedule(shared, "outbox:due", intent.next_attempt_at, &intent.id)?;
+    Ok(Enqueued::New(intent))
+}
+
+/// Take a due intent for sending; `None` if another process has it.
+pub fn claim(shared: &SharedState, id: &str) -> Result<Option<Intent>> {
+    if !unschedule(shared, "outbox:due", id)? {
+        return Ok(None);
+    }
+    let Some(mut intent) = load(shared, id)? else {
+        return Ok(None);
+    };
+    intent.status = Status::Sending;
+    intent.attempts += 1;
+    save(shared, &intent, None)?;
+    schedule(shared, "outbox:sending", Utc::now(), id)?;
+    Ok(Some(intent))
+}
+
+/// Store the outcome of an attempt; pending intents are scheduled again at
+/// `next`, sent ones kept for `keep_sent`.
+pub fn finish(
+    shared: &SharedState,
+    intent: &Intent,
+    (status, error): (Status, Option<&str>),
+    next: DateTime<Utc>,
+    keep_sent: Duration,
+) -> Result<()> {
+    let mut intent = intent.clone();
+    intent.status = status;
+    intent.last_error = error.map(String::from);
+    intent.next_attempt_at = next;
+    unschedule(shared, "outbox:sending", &intent.id)?;
+    save(
+        shared,
+        &intent,
+        (status == Status::Sent).then_some(keep_sent),
+    )?;
+    if status == Status::Pending {
+        schedule(shared, "outbox:due", next, &intent.id)?;
+    }
+    Ok(())
+}
+
+/// Put intents claimed before `stale_before` back in the queue.
+pub fn recover(shared: &SharedState, stale_before: DateTime<Utc>) -> Result<usize> {
+    let mut released = 0;
+    for id in ids_until(shared, "outbox:sending", stale_before)? {
+        if !unschedule(shared, "outbox:sending", &id)? {
+            continue;
+        }
+        if let Some(mut intent) = load(shared, &id)? {
+            intent.status = Status::Pending;
+            save(shared, &intent, None)?;
+            schedule(shared, "outbox:due", Utc::now(), &id)?;
+            released += 1;
+        }
+    }
+    Ok(released)
+}
+
+/// Ids of pending intents whose next attempt is due.
+pub fn due(shared: &SharedState) -> Result<Vec<String>> {
+    ids_until(shared, "outbox:due", Utc::now())
+}
+
+/// Every intent still kept, oldest first.
+pub fn list(shared: &SharedState) -> Result<Vec<Intent>> {
+    let ids = shared
+        .command(&["ZRANGE", &shared.key("outbox:all"), "0", "-1"])?
+        .into_strings();
+    let mut intents = Vec::with_capacity(ids.len());
+    for id in ids {
+        match load(shared, &id)? {
+            Some(intent) => intents.push(intent),
+            // Sent and expired.
+            None => {
+                unschedule(shared, "outbox:all", &id)?;
+            }
+        }
+    }
+    Ok(intents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(id: &str, key: &str) -> Intent {
+        let now = Utc::now();
+        Intent {
+            id: id.into(),
+            dedupe_key: key.into(),
+            source: "notify".into(),
+            channel: "telegram".into(),
+            recipient: "42".into(),
+            message: "Backup failed".into(),
+            status: Status::Pending,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            next_attempt_at: now,
+        }
+    }
+
+    #[test]
+    fn intents_are_claimed_once_and_retried() {
+        let shared = super::super::tests::fake();
+        let window = Duration::hours(1);
+        assert!(matches!(
+            insert(&shared, intent("a", "k1"), window).unwrap(),
+            Enqueued::New(_)
+        ));
+        assert!(matches!(
+            insert(&shared, intent("b", "k1"), window).unwrap(),
+            Enqueued::Duplicate(earlier) if earlier.id == "a"
+        ));
+        assert_eq!(due(&shared).unwrap(), vec!["a".to_string()]);
+
+        let claimed = claim(&shared, "a").unwrap().unwrap();
+        assert_eq!((claimed.status, claimed.attempts), (Status::Sending, 1));
+        assert!(claim(&shared, "a").unwrap().is_none());
+        assert!(due(&shared).unwrap().is_empty());
+
+        let later = Utc::now() + Duration::minutes(5);
+        finish(
+            &shared,
+            &claimed,
+            (Status::Pending, Some("timeout")),
+            later,
+            Duration::days(7),
+        )
+        .unwrap();
+        assert!(due(&shared).unwrap().is_empty());
+        let listed = list(&shared).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].last_error.as_deref(), Some("timeout"));
+
+        // A claim left by a crashed process goes back in the queue.
+        insert(&shared, intent("c", "k2"), window).unwrap();
+        claim(&shared, "c").unwrap().unwrap();
+        assert_eq!(
+            recover(&shared, Utc::now() + Duration::seconds(1)).unwrap(),
+            1
+        );
+        assert_eq!(due(&shared).unwrap(), vec!["c".to_string()]);
+    }
+}
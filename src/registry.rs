@@ -0,0 +1,306 @@
+//! Named registries for tools, channels and providers that can be extended at
+//! runtime (skills, MCP servers, WASM plugins, embedding code), with explicit
+//! collision handling and metadata for introspection.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginKind {
+    Tool,
+    Channel,
+    Provider,
+    Middleware,
+}
+
+/// Where a registration came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "lowercase")]
+pub enum PluginSource {
+    Builtin,
+    Skill(String),
+    Mcp(String),
+    Wasm(String),
+    Embedded,
+}
+
+/// Metadata describing one registered item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub kind: PluginKind,
+    pub source: PluginSource,
+    pub description: String,
+    /// Free-form capability tags; for tools these are the parameter names.
+    pub capabilities: Vec<String>,
+}
+
+impl PluginInfo {
+    pub fn new(kind: PluginKind, name: impl Into<String>, source: PluginSource) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            source,
+            description: String::new(),
+            capabilities: Vec::new(),
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn with_capabilities<I, S>(mut self, capabilities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.capabilities = capabilities.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// What to do when a name is already taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnCollision {
+    /// Refuse the new registration (default).
+    #[default]
+    Reject,
+    /// Swap out the existing entry.
+    Replace,
+    /// Keep the existing entry and silently drop the new one.
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Registered {
+    Added,
+    Replaced,
+    Skipped,
+}
+
+/// Ordered, name-unique collection. Names compare case-insensitively.
+///
+/// Each entry keeps the policy it was registered with, so it can also be
+/// applied later against built-ins that only exist once config is loaded
+/// (see [`Registry::merge_into`]).
+pub struct Registry<T> {
+    entries: Vec<(PluginInfo, T, OnCollision)>,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> Registry<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|(info, _, _)| info.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn register(
+        &mut self,
+        info: PluginInfo,
+        item: T,
+        on_collision: OnCollision,
+    ) -> anyhow::Result<Registered> {
+        let Some(idx) = self.position(&info.name) else {
+            self.entries.push((info, item, on_collision));
+            return Ok(Registered::Added);
+        };
+        match on_collision {
+            OnCollision::Reject => {
+                let existing = &self.entries[idx].0;
+                anyhow::bail!(
+                    "{:?} '{}' is already registered (from {:?})",
+                    info.kind,
+                    info.name,
+                    existing.source
+                )
+            }
+            OnCollision::Replace => {
+                self.entries[idx] = (info, item, on_collision);
+                Ok(Registered::Replaced)
+            }
+            OnCollision::Skip => Ok(Registered::Skipped),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.position(name).map(|idx| &self.entries[idx].1)
+    }
+
+    pub fn info(&self, name: &str) -> Option<&PluginInfo> {
+        self.position(name).map(|idx| &self.entries[idx].0)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<T> {
+        self.position(name).map(|idx| self.entries.remove(idx).1)
+    }
+
+    pub fn infos(&self) -> impl Iterator<Item = &PluginInfo> {
+        self.entries.iter().map(|(info, _, _)| info)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&PluginInfo, &T)> {
+        self.entries.iter().map(|(info, item, _)| (info, item))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Items in registration order.
+    pub fn into_items(self) -> Vec<T> {
+        self.entries.into_iter().map(|(_, item, _)| item).collect()
+    }
+
+    /// Append entries to `builtins`. An entry whose name matches a built-in
+    /// replaces it only if it was registered with [`OnCollision::Replace`];
+    /// otherwise the built-in wins (logged for `Reject`).
+    pub fn merge_into<B>(
+        &self,
+        builtins: &mut Vec<B>,
+        name_of: impl Fn(&B) -> &str,
+        wrap: impl Fn(&T) -> B,
+    ) {
+        for (info, item, on_collision) in &self.entries {
+            let existing = builtins
+                .iter()
+                .position(|b| name_of(b).eq_ignore_ascii_case(&info.name));
+            match (existing, on_collision) {
+                (None, _) => builtins.push(wrap(item)),
+                (Some(idx), OnCollision::Replace) => builtins[idx] = wrap(item),
+                (Some(_), OnCollision::Skip) => {}
+                (Some(_), OnCollision::Reject) => tracing::warn!(
+                    kind = ?info.kind,
+                    name = info.name,
+                    source = ?info.source,
+                    "Plugin name collides with a built-in; keeping the built-in"
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(name: &str, source: PluginSource) -> PluginInfo {
+        PluginInfo::new(PluginKind::Tool, name, source)
+    }
+
+    #[test]
+    fn collisions_follow_policy() {
+        let mut reg = Registry::new();
+        assert_eq!(
+            reg.register(info("shell", PluginSource::Builtin), 1, OnCollision::Reject)
+                .unwrap(),
+            Registered::Added
+        );
+
+        let err = reg
+            .register(
+                info("Shell", PluginSource::Mcp("ops".into())),
+                2,
+                OnCollision::Reject,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+
+        assert_eq!(
+            reg.register(info("shell", PluginSource::Embedded), 3, OnCollision::Skip)
+                .unwrap(),
+            Registered::Skipped
+        );
+        assert_eq!(reg.get("shell"), Some(&1));
+
+        assert_eq!(
+            reg.register(
+                info("shell", PluginSource::Wasm("x".into())),
+                4,
+                OnCollision::Replace
+            )
+            .unwrap(),
+            Registered::Replaced
+        );
+        assert_eq!(reg.get("SHELL"), Some(&4));
+        assert_eq!(
+            reg.info("shell").unwrap().source,
+            PluginSource::Wasm("x".into())
+        );
+        assert_eq!(reg.len(), 1);
+    }
+
+    #[test]
+    fn merge_into_builtins_honours_policy() {
+        let mut reg = Registry::new();
+        reg.register(
+            info("shell", PluginSource::Embedded),
+            "plugin-shell",
+            OnCollision::Replace,
+        )
+        .unwrap();
+        reg.register(
+            info("file_read", PluginSource::Embedded),
+            "plugin-read",
+            OnCollision::Reject,
+        )
+        .unwrap();
+        reg.register(
+            info("jira", PluginSource::Mcp("atl".into())),
+            "jira",
+            OnCollision::Reject,
+        )
+        .unwrap();
+
+        let mut builtins = vec!["shell", "file_read"];
+        reg.merge_into(
+            &mut builtins,
+            |b| match *b {
+                "plugin-shell" => "shell",
+                other => other,
+            },
+            |item| *item,
+        );
+        assert_eq!(builtins, vec!["plugin-shell", "file_read", "jira"]);
+    }
+
+    #[test]
+    fn keeps_registration_order_and_serializes_metadata() {
+        let mut reg = Registry::new();
+        for name in ["b", "a", "c"] {
+            reg.register(info(name, PluginSource::Builtin), name, OnCollision::Reject)
+                .unwrap();
+        }
+        assert_eq!(reg.remove("a"), Some("a"));
+        assert_eq!(reg.into_items(), vec!["b", "c"]);
+
+        let meta = info("lookup", PluginSource::Skill("crm".into()))
+            .with_description("Find a customer")
+            .with_capabilities(["query"]);
+        let json = serde_json::to_value(&meta).unwrap();
+        assert_eq!(json["kind"], "tool");
+        assert_eq!(json["source"]["type"], "skill");
+        assert_eq!(json["capabilities"][0], "query");
+    }
+}
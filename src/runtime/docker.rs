@@ -0,0 +1,179 @@
+use super::traits::{RuntimeAdapter, ShellCommand};
+use crate::config::DockerRuntimeConfig;
+use crate::security::session_secrets::session_secrets;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Host variables the `docker` CLI needs to reach the daemon.
+const DOCKER_ENV_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "DOCKER_HOST",
+    "DOCKER_CONTEXT",
+    "DOCKER_CONFIG",
+];
+
+/// Docker runtime — agent shell commands run in a throwaway container with
+/// only the workspace mounted, so they cannot reach the rest of the host
+pub struct DockerRuntime {
+    config: DockerRuntimeConfig,
+}
+
+impl DockerRuntime {
+    pub fn new(config: DockerRuntimeConfig) -> Self {
+        Self { config }
+    }
+
+    /// `docker run` arguments for one command. The workspace is read-only
+    /// under read-only autonomy and writable otherwise.
+    fn run_args(&self, container: &str, command: &str, security: &SecurityPolicy) -> Vec<String> {
+        let access = if security.autonomy == AutonomyLevel::ReadOnly {
+            "ro"
+        } else {
+            "rw"
+        };
+        let mut args: Vec<String> = [
+            "run",
+            "--rm",
+            "--name",
+            container,
+            "--network",
+            self.config.network.as_str(),
+            "--cap-drop",
+            "ALL",
+            "--security-opt",
+            "no-new-privileges",
+            "--pids-limit",
+            "256",
+            "-v",
+            format!("{}:/workspace:{access}", security.workspace_dir.display()).as_str(),
+            "-w",
+            "/workspace",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        if self.config.cpus > 0.0 {
+            args.extend(["--cpus".into(), self.config.cpus.to_string()]);
+        }
+        if self.config.memory_mb > 0 {
+            let memory = format!("{}m", self.config.memory_mb);
+            // Same value for swap: no swapping past the limit.
+            args.extend([
+                "--memory".into(),
+                memory.clone(),
+                "--memory-swap".into(),
+                memory,
+            ]);
+        }
+        if let Some(user) = &self.config.user {
+            args.extend(["--user".into(), user.clone()]);
+        }
+        // Held secrets by name only; the `docker` CLI's environment has the values.
+        for name in session_secrets().names() {
+            args.extend(["-e".into(), name]);
+        }
+        args.extend([
+            self.config.image.clone(),
+            "sh".into(),
+            "-c".into(),
+            command.into(),
+        ]);
+        args
+    }
+}
+
+fn docker_command() -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("docker");
+    cmd.env_clear();
+    for var in DOCKER_ENV_VARS {
+        if let Ok(val) = std::env::var(var) {
+            cmd.env(var, val);
+        }
+    }
+    cmd
+}
+
+impl RuntimeAdapter for DockerRuntime {
+    fn name(&self) -> &str {
+        "docker"
+    }
+
+    fn has_shell_access(&self) -> bool {
+        true
+    }
+
+    fn has_filesystem_access(&self) -> bool {
+        true
+    }
+
+    fn storage_path(&self) -> PathBuf {
+        directories::UserDirs::new().map_or_else(
+            || PathBuf::from(".zeroclaw"),
+            |u| u.home_dir().join(".zeroclaw"),
+        )
+    }
+
+    fn supports_long_running(&self) -> bool {
+        true
+    }
+
+    fn memory_budget(&self) -> u64 {
+        self.config.memory_mb * 1024 * 1024
+    }
+
+    fn shell_command(
+        &self,
+        command: &str,
+        security: &SecurityPolicy,
+    ) -> anyhow::Result<ShellCommand> {
+        let container = format!(
+            "zeroclaw-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..12]
+        );
+        let mut cmd = docker_command();
+        cmd.args(self.run_args(&container, command, security));
+        // Killing the CLI leaves the container running; remove it by name.
+        let mut cleanup = docker_command();
+        cleanup.args(["rm", "-f", &container]);
+        Ok(ShellCommand {
+            command: cmd,
+            cleanup: Some(cleanup),
+        })
+    }
+
+    fn shell_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn containers_follow_autonomy_and_limits() {
+        let runtime = DockerRuntime::new(DockerRuntimeConfig {
+            user: Some("1000:1000".into()),
+            ..DockerRuntimeConfig::default()
+        });
+        let mut security = SecurityPolicy {
+            workspace_dir: PathBuf::from("/home/me/workspace"),
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let args = runtime.run_args("zeroclaw-test", "ls -la", &security);
+        let joined = args.join(" ");
+        assert!(joined.contains("-v /home/me/workspace:/workspace:ro"));
+        assert!(joined.contains("--network none"));
+        assert!(joined.contains("--cpus 1 --memory 512m --memory-swap 512m --user 1000:1000"));
+        assert!(joined.ends_with("alpine:3.20 sh -c ls -la"));
+        assert_eq!(args.last().map(String::as_str), Some("ls -la"));
+
+        security.autonomy = AutonomyLevel::Supervised;
+        let args = runtime.run_args("zeroclaw-test", "touch x", &security);
+        assert!(args.contains(&"/home/me/workspace:/workspace:rw".to_string()));
+        assert_eq!(runtime.memory_budget(), 512 * 1024 * 1024);
+    }
+}
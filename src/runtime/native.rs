@@ -1,4 +1,6 @@
-use super::traits::RuntimeAdapter;
+use super::traits::{RuntimeAdapter, ShellCommand};
+use crate::security::SecurityPolicy;
+use crate::tools::shell::safe_env_vars;
 use std::path::PathBuf;
 
 /// Native runtime — full access, runs on Mac/Linux/Docker/Raspberry Pi
@@ -33,6 +35,26 @@ impl RuntimeAdapter for NativeRuntime {
     fn supports_long_running(&self) -> bool {
         true
     }
+
+    fn shell_command(
+        &self,
+        command: &str,
+        security: &SecurityPolicy,
+    ) -> anyhow::Result<ShellCommand> {
+        // Clear the environment to prevent leaking API keys and other secrets
+        // (CWE-200), then re-add only safe, functional variables.
+        let mut cmd = crate::platform::shell_command(command, false);
+        cmd.current_dir(&security.workspace_dir).env_clear();
+        for var in safe_env_vars() {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        Ok(ShellCommand {
+            command: cmd,
+            cleanup: None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,13 @@
+use crate::security::SecurityPolicy;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// A shell command ready to run in a runtime.
+pub struct ShellCommand {
+    pub command: tokio::process::Command,
+    /// Run after a timeout to stop anything the killed process left behind
+    pub cleanup: Option<tokio::process::Command>,
+}
 
 /// Runtime adapter — abstracts platform differences so the same agent
 /// code runs on native, Docker, Cloudflare Workers, Raspberry Pi, etc.
@@ -22,4 +31,16 @@ pub trait RuntimeAdapter: Send + Sync {
     fn memory_budget(&self) -> u64 {
         0
     }
+
+    /// Build the process that runs an agent-initiated shell command
+    fn shell_command(
+        &self,
+        command: &str,
+        security: &SecurityPolicy,
+    ) -> anyhow::Result<ShellCommand>;
+
+    /// How long a shell command may run before it is killed
+    fn shell_timeout(&self) -> Duration {
+        Duration::from_mins(1)
+    }
 }
@@ -0,0 +1,123 @@
+//! Out-of-band confirmation for risky tool writes under supervised autonomy.
+//!
+//! The tool describes the exact action and an [`Approver`] puts it to the
+//! user on the terminal or the chat they are in. The model never sees
+//! anything it could replay to approve the action itself. With no approver
+//! installed (daemon, gateway, `mcp serve`) the answer is no.
+//!
+//! Interactive autonomy asks the same way for every call; [`ToolClass`]
+//! decides which tools it stops for.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// Whether a tool only looks or also acts: runs commands, writes files,
+/// deletes memories, sends messages or acts on connected accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolClass {
+    Read,
+    Write,
+}
+
+impl ToolClass {
+    /// `tool`'s class: the configured one, else the tool's own `builtin` one.
+    pub fn of(tool: &str, builtin: Self, overrides: &BTreeMap<String, ToolClass>) -> Self {
+        overrides.get(tool).copied().unwrap_or(builtin)
+    }
+}
+
+/// Puts a yes/no question to the user.
+#[async_trait]
+pub trait Approver: Send + Sync {
+    /// `true` only on an explicit yes; no, silence and nobody to ask are all no.
+    async fn confirm(&self, question: &str) -> bool;
+}
+
+fn installed() -> &'static RwLock<Option<Arc<dyn Approver>>> {
+    static APPROVER: RwLock<Option<Arc<dyn Approver>>> = RwLock::new(None);
+    &APPROVER
+}
+
+/// Make `approver` the one every default [`ApprovalGate`] asks.
+pub fn install_approver(approver: Arc<dyn Approver>) {
+    *installed()
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(approver);
+}
+
+/// Confirms supervised actions with the user. Defaults to the approver
+/// installed for the process.
+#[derive(Default)]
+pub struct ApprovalGate {
+    approver: Option<Arc<dyn Approver>>,
+}
+
+impl ApprovalGate {
+    pub fn new(approver: Arc<dyn Approver>) -> Self {
+        Self {
+            approver: Some(approver),
+        }
+    }
+
+    /// Ask the user whether `action` may go ahead.
+    pub async fn confirm(&self, action: &str) -> bool {
+        let approver = self.approver.clone().or_else(|| {
+            installed()
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone()
+        });
+        let Some(approver) = approver else {
+            tracing::warn!("{action}: nobody to approve it, refused");
+            return false;
+        };
+        approver.confirm(action).await
+    }
+}
+
+/// A user who always gives the same answer.
+#[cfg(test)]
+struct Answer(bool);
+
+#[cfg(test)]
+#[async_trait]
+impl Approver for Answer {
+    async fn confirm(&self, _question: &str) -> bool {
+        self.0
+    }
+}
+
+#[cfg(test)]
+impl ApprovalGate {
+    /// A gate whose user always answers `yes` (or always no).
+    pub fn answering(yes: bool) -> Self {
+        Self::new(Arc::new(Answer(yes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn gate_passes_on_the_approvers_answer() {
+        assert!(ApprovalGate::answering(true).confirm("x").await);
+        assert!(!ApprovalGate::answering(false).confirm("x").await);
+    }
+
+    #[test]
+    fn configured_class_overrides_the_builtin_one() {
+        let overrides = BTreeMap::from([
+            ("shell".to_string(), ToolClass::Read),
+            ("web_search".to_string(), ToolClass::Write),
+        ]);
+        let of = |tool, builtin| ToolClass::of(tool, builtin, &overrides);
+        assert_eq!(of("fs_write", ToolClass::Write), ToolClass::Write);
+        assert_eq!(of("shell", ToolClass::Write), ToolClass::Read);
+        assert_eq!(of("web_search", ToolClass::Read), ToolClass::Write);
+        assert_eq!(of("memory_recall", ToolClass::Read), ToolClass::Read);
+    }
+}
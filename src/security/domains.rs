@@ -0,0 +1,298 @@
+//! Per-domain URL allowlist for tools that drive a browser.
+//!
+//! `[browser].allowed_domains` is the only source of truth: a URL passes
+//! when it is http(s) (or a local `file://` page), its host is not a
+//! loopback/private address, and the host matches an entry exactly, as a
+//! subdomain, or through a `*.` wildcard. A lone `*` allows any public host.
+
+/// Normalized set of domains a browser tool may visit.
+#[derive(Debug, Clone, Default)]
+pub struct DomainAllowlist {
+    domains: Vec<String>,
+}
+
+impl DomainAllowlist {
+    pub fn new(domains: Vec<String>) -> Self {
+        Self {
+            domains: normalize_domains(domains),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    /// Reject `url` unless it is a public http(s) URL whose host is allowed.
+    pub fn check_url(&self, url: &str) -> anyhow::Result<()> {
+        let url = url.trim();
+
+        if url.is_empty() {
+            anyhow::bail!("URL cannot be empty");
+        }
+
+        // Allow file:// URLs for local testing
+        if url.starts_with("file://") {
+            return Ok(());
+        }
+
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            anyhow::bail!("Only http:// and https:// URLs are allowed");
+        }
+
+        if self.domains.is_empty() {
+            anyhow::bail!(
+                "Browser tool enabled but no allowed_domains configured. \
+                Add [browser].allowed_domains in config.toml"
+            );
+        }
+
+        let host = extract_host(url)?;
+
+        if is_private_host(&host) {
+            anyhow::bail!("Blocked local/private host: {host}");
+        }
+
+        if !host_matches_allowlist(&host, &self.domains) {
+            anyhow::bail!("Host '{host}' not in browser.allowed_domains");
+        }
+
+        Ok(())
+    }
+}
+
+fn normalize_domains(domains: Vec<String>) -> Vec<String> {
+    domains
+        .into_iter()
+        .map(|d| d.trim().to_lowercase())
+        .filter(|d| !d.is_empty())
+        .collect()
+}
+
+fn extract_host(url_str: &str) -> anyhow::Result<String> {
+    // Simple host extraction without url crate
+    let url = url_str.trim();
+    let without_scheme = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("file://"))
+        .unwrap_or(url);
+
+    // Extract host — handle bracketed IPv6 addresses like [::1]:8080
+    let authority = without_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or(without_scheme);
+    // Drop userinfo so `https://allowed.com@evil.com` resolves to evil.com
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+
+    let host = if authority.starts_with('[') {
+        // IPv6: take everything up to and including the closing ']'
+        authority.find(']').map_or(authority, |i| &authority[..=i])
+    } else {
+        // IPv4 or hostname: take everything before the port separator
+        authority.split(':').next().unwrap_or(authority)
+    };
+
+    if host.is_empty() {
+        anyhow::bail!("Invalid URL: no host");
+    }
+
+    Ok(host.to_lowercase())
+}
+
+fn is_private_host(host: &str) -> bool {
+    // Strip brackets from IPv6 addresses like [::1]
+    let bare = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+
+    if bare == "localhost" {
+        return true;
+    }
+
+    // Parse as IP address to catch all representations (decimal, hex, octal, mapped)
+    if let Ok(ip) = bare.parse::<std::net::IpAddr>() {
+        return match ip {
+            std::net::IpAddr::V4(v4) => {
+                v4.is_loopback()
+                    || v4.is_private()
+                    || v4.is_link_local()
+                    || v4.is_unspecified()
+                    || v4.is_broadcast()
+            }
+            std::net::IpAddr::V6(v6) => {
+                let segs = v6.segments();
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    // Unique-local (fc00::/7) — IPv6 equivalent of RFC 1918
+                    || (segs[0] & 0xfe00) == 0xfc00
+                    // Link-local (fe80::/10)
+                    || (segs[0] & 0xffc0) == 0xfe80
+                    // IPv4-mapped addresses (::ffff:127.0.0.1)
+                    || v6.to_ipv4_mapped().is_some_and(|v4| {
+                        v4.is_loopback()
+                            || v4.is_private()
+                            || v4.is_link_local()
+                            || v4.is_unspecified()
+                            || v4.is_broadcast()
+                    })
+            }
+        };
+    }
+
+    // Fallback string patterns for hostnames that look like IPs but don't parse
+    // (e.g., partial addresses used in DNS names).
+    let string_patterns = [
+        "127.", "10.", "192.168.", "0.0.0.0", "172.16.", "172.17.", "172.18.", "172.19.",
+        "172.20.", "172.21.", "172.22.", "172.23.", "172.24.", "172.25.", "172.26.", "172.27.",
+        "172.28.", "172.29.", "172.30.", "172.31.",
+    ];
+
+    string_patterns.iter().any(|p| bare.starts_with(p))
+}
+
+fn host_matches_allowlist(host: &str, allowed: &[String]) -> bool {
+    allowed.iter().any(|pattern| {
+        if pattern == "*" {
+            return true;
+        }
+        if pattern.starts_with("*.") {
+            // Wildcard subdomain match
+            let suffix = &pattern[1..]; // ".example.com"
+            host.ends_with(suffix) || host == &pattern[2..]
+        } else {
+            // Exact match or subdomain
+            host == pattern || host.ends_with(&format!(".{pattern}"))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_domains_works() {
+        let domains = vec![
+            "  Example.COM  ".into(),
+            "docs.example.com".into(),
+            String::new(),
+        ];
+        let normalized = normalize_domains(domains);
+        assert_eq!(normalized, vec!["example.com", "docs.example.com"]);
+    }
+
+    #[test]
+    fn extract_host_works() {
+        assert_eq!(
+            extract_host("https://example.com/path").unwrap(),
+            "example.com"
+        );
+        assert_eq!(
+            extract_host("https://Sub.Example.COM:8080/").unwrap(),
+            "sub.example.com"
+        );
+        assert_eq!(
+            extract_host("https://example.com?q=1").unwrap(),
+            "example.com"
+        );
+    }
+
+    #[test]
+    fn extract_host_handles_ipv6() {
+        // IPv6 with brackets (required for URLs with ports)
+        assert_eq!(extract_host("https://[::1]/path").unwrap(), "[::1]");
+        // IPv6 with brackets and port
+        assert_eq!(
+            extract_host("https://[2001:db8::1]:8080/path").unwrap(),
+            "[2001:db8::1]"
+        );
+        // IPv6 with brackets, trailing slash
+        assert_eq!(extract_host("https://[fe80::1]/").unwrap(), "[fe80::1]");
+    }
+
+    #[test]
+    fn extract_host_ignores_userinfo() {
+        assert_eq!(
+            extract_host("https://example.com@evil.test/").unwrap(),
+            "evil.test"
+        );
+    }
+
+    #[test]
+    fn is_private_host_detects_local() {
+        assert!(is_private_host("localhost"));
+        assert!(is_private_host("127.0.0.1"));
+        assert!(is_private_host("192.168.1.1"));
+        assert!(is_private_host("10.0.0.1"));
+        assert!(!is_private_host("example.com"));
+        assert!(!is_private_host("google.com"));
+    }
+
+    #[test]
+    fn is_private_host_catches_ipv6() {
+        assert!(is_private_host("::1"));
+        assert!(is_private_host("[::1]"));
+        assert!(is_private_host("0.0.0.0"));
+    }
+
+    #[test]
+    fn is_private_host_catches_mapped_ipv4() {
+        // IPv4-mapped IPv6 addresses
+        assert!(is_private_host("::ffff:127.0.0.1"));
+        assert!(is_private_host("::ffff:10.0.0.1"));
+        assert!(is_private_host("::ffff:192.168.1.1"));
+    }
+
+    #[test]
+    fn is_private_host_catches_ipv6_private_ranges() {
+        // Unique-local (fc00::/7)
+        assert!(is_private_host("fd00::1"));
+        assert!(is_private_host("fc00::1"));
+        // Link-local (fe80::/10)
+        assert!(is_private_host("fe80::1"));
+        // Public IPv6 should pass
+        assert!(!is_private_host("2001:db8::1"));
+    }
+
+    #[test]
+    fn host_matches_allowlist_exact() {
+        let allowed = vec!["example.com".into()];
+        assert!(host_matches_allowlist("example.com", &allowed));
+        assert!(host_matches_allowlist("sub.example.com", &allowed));
+        assert!(!host_matches_allowlist("notexample.com", &allowed));
+    }
+
+    #[test]
+    fn host_matches_allowlist_wildcard() {
+        let allowed = vec!["*.example.com".into()];
+        assert!(host_matches_allowlist("sub.example.com", &allowed));
+        assert!(host_matches_allowlist("example.com", &allowed));
+        assert!(!host_matches_allowlist("other.com", &allowed));
+    }
+
+    #[test]
+    fn host_matches_allowlist_star() {
+        let allowed = vec!["*".into()];
+        assert!(host_matches_allowlist("anything.com", &allowed));
+        assert!(host_matches_allowlist("example.org", &allowed));
+    }
+
+    #[test]
+    fn check_url_blocks_ipv6_ssrf() {
+        let allowlist = DomainAllowlist::new(vec!["*".into()]);
+        assert!(allowlist.check_url("https://[::1]/").is_err());
+        assert!(allowlist.check_url("https://[::ffff:127.0.0.1]/").is_err());
+        assert!(allowlist
+            .check_url("https://[::ffff:10.0.0.1]:8080/")
+            .is_err());
+    }
+
+    #[test]
+    fn empty_allowlist_blocks_everything() {
+        let allowlist = DomainAllowlist::new(vec![]);
+        assert!(allowlist.is_empty());
+        assert!(allowlist.check_url("https://example.com").is_err());
+    }
+}
@@ -0,0 +1,546 @@
+//! Output policies for agent replies.
+//!
+//! Each channel gets a list of named policies (`[guardrails]` in the
+//! config); every policy is a list of regex, keyword or semantic rules that
+//! either block the reply outright or rewrite the offending text. Every time
+//! a rule fires, an entry is appended to `workspace/guardrails/audit.jsonl`.
+
+use crate::config::{Config, GuardrailAction, GuardrailKind, GuardrailRule, GuardrailsConfig};
+use crate::memory::embeddings::{create_embedding_provider, EmbeddingProvider};
+use crate::memory::vector::cosine_similarity;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// Built-in policy that redacts common credential formats.
+pub const SECRETS_POLICY: &str = "secrets";
+const SECRET_PATTERNS: [&str; 6] = [
+    r"\bsk-[A-Za-z0-9_-]{16,}",
+    r"\bxox[abposr]-[A-Za-z0-9-]{10,}",
+    r"\bAKIA[0-9A-Z]{16}\b",
+    r"\bgh[pousr]_[A-Za-z0-9]{36,}",
+    r"\b\d{8,10}:[A-Za-z0-9_-]{35}\b",
+    r"(?s)-----BEGIN [A-Z ]*PRIVATE KEY-----.*?-----END [A-Z ]*PRIVATE KEY-----",
+];
+const DEFAULT_REPLACEMENT: &str = "[redacted]";
+const DEFAULT_SEMANTIC_THRESHOLD: f32 = 0.82;
+
+enum Matcher {
+    Pattern(Regex),
+    Semantic {
+        examples: Vec<String>,
+        threshold: f32,
+        vectors: OnceCell<Vec<Vec<f32>>>,
+    },
+}
+
+struct Rule {
+    name: String,
+    action: GuardrailAction,
+    matcher: Matcher,
+    replacement: String,
+    message: Option<String>,
+}
+
+impl Rule {
+    fn compile(policy: &str, rule: &GuardrailRule) -> Result<Self> {
+        let context = || {
+            format!(
+                "Invalid guardrail rule '{}' in policy '{policy}'",
+                rule.name
+            )
+        };
+        if rule.patterns.is_empty() {
+            anyhow::bail!("{}: no patterns", context());
+        }
+        let matcher = match rule.kind {
+            GuardrailKind::Regex => {
+                Matcher::Pattern(Regex::new(&rule.patterns.join("|")).with_context(context)?)
+            }
+            GuardrailKind::Keyword => {
+                let words: Vec<String> = rule
+                    .patterns
+                    .iter()
+                    .map(|w| regex::escape(w.trim()))
+                    .collect();
+                let pattern = format!(r"(?i)\b(?:{})\b", words.join("|"));
+                Matcher::Pattern(Regex::new(&pattern).with_context(context)?)
+            }
+            GuardrailKind::Semantic => Matcher::Semantic {
+                examples: rule.patterns.clone(),
+                threshold: rule.threshold.unwrap_or(DEFAULT_SEMANTIC_THRESHOLD),
+                vectors: OnceCell::new(),
+            },
+        };
+        Ok(Self {
+            name: rule.name.clone(),
+            action: rule.action,
+            matcher,
+            replacement: rule
+                .replacement
+                .clone()
+                .unwrap_or_else(|| DEFAULT_REPLACEMENT.into()),
+            message: rule.message.clone(),
+        })
+    }
+
+    fn secrets() -> Self {
+        Self {
+            name: "credentials".into(),
+            action: GuardrailAction::Rewrite,
+            matcher: Matcher::Pattern(
+                Regex::new(&SECRET_PATTERNS.join("|")).expect("built-in secret patterns are valid"),
+            ),
+            replacement: "[REDACTED]".into(),
+            message: None,
+        }
+    }
+}
+
+/// A rule that fired on a reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trigger {
+    pub policy: String,
+    pub rule: String,
+    pub action: GuardrailAction,
+    pub matches: usize,
+}
+
+/// The reply to send, and why it differs from the original (if it does).
+#[derive(Debug, Clone)]
+pub struct Verdict {
+    pub text: String,
+    pub blocked: bool,
+    pub triggers: Vec<Trigger>,
+}
+
+/// Compiled `[guardrails]` policies.
+#[derive(Default)]
+pub struct Guardrails {
+    policies: BTreeMap<String, Vec<Rule>>,
+    channels: BTreeMap<String, Vec<String>>,
+    default: Vec<String>,
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    audit_path: Option<PathBuf>,
+}
+
+impl Guardrails {
+    /// Compile every policy and check that channels only name defined ones.
+    pub fn from_config(config: &GuardrailsConfig) -> Result<Self> {
+        let mut policies = BTreeMap::new();
+        policies.insert(SECRETS_POLICY.to_string(), vec![Rule::secrets()]);
+        for (name, rules) in &config.policies {
+            let compiled = rules
+                .iter()
+                .map(|rule| Rule::compile(name, rule))
+                .collect::<Result<Vec<_>>>()?;
+            policies.insert(name.clone(), compiled);
+        }
+
+        let used = config.default.iter().map(|p| ("default", p)).chain(
+            config
+                .channels
+                .iter()
+                .flat_map(|(channel, names)| names.iter().map(move |p| (channel.as_str(), p))),
+        );
+        for (channel, policy) in used {
+            if !policies.contains_key(policy) {
+                anyhow::bail!("Guardrail policy '{policy}' (used by {channel}) is not defined");
+            }
+        }
+
+        Ok(Self {
+            policies,
+            channels: config.channels.clone(),
+            default: config.default.clone(),
+            embedder: None,
+            audit_path: None,
+        })
+    }
+
+    /// [`Self::from_config`] plus the workspace audit log and, when any rule
+    /// is semantic, the memory embedding provider.
+    pub fn for_config(config: &Config) -> Result<Self> {
+        let mut guardrails = Self::from_config(&config.guardrails)?
+            .with_audit_log(audit_path(&config.workspace_dir));
+        if guardrails.has_semantic_rules() {
+            let embedder: Arc<dyn EmbeddingProvider> = Arc::from(create_embedding_provider(
+                &config.memory.embedding_provider,
+                config.api_key.as_deref(),
+                &config.memory.embedding_model,
+                config.memory.embedding_dimensions,
+            ));
+            if embedder.name() == "none" {
+                tracing::warn!(
+                    "Semantic guardrail rules need memory.embedding_provider; they will not match"
+                );
+            }
+            guardrails = guardrails.with_embedder(embedder);
+        }
+        Ok(guardrails)
+    }
+
+    pub fn with_embedder(mut self, embedder: Arc<dyn EmbeddingProvider>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    pub fn with_audit_log(mut self, path: PathBuf) -> Self {
+        self.audit_path = Some(path);
+        self
+    }
+
+    fn has_semantic_rules(&self) -> bool {
+        self.policies
+            .values()
+            .flatten()
+            .any(|rule| matches!(rule.matcher, Matcher::Semantic { .. }))
+    }
+
+    fn policies_for(&self, channel: &str) -> &[String] {
+        self.channels.get(channel).unwrap_or(&self.default)
+    }
+
+    /// Whether any policy applies to `channel`. Replies there must be
+    /// checked in full, so they are not streamed.
+    pub fn is_active(&self, channel: &str) -> bool {
+        !self.policies_for(channel).is_empty()
+    }
+
+    /// Apply `channel`'s policies in order. A blocking rule stops the check
+    /// and replaces the reply; rewrite rules edit it and continue.
+    pub async fn check(&self, channel: &str, response: &str) -> Verdict {
+        let mut text = response.to_string();
+        let mut triggers = Vec::new();
+
+        for policy in self.policies_for(channel) {
+            let Some(rules) = self.policies.get(policy) else {
+                continue;
+            };
+            for rule in rules {
+                let matches = self.count_matches(rule, &text).await;
+                if matches == 0 {
+                    continue;
+                }
+                let trigger = Trigger {
+                    policy: policy.clone(),
+                    rule: rule.name.clone(),
+                    action: rule.action,
+                    matches,
+                };
+                self.audit(channel, &trigger);
+                triggers.push(trigger);
+
+                match (rule.action, &rule.matcher) {
+                    (GuardrailAction::Block, _) => {
+                        let message = rule.message.clone().unwrap_or_else(|| {
+                            format!(
+                                "⚠️ I can't send that reply (blocked by the '{policy}' policy)."
+                            )
+                        });
+                        return Verdict {
+                            text: message,
+                            blocked: true,
+                            triggers,
+                        };
+                    }
+                    (GuardrailAction::Rewrite, Matcher::Pattern(re)) => {
+                        text = re
+                            .replace_all(&text, rule.replacement.as_str())
+                            .into_owned();
+                    }
+                    (GuardrailAction::Rewrite, Matcher::Semantic { .. }) => {
+                        text.clone_from(&rule.replacement);
+                    }
+                }
+            }
+        }
+
+        Verdict {
+            text,
+            blocked: false,
+            triggers,
+        }
+    }
+
+    async fn count_matches(&self, rule: &Rule, text: &str) -> usize {
+        match &rule.matcher {
+            Matcher::Pattern(re) => re.find_iter(text).count(),
+            Matcher::Semantic {
+                examples,
+                threshold,
+                vectors,
+            } => {
+                let Some(embedder) = &self.embedder else {
+                    return 0;
+                };
+                let examples = match vectors
+                    .get_or_try_init(|| async {
+                        let refs: Vec<&str> = examples.iter().map(String::as_str).collect();
+                        embedder.embed(&refs).await
+                    })
+                    .await
+                {
+                    Ok(vectors) => vectors,
+                    Err(e) => {
+                        tracing::warn!("Guardrail rule '{}': embedding failed: {e}", rule.name);
+                        return 0;
+                    }
+                };
+                match embedder.embed_one(text).await {
+                    Ok(reply) => usize::from(
+                        examples
+                            .iter()
+                            .any(|example| cosine_similarity(example, &reply) >= *threshold),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Guardrail rule '{}': embedding failed: {e}", rule.name);
+                        0
+                    }
+                }
+            }
+        }
+    }
+
+    fn audit(&self, channel: &str, trigger: &Trigger) {
+        tracing::warn!(
+            channel,
+            policy = %trigger.policy,
+            rule = %trigger.rule,
+            "Guardrail triggered ({:?})",
+            trigger.action
+        );
+        let Some(path) = &self.audit_path else {
+            return;
+        };
+        // The matched text itself is never logged: it may be the secret.
+        let entry = serde_json::json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "channel": channel,
+            "policy": trigger.policy,
+            "rule": trigger.rule,
+            "action": trigger.action,
+            "matches": trigger.matches,
+        });
+        if let Err(e) = append_line(path, &entry.to_string()) {
+            tracing::warn!("Failed to write guardrail audit entry: {e}");
+        }
+    }
+}
+
+pub fn audit_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("guardrails").join("audit.jsonl")
+}
+
+fn append_line(path: &Path, line: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    fn rule(
+        name: &str,
+        kind: GuardrailKind,
+        patterns: &[&str],
+        action: GuardrailAction,
+    ) -> GuardrailRule {
+        GuardrailRule {
+            name: name.into(),
+            kind,
+            patterns: patterns.iter().map(|p| (*p).to_string()).collect(),
+            action,
+            replacement: None,
+            message: None,
+            threshold: None,
+        }
+    }
+
+    fn config() -> GuardrailsConfig {
+        let mut config = GuardrailsConfig {
+            default: vec![SECRETS_POLICY.into()],
+            ..GuardrailsConfig::default()
+        };
+        config.policies.insert(
+            "kids".into(),
+            vec![rule(
+                "profanity",
+                GuardrailKind::Keyword,
+                &["darn", "heck"],
+                GuardrailAction::Rewrite,
+            )],
+        );
+        config.policies.insert(
+            "business".into(),
+            vec![GuardrailRule {
+                message: Some("Let's stick to our own products.".into()),
+                ..rule(
+                    "competitors",
+                    GuardrailKind::Regex,
+                    &[r"(?i)acme\s*corp"],
+                    GuardrailAction::Block,
+                )
+            }],
+        );
+        config
+            .channels
+            .insert("telegram".into(), vec!["secrets".into(), "kids".into()]);
+        config
+            .channels
+            .insert("slack".into(), vec!["business".into()]);
+        config.channels.insert("discord".into(), Vec::new());
+        config
+    }
+
+    #[tokio::test]
+    async fn policies_apply_per_channel_and_are_audited() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let log = tmp.path().join("audit.jsonl");
+        let guardrails = Guardrails::from_config(&config())
+            .unwrap()
+            .with_audit_log(log.clone());
+
+        let verdict = guardrails
+            .check("telegram", "Heck yes, your key is sk-abcdefghijklmnopqrstu")
+            .await;
+        assert!(!verdict.blocked);
+        assert_eq!(verdict.text, "[redacted] yes, your key is [REDACTED]");
+        assert_eq!(verdict.triggers.len(), 2);
+        // "darn" only matches whole words.
+        assert!(guardrails
+            .check("telegram", "darnell")
+            .await
+            .triggers
+            .is_empty());
+
+        let verdict = guardrails.check("slack", "ACME Corp is cheaper").await;
+        assert!(verdict.blocked);
+        assert_eq!(verdict.text, "Let's stick to our own products.");
+
+        // Unlisted channels use the default; an empty list disables checks.
+        assert!(guardrails.is_active("cli"));
+        assert!(!guardrails.is_active("discord"));
+        assert_eq!(
+            guardrails
+                .check("discord", "sk-abcdefghijklmnopqrstu")
+                .await
+                .triggers
+                .len(),
+            0
+        );
+
+        let audit = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(audit.lines().count(), 3);
+        assert!(audit.contains(r#""rule":"competitors""#));
+        assert!(!audit.contains("sk-abc"));
+    }
+
+    #[test]
+    fn policies_parse_from_toml() {
+        let config: GuardrailsConfig = toml::from_str(
+            r#"
+            channels = { telegram = ["secrets", "kids"] }
+
+            [[policies.kids]]
+            name = "profanity"
+            kind = "keyword"
+            patterns = ["damn"]
+            action = "rewrite"
+            "#,
+        )
+        .unwrap();
+        let rule = &config.policies["kids"][0];
+        assert_eq!(rule.kind, GuardrailKind::Keyword);
+        assert_eq!(rule.action, GuardrailAction::Rewrite);
+        assert!(config.default.is_empty());
+        assert!(Guardrails::from_config(&config).is_ok());
+    }
+
+    #[test]
+    fn unknown_policies_and_bad_patterns_are_rejected() {
+        let mut bad = config();
+        bad.channels.insert("matrix".into(), vec!["nope".into()]);
+        assert!(Guardrails::from_config(&bad).is_err());
+
+        let mut bad = config();
+        bad.policies.insert(
+            "broken".into(),
+            vec![rule(
+                "r",
+                GuardrailKind::Regex,
+                &["(unclosed"],
+                GuardrailAction::Block,
+            )],
+        );
+        assert!(Guardrails::from_config(&bad).is_err());
+    }
+
+    /// Embeds a text as [1, 0] if it mentions refunds, else [0, 1].
+    struct TopicEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for TopicEmbedding {
+        fn name(&self) -> &str {
+            "topic"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    if t.to_lowercase().contains("refund") {
+                        vec![1.0, 0.0]
+                    } else {
+                        vec![0.0, 1.0]
+                    }
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn semantic_rules_use_embedding_similarity() {
+        let mut config = GuardrailsConfig {
+            default: vec!["support".into()],
+            ..GuardrailsConfig::default()
+        };
+        config.policies.insert(
+            "support".into(),
+            vec![rule(
+                "no-refund-promises",
+                GuardrailKind::Semantic,
+                &["We will refund you in full"],
+                GuardrailAction::Block,
+            )],
+        );
+        let guardrails = Guardrails::from_config(&config)
+            .unwrap()
+            .with_embedder(Arc::new(TopicEmbedding));
+
+        assert!(
+            guardrails
+                .check("cli", "Sure, full refund today!")
+                .await
+                .blocked
+        );
+        assert!(!guardrails.check("cli", "Your order shipped").await.blocked);
+    }
+}
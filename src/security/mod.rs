@@ -1,9 +1,18 @@
+pub mod approval;
+pub mod domains;
+pub mod guardrails;
 pub mod pairing;
 pub mod policy;
 pub mod secrets;
+pub mod session_secrets;
+pub mod vault;
 
+pub use approval::{ApprovalGate, Approver, ToolClass};
+pub use domains::DomainAllowlist;
+pub use guardrails::Guardrails;
 #[allow(unused_imports)]
 pub use pairing::PairingGuard;
 pub use policy::{AutonomyLevel, SecurityPolicy};
 #[allow(unused_imports)]
 pub use secrets::SecretStore;
+pub use vault::{Vault, VaultBackend};
@@ -14,8 +14,14 @@ pub enum AutonomyLevel {
     Supervised,
     /// Full: autonomous execution within policy bounds
     Full,
+    /// Interactive: acts, but pauses before each write-class tool call until
+    /// the user says yes
+    Interactive,
 }
 
+/// Window for `max_actions_per_hour`.
+const ACTION_WINDOW: std::time::Duration = std::time::Duration::from_hours(1);
+
 /// Sliding-window action tracker for rate limiting.
 #[derive(Debug)]
 pub struct ActionTracker {
@@ -31,13 +37,17 @@ impl ActionTracker {
     }
 
     /// Record an action and return the current count within the window.
+    /// With `[redis]` on, actions are counted across every process.
     pub fn record(&self) -> usize {
+        if let Some(n) = crate::redis::count("actions", ACTION_WINDOW) {
+            return usize::try_from(n).unwrap_or(usize::MAX);
+        }
         let mut actions = self
             .actions
             .lock()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
         let cutoff = Instant::now()
-            .checked_sub(std::time::Duration::from_secs(3600))
+            .checked_sub(ACTION_WINDOW)
             .unwrap_or_else(Instant::now);
         actions.retain(|t| *t > cutoff);
         actions.push(Instant::now());
@@ -46,12 +56,15 @@ impl ActionTracker {
 
     /// Count of actions in the current window without recording.
     pub fn count(&self) -> usize {
+        if let Some(n) = crate::redis::counted("actions", ACTION_WINDOW) {
+            return usize::try_from(n).unwrap_or(usize::MAX);
+        }
         let mut actions = self
             .actions
             .lock()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
         let cutoff = Instant::now()
-            .checked_sub(std::time::Duration::from_secs(3600))
+            .checked_sub(ACTION_WINDOW)
             .unwrap_or_else(Instant::now);
         actions.retain(|t| *t > cutoff);
         actions.len()
@@ -103,28 +116,11 @@ impl Default for SecurityPolicy {
                 "head".into(),
                 "tail".into(),
             ],
-            forbidden_paths: vec![
-                // System directories (blocked even when workspace_only=false)
-                "/etc".into(),
-                "/root".into(),
-                "/home".into(),
-                "/usr".into(),
-                "/bin".into(),
-                "/sbin".into(),
-                "/lib".into(),
-                "/opt".into(),
-                "/boot".into(),
-                "/dev".into(),
-                "/proc".into(),
-                "/sys".into(),
-                "/var".into(),
-                "/tmp".into(),
-                // Sensitive dotfiles
-                "~/.ssh".into(),
-                "~/.gnupg".into(),
-                "~/.aws".into(),
-                "~/.config".into(),
-            ],
+            // System directories (blocked even when workspace_only=false)
+            // and sensitive dotfiles
+            forbidden_paths: crate::platform::default_forbidden_paths(
+                crate::platform::Os::current(),
+            ),
             max_actions_per_hour: 20,
             max_cost_per_day_cents: 500,
             tracker: ActionTracker::new(),
@@ -155,6 +151,30 @@ fn skip_env_assignments(s: &str) -> &str {
     }
 }
 
+/// Commands that change or discard state in ways that are hard to undo.
+/// In supervised mode the shell tool asks before running any of them,
+/// even when the base command is on the allowlist.
+const APPROVAL_REQUIRED: &[&str] = &[
+    "rm",
+    "rmdir",
+    "mv",
+    "chmod",
+    "chown",
+    "dd",
+    "kill",
+    "pkill",
+    "git push",
+    "git reset",
+    "git clean",
+    "git checkout",
+    "git rebase",
+    "git branch -D",
+    "npm publish",
+    "npm unpublish",
+    "cargo publish",
+    "cargo yank",
+];
+
 impl SecurityPolicy {
     /// Check if a shell command is allowed.
     ///
@@ -228,6 +248,33 @@ impl SecurityPolicy {
         has_cmd
     }
 
+    /// Check if an allowed shell command should be confirmed by the user first.
+    ///
+    /// Only supervised mode asks; full autonomy runs anything on the allowlist
+    /// and interactive mode asks before the call is made instead.
+    /// A command needs approval when any of its sub-commands starts with an
+    /// entry in [`APPROVAL_REQUIRED`] (e.g. `rm`, `git push`).
+    pub fn command_needs_approval(&self, command: &str) -> bool {
+        if self.autonomy != AutonomyLevel::Supervised {
+            return false;
+        }
+        command
+            .split(['\n', ';', '|', '&'])
+            .map(|segment| skip_env_assignments(segment.trim()))
+            .any(|segment| {
+                let mut words = segment.split_whitespace();
+                let Some(first) = words.next() else {
+                    return false;
+                };
+                let base = first.rsplit('/').next().unwrap_or(first);
+                let words: Vec<&str> = std::iter::once(base).chain(words).collect();
+                APPROVAL_REQUIRED.iter().any(|risky| {
+                    let risky: Vec<&str> = risky.split_whitespace().collect();
+                    words.starts_with(&risky)
+                })
+            })
+    }
+
     /// Check if a file path is allowed (no path traversal, within workspace)
     pub fn is_path_allowed(&self, path: &str) -> bool {
         // Block null bytes (can truncate paths in C-backed syscalls)
@@ -250,15 +297,8 @@ impl SecurityPolicy {
         }
 
         // Expand tilde for comparison
-        let expanded = if let Some(stripped) = path.strip_prefix("~/") {
-            if let Some(home) = std::env::var("HOME").ok().map(PathBuf::from) {
-                home.join(stripped).to_string_lossy().to_string()
-            } else {
-                path.to_string()
-            }
-        } else {
-            path.to_string()
-        };
+        let home = crate::platform::home_dir();
+        let expanded = crate::platform::expand_home(path, home.as_deref());
 
         // Block absolute paths when workspace_only is set
         if self.workspace_only && Path::new(&expanded).is_absolute() {
@@ -266,19 +306,10 @@ impl SecurityPolicy {
         }
 
         // Block forbidden paths using path-component-aware matching
-        let expanded_path = Path::new(&expanded);
+        let os = crate::platform::Os::current();
         for forbidden in &self.forbidden_paths {
-            let forbidden_expanded = if let Some(stripped) = forbidden.strip_prefix("~/") {
-                if let Some(home) = std::env::var("HOME").ok().map(PathBuf::from) {
-                    home.join(stripped).to_string_lossy().to_string()
-                } else {
-                    forbidden.clone()
-                }
-            } else {
-                forbidden.clone()
-            };
-            let forbidden_path = Path::new(&forbidden_expanded);
-            if expanded_path.starts_with(forbidden_path) {
+            let forbidden_expanded = crate::platform::expand_home(forbidden, home.as_deref());
+            if crate::platform::path_starts_with(os, &expanded, &forbidden_expanded) {
                 return false;
             }
         }
@@ -371,6 +402,8 @@ mod tests {
         assert_eq!(parsed, AutonomyLevel::ReadOnly);
         let parsed2: AutonomyLevel = serde_json::from_str("\"supervised\"").unwrap();
         assert_eq!(parsed2, AutonomyLevel::Supervised);
+        let parsed3: AutonomyLevel = serde_json::from_str("\"interactive\"").unwrap();
+        assert_eq!(parsed3, AutonomyLevel::Interactive);
     }
 
     #[test]
@@ -411,6 +444,27 @@ mod tests {
         assert!(!p.is_command_allowed("node malicious.js"));
     }
 
+    #[test]
+    fn supervised_asks_before_destructive_commands() {
+        let p = default_policy();
+        assert!(p.command_needs_approval("rm -rf build"));
+        assert!(p.command_needs_approval("/bin/rm notes.txt"));
+        assert!(p.command_needs_approval("git status && git push origin main"));
+        assert!(p.command_needs_approval("FORCE=1 cargo publish"));
+        assert!(!p.command_needs_approval("git status"));
+        assert!(!p.command_needs_approval("cargo build --release"));
+        assert!(!p.command_needs_approval("ls | grep rm"));
+    }
+
+    #[test]
+    fn full_autonomy_never_asks() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            ..SecurityPolicy::default()
+        };
+        assert!(!p.command_needs_approval("rm -rf build"));
+    }
+
     #[test]
     fn readonly_blocks_all_commands() {
         let p = readonly_policy();
@@ -546,6 +600,7 @@ mod tests {
             forbidden_paths: vec!["/secret".into()],
             max_actions_per_hour: 100,
             max_cost_per_day_cents: 1000,
+            ..crate::config::AutonomyConfig::default()
         };
         let workspace = PathBuf::from("/tmp/test-workspace");
         let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
@@ -853,6 +908,7 @@ mod tests {
             forbidden_paths: vec![],
             max_actions_per_hour: 10,
             max_cost_per_day_cents: 100,
+            ..crate::config::AutonomyConfig::default()
         };
         let workspace = PathBuf::from("/tmp/test");
         let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
@@ -0,0 +1,105 @@
+//! Secrets fetched during a run, kept out of the conversation.
+//!
+//! `password_manager` holds the value here under an environment variable
+//! name and tells the model only that name. `shell` commands get every held
+//! secret in their environment, and tool output is scrubbed of the values
+//! before the model or the conversation store sees it.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Prefix of every held secret's variable name.
+pub const ENV_PREFIX: &str = "ZEROCLAW_SECRET_";
+
+#[derive(Default)]
+pub struct SessionSecrets {
+    values: Mutex<BTreeMap<String, String>>,
+}
+
+/// The secrets held by this process.
+pub fn session_secrets() -> &'static SessionSecrets {
+    static SECRETS: OnceLock<SessionSecrets> = OnceLock::new();
+    SECRETS.get_or_init(SessionSecrets::default)
+}
+
+/// `ZEROCLAW_SECRET_<ITEM>_<FIELD>`, upper-cased with anything else as `_`.
+pub fn env_name(item: &str, field: &str) -> String {
+    let item = item.strip_prefix("op://").unwrap_or(item);
+    let mut name = String::from(ENV_PREFIX);
+    for c in format!("{item}_{field}").chars() {
+        let c = if c.is_ascii_alphanumeric() {
+            c.to_ascii_uppercase()
+        } else {
+            '_'
+        };
+        if !(c == '_' && name.ends_with('_')) {
+            name.push(c);
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+impl SessionSecrets {
+    fn values(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, String>> {
+        self.values
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Keep `value` under `name`, replacing any earlier value.
+    pub fn hold(&self, name: &str, value: String) {
+        self.values().insert(name.to_string(), value);
+    }
+
+    /// Variable names and values, for a child process environment.
+    pub fn env(&self) -> Vec<(String, String)> {
+        self.values()
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Variable names only.
+    pub fn names(&self) -> Vec<String> {
+        self.values().keys().cloned().collect()
+    }
+
+    /// `text` with every held value replaced by `$NAME`.
+    pub fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (name, value) in self.values().iter() {
+            if !value.is_empty() && text.contains(value.as_str()) {
+                text = text.replace(value.as_str(), &format!("${name}"));
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_names_are_shell_safe() {
+        assert_eq!(
+            env_name("Staging DB", "password"),
+            "ZEROCLAW_SECRET_STAGING_DB_PASSWORD"
+        );
+        assert_eq!(
+            env_name("op://Ops/Deploy/token", "password"),
+            "ZEROCLAW_SECRET_OPS_DEPLOY_TOKEN_PASSWORD"
+        );
+    }
+
+    #[test]
+    fn held_values_are_redacted() {
+        let secrets = SessionSecrets::default();
+        secrets.hold("ZEROCLAW_SECRET_X", "hunter2".into());
+        assert_eq!(
+            secrets.redact("login hunter2 ok"),
+            "login $ZEROCLAW_SECRET_X ok"
+        );
+        assert_eq!(secrets.names(), ["ZEROCLAW_SECRET_X"]);
+    }
+}
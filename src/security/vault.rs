@@ -0,0 +1,729 @@
+//! Secrets vault — named credentials kept out of `config.toml`.
+//!
+//! `zeroclaw secrets set openai` stores a value in the OS keychain (macOS
+//! Keychain through `security`, the Linux Secret Service through
+//! `secret-tool`) or, where neither is available, in
+//! `~/.zeroclaw/secrets.json`, encrypted under a key derived from a
+//! passphrase (PBKDF2-HMAC-SHA256), so a copy of the file alone reveals
+//! nothing. The passphrase comes from `ZEROCLAW_VAULT_PASSPHRASE` or a
+//! terminal prompt. Any string in config.toml of the form `secret:<name>` is
+//! replaced by the stored value when the config loads, so providers and
+//! channels get the real credential without knowing where it came from.
+//! Saving the config writes the reference back, never the value.
+
+use crate::config::Config;
+use crate::SecretCommands;
+use anyhow::{Context, Result};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use ring::pbkdf2;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{IsTerminal, Write};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+/// Prefix of a config value that names a vault entry.
+pub const REFERENCE_PREFIX: &str = "secret:";
+/// Where the file backend's passphrase is read from when set.
+pub const PASSPHRASE_ENV: &str = "ZEROCLAW_VAULT_PASSPHRASE";
+/// Keychain service / Secret Service attribute every entry is filed under.
+const SERVICE: &str = "zeroclaw";
+const FILE_NAME: &str = "secrets.json";
+/// PBKDF2 rounds for new vault files (OWASP's figure for HMAC-SHA256).
+const KDF_ROUNDS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Where secrets are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultBackend {
+    /// macOS Keychain (`security` CLI)
+    Keychain,
+    /// freedesktop Secret Service: GNOME Keyring, `KWallet` (`secret-tool` CLI)
+    SecretService,
+    /// `~/.zeroclaw/secrets.json`, each value ChaCha20-Poly1305 encrypted
+    /// under a passphrase-derived key
+    File,
+}
+
+impl VaultBackend {
+    /// `auto` picks the OS keychain when its CLI is usable, the file otherwise.
+    pub fn from_config(raw: &str) -> Result<Self> {
+        match raw.trim() {
+            "" | "auto" => Ok(Self::detect()),
+            "keychain" => Ok(Self::Keychain),
+            "secret-service" | "secret_service" => Ok(Self::SecretService),
+            "file" => Ok(Self::File),
+            other => anyhow::bail!(
+                "Unknown secrets.backend '{other}' (expected auto, keychain, secret-service or file)"
+            ),
+        }
+    }
+
+    fn detect() -> Self {
+        if cfg!(target_os = "macos") && on_path("security") {
+            Self::Keychain
+        } else if cfg!(target_os = "linux")
+            && !crate::util::is_termux()
+            && on_path("secret-tool")
+            && std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some_and(|v| !v.is_empty())
+        {
+            Self::SecretService
+        } else {
+            Self::File
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Keychain => "macOS Keychain",
+            Self::SecretService => "Secret Service",
+            Self::File => "passphrase-encrypted file",
+        }
+    }
+}
+
+fn on_path(binary: &str) -> bool {
+    crate::platform::find_executable(binary).is_some()
+}
+
+/// `secrets.json`: how the key is derived, and the sealed values.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VaultFile {
+    /// Hex PBKDF2 salt; set when the first secret is stored.
+    #[serde(default)]
+    salt: String,
+    #[serde(default)]
+    rounds: u32,
+    /// Hex `nonce ‖ ciphertext ‖ tag` by name.
+    #[serde(default)]
+    entries: BTreeMap<String, String>,
+}
+
+/// A derived file key and the salt it was derived for.
+type FileKey = (String, [u8; 32]);
+
+/// Named secrets in one backend.
+#[derive(Debug, Clone)]
+pub struct Vault {
+    backend: VaultBackend,
+    zeroclaw_dir: PathBuf,
+    passphrase: Option<String>,
+    /// Cached so a vault prompts for its passphrase once.
+    file_key: Arc<Mutex<Option<FileKey>>>,
+}
+
+impl Vault {
+    pub fn new(zeroclaw_dir: &Path, backend: VaultBackend) -> Self {
+        Self {
+            backend,
+            zeroclaw_dir: zeroclaw_dir.to_path_buf(),
+            passphrase: None,
+            file_key: Arc::default(),
+        }
+    }
+
+    /// Use `passphrase` for the file backend instead of asking for one.
+    #[must_use]
+    pub fn with_passphrase(mut self, passphrase: &str) -> Self {
+        self.passphrase = Some(passphrase.to_string());
+        self
+    }
+
+    /// The vault `config` asks for, next to its config file.
+    pub fn for_config(config: &Config) -> Result<Self> {
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .context("Config path has no parent directory")?;
+        Ok(Self::new(
+            zeroclaw_dir,
+            VaultBackend::from_config(&config.secrets.backend)?,
+        ))
+    }
+
+    pub fn backend(&self) -> VaultBackend {
+        self.backend
+    }
+
+    pub fn set(&self, name: &str, value: &str) -> Result<()> {
+        validate_name(name)?;
+        match self.backend {
+            VaultBackend::Keychain => {
+                // An argument would show up in `ps`; `security -i` reads the
+                // command, password included, from stdin instead.
+                anyhow::ensure!(
+                    !value.contains(['\n', '\r']),
+                    "The Keychain backend can't store a multi-line secret"
+                );
+                let command = format!(
+                    "add-generic-password -U -s {SERVICE} -a {name} -w {}\n",
+                    keychain_quote(value)
+                );
+                pipe(Command::new("security").arg("-i"), &command)
+            }
+            VaultBackend::SecretService => pipe(
+                Command::new("secret-tool")
+                    .args(["store", "--label"])
+                    .arg(format!("ZeroClaw: {name}"))
+                    .args(["service", SERVICE, "account", name]),
+                value,
+            ),
+            VaultBackend::File => {
+                let mut file = self.read_file()?;
+                let key = self.key_for(&mut file)?;
+                file.entries.insert(name.to_string(), seal(&key, value)?);
+                self.write_file(&file)
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        validate_name(name)?;
+        match self.backend {
+            VaultBackend::Keychain => lookup(Command::new("security").args([
+                "find-generic-password",
+                "-s",
+                SERVICE,
+                "-a",
+                name,
+                "-w",
+            ])),
+            VaultBackend::SecretService => lookup(
+                Command::new("secret-tool").args(["lookup", "service", SERVICE, "account", name]),
+            ),
+            VaultBackend::File => {
+                let mut file = self.read_file()?;
+                let Some(sealed) = file.entries.get(name).cloned() else {
+                    return Ok(None);
+                };
+                let key = self.key_for(&mut file)?;
+                open(&key, &sealed).map(Some)
+            }
+        }
+    }
+
+    /// Remove `name`; `false` if there was nothing to remove.
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        if self.get(name)?.is_none() {
+            return Ok(false);
+        }
+        match self.backend {
+            VaultBackend::Keychain => run(Command::new("security").args([
+                "delete-generic-password",
+                "-s",
+                SERVICE,
+                "-a",
+                name,
+            ]))
+            .map(drop)?,
+            VaultBackend::SecretService => {
+                run(Command::new("secret-tool")
+                    .args(["clear", "service", SERVICE, "account", name]))?;
+            }
+            VaultBackend::File => {
+                let mut file = self.read_file()?;
+                file.entries.remove(name);
+                self.write_file(&file)?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Names of the stored secrets, sorted.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let mut names = match self.backend {
+            VaultBackend::Keychain => {
+                parse_keychain_dump(&run(Command::new("security").arg("dump-keychain"))?)
+            }
+            VaultBackend::SecretService => parse_secret_tool_search(&run(Command::new(
+                "secret-tool",
+            )
+            .args(["search", "--all", "service", SERVICE]))?),
+            VaultBackend::File => self.read_file()?.entries.into_keys().collect(),
+        };
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn file_path(&self) -> PathBuf {
+        self.zeroclaw_dir.join(FILE_NAME)
+    }
+
+    /// The key `file` is sealed with; a new file gets a fresh salt. The
+    /// passphrase is checked against a stored entry before it is used.
+    fn key_for(&self, file: &mut VaultFile) -> Result<[u8; 32]> {
+        let creating = file.salt.is_empty();
+        if creating {
+            let mut salt = [0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            file.salt = hex::encode(salt);
+            file.rounds = KDF_ROUNDS;
+        }
+        let mut cached = self
+            .file_key
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some((salt, key)) = cached.as_ref() {
+            if *salt == file.salt {
+                return Ok(*key);
+            }
+        }
+        let salt = hex::decode(&file.salt).context("secrets.json has a malformed salt")?;
+        let passphrase = self.passphrase(creating)?;
+        let key = derive_key(
+            &passphrase,
+            &salt,
+            NonZeroU32::new(file.rounds).unwrap_or(NonZeroU32::MIN),
+        );
+        if let Some(sealed) = file.entries.values().next() {
+            open(&key, sealed).map_err(|_| {
+                anyhow::anyhow!("Wrong vault passphrase (or secrets.json was modified)")
+            })?;
+        }
+        *cached = Some((file.salt.clone(), key));
+        Ok(key)
+    }
+
+    fn passphrase(&self, creating: bool) -> Result<String> {
+        if let Some(passphrase) = &self.passphrase {
+            return Ok(passphrase.clone());
+        }
+        if let Some(passphrase) = std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty()) {
+            return Ok(passphrase);
+        }
+        anyhow::ensure!(
+            std::io::stdin().is_terminal(),
+            "{} is encrypted with a passphrase; set {PASSPHRASE_ENV}",
+            self.file_path().display()
+        );
+        let prompt = dialoguer::Password::new().with_prompt("Vault passphrase");
+        let prompt = if creating {
+            prompt.with_confirmation("Repeat it", "Passphrases didn't match")
+        } else {
+            prompt
+        };
+        Ok(prompt.interact()?)
+    }
+
+    fn read_file(&self) -> Result<VaultFile> {
+        let path = self.file_path();
+        if !path.exists() {
+            return Ok(VaultFile::default());
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("{} is corrupt", path.display()))
+    }
+
+    fn write_file(&self, file: &VaultFile) -> Result<()> {
+        let path = self.file_path();
+        std::fs::create_dir_all(&self.zeroclaw_dir)?;
+        std::fs::write(&path, serde_json::to_string_pretty(file)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        crate::platform::restrict_to_owner(&path)?;
+        Ok(())
+    }
+}
+
+/// PBKDF2-HMAC-SHA256 (RFC 8018), 32 bytes.
+fn derive_key(passphrase: &str, salt: &[u8], rounds: NonZeroU32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        rounds,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+fn seal(key: &[u8; 32], value: &str) -> Result<String> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, value.as_bytes())
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
+    Ok(hex::encode([nonce.as_slice(), &ciphertext].concat()))
+}
+
+fn open(key: &[u8; 32], sealed: &str) -> Result<String> {
+    let blob = hex::decode(sealed).context("Malformed vault entry")?;
+    anyhow::ensure!(blob.len() > NONCE_LEN, "Malformed vault entry");
+    let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Vault entry failed to decrypt"))?;
+    String::from_utf8(plaintext).context("Vault entry is not UTF-8")
+}
+
+/// A word for `security -i`, which splits its input line like a shell.
+fn keychain_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    anyhow::ensure!(
+        valid,
+        "Secret names may only use letters, digits, '_', '-' and '.': {name:?}"
+    );
+    Ok(())
+}
+
+/// Stdout of a successful run.
+fn run(command: &mut Command) -> Result<String> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {program}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Run `command` with `input` on stdin, so it never appears in an argument.
+fn pipe(command: &mut Command, input: &str) -> Result<()> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Both CLIs exit non-zero with nothing on stdout when the entry is missing.
+fn lookup(command: &mut Command) -> Result<Option<String>> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {program}"))?;
+    let value = String::from_utf8_lossy(&output.stdout)
+        .trim_end_matches(['\r', '\n'])
+        .to_string();
+    Ok((output.status.success() && !value.is_empty()).then_some(value))
+}
+
+/// Account names of `zeroclaw` entries in `security dump-keychain` output.
+fn parse_keychain_dump(dump: &str) -> Vec<String> {
+    let quoted = |line: &str| {
+        line.split_once("=\"")
+            .map(|(_, rest)| rest.trim_end().trim_end_matches('"').to_string())
+    };
+    let mut names = Vec::new();
+    for item in dump.split("keychain: ") {
+        let mut account = None;
+        let mut ours = false;
+        for line in item.lines().map(str::trim) {
+            if line.starts_with("\"acct\"") {
+                account = quoted(line);
+            } else if line.starts_with("\"svce\"") {
+                ours = quoted(line).as_deref() == Some(SERVICE);
+            }
+        }
+        if let (true, Some(account)) = (ours, account) {
+            names.push(account);
+        }
+    }
+    names
+}
+
+/// `attribute.account = <name>` lines of `secret-tool search` output.
+fn parse_secret_tool_search(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("attribute.account = "))
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// A `secret:` reference in config.toml and the value it stood for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretRef {
+    path: Vec<Segment>,
+    reference: String,
+    resolved: String,
+}
+
+/// Replace every `secret:<name>` string in `value` with the stored secret.
+/// Missing secrets become empty strings (with a warning) so the commands that
+/// fix them — `zeroclaw secrets set` among them — still start.
+pub fn resolve_references(value: &mut toml::Value, vault: &Vault) -> Vec<SecretRef> {
+    let mut refs = Vec::new();
+    resolve_at(value, &mut Vec::new(), vault, &mut refs);
+    refs
+}
+
+fn resolve_at(
+    value: &mut toml::Value,
+    path: &mut Vec<Segment>,
+    vault: &Vault,
+    refs: &mut Vec<SecretRef>,
+) {
+    match value {
+        toml::Value::String(s) => {
+            let Some(name) = s.strip_prefix(REFERENCE_PREFIX).map(str::trim) else {
+                return;
+            };
+            let resolved = match vault.get(name) {
+                Ok(Some(secret)) => secret,
+                Ok(None) => {
+                    tracing::warn!(
+                        "Secret '{name}' is referenced in config.toml but not stored; \
+                         run `zeroclaw secrets set {name}`"
+                    );
+                    String::new()
+                }
+                Err(e) => {
+                    tracing::warn!("Could not read secret '{name}': {e}");
+                    String::new()
+                }
+            };
+            refs.push(SecretRef {
+                path: path.clone(),
+                reference: std::mem::replace(s, resolved.clone()),
+                resolved,
+            });
+        }
+        toml::Value::Table(table) => {
+            for (key, child) in table.iter_mut() {
+                path.push(Segment::Key(key.clone()));
+                resolve_at(child, path, vault, refs);
+                path.pop();
+            }
+        }
+        toml::Value::Array(items) => {
+            for (i, child) in items.iter_mut().enumerate() {
+                path.push(Segment::Index(i));
+                resolve_at(child, path, vault, refs);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Put the references back where the resolved values are still unchanged.
+pub fn restore_references(value: &mut toml::Value, refs: &[SecretRef]) {
+    for secret in refs {
+        let mut target = Some(&mut *value);
+        for segment in &secret.path {
+            target = match (target, segment) {
+                (Some(toml::Value::Table(table)), Segment::Key(key)) => table.get_mut(key),
+                (Some(toml::Value::Array(items)), Segment::Index(i)) => items.get_mut(*i),
+                _ => None,
+            };
+        }
+        if let Some(toml::Value::String(s)) = target {
+            if *s == secret.resolved {
+                s.clone_from(&secret.reference);
+            }
+        }
+    }
+}
+
+fn read_value(name: &str) -> Result<String> {
+    if std::io::stdin().is_terminal() {
+        return Ok(dialoguer::Password::new()
+            .with_prompt(format!("Value for '{name}'"))
+            .interact()?);
+    }
+    let mut value = String::new();
+    std::io::stdin().read_line(&mut value)?;
+    Ok(value.trim_end_matches(['\r', '\n']).to_string())
+}
+
+pub fn handle_command(command: SecretCommands, config: &Config) -> Result<()> {
+    let vault = Vault::for_config(config)?;
+    let backend = vault.backend().label();
+    match command {
+        SecretCommands::Set { name, value } => {
+            let value = match value {
+                Some(value) => value,
+                None => read_value(&name)?,
+            };
+            anyhow::ensure!(!value.is_empty(), "Refusing to store an empty secret");
+            vault.set(&name, &value)?;
+            println!("🔐 Stored '{name}' in the {backend}.");
+            println!("   Use it in config.toml as \"{REFERENCE_PREFIX}{name}\"");
+            Ok(())
+        }
+        SecretCommands::Get { name } => match vault.get(&name)? {
+            Some(value) => {
+                println!("{value}");
+                Ok(())
+            }
+            None => anyhow::bail!("No secret named '{name}' in the {backend}"),
+        },
+        SecretCommands::List => {
+            let names = vault.list()?;
+            if names.is_empty() {
+                println!("No secrets in the {backend} yet.");
+                println!("\nUsage:");
+                println!("  zeroclaw secrets set openai");
+                println!("  api_key = \"{REFERENCE_PREFIX}openai\"   # in config.toml");
+                return Ok(());
+            }
+            println!("🔐 Secrets in the {backend} ({}):", names.len());
+            for name in names {
+                println!("- {name}");
+            }
+            Ok(())
+        }
+        SecretCommands::Delete { name } => {
+            if !vault.delete(&name)? {
+                anyhow::bail!("No secret named '{name}' in the {backend}");
+            }
+            println!("🗑️  Deleted '{name}' from the {backend}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn file_vault(dir: &Path) -> Vault {
+        Vault::new(dir, VaultBackend::File).with_passphrase("correct horse")
+    }
+
+    #[test]
+    fn file_vault_round_trips_encrypted() {
+        let tmp = TempDir::new().unwrap();
+        let vault = file_vault(tmp.path());
+        vault.set("openai", "sk-live-123").unwrap();
+        vault.set("telegram", "bot:456").unwrap();
+
+        assert_eq!(vault.get("openai").unwrap().as_deref(), Some("sk-live-123"));
+        assert_eq!(vault.list().unwrap(), ["openai", "telegram"]);
+        let on_disk = std::fs::read_to_string(tmp.path().join(FILE_NAME)).unwrap();
+        assert!(!on_disk.contains("sk-live-123"));
+
+        assert!(vault.delete("openai").unwrap());
+        assert!(!vault.delete("openai").unwrap());
+        assert_eq!(vault.get("openai").unwrap(), None);
+        assert!(vault.set("two words", "x").is_err());
+    }
+
+    #[test]
+    fn file_vault_needs_the_passphrase() {
+        let tmp = TempDir::new().unwrap();
+        file_vault(tmp.path()).set("openai", "sk-live-123").unwrap();
+        // Nothing next to the file decrypts it.
+        assert!(!tmp.path().join(".secret_key").exists());
+
+        let wrong = Vault::new(tmp.path(), VaultBackend::File).with_passphrase("battery staple");
+        let err = wrong.get("openai").unwrap_err();
+        assert!(err.to_string().contains("Wrong vault passphrase"));
+        assert!(wrong.set("telegram", "bot:456").is_err());
+        assert_eq!(
+            file_vault(tmp.path()).get("openai").unwrap().as_deref(),
+            Some("sk-live-123")
+        );
+    }
+
+    #[test]
+    fn keys_derive_per_rfc_8018_and_keychain_input_is_quoted() {
+        // PBKDF2-HMAC-SHA256 test vectors (RFC 7914, section 11)
+        assert_eq!(
+            hex::encode(derive_key("password", b"salt", NonZeroU32::MIN)),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            hex::encode(derive_key("password", b"salt", NonZeroU32::new(2).unwrap())),
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+        );
+        assert_eq!(keychain_quote(r#"a"b\c d"#), r#""a\"b\\c d""#);
+    }
+
+    #[test]
+    fn references_resolve_on_load_and_come_back_on_save() {
+        let tmp = TempDir::new().unwrap();
+        let vault = file_vault(tmp.path());
+        vault.set("openai", "sk-live-123").unwrap();
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            api_key = "secret:openai"
+            [channels_config.telegram]
+            bot_token = "secret:missing"
+            allowed_users = ["alice"]
+            "#,
+        )
+        .unwrap();
+
+        let refs = resolve_references(&mut value, &vault);
+        assert_eq!(refs.len(), 2);
+        assert_eq!(value["api_key"].as_str(), Some("sk-live-123"));
+        assert_eq!(
+            value["channels_config"]["telegram"]["bot_token"].as_str(),
+            Some("")
+        );
+
+        value["channels_config"]["telegram"]["bot_token"] = "typed-in".into();
+        restore_references(&mut value, &refs);
+        assert_eq!(value["api_key"].as_str(), Some("secret:openai"));
+        assert_eq!(
+            value["channels_config"]["telegram"]["bot_token"].as_str(),
+            Some("typed-in")
+        );
+    }
+
+    #[test]
+    fn os_listings_are_parsed() {
+        let dump = r#"keychain: "/Users/me/Library/Keychains/login.keychain-db"
+class: "genp"
+attributes:
+    "acct"<blob>="openai"
+    "svce"<blob>="zeroclaw"
+keychain: "/Users/me/Library/Keychains/login.keychain-db"
+class: "genp"
+attributes:
+    "acct"<blob>="me@example.com"
+    "svce"<blob>="Safari"
+"#;
+        assert_eq!(parse_keychain_dump(dump), ["openai"]);
+        let search = "[/org/freedesktop/secrets/collection/login/7]\n\
+                      label = ZeroClaw: github\n\
+                      attribute.service = zeroclaw\n\
+                      attribute.account = github\n";
+        assert_eq!(parse_secret_tool_search(search), ["github"]);
+        assert_eq!(
+            VaultBackend::from_config("file").unwrap(),
+            VaultBackend::File
+        );
+        assert!(VaultBackend::from_config("vault").is_err());
+    }
+}
@@ -5,8 +5,16 @@ use std::path::PathBuf;
 use std::process::Command;
 
 const SERVICE_LABEL: &str = "com.zeroclaw.daemon";
+/// Task Scheduler name of the daemon on Windows.
+const WINDOWS_TASK: &str = "ZeroClaw";
 
 pub fn handle_command(command: &crate::ServiceCommands, config: &Config) -> Result<()> {
+    if crate::util::is_termux() {
+        return termux(command);
+    }
+    if cfg!(windows) {
+        return windows(command, config);
+    }
     match command {
         crate::ServiceCommands::Install => install(config),
         crate::ServiceCommands::Start => start(config),
@@ -22,7 +30,7 @@ fn install(config: &Config) -> Result<()> {
     } else if cfg!(target_os = "linux") {
         install_linux(config)
     } else {
-        anyhow::bail!("Service management is supported on macOS and Linux only");
+        anyhow::bail!("Service management is supported on macOS, Linux and Windows only");
     }
 }
 
@@ -40,7 +48,7 @@ fn start(config: &Config) -> Result<()> {
         Ok(())
     } else {
         let _ = config;
-        anyhow::bail!("Service management is supported on macOS and Linux only")
+        anyhow::bail!("Service management is supported on macOS, Linux and Windows only")
     }
 }
 
@@ -62,7 +70,7 @@ fn stop(config: &Config) -> Result<()> {
         Ok(())
     } else {
         let _ = config;
-        anyhow::bail!("Service management is supported on macOS and Linux only")
+        anyhow::bail!("Service management is supported on macOS, Linux and Windows only")
     }
 }
 
@@ -94,7 +102,7 @@ fn status(config: &Config) -> Result<()> {
         return Ok(());
     }
 
-    anyhow::bail!("Service management is supported on macOS and Linux only")
+    anyhow::bail!("Service management is supported on macOS, Linux and Windows only")
 }
 
 fn uninstall(config: &Config) -> Result<()> {
@@ -121,7 +129,7 @@ fn uninstall(config: &Config) -> Result<()> {
         return Ok(());
     }
 
-    anyhow::bail!("Service management is supported on macOS and Linux only")
+    anyhow::bail!("Service management is supported on macOS, Linux and Windows only")
 }
 
 fn install_macos(config: &Config) -> Result<()> {
@@ -131,11 +139,7 @@ fn install_macos(config: &Config) -> Result<()> {
     }
 
     let exe = std::env::current_exe().context("Failed to resolve current executable")?;
-    let logs_dir = config
-        .config_path
-        .parent()
-        .map_or_else(|| PathBuf::from("."), PathBuf::from)
-        .join("logs");
+    let logs_dir = logs_dir(config);
     fs::create_dir_all(&logs_dir)?;
 
     let stdout = logs_dir.join("daemon.stdout.log");
@@ -196,6 +200,185 @@ fn install_linux(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Termux has no systemd; the daemon runs under termux-services (runit).
+fn termux(command: &crate::ServiceCommands) -> Result<()> {
+    let prefix = crate::util::termux_prefix();
+    let dir = prefix.join("var").join("service").join("zeroclaw");
+    match command {
+        crate::ServiceCommands::Install => install_termux(&prefix, &dir),
+        crate::ServiceCommands::Start => {
+            run_checked(Command::new("sv").args(["up", "zeroclaw"]))?;
+            println!("✅ Service started");
+            Ok(())
+        }
+        crate::ServiceCommands::Stop => {
+            let _ = run_checked(Command::new("sv").args(["down", "zeroclaw"]));
+            println!("✅ Service stopped");
+            Ok(())
+        }
+        crate::ServiceCommands::Status => {
+            let out = run_capture(Command::new("sv").args(["status", "zeroclaw"]))
+                .unwrap_or_else(|_| "unknown".into());
+            println!("Service state: {}", out.trim());
+            println!("Unit: {}", dir.display());
+            Ok(())
+        }
+        crate::ServiceCommands::Uninstall => {
+            let _ = run_checked(Command::new("sv").args(["down", "zeroclaw"]));
+            if dir.exists() {
+                fs::remove_dir_all(&dir)
+                    .with_context(|| format!("Failed to remove {}", dir.display()))?;
+            }
+            if let Some(boot) = termux_boot_file() {
+                let _ = fs::remove_file(boot);
+            }
+            println!("✅ Service uninstalled ({})", dir.display());
+            Ok(())
+        }
+    }
+}
+
+/// Windows: a Task Scheduler task that starts the daemon at logon, as the
+/// user and without admin rights. The daemon doesn't speak the service
+/// control protocol, so it can't be a Service Control Manager service.
+fn windows(command: &crate::ServiceCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ServiceCommands::Install => {
+            let exe = std::env::current_exe().context("Failed to resolve current executable")?;
+            let logs_dir = logs_dir(config);
+            fs::create_dir_all(&logs_dir)?;
+            let task = windows_task_command(&exe, &logs_dir.join("daemon.log"));
+            run_checked(Command::new("schtasks").args([
+                "/Create",
+                "/TN",
+                WINDOWS_TASK,
+                "/SC",
+                "ONLOGON",
+                "/RL",
+                "LIMITED",
+                "/F",
+                "/TR",
+                &task,
+            ]))?;
+            println!("✅ Installed scheduled task: {WINDOWS_TASK} (runs at logon)");
+            println!("   Start with: zeroclaw service start");
+            Ok(())
+        }
+        crate::ServiceCommands::Start => {
+            run_checked(Command::new("schtasks").args(["/Run", "/TN", WINDOWS_TASK]))?;
+            println!("✅ Service started");
+            Ok(())
+        }
+        crate::ServiceCommands::Stop => {
+            let _ = run_checked(Command::new("schtasks").args(["/End", "/TN", WINDOWS_TASK]));
+            println!("✅ Service stopped");
+            Ok(())
+        }
+        crate::ServiceCommands::Status => {
+            let out = run_capture(Command::new("schtasks").args([
+                "/Query",
+                "/TN",
+                WINDOWS_TASK,
+                "/FO",
+                "LIST",
+            ]))
+            .unwrap_or_default();
+            let state = out
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("Status:"))
+                .map_or("not installed", str::trim);
+            println!("Service state: {state}");
+            println!("Task: {WINDOWS_TASK}");
+            Ok(())
+        }
+        crate::ServiceCommands::Uninstall => {
+            let _ = run_checked(Command::new("schtasks").args(["/End", "/TN", WINDOWS_TASK]));
+            run_checked(Command::new("schtasks").args(["/Delete", "/TN", WINDOWS_TASK, "/F"]))?;
+            println!("✅ Service uninstalled ({WINDOWS_TASK})");
+            Ok(())
+        }
+    }
+}
+
+/// The task's command line: the daemon in a hidden `PowerShell` window,
+/// appending its output to `log`.
+fn windows_task_command(exe: &std::path::Path, log: &std::path::Path) -> String {
+    let quote = |p: &std::path::Path| format!("'{}'", p.display().to_string().replace('\'', "''"));
+    format!(
+        "powershell -NoProfile -WindowStyle Hidden -Command \"& {} daemon *>> {}\"",
+        quote(exe),
+        quote(log)
+    )
+}
+
+fn logs_dir(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), PathBuf::from)
+        .join("logs")
+}
+
+fn install_termux(prefix: &std::path::Path, dir: &std::path::Path) -> Result<()> {
+    if !prefix.join("bin").join("sv").exists() {
+        anyhow::bail!(
+            "termux-services is not installed: run `pkg install termux-services` and restart Termux"
+        );
+    }
+    let exe = std::env::current_exe().context("Failed to resolve current executable")?;
+    fs::create_dir_all(dir.join("log"))?;
+    let run = dir.join("run");
+    fs::write(
+        &run,
+        termux_script(prefix, &format!("exec {} daemon 2>&1", exe.display())),
+    )?;
+    let logger = prefix
+        .join("share")
+        .join("termux-services")
+        .join("svlogger");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&run, fs::Permissions::from_mode(0o755))?;
+        if logger.exists() && !dir.join("log").join("run").exists() {
+            std::os::unix::fs::symlink(&logger, dir.join("log").join("run"))?;
+        }
+    }
+    println!("✅ Installed termux-services service: {}", dir.display());
+
+    // Termux:Boot runs ~/.termux/boot/* at boot; loading the profile starts runit.
+    if let Some(boot) = termux_boot_file() {
+        if let Some(parent) = boot.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            &boot,
+            termux_script(prefix, "termux-wake-lock\n. \"$PREFIX/etc/profile\""),
+        )?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&boot, fs::Permissions::from_mode(0o755))?;
+        }
+        println!(
+            "   Starts at boot with the Termux:Boot app: {}",
+            boot.display()
+        );
+    }
+    println!("   Start with: zeroclaw service start");
+    println!("   Keep Android from suspending it: termux-wake-lock");
+    Ok(())
+}
+
+/// A shell script using Termux's `sh`; there is no `/bin/sh` on Android.
+fn termux_script(prefix: &std::path::Path, body: &str) -> String {
+    format!("#!{}/bin/sh\n{body}\n", prefix.display())
+}
+
+fn termux_boot_file() -> Option<PathBuf> {
+    directories::UserDirs::new().map(|u| u.home_dir().join(".termux").join("boot").join("zeroclaw"))
+}
+
 fn macos_service_file() -> Result<PathBuf> {
     let home = directories::UserDirs::new()
         .map(|u| u.home_dir().to_path_buf())
@@ -248,6 +431,30 @@ fn xml_escape(raw: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn termux_scripts_use_the_termux_shell() {
+        let script = termux_script(
+            std::path::Path::new("/data/data/com.termux/files/usr"),
+            "exec zeroclaw daemon 2>&1",
+        );
+        assert_eq!(
+            script,
+            "#!/data/data/com.termux/files/usr/bin/sh\nexec zeroclaw daemon 2>&1\n"
+        );
+    }
+
+    #[test]
+    fn windows_task_runs_the_daemon_hidden_with_a_log() {
+        let command = windows_task_command(
+            std::path::Path::new(r"C:\Users\O'Neil\zeroclaw.exe"),
+            std::path::Path::new(r"C:\Users\O'Neil\.zeroclaw\logs\daemon.log"),
+        );
+        assert_eq!(
+            command,
+            r#"powershell -NoProfile -WindowStyle Hidden -Command "& 'C:\Users\O''Neil\zeroclaw.exe' daemon *>> 'C:\Users\O''Neil\.zeroclaw\logs\daemon.log'""#
+        );
+    }
+
     #[test]
     fn xml_escape_escapes_reserved_chars() {
         let escaped = xml_escape("<&>\"' and text");
@@ -255,6 +462,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn run_capture_reads_stdout() {
         let out = run_capture(Command::new("sh").args(["-lc", "echo hello"]))
             .expect("stdout capture should succeed");
@@ -262,6 +470,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn run_capture_falls_back_to_stderr() {
         let out = run_capture(Command::new("sh").args(["-lc", "echo warn 1>&2"]))
             .expect("stderr capture should succeed");
@@ -269,6 +478,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn run_checked_errors_on_non_zero_status() {
         let err = run_checked(Command::new("sh").args(["-lc", "exit 17"]))
             .expect_err("non-zero exit should error");
@@ -276,6 +486,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn linux_service_file_has_expected_suffix() {
         let file = linux_service_file(&Config::default()).unwrap();
         let path = file.to_string_lossy();
@@ -91,9 +91,9 @@ impl Evaluator {
     }
 
     pub fn evaluate(&self, candidate: ScoutResult) -> EvalResult {
-        let compatibility = self.score_compatibility(&candidate);
-        let quality = self.score_quality(&candidate);
-        let security = self.score_security(&candidate);
+        let compatibility = Self::score_compatibility(&candidate);
+        let quality = Self::score_quality(&candidate);
+        let security = Self::score_security(&candidate);
 
         let scores = Scores {
             compatibility,
@@ -121,7 +121,7 @@ impl Evaluator {
     // -- Dimension scorers --------------------------------------------------
 
     /// Compatibility: favour Rust repos; penalise unknown languages.
-    fn score_compatibility(&self, c: &ScoutResult) -> f64 {
+    fn score_compatibility(c: &ScoutResult) -> f64 {
         match c.language.as_deref() {
             Some("Rust") => 1.0,
             Some("Python" | "TypeScript" | "JavaScript") => 0.6,
@@ -131,14 +131,15 @@ impl Evaluator {
     }
 
     /// Quality: based on star count (log scale, capped at 1.0).
-    fn score_quality(&self, c: &ScoutResult) -> f64 {
+    fn score_quality(c: &ScoutResult) -> f64 {
         // log2(stars + 1) / 10, capped at 1.0
-        let raw = ((c.stars as f64) + 1.0).log2() / 10.0;
+        let stars = f64::from(u32::try_from(c.stars).unwrap_or(u32::MAX));
+        let raw = (stars + 1.0).log2() / 10.0;
         raw.min(1.0)
     }
 
     /// Security: license presence + bad-pattern check.
-    fn score_security(&self, c: &ScoutResult) -> f64 {
+    fn score_security(c: &ScoutResult) -> f64 {
         let mut score: f64 = 0.5;
 
         // License bonus
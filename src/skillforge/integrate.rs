@@ -34,8 +34,8 @@ impl Integrator {
         let toml_path = skill_dir.join("SKILL.toml");
         let md_path = skill_dir.join("SKILL.md");
 
-        let toml_content = self.generate_toml(candidate);
-        let md_content = self.generate_md(candidate);
+        let toml_content = Self::generate_toml(candidate);
+        let md_content = Self::generate_md(candidate);
 
         fs::write(&toml_path, &toml_content)
             .with_context(|| format!("Failed to write {}", toml_path.display()))?;
@@ -53,12 +53,11 @@ impl Integrator {
 
     // -- Generators ---------------------------------------------------------
 
-    fn generate_toml(&self, c: &ScoutResult) -> String {
+    fn generate_toml(c: &ScoutResult) -> String {
         let lang = c.language.as_deref().unwrap_or("unknown");
         let updated = c
             .updated_at
-            .map(|d| d.format("%Y-%m-%d").to_string())
-            .unwrap_or_else(|| "unknown".into());
+            .map_or_else(|| "unknown".into(), |d| d.format("%Y-%m-%d").to_string());
 
         format!(
             r#"# Auto-generated by SkillForge on {now}
@@ -93,10 +92,10 @@ forge_timestamp = "{now}"
         )
     }
 
-    fn generate_md(&self, c: &ScoutResult) -> String {
+    fn generate_md(c: &ScoutResult) -> String {
         let lang = c.language.as_deref().unwrap_or("unknown");
         format!(
-            r#"# {name}
+            r"# {name}
 
 > Auto-generated by SkillForge
 
@@ -124,7 +123,7 @@ enabled = true
 
 This manifest was auto-generated from repository metadata.
 Review before enabling in production.
-"#,
+",
             name = c.name,
             url = c.url,
             owner = c.owner,
@@ -162,7 +161,7 @@ fn sanitize_path_component(name: &str) -> Result<String> {
         })
         .collect();
     if sanitized == ".." || sanitized.contains('/') || sanitized.contains('\\') {
-        bail!("Skill name '{}' is unsafe as a path component", name);
+        bail!("Skill name '{name}' is unsafe as a path component");
     }
     Ok(sanitized)
 }
@@ -6,6 +6,9 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, SystemTime};
 
+pub mod trust;
+pub mod wasm;
+
 const OPEN_SKILLS_REPO_URL: &str = "https://github.com/besoeasy/open-skills";
 const OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
 const OPEN_SKILLS_SYNC_INTERVAL_SECS: u64 = 60 * 60 * 24 * 7;
@@ -26,6 +29,10 @@ pub struct Skill {
     pub tools: Vec<SkillTool>,
     #[serde(default)]
     pub prompts: Vec<String>,
+    /// What the skill's tools may do once trusted: "shell", "network",
+    /// "workspace"
+    #[serde(default)]
+    pub permissions: Vec<String>,
     #[serde(skip)]
     pub location: Option<PathBuf>,
 }
@@ -35,12 +42,30 @@ pub struct Skill {
 pub struct SkillTool {
     pub name: String,
     pub description: String,
-    /// "shell", "http", "script"
+    /// "shell", "http", "script", "wasm"
     pub kind: String,
-    /// The command/URL/script to execute
+    /// The command/URL/script/module to execute
     pub command: String,
     #[serde(default)]
     pub args: HashMap<String, String>,
+    /// JSON schema of the tool's arguments; derived from `args` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+}
+
+/// Tool kinds a skill can declare.
+pub const TOOL_KINDS: &[&str] = &["shell", "http", "script", "wasm"];
+
+impl SkillTool {
+    /// The manifest permission this tool's kind needs. Wasm modules run
+    /// sandboxed and need none; "workspace" only widens what they see.
+    pub fn permission(&self) -> Option<&'static str> {
+        match self.kind.as_str() {
+            "shell" | "script" => Some("shell"),
+            "http" => Some("network"),
+            _ => None,
+        }
+    }
 }
 
 /// Skill manifest parsed from SKILL.toml
@@ -63,6 +88,8 @@ struct SkillMeta {
     author: Option<String>,
     #[serde(default)]
     tags: Vec<String>,
+    #[serde(default)]
+    permissions: Vec<String>,
 }
 
 fn default_version() -> String {
@@ -81,7 +108,23 @@ pub fn load_skills(workspace_dir: &Path) -> Vec<Skill> {
     skills
 }
 
-fn load_workspace_skills(workspace_dir: &Path) -> Vec<Skill> {
+/// Like [`load_skills`], but never waits on the network: the local
+/// open-skills copy is used as it is, and a due clone or pull runs as a
+/// detached `git` process whose result shows up on the next run. Used on the
+/// one-shot CLI path, where a sync would dominate startup.
+pub fn load_skills_cached(workspace_dir: &Path) -> Vec<Skill> {
+    let mut skills = Vec::new();
+
+    if let Some(open_skills_dir) = open_skills_repo_in_background() {
+        skills.extend(load_open_skills(&open_skills_dir));
+    }
+
+    skills.extend(load_workspace_skills(workspace_dir));
+    skills
+}
+
+/// Skills from `<workspace>/skills` only — no open-skills sync (never touches the network)
+pub(crate) fn load_workspace_skills(workspace_dir: &Path) -> Vec<Skill> {
     let skills_dir = workspace_dir.join("skills");
     load_skills_from_directory(&skills_dir)
 }
@@ -208,6 +251,53 @@ fn ensure_open_skills_repo() -> Option<PathBuf> {
     Some(repo_dir)
 }
 
+/// The local open-skills directory if it exists, kicking off a background
+/// clone or pull when one is due.
+fn open_skills_repo_in_background() -> Option<PathBuf> {
+    if !open_skills_enabled() {
+        return None;
+    }
+
+    let repo_dir = resolve_open_skills_dir()?;
+
+    if !repo_dir.exists() {
+        if let Some(parent) = repo_dir.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        spawn_git(
+            Command::new("git")
+                .args(["clone", "--depth", "1", OPEN_SKILLS_REPO_URL])
+                .arg(&repo_dir),
+        );
+        return None;
+    }
+
+    if repo_dir.join(".git").exists() && should_sync_open_skills(&repo_dir) {
+        // Mark first so concurrent runs do not start a pull each.
+        let _ = mark_open_skills_synced(&repo_dir);
+        spawn_git(Command::new("git").arg("-C").arg(&repo_dir).args([
+            "pull",
+            "--ff-only",
+            "--quiet",
+        ]));
+    }
+
+    Some(repo_dir)
+}
+
+/// Start `git` without waiting for it; it outlives a short-lived process.
+fn spawn_git(command: &mut Command) {
+    use std::process::Stdio;
+    if let Err(err) = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        tracing::warn!("failed to start background open-skills sync: {err}");
+    }
+}
+
 fn clone_open_skills_repo(repo_dir: &Path) -> bool {
     if let Some(parent) = repo_dir.parent() {
         if let Err(err) = std::fs::create_dir_all(parent) {
@@ -300,6 +390,7 @@ fn load_skill_toml(path: &Path) -> Result<Skill> {
         tags: manifest.skill.tags,
         tools: manifest.tools,
         prompts: manifest.prompts,
+        permissions: manifest.skill.permissions,
         location: Some(path.to_path_buf()),
     })
 }
@@ -321,6 +412,7 @@ fn load_skill_md(path: &Path, dir: &Path) -> Result<Skill> {
         tags: Vec::new(),
         tools: Vec::new(),
         prompts: vec![content],
+        permissions: Vec::new(),
         location: Some(path.to_path_buf()),
     })
 }
@@ -341,6 +433,7 @@ fn load_open_skill_md(path: &Path) -> Result<Skill> {
         tags: vec!["open-skills".to_string()],
         tools: Vec::new(),
         prompts: vec![content],
+        permissions: Vec::new(),
         location: Some(path.to_path_buf()),
     })
 }
@@ -413,7 +506,8 @@ pub fn init_skills_dir(workspace_dir: &Path) -> Result<()> {
              description = \"What this skill does\"\n\
              version = \"0.1.0\"\n\
              author = \"your-name\"\n\
-             tags = [\"productivity\", \"automation\"]\n\n\
+             tags = [\"productivity\", \"automation\"]\n\
+             permissions = [\"shell\"]\n\n\
              [[tools]]\n\
              name = \"my_tool\"\n\
              description = \"What this tool does\"\n\
@@ -427,7 +521,10 @@ pub fn init_skills_dir(workspace_dir: &Path) -> Result<()> {
              ```bash\n\
              zeroclaw skills install <github-url>\n\
              zeroclaw skills list\n\
-             ```\n",
+             ```\n\n\
+             New skills are quarantined: their tool calls are logged to\n\
+             `quarantine.jsonl` and simulated until the skill is trusted\n\
+             (`zeroclaw skills trust <name>`).\n",
         )?;
     }
 
@@ -451,9 +548,23 @@ fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Name of the skill in `dir`, as its manifest declares it.
+fn skill_name_in(dir: &Path) -> Option<String> {
+    let manifest = dir.join("SKILL.toml");
+    if manifest.exists() {
+        load_skill_toml(&manifest).ok().map(|skill| skill.name)
+    } else {
+        dir.file_name()
+            .and_then(|n| n.to_str())
+            .map(ToString::to_string)
+    }
+}
+
 /// Handle the `skills` CLI command
 #[allow(clippy::too_many_lines)]
-pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Result<()> {
+pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Config) -> Result<()> {
+    let workspace_dir = config.workspace_dir.as_path();
+    let trust = trust::TrustStore::open(workspace_dir, &config.skills);
     match command {
         crate::SkillCommands::List => {
             let skills = load_skills(workspace_dir);
@@ -475,6 +586,7 @@ pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Re
                         skill.description
                     );
                     if !skill.tools.is_empty() {
+                        println!("    Trust: {}", trust.status(&skill.name));
                         println!(
                             "    Tools: {}",
                             skill
@@ -499,7 +611,24 @@ pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Re
             let skills_path = skills_dir(workspace_dir);
             std::fs::create_dir_all(&skills_path)?;
 
-            if source.starts_with("https://") || source.starts_with("http://") {
+            if Path::new(&source)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"))
+            {
+                let src = PathBuf::from(&source);
+                if !src.is_file() {
+                    anyhow::bail!("Source path does not exist: {source}");
+                }
+                let dest = wasm::WasmHost::new(&config.skills).install(&src, &skills_path)?;
+                if let Some(name) = skill_name_in(&dest) {
+                    trust.quarantine(&name);
+                }
+                println!(
+                    "  {} Wasm skill installed: {}",
+                    console::style("✓").green().bold(),
+                    dest.display()
+                );
+            } else if source.starts_with("https://") || source.starts_with("http://") {
                 // Git clone
                 let output = std::process::Command::new("git")
                     .args(["clone", "--depth", "1", &source])
@@ -507,6 +636,11 @@ pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Re
                     .output()?;
 
                 if output.status.success() {
+                    let repo = source.trim_end_matches('/').rsplit('/').next();
+                    let dest = skills_path.join(repo.unwrap_or_default().trim_end_matches(".git"));
+                    if let Some(name) = skill_name_in(&dest) {
+                        trust.quarantine(&name);
+                    }
                     println!(
                         "  {} Skill installed successfully!",
                         console::style("✓").green().bold()
@@ -580,6 +714,14 @@ pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Re
                         dest.display()
                     );
                 }
+                if let Some(name) = skill_name_in(&dest) {
+                    trust.quarantine(&name);
+                }
+            }
+            if config.skills.quarantine {
+                println!(
+                    "  Its tools are simulated until it is trusted; `zeroclaw skills trust <name>` skips the wait."
+                );
             }
 
             Ok(())
@@ -606,6 +748,9 @@ pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Re
                 anyhow::bail!("Skill not found: {name}");
             }
 
+            if let Some(skill_name) = skill_name_in(&skill_path) {
+                trust.forget(&skill_name);
+            }
             std::fs::remove_dir_all(&skill_path)?;
             println!(
                 "  {} Skill '{}' removed.",
@@ -614,6 +759,24 @@ pub fn handle_command(command: crate::SkillCommands, workspace_dir: &Path) -> Re
             );
             Ok(())
         }
+        crate::SkillCommands::Trust { name } => {
+            let skills = load_workspace_skills(workspace_dir);
+            let Some(skill) = skills.iter().find(|s| s.name == name) else {
+                anyhow::bail!("Skill not found: {name}");
+            };
+            trust.trust(&skill.name);
+            println!(
+                "  {} Skill '{}' trusted.",
+                console::style("✓").green().bold(),
+                skill.name
+            );
+            if skill.permissions.is_empty() {
+                println!("  Its manifest declares no permissions, so its tools still cannot run.");
+            } else {
+                println!("  Permissions granted: {}", skill.permissions.join(", "));
+            }
+            Ok(())
+        }
     }
 }
 
@@ -697,6 +860,7 @@ command = "echo hello"
             tags: vec![],
             tools: vec![],
             prompts: vec!["Do the thing.".to_string()],
+            permissions: vec![],
             location: None,
         }];
         let prompt = skills_to_prompt(&skills);
@@ -885,8 +1049,10 @@ description = "Bare minimum"
                 kind: "shell".to_string(),
                 command: "curl wttr.in".to_string(),
                 args: HashMap::new(),
+                parameters: None,
             }],
             prompts: vec![],
+            permissions: vec![],
             location: None,
         }];
         let prompt = skills_to_prompt(&skills);
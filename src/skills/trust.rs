@@ -0,0 +1,282 @@
+//! Quarantine for newly installed skills.
+//!
+//! A skill nobody has vouched for yet runs deny-by-default: calls to its
+//! tools are logged to `skills/quarantine.jsonl` and answered with a
+//! simulated result instead of being executed. It is trusted — and its tools
+//! get the permissions its manifest declares — once it has been installed for
+//! `quarantine_days`, made `quarantine_invocations` simulated calls, or been
+//! promoted with `zeroclaw skills trust <name>`.
+//!
+//! State lives in `skills/.trust.json`, keyed by skill name. Skills that show
+//! up without going through `skills install` (copied in by hand, say) start
+//! their quarantine the first time they are seen.
+
+use crate::config::SkillsConfig;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const STATE_FILE: &str = ".trust.json";
+const LOG_FILE: &str = "quarantine.jsonl";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TrustEntry {
+    first_seen: DateTime<Utc>,
+    #[serde(default)]
+    invocations: u32,
+    #[serde(default)]
+    trusted: bool,
+}
+
+/// Where a skill stands. `None` means that criterion never ends quarantine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustStatus {
+    Trusted,
+    Quarantined {
+        days_left: Option<u32>,
+        calls_left: Option<u32>,
+    },
+}
+
+impl std::fmt::Display for TrustStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Trusted => write!(f, "trusted"),
+            Self::Quarantined {
+                days_left,
+                calls_left,
+            } => {
+                let mut left = Vec::new();
+                if let Some(days) = days_left {
+                    left.push(format!("{days} day(s)"));
+                }
+                if let Some(calls) = calls_left {
+                    left.push(format!("{calls} call(s)"));
+                }
+                if left.is_empty() {
+                    write!(f, "quarantined until trusted")
+                } else {
+                    write!(f, "quarantined ({} left)", left.join(" or "))
+                }
+            }
+        }
+    }
+}
+
+/// Per-workspace trust state for skills.
+pub struct TrustStore {
+    dir: PathBuf,
+    policy: SkillsConfig,
+    entries: Mutex<BTreeMap<String, TrustEntry>>,
+}
+
+impl TrustStore {
+    /// Load the trust state of `workspace_dir`; a missing or unreadable file
+    /// starts empty.
+    pub fn open(workspace_dir: &Path, policy: &SkillsConfig) -> Self {
+        let dir = super::skills_dir(workspace_dir);
+        let entries = std::fs::read_to_string(dir.join(STATE_FILE))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self {
+            dir,
+            policy: policy.clone(),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Whether `skill` may run its tools for real. Skills seen for the first
+    /// time start their quarantine now; one that has served it is promoted.
+    pub fn status(&self, skill: &str) -> TrustStatus {
+        if !self.policy.quarantine {
+            return TrustStatus::Trusted;
+        }
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut changed = false;
+        let entry = entries.entry(skill.to_string()).or_insert_with(|| {
+            changed = true;
+            TrustEntry {
+                first_seen: Utc::now(),
+                invocations: 0,
+                trusted: false,
+            }
+        });
+        let status = self.evaluate(entry);
+        if status == TrustStatus::Trusted && !entry.trusted {
+            tracing::info!(skill, "Skill served its quarantine and is now trusted");
+            entry.trusted = true;
+            changed = true;
+        }
+        if changed {
+            self.save(&entries);
+        }
+        status
+    }
+
+    fn evaluate(&self, entry: &TrustEntry) -> TrustStatus {
+        if entry.trusted {
+            return TrustStatus::Trusted;
+        }
+        let days =
+            u32::try_from((Utc::now() - entry.first_seen).num_days().max(0)).unwrap_or(u32::MAX);
+        let days_left = (self.policy.quarantine_days > 0)
+            .then(|| self.policy.quarantine_days.saturating_sub(days));
+        let calls_left = (self.policy.quarantine_invocations > 0).then(|| {
+            self.policy
+                .quarantine_invocations
+                .saturating_sub(entry.invocations)
+        });
+        if days_left == Some(0) || calls_left == Some(0) {
+            TrustStatus::Trusted
+        } else {
+            TrustStatus::Quarantined {
+                days_left,
+                calls_left,
+            }
+        }
+    }
+
+    /// Log a call that was simulated instead of run and count it toward the
+    /// skill's quarantine.
+    pub fn record_simulated(&self, skill: &str, tool: &str, action: &str) {
+        let record = serde_json::json!({
+            "at": Utc::now().to_rfc3339(),
+            "skill": skill,
+            "tool": tool,
+            "action": crate::providers::scrub_secret_patterns(action),
+        });
+        let logged = std::fs::create_dir_all(&self.dir).and_then(|()| {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.dir.join(LOG_FILE))?;
+            writeln!(file, "{record}")
+        });
+        if let Err(e) = logged {
+            tracing::warn!("Failed to log quarantined skill call: {e}");
+        }
+
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(entry) = entries.get_mut(skill) {
+            entry.invocations = entry.invocations.saturating_add(1);
+            self.save(&entries);
+        }
+    }
+
+    /// Start `skill`'s quarantine afresh, e.g. after (re)installing it.
+    pub fn quarantine(&self, skill: &str) {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        entries.insert(
+            skill.to_string(),
+            TrustEntry {
+                first_seen: Utc::now(),
+                invocations: 0,
+                trusted: false,
+            },
+        );
+        self.save(&entries);
+    }
+
+    /// Promote `skill` now.
+    pub fn trust(&self, skill: &str) {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        entries
+            .entry(skill.to_string())
+            .or_insert_with(|| TrustEntry {
+                first_seen: Utc::now(),
+                invocations: 0,
+                trusted: false,
+            })
+            .trusted = true;
+        self.save(&entries);
+    }
+
+    /// Drop `skill`'s state when it is removed.
+    pub fn forget(&self, skill: &str) {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if entries.remove(skill).is_some() {
+            self.save(&entries);
+        }
+    }
+
+    fn save(&self, entries: &BTreeMap<String, TrustEntry>) {
+        let saved: Result<()> = (|| {
+            std::fs::create_dir_all(&self.dir)?;
+            std::fs::write(
+                self.dir.join(STATE_FILE),
+                serde_json::to_string_pretty(entries)?,
+            )?;
+            Ok(())
+        })();
+        if let Err(e) = saved {
+            tracing::warn!("Failed to save skill trust state: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarantine_ends_after_enough_calls_or_a_trust() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = SkillsConfig {
+            quarantine_invocations: 2,
+            ..SkillsConfig::default()
+        };
+        let store = TrustStore::open(dir.path(), &policy);
+        assert_eq!(
+            store.status("weather"),
+            TrustStatus::Quarantined {
+                days_left: Some(7),
+                calls_left: Some(2)
+            }
+        );
+        store.record_simulated("weather", "forecast", "curl wttr.in");
+        store.record_simulated("weather", "forecast", "curl wttr.in");
+        assert_eq!(store.status("weather"), TrustStatus::Trusted);
+
+        let log = std::fs::read_to_string(dir.path().join("skills/quarantine.jsonl")).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.contains("curl wttr.in"));
+
+        // State survives a reopen; a reinstall starts over until trusted.
+        let store = TrustStore::open(dir.path(), &policy);
+        assert_eq!(store.status("weather"), TrustStatus::Trusted);
+        store.quarantine("weather");
+        assert!(matches!(
+            store.status("weather"),
+            TrustStatus::Quarantined { .. }
+        ));
+        store.trust("weather");
+        assert_eq!(store.status("weather"), TrustStatus::Trusted);
+
+        let off = SkillsConfig {
+            quarantine: false,
+            ..SkillsConfig::default()
+        };
+        let store = TrustStore::open(dir.path(), &off);
+        assert_eq!(store.status("brand-new"), TrustStatus::Trusted);
+    }
+}
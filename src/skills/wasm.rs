@@ -0,0 +1,291 @@
+//! WebAssembly skills, run under the `wasmtime` CLI.
+//!
+//! A wasm skill is a WASI command module that speaks JSON over stdio, so it
+//! can be written in any language that targets `wasm32-wasip1`:
+//!
+//! - `{"describe": true}` on stdin asks for its manifest:
+//!   `{"name", "description", "version", "permissions", "tools": [{"name",
+//!   "description", "parameters"}]}`, where `parameters` is the tool's JSON
+//!   schema.
+//!   `zeroclaw skills install plugin.wasm` uses this to write `SKILL.toml`.
+//! - `{"tool": "<name>", "arguments": {...}}` runs one tool call. The module
+//!   prints `{"success", "output", "error"}`, or plain text, which counts as
+//!   success when it exits cleanly.
+//!
+//! Modules get no environment, no network and no filesystem; a skill that
+//! declares the "workspace" permission sees the workspace at `/workspace`.
+
+use super::{SkillManifest, SkillMeta, SkillTool};
+use crate::config::SkillsConfig;
+use crate::tools::ToolResult;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// Output beyond this is cut off.
+const MAX_OUTPUT_BYTES: usize = 1_048_576;
+
+/// File name a wasm skill's module is installed under.
+pub const MODULE_FILE: &str = "skill.wasm";
+
+#[derive(Debug, Deserialize)]
+struct Description {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default = "super::default_version")]
+    version: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    permissions: Vec<String>,
+    #[serde(default)]
+    tools: Vec<DescribedTool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DescribedTool {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    parameters: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Reply {
+    #[serde(default = "default_success")]
+    success: bool,
+    #[serde(default)]
+    output: String,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+fn default_success() -> bool {
+    true
+}
+
+/// Runs wasm skill modules in a fresh `wasmtime` sandbox per call.
+pub struct WasmHost {
+    binary: String,
+    timeout: Duration,
+    memory_mb: u64,
+}
+
+impl WasmHost {
+    pub fn new(config: &SkillsConfig) -> Self {
+        Self {
+            binary: config.wasmtime.clone(),
+            timeout: Duration::from_secs(config.wasm_timeout_secs.max(1)),
+            memory_mb: config.wasm_memory_mb,
+        }
+    }
+
+    /// `wasmtime` arguments for one run of `module`; `workspace` is mounted
+    /// at `/workspace` when given.
+    fn run_args(&self, module: &Path, workspace: Option<&Path>) -> Vec<String> {
+        let mut args = vec!["run".to_string()];
+        if self.memory_mb > 0 {
+            args.extend([
+                "-W".into(),
+                format!("max-memory-size={}", self.memory_mb * 1024 * 1024),
+            ]);
+        }
+        if let Some(workspace) = workspace {
+            args.extend([
+                "--dir".into(),
+                format!("{}::/workspace", workspace.display()),
+            ]);
+        }
+        args.push(module.display().to_string());
+        args
+    }
+
+    fn command(&self, module: &Path, workspace: Option<&Path>) -> tokio::process::Command {
+        let mut cmd = tokio::process::Command::new(&self.binary);
+        cmd.args(self.run_args(module, workspace))
+            .env_clear()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for var in ["PATH", "HOME"] {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        cmd
+    }
+
+    /// Run one tool call of `module`.
+    pub async fn call(
+        &self,
+        module: &Path,
+        tool: &str,
+        arguments: &Value,
+        workspace: Option<&Path>,
+    ) -> ToolResult {
+        let input = serde_json::json!({ "tool": tool, "arguments": arguments });
+        let run = async {
+            let mut child = self.command(module, workspace).spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                use tokio::io::AsyncWriteExt;
+                stdin.write_all(input.to_string().as_bytes()).await?;
+            }
+            child.wait_with_output().await
+        };
+        match tokio::time::timeout(self.timeout, run).await {
+            Ok(Ok(output)) => parse_reply(&output.stdout, &output.stderr, output.status.success()),
+            Ok(Err(e)) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to run `{}`: {e}", self.binary)),
+            },
+            Err(_) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Wasm skill timed out after {}s and was killed",
+                    self.timeout.as_secs()
+                )),
+            },
+        }
+    }
+
+    /// Ask `module` for its manifest.
+    fn describe(&self, module: &Path) -> Result<Description> {
+        let mut child = std::process::Command::new(&self.binary)
+            .args(self.run_args(module, None))
+            .env_clear()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run `{}`; is wasmtime installed?", self.binary))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(br#"{"describe": true}"#)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Module failed to describe itself: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        serde_json::from_slice(&output.stdout)
+            .context("Module did not print a JSON manifest for {\"describe\": true}")
+    }
+
+    /// Install `module` as `<skills_dir>/<name>/`, with a `SKILL.toml`
+    /// generated from its self-description. Returns the install directory.
+    pub fn install(&self, module: &Path, skills_dir: &Path) -> Result<PathBuf> {
+        let description = self.describe(module)?;
+        let name = &description.name;
+        if name.is_empty() || name.contains("..") || name.contains('/') || name.contains('\\') {
+            anyhow::bail!("Invalid skill name: {name}");
+        }
+        let manifest = manifest_for(&description);
+        let dest = skills_dir.join(name);
+        std::fs::create_dir_all(&dest)?;
+        std::fs::copy(module, dest.join(MODULE_FILE))?;
+        std::fs::write(dest.join("SKILL.toml"), toml::to_string(&manifest)?)?;
+        Ok(dest)
+    }
+}
+
+fn manifest_for(description: &Description) -> SkillManifest {
+    SkillManifest {
+        skill: SkillMeta {
+            name: description.name.clone(),
+            description: description.description.clone(),
+            version: description.version.clone(),
+            author: description.author.clone(),
+            tags: vec!["wasm".into()],
+            permissions: description.permissions.clone(),
+        },
+        tools: description
+            .tools
+            .iter()
+            .map(|tool| SkillTool {
+                name: tool.name.clone(),
+                description: tool.description.clone(),
+                kind: "wasm".into(),
+                command: MODULE_FILE.into(),
+                args: std::collections::HashMap::new(),
+                parameters: tool.parameters.clone(),
+            })
+            .collect(),
+        prompts: Vec::new(),
+    }
+}
+
+fn truncated(bytes: &[u8]) -> String {
+    let mut text = String::from_utf8_lossy(bytes).to_string();
+    if text.len() > MAX_OUTPUT_BYTES {
+        text.truncate(text.floor_char_boundary(MAX_OUTPUT_BYTES));
+        text.push_str("\n... [output truncated at 1MB]");
+    }
+    text
+}
+
+fn parse_reply(stdout: &[u8], stderr: &[u8], exited_ok: bool) -> ToolResult {
+    if let Ok(reply) = serde_json::from_slice::<Reply>(stdout) {
+        return ToolResult {
+            success: reply.success && exited_ok,
+            output: reply.output,
+            error: reply.error,
+        };
+    }
+    let stderr = truncated(stderr);
+    ToolResult {
+        success: exited_ok,
+        output: truncated(stdout),
+        error: (!stderr.trim().is_empty()).then_some(stderr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modules_are_sandboxed_and_describe_themselves_as_skills() {
+        let host = WasmHost::new(&SkillsConfig::default());
+        let args = host.run_args(Path::new("/s/skill.wasm"), None);
+        assert_eq!(
+            args,
+            ["run", "-W", "max-memory-size=67108864", "/s/skill.wasm"]
+        );
+        let args = host.run_args(Path::new("/s/skill.wasm"), Some(Path::new("/ws")));
+        assert!(args
+            .join(" ")
+            .contains("--dir /ws::/workspace /s/skill.wasm"));
+
+        let description: Description = serde_json::from_str(
+            r#"{"name": "rot13", "description": "Rotates text", "tools": [
+                {"name": "rot13", "description": "Rotate", "parameters":
+                    {"type": "object", "properties": {"text": {"type": "string"}}}}]}"#,
+        )
+        .unwrap();
+        let manifest = toml::to_string(&manifest_for(&description)).unwrap();
+        let manifest: SkillManifest = toml::from_str(&manifest).unwrap();
+        assert_eq!(manifest.tools[0].kind, "wasm");
+        assert_eq!(manifest.tools[0].command, MODULE_FILE);
+        assert_eq!(
+            manifest.tools[0].parameters.as_ref().unwrap()["properties"]["text"]["type"],
+            "string"
+        );
+
+        let reply = parse_reply(br#"{"success": false, "error": "bad input"}"#, b"", true);
+        assert!(!reply.success);
+        assert_eq!(reply.error.as_deref(), Some("bad input"));
+        let reply = parse_reply(b"plain text\n", b"", true);
+        assert!(reply.success);
+        assert_eq!(reply.output, "plain text\n");
+    }
+}
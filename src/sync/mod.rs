@@ -0,0 +1,738 @@
+//! Sync — one brain across devices.
+//!
+//! Memories, answered conversation turns and the shareable parts of
+//! `config.toml` are kept as keyed records, each stamped with when and on
+//! which device it last changed. Every device writes its whole record set as
+//! one snapshot, `<device>.zcsync`, encrypted with ChaCha20-Poly1305 under a
+//! key only the user's devices hold (`~/.zeroclaw/.sync_key`), to a backend
+//! the user picks: a shared folder, `WebDAV` or S3. The backend never sees
+//! plaintext and no device writes another's file, so there are no write races.
+//!
+//! A sync reads the other devices' snapshots and keeps the newest version of
+//! each record (deletions travel as tombstones). When both sides changed a
+//! record since they last agreed, the older version is also written to
+//! `~/.zeroclaw/sync-conflicts/` so nothing is lost silently.
+
+pub mod backend;
+pub mod sources;
+
+use crate::config::Config;
+use crate::SyncCommands;
+use anyhow::{Context, Result};
+use backend::SyncBackend;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use sha2::{Digest, Sha256};
+use sources::{ConfigSource, MemorySource, SessionSource, SyncSource};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// First bytes of every snapshot file.
+const MAGIC: &[u8] = b"ZCSYNC1";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const SNAPSHOT_EXT: &str = ".zcsync";
+/// Overrides the key file, e.g. on a machine that shouldn't store it.
+const KEY_ENV: &str = "ZEROCLAW_SYNC_KEY";
+/// Deletions are remembered this long, then forgotten.
+const TOMBSTONE_DAYS: i64 = 90;
+
+/// One synced item: a memory, a turn or a config section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub namespace: String,
+    pub key: String,
+    /// `None` once deleted
+    pub value: Option<Json>,
+    pub stamp: DateTime<Utc>,
+    pub device: String,
+}
+
+impl Record {
+    fn id(&self) -> String {
+        format!("{}/{}", self.namespace, self.key)
+    }
+
+    /// Identifies the value; empty for a tombstone.
+    fn hash(&self) -> String {
+        self.value.as_ref().map_or_else(String::new, hash_value)
+    }
+
+    /// Which of two versions wins: the later stamp, then the larger device ID
+    /// so every device picks the same one.
+    fn newer_than(&self, other: &Self) -> bool {
+        (self.stamp, &self.device) > (other.stamp, &other.device)
+    }
+}
+
+fn hash_value(value: &Json) -> String {
+    hex::encode(Sha256::digest(value.to_string().as_bytes()))
+}
+
+/// What this device last agreed on for a record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Known {
+    hash: String,
+    stamp: DateTime<Utc>,
+    device: String,
+}
+
+/// `sync_state.json`, kept next to `config.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    device: String,
+    last_sync: Option<DateTime<Utc>>,
+    #[serde(default)]
+    records: BTreeMap<String, Known>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    device: String,
+    written_at: DateTime<Utc>,
+    records: Vec<Record>,
+}
+
+/// A record both this device and another changed since they last agreed.
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    pub kept: Record,
+    pub lost: Record,
+}
+
+/// What one sync did.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub devices: usize,
+    pub applied: usize,
+    pub conflicts: usize,
+    /// Snapshots that could not be read (another key, damaged)
+    pub unreadable: Vec<String>,
+}
+
+impl std::fmt::Display for SyncReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} other device(s), {} change(s) applied, {} conflict(s)",
+            self.devices, self.applied, self.conflicts
+        )?;
+        if !self.unreadable.is_empty() {
+            write!(f, ", unreadable: {}", self.unreadable.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// ── Keys and snapshots ────────────────────────────────────────────
+
+fn zeroclaw_dir(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+fn key_path(dir: &Path) -> PathBuf {
+    dir.join(".sync_key")
+}
+
+fn parse_key(raw: &str) -> Result<[u8; KEY_LEN]> {
+    let bytes = hex::decode(raw.trim()).context("Sync key is not hex")?;
+    bytes.try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "Sync key must be {KEY_LEN} bytes ({} hex digits)",
+            KEY_LEN * 2
+        )
+    })
+}
+
+/// The sync key: `ZEROCLAW_SYNC_KEY`, else the key file, created on first use.
+fn load_or_create_key(dir: &Path) -> Result<[u8; KEY_LEN]> {
+    if let Ok(raw) = std::env::var(KEY_ENV) {
+        return parse_key(&raw);
+    }
+    let path = key_path(dir);
+    if path.exists() {
+        return parse_key(&std::fs::read_to_string(&path).context("Failed to read sync key")?);
+    }
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    write_key(dir, &key)?;
+    let mut bytes = [0u8; KEY_LEN];
+    bytes.copy_from_slice(&key);
+    Ok(bytes)
+}
+
+fn write_key(dir: &Path, key: &[u8]) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let path = key_path(dir);
+    std::fs::write(&path, hex::encode(key)).context("Failed to write sync key")?;
+    crate::platform::restrict_to_owner(&path)
+}
+
+fn seal(key: &[u8; KEY_LEN], snapshot: &Snapshot) -> Result<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let plaintext = serde_json::to_vec(snapshot)?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
+    let mut blob = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(MAGIC);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+fn open(key: &[u8; KEY_LEN], blob: &[u8]) -> Result<Snapshot> {
+    let body = blob
+        .strip_prefix(MAGIC)
+        .context("Not a ZeroClaw sync snapshot")?;
+    anyhow::ensure!(body.len() > NONCE_LEN, "Sync snapshot is truncated");
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Can't decrypt (different sync key?)"))?;
+    serde_json::from_slice(&plaintext).context("Sync snapshot is damaged")
+}
+
+// ── Merging ───────────────────────────────────────────────────────
+
+/// This device's records: what the sources hold now, stamped with when it
+/// last changed. Values unchanged since the last sync keep their stamp;
+/// records that disappeared become tombstones.
+fn local_records(
+    state: &State,
+    current: BTreeMap<String, (String, String, Json)>,
+    now: DateTime<Utc>,
+) -> BTreeMap<String, Record> {
+    // Before the first sync nothing local has been shared yet; stamp it old
+    // so what the other devices already agree on wins over it.
+    let fresh = if state.last_sync.is_some() {
+        now
+    } else {
+        DateTime::<Utc>::UNIX_EPOCH
+    };
+    let mut records = BTreeMap::new();
+    for (id, (namespace, key, value)) in current {
+        let (stamp, device) = match state.records.get(&id) {
+            Some(known) if known.hash == hash_value(&value) => (known.stamp, known.device.clone()),
+            _ => (fresh, state.device.clone()),
+        };
+        records.insert(
+            id,
+            Record {
+                namespace,
+                key,
+                value: Some(value),
+                stamp,
+                device,
+            },
+        );
+    }
+    for (id, known) in &state.records {
+        if records.contains_key(id) {
+            continue;
+        }
+        let Some((namespace, key)) = id.split_once('/') else {
+            continue;
+        };
+        let (stamp, device) = if known.hash.is_empty() {
+            (known.stamp, known.device.clone())
+        } else {
+            (now, state.device.clone())
+        };
+        if now - stamp > Duration::days(TOMBSTONE_DAYS) {
+            continue;
+        }
+        records.insert(
+            id.clone(),
+            Record {
+                namespace: namespace.to_string(),
+                key: key.to_string(),
+                value: None,
+                stamp,
+                device,
+            },
+        );
+    }
+    records
+}
+
+/// Fold `remote` records into `records`, newest wins. Returns the IDs whose
+/// value changed here and the conflicts found: records whose value both
+/// sides changed since the last agreed version.
+fn merge(
+    records: &mut BTreeMap<String, Record>,
+    known: &BTreeMap<String, Known>,
+    remote: Vec<Record>,
+) -> (Vec<String>, Vec<Conflict>) {
+    let mut changed = Vec::new();
+    let mut conflicts = Vec::new();
+    for theirs in remote {
+        let id = theirs.id();
+        let agreed = known.get(&id).map(|k| k.hash.as_str());
+        let Some(ours) = records.get(&id) else {
+            if theirs.value.is_some() {
+                changed.push(id.clone());
+            }
+            records.insert(id, theirs);
+            continue;
+        };
+        if ours.hash() == theirs.hash() {
+            // Same value; settle on one stamp so both sides stop re-sending it.
+            if theirs.newer_than(ours) {
+                records.insert(id, theirs);
+            }
+            continue;
+        }
+        let both_changed = agreed != Some(ours.hash().as_str())
+            && agreed != Some(theirs.hash().as_str())
+            && ours.value.is_some()
+            && theirs.value.is_some();
+        let theirs_won = theirs.newer_than(ours);
+        let (kept, lost) = if theirs_won {
+            (theirs, ours.clone())
+        } else {
+            (ours.clone(), theirs)
+        };
+        if both_changed {
+            conflicts.push(Conflict {
+                kept: kept.clone(),
+                lost,
+            });
+        }
+        if theirs_won {
+            changed.push(id.clone());
+        }
+        records.insert(id, kept);
+    }
+    changed.sort();
+    changed.dedup();
+    (changed, conflicts)
+}
+
+// ── Engine ────────────────────────────────────────────────────────
+
+/// Syncs the sources `[sync] include` names through the configured backend.
+pub struct SyncEngine {
+    dir: PathBuf,
+    backend: Box<dyn SyncBackend>,
+    sources: Vec<Box<dyn SyncSource>>,
+}
+
+impl SyncEngine {
+    pub fn new(config: &Config) -> Result<Self> {
+        let backend = backend::from_config(&config.sync)?;
+        let mut sources: Vec<Box<dyn SyncSource>> = Vec::new();
+        for name in &config.sync.include {
+            match name.as_str() {
+                "memory" => sources.push(Box::new(MemorySource {
+                    memory: crate::memory::create_memory(
+                        &config.memory,
+                        &config.workspace_dir,
+                        config.api_key.as_deref(),
+                    )?,
+                })),
+                "sessions" => sources.push(Box::new(SessionSource {
+                    store: crate::conversations::ConversationStore::open(&config.workspace_dir)?,
+                })),
+                "config" => sources.push(Box::new(ConfigSource {
+                    path: config.config_path.clone(),
+                })),
+                other => anyhow::bail!(
+                    "Unknown [sync] include '{other}' (expected memory, sessions or config)"
+                ),
+            }
+        }
+        Ok(Self::with(zeroclaw_dir(config), backend, sources))
+    }
+
+    pub fn with(
+        dir: PathBuf,
+        backend: Box<dyn SyncBackend>,
+        sources: Vec<Box<dyn SyncSource>>,
+    ) -> Self {
+        Self {
+            dir,
+            backend,
+            sources,
+        }
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.dir.join("sync_state.json")
+    }
+
+    fn load_state(&self) -> Result<State> {
+        let path = self.state_path();
+        let mut state: State = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)
+                .context("Failed to parse sync state")?
+        } else {
+            State::default()
+        };
+        if state.device.is_empty() {
+            state.device = uuid::Uuid::new_v4().to_string();
+        }
+        Ok(state)
+    }
+
+    fn save_state(&self, state: &State) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.state_path(), serde_json::to_string(state)?)
+            .context("Failed to write sync state")
+    }
+
+    fn source(&self, namespace: &str) -> Option<&dyn SyncSource> {
+        self.sources
+            .iter()
+            .find(|s| s.namespace() == namespace)
+            .map(AsRef::as_ref)
+    }
+
+    /// Pull the other devices' changes, apply them, and publish this
+    /// device's snapshot.
+    pub async fn sync(&self) -> Result<SyncReport> {
+        let key = load_or_create_key(&self.dir)?;
+        let mut state = self.load_state()?;
+        // A namespace dropped from `include` is no longer synced, not deleted.
+        state.records.retain(|id, _| {
+            id.split_once('/')
+                .is_some_and(|(namespace, _)| self.source(namespace).is_some())
+        });
+        let now = Utc::now();
+
+        let mut current = BTreeMap::new();
+        for source in &self.sources {
+            for (key, value) in source.collect().await? {
+                let id = format!("{}/{key}", source.namespace());
+                current.insert(id, (source.namespace().to_string(), key, value));
+            }
+        }
+        let mut records = local_records(&state, current, now);
+
+        let own = format!("{}{SNAPSHOT_EXT}", state.device);
+        let mut report = SyncReport::default();
+        let mut remote = Vec::new();
+        for name in self.backend.list().await? {
+            if name == own || !name.ends_with(SNAPSHOT_EXT) {
+                continue;
+            }
+            let snapshot = match self.backend.get(&name).await.and_then(|b| open(&key, &b)) {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    tracing::warn!("Sync: skipping {name}: {e}");
+                    report.unreadable.push(name);
+                    continue;
+                }
+            };
+            report.devices += 1;
+            remote.extend(
+                snapshot
+                    .records
+                    .into_iter()
+                    .filter(|r| self.source(&r.namespace).is_some()),
+            );
+        }
+
+        let (changed, conflicts) = merge(&mut records, &state.records, remote);
+        for id in &changed {
+            let record = &records[id];
+            let Some(source) = self.source(&record.namespace) else {
+                continue;
+            };
+            match source.apply(&record.key, record.value.as_ref()).await {
+                Ok(()) => report.applied += 1,
+                Err(e) => tracing::warn!("Sync: couldn't apply {id}: {e}"),
+            }
+        }
+        if !conflicts.is_empty() {
+            report.conflicts = conflicts.len();
+            self.save_conflicts(&conflicts, now)?;
+        }
+
+        let snapshot = Snapshot {
+            device: state.device.clone(),
+            written_at: now,
+            records: records.values().cloned().collect(),
+        };
+        self.backend.put(&own, seal(&key, &snapshot)?).await?;
+
+        state.records = records
+            .into_iter()
+            .map(|(id, record)| {
+                let known = Known {
+                    hash: record.hash(),
+                    stamp: record.stamp,
+                    device: record.device,
+                };
+                (id, known)
+            })
+            .collect();
+        state.last_sync = Some(now);
+        self.save_state(&state)?;
+        Ok(report)
+    }
+
+    fn save_conflicts(&self, conflicts: &[Conflict], now: DateTime<Utc>) -> Result<()> {
+        let dir = self.dir.join("sync-conflicts");
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.json", now.format("%Y%m%dT%H%M%S")));
+        std::fs::write(&path, serde_json::to_string_pretty(conflicts)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        for conflict in conflicts {
+            tracing::warn!(
+                "Sync conflict on {}: kept the version from {}, older one saved to {}",
+                conflict.kept.id(),
+                conflict.kept.device,
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Daemon component: sync every `[sync] interval_minutes`.
+pub async fn run(config: Config) -> Result<()> {
+    let engine = SyncEngine::new(&config)?;
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        config.sync.interval_minutes.max(1) * 60,
+    ));
+    loop {
+        interval.tick().await;
+        match engine.sync().await {
+            Ok(report) => {
+                crate::health::mark_component_ok("sync");
+                if report.applied > 0 || report.conflicts > 0 {
+                    tracing::info!("Sync: {report}");
+                }
+            }
+            Err(e) => {
+                crate::health::mark_component_error("sync", e.to_string());
+                tracing::warn!("Sync failed: {e}");
+            }
+        }
+    }
+}
+
+pub async fn handle_command(command: SyncCommands, config: &Config) -> Result<()> {
+    let dir = zeroclaw_dir(config);
+    match command {
+        SyncCommands::Now => {
+            let report = SyncEngine::new(config)?.sync().await?;
+            println!("Synced via {}: {report}", config.sync.backend);
+            if report.conflicts > 0 {
+                println!(
+                    "Older versions of conflicting records are in {}",
+                    dir.join("sync-conflicts").display()
+                );
+            }
+            Ok(())
+        }
+        SyncCommands::Status => {
+            let engine = SyncEngine::new(config)?;
+            let state = engine.load_state()?;
+            println!("Backend:   {}", engine.backend.name());
+            println!("Device:    {}", state.device);
+            println!(
+                "Last sync: {}",
+                state
+                    .last_sync
+                    .map_or_else(|| "never".to_string(), |at| at.to_rfc3339())
+            );
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for (id, known) in &state.records {
+                if !known.hash.is_empty() {
+                    *counts
+                        .entry(id.split_once('/').map_or(id.as_str(), |(ns, _)| ns))
+                        .or_default() += 1;
+                }
+            }
+            for (namespace, count) in counts {
+                println!("  {namespace:<10} {count} record(s)");
+            }
+            if !config.sync.enabled {
+                println!("\nThe daemon doesn't sync until [sync] enabled = true.");
+            }
+            Ok(())
+        }
+        SyncCommands::Key => {
+            let key = load_or_create_key(&dir)?;
+            println!("{}", hex::encode(key));
+            println!("\nRun `zeroclaw sync join <key>` on your other devices. Keep it secret:");
+            println!("it decrypts everything synced.");
+            Ok(())
+        }
+        SyncCommands::Join { key, force } => {
+            let key = parse_key(&key)?;
+            let path = key_path(&dir);
+            if path.exists() && !force {
+                let existing = parse_key(&std::fs::read_to_string(&path)?)?;
+                if existing != key {
+                    anyhow::bail!(
+                        "This device already has a different sync key; pass --force to replace it"
+                    );
+                }
+            }
+            write_key(&dir, &key)?;
+            println!("Sync key saved to {}", path.display());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn record(key: &str, value: Option<Json>, secs: i64, device: &str) -> Record {
+        Record {
+            namespace: "memory".into(),
+            key: key.into(),
+            value,
+            stamp: DateTime::<Utc>::UNIX_EPOCH + Duration::seconds(secs),
+            device: device.into(),
+        }
+    }
+
+    fn known(record: &Record) -> Known {
+        Known {
+            hash: record.hash(),
+            stamp: record.stamp,
+            device: record.device.clone(),
+        }
+    }
+
+    #[test]
+    fn merge_keeps_newest_and_reports_real_conflicts() {
+        let base = record("tz", Some(json!("UTC")), 10, "a");
+        let mut agreed = BTreeMap::new();
+        agreed.insert(base.id(), known(&base));
+
+        // Only the other device changed it: take theirs, no conflict.
+        let mut ours = BTreeMap::from([(base.id(), base.clone())]);
+        let theirs = record("tz", Some(json!("CET")), 20, "b");
+        let (changed, conflicts) = merge(&mut ours, &agreed, vec![theirs.clone()]);
+        assert_eq!(changed, vec![base.id()]);
+        assert!(conflicts.is_empty());
+        assert_eq!(ours[&base.id()], theirs);
+
+        // Both changed it: the newer one wins and the other is kept aside.
+        let mine = record("tz", Some(json!("PST")), 30, "a");
+        let mut ours = BTreeMap::from([(base.id(), mine.clone())]);
+        let (changed, conflicts) = merge(&mut ours, &agreed, vec![theirs.clone()]);
+        assert!(changed.is_empty());
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].lost, theirs);
+        assert_eq!(ours[&base.id()], mine);
+
+        // A deletion elsewhere removes it here; a new record arrives.
+        let mut ours = BTreeMap::from([(base.id(), base.clone())]);
+        let deleted = record("tz", None, 40, "b");
+        let added = record("lang", Some(json!("fr")), 5, "b");
+        let (changed, conflicts) = merge(&mut ours, &agreed, vec![deleted.clone(), added.clone()]);
+        assert_eq!(changed, vec![added.id(), base.id()]);
+        assert!(conflicts.is_empty());
+        assert_eq!(ours[&base.id()].value, None);
+    }
+
+    #[test]
+    fn local_records_stamp_changes_and_deletions() {
+        let now = Utc::now();
+        let old = record("kept", Some(json!(1)), 10, "b");
+        let gone = record("gone", Some(json!(2)), 10, "b");
+        let state = State {
+            device: "a".into(),
+            last_sync: Some(now - Duration::hours(1)),
+            records: BTreeMap::from([(old.id(), known(&old)), (gone.id(), known(&gone))]),
+        };
+        let current = BTreeMap::from([
+            (old.id(), ("memory".into(), "kept".into(), json!(1))),
+            (
+                "memory/new".into(),
+                ("memory".into(), "new".into(), json!(3)),
+            ),
+        ]);
+        let records = local_records(&state, current, now);
+        assert_eq!(records[&old.id()], old);
+        assert_eq!(records["memory/new"].stamp, now);
+        assert_eq!(records["memory/new"].device, "a");
+        assert_eq!(records[&gone.id()].value, None);
+        assert_eq!(records[&gone.id()].stamp, now);
+    }
+
+    #[tokio::test]
+    async fn two_devices_converge_through_a_folder() {
+        struct Values(Arc<Mutex<BTreeMap<String, Json>>>);
+
+        #[async_trait::async_trait]
+        impl SyncSource for Values {
+            fn namespace(&self) -> &'static str {
+                "memory"
+            }
+            async fn collect(&self) -> Result<BTreeMap<String, Json>> {
+                Ok(self.0.lock().unwrap().clone())
+            }
+            async fn apply(&self, key: &str, value: Option<&Json>) -> Result<()> {
+                let mut values = self.0.lock().unwrap();
+                match value {
+                    Some(value) => values.insert(key.into(), value.clone()),
+                    None => values.remove(key),
+                };
+                Ok(())
+            }
+        }
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let shared = tmp.path().join("shared");
+        let key = "11".repeat(KEY_LEN);
+        let device = |name: &str, values: &[(&str, Json)]| {
+            let dir = tmp.path().join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(".sync_key"), &key).unwrap();
+            let values: BTreeMap<String, Json> = values
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), v.clone()))
+                .collect();
+            let values = Arc::new(Mutex::new(values));
+            let engine = SyncEngine::with(
+                dir,
+                Box::new(backend::FolderBackend {
+                    dir: shared.clone(),
+                }),
+                vec![Box::new(Values(values.clone()))],
+            );
+            (engine, values)
+        };
+
+        let (laptop, laptop_values) = device("laptop", &[("editor", json!("helix"))]);
+        let (server, server_values) = device("server", &[("city", json!("Lyon"))]);
+        laptop.sync().await.unwrap();
+        let report = server.sync().await.unwrap();
+        assert_eq!(report.devices, 1);
+        assert_eq!(report.applied, 1);
+        laptop.sync().await.unwrap();
+        assert_eq!(
+            *laptop_values.lock().unwrap(),
+            *server_values.lock().unwrap()
+        );
+
+        // A deletion on one side reaches the other.
+        laptop_values.lock().unwrap().remove("city");
+        laptop.sync().await.unwrap();
+        server.sync().await.unwrap();
+        assert!(!server_values.lock().unwrap().contains_key("city"));
+
+        // Snapshots are unreadable without the key.
+        let blob =
+            std::fs::read(shared.read_dir().unwrap().next().unwrap().unwrap().path()).unwrap();
+        assert!(open(&[0u8; KEY_LEN], &blob).is_err());
+        assert!(!String::from_utf8_lossy(&blob).contains("helix"));
+    }
+}
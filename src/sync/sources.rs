@@ -0,0 +1,332 @@
+//! What gets synced: memories, answered conversation turns and the
+//! shareable parts of `config.toml`. Each source turns its data into keyed
+//! JSON values and applies values (or deletions) that came from elsewhere.
+
+use crate::conversations::ConversationStore;
+use crate::memory::{Memory, MemoryCategory};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value as Json};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Top-level config keys that describe this machine, not the user: ports,
+/// paths, tunnels, channel logins, secrets handling and sync itself.
+pub const MACHINE_KEYS: &[&str] = &[
+    "gateway",
+    "tunnel",
+    "runtime",
+    "secrets",
+    "sync",
+    "channels_config",
+    "observability",
+    "presence",
+    "federation",
+    "browser",
+];
+
+#[async_trait]
+pub trait SyncSource: Send + Sync {
+    /// Namespace of this source's records: "memory", "sessions" or "config".
+    fn namespace(&self) -> &'static str;
+
+    /// Everything this source has now, by key.
+    async fn collect(&self) -> Result<BTreeMap<String, Json>>;
+
+    /// Take `value` for `key` from another device; `None` deletes it.
+    async fn apply(&self, key: &str, value: Option<&Json>) -> Result<()>;
+}
+
+// ── Memory ────────────────────────────────────────────────────────
+
+/// Memories except the per-conversation category, which stays with the
+/// device the conversation happened on.
+pub struct MemorySource {
+    pub memory: Box<dyn Memory>,
+}
+
+fn parse_category(raw: &str) -> MemoryCategory {
+    match raw {
+        "core" => MemoryCategory::Core,
+        "daily" => MemoryCategory::Daily,
+        "conversation" => MemoryCategory::Conversation,
+        other => MemoryCategory::Custom(other.to_string()),
+    }
+}
+
+#[async_trait]
+impl SyncSource for MemorySource {
+    fn namespace(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn collect(&self) -> Result<BTreeMap<String, Json>> {
+        Ok(self
+            .memory
+            .list(None)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.category != MemoryCategory::Conversation)
+            .map(|entry| {
+                let value = json!({
+                    "content": entry.content,
+                    "category": entry.category.to_string(),
+                });
+                (entry.key, value)
+            })
+            .collect())
+    }
+
+    async fn apply(&self, key: &str, value: Option<&Json>) -> Result<()> {
+        match value {
+            Some(value) => {
+                let content = value
+                    .get("content")
+                    .and_then(Json::as_str)
+                    .context("Synced memory without content")?;
+                let category = value
+                    .get("category")
+                    .and_then(Json::as_str)
+                    .map_or(MemoryCategory::Core, parse_category);
+                self.memory.store(key, content, category).await
+            }
+            None => self.memory.forget(key).await.map(|_| ()),
+        }
+    }
+}
+
+// ── Sessions ──────────────────────────────────────────────────────
+
+/// Answered turns of every session, so a conversation started on one device
+/// can go on from another.
+pub struct SessionSource {
+    pub store: ConversationStore,
+}
+
+/// Stable across devices: turn IDs are not.
+fn turn_key(session: &str, started_at: DateTime<Utc>, user_message: &str) -> String {
+    let digest = hex::encode(Sha256::digest(user_message.as_bytes()));
+    format!("{session}|{}|{}", started_at.to_rfc3339(), &digest[..12])
+}
+
+fn parse_time(value: &Json, field: &str) -> Option<DateTime<Utc>> {
+    value
+        .get(field)
+        .and_then(Json::as_str)
+        .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[async_trait]
+impl SyncSource for SessionSource {
+    fn namespace(&self) -> &'static str {
+        "sessions"
+    }
+
+    async fn collect(&self) -> Result<BTreeMap<String, Json>> {
+        Ok(self
+            .store
+            .complete_turns()?
+            .into_iter()
+            .map(|turn| {
+                let key = turn_key(&turn.session, turn.started_at, &turn.user_message);
+                let value = json!({
+                    "session": turn.session,
+                    "user_message": turn.user_message,
+                    "response": turn.response,
+                    "started_at": turn.started_at.to_rfc3339(),
+                    "finished_at": turn.finished_at.map(|at| at.to_rfc3339()),
+                });
+                (key, value)
+            })
+            .collect())
+    }
+
+    async fn apply(&self, key: &str, value: Option<&Json>) -> Result<()> {
+        let Some(value) = value else {
+            for turn in self.store.complete_turns()? {
+                if turn_key(&turn.session, turn.started_at, &turn.user_message) == key {
+                    self.store.delete_turn(turn.id)?;
+                }
+            }
+            return Ok(());
+        };
+        let field = |name: &str| value.get(name).and_then(Json::as_str);
+        let (Some(session), Some(user_message), Some(started_at)) = (
+            field("session"),
+            field("user_message"),
+            parse_time(value, "started_at"),
+        ) else {
+            anyhow::bail!("Synced turn {key} is incomplete");
+        };
+        self.store.import_turn(
+            session,
+            user_message,
+            field("response").unwrap_or_default(),
+            started_at,
+            parse_time(value, "finished_at"),
+        )?;
+        Ok(())
+    }
+}
+
+// ── Config ────────────────────────────────────────────────────────
+
+/// Top-level keys of `config.toml` other than [`MACHINE_KEYS`]. Values
+/// encrypted with this machine's secret key (`enc:`/`enc2:`) can't be read
+/// anywhere else, so they are left out and kept locally when a section is
+/// replaced.
+pub struct ConfigSource {
+    pub path: PathBuf,
+}
+
+impl ConfigSource {
+    fn read(&self) -> Result<toml::Value> {
+        let contents = std::fs::read_to_string(&self.path).context("Failed to read config file")?;
+        toml::from_str(&contents).context("Failed to parse config file")
+    }
+}
+
+fn is_local_secret(value: &toml::Value) -> bool {
+    value
+        .as_str()
+        .is_some_and(|s| s.starts_with("enc:") || s.starts_with("enc2:"))
+}
+
+/// `value` without this machine's encrypted strings.
+fn strip_local_secrets(value: &toml::Value) -> Option<toml::Value> {
+    match value {
+        v if is_local_secret(v) => None,
+        toml::Value::Table(table) => Some(toml::Value::Table(
+            table
+                .iter()
+                .filter_map(|(k, v)| strip_local_secrets(v).map(|v| (k.clone(), v)))
+                .collect(),
+        )),
+        toml::Value::Array(items) => Some(toml::Value::Array(
+            items.iter().filter_map(strip_local_secrets).collect(),
+        )),
+        other => Some(other.clone()),
+    }
+}
+
+/// Put this machine's encrypted strings from `local` back into `incoming`
+/// wherever `incoming` leaves them out.
+fn keep_local_secrets(incoming: &mut toml::Value, local: &toml::Value) {
+    let (Some(incoming), Some(local)) = (incoming.as_table_mut(), local.as_table()) else {
+        return;
+    };
+    for (key, value) in local {
+        match incoming.get_mut(key) {
+            Some(existing) => keep_local_secrets(existing, value),
+            None if is_local_secret(value) => {
+                incoming.insert(key.clone(), value.clone());
+            }
+            None => {}
+        }
+    }
+}
+
+#[async_trait]
+impl SyncSource for ConfigSource {
+    fn namespace(&self) -> &'static str {
+        "config"
+    }
+
+    async fn collect(&self) -> Result<BTreeMap<String, Json>> {
+        let raw = self.read()?;
+        let Some(table) = raw.as_table() else {
+            return Ok(BTreeMap::new());
+        };
+        let mut values = BTreeMap::new();
+        for (key, value) in table {
+            if MACHINE_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            if let Some(value) = strip_local_secrets(value) {
+                values.insert(key.clone(), serde_json::to_value(value)?);
+            }
+        }
+        Ok(values)
+    }
+
+    async fn apply(&self, key: &str, value: Option<&Json>) -> Result<()> {
+        if MACHINE_KEYS.contains(&key) {
+            return Ok(());
+        }
+        let mut raw = self.read()?;
+        let table = raw.as_table_mut().context("Config file is not a table")?;
+        match value {
+            Some(value) => {
+                let mut incoming = toml::Value::try_from(value)
+                    .with_context(|| format!("Synced config '{key}' is not valid TOML"))?;
+                if let Some(local) = table.get(key) {
+                    keep_local_secrets(&mut incoming, local);
+                }
+                table.insert(key.to_string(), incoming);
+            }
+            None => {
+                table.remove(key);
+            }
+        }
+        raw.clone()
+            .try_into::<crate::config::Config>()
+            .with_context(|| format!("Synced config '{key}' does not load here"))?;
+        std::fs::write(&self.path, toml::to_string_pretty(&raw)?)
+            .context("Failed to write config file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn config_source_skips_machine_keys_and_keeps_local_secrets() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            default_temperature = 0.7
+            api_key = "enc2:abcd"
+
+            [gateway]
+            port = 4000
+
+            [weather]
+            api_key = "enc2:beef"
+
+            [styles.telegram]
+            max_words = 80
+            "#,
+        )
+        .unwrap();
+        let source = ConfigSource { path: path.clone() };
+
+        let values = source.collect().await.unwrap();
+        assert!(!values.contains_key("gateway"));
+        assert!(!values.contains_key("api_key"));
+        assert_eq!(values["weather"], json!({}));
+        assert_eq!(values["styles"], json!({ "telegram": { "max_words": 80 } }));
+
+        source.apply("weather", Some(&json!({}))).await.unwrap();
+        source
+            .apply("styles", Some(&json!({ "telegram": { "max_words": 40 } })))
+            .await
+            .unwrap();
+        source
+            .apply("gateway", Some(&json!({ "port": 1 })))
+            .await
+            .unwrap();
+        let raw: toml::Value = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            raw["styles"]["telegram"]["max_words"].as_integer(),
+            Some(40)
+        );
+        assert_eq!(raw["weather"]["api_key"].as_str(), Some("enc2:beef"));
+        assert_eq!(raw["gateway"]["port"].as_integer(), Some(4000));
+    }
+}
@@ -0,0 +1,485 @@
+//! Tracked units of multi-step work.
+//!
+//! A task is opened by a user (`zeroclaw tasks add`, or the agent on the
+//! user's behalf) or by a cron run, and carries its status and a log of
+//! progress notes in `workspace/tasks/tasks.db`. Work cut short by a restart
+//! is still listed as open and can be picked up with `zeroclaw tasks resume`.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Pending,
+    Running,
+    WaitingApproval,
+    Done,
+    Failed,
+}
+
+impl TaskStatus {
+    pub const ALL: [Self; 5] = [
+        Self::Pending,
+        Self::Running,
+        Self::WaitingApproval,
+        Self::Done,
+        Self::Failed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::WaitingApproval => "waiting-approval",
+            Self::Done => "done",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.as_str() == raw.trim())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown task status '{raw}' (expected one of: pending, running, waiting-approval, done, failed)"
+                )
+            })
+    }
+
+    /// Done and failed tasks are closed; everything else can be resumed.
+    pub fn is_open(self) -> bool {
+        !matches!(self, Self::Done | Self::Failed)
+    }
+}
+
+/// Who opened the task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOrigin {
+    User,
+    Cron,
+}
+
+impl TaskOrigin {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Cron => "cron",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "user" => Ok(Self::User),
+            "cron" => Ok(Self::Cron),
+            other => anyhow::bail!("Unknown task origin in tasks DB: {other}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Task {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub origin: TaskOrigin,
+    pub result: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Task {
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(8)]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskNote {
+    pub at: DateTime<Utc>,
+    pub text: String,
+}
+
+pub struct TaskStore {
+    conn: Mutex<Connection>,
+    db_path: PathBuf,
+}
+
+impl TaskStore {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("tasks").join("tasks.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create tasks directory: {}", parent.display())
+            })?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open tasks DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS tasks (
+                id         TEXT PRIMARY KEY,
+                title      TEXT NOT NULL,
+                status     TEXT NOT NULL,
+                origin     TEXT NOT NULL,
+                result     TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS task_notes (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id    TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                note       TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_task_notes_task ON task_notes(task_id, id);",
+        )
+        .context("Failed to initialize tasks schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            db_path,
+        })
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub fn create(&self, title: &str, origin: TaskOrigin, status: TaskStatus) -> Result<Task> {
+        let now = Utc::now();
+        let task = Task {
+            id: Uuid::new_v4().to_string(),
+            title: title.trim().to_string(),
+            status,
+            origin,
+            result: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.conn()
+            .execute(
+                "INSERT INTO tasks (id, title, status, origin, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![
+                    task.id,
+                    task.title,
+                    status.as_str(),
+                    origin.as_str(),
+                    now.to_rfc3339()
+                ],
+            )
+            .context("Failed to insert task")?;
+        Ok(task)
+    }
+
+    /// Move a task to `status`, optionally logging a note. Closing a task
+    /// with a note also records the note as the task's result.
+    pub fn set_status(&self, id: &str, status: TaskStatus, note: Option<&str>) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let result = note.filter(|_| !status.is_open());
+        let changed = self
+            .conn()
+            .execute(
+                "UPDATE tasks SET status = ?1, updated_at = ?2, result = COALESCE(?3, result)
+                 WHERE id = ?4",
+                params![status.as_str(), now, result, id],
+            )
+            .context("Failed to update task")?;
+        if changed == 0 {
+            anyhow::bail!("Task '{id}' not found");
+        }
+        if let Some(note) = note {
+            self.add_note(id, note)?;
+        }
+        Ok(())
+    }
+
+    /// Append a progress note (a checkpoint the agent can resume from).
+    pub fn add_note(&self, id: &str, note: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO task_notes (task_id, created_at, note) VALUES (?1, ?2, ?3)",
+            params![id, now, note],
+        )
+        .context("Failed to add task note")?;
+        conn.execute(
+            "UPDATE tasks SET updated_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?;
+        Ok(())
+    }
+
+    pub fn notes(&self, id: &str) -> Result<Vec<TaskNote>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT created_at, note FROM task_notes WHERE task_id = ?1 ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut notes = Vec::new();
+        for row in rows {
+            let (at, text) = row?;
+            notes.push(TaskNote {
+                at: parse_rfc3339(&at)?,
+                text,
+            });
+        }
+        Ok(notes)
+    }
+
+    /// All tasks, most recently updated first; closed ones only with `include_closed`.
+    pub fn list(&self, include_closed: bool) -> Result<Vec<Task>> {
+        let tasks = self.query("ORDER BY updated_at DESC", [])?;
+        Ok(tasks
+            .into_iter()
+            .filter(|t| include_closed || t.status.is_open())
+            .collect())
+    }
+
+    pub fn get(&self, id: &str) -> Result<Task> {
+        self.query("WHERE id = ?1", [id])?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Task '{id}' not found"))
+    }
+
+    /// Expand a unique ID prefix (as shown by `tasks list`) to the full ID.
+    pub fn resolve_id(&self, prefix: &str) -> Result<String> {
+        let pattern = format!("{}%", prefix.trim());
+        let matches = self.query("WHERE id LIKE ?1", [pattern])?;
+        match matches.as_slice() {
+            [one] => Ok(one.id.clone()),
+            [] => anyhow::bail!("Task '{prefix}' not found"),
+            _ => anyhow::bail!("Task ID '{prefix}' is ambiguous; use more characters"),
+        }
+    }
+
+    fn query(&self, clause: &str, args: impl rusqlite::Params) -> Result<Vec<Task>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, title, status, origin, result, created_at, updated_at FROM tasks {clause}"
+        ))?;
+        let rows = stmt.query_map(args, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?;
+
+        let mut tasks = Vec::new();
+        for row in rows {
+            let (id, title, status, origin, result, created, updated) = row?;
+            tasks.push(Task {
+                id,
+                title,
+                status: TaskStatus::parse(&status)?,
+                origin: TaskOrigin::parse(&origin)?,
+                result,
+                created_at: parse_rfc3339(&created)?,
+                updated_at: parse_rfc3339(&updated)?,
+            });
+        }
+        Ok(tasks)
+    }
+}
+
+/// The message that hands an open task back to the agent.
+pub fn resume_prompt(task: &Task, notes: &[TaskNote]) -> String {
+    let mut prompt = format!(
+        "Resume task {} (\"{}\"), currently {}.\n",
+        task.short_id(),
+        task.title,
+        task.status.as_str()
+    );
+    if notes.is_empty() {
+        prompt.push_str("No progress has been recorded yet.\n");
+    } else {
+        prompt.push_str("Progress so far:\n");
+        for note in notes {
+            let _ = writeln!(
+                prompt,
+                "- [{}] {}",
+                note.at.format("%Y-%m-%d %H:%M"),
+                note.text
+            );
+        }
+    }
+    let _ = write!(
+        prompt,
+        "Continue from the last step. Record progress with the update_task tool \
+         (id {}) and set it to done or failed when finished.",
+        task.short_id()
+    );
+    prompt
+}
+
+pub async fn handle_command(command: Option<crate::TaskCommands>, config: &Config) -> Result<()> {
+    let store = TaskStore::open(&config.workspace_dir)?;
+    match command.unwrap_or(crate::TaskCommands::List { all: false }) {
+        crate::TaskCommands::List { all } => {
+            let tasks = store.list(all)?;
+            if tasks.is_empty() {
+                println!("No open tasks.");
+                println!("\nUsage:");
+                println!("  zeroclaw tasks add \"Migrate the blog to the new host\"");
+                return Ok(());
+            }
+            println!("📋 Tasks ({}):", tasks.len());
+            for task in &tasks {
+                println!(
+                    "- {} | {:<16} | {} | updated {}\n    {}",
+                    task.short_id(),
+                    task.status.as_str(),
+                    task.origin.as_str(),
+                    task.updated_at.to_rfc3339(),
+                    task.title
+                );
+            }
+            Ok(())
+        }
+        crate::TaskCommands::Show { id } => {
+            let task = store.get(&store.resolve_id(&id)?)?;
+            println!("📋 {} — {}", task.short_id(), task.title);
+            println!("  Status : {}", task.status.as_str());
+            println!("  Origin : {}", task.origin.as_str());
+            println!("  Created: {}", task.created_at.to_rfc3339());
+            if let Some(result) = &task.result {
+                println!("  Result : {result}");
+            }
+            for note in store.notes(&task.id)? {
+                println!("  [{}] {}", note.at.to_rfc3339(), note.text);
+            }
+            Ok(())
+        }
+        crate::TaskCommands::Add { title } => {
+            let task = store.create(&title, TaskOrigin::User, TaskStatus::Pending)?;
+            println!("✅ Added task {}", task.short_id());
+            println!("  Run it with: zeroclaw tasks resume {}", task.short_id());
+            Ok(())
+        }
+        crate::TaskCommands::Resume { id } => {
+            let task = store.get(&store.resolve_id(&id)?)?;
+            if !task.status.is_open() {
+                anyhow::bail!(
+                    "Task {} is already {}",
+                    task.short_id(),
+                    task.status.as_str()
+                );
+            }
+            let prompt = resume_prompt(&task, &store.notes(&task.id)?);
+            store.set_status(&task.id, TaskStatus::Running, Some("Resumed from the CLI"))?;
+            if let Err(e) = Box::pin(crate::agent::run(
+                config.clone(),
+                Some(prompt),
+                None,
+                None,
+                config.default_temperature,
+                None,
+                false,
+                None,
+            ))
+            .await
+            {
+                store.set_status(&task.id, TaskStatus::Failed, Some(&format!("{e:#}")))?;
+                return Err(e);
+            }
+            Ok(())
+        }
+        crate::TaskCommands::Cancel { id } => {
+            let id = store.resolve_id(&id)?;
+            store.set_status(&id, TaskStatus::Failed, Some("Cancelled"))?;
+            println!("🛑 Cancelled task {}", &id[..8]);
+            Ok(())
+        }
+    }
+}
+
+fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Invalid RFC3339 timestamp in tasks DB: {raw}"))?;
+    Ok(parsed.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn task_lifecycle_is_persisted() {
+        let tmp = TempDir::new().unwrap();
+        let id = {
+            let store = TaskStore::open(tmp.path()).unwrap();
+            let task = store
+                .create("Migrate blog", TaskOrigin::User, TaskStatus::Pending)
+                .unwrap();
+            store
+                .set_status(&task.id, TaskStatus::Running, Some("Exported posts"))
+                .unwrap();
+            store.add_note(&task.id, "Uploading images").unwrap();
+            task.id
+        };
+
+        // A fresh store (as after a restart) still sees the open task and its notes.
+        let store = TaskStore::open(tmp.path()).unwrap();
+        let open = store.list(false).unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].status, TaskStatus::Running);
+        let notes = store.notes(&id).unwrap();
+        assert_eq!(notes.len(), 2);
+
+        let prompt = resume_prompt(&open[0], &notes);
+        assert!(prompt.contains("Exported posts") && prompt.contains("Uploading images"));
+        assert!(prompt.contains(open[0].short_id()));
+
+        store
+            .set_status(&id, TaskStatus::Done, Some("Live on new host"))
+            .unwrap();
+        assert!(store.list(false).unwrap().is_empty());
+        let done = store.get(&id).unwrap();
+        assert_eq!(done.result.as_deref(), Some("Live on new host"));
+        assert_eq!(store.list(true).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn ids_resolve_by_prefix_and_statuses_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let store = TaskStore::open(tmp.path()).unwrap();
+        let task = store
+            .create(
+                "nightly backup",
+                TaskOrigin::Cron,
+                TaskStatus::WaitingApproval,
+            )
+            .unwrap();
+        assert_eq!(store.resolve_id(&task.id[..6]).unwrap(), task.id);
+        assert!(store.resolve_id("zzzz").is_err());
+        assert!(store.set_status("missing", TaskStatus::Done, None).is_err());
+
+        for status in TaskStatus::ALL {
+            assert_eq!(TaskStatus::parse(status.as_str()).unwrap(), status);
+        }
+        assert!(TaskStatus::parse("paused").is_err());
+        assert_eq!(store.get(&task.id).unwrap().origin, TaskOrigin::Cron);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::channels::traits::{Channel, ChannelMessage, Reaction, SenderProfile};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A message the agent sent through a [`MemoryChannel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentMessage {
+    pub recipient: String,
+    pub content: String,
+}
+
+/// Channel that keeps everything in memory: inbound messages are built with
+/// [`MemoryChannel::inbound`] and outbound ones are captured for assertions.
+#[derive(Debug)]
+pub struct MemoryChannel {
+    name: String,
+    sent: Mutex<Vec<SentMessage>>,
+    /// Latest reaction per message ID, in the order first reacted to
+    reactions: Mutex<Vec<(String, Reaction)>>,
+    failing: AtomicBool,
+    next_id: AtomicU64,
+}
+
+impl MemoryChannel {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sent: Mutex::new(Vec::new()),
+            reactions: Mutex::new(Vec::new()),
+            failing: AtomicBool::new(false),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// A message from `sender` arriving on this channel.
+    pub fn inbound(&self, sender: &str, content: &str) -> ChannelMessage {
+        let n = self.next_id.fetch_add(1, Ordering::SeqCst);
+        ChannelMessage {
+            id: format!("{}-{n}", self.name),
+            sender: sender.to_string(),
+            content: content.to_string(),
+            channel: self.name.clone(),
+            timestamp: n,
+            profile: SenderProfile::default(),
+            images: Vec::new(),
+        }
+    }
+
+    /// Make subsequent sends fail (to exercise retries and the DLQ).
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    pub fn sent(&self) -> Vec<SentMessage> {
+        self.sent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Reaction each inbound message was last left with.
+    pub fn reactions(&self) -> Vec<(String, Reaction)> {
+        self.reactions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl Channel for MemoryChannel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            anyhow::bail!("MemoryChannel '{}' is set to fail", self.name);
+        }
+        self.sent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(SentMessage {
+                recipient: recipient.to_string(),
+                content: message.to_string(),
+            });
+        Ok(())
+    }
+
+    fn supports_reactions(&self) -> bool {
+        true
+    }
+
+    async fn react(&self, message: &ChannelMessage, reaction: Reaction) -> anyhow::Result<()> {
+        let mut reactions = self
+            .reactions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match reactions.iter_mut().find(|(id, _)| *id == message.id) {
+            Some(entry) => entry.1 = reaction,
+            None => reactions.push((message.id.clone(), reaction)),
+        }
+        Ok(())
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tx.closed().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn captures_sends_and_can_fail() {
+        let ch = MemoryChannel::new("test");
+        let msg = ch.inbound("alice", "hi");
+        assert_eq!(msg.channel, "test");
+        assert_ne!(msg.id, ch.inbound("alice", "again").id);
+
+        ch.send("hello", "alice").await.unwrap();
+        ch.set_failing(true);
+        assert!(ch.send("lost", "alice").await.is_err());
+        assert_eq!(
+            ch.sent(),
+            vec![SentMessage {
+                recipient: "alice".into(),
+                content: "hello".into()
+            }]
+        );
+    }
+}
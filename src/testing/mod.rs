@@ -0,0 +1,56 @@
+//! Deterministic end-to-end testing without network access (`testing` feature).
+//!
+//! [`MockProvider`] answers from a script, [`MemoryChannel`] captures what the
+//! agent sends, and [`scenario`] drives both through the real channel message
+//! pipeline from a TOML file: `zeroclaw test scenario tests/scenarios/hello.toml`.
+
+pub mod channel;
+pub mod provider;
+pub mod scenario;
+
+pub use channel::MemoryChannel;
+pub use provider::MockProvider;
+pub use scenario::{Scenario, ScenarioReport};
+
+use anyhow::Result;
+
+pub async fn handle_command(command: crate::TestCommands) -> Result<()> {
+    match command {
+        crate::TestCommands::Scenario { files } => {
+            let mut failed = 0;
+            for file in &files {
+                let scenario = Scenario::load(file)?;
+                let base_dir = file.parent().unwrap_or_else(|| std::path::Path::new("."));
+                let report = scenario::run(&scenario, base_dir).await?;
+                print_report(&report);
+                if !report.passed() {
+                    failed += 1;
+                }
+            }
+            if failed > 0 {
+                anyhow::bail!("{failed} of {} scenario(s) failed", files.len());
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_report(report: &ScenarioReport) {
+    let icon = if report.passed() { "✅" } else { "❌" };
+    println!("{icon} {}", report.name);
+    for (i, step) in report.steps.iter().enumerate() {
+        let mark = if step.failures.is_empty() {
+            "✓"
+        } else {
+            "✗"
+        };
+        println!(
+            "   {mark} step {}: {}",
+            i + 1,
+            crate::util::truncate_with_ellipsis(&step.user, 60)
+        );
+        for failure in &step.failures {
+            println!("       - {failure}");
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::providers::Provider;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// One request the mock provider received.
+#[derive(Debug, Clone)]
+pub struct MockCall {
+    pub system_prompt: Option<String>,
+    pub message: String,
+    pub model: String,
+}
+
+#[derive(Debug, Clone)]
+enum Scripted {
+    Reply(String),
+    Error(String),
+}
+
+/// Provider that answers from a script instead of the network.
+///
+/// Responses are consumed in order; running out is an error so a scenario
+/// that triggers more LLM calls than expected fails loudly — unless a fallback
+/// (typically a cassette replay) is set to answer the unscripted calls.
+#[derive(Default)]
+pub struct MockProvider {
+    script: Mutex<VecDeque<Scripted>>,
+    calls: Mutex<Vec<MockCall>>,
+    fallback: Option<Box<dyn Provider>>,
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer unscripted calls with `fallback` instead of failing.
+    pub fn with_fallback(fallback: Box<dyn Provider>) -> Self {
+        Self {
+            fallback: Some(fallback),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_replies<I, S>(replies: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let provider = Self::new();
+        for reply in replies {
+            provider.push_reply(reply);
+        }
+        provider
+    }
+
+    pub fn push_reply(&self, reply: impl Into<String>) {
+        self.script
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push_back(Scripted::Reply(reply.into()));
+    }
+
+    /// Make the next call fail, e.g. to exercise error replies.
+    pub fn push_error(&self, error: impl Into<String>) {
+        self.script
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push_back(Scripted::Error(error.into()));
+    }
+
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.script
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .len()
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(MockCall {
+                system_prompt: system_prompt.map(str::to_string),
+                message: message.to_string(),
+                model: model.to_string(),
+            });
+        let next = self
+            .script
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .pop_front();
+        match (next, &self.fallback) {
+            (Some(Scripted::Reply(reply)), _) => Ok(reply),
+            (Some(Scripted::Error(error)), _) => anyhow::bail!("{error}"),
+            (None, Some(fallback)) => {
+                fallback
+                    .chat_with_system(system_prompt, message, model, temperature)
+                    .await
+            }
+            (None, None) => {
+                anyhow::bail!("MockProvider: no scripted response left for {message:?}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replies_in_order_then_errors() {
+        let provider = MockProvider::with_replies(["one", "two"]);
+        assert_eq!(provider.chat("a", "m", 0.0).await.unwrap(), "one");
+        assert_eq!(provider.chat("b", "m", 0.0).await.unwrap(), "two");
+        assert!(provider.chat("c", "m", 0.0).await.is_err());
+        assert_eq!(provider.calls().len(), 3);
+        assert_eq!(provider.calls()[1].message, "b");
+    }
+
+    #[tokio::test]
+    async fn unscripted_calls_use_fallback() {
+        let provider = MockProvider::with_fallback(Box::new(MockProvider::with_replies(["fb"])));
+        provider.push_reply("scripted");
+        assert_eq!(provider.chat("a", "m", 0.0).await.unwrap(), "scripted");
+        assert_eq!(provider.chat("b", "m", 0.0).await.unwrap(), "fb");
+        assert_eq!(provider.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn scripted_error() {
+        let provider = MockProvider::new();
+        provider.push_error("rate limited");
+        let err = provider.chat("a", "m", 0.0).await.unwrap_err();
+        assert!(err.to_string().contains("rate limited"));
+    }
+}
@@ -0,0 +1,365 @@
+use super::{MemoryChannel, MockProvider};
+use crate::channels::{self, Channel, ChannelRuntime};
+use crate::config::Config;
+use crate::conversations::ConversationStore;
+use crate::memory;
+use crate::providers::recording::ReplayProvider;
+use crate::security::SecurityPolicy;
+use crate::tools::{self, Tool};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const MOCK_MODEL: &str = "mock-model";
+
+/// A deterministic end-to-end test, loaded from TOML.
+///
+/// ```toml
+/// name = "remembers preferences"
+/// workspace = "fixtures/skills-ws"   # optional, copied into a scratch workspace
+///
+/// [[steps]]
+/// user = "I like teal"
+/// reply = "Noted, teal it is."
+/// expect_reply_contains = ["teal"]
+///
+/// [[steps.tool_calls]]
+/// name = "memory_store"
+/// args = { key = "colour", content = "teal" }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    /// Directory (relative to the scenario file) seeded into the workspace.
+    #[serde(default)]
+    pub workspace: Option<PathBuf>,
+    /// Provider cassette (relative to the scenario file) that answers steps
+    /// without a scripted `reply`; record one with `zeroclaw agent --record`.
+    #[serde(default)]
+    pub cassette: Option<PathBuf>,
+    #[serde(default = "default_channel")]
+    pub channel: String,
+    #[serde(default = "default_sender")]
+    pub sender: String,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+/// One inbound message and what should happen in response.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Step {
+    pub user: String,
+    /// What the mock LLM answers.
+    pub reply: Option<String>,
+    /// Make the LLM call fail with this error instead.
+    pub provider_error: Option<String>,
+    /// Tool calls the model makes for this turn, run against the real tool registry.
+    pub tool_calls: Vec<ToolCall>,
+    pub expect_reply_contains: Vec<String>,
+    pub expect_reply_not_contains: Vec<String>,
+    /// Substrings of the prompt the provider received (system prompt + message).
+    pub expect_prompt_contains: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolCall {
+    pub name: String,
+    #[serde(default = "empty_args")]
+    pub args: serde_json::Value,
+    #[serde(default = "default_true")]
+    pub expect_success: bool,
+    #[serde(default)]
+    pub expect_output_contains: Vec<String>,
+}
+
+fn default_channel() -> String {
+    "test".into()
+}
+
+fn default_sender() -> String {
+    "tester".into()
+}
+
+fn empty_args() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read scenario {}", path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("Invalid scenario {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StepOutcome {
+    pub user: String,
+    pub reply: Option<String>,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps: Vec<StepOutcome>,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.steps.iter().all(|s| s.failures.is_empty())
+    }
+}
+
+/// Run `scenario` in a scratch workspace with the mock provider and an in-memory
+/// channel. Messages go through the same pipeline as `zeroclaw channel start`.
+pub async fn run(scenario: &Scenario, base_dir: &Path) -> Result<ScenarioReport> {
+    let tmp = tempfile::TempDir::new()?;
+    let workspace = tmp.path().join("workspace");
+    std::fs::create_dir_all(&workspace)?;
+    if let Some(ref seed) = scenario.workspace {
+        copy_dir(&base_dir.join(seed), &workspace)
+            .with_context(|| format!("Failed to seed workspace from {}", seed.display()))?;
+    }
+
+    let config = Config {
+        workspace_dir: workspace.clone(),
+        config_path: tmp.path().join("config.toml"),
+        default_provider: Some("mock".into()),
+        default_model: Some(MOCK_MODEL.into()),
+        ..Config::default()
+    };
+    let security = Arc::new(SecurityPolicy::from_config(&config.autonomy, &workspace));
+    let mem: Arc<dyn memory::Memory> =
+        Arc::from(memory::create_memory(&config.memory, &workspace, None)?);
+    let tools = tools::all_tools(
+        &security,
+        mem.clone(),
+        None,
+        &config.browser,
+        &config.integrations,
+        None,
+        &(Arc::new(crate::runtime::NativeRuntime::new())
+            as Arc<dyn crate::runtime::RuntimeAdapter>),
+    );
+    let tool_descs: Vec<(&str, &str)> = tools.iter().map(|t| (t.name(), t.description())).collect();
+    // Workspace skills only: open-skills would sync over the network.
+    let skills = crate::skills::load_workspace_skills(&workspace);
+    let system_prompt = channels::build_system_prompt(&workspace, MOCK_MODEL, &tool_descs, &skills);
+
+    let provider = Arc::new(match scenario.cassette {
+        Some(ref cassette) => MockProvider::with_fallback(Box::new(ReplayProvider::load(
+            &base_dir.join(cassette),
+            Vec::new(),
+        )?)),
+        None => MockProvider::new(),
+    });
+    let channel = Arc::new(MemoryChannel::new(scenario.channel.clone()));
+    let runtime = ChannelRuntime {
+        conversations: Some(ConversationStore::open(&workspace)?),
+        guardrails: crate::security::Guardrails::for_config(&config)?,
+        config,
+        channels: vec![channel.clone() as Arc<dyn Channel>],
+        provider: provider.clone(),
+        mem,
+        system_prompt,
+        model: MOCK_MODEL.into(),
+        observer: Arc::new(crate::observability::NoopObserver),
+        experiment: None,
+    };
+
+    let mut outcomes = Vec::new();
+    for step in &scenario.steps {
+        let mut failures = Vec::new();
+
+        for call in &step.tool_calls {
+            check_tool_call(&tools, call, &mut failures).await;
+        }
+
+        if let Some(ref reply) = step.reply {
+            provider.push_reply(reply.clone());
+        } else if let Some(ref error) = step.provider_error {
+            provider.push_error(error.clone());
+        }
+
+        let sent_before = channel.sent().len();
+        channels::process_message(&runtime, channel.inbound(&scenario.sender, &step.user)).await;
+
+        let reply = channel
+            .sent()
+            .into_iter()
+            .skip(sent_before)
+            .map(|m| m.content)
+            .reduce(|a, b| format!("{a}\n{b}"));
+        check_reply(step, reply.as_deref(), &provider, &mut failures);
+
+        outcomes.push(StepOutcome {
+            user: step.user.clone(),
+            reply,
+            failures,
+        });
+    }
+
+    Ok(ScenarioReport {
+        name: scenario.name.clone(),
+        steps: outcomes,
+    })
+}
+
+/// Check the step's reply and prompt expectations.
+fn check_reply(
+    step: &Step,
+    reply: Option<&str>,
+    provider: &MockProvider,
+    failures: &mut Vec<String>,
+) {
+    let text = reply.unwrap_or_default();
+    for needle in &step.expect_reply_contains {
+        if !text.contains(needle.as_str()) {
+            failures.push(format!("reply missing {needle:?}"));
+        }
+    }
+    for needle in &step.expect_reply_not_contains {
+        if text.contains(needle.as_str()) {
+            failures.push(format!("reply unexpectedly contains {needle:?}"));
+        }
+    }
+    if !step.expect_prompt_contains.is_empty() {
+        let prompt = provider
+            .calls()
+            .last()
+            .map(|c| {
+                format!(
+                    "{}\n{}",
+                    c.system_prompt.clone().unwrap_or_default(),
+                    c.message
+                )
+            })
+            .unwrap_or_default();
+        for needle in &step.expect_prompt_contains {
+            if !prompt.contains(needle.as_str()) {
+                failures.push(format!("prompt missing {needle:?}"));
+            }
+        }
+    }
+}
+
+/// Run one scripted tool call and record any expectation it misses.
+async fn check_tool_call(tools: &[Box<dyn Tool>], call: &ToolCall, failures: &mut Vec<String>) {
+    let Some(tool) = tools.iter().find(|t| t.name() == call.name) else {
+        failures.push(format!("unknown tool '{}'", call.name));
+        return;
+    };
+    match tool.execute(call.args.clone()).await {
+        Ok(result) => {
+            if result.success != call.expect_success {
+                failures.push(format!(
+                    "tool '{}' success={} (expected {}): {}",
+                    call.name,
+                    result.success,
+                    call.expect_success,
+                    result.error.as_deref().unwrap_or(&result.output)
+                ));
+            }
+            for needle in &call.expect_output_contains {
+                if !result.output.contains(needle.as_str()) {
+                    failures.push(format!("tool '{}' output missing {needle:?}", call.name));
+                }
+            }
+        }
+        Err(e) => failures.push(format!("tool '{}' errored: {e}", call.name)),
+    }
+}
+
+fn copy_dir(src: &Path, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let dest_path = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest_path)?;
+        } else {
+            std::fs::copy(entry.path(), &dest_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(raw: &str) -> Scenario {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[tokio::test]
+    async fn bundled_example_scenario_passes() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios/hello.toml");
+        let report = run(&Scenario::load(&path).unwrap(), path.parent().unwrap())
+            .await
+            .unwrap();
+        assert!(report.passed(), "{report:?}");
+    }
+
+    #[tokio::test]
+    async fn failed_expectations_are_reported() {
+        let s = scenario(
+            r#"
+            name = "mismatch"
+            [[steps]]
+            user = "hi"
+            reply = "Hello"
+            expect_reply_contains = ["Goodbye"]
+            [[steps.tool_calls]]
+            name = "no_such_tool"
+            "#,
+        );
+        let report = run(&s, Path::new(".")).await.unwrap();
+        assert!(!report.passed());
+        let failures = &report.steps[0].failures;
+        assert!(failures.iter().any(|f| f.contains("no_such_tool")));
+        assert!(failures.iter().any(|f| f.contains("Goodbye")));
+    }
+
+    #[tokio::test]
+    async fn cassette_answers_unscripted_steps() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("cassette.json"),
+            r#"{"version": 1, "interactions": [{"model": "gpt", "temperature": 0.7,
+                "message": "What's the weather?", "response": "Sunny, 21°C"}]}"#,
+        )
+        .unwrap();
+        let s = scenario(
+            r#"
+            name = "replayed"
+            cassette = "cassette.json"
+            [[steps]]
+            user = "What's the weather?"
+            expect_reply_contains = ["Sunny"]
+            "#,
+        );
+        assert!(run(&s, tmp.path()).await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn provider_errors_surface_as_error_replies() {
+        let s = scenario(
+            r#"
+            name = "outage"
+            [[steps]]
+            user = "hi"
+            provider_error = "upstream 503"
+            expect_reply_contains = ["⚠️ Error", "upstream 503"]
+            "#,
+        );
+        assert!(run(&s, Path::new(".")).await.unwrap().passed());
+    }
+}
@@ -0,0 +1,361 @@
+// Interactive approval — under `autonomy.level = "interactive"` every
+// write-class tool call waits for the user to say yes. Each tool says which
+// of its calls are write-class (`Tool::call_class`); `[autonomy.approvals]`
+// can override that per tool. One-shot runs ask on the terminal; the
+// interactive CLI puts the question in the conversation and its input loop
+// hands the next line over through `answer`.
+
+use super::middleware::ToolMiddleware;
+use super::reply::{choices_block, Choice};
+use super::traits::{Tool, ToolResult};
+use crate::channels::Channel;
+use crate::config::ApprovalsConfig;
+pub use crate::security::Approver;
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{IsTerminal, Write};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+fn is_yes(answer: &str) -> bool {
+    matches!(
+        answer.trim().to_lowercase().as_str(),
+        "y" | "yes" | "approve" | "ok" | "👍"
+    )
+}
+
+/// Asks on stdin; refuses outright when stdin is not a terminal (daemon, cron).
+pub struct ConsoleApprover {
+    timeout: Duration,
+}
+
+impl ConsoleApprover {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[async_trait]
+impl Approver for ConsoleApprover {
+    async fn confirm(&self, question: &str) -> bool {
+        if !std::io::stdin().is_terminal() {
+            eprintln!("  ⛔ {question} — no terminal to approve it, refused");
+            return false;
+        }
+        print!("\n  ⚠️  {question}\n  approve? [y/N] ");
+        let _ = std::io::stdout().flush();
+        let read = tokio::task::spawn_blocking(|| {
+            let mut line = String::new();
+            std::io::stdin().read_line(&mut line).map(|_| line)
+        });
+        matches!(
+            tokio::time::timeout(self.timeout, read).await,
+            Ok(Ok(Ok(line))) if is_yes(&line)
+        )
+    }
+}
+
+type Waiting = Mutex<HashMap<String, oneshot::Sender<String>>>;
+
+fn waiting() -> &'static Waiting {
+    static WAITING: OnceLock<Waiting> = OnceLock::new();
+    WAITING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Hand a message from `session` to the approval question waiting on it.
+/// Returns `true` if the message was the answer and must not be processed
+/// as a normal message.
+pub fn answer(session: &str, text: &str) -> bool {
+    let pending = waiting()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(session);
+    match pending {
+        Some(reply) => reply.send(text.to_string()).is_ok(),
+        None => false,
+    }
+}
+
+/// Asks `recipient` on `channel` and waits for their next message.
+pub struct ChannelApprover {
+    channel: Arc<dyn Channel>,
+    recipient: String,
+    timeout: Duration,
+}
+
+impl ChannelApprover {
+    pub fn new(channel: Arc<dyn Channel>, recipient: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            channel,
+            recipient: recipient.into(),
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl Approver for ChannelApprover {
+    async fn confirm(&self, question: &str) -> bool {
+        let session = crate::agent::session_key(self.channel.name(), &self.recipient);
+        let (tx, rx) = oneshot::channel();
+        waiting()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(session.clone(), tx);
+        let mut ask = format!("⚠️ {question}\napprove? y/n");
+        if self.channel.supports_choices() {
+            let buttons = [
+                Choice::new("✅ Approve", "yes"),
+                Choice::new("❌ Deny", "no"),
+            ];
+            ask = format!("{ask}\n{}", choices_block(&buttons));
+        }
+        let asked = self.channel.send(&ask, &self.recipient).await;
+        let approved = match asked {
+            Ok(()) => matches!(
+                tokio::time::timeout(self.timeout, rx).await,
+                Ok(Ok(reply)) if is_yes(&reply)
+            ),
+            Err(e) => {
+                tracing::warn!("Could not ask {session} for approval: {e}");
+                false
+            }
+        };
+        // Unanswered: don't let a late reply count for the next question.
+        waiting()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&session);
+        approved
+    }
+}
+
+/// Stops write-class tool calls until the [`Approver`] says yes.
+pub struct AskApproval {
+    overrides: BTreeMap<String, ToolClass>,
+    timeout: Duration,
+    approver: Arc<dyn Approver>,
+}
+
+impl AskApproval {
+    pub fn new(config: &ApprovalsConfig, approver: Arc<dyn Approver>) -> Self {
+        Self {
+            overrides: config.tools.clone(),
+            timeout: Duration::from_secs(config.timeout_secs),
+            approver,
+        }
+    }
+}
+
+/// What the user is asked, e.g. `Run shell: {"command":"rm -r build"}?`.
+fn question(tool: &str, args: &Value) -> String {
+    let args = crate::providers::scrub_secret_patterns(&args.to_string());
+    format!(
+        "Run {tool}: {}?",
+        crate::util::truncate_with_ellipsis(&args, 300)
+    )
+}
+
+#[async_trait]
+impl ToolMiddleware for AskApproval {
+    fn name(&self) -> &str {
+        "approval"
+    }
+
+    async fn before(
+        &self,
+        tool: &dyn Tool,
+        args: &mut Value,
+    ) -> anyhow::Result<Option<ToolResult>> {
+        if ToolClass::of(tool.name(), tool.call_class(args), &self.overrides) == ToolClass::Read {
+            return Ok(None);
+        }
+        if self.approver.confirm(&question(tool.name(), args)).await {
+            return Ok(None);
+        }
+        Ok(Some(ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!(
+                "The user did not approve this {} call (declined or no answer within {}s), \
+                 so it was not run. Don't retry it; ask what they would like instead.",
+                tool.name(),
+                self.timeout.as_secs()
+            )),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use serde_json::json;
+
+    struct Recorded(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl Channel for Recorded {
+        fn name(&self) -> &str {
+            "recorded"
+        }
+
+        async fn send(&self, message: &str, _recipient: &str) -> anyhow::Result<()> {
+            self.0.lock().unwrap().push(message.to_string());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Touch;
+
+    #[async_trait]
+    impl Tool for Touch {
+        fn name(&self) -> &str {
+            "fs_write"
+        }
+
+        fn description(&self) -> &str {
+            "writes"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn class(&self) -> ToolClass {
+            ToolClass::Write
+        }
+
+        async fn execute(&self, _args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: "written".into(),
+                error: None,
+            })
+        }
+    }
+
+    /// A network tool that can list or post.
+    struct Forum;
+
+    #[async_trait]
+    impl Tool for Forum {
+        fn name(&self) -> &str {
+            "forum"
+        }
+
+        fn description(&self) -> &str {
+            "reads and posts"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn needs_network(&self) -> bool {
+            true
+        }
+
+        fn call_class(&self, args: &Value) -> ToolClass {
+            if args["action"] == "list" {
+                ToolClass::Read
+            } else {
+                self.class()
+            }
+        }
+
+        async fn execute(&self, _args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: "done".into(),
+                error: None,
+            })
+        }
+    }
+
+    async fn call(
+        approval: Arc<AskApproval>,
+        session: &'static str,
+        reply: &'static str,
+    ) -> ToolResult {
+        let tools = super::super::middleware::apply(vec![Box::new(Touch)], vec![approval]);
+        let answering = tokio::spawn(async move {
+            while !answer(session, reply) {
+                tokio::task::yield_now().await;
+            }
+        });
+        let result = tools[0].execute(json!({"path": "a.txt"})).await.unwrap();
+        answering.await.unwrap();
+        result
+    }
+
+    #[tokio::test]
+    async fn write_calls_wait_for_the_senders_answer() {
+        let channel = Arc::new(Recorded(Mutex::new(Vec::new())));
+        let alice = ChannelApprover::new(channel.clone(), "alice", Duration::from_secs(5));
+        let approval = Arc::new(AskApproval::new(
+            &ApprovalsConfig::default(),
+            Arc::new(alice),
+        ));
+
+        let approved = call(approval.clone(), "recorded:alice", "yes").await;
+        assert_eq!(approved.output, "written");
+        let asked = channel.0.lock().unwrap()[0].clone();
+        assert!(asked.contains(r#"Run fs_write: {"path":"a.txt"}?"#));
+        assert!(asked.ends_with("approve? y/n"));
+
+        let declined = call(approval, "recorded:alice", "no way").await;
+        assert!(!declined.success);
+        assert!(declined.error.unwrap().contains("did not approve"));
+        assert!(!answer("recorded:alice", "y"));
+    }
+
+    #[tokio::test]
+    async fn read_class_calls_run_and_silence_means_no() {
+        let approver: Arc<dyn Approver> = Arc::new(ChannelApprover::new(
+            Arc::new(Recorded(Mutex::new(Vec::new()))),
+            "bob",
+            Duration::ZERO,
+        ));
+        let read_only = ApprovalsConfig {
+            tools: BTreeMap::from([("fs_write".to_string(), ToolClass::Read)]),
+            ..ApprovalsConfig::default()
+        };
+        let mut args = json!({});
+        let skipped = AskApproval::new(&read_only, approver.clone());
+        assert!(skipped.before(&Touch, &mut args).await.unwrap().is_none());
+
+        let unanswered = AskApproval::new(&ApprovalsConfig::default(), approver);
+        assert!(unanswered
+            .before(&Touch, &mut args)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(!answer("recorded:bob", "y"));
+    }
+
+    #[tokio::test]
+    async fn network_tools_ask_unless_the_call_only_reads() {
+        let approver: Arc<dyn Approver> = Arc::new(ChannelApprover::new(
+            Arc::new(Recorded(Mutex::new(Vec::new()))),
+            "carol",
+            Duration::ZERO,
+        ));
+        let approval = AskApproval::new(&ApprovalsConfig::default(), approver);
+        assert_eq!(Forum.class(), ToolClass::Write);
+        let mut list = json!({"action": "list"});
+        assert!(approval.before(&Forum, &mut list).await.unwrap().is_none());
+        let mut post = json!({"action": "post"});
+        assert!(approval.before(&Forum, &mut post).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,304 @@
+use super::cloud_cost::{CostSeries, CostSource, DailySpend};
+use crate::config::AwsCostConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const SERVICE: &str = "ce";
+const TARGET: &str = "AWSInsightsIndexService.GetCostAndUsage";
+/// Page cap so a misbehaving `NextPageToken` can't loop forever.
+const MAX_PAGES: usize = 20;
+
+/// AWS Cost Explorer backend (`GetCostAndUsage`, daily unblended cost by service).
+pub struct AwsCostExplorer {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    region: String,
+    client: LazyClient,
+}
+
+impl AwsCostExplorer {
+    pub fn new(config: &AwsCostConfig) -> Self {
+        Self {
+            access_key_id: config.access_key_id.clone(),
+            secret_access_key: config.secret_access_key.clone(),
+            session_token: config.session_token.clone(),
+            region: config.region.clone(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    fn host(&self) -> String {
+        format!("{SERVICE}.{}.amazonaws.com", self.region)
+    }
+
+    /// `Authorization` header value for a signed Cost Explorer POST.
+    fn authorization(&self, host: &str, body: &str, now: DateTime<Utc>) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.1".to_string()),
+            ("host", host.to_string()),
+            ("x-amz-date", amz_date.clone()),
+            ("x-amz-target", TARGET.to_string()),
+        ];
+        if let Some(ref token) = self.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        headers.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut canonical_headers = String::new();
+        for (k, v) in &headers {
+            let _ = writeln!(canonical_headers, "{k}:{}", v.trim());
+        }
+        let signed_headers = headers
+            .iter()
+            .map(|(k, _)| *k)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "POST\n/\n\n{canonical_headers}\n{signed_headers}\n{}",
+            hex::encode(Sha256::digest(body.as_bytes()))
+        );
+
+        let scope = format!("{date}/{}/{SERVICE}/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(&self.secret_access_key, &date, &self.region, SERVICE);
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.access_key_id
+        )
+    }
+
+    async fn fetch_page(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        page_token: Option<&str>,
+    ) -> anyhow::Result<CostAndUsageResponse> {
+        let mut body = json!({
+            "TimePeriod": {
+                "Start": start.format("%Y-%m-%d").to_string(),
+                "End": end.format("%Y-%m-%d").to_string(),
+            },
+            "Granularity": "DAILY",
+            "Metrics": ["UnblendedCost"],
+            "GroupBy": [{ "Type": "DIMENSION", "Key": "SERVICE" }],
+        });
+        if let Some(token) = page_token {
+            body["NextPageToken"] = json!(token);
+        }
+        let body = body.to_string();
+
+        let host = self.host();
+        let now = Utc::now();
+        let mut req = self
+            .client
+            .post(format!("https://{host}/"))
+            .header("Content-Type", "application/x-amz-json-1.1")
+            .header("X-Amz-Date", now.format("%Y%m%dT%H%M%SZ").to_string())
+            .header("X-Amz-Target", TARGET)
+            .header("Authorization", self.authorization(&host, &body, now));
+        if let Some(ref token) = self.session_token {
+            req = req.header("X-Amz-Security-Token", token);
+        }
+        let resp = req.body(body).send().await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("AWS Cost Explorer", resp).await);
+        }
+        Ok(resp.json().await?)
+    }
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub(crate) fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{secret}").as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+/// Fold Cost Explorer results into per-day, per-service spend.
+fn collect_days(results: Vec<ResultByTime>, currency: &mut String) -> Vec<DailySpend> {
+    results
+        .into_iter()
+        .filter_map(|result| {
+            let date = NaiveDate::parse_from_str(&result.time_period.start, "%Y-%m-%d").ok()?;
+            let mut by_service = BTreeMap::new();
+            for group in result.groups {
+                let Some(metric) = group.metrics.get("UnblendedCost") else {
+                    continue;
+                };
+                let amount: f64 = metric.amount.parse().unwrap_or(0.0);
+                if currency.is_empty() {
+                    currency.clone_from(&metric.unit);
+                }
+                let service = group.keys.into_iter().next().unwrap_or_default();
+                *by_service.entry(service).or_insert(0.0) += amount;
+            }
+            Some(DailySpend { date, by_service })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl CostSource for AwsCostExplorer {
+    fn name(&self) -> &str {
+        "aws"
+    }
+
+    fn display_name(&self) -> &str {
+        "AWS"
+    }
+
+    async fn daily_spend(&self, start: NaiveDate, end: NaiveDate) -> anyhow::Result<CostSeries> {
+        let mut series = CostSeries::default();
+        let mut page_token: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let page = self.fetch_page(start, end, page_token.as_deref()).await?;
+            // Group pages can split one day across responses, so merge per date.
+            for day in collect_days(page.results_by_time, &mut series.currency) {
+                match series.days.iter_mut().find(|d| d.date == day.date) {
+                    Some(existing) => {
+                        for (service, amount) in day.by_service {
+                            *existing.by_service.entry(service).or_insert(0.0) += amount;
+                        }
+                    }
+                    None => series.days.push(day),
+                }
+            }
+            page_token = page.next_page_token.filter(|t| !t.is_empty());
+            if page_token.is_none() {
+                break;
+            }
+        }
+        if series.currency.is_empty() {
+            series.currency = "USD".into();
+        }
+        series.days.sort_by_key(|d| d.date);
+        Ok(series)
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct CostAndUsageResponse {
+    #[serde(rename = "ResultsByTime", default)]
+    results_by_time: Vec<ResultByTime>,
+    #[serde(rename = "NextPageToken", default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResultByTime {
+    #[serde(rename = "TimePeriod")]
+    time_period: TimePeriod,
+    #[serde(rename = "Groups", default)]
+    groups: Vec<CostGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimePeriod {
+    #[serde(rename = "Start")]
+    start: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CostGroup {
+    #[serde(rename = "Keys", default)]
+    keys: Vec<String>,
+    #[serde(rename = "Metrics", default)]
+    metrics: BTreeMap<String, MetricValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetricValue {
+    #[serde(rename = "Amount")]
+    amount: String,
+    #[serde(rename = "Unit", default)]
+    unit: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_key_matches_aws_reference() {
+        // Example from the AWS SigV4 documentation
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn authorization_header_has_scope_and_signed_headers() {
+        let explorer = AwsCostExplorer::new(&AwsCostConfig {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "secret".into(),
+            session_token: Some("session".into()),
+            region: "us-east-1".into(),
+        });
+        let now = DateTime::parse_from_rfc3339("2026-10-14T07:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let auth = explorer.authorization("ce.us-east-1.amazonaws.com", "{}", now);
+        assert!(auth.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20261014/us-east-1/ce/aws4_request, "
+        ));
+        assert!(auth.contains(
+            "SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target, "
+        ));
+        // Deterministic for the same inputs
+        assert_eq!(
+            auth,
+            explorer.authorization("ce.us-east-1.amazonaws.com", "{}", now)
+        );
+    }
+
+    #[test]
+    fn results_are_grouped_by_day_and_service() {
+        let raw = r#"{"ResultsByTime": [
+            {"TimePeriod": {"Start": "2026-10-12", "End": "2026-10-13"}, "Groups": [
+                {"Keys": ["Amazon Elastic Compute Cloud - Compute"],
+                 "Metrics": {"UnblendedCost": {"Amount": "101.5", "Unit": "USD"}}},
+                {"Keys": ["Amazon Simple Storage Service"],
+                 "Metrics": {"UnblendedCost": {"Amount": "9.25", "Unit": "USD"}}}
+            ]},
+            {"TimePeriod": {"Start": "2026-10-13", "End": "2026-10-14"}, "Groups": []}
+        ]}"#;
+        let resp: CostAndUsageResponse = serde_json::from_str(raw).unwrap();
+        let mut currency = String::new();
+        let days = collect_days(resp.results_by_time, &mut currency);
+        assert_eq!(currency, "USD");
+        assert_eq!(days.len(), 2);
+        assert!((days[0].total() - 110.75).abs() < f64::EPSILON);
+        assert!(days[1].by_service.is_empty());
+    }
+}
@@ -0,0 +1,380 @@
+use super::code_forge::{
+    forge_client, forge_error, normalize_pipeline_status, CodeForge, ForgeIssue, ForgeMergeRequest,
+    ForgePipeline, ForgeState,
+};
+use crate::config::BitbucketConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+const BITBUCKET_API: &str = "https://api.bitbucket.org/2.0";
+const BITBUCKET_WEB: &str = "https://bitbucket.org";
+
+/// Bitbucket Cloud REST 2.0 backend (app-password auth).
+pub struct BitbucketForge {
+    username: String,
+    app_password: String,
+    default_repo: Option<String>,
+    client: LazyClient,
+}
+
+impl BitbucketForge {
+    pub fn new(config: &BitbucketConfig) -> Self {
+        Self {
+            username: config.username.clone(),
+            app_password: config.app_password.clone(),
+            default_repo: config.default_repo.clone(),
+            client: forge_client(),
+        }
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<T> {
+        let resp = self
+            .client
+            .get(url)
+            .basic_auth(&self.username, Some(&self.app_password))
+            .query(query)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(forge_error("Bitbucket", resp).await);
+        }
+        Ok(resp.json().await?)
+    }
+}
+
+fn repo_url(repo: &str) -> String {
+    format!(
+        "{BITBUCKET_API}/repositories/{}",
+        repo.trim().trim_matches('/')
+    )
+}
+
+/// Bitbucket's issue tracker has many terminal states; group them like GitHub does.
+fn issue_query(state: ForgeState) -> Option<&'static str> {
+    match state {
+        ForgeState::Open => Some(r#"state="new" OR state="open" OR state="on hold""#),
+        ForgeState::Closed => Some(
+            r#"state="resolved" OR state="closed" OR state="invalid" OR state="duplicate" OR state="wontfix""#,
+        ),
+        ForgeState::All => None,
+    }
+}
+
+fn pull_request_states(state: ForgeState) -> &'static [&'static str] {
+    match state {
+        ForgeState::Open => &["OPEN"],
+        ForgeState::Closed => &["MERGED", "DECLINED", "SUPERSEDED"],
+        ForgeState::All => &["OPEN", "MERGED", "DECLINED", "SUPERSEDED"],
+    }
+}
+
+#[async_trait]
+impl CodeForge for BitbucketForge {
+    fn name(&self) -> &str {
+        "bitbucket"
+    }
+
+    fn display_name(&self) -> &str {
+        "Bitbucket"
+    }
+
+    fn default_repo(&self) -> Option<&str> {
+        self.default_repo.as_deref()
+    }
+
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeIssue>> {
+        let url = format!("{}/issues", repo_url(repo));
+        let mut query = vec![("pagelen", limit.to_string())];
+        if let Some(q) = issue_query(state) {
+            query.push(("q", q.to_string()));
+        }
+        let page: Page<BitbucketIssue> = self.get_json(&url, &query).await?;
+        Ok(page.values.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_issue(&self, repo: &str, number: u64) -> anyhow::Result<ForgeIssue> {
+        let url = format!("{}/issues/{number}", repo_url(repo));
+        let issue: BitbucketIssue = self.get_json(&url, &[]).await?;
+        Ok(issue.into())
+    }
+
+    async fn list_merge_requests(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeMergeRequest>> {
+        let url = format!("{}/pullrequests", repo_url(repo));
+        let mut query = vec![("pagelen", limit.to_string())];
+        for s in pull_request_states(state) {
+            query.push(("state", (*s).to_string()));
+        }
+        let page: Page<BitbucketPullRequest> = self.get_json(&url, &query).await?;
+        Ok(page.values.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_pipelines(
+        &self,
+        repo: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgePipeline>> {
+        let url = format!("{}/pipelines/", repo_url(repo));
+        // Branch filtering isn't supported server-side, so over-fetch and filter.
+        let pagelen = if branch.is_some() { 100 } else { limit };
+        let page: Page<BitbucketPipeline> = self
+            .get_json(
+                &url,
+                &[
+                    ("sort", "-created_on".to_string()),
+                    ("pagelen", pagelen.to_string()),
+                ],
+            )
+            .await?;
+        Ok(page
+            .values
+            .into_iter()
+            .map(|p| p.into_forge(repo))
+            .filter(|p| branch.is_none_or(|b| p.branch == b))
+            .take(limit)
+            .collect())
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketUser {
+    display_name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BitbucketLinks {
+    #[serde(default)]
+    html: Option<BitbucketHref>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketHref {
+    href: String,
+}
+
+impl BitbucketLinks {
+    fn html_url(self) -> String {
+        self.html.map(|h| h.href).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketContent {
+    #[serde(default)]
+    raw: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketIssue {
+    id: u64,
+    title: String,
+    state: String,
+    #[serde(default)]
+    reporter: Option<BitbucketUser>,
+    #[serde(default)]
+    links: BitbucketLinks,
+    #[serde(default)]
+    content: Option<BitbucketContent>,
+}
+
+impl From<BitbucketIssue> for ForgeIssue {
+    fn from(i: BitbucketIssue) -> Self {
+        Self {
+            number: i.id,
+            title: i.title,
+            state: i.state,
+            author: i
+                .reporter
+                .map_or_else(|| "unknown".into(), |r| r.display_name),
+            url: i.links.html_url(),
+            body: i.content.and_then(|c| c.raw).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketBranchRef {
+    branch: BitbucketBranchName,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketBranchName {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketPullRequest {
+    id: u64,
+    title: String,
+    state: String,
+    author: BitbucketUser,
+    source: BitbucketBranchRef,
+    destination: BitbucketBranchRef,
+    #[serde(default)]
+    links: BitbucketLinks,
+}
+
+impl From<BitbucketPullRequest> for ForgeMergeRequest {
+    fn from(pr: BitbucketPullRequest) -> Self {
+        Self {
+            number: pr.id,
+            title: pr.title,
+            state: pr.state.to_lowercase(),
+            author: pr.author.display_name,
+            source_branch: pr.source.branch.name,
+            target_branch: pr.destination.branch.name,
+            url: pr.links.html_url(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketPipeline {
+    build_number: u64,
+    state: BitbucketPipelineState,
+    #[serde(default)]
+    target: Option<BitbucketPipelineTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketPipelineState {
+    name: String,
+    #[serde(default)]
+    result: Option<BitbucketNamed>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketNamed {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketPipelineTarget {
+    #[serde(default)]
+    ref_name: Option<String>,
+    #[serde(default)]
+    commit: Option<BitbucketCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketCommit {
+    hash: String,
+}
+
+impl BitbucketPipeline {
+    fn into_forge(self, repo: &str) -> ForgePipeline {
+        // Completed pipelines carry the outcome in `result`; otherwise the state is the status.
+        let raw_status = self.state.result.map_or(self.state.name, |r| r.name);
+        let (branch, commit) = self.target.map_or_else(
+            || (String::new(), String::new()),
+            |t| {
+                (
+                    t.ref_name.unwrap_or_default(),
+                    t.commit.map(|c| c.hash).unwrap_or_default(),
+                )
+            },
+        );
+        ForgePipeline {
+            id: self.build_number,
+            status: normalize_pipeline_status(&raw_status),
+            branch,
+            commit,
+            url: format!(
+                "{BITBUCKET_WEB}/{}/pipelines/results/{}",
+                repo.trim().trim_matches('/'),
+                self.build_number
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pull_request_states_cover_closed_variants() {
+        assert_eq!(pull_request_states(ForgeState::Open), &["OPEN"]);
+        assert!(pull_request_states(ForgeState::Closed).contains(&"MERGED"));
+        assert_eq!(pull_request_states(ForgeState::All).len(), 4);
+    }
+
+    #[test]
+    fn issue_query_omitted_for_all() {
+        assert!(issue_query(ForgeState::All).is_none());
+        assert!(issue_query(ForgeState::Open).unwrap().contains("\"open\""));
+    }
+
+    #[test]
+    fn pull_request_maps_to_merge_request() {
+        let raw = r#"{
+            "id": 5, "title": "Add feature", "state": "OPEN",
+            "author": {"display_name": "Carol"},
+            "source": {"branch": {"name": "feature"}},
+            "destination": {"branch": {"name": "main"}},
+            "links": {"html": {"href": "https://bitbucket.org/ws/repo/pull-requests/5"}}
+        }"#;
+        let mr: ForgeMergeRequest = serde_json::from_str::<BitbucketPullRequest>(raw)
+            .unwrap()
+            .into();
+        assert_eq!(mr.state, "open");
+        assert_eq!(mr.source_branch, "feature");
+        assert!(mr.url.ends_with("/pull-requests/5"));
+    }
+
+    #[test]
+    fn completed_pipeline_uses_result_status() {
+        let raw = r#"{
+            "build_number": 12,
+            "state": {"name": "COMPLETED", "result": {"name": "FAILED"}},
+            "target": {"ref_name": "main", "commit": {"hash": "deadbeef"}}
+        }"#;
+        let p = serde_json::from_str::<BitbucketPipeline>(raw)
+            .unwrap()
+            .into_forge("ws/repo");
+        assert_eq!(p.status, "failed");
+        assert_eq!(p.branch, "main");
+        assert_eq!(p.url, "https://bitbucket.org/ws/repo/pipelines/results/12");
+    }
+
+    #[test]
+    fn running_pipeline_uses_state_name() {
+        let raw = r#"{"build_number": 3, "state": {"name": "IN_PROGRESS"}}"#;
+        let p = serde_json::from_str::<BitbucketPipeline>(raw)
+            .unwrap()
+            .into_forge("ws/repo");
+        assert_eq!(p.status, "running");
+        assert!(p.branch.is_empty());
+    }
+
+    #[test]
+    fn issue_without_reporter_defaults_author() {
+        let raw = r#"{"id": 1, "title": "Bug", "state": "new"}"#;
+        let issue: ForgeIssue = serde_json::from_str::<BitbucketIssue>(raw).unwrap().into();
+        assert_eq!(issue.author, "unknown");
+        assert!(issue.url.is_empty());
+    }
+}
@@ -2,25 +2,45 @@
 //!
 //! This tool provides AI-optimized web browsing capabilities via the agent-browser CLI.
 //! It supports semantic element selection, accessibility snapshots, and JSON output
-//! for efficient LLM integration.
-
-use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+//! for efficient LLM integration. With `[browser].backend = "cdp"` the same actions
+//! drive a headless Chromium directly (see [`super::browser_cdp`]) instead.
+//!
+//! Either way every URL the page lands on is checked against the security
+//! module's [`DomainAllowlist`].
+
+use super::browser_cdp::{self, CdpBrowser};
+use super::traits::{Latency, Tool, ToolResult};
+use crate::artifacts::ArtifactStore;
+use crate::config::BrowserBackend;
+use crate::security::{DomainAllowlist, SecurityPolicy};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::process::Command;
+use tokio::sync::Mutex;
 use tracing::debug;
 
 /// Browser automation tool using agent-browser CLI
 pub struct BrowserTool {
     security: Arc<SecurityPolicy>,
-    allowed_domains: Vec<String>,
+    allowlist: DomainAllowlist,
     session_name: Option<String>,
+    backend: BrowserBackend,
+    chromium_path: Option<String>,
+    /// Headless Chromium for the `cdp` backend, launched on first use
+    cdp: Mutex<Option<CdpBrowser>>,
+    /// Where screenshots go when no path is given; the workspace otherwise
+    artifacts: Option<Arc<ArtifactStore>>,
 }
 
+/// Pause after clicks and key presses so a navigation they trigger can start
+const CDP_SETTLE: Duration = Duration::from_millis(300);
+const CDP_WAIT_TIMEOUT_MS: u64 = 10_000;
+const CDP_MAX_TEXT_CHARS: usize = 20_000;
+
 /// Response from agent-browser --json commands
 #[derive(Debug, Deserialize)]
 struct AgentBrowserResponse {
@@ -104,9 +124,68 @@ impl BrowserTool {
     ) -> Self {
         Self {
             security,
-            allowed_domains: normalize_domains(allowed_domains),
+            allowlist: DomainAllowlist::new(allowed_domains),
             session_name,
+            backend: BrowserBackend::default(),
+            chromium_path: None,
+            cdp: Mutex::new(None),
+            artifacts: None,
+        }
+    }
+
+    /// Select the backend; `chromium_path` only matters for `cdp`.
+    #[must_use]
+    pub fn with_backend(mut self, backend: BrowserBackend, chromium_path: Option<String>) -> Self {
+        self.backend = backend;
+        self.chromium_path = chromium_path;
+        self
+    }
+
+    /// Upload screenshots taken without a path and answer with their link.
+    #[must_use]
+    pub fn with_artifacts(mut self, store: Option<Arc<ArtifactStore>>) -> Self {
+        self.artifacts = store;
+        self
+    }
+
+    /// Take a screenshot with agent-browser into a temp file and upload it.
+    async fn upload_screenshot(
+        &self,
+        store: &ArtifactStore,
+        full_page: bool,
+    ) -> anyhow::Result<ToolResult> {
+        let temp =
+            std::env::temp_dir().join(format!("zeroclaw-screenshot-{}.png", uuid::Uuid::new_v4()));
+        let temp_path = temp.display().to_string();
+        let mut args = vec!["screenshot", temp_path.as_str()];
+        if full_page {
+            args.push("--full");
         }
+        let resp = self.run_command(&args).await?;
+        if !resp.success {
+            return self.to_result(resp);
+        }
+        let png = tokio::fs::read(&temp).await;
+        let _ = tokio::fs::remove_file(&temp).await;
+        Ok(match Self::share_screenshot(store, png?).await {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => cdp_failure(format!("Screenshot upload failed: {e:#}")),
+        })
+    }
+
+    /// Upload a screenshot to `store` and describe where it went.
+    async fn share_screenshot(store: &ArtifactStore, png: Vec<u8>) -> anyhow::Result<String> {
+        let size = png.len();
+        let name = format!(
+            "screenshot-{}.png",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        );
+        let url = store.upload(&name, png).await?;
+        Ok(format!("Screenshot ({size} bytes): {url}"))
     }
 
     /// Check if agent-browser CLI is available
@@ -123,39 +202,7 @@ impl BrowserTool {
 
     /// Validate URL against allowlist
     fn validate_url(&self, url: &str) -> anyhow::Result<()> {
-        let url = url.trim();
-
-        if url.is_empty() {
-            anyhow::bail!("URL cannot be empty");
-        }
-
-        // Allow file:// URLs for local testing
-        if url.starts_with("file://") {
-            return Ok(());
-        }
-
-        if !url.starts_with("https://") && !url.starts_with("http://") {
-            anyhow::bail!("Only http:// and https:// URLs are allowed");
-        }
-
-        if self.allowed_domains.is_empty() {
-            anyhow::bail!(
-                "Browser tool enabled but no allowed_domains configured. \
-                Add [browser].allowed_domains in config.toml"
-            );
-        }
-
-        let host = extract_host(url)?;
-
-        if is_private_host(&host) {
-            anyhow::bail!("Blocked local/private host: {host}");
-        }
-
-        if !host_matches_allowlist(&host, &self.allowed_domains) {
-            anyhow::bail!("Host '{host}' not in browser.allowed_domains");
-        }
-
-        Ok(())
+        self.allowlist.check_url(url)
     }
 
     /// Execute an agent-browser command
@@ -209,6 +256,10 @@ impl BrowserTool {
     /// Execute a browser action
     #[allow(clippy::too_many_lines)]
     async fn execute_action(&self, action: BrowserAction) -> anyhow::Result<ToolResult> {
+        if self.backend == BrowserBackend::Cdp {
+            return Ok(self.execute_cdp(action).await);
+        }
+
         match action {
             BrowserAction::Open { url } => {
                 self.validate_url(&url)?;
@@ -269,6 +320,9 @@ impl BrowserTool {
             }
 
             BrowserAction::Screenshot { path, full_page } => {
+                if let (None, Some(store)) = (&path, self.artifacts.as_deref()) {
+                    return self.upload_screenshot(store, full_page).await;
+                }
                 let mut args = vec!["screenshot"];
                 if let Some(ref p) = path {
                     args.push(p);
@@ -343,6 +397,222 @@ impl BrowserTool {
         }
     }
 
+    /// Run an action on the headless Chromium session, launching it on first use.
+    async fn execute_cdp(&self, action: BrowserAction) -> ToolResult {
+        let mut guard = self.cdp.lock().await;
+        if matches!(action, BrowserAction::Close) {
+            let output = if guard.take().is_some() {
+                "Browser closed"
+            } else {
+                "Browser was not running"
+            };
+            return ToolResult {
+                success: true,
+                output: output.into(),
+                error: None,
+            };
+        }
+
+        // Relaunch if Chromium crashed or was closed from outside
+        if guard.as_mut().is_some_and(|b| !b.is_alive()) {
+            *guard = None;
+        }
+        let browser = match guard.take() {
+            Some(browser) => guard.insert(browser),
+            None => match CdpBrowser::launch(self.chromium_path.as_deref()).await {
+                Ok(browser) => guard.insert(browser),
+                Err(e) => return cdp_failure(format!("{e:#}")),
+            },
+        };
+
+        let outcome = self.cdp_action(browser, action).await;
+
+        // Clicks, key presses and redirects can navigate anywhere; pull the
+        // page back out as soon as it lands outside the allowlist
+        if let Ok(url) = browser.current_url().await {
+            let internal = url.starts_with("about:") || url.starts_with("chrome-error:");
+            if !internal {
+                if let Err(e) = self.validate_url(&url) {
+                    let _ = browser.navigate("about:blank").await;
+                    return cdp_failure(format!("{e}; navigation was stopped"));
+                }
+            }
+        }
+
+        match outcome {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => cdp_failure(format!("{e:#}")),
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn cdp_action(
+        &self,
+        browser: &mut CdpBrowser,
+        action: BrowserAction,
+    ) -> anyhow::Result<String> {
+        match action {
+            BrowserAction::Open { url } => {
+                self.validate_url(&url)?;
+                browser.navigate(url.trim()).await?;
+                let title = browser.title().await?;
+                Ok(format!("Opened {} — {title}", browser.current_url().await?))
+            }
+
+            BrowserAction::Click { selector } => {
+                browser
+                    .evaluate(&browser_cdp::click_script(&selector))
+                    .await?;
+                tokio::time::sleep(CDP_SETTLE).await;
+                browser.wait_for_load().await?;
+                Ok(format!("Clicked {selector}"))
+            }
+
+            BrowserAction::Fill { selector, value } => {
+                browser
+                    .evaluate(&browser_cdp::fill_script(&selector, &value))
+                    .await?;
+                Ok(format!("Filled {selector}"))
+            }
+
+            BrowserAction::Type { selector, text } => {
+                browser
+                    .evaluate(&browser_cdp::element_script(
+                        &selector,
+                        "el.focus(); return true;",
+                    ))
+                    .await?;
+                browser.insert_text(&text).await?;
+                Ok(format!("Typed into {selector}"))
+            }
+
+            BrowserAction::GetText { selector } => {
+                let text = browser
+                    .evaluate(&browser_cdp::element_script(
+                        &selector,
+                        "return el.innerText;",
+                    ))
+                    .await?;
+                Ok(crate::util::truncate_with_ellipsis(
+                    text.as_str().unwrap_or_default(),
+                    CDP_MAX_TEXT_CHARS,
+                ))
+            }
+
+            BrowserAction::GetTitle => browser.title().await,
+
+            BrowserAction::GetUrl => browser.current_url().await,
+
+            BrowserAction::Screenshot { path, full_page } => {
+                if let (None, Some(store)) = (&path, self.artifacts.as_deref()) {
+                    let png = browser.screenshot(full_page).await?;
+                    return Self::share_screenshot(store, png).await;
+                }
+                let path = path.unwrap_or_else(|| {
+                    format!(
+                        "screenshots/screenshot-{}.png",
+                        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+                    )
+                });
+                if !self.security.is_path_allowed(&path) {
+                    anyhow::bail!("Path not allowed: {path}");
+                }
+                let png = browser.screenshot(full_page).await?;
+                let full_path = self.security.workspace_dir.join(&path);
+                if let Some(parent) = full_path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(&full_path, &png).await?;
+                Ok(format!(
+                    "Saved screenshot ({} bytes) to {}",
+                    png.len(),
+                    full_path.display()
+                ))
+            }
+
+            BrowserAction::Wait { selector, ms, text } => {
+                let (expression, what) = if let Some(selector) = selector {
+                    let selector_json = json!(selector);
+                    (
+                        format!("!!document.querySelector({selector_json})"),
+                        selector,
+                    )
+                } else if let Some(text) = text {
+                    let text_json = json!(text);
+                    (
+                        format!("!!document.body && document.body.innerText.includes({text_json})"),
+                        format!("text {text_json}"),
+                    )
+                } else {
+                    let millis = ms.unwrap_or(1000).min(CDP_WAIT_TIMEOUT_MS);
+                    tokio::time::sleep(Duration::from_millis(millis)).await;
+                    return Ok(format!("Waited {millis} ms"));
+                };
+                let timeout = Duration::from_millis(ms.unwrap_or(CDP_WAIT_TIMEOUT_MS));
+                if browser.wait_until(&expression, timeout).await? {
+                    Ok(format!("Found {what}"))
+                } else {
+                    anyhow::bail!("Timed out waiting for {what}")
+                }
+            }
+
+            BrowserAction::Press { key } => {
+                browser.press_key(&key).await?;
+                tokio::time::sleep(CDP_SETTLE).await;
+                browser.wait_for_load().await?;
+                Ok(format!("Pressed {key}"))
+            }
+
+            BrowserAction::Hover { selector } => {
+                browser
+                    .evaluate(&browser_cdp::element_script(
+                        &selector,
+                        "for (const type of ['mouseover', 'mouseenter']) \
+                         el.dispatchEvent(new MouseEvent(type, {bubbles: true})); return true;",
+                    ))
+                    .await?;
+                Ok(format!("Hovered {selector}"))
+            }
+
+            BrowserAction::Scroll { direction, pixels } => {
+                let px = i64::from(pixels.unwrap_or(500));
+                let (dx, dy) = match direction.as_str() {
+                    "up" => (0, -px),
+                    "down" => (0, px),
+                    "left" => (-px, 0),
+                    "right" => (px, 0),
+                    other => anyhow::bail!("Unknown scroll direction: {other}"),
+                };
+                browser
+                    .evaluate(&format!("window.scrollBy({dx}, {dy})"))
+                    .await?;
+                Ok(format!("Scrolled {direction} {px}px"))
+            }
+
+            BrowserAction::IsVisible { selector } => {
+                let visible = browser
+                    .evaluate(&browser_cdp::element_script(
+                        &selector,
+                        "const r = el.getBoundingClientRect(); const s = getComputedStyle(el); \
+                         return r.width > 0 && r.height > 0 && s.visibility !== 'hidden';",
+                    ))
+                    .await?;
+                Ok(visible.as_bool().unwrap_or(false).to_string())
+            }
+
+            BrowserAction::Snapshot { .. } | BrowserAction::Find { .. } => anyhow::bail!(
+                "snapshot and find need the agent-browser backend; \
+                 use CSS selectors with the cdp backend"
+            ),
+
+            BrowserAction::Close => Ok("Browser closed".into()),
+        }
+    }
+
     #[allow(clippy::unnecessary_wraps, clippy::unused_self)]
     fn to_result(&self, resp: AgentBrowserResponse) -> anyhow::Result<ToolResult> {
         if resp.success {
@@ -365,6 +635,14 @@ impl BrowserTool {
     }
 }
 
+fn cdp_failure(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 #[async_trait]
 impl Tool for BrowserTool {
@@ -372,11 +650,20 @@ impl Tool for BrowserTool {
         "browser"
     }
 
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn latency(&self) -> Latency {
+        Latency::Slow
+    }
+
     fn description(&self) -> &str {
         "Web browser automation using agent-browser. Supports navigation, clicking, \
         filling forms, taking screenshots, and getting accessibility snapshots with refs. \
         Use 'snapshot' to get interactive elements with refs (@e1, @e2), then use refs \
-        for precise element interaction. Allowed domains only."
+        for precise element interaction; when snapshot is unavailable, use CSS selectors. \
+        Allowed domains only."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -437,7 +724,7 @@ impl Tool for BrowserTool {
                 },
                 "path": {
                     "type": "string",
-                    "description": "File path for screenshot"
+                    "description": "File path for screenshot; leave out to get a link when artifact storage is on"
                 },
                 "ms": {
                     "type": "integer",
@@ -481,7 +768,7 @@ impl Tool for BrowserTool {
         }
 
         // Check if agent-browser is available
-        if !Self::is_available().await {
+        if self.backend == BrowserBackend::AgentBrowser && !Self::is_available().await {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -658,187 +945,10 @@ impl Tool for BrowserTool {
     }
 }
 
-// ── Helper functions ─────────────────────────────────────────────
-
-fn normalize_domains(domains: Vec<String>) -> Vec<String> {
-    domains
-        .into_iter()
-        .map(|d| d.trim().to_lowercase())
-        .filter(|d| !d.is_empty())
-        .collect()
-}
-
-fn extract_host(url_str: &str) -> anyhow::Result<String> {
-    // Simple host extraction without url crate
-    let url = url_str.trim();
-    let without_scheme = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))
-        .or_else(|| url.strip_prefix("file://"))
-        .unwrap_or(url);
-
-    // Extract host — handle bracketed IPv6 addresses like [::1]:8080
-    let authority = without_scheme.split('/').next().unwrap_or(without_scheme);
-
-    let host = if authority.starts_with('[') {
-        // IPv6: take everything up to and including the closing ']'
-        authority.find(']').map_or(authority, |i| &authority[..=i])
-    } else {
-        // IPv4 or hostname: take everything before the port separator
-        authority.split(':').next().unwrap_or(authority)
-    };
-
-    if host.is_empty() {
-        anyhow::bail!("Invalid URL: no host");
-    }
-
-    Ok(host.to_lowercase())
-}
-
-fn is_private_host(host: &str) -> bool {
-    // Strip brackets from IPv6 addresses like [::1]
-    let bare = host
-        .strip_prefix('[')
-        .and_then(|h| h.strip_suffix(']'))
-        .unwrap_or(host);
-
-    if bare == "localhost" {
-        return true;
-    }
-
-    // Parse as IP address to catch all representations (decimal, hex, octal, mapped)
-    if let Ok(ip) = bare.parse::<std::net::IpAddr>() {
-        return match ip {
-            std::net::IpAddr::V4(v4) => {
-                v4.is_loopback()
-                    || v4.is_private()
-                    || v4.is_link_local()
-                    || v4.is_unspecified()
-                    || v4.is_broadcast()
-            }
-            std::net::IpAddr::V6(v6) => {
-                let segs = v6.segments();
-                v6.is_loopback()
-                    || v6.is_unspecified()
-                    // Unique-local (fc00::/7) — IPv6 equivalent of RFC 1918
-                    || (segs[0] & 0xfe00) == 0xfc00
-                    // Link-local (fe80::/10)
-                    || (segs[0] & 0xffc0) == 0xfe80
-                    // IPv4-mapped addresses (::ffff:127.0.0.1)
-                    || v6.to_ipv4_mapped().is_some_and(|v4| {
-                        v4.is_loopback()
-                            || v4.is_private()
-                            || v4.is_link_local()
-                            || v4.is_unspecified()
-                            || v4.is_broadcast()
-                    })
-            }
-        };
-    }
-
-    // Fallback string patterns for hostnames that look like IPs but don't parse
-    // (e.g., partial addresses used in DNS names).
-    let string_patterns = [
-        "127.", "10.", "192.168.", "0.0.0.0", "172.16.", "172.17.", "172.18.", "172.19.",
-        "172.20.", "172.21.", "172.22.", "172.23.", "172.24.", "172.25.", "172.26.", "172.27.",
-        "172.28.", "172.29.", "172.30.", "172.31.",
-    ];
-
-    string_patterns.iter().any(|p| bare.starts_with(p))
-}
-
-fn host_matches_allowlist(host: &str, allowed: &[String]) -> bool {
-    allowed.iter().any(|pattern| {
-        if pattern == "*" {
-            return true;
-        }
-        if pattern.starts_with("*.") {
-            // Wildcard subdomain match
-            let suffix = &pattern[1..]; // ".example.com"
-            host.ends_with(suffix) || host == &pattern[2..]
-        } else {
-            // Exact match or subdomain
-            host == pattern || host.ends_with(&format!(".{pattern}"))
-        }
-    })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn normalize_domains_works() {
-        let domains = vec![
-            "  Example.COM  ".into(),
-            "docs.example.com".into(),
-            String::new(),
-        ];
-        let normalized = normalize_domains(domains);
-        assert_eq!(normalized, vec!["example.com", "docs.example.com"]);
-    }
-
-    #[test]
-    fn extract_host_works() {
-        assert_eq!(
-            extract_host("https://example.com/path").unwrap(),
-            "example.com"
-        );
-        assert_eq!(
-            extract_host("https://Sub.Example.COM:8080/").unwrap(),
-            "sub.example.com"
-        );
-    }
-
-    #[test]
-    fn extract_host_handles_ipv6() {
-        // IPv6 with brackets (required for URLs with ports)
-        assert_eq!(extract_host("https://[::1]/path").unwrap(), "[::1]");
-        // IPv6 with brackets and port
-        assert_eq!(
-            extract_host("https://[2001:db8::1]:8080/path").unwrap(),
-            "[2001:db8::1]"
-        );
-        // IPv6 with brackets, trailing slash
-        assert_eq!(extract_host("https://[fe80::1]/").unwrap(), "[fe80::1]");
-    }
-
-    #[test]
-    fn is_private_host_detects_local() {
-        assert!(is_private_host("localhost"));
-        assert!(is_private_host("127.0.0.1"));
-        assert!(is_private_host("192.168.1.1"));
-        assert!(is_private_host("10.0.0.1"));
-        assert!(!is_private_host("example.com"));
-        assert!(!is_private_host("google.com"));
-    }
-
-    #[test]
-    fn is_private_host_catches_ipv6() {
-        assert!(is_private_host("::1"));
-        assert!(is_private_host("[::1]"));
-        assert!(is_private_host("0.0.0.0"));
-    }
-
-    #[test]
-    fn is_private_host_catches_mapped_ipv4() {
-        // IPv4-mapped IPv6 addresses
-        assert!(is_private_host("::ffff:127.0.0.1"));
-        assert!(is_private_host("::ffff:10.0.0.1"));
-        assert!(is_private_host("::ffff:192.168.1.1"));
-    }
-
-    #[test]
-    fn is_private_host_catches_ipv6_private_ranges() {
-        // Unique-local (fc00::/7)
-        assert!(is_private_host("fd00::1"));
-        assert!(is_private_host("fc00::1"));
-        // Link-local (fe80::/10)
-        assert!(is_private_host("fe80::1"));
-        // Public IPv6 should pass
-        assert!(!is_private_host("2001:db8::1"));
-    }
-
     #[test]
     fn validate_url_blocks_ipv6_ssrf() {
         let security = Arc::new(SecurityPolicy::default());
@@ -850,29 +960,6 @@ mod tests {
             .is_err());
     }
 
-    #[test]
-    fn host_matches_allowlist_exact() {
-        let allowed = vec!["example.com".into()];
-        assert!(host_matches_allowlist("example.com", &allowed));
-        assert!(host_matches_allowlist("sub.example.com", &allowed));
-        assert!(!host_matches_allowlist("notexample.com", &allowed));
-    }
-
-    #[test]
-    fn host_matches_allowlist_wildcard() {
-        let allowed = vec!["*.example.com".into()];
-        assert!(host_matches_allowlist("sub.example.com", &allowed));
-        assert!(host_matches_allowlist("example.com", &allowed));
-        assert!(!host_matches_allowlist("other.com", &allowed));
-    }
-
-    #[test]
-    fn host_matches_allowlist_star() {
-        let allowed = vec!["*".into()];
-        assert!(host_matches_allowlist("anything.com", &allowed));
-        assert!(host_matches_allowlist("example.org", &allowed));
-    }
-
     #[test]
     fn browser_tool_name() {
         let security = Arc::new(SecurityPolicy::default());
@@ -909,4 +996,17 @@ mod tests {
         let tool = BrowserTool::new(security, vec![], None);
         assert!(tool.validate_url("https://example.com").is_err());
     }
+
+    #[tokio::test]
+    async fn cdp_backend_reports_launch_failure() {
+        let security = Arc::new(SecurityPolicy::default());
+        let tool = BrowserTool::new(security, vec!["example.com".into()], None)
+            .with_backend(BrowserBackend::Cdp, Some("/nonexistent/chromium".into()));
+        let result = tool
+            .execute(json!({"action": "open", "url": "https://example.com"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Failed to start"));
+    }
 }
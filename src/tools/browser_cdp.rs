@@ -0,0 +1,406 @@
+//! Headless Chromium driven over the Chrome `DevTools` Protocol.
+//!
+//! Backend for the `browser` tool when `[browser].backend = "cdp"`. Chromium
+//! is launched on first use with `--remote-debugging-port=0`; the `DevTools`
+//! websocket address is read from its stderr, a page target is created and
+//! attached as a flat session, and every action is a JSON-RPC command on
+//! that socket. Element actions take CSS selectors.
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::process::{Child, Command};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(20);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+const LOAD_POLL: Duration = Duration::from_millis(100);
+const LOAD_POLLS: u32 = 150;
+
+const CHROMIUM_CANDIDATES: [&str; 7] = [
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+    "C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe",
+    "C:\\Program Files (x86)\\Microsoft\\Edge\\Application\\msedge.exe",
+];
+
+/// One headless Chromium process with a single attached page.
+pub struct CdpBrowser {
+    child: Child,
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    session_id: Option<String>,
+    next_id: u64,
+    profile_dir: PathBuf,
+}
+
+impl CdpBrowser {
+    /// Start Chromium (`chromium_path`, or the first one found on `PATH`)
+    /// and attach to a blank page.
+    pub async fn launch(chromium_path: Option<&str>) -> Result<Self> {
+        let binary = find_chromium(chromium_path).context(
+            "Chromium not found. Install chromium or set [browser].chromium_path in config.toml",
+        )?;
+        let profile_dir =
+            std::env::temp_dir().join(format!("zeroclaw-chromium-{}", uuid::Uuid::new_v4()));
+
+        let mut child = Command::new(&binary)
+            .args([
+                "--headless=new",
+                "--disable-gpu",
+                "--no-first-run",
+                "--no-default-browser-check",
+                "--disable-extensions",
+                "--remote-debugging-port=0",
+            ])
+            .arg(format!("--user-data-dir={}", profile_dir.display()))
+            .arg("about:blank")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", binary.display()))?;
+
+        let stderr = child.stderr.take().context("Chromium stderr unavailable")?;
+        let mut lines = BufReader::new(stderr).lines();
+        let ws_url = tokio::time::timeout(LAUNCH_TIMEOUT, async {
+            while let Some(line) = lines.next_line().await? {
+                if let Some(url) = devtools_url(&line) {
+                    return Ok(url);
+                }
+            }
+            anyhow::bail!("Chromium exited before opening DevTools")
+        })
+        .await
+        .context("Timed out waiting for Chromium DevTools")??;
+        // Keep draining stderr so a chatty Chromium never blocks on a full pipe
+        tokio::spawn(async move { while let Ok(Some(_)) = lines.next_line().await {} });
+
+        let (ws, _) = tokio_tungstenite::connect_async(&ws_url)
+            .await
+            .context("Failed to connect to Chromium DevTools")?;
+
+        let mut browser = Self {
+            child,
+            ws,
+            session_id: None,
+            next_id: 0,
+            profile_dir,
+        };
+        let target = browser
+            .call("Target.createTarget", json!({ "url": "about:blank" }))
+            .await?;
+        let target_id = target["targetId"]
+            .as_str()
+            .context("Target.createTarget returned no targetId")?
+            .to_string();
+        let attached = browser
+            .call(
+                "Target.attachToTarget",
+                json!({ "targetId": target_id, "flatten": true }),
+            )
+            .await?;
+        browser.session_id = Some(
+            attached["sessionId"]
+                .as_str()
+                .context("Target.attachToTarget returned no sessionId")?
+                .to_string(),
+        );
+        browser.call("Page.enable", json!({})).await?;
+        Ok(browser)
+    }
+
+    /// Whether the Chromium process is still running.
+    pub fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    async fn call(&mut self, method: &str, params: Value) -> Result<Value> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let message = command(id, method, params, self.session_id.as_deref());
+        self.ws.send(Message::Text(message.to_string())).await?;
+
+        let ws = &mut self.ws;
+        tokio::time::timeout(COMMAND_TIMEOUT, async move {
+            while let Some(frame) = ws.next().await {
+                let Message::Text(text) = frame? else {
+                    continue;
+                };
+                let reply: Value = serde_json::from_str(&text)?;
+                // Events and replies to other sessions share the socket
+                if reply.get("id").and_then(Value::as_u64) != Some(id) {
+                    continue;
+                }
+                if let Some(error) = reply.get("error") {
+                    anyhow::bail!(
+                        "{method} failed: {}",
+                        error["message"].as_str().unwrap_or("unknown error")
+                    );
+                }
+                return Ok(reply.get("result").cloned().unwrap_or(Value::Null));
+            }
+            anyhow::bail!("Chromium DevTools connection closed")
+        })
+        .await
+        .with_context(|| format!("{method} timed out"))?
+    }
+
+    /// Evaluate `expression` in the page and return its JSON value.
+    pub async fn evaluate(&mut self, expression: &str) -> Result<Value> {
+        let result = self
+            .call(
+                "Runtime.evaluate",
+                json!({
+                    "expression": expression,
+                    "returnByValue": true,
+                    "awaitPromise": true,
+                }),
+            )
+            .await?;
+        if let Some(details) = result.get("exceptionDetails") {
+            let message = details
+                .pointer("/exception/description")
+                .or_else(|| details.get("text"))
+                .and_then(Value::as_str)
+                .unwrap_or("script error");
+            anyhow::bail!("{}", message.lines().next().unwrap_or(message));
+        }
+        Ok(result
+            .pointer("/result/value")
+            .cloned()
+            .unwrap_or(Value::Null))
+    }
+
+    /// Navigate and wait for the document to finish loading.
+    pub async fn navigate(&mut self, url: &str) -> Result<()> {
+        let result = self.call("Page.navigate", json!({ "url": url })).await?;
+        if let Some(error) = result.get("errorText").and_then(Value::as_str) {
+            anyhow::bail!("Navigation failed: {error}");
+        }
+        self.wait_for_load().await
+    }
+
+    /// Wait (bounded) for `document.readyState` to reach `complete`.
+    pub async fn wait_for_load(&mut self) -> Result<()> {
+        for _ in 0..LOAD_POLLS {
+            if self.evaluate("document.readyState").await?.as_str() == Some("complete") {
+                return Ok(());
+            }
+            tokio::time::sleep(LOAD_POLL).await;
+        }
+        // Slow pages are still usable; act on whatever has rendered
+        Ok(())
+    }
+
+    /// Poll `expression` until it is truthy or `timeout` elapses.
+    pub async fn wait_until(&mut self, expression: &str, timeout: Duration) -> Result<bool> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if self.evaluate(expression).await?.as_bool() == Some(true) {
+                return Ok(true);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Ok(false);
+            }
+            tokio::time::sleep(LOAD_POLL).await;
+        }
+    }
+
+    pub async fn current_url(&mut self) -> Result<String> {
+        Ok(self
+            .evaluate("location.href")
+            .await?
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    pub async fn title(&mut self) -> Result<String> {
+        Ok(self
+            .evaluate("document.title")
+            .await?
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Capture the viewport (or the whole page) as PNG bytes.
+    pub async fn screenshot(&mut self, full_page: bool) -> Result<Vec<u8>> {
+        let result = self
+            .call(
+                "Page.captureScreenshot",
+                json!({ "format": "png", "captureBeyondViewport": full_page }),
+            )
+            .await?;
+        let data = result["data"]
+            .as_str()
+            .context("Page.captureScreenshot returned no data")?;
+        crate::util::base64_decode(data).context("Screenshot data is not valid base64")
+    }
+
+    /// Insert `text` at the current focus, as if typed.
+    pub async fn insert_text(&mut self, text: &str) -> Result<()> {
+        self.call("Input.insertText", json!({ "text": text }))
+            .await
+            .map(|_| ())
+    }
+
+    /// Press and release a key (`Enter`, `Tab`, `ArrowDown`, a single character…).
+    pub async fn press_key(&mut self, key: &str) -> Result<()> {
+        self.call("Input.dispatchKeyEvent", key_event("keyDown", key))
+            .await?;
+        self.call("Input.dispatchKeyEvent", key_event("keyUp", key))
+            .await
+            .map(|_| ())
+    }
+}
+
+impl Drop for CdpBrowser {
+    fn drop(&mut self) {
+        let _ = self.child.start_kill();
+        let _ = std::fs::remove_dir_all(&self.profile_dir);
+    }
+}
+
+fn find_chromium(configured: Option<&str>) -> Option<PathBuf> {
+    if let Some(path) = configured.map(str::trim).filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    CHROMIUM_CANDIDATES.iter().find_map(|candidate| {
+        let path = Path::new(candidate);
+        if path.is_absolute() {
+            return path.is_file().then(|| path.to_path_buf());
+        }
+        crate::platform::find_executable(candidate)
+    })
+}
+
+/// Extract the websocket URL from Chromium's `DevTools listening on ...` line.
+fn devtools_url(line: &str) -> Option<String> {
+    line.split_once("DevTools listening on ")
+        .map(|(_, url)| url.trim().to_string())
+        .filter(|url| url.starts_with("ws://"))
+}
+
+fn command(id: u64, method: &str, params: Value, session_id: Option<&str>) -> Value {
+    let mut message = json!({ "id": id, "method": method });
+    message["params"] = params;
+    if let Some(session_id) = session_id {
+        message["sessionId"] = json!(session_id);
+    }
+    message
+}
+
+fn key_event(kind: &str, key: &str) -> Value {
+    let (code, text) = match key {
+        "Enter" => (13, Some("\r")),
+        "Tab" => (9, None),
+        "Escape" => (27, None),
+        "Backspace" => (8, None),
+        "Delete" => (46, None),
+        "Space" | " " => (32, Some(" ")),
+        "ArrowLeft" => (37, None),
+        "ArrowUp" => (38, None),
+        "ArrowRight" => (39, None),
+        "ArrowDown" => (40, None),
+        "PageUp" => (33, None),
+        "PageDown" => (34, None),
+        "Home" => (36, None),
+        "End" => (35, None),
+        _ => (0, Some(key)),
+    };
+    let mut event = json!({ "type": kind, "key": key });
+    if code != 0 {
+        event["windowsVirtualKeyCode"] = json!(code);
+    }
+    if let (Some(text), "keyDown") = (text, kind) {
+        event["text"] = json!(text);
+    }
+    event
+}
+
+/// Wrap `body` in a script that binds `el` to the first match for `selector`
+/// and throws when nothing matches.
+pub fn element_script(selector: &str, body: &str) -> String {
+    let selector = json!(selector);
+    format!(
+        "(() => {{ const el = document.querySelector({selector}); \
+         if (!el) throw new Error('No element matches ' + {selector}); {body} }})()"
+    )
+}
+
+pub fn click_script(selector: &str) -> String {
+    element_script(
+        selector,
+        "el.scrollIntoView({block: 'center'}); el.click(); return true;",
+    )
+}
+
+pub fn fill_script(selector: &str, value: &str) -> String {
+    let value = json!(value);
+    element_script(
+        selector,
+        &format!(
+            "el.focus(); el.value = {value}; \
+             el.dispatchEvent(new Event('input', {{bubbles: true}})); \
+             el.dispatchEvent(new Event('change', {{bubbles: true}})); return true;"
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn devtools_url_parsed_from_stderr() {
+        let line = "DevTools listening on ws://127.0.0.1:41235/devtools/browser/abc-123";
+        assert_eq!(
+            devtools_url(line).as_deref(),
+            Some("ws://127.0.0.1:41235/devtools/browser/abc-123")
+        );
+        assert!(devtools_url("[0101/000000.1:ERROR] something else").is_none());
+    }
+
+    #[test]
+    fn commands_carry_session_once_attached() {
+        let before = command(1, "Target.createTarget", json!({}), None);
+        assert!(before.get("sessionId").is_none());
+        let after = command(
+            2,
+            "Page.navigate",
+            json!({ "url": "https://a.test" }),
+            Some("S1"),
+        );
+        assert_eq!(after["id"], 2);
+        assert_eq!(after["sessionId"], "S1");
+        assert_eq!(after["params"]["url"], "https://a.test");
+    }
+
+    #[test]
+    fn scripts_escape_selector_and_value() {
+        let script = fill_script("input[name=\"q\"]", "it's \"quoted\"");
+        assert!(script.contains(r#"document.querySelector("input[name=\"q\"]")"#));
+        assert!(script.contains(r#"el.value = "it's \"quoted\"""#));
+        assert!(click_script("#go").contains("el.click()"));
+    }
+
+    #[test]
+    fn enter_key_sends_carriage_return_on_key_down() {
+        let down = key_event("keyDown", "Enter");
+        assert_eq!(down["windowsVirtualKeyCode"], 13);
+        assert_eq!(down["text"], "\r");
+        assert!(key_event("keyUp", "Enter").get("text").is_none());
+        assert_eq!(key_event("keyDown", "a")["text"], "a");
+    }
+}
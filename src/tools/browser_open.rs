@@ -59,6 +59,10 @@ impl Tool for BrowserOpenTool {
         "browser_open"
     }
 
+    fn needs_network(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> &str {
         "Open an approved HTTPS URL in Brave Browser. Security constraints: allowlist-only domains, no local/private hosts, no scraping."
     }
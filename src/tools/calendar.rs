@@ -0,0 +1,527 @@
+// Calendar tool — upcoming events from iCalendar (.ics) feeds.
+//
+// Feeds are fetched on demand and parsed here rather than through a CalDAV
+// client: every major provider publishes a read-only .ics URL. Daily and
+// weekly RRULEs are expanded (with EXDATE and moved instances); times with
+// a TZID are read in the machine's local zone.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{CalendarConfig, CalendarFeed};
+use crate::security::ToolClass;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde_json::json;
+use std::fmt::Write;
+
+const DEFAULT_HOURS: u64 = 24;
+const MAX_HOURS: u64 = 24 * 14;
+/// Upper bound on generated occurrences per recurring event.
+const MAX_OCCURRENCES: usize = 2000;
+
+/// One occurrence of a calendar event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub calendar: String,
+    pub uid: String,
+    pub summary: String,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub all_day: bool,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    /// "Name <email>" or just the email
+    pub attendees: Vec<String>,
+    recurrence: Option<Recurrence>,
+    excluded: Vec<DateTime<Utc>>,
+    recurrence_id: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Recurrence {
+    freq: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<DateTime<Utc>>,
+    /// Weekdays for weekly rules, as days from Monday
+    by_day: Vec<u32>,
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", "\n")
+        .replace("\\N", "\n")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+/// `(value, all_day)` for a DTSTART/DTEND-style property.
+fn parse_time(params: &str, value: &str) -> Option<(DateTime<Utc>, bool)> {
+    if params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        let local = Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()?;
+        return Some((local.with_timezone(&Utc), true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((Utc.from_utc_datetime(&naive), false));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let local = Local.from_local_datetime(&naive).earliest()?;
+    Some((local.with_timezone(&Utc), false))
+}
+
+fn param<'a>(params: &'a str, name: &str) -> Option<&'a str> {
+    params.split(';').find_map(|p| {
+        let (key, value) = p.split_once('=')?;
+        key.eq_ignore_ascii_case(name)
+            .then(|| value.trim_matches('"'))
+    })
+}
+
+fn parse_rrule(value: &str) -> Option<Recurrence> {
+    let mut rule = Recurrence {
+        freq: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+    };
+    let mut freq = None;
+    for part in value.split(';') {
+        let (key, val) = part.split_once('=')?;
+        match key {
+            "FREQ" => {
+                freq = match val {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    // Monthly/yearly rules are rare for meetings; show the first one only.
+                    _ => None,
+                };
+            }
+            "INTERVAL" => rule.interval = val.parse().ok().filter(|&n| n > 0)?,
+            "COUNT" => rule.count = val.parse().ok(),
+            "UNTIL" => rule.until = parse_time("", val).map(|(t, _)| t),
+            "BYDAY" => {
+                const DAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+                rule.by_day = val
+                    .split(',')
+                    .filter_map(|d| DAYS.iter().position(|day| d.ends_with(day)))
+                    .filter_map(|i| u32::try_from(i).ok())
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    rule.freq = freq?;
+    Some(rule)
+}
+
+/// Every VEVENT in an iCalendar document, recurring ones unexpanded.
+pub fn parse_ics(text: &str, calendar: &str) -> Vec<CalendarEvent> {
+    // Unfold continuation lines (RFC 5545 §3.1).
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<CalendarEvent> = None;
+    let mut cancelled = false;
+    for line in &lines {
+        if line == "BEGIN:VEVENT" {
+            cancelled = false;
+            current = Some(CalendarEvent {
+                calendar: calendar.to_string(),
+                uid: String::new(),
+                summary: "(no title)".into(),
+                start: DateTime::<Utc>::MIN_UTC,
+                end: None,
+                all_day: false,
+                location: None,
+                description: None,
+                attendees: Vec::new(),
+                recurrence: None,
+                excluded: Vec::new(),
+                recurrence_id: None,
+            });
+            continue;
+        }
+        let Some(event) = current.as_mut() else {
+            continue;
+        };
+        if line == "END:VEVENT" {
+            if let Some(event) = current.take() {
+                if !cancelled && event.start != DateTime::<Utc>::MIN_UTC {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+        let Some((head, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = head.split_once(';').unwrap_or((head, ""));
+        match name {
+            "UID" => event.uid = value.to_string(),
+            "SUMMARY" => event.summary = unescape(value),
+            "LOCATION" if !value.is_empty() => event.location = Some(unescape(value)),
+            "DESCRIPTION" if !value.is_empty() => event.description = Some(unescape(value)),
+            "STATUS" => cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            "DTSTART" => {
+                if let Some((start, all_day)) = parse_time(params, value) {
+                    event.start = start;
+                    event.all_day = all_day;
+                }
+            }
+            "DTEND" => event.end = parse_time(params, value).map(|(t, _)| t),
+            "RRULE" => event.recurrence = parse_rrule(value),
+            "EXDATE" => event.excluded.extend(
+                value
+                    .split(',')
+                    .filter_map(|v| parse_time(params, v).map(|(t, _)| t)),
+            ),
+            "RECURRENCE-ID" => event.recurrence_id = parse_time(params, value).map(|(t, _)| t),
+            "ATTENDEE" => {
+                let email = value
+                    .strip_prefix("mailto:")
+                    .or_else(|| value.strip_prefix("MAILTO:"))
+                    .unwrap_or(value);
+                event.attendees.push(match param(params, "CN") {
+                    Some(cn) if cn != email => format!("{cn} <{email}>"),
+                    _ => email.to_string(),
+                });
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Start times of `event` from its first occurrence, in order.
+fn recurrence_starts(event: &CalendarEvent, rule: &Recurrence) -> Vec<DateTime<Utc>> {
+    let interval = i64::from(rule.interval);
+    let mut starts = Vec::new();
+    let mut push = |start: DateTime<Utc>| {
+        if rule.until.is_some_and(|until| start > until)
+            || rule.count.is_some_and(|count| starts.len() >= count)
+            || starts.len() >= MAX_OCCURRENCES
+        {
+            return false;
+        }
+        starts.push(start);
+        true
+    };
+    match rule.freq {
+        Frequency::Daily => {
+            for k in 0.. {
+                if !push(event.start + Duration::days(k * interval)) {
+                    break;
+                }
+            }
+        }
+        Frequency::Weekly => {
+            let first_day = event.start.weekday().num_days_from_monday();
+            let mut days = if rule.by_day.is_empty() {
+                vec![first_day]
+            } else {
+                rule.by_day.clone()
+            };
+            days.sort_unstable();
+            let week_start = event.start - Duration::days(i64::from(first_day));
+            'weeks: for week in 0.. {
+                for &day in &days {
+                    let start = week_start + Duration::days(week * 7 * interval + i64::from(day));
+                    if start < event.start {
+                        continue;
+                    }
+                    if !push(start) {
+                        break 'weeks;
+                    }
+                }
+            }
+        }
+    }
+    starts
+}
+
+/// Occurrences of `events` starting in `[from, to)`, sorted by start.
+pub fn occurrences(
+    events: &[CalendarEvent],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<CalendarEvent> {
+    let moved: Vec<(&str, DateTime<Utc>)> = events
+        .iter()
+        .filter_map(|e| e.recurrence_id.map(|id| (e.uid.as_str(), id)))
+        .collect();
+    let mut out = Vec::new();
+    for event in events {
+        let starts = match (&event.recurrence, event.recurrence_id) {
+            (Some(rule), None) => recurrence_starts(event, rule),
+            _ => vec![event.start],
+        };
+        let length = event.end.map(|end| end - event.start);
+        for start in starts {
+            if start < from || start >= to || event.excluded.contains(&start) {
+                continue;
+            }
+            let is_master = event.recurrence_id.is_none();
+            if is_master && moved.contains(&(event.uid.as_str(), start)) {
+                continue;
+            }
+            out.push(CalendarEvent {
+                start,
+                end: length.map(|len| start + len),
+                recurrence: None,
+                ..event.clone()
+            });
+        }
+    }
+    out.sort_by_key(|e| e.start);
+    out
+}
+
+pub fn http_client() -> LazyClient {
+    LazyClient::new(std::time::Duration::from_secs(30))
+}
+
+/// Fetch and parse one feed.
+pub async fn fetch(client: &LazyClient, feed: &CalendarFeed) -> anyhow::Result<Vec<CalendarEvent>> {
+    let resp = client.get(&feed.url).send().await?;
+    if !resp.status().is_success() {
+        return Err(crate::providers::api_error("Calendar", resp).await);
+    }
+    Ok(parse_ics(&resp.text().await?, &feed.name))
+}
+
+pub fn format_event(event: &CalendarEvent) -> String {
+    let start = event.start.with_timezone(&Local);
+    let mut line = if event.all_day {
+        format!("{} (all day)", start.format("%a %Y-%m-%d"))
+    } else {
+        let mut when = start.format("%a %Y-%m-%d %H:%M").to_string();
+        if let Some(end) = event.end {
+            let _ = write!(when, "–{}", end.with_timezone(&Local).format("%H:%M"));
+        }
+        when
+    };
+    let _ = write!(line, " [{}] {}", event.calendar, event.summary);
+    if let Some(location) = &event.location {
+        let _ = write!(line, " ({location})");
+    }
+    if !event.attendees.is_empty() {
+        let _ = write!(line, " — with {}", event.attendees.join(", "));
+    }
+    line
+}
+
+/// List upcoming events from the configured calendars.
+pub struct CalendarTool {
+    config: CalendarConfig,
+    client: LazyClient,
+}
+
+impl CalendarTool {
+    pub fn new(config: CalendarConfig) -> Self {
+        Self {
+            config,
+            client: http_client(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarTool {
+    fn name(&self) -> &str {
+        "calendar"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        "List upcoming events (time, title, location, attendees) from the user's calendars."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "hours": {
+                    "type": "integer",
+                    "description": "How far ahead to look (default 24, max 336)"
+                },
+                "calendar": {
+                    "type": "string",
+                    "description": "Only this calendar (by configured name)"
+                }
+            }
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let hours = args
+            .get("hours")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_HOURS)
+            .clamp(1, MAX_HOURS);
+        let only = args.get("calendar").and_then(|v| v.as_str());
+        let now = Utc::now();
+        let until = now + Duration::hours(i64::try_from(hours).unwrap_or(24));
+
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
+        for feed in &self.config.calendars {
+            if only.is_some_and(|name| !name.eq_ignore_ascii_case(&feed.name)) {
+                continue;
+            }
+            match fetch(&self.client, feed).await {
+                Ok(parsed) => events.extend(occurrences(&parsed, now, until)),
+                Err(e) => errors.push(format!("{}: {e}", feed.name)),
+            }
+        }
+        events.sort_by_key(|e| e.start);
+
+        if events.is_empty() && !errors.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(errors.join("; ")),
+            });
+        }
+        let mut output = if events.is_empty() {
+            format!("No events in the next {hours}h.")
+        } else {
+            format!("{} event(s) in the next {hours}h:", events.len())
+        };
+        for event in &events {
+            let _ = write!(output, "\n- {}", format_event(event));
+        }
+        for error in errors {
+            let _ = write!(output, "\n(failed to load {error})");
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:review-1\r\n\
+DTSTART:20261014T150000Z\r\n\
+DTEND:20261014T153000Z\r\n\
+SUMMARY:Design review\\, Q4\r\n\
+LOCATION:Room 4\r\n\
+ATTENDEE;CN=\"Jane Doe\";ROLE=REQ-PARTICIPANT:mailto:jane@example.com\r\n\
+ATTENDEE:mailto:bob@exam\r\n ple.com\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+DTSTART:20261012T090000Z\r\n\
+DTEND:20261012T091500Z\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=6\r\n\
+EXDATE:20261016T090000Z\r\n\
+SUMMARY:Standup\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+RECURRENCE-ID:20261019T090000Z\r\n\
+DTSTART:20261019T100000Z\r\n\
+SUMMARY:Standup (moved)\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:gone\r\n\
+DTSTART:20261014T120000Z\r\n\
+STATUS:CANCELLED\r\n\
+SUMMARY:Cancelled sync\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parses_events_attendees_and_escapes() {
+        let events = parse_ics(ICS, "work");
+        assert_eq!(events.len(), 3);
+        let review = &events[0];
+        assert_eq!(review.summary, "Design review, Q4");
+        assert_eq!(review.start, utc("2026-10-14T15:00:00Z"));
+        assert_eq!(review.end, Some(utc("2026-10-14T15:30:00Z")));
+        assert_eq!(
+            review.attendees,
+            ["Jane Doe <jane@example.com>", "bob@example.com"]
+        );
+        assert!(format_event(review).contains("[work] Design review, Q4 (Room 4) — with Jane Doe"));
+    }
+
+    #[test]
+    fn weekly_rules_expand_with_exdates_and_moved_instances() {
+        let events = parse_ics(ICS, "work");
+        let week = occurrences(
+            &events,
+            utc("2026-10-12T00:00:00Z"),
+            utc("2026-10-26T00:00:00Z"),
+        );
+        let standups: Vec<(String, DateTime<Utc>)> = week
+            .iter()
+            .filter(|e| e.uid == "standup")
+            .map(|e| (e.summary.clone(), e.start))
+            .collect();
+        assert_eq!(
+            standups,
+            [
+                ("Standup".to_string(), utc("2026-10-12T09:00:00Z")),
+                ("Standup".to_string(), utc("2026-10-14T09:00:00Z")),
+                ("Standup (moved)".to_string(), utc("2026-10-19T10:00:00Z")),
+                ("Standup".to_string(), utc("2026-10-21T09:00:00Z")),
+                // COUNT includes the excluded and moved dates.
+                ("Standup".to_string(), utc("2026-10-23T09:00:00Z")),
+            ]
+        );
+        // Fixed length carries over to each occurrence.
+        assert_eq!(week[0].end.unwrap() - week[0].start, Duration::minutes(15));
+    }
+
+    #[test]
+    fn daily_rules_stop_at_until() {
+        let ics = "BEGIN:VEVENT\nUID:d\nDTSTART:20261001T070000Z\nRRULE:FREQ=DAILY;INTERVAL=2;UNTIL=20261007T000000Z\nSUMMARY:Gym\nEND:VEVENT\n";
+        let days: Vec<u32> = occurrences(
+            &parse_ics(ics, "me"),
+            utc("2026-09-01T00:00:00Z"),
+            utc("2026-12-01T00:00:00Z"),
+        )
+        .iter()
+        .map(|e| e.start.day())
+        .collect();
+        assert_eq!(days, [1, 3, 5]);
+    }
+}
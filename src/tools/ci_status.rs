@@ -0,0 +1,605 @@
+// CI status tool — latest pipeline runs across every configured repo and forge.
+//
+// `status` summarizes the newest run per repo, `explain` pulls the log tails of
+// a red run's failed jobs so the agent can say *why* it failed, and `watch` is
+// meant for cron: it remembers the last outcome per repo and only reports
+// red/green transitions, optionally posting "main is red" alerts to Slack.
+
+use super::code_forge::{status_icon, CodeForge, ForgePipeline};
+use super::reply::{StructuredReply, COLOR_FAILURE, COLOR_PENDING, COLOR_SUCCESS};
+use super::traits::{Tool, ToolResult};
+use crate::config::CiStatusConfig;
+use crate::security::ToolClass;
+use crate::util::LazyClient;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "ci_status.json";
+/// Runs scanned when looking for the latest failure to explain.
+const EXPLAIN_SCAN_LIMIT: usize = 10;
+
+/// Check, explain and watch CI across GitHub Actions, GitLab CI and Bitbucket Pipelines.
+pub struct CiStatusTool {
+    config: CiStatusConfig,
+    forges: Vec<Box<dyn CodeForge>>,
+    workspace_dir: PathBuf,
+    client: LazyClient,
+}
+
+/// Last terminal outcome seen per repo spec; drives `watch` transitions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchState {
+    #[serde(default)]
+    last_status: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transition {
+    BrokeBuild,
+    Recovered,
+}
+
+impl CiStatusTool {
+    pub fn new(
+        config: CiStatusConfig,
+        forges: Vec<Box<dyn CodeForge>>,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            config,
+            forges,
+            workspace_dir: workspace_dir.to_path_buf(),
+            client: LazyClient::new(std::time::Duration::from_secs(15)),
+        }
+    }
+
+    /// Resolve "forge:owner/repo" (or a bare path when only one forge is configured).
+    fn resolve<'a>(&self, spec: &'a str) -> anyhow::Result<(&dyn CodeForge, &'a str)> {
+        let spec = spec.trim();
+        if let Some((prefix, repo)) = spec.split_once(':') {
+            let forge = self
+                .forges
+                .iter()
+                .find(|f| f.name().eq_ignore_ascii_case(prefix.trim()))
+                .ok_or_else(|| anyhow::anyhow!("No '{prefix}' integration configured"))?;
+            return Ok((forge.as_ref(), repo.trim()));
+        }
+        match self.forges.as_slice() {
+            [only] => Ok((only.as_ref(), spec)),
+            [] => anyhow::bail!("No code forge integrations configured"),
+            _ => anyhow::bail!(
+                "Ambiguous repo '{spec}': prefix it with the forge, e.g. 'github:{spec}'"
+            ),
+        }
+    }
+
+    fn requested_specs(&self, args: &serde_json::Value) -> Vec<String> {
+        match args.get("repo").and_then(|v| v.as_str()).map(str::trim) {
+            Some(repo) if !repo.is_empty() => vec![repo.to_string()],
+            _ => self.config.repos.clone(),
+        }
+    }
+
+    fn branch<'a>(&'a self, args: &'a serde_json::Value) -> &'a str {
+        args.get("branch")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+            .unwrap_or(&self.config.branch)
+    }
+
+    async fn latest_run(&self, spec: &str, branch: &str) -> anyhow::Result<Option<ForgePipeline>> {
+        let (forge, repo) = self.resolve(spec)?;
+        Ok(forge
+            .list_pipelines(repo, Some(branch), 1)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn run_status(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let specs = self.requested_specs(args);
+        if specs.is_empty() {
+            anyhow::bail!("Missing 'repo' parameter and no [integrations.ci] repos configured");
+        }
+        let branch = self.branch(args);
+
+        let mut out = format!("Latest CI runs on {branch}:\n");
+        let mut statuses = Vec::new();
+        let mut card = StructuredReply::new(format!("CI on {branch}"));
+        for spec in &specs {
+            match self.latest_run(spec, branch).await {
+                Ok(Some(run)) => {
+                    let short_sha: String = run.commit.chars().take(8).collect();
+                    let icon = status_icon(&run.status);
+                    let _ = writeln!(
+                        out,
+                        "- {icon} {spec}: {} ({short_sha})\n  {}",
+                        run.status, run.url
+                    );
+                    card = card.field(
+                        spec.as_str(),
+                        format!("{icon} {} · [{short_sha}]({})", run.status, run.url),
+                        false,
+                    );
+                    statuses.push(run.status);
+                }
+                Ok(None) => {
+                    let _ = writeln!(out, "- {spec}: no runs found");
+                    card = card.field(spec.as_str(), "no runs found", false);
+                }
+                Err(e) => {
+                    let _ = writeln!(out, "- ⚠️ {spec}: {e}");
+                    card = card.field(spec.as_str(), format!("⚠️ {e}"), false);
+                }
+            }
+        }
+        Ok(card
+            .color(card_color(&statuses))
+            .into_result(out.trim_end()))
+    }
+
+    async fn run_explain(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let specs = self.requested_specs(args);
+        let spec = match specs.as_slice() {
+            [one] => one.clone(),
+            [] => anyhow::bail!("Missing 'repo' parameter"),
+            _ => anyhow::bail!("Pass 'repo' to choose which repo to explain"),
+        };
+        let branch = self.branch(args);
+        let (forge, repo) = self.resolve(&spec)?;
+
+        let run_id = if let Some(id) = args.get("pipeline_id").and_then(serde_json::Value::as_u64) {
+            id
+        } else {
+            let runs = match forge
+                .list_pipelines(repo, Some(branch), EXPLAIN_SCAN_LIMIT)
+                .await
+            {
+                Ok(runs) => runs,
+                Err(e) => return Ok(failed(format!("Failed to list runs: {e}"))),
+            };
+            let Some(run) = runs.into_iter().find(|r| r.status == "failed") else {
+                return Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "No failed runs among the last {EXPLAIN_SCAN_LIMIT} on {branch} in {spec}."
+                    ),
+                    error: None,
+                });
+            };
+            run.id
+        };
+
+        let logs = match forge.failed_job_logs(repo, run_id).await {
+            Ok(logs) => logs,
+            Err(e) => return Ok(failed(format!("Failed to fetch job logs: {e}"))),
+        };
+        if logs.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("Run #{run_id} in {spec} has no failed jobs."),
+                error: None,
+            });
+        }
+
+        let mut out = format!(
+            "Run #{run_id} in {spec}: {} failed job(s). Log tails follow; summarize the root cause.\n",
+            logs.len()
+        );
+        for job in &logs {
+            let _ = write!(out, "\n── {} ──\n{}\n{}\n", job.name, job.url, job.log_tail);
+        }
+        Ok(ToolResult {
+            success: true,
+            output: out.trim_end().to_string(),
+            error: None,
+        })
+    }
+
+    async fn run_watch(&self) -> anyhow::Result<ToolResult> {
+        if self.config.repos.is_empty() {
+            anyhow::bail!("No [integrations.ci] repos configured to watch");
+        }
+        let branch = &self.config.branch;
+        let mut state = self.load_state();
+        let mut alerts = Vec::new();
+        let mut problems = Vec::new();
+
+        for spec in &self.config.repos {
+            let run = match self.latest_run(spec, branch).await {
+                Ok(Some(run)) => run,
+                Ok(None) => continue,
+                Err(e) => {
+                    problems.push(format!("{spec}: {e}"));
+                    continue;
+                }
+            };
+            // Only settled outcomes count; a running pipeline keeps the previous state.
+            if run.status != "success" && run.status != "failed" {
+                continue;
+            }
+            let previous = state.last_status.get(spec).map(String::as_str);
+            match detect_transition(previous, &run.status) {
+                Some(Transition::BrokeBuild) => {
+                    alerts.push(format!("🔴 {branch} is red in {spec}\n{}", run.url));
+                }
+                Some(Transition::Recovered) => {
+                    alerts.push(format!("🟢 {branch} is green again in {spec}\n{}", run.url));
+                }
+                None => {}
+            }
+            state.last_status.insert(spec.clone(), run.status);
+        }
+        self.save_state(&state)?;
+
+        let mut out = if alerts.is_empty() {
+            format!("No CI changes on {branch}.")
+        } else {
+            alerts.join("\n\n")
+        };
+        if !alerts.is_empty() {
+            if let Some(ref url) = self.config.slack_webhook_url {
+                if let Err(e) = self.notify_slack(url, &alerts.join("\n\n")).await {
+                    let _ = write!(out, "\n\n⚠️ Slack notification failed: {e}");
+                }
+            }
+        }
+        if !problems.is_empty() {
+            let _ = write!(out, "\n\nCould not check:\n- {}", problems.join("\n- "));
+        }
+        Ok(ToolResult {
+            success: true,
+            output: out,
+            error: None,
+        })
+    }
+
+    async fn notify_slack(&self, webhook_url: &str, text: &str) -> anyhow::Result<()> {
+        let resp = self
+            .client
+            .post(webhook_url)
+            .json(&json!({ "text": text }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Slack webhook returned {}", resp.status());
+        }
+        Ok(())
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.workspace_dir.join("state").join(STATE_FILE)
+    }
+
+    fn load_state(&self) -> WatchState {
+        std::fs::read(self.state_path())
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_state(&self, state: &WatchState) -> anyhow::Result<()> {
+        let path = self.state_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_vec_pretty(state)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// A repo seen red for the first time counts as broken; green only matters after red.
+fn detect_transition(previous: Option<&str>, current: &str) -> Option<Transition> {
+    match (previous, current) {
+        (Some("failed"), "failed") => None,
+        (_, "failed") => Some(Transition::BrokeBuild),
+        (Some("failed"), "success") => Some(Transition::Recovered),
+        _ => None,
+    }
+}
+
+/// Red if anything failed, yellow while anything is still going, else green.
+fn card_color(statuses: &[String]) -> u32 {
+    if statuses.iter().any(|s| s == "failed") {
+        COLOR_FAILURE
+    } else if statuses.iter().any(|s| s != "success") {
+        COLOR_PENDING
+    } else {
+        COLOR_SUCCESS
+    }
+}
+
+fn failed(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+#[async_trait]
+impl Tool for CiStatusTool {
+    fn name(&self) -> &str {
+        "ci_status"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        "Check CI (GitHub Actions, GitLab CI, Bitbucket Pipelines) for configured repos. \
+         'status' shows the latest run per repo, 'explain' fetches failed job logs for a red run, \
+         'watch' reports red/green transitions since the last check and alerts Slack if configured."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["status", "explain", "watch"],
+                    "description": "Operation to perform (default: status)"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repo as 'forge:path', e.g. 'github:owner/repo' (defaults to configured repos)"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch to check (defaults to config, usually 'main')"
+                },
+                "pipeline_id": {
+                    "type": "integer",
+                    "description": "Run/pipeline to explain (default: latest failed run)"
+                }
+            }
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("status" | "explain") => ToolClass::Read,
+            _ => ToolClass::Write,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("status");
+        match action {
+            "status" => self.run_status(&args).await,
+            "explain" => self.run_explain(&args).await,
+            "watch" => self.run_watch().await,
+            _ => Ok(failed(format!(
+                "Unknown action '{action}'. Use 'status', 'explain', or 'watch'."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::code_forge::{ForgeIssue, ForgeJobLog, ForgeMergeRequest, ForgeState};
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Serves whatever pipeline status the test sets.
+    struct FakeForge {
+        name: &'static str,
+        status: Mutex<String>,
+    }
+
+    impl FakeForge {
+        fn boxed(name: &'static str, status: &str) -> Box<dyn CodeForge> {
+            Box::new(Self {
+                name,
+                status: Mutex::new(status.into()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CodeForge for FakeForge {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn display_name(&self) -> &str {
+            self.name
+        }
+        fn default_repo(&self) -> Option<&str> {
+            None
+        }
+        async fn list_issues(
+            &self,
+            _repo: &str,
+            _state: ForgeState,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<ForgeIssue>> {
+            Ok(Vec::new())
+        }
+        async fn get_issue(&self, _repo: &str, _number: u64) -> anyhow::Result<ForgeIssue> {
+            anyhow::bail!("unused")
+        }
+        async fn list_merge_requests(
+            &self,
+            _repo: &str,
+            _state: ForgeState,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<ForgeMergeRequest>> {
+            Ok(Vec::new())
+        }
+        async fn list_pipelines(
+            &self,
+            repo: &str,
+            branch: Option<&str>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<ForgePipeline>> {
+            Ok(vec![ForgePipeline {
+                id: 42,
+                status: self.status.lock().unwrap().clone(),
+                branch: branch.unwrap_or("main").into(),
+                commit: "0123456789abcdef".into(),
+                url: format!("https://{}/{repo}/runs/42", self.name),
+            }])
+        }
+        async fn failed_job_logs(
+            &self,
+            _repo: &str,
+            pipeline_id: u64,
+        ) -> anyhow::Result<Vec<ForgeJobLog>> {
+            assert_eq!(pipeline_id, 42);
+            Ok(vec![ForgeJobLog {
+                name: "test".into(),
+                url: "https://job".into(),
+                log_tail: "assertion failed: left == right".into(),
+            }])
+        }
+    }
+
+    fn tool(repos: &[&str], forges: Vec<Box<dyn CodeForge>>, dir: &Path) -> CiStatusTool {
+        CiStatusTool::new(
+            CiStatusConfig {
+                repos: repos.iter().map(|r| (*r).to_string()).collect(),
+                ..CiStatusConfig::default()
+            },
+            forges,
+            dir,
+        )
+    }
+
+    #[test]
+    fn transitions_only_fire_on_change() {
+        assert_eq!(
+            detect_transition(None, "failed"),
+            Some(Transition::BrokeBuild)
+        );
+        assert_eq!(
+            detect_transition(Some("success"), "failed"),
+            Some(Transition::BrokeBuild)
+        );
+        assert_eq!(detect_transition(Some("failed"), "failed"), None);
+        assert_eq!(
+            detect_transition(Some("failed"), "success"),
+            Some(Transition::Recovered)
+        );
+        assert_eq!(detect_transition(None, "success"), None);
+    }
+
+    #[test]
+    fn resolve_requires_prefix_with_multiple_forges() {
+        let tmp = TempDir::new().unwrap();
+        let t = tool(
+            &[],
+            vec![
+                FakeForge::boxed("github", "success"),
+                FakeForge::boxed("gitlab", "success"),
+            ],
+            tmp.path(),
+        );
+        let (forge, repo) = t.resolve("gitlab:group/app").unwrap();
+        assert_eq!(forge.name(), "gitlab");
+        assert_eq!(repo, "group/app");
+        assert!(t.resolve("group/app").is_err());
+        assert!(t.resolve("bitbucket:ws/app").is_err());
+    }
+
+    #[test]
+    fn bare_repo_uses_the_only_forge() {
+        let tmp = TempDir::new().unwrap();
+        let t = tool(&[], vec![FakeForge::boxed("github", "success")], tmp.path());
+        assert_eq!(t.resolve("owner/repo").unwrap().0.name(), "github");
+    }
+
+    #[tokio::test]
+    async fn status_lists_each_configured_repo() {
+        let tmp = TempDir::new().unwrap();
+        let t = tool(
+            &["github:a/one", "gitlab:b/two"],
+            vec![
+                FakeForge::boxed("github", "success"),
+                FakeForge::boxed("gitlab", "failed"),
+            ],
+            tmp.path(),
+        );
+        let result = t.execute(json!({})).await.unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("✅ github:a/one: success (01234567)"));
+        assert!(result.output.contains("❌ gitlab:b/two: failed"));
+
+        let (_, cards) = crate::tools::reply::extract(&result.output);
+        assert_eq!(cards[0].color, Some(COLOR_FAILURE));
+        assert_eq!(cards[0].fields[1].name, "gitlab:b/two");
+    }
+
+    #[tokio::test]
+    async fn explain_includes_failed_job_log() {
+        let tmp = TempDir::new().unwrap();
+        let t = tool(
+            &["github:a/one"],
+            vec![FakeForge::boxed("github", "failed")],
+            tmp.path(),
+        );
+        let result = t.execute(json!({"action": "explain"})).await.unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("Run #42 in github:a/one: 1 failed job(s)"));
+        assert!(result.output.contains("assertion failed"));
+    }
+
+    #[tokio::test]
+    async fn watch_alerts_once_then_reports_recovery() {
+        let tmp = TempDir::new().unwrap();
+        let t = tool(
+            &["github:a/one"],
+            vec![FakeForge::boxed("github", "failed")],
+            tmp.path(),
+        );
+
+        let first = t.execute(json!({"action": "watch"})).await.unwrap();
+        assert!(first.output.contains("🔴 main is red in github:a/one"));
+
+        let second = t.execute(json!({"action": "watch"})).await.unwrap();
+        assert_eq!(second.output, "No CI changes on main.");
+
+        // Re-create the tool with a green forge; state persists on disk.
+        let t = tool(
+            &["github:a/one"],
+            vec![FakeForge::boxed("github", "success")],
+            tmp.path(),
+        );
+        let third = t.execute(json!({"action": "watch"})).await.unwrap();
+        assert!(third.output.contains("🟢 main is green again"));
+    }
+
+    #[tokio::test]
+    async fn watch_ignores_running_pipelines() {
+        let tmp = TempDir::new().unwrap();
+        let t = tool(
+            &["github:a/one"],
+            vec![FakeForge::boxed("github", "running")],
+            tmp.path(),
+        );
+        let result = t.execute(json!({"action": "watch"})).await.unwrap();
+        assert_eq!(result.output, "No CI changes on main.");
+        assert!(t.load_state().last_status.is_empty());
+    }
+}
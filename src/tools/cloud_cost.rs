@@ -0,0 +1,469 @@
+// Cloud cost report — read-only daily spend deltas across billing backends.
+//
+// AWS Cost Explorer and the GCP billing export implement `CostSource`;
+// `CloudCostTool` pulls the last N complete days from each, compares the
+// latest day with the day before and with the trailing average, and calls
+// out anomalies (total or per-service). Meant for a morning digest cron job.
+
+use super::traits::{Cost, Tool, ToolResult};
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, Utc};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const DEFAULT_BASELINE_DAYS: u64 = 14;
+const MAX_BASELINE_DAYS: u64 = 60;
+const DEFAULT_THRESHOLD_PCT: f64 = 30.0;
+const DEFAULT_MIN_DELTA: f64 = 10.0;
+/// Services listed under "Top movers".
+const TOP_MOVERS: usize = 5;
+
+/// Spend for one calendar day (UTC), broken down by service.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DailySpend {
+    pub date: NaiveDate,
+    pub by_service: BTreeMap<String, f64>,
+}
+
+impl DailySpend {
+    pub fn total(&self) -> f64 {
+        self.by_service.values().sum()
+    }
+}
+
+/// Daily spend for a date range, as returned by a billing backend.
+#[derive(Debug, Clone, Default)]
+pub struct CostSeries {
+    pub currency: String,
+    pub days: Vec<DailySpend>,
+}
+
+/// Read-only access to a cloud billing backend.
+#[async_trait]
+pub trait CostSource: Send + Sync {
+    /// Short backend name used to pick a provider (e.g. "aws")
+    fn name(&self) -> &str;
+
+    /// Human-readable backend name for output
+    fn display_name(&self) -> &str;
+
+    /// Per-service spend for each day in `[start, end)`.
+    async fn daily_spend(&self, start: NaiveDate, end: NaiveDate) -> anyhow::Result<CostSeries>;
+}
+
+/// Thresholds a day has to exceed before it is called out.
+#[derive(Debug, Clone, Copy)]
+struct AnomalyRule {
+    /// Percent above the trailing average
+    threshold_pct: f64,
+    /// Absolute increase over the trailing average, in the series currency
+    min_delta: f64,
+}
+
+impl AnomalyRule {
+    fn is_anomalous(self, value: f64, baseline: f64) -> bool {
+        let delta = value - baseline;
+        delta >= self.min_delta
+            && (baseline <= 0.0 || delta / baseline * 100.0 >= self.threshold_pct)
+    }
+}
+
+/// Summarize spend and anomalies across every configured billing backend.
+pub struct CloudCostTool {
+    sources: Vec<Box<dyn CostSource>>,
+}
+
+impl CloudCostTool {
+    pub fn new(sources: Vec<Box<dyn CostSource>>) -> Self {
+        Self { sources }
+    }
+
+    async fn report(
+        &self,
+        source: &dyn CostSource,
+        baseline_days: u64,
+        rule: AnomalyRule,
+    ) -> anyhow::Result<String> {
+        // Today is still accruing, so the window ends at yesterday.
+        let end = Utc::now().date_naive();
+        let start = end
+            .checked_sub_days(Days::new(baseline_days + 1))
+            .ok_or_else(|| anyhow::anyhow!("Invalid date range"))?;
+        let series = source.daily_spend(start, end).await?;
+        let days = fill_days(start, end, series.days);
+        Ok(format_report(
+            source.display_name(),
+            &series.currency,
+            &days,
+            rule,
+        ))
+    }
+}
+
+/// One entry per day in `[start, end)`, with zero spend for days a backend skipped.
+fn fill_days(start: NaiveDate, end: NaiveDate, days: Vec<DailySpend>) -> Vec<DailySpend> {
+    let mut by_date: BTreeMap<NaiveDate, DailySpend> =
+        days.into_iter().map(|d| (d.date, d)).collect();
+    start
+        .iter_days()
+        .take_while(|date| *date < end)
+        .map(|date| {
+            by_date.remove(&date).unwrap_or(DailySpend {
+                date,
+                by_service: BTreeMap::new(),
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mean(values: impl Iterator<Item = f64>, count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        values.sum::<f64>() / count as f64
+    }
+}
+
+fn signed(value: f64) -> String {
+    if value >= 0.0 {
+        format!("+{value:.2}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+fn pct_change(value: f64, baseline: f64) -> Option<f64> {
+    (baseline > 0.0).then(|| (value - baseline) / baseline * 100.0)
+}
+
+fn format_report(backend: &str, currency: &str, days: &[DailySpend], rule: AnomalyRule) -> String {
+    let Some((latest, history)) = days.split_last() else {
+        return format!("No {backend} billing data for the requested window.");
+    };
+    let latest_total = latest.total();
+    let avg_total = mean(history.iter().map(DailySpend::total), history.len());
+
+    let mut out = format!("{backend} daily spend ({currency}) for {}:\n", latest.date);
+    let _ = write!(out, "Total: {latest_total:.2}");
+    if let Some(previous) = history.last() {
+        let delta = latest_total - previous.total();
+        let _ = write!(out, " ({} vs {}", signed(delta), previous.date);
+        if let Some(pct) = pct_change(latest_total, previous.total()) {
+            let _ = write!(out, ", {pct:+.1}%");
+        }
+        let _ = write!(out, "; {}-day avg {avg_total:.2})", history.len());
+    }
+    out.push('\n');
+
+    // Every service seen in the window, so a service that dropped to zero still shows.
+    let mut services: Vec<&str> = days
+        .iter()
+        .flat_map(|d| d.by_service.keys().map(String::as_str))
+        .collect();
+    services.sort_unstable();
+    services.dedup();
+
+    let service_cost = |day: &DailySpend, service: &str| -> f64 {
+        day.by_service.get(service).copied().unwrap_or(0.0)
+    };
+
+    if let Some(previous) = history.last() {
+        let mut movers: Vec<(&str, f64, f64)> = services
+            .iter()
+            .map(|s| {
+                let now = service_cost(latest, s);
+                (*s, now, now - service_cost(previous, s))
+            })
+            .filter(|(_, _, delta)| delta.abs() >= 0.01)
+            .collect();
+        movers.sort_by(|a, b| b.2.abs().total_cmp(&a.2.abs()));
+        if !movers.is_empty() {
+            out.push_str("Top movers:\n");
+            for (service, now, delta) in movers.into_iter().take(TOP_MOVERS) {
+                let _ = writeln!(out, "- {service}: {now:.2} ({})", signed(delta));
+            }
+        }
+    }
+
+    let mut anomalies = Vec::new();
+    if !history.is_empty() && rule.is_anomalous(latest_total, avg_total) {
+        let pct = pct_change(latest_total, avg_total).unwrap_or(0.0);
+        anomalies.push(format!(
+            "Total spend {latest_total:.2} is {pct:.0}% above the {}-day average ({avg_total:.2})",
+            history.len()
+        ));
+    }
+    for service in &services {
+        let now = service_cost(latest, service);
+        let avg = mean(
+            history.iter().map(|d| service_cost(d, service)),
+            history.len(),
+        );
+        if history.is_empty() || !rule.is_anomalous(now, avg) {
+            continue;
+        }
+        match pct_change(now, avg) {
+            Some(pct) => {
+                anomalies.push(format!("{service}: {now:.2} vs avg {avg:.2} ({pct:+.0}%)"));
+            }
+            None => anomalies.push(format!("{service}: new spend {now:.2}")),
+        }
+    }
+
+    if anomalies.is_empty() {
+        let _ = write!(
+            out,
+            "No anomalies (threshold +{:.0}% and +{:.2} over average).",
+            rule.threshold_pct, rule.min_delta
+        );
+    } else {
+        out.push_str("⚠️ Anomalies:\n");
+        for anomaly in &anomalies {
+            let _ = writeln!(out, "- {anomaly}");
+        }
+    }
+    out.trim_end().to_string()
+}
+
+#[async_trait]
+impl Tool for CloudCostTool {
+    fn name(&self) -> &str {
+        "cloud_cost"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn cost(&self) -> Cost {
+        Cost::Metered
+    }
+
+    fn description(&self) -> &str {
+        "Read-only cloud billing report: yesterday's spend per provider, the change versus the \
+         previous day and the trailing average, top movers by service, and anomaly callouts. \
+         Use it for morning digests and 'why did our bill jump' questions."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let providers: Vec<&str> = self.sources.iter().map(|s| s.name()).collect();
+        json!({
+            "type": "object",
+            "properties": {
+                "provider": {
+                    "type": "string",
+                    "enum": providers,
+                    "description": "Only report this provider (default: all configured)"
+                },
+                "baseline_days": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_BASELINE_DAYS,
+                    "description": "Days in the trailing average (default: 14)"
+                },
+                "threshold_pct": {
+                    "type": "number",
+                    "minimum": 0,
+                    "description": "Percent above average that counts as an anomaly (default: 30)"
+                },
+                "min_delta": {
+                    "type": "number",
+                    "minimum": 0,
+                    "description": "Ignore increases smaller than this amount (default: 10)"
+                }
+            }
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let baseline_days = args
+            .get("baseline_days")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_BASELINE_DAYS)
+            .clamp(1, MAX_BASELINE_DAYS);
+        let rule = AnomalyRule {
+            threshold_pct: args
+                .get("threshold_pct")
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(DEFAULT_THRESHOLD_PCT)
+                .max(0.0),
+            min_delta: args
+                .get("min_delta")
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(DEFAULT_MIN_DELTA)
+                .max(0.0),
+        };
+
+        let provider = args
+            .get("provider")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        let sources: Vec<&dyn CostSource> = self
+            .sources
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|s| provider.is_none_or(|p| s.name().eq_ignore_ascii_case(p)))
+            .collect();
+        if sources.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(match provider {
+                    Some(p) => format!("No '{p}' billing integration configured"),
+                    None => "No cloud billing integrations configured".into(),
+                }),
+            });
+        }
+
+        let mut sections = Vec::new();
+        let mut failures = 0;
+        for source in &sources {
+            match self.report(*source, baseline_days, rule).await {
+                Ok(section) => sections.push(section),
+                Err(e) => {
+                    failures += 1;
+                    sections.push(format!(
+                        "⚠️ {} billing request failed: {e}",
+                        source.display_name()
+                    ));
+                }
+            }
+        }
+
+        let output = sections.join("\n\n");
+        if failures == sources.len() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(output),
+            });
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn day(s: &str, services: &[(&str, f64)]) -> DailySpend {
+        DailySpend {
+            date: date(s),
+            by_service: services
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), *v))
+                .collect(),
+        }
+    }
+
+    const RULE: AnomalyRule = AnomalyRule {
+        threshold_pct: 30.0,
+        min_delta: 10.0,
+    };
+
+    struct FakeSource(anyhow::Result<CostSeries>);
+
+    #[async_trait]
+    impl CostSource for FakeSource {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn display_name(&self) -> &str {
+            "Fake"
+        }
+
+        async fn daily_spend(&self, _: NaiveDate, _: NaiveDate) -> anyhow::Result<CostSeries> {
+            match &self.0 {
+                Ok(series) => Ok(series.clone()),
+                Err(e) => anyhow::bail!("{e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn fill_days_inserts_missing_days() {
+        let days = fill_days(
+            date("2026-10-01"),
+            date("2026-10-04"),
+            vec![day("2026-10-03", &[("EC2", 5.0)])],
+        );
+        assert_eq!(days.len(), 3);
+        assert!(days[0].by_service.is_empty());
+        assert_eq!(days[2].date, date("2026-10-03"));
+        assert!((days[2].total() - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn anomaly_needs_both_percent_and_absolute_jump() {
+        assert!(RULE.is_anomalous(150.0, 100.0));
+        assert!(!RULE.is_anomalous(120.0, 100.0));
+        // +100% but only 5 more than usual
+        assert!(!RULE.is_anomalous(10.0, 5.0));
+        assert!(RULE.is_anomalous(12.0, 0.0));
+    }
+
+    #[test]
+    fn report_shows_delta_movers_and_anomalies() {
+        let days = vec![
+            day("2026-10-11", &[("EC2", 100.0), ("S3", 10.0)]),
+            day("2026-10-12", &[("EC2", 100.0), ("S3", 10.0)]),
+            day(
+                "2026-10-13",
+                &[("EC2", 105.0), ("S3", 60.0), ("Lambda", 15.0)],
+            ),
+        ];
+        let out = format_report("AWS", "USD", &days, RULE);
+        assert!(out.contains("for 2026-10-13"));
+        assert!(out.contains("Total: 180.00 (+70.00 vs 2026-10-12, +63.6%; 2-day avg 110.00)"));
+        assert!(out.contains("- S3: 60.00 (+50.00)"));
+        assert!(out.contains("Total spend 180.00 is 64% above"));
+        assert!(out.contains("S3: 60.00 vs avg 10.00 (+500%)"));
+        assert!(out.contains("Lambda: new spend 15.00"));
+        assert!(!out.contains("EC2: 105.00 vs avg"));
+    }
+
+    #[test]
+    fn steady_spend_has_no_anomalies() {
+        let days = vec![
+            day("2026-10-12", &[("EC2", 100.0)]),
+            day("2026-10-13", &[("EC2", 101.0)]),
+        ];
+        let out = format_report("AWS", "USD", &days, RULE);
+        assert!(out.contains("No anomalies"));
+    }
+
+    #[tokio::test]
+    async fn unknown_provider_is_reported() {
+        let tool = CloudCostTool::new(vec![Box::new(FakeSource(Ok(CostSeries::default())))]);
+        let result = tool.execute(json!({"provider": "gcp"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("'gcp'"));
+    }
+
+    #[tokio::test]
+    async fn backend_failure_is_not_success() {
+        let tool = CloudCostTool::new(vec![Box::new(FakeSource(Err(anyhow::anyhow!(
+            "403 AccessDenied"
+        ))))]);
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("403 AccessDenied"));
+    }
+}
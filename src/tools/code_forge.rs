@@ -0,0 +1,590 @@
+// Code forge abstraction — one tool surface for GitHub-style hosting services.
+//
+// Each forge (GitLab, Bitbucket, …) implements `CodeForge`; `CodeForgeTool`
+// wraps any implementation and exposes the same actions to the LLM, so an
+// organization's choice of host doesn't change what the agent can do.
+
+use super::traits::{Tool, ToolResult};
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+const MAX_LOG_TAIL_CHARS: usize = 4_000;
+
+/// Issue / merge-request state filter, mapped to each forge's vocabulary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeState {
+    Open,
+    Closed,
+    All,
+}
+
+impl ForgeState {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "open" | "opened" => Some(Self::Open),
+            "closed" | "merged" | "resolved" => Some(Self::Closed),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeIssue {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub author: String,
+    pub url: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeMergeRequest {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub author: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgePipeline {
+    pub id: u64,
+    /// Normalized status: "success", "failed", "running", "pending", "cancelled", …
+    pub status: String,
+    pub branch: String,
+    pub commit: String,
+    pub url: String,
+}
+
+/// Tail of a failed CI job's log, used to explain why a pipeline is red.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeJobLog {
+    pub name: String,
+    pub url: String,
+    pub log_tail: String,
+}
+
+/// Read access to a code hosting service's repos, issues, MRs and pipelines.
+#[async_trait]
+pub trait CodeForge: Send + Sync {
+    /// Short forge name; also used as the tool name (e.g. "gitlab")
+    fn name(&self) -> &str;
+
+    /// Human-readable service name for descriptions and output
+    fn display_name(&self) -> &str;
+
+    /// Repo used when the LLM doesn't pass one
+    fn default_repo(&self) -> Option<&str>;
+
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeIssue>>;
+
+    async fn get_issue(&self, repo: &str, number: u64) -> anyhow::Result<ForgeIssue>;
+
+    async fn list_merge_requests(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeMergeRequest>>;
+
+    async fn list_pipelines(
+        &self,
+        repo: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgePipeline>>;
+
+    /// Log tails of the failed jobs in a pipeline. Forges without log access keep the default.
+    async fn failed_job_logs(
+        &self,
+        _repo: &str,
+        _pipeline_id: u64,
+    ) -> anyhow::Result<Vec<ForgeJobLog>> {
+        anyhow::bail!("{} does not expose job logs", self.display_name())
+    }
+}
+
+/// Exposes any `CodeForge` backend as an agent tool.
+pub struct CodeForgeTool {
+    forge: Box<dyn CodeForge>,
+    description: String,
+}
+
+impl CodeForgeTool {
+    pub fn new(forge: Box<dyn CodeForge>) -> Self {
+        let description = format!(
+            "Read {} repositories: list/get issues, list merge requests, and check pipeline status. \
+             Actions: 'list_issues', 'get_issue', 'list_merge_requests', 'pipelines'.",
+            forge.display_name()
+        );
+        Self { forge, description }
+    }
+
+    fn resolve_repo<'a>(&'a self, args: &'a serde_json::Value) -> anyhow::Result<&'a str> {
+        args.get("repo")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .or_else(|| self.forge.default_repo())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing 'repo' parameter and no default repo configured for {}",
+                    self.forge.name()
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl Tool for CodeForgeTool {
+    fn name(&self) -> &str {
+        self.forge.name()
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list_issues", "get_issue", "list_merge_requests", "pipelines"],
+                    "description": "Operation to perform"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository path, e.g. 'group/project' or 'workspace/repo' (defaults to config)"
+                },
+                "state": {
+                    "type": "string",
+                    "enum": ["open", "closed", "all"],
+                    "description": "State filter for issues / merge requests (default: open)"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Issue number for 'get_issue'"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch filter for 'pipelines'"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": "Maximum results (default: 10)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let repo = self.resolve_repo(&args)?;
+        let state = match args.get("state").and_then(|v| v.as_str()) {
+            None => ForgeState::Open,
+            Some(raw) => {
+                ForgeState::parse(raw).ok_or_else(|| anyhow::anyhow!("Invalid 'state': {raw}"))?
+            }
+        };
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let result = match action {
+            "list_issues" => self
+                .forge
+                .list_issues(repo, state, limit)
+                .await
+                .map(|issues| format_issues(repo, &issues)),
+            "get_issue" => {
+                let number = args
+                    .get("number")
+                    .and_then(serde_json::Value::as_u64)
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'number' for get_issue"))?;
+                self.forge
+                    .get_issue(repo, number)
+                    .await
+                    .map(|issue| format_issue_detail(&issue))
+            }
+            "list_merge_requests" => self
+                .forge
+                .list_merge_requests(repo, state, limit)
+                .await
+                .map(|mrs| format_merge_requests(repo, &mrs)),
+            "pipelines" => {
+                let branch = args.get("branch").and_then(|v| v.as_str());
+                self.forge
+                    .list_pipelines(repo, branch, limit)
+                    .await
+                    .map(|pipelines| format_pipelines(repo, &pipelines))
+            }
+            _ => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Unknown action '{action}'. Use 'list_issues', 'get_issue', \
+                         'list_merge_requests', or 'pipelines'."
+                    )),
+                })
+            }
+        };
+
+        match result {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{} request failed: {e}", self.forge.display_name())),
+            }),
+        }
+    }
+}
+
+// ── Shared helpers for forge backends ───────────────────────────
+
+/// Default HTTP client for forge backends.
+pub fn forge_client() -> crate::util::LazyClient {
+    crate::util::LazyClient::new(std::time::Duration::from_secs(30)).with_user_agent("zeroclaw")
+}
+
+/// Turn a non-2xx response into an error with a short, secret-scrubbed body.
+pub async fn forge_error(forge: &str, response: reqwest::Response) -> anyhow::Error {
+    crate::providers::api_error(forge, response).await
+}
+
+/// Map forge-specific pipeline states onto a common vocabulary.
+pub fn normalize_pipeline_status(raw: &str) -> String {
+    match raw.to_ascii_lowercase().as_str() {
+        "success" | "successful" | "passed" | "completed" => "success".into(),
+        "failed" | "failure" | "error" | "timed_out" => "failed".into(),
+        "running" | "in_progress" => "running".into(),
+        "pending"
+        | "queued"
+        | "created"
+        | "waiting"
+        | "scheduled"
+        | "preparing"
+        | "waiting_for_resource"
+        | "requested" => "pending".into(),
+        "canceled" | "cancelled" | "stopped" | "skipped" => "cancelled".into(),
+        other => other.to_string(),
+    }
+}
+
+/// Keep the end of a CI log, where the failure usually is: strips ANSI colour
+/// codes and GitHub's per-line timestamps, then takes the last `max_lines`.
+pub fn log_tail(raw: &str, max_lines: usize) -> String {
+    let lines: Vec<String> = raw
+        .lines()
+        .map(|line| {
+            strip_log_timestamp(&strip_ansi(line))
+                .trim_end()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    let start = lines.len().saturating_sub(max_lines);
+    let tail = lines[start..].join("\n");
+    let count = tail.chars().count();
+    if count <= MAX_LOG_TAIL_CHARS {
+        return tail;
+    }
+    let kept: String = tail.chars().skip(count - MAX_LOG_TAIL_CHARS).collect();
+    format!("...{kept}")
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // CSI sequences end at the first ASCII letter
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// GitHub prefixes every log line with an RFC 3339 timestamp.
+fn strip_log_timestamp(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((ts, rest)) if chrono::DateTime::parse_from_rfc3339(ts).is_ok() => rest,
+        _ => line,
+    }
+}
+
+pub fn status_icon(status: &str) -> &'static str {
+    match status {
+        "success" => "✅",
+        "failed" => "❌",
+        "running" => "🔄",
+        "pending" => "⏳",
+        "cancelled" => "⏹️",
+        _ => "•",
+    }
+}
+
+fn format_issues(repo: &str, issues: &[ForgeIssue]) -> String {
+    if issues.is_empty() {
+        return format!("No issues found in {repo}.");
+    }
+    let mut out = format!("{} issue(s) in {repo}:\n", issues.len());
+    for issue in issues {
+        let _ = writeln!(
+            out,
+            "- #{} [{}] {} (by {})\n  {}",
+            issue.number, issue.state, issue.title, issue.author, issue.url
+        );
+    }
+    out.trim_end().to_string()
+}
+
+fn format_issue_detail(issue: &ForgeIssue) -> String {
+    let body = if issue.body.trim().is_empty() {
+        "(no description)".to_string()
+    } else {
+        crate::util::truncate_with_ellipsis(issue.body.trim(), 2_000)
+    };
+    format!(
+        "#{} {}\nState: {} | Author: {}\n{}\n\n{body}",
+        issue.number, issue.title, issue.state, issue.author, issue.url
+    )
+}
+
+fn format_merge_requests(repo: &str, mrs: &[ForgeMergeRequest]) -> String {
+    if mrs.is_empty() {
+        return format!("No merge requests found in {repo}.");
+    }
+    let mut out = format!("{} merge request(s) in {repo}:\n", mrs.len());
+    for mr in mrs {
+        let _ = writeln!(
+            out,
+            "- !{} [{}] {} ({} → {}, by {})\n  {}",
+            mr.number, mr.state, mr.title, mr.source_branch, mr.target_branch, mr.author, mr.url
+        );
+    }
+    out.trim_end().to_string()
+}
+
+fn format_pipelines(repo: &str, pipelines: &[ForgePipeline]) -> String {
+    if pipelines.is_empty() {
+        return format!("No pipelines found in {repo}.");
+    }
+    let mut out = format!("Latest pipelines in {repo}:\n");
+    for p in pipelines {
+        let short_sha: String = p.commit.chars().take(8).collect();
+        let _ = writeln!(
+            out,
+            "- {} #{} {} on {} ({short_sha})\n  {}",
+            status_icon(&p.status),
+            p.id,
+            p.status,
+            p.branch,
+            p.url
+        );
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeForge;
+
+    #[async_trait]
+    impl CodeForge for FakeForge {
+        fn name(&self) -> &str {
+            "fakeforge"
+        }
+        fn display_name(&self) -> &str {
+            "FakeForge"
+        }
+        fn default_repo(&self) -> Option<&str> {
+            None
+        }
+        async fn list_issues(
+            &self,
+            _repo: &str,
+            state: ForgeState,
+            limit: usize,
+        ) -> anyhow::Result<Vec<ForgeIssue>> {
+            assert_eq!(state, ForgeState::Closed);
+            Ok((1..=limit as u64)
+                .map(|n| ForgeIssue {
+                    number: n,
+                    title: format!("Issue {n}"),
+                    state: "closed".into(),
+                    author: "alice".into(),
+                    url: format!("https://forge/{n}"),
+                    body: String::new(),
+                })
+                .collect())
+        }
+        async fn get_issue(&self, _repo: &str, _number: u64) -> anyhow::Result<ForgeIssue> {
+            anyhow::bail!("not found")
+        }
+        async fn list_merge_requests(
+            &self,
+            _repo: &str,
+            _state: ForgeState,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<ForgeMergeRequest>> {
+            Ok(Vec::new())
+        }
+        async fn list_pipelines(
+            &self,
+            _repo: &str,
+            _branch: Option<&str>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<ForgePipeline>> {
+            Ok(vec![ForgePipeline {
+                id: 7,
+                status: "failed".into(),
+                branch: "main".into(),
+                commit: "abcdef1234567890".into(),
+                url: "https://forge/p/7".into(),
+            }])
+        }
+    }
+
+    #[test]
+    fn forge_state_parses_aliases() {
+        assert_eq!(ForgeState::parse("opened"), Some(ForgeState::Open));
+        assert_eq!(ForgeState::parse("Merged"), Some(ForgeState::Closed));
+        assert_eq!(ForgeState::parse("all"), Some(ForgeState::All));
+        assert_eq!(ForgeState::parse("bogus"), None);
+    }
+
+    #[test]
+    fn pipeline_status_normalizes_across_forges() {
+        assert_eq!(normalize_pipeline_status("SUCCESSFUL"), "success");
+        assert_eq!(normalize_pipeline_status("failure"), "failed");
+        assert_eq!(normalize_pipeline_status("in_progress"), "running");
+        assert_eq!(normalize_pipeline_status("canceled"), "cancelled");
+        assert_eq!(normalize_pipeline_status("manual"), "manual");
+    }
+
+    #[test]
+    fn log_tail_strips_noise_and_keeps_the_end() {
+        let raw = "2024-05-01T10:00:00.1234567Z setup\n\
+                   \u{1b}[31merror[E0308]: mismatched types\u{1b}[0m\n\
+                   \n\
+                   2024-05-01T10:00:02.0000000Z Process completed with exit code 101.";
+        let tail = log_tail(raw, 2);
+        assert_eq!(
+            tail,
+            "error[E0308]: mismatched types\nProcess completed with exit code 101."
+        );
+    }
+
+    #[tokio::test]
+    async fn job_logs_default_to_unsupported() {
+        let err = FakeForge.failed_job_logs("a/b", 1).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("FakeForge does not expose job logs"));
+    }
+
+    #[test]
+    fn tool_takes_name_from_forge() {
+        let tool = CodeForgeTool::new(Box::new(FakeForge));
+        assert_eq!(tool.name(), "fakeforge");
+        assert!(tool.description().contains("FakeForge"));
+    }
+
+    #[tokio::test]
+    async fn missing_repo_without_default_is_error() {
+        let tool = CodeForgeTool::new(Box::new(FakeForge));
+        let result = tool.execute(json!({"action": "list_issues"})).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_issues_respects_state_and_limit() {
+        let tool = CodeForgeTool::new(Box::new(FakeForge));
+        let result = tool
+            .execute(json!({"action": "list_issues", "repo": "a/b", "state": "closed", "limit": 2}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("2 issue(s) in a/b"));
+        assert!(result.output.contains("#2"));
+    }
+
+    #[tokio::test]
+    async fn backend_errors_become_failed_results() {
+        let tool = CodeForgeTool::new(Box::new(FakeForge));
+        let result = tool
+            .execute(json!({"action": "get_issue", "repo": "a/b", "number": 1}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("FakeForge request failed"));
+    }
+
+    #[tokio::test]
+    async fn pipelines_show_status_and_short_sha() {
+        let tool = CodeForgeTool::new(Box::new(FakeForge));
+        let result = tool
+            .execute(json!({"action": "pipelines", "repo": "a/b"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("❌ #7 failed on main (abcdef12)"));
+    }
+
+    #[tokio::test]
+    async fn unknown_action_returns_error() {
+        let tool = CodeForgeTool::new(Box::new(FakeForge));
+        let result = tool
+            .execute(json!({"action": "merge", "repo": "a/b"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
@@ -6,9 +6,9 @@
 // This is opt-in. Users who prefer sovereign/local-only mode skip this entirely.
 // The Composio API key is stored in the encrypted secret store.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Cost, Tool, ToolResult};
+use crate::util::LazyClient;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -17,18 +17,14 @@ const COMPOSIO_API_BASE: &str = "https://backend.composio.dev/api/v2";
 /// A tool that proxies actions to the Composio managed tool platform.
 pub struct ComposioTool {
     api_key: String,
-    client: Client,
+    client: LazyClient,
 }
 
 impl ComposioTool {
     pub fn new(api_key: &str) -> Self {
         Self {
             api_key: api_key.to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(60))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: LazyClient::new(std::time::Duration::from_secs(60)),
         }
     }
 
@@ -133,6 +129,14 @@ impl Tool for ComposioTool {
         "composio"
     }
 
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn cost(&self) -> Cost {
+        Cost::Metered
+    }
+
     fn description(&self) -> &str {
         "Execute actions on 1000+ apps via Composio (Gmail, Notion, GitHub, Slack, etc.). \
          Use action='list' to see available actions, or action='execute' with action_name and params."
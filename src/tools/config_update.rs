@@ -0,0 +1,162 @@
+// Config update tool — change a safe config.toml setting from chat.
+//
+// Every change is confirmed regardless of autonomy: the edit is validated,
+// its diff is put to the user out of band (terminal or chat), and the file
+// is written only on their yes.
+// The running agent keeps its loaded config; changes apply on next start.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::edit::{self, EditOp};
+use crate::security::{ApprovalGate, SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Validates, previews and writes single-key edits to `config.toml`
+pub struct ConfigUpdateTool {
+    config_path: PathBuf,
+    security: Arc<SecurityPolicy>,
+    approvals: ApprovalGate,
+}
+
+impl ConfigUpdateTool {
+    pub fn new(config_path: PathBuf, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config_path,
+            security,
+            approvals: ApprovalGate::default(),
+        }
+    }
+
+    async fn run(&self, args: &serde_json::Value) -> anyhow::Result<String> {
+        let key = args
+            .get("key")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))?;
+        let op = EditOp::parse(args.get("op").and_then(|v| v.as_str()).unwrap_or("set"))?;
+        let value = args
+            .get("value")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'value' parameter"))?;
+        let preview = edit::preview(&self.config_path, key, op, value)?;
+        if !preview.changed {
+            return Ok(format!("Nothing to change; {key} already has that value."));
+        }
+
+        let question = format!("Change config.toml?\n{}", preview.diff);
+        if !self.approvals.confirm(&question).await {
+            anyhow::bail!("The user did not approve this change; config.toml is unchanged.");
+        }
+
+        if !self.security.record_action() {
+            anyhow::bail!("Action blocked: rate limit exceeded");
+        }
+        edit::apply(&self.config_path, &preview)?;
+        tracing::info!("config.toml: {key} changed from chat");
+        Ok(format!(
+            "Updated config.toml:\n{}\nIt takes effect the next time ZeroClaw starts.",
+            preview.diff
+        ))
+    }
+}
+
+#[async_trait]
+impl Tool for ConfigUpdateTool {
+    fn name(&self) -> &str {
+        "config_update"
+    }
+
+    fn description(&self) -> &str {
+        "Change one ZeroClaw setting in config.toml, e.g. heartbeat.interval_minutes or \
+         channels_config.imessage.allowed_contacts. Only safe settings can be changed; provider \
+         keys, autonomy and gateway can't. The user is shown the diff and asked to confirm \
+         before anything is written."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "description": "Dotted setting name, e.g. 'heartbeat.interval_minutes'"
+                },
+                "op": {
+                    "type": "string",
+                    "enum": ["set", "add", "remove"],
+                    "description": "set replaces the value; add and remove change one entry of a list (default: set)"
+                },
+                "value": {
+                    "description": "New value, or the list entry to add or remove"
+                }
+            },
+            "required": ["key", "value"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+        match self.run(&args).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn change_is_written_only_after_confirmation() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "default_temperature = 0.7\n\n[heartbeat]\nenabled = true\ninterval_minutes = 60\n",
+        )
+        .unwrap();
+        let mut tool = ConfigUpdateTool::new(path.clone(), Arc::new(SecurityPolicy::default()));
+        let args = json!({"key": "heartbeat.interval_minutes", "value": 30});
+
+        let refused = tool.execute(args.clone()).await.unwrap();
+        assert!(!refused.success);
+        assert!(!refused.output.contains("approval_token"));
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("interval_minutes = 60"));
+
+        tool.approvals = ApprovalGate::answering(false);
+        let denied = tool.execute(args.clone()).await.unwrap();
+        assert!(denied.error.unwrap().contains("did not approve"));
+
+        tool.approvals = ApprovalGate::answering(true);
+        let applied = tool.execute(args).await.unwrap();
+        assert!(applied.output.starts_with("Updated config.toml"));
+        assert!(applied.output.contains("- 60\n+ 30"));
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("interval_minutes = 30"));
+    }
+}
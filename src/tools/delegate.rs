@@ -0,0 +1,225 @@
+// Delegate tool — fan parallel subtasks out to bounded sub-agents.
+//
+// Each subtask gets a fresh agent from the factory: an empty context, the
+// tools listed in `[agent.delegate].tools` and a limit on tool rounds.
+// Sub-agents run concurrently; their answers come back as one result, in
+// the order the subtasks were given, for the parent to combine.
+
+use super::traits::{Cost, Latency, Tool, ToolResult};
+use crate::agent::Agent;
+use crate::config::DelegateConfig;
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Builds one sub-agent; called once per subtask.
+pub type SubAgentFactory = Arc<dyn Fn() -> anyhow::Result<Agent> + Send + Sync>;
+
+/// System prompt for sub-agents.
+pub const SUBAGENT_PROMPT: &str = "You are a ZeroClaw sub-agent working on one subtask of a \
+    larger request. Another agent will combine your answer with others, so answer only the \
+    subtask, with concrete findings and their sources. You cannot ask the user anything.";
+
+/// Run subtasks on sub-agents and gather their answers
+pub struct DelegateTool {
+    config: DelegateConfig,
+    spawn: SubAgentFactory,
+    description: String,
+}
+
+impl DelegateTool {
+    pub fn new(config: DelegateConfig, spawn: SubAgentFactory) -> Self {
+        let description = format!(
+            "Split work into independent subtasks and run them in parallel on sub-agents (at \
+             most {}). Each sub-agent starts with no context, so every subtask must say \
+             everything needed. Sub-agents have these tools: {}. Use for parallel research or \
+             checks, not for steps that depend on each other.",
+            config.max_subtasks,
+            if config.tools.is_empty() {
+                "none".to_string()
+            } else {
+                config.tools.join(", ")
+            }
+        );
+        Self {
+            config,
+            spawn,
+            description,
+        }
+    }
+
+    async fn run(&self, tasks: Vec<String>) -> Vec<anyhow::Result<String>> {
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let mut running = tokio::task::JoinSet::new();
+        for (i, task) in tasks.into_iter().enumerate() {
+            let spawn = self.spawn.clone();
+            running.spawn(async move {
+                let answer = async {
+                    let agent = spawn()?;
+                    match tokio::time::timeout(timeout, agent.chat(&task)).await {
+                        Ok(answer) => answer,
+                        Err(_) => anyhow::bail!("timed out after {}s", timeout.as_secs()),
+                    }
+                };
+                (i, answer.await)
+            });
+        }
+        let mut answers: Vec<Option<anyhow::Result<String>>> = std::iter::repeat_with(|| None)
+            .take(running.len())
+            .collect();
+        while let Some(joined) = running.join_next().await {
+            match joined {
+                Ok((i, answer)) => answers[i] = Some(answer),
+                Err(e) => tracing::warn!("Sub-agent task panicked: {e}"),
+            }
+        }
+        answers
+            .into_iter()
+            .map(|answer| answer.unwrap_or_else(|| Err(anyhow::anyhow!("sub-agent crashed"))))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Tool for DelegateTool {
+    fn name(&self) -> &str {
+        "delegate"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "tasks": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "maxItems": self.config.max_subtasks,
+                    "description": "Self-contained subtasks, one per sub-agent"
+                }
+            },
+            "required": ["tasks"]
+        })
+    }
+
+    fn latency(&self) -> Latency {
+        Latency::Slow
+    }
+
+    // Every sub-agent makes its own model calls.
+    fn cost(&self) -> Cost {
+        Cost::Expensive
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let tasks: Vec<String> = args
+            .get("tasks")
+            .and_then(serde_json::Value::as_array)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'tasks' parameter"))?
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+        if tasks.is_empty() || tasks.len() > self.config.max_subtasks {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Give between 1 and {} subtasks",
+                    self.config.max_subtasks
+                )),
+            });
+        }
+
+        let answers = self.run(tasks.clone()).await;
+        let mut output = String::new();
+        let mut answered = 0;
+        for (i, (task, answer)) in tasks.iter().zip(answers).enumerate() {
+            let _ = writeln!(output, "## Subtask {}: {task}", i + 1);
+            match answer {
+                Ok(answer) => {
+                    answered += 1;
+                    let _ = writeln!(output, "{}\n", answer.trim());
+                }
+                Err(e) => {
+                    let _ = writeln!(output, "⚠️ Failed: {e:#}\n");
+                }
+            }
+        }
+        Ok(ToolResult {
+            success: answered > 0,
+            output: output.trim_end().to_string(),
+            error: (answered == 0).then(|| "Every subtask failed".to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::Provider;
+    use crate::testing::MockProvider;
+
+    fn delegate(provider: impl Fn() -> MockProvider + Send + Sync + 'static) -> DelegateTool {
+        DelegateTool::new(
+            DelegateConfig {
+                max_subtasks: 2,
+                ..DelegateConfig::default()
+            },
+            Arc::new(move || {
+                let provider: Box<dyn Provider> = Box::new(provider());
+                Agent::builder()
+                    .provider_boxed(provider)
+                    .model("m")
+                    .system_prompt(SUBAGENT_PROMPT)
+                    .auto_save(false)
+                    .build()
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn answers_come_back_in_task_order() {
+        let tool = delegate(|| MockProvider::with_replies(["Found three sources."]));
+        assert_eq!(tool.class(), ToolClass::Write);
+        let result = tool
+            .execute(json!({"tasks": ["research rust", "research zig"]}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let rust = result.output.find("## Subtask 1: research rust").unwrap();
+        let zig = result.output.find("## Subtask 2: research zig").unwrap();
+        assert!(rust < zig);
+        assert_eq!(result.output.matches("Found three sources.").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_subtasks_are_reported_and_limits_enforced() {
+        // An unscripted mock fails every call.
+        let tool = delegate(MockProvider::new);
+        let result = tool.execute(json!({"tasks": ["a"]})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("⚠️ Failed"));
+
+        let result = tool
+            .execute(json!({"tasks": ["a", "b", "c"]}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Give between 1 and 2 subtasks")
+        );
+    }
+}
@@ -0,0 +1,400 @@
+// Feature-flag abstraction — read and toggle flags across flag services.
+//
+// LaunchDarkly and Unleash implement `FeatureFlagService`; `FeatureFlagTool`
+// wraps a backend. Reads are free, but a toggle is a production change, so
+// under supervised autonomy the user is asked out of band (terminal or chat)
+// before the flag flips. Full autonomy toggles directly; read-only blocks.
+
+use super::traits::{Tool, ToolResult};
+use crate::security::{ApprovalGate, AutonomyLevel, SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+
+/// A flag's state in the configured environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFlag {
+    pub key: String,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Read/toggle access to a feature-flag service, scoped to one environment.
+#[async_trait]
+pub trait FeatureFlagService: Send + Sync {
+    /// Short service name; also used as the tool name (e.g. "launchdarkly")
+    fn name(&self) -> &str;
+
+    /// Human-readable service name for descriptions and output
+    fn display_name(&self) -> &str;
+
+    /// Environment this backend reads and toggles (e.g. "production")
+    fn environment(&self) -> &str;
+
+    async fn list_flags(&self, limit: usize) -> anyhow::Result<Vec<FeatureFlag>>;
+
+    async fn get_flag(&self, key: &str) -> anyhow::Result<FeatureFlag>;
+
+    async fn set_flag(&self, key: &str, enabled: bool) -> anyhow::Result<()>;
+}
+
+/// Exposes any `FeatureFlagService` backend as an agent tool.
+pub struct FeatureFlagTool {
+    service: Box<dyn FeatureFlagService>,
+    security: Arc<SecurityPolicy>,
+    approvals: ApprovalGate,
+    description: String,
+}
+
+impl FeatureFlagTool {
+    pub fn new(service: Box<dyn FeatureFlagService>, security: Arc<SecurityPolicy>) -> Self {
+        let description = format!(
+            "Read and toggle {} feature flags in the '{}' environment. Actions: 'list', 'get', \
+             'toggle'. In supervised mode the user is asked to confirm each toggle first.",
+            service.display_name(),
+            service.environment()
+        );
+        Self {
+            service,
+            security,
+            approvals: ApprovalGate::default(),
+            description,
+        }
+    }
+
+    async fn toggle(&self, key: &str, enabled: bool) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(blocked("Action blocked: autonomy is read-only"));
+        }
+
+        let env = self.service.environment();
+        let state = if enabled { "ON" } else { "OFF" };
+
+        if self.security.autonomy == AutonomyLevel::Supervised {
+            let question = format!(
+                "Turn {state} feature flag '{key}' in {} {env}?",
+                self.service.display_name()
+            );
+            if !self.approvals.confirm(&question).await {
+                return Ok(blocked(&format!(
+                    "The user did not approve turning {state} '{key}'; nothing has changed."
+                )));
+            }
+        }
+
+        if !self.security.record_action() {
+            return Ok(blocked("Action blocked: rate limit exceeded"));
+        }
+
+        match self.service.set_flag(key, enabled).await {
+            Ok(()) => {
+                tracing::info!(
+                    "Feature flag '{key}' turned {state} in {} {env}",
+                    self.service.display_name()
+                );
+                Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "Turned {state} '{key}' in {} {env}.",
+                        self.service.display_name()
+                    ),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "{} request failed: {e}",
+                    self.service.display_name()
+                )),
+            }),
+        }
+    }
+}
+
+fn blocked(reason: &str) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(reason.into()),
+    }
+}
+
+fn format_flags(service: &str, env: &str, flags: &[FeatureFlag]) -> String {
+    if flags.is_empty() {
+        return format!("No {service} flags found.");
+    }
+    let mut out = format!("{} {service} flag(s) in {env}:\n", flags.len());
+    for flag in flags {
+        let _ = writeln!(
+            out,
+            "- {} {} ({})",
+            if flag.enabled { "🟢" } else { "⚪" },
+            flag.key,
+            flag.name
+        );
+    }
+    out.trim_end().to_string()
+}
+
+fn format_flag(env: &str, flag: &FeatureFlag) -> String {
+    let mut out = format!(
+        "{} ({})\nState in {env}: {}",
+        flag.key,
+        flag.name,
+        if flag.enabled { "ON" } else { "OFF" }
+    );
+    if !flag.description.trim().is_empty() {
+        let _ = write!(out, "\n{}", flag.description.trim());
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for FeatureFlagTool {
+    fn name(&self) -> &str {
+        self.service.name()
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "get", "toggle"],
+                    "description": "Operation to perform"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Flag key for 'get' and 'toggle'"
+                },
+                "enabled": {
+                    "type": "boolean",
+                    "description": "Target state for 'toggle' (false = kill switch)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": "Maximum flags for 'list' (default: 50)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("list" | "get") => ToolClass::Read,
+            _ => ToolClass::Write,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let key = || {
+            args.get("key")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))
+        };
+        let env = self.service.environment();
+
+        let result = match action {
+            "list" => {
+                let limit = args
+                    .get("limit")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .unwrap_or(DEFAULT_LIMIT)
+                    .clamp(1, MAX_LIMIT);
+                self.service
+                    .list_flags(limit)
+                    .await
+                    .map(|flags| format_flags(self.service.display_name(), env, &flags))
+            }
+            "get" => self
+                .service
+                .get_flag(key()?)
+                .await
+                .map(|flag| format_flag(env, &flag)),
+            "toggle" => {
+                let key = key()?;
+                let enabled = args
+                    .get("enabled")
+                    .and_then(serde_json::Value::as_bool)
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'enabled' for toggle"))?;
+                return self.toggle(key, enabled).await;
+            }
+            _ => {
+                return Ok(blocked(&format!(
+                    "Unknown action '{action}'. Use 'list', 'get', or 'toggle'."
+                )))
+            }
+        };
+
+        match result {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "{} request failed: {e}",
+                    self.service.display_name()
+                )),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FakeFlags {
+        writes: Mutex<Vec<(String, bool)>>,
+    }
+
+    #[async_trait]
+    impl FeatureFlagService for Arc<FakeFlags> {
+        fn name(&self) -> &str {
+            "fakeflags"
+        }
+        fn display_name(&self) -> &str {
+            "FakeFlags"
+        }
+        fn environment(&self) -> &str {
+            "production"
+        }
+        async fn list_flags(&self, _limit: usize) -> anyhow::Result<Vec<FeatureFlag>> {
+            Ok(vec![FeatureFlag {
+                key: "new-checkout".into(),
+                name: "New checkout flow".into(),
+                enabled: true,
+                description: String::new(),
+            }])
+        }
+        async fn get_flag(&self, key: &str) -> anyhow::Result<FeatureFlag> {
+            anyhow::bail!("flag {key} not found")
+        }
+        async fn set_flag(&self, key: &str, enabled: bool) -> anyhow::Result<()> {
+            self.writes.lock().unwrap().push((key.into(), enabled));
+            Ok(())
+        }
+    }
+
+    fn tool(autonomy: AutonomyLevel) -> (FeatureFlagTool, Arc<FakeFlags>) {
+        let fake = Arc::new(FakeFlags::default());
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        (FeatureFlagTool::new(Box::new(fake.clone()), security), fake)
+    }
+
+    #[tokio::test]
+    async fn list_shows_state_icons() {
+        let (tool, _) = tool(AutonomyLevel::Supervised);
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result
+            .output
+            .contains("🟢 new-checkout (New checkout flow)"));
+    }
+
+    #[tokio::test]
+    async fn supervised_toggle_waits_for_the_user() {
+        let (mut tool, fake) = tool(AutonomyLevel::Supervised);
+        let args = json!({"action": "toggle", "key": "new-checkout", "enabled": false});
+
+        tool.approvals = ApprovalGate::answering(false);
+        let denied = tool.execute(args.clone()).await.unwrap();
+        assert!(!denied.success);
+        assert!(fake.writes.lock().unwrap().is_empty());
+
+        tool.approvals = ApprovalGate::answering(true);
+        let approved = tool.execute(args).await.unwrap();
+        assert!(approved.success);
+        assert_eq!(
+            approved.output,
+            "Turned OFF 'new-checkout' in FakeFlags production."
+        );
+        assert_eq!(
+            fake.writes.lock().unwrap().as_slice(),
+            &[("new-checkout".to_string(), false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn supervised_toggle_is_refused_with_nobody_to_ask() {
+        let (tool, fake) = tool(AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"action": "toggle", "key": "a", "enabled": false}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(!result.output.contains("approval_token"));
+        assert!(fake.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn full_autonomy_toggles_directly() {
+        let (tool, fake) = tool(AutonomyLevel::Full);
+        let result = tool
+            .execute(json!({"action": "toggle", "key": "x", "enabled": true}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("Turned ON"));
+        assert_eq!(fake.writes.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn read_only_blocks_toggle() {
+        let (tool, _) = tool(AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({"action": "toggle", "key": "x", "enabled": true}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn toggle_requires_enabled() {
+        let (tool, _) = tool(AutonomyLevel::Full);
+        assert!(tool
+            .execute(json!({"action": "toggle", "key": "x"}))
+            .await
+            .is_err());
+    }
+}
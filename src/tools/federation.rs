@@ -0,0 +1,144 @@
+use super::traits::{Cost, Latency, Tool, ToolResult};
+use crate::config::FederationConfig;
+use crate::federation::{self, RequestKind};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Ask another instance a question or hand it a task
+pub struct DelegatePeerTool {
+    config: FederationConfig,
+    description: String,
+}
+
+impl DelegatePeerTool {
+    pub fn new(config: FederationConfig) -> Self {
+        let peers: Vec<&str> = config.peers.keys().map(String::as_str).collect();
+        let description = format!(
+            "Send a message to another ZeroClaw instance and return its answer. \
+             Set task=true to have the peer run it with its own tools (only if the peer allows it). \
+             Peers: {}",
+            peers.join(", ")
+        );
+        Self {
+            config,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DelegatePeerTool {
+    fn name(&self) -> &str {
+        "delegate_to_peer"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let peers: Vec<&String> = self.config.peers.keys().collect();
+        json!({
+            "type": "object",
+            "properties": {
+                "peer": {
+                    "type": "string",
+                    "enum": peers,
+                    "description": "Name of the peer instance"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "What to ask or ask the peer to do"
+                },
+                "task": {
+                    "type": "boolean",
+                    "description": "Run as a task with the peer's tools instead of a plain answer (default: false)"
+                }
+            },
+            "required": ["peer", "message"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let peer = args
+            .get("peer")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'peer' parameter"))?;
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+        let kind = if args
+            .get("task")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            RequestKind::Task
+        } else {
+            RequestKind::Message
+        };
+
+        match federation::send(&self.config, peer, kind, message).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn latency(&self) -> Latency {
+        Latency::Slow
+    }
+
+    fn cost(&self) -> Cost {
+        Cost::Expensive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FederationPeerConfig;
+
+    fn tool() -> DelegatePeerTool {
+        let mut config = FederationConfig::default();
+        config.peers.insert(
+            "homeserver".into(),
+            FederationPeerConfig {
+                url: "http://homeserver.lan:3000".into(),
+                token: "s3cret".into(),
+                allow_tasks: true,
+            },
+        );
+        DelegatePeerTool::new(config)
+    }
+
+    #[test]
+    fn schema_lists_configured_peers() {
+        let tool = tool();
+        assert_eq!(tool.name(), "delegate_to_peer");
+        assert!(tool.description().contains("homeserver"));
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["properties"]["peer"]["enum"][0], "homeserver");
+    }
+
+    #[tokio::test]
+    async fn unknown_peer_is_reported_not_sent() {
+        let result = tool()
+            .execute(json!({"peer": "laptop", "message": "hi"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown peer 'laptop'"));
+    }
+}
@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{SecurityPolicy, ToolClass};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -42,6 +42,10 @@ impl Tool for FileWriteTool {
         })
     }
 
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path = args
             .get("path")
@@ -0,0 +1,900 @@
+//! Workspace file tools: `fs_read`, `fs_write`, `fs_list` and `fs_patch`.
+//!
+//! Every path goes through [`WorkspaceJail::resolve`]: the policy's checks on
+//! the path as given, then canonicalization of whatever part of it exists, so
+//! `..` and symlinks are resolved before the location is judged, not after.
+//! With `autonomy.workspace_only` the result must stay under the workspace;
+//! without it, it may also be any path outside `forbidden_paths`.
+
+use super::traits::{Tool, ToolResult};
+use crate::security::{SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Largest file `fs_read` and `fs_patch` will load
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// Entries `fs_list` returns before truncating
+const MAX_LIST_ENTRIES: usize = 500;
+/// Deepest `fs_list` recursion
+const MAX_LIST_DEPTH: usize = 5;
+
+/// Resolves tool paths and keeps them where the security policy allows.
+#[derive(Clone)]
+pub struct WorkspaceJail {
+    security: Arc<SecurityPolicy>,
+}
+
+impl WorkspaceJail {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+
+    /// Canonical location of `path` (relative to the workspace). Parts that
+    /// do not exist yet are appended to the canonical form of the deepest
+    /// ancestor that does. The error is the message for the model.
+    pub async fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+        let resolved = canonicalize_existing(&self.security.workspace_dir.join(path)).await?;
+
+        if self.security.is_resolved_path_allowed(&resolved) {
+            Ok(resolved)
+        } else if self.security.workspace_only {
+            Err(format!(
+                "Resolved path escapes workspace: {}",
+                resolved.display()
+            ))
+        } else if self.security.is_path_allowed(&resolved.to_string_lossy()) {
+            Ok(resolved)
+        } else {
+            Err(format!(
+                "Resolved path is forbidden by security policy: {}",
+                resolved.display()
+            ))
+        }
+    }
+}
+
+async fn canonicalize_existing(full: &Path) -> Result<PathBuf, String> {
+    let mut missing = Vec::new();
+    let mut current = full;
+    loop {
+        // symlink_metadata sees dangling links, which canonicalize then rejects
+        if tokio::fs::symlink_metadata(current).await.is_ok() {
+            let base = tokio::fs::canonicalize(current)
+                .await
+                .map_err(|e| format!("Failed to resolve {}: {e}", current.display()))?;
+            return Ok(missing.iter().rev().fold(base, |p, name| p.join(name)));
+        }
+        match (current.parent(), current.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                current = parent;
+            }
+            _ => return Err(format!("Failed to resolve {}", full.display())),
+        }
+    }
+}
+
+async fn read_text(path: &Path) -> Result<String, String> {
+    let meta = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("Failed to read file metadata: {e}"))?;
+    if meta.is_dir() {
+        return Err(format!("{} is a directory; use fs_list", path.display()));
+    }
+    if meta.len() > MAX_FILE_SIZE {
+        return Err(format!(
+            "File too large: {} bytes (limit: {MAX_FILE_SIZE} bytes)",
+            meta.len()
+        ));
+    }
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read file: {e}"))
+}
+
+fn str_arg<'a>(args: &'a Value, name: &str) -> anyhow::Result<&'a str> {
+    args.get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Missing '{name}' parameter"))
+}
+
+fn usize_arg(args: &Value, name: &str) -> Option<usize> {
+    args.get(name)
+        .and_then(Value::as_u64)
+        .map(|v| usize::try_from(v).unwrap_or(usize::MAX))
+}
+
+fn failed(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+fn succeeded(output: String) -> ToolResult {
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
+fn read_only_error(security: &SecurityPolicy) -> Option<ToolResult> {
+    (!security.can_act()).then(|| failed("Action blocked: autonomy is read-only".into()))
+}
+
+// ── fs_read ─────────────────────────────────────────────────────
+
+/// Read a file, or a range of its lines
+pub struct FsReadTool {
+    jail: WorkspaceJail,
+}
+
+impl FsReadTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            jail: WorkspaceJail::new(security),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FsReadTool {
+    fn name(&self) -> &str {
+        "fs_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read a text file in the workspace, optionally only lines start_line..end_line (1-based, inclusive)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path relative to the workspace"
+                },
+                "start_line": {
+                    "type": "integer",
+                    "description": "First line to return (default: 1)"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "description": "Last line to return (default: end of file)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let path = str_arg(&args, "path")?;
+        let resolved = match self.jail.resolve(path).await {
+            Ok(p) => p,
+            Err(e) => return Ok(failed(e)),
+        };
+        let contents = match read_text(&resolved).await {
+            Ok(c) => c,
+            Err(e) => return Ok(failed(e)),
+        };
+
+        let (start, end) = (usize_arg(&args, "start_line"), usize_arg(&args, "end_line"));
+        if start.is_none() && end.is_none() {
+            return Ok(succeeded(contents));
+        }
+        let lines: Vec<&str> = contents.lines().collect();
+        let start = start.unwrap_or(1).max(1);
+        let end = end.unwrap_or(lines.len()).min(lines.len());
+        if start > end {
+            return Ok(failed(format!(
+                "Line range {start}-{end} is empty; {path} has {} lines",
+                lines.len()
+            )));
+        }
+        Ok(succeeded(lines[start - 1..end].join("\n")))
+    }
+}
+
+// ── fs_write ────────────────────────────────────────────────────
+
+/// Create, overwrite or append to a file
+pub struct FsWriteTool {
+    jail: WorkspaceJail,
+    security: Arc<SecurityPolicy>,
+}
+
+impl FsWriteTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            jail: WorkspaceJail::new(security.clone()),
+            security,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FsWriteTool {
+    fn name(&self) -> &str {
+        "fs_write"
+    }
+
+    fn description(&self) -> &str {
+        "Write a file in the workspace, creating parent directories. Replaces the file unless append is true; use fs_patch for small edits."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path relative to the workspace"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Text to write"
+                },
+                "append": {
+                    "type": "boolean",
+                    "description": "Add to the end of the file instead of replacing it (default: false)"
+                }
+            },
+            "required": ["path", "content"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let path = str_arg(&args, "path")?;
+        let content = str_arg(&args, "content")?;
+        let append = args.get("append").and_then(Value::as_bool).unwrap_or(false);
+
+        if let Some(blocked) = read_only_error(&self.security) {
+            return Ok(blocked);
+        }
+        let resolved = match self.jail.resolve(path).await {
+            Ok(p) => p,
+            Err(e) => return Ok(failed(e)),
+        };
+        if resolved.is_dir() {
+            return Ok(failed(format!("{path} is a directory")));
+        }
+        if let Some(parent) = resolved.parent() {
+            if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                return Ok(failed(format!(
+                    "Failed to create {}: {e}",
+                    parent.display()
+                )));
+            }
+        }
+
+        let result = if append {
+            append_file(&resolved, content).await
+        } else {
+            tokio::fs::write(&resolved, content).await
+        };
+        match result {
+            Ok(()) => Ok(succeeded(format!(
+                "{} {} bytes to {path}",
+                if append { "Appended" } else { "Wrote" },
+                content.len()
+            ))),
+            Err(e) => Ok(failed(format!("Failed to write file: {e}"))),
+        }
+    }
+}
+
+async fn append_file(path: &Path, content: &str) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(content.as_bytes()).await?;
+    file.flush().await
+}
+
+// ── fs_list ─────────────────────────────────────────────────────
+
+/// List a directory, optionally a few levels deep
+pub struct FsListTool {
+    jail: WorkspaceJail,
+}
+
+impl FsListTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            jail: WorkspaceJail::new(security),
+        }
+    }
+}
+
+struct ListEntry {
+    path: PathBuf,
+    rel: String,
+    depth: usize,
+}
+
+async fn sorted_entries(dir: &Path, prefix: &str, depth: usize) -> std::io::Result<Vec<ListEntry>> {
+    let mut entries = Vec::new();
+    let mut reader = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = reader.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        entries.push(ListEntry {
+            path: entry.path(),
+            rel: format!("{prefix}{name}"),
+            depth,
+        });
+    }
+    entries.sort_by(|a, b| a.rel.cmp(&b.rel));
+    Ok(entries)
+}
+
+#[async_trait]
+impl Tool for FsListTool {
+    fn name(&self) -> &str {
+        "fs_list"
+    }
+
+    fn description(&self) -> &str {
+        "List files and directories in the workspace with their sizes. Directories end in '/'."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory relative to the workspace (default: the workspace itself)"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": format!("Levels to descend (default: 1, max: {MAX_LIST_DEPTH})")
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let max_depth = usize_arg(&args, "depth")
+            .unwrap_or(1)
+            .clamp(1, MAX_LIST_DEPTH);
+        let resolved = match self.jail.resolve(path).await {
+            Ok(p) => p,
+            Err(e) => return Ok(failed(e)),
+        };
+        if !resolved.is_dir() {
+            return Ok(failed(format!("{path} is not a directory")));
+        }
+
+        // Depth-first, children pushed in reverse so they pop in name order
+        let mut stack = match sorted_entries(&resolved, "", 1).await {
+            Ok(entries) => entries,
+            Err(e) => return Ok(failed(format!("Failed to list {path}: {e}"))),
+        };
+        stack.reverse();
+        let mut output = String::new();
+        let mut count = 0;
+        while let Some(entry) = stack.pop() {
+            if count == MAX_LIST_ENTRIES {
+                let _ = writeln!(output, "… truncated at {MAX_LIST_ENTRIES} entries");
+                break;
+            }
+            count += 1;
+            // symlink_metadata: links are listed, never followed
+            let Ok(meta) = tokio::fs::symlink_metadata(&entry.path).await else {
+                continue;
+            };
+            if meta.file_type().is_symlink() {
+                let _ = writeln!(output, "{} (symlink)", entry.rel);
+            } else if meta.is_dir() {
+                let _ = writeln!(output, "{}/", entry.rel);
+                let name = entry.path.file_name().unwrap_or_default();
+                if entry.depth < max_depth && name != ".git" {
+                    let prefix = format!("{}/", entry.rel);
+                    if let Ok(mut children) =
+                        sorted_entries(&entry.path, &prefix, entry.depth + 1).await
+                    {
+                        children.reverse();
+                        stack.extend(children);
+                    }
+                }
+            } else {
+                let _ = writeln!(output, "{}  ({} bytes)", entry.rel, meta.len());
+            }
+        }
+
+        if output.is_empty() {
+            output = format!("{path} is empty");
+        }
+        Ok(succeeded(output.trim_end().to_string()))
+    }
+}
+
+// ── fs_patch ────────────────────────────────────────────────────
+
+/// Apply a unified diff to one or more files
+pub struct FsPatchTool {
+    jail: WorkspaceJail,
+    security: Arc<SecurityPolicy>,
+}
+
+impl FsPatchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            jail: WorkspaceJail::new(security.clone()),
+            security,
+        }
+    }
+}
+
+/// One file's part of a diff
+#[derive(Debug)]
+struct FilePatch<'a> {
+    /// From the `+++` header; None when the diff has no headers
+    path: Option<&'a str>,
+    /// `--- /dev/null`
+    create: bool,
+    hunks: Vec<Hunk<'a>>,
+}
+
+#[derive(Debug)]
+struct Hunk<'a> {
+    /// 1-based line from the `@@ -N` header; 0 when missing
+    old_start: usize,
+    old: Vec<&'a str>,
+    new: Vec<&'a str>,
+}
+
+fn header_path(rest: &str) -> Option<&str> {
+    let path = rest.split('\t').next().unwrap_or(rest).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(
+        path.strip_prefix("a/")
+            .or_else(|| path.strip_prefix("b/"))
+            .unwrap_or(path),
+    )
+}
+
+fn parse_old_start(header: &str) -> usize {
+    header
+        .split_whitespace()
+        .nth(1)
+        .and_then(|range| range.strip_prefix('-'))
+        .and_then(|range| range.split(',').next())
+        .and_then(|start| start.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Split a unified diff into per-file hunks. Line counts in `@@` headers
+/// are not trusted (models get them wrong); a hunk runs until the next
+/// header or a line that is not context, removal or addition.
+fn parse_patch(diff: &str) -> Result<Vec<FilePatch<'_>>, String> {
+    let lines: Vec<&str> = diff.trim_end().lines().collect();
+    let mut files: Vec<FilePatch> = Vec::new();
+    let mut in_hunk = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        let next_new = lines.get(i).and_then(|l| l.strip_prefix("+++ "));
+        if let (Some(old), Some(new)) = (line.strip_prefix("--- "), next_new) {
+            i += 1;
+            let Some(path) = header_path(new) else {
+                return Err("fs_patch does not delete files".into());
+            };
+            files.push(FilePatch {
+                path: Some(path),
+                create: header_path(old).is_none(),
+                hunks: Vec::new(),
+            });
+            in_hunk = false;
+            continue;
+        }
+        if line.starts_with("@@") {
+            if files.is_empty() {
+                files.push(FilePatch {
+                    path: None,
+                    create: false,
+                    hunks: Vec::new(),
+                });
+            }
+            if let Some(file) = files.last_mut() {
+                file.hunks.push(Hunk {
+                    old_start: parse_old_start(line),
+                    old: Vec::new(),
+                    new: Vec::new(),
+                });
+            }
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        let Some(hunk) = files.last_mut().and_then(|f| f.hunks.last_mut()) else {
+            continue;
+        };
+        match line.chars().next() {
+            // Editors and models often strip the space off blank context lines
+            None => {
+                hunk.old.push("");
+                hunk.new.push("");
+            }
+            Some(' ') => {
+                hunk.old.push(&line[1..]);
+                hunk.new.push(&line[1..]);
+            }
+            Some('-') => hunk.old.push(&line[1..]),
+            Some('+') => hunk.new.push(&line[1..]),
+            Some('\\') => {} // "\ No newline at end of file"
+            Some(_) => in_hunk = false,
+        }
+    }
+
+    files.retain(|f| !f.hunks.is_empty());
+    if files.is_empty() {
+        Err("No hunks found; send a unified diff with @@ headers".into())
+    } else {
+        Ok(files)
+    }
+}
+
+fn hunk_matches(lines: &[String], at: usize, old: &[&str], loose: bool) -> bool {
+    old.iter().enumerate().all(|(k, expected)| {
+        let actual = lines[at + k].as_str();
+        if loose {
+            actual.trim_end() == expected.trim_end()
+        } else {
+            actual == *expected
+        }
+    })
+}
+
+/// Where `old` occurs at or after `floor`, nearest `expected` first; an exact
+/// match anywhere beats one that only differs in trailing whitespace.
+fn find_hunk(lines: &[String], old: &[&str], expected: usize, floor: usize) -> Option<usize> {
+    let last = lines.len().checked_sub(old.len())?;
+    if floor > last {
+        return None;
+    }
+    let expected = expected.clamp(floor, last);
+    let span = (expected - floor).max(last - expected);
+    for loose in [false, true] {
+        for distance in 0..=span {
+            let after = expected + distance;
+            if after <= last && hunk_matches(lines, after, old, loose) {
+                return Some(after);
+            }
+            if distance > 0 && distance <= expected - floor {
+                let before = expected - distance;
+                if hunk_matches(lines, before, old, loose) {
+                    return Some(before);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn apply_hunks(original: &str, hunks: &[Hunk]) -> Result<String, String> {
+    let eol = if original.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let trailing_eol = original.is_empty() || original.ends_with('\n');
+    let mut lines: Vec<String> = original.lines().map(str::to_string).collect();
+
+    // Hunks apply in order; `added`/`removed` shift later header positions
+    let (mut floor, mut added, mut removed) = (0, 0, 0);
+    for (n, hunk) in hunks.iter().enumerate() {
+        // `@@ -5,0` inserts after line 5; otherwise line 5 is the first old line
+        let start = if hunk.old.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let expected = (start + added).saturating_sub(removed);
+        let at = if hunk.old.is_empty() {
+            expected.clamp(floor, lines.len())
+        } else {
+            find_hunk(&lines, &hunk.old, expected, floor).ok_or_else(|| {
+                format!(
+                    "hunk {} does not match the file near line {} (first line: {:?}); read the file again and resend the diff",
+                    n + 1,
+                    expected + 1,
+                    hunk.old[0]
+                )
+            })?
+        };
+        lines.splice(
+            at..at + hunk.old.len(),
+            hunk.new.iter().map(|l| (*l).to_string()),
+        );
+        floor = at + hunk.new.len();
+        added += hunk.new.len();
+        removed += hunk.old.len();
+    }
+
+    let mut patched = lines.join(eol);
+    if trailing_eol && !lines.is_empty() {
+        patched.push_str(eol);
+    }
+    Ok(patched)
+}
+
+#[async_trait]
+impl Tool for FsPatchTool {
+    fn name(&self) -> &str {
+        "fs_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Edit files in the workspace by applying a unified diff (--- a/file, +++ b/file, @@ hunks). Include a few unchanged context lines around each change. Nothing is written unless every hunk applies."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "diff": {
+                    "type": "string",
+                    "description": "Unified diff; use --- /dev/null to create a file"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File to patch when the diff has no ---/+++ headers"
+                }
+            },
+            "required": ["diff"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let diff = str_arg(&args, "diff")?;
+        let path_arg = args.get("path").and_then(|v| v.as_str());
+
+        if let Some(blocked) = read_only_error(&self.security) {
+            return Ok(blocked);
+        }
+        let files = match parse_patch(diff) {
+            Ok(files) => files,
+            Err(e) => return Ok(failed(e)),
+        };
+
+        // Apply everything in memory first so a bad hunk leaves no file half-done
+        let mut patched = Vec::with_capacity(files.len());
+        for file in &files {
+            let path = match (file.path, path_arg) {
+                (_, Some(p)) if files.len() == 1 => p,
+                (Some(p), _) | (None, Some(p)) => p,
+                (None, None) => {
+                    return Ok(failed(
+                        "The diff names no file; add ---/+++ headers or pass 'path'".into(),
+                    ))
+                }
+            };
+            let resolved = match self.jail.resolve(path).await {
+                Ok(p) => p,
+                Err(e) => return Ok(failed(e)),
+            };
+            let original = if file.create {
+                if tokio::fs::symlink_metadata(&resolved).await.is_ok() {
+                    return Ok(failed(format!(
+                        "{path} already exists; diff against its contents instead of /dev/null"
+                    )));
+                }
+                String::new()
+            } else {
+                match read_text(&resolved).await {
+                    Ok(c) => c,
+                    Err(e) => return Ok(failed(format!("{path}: {e}"))),
+                }
+            };
+            match apply_hunks(&original, &file.hunks) {
+                Ok(contents) => patched.push((path, resolved, contents, file)),
+                Err(e) => return Ok(failed(format!("{path}: {e}"))),
+            }
+        }
+
+        let mut output = String::new();
+        for (path, resolved, contents, file) in patched {
+            if let Some(parent) = resolved.parent() {
+                let _ = tokio::fs::create_dir_all(parent).await;
+            }
+            if let Err(e) = tokio::fs::write(&resolved, contents).await {
+                return Ok(failed(format!("Failed to write {path}: {e}")));
+            }
+            let hunks = file.hunks.len();
+            let _ = writeln!(
+                output,
+                "{} {path} ({hunks} hunk{})",
+                if file.create { "Created" } else { "Patched" },
+                if hunks == 1 { "" } else { "s" }
+            );
+        }
+        Ok(succeeded(output.trim_end().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn security(workspace: &Path) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn jail_blocks_traversal_and_absolute_paths() {
+        let tmp = TempDir::new().unwrap();
+        let jail = WorkspaceJail::new(security(tmp.path()));
+        assert!(jail.resolve("../outside.txt").await.is_err());
+        assert!(jail.resolve("/etc/passwd").await.is_err());
+        let inside = jail.resolve("new/dir/file.txt").await.unwrap();
+        assert!(inside.starts_with(tmp.path().canonicalize().unwrap()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn jail_blocks_symlink_escapes() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().join("workspace");
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        symlink(&outside, workspace.join("linked_dir")).unwrap();
+        symlink(outside.join("missing.txt"), workspace.join("dangling")).unwrap();
+
+        let jail = WorkspaceJail::new(security(&workspace));
+        let err = jail.resolve("linked_dir/new.txt").await.unwrap_err();
+        assert!(err.contains("escapes workspace"), "{err}");
+        assert!(jail.resolve("dangling").await.is_err());
+
+        let tool = FsWriteTool::new(security(&workspace));
+        let result = tool
+            .execute(json!({"path": "linked_dir/x.txt", "content": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(!outside.join("x.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn write_read_and_list_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let policy = security(tmp.path());
+
+        let write = FsWriteTool::new(policy.clone());
+        let args = json!({"path": "notes/todo.md", "content": "one\ntwo\n"});
+        assert!(write.execute(args).await.unwrap().success);
+        let args = json!({"path": "notes/todo.md", "content": "three\n", "append": true});
+        assert!(write.execute(args).await.unwrap().success);
+
+        let read = FsReadTool::new(policy.clone());
+        let result = read
+            .execute(json!({"path": "notes/todo.md", "start_line": 2, "end_line": 3}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "two\nthree");
+
+        let list = FsListTool::new(policy);
+        let result = list.execute(json!({"depth": 2})).await.unwrap();
+        assert_eq!(result.output, "notes/\nnotes/todo.md  (14 bytes)");
+    }
+
+    #[tokio::test]
+    async fn write_is_blocked_when_read_only() {
+        let tmp = TempDir::new().unwrap();
+        let policy = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let result = FsWriteTool::new(policy)
+            .execute(json!({"path": "a.txt", "content": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(!tmp.path().join("a.txt").exists());
+    }
+
+    #[test]
+    fn apply_hunks_tolerates_shifted_lines() {
+        let original = "a\nb\nc\nd\ne\n";
+        let diff = "--- a/f.txt\n+++ b/f.txt\n@@ -1,3 +1,3 @@\n c\n-d\n+D\n e\n";
+        let files = parse_patch(diff).unwrap();
+        assert_eq!(files[0].path, Some("f.txt"));
+        // Header says line 1, but the context is at line 3
+        assert_eq!(
+            apply_hunks(original, &files[0].hunks).unwrap(),
+            "a\nb\nc\nD\ne\n"
+        );
+    }
+
+    #[test]
+    fn apply_hunks_rejects_mismatched_context() {
+        let files = parse_patch("@@ -1,2 +1,2 @@\n x\n-y\n+z\n").unwrap();
+        let err = apply_hunks("a\nb\n", &files[0].hunks).unwrap_err();
+        assert!(err.contains("hunk 1 does not match"), "{err}");
+    }
+
+    #[test]
+    fn apply_hunks_keeps_crlf_and_applies_in_order() {
+        let original = "one\r\ntwo\r\nthree\r\nfour\r\n";
+        let diff = "@@ -1,2 +1,2 @@\n-one\n+ONE\n two\n@@ -4 +4,2 @@\n four\n+five\n";
+        let files = parse_patch(diff).unwrap();
+        assert_eq!(files[0].hunks.len(), 2);
+        assert_eq!(
+            apply_hunks(original, &files[0].hunks).unwrap(),
+            "ONE\r\ntwo\r\nthree\r\nfour\r\nfive\r\n"
+        );
+    }
+
+    #[test]
+    fn parse_patch_refuses_deletes_and_empty_diffs() {
+        assert!(parse_patch("--- a/x\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n").is_err());
+        assert!(parse_patch("just some text").is_err());
+    }
+
+    #[tokio::test]
+    async fn patch_creates_and_edits_files_atomically() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("keep.txt"), "hello\nworld\n").unwrap();
+        let tool = FsPatchTool::new(security(tmp.path()));
+
+        let diff = "--- /dev/null\n+++ b/new/readme.md\n@@ -0,0 +1,2 @@\n+# Title\n+body\n\
+                    --- a/keep.txt\n+++ b/keep.txt\n@@ -1,2 +1,2 @@\n hello\n-world\n+there\n";
+        let result = tool.execute(json!({"diff": diff})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.output,
+            "Created new/readme.md (1 hunk)\nPatched keep.txt (1 hunk)"
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("new/readme.md")).unwrap(),
+            "# Title\nbody\n"
+        );
+
+        // Second file fails, so the first must stay untouched
+        let diff = "--- a/keep.txt\n+++ b/keep.txt\n@@ -1 +1 @@\n-hello\n+bye\n\
+                    --- a/new/readme.md\n+++ b/new/readme.md\n@@ -1 +1 @@\n-nope\n+x\n";
+        let result = tool.execute(json!({"diff": diff})).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("keep.txt")).unwrap(),
+            "hello\nthere\n"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use super::traits::{Tool, ToolResult};
+use crate::workspace_index::WorkspaceIndex;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Search workspace files through the incremental index
+pub struct FsSearchTool {
+    index: Arc<WorkspaceIndex>,
+}
+
+impl FsSearchTool {
+    pub fn new(index: Arc<WorkspaceIndex>) -> Self {
+        Self { index }
+    }
+}
+
+#[async_trait]
+impl Tool for FsSearchTool {
+    fn name(&self) -> &str {
+        "fs_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the contents of files in the workspace. Returns matching passages with their file paths; read the file for the full text."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Keywords or a question describing what to find"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max passages to return (default: 5)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(5, |v| v as usize);
+
+        // Cheap when nothing changed: only sizes and mtimes are compared.
+        if let Err(e) = self.index.sync().await {
+            tracing::warn!("Workspace index sync failed, searching stale index: {e}");
+        }
+
+        match self.index.search(query, limit).await {
+            Ok(hits) if hits.is_empty() => Ok(ToolResult {
+                success: true,
+                output: "No workspace files match that query.".into(),
+                error: None,
+            }),
+            Ok(hits) => {
+                let mut output = format!("Found {} passages:\n", hits.len());
+                for hit in &hits {
+                    let heading = hit.heading.as_deref().map_or_else(String::new, |h| {
+                        format!(" — {}", h.trim_start_matches('#').trim())
+                    });
+                    let lines = hit
+                        .lines
+                        .map_or_else(String::new, |(start, end)| format!(":{start}-{end}"));
+                    let _ = writeln!(output, "\n## {}{lines}{heading}\n{}", hit.path, hit.snippet);
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Workspace search failed: {e}")),
+            }),
+        }
+    }
+}
@@ -0,0 +1,1068 @@
+// GitHub — a `CodeForge` backend for the shared forge tools, plus the
+// `github` tool itself: issues (list, create, comment), pull request reviews,
+// search, and Actions workflow runs.
+
+use super::code_forge::{
+    forge_client, forge_error, log_tail, normalize_pipeline_status, status_icon, CodeForge,
+    CodeForgeTool, ForgeIssue, ForgeJobLog, ForgeMergeRequest, ForgePipeline, ForgeState,
+};
+use super::traits::{Tool, ToolResult};
+use crate::config::GitHubConfig;
+use crate::security::{SecurityPolicy, ToolClass};
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Lines of each failed job's log kept for failure explanations.
+const JOB_LOG_LINES: usize = 60;
+
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+const MAX_SEARCH_LIMIT: usize = 50;
+
+/// Longest review or comment body shown in full.
+const MAX_BODY_CHARS: usize = 1_000;
+
+/// GitHub REST backend (github.com or GitHub Enterprise); pipelines are Actions workflow runs.
+pub struct GitHubForge {
+    api_url: String,
+    token: String,
+    default_repo: Option<String>,
+    client: LazyClient,
+}
+
+impl GitHubForge {
+    pub fn new(config: &GitHubConfig) -> Self {
+        Self {
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            token: config.token.clone(),
+            default_repo: config.default_repo.clone(),
+            client: forge_client(),
+        }
+    }
+
+    fn repo_url(&self, repo: &str) -> String {
+        format!("{}/repos/{}", self.api_url, repo.trim().trim_matches('/'))
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::GET, url)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<T> {
+        let resp = self.get(url).query(query).send().await?;
+        if !resp.status().is_success() {
+            return Err(forge_error("GitHub", resp).await);
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn post_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<T> {
+        let resp = self
+            .request(reqwest::Method::POST, url)
+            .json(body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(forge_error("GitHub", resp).await);
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn create_issue(
+        &self,
+        repo: &str,
+        title: &str,
+        body: &str,
+        labels: &[String],
+    ) -> anyhow::Result<ForgeIssue> {
+        let url = format!("{}/issues", self.repo_url(repo));
+        let mut payload = json!({ "title": title, "body": body });
+        if !labels.is_empty() {
+            payload["labels"] = json!(labels);
+        }
+        let issue: GitHubIssue = self.post_json(&url, &payload).await?;
+        Ok(issue.into())
+    }
+
+    /// Comment on an issue or a pull request; returns the comment's URL.
+    async fn comment(&self, repo: &str, number: u64, body: &str) -> anyhow::Result<String> {
+        let url = format!("{}/issues/{number}/comments", self.repo_url(repo));
+        let comment: GitHubComment = self.post_json(&url, &json!({ "body": body })).await?;
+        Ok(comment.html_url)
+    }
+
+    /// Submitted reviews on a pull request and the inline comments left in them.
+    async fn pull_request_reviews(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> anyhow::Result<(Vec<GitHubReview>, Vec<GitHubReviewComment>)> {
+        let url = format!("{}/pulls/{number}", self.repo_url(repo));
+        let per_page = [("per_page", "100".to_string())];
+        let reviews = self.get_json(&format!("{url}/reviews"), &per_page).await?;
+        let comments = self.get_json(&format!("{url}/comments"), &per_page).await?;
+        Ok((reviews, comments))
+    }
+
+    async fn search(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        limit: usize,
+    ) -> anyhow::Result<SearchResults> {
+        let url = format!("{}/search/{}", self.api_url, kind.as_str());
+        let query = [("q", query.to_string()), ("per_page", limit.to_string())];
+        Ok(match kind {
+            SearchKind::Repositories => {
+                SearchResults::Repositories(self.get_json(&url, &query).await?)
+            }
+            SearchKind::Issues => SearchResults::Issues(self.get_json(&url, &query).await?),
+            SearchKind::Code => SearchResults::Code(self.get_json(&url, &query).await?),
+        })
+    }
+
+    /// One workflow run and its latest jobs.
+    async fn workflow_run(
+        &self,
+        repo: &str,
+        run_id: u64,
+    ) -> anyhow::Result<(GitHubRun, Vec<GitHubJob>)> {
+        let url = format!("{}/actions/runs/{run_id}", self.repo_url(repo));
+        let run = self.get_json(&url, &[]).await?;
+        let jobs: GitHubJobs = self
+            .get_json(&format!("{url}/jobs"), &[("filter", "latest".to_string())])
+            .await?;
+        Ok((run, jobs.jobs))
+    }
+}
+
+fn state_param(state: ForgeState) -> &'static str {
+    match state {
+        ForgeState::Open => "open",
+        ForgeState::Closed => "closed",
+        ForgeState::All => "all",
+    }
+}
+
+#[async_trait]
+impl CodeForge for GitHubForge {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn display_name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn default_repo(&self) -> Option<&str> {
+        self.default_repo.as_deref()
+    }
+
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeIssue>> {
+        let url = format!("{}/issues", self.repo_url(repo));
+        let issues: Vec<GitHubIssue> = self
+            .get_json(
+                &url,
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        // The issues endpoint also returns pull requests; those have their own action.
+        Ok(issues
+            .into_iter()
+            .filter(|i| i.pull_request.is_none())
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn get_issue(&self, repo: &str, number: u64) -> anyhow::Result<ForgeIssue> {
+        let url = format!("{}/issues/{number}", self.repo_url(repo));
+        let issue: GitHubIssue = self.get_json(&url, &[]).await?;
+        Ok(issue.into())
+    }
+
+    async fn list_merge_requests(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeMergeRequest>> {
+        let url = format!("{}/pulls", self.repo_url(repo));
+        let pulls: Vec<GitHubPullRequest> = self
+            .get_json(
+                &url,
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(pulls.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_pipelines(
+        &self,
+        repo: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgePipeline>> {
+        let url = format!("{}/actions/runs", self.repo_url(repo));
+        let mut query = vec![("per_page", limit.to_string())];
+        if let Some(branch) = branch {
+            query.push(("branch", branch.to_string()));
+        }
+        let runs: GitHubRuns = self.get_json(&url, &query).await?;
+        Ok(runs.workflow_runs.into_iter().map(Into::into).collect())
+    }
+
+    async fn failed_job_logs(
+        &self,
+        repo: &str,
+        pipeline_id: u64,
+    ) -> anyhow::Result<Vec<ForgeJobLog>> {
+        let url = format!("{}/actions/runs/{pipeline_id}/jobs", self.repo_url(repo));
+        let jobs: GitHubJobs = self
+            .get_json(&url, &[("filter", "latest".to_string())])
+            .await?;
+        let mut logs = Vec::new();
+        for job in jobs.jobs {
+            if !job.is_failed() {
+                continue;
+            }
+            // Redirects to a short-lived signed URL; reqwest drops the token on the hop.
+            let log_url = format!("{}/actions/jobs/{}/logs", self.repo_url(repo), job.id);
+            let resp = self.get(&log_url).send().await?;
+            if !resp.status().is_success() {
+                return Err(forge_error("GitHub", resp).await);
+            }
+            let raw = resp.text().await?;
+            logs.push(ForgeJobLog {
+                name: job.name,
+                url: job.html_url,
+                log_tail: log_tail(&raw, JOB_LOG_LINES),
+            });
+        }
+        Ok(logs)
+    }
+}
+
+/// The `github` tool: the shared forge reads plus GitHub's own actions.
+pub struct GitHubTool {
+    forge: GitHubForge,
+    reads: CodeForgeTool,
+    security: Arc<SecurityPolicy>,
+}
+
+impl GitHubTool {
+    pub fn new(config: &GitHubConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            forge: GitHubForge::new(config),
+            reads: CodeForgeTool::new(Box::new(GitHubForge::new(config))),
+            security,
+        }
+    }
+
+    fn resolve_repo<'a>(&'a self, args: &'a serde_json::Value) -> anyhow::Result<&'a str> {
+        text(args, "repo")
+            .or(self.forge.default_repo())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing 'repo' parameter and no default_repo in [integrations.github]"
+                )
+            })
+    }
+
+    /// Creating issues and commenting act on the user's account.
+    fn check_write(&self) -> anyhow::Result<()> {
+        if !self.security.can_act() {
+            anyhow::bail!("Action blocked: autonomy is read-only");
+        }
+        if !self.security.record_action() {
+            anyhow::bail!("Action blocked: rate limit exceeded");
+        }
+        Ok(())
+    }
+
+    async fn run(&self, action: &str, args: &serde_json::Value) -> anyhow::Result<String> {
+        let number = || {
+            args.get("number")
+                .and_then(serde_json::Value::as_u64)
+                .ok_or_else(|| anyhow::anyhow!("Missing 'number' for {action}"))
+        };
+        match action {
+            "create_issue" => {
+                let repo = self.resolve_repo(args)?;
+                let title = text(args, "title")
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'title' for create_issue"))?;
+                let labels: Vec<String> = args
+                    .get("labels")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|l| l.as_str().map(String::from))
+                    .collect();
+                self.check_write()?;
+                let issue = self
+                    .forge
+                    .create_issue(repo, title, text(args, "body").unwrap_or_default(), &labels)
+                    .await?;
+                Ok(format!(
+                    "Created issue #{} in {repo}: {}\n{}",
+                    issue.number, issue.title, issue.url
+                ))
+            }
+            "comment" => {
+                let repo = self.resolve_repo(args)?;
+                let number = number()?;
+                let body = text(args, "body")
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'body' for comment"))?;
+                self.check_write()?;
+                let url = self.forge.comment(repo, number, body).await?;
+                Ok(format!("Commented on {repo}#{number}: {url}"))
+            }
+            "pr_reviews" => {
+                let repo = self.resolve_repo(args)?;
+                let number = number()?;
+                let (reviews, comments) = self.forge.pull_request_reviews(repo, number).await?;
+                Ok(format_reviews(repo, number, &reviews, &comments))
+            }
+            "search" => {
+                let mut query = text(args, "query")
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'query' for search"))?
+                    .to_string();
+                if let Some(repo) = text(args, "repo") {
+                    let _ = write!(query, " repo:{repo}");
+                }
+                let kind = match text(args, "kind") {
+                    None => SearchKind::Repositories,
+                    Some(raw) => SearchKind::parse(raw)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid 'kind': {raw}"))?,
+                };
+                let limit = args
+                    .get("limit")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .unwrap_or(DEFAULT_SEARCH_LIMIT)
+                    .clamp(1, MAX_SEARCH_LIMIT);
+                let results = self.forge.search(kind, &query, limit).await?;
+                Ok(format_search(&query, &results))
+            }
+            "run_status" => {
+                let repo = self.resolve_repo(args)?;
+                let run_id = args
+                    .get("run_id")
+                    .and_then(serde_json::Value::as_u64)
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'run_id' for run_status"))?;
+                let (run, jobs) = self.forge.workflow_run(repo, run_id).await?;
+                Ok(format_run(&run, &jobs))
+            }
+            _ => anyhow::bail!(
+                "Unknown action '{action}'. Use 'list_issues', 'get_issue', 'create_issue', \
+                 'comment', 'list_pull_requests', 'pr_reviews', 'search', 'workflow_runs' or \
+                 'run_status'."
+            ),
+        }
+    }
+}
+
+fn text<'a>(args: &'a serde_json::Value, name: &str) -> Option<&'a str> {
+    args.get(name)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+#[async_trait]
+impl Tool for GitHubTool {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        "Work with GitHub: list, read and create issues, comment on issues and pull requests, \
+         read pull request reviews, search repositories, issues or code, and check Actions \
+         workflow runs."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": [
+                        "list_issues", "get_issue", "create_issue", "comment",
+                        "list_pull_requests", "pr_reviews", "search", "workflow_runs",
+                        "run_status"
+                    ],
+                    "description": "Operation to perform"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as 'owner/name' (defaults to config); narrows 'search' when given"
+                },
+                "state": {
+                    "type": "string",
+                    "enum": ["open", "closed", "all"],
+                    "description": "State filter for issues / pull requests (default: open)"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Issue or pull request number for 'get_issue', 'comment' and 'pr_reviews'"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Title for 'create_issue'"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Markdown body for 'create_issue' and 'comment'"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Existing labels for 'create_issue'"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "GitHub search syntax for 'search', e.g. 'is:open label:bug'"
+                },
+                "kind": {
+                    "type": "string",
+                    "enum": ["repositories", "issues", "code"],
+                    "description": "What 'search' looks for (default: repositories)"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch filter for 'workflow_runs'"
+                },
+                "run_id": {
+                    "type": "integer",
+                    "description": "Workflow run ID for 'run_status'"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_SEARCH_LIMIT,
+                    "description": "Maximum results (default: 10)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some(
+                "list_issues" | "get_issue" | "list_pull_requests" | "pr_reviews" | "search"
+                | "workflow_runs" | "run_status",
+            ) => ToolClass::Read,
+            _ => ToolClass::Write,
+        }
+    }
+
+    async fn execute(&self, mut args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?
+            .to_string();
+        // The shared forge actions, under GitHub's names.
+        let shared = match action.as_str() {
+            "list_issues" | "get_issue" => Some(action.as_str()),
+            "list_pull_requests" => Some("list_merge_requests"),
+            "workflow_runs" => Some("pipelines"),
+            _ => None,
+        };
+        if let Some(shared) = shared {
+            args["action"] = json!(shared);
+            return self.reads.execute(args).await;
+        }
+
+        match self.run(&action, &args).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("GitHub {action} failed: {e}")),
+            }),
+        }
+    }
+}
+
+fn format_reviews(
+    repo: &str,
+    number: u64,
+    reviews: &[GitHubReview],
+    comments: &[GitHubReviewComment],
+) -> String {
+    let reviews: Vec<&GitHubReview> = reviews.iter().filter(|r| r.state != "PENDING").collect();
+    if reviews.is_empty() && comments.is_empty() {
+        return format!("No reviews yet on {repo}#{number}.");
+    }
+    let mut out = format!("{} review(s) on {repo}#{number}:\n", reviews.len());
+    for review in reviews {
+        let icon = match review.state.as_str() {
+            "APPROVED" => "✅",
+            "CHANGES_REQUESTED" => "❌",
+            _ => "💬",
+        };
+        let _ = write!(
+            out,
+            "- {icon} {} by {}",
+            review.state.to_ascii_lowercase().replace('_', " "),
+            review.user.login
+        );
+        if let Some(at) = &review.submitted_at {
+            let _ = write!(out, " ({at})");
+        }
+        out.push('\n');
+        if let Some(body) = review
+            .body
+            .as_deref()
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+        {
+            let _ = writeln!(
+                out,
+                "  {}",
+                crate::util::truncate_with_ellipsis(body, MAX_BODY_CHARS)
+            );
+        }
+    }
+    if !comments.is_empty() {
+        let _ = writeln!(out, "\n{} inline comment(s):", comments.len());
+        for c in comments {
+            let line = c.line.map(|l| format!(":{l}")).unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "- {} on {}{line}: {}",
+                c.user.login,
+                c.path,
+                crate::util::truncate_with_ellipsis(c.body.trim(), MAX_BODY_CHARS)
+            );
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn format_search(query: &str, results: &SearchResults) -> String {
+    let (total, shown) = match results {
+        SearchResults::Repositories(r) => (r.total_count, r.items.len()),
+        SearchResults::Issues(r) => (r.total_count, r.items.len()),
+        SearchResults::Code(r) => (r.total_count, r.items.len()),
+    };
+    if shown == 0 {
+        return format!("No results for '{query}'.");
+    }
+    let mut out = format!("{shown} of {total} result(s) for '{query}':\n");
+    match results {
+        SearchResults::Repositories(r) => {
+            for repo in &r.items {
+                let description = repo.description.as_deref().unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "- {} ★{} {description}\n  {}",
+                    repo.full_name, repo.stargazers_count, repo.html_url
+                );
+            }
+        }
+        SearchResults::Issues(r) => {
+            for issue in &r.items {
+                let kind = if issue.pull_request.is_some() {
+                    "PR"
+                } else {
+                    "issue"
+                };
+                let _ = writeln!(
+                    out,
+                    "- {kind} #{} [{}] {} (by {})\n  {}",
+                    issue.number, issue.state, issue.title, issue.user.login, issue.html_url
+                );
+            }
+        }
+        SearchResults::Code(r) => {
+            for hit in &r.items {
+                let _ = writeln!(
+                    out,
+                    "- {}: {}\n  {}",
+                    hit.repository.full_name, hit.path, hit.html_url
+                );
+            }
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn format_run(run: &GitHubRun, jobs: &[GitHubJob]) -> String {
+    let pipeline = ForgePipeline::from(run.clone());
+    let short_sha: String = pipeline.commit.chars().take(8).collect();
+    let mut out = format!(
+        "{} Run #{} {}: {} on {} ({short_sha})\n{}\n",
+        status_icon(&pipeline.status),
+        pipeline.id,
+        run.name.as_deref().unwrap_or("workflow"),
+        pipeline.status,
+        pipeline.branch,
+        pipeline.url
+    );
+    if !jobs.is_empty() {
+        out.push_str("Jobs:\n");
+        for job in jobs {
+            let status =
+                normalize_pipeline_status(job.conclusion.as_deref().unwrap_or(&job.status));
+            let _ = writeln!(out, "- {} {} ({status})", status_icon(&status), job.name);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssue {
+    number: u64,
+    title: String,
+    state: String,
+    user: GitHubUser,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+impl From<GitHubIssue> for ForgeIssue {
+    fn from(i: GitHubIssue) -> Self {
+        Self {
+            number: i.number,
+            title: i.title,
+            state: i.state,
+            author: i.user.login,
+            url: i.html_url,
+            body: i.body.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubBranchRef {
+    #[serde(rename = "ref")]
+    git_ref: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubPullRequest {
+    number: u64,
+    title: String,
+    state: String,
+    user: GitHubUser,
+    head: GitHubBranchRef,
+    base: GitHubBranchRef,
+    html_url: String,
+    #[serde(default)]
+    merged_at: Option<String>,
+}
+
+impl From<GitHubPullRequest> for ForgeMergeRequest {
+    fn from(pr: GitHubPullRequest) -> Self {
+        let state = if pr.merged_at.is_some() {
+            "merged".to_string()
+        } else {
+            pr.state
+        };
+        Self {
+            number: pr.number,
+            title: pr.title,
+            state,
+            author: pr.user.login,
+            source_branch: pr.head.git_ref,
+            target_branch: pr.base.git_ref,
+            url: pr.html_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubComment {
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReview {
+    user: GitHubUser,
+    state: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    submitted_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReviewComment {
+    user: GitHubUser,
+    path: String,
+    #[serde(default)]
+    line: Option<u64>,
+    body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchKind {
+    Repositories,
+    Issues,
+    Code,
+}
+
+impl SearchKind {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "repositories" | "repos" | "repo" => Some(Self::Repositories),
+            "issues" | "prs" | "pull_requests" => Some(Self::Issues),
+            "code" => Some(Self::Code),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Repositories => "repositories",
+            Self::Issues => "issues",
+            Self::Code => "code",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubSearch<T> {
+    #[serde(default)]
+    total_count: u64,
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+}
+
+enum SearchResults {
+    Repositories(GitHubSearch<GitHubRepo>),
+    Issues(GitHubSearch<GitHubIssue>),
+    Code(GitHubSearch<GitHubCodeHit>),
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRepo {
+    full_name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    stargazers_count: u64,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCodeRepo {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCodeHit {
+    path: String,
+    repository: GitHubCodeRepo,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRuns {
+    #[serde(default)]
+    workflow_runs: Vec<GitHubRun>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GitHubRun {
+    id: u64,
+    #[serde(default)]
+    name: Option<String>,
+    status: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+    #[serde(default)]
+    head_branch: Option<String>,
+    head_sha: String,
+    html_url: String,
+}
+
+impl From<GitHubRun> for ForgePipeline {
+    fn from(run: GitHubRun) -> Self {
+        // Finished runs report the outcome in `conclusion`; `status` is just "completed".
+        let raw_status = match run.conclusion {
+            Some(conclusion) if run.status == "completed" => conclusion,
+            _ => run.status,
+        };
+        Self {
+            id: run.id,
+            status: normalize_pipeline_status(&raw_status),
+            branch: run.head_branch.unwrap_or_default(),
+            commit: run.head_sha,
+            url: run.html_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubJobs {
+    #[serde(default)]
+    jobs: Vec<GitHubJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubJob {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+    html_url: String,
+}
+
+impl GitHubJob {
+    fn is_failed(&self) -> bool {
+        matches!(
+            self.conclusion.as_deref(),
+            Some("failure" | "timed_out" | "startup_failure")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_url_uses_configured_api_root() {
+        let forge = GitHubForge::new(&GitHubConfig {
+            api_url: "https://ghe.example.com/api/v3/".into(),
+            token: "t".into(),
+            default_repo: Some("acme/app".into()),
+        });
+        assert_eq!(
+            forge.repo_url("/acme/app/"),
+            "https://ghe.example.com/api/v3/repos/acme/app"
+        );
+        assert_eq!(forge.default_repo(), Some("acme/app"));
+    }
+
+    #[test]
+    fn completed_run_uses_conclusion() {
+        let raw = r#"{"id": 11, "status": "completed", "conclusion": "failure", "head_branch": "main", "head_sha": "abc", "html_url": "https://github.com/a/b/actions/runs/11"}"#;
+        let p: ForgePipeline = serde_json::from_str::<GitHubRun>(raw).unwrap().into();
+        assert_eq!(p.status, "failed");
+        assert_eq!(p.branch, "main");
+    }
+
+    #[test]
+    fn in_progress_run_uses_status() {
+        let raw = r#"{"id": 12, "status": "in_progress", "conclusion": null, "head_branch": null, "head_sha": "abc", "html_url": "u"}"#;
+        let p: ForgePipeline = serde_json::from_str::<GitHubRun>(raw).unwrap().into();
+        assert_eq!(p.status, "running");
+        assert!(p.branch.is_empty());
+    }
+
+    #[test]
+    fn merged_pull_request_reports_merged() {
+        let raw = r#"{"number": 3, "title": "Fix", "state": "closed", "user": {"login": "dev"},
+            "head": {"ref": "fix"}, "base": {"ref": "main"}, "html_url": "u",
+            "merged_at": "2024-05-01T10:00:00Z"}"#;
+        let mr: ForgeMergeRequest = serde_json::from_str::<GitHubPullRequest>(raw)
+            .unwrap()
+            .into();
+        assert_eq!(mr.state, "merged");
+        assert_eq!(mr.source_branch, "fix");
+    }
+
+    fn tool(autonomy: crate::security::AutonomyLevel) -> GitHubTool {
+        let security = SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        };
+        GitHubTool::new(
+            &GitHubConfig {
+                api_url: "https://api.github.com".into(),
+                token: "t".into(),
+                default_repo: Some("acme/app".into()),
+            },
+            Arc::new(security),
+        )
+    }
+
+    #[tokio::test]
+    async fn writes_need_autonomy_and_their_fields() {
+        use crate::security::AutonomyLevel;
+
+        let readonly = tool(AutonomyLevel::ReadOnly)
+            .execute(json!({"action": "create_issue", "title": "Flaky test"}))
+            .await
+            .unwrap();
+        assert!(!readonly.success);
+        assert!(readonly.error.unwrap().contains("read-only"));
+
+        let tool = tool(AutonomyLevel::Supervised);
+        let untitled = tool
+            .execute(json!({"action": "create_issue", "body": "Fails on CI"}))
+            .await
+            .unwrap();
+        assert!(untitled.error.unwrap().contains("Missing 'title'"));
+        let unnumbered = tool
+            .execute(json!({"action": "comment", "body": "LGTM"}))
+            .await
+            .unwrap();
+        assert!(unnumbered.error.unwrap().contains("Missing 'number'"));
+        let bad_kind = tool
+            .execute(json!({"action": "search", "query": "zeroclaw", "kind": "users"}))
+            .await
+            .unwrap();
+        assert!(bad_kind.error.unwrap().contains("Invalid 'kind'"));
+        let unknown = tool.execute(json!({"action": "merge"})).await.unwrap();
+        assert!(unknown.error.unwrap().contains("Unknown action"));
+    }
+
+    #[test]
+    fn only_issue_and_comment_calls_are_write_class() {
+        let tool = tool(crate::security::AutonomyLevel::Interactive);
+        assert_eq!(tool.class(), ToolClass::Write);
+        for action in ["create_issue", "comment", "merge"] {
+            assert_eq!(
+                tool.call_class(&json!({"action": action})),
+                ToolClass::Write
+            );
+        }
+        for action in ["list_issues", "pr_reviews", "search", "run_status"] {
+            assert_eq!(tool.call_class(&json!({"action": action})), ToolClass::Read);
+        }
+    }
+
+    #[test]
+    fn reviews_skip_pending_and_list_inline_comments() {
+        let reviews: Vec<GitHubReview> = serde_json::from_str(
+            r#"[
+            {"user": {"login": "ana"}, "state": "CHANGES_REQUESTED", "body": "Needs a test", "submitted_at": "2024-05-01T10:00:00Z"},
+            {"user": {"login": "bo"}, "state": "APPROVED", "body": ""},
+            {"user": {"login": "me"}, "state": "PENDING", "body": "draft"}
+        ]"#,
+        )
+        .unwrap();
+        let comments: Vec<GitHubReviewComment> = serde_json::from_str(
+            r#"[{"user": {"login": "ana"}, "path": "src/lib.rs", "line": 42, "body": "off by one"}]"#,
+        )
+        .unwrap();
+        let out = format_reviews("acme/app", 7, &reviews, &comments);
+        assert!(out.starts_with("2 review(s) on acme/app#7"));
+        assert!(out.contains("❌ changes requested by ana (2024-05-01T10:00:00Z)\n  Needs a test"));
+        assert!(out.contains("✅ approved by bo"));
+        assert!(!out.contains("draft"));
+        assert!(out.contains("- ana on src/lib.rs:42: off by one"));
+        assert_eq!(
+            format_reviews("acme/app", 7, &[], &[]),
+            "No reviews yet on acme/app#7."
+        );
+    }
+
+    #[test]
+    fn search_results_name_their_kind() {
+        let issues: GitHubSearch<GitHubIssue> = serde_json::from_str(
+            r#"{"total_count": 31, "items": [
+            {"number": 5, "title": "Crash on start", "state": "open", "user": {"login": "dev"}, "html_url": "u5"},
+            {"number": 6, "title": "Fix crash", "state": "closed", "user": {"login": "dev"}, "html_url": "u6", "pull_request": {}}
+        ]}"#,
+        )
+        .unwrap();
+        let out = format_search("crash repo:acme/app", &SearchResults::Issues(issues));
+        assert!(out.starts_with("2 of 31 result(s) for 'crash repo:acme/app'"));
+        assert!(out.contains("- issue #5 [open] Crash on start"));
+        assert!(out.contains("- PR #6 [closed] Fix crash"));
+
+        let code: GitHubSearch<GitHubCodeHit> = serde_json::from_str(
+            r#"{"total_count": 1, "items": [{"path": "src/main.rs", "repository": {"full_name": "acme/app"}, "html_url": "u"}]}"#,
+        )
+        .unwrap();
+        assert!(
+            format_search("fn main", &SearchResults::Code(code)).contains("acme/app: src/main.rs")
+        );
+        assert_eq!(SearchKind::parse("Repos"), Some(SearchKind::Repositories));
+    }
+
+    #[test]
+    fn run_status_lists_each_job() {
+        let run: GitHubRun = serde_json::from_str(
+            r#"{"id": 11, "name": "CI", "status": "completed", "conclusion": "failure", "head_branch": "main", "head_sha": "abcdef123456", "html_url": "u"}"#,
+        )
+        .unwrap();
+        let jobs: GitHubJobs = serde_json::from_str(
+            r#"{"jobs": [
+            {"id": 1, "name": "lint", "status": "completed", "conclusion": "success", "html_url": "u1"},
+            {"id": 2, "name": "test", "status": "completed", "conclusion": "failure", "html_url": "u2"},
+            {"id": 3, "name": "deploy", "status": "queued", "conclusion": null, "html_url": "u3"}
+        ]}"#,
+        )
+        .unwrap();
+        let out = format_run(&run, &jobs.jobs);
+        assert!(out.starts_with("❌ Run #11 CI: failed on main (abcdef12)"));
+        assert!(out.contains("- ✅ lint (success)"));
+        assert!(out.contains("- ❌ test (failed)"));
+        assert!(out.contains("- ⏳ deploy (pending)"));
+    }
+
+    #[test]
+    fn only_failed_jobs_are_selected() {
+        let raw = r#"{"jobs": [
+            {"id": 1, "name": "lint", "conclusion": "success", "html_url": "u1"},
+            {"id": 2, "name": "test", "conclusion": "failure", "html_url": "u2"},
+            {"id": 3, "name": "build", "conclusion": null, "html_url": "u3"}
+        ]}"#;
+        let jobs: GitHubJobs = serde_json::from_str(raw).unwrap();
+        let failed: Vec<_> = jobs.jobs.iter().filter(|j| j.is_failed()).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].name, "test");
+    }
+}
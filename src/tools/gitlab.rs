@@ -0,0 +1,309 @@
+use super::code_forge::{
+    forge_client, forge_error, log_tail, normalize_pipeline_status, CodeForge, ForgeIssue,
+    ForgeJobLog, ForgeMergeRequest, ForgePipeline, ForgeState,
+};
+use crate::config::GitLabConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// Lines of each failed job's trace kept for failure explanations.
+const JOB_LOG_LINES: usize = 60;
+
+/// GitLab REST v4 backend (gitlab.com or self-managed).
+pub struct GitLabForge {
+    base_url: String,
+    token: String,
+    default_project: Option<String>,
+    client: LazyClient,
+}
+
+impl GitLabForge {
+    pub fn new(config: &GitLabConfig) -> Self {
+        Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            token: config.token.clone(),
+            default_project: config.default_project.clone(),
+            client: forge_client(),
+        }
+    }
+
+    fn project_url(&self, project: &str) -> String {
+        format!(
+            "{}/api/v4/projects/{}",
+            self.base_url,
+            encode_project_path(project)
+        )
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<T> {
+        let resp = self
+            .client
+            .get(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .query(query)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(forge_error("GitLab", resp).await);
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn get_text(&self, url: &str) -> anyhow::Result<String> {
+        let resp = self
+            .client
+            .get(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(forge_error("GitLab", resp).await);
+        }
+        Ok(resp.text().await?)
+    }
+}
+
+/// Projects can be addressed by numeric ID or URL-encoded `group/project` path.
+fn encode_project_path(project: &str) -> String {
+    project.trim().trim_matches('/').replace('/', "%2F")
+}
+
+fn state_param(state: ForgeState) -> &'static str {
+    match state {
+        ForgeState::Open => "opened",
+        ForgeState::Closed => "closed",
+        ForgeState::All => "all",
+    }
+}
+
+#[async_trait]
+impl CodeForge for GitLabForge {
+    fn name(&self) -> &str {
+        "gitlab"
+    }
+
+    fn display_name(&self) -> &str {
+        "GitLab"
+    }
+
+    fn default_repo(&self) -> Option<&str> {
+        self.default_project.as_deref()
+    }
+
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeIssue>> {
+        let url = format!("{}/issues", self.project_url(repo));
+        let issues: Vec<GitLabIssue> = self
+            .get_json(
+                &url,
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(issues.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_issue(&self, repo: &str, number: u64) -> anyhow::Result<ForgeIssue> {
+        let url = format!("{}/issues/{number}", self.project_url(repo));
+        let issue: GitLabIssue = self.get_json(&url, &[]).await?;
+        Ok(issue.into())
+    }
+
+    async fn list_merge_requests(
+        &self,
+        repo: &str,
+        state: ForgeState,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgeMergeRequest>> {
+        let url = format!("{}/merge_requests", self.project_url(repo));
+        let mrs: Vec<GitLabMergeRequest> = self
+            .get_json(
+                &url,
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(mrs.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_pipelines(
+        &self,
+        repo: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ForgePipeline>> {
+        let url = format!("{}/pipelines", self.project_url(repo));
+        let mut query = vec![("per_page", limit.to_string())];
+        if let Some(branch) = branch {
+            query.push(("ref", branch.to_string()));
+        }
+        let pipelines: Vec<GitLabPipeline> = self.get_json(&url, &query).await?;
+        Ok(pipelines.into_iter().map(Into::into).collect())
+    }
+
+    async fn failed_job_logs(
+        &self,
+        repo: &str,
+        pipeline_id: u64,
+    ) -> anyhow::Result<Vec<ForgeJobLog>> {
+        let url = format!("{}/pipelines/{pipeline_id}/jobs", self.project_url(repo));
+        let jobs: Vec<GitLabJob> = self
+            .get_json(&url, &[("scope[]", "failed".to_string())])
+            .await?;
+        let mut logs = Vec::with_capacity(jobs.len());
+        for job in jobs {
+            let trace_url = format!("{}/jobs/{}/trace", self.project_url(repo), job.id);
+            let raw = self.get_text(&trace_url).await?;
+            logs.push(ForgeJobLog {
+                name: job.name,
+                url: job.web_url,
+                log_tail: log_tail(&raw, JOB_LOG_LINES),
+            });
+        }
+        Ok(logs)
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabIssue {
+    iid: u64,
+    title: String,
+    state: String,
+    author: GitLabUser,
+    web_url: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+impl From<GitLabIssue> for ForgeIssue {
+    fn from(i: GitLabIssue) -> Self {
+        Self {
+            number: i.iid,
+            title: i.title,
+            state: i.state,
+            author: i.author.username,
+            url: i.web_url,
+            body: i.description.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    title: String,
+    state: String,
+    author: GitLabUser,
+    source_branch: String,
+    target_branch: String,
+    web_url: String,
+}
+
+impl From<GitLabMergeRequest> for ForgeMergeRequest {
+    fn from(mr: GitLabMergeRequest) -> Self {
+        Self {
+            number: mr.iid,
+            title: mr.title,
+            state: mr.state,
+            author: mr.author.username,
+            source_branch: mr.source_branch,
+            target_branch: mr.target_branch,
+            url: mr.web_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabPipeline {
+    id: u64,
+    status: String,
+    #[serde(rename = "ref")]
+    git_ref: String,
+    sha: String,
+    web_url: String,
+}
+
+impl From<GitLabPipeline> for ForgePipeline {
+    fn from(p: GitLabPipeline) -> Self {
+        Self {
+            id: p.id,
+            status: normalize_pipeline_status(&p.status),
+            branch: p.git_ref,
+            commit: p.sha,
+            url: p.web_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabJob {
+    id: u64,
+    name: String,
+    web_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_path_is_url_encoded() {
+        assert_eq!(
+            encode_project_path("group/sub/project"),
+            "group%2Fsub%2Fproject"
+        );
+        assert_eq!(encode_project_path("/group/project/"), "group%2Fproject");
+        assert_eq!(encode_project_path("1234"), "1234");
+    }
+
+    #[test]
+    fn base_url_trailing_slash_is_trimmed() {
+        let forge = GitLabForge::new(&GitLabConfig {
+            base_url: "https://gitlab.example.com/".into(),
+            token: "t".into(),
+            default_project: Some("team/app".into()),
+        });
+        assert_eq!(
+            forge.project_url("team/app"),
+            "https://gitlab.example.com/api/v4/projects/team%2Fapp"
+        );
+        assert_eq!(forge.default_repo(), Some("team/app"));
+    }
+
+    #[test]
+    fn issue_maps_to_forge_issue() {
+        let raw = r#"{"iid": 42, "title": "Crash", "state": "opened", "author": {"username": "bob"}, "web_url": "https://gitlab.com/a/b/-/issues/42", "description": null}"#;
+        let issue: ForgeIssue = serde_json::from_str::<GitLabIssue>(raw).unwrap().into();
+        assert_eq!(issue.number, 42);
+        assert_eq!(issue.author, "bob");
+        assert!(issue.body.is_empty());
+    }
+
+    #[test]
+    fn pipeline_maps_and_normalizes_status() {
+        let raw = r#"{"id": 9, "status": "canceled", "ref": "main", "sha": "abc", "web_url": "https://gitlab.com/a/b/-/pipelines/9"}"#;
+        let p: ForgePipeline = serde_json::from_str::<GitLabPipeline>(raw).unwrap().into();
+        assert_eq!(p.status, "cancelled");
+        assert_eq!(p.branch, "main");
+    }
+}
@@ -0,0 +1,199 @@
+// Goal tool — lets the user manage heartbeat goals from chat.
+
+use super::traits::{Tool, ToolResult};
+use crate::goals::{GoalStatus, GoalStore};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// List, add, pause, resume or remove standing goals
+pub struct GoalsTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl GoalsTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+
+    fn run(store: &GoalStore, args: &serde_json::Value) -> anyhow::Result<String> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
+        let id = || {
+            args.get("id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("'{action}' needs the goal 'id'"))
+                .and_then(|prefix| store.resolve_id(prefix))
+        };
+        match action {
+            "list" => {
+                let goals = store.list()?;
+                if goals.is_empty() {
+                    return Ok("No goals.".into());
+                }
+                let mut output = format!("{} goal(s):\n", goals.len());
+                for goal in &goals {
+                    let last = store.checks(&goal.id, 1)?.pop().map_or_else(
+                        || "never checked".to_string(),
+                        |c| format!("last: {} — {}", c.verdict.as_str(), c.detail),
+                    );
+                    let _ = writeln!(
+                        output,
+                        "- {} [{}] {} ({last})",
+                        goal.short_id(),
+                        goal.status.as_str(),
+                        goal.description
+                    );
+                }
+                Ok(output)
+            }
+            "add" => {
+                let description = args
+                    .get("description")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("'add' needs a 'description'"))?;
+                let every = args
+                    .get("interval_minutes")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|m| u32::try_from(m).ok());
+                let goal = store.create(description, every)?;
+                Ok(format!(
+                    "Added goal {}; the heartbeat will check it from now on.",
+                    goal.short_id()
+                ))
+            }
+            "history" => {
+                let id = id()?;
+                let mut output = String::new();
+                for check in store.checks(&id, 10)? {
+                    let _ = writeln!(
+                        output,
+                        "- [{}] {}: {}",
+                        check.at.to_rfc3339(),
+                        check.verdict.as_str(),
+                        check.detail
+                    );
+                }
+                if output.is_empty() {
+                    output = "Not checked yet.".into();
+                }
+                Ok(output)
+            }
+            "pause" | "resume" => {
+                let id = id()?;
+                let status = if action == "pause" {
+                    GoalStatus::Paused
+                } else {
+                    GoalStatus::Active
+                };
+                store.set_status(&id, status)?;
+                Ok(format!("Goal {} is now {}.", &id[..8], status.as_str()))
+            }
+            "remove" => {
+                let id = id()?;
+                store.remove(&id)?;
+                Ok(format!("Removed goal {}.", &id[..8]))
+            }
+            other => anyhow::bail!(
+                "Unknown action '{other}' (expected list, add, history, pause, resume or remove)"
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for GoalsTool {
+    fn name(&self) -> &str {
+        "goals"
+    }
+
+    fn description(&self) -> &str {
+        "Manage standing goals the heartbeat checks on a schedule and acts on within policy limits (e.g. 'keep my inbox under 20 unread'). Actions: list, add, history, pause, resume, remove."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "add", "history", "pause", "resume", "remove"],
+                    "description": "What to do (default: list)"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Goal ID (prefix is enough) for history, pause, resume and remove"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "For add: the condition to keep, in plain words"
+                },
+                "interval_minutes": {
+                    "type": "integer",
+                    "description": "For add: minutes between checks (default: every heartbeat)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let store = GoalStore::open(&self.security.workspace_dir)?;
+        match Self::run(&store, &args) {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn add_pause_and_list_goals() {
+        let tmp = TempDir::new().unwrap();
+        let tool = GoalsTool::new(Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }));
+
+        let added = tool
+            .execute(json!({"action": "add", "description": "Inbox under 20 unread"}))
+            .await
+            .unwrap();
+        assert!(added.success);
+        let id = added
+            .output
+            .split_whitespace()
+            .nth(2)
+            .unwrap()
+            .trim_end_matches(';');
+
+        let paused = tool
+            .execute(json!({"action": "pause", "id": id}))
+            .await
+            .unwrap();
+        assert!(paused.output.contains("paused"));
+
+        let listed = tool.execute(json!({})).await.unwrap();
+        assert!(listed
+            .output
+            .contains("[paused] Inbox under 20 unread (never checked)"));
+
+        let missing = tool.execute(json!({"action": "remove"})).await.unwrap();
+        assert!(!missing.success);
+    }
+}
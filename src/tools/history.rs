@@ -0,0 +1,432 @@
+// Workspace history — every tool call that changes files in the workspace is
+// committed to a local git repo under `~/.zeroclaw/history/`, kept apart from
+// any repo the user has in the workspace. The git dir lives outside the
+// workspace so the file tools can't plant hooks or config that git would run.
+// Edits made between calls are committed first under their own message, so
+// `zeroclaw undo` only reverts the agent's.
+
+use super::middleware::ToolMiddleware;
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+const HISTORY_DIR: &str = "history";
+/// Never versioned: the old in-workspace history and live databases.
+const EXCLUDE: [&str; 6] = [
+    "/.history/",
+    "*.db",
+    "*.db-*",
+    "*.sqlite",
+    "*.sqlite-*",
+    "/audit/",
+];
+const MANUAL_SUBJECT: &str = "Changes outside the agent";
+const ARGS_PREVIEW_CHARS: usize = 200;
+
+/// The history repo for one workspace.
+pub struct Repo {
+    git_dir: PathBuf,
+    work_tree: PathBuf,
+}
+
+/// One agent change, newest first in `Repo::changes`.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub hash: String,
+    pub subject: String,
+    pub session: String,
+    pub date: String,
+    pub undone: bool,
+}
+
+impl Repo {
+    /// The history of `workspace_dir`, in `zeroclaw_dir/history/<hash>`.
+    pub fn new(zeroclaw_dir: &Path, workspace_dir: &Path) -> Self {
+        let digest = hex::encode(Sha256::digest(workspace_dir.to_string_lossy().as_bytes()));
+        Self {
+            git_dir: zeroclaw_dir.join(HISTORY_DIR).join(&digest[..16]),
+            work_tree: workspace_dir.to_path_buf(),
+        }
+    }
+
+    pub fn for_config(config: &Config) -> Self {
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        Self::new(&zeroclaw_dir, &config.workspace_dir)
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String> {
+        let output = tokio::process::Command::new("git")
+            .arg("--git-dir")
+            .arg(&self.git_dir)
+            .arg("--work-tree")
+            .arg(&self.work_tree)
+            .args([
+                "-c",
+                "user.name=ZeroClaw",
+                "-c",
+                "user.email=zeroclaw@localhost",
+                "-c",
+                "commit.gpgsign=false",
+                // Nothing in the repo's config may run a command.
+                "-c",
+                "core.fsmonitor=",
+                "-c",
+                "core.hooksPath=/dev/null",
+            ])
+            .args(args)
+            .current_dir(&self.work_tree)
+            .output()
+            .await
+            .context("Failed to run git; is it installed?")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    async fn ensure(&self) -> Result<()> {
+        if self.git_dir.join("HEAD").exists() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.work_tree)?;
+        std::fs::create_dir_all(&self.git_dir)?;
+        self.git(&["init", "--quiet"]).await?;
+        let info = self.git_dir.join("info");
+        std::fs::create_dir_all(&info)?;
+        std::fs::write(info.join("exclude"), EXCLUDE.join("\n") + "\n")?;
+        Ok(())
+    }
+
+    /// Commit everything that changed; `false` if nothing did.
+    pub async fn commit_all(&self, message: &str) -> Result<bool> {
+        self.ensure().await?;
+        self.git(&["add", "--all"]).await?;
+        if self
+            .git(&["status", "--porcelain"])
+            .await?
+            .trim()
+            .is_empty()
+        {
+            return Ok(false);
+        }
+        self.git(&["commit", "--quiet", "--no-verify", "-m", message])
+            .await?;
+        Ok(true)
+    }
+
+    /// Agent changes, newest first, with the ones already undone marked.
+    pub async fn changes(&self) -> Result<Vec<Change>> {
+        if !self.git_dir.join("HEAD").exists() {
+            return Ok(Vec::new());
+        }
+        // Fails before the first commit
+        let Ok(log) = self
+            .git(&[
+                "log",
+                "--format=%H%x1f%s%x1f%as%x1f%(trailers:key=Tool,valueonly)%x1f\
+                 %(trailers:key=Session,valueonly)%x1f%(trailers:key=Reverts,valueonly)%x1e",
+            ])
+            .await
+        else {
+            return Ok(Vec::new());
+        };
+        let mut reverted: Vec<String> = Vec::new();
+        let mut changes = Vec::new();
+        for record in log.split('\x1e') {
+            let fields: Vec<&str> = record.trim_start().split('\x1f').collect();
+            let [hash, subject, date, tool, session, reverts] = fields[..] else {
+                continue;
+            };
+            reverted.extend(reverts.lines().map(|h| h.trim().to_string()));
+            if tool.trim().is_empty() {
+                continue;
+            }
+            changes.push(Change {
+                hash: hash.to_string(),
+                subject: subject.to_string(),
+                session: session.trim().to_string(),
+                date: date.to_string(),
+                undone: false,
+            });
+        }
+        for change in &mut changes {
+            change.undone = reverted.contains(&change.hash);
+        }
+        Ok(changes)
+    }
+
+    /// Revert the last `n` agent changes not yet undone, in one commit.
+    pub async fn undo(&self, n: usize) -> Result<Vec<Change>> {
+        let targets: Vec<Change> = self
+            .changes()
+            .await?
+            .into_iter()
+            .filter(|c| !c.undone)
+            .take(n)
+            .collect();
+        if targets.is_empty() {
+            return Ok(targets);
+        }
+        self.commit_all(MANUAL_SUBJECT).await?;
+        let mut args = vec!["revert", "--no-commit"];
+        args.extend(targets.iter().map(|c| c.hash.as_str()));
+        if let Err(e) = self.git(&args).await {
+            let _ = self.git(&["revert", "--abort"]).await;
+            return Err(e.context("Later edits overlap with these changes; nothing was undone"));
+        }
+        let mut message = match &targets[..] {
+            [only] => format!("Undo: {}\n\n", only.subject),
+            _ => format!("Undo {} agent changes\n\n", targets.len()),
+        };
+        for change in &targets {
+            message.push_str("Reverts: ");
+            message.push_str(&change.hash);
+            message.push('\n');
+        }
+        self.git(&["commit", "--quiet", "--no-verify", "-m", &message])
+            .await?;
+        Ok(targets)
+    }
+}
+
+/// Commit subject for a call: the tool and what it touched.
+fn subject(tool: &str, args: &Value) -> String {
+    let target = ["path", "file", "command", "name"]
+        .iter()
+        .find_map(|key| args.get(*key).and_then(Value::as_str))
+        .map(|t| t.lines().next().unwrap_or_default());
+    match target {
+        Some(t) if t.chars().count() > 60 => {
+            format!("{tool}: {}…", t.chars().take(60).collect::<String>())
+        }
+        Some(t) => format!("{tool}: {t}"),
+        None => tool.to_string(),
+    }
+}
+
+/// Commits the workspace after each tool call that changed it.
+pub struct WorkspaceHistory {
+    repo: Repo,
+    session: String,
+    /// Serializes commits from concurrent calls
+    lock: tokio::sync::Mutex<()>,
+    warned: AtomicBool,
+}
+
+impl WorkspaceHistory {
+    /// `session` is recorded in each commit, e.g. `cli:default` or `mcp`.
+    pub fn new(repo: Repo, session: &str) -> Self {
+        Self {
+            repo,
+            session: session.to_string(),
+            lock: tokio::sync::Mutex::new(()),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    fn warn(&self, e: &anyhow::Error) {
+        if !self.warned.swap(true, Ordering::Relaxed) {
+            tracing::warn!("Workspace history is not recording: {e:#}");
+        }
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for WorkspaceHistory {
+    fn name(&self) -> &str {
+        "workspace_history"
+    }
+
+    async fn before(
+        &self,
+        _tool: &dyn Tool,
+        _args: &mut Value,
+    ) -> anyhow::Result<Option<ToolResult>> {
+        let _guard = self.lock.lock().await;
+        if let Err(e) = self.repo.commit_all(MANUAL_SUBJECT).await {
+            self.warn(&e);
+        }
+        Ok(None)
+    }
+
+    async fn after(
+        &self,
+        tool: &dyn Tool,
+        args: &Value,
+        result: anyhow::Result<ToolResult>,
+        _elapsed: Duration,
+    ) -> anyhow::Result<ToolResult> {
+        if !result.as_ref().is_ok_and(|r| r.success) {
+            return result;
+        }
+        let preview: String = crate::providers::scrub_secret_patterns(&args.to_string())
+            .replace('\n', " ")
+            .chars()
+            .take(ARGS_PREVIEW_CHARS)
+            .collect();
+        let message = format!(
+            "{}\n\nSession: {}\nTool: {}\nArgs: {preview}\n",
+            subject(tool.name(), args),
+            self.session,
+            tool.name()
+        );
+        let _guard = self.lock.lock().await;
+        if let Err(e) = self.repo.commit_all(&message).await {
+            self.warn(&e);
+        }
+        result
+    }
+}
+
+/// `zeroclaw undo [n]` and `zeroclaw undo --list`.
+pub async fn handle_undo(config: &Config, n: usize, list: bool) -> Result<()> {
+    let repo = Repo::for_config(config);
+    if list {
+        let changes = repo.changes().await?;
+        if changes.is_empty() {
+            println!("No agent changes recorded yet.");
+        }
+        for change in changes.iter().take(20) {
+            println!(
+                "{} {} {}{} [{}]",
+                &change.hash[..8],
+                change.date,
+                change.subject,
+                if change.undone { " (undone)" } else { "" },
+                change.session
+            );
+        }
+        return Ok(());
+    }
+    let undone = repo.undo(n).await?;
+    if undone.is_empty() {
+        println!("Nothing to undo.");
+    }
+    for change in &undone {
+        println!("↩️  Undid {} ({})", change.subject, &change.hash[..8]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    /// Writes `content` to `path` in its workspace.
+    struct Write(PathBuf);
+
+    #[async_trait]
+    impl Tool for Write {
+        fn name(&self) -> &str {
+            "file_write"
+        }
+        fn description(&self) -> &str {
+            "Write a file"
+        }
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            std::fs::write(
+                self.0.join(args["path"].as_str().unwrap()),
+                args["content"].as_str().unwrap(),
+            )?;
+            Ok(ToolResult {
+                success: true,
+                output: "written".into(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn agent_changes_are_committed_and_undone() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = &tmp.path().join("workspace");
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("brain.db"), "not versioned").unwrap();
+        let repo = || Repo::new(tmp.path(), dir);
+        let history: Arc<dyn ToolMiddleware> = Arc::new(WorkspaceHistory::new(repo(), "cli:test"));
+        let tool = super::super::middleware::apply(
+            vec![Box::new(Write(dir.to_path_buf()))],
+            vec![history],
+        )
+        .remove(0);
+
+        let write = |content: &str| json!({"path": "notes.md", "content": content});
+        tool.execute(write("one")).await.unwrap();
+        tool.execute(write("two")).await.unwrap();
+        // Unchanged content: no new commit.
+        tool.execute(write("two")).await.unwrap();
+        std::fs::write(dir.join("mine.md"), "by hand").unwrap();
+
+        let repo = repo();
+        assert!(!repo.git_dir.starts_with(dir));
+        let changes = repo.changes().await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].subject, "file_write: notes.md");
+        assert_eq!(changes[0].session, "cli:test");
+
+        let undone = repo.undo(1).await.unwrap();
+        assert_eq!(undone[0].hash, changes[0].hash);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes.md")).unwrap(),
+            "one"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("mine.md")).unwrap(),
+            "by hand"
+        );
+
+        // The next undo skips what is already undone.
+        repo.undo(1).await.unwrap();
+        assert!(!dir.join("notes.md").exists());
+        assert!(repo.undo(1).await.unwrap().is_empty());
+        let tracked = repo.git(&["ls-files"]).await.unwrap();
+        assert!(!tracked.contains("brain.db"));
+    }
+
+    #[tokio::test]
+    async fn repo_config_cannot_run_commands() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = &tmp.path().join("workspace");
+        let repo = Repo::new(tmp.path(), dir);
+        repo.ensure().await.unwrap();
+        let marker = tmp.path().join("ran");
+        let command = format!("touch {}", marker.display());
+        repo.git(&["config", "core.fsmonitor", &command])
+            .await
+            .unwrap();
+        let hooks = repo.git_dir.join("hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("post-commit"), format!("#!/bin/sh\n{command}\n")).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(
+                hooks.join("post-commit"),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+        }
+
+        std::fs::write(dir.join("notes.md"), "hi").unwrap();
+        assert!(repo.commit_all("test").await.unwrap());
+        assert!(!marker.exists());
+    }
+}
@@ -0,0 +1,363 @@
+// Incident management abstraction — one tool surface for paging services.
+//
+// PagerDuty and Opsgenie implement `IncidentService`; `IncidentTool` wraps a
+// backend and lets the agent list what is currently paging, acknowledge it,
+// or trigger a new page that escalates through the service's on-call policy.
+
+use super::traits::{Tool, ToolResult};
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write;
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+/// Severity of a page triggered by the agent, mapped to each service's scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncidentSeverity {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+impl IncidentSeverity {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "critical" | "p1" | "high" => Some(Self::Critical),
+            "error" | "p2" => Some(Self::Error),
+            "warning" | "p3" | "medium" => Some(Self::Warning),
+            "info" | "p4" | "p5" | "low" => Some(Self::Info),
+            _ => None,
+        }
+    }
+}
+
+/// An open incident/alert, normalized across services.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: String,
+    pub title: String,
+    /// "triggered" or "acknowledged"
+    pub status: String,
+    pub urgency: String,
+    pub service: String,
+    pub url: String,
+    pub created_at: String,
+}
+
+/// Read/ack/trigger access to an on-call paging service.
+#[async_trait]
+pub trait IncidentService: Send + Sync {
+    /// Short service name; also used as the tool name (e.g. "pagerduty")
+    fn name(&self) -> &str;
+
+    /// Human-readable service name for descriptions and output
+    fn display_name(&self) -> &str;
+
+    /// Incidents that are triggered or acknowledged but not resolved
+    async fn list_open(&self, limit: usize) -> anyhow::Result<Vec<Incident>>;
+
+    async fn acknowledge(&self, id: &str) -> anyhow::Result<()>;
+
+    /// Open a new page; returns the service's identifier for it.
+    async fn trigger(
+        &self,
+        summary: &str,
+        severity: IncidentSeverity,
+        details: Option<&str>,
+    ) -> anyhow::Result<String>;
+}
+
+/// Exposes any `IncidentService` backend as an agent tool.
+pub struct IncidentTool {
+    service: Box<dyn IncidentService>,
+    description: String,
+}
+
+impl IncidentTool {
+    pub fn new(service: Box<dyn IncidentService>) -> Self {
+        let description = format!(
+            "Manage {} incidents: list open incidents, acknowledge one, or trigger a new page \
+             that escalates to whoever is on call. Actions: 'list', 'acknowledge', 'trigger'.",
+            service.display_name()
+        );
+        Self {
+            service,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for IncidentTool {
+    fn name(&self) -> &str {
+        self.service.name()
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "acknowledge", "trigger"],
+                    "description": "Operation to perform"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Incident/alert ID for 'acknowledge'"
+                },
+                "summary": {
+                    "type": "string",
+                    "description": "One-line description for 'trigger'"
+                },
+                "severity": {
+                    "type": "string",
+                    "enum": ["critical", "error", "warning", "info"],
+                    "description": "Severity for 'trigger' (default: error)"
+                },
+                "details": {
+                    "type": "string",
+                    "description": "Extra context attached to a triggered page"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": "Maximum incidents for 'list' (default: 20)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("list") => ToolClass::Read,
+            _ => ToolClass::Write,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        let result = match action {
+            "list" => {
+                let limit = args
+                    .get("limit")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .unwrap_or(DEFAULT_LIMIT)
+                    .clamp(1, MAX_LIMIT);
+                self.service
+                    .list_open(limit)
+                    .await
+                    .map(|incidents| format_incidents(self.service.display_name(), &incidents))
+            }
+            "acknowledge" => {
+                let id = args
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'id' for acknowledge"))?;
+                self.service
+                    .acknowledge(id)
+                    .await
+                    .map(|()| format!("Acknowledged {id}."))
+            }
+            "trigger" => {
+                let summary = args
+                    .get("summary")
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'summary' for trigger"))?;
+                let severity = match args.get("severity").and_then(|v| v.as_str()) {
+                    None => IncidentSeverity::Error,
+                    Some(raw) => IncidentSeverity::parse(raw)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid 'severity': {raw}"))?,
+                };
+                let details = args.get("details").and_then(|v| v.as_str());
+                self.service
+                    .trigger(summary, severity, details)
+                    .await
+                    .map(|key| format!("Triggered page ({key}): {summary}"))
+            }
+            _ => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Unknown action '{action}'. Use 'list', 'acknowledge', or 'trigger'."
+                    )),
+                })
+            }
+        };
+
+        match result {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "{} request failed: {e}",
+                    self.service.display_name()
+                )),
+            }),
+        }
+    }
+}
+
+fn format_incidents(service: &str, incidents: &[Incident]) -> String {
+    if incidents.is_empty() {
+        return format!("No open {service} incidents.");
+    }
+    let mut out = format!("{} open {service} incident(s):\n", incidents.len());
+    for i in incidents {
+        let icon = if i.status == "acknowledged" {
+            "🟡"
+        } else {
+            "🔴"
+        };
+        let _ = writeln!(
+            out,
+            "- {icon} [{}] {} — {} ({}, {})\n  id: {} | {}",
+            i.urgency, i.title, i.service, i.status, i.created_at, i.id, i.url
+        );
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FakeService {
+        acked: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl IncidentService for FakeService {
+        fn name(&self) -> &str {
+            "fakepager"
+        }
+        fn display_name(&self) -> &str {
+            "FakePager"
+        }
+        async fn list_open(&self, limit: usize) -> anyhow::Result<Vec<Incident>> {
+            Ok((0..limit.min(2))
+                .map(|n| Incident {
+                    id: format!("INC{n}"),
+                    title: "Database down".into(),
+                    status: if n == 0 { "triggered" } else { "acknowledged" }.into(),
+                    urgency: "high".into(),
+                    service: "db".into(),
+                    url: format!("https://pager/{n}"),
+                    created_at: "2024-05-01T10:00:00Z".into(),
+                })
+                .collect())
+        }
+        async fn acknowledge(&self, id: &str) -> anyhow::Result<()> {
+            if id == "missing" {
+                anyhow::bail!("404 not found");
+            }
+            self.acked.lock().unwrap().push(id.to_string());
+            Ok(())
+        }
+        async fn trigger(
+            &self,
+            _summary: &str,
+            severity: IncidentSeverity,
+            _details: Option<&str>,
+        ) -> anyhow::Result<String> {
+            Ok(format!("{severity:?}"))
+        }
+    }
+
+    #[test]
+    fn severity_parses_priorities() {
+        assert_eq!(
+            IncidentSeverity::parse("P1"),
+            Some(IncidentSeverity::Critical)
+        );
+        assert_eq!(
+            IncidentSeverity::parse("warning"),
+            Some(IncidentSeverity::Warning)
+        );
+        assert_eq!(IncidentSeverity::parse("urgent"), None);
+    }
+
+    #[tokio::test]
+    async fn list_marks_acknowledged_incidents() {
+        let tool = IncidentTool::new(Box::new(FakeService::default()));
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.starts_with("2 open FakePager incident(s)"));
+        assert!(result.output.contains("🔴 [high] Database down"));
+        assert!(result.output.contains("🟡"));
+    }
+
+    #[tokio::test]
+    async fn acknowledge_requires_id() {
+        let tool = IncidentTool::new(Box::new(FakeService::default()));
+        assert!(tool
+            .execute(json!({"action": "acknowledge"}))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn acknowledge_failure_is_reported() {
+        let tool = IncidentTool::new(Box::new(FakeService::default()));
+        let result = tool
+            .execute(json!({"action": "acknowledge", "id": "missing"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("FakePager request failed"));
+    }
+
+    #[tokio::test]
+    async fn trigger_defaults_to_error_severity() {
+        let tool = IncidentTool::new(Box::new(FakeService::default()));
+        let result = tool
+            .execute(json!({"action": "trigger", "summary": "Disk full"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "Triggered page (Error): Disk full");
+    }
+
+    #[tokio::test]
+    async fn trigger_rejects_unknown_severity() {
+        let tool = IncidentTool::new(Box::new(FakeService::default()));
+        let result = tool
+            .execute(json!({"action": "trigger", "summary": "x", "severity": "apocalyptic"}))
+            .await;
+        assert!(result.is_err());
+    }
+}
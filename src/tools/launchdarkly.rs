@@ -0,0 +1,186 @@
+use super::feature_flags::{FeatureFlag, FeatureFlagService};
+use crate::config::LaunchDarklyConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// `LaunchDarkly` REST v2 backend; toggles use semantic patch so only `on` changes.
+pub struct LaunchDarklyService {
+    base_url: String,
+    api_token: String,
+    project_key: String,
+    environment_key: String,
+    client: LazyClient,
+}
+
+impl LaunchDarklyService {
+    pub fn new(config: &LaunchDarklyConfig) -> Self {
+        Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            api_token: config.api_token.clone(),
+            project_key: config.project_key.clone(),
+            environment_key: config.environment_key.clone(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    fn flags_url(&self) -> String {
+        format!("{}/api/v2/flags/{}", self.base_url, self.project_key)
+    }
+
+    fn to_flag(&self, raw: LaunchDarklyFlag) -> FeatureFlag {
+        FeatureFlag {
+            enabled: raw
+                .environments
+                .get(&self.environment_key)
+                .is_some_and(|env| env.on),
+            key: raw.key,
+            name: raw.name,
+            description: raw.description.unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureFlagService for LaunchDarklyService {
+    fn name(&self) -> &str {
+        "launchdarkly"
+    }
+
+    fn display_name(&self) -> &str {
+        "LaunchDarkly"
+    }
+
+    fn environment(&self) -> &str {
+        &self.environment_key
+    }
+
+    async fn list_flags(&self, limit: usize) -> anyhow::Result<Vec<FeatureFlag>> {
+        let resp = self
+            .client
+            .get(self.flags_url())
+            .header("Authorization", &self.api_token)
+            .query(&[("env", self.environment_key.as_str()), ("summary", "true")])
+            .query(&[("limit", limit)])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("LaunchDarkly", resp).await);
+        }
+        let page: LaunchDarklyFlags = resp.json().await?;
+        Ok(page
+            .items
+            .into_iter()
+            .map(|raw| self.to_flag(raw))
+            .collect())
+    }
+
+    async fn get_flag(&self, key: &str) -> anyhow::Result<FeatureFlag> {
+        let resp = self
+            .client
+            .get(format!("{}/{key}", self.flags_url()))
+            .header("Authorization", &self.api_token)
+            .query(&[("env", self.environment_key.as_str())])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("LaunchDarkly", resp).await);
+        }
+        Ok(self.to_flag(resp.json().await?))
+    }
+
+    async fn set_flag(&self, key: &str, enabled: bool) -> anyhow::Result<()> {
+        let resp = self
+            .client
+            .patch(format!("{}/{key}", self.flags_url()))
+            .header("Authorization", &self.api_token)
+            .header(
+                "Content-Type",
+                "application/json; domain-model=launchdarkly.semanticpatch",
+            )
+            .body(toggle_patch(&self.environment_key, enabled).to_string())
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("LaunchDarkly", resp).await);
+        }
+        Ok(())
+    }
+}
+
+fn toggle_patch(environment_key: &str, enabled: bool) -> serde_json::Value {
+    json!({
+        "environmentKey": environment_key,
+        "comment": "Toggled by ZeroClaw",
+        "instructions": [{ "kind": if enabled { "turnFlagOn" } else { "turnFlagOff" } }],
+    })
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct LaunchDarklyFlags {
+    #[serde(default)]
+    items: Vec<LaunchDarklyFlag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LaunchDarklyFlag {
+    key: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    environments: HashMap<String, LaunchDarklyEnvironment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LaunchDarklyEnvironment {
+    #[serde(default)]
+    on: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> LaunchDarklyService {
+        LaunchDarklyService::new(&LaunchDarklyConfig {
+            base_url: "https://app.launchdarkly.com/".into(),
+            api_token: "api-123".into(),
+            project_key: "web".into(),
+            environment_key: "production".into(),
+        })
+    }
+
+    #[test]
+    fn flag_state_comes_from_configured_environment() {
+        let raw = r#"{"key": "new-checkout", "name": "New checkout", "environments": {
+            "production": {"on": false}, "staging": {"on": true}}}"#;
+        let flag = service().to_flag(serde_json::from_str(raw).unwrap());
+        assert!(!flag.enabled);
+        assert_eq!(flag.key, "new-checkout");
+    }
+
+    #[test]
+    fn toggle_patch_uses_semantic_instructions() {
+        let patch = toggle_patch("production", false);
+        assert_eq!(patch["environmentKey"], "production");
+        assert_eq!(patch["instructions"][0]["kind"], "turnFlagOff");
+        assert_eq!(
+            toggle_patch("production", true)["instructions"][0]["kind"],
+            "turnFlagOn"
+        );
+    }
+
+    #[test]
+    fn flags_url_includes_project() {
+        assert_eq!(
+            service().flags_url(),
+            "https://app.launchdarkly.com/api/v2/flags/web"
+        );
+    }
+}
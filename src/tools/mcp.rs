@@ -0,0 +1,64 @@
+// MCP tool — one tool of a Model Context Protocol server, named
+// `<server>__<tool>` so servers can't shadow built-ins or each other.
+
+use super::traits::{Tool, ToolResult};
+use crate::mcp::{McpClient, McpToolInfo};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct McpTool {
+    client: Arc<McpClient>,
+    info: McpToolInfo,
+    name: String,
+    description: String,
+    remote: bool,
+}
+
+impl McpTool {
+    /// `remote` marks tools of a server reached over the network.
+    pub fn new(client: Arc<McpClient>, info: McpToolInfo, remote: bool) -> Self {
+        let name = format!("{}__{}", client.server(), info.name);
+        let description = if info.description.is_empty() {
+            format!("`{}` from the {} MCP server", info.name, client.server())
+        } else {
+            format!("{} (MCP server: {})", info.description, client.server())
+        };
+        Self {
+            client,
+            info,
+            name,
+            description,
+            remote,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.info.input_schema.clone()
+    }
+
+    fn needs_network(&self) -> bool {
+        self.remote
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        match self.client.call_tool(&self.info.name, args).await {
+            Ok(result) => Ok(result),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            }),
+        }
+    }
+}
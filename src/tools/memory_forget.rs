@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
 use crate::memory::Memory;
+use crate::security::ToolClass;
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -38,6 +39,10 @@ impl Tool for MemoryForgetTool {
         })
     }
 
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let key = args
             .get("key")
@@ -0,0 +1,684 @@
+// Middleware around `Tool::execute` — argument validation, rate limits,
+// audit logging, secret redaction and call accounting are written once and
+// wrapped around every tool. Skills and embedding code add their own with
+// `tools::registry::register_middleware`; `from_config` appends them after
+// the built-ins.
+
+use super::traits::{Cost, Latency, Tool, ToolResult};
+use crate::config::Config;
+use crate::registry::{OnCollision, PluginInfo, PluginKind, PluginSource, Registered, Registry};
+use crate::security::session_secrets::session_secrets;
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Hooks run around a tool call.
+///
+/// `before` hooks run in chain order and `after` hooks in reverse, so the
+/// first middleware sees the call first and the final result last.
+#[async_trait]
+pub trait ToolMiddleware: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Inspect or rewrite the arguments. Returning a result skips the tool
+    /// and the rest of the chain; an error does the same.
+    async fn before(
+        &self,
+        _tool: &dyn Tool,
+        _args: &mut Value,
+    ) -> anyhow::Result<Option<ToolResult>> {
+        Ok(None)
+    }
+
+    /// Inspect or replace the outcome. Only middlewares whose `before` ran
+    /// see it.
+    async fn after(
+        &self,
+        _tool: &dyn Tool,
+        _args: &Value,
+        result: anyhow::Result<ToolResult>,
+        _elapsed: Duration,
+    ) -> anyhow::Result<ToolResult> {
+        result
+    }
+}
+
+type Chain = Arc<Vec<Arc<dyn ToolMiddleware>>>;
+
+/// A tool with a middleware chain around its `execute`.
+pub struct MiddlewareTool {
+    inner: Box<dyn Tool>,
+    chain: Chain,
+}
+
+#[async_trait]
+impl Tool for MiddlewareTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.inner.parameters_schema()
+    }
+
+    fn needs_network(&self) -> bool {
+        self.inner.needs_network()
+    }
+
+    fn latency(&self) -> Latency {
+        self.inner.latency()
+    }
+
+    fn cost(&self) -> Cost {
+        self.inner.cost()
+    }
+
+    fn class(&self) -> ToolClass {
+        self.inner.class()
+    }
+
+    fn call_class(&self, args: &Value) -> ToolClass {
+        self.inner.call_class(args)
+    }
+
+    async fn execute(&self, mut args: Value) -> anyhow::Result<ToolResult> {
+        let tool = self.inner.as_ref();
+        let started = Instant::now();
+        let mut ran = 0;
+        let mut answered = None;
+        for middleware in self.chain.iter() {
+            ran += 1;
+            match middleware.before(tool, &mut args).await {
+                Ok(None) => {}
+                Ok(Some(result)) => {
+                    answered = Some(Ok(result));
+                    break;
+                }
+                Err(e) => {
+                    answered = Some(Err(e));
+                    break;
+                }
+            }
+        }
+        let mut result = match answered {
+            Some(result) => result,
+            None => tool.execute(args.clone()).await,
+        };
+        let elapsed = started.elapsed();
+        for middleware in self.chain[..ran].iter().rev() {
+            result = middleware.after(tool, &args, result, elapsed).await;
+        }
+        result
+    }
+}
+
+/// Wrap every tool in `chain`; an empty chain leaves the tools untouched.
+pub fn apply(tools: Vec<Box<dyn Tool>>, chain: Vec<Arc<dyn ToolMiddleware>>) -> Vec<Box<dyn Tool>> {
+    if chain.is_empty() {
+        return tools;
+    }
+    let chain: Chain = Arc::new(chain);
+    tools
+        .into_iter()
+        .map(|inner| {
+            Box::new(MiddlewareTool {
+                inner,
+                chain: chain.clone(),
+            }) as Box<dyn Tool>
+        })
+        .collect()
+}
+
+/// The built-ins enabled in `[tool_middleware]`, then every registered one.
+/// `session` labels workspace history commits.
+pub fn from_config(config: &Config, session: &str) -> Vec<Arc<dyn ToolMiddleware>> {
+    let settings = &config.tool_middleware;
+    let mut chain: Vec<Arc<dyn ToolMiddleware>> = Vec::new();
+    if settings.audit_log {
+        chain.push(Arc::new(AuditLog::new(
+            config.workspace_dir.join("audit").join("tools.jsonl"),
+        )));
+    }
+    if !settings.rate_limits.is_empty() {
+        chain.push(Arc::new(RateLimit::new(
+            settings
+                .rate_limits
+                .iter()
+                .map(|(tool, per_minute)| (tool.clone(), *per_minute)),
+        )));
+    }
+    if settings.validate_args {
+        chain.push(Arc::new(ValidateArgs));
+    }
+    if settings.workspace_history {
+        chain.push(Arc::new(super::history::WorkspaceHistory::new(
+            super::history::Repo::for_config(config),
+            session,
+        )));
+    }
+    chain.extend(
+        registry()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(_, middleware)| middleware.clone()),
+    );
+    // Last in the chain, so its `after` runs first and every other
+    // middleware already sees the redacted result.
+    chain.push(Arc::new(RedactSecrets));
+    chain
+}
+
+fn refuse(message: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message),
+    }
+}
+
+// ── Argument validation ──────────────────────────────────────────
+
+/// Rejects calls missing required arguments or passing the wrong JSON type,
+/// so the model gets a precise error instead of a confused tool.
+pub struct ValidateArgs;
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Why `args` does not fit `schema`, if it does not. Only the top level and
+/// the JSON types are checked; tools still validate their own values.
+pub fn argument_error(schema: &Value, args: &Value) -> Option<String> {
+    let Some(args) = args.as_object() else {
+        return Some("arguments must be a JSON object".into());
+    };
+    for required in schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if args.get(required).is_none_or(Value::is_null) {
+            return Some(format!("missing required argument \"{required}\""));
+        }
+    }
+    let properties = schema.get("properties").and_then(Value::as_object)?;
+    for (name, value) in args {
+        let Some(expected) = properties.get(name).and_then(|p| p.get("type")) else {
+            continue;
+        };
+        let fits = match expected {
+            Value::String(t) => type_matches(t, value),
+            // `"type": ["string", "null"]`
+            Value::Array(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|t| type_matches(t, value)),
+            _ => true,
+        };
+        if !fits {
+            return Some(format!("argument \"{name}\" must be of type {expected}"));
+        }
+    }
+    None
+}
+
+#[async_trait]
+impl ToolMiddleware for ValidateArgs {
+    fn name(&self) -> &str {
+        "validate_args"
+    }
+
+    async fn before(
+        &self,
+        tool: &dyn Tool,
+        args: &mut Value,
+    ) -> anyhow::Result<Option<ToolResult>> {
+        // Models often send nothing at all for tools without parameters.
+        if args.is_null() {
+            *args = json!({});
+        }
+        Ok(argument_error(&tool.parameters_schema(), args)
+            .map(|e| refuse(format!("Invalid arguments for {}: {e}", tool.name()))))
+    }
+}
+
+// ── Rate limiting ────────────────────────────────────────────────
+
+/// Caps calls per tool over a sliding minute.
+pub struct RateLimit {
+    per_minute: HashMap<String, u32>,
+    calls: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RateLimit {
+    pub fn new(limits: impl IntoIterator<Item = (String, u32)>) -> Self {
+        Self {
+            per_minute: limits.into_iter().collect(),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for RateLimit {
+    fn name(&self) -> &str {
+        "rate_limit"
+    }
+
+    async fn before(
+        &self,
+        tool: &dyn Tool,
+        _args: &mut Value,
+    ) -> anyhow::Result<Option<ToolResult>> {
+        let Some(&limit) = self.per_minute.get(tool.name()) else {
+            return Ok(None);
+        };
+        let refusal = || {
+            refuse(format!(
+                "Rate limit: {} allows {limit} calls per minute; try again later",
+                tool.name()
+            ))
+        };
+        // Shared across processes with `[redis]`, in fixed minutes.
+        if let Some(n) =
+            crate::redis::count(&format!("tool:{}", tool.name()), Duration::from_mins(1))
+        {
+            return Ok((n > u64::from(limit)).then(refusal));
+        }
+        let now = Instant::now();
+        let mut calls = self
+            .calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let recent = calls.entry(tool.name().to_string()).or_default();
+        while recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Duration::from_mins(1))
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= limit as usize {
+            return Ok(Some(refusal()));
+        }
+        recent.push_back(now);
+        Ok(None)
+    }
+}
+
+// ── Secret redaction ─────────────────────────────────────────────
+
+/// Replaces the values of secrets held for this session (see
+/// `security::session_secrets`) with their variable names in every result.
+pub struct RedactSecrets;
+
+#[async_trait]
+impl ToolMiddleware for RedactSecrets {
+    fn name(&self) -> &str {
+        "redact_secrets"
+    }
+
+    async fn after(
+        &self,
+        _tool: &dyn Tool,
+        _args: &Value,
+        result: anyhow::Result<ToolResult>,
+        _elapsed: Duration,
+    ) -> anyhow::Result<ToolResult> {
+        let secrets = session_secrets();
+        match result {
+            Ok(mut result) => {
+                result.output = secrets.redact(&result.output);
+                result.error = result.error.map(|e| secrets.redact(&e));
+                Ok(result)
+            }
+            Err(e) => Err(anyhow::anyhow!(secrets.redact(&format!("{e:#}")))),
+        }
+    }
+}
+
+// ── Audit log ────────────────────────────────────────────────────
+
+/// Appends one JSON line per call, with secrets scrubbed from the arguments.
+pub struct AuditLog {
+    path: PathBuf,
+    file: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: Mutex::new(()),
+        }
+    }
+
+    fn append(&self, line: &Value) -> std::io::Result<()> {
+        let _guard = self
+            .file
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for AuditLog {
+    fn name(&self) -> &str {
+        "audit_log"
+    }
+
+    async fn after(
+        &self,
+        tool: &dyn Tool,
+        args: &Value,
+        result: anyhow::Result<ToolResult>,
+        elapsed: Duration,
+    ) -> anyhow::Result<ToolResult> {
+        let (success, error) = match &result {
+            Ok(r) => (r.success, r.error.clone()),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        let line = json!({
+            "ts": chrono::Utc::now().to_rfc3339(),
+            "tool": tool.name(),
+            "args": crate::providers::scrub_secret_patterns(&args.to_string()),
+            "success": success,
+            "error": error,
+            "ms": u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        });
+        if let Err(e) = self.append(&line) {
+            tracing::warn!("Writing tool audit log {} failed: {e}", self.path.display());
+        }
+        result
+    }
+}
+
+// ── Call accounting ──────────────────────────────────────────────
+
+/// Calls, failures and time spent for one tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolUsage {
+    pub calls: u64,
+    pub failures: u64,
+    pub total_time: Duration,
+}
+
+/// Counts calls and time per tool, for cost reports.
+#[derive(Default)]
+pub struct CallStats {
+    usage: Mutex<HashMap<String, ToolUsage>>,
+}
+
+impl CallStats {
+    /// Usage per tool, sorted by name.
+    pub fn snapshot(&self) -> Vec<(String, ToolUsage)> {
+        let mut usage: Vec<_> = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(name, usage)| (name.clone(), *usage))
+            .collect();
+        usage.sort_by(|a, b| a.0.cmp(&b.0));
+        usage
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for CallStats {
+    fn name(&self) -> &str {
+        "call_stats"
+    }
+
+    async fn after(
+        &self,
+        tool: &dyn Tool,
+        _args: &Value,
+        result: anyhow::Result<ToolResult>,
+        elapsed: Duration,
+    ) -> anyhow::Result<ToolResult> {
+        let mut usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = usage.entry(tool.name().to_string()).or_default();
+        entry.calls += 1;
+        entry.failures += u64::from(!result.as_ref().is_ok_and(|r| r.success));
+        entry.total_time += elapsed;
+        drop(usage);
+        result
+    }
+}
+
+// ── Registration ─────────────────────────────────────────────────
+
+static MIDDLEWARE: OnceLock<Mutex<Registry<Arc<dyn ToolMiddleware>>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry<Arc<dyn ToolMiddleware>>> {
+    MIDDLEWARE.get_or_init(|| Mutex::new(Registry::new()))
+}
+
+/// Register middleware for every agent built after this call. It runs after
+/// the built-ins, in registration order.
+pub fn register_middleware(
+    middleware: Arc<dyn ToolMiddleware>,
+    source: PluginSource,
+    on_collision: OnCollision,
+) -> anyhow::Result<Registered> {
+    let info = PluginInfo::new(PluginKind::Middleware, middleware.name(), source);
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .register(info, middleware, on_collision)
+}
+
+pub fn unregister_middleware(name: &str) -> bool {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(name)
+        .is_some()
+}
+
+/// Metadata for every registered middleware.
+pub fn registered_middleware() -> Vec<PluginInfo> {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .infos()
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    #[async_trait]
+    impl Tool for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the input back"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({
+                "type": "object",
+                "properties": {"text": {"type": "string"}, "times": {"type": "integer"}},
+                "required": ["text"]
+            })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args["text"].as_str().unwrap_or_default().to_string(),
+                error: None,
+            })
+        }
+    }
+
+    /// Upper-cases arguments on the way in and tags output on the way out.
+    struct Shout;
+
+    #[async_trait]
+    impl ToolMiddleware for Shout {
+        fn name(&self) -> &str {
+            "shout"
+        }
+
+        async fn before(
+            &self,
+            _tool: &dyn Tool,
+            args: &mut Value,
+        ) -> anyhow::Result<Option<ToolResult>> {
+            if let Some(text) = args["text"].as_str() {
+                args["text"] = json!(text.to_uppercase());
+            }
+            Ok(None)
+        }
+
+        async fn after(
+            &self,
+            _tool: &dyn Tool,
+            _args: &Value,
+            result: anyhow::Result<ToolResult>,
+            _elapsed: Duration,
+        ) -> anyhow::Result<ToolResult> {
+            result.map(|mut r| {
+                r.output = format!("<{}>", r.output);
+                r
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn held_secrets_are_redacted_from_results() {
+        session_secrets().hold("ZEROCLAW_SECRET_MIDDLEWARE_TEST", "s3cr3t-mw".into());
+        let tool = apply(vec![Box::new(Echo)], vec![Arc::new(RedactSecrets)]).remove(0);
+        let result = tool
+            .execute(json!({"text": "token=s3cr3t-mw"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "token=$ZEROCLAW_SECRET_MIDDLEWARE_TEST");
+    }
+
+    #[tokio::test]
+    async fn chain_rewrites_validates_and_accounts() {
+        let stats = Arc::new(CallStats::default());
+        let chain: Vec<Arc<dyn ToolMiddleware>> =
+            vec![stats.clone(), Arc::new(ValidateArgs), Arc::new(Shout)];
+        let tool = apply(vec![Box::new(Echo)], chain).remove(0);
+
+        let ok = tool.execute(json!({"text": "hi"})).await.unwrap();
+        assert_eq!(ok.output, "<HI>");
+
+        let missing = tool.execute(json!({"times": 2})).await.unwrap();
+        assert!(!missing.success);
+        assert_eq!(
+            missing.error.as_deref(),
+            Some("Invalid arguments for echo: missing required argument \"text\"")
+        );
+        // Validation answered, so `Shout` never saw the call.
+        assert_eq!(missing.output, "");
+
+        let wrong_type = tool
+            .execute(json!({"text": "a", "times": "2"}))
+            .await
+            .unwrap();
+        assert!(wrong_type
+            .error
+            .unwrap()
+            .contains("\"times\" must be of type"));
+
+        let usage = stats.snapshot();
+        assert_eq!(usage[0].0, "echo");
+        assert_eq!(usage[0].1.calls, 3);
+        assert_eq!(usage[0].1.failures, 2);
+    }
+
+    #[tokio::test]
+    async fn rate_limits_and_audit_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit").join("tools.jsonl");
+        let chain: Vec<Arc<dyn ToolMiddleware>> = vec![
+            Arc::new(AuditLog::new(path.clone())),
+            Arc::new(RateLimit::new([("echo".to_string(), 2)])),
+        ];
+        let tool = apply(vec![Box::new(Echo)], chain).remove(0);
+        for _ in 0..2 {
+            assert!(
+                tool.execute(json!({"text": "sk-abcdefghijklmnopqrstuvwx"}))
+                    .await
+                    .unwrap()
+                    .success
+            );
+        }
+        let limited = tool.execute(json!({"text": "x"})).await.unwrap();
+        assert!(limited
+            .error
+            .unwrap()
+            .starts_with("Rate limit: echo allows 2"));
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<Value> = log
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2]["success"], false);
+        assert!(!lines[0]["args"]
+            .as_str()
+            .unwrap()
+            .contains("abcdefghijklmnop"));
+    }
+    #[test]
+    fn skills_register_through_tools_registry() {
+        use crate::tools::registry;
+
+        let source = PluginSource::Skill("loud".into());
+        registry::register_middleware(Arc::new(Shout), source.clone(), OnCollision::Reject)
+            .unwrap();
+        assert!(registry::registered_middleware()
+            .iter()
+            .any(|info| info.name == "shout" && info.source == source));
+        assert!(from_config(&Config::default(), "test")
+            .iter()
+            .any(|m| m.name() == "shout"));
+        assert!(registry::unregister_middleware("shout"));
+        assert!(!registry::unregister_middleware("shout"));
+    }
+}
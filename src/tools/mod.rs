@@ -1,30 +1,112 @@
+pub mod approval;
+pub mod aws_cost;
+pub mod bitbucket;
 pub mod browser;
+pub mod browser_cdp;
 pub mod browser_open;
+pub mod calendar;
+pub mod ci_status;
+pub mod cloud_cost;
+pub mod code_forge;
 pub mod composio;
+pub mod config_update;
+pub mod delegate;
+pub mod dry_run;
+pub mod feature_flags;
+pub mod federation;
 pub mod file_read;
 pub mod file_write;
+pub mod fs;
+pub mod fs_search;
+pub mod gcp_billing;
+pub mod github;
+pub mod gitlab;
+pub mod goals;
+pub mod history;
+pub mod incident;
+pub mod launchdarkly;
+pub mod mcp;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
+pub mod middleware;
+pub mod notify;
+pub mod offline;
+pub mod opsgenie;
+pub mod pagerduty;
+pub mod password_manager;
+pub mod plugin;
+pub mod reddit;
+pub mod registry;
+pub mod reply;
+pub mod send_message;
+pub mod sentry;
+pub mod share_file;
 pub mod shell;
+pub mod shortcuts;
+pub mod skill;
+pub mod speak;
+pub mod tasks;
+pub mod terraform_plan;
 pub mod traits;
+pub mod unleash;
 pub mod weather_api;
+pub mod web_search;
 
+pub use aws_cost::AwsCostExplorer;
+pub use bitbucket::BitbucketForge;
 pub use browser::BrowserTool;
 pub use browser_open::BrowserOpenTool;
+pub use calendar::CalendarTool;
+pub use ci_status::CiStatusTool;
+pub use cloud_cost::CloudCostTool;
+pub use code_forge::CodeForgeTool;
 pub use composio::ComposioTool;
+pub use config_update::ConfigUpdateTool;
+pub use delegate::DelegateTool;
+pub use feature_flags::FeatureFlagTool;
+pub use federation::DelegatePeerTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
+pub use fs::{FsListTool, FsPatchTool, FsReadTool, FsWriteTool, WorkspaceJail};
+pub use fs_search::FsSearchTool;
+pub use gcp_billing::GcpBillingExport;
+pub use github::{GitHubForge, GitHubTool};
+pub use gitlab::GitLabForge;
+pub use goals::GoalsTool;
+pub use incident::IncidentTool;
+pub use launchdarkly::LaunchDarklyService;
+pub use mcp::McpTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
+pub use middleware::ToolMiddleware;
+pub use notify::NotifyTool;
+pub use opsgenie::OpsgenieService;
+pub use pagerduty::PagerDutyService;
+pub use password_manager::PasswordManagerTool;
+pub use plugin::{register_tool, registered_tools, SharedTool};
+pub use reddit::RedditTool;
+pub use reply::StructuredReply;
+pub use send_message::SendMessageTool;
+pub use sentry::SentryTool;
+pub use share_file::ShareFileTool;
 pub use shell::ShellTool;
+pub use shortcuts::ShortcutsTool;
+pub use skill::{skill_tools, SkillToolAdapter};
+pub use speak::SpeakTool;
+pub use tasks::{ListTasksTool, UpdateTaskTool};
+pub use terraform_plan::TerraformPlanTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{Cost, Latency, ToolResult, ToolSpec};
+pub use unleash::UnleashService;
 pub use weather_api::WeatherApiTool;
+pub use web_search::WebSearchTool;
 
+use crate::artifacts::ArtifactStore;
 use crate::memory::Memory;
+use crate::runtime::RuntimeAdapter;
 use crate::security::SecurityPolicy;
 use std::sync::Arc;
 
@@ -38,19 +120,30 @@ pub fn default_tools(security: Arc<SecurityPolicy>) -> Vec<Box<dyn Tool>> {
 }
 
 /// Create full tool registry including memory tools and optional Composio
+#[allow(clippy::too_many_lines)]
 pub fn all_tools(
     security: &Arc<SecurityPolicy>,
     memory: Arc<dyn Memory>,
     composio_key: Option<&str>,
     browser_config: &crate::config::BrowserConfig,
+    integrations: &crate::config::IntegrationsConfig,
+    artifacts: Option<&Arc<ArtifactStore>>,
+    runtime: &Arc<dyn RuntimeAdapter>,
 ) -> Vec<Box<dyn Tool>> {
     let mut tools: Vec<Box<dyn Tool>> = vec![
-        Box::new(ShellTool::new(security.clone())),
+        Box::new(ShellTool::with_runtime(security.clone(), runtime.clone())),
         Box::new(FileReadTool::new(security.clone())),
         Box::new(FileWriteTool::new(security.clone())),
+        Box::new(FsReadTool::new(security.clone())),
+        Box::new(FsWriteTool::new(security.clone())),
+        Box::new(FsListTool::new(security.clone())),
+        Box::new(FsPatchTool::new(security.clone())),
         Box::new(MemoryStoreTool::new(memory.clone())),
         Box::new(MemoryRecallTool::new(memory.clone())),
         Box::new(MemoryForgetTool::new(memory)),
+        Box::new(ListTasksTool::new(security.clone())),
+        Box::new(UpdateTaskTool::new(security.clone())),
+        Box::new(GoalsTool::new(security.clone())),
         Box::new(WeatherApiTool::new()),
     ];
 
@@ -60,11 +153,22 @@ pub fn all_tools(
             security.clone(),
             browser_config.allowed_domains.clone(),
         )));
-        // Add full browser automation tool (agent-browser)
-        tools.push(Box::new(BrowserTool::new(
+        // Add full browser automation tool (agent-browser or headless Chromium)
+        tools.push(Box::new(
+            BrowserTool::new(
+                security.clone(),
+                browser_config.allowed_domains.clone(),
+                browser_config.session_name.clone(),
+            )
+            .with_backend(browser_config.backend, browser_config.chromium_path.clone())
+            .with_artifacts(artifacts.cloned()),
+        ));
+    }
+
+    if let Some(store) = artifacts {
+        tools.push(Box::new(ShareFileTool::new(
             security.clone(),
-            browser_config.allowed_domains.clone(),
-            browser_config.session_name.clone(),
+            store.clone(),
         )));
     }
 
@@ -74,15 +178,182 @@ pub fn all_tools(
         }
     }
 
+    if let Some(ref reddit) = integrations.reddit {
+        tools.push(Box::new(RedditTool::new(
+            reddit.clone(),
+            &security.workspace_dir,
+        )));
+    }
+
+    if let Some(ref sentry) = integrations.sentry {
+        tools.push(Box::new(SentryTool::new(sentry.clone())));
+    }
+
+    if let Some(ref github) = integrations.github {
+        tools.push(Box::new(GitHubTool::new(github, security.clone())));
+    }
+
+    // GitHub has its own tool above; the other forges share one.
+    for forge in code_forges(integrations) {
+        if forge.name() != "github" {
+            tools.push(Box::new(CodeForgeTool::new(forge)));
+        }
+    }
+
+    if let Some(ref ci) = integrations.ci {
+        tools.push(Box::new(CiStatusTool::new(
+            ci.clone(),
+            code_forges(integrations),
+            &security.workspace_dir,
+        )));
+    }
+
+    if let Some(ref pagerduty) = integrations.pagerduty {
+        tools.push(Box::new(IncidentTool::new(Box::new(
+            PagerDutyService::new(pagerduty),
+        ))));
+    }
+
+    if let Some(ref opsgenie) = integrations.opsgenie {
+        tools.push(Box::new(IncidentTool::new(Box::new(OpsgenieService::new(
+            opsgenie,
+        )))));
+    }
+
+    if let Some(ref launchdarkly) = integrations.launchdarkly {
+        tools.push(Box::new(FeatureFlagTool::new(
+            Box::new(LaunchDarklyService::new(launchdarkly)),
+            security.clone(),
+        )));
+    }
+
+    if let Some(ref unleash) = integrations.unleash {
+        tools.push(Box::new(FeatureFlagTool::new(
+            Box::new(UnleashService::new(unleash)),
+            security.clone(),
+        )));
+    }
+
+    if let Some(ref password_manager) = integrations.password_manager {
+        // Without an allowlist there is nothing the tool may read.
+        if !password_manager.allowed_items.is_empty() {
+            tools.push(Box::new(PasswordManagerTool::new(
+                password_manager.clone(),
+                security.clone(),
+            )));
+        }
+    }
+
+    if let Some(ref terraform) = integrations.terraform {
+        tools.push(Box::new(TerraformPlanTool::new(
+            terraform.clone(),
+            security.clone(),
+        )));
+    }
+
+    if let Some(ref calendar) = integrations.calendar {
+        if !calendar.calendars.is_empty() {
+            tools.push(Box::new(CalendarTool::new(calendar.clone())));
+        }
+    }
+
+    if let Some(ref web_search) = integrations.web_search {
+        match web_search::engine(web_search) {
+            Ok(engine) => tools.push(Box::new(WebSearchTool::new(engine, web_search.max_results))),
+            Err(e) => tracing::warn!("web_search disabled: {e}"),
+        }
+    }
+
+    if let Some(ref shortcuts) = integrations.shortcuts {
+        // The `shortcuts` CLI only exists on macOS.
+        if cfg!(target_os = "macos") && !shortcuts.allowed.is_empty() {
+            tools.push(Box::new(ShortcutsTool::new(
+                shortcuts.clone(),
+                security.clone(),
+            )));
+        }
+    }
+
+    let mut cost_sources: Vec<Box<dyn cloud_cost::CostSource>> = Vec::new();
+    if let Some(ref aws) = integrations.aws_cost {
+        cost_sources.push(Box::new(AwsCostExplorer::new(aws)));
+    }
+    if let Some(ref gcp) = integrations.gcp_billing {
+        cost_sources.push(Box::new(GcpBillingExport::new(gcp)));
+    }
+    if !cost_sources.is_empty() {
+        tools.push(Box::new(CloudCostTool::new(cost_sources)));
+    }
+
+    plugin::merge_registered(&mut tools);
     tools
 }
 
+/// The tools `config` enables outside an agent session (MCP serving,
+/// workflows), with `[tool_middleware]` applied. `session` labels workspace
+/// history commits.
+pub fn for_config(
+    config: &crate::config::Config,
+    session: &str,
+) -> anyhow::Result<Vec<Box<dyn Tool>>> {
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let runtime: Arc<dyn RuntimeAdapter> =
+        Arc::from(crate::runtime::create_runtime(&config.runtime)?);
+    let mem: Arc<dyn Memory> = Arc::from(crate::memory::create_memory(
+        &config.memory,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+    let composio_key = if config.composio.enabled {
+        config.composio.api_key.as_deref()
+    } else {
+        None
+    };
+    let tools = all_tools(
+        &security,
+        mem,
+        composio_key,
+        &config.browser,
+        &config.integrations,
+        ArtifactStore::for_config(&config.artifacts).as_ref(),
+        &runtime,
+    );
+    Ok(middleware::apply(
+        tools,
+        middleware::from_config(config, session),
+    ))
+}
+
+/// One backend per configured code hosting integration.
+pub fn code_forges(
+    integrations: &crate::config::IntegrationsConfig,
+) -> Vec<Box<dyn code_forge::CodeForge>> {
+    let mut forges: Vec<Box<dyn code_forge::CodeForge>> = Vec::new();
+    if let Some(ref github) = integrations.github {
+        forges.push(Box::new(GitHubForge::new(github)));
+    }
+    if let Some(ref gitlab) = integrations.gitlab {
+        forges.push(Box::new(GitLabForge::new(gitlab)));
+    }
+    if let Some(ref bitbucket) = integrations.bitbucket {
+        forges.push(Box::new(BitbucketForge::new(bitbucket)));
+    }
+    forges
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{BrowserConfig, MemoryConfig};
+    use crate::config::{BrowserConfig, IntegrationsConfig, MemoryConfig};
     use tempfile::TempDir;
 
+    fn native() -> Arc<dyn RuntimeAdapter> {
+        Arc::new(crate::runtime::NativeRuntime::new())
+    }
+
     #[test]
     fn default_tools_has_three() {
         let security = Arc::new(SecurityPolicy::default());
@@ -104,10 +375,18 @@ mod tests {
         let browser = BrowserConfig {
             enabled: false,
             allowed_domains: vec!["example.com".into()],
-            session_name: None,
+            ..BrowserConfig::default()
         };
 
-        let tools = all_tools(&security, mem, None, &browser);
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &IntegrationsConfig::default(),
+            None,
+            &native(),
+        );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
     }
@@ -126,14 +405,96 @@ mod tests {
         let browser = BrowserConfig {
             enabled: true,
             allowed_domains: vec!["example.com".into()],
-            session_name: None,
+            ..BrowserConfig::default()
         };
 
-        let tools = all_tools(&security, mem, None, &browser);
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &IntegrationsConfig::default(),
+            None,
+            &native(),
+        );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"browser_open"));
     }
 
+    #[test]
+    fn all_tools_includes_reddit_only_when_configured() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let browser = BrowserConfig::default();
+
+        let tools = all_tools(
+            &security,
+            mem.clone(),
+            None,
+            &browser,
+            &IntegrationsConfig::default(),
+            None,
+            &native(),
+        );
+        assert!(!tools.iter().any(|t| t.name() == "reddit"));
+
+        let integrations = IntegrationsConfig {
+            reddit: Some(crate::config::RedditConfig::default()),
+            ..IntegrationsConfig::default()
+        };
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &integrations,
+            None,
+            &native(),
+        );
+        assert!(tools.iter().any(|t| t.name() == "reddit"));
+    }
+
+    #[test]
+    fn all_tools_registers_forges_and_ci_status() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+
+        let integrations = IntegrationsConfig {
+            github: Some(crate::config::GitHubConfig {
+                api_url: "https://api.github.com".into(),
+                token: "t".into(),
+                default_repo: None,
+            }),
+            ci: Some(crate::config::CiStatusConfig::default()),
+            ..IntegrationsConfig::default()
+        };
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &BrowserConfig::default(),
+            &integrations,
+            None,
+            &native(),
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names.iter().filter(|n| **n == "github").count(), 1);
+        assert!(names.contains(&"ci_status"));
+        assert!(!names.contains(&"gitlab"));
+    }
+
     #[test]
     fn default_tools_names() {
         let security = Arc::new(SecurityPolicy::default());
@@ -221,10 +582,14 @@ mod tests {
             name: "test".into(),
             description: "A test tool".into(),
             parameters: serde_json::json!({"type": "object"}),
+            latency: Latency::Slow,
+            cost: Cost::Metered,
         };
         let json = serde_json::to_string(&spec).unwrap();
         let parsed: ToolSpec = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.name, "test");
         assert_eq!(parsed.description, "A test tool");
+        assert_eq!(parsed.latency, Latency::Slow);
+        assert_eq!(parsed.cost, Cost::Metered);
     }
 }
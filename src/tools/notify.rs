@@ -0,0 +1,134 @@
+// Notify tool — raise an alert for the user through `[notifications]`, which
+// decides the channel, honours quiet hours and focus, and retries delivery.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::notifications::{self, Alert, Outcome};
+use crate::security::{SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct NotifyTool {
+    config: Config,
+    security: Arc<SecurityPolicy>,
+}
+
+impl NotifyTool {
+    pub fn new(config: Config, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    async fn run(&self, args: &serde_json::Value) -> anyhow::Result<Outcome> {
+        let field = |name: &str| {
+            args.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let message = field("message").ok_or_else(|| anyhow::anyhow!("Missing 'message'"))?;
+        let severity = field("severity").unwrap_or("info").parse()?;
+        if !self.security.record_action() {
+            anyhow::bail!("Action blocked: rate limit exceeded");
+        }
+        let alert = Alert::new("agent", message)
+            .severity(severity)
+            .via(None, field("to"));
+        notifications::send(&self.config, &alert).await
+    }
+}
+
+#[async_trait]
+impl Tool for NotifyTool {
+    fn name(&self) -> &str {
+        "notify"
+    }
+
+    fn description(&self) -> &str {
+        "Alert the user (or a household member) outside this conversation. The channel, quiet \
+         hours and retries are handled for you."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "The alert, self-contained: it is read without this conversation"
+                },
+                "severity": {
+                    "type": "string",
+                    "enum": ["info", "warning", "critical"],
+                    "description": "critical interrupts quiet hours and focus sessions; default info"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Optional @name of a household member; default is the user"
+                }
+            },
+            "required": ["message"]
+        })
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+        match self.run(&args).await {
+            Ok(Outcome::Unrouted) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(Outcome::Unrouted.to_string()),
+            }),
+            Ok(outcome) => Ok(ToolResult {
+                success: true,
+                output: outcome.to_string(),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn notify_needs_a_route_and_a_known_severity() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let tool = NotifyTool::new(config, Arc::new(SecurityPolicy::default()));
+        assert_eq!(tool.class(), ToolClass::Write);
+
+        let unrouted = tool.execute(json!({"message": "Done"})).await.unwrap();
+        assert!(!unrouted.success);
+        assert!(unrouted.error.unwrap().contains("[notifications]"));
+        let bad = tool
+            .execute(json!({"message": "Done", "severity": "urgent"}))
+            .await
+            .unwrap();
+        assert!(bad.error.unwrap().contains("Unknown severity"));
+    }
+}
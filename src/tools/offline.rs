@@ -0,0 +1,106 @@
+// Offline wrapper — network tools stay visible to the model, but calls are
+// answered with a clear "offline" result instead of timing out.
+
+use super::traits::{Cost, Latency, Tool, ToolResult};
+use crate::security::ToolClass;
+use async_trait::async_trait;
+
+pub struct OfflineTool {
+    inner: Box<dyn Tool>,
+}
+
+impl OfflineTool {
+    pub fn new(inner: Box<dyn Tool>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Wrap every tool that needs the network; local tools are left as they are.
+pub fn disable_network(tools: Vec<Box<dyn Tool>>) -> Vec<Box<dyn Tool>> {
+    tools
+        .into_iter()
+        .map(|t| {
+            if t.needs_network() {
+                Box::new(OfflineTool::new(t)) as Box<dyn Tool>
+            } else {
+                t
+            }
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Tool for OfflineTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn latency(&self) -> Latency {
+        self.inner.latency()
+    }
+
+    fn cost(&self) -> Cost {
+        self.inner.cost()
+    }
+
+    fn class(&self) -> ToolClass {
+        self.inner.class()
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        self.inner.call_class(args)
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        Ok(ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!(
+                "`{}` needs a network connection and the agent is offline. Do not retry; \
+                 answer from local files and memory, and tell the user this can be done once \
+                 they are back online.",
+                self.name()
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::SecurityPolicy;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn only_network_tools_are_disabled() {
+        let security = Arc::new(SecurityPolicy::default());
+        let tools = disable_network(vec![
+            Box::new(crate::tools::FileReadTool::new(security)),
+            Box::new(crate::tools::WeatherApiTool::new()),
+        ]);
+        assert_eq!(tools[1].name(), "weather_api");
+
+        let weather = tools[1].execute(json!({"query": "Oslo"})).await.unwrap();
+        assert!(!weather.success);
+        assert!(weather.error.unwrap().contains("offline"));
+
+        let read = tools[0]
+            .execute(json!({"path": "missing.txt"}))
+            .await
+            .unwrap();
+        assert!(read.error.is_none_or(|e| !e.contains("offline")));
+    }
+}
@@ -0,0 +1,191 @@
+use super::incident::{Incident, IncidentService, IncidentSeverity};
+use crate::config::OpsgenieConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Opsgenie Alert API v2 backend.
+pub struct OpsgenieService {
+    api_url: String,
+    api_key: String,
+    client: LazyClient,
+}
+
+impl OpsgenieService {
+    pub fn new(config: &OpsgenieConfig) -> Self {
+        Self {
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    fn auth_header(&self) -> String {
+        format!("GenieKey {}", self.api_key)
+    }
+}
+
+fn priority_param(severity: IncidentSeverity) -> &'static str {
+    match severity {
+        IncidentSeverity::Critical => "P1",
+        IncidentSeverity::Error => "P2",
+        IncidentSeverity::Warning => "P3",
+        IncidentSeverity::Info => "P4",
+    }
+}
+
+#[async_trait]
+impl IncidentService for OpsgenieService {
+    fn name(&self) -> &str {
+        "opsgenie"
+    }
+
+    fn display_name(&self) -> &str {
+        "Opsgenie"
+    }
+
+    async fn list_open(&self, limit: usize) -> anyhow::Result<Vec<Incident>> {
+        let resp = self
+            .client
+            .get(format!("{}/v2/alerts", self.api_url))
+            .header("Authorization", self.auth_header())
+            .query(&[
+                ("query", "status:open"),
+                ("sort", "createdAt"),
+                ("order", "desc"),
+            ])
+            .query(&[("limit", limit)])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Opsgenie", resp).await);
+        }
+        let page: OpsgenieAlerts = resp.json().await?;
+        Ok(page.data.into_iter().map(Into::into).collect())
+    }
+
+    async fn acknowledge(&self, id: &str) -> anyhow::Result<()> {
+        let resp = self
+            .client
+            .post(format!(
+                "{}/v2/alerts/{}/acknowledge",
+                self.api_url,
+                id.trim()
+            ))
+            .header("Authorization", self.auth_header())
+            .query(&[("identifierType", "id")])
+            .json(&json!({ "source": "zeroclaw" }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Opsgenie", resp).await);
+        }
+        Ok(())
+    }
+
+    async fn trigger(
+        &self,
+        summary: &str,
+        severity: IncidentSeverity,
+        details: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut body = json!({
+            "message": crate::util::truncate_with_ellipsis(summary, 130),
+            "priority": priority_param(severity),
+            "source": "zeroclaw",
+        });
+        if let Some(details) = details {
+            body["description"] = json!(details);
+        }
+        let resp = self
+            .client
+            .post(format!("{}/v2/alerts", self.api_url))
+            .header("Authorization", self.auth_header())
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Opsgenie", resp).await);
+        }
+        // Alert creation is asynchronous; the request ID tracks it.
+        let ack: OpsgenieAccepted = resp.json().await?;
+        Ok(ack.request_id)
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct OpsgenieAlerts {
+    #[serde(default)]
+    data: Vec<OpsgenieAlert>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpsgenieAlert {
+    id: String,
+    #[serde(default)]
+    tiny_id: String,
+    message: String,
+    #[serde(default)]
+    acknowledged: bool,
+    #[serde(default)]
+    priority: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    created_at: String,
+}
+
+impl From<OpsgenieAlert> for Incident {
+    fn from(a: OpsgenieAlert) -> Self {
+        Self {
+            url: format!("https://app.opsgenie.com/alert/detail/{}/details", a.id),
+            id: a.id,
+            title: if a.tiny_id.is_empty() {
+                a.message
+            } else {
+                format!("#{} {}", a.tiny_id, a.message)
+            },
+            status: if a.acknowledged {
+                "acknowledged".into()
+            } else {
+                "triggered".into()
+            },
+            urgency: a.priority,
+            service: a.tags.join(", "),
+            created_at: a.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpsgenieAccepted {
+    request_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_maps_to_priority() {
+        assert_eq!(priority_param(IncidentSeverity::Critical), "P1");
+        assert_eq!(priority_param(IncidentSeverity::Warning), "P3");
+    }
+
+    #[test]
+    fn acknowledged_alert_maps_status() {
+        let raw = r#"{"id": "abc-123", "tinyId": "42", "message": "Queue backlog",
+            "acknowledged": true, "priority": "P2", "tags": ["payments"],
+            "createdAt": "2024-05-01T10:00:00Z"}"#;
+        let incident: Incident = serde_json::from_str::<OpsgenieAlert>(raw).unwrap().into();
+        assert_eq!(incident.status, "acknowledged");
+        assert_eq!(incident.title, "#42 Queue backlog");
+        assert_eq!(incident.service, "payments");
+        assert!(incident.url.contains("abc-123"));
+    }
+}
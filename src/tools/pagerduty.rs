@@ -0,0 +1,210 @@
+use super::incident::{Incident, IncidentService, IncidentSeverity};
+use crate::config::PagerDutyConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+const PAGERDUTY_API: &str = "https://api.pagerduty.com";
+const PAGERDUTY_EVENTS: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// `PagerDuty` REST v2 (incidents) + Events v2 (triggering) backend.
+pub struct PagerDutyService {
+    api_token: String,
+    from_email: String,
+    routing_key: Option<String>,
+    client: LazyClient,
+}
+
+impl PagerDutyService {
+    pub fn new(config: &PagerDutyConfig) -> Self {
+        Self {
+            api_token: config.api_token.clone(),
+            from_email: config.from_email.clone(),
+            routing_key: config.routing_key.clone(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    fn auth_header(&self) -> String {
+        format!("Token token={}", self.api_token)
+    }
+}
+
+fn severity_param(severity: IncidentSeverity) -> &'static str {
+    match severity {
+        IncidentSeverity::Critical => "critical",
+        IncidentSeverity::Error => "error",
+        IncidentSeverity::Warning => "warning",
+        IncidentSeverity::Info => "info",
+    }
+}
+
+#[async_trait]
+impl IncidentService for PagerDutyService {
+    fn name(&self) -> &str {
+        "pagerduty"
+    }
+
+    fn display_name(&self) -> &str {
+        "PagerDuty"
+    }
+
+    async fn list_open(&self, limit: usize) -> anyhow::Result<Vec<Incident>> {
+        let resp = self
+            .client
+            .get(format!("{PAGERDUTY_API}/incidents"))
+            .header("Authorization", self.auth_header())
+            .header("Accept", "application/json")
+            .query(&[
+                ("statuses[]", "triggered"),
+                ("statuses[]", "acknowledged"),
+                ("sort_by", "created_at:desc"),
+            ])
+            .query(&[("limit", limit)])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("PagerDuty", resp).await);
+        }
+        let page: PagerDutyIncidents = resp.json().await?;
+        Ok(page.incidents.into_iter().map(Into::into).collect())
+    }
+
+    async fn acknowledge(&self, id: &str) -> anyhow::Result<()> {
+        let resp = self
+            .client
+            .put(format!("{PAGERDUTY_API}/incidents/{}", id.trim()))
+            .header("Authorization", self.auth_header())
+            .header("Accept", "application/json")
+            .header("From", &self.from_email)
+            .json(&json!({
+                "incident": { "type": "incident_reference", "status": "acknowledged" }
+            }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("PagerDuty", resp).await);
+        }
+        Ok(())
+    }
+
+    async fn trigger(
+        &self,
+        summary: &str,
+        severity: IncidentSeverity,
+        details: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let routing_key = self.routing_key.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("Set [integrations.pagerduty] routing_key to trigger pages")
+        })?;
+        let mut payload = json!({
+            "summary": summary,
+            "source": "zeroclaw",
+            "severity": severity_param(severity),
+        });
+        if let Some(details) = details {
+            payload["custom_details"] = json!({ "details": details });
+        }
+        let resp = self
+            .client
+            .post(PAGERDUTY_EVENTS)
+            .json(&json!({
+                "routing_key": routing_key,
+                "event_action": "trigger",
+                "payload": payload,
+            }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("PagerDuty", resp).await);
+        }
+        let ack: PagerDutyEventAck = resp.json().await?;
+        Ok(ack.dedup_key)
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyIncidents {
+    #[serde(default)]
+    incidents: Vec<PagerDutyIncident>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyReference {
+    #[serde(default)]
+    summary: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyIncident {
+    id: String,
+    title: String,
+    status: String,
+    #[serde(default)]
+    urgency: String,
+    #[serde(default)]
+    service: Option<PagerDutyReference>,
+    html_url: String,
+    created_at: String,
+}
+
+impl From<PagerDutyIncident> for Incident {
+    fn from(i: PagerDutyIncident) -> Self {
+        Self {
+            id: i.id,
+            title: i.title,
+            status: i.status,
+            urgency: i.urgency,
+            service: i.service.map(|s| s.summary).unwrap_or_default(),
+            url: i.html_url,
+            created_at: i.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyEventAck {
+    dedup_key: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_maps_to_events_api_values() {
+        assert_eq!(severity_param(IncidentSeverity::Critical), "critical");
+        assert_eq!(severity_param(IncidentSeverity::Info), "info");
+    }
+
+    #[test]
+    fn incident_maps_with_service_summary() {
+        let raw = r#"{"id": "PABC", "title": "API 5xx", "status": "triggered", "urgency": "high",
+            "service": {"id": "S1", "summary": "checkout-api"},
+            "html_url": "https://acme.pagerduty.com/incidents/PABC",
+            "created_at": "2024-05-01T10:00:00Z"}"#;
+        let incident: Incident = serde_json::from_str::<PagerDutyIncident>(raw)
+            .unwrap()
+            .into();
+        assert_eq!(incident.id, "PABC");
+        assert_eq!(incident.service, "checkout-api");
+    }
+
+    #[tokio::test]
+    async fn trigger_without_routing_key_is_error() {
+        let service = PagerDutyService::new(&PagerDutyConfig {
+            api_token: "t".into(),
+            from_email: "oncall@example.com".into(),
+            routing_key: None,
+            webhook_secret: None,
+        });
+        let err = service
+            .trigger("x", IncidentSeverity::Error, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("routing_key"));
+    }
+}
@@ -0,0 +1,362 @@
+// Password manager tool — fetch one allowlisted secret via the `op` / `bw` CLI.
+//
+// Deliberately narrow: there is no list or search action, only items named in
+// `[integrations.password_manager] allowed_items` can be read, the CLI is run
+// directly (no shell) with a scrubbed environment, and unless autonomy is full
+// the user confirms every read out of band. The value never reaches the
+// model: it is held as a `ZEROCLAW_SECRET_*` variable for later shell
+// commands and the model is told only the variable name.
+
+use super::shell::safe_env_vars;
+use super::traits::{Tool, ToolResult};
+use crate::config::{PasswordManagerBackend, PasswordManagerConfig};
+use crate::security::session_secrets::{env_name, session_secrets};
+use crate::security::{ApprovalGate, AutonomyLevel, SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CLI_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_FIELD: &str = "password";
+/// Session/auth variables each CLI needs on top of `safe_env_vars()`.
+const OP_ENV_VARS: &[&str] = &["OP_SERVICE_ACCOUNT_TOKEN", "OP_ACCOUNT", "XDG_CONFIG_HOME"];
+const BW_ENV_VARS: &[&str] = &["BW_SESSION", "BITWARDENCLI_APPDATA_DIR"];
+/// Fields `bw get <field> <item>` can return directly; anything else is a custom field.
+const BW_BUILTIN_FIELDS: &[&str] = &["password", "username", "totp", "notes"];
+
+/// Read individual secrets from 1Password or Bitwarden.
+pub struct PasswordManagerTool {
+    config: PasswordManagerConfig,
+    security: Arc<SecurityPolicy>,
+    approvals: ApprovalGate,
+}
+
+impl PasswordManagerTool {
+    pub fn new(config: PasswordManagerConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config,
+            security,
+            approvals: ApprovalGate::default(),
+        }
+    }
+
+    fn backend_name(&self) -> &'static str {
+        match self.config.backend {
+            PasswordManagerBackend::OnePassword => "1Password",
+            PasswordManagerBackend::Bitwarden => "Bitwarden",
+        }
+    }
+
+    fn is_allowed(&self, item: &str) -> bool {
+        self.config.allowed_items.iter().any(|a| a.trim() == item)
+    }
+
+    /// Program and arguments for one lookup. Never a shell string.
+    fn command(&self, item: &str, field: &str) -> (&'static str, Vec<String>) {
+        match self.config.backend {
+            PasswordManagerBackend::OnePassword if item.starts_with("op://") => (
+                "op",
+                vec!["read".into(), "--no-newline".into(), item.into()],
+            ),
+            PasswordManagerBackend::OnePassword => {
+                let mut args = vec![
+                    "item".into(),
+                    "get".into(),
+                    item.into(),
+                    "--fields".into(),
+                    format!("label={field}"),
+                    "--reveal".into(),
+                ];
+                if let Some(ref vault) = self.config.vault {
+                    args.push("--vault".into());
+                    args.push(vault.clone());
+                }
+                ("op", args)
+            }
+            PasswordManagerBackend::Bitwarden if BW_BUILTIN_FIELDS.contains(&field) => {
+                ("bw", vec!["get".into(), field.into(), item.into()])
+            }
+            PasswordManagerBackend::Bitwarden => {
+                ("bw", vec!["get".into(), "item".into(), item.into()])
+            }
+        }
+    }
+
+    async fn run_cli(&self, item: &str, field: &str) -> anyhow::Result<String> {
+        let (program, args) = self.command(item, field);
+        let extra_env = match self.config.backend {
+            PasswordManagerBackend::OnePassword => OP_ENV_VARS,
+            PasswordManagerBackend::Bitwarden => BW_ENV_VARS,
+        };
+
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.args(&args).env_clear().kill_on_drop(true);
+        for var in safe_env_vars().chain(extra_env.iter().copied()) {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        // 1Password service accounts also use per-account session variables.
+        for (key, val) in std::env::vars() {
+            if key.starts_with("OP_SESSION_") {
+                cmd.env(key, val);
+            }
+        }
+
+        let output = tokio::time::timeout(Duration::from_secs(CLI_TIMEOUT_SECS), cmd.output())
+            .await
+            .map_err(|_| anyhow::anyhow!("`{program}` timed out after {CLI_TIMEOUT_SECS}s"))?
+            .map_err(|e| anyhow::anyhow!("Failed to run `{program}` (is it installed?): {e}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason: String = stderr.trim().chars().take(300).collect();
+            anyhow::bail!("`{program}` failed: {reason}");
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string();
+
+        if program == "bw" && args.get(1).is_some_and(|a| a == "item") {
+            return bitwarden_custom_field(&stdout, field);
+        }
+        Ok(stdout)
+    }
+}
+
+/// Extract a custom field from `bw get item` JSON.
+fn bitwarden_custom_field(item_json: &str, field: &str) -> anyhow::Result<String> {
+    let item: serde_json::Value = serde_json::from_str(item_json)?;
+    item.get("fields")
+        .and_then(|f| f.as_array())
+        .into_iter()
+        .flatten()
+        .find(|f| {
+            f.get("name")
+                .and_then(|n| n.as_str())
+                .is_some_and(|n| n.eq_ignore_ascii_case(field))
+        })
+        .and_then(|f| f.get("value").and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Item has no field named '{field}'"))
+}
+
+fn failed(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+#[async_trait]
+impl Tool for PasswordManagerTool {
+    fn name(&self) -> &str {
+        "password_manager"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch one specific, pre-approved secret (password, token, TOTP or named field) from the \
+         user's password manager. Only allowlisted items can be read and the vault cannot be \
+         listed. The value is not returned: it is put in an environment variable for later \
+         shell commands, e.g. curl -u \"admin:$ZEROCLAW_SECRET_STAGING_DB_PASSWORD\"."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "item": {
+                    "type": "string",
+                    "enum": self.config.allowed_items,
+                    "description": "Allowlisted item name, ID or op:// reference"
+                },
+                "field": {
+                    "type": "string",
+                    "description": "Field to read (default: password; e.g. username, totp, api_key)"
+                }
+            },
+            "required": ["item"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let item = args
+            .get("item")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'item' parameter"))?;
+        let field = args
+            .get("field")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(DEFAULT_FIELD);
+
+        if !self.security.can_act() {
+            return Ok(failed("Action blocked: autonomy is read-only".into()));
+        }
+        if !self.is_allowed(item) {
+            return Ok(failed(format!(
+                "'{item}' is not in [integrations.password_manager] allowed_items"
+            )));
+        }
+        // Arguments are passed without a shell, but a leading '-' would still be a CLI flag.
+        if item.starts_with('-') || field.starts_with('-') {
+            return Ok(failed("Item and field must not start with '-'".into()));
+        }
+
+        if self.security.autonomy == AutonomyLevel::Supervised {
+            let question = format!(
+                "Let the agent use the '{field}' of '{item}' from {}?",
+                self.backend_name()
+            );
+            if !self.approvals.confirm(&question).await {
+                return Ok(failed(format!(
+                    "The user did not approve reading '{item}'; nothing has been read."
+                )));
+            }
+        }
+
+        if !self.security.record_action() {
+            return Ok(failed("Action blocked: rate limit exceeded".into()));
+        }
+
+        match self.run_cli(item, field).await {
+            Ok(secret) if secret.is_empty() => {
+                Ok(failed(format!("'{item}' has no value for field '{field}'")))
+            }
+            Ok(secret) => {
+                // Log the access, never the value.
+                tracing::info!(
+                    "Secret '{field}' of '{item}' read from {}",
+                    self.backend_name()
+                );
+                let var = env_name(item, field);
+                session_secrets().hold(&var, secret);
+                Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "The '{field}' of '{item}' is in ${var} for shell commands this \
+                         session. Use it as \"${var}\"; its value is kept out of the \
+                         conversation."
+                    ),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(failed(format!(
+                "{} lookup failed: {e}",
+                self.backend_name()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(backend: PasswordManagerBackend, autonomy: AutonomyLevel) -> PasswordManagerTool {
+        PasswordManagerTool::new(
+            PasswordManagerConfig {
+                backend,
+                allowed_items: vec!["Staging DB".into(), "op://Ops/Deploy/token".into()],
+                vault: Some("Ops".into()),
+            },
+            Arc::new(SecurityPolicy {
+                autonomy,
+                ..SecurityPolicy::default()
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn items_outside_allowlist_are_refused() {
+        let t = tool(PasswordManagerBackend::Bitwarden, AutonomyLevel::Full);
+        let result = t.execute(json!({"item": "Bank"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_items"));
+    }
+
+    #[tokio::test]
+    async fn supervised_read_needs_the_users_yes() {
+        let mut t = tool(
+            PasswordManagerBackend::OnePassword,
+            AutonomyLevel::Supervised,
+        );
+        let refused = t.execute(json!({"item": "Staging DB"})).await.unwrap();
+        assert!(!refused.success);
+        assert!(!refused.output.contains("approval_token"));
+
+        t.approvals = ApprovalGate::answering(false);
+        let denied = t.execute(json!({"item": "Staging DB"})).await.unwrap();
+        assert!(denied.error.unwrap().contains("did not approve"));
+    }
+
+    #[tokio::test]
+    async fn read_only_blocks_reads() {
+        let t = tool(PasswordManagerBackend::OnePassword, AutonomyLevel::ReadOnly);
+        let result = t.execute(json!({"item": "Staging DB"})).await.unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn flag_like_field_is_rejected() {
+        let t = tool(PasswordManagerBackend::Bitwarden, AutonomyLevel::Full);
+        let result = t
+            .execute(json!({"item": "Staging DB", "field": "--raw"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn onepassword_commands() {
+        let t = tool(PasswordManagerBackend::OnePassword, AutonomyLevel::Full);
+        let (program, args) = t.command("op://Ops/Deploy/token", "password");
+        assert_eq!(program, "op");
+        assert_eq!(args, ["read", "--no-newline", "op://Ops/Deploy/token"]);
+
+        let (_, args) = t.command("Staging DB", "username");
+        assert_eq!(
+            args,
+            [
+                "item",
+                "get",
+                "Staging DB",
+                "--fields",
+                "label=username",
+                "--reveal",
+                "--vault",
+                "Ops"
+            ]
+        );
+    }
+
+    #[test]
+    fn bitwarden_commands() {
+        let t = tool(PasswordManagerBackend::Bitwarden, AutonomyLevel::Full);
+        assert_eq!(
+            t.command("Staging DB", "totp").1,
+            ["get", "totp", "Staging DB"]
+        );
+        assert_eq!(
+            t.command("Staging DB", "api_key").1,
+            ["get", "item", "Staging DB"]
+        );
+    }
+
+    #[test]
+    fn bitwarden_custom_field_lookup() {
+        let raw = r#"{"name": "Staging DB", "fields": [
+            {"name": "API_KEY", "value": "s3cr3t", "type": 1}
+        ]}"#;
+        assert_eq!(bitwarden_custom_field(raw, "api_key").unwrap(), "s3cr3t");
+        assert!(bitwarden_custom_field(raw, "other").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+// Runtime tool registration — skills, MCP servers, WASM plugins or embedding
+// code add tools here and `all_tools` merges them after the built-ins.
+
+use super::traits::{Cost, Latency, Tool, ToolResult};
+use crate::registry::{OnCollision, PluginInfo, PluginKind, PluginSource, Registered, Registry};
+use crate::security::ToolClass;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex, OnceLock};
+
+static TOOLS: OnceLock<Mutex<Registry<Arc<dyn Tool>>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry<Arc<dyn Tool>>> {
+    TOOLS.get_or_init(|| Mutex::new(Registry::new()))
+}
+
+/// Register a tool for every agent built after this call.
+///
+/// `on_collision` decides what happens when the name is already taken by
+/// another plugin (checked now) or by a built-in (checked in `all_tools`).
+pub fn register_tool(
+    tool: Arc<dyn Tool>,
+    source: PluginSource,
+    on_collision: OnCollision,
+) -> anyhow::Result<Registered> {
+    let info = tool_info(tool.as_ref(), source);
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .register(info, tool, on_collision)
+}
+
+pub fn unregister_tool(name: &str) -> bool {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(name)
+        .is_some()
+}
+
+/// Metadata for every runtime-registered tool.
+pub fn registered_tools() -> Vec<PluginInfo> {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .infos()
+        .cloned()
+        .collect()
+}
+
+/// Describe a tool; its capabilities are the parameter names in its schema.
+pub fn tool_info(tool: &dyn Tool, source: PluginSource) -> PluginInfo {
+    let schema = tool.parameters_schema();
+    let params = schema
+        .get("properties")
+        .and_then(serde_json::Value::as_object)
+        .map(|props| props.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    PluginInfo::new(PluginKind::Tool, tool.name(), source)
+        .with_description(tool.description())
+        .with_capabilities(params)
+}
+
+pub(crate) fn merge_registered(tools: &mut Vec<Box<dyn Tool>>) {
+    registry()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .merge_into(
+            tools,
+            |t| t.name(),
+            |t| Box::new(SharedTool(t.clone())) as Box<dyn Tool>,
+        );
+}
+
+/// Adapter so one registered tool can back many agents' `Box<dyn Tool>` lists.
+pub struct SharedTool(pub Arc<dyn Tool>);
+
+#[async_trait]
+impl Tool for SharedTool {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn description(&self) -> &str {
+        self.0.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.0.parameters_schema()
+    }
+
+    fn needs_network(&self) -> bool {
+        self.0.needs_network()
+    }
+
+    fn latency(&self) -> Latency {
+        self.0.latency()
+    }
+
+    fn cost(&self) -> Cost {
+        self.0.cost()
+    }
+
+    fn class(&self) -> ToolClass {
+        self.0.class()
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        self.0.call_class(args)
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.0.execute(args).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Echo(&'static str);
+
+    #[async_trait]
+    impl Tool for Echo {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "Echo the input back"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object", "properties": {"text": {"type": "string"}}})
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args["text"].as_str().unwrap_or_default().to_string(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn registered_tool_is_merged_and_introspectable() {
+        let name = "plugin_test_echo";
+        register_tool(
+            Arc::new(Echo(name)),
+            PluginSource::Mcp("echo-server".into()),
+            OnCollision::Reject,
+        )
+        .unwrap();
+        assert!(register_tool(
+            Arc::new(Echo(name)),
+            PluginSource::Embedded,
+            OnCollision::Reject
+        )
+        .is_err());
+
+        let info = registered_tools()
+            .into_iter()
+            .find(|i| i.name == name)
+            .unwrap();
+        assert_eq!(info.capabilities, vec!["text"]);
+        assert_eq!(info.source, PluginSource::Mcp("echo-server".into()));
+
+        let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+        merge_registered(&mut tools);
+        let tool = tools.iter().find(|t| t.name() == name).unwrap();
+        let result = tool.execute(json!({"text": "hi"})).await.unwrap();
+        assert_eq!(result.output, "hi");
+
+        assert!(unregister_tool(name));
+    }
+
+    #[test]
+    fn rejected_plugin_does_not_shadow_builtin() {
+        let name = "plugin_test_builtin";
+        register_tool(
+            Arc::new(Echo(name)),
+            PluginSource::Embedded,
+            OnCollision::Reject,
+        )
+        .unwrap();
+
+        let mut tools: Vec<Box<dyn Tool>> = vec![Box::new(Echo(name))];
+        merge_registered(&mut tools);
+        assert_eq!(tools.iter().filter(|t| t.name() == name).count(), 1);
+        assert!(unregister_tool(name));
+    }
+}
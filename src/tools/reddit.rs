@@ -0,0 +1,534 @@
+// Reddit tool — subreddit search and keyword monitoring over the public JSON API.
+//
+// `search` is a one-shot query. `monitor` is meant to be driven by cron: it
+// checks the newest posts in the watched subreddits for keyword matches and
+// only reports threads it has not reported before, so a scheduled agent run
+// can post "new mentions of <project>" to a channel without repeating itself.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::RedditConfig;
+use crate::security::ToolClass;
+use crate::util::LazyClient;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const REDDIT_BASE: &str = "https://www.reddit.com";
+/// Upper bound on remembered thread IDs so the state file stays small.
+const MAX_SEEN_IDS: usize = 2_000;
+const STATE_FILE: &str = "reddit_monitor.json";
+const DEFAULT_LIMIT: u64 = 10;
+const MAX_LIMIT: u64 = 50;
+
+/// Search subreddits and watch them for keyword mentions.
+pub struct RedditTool {
+    config: RedditConfig,
+    workspace_dir: PathBuf,
+    client: LazyClient,
+}
+
+impl RedditTool {
+    pub fn new(config: RedditConfig, workspace_dir: &Path) -> Self {
+        Self {
+            config,
+            workspace_dir: workspace_dir.to_path_buf(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    async fn fetch_listing(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<Vec<RedditPost>> {
+        let resp = self
+            .client
+            .get(url)
+            .header(reqwest::header::USER_AGENT, &self.config.user_agent)
+            .query(query)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Reddit API error ({status}): {}",
+                crate::util::truncate_with_ellipsis(&body, 200)
+            );
+        }
+
+        let listing: RedditListing = resp.json().await?;
+        Ok(listing.data.children.into_iter().map(|c| c.data).collect())
+    }
+
+    /// Search Reddit, optionally restricted to a single subreddit.
+    pub async fn search(
+        &self,
+        query: &str,
+        subreddit: Option<&str>,
+        sort: &str,
+        time: &str,
+        limit: u64,
+    ) -> anyhow::Result<Vec<RedditPost>> {
+        let mut params = vec![
+            ("q", query.to_string()),
+            ("sort", sort.to_string()),
+            ("t", time.to_string()),
+            ("limit", limit.to_string()),
+            ("raw_json", "1".to_string()),
+        ];
+        let url = match subreddit {
+            Some(sub) => {
+                params.push(("restrict_sr", "1".to_string()));
+                format!("{REDDIT_BASE}/r/{}/search.json", normalize_subreddit(sub))
+            }
+            None => format!("{REDDIT_BASE}/search.json"),
+        };
+        self.fetch_listing(&url, &params).await
+    }
+
+    /// Newest posts across one or more subreddits (joined as `r/a+b+c`).
+    pub async fn newest(
+        &self,
+        subreddits: &[String],
+        limit: u64,
+    ) -> anyhow::Result<Vec<RedditPost>> {
+        let joined = subreddits
+            .iter()
+            .map(|s| normalize_subreddit(s))
+            .collect::<Vec<_>>()
+            .join("+");
+        let url = format!("{REDDIT_BASE}/r/{joined}/new.json");
+        self.fetch_listing(
+            &url,
+            &[("limit", limit.to_string()), ("raw_json", "1".into())],
+        )
+        .await
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.workspace_dir.join("state").join(STATE_FILE)
+    }
+
+    fn load_state(&self) -> MonitorState {
+        std::fs::read(self.state_path())
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_state(&self, state: &MonitorState) -> anyhow::Result<()> {
+        let path = self.state_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_vec_pretty(state)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    async fn run_monitor(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let subreddits = string_list(args.get("subreddits"))
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| self.config.subreddits.clone());
+        let keywords = string_list(args.get("keywords"))
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| self.config.keywords.clone());
+
+        if subreddits.is_empty() || keywords.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "Monitoring needs subreddits and keywords — pass them or set \
+                     [integrations.reddit] subreddits/keywords in config.toml"
+                        .into(),
+                ),
+            });
+        }
+
+        let posts = match self.newest(&subreddits, MAX_LIMIT).await {
+            Ok(posts) => posts,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Reddit monitor failed: {e}")),
+                })
+            }
+        };
+
+        let mut state = self.load_state();
+        let notable = select_notable(&posts, &keywords, self.config.min_score, &state);
+        for post in &notable {
+            state.remember(&post.id);
+        }
+        self.save_state(&state)?;
+
+        let output = if notable.is_empty() {
+            format!(
+                "No new threads mentioning {} in {}.",
+                keywords.join(", "),
+                subreddits
+                    .iter()
+                    .map(|s| format!("r/{}", normalize_subreddit(s)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        } else {
+            format!(
+                "{} new notable thread(s):\n{}",
+                notable.len(),
+                format_posts(&notable)
+            )
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for RedditTool {
+    fn name(&self) -> &str {
+        "reddit"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        "Search Reddit or monitor subreddits for keyword mentions. \
+         Use action='search' for a one-off query, or action='monitor' (e.g. from cron) \
+         to report only threads not seen in earlier runs."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["search", "monitor"],
+                    "description": "'search' runs a query; 'monitor' reports new keyword matches since the last run"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Search terms (required for 'search')"
+                },
+                "subreddit": {
+                    "type": "string",
+                    "description": "Restrict 'search' to one subreddit (e.g. 'rust')"
+                },
+                "sort": {
+                    "type": "string",
+                    "enum": ["relevance", "hot", "top", "new", "comments"],
+                    "description": "Sort order for 'search' (default: relevance)"
+                },
+                "time": {
+                    "type": "string",
+                    "enum": ["hour", "day", "week", "month", "year", "all"],
+                    "description": "Time window for 'search' (default: week)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": "Maximum results for 'search' (default: 10)"
+                },
+                "subreddits": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Subreddits to watch for 'monitor' (defaults to config)"
+                },
+                "keywords": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Keywords to watch for 'monitor' (defaults to config)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        match action {
+            "search" => {
+                let query = args
+                    .get("query")
+                    .and_then(|v| v.as_str())
+                    .filter(|q| !q.trim().is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'query' for search"))?;
+                let subreddit = args.get("subreddit").and_then(|v| v.as_str());
+                let sort = args
+                    .get("sort")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("relevance");
+                let time = args.get("time").and_then(|v| v.as_str()).unwrap_or("week");
+                let limit = args
+                    .get("limit")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or(DEFAULT_LIMIT)
+                    .clamp(1, MAX_LIMIT);
+
+                match self.search(query, subreddit, sort, time, limit).await {
+                    Ok(posts) if posts.is_empty() => Ok(ToolResult {
+                        success: true,
+                        output: format!("No Reddit threads found for '{query}'."),
+                        error: None,
+                    }),
+                    Ok(posts) => Ok(ToolResult {
+                        success: true,
+                        output: format!(
+                            "Found {} thread(s) for '{query}':\n{}",
+                            posts.len(),
+                            format_posts(&posts.iter().collect::<Vec<_>>())
+                        ),
+                        error: None,
+                    }),
+                    Err(e) => Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Reddit search failed: {e}")),
+                    }),
+                }
+            }
+            "monitor" => self.run_monitor(&args).await,
+            _ => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unknown action '{action}'. Use 'search' or 'monitor'."
+                )),
+            }),
+        }
+    }
+}
+
+/// Strip `r/` or `/r/` prefixes users tend to include.
+fn normalize_subreddit(name: &str) -> &str {
+    let trimmed = name.trim().trim_start_matches('/');
+    trimmed.strip_prefix("r/").unwrap_or(trimmed)
+}
+
+fn string_list(value: Option<&serde_json::Value>) -> Option<Vec<String>> {
+    value.and_then(|v| v.as_array()).map(|items| {
+        items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()
+    })
+}
+
+/// Pick unseen posts that mention a keyword and clear the score bar,
+/// most upvoted + discussed first.
+fn select_notable<'a>(
+    posts: &'a [RedditPost],
+    keywords: &[String],
+    min_score: i64,
+    state: &MonitorState,
+) -> Vec<&'a RedditPost> {
+    let needles: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+    let mut matches: Vec<&RedditPost> = posts
+        .iter()
+        .filter(|p| !state.has_seen(&p.id))
+        .filter(|p| p.score >= min_score)
+        .filter(|p| {
+            let haystack = format!("{} {}", p.title, p.selftext).to_lowercase();
+            needles.iter().any(|n| haystack.contains(n.as_str()))
+        })
+        .collect();
+    matches.sort_by_key(|p| std::cmp::Reverse(p.score + p.num_comments));
+    matches
+}
+
+fn format_posts(posts: &[&RedditPost]) -> String {
+    let mut out = String::new();
+    for post in posts {
+        let _ = writeln!(
+            out,
+            "- [r/{}] {} ({} points, {} comments)\n  https://www.reddit.com{}",
+            post.subreddit, post.title, post.score, post.num_comments, post.permalink
+        );
+    }
+    out.trim_end().to_string()
+}
+
+// ── Monitor state ───────────────────────────────────────────────
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MonitorState {
+    #[serde(default)]
+    seen: VecDeque<String>,
+}
+
+impl MonitorState {
+    fn has_seen(&self, id: &str) -> bool {
+        self.seen.iter().any(|s| s == id)
+    }
+
+    fn remember(&mut self, id: &str) {
+        if self.has_seen(id) {
+            return;
+        }
+        self.seen.push_back(id.to_string());
+        while self.seen.len() > MAX_SEEN_IDS {
+            self.seen.pop_front();
+        }
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct RedditListing {
+    data: RedditListingData,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditListingData {
+    #[serde(default)]
+    children: Vec<RedditChild>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditChild {
+    data: RedditPost,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedditPost {
+    pub id: String,
+    pub title: String,
+    pub subreddit: String,
+    pub permalink: String,
+    #[serde(default)]
+    pub selftext: String,
+    #[serde(default)]
+    pub score: i64,
+    #[serde(default)]
+    pub num_comments: i64,
+    #[serde(default)]
+    pub created_utc: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn post(id: &str, title: &str, score: i64, comments: i64) -> RedditPost {
+        RedditPost {
+            id: id.into(),
+            title: title.into(),
+            subreddit: "rust".into(),
+            permalink: format!("/r/rust/comments/{id}/"),
+            selftext: String::new(),
+            score,
+            num_comments: comments,
+            created_utc: 0.0,
+        }
+    }
+
+    #[test]
+    fn reddit_tool_name_and_schema() {
+        let tmp = TempDir::new().unwrap();
+        let tool = RedditTool::new(RedditConfig::default(), tmp.path());
+        assert_eq!(tool.name(), "reddit");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["action"].is_object());
+        assert!(schema["required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("action")));
+    }
+
+    #[test]
+    fn normalize_subreddit_strips_prefixes() {
+        assert_eq!(normalize_subreddit("rust"), "rust");
+        assert_eq!(normalize_subreddit("r/rust"), "rust");
+        assert_eq!(normalize_subreddit("/r/rust"), "rust");
+    }
+
+    #[test]
+    fn select_notable_filters_keywords_score_and_seen() {
+        let posts = vec![
+            post("a", "ZeroClaw is fast", 10, 3),
+            post("b", "Unrelated thread", 50, 20),
+            post("c", "Tried zeroclaw today", 1, 0),
+            post("d", "zeroclaw vs others", 30, 15),
+        ];
+        let mut state = MonitorState::default();
+        state.remember("d");
+
+        let notable = select_notable(&posts, &["ZeroClaw".into()], 2, &state);
+        let ids: Vec<&str> = notable.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["a"]);
+    }
+
+    #[test]
+    fn select_notable_orders_by_engagement() {
+        let posts = vec![
+            post("a", "zeroclaw one", 5, 0),
+            post("b", "zeroclaw two", 5, 40),
+        ];
+        let notable = select_notable(&posts, &["zeroclaw".into()], 0, &MonitorState::default());
+        assert_eq!(notable[0].id, "b");
+    }
+
+    #[test]
+    fn monitor_state_is_bounded() {
+        let mut state = MonitorState::default();
+        for i in 0..(MAX_SEEN_IDS + 10) {
+            state.remember(&i.to_string());
+        }
+        assert_eq!(state.seen.len(), MAX_SEEN_IDS);
+        assert!(!state.has_seen("0"));
+        assert!(state.has_seen(&(MAX_SEEN_IDS + 9).to_string()));
+    }
+
+    #[test]
+    fn listing_deserializes() {
+        let raw = r#"{"data": {"children": [{"data": {"id": "x1", "title": "Hi", "subreddit": "rust", "permalink": "/r/rust/comments/x1/", "score": 12, "num_comments": 4}}]}}"#;
+        let listing: RedditListing = serde_json::from_str(raw).unwrap();
+        assert_eq!(listing.data.children.len(), 1);
+        assert_eq!(listing.data.children[0].data.score, 12);
+    }
+
+    #[tokio::test]
+    async fn monitor_without_targets_fails_gracefully() {
+        let tmp = TempDir::new().unwrap();
+        let tool = RedditTool::new(RedditConfig::default(), tmp.path());
+        let result = tool.execute(json!({"action": "monitor"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("subreddits and keywords"));
+    }
+
+    #[tokio::test]
+    async fn search_without_query_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let tool = RedditTool::new(RedditConfig::default(), tmp.path());
+        assert!(tool.execute(json!({"action": "search"})).await.is_err());
+    }
+}
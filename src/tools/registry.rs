@@ -0,0 +1,9 @@
+// Runtime registration in one place — skills, WASM plugins and embedding
+// code register tools (`plugin`) and the middleware wrapped around them
+// (`middleware`) through this module.
+
+pub use super::middleware::{
+    register_middleware, registered_middleware, unregister_middleware, ToolMiddleware,
+};
+pub use super::plugin::{register_tool, registered_tools, unregister_tool, SharedTool};
+pub use crate::registry::{OnCollision, PluginInfo, PluginKind, PluginSource, Registered};
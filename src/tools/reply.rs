@@ -0,0 +1,367 @@
+// Structured replies — tool results shaped as a card (title, fields, colour)
+// instead of a wall of text. A tool attaches one to its output as a fenced
+// `embed` block; the model passes the block through to its reply, and the
+// channel that sends the reply decides how to show it: Discord as an embed,
+// everything else as plain text via `flatten`.
+//
+// A `choices` block works the same way for answers the user can pick from:
+// one option per line, shown as buttons where the channel has them and as a
+// numbered list everywhere else.
+//
+// A `speech` block holds text to be said rather than shown: channels with a
+// voice client send it as a voice message, the rest show the words.
+
+use super::traits::ToolResult;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Info string of the fenced block that carries a [`StructuredReply`].
+const FENCE: &str = "```embed";
+/// Info string of the fenced block that carries a list of [`Choice`]s.
+const CHOICES_FENCE: &str = "```choices";
+/// Info string of the fenced block that carries text to speak.
+const SPEECH_FENCE: &str = "```speech";
+/// Separates a choice's label from the reply it sends, when they differ.
+const CHOICE_REPLY: &str = " => ";
+
+pub const COLOR_SUCCESS: u32 = 0x2E_CC_71;
+pub const COLOR_FAILURE: u32 = 0xE7_4C_3C;
+pub const COLOR_PENDING: u32 = 0xF1_C4_0F;
+pub const COLOR_INFO: u32 = 0x34_98_DB;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplyField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructuredReply {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// RGB, e.g. [`COLOR_SUCCESS`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<ReplyField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+}
+
+impl StructuredReply {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    #[must_use]
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    #[must_use]
+    pub fn color(mut self, color: u32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    #[must_use]
+    pub fn field(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        inline: bool,
+    ) -> Self {
+        self.fields.push(ReplyField {
+            name: name.into(),
+            value: value.into(),
+            inline,
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn footer(mut self, footer: impl Into<String>) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    /// The fenced block that carries this reply through the model's answer.
+    pub fn to_block(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{FENCE}\n{json}\n```")
+    }
+
+    /// Plain-text rendering for channels without embeds.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("**{}**", self.title);
+        if let Some(url) = &self.url {
+            let _ = write!(text, " ({url})");
+        }
+        if let Some(description) = &self.description {
+            let _ = write!(text, "\n{description}");
+        }
+        for field in &self.fields {
+            let _ = write!(text, "\n{}: {}", field.name, field.value);
+        }
+        if let Some(footer) = &self.footer {
+            let _ = write!(text, "\n_{footer}_");
+        }
+        text
+    }
+
+    /// A successful tool result: `summary` for the model to read, followed
+    /// by this reply's block for it to pass on.
+    pub fn into_result(self, summary: &str) -> ToolResult {
+        ToolResult {
+            success: true,
+            output: format!("{summary}\n\n{}", self.to_block()),
+            error: None,
+        }
+    }
+}
+
+/// An answer the user can pick with one tap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Choice {
+    /// What the button shows
+    pub label: String,
+    /// What picking it sends back, as if the user had typed it
+    pub reply: String,
+}
+
+impl Choice {
+    pub fn new(label: impl Into<String>, reply: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            reply: reply.into(),
+        }
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().trim_start_matches(['-', '*']).trim();
+        if line.is_empty() {
+            return None;
+        }
+        Some(match line.split_once(CHOICE_REPLY) {
+            Some((label, reply)) if !label.trim().is_empty() && !reply.trim().is_empty() => {
+                Self::new(label.trim(), reply.trim())
+            }
+            _ => Self::new(line, line),
+        })
+    }
+}
+
+/// The fenced block that offers `choices` to the user.
+pub fn choices_block(choices: &[Choice]) -> String {
+    let mut block = CHOICES_FENCE.to_string();
+    for choice in choices {
+        block.push('\n');
+        block.push_str(&choice.label);
+        if choice.reply != choice.label {
+            block.push_str(CHOICE_REPLY);
+            block.push_str(&choice.reply);
+        }
+    }
+    block.push_str("\n```");
+    block
+}
+
+/// Split `text` into the text around its `choices` blocks and the options
+/// they offer, in order.
+pub fn extract_choices(text: &str) -> (String, Vec<Choice>) {
+    let mut rest = text;
+    let mut kept = String::new();
+    let mut choices = Vec::new();
+    while let Some(start) = rest.find(CHOICES_FENCE) {
+        let body = &rest[start + CHOICES_FENCE.len()..];
+        let Some(end) = body.find("```") else {
+            break;
+        };
+        kept.push_str(&rest[..start]);
+        choices.extend(body[..end].lines().filter_map(Choice::parse));
+        rest = &body[end + 3..];
+    }
+    kept.push_str(rest);
+    let kept = kept.trim().to_string();
+    (kept, choices)
+}
+
+/// The fenced block that asks for `text` to be sent as a voice message.
+pub fn speech_block(text: &str) -> String {
+    format!("{SPEECH_FENCE}\n{}\n```", text.trim())
+}
+
+/// Split `text` into the text around its `speech` blocks and what they ask
+/// to have said, joined into one passage.
+pub fn extract_speech(text: &str) -> (String, Option<String>) {
+    let mut rest = text;
+    let mut kept = String::new();
+    let mut spoken: Vec<&str> = Vec::new();
+    while let Some(start) = rest.find(SPEECH_FENCE) {
+        let body = &rest[start + SPEECH_FENCE.len()..];
+        let Some(end) = body.find("```") else {
+            break;
+        };
+        kept.push_str(&rest[..start]);
+        spoken.push(body[..end].trim());
+        rest = &body[end + 3..];
+    }
+    kept.push_str(rest);
+    let spoken = spoken.join("\n\n");
+    (
+        kept.trim().to_string(),
+        (!spoken.is_empty()).then_some(spoken),
+    )
+}
+
+/// Split `text` into the text around its `embed` blocks and the replies
+/// they carry. Blocks that do not parse are left in the text.
+pub fn extract(text: &str) -> (String, Vec<StructuredReply>) {
+    let mut rest = text;
+    let mut kept = String::new();
+    let mut replies = Vec::new();
+    while let Some(start) = rest.find(FENCE) {
+        let body = &rest[start + FENCE.len()..];
+        let Some(end) = body.find("```") else {
+            break;
+        };
+        match serde_json::from_str::<StructuredReply>(body[..end].trim()) {
+            Ok(reply) => {
+                kept.push_str(&rest[..start]);
+                replies.push(reply);
+            }
+            Err(_) => kept.push_str(&rest[..start + FENCE.len() + end + 3]),
+        }
+        rest = &body[end + 3..];
+    }
+    kept.push_str(rest);
+    let kept = kept.trim().to_string();
+    (kept, replies)
+}
+
+/// `text` with every `embed`, `choices` and `speech` block replaced by its
+/// plain-text rendering.
+pub fn flatten(text: &str) -> String {
+    flatten_speech(&flatten_choices(&flatten_embeds(text)))
+}
+
+/// `text` with every `speech` block replaced by the words it would say.
+pub fn flatten_speech(text: &str) -> String {
+    if !text.contains(SPEECH_FENCE) {
+        return text.to_string();
+    }
+    match extract_speech(text) {
+        (kept, Some(spoken)) if kept.is_empty() => spoken,
+        (kept, Some(spoken)) => format!("{kept}\n\n{spoken}"),
+        (kept, None) => kept,
+    }
+}
+
+/// `text` with every `choices` block replaced by a numbered list.
+pub fn flatten_choices(text: &str) -> String {
+    if !text.contains(CHOICES_FENCE) {
+        return text.to_string();
+    }
+    let (mut kept, choices) = extract_choices(text);
+    if !kept.is_empty() && !choices.is_empty() {
+        kept.push('\n');
+    }
+    for (i, choice) in choices.iter().enumerate() {
+        let _ = write!(kept, "\n{}. {}", i + 1, choice.label);
+    }
+    kept
+}
+
+/// `text` with every `embed` block replaced by its plain-text rendering.
+pub fn flatten_embeds(text: &str) -> String {
+    if !text.contains(FENCE) {
+        return text.to_string();
+    }
+    let (mut kept, replies) = extract(text);
+    for reply in replies {
+        if !kept.is_empty() {
+            kept.push_str("\n\n");
+        }
+        kept.push_str(&reply.to_text());
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_round_trip_through_text_and_flatten_elsewhere() {
+        let card = StructuredReply::new("Berlin")
+            .description("Partly cloudy")
+            .color(COLOR_INFO)
+            .field("Temp", "18.0 C", true)
+            .footer("WeatherAPI.com");
+        let reply = format!("Here you go:\n{}\nStay dry!", card.to_block());
+
+        let (text, cards) = extract(&reply);
+        assert_eq!(text, "Here you go:\n\nStay dry!");
+        assert_eq!(cards, vec![card]);
+        assert_eq!(
+            flatten(&reply),
+            "Here you go:\n\nStay dry!\n\n**Berlin**\nPartly cloudy\nTemp: 18.0 C\n_WeatherAPI.com_"
+        );
+
+        let broken = "```embed\nnot json\n```";
+        assert_eq!(extract(broken), (broken.to_string(), vec![]));
+        assert_eq!(flatten("plain"), "plain");
+    }
+
+    #[test]
+    fn choices_round_trip_and_number_elsewhere() {
+        let offered = [
+            Choice::new("Tomorrow 9:00", "Tomorrow 9:00"),
+            Choice::new("✅ Approve", "yes"),
+        ];
+        let reply = format!("When should I book it?\n{}", choices_block(&offered));
+        assert!(reply.ends_with("Tomorrow 9:00\n✅ Approve => yes\n```"));
+
+        let (text, choices) = extract_choices(&reply);
+        assert_eq!(text, "When should I book it?");
+        assert_eq!(choices, offered);
+        assert_eq!(
+            flatten(&reply),
+            "When should I book it?\n\n1. Tomorrow 9:00\n2. ✅ Approve"
+        );
+        assert_eq!(
+            extract_choices("```choices\n- Red\n\n- Blue\n```").1,
+            vec![Choice::new("Red", "Red"), Choice::new("Blue", "Blue")]
+        );
+    }
+
+    #[test]
+    fn speech_is_split_off_or_shown_as_text() {
+        let reply = format!("Here's the summary.\n{}", speech_block(" Good morning! "));
+        assert_eq!(
+            extract_speech(&reply),
+            (
+                "Here's the summary.".to_string(),
+                Some("Good morning!".to_string())
+            )
+        );
+        assert_eq!(flatten(&reply), "Here's the summary.\n\nGood morning!");
+        assert_eq!(flatten_speech(&speech_block("Hi")), "Hi");
+        assert_eq!(extract_speech("plain"), ("plain".to_string(), None));
+    }
+}
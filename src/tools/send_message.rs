@@ -0,0 +1,182 @@
+// Send message tool — deliver a message on a configured channel through the
+// outbox, so the send survives a crash and a repeated call doesn't send twice.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::outbox::{self, Enqueued, Status};
+use crate::security::{SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct SendMessageTool {
+    config: Config,
+    security: Arc<SecurityPolicy>,
+    channels: Vec<String>,
+}
+
+impl SendMessageTool {
+    /// `channels` are the names offered to the model.
+    pub fn new(config: Config, security: Arc<SecurityPolicy>, channels: Vec<String>) -> Self {
+        Self {
+            config,
+            security,
+            channels,
+        }
+    }
+
+    async fn run(&self, args: &serde_json::Value) -> anyhow::Result<String> {
+        let field = |name: &str| {
+            args.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let channel = field("channel").ok_or_else(|| anyhow::anyhow!("Missing 'channel'"))?;
+        let recipient = field("recipient").ok_or_else(|| anyhow::anyhow!("Missing 'recipient'"))?;
+        let message = field("message").ok_or_else(|| anyhow::anyhow!("Missing 'message'"))?;
+        let (channel, recipient) = crate::household::route(&self.config, channel, recipient);
+
+        if !self.security.record_action() {
+            anyhow::bail!("Action blocked: rate limit exceeded");
+        }
+
+        let intent = match outbox::enqueue(
+            &self.config,
+            "send_message",
+            &channel,
+            &recipient,
+            message,
+            field("idempotency_key"),
+        )? {
+            Enqueued::Duplicate(intent) => {
+                let state = if intent.status == Status::Sent {
+                    "was already sent"
+                } else {
+                    "is already queued"
+                };
+                return Ok(format!(
+                    "Not sent again: this message to {recipient} on {channel} {state}."
+                ));
+            }
+            Enqueued::New(intent) => intent,
+        };
+        let delivered = outbox::deliver(&self.config, &intent.id, |name| {
+            crate::channels::find_channel(&self.config, name)
+        })
+        .await?;
+        Ok(match delivered {
+            Some((Status::Sent, _)) => format!("Sent to {recipient} on {channel}."),
+            Some((Status::Dead, error)) => anyhow::bail!(
+                "Could not send to {recipient} on {channel}: {}",
+                error.unwrap_or_default()
+            ),
+            _ => format!(
+                "Queued: sending to {recipient} on {channel} failed and will be retried \
+                 automatically. Don't send it again."
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for SendMessageTool {
+    fn name(&self) -> &str {
+        "send_message"
+    }
+
+    fn description(&self) -> &str {
+        "Send a message to someone on a messaging channel. Delivery is retried if the channel \
+         is down, and the same message to the same recipient is only sent once."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "channel": {
+                    "type": "string",
+                    "enum": self.channels,
+                    "description": "Channel to send on"
+                },
+                "recipient": {
+                    "type": "string",
+                    "description": "Chat, user or address on that channel, or @name for a household member"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Text to send"
+                },
+                "idempotency_key": {
+                    "type": "string",
+                    "description": "Optional key; another send with the same key is skipped. Use it to send identical text twice on purpose with different keys."
+                }
+            },
+            "required": ["channel", "recipient", "message"]
+        })
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+        match self.run(&args).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn unconfigured_channel_is_queued_once() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let tool = SendMessageTool::new(
+            config.clone(),
+            Arc::new(SecurityPolicy::default()),
+            vec!["slack".into()],
+        );
+        let args = json!({"channel": "slack", "recipient": "U1", "message": "Hello"});
+        // Sending acts for the user, so interactive autonomy asks first.
+        assert_eq!(tool.class(), ToolClass::Write);
+
+        let first = tool.execute(args.clone()).await.unwrap();
+        assert!(first.success);
+        assert!(first.output.starts_with("Queued"), "{}", first.output);
+        let second = tool.execute(args).await.unwrap();
+        assert!(
+            second.output.contains("already queued"),
+            "{}",
+            second.output
+        );
+        assert_eq!(outbox::list(&config, false).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,557 @@
+// Sentry tool — error-tracker triage over the Sentry Web API.
+//
+// `new_issues` lists unresolved issues first seen within a time window, which
+// is what a cron-driven "overnight error triage" report needs. `issue` digs
+// into one issue and its latest event's stack trace; `events` lists recent
+// occurrences so the agent can tell a one-off from a regression.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::SentryConfig;
+use crate::security::ToolClass;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt::Write;
+
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 100;
+const DEFAULT_SINCE_HOURS: u64 = 24;
+/// Frames shown per exception; the innermost ones are where the error was raised.
+const MAX_FRAMES: usize = 15;
+
+/// Triage Sentry issues: new issues, issue details with stack traces, recent events.
+pub struct SentryTool {
+    config: SentryConfig,
+    client: LazyClient,
+}
+
+impl SentryTool {
+    pub fn new(config: SentryConfig) -> Self {
+        Self {
+            config,
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "{}/api/0/{}",
+            self.config.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<T> {
+        let resp = self
+            .client
+            .get(self.api_url(path))
+            .bearer_auth(&self.config.auth_token)
+            .query(query)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Sentry", resp).await);
+        }
+        Ok(resp.json().await?)
+    }
+
+    fn issues_path(&self, project: Option<&str>) -> String {
+        let org = &self.config.organization;
+        match project.or(self.config.project.as_deref()) {
+            Some(project) => format!("projects/{org}/{project}/issues/"),
+            None => format!("organizations/{org}/issues/"),
+        }
+    }
+
+    async fn new_issues(
+        &self,
+        project: Option<&str>,
+        since_hours: u64,
+        limit: u64,
+    ) -> anyhow::Result<String> {
+        let issues: Vec<SentryIssue> = self
+            .get_json(
+                &self.issues_path(project),
+                &[
+                    ("query", format!("is:unresolved firstSeen:-{since_hours}h")),
+                    ("sort", "new".into()),
+                    ("limit", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(format_issue_list(&issues, since_hours))
+    }
+
+    async fn issue_detail(&self, issue_id: &str) -> anyhow::Result<String> {
+        let org = &self.config.organization;
+        let issue: SentryIssue = self
+            .get_json(&format!("organizations/{org}/issues/{issue_id}/"), &[])
+            .await?;
+        let event: SentryEvent = self
+            .get_json(
+                &format!("organizations/{org}/issues/{issue_id}/events/latest/"),
+                &[],
+            )
+            .await?;
+        Ok(format!(
+            "{}\n\n{}",
+            format_issue_header(&issue),
+            format_event(&event)
+        ))
+    }
+
+    async fn recent_events(&self, issue_id: &str, limit: u64) -> anyhow::Result<String> {
+        let org = &self.config.organization;
+        let events: Vec<SentryEventSummary> = self
+            .get_json(
+                &format!("organizations/{org}/issues/{issue_id}/events/"),
+                &[("limit", limit.to_string())],
+            )
+            .await?;
+        if events.is_empty() {
+            return Ok(format!("No events recorded for issue {issue_id}."));
+        }
+        let mut out = format!("{} recent event(s) for issue {issue_id}:\n", events.len());
+        for e in &events {
+            let release = e
+                .tag("release")
+                .map(|r| format!(" release={r}"))
+                .unwrap_or_default();
+            let env = e
+                .tag("environment")
+                .map(|r| format!(" env={r}"))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "- {} {}{release}{env}\n  event: {}",
+                e.date_created, e.title, e.event_id
+            );
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+fn format_issue_list(issues: &[SentryIssue], since_hours: u64) -> String {
+    if issues.is_empty() {
+        return format!("No new unresolved issues in the last {since_hours}h.");
+    }
+    let mut out = format!(
+        "{} new unresolved issue(s) in the last {since_hours}h:\n",
+        issues.len()
+    );
+    for issue in issues {
+        let _ = writeln!(
+            out,
+            "- [{}] {} {} — {} events, {} users\n  id: {} | {}",
+            issue.level,
+            issue.short_id,
+            issue.title,
+            issue.count,
+            issue.user_count,
+            issue.id,
+            issue.permalink
+        );
+    }
+    out.trim_end().to_string()
+}
+
+fn format_issue_header(issue: &SentryIssue) -> String {
+    let project = issue.project.as_ref().map_or("", |p| p.slug.as_str());
+    format!(
+        "{} {} [{}]\nProject: {project} | Status: {} | Culprit: {}\n\
+         Events: {} | Users: {} | First seen: {} | Last seen: {}\n{}",
+        issue.short_id,
+        issue.title,
+        issue.level,
+        issue.status,
+        issue.culprit,
+        issue.count,
+        issue.user_count,
+        issue.first_seen,
+        issue.last_seen,
+        issue.permalink
+    )
+}
+
+/// Latest event: message, notable tags, and each exception's stack trace
+/// (innermost frame first, in-app frames preferred).
+fn format_event(event: &SentryEvent) -> String {
+    let mut out = format!("Latest event {} at {}", event.event_id, event.date_created);
+    for key in ["release", "environment", "server_name"] {
+        if let Some(value) = event.tags.iter().find(|t| t.key == key) {
+            let _ = write!(out, "\n{key}: {}", value.value);
+        }
+    }
+    if !event.message.trim().is_empty() {
+        let _ = write!(
+            out,
+            "\nMessage: {}",
+            crate::util::truncate_with_ellipsis(event.message.trim(), 500)
+        );
+    }
+
+    let exceptions = event
+        .entries
+        .iter()
+        .filter(|e| e.entry_type == "exception")
+        .filter_map(|e| serde_json::from_value::<SentryExceptionData>(e.data.clone()).ok())
+        .flat_map(|data| data.values);
+    for exc in exceptions {
+        let _ = write!(out, "\n\n{}: {}", exc.exc_type, exc.value);
+        let frames = exc.stacktrace.map(|s| s.frames).unwrap_or_default();
+        let has_in_app = frames.iter().any(|f| f.in_app);
+        let mut shown = 0;
+        for frame in frames.iter().rev() {
+            if has_in_app && !frame.in_app {
+                continue;
+            }
+            if shown == MAX_FRAMES {
+                out.push_str("\n  …");
+                break;
+            }
+            let _ = write!(
+                out,
+                "\n  at {} ({}:{})",
+                frame.function.as_deref().unwrap_or("?"),
+                frame
+                    .filename
+                    .as_deref()
+                    .or(frame.module.as_deref())
+                    .unwrap_or("?"),
+                frame.line_no.map_or_else(|| "?".into(), |n| n.to_string())
+            );
+            shown += 1;
+        }
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for SentryTool {
+    fn name(&self) -> &str {
+        "sentry"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        "Triage Sentry errors. 'new_issues' lists unresolved issues first seen in the last N hours \
+         (for overnight triage reports), 'issue' shows an issue with its latest stack trace, \
+         'events' lists recent occurrences of an issue."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["new_issues", "issue", "events"],
+                    "description": "Operation to perform"
+                },
+                "issue_id": {
+                    "type": "string",
+                    "description": "Numeric issue ID for 'issue' and 'events'"
+                },
+                "project": {
+                    "type": "string",
+                    "description": "Project slug for 'new_issues' (defaults to config, or all projects)"
+                },
+                "since_hours": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Window for 'new_issues' (default: 24)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": "Maximum results (default: 20)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let issue_id = || {
+            // Accept numbers too; the LLM often passes IDs unquoted.
+            args.get("issue_id")
+                .and_then(|v| {
+                    v.as_str()
+                        .map(|s| s.trim().to_string())
+                        .or_else(|| v.as_u64().map(|n| n.to_string()))
+                })
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Missing 'issue_id' parameter"))
+        };
+
+        let result = match action {
+            "new_issues" => {
+                let since_hours = args
+                    .get("since_hours")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or(DEFAULT_SINCE_HOURS)
+                    .max(1);
+                let project = args.get("project").and_then(|v| v.as_str());
+                self.new_issues(project, since_hours, limit).await
+            }
+            "issue" => self.issue_detail(&issue_id()?).await,
+            "events" => self.recent_events(&issue_id()?, limit).await,
+            _ => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Unknown action '{action}'. Use 'new_issues', 'issue', or 'events'."
+                    )),
+                })
+            }
+        };
+
+        match result {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Sentry request failed: {e}")),
+            }),
+        }
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct SentryProjectRef {
+    slug: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryIssue {
+    id: String,
+    #[serde(default)]
+    short_id: String,
+    title: String,
+    #[serde(default)]
+    culprit: String,
+    #[serde(default)]
+    level: String,
+    #[serde(default)]
+    status: String,
+    /// Sentry returns the event count as a string
+    #[serde(default)]
+    count: String,
+    #[serde(default)]
+    user_count: u64,
+    #[serde(default)]
+    first_seen: String,
+    #[serde(default)]
+    last_seen: String,
+    #[serde(default)]
+    permalink: String,
+    #[serde(default)]
+    project: Option<SentryProjectRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryTag {
+    key: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryEventSummary {
+    #[serde(rename = "eventID")]
+    event_id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    date_created: String,
+    #[serde(default)]
+    tags: Vec<SentryTag>,
+}
+
+impl SentryEventSummary {
+    fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|t| t.key == key)
+            .map(|t| t.value.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryEvent {
+    #[serde(rename = "eventID")]
+    event_id: String,
+    #[serde(default)]
+    date_created: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    tags: Vec<SentryTag>,
+    #[serde(default)]
+    entries: Vec<SentryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryEntry {
+    #[serde(rename = "type")]
+    entry_type: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryExceptionData {
+    #[serde(default)]
+    values: Vec<SentryException>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryException {
+    #[serde(rename = "type", default)]
+    exc_type: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    stacktrace: Option<SentryStacktrace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryStacktrace {
+    #[serde(default)]
+    frames: Vec<SentryFrame>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryFrame {
+    #[serde(default)]
+    filename: Option<String>,
+    #[serde(default)]
+    module: Option<String>,
+    #[serde(default)]
+    function: Option<String>,
+    #[serde(default)]
+    line_no: Option<u64>,
+    #[serde(default)]
+    in_app: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(project: Option<&str>) -> SentryTool {
+        SentryTool::new(SentryConfig {
+            base_url: "https://sentry.example.com/".into(),
+            auth_token: "t".into(),
+            organization: "acme".into(),
+            project: project.map(Into::into),
+        })
+    }
+
+    #[test]
+    fn issues_path_prefers_project_scope() {
+        assert_eq!(tool(None).issues_path(None), "organizations/acme/issues/");
+        assert_eq!(
+            tool(Some("web")).issues_path(None),
+            "projects/acme/web/issues/"
+        );
+        assert_eq!(
+            tool(Some("web")).issues_path(Some("api")),
+            "projects/acme/api/issues/"
+        );
+        assert_eq!(
+            tool(None).api_url("/organizations/acme/issues/"),
+            "https://sentry.example.com/api/0/organizations/acme/issues/"
+        );
+    }
+
+    #[test]
+    fn issue_list_formats_counts() {
+        let raw = r#"[{"id": "101", "shortId": "WEB-1A", "title": "TypeError: x is undefined",
+            "level": "error", "count": "37", "userCount": 12,
+            "permalink": "https://sentry.io/organizations/acme/issues/101/"}]"#;
+        let issues: Vec<SentryIssue> = serde_json::from_str(raw).unwrap();
+        let out = format_issue_list(&issues, 12);
+        assert!(out.starts_with("1 new unresolved issue(s) in the last 12h"));
+        assert!(out.contains("[error] WEB-1A TypeError: x is undefined — 37 events, 12 users"));
+        assert_eq!(
+            format_issue_list(&[], 24),
+            "No new unresolved issues in the last 24h."
+        );
+    }
+
+    #[test]
+    fn event_stack_trace_is_innermost_first_and_in_app_only() {
+        let raw = r#"{
+            "eventID": "e1", "dateCreated": "2024-05-01T03:12:00Z", "message": "",
+            "tags": [{"key": "release", "value": "1.4.2"}, {"key": "browser", "value": "Firefox"}],
+            "entries": [
+                {"type": "breadcrumbs", "data": {"values": []}},
+                {"type": "exception", "data": {"values": [{
+                    "type": "ValueError", "value": "bad input",
+                    "stacktrace": {"frames": [
+                        {"filename": "lib/framework.py", "function": "dispatch", "lineNo": 10, "inApp": false},
+                        {"filename": "app/views.py", "function": "handler", "lineNo": 42, "inApp": true},
+                        {"filename": "app/parse.py", "function": "parse", "lineNo": 7, "inApp": true}
+                    ]}
+                }]}}
+            ]
+        }"#;
+        let event: SentryEvent = serde_json::from_str(raw).unwrap();
+        let out = format_event(&event);
+        assert!(out.contains("release: 1.4.2"));
+        assert!(!out.contains("browser"));
+        assert!(out.contains("ValueError: bad input"));
+        let parse = out.find("at parse (app/parse.py:7)").unwrap();
+        let handler = out.find("at handler (app/views.py:42)").unwrap();
+        assert!(parse < handler);
+        assert!(!out.contains("framework.py"));
+    }
+
+    #[tokio::test]
+    async fn issue_requires_issue_id() {
+        let result = tool(None).execute(json!({"action": "issue"})).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn unknown_action_returns_error() {
+        let result = tool(None)
+            .execute(json!({"action": "resolve"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,131 @@
+// Share file tool — upload a workspace file (an image, a recording, a PDF,
+// a large log) to `[artifacts]` storage and get a link to put in the reply,
+// instead of pasting its contents into the conversation.
+
+use super::traits::{Tool, ToolResult};
+use crate::artifacts::ArtifactStore;
+use crate::security::{SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Largest file the tool uploads.
+const MAX_SHARE_BYTES: u64 = 100 * 1024 * 1024;
+
+pub struct ShareFileTool {
+    security: Arc<SecurityPolicy>,
+    store: Arc<ArtifactStore>,
+}
+
+impl ShareFileTool {
+    pub fn new(security: Arc<SecurityPolicy>, store: Arc<ArtifactStore>) -> Self {
+        Self { security, store }
+    }
+
+    fn failure(error: String) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ShareFileTool {
+    fn name(&self) -> &str {
+        "share_file"
+    }
+
+    fn description(&self) -> &str {
+        "Upload a workspace file (image, audio, PDF, log, archive) and get a link to it \
+         for your reply"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to the file within the workspace"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        if !self.security.is_path_allowed(path) {
+            return Ok(Self::failure(format!(
+                "Path not allowed by security policy: {path}"
+            )));
+        }
+        let resolved = match tokio::fs::canonicalize(self.security.workspace_dir.join(path)).await {
+            Ok(p) => p,
+            Err(e) => return Ok(Self::failure(format!("Failed to resolve file path: {e}"))),
+        };
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(Self::failure(format!(
+                "Resolved path escapes workspace: {}",
+                resolved.display()
+            )));
+        }
+        let size = tokio::fs::metadata(&resolved).await?.len();
+        if size > MAX_SHARE_BYTES {
+            return Ok(Self::failure(format!(
+                "File too large: {size} bytes (limit: {MAX_SHARE_BYTES} bytes)"
+            )));
+        }
+        let data = tokio::fs::read(&resolved).await?;
+        match self.store.upload(path, data).await {
+            Ok(url) => Ok(ToolResult {
+                success: true,
+                output: format!("Shared {path} ({size} bytes): {url}"),
+                error: None,
+            }),
+            Err(e) => Ok(Self::failure(format!("Upload failed: {e:#}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ArtifactsConfig;
+
+    #[tokio::test]
+    async fn share_file_stays_in_the_workspace() {
+        let store = ArtifactStore::new(&ArtifactsConfig {
+            enabled: true,
+            bucket: Some("agent".into()),
+            access_key_id: Some("AKID".into()),
+            secret_access_key: Some("secret".into()),
+            ..ArtifactsConfig::default()
+        })
+        .unwrap();
+        let tool = ShareFileTool::new(Arc::new(SecurityPolicy::default()), Arc::new(store));
+        assert!(tool.needs_network());
+        assert_eq!(tool.class(), ToolClass::Write);
+
+        let escape = tool
+            .execute(json!({ "path": "../../etc/passwd" }))
+            .await
+            .unwrap();
+        assert!(!escape.success);
+        assert!(escape.error.unwrap().contains("not allowed"));
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
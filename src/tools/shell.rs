@@ -1,28 +1,59 @@
-use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use super::traits::{Latency, Tool, ToolResult};
+use crate::runtime::{NativeRuntime, RuntimeAdapter, ShellCommand};
+use crate::security::session_secrets::session_secrets;
+use crate::security::{ApprovalGate, SecurityPolicy, ToolClass};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
 use std::time::Duration;
 
-/// Maximum shell command execution time before kill.
-const SHELL_TIMEOUT_SECS: u64 = 60;
 /// Maximum output size in bytes (1MB).
 const MAX_OUTPUT_BYTES: usize = 1_048_576;
 /// Environment variables safe to pass to shell commands.
 /// Only functional variables are included — never API keys or secrets.
-const SAFE_ENV_VARS: &[&str] = &[
+pub(crate) const SAFE_ENV_VARS: &[&str] = &[
     "PATH", "HOME", "TERM", "LANG", "LC_ALL", "LC_CTYPE", "USER", "SHELL", "TMPDIR",
 ];
+/// Also kept under Termux: `LD_PRELOAD` loads termux-exec, without which
+/// `#!/usr/bin/env` scripts don't start, and `termux-*` tools need the rest.
+const TERMUX_ENV_VARS: &[&str] = &[
+    "PREFIX",
+    "LD_PRELOAD",
+    "ANDROID_DATA",
+    "ANDROID_ROOT",
+    "TERMUX_VERSION",
+];
+
+/// `SAFE_ENV_VARS`, plus what Termux or Windows need when running there.
+pub(crate) fn safe_env_vars() -> impl Iterator<Item = &'static str> {
+    let termux: &[&str] = if crate::util::is_termux() {
+        TERMUX_ENV_VARS
+    } else {
+        &[]
+    };
+    let platform = crate::platform::extra_env_vars(crate::platform::Os::current());
+    SAFE_ENV_VARS.iter().chain(termux).chain(platform).copied()
+}
 
 /// Shell command execution tool with sandboxing
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    approvals: ApprovalGate,
 }
 
 impl ShellTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self::with_runtime(security, Arc::new(NativeRuntime::new()))
+    }
+
+    /// Run commands through `runtime`, e.g. inside a Docker container.
+    pub fn with_runtime(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
+        Self {
+            security,
+            runtime,
+            approvals: ApprovalGate::default(),
+        }
     }
 }
 
@@ -33,7 +64,9 @@ impl Tool for ShellTool {
     }
 
     fn description(&self) -> &str {
-        "Execute a shell command in the workspace directory"
+        "Execute a shell command in the workspace directory. Only allowlisted commands run; \
+         in supervised mode the user is asked to confirm destructive ones (rm, git push, …) \
+         before they run."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -43,12 +76,24 @@ impl Tool for ShellTool {
                 "command": {
                     "type": "string",
                     "description": "The shell command to execute"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Give up after this many seconds (can only shorten the runtime's limit)"
                 }
             },
             "required": ["command"]
         })
     }
 
+    fn latency(&self) -> Latency {
+        Latency::Medium
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let command = args
             .get("command")
@@ -64,23 +109,49 @@ impl Tool for ShellTool {
             });
         }
 
-        // Execute with timeout to prevent hanging commands.
-        // Clear the environment to prevent leaking API keys and other secrets
-        // (CWE-200), then re-add only safe, functional variables.
-        let mut cmd = tokio::process::Command::new("sh");
-        cmd.arg("-c")
-            .arg(command)
-            .current_dir(&self.security.workspace_dir)
-            .env_clear();
-
-        for var in SAFE_ENV_VARS {
-            if let Ok(val) = std::env::var(var) {
-                cmd.env(var, val);
-            }
+        if self.security.command_needs_approval(command)
+            && !self.approvals.confirm(&format!("Run `{command}`?")).await
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "The user did not approve `{command}`; nothing has run."
+                )),
+            });
         }
 
-        let result =
-            tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let ShellCommand {
+            command: mut cmd,
+            cleanup,
+        } = match self.runtime.shell_command(command, &self.security) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to prepare command: {e}")),
+                })
+            }
+        };
+        // Secrets from `password_manager`, referenced by name in the command.
+        cmd.envs(session_secrets().env()).kill_on_drop(true);
+
+        // Execute with timeout to prevent hanging commands.
+        let limit = self.runtime.shell_timeout();
+        let timeout = args
+            .get("timeout_secs")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(limit, |secs| Duration::from_secs(secs.max(1)).min(limit));
+        let result = tokio::time::timeout(timeout, cmd.output()).await;
 
         match result {
             Ok(Ok(output)) => {
@@ -112,13 +183,19 @@ impl Tool for ShellTool {
                 output: String::new(),
                 error: Some(format!("Failed to execute command: {e}")),
             }),
-            Err(_) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!(
-                    "Command timed out after {SHELL_TIMEOUT_SECS}s and was killed"
-                )),
-            }),
+            Err(_) => {
+                if let Some(mut cleanup) = cleanup {
+                    let _ = cleanup.output().await;
+                }
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Command timed out after {}s and was killed",
+                        timeout.as_secs()
+                    )),
+                })
+            }
         }
     }
 }
@@ -212,6 +289,76 @@ mod tests {
         assert!(!result.success);
     }
 
+    #[tokio::test]
+    async fn shell_asks_before_destructive_command() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("scratch.txt"), "x").unwrap();
+        let mut tool = ShellTool::new(Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            allowed_commands: vec!["rm".into()],
+            ..SecurityPolicy::default()
+        }));
+
+        tool.approvals = ApprovalGate::answering(false);
+        let denied = tool
+            .execute(json!({"command": "rm scratch.txt"}))
+            .await
+            .unwrap();
+        assert!(!denied.success);
+        assert!(tmp.path().join("scratch.txt").exists());
+
+        tool.approvals = ApprovalGate::answering(true);
+        let result = tool
+            .execute(json!({"command": "rm scratch.txt"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(!tmp.path().join("scratch.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn shell_refuses_destructive_command_with_nobody_to_ask() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("scratch.txt"), "x").unwrap();
+        let tool = ShellTool::new(Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            allowed_commands: vec!["rm".into()],
+            ..SecurityPolicy::default()
+        }));
+        let result = tool
+            .execute(json!({"command": "rm scratch.txt", "approval_token": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(tmp.path().join("scratch.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn shell_sees_held_secrets_by_name() {
+        session_secrets().hold("ZEROCLAW_SECRET_SHELL_TEST", "from-the-vault".into());
+        let tool = ShellTool::new(test_security(AutonomyLevel::Full));
+        let result = tool
+            .execute(json!({"command": "echo $ZEROCLAW_SECRET_SHELL_TEST"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output.trim(), "from-the-vault");
+    }
+
+    #[tokio::test]
+    async fn shell_honours_shorter_timeout() {
+        let tool = ShellTool::new(Arc::new(SecurityPolicy {
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["sleep".into()],
+            ..SecurityPolicy::default()
+        }));
+        let result = tool
+            .execute(json!({"command": "sleep 5", "timeout_secs": 1}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("timed out after 1s"));
+    }
+
     fn test_security_with_env_cmd() -> Arc<SecurityPolicy> {
         Arc::new(SecurityPolicy {
             autonomy: AutonomyLevel::Supervised,
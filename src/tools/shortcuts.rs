@@ -0,0 +1,286 @@
+// Apple Shortcuts — run allowlisted shortcuts with the macOS `shortcuts` CLI,
+// so the agent can reach HomeKit scenes, Focus modes, Reminders and whatever
+// else the user has already automated. Input goes in through a temp file and
+// the shortcut's output comes back as plain text.
+
+use super::traits::{Latency, Tool, ToolResult};
+use crate::config::ShortcutsConfig;
+use crate::security::{SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const BINARY: &str = "shortcuts";
+/// Output returned to the model is capped; a shortcut can produce a lot.
+const MAX_OUTPUT_CHARS: usize = 8_000;
+
+/// Run named Apple Shortcuts.
+pub struct ShortcutsTool {
+    config: ShortcutsConfig,
+    security: Arc<SecurityPolicy>,
+    binary: PathBuf,
+}
+
+impl ShortcutsTool {
+    pub fn new(config: ShortcutsConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config,
+            security,
+            binary: PathBuf::from(BINARY),
+        }
+    }
+
+    /// Use another `shortcuts` executable (tests).
+    #[must_use]
+    pub fn with_binary(mut self, binary: PathBuf) -> Self {
+        self.binary = binary;
+        self
+    }
+
+    /// The allowlisted spelling of `name`, if it is allowed.
+    fn allowed(&self, name: &str) -> Option<&str> {
+        self.config
+            .allowed
+            .iter()
+            .find(|a| a.trim().eq_ignore_ascii_case(name.trim()))
+            .map(String::as_str)
+    }
+
+    async fn cli(&self, args: &[&std::ffi::OsStr]) -> anyhow::Result<String> {
+        let mut cmd = tokio::process::Command::new(&self.binary);
+        cmd.args(args).kill_on_drop(true);
+        let timeout = Duration::from_secs(self.config.timeout_secs.max(1));
+        let output = tokio::time::timeout(timeout, cmd.output())
+            .await
+            .map_err(|_| anyhow::anyhow!("shortcut timed out after {}s", timeout.as_secs()))?
+            .map_err(|e| anyhow::anyhow!("Failed to run `shortcuts` (macOS only): {e}"))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "shortcuts failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Names of the shortcuts installed on this Mac.
+    pub async fn installed(&self) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .cli(&["list".as_ref()])
+            .await?
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    async fn run(&self, name: &str, input: Option<&str>) -> anyhow::Result<String> {
+        let dir = std::env::temp_dir().join(format!("zeroclaw-shortcut-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        let output_path = dir.join("output.txt");
+        let input_path = dir.join("input.txt");
+        let mut args: Vec<&std::ffi::OsStr> = vec!["run".as_ref(), name.as_ref()];
+        if let Some(input) = input {
+            std::fs::write(&input_path, input)?;
+            args.extend(["--input-path".as_ref(), input_path.as_os_str()]);
+        }
+        args.extend([
+            "--output-path".as_ref(),
+            output_path.as_os_str(),
+            "--output-type".as_ref(),
+            "public.plain-text".as_ref(),
+        ]);
+        let result = self.cli(&args).await;
+        let output = std::fs::read_to_string(&output_path).unwrap_or_default();
+        let _ = std::fs::remove_dir_all(&dir);
+        let stdout = result?;
+        let text = if output.trim().is_empty() {
+            stdout
+        } else {
+            output
+        };
+        Ok(crate::util::truncate_with_ellipsis(
+            text.trim(),
+            MAX_OUTPUT_CHARS,
+        ))
+    }
+}
+
+fn failed(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+#[async_trait]
+impl Tool for ShortcutsTool {
+    fn name(&self) -> &str {
+        "shortcuts"
+    }
+
+    fn description(&self) -> &str {
+        "Run the user's Apple Shortcuts by name (home scenes, Focus modes, reminders and other \
+         automations), optionally passing text input, and return their text output. Use \
+         action \"list\" to see which ones are allowed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "run"],
+                    "description": "list = allowed shortcuts, run = run one"
+                },
+                "name": {
+                    "type": "string",
+                    "enum": self.config.allowed,
+                    "description": "Shortcut to run"
+                },
+                "input": {
+                    "type": "string",
+                    "description": "Optional text passed to the shortcut as its input"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn latency(&self) -> Latency {
+        Latency::Medium
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    fn call_class(&self, args: &serde_json::Value) -> ToolClass {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("list") => ToolClass::Read,
+            _ => ToolClass::Write,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let field = |name: &str| {
+            args.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
+        match field("action").unwrap_or("run") {
+            "list" => {
+                let installed = self.installed().await.unwrap_or_default();
+                let lines: Vec<String> = self
+                    .config
+                    .allowed
+                    .iter()
+                    .map(|name| {
+                        if installed.is_empty() || installed.iter().any(|i| i == name) {
+                            format!("- {name}")
+                        } else {
+                            format!("- {name} (not installed on this Mac)")
+                        }
+                    })
+                    .collect();
+                Ok(ToolResult {
+                    success: true,
+                    output: lines.join("\n"),
+                    error: None,
+                })
+            }
+            "run" => {
+                let Some(requested) = field("name") else {
+                    return Ok(failed("Missing 'name'".into()));
+                };
+                let Some(name) = self.allowed(requested) else {
+                    return Ok(failed(format!(
+                        "Shortcut '{requested}' is not in [integrations.shortcuts].allowed"
+                    )));
+                };
+                if !self.security.can_act() {
+                    return Ok(failed("Action blocked: autonomy is read-only".into()));
+                }
+                if !self.security.record_action() {
+                    return Ok(failed("Action blocked: rate limit exceeded".into()));
+                }
+                match self.run(name, field("input")).await {
+                    Ok(output) => Ok(ToolResult {
+                        success: true,
+                        output: if output.is_empty() {
+                            format!("Ran '{name}'.")
+                        } else {
+                            output
+                        },
+                        error: None,
+                    }),
+                    Err(e) => Ok(failed(format!("{e:#}"))),
+                }
+            }
+            other => Ok(failed(format!("Unknown action '{other}'"))),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Stands in for `shortcuts`: lists two shortcuts and echoes its input.
+    const FAKE_CLI: &str = r#"#!/bin/sh
+case "$1" in
+  list) printf 'Arrive Home\nGood Night\n' ;;
+  run)
+    name="$2"; shift 2
+    while [ $# -gt 0 ]; do
+      case "$1" in
+        --input-path) input=$(cat "$2") ;;
+        --output-path) out="$2" ;;
+      esac
+      shift
+    done
+    printf 'ran %s with %s' "$name" "$input" > "$out" ;;
+esac
+"#;
+
+    #[tokio::test]
+    async fn runs_only_allowlisted_shortcuts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cli = tmp.path().join("shortcuts");
+        std::fs::write(&cli, FAKE_CLI).unwrap();
+        std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let config = ShortcutsConfig {
+            allowed: vec!["Arrive Home".into(), "Vacation Mode".into()],
+            ..ShortcutsConfig::default()
+        };
+        let tool = ShortcutsTool::new(config, Arc::new(SecurityPolicy::default())).with_binary(cli);
+        // Running one acts on the Mac; listing them doesn't.
+        assert_eq!(tool.call_class(&json!({"name": "x"})), ToolClass::Write);
+        assert_eq!(tool.call_class(&json!({"action": "list"})), ToolClass::Read);
+
+        let ran = tool
+            .execute(json!({"action": "run", "name": "arrive home", "input": "hi"}))
+            .await
+            .unwrap();
+        assert_eq!(ran.output, "ran Arrive Home with hi");
+
+        let refused = tool
+            .execute(json!({"action": "run", "name": "Good Night"}))
+            .await
+            .unwrap();
+        assert!(!refused.success);
+
+        let listed = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert_eq!(
+            listed.output,
+            "- Arrive Home\n- Vacation Mode (not installed on this Mac)"
+        );
+    }
+}
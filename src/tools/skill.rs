@@ -0,0 +1,296 @@
+// Skill tools — the `[[tools]]` a skill manifest declares, exposed to the
+// agent. Untrusted skills are answered with a simulated result (see
+// `skills::trust`); trusted ones run with the permissions their manifest
+// declares and nothing more.
+
+use super::shell::ShellTool;
+use super::traits::{Tool, ToolResult};
+use crate::runtime::RuntimeAdapter;
+use crate::security::{SecurityPolicy, ToolClass};
+use crate::skills::trust::{TrustStatus, TrustStore};
+use crate::skills::wasm::WasmHost;
+use crate::skills::{Skill, SkillTool, TOOL_KINDS};
+use crate::util::{percent_encode, LazyClient};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Response bodies beyond this are cut off.
+const MAX_HTTP_BYTES: usize = 65_536;
+
+pub struct SkillToolAdapter {
+    skill: String,
+    permissions: Vec<String>,
+    tool: SkillTool,
+    /// For `script` tools: the script, relative to the workspace when inside it
+    script: Option<String>,
+    /// For `wasm` tools: the module
+    module: Option<PathBuf>,
+    workspace: PathBuf,
+    trust: Arc<TrustStore>,
+    shell: ShellTool,
+    wasm: Arc<WasmHost>,
+    client: LazyClient,
+}
+
+/// Tools for every skill that declares some.
+pub fn skill_tools(
+    skills: &[Skill],
+    security: &Arc<SecurityPolicy>,
+    runtime: &Arc<dyn RuntimeAdapter>,
+    trust: &Arc<TrustStore>,
+    wasm: &Arc<WasmHost>,
+) -> Vec<Box<dyn Tool>> {
+    let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+    for skill in skills {
+        let dir = skill.location.as_deref().and_then(Path::parent);
+        for tool in &skill.tools {
+            if !TOOL_KINDS.contains(&tool.kind.as_str()) {
+                tracing::warn!(skill = %skill.name, tool = %tool.name, kind = %tool.kind, "Unknown skill tool kind; skipped");
+                continue;
+            }
+            let script = (tool.kind == "script")
+                .then(|| dir.map(|d| d.join(&tool.command)))
+                .flatten()
+                .map(|path| {
+                    path.strip_prefix(&security.workspace_dir)
+                        .unwrap_or(&path)
+                        .display()
+                        .to_string()
+                });
+            let module = (tool.kind == "wasm")
+                .then(|| dir.map(|d| d.join(&tool.command)))
+                .flatten();
+            tools.push(Box::new(SkillToolAdapter {
+                skill: skill.name.clone(),
+                permissions: skill.permissions.clone(),
+                tool: tool.clone(),
+                script,
+                module,
+                workspace: security.workspace_dir.clone(),
+                trust: trust.clone(),
+                shell: ShellTool::with_runtime(security.clone(), runtime.clone()),
+                wasm: wasm.clone(),
+                client: LazyClient::new(std::time::Duration::from_secs(30)),
+            }));
+        }
+    }
+    tools
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn failure(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+impl SkillToolAdapter {
+    /// The command or URL this call would run, with `{{arg}}` placeholders
+    /// filled in — quoted for shell kinds, percent-encoded for `http`. Wasm
+    /// modules get the arguments as JSON instead.
+    fn render(&self, args: &Value) -> Result<String, String> {
+        if let Some(module) = &self.module {
+            return Ok(format!("wasm {} {args}", module.display()));
+        }
+        let mut action = match &self.script {
+            Some(script) => format!("sh {}", shell_quote(script)),
+            None => self.tool.command.clone(),
+        };
+        for name in self.tool.args.keys() {
+            let value = match args.get(name) {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Null) | None => return Err(format!("Missing '{name}' parameter")),
+                Some(other) => other.to_string(),
+            };
+            let value = if self.tool.kind == "http" {
+                percent_encode(&value)
+            } else {
+                shell_quote(&value)
+            };
+            let placeholder = format!("{{{{{name}}}}}");
+            if self.script.is_some() && !self.tool.command.contains(&placeholder) {
+                // Scripts take their arguments positionally as well.
+                action.push(' ');
+                action.push_str(&value);
+            }
+            action = action.replace(&placeholder, &value);
+        }
+        Ok(action)
+    }
+
+    async fn fetch(&self, url: &str) -> ToolResult {
+        let response = match self.client.get(url).send().await {
+            Ok(response) => response,
+            Err(e) => return failure(format!("Request failed: {e}")),
+        };
+        let status = response.status();
+        match response.text().await {
+            Ok(mut body) => {
+                if body.len() > MAX_HTTP_BYTES {
+                    body.truncate(body.floor_char_boundary(MAX_HTTP_BYTES));
+                    body.push_str("\n... [response truncated]");
+                }
+                ToolResult {
+                    success: status.is_success(),
+                    error: (!status.is_success()).then(|| format!("HTTP {status}")),
+                    output: body,
+                }
+            }
+            Err(e) => failure(format!("Failed to read response: {e}")),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SkillToolAdapter {
+    fn name(&self) -> &str {
+        &self.tool.name
+    }
+
+    fn description(&self) -> &str {
+        &self.tool.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        if let Some(schema) = &self.tool.parameters {
+            return schema.clone();
+        }
+        let properties: BTreeMap<&String, Value> = self
+            .tool
+            .args
+            .iter()
+            .map(|(name, description)| {
+                (name, json!({"type": "string", "description": description}))
+            })
+            .collect();
+        let mut required: Vec<&String> = self.tool.args.keys().collect();
+        required.sort();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required
+        })
+    }
+
+    fn needs_network(&self) -> bool {
+        self.tool.kind == "http"
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Write
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = match self.render(&args) {
+            Ok(action) => action,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        if let status @ TrustStatus::Quarantined { .. } = self.trust.status(&self.skill) {
+            self.trust
+                .record_simulated(&self.skill, &self.tool.name, &action);
+            tracing::info!(skill = %self.skill, tool = %self.tool.name, "Quarantined skill call simulated");
+            return Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "[quarantine] Skill '{}' is {status}, so `{}` was logged instead of run: \
+                     {action}\nNothing was executed. Tell the user the result is simulated and \
+                     that `zeroclaw skills trust {}` lets it run for real.",
+                    self.skill, self.tool.name, self.skill
+                ),
+                error: None,
+            });
+        }
+
+        if let Some(permission) = self.tool.permission() {
+            if !self.permissions.iter().any(|p| p == permission) {
+                return Ok(failure(format!(
+                    "Skill '{}' does not declare the '{permission}' permission in its manifest",
+                    self.skill
+                )));
+            }
+        }
+
+        if let Some(module) = &self.module {
+            let workspace = self
+                .permissions
+                .iter()
+                .any(|p| p == "workspace")
+                .then_some(self.workspace.as_path());
+            Ok(self
+                .wasm
+                .call(module, &self.tool.name, &args, workspace)
+                .await)
+        } else if self.tool.kind == "http" {
+            Ok(self.fetch(&action).await)
+        } else {
+            self.shell.execute(json!({ "command": action })).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SkillsConfig;
+    use crate::runtime::NativeRuntime;
+    use std::collections::HashMap;
+
+    fn skill(permissions: &[&str]) -> Skill {
+        Skill {
+            name: "greeter".into(),
+            description: "Greets".into(),
+            version: "1.0.0".into(),
+            author: None,
+            tags: vec![],
+            tools: vec![SkillTool {
+                name: "greet".into(),
+                description: "Say hello".into(),
+                kind: "shell".into(),
+                command: "echo hello {{who}}".into(),
+                args: HashMap::from([("who".into(), "Who to greet".into())]),
+                parameters: None,
+            }],
+            prompts: vec![],
+            permissions: permissions.iter().map(ToString::to_string).collect(),
+            location: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn untrusted_skills_are_simulated_and_trusted_ones_keep_to_their_permissions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let runtime: Arc<dyn RuntimeAdapter> = Arc::new(NativeRuntime::new());
+        let trust = Arc::new(TrustStore::open(tmp.path(), &SkillsConfig::default()));
+        let wasm = Arc::new(WasmHost::new(&SkillsConfig::default()));
+
+        let tools = skill_tools(&[skill(&["shell"])], &security, &runtime, &trust, &wasm);
+        assert_eq!(tools[0].parameters_schema()["required"], json!(["who"]));
+        let result = tools[0].execute(json!({"who": "it's me"})).await.unwrap();
+        assert!(result.output.starts_with("[quarantine]"));
+        assert!(result.output.contains(r"echo hello 'it'\''s me'"));
+        assert!(tmp.path().join("skills/quarantine.jsonl").exists());
+
+        trust.trust("greeter");
+        let result = tools[0].execute(json!({"who": "world"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.trim(), "hello world");
+
+        let tools = skill_tools(&[skill(&[])], &security, &runtime, &trust, &wasm);
+        let result = tools[0].execute(json!({"who": "world"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("'shell' permission"));
+    }
+}
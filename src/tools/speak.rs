@@ -0,0 +1,111 @@
+// Speak tool — answer with a voice message. The tool hands back a `speech`
+// block for the model to pass through; the channel that sends the reply
+// synthesizes it with `[voice]` (OpenAI, ElevenLabs or piper) and shows the
+// words instead where it can't send audio.
+
+use super::reply;
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Roughly what the speech APIs accept in one request.
+const MAX_SPEECH_CHARS: usize = 4_000;
+
+pub struct SpeakTool;
+
+impl SpeakTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SpeakTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for SpeakTool {
+    fn name(&self) -> &str {
+        "speak"
+    }
+
+    fn description(&self) -> &str {
+        "Answer with a voice message. Returns a block to copy unchanged into your reply; \
+         channels that send audio say its text, the rest show it."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": "What to say, written for listening: no tables, links or code"
+                }
+            },
+            "required": ["text"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text = args
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        let error = if text.is_empty() {
+            Some("Missing 'text'".to_string())
+        } else if text.chars().count() > MAX_SPEECH_CHARS {
+            Some(format!(
+                "Too long to speak ({} characters, at most {MAX_SPEECH_CHARS}); shorten it",
+                text.chars().count()
+            ))
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Put this block in your reply unchanged; it is sent as a voice message.\n\n{}",
+                reply::speech_block(text)
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn speak_returns_a_speech_block() {
+        let result = SpeakTool::new()
+            .execute(json!({ "text": "Your flight boards at 9." }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(
+            reply::extract_speech(&result.output).1.as_deref(),
+            Some("Your flight boards at 9.")
+        );
+
+        let empty = SpeakTool::new().execute(json!({})).await.unwrap();
+        assert!(!empty.success);
+        let long = "a".repeat(MAX_SPEECH_CHARS + 1);
+        let long = SpeakTool::new()
+            .execute(json!({ "text": long }))
+            .await
+            .unwrap();
+        assert!(long.error.unwrap().contains("shorten"));
+    }
+}
@@ -0,0 +1,266 @@
+// Task tools — let the agent see tracked work and checkpoint its own
+// progress so a multi-step job can be resumed after a restart.
+
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use crate::tasks::{TaskOrigin, TaskStatus, TaskStore};
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// List tracked tasks with their status
+pub struct ListTasksTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ListTasksTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for ListTasksTool {
+    fn name(&self) -> &str {
+        "list_tasks"
+    }
+
+    fn description(&self) -> &str {
+        "List tracked tasks (multi-step work started by the user or by cron) with their status. Pass an ID to see its progress notes."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Task ID (prefix is enough) to show in detail"
+                },
+                "include_closed": {
+                    "type": "boolean",
+                    "description": "Also list done and failed tasks (default: false)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let store = TaskStore::open(&self.security.workspace_dir)?;
+
+        if let Some(prefix) = args.get("id").and_then(|v| v.as_str()) {
+            let task = match store.resolve_id(prefix).and_then(|id| store.get(&id)) {
+                Ok(task) => task,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(e.to_string()),
+                    })
+                }
+            };
+            let mut output = format!(
+                "{} [{}] {} (from {})\n",
+                task.short_id(),
+                task.status.as_str(),
+                task.title,
+                task.origin.as_str()
+            );
+            if let Some(result) = &task.result {
+                let _ = writeln!(output, "Result: {result}");
+            }
+            for note in store.notes(&task.id)? {
+                let _ = writeln!(output, "- [{}] {}", note.at.to_rfc3339(), note.text);
+            }
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        }
+
+        let include_closed = args
+            .get("include_closed")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let tasks = store.list(include_closed)?;
+        if tasks.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "No tasks.".into(),
+                error: None,
+            });
+        }
+        let mut output = format!("{} task(s):\n", tasks.len());
+        for task in &tasks {
+            let _ = writeln!(
+                output,
+                "- {} [{}] {} (from {}, updated {})",
+                task.short_id(),
+                task.status.as_str(),
+                task.title,
+                task.origin.as_str(),
+                task.updated_at.to_rfc3339()
+            );
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Open a task or record progress on one
+pub struct UpdateTaskTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl UpdateTaskTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for UpdateTaskTool {
+    fn name(&self) -> &str {
+        "update_task"
+    }
+
+    fn description(&self) -> &str {
+        "Track multi-step work. Without an ID, opens a new task with the given title. With an ID, records a progress note and/or changes its status (pending, running, waiting-approval, done, failed)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Task ID (prefix is enough); omit to open a new task"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Title for a new task"
+                },
+                "status": {
+                    "type": "string",
+                    "enum": ["pending", "running", "waiting-approval", "done", "failed"]
+                },
+                "note": {
+                    "type": "string",
+                    "description": "What was just done or what is left (used to resume later)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let store = TaskStore::open(&self.security.workspace_dir)?;
+        let note = args.get("note").and_then(|v| v.as_str());
+        let status = match args.get("status").and_then(|v| v.as_str()) {
+            Some(raw) => match TaskStatus::parse(raw) {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(e.to_string()),
+                    })
+                }
+            },
+            None => None,
+        };
+
+        let result = match args.get("id").and_then(|v| v.as_str()) {
+            None => {
+                let Some(title) = args.get("title").and_then(|v| v.as_str()) else {
+                    anyhow::bail!("Missing 'title' parameter for a new task");
+                };
+                store
+                    .create(
+                        title,
+                        TaskOrigin::User,
+                        status.unwrap_or(TaskStatus::Running),
+                    )
+                    .and_then(|task| {
+                        if let Some(note) = note {
+                            store.add_note(&task.id, note)?;
+                        }
+                        Ok(format!("Opened task {}", task.short_id()))
+                    })
+            }
+            Some(prefix) => store.resolve_id(prefix).and_then(|id| {
+                match (status, note) {
+                    (Some(status), note) => store.set_status(&id, status, note)?,
+                    (None, Some(note)) => store.add_note(&id, note)?,
+                    (None, None) => anyhow::bail!("Nothing to update: pass a status or a note"),
+                }
+                let task = store.get(&id)?;
+                Ok(format!(
+                    "Task {} is {}",
+                    task.short_id(),
+                    task.status.as_str()
+                ))
+            }),
+        };
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn agent_can_open_checkpoint_and_list_tasks() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let update = UpdateTaskTool::new(security.clone());
+        let list = ListTasksTool::new(security);
+
+        let opened = update
+            .execute(json!({"title": "Rotate API keys", "note": "Listed 4 keys"}))
+            .await
+            .unwrap();
+        assert!(opened.success);
+        let id = opened.output.rsplit(' ').next().unwrap().to_string();
+
+        let waiting = update
+            .execute(json!({"id": id, "status": "waiting-approval", "note": "Need prod access"}))
+            .await
+            .unwrap();
+        assert_eq!(waiting.output, format!("Task {id} is waiting-approval"));
+
+        let listed = list.execute(json!({})).await.unwrap();
+        assert!(listed.output.contains("[waiting-approval] Rotate API keys"));
+        let detail = list.execute(json!({"id": id})).await.unwrap();
+        assert!(
+            detail.output.contains("Listed 4 keys") && detail.output.contains("Need prod access")
+        );
+
+        let bad = update
+            .execute(json!({"id": id, "status": "paused"}))
+            .await
+            .unwrap();
+        assert!(!bad.success);
+    }
+}
@@ -0,0 +1,588 @@
+// Terraform / OpenTofu plan summarizer — `plan -json` in a workspace repo.
+//
+// Runs a plan only (never apply), without taking the state lock and without
+// writing a plan file, then turns the machine-readable event stream into a
+// short change list with risk flags: destroys and replacements of stateful
+// resources, IAM changes and network exposure. The output is what a human
+// should read before approving an apply.
+
+use super::shell::safe_env_vars;
+use super::traits::{Latency, Tool, ToolResult};
+use crate::config::TerraformConfig;
+use crate::security::{SecurityPolicy, ToolClass};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Changes listed individually; the rest are counted.
+const MAX_LISTED_CHANGES: usize = 40;
+
+/// Resource types whose destruction loses data.
+const STATEFUL_TYPES: &[&str] = &[
+    "aws_db_instance",
+    "aws_rds_cluster",
+    "aws_dynamodb_table",
+    "aws_s3_bucket",
+    "aws_efs_file_system",
+    "aws_ebs_volume",
+    "aws_elasticache_cluster",
+    "aws_kms_key",
+    "google_sql_database_instance",
+    "google_storage_bucket",
+    "google_bigquery_dataset",
+    "google_compute_disk",
+    "google_kms_crypto_key",
+    "azurerm_storage_account",
+    "azurerm_mssql_database",
+    "azurerm_postgresql_flexible_server",
+    "azurerm_key_vault",
+];
+
+/// Substrings that mark identity / permission resources.
+const IAM_MARKERS: &[&str] = &["_iam_", "role_assignment", "_policy", "service_account"];
+
+/// Substrings that mark network exposure resources.
+const NETWORK_MARKERS: &[&str] = &[
+    "security_group",
+    "firewall",
+    "network_acl",
+    "_route53_record",
+    "dns_record",
+    "cloudflare_record",
+    "load_balancer",
+    "_lb_listener",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl Risk {
+    fn icon(self) -> &'static str {
+        match self {
+            Risk::Low => "🟢",
+            Risk::Medium => "🟠",
+            Risk::High => "🔴",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlannedChange {
+    address: String,
+    resource_type: String,
+    action: String,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PlanSummary {
+    changes: Vec<PlannedChange>,
+    add: u64,
+    change: u64,
+    remove: u64,
+    drifted: Vec<String>,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+/// Summarize a Terraform or `OpenTofu` plan for approval.
+pub struct TerraformPlanTool {
+    config: TerraformConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl TerraformPlanTool {
+    pub fn new(config: TerraformConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    async fn run_plan(
+        &self,
+        dir: &std::path::Path,
+        var_file: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut cmd = tokio::process::Command::new(&self.config.binary);
+        cmd.args(["plan", "-json", "-input=false", "-lock=false", "-no-color"]);
+        if let Some(var_file) = var_file {
+            cmd.arg(format!("-var-file={var_file}"));
+        }
+        cmd.current_dir(dir).env_clear().kill_on_drop(true);
+        for var in safe_env_vars() {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        for (key, val) in std::env::vars() {
+            if self
+                .config
+                .env_prefixes
+                .iter()
+                .any(|p| key.starts_with(p.as_str()))
+            {
+                cmd.env(key, val);
+            }
+        }
+
+        let timeout = Duration::from_secs(self.config.timeout_secs.max(10));
+        let output = tokio::time::timeout(timeout, cmd.output())
+            .await
+            .map_err(|_| anyhow::anyhow!("plan timed out after {}s", timeout.as_secs()))?
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to run `{}` (is it installed?): {e}",
+                    self.config.binary
+                )
+            })?;
+        // With -json, failures are reported as diagnostic events on stdout.
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+fn parse_plan_stream(stream: &str) -> PlanSummary {
+    let mut summary = PlanSummary::default();
+    for line in stream.lines() {
+        let Ok(event) = serde_json::from_str::<PlanEvent>(line) else {
+            continue;
+        };
+        match event.kind.as_str() {
+            "planned_change" => {
+                if let Some(change) = event.change {
+                    summary.changes.push(PlannedChange {
+                        address: change.resource.addr,
+                        resource_type: change.resource.resource_type,
+                        action: change.action,
+                        reason: change.reason,
+                    });
+                }
+            }
+            "resource_drift" => {
+                if let Some(change) = event.change {
+                    summary.drifted.push(change.resource.addr);
+                }
+            }
+            "change_summary" => {
+                if let Some(counts) = event.changes {
+                    summary.add = counts.add;
+                    summary.change = counts.change;
+                    summary.remove = counts.remove;
+                }
+            }
+            "diagnostic" => {
+                if let Some(diag) = event.diagnostic {
+                    let text = match diag.address {
+                        Some(addr) => format!("{addr}: {}", diag.summary),
+                        None => diag.summary,
+                    };
+                    if diag.severity == "error" {
+                        summary.errors.push(text);
+                    } else {
+                        summary.warnings.push(text);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    summary
+}
+
+/// Risk of one change plus the reason it was flagged.
+fn assess(change: &PlannedChange) -> (Risk, Option<&'static str>) {
+    let ty = change.resource_type.as_str();
+    let destructive = matches!(change.action.as_str(), "delete" | "replace");
+    if destructive && STATEFUL_TYPES.contains(&ty) {
+        return (Risk::High, Some("stateful resource; data loss possible"));
+    }
+    if change.action == "noop" || change.action == "read" {
+        return (Risk::Low, None);
+    }
+    if IAM_MARKERS.iter().any(|m| ty.contains(m)) {
+        return (Risk::Medium, Some("IAM / permissions"));
+    }
+    if NETWORK_MARKERS.iter().any(|m| ty.contains(m)) {
+        return (Risk::Medium, Some("network exposure"));
+    }
+    if change.action == "replace" {
+        return (Risk::Medium, Some("destroy and re-create"));
+    }
+    if change.action == "delete" {
+        return (Risk::Medium, Some("destroyed"));
+    }
+    (Risk::Low, None)
+}
+
+fn action_symbol(action: &str) -> &'static str {
+    match action {
+        "create" => "+",
+        "update" => "~",
+        "delete" | "remove" => "-",
+        "replace" => "-/+",
+        "move" => "→",
+        "import" => "⇐",
+        _ => "·",
+    }
+}
+
+fn format_summary(label: &str, summary: &PlanSummary) -> String {
+    if !summary.errors.is_empty() {
+        let mut out = format!("Plan for {label} failed:\n");
+        for error in &summary.errors {
+            let _ = writeln!(out, "- {error}");
+        }
+        return out.trim_end().to_string();
+    }
+
+    let changes: Vec<&PlannedChange> = summary
+        .changes
+        .iter()
+        .filter(|c| c.action != "noop" && c.action != "read")
+        .collect();
+    if changes.is_empty() {
+        return format!("Plan for {label}: no changes. Infrastructure matches the configuration.");
+    }
+
+    let assessed: Vec<(&PlannedChange, Risk, Option<&str>)> = changes
+        .iter()
+        .map(|c| {
+            let (risk, why) = assess(c);
+            (*c, risk, why)
+        })
+        .collect();
+    let overall = assessed
+        .iter()
+        .map(|(_, risk, _)| *risk)
+        .max()
+        .unwrap_or(Risk::Low);
+
+    let mut out = format!(
+        "Plan for {label}: {} to add, {} to change, {} to destroy. Overall risk: {} {:?}\n",
+        summary.add,
+        summary.change,
+        summary.remove,
+        overall.icon(),
+        overall
+    );
+
+    let flagged: Vec<_> = assessed
+        .iter()
+        .filter(|(_, _, why)| why.is_some())
+        .collect();
+    if !flagged.is_empty() {
+        out.push_str("\n⚠️ Risk flags:\n");
+        for (change, risk, why) in flagged {
+            let _ = writeln!(
+                out,
+                "- {} {} will be {} ({})",
+                risk.icon(),
+                change.address,
+                past_tense(&change.action),
+                why.unwrap_or_default()
+            );
+        }
+    }
+
+    out.push_str("\nChanges:\n");
+    for change in changes.iter().take(MAX_LISTED_CHANGES) {
+        let _ = write!(out, "{} {}", action_symbol(&change.action), change.address);
+        if let Some(ref reason) = change.reason {
+            let _ = write!(out, " ({})", reason.replace('_', " "));
+        }
+        out.push('\n');
+    }
+    if changes.len() > MAX_LISTED_CHANGES {
+        let _ = writeln!(out, "… and {} more", changes.len() - MAX_LISTED_CHANGES);
+    }
+
+    if !summary.drifted.is_empty() {
+        let _ = writeln!(
+            out,
+            "\nDrift detected outside Terraform: {}",
+            summary.drifted.join(", ")
+        );
+    }
+    if !summary.warnings.is_empty() {
+        let _ = writeln!(out, "\nWarnings:\n- {}", summary.warnings.join("\n- "));
+    }
+    out.trim_end().to_string()
+}
+
+fn past_tense(action: &str) -> &str {
+    match action {
+        "create" => "created",
+        "update" => "updated in place",
+        "delete" => "DESTROYED",
+        "replace" => "REPLACED",
+        "remove" => "removed from state",
+        "move" => "moved",
+        "import" => "imported",
+        other => other,
+    }
+}
+
+fn failed(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+#[async_trait]
+impl Tool for TerraformPlanTool {
+    fn name(&self) -> &str {
+        "terraform_plan"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn latency(&self) -> Latency {
+        Latency::Slow
+    }
+
+    fn description(&self) -> &str {
+        "Run a read-only Terraform/OpenTofu plan (no apply, no state lock) in a workspace \
+         directory and summarize resource changes with risk flags. Use before asking the user \
+         to approve an apply."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "dir": {
+                    "type": "string",
+                    "description": "Terraform root module, relative to the workspace (default: '.')"
+                },
+                "var_file": {
+                    "type": "string",
+                    "description": "Optional .tfvars file, relative to dir"
+                }
+            }
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let dir = args
+            .get("dir")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(".");
+        let var_file = args
+            .get("var_file")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+
+        if !self.security.can_act() {
+            return Ok(failed("Action blocked: autonomy is read-only".into()));
+        }
+        if !self.security.is_path_allowed(dir) {
+            return Ok(failed(format!(
+                "Path not allowed by security policy: {dir}"
+            )));
+        }
+        if let Some(var_file) = var_file {
+            if var_file.starts_with('-') || !self.security.is_path_allowed(var_file) {
+                return Ok(failed(format!(
+                    "Path not allowed by security policy: {var_file}"
+                )));
+            }
+        }
+
+        let resolved = match tokio::fs::canonicalize(self.security.workspace_dir.join(dir)).await {
+            Ok(p) => p,
+            Err(e) => return Ok(failed(format!("Failed to resolve '{dir}': {e}"))),
+        };
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(failed(format!(
+                "Resolved path escapes workspace: {}",
+                resolved.display()
+            )));
+        }
+        if !resolved.join(".terraform").exists() {
+            return Ok(failed(format!(
+                "'{dir}' is not initialized; run `{} init` there first",
+                self.config.binary
+            )));
+        }
+
+        if !self.security.record_action() {
+            return Ok(failed("Action blocked: rate limit exceeded".into()));
+        }
+
+        let stream = match self.run_plan(&resolved, var_file).await {
+            Ok(stream) => stream,
+            Err(e) => return Ok(failed(e.to_string())),
+        };
+        let summary = parse_plan_stream(&stream);
+        let label = format!("{dir} ({})", self.config.binary);
+        let output = format_summary(&label, &summary);
+        if !summary.errors.is_empty() {
+            return Ok(failed(output));
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+// ── plan -json event types ──────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct PlanEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    change: Option<EventChange>,
+    #[serde(default)]
+    changes: Option<ChangeCounts>,
+    #[serde(default)]
+    diagnostic: Option<Diagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventChange {
+    resource: EventResource,
+    action: String,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventResource {
+    addr: String,
+    #[serde(default)]
+    resource_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangeCounts {
+    #[serde(default)]
+    add: u64,
+    #[serde(default)]
+    change: u64,
+    #[serde(default)]
+    remove: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    severity: String,
+    summary: String,
+    #[serde(default)]
+    address: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    const STREAM: &str = r#"{"@level":"info","@message":"OpenTofu 1.8.0","type":"version"}
+{"type":"resource_drift","change":{"resource":{"addr":"aws_instance.web","resource_type":"aws_instance"},"action":"update"}}
+{"type":"planned_change","change":{"resource":{"addr":"aws_db_instance.main","resource_type":"aws_db_instance"},"action":"replace","reason":"cannot_update"}}
+{"type":"planned_change","change":{"resource":{"addr":"aws_iam_role_policy.ci","resource_type":"aws_iam_role_policy"},"action":"update"}}
+{"type":"planned_change","change":{"resource":{"addr":"aws_s3_bucket.logs","resource_type":"aws_s3_bucket"},"action":"create"}}
+{"type":"diagnostic","diagnostic":{"severity":"warning","summary":"Deprecated attribute"}}
+{"type":"change_summary","changes":{"add":2,"change":1,"remove":1,"operation":"plan"}}"#;
+
+    fn change(ty: &str, action: &str) -> PlannedChange {
+        PlannedChange {
+            address: format!("{ty}.x"),
+            resource_type: ty.into(),
+            action: action.into(),
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn parses_changes_counts_drift_and_diagnostics() {
+        let summary = parse_plan_stream(STREAM);
+        assert_eq!(summary.changes.len(), 3);
+        assert_eq!((summary.add, summary.change, summary.remove), (2, 1, 1));
+        assert_eq!(summary.drifted, ["aws_instance.web"]);
+        assert_eq!(summary.warnings, ["Deprecated attribute"]);
+        assert!(summary.errors.is_empty());
+    }
+
+    #[test]
+    fn risk_assessment() {
+        assert_eq!(assess(&change("aws_db_instance", "replace")).0, Risk::High);
+        assert_eq!(assess(&change("aws_db_instance", "update")).0, Risk::Low);
+        assert_eq!(
+            assess(&change("google_project_iam_member", "create")).0,
+            Risk::Medium
+        );
+        assert_eq!(
+            assess(&change("aws_security_group_rule", "delete")).0,
+            Risk::Medium
+        );
+        assert_eq!(assess(&change("aws_instance", "replace")).0, Risk::Medium);
+        assert_eq!(assess(&change("aws_instance", "create")).0, Risk::Low);
+    }
+
+    #[test]
+    fn summary_lists_flags_and_changes() {
+        let out = format_summary("infra (tofu)", &parse_plan_stream(STREAM));
+        assert!(out.starts_with("Plan for infra (tofu): 2 to add, 1 to change, 1 to destroy."));
+        assert!(out.contains("Overall risk: 🔴 High"));
+        assert!(out.contains("🔴 aws_db_instance.main will be REPLACED"));
+        assert!(out.contains("🟠 aws_iam_role_policy.ci will be updated in place (IAM"));
+        assert!(out.contains("-/+ aws_db_instance.main (cannot update)"));
+        assert!(out.contains("+ aws_s3_bucket.logs"));
+        assert!(out.contains("Drift detected outside Terraform: aws_instance.web"));
+    }
+
+    #[test]
+    fn errors_and_empty_plans() {
+        let errored = parse_plan_stream(
+            r#"{"type":"diagnostic","diagnostic":{"severity":"error","summary":"No valid credential sources found"}}"#,
+        );
+        assert!(format_summary("x", &errored).contains("failed:\n- No valid credential"));
+        assert!(format_summary("x", &PlanSummary::default()).contains("no changes"));
+    }
+
+    #[tokio::test]
+    async fn uninitialized_dir_is_reported() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("infra")).unwrap();
+        let tool = TerraformPlanTool::new(
+            TerraformConfig::default(),
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::Full,
+                workspace_dir: tmp.path().to_path_buf(),
+                ..SecurityPolicy::default()
+            }),
+        );
+        let result = tool.execute(json!({"dir": "infra"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("tofu init"));
+    }
+
+    #[tokio::test]
+    async fn paths_outside_workspace_are_blocked() {
+        let tool = TerraformPlanTool::new(
+            TerraformConfig::default(),
+            Arc::new(SecurityPolicy::default()),
+        );
+        let result = tool.execute(json!({"dir": "../other"})).await.unwrap();
+        assert!(!result.success);
+    }
+}
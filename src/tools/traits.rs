@@ -1,3 +1,4 @@
+use crate::security::ToolClass;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -9,12 +10,70 @@ pub struct ToolResult {
     pub error: Option<String>,
 }
 
+/// How long a call usually takes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Latency {
+    /// Local work, well under a second
+    #[default]
+    Fast,
+    /// A network round trip or a short command, a few seconds
+    Medium,
+    /// Tens of seconds or more: page loads, sub-agents, plans
+    Slow,
+}
+
+impl Latency {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Medium => "medium",
+            Self::Slow => "slow",
+        }
+    }
+}
+
+/// What a call costs beyond the turn's own model tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cost {
+    #[default]
+    Free,
+    /// A billed API request
+    Metered,
+    /// More model calls or a billed job
+    Expensive,
+}
+
+impl Cost {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Free => "free",
+            Self::Metered => "metered",
+            Self::Expensive => "expensive",
+        }
+    }
+
+    /// Budget units one call spends
+    pub fn units(self) -> u32 {
+        match self {
+            Self::Free => 0,
+            Self::Metered => 1,
+            Self::Expensive => 5,
+        }
+    }
+}
+
 /// Description of a tool for the LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolSpec {
     pub name: String,
     pub description: String,
     pub parameters: serde_json::Value,
+    #[serde(default)]
+    pub latency: Latency,
+    #[serde(default)]
+    pub cost: Cost,
 }
 
 /// Core tool trait — implement for any capability
@@ -32,12 +91,51 @@ pub trait Tool: Send + Sync {
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
+    /// Whether the tool only works with a network connection; offline mode
+    /// disables these.
+    fn needs_network(&self) -> bool {
+        false
+    }
+
+    /// Typical duration of a call; network tools default to medium
+    fn latency(&self) -> Latency {
+        if self.needs_network() {
+            Latency::Medium
+        } else {
+            Latency::Fast
+        }
+    }
+
+    /// What a call costs, for the per-turn tool budget
+    fn cost(&self) -> Cost {
+        Cost::Free
+    }
+
+    /// Whether the tool only looks or can also act, for interactive approvals
+    /// and `mcp serve`. Network tools default to write, since most of them
+    /// can post, send or change something; read-only ones say so.
+    fn class(&self) -> ToolClass {
+        if self.needs_network() {
+            ToolClass::Write
+        } else {
+            ToolClass::Read
+        }
+    }
+
+    /// The class of one call, for tools whose actions differ (listing issues
+    /// vs opening one); defaults to `class`
+    fn call_class(&self, _args: &serde_json::Value) -> ToolClass {
+        self.class()
+    }
+
     /// Get the full spec for LLM registration
     fn spec(&self) -> ToolSpec {
         ToolSpec {
             name: self.name().to_string(),
             description: self.description().to_string(),
             parameters: self.parameters_schema(),
+            latency: self.latency(),
+            cost: self.cost(),
         }
     }
 }
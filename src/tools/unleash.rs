@@ -0,0 +1,168 @@
+use super::feature_flags::{FeatureFlag, FeatureFlagService};
+use crate::config::UnleashConfig;
+use crate::util::LazyClient;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// Unleash Admin API backend (self-hosted or Unleash Cloud).
+pub struct UnleashService {
+    api_url: String,
+    api_token: String,
+    project: String,
+    environment: String,
+    client: LazyClient,
+}
+
+impl UnleashService {
+    pub fn new(config: &UnleashConfig) -> Self {
+        Self {
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            api_token: config.api_token.clone(),
+            project: config.project.clone(),
+            environment: config.environment.clone(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
+        }
+    }
+
+    fn features_url(&self) -> String {
+        format!("{}/admin/projects/{}/features", self.api_url, self.project)
+    }
+
+    fn to_flag(&self, raw: UnleashFeature) -> FeatureFlag {
+        FeatureFlag {
+            enabled: raw
+                .environments
+                .iter()
+                .any(|env| env.name == self.environment && env.enabled),
+            key: raw.name.clone(),
+            name: raw.name,
+            description: raw.description.unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureFlagService for UnleashService {
+    fn name(&self) -> &str {
+        "unleash"
+    }
+
+    fn display_name(&self) -> &str {
+        "Unleash"
+    }
+
+    fn environment(&self) -> &str {
+        &self.environment
+    }
+
+    async fn list_flags(&self, limit: usize) -> anyhow::Result<Vec<FeatureFlag>> {
+        let resp = self
+            .client
+            .get(self.features_url())
+            .header("Authorization", &self.api_token)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Unleash", resp).await);
+        }
+        let page: UnleashFeatures = resp.json().await?;
+        Ok(page
+            .features
+            .into_iter()
+            .filter(|f| !f.archived)
+            .take(limit)
+            .map(|raw| self.to_flag(raw))
+            .collect())
+    }
+
+    async fn get_flag(&self, key: &str) -> anyhow::Result<FeatureFlag> {
+        let resp = self
+            .client
+            .get(format!("{}/{key}", self.features_url()))
+            .header("Authorization", &self.api_token)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Unleash", resp).await);
+        }
+        Ok(self.to_flag(resp.json().await?))
+    }
+
+    async fn set_flag(&self, key: &str, enabled: bool) -> anyhow::Result<()> {
+        let url = format!(
+            "{}/{key}/environments/{}/{}",
+            self.features_url(),
+            self.environment,
+            if enabled { "on" } else { "off" }
+        );
+        let resp = self
+            .client
+            .post(url)
+            .header("Authorization", &self.api_token)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(crate::providers::api_error("Unleash", resp).await);
+        }
+        Ok(())
+    }
+}
+
+// ── API response types ──────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct UnleashFeatures {
+    #[serde(default)]
+    features: Vec<UnleashFeature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnleashFeature {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    environments: Vec<UnleashEnvironment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnleashEnvironment {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> UnleashService {
+        UnleashService::new(&UnleashConfig {
+            api_url: "https://unleash.example.com/api/".into(),
+            api_token: "*:*.abc".into(),
+            project: "default".into(),
+            environment: "production".into(),
+        })
+    }
+
+    #[test]
+    fn features_url_is_project_scoped() {
+        assert_eq!(
+            service().features_url(),
+            "https://unleash.example.com/api/admin/projects/default/features"
+        );
+    }
+
+    #[test]
+    fn flag_state_comes_from_configured_environment() {
+        let raw = r#"{"name": "new-checkout", "description": "Rewrite", "environments": [
+            {"name": "development", "enabled": true},
+            {"name": "production", "enabled": false}
+        ]}"#;
+        let flag = service().to_flag(serde_json::from_str(raw).unwrap());
+        assert!(!flag.enabled);
+        assert_eq!(flag.description, "Rewrite");
+    }
+}
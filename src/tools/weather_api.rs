@@ -1,20 +1,22 @@
+use super::reply::{StructuredReply, COLOR_INFO};
 use super::traits::{Tool, ToolResult};
+use crate::security::ToolClass;
+use crate::util::LazyClient;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use reqwest::Client;
 use serde_json::{json, Value};
 
 const BASE_URL: &str = "https://api.weatherapi.com/v1";
 
 /// WeatherAPI.com integration for current conditions and 7-day forecasts.
 pub struct WeatherApiTool {
-    client: Client,
+    client: LazyClient,
 }
 
 impl WeatherApiTool {
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: LazyClient::new(std::time::Duration::from_secs(30)),
         }
     }
 }
@@ -25,6 +27,10 @@ impl Tool for WeatherApiTool {
         "weather_api"
     }
 
+    fn needs_network(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> &str {
         "Fetch current weather or a 7-day forecast using WeatherAPI.com"
     }
@@ -53,6 +59,10 @@ impl Tool for WeatherApiTool {
         })
     }
 
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
     async fn execute(&self, args: Value) -> Result<ToolResult> {
         let arg_api_key = args
             .get("api_key")
@@ -140,6 +150,15 @@ impl Tool for WeatherApiTool {
             summarize_current(&parsed).unwrap_or_else(|| pretty_body.clone())
         };
 
+        let card = if normalized_days > 1 {
+            forecast_card(&parsed, normalized_days.into())
+        } else {
+            current_card(&parsed)
+        };
+        if let Some(card) = card {
+            return Ok(card.into_result(&summary));
+        }
+
         Ok(ToolResult {
             success: true,
             output: summary,
@@ -148,6 +167,68 @@ impl Tool for WeatherApiTool {
     }
 }
 
+fn place_name(data: &Value) -> Option<String> {
+    let location = data.get("location")?;
+    let name = location.get("name")?.as_str()?;
+    let country = location.get("country")?.as_str()?;
+    Some(format!("{name}, {country}"))
+}
+
+fn current_card(data: &Value) -> Option<StructuredReply> {
+    let current = data.get("current")?;
+    let condition = current.get("condition")?.get("text")?.as_str()?;
+    let temp = current.get("temp_c")?.as_f64()?;
+    let feels_like = current.get("feelslike_c")?.as_f64()?;
+    let humidity = current.get("humidity")?.as_i64()?;
+    let wind_kph = current.get("wind_kph")?.as_f64()?;
+    let wind_dir = current
+        .get("wind_dir")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let mut card = StructuredReply::new(place_name(data)?)
+        .description(condition)
+        .color(COLOR_INFO)
+        .field("Temperature", format!("{temp:.1} C"), true)
+        .field("Feels like", format!("{feels_like:.1} C"), true)
+        .field("Humidity", format!("{humidity}%"), true)
+        .field(
+            "Wind",
+            format!("{wind_kph:.1} kph {wind_dir}").trim_end(),
+            true,
+        );
+    if let Some(updated) = current.get("last_updated").and_then(Value::as_str) {
+        card = card.footer(format!("WeatherAPI.com · updated {updated}"));
+    }
+    Some(card)
+}
+
+fn forecast_card(weather: &Value, days: usize) -> Option<StructuredReply> {
+    let forecast = weather.get("forecast")?.get("forecastday")?.as_array()?;
+    let mut card = StructuredReply::new(place_name(weather)?)
+        .description(format!(
+            "Forecast for the next {} day(s)",
+            days.min(forecast.len())
+        ))
+        .color(COLOR_INFO)
+        .footer("WeatherAPI.com");
+    for day in forecast.iter().take(days) {
+        let date = day.get("date")?.as_str()?;
+        let details = day.get("day")?;
+        let condition = details.get("condition")?.get("text")?.as_str()?;
+        let max = details.get("maxtemp_c")?.as_f64()?;
+        let min = details.get("mintemp_c")?.as_f64()?;
+        let rain = extract_percentage(details.get("daily_chance_of_rain"))
+            .map(|rain| format!("\nRain {rain}"))
+            .unwrap_or_default();
+        card = card.field(
+            date,
+            format!("{condition}\n{min:.1} / {max:.1} C{rain}"),
+            true,
+        );
+    }
+    Some(card)
+}
+
 fn summarize_current(data: &Value) -> Option<String> {
     let location_line = build_location_line(data)?;
     let current = data.get("current")?;
@@ -274,6 +355,11 @@ mod tests {
         let summary = summarize_current(&sample).unwrap();
         assert!(summary.contains("London"));
         assert!(summary.contains("Partly cloudy"));
+
+        let card = current_card(&sample).unwrap();
+        assert_eq!(card.title, "London, United Kingdom");
+        assert_eq!(card.fields[0].value, "13.5 C");
+        assert_eq!(card.fields[3].value, "10.2 kph SW");
     }
 
     #[test]
@@ -0,0 +1,462 @@
+// Web search — ranked results with URLs from a configurable engine.
+//
+// Brave Search (API key), SearXNG (self-hosted, JSON output) and DuckDuckGo
+// (no key; its HTML endpoint) implement `SearchEngine`. Results are numbered
+// so the agent can cite them as [n] next to the URL.
+
+use super::traits::{Cost, Tool, ToolResult};
+use crate::config::WebSearchConfig;
+use crate::security::ToolClass;
+use crate::util::LazyClient;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+const MAX_RESULTS: u64 = 20;
+/// Snippets are cut to this many characters.
+const SNIPPET_CHARS: usize = 300;
+
+/// One search result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// A web search backend.
+#[async_trait]
+pub trait SearchEngine: Send + Sync {
+    /// Engine name as configured (e.g. "brave")
+    fn name(&self) -> &str;
+
+    /// Up to `count` results for `query`, best first.
+    async fn search(&self, query: &str, count: usize) -> anyhow::Result<Vec<SearchHit>>;
+}
+
+/// The engine `config` selects.
+pub fn engine(config: &WebSearchConfig) -> anyhow::Result<Box<dyn SearchEngine>> {
+    let client = LazyClient::new(std::time::Duration::from_secs(config.timeout_secs))
+        .with_user_agent("Mozilla/5.0 (compatible; zeroclaw)");
+    match config.engine.as_str() {
+        "brave" => {
+            let api_key = config
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("BRAVE_API_KEY").ok())
+                .filter(|k| !k.trim().is_empty())
+                .context(
+                    "Brave Search needs api_key in [integrations.web_search] or BRAVE_API_KEY",
+                )?;
+            Ok(Box::new(Brave { api_key, client }))
+        }
+        "searxng" => {
+            let url = config
+                .url
+                .clone()
+                .context("SearXNG needs url in [integrations.web_search]")?;
+            Ok(Box::new(SearXng {
+                url: url.trim_end_matches('/').to_string(),
+                client,
+            }))
+        }
+        "duckduckgo" => Ok(Box::new(DuckDuckGo { client })),
+        other => anyhow::bail!(
+            "Unknown web search engine '{other}'. Use 'brave', 'searxng' or 'duckduckgo'."
+        ),
+    }
+}
+
+async fn get_json(request: reqwest::RequestBuilder, engine: &str) -> anyhow::Result<Value> {
+    let resp = request.send().await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        anyhow::bail!(
+            "{engine} error ({status}): {}",
+            crate::util::truncate_with_ellipsis(&body, 200)
+        );
+    }
+    Ok(resp.json().await?)
+}
+
+fn hits_from(results: Option<&Value>, snippet_field: &str) -> Vec<SearchHit> {
+    results
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|r| {
+            let field = |name: &str| r.get(name).and_then(Value::as_str).unwrap_or_default();
+            let url = field("url");
+            (!url.is_empty()).then(|| SearchHit {
+                title: strip_tags(field("title")),
+                url: url.to_string(),
+                snippet: strip_tags(field(snippet_field)),
+            })
+        })
+        .collect()
+}
+
+struct Brave {
+    api_key: String,
+    client: LazyClient,
+}
+
+#[async_trait]
+impl SearchEngine for Brave {
+    fn name(&self) -> &str {
+        "brave"
+    }
+
+    async fn search(&self, query: &str, count: usize) -> anyhow::Result<Vec<SearchHit>> {
+        let body = get_json(
+            self.client
+                .get("https://api.search.brave.com/res/v1/web/search")
+                .header("X-Subscription-Token", &self.api_key)
+                .header(reqwest::header::ACCEPT, "application/json")
+                .query(&[("q", query.to_string()), ("count", count.to_string())]),
+            "Brave Search",
+        )
+        .await?;
+        Ok(hits_from(body.pointer("/web/results"), "description"))
+    }
+}
+
+struct SearXng {
+    url: String,
+    client: LazyClient,
+}
+
+#[async_trait]
+impl SearchEngine for SearXng {
+    fn name(&self) -> &str {
+        "searxng"
+    }
+
+    async fn search(&self, query: &str, count: usize) -> anyhow::Result<Vec<SearchHit>> {
+        // The instance needs `json` in `search.formats` of its settings.yml.
+        let body = get_json(
+            self.client
+                .get(format!("{}/search", self.url))
+                .query(&[("q", query), ("format", "json")]),
+            "SearXNG",
+        )
+        .await?;
+        let mut hits = hits_from(body.get("results"), "content");
+        hits.truncate(count);
+        Ok(hits)
+    }
+}
+
+struct DuckDuckGo {
+    client: LazyClient,
+}
+
+#[async_trait]
+impl SearchEngine for DuckDuckGo {
+    fn name(&self) -> &str {
+        "duckduckgo"
+    }
+
+    async fn search(&self, query: &str, count: usize) -> anyhow::Result<Vec<SearchHit>> {
+        let resp = self
+            .client
+            .post("https://html.duckduckgo.com/html/")
+            .form(&[("q", query)])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("DuckDuckGo error ({})", resp.status());
+        }
+        let mut hits = parse_duckduckgo(&resp.text().await?);
+        hits.truncate(count);
+        Ok(hits)
+    }
+}
+
+/// Results from the `DuckDuckGo` HTML page: `result__a` links (which go through
+/// a `/l/?uddg=<target>` redirect) and the `result__snippet` after each.
+fn parse_duckduckgo(html: &str) -> Vec<SearchHit> {
+    static LINK: OnceLock<regex::Regex> = OnceLock::new();
+    static SNIPPET: OnceLock<regex::Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| {
+        regex::Regex::new(r#"(?s)<a[^>]*class="result__a"[^>]*href="([^"]+)"[^>]*>(.*?)</a>"#)
+            .expect("valid regex")
+    });
+    let snippet = SNIPPET.get_or_init(|| {
+        regex::Regex::new(r#"(?s)class="result__snippet"[^>]*>(.*?)</a>"#).expect("valid regex")
+    });
+
+    let links: Vec<_> = link.captures_iter(html).collect();
+    let mut hits = Vec::new();
+    for (i, caps) in links.iter().enumerate() {
+        let Some(url) = result_url(&decode_entities(&caps[1])) else {
+            continue;
+        };
+        let start = caps.get(0).map_or(0, |m| m.end());
+        let end = links
+            .get(i + 1)
+            .and_then(|next| next.get(0))
+            .map_or(html.len(), |m| m.start());
+        let snippet = snippet
+            .captures(&html[start..end])
+            .map(|s| strip_tags(&s[1]))
+            .unwrap_or_default();
+        hits.push(SearchHit {
+            title: strip_tags(&caps[2]),
+            url,
+            snippet,
+        });
+    }
+    hits
+}
+
+/// The target of a `DuckDuckGo` result link; ads (`y.js`) are dropped.
+fn result_url(href: &str) -> Option<String> {
+    let absolute = if href.starts_with("//") {
+        format!("https:{href}")
+    } else {
+        href.to_string()
+    };
+    let url = reqwest::Url::parse(&absolute).ok()?;
+    if url.domain() == Some("duckduckgo.com") {
+        return url
+            .query_pairs()
+            .find(|(k, _)| k == "uddg")
+            .map(|(_, v)| v.into_owned());
+    }
+    Some(absolute)
+}
+
+/// Text of an HTML fragment: tags removed, entities decoded, whitespace collapsed.
+fn strip_tags(fragment: &str) -> String {
+    let mut text = String::with_capacity(fragment.len());
+    let mut in_tag = false;
+    for c in fragment.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Numbered results, for the agent to cite as [n].
+fn format_hits(query: &str, engine: &str, hits: &[SearchHit]) -> String {
+    let mut out = format!("Results for \"{query}\" ({engine}):\n");
+    for (i, hit) in hits.iter().enumerate() {
+        let _ = write!(out, "\n[{}] {}\n    {}\n", i + 1, hit.title, hit.url);
+        if !hit.snippet.is_empty() {
+            let _ = writeln!(
+                out,
+                "    {}",
+                crate::util::truncate_with_ellipsis(&hit.snippet, SNIPPET_CHARS)
+            );
+        }
+    }
+    out.push_str("\nCite sources as [n] with their URL.");
+    out
+}
+
+/// Search the web through the configured engine.
+pub struct WebSearchTool {
+    engine: Box<dyn SearchEngine>,
+    max_results: u64,
+}
+
+impl WebSearchTool {
+    pub fn new(engine: Box<dyn SearchEngine>, max_results: u64) -> Self {
+        Self {
+            engine,
+            max_results: max_results.clamp(1, MAX_RESULTS),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WebSearchTool {
+    fn name(&self) -> &str {
+        "web_search"
+    }
+
+    fn needs_network(&self) -> bool {
+        true
+    }
+
+    fn cost(&self) -> Cost {
+        Cost::Metered
+    }
+
+    fn description(&self) -> &str {
+        "Search the web for current information. Returns numbered results with \
+         title, URL and snippet; cite the ones you use as [n] with the URL."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Search terms"
+                },
+                "count": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_RESULTS,
+                    "description": "Number of results (default from config)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn class(&self) -> ToolClass {
+        ToolClass::Read
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+        let count = args
+            .get("count")
+            .and_then(Value::as_u64)
+            .unwrap_or(self.max_results)
+            .clamp(1, MAX_RESULTS);
+
+        #[allow(clippy::cast_possible_truncation)]
+        match self.engine.search(query, count as usize).await {
+            Ok(hits) if hits.is_empty() => Ok(ToolResult {
+                success: true,
+                output: format!("No results for \"{query}\"."),
+                error: None,
+            }),
+            Ok(hits) => Ok(ToolResult {
+                success: true,
+                output: format_hits(query, self.engine.name(), &hits),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Web search failed: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedEngine(Vec<SearchHit>);
+
+    #[async_trait]
+    impl SearchEngine for FixedEngine {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn search(&self, _query: &str, count: usize) -> anyhow::Result<Vec<SearchHit>> {
+            Ok(self.0.iter().take(count).cloned().collect())
+        }
+    }
+
+    fn hit(n: u32) -> SearchHit {
+        SearchHit {
+            title: format!("Title {n}"),
+            url: format!("https://example.com/{n}"),
+            snippet: format!("Snippet {n}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn results_are_numbered_for_citation() {
+        let tool = WebSearchTool::new(Box::new(FixedEngine(vec![hit(1), hit(2), hit(3)])), 2);
+        let result = tool.execute(json!({"query": "rust"})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(
+            result.output,
+            "Results for \"rust\" (fixed):\n\n[1] Title 1\n    https://example.com/1\n    Snippet 1\n\n\
+             [2] Title 2\n    https://example.com/2\n    Snippet 2\n\nCite sources as [n] with their URL."
+        );
+
+        let empty = WebSearchTool::new(Box::new(FixedEngine(vec![])), 5);
+        let result = empty.execute(json!({"query": "nothing"})).await.unwrap();
+        assert_eq!(result.output, "No results for \"nothing\".");
+        assert!(empty.execute(json!({"query": " "})).await.is_err());
+    }
+
+    #[test]
+    fn duckduckgo_results_are_parsed_from_html() {
+        let html = r#"
+            <div class="result results_links"><h2 class="result__title">
+              <a rel="nofollow" class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust-lang.org%2F&amp;rut=abc">Rust <b>Programming</b> Language</a>
+            </h2>
+            <a class="result__snippet" href="x">A language empowering everyone to build <b>reliable</b> &amp; efficient software.</a></div>
+            <div class="result"><a rel="nofollow" class="result__a" href="https://doc.rust-lang.org/book/">The Book</a></div>
+        "#;
+        let hits = parse_duckduckgo(html);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].title, "Rust Programming Language");
+        assert_eq!(hits[0].url, "https://www.rust-lang.org/");
+        assert_eq!(
+            hits[0].snippet,
+            "A language empowering everyone to build reliable & efficient software."
+        );
+        assert_eq!(hits[1].url, "https://doc.rust-lang.org/book/");
+        assert!(hits[1].snippet.is_empty());
+    }
+
+    #[test]
+    fn json_engines_map_their_fields() {
+        let brave = json!({"web": {"results": [
+            {"title": "<strong>Brave</strong>", "url": "https://brave.com", "description": "Private"},
+            {"title": "no url"}
+        ]}});
+        assert_eq!(
+            hits_from(brave.pointer("/web/results"), "description"),
+            vec![SearchHit {
+                title: "Brave".into(),
+                url: "https://brave.com".into(),
+                snippet: "Private".into(),
+            }]
+        );
+
+        let config = WebSearchConfig {
+            engine: "searxng".into(),
+            ..WebSearchConfig::default()
+        };
+        assert!(engine(&config).is_err());
+        let config = WebSearchConfig {
+            engine: "altavista".into(),
+            ..WebSearchConfig::default()
+        };
+        assert!(engine(&config).is_err());
+        assert_eq!(
+            engine(&WebSearchConfig::default()).unwrap().name(),
+            "duckduckgo"
+        );
+    }
+}
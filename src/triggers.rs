@@ -0,0 +1,536 @@
+//! Wake-on-event triggers: the daemon watches a few local event sources and
+//! runs the agent with the prompt mapped to each event in `[triggers]`.
+//!
+//! Sources are polled and diffed against the previous snapshot, so nothing
+//! fires for the state found at startup. USB, battery and network events are
+//! read from Linux sysfs; on other platforms only folder watches fire.
+
+use crate::config::{Config, TriggerEvent, TriggerRule, TriggersConfig};
+use crate::presence::Presence;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const USB_ROOT: &str = "/sys/bus/usb/devices";
+const POWER_ROOT: &str = "/sys/class/power_supply";
+const NET_ROOT: &str = "/sys/class/net";
+const DEFAULT_BATTERY_THRESHOLD: u8 = 20;
+
+/// Something that happened, with the values its prompt can reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub kind: TriggerEvent,
+    pub fields: BTreeMap<&'static str, String>,
+}
+
+impl Event {
+    fn new(kind: TriggerEvent, fields: &[(&'static str, String)]) -> Self {
+        Self {
+            kind,
+            fields: fields.iter().cloned().collect(),
+        }
+    }
+
+    /// Fill `{name}` placeholders in `template`; unknown ones are left as is.
+    pub fn render(&self, template: &str) -> String {
+        self.fields
+            .iter()
+            .fold(template.to_string(), |text, (key, value)| {
+                text.replace(&format!("{{{key}}}"), value)
+            })
+    }
+}
+
+trait Source: Send {
+    /// Events since the previous poll. The first poll only records state.
+    fn poll(&mut self) -> Vec<Event>;
+}
+
+struct FolderWatch {
+    dir: PathBuf,
+    seen: Option<HashSet<PathBuf>>,
+}
+
+impl Source for FolderWatch {
+    fn poll(&mut self) -> Vec<Event> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let current: HashSet<PathBuf> = entries
+            .flatten()
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+            .map(|e| e.path())
+            .collect();
+        let Some(seen) = self.seen.replace(current.clone()) else {
+            return Vec::new();
+        };
+        let mut created: Vec<_> = current.difference(&seen).collect();
+        created.sort();
+        created
+            .into_iter()
+            .map(|path| {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                Event::new(
+                    TriggerEvent::FileCreated,
+                    &[("path", path.display().to_string()), ("name", name)],
+                )
+            })
+            .collect()
+    }
+}
+
+struct UsbWatch {
+    root: PathBuf,
+    kind: TriggerEvent,
+    seen: Option<BTreeMap<String, String>>,
+}
+
+impl UsbWatch {
+    fn devices(&self) -> BTreeMap<String, String> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return BTreeMap::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let dir = entry.path();
+                // Interfaces ("1-1:1.0") and bare hubs have no vendor ID.
+                let vendor = read_trimmed(&dir.join("idVendor"))?;
+                let product_id = read_trimmed(&dir.join("idProduct")).unwrap_or_default();
+                let label = match (
+                    read_trimmed(&dir.join("manufacturer")),
+                    read_trimmed(&dir.join("product")),
+                ) {
+                    (Some(maker), Some(product)) => format!("{maker} {product}"),
+                    (None, Some(product)) => product,
+                    _ => format!("{vendor}:{product_id}"),
+                };
+                Some((entry.file_name().to_string_lossy().into_owned(), label))
+            })
+            .collect()
+    }
+}
+
+impl Source for UsbWatch {
+    fn poll(&mut self) -> Vec<Event> {
+        let current = self.devices();
+        let Some(seen) = self.seen.replace(current.clone()) else {
+            return Vec::new();
+        };
+        let (from, to) = match self.kind {
+            TriggerEvent::UsbDisconnected => (&current, &seen),
+            _ => (&seen, &current),
+        };
+        to.iter()
+            .filter(|(port, _)| !from.contains_key(*port))
+            .map(|(_, label)| Event::new(self.kind, &[("device", label.clone())]))
+            .collect()
+    }
+}
+
+struct BatteryWatch {
+    root: PathBuf,
+    threshold: u8,
+    fired: bool,
+}
+
+impl Source for BatteryWatch {
+    fn poll(&mut self) -> Vec<Event> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let battery = entries.flatten().map(|e| e.path()).find(|dir| {
+            read_trimmed(&dir.join("type")).is_some_and(|t| t.eq_ignore_ascii_case("battery"))
+        });
+        let Some(dir) = battery else {
+            return Vec::new();
+        };
+        let level: Option<u8> = read_trimmed(&dir.join("capacity")).and_then(|c| c.parse().ok());
+        let discharging = read_trimmed(&dir.join("status")).as_deref() == Some("Discharging");
+        match level {
+            Some(level) if discharging && level <= self.threshold => {
+                if std::mem::replace(&mut self.fired, true) {
+                    Vec::new()
+                } else {
+                    vec![Event::new(
+                        TriggerEvent::BatteryLow,
+                        &[("level", level.to_string())],
+                    )]
+                }
+            }
+            _ => {
+                self.fired = false;
+                Vec::new()
+            }
+        }
+    }
+}
+
+struct NetworkWatch {
+    root: PathBuf,
+    seen: Option<BTreeMap<String, String>>,
+}
+
+impl Source for NetworkWatch {
+    fn poll(&mut self) -> Vec<Event> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let current: BTreeMap<String, String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|name| name != "lo")
+            .map(|name| {
+                let state = read_trimmed(&self.root.join(&name).join("operstate"))
+                    .unwrap_or_else(|| "unknown".into());
+                (name, state)
+            })
+            .collect();
+        let Some(seen) = self.seen.replace(current.clone()) else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+        for (interface, state) in &current {
+            if seen.get(interface) != Some(state) {
+                events.push(Event::new(
+                    TriggerEvent::NetworkChange,
+                    &[("interface", interface.clone()), ("state", state.clone())],
+                ));
+            }
+        }
+        for interface in seen.keys().filter(|i| !current.contains_key(*i)) {
+            events.push(Event::new(
+                TriggerEvent::NetworkChange,
+                &[
+                    ("interface", interface.clone()),
+                    ("state", "removed".into()),
+                ],
+            ));
+        }
+        events
+    }
+}
+
+/// Follows the state file the presence component writes.
+struct PresenceWatch {
+    workspace_dir: PathBuf,
+    kind: TriggerEvent,
+    primed: bool,
+    seen: Option<Presence>,
+}
+
+impl Source for PresenceWatch {
+    fn poll(&mut self) -> Vec<Event> {
+        let status = crate::presence::load(&self.workspace_dir);
+        let current = status.as_ref().map(|s| s.state);
+        let previous = std::mem::replace(&mut self.seen, current);
+        if !std::mem::replace(&mut self.primed, true) {
+            return Vec::new();
+        }
+        let wanted = match self.kind {
+            TriggerEvent::OwnerArrived => Presence::Home,
+            _ => Presence::Away,
+        };
+        match status {
+            Some(status)
+                if previous.is_some() && previous != current && current == Some(wanted) =>
+            {
+                vec![Event::new(
+                    self.kind,
+                    &[("since", status.since.format("%H:%M UTC").to_string())],
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+struct Watch {
+    rule: TriggerRule,
+    source: Box<dyn Source>,
+    last_run: Option<Instant>,
+}
+
+/// The configured rules with their event sources.
+pub struct TriggerEngine {
+    watches: Vec<Watch>,
+}
+
+impl TriggerEngine {
+    /// `workspace_dir` is where presence state is read from.
+    pub fn from_config(config: &TriggersConfig, workspace_dir: &Path) -> Result<Self> {
+        Self::with_roots(config, workspace_dir, Path::new("/"))
+    }
+
+    /// Like [`TriggerEngine::from_config`], reading sysfs under `root`.
+    fn with_roots(config: &TriggersConfig, workspace_dir: &Path, root: &Path) -> Result<Self> {
+        let sys = |path: &str| root.join(path.trim_start_matches('/'));
+        let mut watches = Vec::new();
+        for rule in &config.rules {
+            let source: Box<dyn Source> = match rule.event {
+                TriggerEvent::FileCreated => {
+                    let Some(path) = rule.path.as_deref() else {
+                        anyhow::bail!("Trigger '{}' needs a `path` to watch", rule.name);
+                    };
+                    Box::new(FolderWatch {
+                        dir: PathBuf::from(shellexpand::tilde(path).as_ref()),
+                        seen: None,
+                    })
+                }
+                TriggerEvent::UsbConnected | TriggerEvent::UsbDisconnected => Box::new(UsbWatch {
+                    root: sys(USB_ROOT),
+                    kind: rule.event,
+                    seen: None,
+                }),
+                TriggerEvent::BatteryLow => Box::new(BatteryWatch {
+                    root: sys(POWER_ROOT),
+                    threshold: rule.threshold.unwrap_or(DEFAULT_BATTERY_THRESHOLD),
+                    fired: false,
+                }),
+                TriggerEvent::NetworkChange => Box::new(NetworkWatch {
+                    root: sys(NET_ROOT),
+                    seen: None,
+                }),
+                TriggerEvent::OwnerArrived | TriggerEvent::OwnerLeft => Box::new(PresenceWatch {
+                    workspace_dir: workspace_dir.to_path_buf(),
+                    kind: rule.event,
+                    primed: false,
+                    seen: None,
+                }),
+            };
+            watches.push(Watch {
+                rule: rule.clone(),
+                source,
+                last_run: None,
+            });
+        }
+        Ok(Self { watches })
+    }
+
+    /// Poll every source and return `(rule name, prompt)` for each rule that
+    /// fired and is out of its cooldown.
+    pub fn poll(&mut self) -> Vec<(String, String)> {
+        let mut fired = Vec::new();
+        for watch in &mut self.watches {
+            let cooldown = Duration::from_secs(watch.rule.cooldown_secs);
+            for event in watch.source.poll() {
+                if watch.last_run.is_some_and(|at| at.elapsed() < cooldown) {
+                    continue;
+                }
+                watch.last_run = Some(Instant::now());
+                fired.push((watch.rule.name.clone(), event.render(&watch.rule.prompt)));
+            }
+        }
+        fired
+    }
+}
+
+/// Daemon component: poll the sources and run the agent for each trigger.
+pub async fn run(config: Config) -> Result<()> {
+    let mut engine = TriggerEngine::from_config(&config.triggers, &config.workspace_dir)?;
+    let mut interval = tokio::time::interval(Duration::from_secs(config.triggers.poll_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+        for (name, prompt) in engine.poll() {
+            tracing::info!("Trigger '{name}' fired");
+            let prompt = format!("[Trigger: {name}] {prompt}");
+            let temp = config.default_temperature;
+            if let Err(e) = Box::pin(crate::agent::run(
+                config.clone(),
+                Some(prompt),
+                None,
+                None,
+                temp,
+                None,
+                false,
+                None,
+            ))
+            .await
+            {
+                crate::health::mark_component_error("triggers", e.to_string());
+                tracing::warn!("Trigger '{name}' failed: {e}");
+            } else {
+                crate::health::mark_component_ok("triggers");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rule(name: &str, event: TriggerEvent, prompt: &str) -> TriggerRule {
+        TriggerRule {
+            name: name.into(),
+            event,
+            prompt: prompt.into(),
+            path: None,
+            threshold: None,
+            cooldown_secs: 0,
+        }
+    }
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn new_files_fire_after_the_initial_snapshot() {
+        let tmp = TempDir::new().unwrap();
+        write(&tmp.path().join("old.txt"), "");
+        let mut downloads = rule("downloads", TriggerEvent::FileCreated, "Summarize {path}");
+        downloads.path = Some(tmp.path().display().to_string());
+        let mut engine = TriggerEngine::from_config(
+            &TriggersConfig {
+                poll_secs: 1,
+                rules: vec![downloads],
+            },
+            tmp.path(),
+        )
+        .unwrap();
+
+        assert!(engine.poll().is_empty());
+        write(&tmp.path().join("report.pdf"), "%PDF");
+        let fired = engine.poll();
+        assert_eq!(
+            fired,
+            vec![(
+                "downloads".to_string(),
+                format!("Summarize {}", tmp.path().join("report.pdf").display())
+            )]
+        );
+        assert!(engine.poll().is_empty());
+    }
+
+    #[test]
+    fn usb_and_network_changes_are_diffed() {
+        let root = TempDir::new().unwrap();
+        let usb = root.path().join("sys/bus/usb/devices");
+        let net = root.path().join("sys/class/net");
+        write(&net.join("eth0/operstate"), "up\n");
+        write(&net.join("lo/operstate"), "unknown\n");
+        std::fs::create_dir_all(&usb).unwrap();
+        let config = TriggersConfig {
+            poll_secs: 1,
+            rules: vec![
+                rule("plug", TriggerEvent::UsbConnected, "Plugged in: {device}"),
+                rule("unplug", TriggerEvent::UsbDisconnected, "Removed: {device}"),
+                rule("net", TriggerEvent::NetworkChange, "{interface} is {state}"),
+            ],
+        };
+        let mut engine = TriggerEngine::with_roots(&config, root.path(), root.path()).unwrap();
+        assert!(engine.poll().is_empty());
+
+        write(&usb.join("1-2/idVendor"), "0781");
+        write(&usb.join("1-2/manufacturer"), "SanDisk");
+        write(&usb.join("1-2/product"), "Ultra");
+        write(&usb.join("1-2:1.0/bInterfaceClass"), "08");
+        write(&net.join("eth0/operstate"), "down\n");
+        let prompts: Vec<String> = engine.poll().into_iter().map(|(_, p)| p).collect();
+        assert_eq!(prompts, ["Plugged in: SanDisk Ultra", "eth0 is down"]);
+
+        std::fs::remove_dir_all(usb.join("1-2")).unwrap();
+        let prompts: Vec<String> = engine.poll().into_iter().map(|(_, p)| p).collect();
+        assert_eq!(prompts, ["Removed: SanDisk Ultra"]);
+    }
+
+    #[test]
+    fn battery_fires_once_per_discharge_and_respects_cooldown() {
+        let root = TempDir::new().unwrap();
+        let bat = root.path().join("sys/class/power_supply/BAT0");
+        write(&bat.join("type"), "Battery");
+        write(&bat.join("status"), "Discharging");
+        write(&bat.join("capacity"), "40");
+        let mut low = rule("battery", TriggerEvent::BatteryLow, "Battery at {level}%");
+        low.threshold = Some(15);
+        let mut engine = TriggerEngine::with_roots(
+            &TriggersConfig {
+                poll_secs: 1,
+                rules: vec![low],
+            },
+            root.path(),
+            root.path(),
+        )
+        .unwrap();
+
+        assert!(engine.poll().is_empty());
+        write(&bat.join("capacity"), "12");
+        assert_eq!(engine.poll()[0].1, "Battery at 12%");
+        write(&bat.join("capacity"), "9");
+        assert!(engine.poll().is_empty());
+
+        // Charging re-arms the rule.
+        write(&bat.join("status"), "Charging");
+        assert!(engine.poll().is_empty());
+        write(&bat.join("status"), "Discharging");
+        assert_eq!(engine.poll().len(), 1);
+
+        engine.watches[0].rule.cooldown_secs = 3600;
+        write(&bat.join("status"), "Charging");
+        engine.poll();
+        write(&bat.join("status"), "Discharging");
+        assert!(engine.poll().is_empty());
+    }
+
+    #[test]
+    fn file_rule_without_path_is_rejected() {
+        let config = TriggersConfig {
+            poll_secs: 1,
+            rules: vec![rule("x", TriggerEvent::FileCreated, "p")],
+        };
+        assert!(TriggerEngine::from_config(&config, Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn presence_changes_fire_owner_rules() {
+        use crate::presence::{state_path, PresenceStatus};
+
+        let ws = TempDir::new().unwrap();
+        let set = |state: Presence| {
+            let status = PresenceStatus {
+                state,
+                since: chrono::Utc::now(),
+                checked_at: chrono::Utc::now(),
+                last_seen: None,
+            };
+            write(
+                &state_path(ws.path()),
+                &serde_json::to_string(&status).unwrap(),
+            );
+        };
+        let config = TriggersConfig {
+            poll_secs: 1,
+            rules: vec![
+                rule("left", TriggerEvent::OwnerLeft, "Owner left at {since}"),
+                rule("back", TriggerEvent::OwnerArrived, "Welcome back"),
+            ],
+        };
+        let mut engine = TriggerEngine::from_config(&config, ws.path()).unwrap();
+
+        assert!(engine.poll().is_empty());
+        // The first state written is not a change.
+        set(Presence::Home);
+        assert!(engine.poll().is_empty());
+        set(Presence::Away);
+        let fired = engine.poll();
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].1.starts_with("Owner left at "));
+        set(Presence::Home);
+        assert_eq!(engine.poll(), vec![("back".into(), "Welcome back".into())]);
+    }
+}
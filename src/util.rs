@@ -43,6 +43,218 @@ pub fn truncate_with_ellipsis(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Whether we run inside Termux on Android: no keychain and no systemd, and
+/// the userland lives under `$PREFIX` rather than `/usr`.
+pub fn is_termux() -> bool {
+    std::env::var_os("TERMUX_VERSION").is_some()
+        || std::env::var("PREFIX").is_ok_and(|prefix| prefix.contains("/com.termux/"))
+}
+
+/// Termux's userland root (`$PREFIX`).
+pub fn termux_prefix() -> std::path::PathBuf {
+    std::env::var_os("PREFIX").map_or_else(
+        || std::path::PathBuf::from("/data/data/com.termux/files/usr"),
+        std::path::PathBuf::from,
+    )
+}
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode as standard, padded base64 without pulling in a crate.
+pub fn base64_encode(bytes: &[u8]) -> String {
+    encode_base64(bytes, BASE64_ALPHABET, true)
+}
+
+/// Encode as unpadded base64url (RFC 4648 §5), as in PKCE challenges.
+pub fn base64_url_encode(bytes: &[u8]) -> String {
+    encode_base64(bytes, BASE64_URL_ALPHABET, false)
+}
+
+fn encode_base64(bytes: &[u8], alphabet: &[u8], pad: bool) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(alphabet[(n >> (18 - 6 * i)) as usize & 63]));
+            } else if pad {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decode standard (optionally unpadded) base64 without pulling in a crate.
+///
+/// Returns `None` on any character outside the alphabet.
+#[allow(clippy::cast_possible_truncation)]
+pub fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let padded = match input.len() % 4 {
+        2 => format!("{input}=="),
+        3 => format!("{input}="),
+        _ => input.to_string(),
+    };
+
+    let mut bytes = Vec::with_capacity(padded.len() / 4 * 3);
+    let chars: Vec<u8> = padded.bytes().collect();
+
+    for chunk in chars.chunks(4) {
+        if chunk.len() < 4 {
+            break;
+        }
+
+        let mut v = [0usize; 4];
+        for (i, &b) in chunk.iter().enumerate() {
+            if b == b'=' {
+                v[i] = 0;
+            } else {
+                v[i] = BASE64_ALPHABET.iter().position(|&a| a == b)?;
+            }
+        }
+
+        bytes.push(((v[0] << 2) | (v[1] >> 4)) as u8);
+        if chunk[2] != b'=' {
+            bytes.push((((v[1] & 0xF) << 4) | (v[2] >> 2)) as u8);
+        }
+        if chunk[3] != b'=' {
+            bytes.push((((v[2] & 0x3) << 6) | v[3]) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters, so the
+/// result is safe in a path segment or a query value.
+pub fn percent_encode(raw: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::with_capacity(raw.len());
+    for byte in raw.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(char::from(byte));
+            }
+            _ => {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        }
+    }
+    out
+}
+
+/// Undo `%XX` escapes; malformed ones are kept as-is and invalid UTF-8 is
+/// replaced.
+pub fn percent_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = raw
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(byte) = escaped {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The process-wide HTTP client, built on first use.
+///
+/// Every provider, tool and channel shares its connection pool, so a
+/// connection opened by one request (or by [`crate::providers::Provider::warmup`])
+/// is kept alive and reused by the next one to the same host instead of
+/// paying for DNS and a TLS handshake again. It has no overall timeout;
+/// callers set one per request, as [`LazyClient`] does.
+pub fn shared_client() -> &'static reqwest::Client {
+    static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .pool_idle_timeout(std::time::Duration::from_secs(90))
+            .pool_max_idle_per_host(8)
+            .tcp_keepalive(std::time::Duration::from_mins(1))
+            .tcp_nodelay(true)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new())
+    })
+}
+
+/// A handle on [`shared_client`] with its own request timeout.
+///
+/// Building a `reqwest::Client` loads the TLS root store, so tools and
+/// providers hold one of these instead: constructing it is free, a one-shot
+/// `zeroclaw agent -m` only builds the shared client if it makes a request,
+/// and all handles share one connection pool.
+pub struct LazyClient {
+    timeout: std::time::Duration,
+    user_agent: Option<&'static str>,
+}
+
+impl LazyClient {
+    /// Requests time out after `timeout`; connecting after 10s.
+    pub const fn new(timeout: std::time::Duration) -> Self {
+        Self {
+            timeout,
+            user_agent: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_user_agent(mut self, user_agent: &'static str) -> Self {
+        self.user_agent = Some(user_agent);
+        self
+    }
+
+    pub fn request(
+        &self,
+        method: reqwest::Method,
+        url: impl reqwest::IntoUrl,
+    ) -> reqwest::RequestBuilder {
+        let builder = shared_client().request(method, url).timeout(self.timeout);
+        match self.user_agent {
+            Some(user_agent) => builder.header(reqwest::header::USER_AGENT, user_agent),
+            None => builder,
+        }
+    }
+
+    pub fn get(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::POST, url)
+    }
+
+    pub fn put(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::PUT, url)
+    }
+
+    pub fn patch(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::PATCH, url)
+    }
+
+    pub fn delete(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::DELETE, url)
+    }
+
+    pub fn head(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::HEAD, url)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +276,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lazy_clients_share_one_pool_and_keep_their_timeouts() {
+        assert!(std::ptr::eq(shared_client(), shared_client()));
+        let client = LazyClient::new(std::time::Duration::from_secs(5)).with_user_agent("test");
+        let request = client.get("https://example.com/").build().unwrap();
+        assert_eq!(request.timeout(), Some(&std::time::Duration::from_secs(5)));
+        assert_eq!(request.headers()[reqwest::header::USER_AGENT], "test");
+        assert_eq!(request.method(), reqwest::Method::GET);
+    }
+
+    #[test]
+    fn base64url_matches_the_rfc_7636_pkce_example() {
+        use sha2::{Digest, Sha256};
+        // RFC 7636 Appendix B: the S256 challenge for this verifier.
+        let digest = Sha256::digest(b"dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk");
+        assert_eq!(
+            base64_url_encode(&digest),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(base64_url_encode(&[0xfb, 0xff]), "-_8");
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn percent_coding_round_trips() {
+        assert_eq!(percent_encode("a b/c~é"), "a%20b%2Fc~%C3%A9");
+        assert_eq!(percent_decode("a%20b%2Fc~%C3%A9"), "a b/c~é");
+        assert_eq!(percent_decode("p%40ss%zz%4"), "p@ss%zz%4");
+    }
+
     #[test]
     fn test_truncate_empty_string() {
         assert_eq!(truncate_with_ellipsis("", 10), "");
@@ -130,6 +372,16 @@ mod tests {
         assert!(result.ends_with("..."));
     }
 
+    #[test]
+    fn base64_decode_binary() {
+        assert_eq!(base64_decode("iVBORw==").unwrap(), [0x89, b'P', b'N', b'G']);
+        assert_eq!(base64_decode("aGk").unwrap(), b"hi");
+        assert!(base64_decode("a$b=").is_none());
+        assert_eq!(base64_encode(b"hi"), "aGk=");
+        assert_eq!(base64_encode(&[0x89, b'P', b'N', b'G']), "iVBORw==");
+        assert_eq!(base64_encode(b"abc"), "YWJj");
+    }
+
     #[test]
     fn test_truncate_zero_max_chars() {
         // Edge case: max_chars = 0
@@ -0,0 +1,236 @@
+//! Photos for vision-capable models (`[vision]` in the config).
+//!
+//! Channels that receive photos download them (up to
+//! [`MAX_DOWNLOAD_BYTES`] each) into [`ImageAttachment`]s on the incoming
+//! message; [`prepare`] then caps how many go to the model and shrinks the
+//! ones that are too large, re-encoding them as JPEG with `ffmpeg`.
+
+use crate::config::VisionConfig;
+use crate::providers::ImageAttachment;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Largest photo a channel downloads; anything bigger is ignored.
+pub const MAX_DOWNLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+/// Message text for a photo sent without a caption.
+pub const PHOTO_PLACEHOLDER: &str = "[photo]";
+
+/// What every vision provider accepts; anything else is converted to JPEG.
+const SUPPORTED_MIME_TYPES: &[&str] = &["image/jpeg", "image/png", "image/gif", "image/webp"];
+
+/// JPEG qualities (`ffmpeg -q:v`, lower is better) tried while shrinking.
+const JPEG_QUALITIES: &[u32] = &[3, 6, 10];
+
+/// Message text for `caption`, or the placeholder when there is none.
+pub fn caption_or_placeholder(caption: Option<&str>) -> String {
+    caption
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .unwrap_or(PHOTO_PLACEHOLDER)
+        .to_string()
+}
+
+/// Whether a declared content type is an image worth downloading.
+pub fn is_image(content_type: &str) -> bool {
+    content_type
+        .trim()
+        .to_ascii_lowercase()
+        .starts_with("image/")
+}
+
+/// Image type from the first bytes of `data`.
+pub fn sniff(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\xFF\xD8\xFF") {
+        Some("image/jpeg")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Width and height from a PNG, GIF or JPEG header; `None` for anything else.
+pub fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| -> Option<u32> {
+        Some(u32::from(u16::from_be_bytes(
+            data.get(at..at + 2)?.try_into().ok()?,
+        )))
+    };
+    match sniff(data)? {
+        "image/png" => {
+            let be32 = |at: usize| -> Option<u32> {
+                Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+            };
+            Some((be32(16)?, be32(20)?))
+        }
+        "image/gif" => {
+            let le16 = |at: usize| -> Option<u32> {
+                Some(u32::from(u16::from_le_bytes(
+                    data.get(at..at + 2)?.try_into().ok()?,
+                )))
+            };
+            Some((le16(6)?, le16(8)?))
+        }
+        "image/jpeg" => {
+            let mut at = 2;
+            loop {
+                if *data.get(at)? != 0xFF {
+                    return None;
+                }
+                let marker = *data.get(at + 1)?;
+                match marker {
+                    0xFF => at += 1,
+                    0x01 | 0xD0..=0xD8 => at += 2,
+                    // SOF0–SOF15, except DHT, JPG and DAC
+                    0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                        return Some((be16(at + 7)?, be16(at + 5)?));
+                    }
+                    _ => at += 2 + usize::try_from(be16(at + 2)?).ok()?,
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The images to send with a message: at most `max_images`, each a type
+/// every provider reads and within `max_dimension` and `max_image_kb`.
+/// Images that can't be shrunk enough are dropped with a warning.
+pub async fn prepare(images: &[ImageAttachment], config: &VisionConfig) -> Vec<ImageAttachment> {
+    if images.len() > config.max_images {
+        tracing::info!(
+            "Sending {} of {} images to the model",
+            config.max_images,
+            images.len()
+        );
+    }
+    let mut prepared = Vec::new();
+    for image in images.iter().take(config.max_images) {
+        match prepare_one(image, config).await {
+            Ok(image) => prepared.push(image),
+            Err(e) => tracing::warn!("Dropping image: {e:#}"),
+        }
+    }
+    prepared
+}
+
+async fn prepare_one(image: &ImageAttachment, config: &VisionConfig) -> Result<ImageAttachment> {
+    let max_bytes = config.max_image_kb.saturating_mul(1024);
+    let mime_type = sniff(&image.data).unwrap_or(image.mime_type.as_str());
+    let fits = SUPPORTED_MIME_TYPES.contains(&mime_type)
+        && image.data.len() <= max_bytes
+        && dimensions(&image.data).is_none_or(|(w, h)| w.max(h) <= config.max_dimension);
+    if fits {
+        return Ok(ImageAttachment::new(mime_type, image.data.clone()));
+    }
+
+    let dir = std::env::temp_dir().join(format!("zeroclaw-vision-{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir_all(&dir).await?;
+    let result = shrink(&dir, &image.data, config.max_dimension, max_bytes).await;
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    Ok(ImageAttachment::new("image/jpeg", result?))
+}
+
+/// `data` as a JPEG within `max_dimension` and `max_bytes`, trying lower
+/// qualities and then smaller sizes until it fits.
+async fn shrink(dir: &Path, data: &[u8], max_dimension: u32, max_bytes: usize) -> Result<Vec<u8>> {
+    let input = dir.join("image-in");
+    let output = dir.join("image-out.jpg");
+    tokio::fs::write(&input, data).await?;
+    let mut dimension = max_dimension.max(1);
+    loop {
+        for quality in JPEG_QUALITIES {
+            run_ffmpeg(&downscale_args(&input, &output, dimension, *quality)).await?;
+            let jpeg = tokio::fs::read(&output).await?;
+            if jpeg.len() <= max_bytes {
+                return Ok(jpeg);
+            }
+        }
+        if dimension <= 256 {
+            anyhow::bail!("still over {} KiB at {dimension}px", max_bytes / 1024);
+        }
+        dimension = dimension * 3 / 4;
+    }
+}
+
+/// First frame of `input`, no larger than `dimension` on its longest side
+/// (never enlarged), as a JPEG at `quality`.
+fn downscale_args(input: &Path, output: &Path, dimension: u32, quality: u32) -> Vec<String> {
+    vec![
+        "-nostdin".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-y".into(),
+        "-i".into(),
+        input.display().to_string(),
+        "-vf".into(),
+        format!(
+            "scale='min({dimension},iw)':'min({dimension},ih)':force_original_aspect_ratio=decrease"
+        ),
+        "-frames:v".into(),
+        "1".into(),
+        "-q:v".into(),
+        quality.to_string(),
+        output.display().to_string(),
+    ]
+}
+
+async fn run_ffmpeg(args: &[String]) -> Result<()> {
+    let output = tokio::process::Command::new("ffmpeg")
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .context("Could not run ffmpeg to downscale the image")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "ffmpeg failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        data.extend(width.to_be_bytes());
+        data.extend(height.to_be_bytes());
+        data
+    }
+
+    #[tokio::test]
+    async fn images_are_sniffed_measured_and_capped() {
+        assert_eq!(dimensions(&png(640, 480)), Some((640, 480)));
+        assert_eq!(dimensions(b"GIF89a\x20\x03\x58\x02"), Some((800, 600)));
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // SOI, APP0
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x0B, 0xB8, 0x0F, 0xA0, // SOF0 3000×4000
+        ];
+        assert_eq!(dimensions(&jpeg), Some((4000, 3000)));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff(b"%PDF-1.7"), None);
+        assert!(is_image("Image/PNG"));
+        assert_eq!(caption_or_placeholder(Some("  ")), PHOTO_PLACEHOLDER);
+
+        let config = VisionConfig {
+            max_images: 2,
+            ..VisionConfig::default()
+        };
+        let small = ImageAttachment::new("application/octet-stream", png(640, 480));
+        let prepared = prepare(&[small.clone(), small.clone(), small], &config).await;
+        assert_eq!(prepared.len(), 2);
+        assert_eq!(prepared[0].mime_type, "image/png");
+        assert_eq!(prepared[0].data, png(640, 480));
+    }
+}
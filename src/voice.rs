@@ -0,0 +1,495 @@
+//! Voice notes in and out: transcription and speech synthesis through an
+//! OpenAI-compatible audio API (`[voice]` in the config). Transcription can
+//! also go to Groq's hosted Whisper or stay on the machine with whisper.cpp;
+//! speech can come from `ElevenLabs` or a local piper voice.
+//!
+//! Channels that receive voice notes hand the audio to
+//! [`VoiceClient::transcribe`] so the agent sees text; channels configured
+//! for voice replies, and replies carrying a `speech` block from the `speak`
+//! tool, turn text back into audio with [`VoiceClient::speak`].
+
+use crate::config::VoiceConfig;
+use crate::util::LazyClient;
+use anyhow::{Context, Result};
+use reqwest::multipart::{Form, Part};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const GROQ_API_URL: &str = "https://api.groq.com/openai/v1";
+/// Used on Groq while `stt_model` is still the `whisper-1` default.
+const GROQ_STT_MODEL: &str = "whisper-large-v3-turbo";
+/// whisper.cpp reads 16 kHz mono WAV only.
+const WHISPER_SAMPLE_RATE: &str = "16000";
+const ELEVENLABS_API_URL: &str = "https://api.elevenlabs.io/v1";
+/// Used on `ElevenLabs` while `tts_voice` is still the `alloy` default.
+const ELEVENLABS_VOICE: &str = "21m00Tcm4TlvDq8ikWAM";
+/// Used on `ElevenLabs` while `tts_model` is still the `tts-1` default.
+const ELEVENLABS_MODEL: &str = "eleven_multilingual_v2";
+
+/// Client for the transcription and speech endpoints.
+pub struct VoiceClient {
+    config: VoiceConfig,
+    api_key: Option<String>,
+    client: LazyClient,
+}
+
+impl VoiceClient {
+    pub fn new(config: &VoiceConfig) -> Self {
+        let api_key = config
+            .api_key
+            .clone()
+            .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+            .filter(|key| !key.trim().is_empty());
+        Self {
+            config: config.clone(),
+            api_key,
+            client: LazyClient::new(Duration::from_mins(2)),
+        }
+    }
+
+    /// The client for `config`, when voice notes are to be transcribed or
+    /// the agent may speak.
+    pub fn for_config(config: &VoiceConfig) -> Option<Self> {
+        (config.transcribe || config.speak_tool).then(|| Self::new(config))
+    }
+
+    /// Whether incoming voice notes are to be transcribed.
+    pub fn transcribes(&self) -> bool {
+        self.config.transcribe
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.config.api_url.trim_end_matches('/'))
+    }
+
+    fn authorized(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// Base URL, key and model transcriptions are sent to.
+    fn stt_endpoint(&self) -> (String, Option<String>, String) {
+        if self.config.stt_provider.trim().eq_ignore_ascii_case("groq") {
+            let key = self
+                .config
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("GROQ_API_KEY").ok())
+                .filter(|key| !key.trim().is_empty());
+            let model = if self.config.stt_model == "whisper-1" {
+                GROQ_STT_MODEL.to_string()
+            } else {
+                self.config.stt_model.clone()
+            };
+            return (GROQ_API_URL.to_string(), key, model);
+        }
+        (
+            self.config.api_url.trim_end_matches('/').to_string(),
+            self.api_key.clone(),
+            self.config.stt_model.clone(),
+        )
+    }
+
+    fn is_local(&self) -> bool {
+        self.config
+            .stt_provider
+            .trim()
+            .eq_ignore_ascii_case("whisper_cpp")
+    }
+
+    /// Text spoken in `audio`; `file_name` tells the API the format.
+    pub async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> Result<String> {
+        if self.is_local() {
+            return self.transcribe_locally(audio, file_name).await;
+        }
+        let (base, key, model) = self.stt_endpoint();
+        let form = Form::new()
+            .text("model", model)
+            .part("file", Part::bytes(audio).file_name(file_name.to_string()));
+        let mut request = self
+            .client
+            .post(format!("{base}/audio/transcriptions"))
+            .multipart(form);
+        if let Some(key) = key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Transcription failed ({status}): {body}");
+        }
+        let body: Value = response.json().await?;
+        body.get("text")
+            .and_then(Value::as_str)
+            .map(|text| text.trim().to_string())
+            .context("Transcription response has no text")
+    }
+
+    /// Transcribe with the whisper.cpp CLI, converting to WAV with `ffmpeg`
+    /// first unless `audio` already is one.
+    async fn transcribe_locally(&self, audio: Vec<u8>, file_name: &str) -> Result<String> {
+        let model = self
+            .config
+            .whisper_cpp_model
+            .as_deref()
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .context("stt_provider = \"whisper_cpp\" needs [voice].whisper_cpp_model")?;
+        let model = PathBuf::from(shellexpand::tilde(model).as_ref());
+        in_temp_dir(|dir| async move {
+            let input = dir.join(sanitize_file_name(file_name));
+            tokio::fs::write(&input, audio).await?;
+            let wav = if is_wav(file_name) {
+                input
+            } else {
+                let wav = dir.join("voice.wav");
+                run("ffmpeg", &ffmpeg_args(&input, &wav), None)
+                    .await
+                    .context("ffmpeg could not convert the voice note to WAV")?;
+                wav
+            };
+            let output = run(
+                &self.config.whisper_cpp_bin,
+                &whisper_cpp_args(&model, &wav),
+                None,
+            )
+            .await?;
+            Ok(output.trim().to_string())
+        })
+        .await
+    }
+
+    fn speech_request(&self, text: &str, format: &str) -> Value {
+        json!({
+            "model": self.config.tts_model,
+            "voice": self.config.tts_voice,
+            "input": text,
+            "response_format": format
+        })
+    }
+
+    /// `text` as Ogg/Opus audio.
+    pub async fn speak(&self, text: &str) -> Result<Vec<u8>> {
+        // Opus in an Ogg container is what messengers expect of a voice note.
+        self.speak_as(text, "opus").await
+    }
+
+    /// `text` as audio in `format` (`opus`, `aac`, `mp3`, …).
+    pub async fn speak_as(&self, text: &str, format: &str) -> Result<Vec<u8>> {
+        match self
+            .config
+            .tts_provider
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "elevenlabs" => self.speak_elevenlabs(text, format).await,
+            "piper" => self.speak_locally(text, format).await,
+            _ => self.speak_openai(text, format).await,
+        }
+    }
+
+    async fn speak_openai(&self, text: &str, format: &str) -> Result<Vec<u8>> {
+        let response = self
+            .authorized(self.client.post(self.url("audio/speech")))
+            .json(&self.speech_request(text, format))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Speech synthesis failed ({status}): {body}");
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// URL and body of an `ElevenLabs` text-to-speech request for MP3.
+    fn elevenlabs_request(&self, text: &str) -> (String, Value) {
+        let voice = match self.config.tts_voice.trim() {
+            "" | "alloy" => ELEVENLABS_VOICE,
+            voice => voice,
+        };
+        let model = match self.config.tts_model.trim() {
+            "" | "tts-1" => ELEVENLABS_MODEL,
+            model => model,
+        };
+        (
+            format!("{ELEVENLABS_API_URL}/text-to-speech/{voice}?output_format=mp3_44100_128"),
+            json!({ "text": text, "model_id": model }),
+        )
+    }
+
+    async fn speak_elevenlabs(&self, text: &str, format: &str) -> Result<Vec<u8>> {
+        let key = self
+            .config
+            .api_key
+            .clone()
+            .or_else(|| std::env::var("ELEVENLABS_API_KEY").ok())
+            .filter(|key| !key.trim().is_empty())
+            .context("tts_provider = \"elevenlabs\" needs ELEVENLABS_API_KEY or [voice].api_key")?;
+        let (url, body) = self.elevenlabs_request(text);
+        let response = self
+            .client
+            .post(url)
+            .header("xi-api-key", key)
+            .json(&body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ElevenLabs speech synthesis failed ({status}): {body}");
+        }
+        let mp3 = response.bytes().await?.to_vec();
+        if format == "mp3" {
+            return Ok(mp3);
+        }
+        in_temp_dir(|dir| async move {
+            let input = dir.join("speech.mp3");
+            tokio::fs::write(&input, mp3).await?;
+            encode(&dir, &input, format).await
+        })
+        .await
+    }
+
+    /// Speak with the piper CLI, then encode its WAV with `ffmpeg`.
+    async fn speak_locally(&self, text: &str, format: &str) -> Result<Vec<u8>> {
+        let model = self
+            .config
+            .piper_model
+            .as_deref()
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .context("tts_provider = \"piper\" needs [voice].piper_model")?;
+        let model = PathBuf::from(shellexpand::tilde(model).as_ref());
+        in_temp_dir(|dir| async move {
+            let wav = dir.join("speech.wav");
+            run(
+                &self.config.piper_bin,
+                &piper_args(&model, &wav),
+                Some(text),
+            )
+            .await?;
+            if format == "wav" {
+                return Ok(tokio::fs::read(&wav).await?);
+            }
+            encode(&dir, &wav, format).await
+        })
+        .await
+    }
+}
+
+/// Run `work` in a fresh temp directory that is removed afterwards.
+async fn in_temp_dir<F, Fut, T>(work: F) -> Result<T>
+where
+    F: FnOnce(PathBuf) -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let dir = std::env::temp_dir().join(format!("zeroclaw-voice-{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir_all(&dir).await?;
+    let result = work(dir.clone()).await;
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    result
+}
+
+/// `input` re-encoded by `ffmpeg` as `format`.
+async fn encode(dir: &Path, input: &Path, format: &str) -> Result<Vec<u8>> {
+    let (output, args) = encode_args(dir, input, format);
+    run("ffmpeg", &args, None)
+        .await
+        .with_context(|| format!("ffmpeg could not encode the speech as {format}"))?;
+    Ok(tokio::fs::read(output).await?)
+}
+
+/// Output file and `ffmpeg` arguments for encoding `input` as `format`.
+fn encode_args(dir: &Path, input: &Path, format: &str) -> (PathBuf, Vec<String>) {
+    let mut args: Vec<String> = vec![
+        "-nostdin".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-y".into(),
+        "-i".into(),
+        input.display().to_string(),
+    ];
+    let extension = match format {
+        // Voice notes are Opus in an Ogg container; plain `.ogg` would be Vorbis.
+        "opus" => {
+            args.extend(["-c:a".into(), "libopus".into(), "-b:a".into(), "32k".into()]);
+            "ogg"
+        }
+        "aac" => "m4a",
+        other => other,
+    };
+    let output = dir.join(format!("speech-out.{extension}"));
+    args.push(output.display().to_string());
+    (output, args)
+}
+
+fn piper_args(model: &Path, wav: &Path) -> Vec<String> {
+    vec![
+        "--model".into(),
+        model.display().to_string(),
+        "--output_file".into(),
+        wav.display().to_string(),
+    ]
+}
+
+fn is_wav(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+}
+
+/// `file_name` without any directory part, so it stays in the temp dir.
+fn sanitize_file_name(file_name: &str) -> String {
+    let name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    if name.is_empty() || name.starts_with('.') {
+        "voice.ogg".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn ffmpeg_args(input: &Path, wav: &Path) -> Vec<String> {
+    vec![
+        "-nostdin".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-y".into(),
+        "-i".into(),
+        input.display().to_string(),
+        "-ar".into(),
+        WHISPER_SAMPLE_RATE.into(),
+        "-ac".into(),
+        "1".into(),
+        wav.display().to_string(),
+    ]
+}
+
+/// Plain text on stdout: no timestamps, no progress.
+fn whisper_cpp_args(model: &Path, wav: &Path) -> Vec<String> {
+    vec![
+        "-m".into(),
+        model.display().to_string(),
+        "-f".into(),
+        wav.display().to_string(),
+        "-l".into(),
+        "auto".into(),
+        "-nt".into(),
+        "-np".into(),
+    ]
+}
+
+/// Stdout of `program`, fed `input` on stdin; an error with its stderr
+/// when it fails.
+async fn run(program: &str, args: &[String], input: Option<&str>) -> Result<String> {
+    use tokio::io::AsyncWriteExt;
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(if input.is_some() {
+            std::process::Stdio::piped()
+        } else {
+            std::process::Stdio::null()
+        })
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Could not run {program}"))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input.as_bytes()).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{program} failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speech_is_requested_as_an_opus_voice_note() {
+        assert!(VoiceClient::for_config(&VoiceConfig::default()).is_none());
+        let client = VoiceClient::new(&VoiceConfig {
+            transcribe: true,
+            api_url: "https://api.example.com/v1/".into(),
+            api_key: Some("sk-test".into()),
+            ..VoiceConfig::default()
+        });
+        assert_eq!(
+            client.url("audio/speech"),
+            "https://api.example.com/v1/audio/speech"
+        );
+        let body = client.speech_request("Hallo", "opus");
+        assert_eq!(body["response_format"], "opus");
+        assert_eq!(body["voice"], "alloy");
+        assert_eq!(body["input"], "Hallo");
+    }
+
+    #[test]
+    fn transcription_goes_to_groq_or_stays_local() {
+        let groq = VoiceClient::new(&VoiceConfig {
+            stt_provider: "groq".into(),
+            api_key: Some("gsk-test".into()),
+            ..VoiceConfig::default()
+        });
+        let (url, key, model) = groq.stt_endpoint();
+        assert_eq!(url, GROQ_API_URL);
+        assert_eq!(key.as_deref(), Some("gsk-test"));
+        assert_eq!(model, GROQ_STT_MODEL);
+
+        let local = VoiceClient::new(&VoiceConfig {
+            stt_provider: "whisper_cpp".into(),
+            ..VoiceConfig::default()
+        });
+        assert!(local.is_local());
+        assert_eq!(sanitize_file_name("../../etc/voice.ogg"), "voice.ogg");
+        assert_eq!(sanitize_file_name(".."), "voice.ogg");
+        assert!(is_wav("Audio Message.WAV"));
+        let args = whisper_cpp_args(Path::new("/m/ggml-base.bin"), Path::new("/t/voice.wav"));
+        assert_eq!(args[..4], ["-m", "/m/ggml-base.bin", "-f", "/t/voice.wav"]);
+        assert!(args.contains(&"-nt".to_string()));
+    }
+
+    #[test]
+    fn speech_goes_to_elevenlabs_or_piper() {
+        let eleven = VoiceClient::new(&VoiceConfig {
+            tts_provider: "elevenlabs".into(),
+            speak_tool: true,
+            ..VoiceConfig::default()
+        });
+        assert!(!eleven.transcribes());
+        let (url, body) = eleven.elevenlabs_request("Hi");
+        assert_eq!(
+            url,
+            format!("{ELEVENLABS_API_URL}/text-to-speech/{ELEVENLABS_VOICE}?output_format=mp3_44100_128")
+        );
+        assert_eq!(body["model_id"], ELEVENLABS_MODEL);
+        assert!(VoiceClient::for_config(&eleven.config).is_some());
+
+        let (output, args) = encode_args(Path::new("/t"), Path::new("/t/speech.wav"), "opus");
+        assert_eq!(output, Path::new("/t/speech-out.ogg"));
+        assert!(args.windows(2).any(|w| w == ["-c:a", "libopus"]));
+        assert_eq!(args.last().unwrap(), "/t/speech-out.ogg");
+        assert_eq!(
+            piper_args(Path::new("/v/lessac.onnx"), Path::new("/t/speech.wav")),
+            [
+                "--model",
+                "/v/lessac.onnx",
+                "--output_file",
+                "/t/speech.wav"
+            ]
+        );
+    }
+}
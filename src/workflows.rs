@@ -0,0 +1,804 @@
+//! Named workflows: multi-step automations defined in
+//! `<workspace>/workflows/<name>.yaml`.
+//!
+//! ```yaml
+//! description: Morning weather digest
+//! trigger:
+//!   cron: "0 7 * * *"
+//! inputs:
+//!   city: Berlin
+//! steps:
+//!   - id: forecast
+//!     tool: weather_api
+//!     args: { location: "{{ city }}" }
+//!     retries: 2
+//!   - id: digest
+//!     agent: "Turn this forecast into two friendly sentences: {{ forecast }}"
+//!   - id: notify
+//!     when: { step: forecast, contains: rain }
+//!     send: { channel: telegram, to: "@alice", message: "☔ {{ digest }}" }
+//! ```
+//!
+//! Steps run in order. A `tool` step calls one of the configured tools, an
+//! `agent` step asks the model, and a `send` step delivers through the
+//! outbox. Each step's output is available to later steps as `{{ <id> }}`,
+//! next to the inputs and the prompt-template built-ins (`date`, `time`,
+//! `weekday`). A step with `when` only runs if the step it names succeeded
+//! (or failed, with `failed: true`) and its output contains `contains`.
+//! A failing step is retried `retries` times, then stops the run unless it
+//! has `continue_on_error`.
+//!
+//! Workflows run from chat or `/webhook` as `/run <name> key=value`, from
+//! `zeroclaw workflows run`, and on their `trigger.cron` schedule while the
+//! daemon runs.
+
+use crate::config::Config;
+use crate::outbox::{self, Enqueued, Status};
+use crate::providers::{self, Provider};
+use crate::tools::{self, Tool};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const EXTENSIONS: [&str; 2] = ["yaml", "yml"];
+/// How often the daemon checks `trigger.cron` schedules.
+const TICK_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Workflow {
+    /// File stem, e.g. `morning-digest`
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub trigger: Trigger,
+    /// Input defaults; `/run <name> key=value` overrides them
+    #[serde(default)]
+    pub inputs: BTreeMap<String, String>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Trigger {
+    /// Cron expression; the daemon runs the workflow on this schedule
+    pub cron: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    pub id: String,
+    /// Tool to call with `args`
+    pub tool: Option<String>,
+    #[serde(default)]
+    pub args: Value,
+    /// Prompt for the model
+    pub agent: Option<String>,
+    /// Model for an `agent` step instead of `default_model`
+    pub model: Option<String>,
+    /// Message to deliver
+    pub send: Option<Delivery>,
+    pub when: Option<Condition>,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default = "default_retry_delay_secs")]
+    pub retry_delay_secs: u64,
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+fn default_retry_delay_secs() -> u64 {
+    5
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Delivery {
+    pub channel: String,
+    /// Recipient on the channel, or `@name` for a household member
+    pub to: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    /// An earlier step
+    pub step: String,
+    /// Run only if that step's output contains this (case-insensitive)
+    pub contains: Option<String>,
+    /// Run only if that step failed, instead of only if it succeeded
+    #[serde(default)]
+    pub failed: bool,
+}
+
+enum Action<'a> {
+    Tool(&'a str),
+    Agent(&'a str),
+    Send(&'a Delivery),
+}
+
+impl Step {
+    fn action(&self) -> Result<Action<'_>> {
+        match (&self.tool, &self.agent, &self.send) {
+            (Some(tool), None, None) => Ok(Action::Tool(tool)),
+            (None, Some(prompt), None) => Ok(Action::Agent(prompt)),
+            (None, None, Some(delivery)) => Ok(Action::Send(delivery)),
+            _ => anyhow::bail!(
+                "Step '{}' needs exactly one of `tool`, `agent` or `send`",
+                self.id
+            ),
+        }
+    }
+}
+
+impl Workflow {
+    pub fn parse(name: &str, source: &str) -> Result<Self> {
+        let mut workflow: Self =
+            serde_yaml::from_str(source).with_context(|| format!("Invalid workflow '{name}'"))?;
+        workflow.name = name.to_string();
+        workflow.validate()?;
+        Ok(workflow)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.steps.is_empty() {
+            anyhow::bail!("Workflow '{}' has no steps", self.name);
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        for step in &self.steps {
+            let id = step.id.as_str();
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                anyhow::bail!("Step id '{id}' must be letters, digits and underscores");
+            }
+            if seen.contains(&id) {
+                anyhow::bail!("Step id '{id}' is used twice");
+            }
+            step.action()?;
+            if let Some(condition) = &step.when {
+                if !seen.contains(&condition.step.as_str()) {
+                    anyhow::bail!(
+                        "Step '{id}' depends on '{}', which is not an earlier step",
+                        condition.step
+                    );
+                }
+            }
+            seen.push(id);
+        }
+        if let Some(expression) = &self.trigger.cron {
+            crate::cron::next_run_for(expression, Utc::now())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn workflows_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("workflows")
+}
+
+fn is_workflow_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn workflow_path(workspace_dir: &Path, name: &str) -> Option<PathBuf> {
+    if !is_workflow_name(name) {
+        return None;
+    }
+    EXTENSIONS
+        .iter()
+        .map(|ext| workflows_dir(workspace_dir).join(format!("{name}.{ext}")))
+        .find(|path| path.is_file())
+}
+
+/// The workflow called `name`; `None` if there is no such file.
+pub fn load(workspace_dir: &Path, name: &str) -> Option<Result<Workflow>> {
+    let path = workflow_path(workspace_dir, name)?;
+    Some(
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .and_then(|source| Workflow::parse(name, &source)),
+    )
+}
+
+/// Every workflow in the workspace by name, including ones that fail to load.
+pub fn load_all(workspace_dir: &Path) -> Vec<(String, Result<Workflow>)> {
+    let Ok(entries) = std::fs::read_dir(workflows_dir(workspace_dir)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| EXTENSIONS.contains(&e))
+        })
+        .filter_map(|p| p.file_stem()?.to_str().map(String::from))
+        .filter(|name| is_workflow_name(name))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let workflow = load(workspace_dir, &name)?;
+            Some((name, workflow))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepStatus {
+    Ok,
+    Failed(String),
+    /// Its `when` did not hold, or an earlier step stopped the run
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepOutcome {
+    pub id: String,
+    pub status: StepStatus,
+    pub output: String,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub workflow: String,
+    pub steps: Vec<StepOutcome>,
+}
+
+impl RunReport {
+    /// True when no step failed without `continue_on_error`.
+    pub fn succeeded(&self, workflow: &Workflow) -> bool {
+        self.steps
+            .iter()
+            .zip(&workflow.steps)
+            .all(|(outcome, step)| {
+                !matches!(outcome.status, StepStatus::Failed(_)) || step.continue_on_error
+            })
+    }
+
+    /// Per-step status lines, then the output of the last step that ran.
+    pub fn summary(&self) -> String {
+        let failed = self
+            .steps
+            .iter()
+            .any(|s| matches!(s.status, StepStatus::Failed(_)));
+        let mut text = if failed {
+            format!("⚠️ Workflow {} had failures", self.workflow)
+        } else {
+            format!("✅ Workflow {} finished", self.workflow)
+        };
+        for step in &self.steps {
+            let _ = match &step.status {
+                StepStatus::Ok if step.attempts > 1 => {
+                    write!(text, "\n- {}: ok after {} attempts", step.id, step.attempts)
+                }
+                StepStatus::Ok => write!(text, "\n- {}: ok", step.id),
+                StepStatus::Failed(error) => write!(text, "\n- {}: failed: {error}", step.id),
+                StepStatus::Skipped => write!(text, "\n- {}: skipped", step.id),
+            };
+        }
+        if let Some(last) = self
+            .steps
+            .iter()
+            .rev()
+            .find(|s| s.status == StepStatus::Ok && !s.output.is_empty())
+        {
+            let _ = write!(text, "\n\n{}", last.output);
+        }
+        text
+    }
+}
+
+/// Runs workflow steps with a set of tools and a provider.
+pub struct Runner<'a> {
+    config: &'a Config,
+    tools: Vec<Box<dyn Tool>>,
+    provider: Box<dyn Provider>,
+    model: String,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(
+        config: &'a Config,
+        tools: Vec<Box<dyn Tool>>,
+        provider: Box<dyn Provider>,
+        model: String,
+    ) -> Self {
+        Self {
+            config,
+            tools,
+            provider,
+            model,
+        }
+    }
+
+    /// The configured tools and the default provider and model; `session`
+    /// labels workspace history commits.
+    pub fn for_config(config: &'a Config, session: &str) -> Result<Self> {
+        let provider = providers::create_resilient_provider(
+            config.default_provider.as_deref().unwrap_or("openrouter"),
+            config.api_key.as_deref(),
+            &config.reliability,
+            &config.providers.fallback,
+        )?;
+        let model = config
+            .default_model
+            .clone()
+            .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+        Ok(Self::new(
+            config,
+            tools::for_config(config, session)?,
+            provider,
+            model,
+        ))
+    }
+
+    /// Run every step of `workflow`; `vars` override its input defaults.
+    pub async fn run(&self, workflow: &Workflow, vars: &BTreeMap<String, String>) -> RunReport {
+        let mut scope = workflow.inputs.clone();
+        scope.extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+        let mut outcomes: Vec<StepOutcome> = Vec::new();
+        let mut stopped = false;
+        for step in &workflow.steps {
+            let skip = stopped
+                || step
+                    .when
+                    .as_ref()
+                    .is_some_and(|condition| !holds(condition, &outcomes));
+            if skip {
+                outcomes.push(StepOutcome {
+                    id: step.id.clone(),
+                    status: StepStatus::Skipped,
+                    output: String::new(),
+                    attempts: 0,
+                });
+                continue;
+            }
+            let mut attempts = 0;
+            let result = loop {
+                attempts += 1;
+                match self.execute(workflow, step, &scope).await {
+                    Err(e) if attempts <= step.retries => {
+                        tracing::warn!(
+                            "Workflow {} step {} failed (attempt {attempts}): {e:#}",
+                            workflow.name,
+                            step.id
+                        );
+                        tokio::time::sleep(Duration::from_secs(step.retry_delay_secs)).await;
+                    }
+                    result => break result,
+                }
+            };
+            let (status, output) = match result {
+                Ok(output) => (StepStatus::Ok, output),
+                Err(e) => {
+                    stopped = !step.continue_on_error;
+                    (StepStatus::Failed(format!("{e:#}")), String::new())
+                }
+            };
+            scope.insert(step.id.clone(), output.clone());
+            outcomes.push(StepOutcome {
+                id: step.id.clone(),
+                status,
+                output,
+                attempts,
+            });
+        }
+        RunReport {
+            workflow: workflow.name.clone(),
+            steps: outcomes,
+        }
+    }
+
+    async fn execute(
+        &self,
+        workflow: &Workflow,
+        step: &Step,
+        scope: &BTreeMap<String, String>,
+    ) -> Result<String> {
+        let render = |text: &str| {
+            crate::prompts::render_inline(&self.config.workspace_dir, text, scope)
+                .with_context(|| format!("Step '{}'", step.id))
+        };
+        match step.action()? {
+            Action::Tool(name) => {
+                let tool = self
+                    .tools
+                    .iter()
+                    .find(|t| t.name() == name)
+                    .with_context(|| format!("No tool named '{name}' is enabled"))?;
+                let args = render_value(&step.args, &render)?;
+                let result = tool.execute(args).await?;
+                if result.success {
+                    Ok(result.output)
+                } else {
+                    anyhow::bail!(result.error.unwrap_or(result.output))
+                }
+            }
+            Action::Agent(prompt) => {
+                let system = format!(
+                    "You are running step '{}' of the '{}' workflow{}. Reply with the result only.",
+                    step.id,
+                    workflow.name,
+                    if workflow.description.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", workflow.description)
+                    }
+                );
+                let model = step.model.as_deref().unwrap_or(&self.model);
+                self.provider
+                    .chat_with_system(
+                        Some(&system),
+                        &render(prompt)?,
+                        model,
+                        self.config.default_temperature,
+                    )
+                    .await
+            }
+            Action::Send(delivery) => {
+                let (channel, to) = crate::household::route(
+                    self.config,
+                    &render(&delivery.channel)?,
+                    &render(&delivery.to)?,
+                );
+                let message = render(&delivery.message)?;
+                let intent = match outbox::enqueue(
+                    self.config,
+                    &format!("workflow:{}", workflow.name),
+                    &channel,
+                    &to,
+                    &message,
+                    None,
+                )? {
+                    Enqueued::Duplicate(_) => return Ok(format!("Already sent to {to}")),
+                    Enqueued::New(intent) => intent,
+                };
+                let lookup = |name: &str| crate::channels::find_channel(self.config, name);
+                match outbox::deliver(self.config, &intent.id, lookup).await? {
+                    Some((Status::Dead, error)) => anyhow::bail!(
+                        "Could not send to {to} on {channel}: {}",
+                        error.unwrap_or_default()
+                    ),
+                    Some((Status::Sent, _)) => Ok(format!("Sent to {to} on {channel}")),
+                    _ => Ok(format!("Queued for {to} on {channel}")),
+                }
+            }
+        }
+    }
+}
+
+/// `args` with every string rendered as a template.
+fn render_value(value: &Value, render: &impl Fn(&str) -> Result<String>) -> Result<Value> {
+    Ok(match value {
+        Value::String(text) => Value::String(render(text)?),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_value(item, render))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), render_value(v, render)?)))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Null => Value::Object(serde_json::Map::new()),
+        other => other.clone(),
+    })
+}
+
+fn holds(condition: &Condition, outcomes: &[StepOutcome]) -> bool {
+    let Some(outcome) = outcomes.iter().find(|o| o.id == condition.step) else {
+        return false;
+    };
+    let status_matches = match outcome.status {
+        StepStatus::Ok => !condition.failed,
+        StepStatus::Failed(_) => condition.failed,
+        StepStatus::Skipped => false,
+    };
+    status_matches
+        && condition.contains.as_ref().is_none_or(|needle| {
+            outcome
+                .output
+                .to_lowercase()
+                .contains(&needle.to_lowercase())
+        })
+}
+
+/// Run `/run <name> key=value` when `<name>` is a workflow; `None` for any
+/// other message, including `/run` of a prompt template.
+pub async fn run_command(config: &Config, message: &str) -> Option<Result<String>> {
+    let args = crate::prompts::command_args(message, crate::prompts::RUN_COMMAND)?;
+    let invocation = crate::prompts::parse_invocation(args).ok()?;
+    let workflow = load(&config.workspace_dir, &invocation.name)?;
+    Some(run_workflow(config, workflow, &invocation.vars).await)
+}
+
+async fn run_workflow(
+    config: &Config,
+    workflow: Result<Workflow>,
+    vars: &BTreeMap<String, String>,
+) -> Result<String> {
+    let workflow = workflow?;
+    let report = Runner::for_config(config, &format!("workflow:{}", workflow.name))?
+        .run(&workflow, vars)
+        .await;
+    tracing::info!(
+        "Workflow {} ran: {}",
+        workflow.name,
+        if report.succeeded(&workflow) {
+            "ok"
+        } else {
+            "failed"
+        }
+    );
+    Ok(report.summary())
+}
+
+/// Daemon component: run workflows whose `trigger.cron` came due.
+pub async fn run(config: Config) -> Result<()> {
+    let mut last: DateTime<Utc> = Utc::now();
+    let mut interval = tokio::time::interval(Duration::from_secs(TICK_SECS));
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok("workflows");
+        let now = Utc::now();
+        for (name, workflow) in load_all(&config.workspace_dir) {
+            let workflow = match workflow {
+                Ok(workflow) => workflow,
+                Err(e) => {
+                    tracing::warn!("Workflow {name} skipped: {e:#}");
+                    continue;
+                }
+            };
+            let due = workflow.trigger.cron.as_deref().is_some_and(|expression| {
+                crate::cron::next_run_for(expression, last).is_ok_and(|at| at <= now)
+            });
+            if due {
+                let config = config.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = run_workflow(&config, Ok(workflow), &BTreeMap::new()).await {
+                        tracing::warn!("Workflow {name} failed to start: {e:#}");
+                    }
+                });
+            }
+        }
+        last = now;
+    }
+}
+
+pub async fn handle_command(command: crate::WorkflowCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::WorkflowCommands::List => {
+            let workflows = load_all(&config.workspace_dir);
+            if workflows.is_empty() {
+                println!(
+                    "No workflows yet. Add one as {}/<name>.yaml",
+                    workflows_dir(&config.workspace_dir).display()
+                );
+            }
+            for (name, workflow) in workflows {
+                match workflow {
+                    Ok(w) => {
+                        let schedule = w
+                            .trigger
+                            .cron
+                            .as_deref()
+                            .map(|c| format!(" [cron: {c}]"))
+                            .unwrap_or_default();
+                        println!(
+                            "- {name} ({} steps){schedule} {}",
+                            w.steps.len(),
+                            w.description
+                        );
+                    }
+                    Err(e) => println!("- {name} ⚠️ {e:#}"),
+                }
+            }
+            Ok(())
+        }
+        crate::WorkflowCommands::Run { name, vars } => {
+            let invocation =
+                crate::prompts::parse_invocation(&format!("{name} {}", vars.join(" ")))?;
+            let workflow = load(&config.workspace_dir, &invocation.name)
+                .with_context(|| format!("No workflow named '{name}'"))?;
+            println!(
+                "{}",
+                run_workflow(config, workflow, &invocation.vars).await?
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockProvider;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Fails until it has been called `fail_times` times, then echoes `text`.
+    struct Flaky {
+        calls: Arc<AtomicU32>,
+        fail_times: u32,
+    }
+
+    #[async_trait]
+    impl Tool for Flaky {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+        fn description(&self) -> &str {
+            "Echo text, eventually"
+        }
+        fn parameters_schema(&self) -> Value {
+            serde_json::json!({"type": "object"})
+        }
+        async fn execute(&self, args: Value) -> Result<ToolResult> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.fail_times {
+                anyhow::bail!("temporarily unavailable");
+            }
+            Ok(ToolResult {
+                success: true,
+                output: args["text"].as_str().unwrap_or_default().to_string(),
+                error: None,
+            })
+        }
+    }
+
+    const DIGEST: &str = r#"
+description: Weather digest
+inputs:
+  city: Berlin
+steps:
+  - id: forecast
+    tool: flaky
+    args: { text: "Rain in {{ city }}" }
+    retries: 1
+    retry_delay_secs: 0
+  - id: digest
+    agent: "Summarize: {{ forecast }}"
+  - id: sunny
+    when: { step: forecast, contains: sun }
+    agent: "Never asked"
+  - id: notify
+    when: { step: forecast, contains: rain }
+    send: { channel: telegram, to: "123", message: "{{ digest }}" }
+"#;
+
+    fn runner<'a>(
+        config: &'a Config,
+        provider: MockProvider,
+        calls: &Arc<AtomicU32>,
+    ) -> Runner<'a> {
+        let tool = Flaky {
+            calls: calls.clone(),
+            fail_times: 1,
+        };
+        Runner::new(
+            config,
+            vec![Box::new(tool)],
+            Box::new(provider),
+            "test-model".into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn steps_retry_branch_and_deliver() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let workflow = Workflow::parse("digest", DIGEST).unwrap();
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = MockProvider::with_replies(["Bring an umbrella."]);
+        let vars = BTreeMap::from([("city".to_string(), "Oslo".to_string())]);
+
+        let report = runner(&config, provider, &calls)
+            .run(&workflow, &vars)
+            .await;
+
+        assert!(report.succeeded(&workflow), "{}", report.summary());
+        let statuses: Vec<_> = report.steps.iter().map(|s| s.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            [
+                StepStatus::Ok,
+                StepStatus::Ok,
+                StepStatus::Skipped,
+                StepStatus::Ok
+            ]
+        );
+        assert_eq!(report.steps[0].output, "Rain in Oslo");
+        assert_eq!(report.steps[0].attempts, 2);
+        // The message waits in the outbox: no channel is configured.
+        let queued = outbox::list(&config, false).unwrap();
+        assert_eq!(queued[0].message, "Bring an umbrella.");
+        assert_eq!(queued[0].source, "workflow:digest");
+    }
+
+    #[tokio::test]
+    async fn a_failed_step_stops_the_run() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let workflow = Workflow::parse(
+            "broken",
+            "steps:\n  - id: first\n    tool: missing\n  - id: second\n    agent: hi\n",
+        )
+        .unwrap();
+        let calls = Arc::new(AtomicU32::new(0));
+        let report = runner(&config, MockProvider::new(), &calls)
+            .run(&workflow, &BTreeMap::new())
+            .await;
+        assert!(!report.succeeded(&workflow));
+        assert_eq!(report.steps[1].status, StepStatus::Skipped);
+        assert!(report.summary().contains("No tool named 'missing'"));
+    }
+
+    #[test]
+    fn invalid_workflows_are_rejected() {
+        for (source, error) in [
+            ("steps: []", "no steps"),
+            (
+                "steps:\n  - id: a\n    tool: x\n    agent: y",
+                "exactly one",
+            ),
+            (
+                "steps:\n  - id: a\n    tool: x\n  - id: a\n    tool: x",
+                "twice",
+            ),
+            (
+                "steps:\n  - id: a\n    when: { step: b }\n    tool: x\n  - id: b\n    tool: x",
+                "not an earlier step",
+            ),
+            (
+                "trigger: { cron: nope }\nsteps:\n  - id: a\n    tool: x",
+                "cron",
+            ),
+        ] {
+            let err = format!("{:#}", Workflow::parse("w", source).unwrap_err());
+            assert!(err.contains(error), "{source}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn run_command_only_takes_workflows() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        assert!(run_command(&config, "/run digest").await.is_none());
+        assert!(run_command(&config, "hello").await.is_none());
+
+        std::fs::create_dir_all(workflows_dir(tmp.path())).unwrap();
+        std::fs::write(workflows_dir(tmp.path()).join("digest.yaml"), "steps: [").unwrap();
+        let result = run_command(&config, "/run digest city=Oslo").await.unwrap();
+        assert!(result.is_err());
+        assert_eq!(load_all(tmp.path()).len(), 1);
+    }
+}
@@ -0,0 +1,651 @@
+//! Keep a search index of the workspace up to date.
+//!
+//! Reading every file on each search stops working past a small workspace.
+//! The index keeps chunks of every text file in `index/files.db` (FTS5 for
+//! keywords, plus embeddings when `[memory].embedding_provider` is set),
+//! keyed by path, size and modification time, so a sync only re-reads what
+//! changed. The daemon re-syncs every `[workspace_index].watch_interval_secs`
+//! and `fs_search` syncs before it searches, so results are never stale.
+
+use crate::config::Config;
+use crate::memory::chunker::chunk_markdown;
+use crate::memory::embeddings::{self, EmbeddingProvider};
+use crate::memory::vector;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Workspace directory holding the index; never indexed itself.
+const INDEX_DIR: &str = "index";
+/// Build output and dependency trees that only add noise.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+const CHUNK_TOKENS: usize = 256;
+const EMBED_BATCH: usize = 64;
+const SNIPPET_CHARS: usize = 400;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileMeta {
+    /// Relative to the workspace, always `/`-separated
+    path: String,
+    modified: i64,
+    size: i64,
+}
+
+/// What a sync changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+impl SyncReport {
+    pub fn changed(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+impl std::fmt::Display for SyncReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} added, {} updated, {} removed, {} unchanged",
+            self.added, self.updated, self.removed, self.unchanged
+        )
+    }
+}
+
+/// A chunk matching a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub path: String,
+    /// First and last line of the chunk in the file, 1-based
+    pub lines: Option<(usize, usize)>,
+    pub heading: Option<String>,
+    pub snippet: String,
+    pub score: f32,
+}
+
+/// Size of the index, for `zeroclaw index status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexStatus {
+    pub db_path: PathBuf,
+    pub files: usize,
+    pub chunks: usize,
+    /// Chunks with an embedding; zero without an embedding provider
+    pub embedded: usize,
+    pub last_sync: Option<String>,
+}
+
+/// Every indexable file under `root`: text-sized, not hidden, not in the
+/// index, build or dependency directories.
+fn scan(root: &Path, max_file_bytes: u64) -> Vec<FileMeta> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            if name.starts_with('.') || kind.is_symlink() {
+                continue;
+            }
+            let path = entry.path();
+            if kind.is_dir() {
+                let top_level_index = dir == root && name == INDEX_DIR;
+                if !top_level_index && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                    pending.push(path);
+                }
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.len() > max_file_bytes {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX));
+            files.push(FileMeta {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                modified,
+                size: i64::try_from(meta.len()).unwrap_or(i64::MAX),
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+/// The workspace search index.
+pub struct WorkspaceIndex {
+    conn: Mutex<Connection>,
+    root: PathBuf,
+    db_path: PathBuf,
+    embedder: Arc<dyn EmbeddingProvider>,
+    max_file_bytes: u64,
+    vector_weight: f32,
+    keyword_weight: f32,
+}
+
+impl WorkspaceIndex {
+    /// Open the index for the configured workspace, embedding with the
+    /// memory settings.
+    pub fn open(config: &Config) -> Result<Self> {
+        let embedder: Arc<dyn EmbeddingProvider> =
+            Arc::from(embeddings::create_embedding_provider(
+                &config.memory.embedding_provider,
+                config.api_key.as_deref(),
+                &config.memory.embedding_model,
+                config.memory.embedding_dimensions,
+            ));
+        let mut index = Self::with_embedder(
+            &config.workspace_dir,
+            embedder,
+            config.workspace_index.max_file_kb * 1024,
+        )?;
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            index.vector_weight = config.memory.vector_weight as f32;
+            index.keyword_weight = config.memory.keyword_weight as f32;
+        }
+        Ok(index)
+    }
+
+    pub fn with_embedder(
+        workspace_dir: &Path,
+        embedder: Arc<dyn EmbeddingProvider>,
+        max_file_bytes: u64,
+    ) -> Result<Self> {
+        let db_path = workspace_dir.join(INDEX_DIR).join("files.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn =
+            Connection::open(&db_path).with_context(|| format!("opening {}", db_path.display()))?;
+        Self::init_schema(&conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            root: workspace_dir.to_path_buf(),
+            db_path,
+            embedder,
+            max_file_bytes,
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+        })
+    }
+
+    fn init_schema(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS files (
+                path        TEXT PRIMARY KEY,
+                modified    INTEGER NOT NULL,
+                size        INTEGER NOT NULL,
+                indexed_at  TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS chunks (
+                id          INTEGER PRIMARY KEY,
+                path        TEXT NOT NULL,
+                heading     TEXT,
+                content     TEXT NOT NULL,
+                embedding   BLOB,
+                start_line  INTEGER,
+                end_line    INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_chunks_path ON chunks(path);
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS chunks_fts USING fts5(
+                path, content, content=chunks, content_rowid=id
+            );
+            CREATE TRIGGER IF NOT EXISTS chunks_ai AFTER INSERT ON chunks BEGIN
+                INSERT INTO chunks_fts(rowid, path, content)
+                VALUES (new.id, new.path, new.content);
+            END;
+            CREATE TRIGGER IF NOT EXISTS chunks_ad AFTER DELETE ON chunks BEGIN
+                INSERT INTO chunks_fts(chunks_fts, rowid, path, content)
+                VALUES ('delete', old.id, old.path, old.content);
+            END;
+
+            CREATE TABLE IF NOT EXISTS meta (
+                key         TEXT PRIMARY KEY,
+                value       TEXT NOT NULL
+            );",
+        )?;
+        Self::migrate(conn)
+    }
+
+    /// Add the line-range columns to indexes built before they existed. The
+    /// file list is cleared so the next sync re-reads everything and fills
+    /// them in.
+    fn migrate(conn: &Connection) -> Result<()> {
+        let mut stmt = conn.prepare("PRAGMA table_info(chunks)")?;
+        let columns = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        if !columns.iter().any(|c| c == "start_line") {
+            conn.execute_batch(
+                "ALTER TABLE chunks ADD COLUMN start_line INTEGER;
+                 ALTER TABLE chunks ADD COLUMN end_line INTEGER;
+                 DELETE FROM files;",
+            )
+            .context("Failed to migrate workspace index schema")?;
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Bring the index in line with the workspace, re-reading only files
+    /// whose size or modification time changed.
+    pub async fn sync(&self) -> Result<SyncReport> {
+        let root = self.root.clone();
+        let max_file_bytes = self.max_file_bytes;
+        let on_disk = tokio::task::spawn_blocking(move || scan(&root, max_file_bytes)).await?;
+
+        let known: HashMap<String, (i64, i64)> = {
+            let conn = self.lock();
+            let mut stmt = conn.prepare("SELECT path, modified, size FROM files")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut report = SyncReport::default();
+        let present: HashSet<&str> = on_disk.iter().map(|f| f.path.as_str()).collect();
+        for path in known.keys().filter(|p| !present.contains(p.as_str())) {
+            let conn = self.lock();
+            conn.execute("DELETE FROM chunks WHERE path = ?1", params![path])?;
+            conn.execute("DELETE FROM files WHERE path = ?1", params![path])?;
+            report.removed += 1;
+        }
+        for file in &on_disk {
+            match known.get(&file.path) {
+                Some(&(modified, size)) if modified == file.modified && size == file.size => {
+                    report.unchanged += 1;
+                    continue;
+                }
+                Some(_) => report.updated += 1,
+                None => report.added += 1,
+            }
+            self.index_file(file).await?;
+        }
+
+        self.lock().execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('last_sync', ?1)",
+            params![chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(report)
+    }
+
+    /// Drop everything and index the workspace from scratch, e.g. after
+    /// changing the embedding model.
+    pub async fn rebuild(&self) -> Result<SyncReport> {
+        self.lock()
+            .execute_batch("DELETE FROM chunks; DELETE FROM files;")?;
+        self.sync().await
+    }
+
+    async fn index_file(&self, file: &FileMeta) -> Result<()> {
+        let bytes = tokio::fs::read(self.root.join(&file.path)).await?;
+        // Binary files are recorded without chunks so they are not re-read.
+        let (chunks, ranges) = match String::from_utf8(bytes) {
+            Ok(text) => {
+                let chunks = chunk_markdown(&text, CHUNK_TOKENS);
+                let lines: Vec<&str> = text.lines().collect();
+                let mut from = 0;
+                let ranges: Vec<Option<(usize, usize)>> = chunks
+                    .iter()
+                    .map(|chunk| {
+                        let range = locate(&lines, &chunk.content, from);
+                        if let Some((_, end)) = range {
+                            from = end;
+                        }
+                        range
+                    })
+                    .collect();
+                (chunks, ranges)
+            }
+            Err(_) => (Vec::new(), Vec::new()),
+        };
+
+        let mut embeddings: Vec<Option<Vec<f32>>> = vec![None; chunks.len()];
+        if self.embedder.dimensions() > 0 {
+            for (batch_no, batch) in chunks.chunks(EMBED_BATCH).enumerate() {
+                let texts: Vec<&str> = batch.iter().map(|c| c.content.as_str()).collect();
+                match self.embedder.embed(&texts).await {
+                    Ok(vectors) => {
+                        for (i, v) in vectors.into_iter().enumerate() {
+                            embeddings[batch_no * EMBED_BATCH + i] = Some(v);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Embedding {} failed, indexing keywords only: {e}",
+                            file.path
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM chunks WHERE path = ?1", params![file.path])?;
+        for ((chunk, embedding), range) in chunks.iter().zip(&embeddings).zip(&ranges) {
+            let line = |n: usize| i64::try_from(n).unwrap_or(i64::MAX);
+            tx.execute(
+                "INSERT INTO chunks (path, heading, content, embedding, start_line, end_line)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    file.path,
+                    chunk.heading,
+                    chunk.content,
+                    embedding.as_deref().map(vector::vec_to_bytes),
+                    range.map(|(start, _)| line(start)),
+                    range.map(|(_, end)| line(end))
+                ],
+            )?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO files (path, modified, size, indexed_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                file.path,
+                file.modified,
+                file.size,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn keyword_search(conn: &Connection, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+        let fts_query = query
+            .split_whitespace()
+            .map(|w| format!("\"{}\"", w.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        if fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare(
+            "SELECT rowid, bm25(chunks_fts) AS score FROM chunks_fts
+             WHERE chunks_fts MATCH ?1 ORDER BY score LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(
+            params![fts_query, i64::try_from(limit).unwrap_or(i64::MAX)],
+            |row| {
+                let id: i64 = row.get(0)?;
+                let score: f64 = row.get(1)?;
+                // BM25 is negative, lower is better.
+                #[allow(clippy::cast_possible_truncation)]
+                Ok((id.to_string(), (-score) as f32))
+            },
+        )?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn vector_search(conn: &Connection, query: &[f32], limit: usize) -> Result<Vec<(String, f32)>> {
+        let mut stmt =
+            conn.prepare("SELECT id, embedding FROM chunks WHERE embedding IS NOT NULL")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        let mut scored = Vec::new();
+        for row in rows {
+            let (id, blob) = row?;
+            let similarity = vector::cosine_similarity(query, &vector::bytes_to_vec(&blob));
+            if similarity > 0.0 {
+                scored.push((id.to_string(), similarity));
+            }
+        }
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
+    /// The chunks best matching `query`, by keywords and (with embeddings)
+    /// meaning.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let query_embedding = if self.embedder.dimensions() > 0 {
+            self.embedder.embed_one(query).await.ok()
+        } else {
+            None
+        };
+
+        let conn = self.lock();
+        let keyword = Self::keyword_search(&conn, query, limit * 2).unwrap_or_default();
+        let semantic = match &query_embedding {
+            Some(embedding) => Self::vector_search(&conn, embedding, limit * 2)?,
+            None => Vec::new(),
+        };
+        let merged = if semantic.is_empty() {
+            keyword
+                .into_iter()
+                .take(limit)
+                .map(|(id, score)| vector::ScoredResult {
+                    id,
+                    vector_score: None,
+                    keyword_score: Some(score),
+                    final_score: score,
+                })
+                .collect()
+        } else {
+            vector::hybrid_merge(
+                &semantic,
+                &keyword,
+                self.vector_weight,
+                self.keyword_weight,
+                limit,
+            )
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT path, heading, content, start_line, end_line FROM chunks WHERE id = ?1",
+        )?;
+        let mut hits = Vec::new();
+        for scored in merged {
+            let Ok(id) = scored.id.parse::<i64>() else {
+                continue;
+            };
+            let hit = stmt
+                .query_row(params![id], |row| {
+                    let content: String = row.get(2)?;
+                    let line = |i| {
+                        row.get::<_, Option<i64>>(i)
+                            .map(|n| n.and_then(|n| usize::try_from(n).ok()))
+                    };
+                    Ok(SearchHit {
+                        path: row.get(0)?,
+                        lines: line(3)?.zip(line(4)?),
+                        heading: row.get(1)?,
+                        snippet: content.chars().take(SNIPPET_CHARS).collect(),
+                        score: scored.final_score,
+                    })
+                })
+                .optional()?;
+            hits.extend(hit);
+        }
+        Ok(hits)
+    }
+
+    pub fn status(&self) -> Result<IndexStatus> {
+        let conn = self.lock();
+        let count = |sql: &str| -> Result<usize> {
+            let n: i64 = conn.query_row(sql, [], |row| row.get(0))?;
+            Ok(usize::try_from(n).unwrap_or(0))
+        };
+        Ok(IndexStatus {
+            db_path: self.db_path.clone(),
+            files: count("SELECT COUNT(*) FROM files")?,
+            chunks: count("SELECT COUNT(*) FROM chunks")?,
+            embedded: count("SELECT COUNT(*) FROM chunks WHERE embedding IS NOT NULL")?,
+            last_sync: conn
+                .query_row(
+                    "SELECT value FROM meta WHERE key = 'last_sync'",
+                    [],
+                    |row| row.get(0),
+                )
+                .optional()?,
+        })
+    }
+}
+
+/// 1-based first and last line of `chunk` in `lines`, looking no earlier
+/// than line index `from`. Chunks split from one section repeat its heading,
+/// so when the first line is not found the second one anchors the start.
+fn locate(lines: &[&str], chunk: &str, from: usize) -> Option<(usize, usize)> {
+    let wanted: Vec<&str> = chunk
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let find = |needle: &str, from: usize| {
+        lines
+            .iter()
+            .skip(from)
+            .position(|l| l.trim() == needle)
+            .map(|i| i + from)
+    };
+    let (first, rest) = wanted.split_first()?;
+    let start = find(first, from).or_else(|| rest.first().and_then(|l| find(l, from)))?;
+    let end = wanted.last().and_then(|l| find(l, start)).unwrap_or(start);
+    Some((start + 1, end + 1))
+}
+
+/// Daemon component: re-sync whenever files change.
+pub async fn run(config: Config) -> Result<()> {
+    let index = WorkspaceIndex::open(&config)?;
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.workspace_index.watch_interval_secs.max(5),
+    ));
+    loop {
+        interval.tick().await;
+        match index.sync().await {
+            Ok(report) => {
+                crate::health::mark_component_ok("workspace_index");
+                if report.changed() {
+                    tracing::info!("Workspace index: {report}");
+                }
+            }
+            Err(e) => {
+                crate::health::mark_component_error("workspace_index", e.to_string());
+                tracing::warn!("Workspace index sync failed: {e}");
+            }
+        }
+    }
+}
+
+pub async fn handle_command(command: crate::IndexCommands, config: &Config) -> Result<()> {
+    let index = WorkspaceIndex::open(config)?;
+    match command {
+        crate::IndexCommands::Status => {
+            let status = index.status()?;
+            println!("🗂  Workspace index: {}", status.db_path.display());
+            println!("   Files:    {}", status.files);
+            println!(
+                "   Chunks:   {} ({} with embeddings)",
+                status.chunks, status.embedded
+            );
+            println!(
+                "   Synced:   {}",
+                status.last_sync.as_deref().unwrap_or("never")
+            );
+            if !config.workspace_index.enabled {
+                println!("   Disabled: set [workspace_index].enabled = true for fs_search");
+            }
+        }
+        crate::IndexCommands::Rebuild => {
+            let report = index.rebuild().await?;
+            println!("✅ Rebuilt workspace index: {report}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embeddings::NoopEmbedding;
+
+    #[tokio::test]
+    async fn sync_only_touches_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(
+            root.join("notes/trip.md"),
+            "# Trip\nFlight to Lisbon on Friday.",
+        )
+        .unwrap();
+        std::fs::write(root.join("todo.txt"), "buy milk").unwrap();
+        std::fs::write(root.join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+
+        let index = WorkspaceIndex::with_embedder(root, Arc::new(NoopEmbedding), 1024).unwrap();
+        let first = index.sync().await.unwrap();
+        assert_eq!((first.added, first.unchanged), (2, 0));
+        assert_eq!(index.sync().await.unwrap().unchanged, 2);
+
+        std::fs::write(root.join("todo.txt"), "buy milk and a Lisbon guidebook").unwrap();
+        std::fs::remove_file(root.join("notes/trip.md")).unwrap();
+        std::fs::write(root.join("big.log"), "x".repeat(2048)).unwrap();
+        let second = index.sync().await.unwrap();
+        assert_eq!(
+            second,
+            SyncReport {
+                added: 0,
+                updated: 1,
+                removed: 1,
+                unchanged: 0
+            }
+        );
+
+        let hits = index.search("lisbon", 5).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "todo.txt");
+        assert_eq!(hits[0].lines, Some((1, 1)));
+        let status = index.status().unwrap();
+        assert_eq!((status.files, status.embedded), (1, 0));
+        assert!(status.last_sync.is_some());
+    }
+
+    #[test]
+    fn chunks_map_back_to_their_lines() {
+        let text = "# Deploys\n\nFreeze on Fridays.\n\nRoll back with `make revert`.\n\n## Oncall\nPage the SRE.";
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            locate(&lines, "# Deploys\nFreeze on Fridays.", 0),
+            Some((1, 3))
+        );
+        // A later chunk of the same section, heading repeated.
+        assert_eq!(
+            locate(&lines, "# Deploys\nRoll back with `make revert`.", 3),
+            Some((5, 5))
+        );
+        assert_eq!(locate(&lines, "## Oncall\nPage the SRE.", 5), Some((7, 8)));
+        assert_eq!(locate(&lines, "not in the file", 0), None);
+    }
+}